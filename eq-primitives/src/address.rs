@@ -0,0 +1,254 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Account formats of chains reachable by XCM transfers
+
+use crate::xcm_origins::{dot, ksm, RELAY};
+use crate::AccountType;
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+#[cfg(feature = "std")]
+use sp_std::convert::TryFrom;
+use xcm::v3::{MultiLocation, NetworkId};
+
+/// Format of accounts on a chain
+#[derive(Decode, Encode, Copy, Clone, RuntimeDebug, Eq, PartialEq, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Deserialize, Serialize))]
+pub enum AccountFormat {
+    /// 32 bytes public key, substrate chains
+    Id32,
+    /// 20 bytes address, EVM chains
+    Key20,
+}
+
+/// Chain with its account format and SS58 prefix
+struct KnownChain {
+    location: MultiLocation,
+    format: AccountFormat,
+    ss58_prefix: u16,
+}
+
+const fn chain(location: MultiLocation, format: AccountFormat, ss58_prefix: u16) -> KnownChain {
+    KnownChain {
+        location,
+        format,
+        ss58_prefix,
+    }
+}
+
+const POLKADOT_CHAINS: &[KnownChain] = &[
+    chain(RELAY, AccountFormat::Id32, 0),
+    chain(dot::PARACHAIN_STATEMINT, AccountFormat::Id32, 0),
+    chain(dot::PARACHAIN_ACALA, AccountFormat::Id32, 10),
+    chain(dot::PARACHAIN_MOONBEAM, AccountFormat::Key20, 1284),
+    chain(dot::PARACHAIN_PARALLEL, AccountFormat::Id32, 172),
+    chain(dot::PARACHAIN_INTERLAY, AccountFormat::Id32, 2032),
+    chain(dot::PARACHAIN_ASTAR, AccountFormat::Id32, 5),
+    chain(dot::PARACHAIN_BIFROST, AccountFormat::Id32, 6),
+    chain(dot::PARACHAIN_PHALA, AccountFormat::Id32, 30),
+    chain(dot::PARACHAIN_LITENTRY, AccountFormat::Id32, 31),
+];
+
+const KUSAMA_CHAINS: &[KnownChain] = &[
+    chain(RELAY, AccountFormat::Id32, 2),
+    chain(ksm::PARACHAIN_KARURA, AccountFormat::Id32, 8),
+    chain(ksm::PARACHAIN_MOONRIVER, AccountFormat::Key20, 1285),
+    chain(ksm::PARACHAIN_HEIKO, AccountFormat::Id32, 110),
+    chain(ksm::PARACHAIN_KINTSUGI, AccountFormat::Id32, 2092),
+    chain(ksm::PARACHAIN_SHIDEN, AccountFormat::Id32, 5),
+    chain(ksm::PARACHAIN_BIFROST, AccountFormat::Id32, 6),
+];
+
+fn known_chain(network: NetworkId, destination: &MultiLocation) -> Option<&'static KnownChain> {
+    let chains = match network {
+        NetworkId::Polkadot => POLKADOT_CHAINS,
+        NetworkId::Kusama => KUSAMA_CHAINS,
+        _ => return None,
+    };
+    chains.iter().find(|c| c.location == *destination)
+}
+
+/// Account format of `destination` chain reachable from a chain of `network` consensus.
+/// Chains unknown to Equilibrium are expected to be substrate chains with `Id32` accounts.
+pub fn account_format(network: Option<NetworkId>, destination: &MultiLocation) -> AccountFormat {
+    network
+        .and_then(|network| known_chain(network, destination))
+        .map_or(AccountFormat::Id32, |c| c.format)
+}
+
+/// SS58 prefix of `destination` chain reachable from a chain of `network` consensus,
+/// `None` if the chain is unknown
+pub fn ss58_prefix(network: Option<NetworkId>, destination: &MultiLocation) -> Option<u16> {
+    network
+        .and_then(|network| known_chain(network, destination))
+        .map(|c| c.ss58_prefix)
+}
+
+/// Reasons of rejecting an account for XCM transfer
+#[derive(Copy, Clone, RuntimeDebug, Eq, PartialEq)]
+pub enum AccountTypeError {
+    /// Account key is all zeros, funds sent to it are lost
+    ZeroAccount,
+    /// Account doesn't match the account format of the destination chain
+    WrongFormat,
+    /// Address can't be decoded
+    InvalidAddress,
+    /// SS58 address is encoded for another network
+    WrongNetwork,
+}
+
+impl AccountType {
+    /// Format of the account
+    pub fn format(&self) -> AccountFormat {
+        match self {
+            AccountType::Id32(_) => AccountFormat::Id32,
+            AccountType::Key20(_) => AccountFormat::Key20,
+        }
+    }
+
+    /// Checks that the account may receive funds on a chain with accounts of `format`
+    pub fn validate(&self, format: AccountFormat) -> Result<(), AccountTypeError> {
+        let is_zero = match self {
+            AccountType::Id32(id) => id.iter().all(|b| *b == 0),
+            AccountType::Key20(key) => key.iter().all(|b| *b == 0),
+        };
+        if is_zero {
+            Err(AccountTypeError::ZeroAccount)
+        } else if self.format() != format {
+            Err(AccountTypeError::WrongFormat)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Decodes SS58 `address`, it must be encoded with `prefix` if provided
+    #[cfg(feature = "std")]
+    pub fn from_ss58(address: &str, prefix: Option<u16>) -> Result<Self, AccountTypeError> {
+        use sp_core::crypto::{AccountId32, Ss58Codec};
+
+        let (account, format) = AccountId32::from_ss58check_with_version(address)
+            .map_err(|_| AccountTypeError::InvalidAddress)?;
+        if prefix.map_or(false, |prefix| prefix != u16::from(format)) {
+            return Err(AccountTypeError::WrongNetwork);
+        }
+        Ok(AccountType::Id32(account.into()))
+    }
+
+    /// Encodes `Id32` account as SS58 address with `prefix`, `None` for `Key20` accounts
+    #[cfg(feature = "std")]
+    pub fn to_ss58(&self, prefix: u16) -> Option<String> {
+        use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+
+        match self {
+            AccountType::Id32(id) => Some(
+                AccountId32::from(*id).to_ss58check_with_version(Ss58AddressFormat::custom(prefix)),
+            ),
+            AccountType::Key20(_) => None,
+        }
+    }
+
+    /// Decodes `0x` prefixed hex address of an EVM chain
+    #[cfg(feature = "std")]
+    pub fn from_hex(address: &str) -> Result<Self, AccountTypeError> {
+        let bytes =
+            sp_core::bytes::from_hex(address).map_err(|_| AccountTypeError::InvalidAddress)?;
+        match bytes.len() {
+            20 => Self::try_from(bytes).map_err(|_| AccountTypeError::InvalidAddress),
+            _ => Err(AccountTypeError::InvalidAddress),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xcm::v3::{Junction::Parachain, Junctions::X1};
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn account_format_depends_on_network() {
+        let moonbeam = dot::PARACHAIN_MOONBEAM;
+        assert_eq!(
+            account_format(Some(NetworkId::Polkadot), &moonbeam),
+            AccountFormat::Key20
+        );
+        // parachain 2004 on Kusama is a substrate chain
+        assert_eq!(
+            account_format(Some(NetworkId::Kusama), &moonbeam),
+            AccountFormat::Id32
+        );
+        assert_eq!(account_format(None, &moonbeam), AccountFormat::Id32);
+
+        let unknown = MultiLocation::new(1, X1(Parachain(9999)));
+        assert_eq!(
+            account_format(Some(NetworkId::Polkadot), &unknown),
+            AccountFormat::Id32
+        );
+        assert_eq!(ss58_prefix(Some(NetworkId::Polkadot), &unknown), None);
+        assert_eq!(ss58_prefix(Some(NetworkId::Kusama), &RELAY), Some(2));
+    }
+
+    #[test]
+    fn validate_rejects_zero_and_wrong_format() {
+        assert_eq!(
+            AccountType::Id32([0; 32]).validate(AccountFormat::Id32),
+            Err(AccountTypeError::ZeroAccount)
+        );
+        assert_eq!(
+            AccountType::Key20([1; 20]).validate(AccountFormat::Id32),
+            Err(AccountTypeError::WrongFormat)
+        );
+        assert_eq!(
+            AccountType::Key20([1; 20]).validate(AccountFormat::Key20),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn ss58_round_trip() {
+        let account = AccountType::from_ss58(ALICE, Some(42)).unwrap();
+        assert_eq!(account.to_ss58(42).as_deref(), Some(ALICE));
+        assert_eq!(
+            AccountType::from_ss58(ALICE, Some(0)),
+            Err(AccountTypeError::WrongNetwork)
+        );
+
+        let polkadot = account.to_ss58(0).unwrap();
+        assert_eq!(AccountType::from_ss58(&polkadot, Some(0)), Ok(account));
+        assert_eq!(
+            AccountType::from_ss58("not an address", None),
+            Err(AccountTypeError::InvalidAddress)
+        );
+    }
+
+    #[test]
+    fn from_hex_accepts_only_evm_addresses() {
+        assert_eq!(
+            AccountType::from_hex("0x0101010101010101010101010101010101010101"),
+            Ok(AccountType::Key20([1; 20]))
+        );
+        assert_eq!(
+            AccountType::from_hex("0x01"),
+            Err(AccountTypeError::InvalidAddress)
+        );
+        assert!(AccountType::Key20([1; 20]).to_ss58(0).is_none());
+    }
+}
@@ -123,6 +123,62 @@ pub enum AssetType {
     Lp(AmmPool),
 }
 
+/// Risk tier of an asset, limits leverage and lending against it.
+/// Tier1 is the least risky.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Debug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RiskTier {
+    Tier1,
+    Tier2,
+    Tier3,
+}
+
+impl RiskTier {
+    /// Tier assigned to a new asset of `asset_type` until governance changes it
+    pub fn default_for(asset_type: &AssetType) -> Self {
+        match asset_type {
+            AssetType::Native | AssetType::Physical => RiskTier::Tier1,
+            AssetType::Synthetic => RiskTier::Tier2,
+            AssetType::Lp(_) => RiskTier::Tier3,
+        }
+    }
+
+    /// Only Tier1 and Tier2 assets are accepted as bailsman collateral
+    pub fn is_bailsman_eligible(&self) -> bool {
+        *self != RiskTier::Tier3
+    }
+}
+
+/// Parameter value for each risk tier
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct RiskTierParams<V> {
+    pub tier1: V,
+    pub tier2: V,
+    pub tier3: V,
+}
+
+impl<V: Copy> RiskTierParams<V> {
+    pub fn get(&self, tier: RiskTier) -> V {
+        match tier {
+            RiskTier::Tier1 => self.tier1,
+            RiskTier::Tier2 => self.tier2,
+            RiskTier::Tier3 => self.tier3,
+        }
+    }
+}
+
 // well known assets
 pub const EQD: Asset = Asset(6648164); //::from_bytes(b"eqd"); 0x657164
 pub const BTC: Asset = Asset(6452323); //::from_bytes(b"btc"); 0x627463
@@ -183,6 +239,7 @@ pub const HKO: Asset = Asset(6843247); //::from_bytes(b"hko"); 0x686B6F
 pub const KAR: Asset = Asset(7037298); //::from_bytes(b"kar"); 0x6B6172
 pub const KUSD: Asset = Asset(1802859364); //::from_bytes(b"kusd""); 0x6B757364
 pub const LKSM: Asset = Asset(1818981229); //::from_bytes(b"lksm"); 0x6C6B736D
+pub const EQKSM: Asset = Asset(435694564205); //::from_bytes(b"eqksm"); 0x65716B736D
 pub const KBTC: Asset = Asset(1801614435); //::from_bytes(b"kbtc"); 0x6B627463
 pub const SDN: Asset = Asset(7562350); //::from_bytes(b"sdn"); 0x73646E
 pub const BNC: Asset = Asset(6450787); //::from_bytes(b"bnc"); 0x626E63
@@ -404,6 +461,22 @@ pub trait AssetGetter {
     fn get_main_asset() -> Asset;
 
     fn collateral_discount(asset: &Asset) -> EqFixedU128;
+
+    /// Existential deposit of `asset` in asset units, overriding the default one
+    fn existential_deposit(_asset: &Asset) -> Option<crate::balance::Balance> {
+        None
+    }
+
+    /// Risk tier of `asset`
+    fn risk_tier(_asset: &Asset) -> RiskTier {
+        RiskTier::Tier1
+    }
+
+    /// Decimals of `asset` amounts outside of the chain: on its XCM reserve or bridged
+    /// networks. Balances on the chain always have `DECIMALS`.
+    fn decimals(_asset: &Asset) -> u8 {
+        crate::DECIMALS
+    }
 }
 
 pub trait AssetXcmGetter {
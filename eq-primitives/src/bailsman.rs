@@ -19,7 +19,8 @@
 use crate::vec_map::{SortedVec, VecMap};
 use crate::{asset::Asset, balance_number::EqFixedU128, signed_balance::EqMember, SignedBalance};
 use codec::{Decode, Encode};
-use sp_runtime::DispatchError;
+use sp_runtime::{DispatchError, Permill};
+use sp_std::vec::Vec;
 
 pub type DistributionId = u32;
 
@@ -92,3 +93,53 @@ where
     /// Length of distribution queue
     fn distribution_queue_len() -> u32;
 }
+
+/// Coverage of borrowers debt by the bailsman pool
+pub trait BailsmanCoverage {
+    /// Ratio of bailsman pool value to borrowers debt, `None` if there is no debt
+    fn coverage_ratio() -> Option<EqFixedU128>;
+}
+
+/// Priority liquidity line opened by the treasury for the bailsman pool
+#[derive(Decode, Encode, Clone, Debug, Eq, PartialEq, scale_info::TypeInfo)]
+pub struct LiquidityLine<Balance> {
+    /// Asset lent by the treasury (EQD or EQ)
+    pub asset: Asset,
+    /// Maximum principal that may be drawn from the treasury
+    pub cap: Balance,
+    /// Pool is topped up when its coverage of borrowers debt is below this ratio
+    pub coverage_threshold: EqFixedU128,
+    /// Fixed annual interest rate charged on drawn principal
+    pub rate: Permill,
+}
+
+/// Outstanding debt of the bailsman pool on the liquidity line
+#[derive(Decode, Encode, Clone, Debug, Default, Eq, PartialEq, scale_info::TypeInfo)]
+pub struct LiquidityLineDebt<Balance> {
+    /// Drawn and not yet repaid amount
+    pub principal: Balance,
+    /// Accrued and not yet repaid interest
+    pub interest: Balance,
+    /// Unix time in seconds of the last interest accrual
+    pub last_update: u64,
+}
+
+/// Liquidity line utilization exposed via runtime api
+#[derive(Decode, Encode, Clone, Debug, Eq, PartialEq, scale_info::TypeInfo)]
+pub struct LiquidityLineUtilization<Balance> {
+    pub line: LiquidityLine<Balance>,
+    pub principal: Balance,
+    pub interest: Balance,
+    /// Current coverage of borrowers debt by the bailsman pool, `None` if there is no debt
+    pub coverage: Option<EqFixedU128>,
+}
+
+/// Queued distribution and amounts it brings to a single bailsman, exposed via runtime api
+#[derive(Decode, Encode, Clone, Debug, Eq, PartialEq, scale_info::TypeInfo)]
+pub struct QueuedDistribution<Balance> {
+    pub id: DistributionId,
+    /// Bailsmen that are still not redistributed for this distribution
+    pub remaining_bailsmen: u32,
+    /// Amounts the bailsman receives (positive) or covers (negative) on redistribution
+    pub amounts: Vec<(Asset, SignedBalance<Balance>)>,
+}
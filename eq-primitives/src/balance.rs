@@ -18,13 +18,14 @@
 
 use crate::asset::Asset;
 use crate::balance_adapter::NegativeImbalance;
+use crate::balance_number::EqFixedU128;
 use crate::vec_map::VecMap;
 use crate::{AccountType, PriceGetter, SignedBalance, TransferReason};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::traits::{BalanceStatus, ExistenceRequirement, LockIdentifier, WithdrawReasons};
 use frame_support::RuntimeDebug;
 use impl_trait_for_tuples::impl_for_tuples;
-use sp_arithmetic::traits::AtLeast32BitUnsigned;
+use sp_arithmetic::traits::{AtLeast32BitUnsigned, Zero};
 use sp_core::sp_std::fmt::Debug;
 use sp_runtime::traits::{Get, Member};
 use sp_runtime::{DispatchError, DispatchResult};
@@ -136,17 +137,22 @@ pub trait EqCurrency<AccountId, Balance> {
         values: (Balance, Balance),
     ) -> Result<(), (DispatchError, Option<AccountId>)>;
 
+    /// Total reserved balance of `who`, including named reserves
     fn reserved_balance(who: &AccountId, asset: Asset) -> Balance;
 
     /// Reserve `amount` of `asset` from `who` to special account
     fn reserve(who: &AccountId, asset: Asset, amount: Balance) -> DispatchResult;
 
+    /// Slashes up to `value` from reserved balance.
+    /// Returns imbalance of slashed amount and reserved balance left.
     fn slash_reserved(
         who: &AccountId,
         asset: Asset,
         value: Balance,
     ) -> (NegativeImbalance<Balance>, Balance);
 
+    /// Moves up to `value` of reserved balance of `slashed` to free or reserved
+    /// balance of `beneficiary`. Returns reserved balance of `slashed` left.
     fn repatriate_reserved(
         slashed: &AccountId,
         beneficiary: &AccountId,
@@ -155,7 +161,8 @@ pub trait EqCurrency<AccountId, Balance> {
         status: BalanceStatus,
     ) -> Result<Balance, DispatchError>;
 
-    /// Return reserved balance back to account
+    /// Return reserved balance back to account.
+    /// Returns unreserved amount.
     fn unreserve(who: &AccountId, asset: Asset, amount: Balance) -> Balance;
 
     /// Send asset via xcm
@@ -179,6 +186,107 @@ pub trait EqCurrency<AccountId, Balance> {
     fn remove_lock(id: LockIdentifier, who: &AccountId);
 }
 
+/// Identifier of named reserve, same as `pallet_balances` uses
+pub type ReserveIdentifier = [u8; 8];
+
+/// Reserves of `EqCurrency` under identifiers, per asset.
+/// Semantics of returned values follow `NamedReservableCurrency`:
+/// methods return amounts which could not be unreserved, slashed or moved.
+pub trait EqNamedReservableCurrency<AccountId, Balance>: EqCurrency<AccountId, Balance> {
+    /// Amount reserved under `id`
+    fn reserved_balance_named(id: &ReserveIdentifier, who: &AccountId, asset: Asset) -> Balance;
+
+    /// Reserves `amount` under `id`, it's also a part of `reserved_balance`
+    fn reserve_named(
+        id: &ReserveIdentifier,
+        who: &AccountId,
+        asset: Asset,
+        amount: Balance,
+    ) -> DispatchResult;
+
+    /// Unreserves up to `amount` reserved under `id`. Returns amount left to unreserve.
+    fn unreserve_named(
+        id: &ReserveIdentifier,
+        who: &AccountId,
+        asset: Asset,
+        amount: Balance,
+    ) -> Balance;
+
+    /// Slashes up to `value` reserved under `id`. Returns amount left to slash.
+    fn slash_reserved_named(
+        id: &ReserveIdentifier,
+        who: &AccountId,
+        asset: Asset,
+        value: Balance,
+    ) -> (NegativeImbalance<Balance>, Balance);
+
+    /// Moves up to `value` reserved under `id` to `beneficiary`. With `Reserved` status
+    /// it is reserved under the same `id` on `beneficiary`. Returns amount left to move.
+    fn repatriate_reserved_named(
+        id: &ReserveIdentifier,
+        slashed: &AccountId,
+        beneficiary: &AccountId,
+        asset: Asset,
+        value: Balance,
+        status: BalanceStatus,
+    ) -> Result<Balance, DispatchError>;
+}
+
+/// Identifies which `BalanceChecker` rejected a balance change
+#[derive(
+    Encode, Decode, Clone, Copy, Debug, Eq, PartialEq, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub enum BalanceCheckerKind {
+    /// Funds are locked (vesting, staking, lockdrop etc.)
+    Locked,
+    /// Bailsman margin or temp balances restrictions
+    Bailsman,
+    /// Lending pool reserve restrictions
+    Lending,
+    /// Master account / subaccount restrictions
+    Subaccounts,
+    /// Any other checker, e.g. mocks
+    Other,
+}
+
+/// Structured error of a failed balance check. `required` and `available` are
+/// amounts of the checked asset (or zero when the checker doesn't operate with
+/// a single amount, e.g. margin checks).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceCheckError<Balance> {
+    /// Checker that rejected the change
+    pub checker: BalanceCheckerKind,
+    /// Amount needed for the change to pass
+    pub required: Balance,
+    /// Amount available on the account
+    pub available: Balance,
+    /// Underlying dispatch error returned to the caller
+    pub error: DispatchError,
+}
+
+impl<Balance: Zero> BalanceCheckError<Balance> {
+    pub fn new(checker: BalanceCheckerKind, error: impl Into<DispatchError>) -> Self {
+        Self {
+            checker,
+            required: Balance::zero(),
+            available: Balance::zero(),
+            error: error.into(),
+        }
+    }
+
+    pub fn with_amounts(mut self, required: Balance, available: Balance) -> Self {
+        self.required = required;
+        self.available = available;
+        self
+    }
+}
+
+impl<Balance> From<BalanceCheckError<Balance>> for DispatchError {
+    fn from(e: BalanceCheckError<Balance>) -> Self {
+        e.error
+    }
+}
+
 /// Interface for balance checks
 pub trait BalanceChecker<Balance, AccountId, BalanceGetter, SubaccountsManager>
 where
@@ -224,7 +332,7 @@ where
         who: &AccountId,
         changes: &Vec<(Asset, SignedBalance<Balance>)>,
         withdraw_reasons: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError>;
+    ) -> Result<(), BalanceCheckError<Balance>>;
 
     /// Checks whether a specific operation can be performed on user's balance.
     /// Not for direct implementation.
@@ -232,7 +340,7 @@ where
         who: &AccountId,
         changes: &Vec<(Asset, SignedBalance<Balance>)>,
         withdraw_reasons: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), BalanceCheckError<Balance>> {
         if Self::need_to_check(who, changes) {
             Self::can_change_balance_impl(who, changes, withdraw_reasons)
         } else {
@@ -259,7 +367,7 @@ where
         who: &AccountId,
         changes: &Vec<(Asset, SignedBalance<Balance>)>,
         reason: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), BalanceCheckError<Balance>> {
         for_tuples!( #( Tuple::can_change_balance(who, changes, reason)?; )* );
 
         Ok(())
@@ -269,7 +377,7 @@ where
         who: &AccountId,
         changes: &Vec<(Asset, SignedBalance<Balance>)>,
         reason: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), BalanceCheckError<Balance>> {
         for_tuples!( #( Tuple::can_change_balance_impl(who, changes, reason)?; )* );
 
         Ok(())
@@ -467,8 +575,12 @@ mod tests {
             _who: &u64,
             _changes: &Vec<(Asset, SignedBalance<crate::balance::Balance>)>,
             _reason: Option<WithdrawReasons>,
-        ) -> Result<(), sp_runtime::DispatchError> {
-            Err(sp_runtime::DispatchError::Other("Custom error"))
+        ) -> Result<(), BalanceCheckError<crate::balance::Balance>> {
+            Err(BalanceCheckError::new(
+                BalanceCheckerKind::Other,
+                sp_runtime::DispatchError::Other("Custom error"),
+            )
+            .with_amounts(2, 1))
         }
     }
 
@@ -481,7 +593,7 @@ mod tests {
             _who: &u64,
             _changes: &Vec<(Asset, SignedBalance<crate::balance::Balance>)>,
             _reason: Option<WithdrawReasons>,
-        ) -> Result<(), sp_runtime::DispatchError> {
+        ) -> Result<(), BalanceCheckError<crate::balance::Balance>> {
             Ok(())
         }
     }
@@ -506,7 +618,12 @@ mod tests {
             )>::can_change_balance(
                 &accounts::NEED_CUSTOM_CHECK, &negative_changes, None,
             ),
-            sp_runtime::DispatchError::Other("Custom error")
+            BalanceCheckError {
+                checker: BalanceCheckerKind::Other,
+                required: 2,
+                available: 1,
+                error: sp_runtime::DispatchError::Other("Custom error"),
+            }
         );
 
         assert_ok!(BalanceCheckerDefaultNeedToCheck::can_change_balance(
@@ -519,7 +636,8 @@ mod tests {
             <(
                 BalanceCheckerDefaultNeedToCheck,
                 BalanceCheckerCustomNeedToCheck
-            )>::can_change_balance(&accounts::MASTER, &negative_changes, None),
+            )>::can_change_balance(&accounts::MASTER, &negative_changes, None)
+            .map_err(DispatchError::from),
             sp_runtime::DispatchError::Other("Custom error")
         );
 
@@ -570,8 +688,51 @@ mod tests {
         assert_eq!(first, 33u64);
         assert_eq!(second, 67u64);
     }
+
+    #[test]
+    fn account_data_is_upgraded_on_change() {
+        use crate::asset::{BTC, EQ};
+
+        let mut balance = VecMap::new();
+        balance.insert(EQ, SignedBalance::Positive(10u128));
+        let v0 = AccountData::V0 {
+            lock: 5u128,
+            balance,
+        };
+        let encoded = v0.encode();
+
+        // reads don't upgrade stored data
+        let mut data = AccountData::<u128>::decode(&mut &encoded[..]).unwrap();
+        assert!(!data.is_latest());
+        assert_eq!(data.get(&EQ), SignedBalance::Positive(10));
+        assert_eq!(*data.lock(), 5);
+        assert_eq!(data.flags(&EQ), AssetFlags::default());
+
+        *data.entry(BTC).or_default() = SignedBalance::Negative(1);
+        assert!(data.is_latest());
+        assert_eq!(data.get(&EQ), SignedBalance::Positive(10));
+        assert_eq!(data.get(&BTC), SignedBalance::Negative(1));
+        assert_eq!(*data.lock(), 5);
+
+        let frozen = AssetFlags {
+            frozen: true,
+            ..Default::default()
+        };
+        data.set_flags(EQ, frozen);
+        assert_eq!(data.flags(&EQ), frozen);
+        data.set_flags(EQ, AssetFlags::default());
+        assert_eq!(
+            data,
+            AccountData::V1 {
+                lock: 5,
+                balance: data.clone().into_balances(),
+                flags: VecMap::new(),
+            }
+        );
+    }
 }
 
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
 pub enum XcmDestination {
     /// Send to some multilocation
     Common(xcm::v3::MultiLocation),
@@ -595,6 +756,42 @@ pub enum XcmTransferDealWithFee {
     ThisAccWillPay,
 }
 
+/// Side of a transfer rejected by `BalanceChecker`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode, scale_info::TypeInfo)]
+pub enum TransferSide {
+    Sender,
+    Receiver,
+}
+
+/// Account margin after the checked change compared to the `initial_margin` setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode, scale_info::TypeInfo)]
+pub struct MarginShortfall {
+    /// Margin of the account if the change was applied
+    pub margin: EqFixedU128,
+    /// Margin required to perform the change
+    pub required: EqFixedU128,
+}
+
+/// Result of a transfer dry run, see `EqBalancesApi::can_transfer`
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, scale_info::TypeInfo)]
+pub enum TransferCheck {
+    /// Transfer can be performed
+    Allowed,
+    /// Transfer is rejected before balance checks: transfers are disabled,
+    /// unknown asset, destination is a subaccount or amount is not enough to keep it alive
+    Rejected(DispatchError),
+    /// One of `BalanceChecker`s rejected the balance change of `side`.
+    /// `error` is a module error of the failed checker (subaccounts, locks, lending, bailsman),
+    /// `checker` identifies the failed checker,
+    /// `margin` is filled when the sender margin becomes lower than `initial_margin`
+    CheckFailed {
+        side: TransferSide,
+        checker: BalanceCheckerKind,
+        error: DispatchError,
+        margin: Option<MarginShortfall>,
+    },
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Decode, Encode, scale_info::TypeInfo)]
 pub enum DepositReason {
     /// External call for mint
@@ -609,6 +806,8 @@ pub enum DepositReason {
     Staking,
     /// Swap Crowdloan DOT to DOT
     CrowdloanDotSwap,
+    /// Surplus XCM fee reported back by destination chain
+    XcmFeeRefund,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Decode, Encode, scale_info::TypeInfo)]
@@ -627,8 +826,37 @@ pub enum WithdrawReason {
     Staking,
     /// Swap Crowdloan DOT to DOT
     CrowdloanDotSwap,
+    /// Burn of main asset bought back by treasury
+    Buyback,
+}
+
+/// Per-asset flags of an account, set by `set_asset_flags` of balances pallet
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Eq,
+    PartialEq,
+    Decode,
+    Encode,
+    scale_info::TypeInfo,
+    codec::MaxEncodedLen,
+)]
+pub struct AssetFlags {
+    /// Balance and locks of the asset can't be changed, checked by balances pallet
+    pub frozen: bool,
+    /// Balance of the asset is deposited to lending, informational only
+    pub lending_enabled: bool,
+    /// Balance of the asset is deposited to savings, informational only
+    pub savings: bool,
 }
 
+/// Balances of an account kept in `frame_system` account data.
+///
+/// Accounts stored with an older version are upgraded to the latest one on the first
+/// balance change or in bulk by `SteppedMigration`, so new versions don't need
+/// a one-shot rewrite of all accounts.
 #[derive(
     Debug, Clone, Eq, PartialEq, Decode, Encode, scale_info::TypeInfo, codec::MaxEncodedLen,
 )]
@@ -637,13 +865,20 @@ pub enum AccountData<Balance> {
         lock: Balance,
         balance: VecMap<Asset, SignedBalance<Balance>>,
     },
+    /// Adds per-asset flags, assets without flags set are not stored
+    V1 {
+        lock: Balance,
+        balance: VecMap<Asset, SignedBalance<Balance>>,
+        flags: VecMap<Asset, AssetFlags>,
+    },
 }
 
 impl<Balance: Default> Default for AccountData<Balance> {
     fn default() -> Self {
-        AccountData::V0 {
+        AccountData::V1 {
             lock: Default::default(),
             balance: Default::default(),
+            flags: Default::default(),
         }
     }
 }
@@ -653,35 +888,172 @@ impl<Balance> AccountData<Balance> {
     where
         Balance: Clone + Default,
     {
-        match self {
-            Self::V0 { balance, lock: _ } => balance.get(asset).cloned().unwrap_or_default(),
-        }
+        self.balances().get(asset).cloned().unwrap_or_default()
     }
 
     pub fn entry(
         &mut self,
         asset: Asset,
-    ) -> crate::vec_map::entry::Entry<'_, Asset, SignedBalance<Balance>> {
+    ) -> crate::vec_map::entry::Entry<'_, Asset, SignedBalance<Balance>>
+    where
+        Balance: Default,
+    {
+        self.balances_mut().entry(asset)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Asset, &SignedBalance<Balance>)> {
+        self.balances().iter()
+    }
+
+    pub fn retain<F>(&mut self, f: F)
+    where
+        Balance: Default,
+        F: FnMut(&Asset, &mut SignedBalance<Balance>) -> bool,
+    {
+        self.balances_mut().retain(f)
+    }
+
+    /// Balances of all assets
+    pub fn into_balances(self) -> VecMap<Asset, SignedBalance<Balance>> {
         match self {
-            Self::V0 { balance, lock: _ } => balance.entry(asset),
+            Self::V0 { balance, .. } | Self::V1 { balance, .. } => balance,
         }
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (&Asset, &SignedBalance<Balance>)> {
+    /// The largest lock of the main asset
+    pub fn lock(&self) -> &Balance {
         match self {
-            Self::V0 { balance, lock: _ } => balance.iter(),
+            Self::V0 { lock, .. } | Self::V1 { lock, .. } => lock,
         }
     }
-    pub fn retain<F>(&mut self, f: F)
+
+    pub fn lock_mut(&mut self) -> &mut Balance
     where
-        F: FnMut(&Asset, &mut SignedBalance<Balance>) -> bool,
+        Balance: Default,
+    {
+        self.upgrade();
+        match self {
+            Self::V0 { lock, .. } | Self::V1 { lock, .. } => lock,
+        }
+    }
+
+    /// Flags of `asset`, all flags are unset for accounts of older versions
+    pub fn flags(&self, asset: &Asset) -> AssetFlags {
+        match self {
+            Self::V0 { .. } => AssetFlags::default(),
+            Self::V1 { flags, .. } => flags.get(asset).copied().unwrap_or_default(),
+        }
+    }
+
+    pub fn set_flags(&mut self, asset: Asset, asset_flags: AssetFlags)
+    where
+        Balance: Default,
+    {
+        self.upgrade();
+        if let Self::V1 { flags, .. } = self {
+            if asset_flags == AssetFlags::default() {
+                flags.remove(&asset);
+            } else {
+                flags.insert(asset, asset_flags);
+            }
+        }
+    }
+
+    /// Whether the account data is stored with the latest version
+    pub fn is_latest(&self) -> bool {
+        matches!(self, Self::V1 { .. })
+    }
+
+    /// Converts the account data to the latest version keeping balances and lock
+    pub fn upgrade(&mut self)
+    where
+        Balance: Default,
+    {
+        if let Self::V0 { lock, balance } = self {
+            *self = Self::V1 {
+                lock: sp_std::mem::take(lock),
+                balance: sp_std::mem::take(balance),
+                flags: Default::default(),
+            };
+        }
+    }
+
+    fn balances(&self) -> &VecMap<Asset, SignedBalance<Balance>> {
+        match self {
+            Self::V0 { balance, .. } | Self::V1 { balance, .. } => balance,
+        }
+    }
+
+    /// Balances to change, upgrades the account data to the latest version
+    fn balances_mut(&mut self) -> &mut VecMap<Asset, SignedBalance<Balance>>
+    where
+        Balance: Default,
     {
+        self.upgrade();
         match self {
-            Self::V0 { balance, lock: _ } => balance.retain(f),
+            Self::V0 { balance, .. } | Self::V1 { balance, .. } => balance,
         }
     }
 }
 
 pub trait LockGetter<AccountId, Balance> {
+    /// Lock `id` on the main asset
     fn get_lock(who: AccountId, id: LockIdentifier) -> Balance;
+
+    /// Lock `id` on `asset`, zero if there is no lock or it's expired
+    fn get_asset_lock(who: AccountId, asset: Asset, id: LockIdentifier) -> Balance;
+}
+
+/// Tag of the pallet managing a lock. Locks set with `EqCurrency::set_lock`
+/// are owned by their `LockIdentifier`
+pub type LockOwner = [u8; 8];
+
+/// Lock on balance of an asset
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct AssetLock<Balance, BlockNumber> {
+    pub amount: Balance,
+    /// Last block when the lock is active, `None` for locks without expiry
+    pub expiry: Option<BlockNumber>,
+    /// Only the owner may change or remove the lock
+    pub owner: LockOwner,
+}
+
+impl<Balance, BlockNumber: PartialOrd> AssetLock<Balance, BlockNumber> {
+    pub fn is_active(&self, now: &BlockNumber) -> bool {
+        self.expiry.as_ref().map_or(true, |expiry| now <= expiry)
+    }
+}
+
+/// Locks on balances of arbitrary assets with expiry and owner
+pub trait AssetLocks<AccountId, Balance, BlockNumber> {
+    /// Sets lock `id` on `asset` balance of `who`, replacing the existing one.
+    /// Fails if the existing lock is owned by another pallet.
+    fn set_asset_lock(
+        id: LockIdentifier,
+        who: &AccountId,
+        asset: Asset,
+        amount: Balance,
+        expiry: Option<BlockNumber>,
+        owner: LockOwner,
+    ) -> DispatchResult;
+
+    /// Removes lock `id` on `asset` balance of `who`, fails if the lock is owned by another pallet
+    fn remove_asset_lock(
+        id: LockIdentifier,
+        who: &AccountId,
+        asset: Asset,
+        owner: LockOwner,
+    ) -> DispatchResult;
+
+    /// Hands over management of lock `id` on `asset` balance of `who` to `new_owner`
+    fn delegate_asset_lock(
+        id: LockIdentifier,
+        who: &AccountId,
+        asset: Asset,
+        owner: LockOwner,
+        new_owner: LockOwner,
+    ) -> DispatchResult;
+
+    /// Amount of `asset` that can't be withdrawn: the largest active lock
+    fn asset_locked(who: &AccountId, asset: Asset) -> Balance;
 }
@@ -21,15 +21,18 @@
 
 use codec::{FullCodec, MaxEncodedLen};
 use frame_support::traits::{
-    BalanceStatus, Currency, Get, LockableCurrency, ReservableCurrency, SignedImbalance,
+    BalanceStatus, Currency, Get, Imbalance, LockableCurrency, NamedReservableCurrency,
+    ReservableCurrency, SignedImbalance,
 };
 use sp_arithmetic::FixedPointOperand;
-use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Zero};
+use sp_runtime::traits::{
+    AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Saturating, Zero,
+};
 use sp_runtime::{DispatchError, DispatchResult};
 use sp_std::fmt::Debug;
 use sp_std::marker;
 
-use crate::balance::EqCurrency;
+use crate::balance::{EqCurrency, EqNamedReservableCurrency, ReserveIdentifier};
 pub use crate::imbalances::{NegativeImbalance, PositiveImbalance};
 pub use crate::signed_balance::{SignedBalance, SignedBalance::*};
 
@@ -227,7 +230,8 @@ where
     /// Is a no-op if the value to be unreserved is zero or the account does not exist.
     fn unreserve(who: &AccountId, value: Self::Balance) -> Self::Balance {
         let asset = CurrencyGetter::get();
-        MultiCurrency::unreserve(who, asset, value)
+        let unreserved = MultiCurrency::unreserve(who, asset, value);
+        value.saturating_sub(unreserved)
     }
 
     /// Slash from reserved balance, returning the negative imbalance created,
@@ -239,7 +243,9 @@ where
         value: Self::Balance,
     ) -> (Self::NegativeImbalance, Self::Balance) {
         let asset = CurrencyGetter::get();
-        MultiCurrency::slash_reserved(who, asset, value)
+        let (imbalance, _) = MultiCurrency::slash_reserved(who, asset, value);
+        let not_slashed = value.saturating_sub(imbalance.peek());
+        (imbalance, not_slashed)
     }
 
     /// Move the reserved balance of one account into the balance of another, according to `status`.
@@ -254,7 +260,74 @@ where
         status: BalanceStatus,
     ) -> Result<Self::Balance, DispatchError> {
         let asset = CurrencyGetter::get();
-        MultiCurrency::repatriate_reserved(slashed, beneficiary, asset, value, status)
+        let reserved = MultiCurrency::reserved_balance(slashed, asset);
+        let left = MultiCurrency::repatriate_reserved(slashed, beneficiary, asset, value, status)?;
+        let moved = reserved.saturating_sub(left);
+        Ok(value.saturating_sub(moved))
+    }
+}
+
+impl<AccountId, Balance, MultiCurrency, CurrencyGetter> NamedReservableCurrency<AccountId>
+    for BalanceAdapter<Balance, MultiCurrency, CurrencyGetter>
+where
+    Balance: Member
+        + AtLeast32BitUnsigned
+        + FullCodec
+        + Copy
+        + MaybeSerializeDeserialize
+        + Debug
+        + Default
+        + MaxEncodedLen
+        + scale_info::TypeInfo
+        + FixedPointOperand,
+    MultiCurrency: EqNamedReservableCurrency<AccountId, Balance>,
+    CurrencyGetter: Get<crate::asset::Asset>,
+{
+    type ReserveIdentifier = ReserveIdentifier;
+
+    fn reserved_balance_named(id: &Self::ReserveIdentifier, who: &AccountId) -> Self::Balance {
+        MultiCurrency::reserved_balance_named(id, who, CurrencyGetter::get())
+    }
+
+    fn reserve_named(
+        id: &Self::ReserveIdentifier,
+        who: &AccountId,
+        value: Self::Balance,
+    ) -> DispatchResult {
+        MultiCurrency::reserve_named(id, who, CurrencyGetter::get(), value)
+    }
+
+    fn unreserve_named(
+        id: &Self::ReserveIdentifier,
+        who: &AccountId,
+        value: Self::Balance,
+    ) -> Self::Balance {
+        MultiCurrency::unreserve_named(id, who, CurrencyGetter::get(), value)
+    }
+
+    fn slash_reserved_named(
+        id: &Self::ReserveIdentifier,
+        who: &AccountId,
+        value: Self::Balance,
+    ) -> (Self::NegativeImbalance, Self::Balance) {
+        MultiCurrency::slash_reserved_named(id, who, CurrencyGetter::get(), value)
+    }
+
+    fn repatriate_reserved_named(
+        id: &Self::ReserveIdentifier,
+        slashed: &AccountId,
+        beneficiary: &AccountId,
+        value: Self::Balance,
+        status: BalanceStatus,
+    ) -> Result<Self::Balance, DispatchError> {
+        MultiCurrency::repatriate_reserved_named(
+            id,
+            slashed,
+            beneficiary,
+            CurrencyGetter::get(),
+            value,
+            status,
+        )
     }
 }
 
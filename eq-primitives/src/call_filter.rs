@@ -0,0 +1,53 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Verdicts of runtime `CallFilter`, see `EqCallFilterApi`.
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use sp_std::boxed::Box;
+
+/// Why call is rejected by runtime `CallFilter`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum FilterReason {
+    /// Call is disabled in production
+    Disabled,
+    /// XCM calls are allowed only in multisig proposals
+    XcmNotAllowed,
+    /// Only system and multisig calls are allowed while migration is in progress
+    MigrationInProgress,
+    /// Call at `index` of batch is rejected
+    BatchedCall {
+        index: u32,
+        reason: Box<FilterReason>,
+    },
+    /// XCM `Transact` of multisig proposal is not allowed for its destination
+    XcmTransactNotAllowed,
+}
+
+/// Result of checking a call against runtime `CallFilter` without dispatching
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum FilterVerdictWithReason {
+    Allowed,
+    Rejected(FilterReason),
+}
+
+impl FilterVerdictWithReason {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, FilterVerdictWithReason::Allowed)
+    }
+}
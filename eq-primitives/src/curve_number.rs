@@ -17,12 +17,13 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::balance::Balance;
-use crate::ONE_TOKEN;
+use crate::decimals::balance_swap_decimals;
+use crate::DECIMALS;
 use equilibrium_curve_amm::traits::CheckedConvert;
 use frame_support::codec::{CompactAs, Decode, Encode};
 use sp_arithmetic::per_things::Rounding;
 use sp_runtime::helpers_128bit::multiply_by_rational_with_rounding;
-use sp_runtime::sp_std::convert::{TryFrom, TryInto};
+use sp_runtime::sp_std::convert::TryFrom;
 use sp_runtime::traits::Convert;
 use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
 use sp_runtime::{PerThing, Permill};
@@ -45,6 +46,7 @@ use sp_std::ops::{Add, Div, Mul, Sub};
 pub struct CurveNumber(u128);
 
 const CURVE_NUMBER_DIV: u128 = 1_000_000_000_000_000;
+const CURVE_NUMBER_DECIMALS: u8 = 15;
 
 impl CurveNumber {
     pub fn max_value() -> CurveNumber {
@@ -174,29 +176,17 @@ pub struct CurveNumberConvert;
 
 impl Convert<Balance, CurveNumber> for CurveNumberConvert {
     fn convert(a: Balance) -> CurveNumber {
-        if ONE_TOKEN > CurveNumber::accuracy() {
-            let accuracy = ONE_TOKEN / CurveNumber::accuracy();
-            CurveNumber::from_inner(a / accuracy)
-        } else {
-            let accuracy = CurveNumber::accuracy() / ONE_TOKEN;
-            CurveNumber::from_inner(a * accuracy)
-        }
+        CurveNumber::from_inner(
+            balance_swap_decimals(a, DECIMALS, CURVE_NUMBER_DECIMALS)
+                .expect("Wrong conversion from Balance to CurveNumber"),
+        )
     }
 }
 
 impl Convert<CurveNumber, Balance> for CurveNumberConvert {
     fn convert(a: CurveNumber) -> Balance {
-        if ONE_TOKEN > CurveNumber::accuracy() {
-            let accuracy = ONE_TOKEN / CurveNumber::accuracy();
-            (a.into_inner() * accuracy)
-                .try_into()
-                .expect("Wrong conversion from CurveNumber to Balance")
-        } else {
-            let accuracy = CurveNumber::accuracy() / ONE_TOKEN;
-            (a.into_inner() / accuracy)
-                .try_into()
-                .expect("Wrong conversion from CurveNumber to Balance")
-        }
+        balance_swap_decimals(a.into_inner(), CURVE_NUMBER_DECIMALS, DECIMALS)
+            .expect("Wrong conversion from CurveNumber to Balance")
     }
 }
 
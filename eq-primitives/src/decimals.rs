@@ -0,0 +1,126 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Scaling of amounts between native decimals and decimals of assets outside of the chain.
+//! Balances on the chain always have `DECIMALS`, external decimals of an asset are given by
+//! `AssetGetter::decimals`.
+
+use crate::{
+    asset::{Asset, AssetGetter},
+    XcmBalance, DECIMALS,
+};
+use sp_std::convert::{TryFrom, TryInto};
+
+/// Largest decimals amounts can be scaled from or to native ones
+pub const MAX_DECIMALS: u8 = 32;
+
+/// Scales `value` from `from` decimals to `to` decimals rounding down,
+/// `None` on overflow or if decimals differ too much
+pub fn balance_swap_decimals(value: XcmBalance, from: u8, to: u8) -> Option<XcmBalance> {
+    const POW_TEN: [u128; 24] = [
+        1,
+        10,
+        100,
+        1_000,
+        10_000,
+        100_000,
+        1_000_000,
+        10_000_000,
+        100_000_000,
+        1_000_000_000,
+        10_000_000_000,
+        100_000_000_000,
+        1_000_000_000_000,
+        10_000_000_000_000,
+        100_000_000_000_000,
+        1_000_000_000_000_000,
+        10_000_000_000_000_000,
+        100_000_000_000_000_000,
+        1_000_000_000_000_000_000,
+        10_000_000_000_000_000_000,
+        100_000_000_000_000_000_000,
+        1_000_000_000_000_000_000_000,
+        10_000_000_000_000_000_000_000,
+        100_000_000_000_000_000_000_000,
+    ];
+    use sp_std::cmp::Ordering::*;
+    Some(match from.cmp(&to) {
+        Less => value.checked_mul(*POW_TEN.get((to - from) as usize)?)?,
+        Equal => value,
+        Greater => value.checked_div(*POW_TEN.get((from - to) as usize)?)?,
+    })
+}
+
+/// Converts `value` in `decimals` into native balance, rounding down
+pub fn balance_from_decimals<Balance>(value: XcmBalance, decimals: u8) -> Option<Balance>
+where
+    Balance: TryFrom<XcmBalance>,
+{
+    let value = balance_swap_decimals(value, decimals, DECIMALS)?;
+    Balance::try_from(value).ok()
+}
+
+/// Converts native `value` into `decimals`, rounding down
+pub fn balance_into_decimals<Balance>(value: Balance, decimals: u8) -> Option<XcmBalance>
+where
+    Balance: TryInto<XcmBalance>,
+{
+    let value = value.try_into().ok()?;
+    balance_swap_decimals(value, DECIMALS, decimals)
+}
+
+/// Converts `value` of `asset` in its external decimals into native balance, rounding down
+pub fn to_native<A: AssetGetter, Balance>(asset: &Asset, value: XcmBalance) -> Option<Balance>
+where
+    Balance: TryFrom<XcmBalance>,
+{
+    balance_from_decimals(value, A::decimals(asset))
+}
+
+/// Converts native `value` of `asset` into its external decimals, rounding down
+pub fn from_native<A: AssetGetter, Balance>(asset: &Asset, value: Balance) -> Option<XcmBalance>
+where
+    Balance: TryInto<XcmBalance>,
+{
+    balance_into_decimals(value, A::decimals(asset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_decimals_scales_both_ways() {
+        assert_eq!(balance_swap_decimals(1_234_567, 6, 9), Some(1_234_567_000));
+        assert_eq!(balance_swap_decimals(1_234_567_891, 9, 6), Some(1_234_567));
+        assert_eq!(balance_swap_decimals(42, 18, 18), Some(42));
+        assert_eq!(balance_swap_decimals(u128::MAX, 9, 18), None);
+        assert_eq!(balance_swap_decimals(1, 0, MAX_DECIMALS + 1), None);
+        assert!(balance_swap_decimals(1, DECIMALS, MAX_DECIMALS).is_some());
+    }
+
+    #[test]
+    fn native_balance_from_and_into_decimals() {
+        assert_eq!(
+            balance_from_decimals::<u64>(1_000_000_000_000_000_000, 18),
+            Some(1_000_000_000)
+        );
+        assert_eq!(balance_from_decimals::<u64>(u128::MAX, 6), None);
+        assert_eq!(balance_into_decimals(1_000_000_000u64, 6), Some(1_000_000));
+    }
+}
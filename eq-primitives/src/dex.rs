@@ -18,7 +18,7 @@
 
 use crate::vec_map::VecMap;
 use crate::{asset::Asset, balance_number::EqFixedU128};
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::dispatch::DispatchResultWithPostInfo;
 use sp_arithmetic::traits::{CheckedAdd, CheckedMul, CheckedSub, Zero};
 use sp_arithmetic::FixedI64;
@@ -177,7 +177,21 @@ impl<AccountId> OrderAggregates<AccountId> for () {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Decode, Encode, scale_info::TypeInfo)]
+/// Provides the market price of an asset formed by the order book
+pub trait MarkPriceGetter {
+    /// Mid price between the best ask and the best bid, `None` if one of the sides is empty
+    fn mark_price(asset: &Asset) -> Option<FixedI64>;
+}
+
+impl MarkPriceGetter for () {
+    fn mark_price(_asset: &Asset) -> Option<FixedI64> {
+        None
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Decode, Encode, MaxEncodedLen, scale_info::TypeInfo,
+)]
 pub enum DeleteOrderReason {
     /// Deleted by offchain worker due to going out of the corridor
     OutOfCorridor,
@@ -191,4 +205,6 @@ pub enum DeleteOrderReason {
     Match,
     /// Deleted by matching due to exchange error on maker side
     MakerError,
+    /// Deleted by self-trade prevention of the order owner
+    SelfTrade,
 }
@@ -0,0 +1,37 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Lending pool reward history, see `EqLendingApi::reward_checkpoints`
+
+use crate::balance_number::EqFixedU128;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+
+/// Reward index of a lending pool as of the end of `block`. The index stays the same
+/// until the block of the next checkpoint.
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub struct RewardCheckpoint<BlockNumber> {
+    /// Block of the index change
+    pub block: BlockNumber,
+    /// Timestamp of the block, in seconds
+    pub timestamp: u64,
+    /// Cumulative reward per lent unit, in main asset
+    pub index: EqFixedU128,
+}
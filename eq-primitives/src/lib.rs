@@ -22,9 +22,11 @@
 use balance::Balance;
 use balance_number::EqFixedU128;
 use frame_support::{
-    codec::{Decode, Encode, FullCodec},
+    codec::{Decode, Encode, FullCodec, MaxEncodedLen},
     dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+    weights::Weight,
 };
+use impl_trait_for_tuples::impl_for_tuples;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_arithmetic::traits::Zero;
@@ -46,23 +48,30 @@ pub use crate::dex::*;
 pub use crate::price::{PriceGetter, PriceSetter};
 pub use crate::signed_balance::SignedBalance;
 
+pub mod address;
 pub mod asset;
 pub mod bailsman;
 pub mod bailsman_redistribute_weight;
 pub mod balance;
 pub mod balance_adapter;
 pub mod balance_number;
+pub mod call_filter;
 pub mod chainbridge;
 pub mod curve_number;
+pub mod decimals;
 pub mod dex;
 pub mod financial_storage;
 pub mod imbalances;
+pub mod lending;
 #[cfg(feature = "std")]
 pub mod mocks;
 pub mod offchain_batcher;
+pub mod portfolio;
 pub mod price;
 pub mod proxy;
 pub mod signed_balance;
+pub mod solvency;
+pub mod state_export;
 pub mod subaccount;
 pub mod vec_map;
 pub mod vestings;
@@ -95,6 +104,8 @@ pub enum UserGroup {
     Bailsmen = 2,
     /// User is a borrower
     Borrowers = 3,
+    /// Account keeps EQD savings deposits
+    Savers = 4,
 }
 
 impl Eq for UserGroup {}
@@ -106,6 +117,7 @@ impl UserGroup {
             UserGroup::Balances,
             UserGroup::Bailsmen,
             UserGroup::Borrowers,
+            UserGroup::Savers,
         ])
     }
 }
@@ -149,6 +161,22 @@ where
     /// Used to iterate over all accounts of a user group
     fn iter_account(user_group: UserGroup) -> Box<dyn Iterator<Item = AccountId>>;
 
+    /// Used to resume iteration over accounts of a user group after `start_after`
+    fn iter_account_from(
+        user_group: UserGroup,
+        start_after: &AccountId,
+    ) -> Box<dyn Iterator<Item = AccountId>>
+    where
+        AccountId: Clone + PartialEq + 'static,
+    {
+        let start_after = start_after.clone();
+        Box::new(
+            Self::iter_account(user_group)
+                .skip_while(move |acc| *acc != start_after)
+                .skip(1),
+        )
+    }
+
     /// Used to iterate over currency total of a user group
     fn iter_total(
         user_group: UserGroup,
@@ -232,6 +260,30 @@ pub enum TransferReason {
 
     /// Swap asset to Q
     QSwap,
+
+    /// Protocol fee for outbound bridge transfer
+    BridgeFee,
+
+    /// Treasury liquidity line draws and repayments of the bailsman pool
+    LiquidityLine,
+
+    /// Treasury compensation of a liquidation caused by a price misprice
+    LiquidationCompensation,
+
+    /// Return of funds held for XCM transfer that could not be sent
+    XcmTransferRefund,
+
+    /// Transfer held in escrow until unlock block
+    Timelock,
+
+    /// Move of balances to the new account of the owner
+    AccountMigration,
+
+    /// Payout of inbound XCM deposit worth less than existential deposit
+    XcmDust,
+
+    /// Settlement of perpetual futures positions results
+    PerpsSettlement,
 }
 
 impl Eq for TransferReason {}
@@ -259,6 +311,16 @@ pub trait EqBuyout<AccountId, Balance> {
         amount_buyout: Balance,
     ) -> Result<bool, DispatchError>;
 }
+/// Receiver of the treasury part of interest fees charged in eq-rate
+pub trait InterestIncomeReceiver<Balance> {
+    /// `amount` of `asset` is charged as treasury interest fee
+    fn on_interest_income(asset: Asset, amount: Balance);
+}
+
+impl<Balance> InterestIncomeReceiver<Balance> for () {
+    fn on_interest_income(_: Asset, _: Balance) {}
+}
+
 pub trait LendingPoolManager<Balance, AccountId> {
     /// Adds new rewards in lending pool
     fn add_reward(asset: Asset, reward: Balance) -> DispatchResult;
@@ -266,6 +328,12 @@ pub trait LendingPoolManager<Balance, AccountId> {
     fn add_deposit(account: &AccountId, asset: &Asset, amount: &Balance) -> DispatchResult;
 
     fn remove_deposit(account: &AccountId, asset: &Asset) -> Result<Balance, DispatchError>;
+
+    /// Transfers `amount` of `asset` from `account` balance into the lending pool
+    fn supply(account: &AccountId, asset: &Asset, amount: &Balance) -> DispatchResult;
+
+    /// Transfers `amount` of `asset` supplied by `account` back to its balance
+    fn redeem(account: &AccountId, asset: &Asset, amount: &Balance) -> DispatchResult;
 }
 
 /// Empty implementation for using in unit tests
@@ -284,6 +352,69 @@ where
     fn remove_deposit(_account: &AccountId, _asset: &Asset) -> Result<Balance, DispatchError> {
         Ok(Balance::zero())
     }
+
+    fn supply(_account: &AccountId, _asset: &Asset, _amount: &Balance) -> DispatchResult {
+        Ok(())
+    }
+
+    fn redeem(_account: &AccountId, _asset: &Asset, _amount: &Balance) -> DispatchResult {
+        Ok(())
+    }
+}
+
+pub use equilibrium_curve_amm::PoolId as CurvePoolId;
+
+/// Liquidity provision into Curve pools
+pub trait CurveLiquidity<AccountId, Balance> {
+    /// Assets of the pool with their balances in the pool
+    fn pool_balances(pool_id: CurvePoolId) -> Option<Vec<(Asset, Balance)>>;
+
+    /// Deposits `amounts` of pool assets from `who` balance into the pool,
+    /// fails if less than `min_mint_amount` of LP tokens is minted
+    fn add_liquidity(
+        who: &AccountId,
+        pool_id: CurvePoolId,
+        amounts: Vec<Balance>,
+        min_mint_amount: Balance,
+    ) -> DispatchResult;
+
+    /// Exchanges `dx` of pool asset with index `i` from `who` balance to pool asset with index `j`,
+    /// fails if less than `min_dy` is received
+    fn exchange(
+        who: &AccountId,
+        pool_id: CurvePoolId,
+        i: u32,
+        j: u32,
+        dx: Balance,
+        min_dy: Balance,
+    ) -> DispatchResult;
+}
+
+/// Empty implementation for using in unit tests
+impl<AccountId, Balance> CurveLiquidity<AccountId, Balance> for () {
+    fn pool_balances(_pool_id: CurvePoolId) -> Option<Vec<(Asset, Balance)>> {
+        None
+    }
+
+    fn add_liquidity(
+        _who: &AccountId,
+        _pool_id: CurvePoolId,
+        _amounts: Vec<Balance>,
+        _min_mint_amount: Balance,
+    ) -> DispatchResult {
+        Err(DispatchError::Other("No curve pools"))
+    }
+
+    fn exchange(
+        _who: &AccountId,
+        _pool_id: CurvePoolId,
+        _i: u32,
+        _j: u32,
+        _dx: Balance,
+        _min_dy: Balance,
+    ) -> DispatchResult {
+        Err(DispatchError::Other("No curve pools"))
+    }
 }
 
 pub trait LendingAssetRemoval<AccountId> {
@@ -298,6 +429,24 @@ impl<AccountId> LendingAssetRemoval<AccountId> for () {
     fn remove_from_aggregates_and_rewards(_: &Asset) {}
     fn remove_from_lenders(_: &Asset, _: &AccountId) {}
 }
+
+/// Isolation mode of lending pools for long-tail assets
+pub trait LendingIsolation {
+    /// Collateral assets `asset` can be borrowed against, `None` if `asset` pool isn't isolated.
+    /// Isolated assets have zero collateral weight.
+    fn isolated_collateral(asset: &Asset) -> Option<Vec<Asset>>;
+
+    fn is_isolated(asset: &Asset) -> bool {
+        Self::isolated_collateral(asset).is_some()
+    }
+}
+
+/// Empty implementation for using in unit tests
+impl LendingIsolation for () {
+    fn isolated_collateral(_: &Asset) -> Option<Vec<Asset>> {
+        None
+    }
+}
 /// Equilibrium Rate pallet trait, used to set timestamp of account last update.
 /// Used for reinits and fee calculations
 pub trait UpdateTimeManager<AccountId> {
@@ -390,6 +539,15 @@ impl MarginState {
     }
 }
 
+/// Margin of an account after hypothetical changes, see `EqMarginCallApi::simulate`
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MarginSimulation {
+    /// Margin state the account would have
+    pub state: MarginState,
+    /// Portfolio margin the account would have
+    pub margin: EqFixedU128,
+}
+
 pub trait MarginCallManager<AccountId, Balance>
 where
     Balance: Member + Debug,
@@ -411,6 +569,220 @@ where
     fn get_critical_margin() -> EqFixedU128;
 }
 
+/// Open derivative positions, accounted in the margin calculation and closed before
+/// the account is liquidated
+pub trait DerivativePositions<AccountId> {
+    /// Exposure of `who` positions represented as orders filled at the entry price
+    fn get_position_changes(who: &AccountId) -> Vec<OrderChange>;
+
+    /// Closes all positions of `who` and settles their result into account balances.
+    /// Must not fail because the counterparty can't pay the result.
+    fn close_positions(who: &AccountId) -> DispatchResult;
+}
+
+impl<AccountId> DerivativePositions<AccountId> for () {
+    fn get_position_changes(_who: &AccountId) -> Vec<OrderChange> {
+        Vec::new()
+    }
+
+    fn close_positions(_who: &AccountId) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// Category of treasury flows in treasury financial reports
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TreasuryFlowCategory {
+    /// Transaction fees
+    TxFees,
+    /// Penalties of DEX orders
+    DexPenalties,
+    /// Assets exchanged for the basic asset in treasury buyouts
+    Buyouts,
+    /// Treasury share of interest fees
+    InterestShare,
+}
+
+/// Records treasury inflows and outflows for treasury financial reports
+pub trait TreasuryAccounting<Balance> {
+    /// Treasury received `amount` of `asset`
+    fn record_inflow(category: TreasuryFlowCategory, asset: Asset, amount: Balance);
+
+    /// Treasury paid `amount` of `asset`
+    fn record_outflow(category: TreasuryFlowCategory, asset: Asset, amount: Balance);
+}
+
+impl<Balance> TreasuryAccounting<Balance> for () {
+    fn record_inflow(_category: TreasuryFlowCategory, _asset: Asset, _amount: Balance) {}
+
+    fn record_outflow(_category: TreasuryFlowCategory, _asset: Asset, _amount: Balance) {}
+}
+
+/// Critical risk event kept in a per-account notifications buffer
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub enum RiskNotificationKind {
+    /// Margin fell below `initial_margin`, borrowing is prohibited
+    MarginBelowInitial,
+    /// Margin fell below `maintenance_margin`, account should be topped up before `deadline`
+    ApproachingMaintenance { deadline: u64 },
+    /// Order was deleted by offchain worker and penalty fee was charged
+    OrderPenalized {
+        asset: Asset,
+        order_id: OrderId,
+        reason: DeleteOrderReason,
+    },
+    /// Account will be liquidated as soon as its orders are deleted
+    IncomingLiquidation,
+}
+
+#[derive(
+    Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub struct RiskNotification<AccountId> {
+    /// Sequential per-account identifier, used to acknowledge notifications
+    pub id: u64,
+    pub kind: RiskNotificationKind,
+    /// Subaccount the event relates to, `None` for the main account
+    pub subaccount: Option<(subaccount::SubAccType, AccountId)>,
+    /// Unix time in seconds
+    pub timestamp: u64,
+}
+
+/// Cumulative interest charged from an account in a fee asset
+#[derive(
+    Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub struct InterestStatement<Balance> {
+    /// Total interest paid
+    pub total: Balance,
+    /// Paid to Treasury and block authors
+    pub treasury: Balance,
+    /// Paid to bailsmen
+    pub bailsman: Balance,
+    /// Paid to lending pools
+    pub lender: Balance,
+}
+
+/// Stores critical risk notifications for accounts
+pub trait RiskNotifier<AccountId> {
+    /// Adds notification for `who`, subaccount events are stored for the owner
+    fn notify(who: &AccountId, kind: RiskNotificationKind);
+}
+
+impl<AccountId> RiskNotifier<AccountId> for () {
+    fn notify(_who: &AccountId, _kind: RiskNotificationKind) {}
+}
+
+//------------- for eq-staking --------------------
+/// Democracy voting on behalf of stakers
+pub trait ConvictionVoting<AccountId, Balance> {
+    /// Duration of a single conviction lock period (`Locked1x`) in seconds
+    fn lock_period_secs() -> u64;
+
+    /// Longest possible democracy lock for a vote made now in seconds:
+    /// voting period plus `Locked6x` lock duration
+    fn max_lock_secs() -> u64;
+
+    /// Seconds left until the end of referendum `ref_index`, `None` if it is not ongoing
+    fn referendum_remaining_secs(ref_index: u32) -> Option<u64>;
+
+    /// Votes in referendum `ref_index` with `balance` and `conviction`,
+    /// where `conviction` in `1..=6` stands for `Locked1x..=Locked6x`
+    fn vote(
+        who: &AccountId,
+        ref_index: u32,
+        aye: bool,
+        balance: Balance,
+        conviction: u8,
+    ) -> DispatchResult;
+}
+
+impl<AccountId, Balance> ConvictionVoting<AccountId, Balance> for () {
+    fn lock_period_secs() -> u64 {
+        0
+    }
+
+    fn max_lock_secs() -> u64 {
+        u64::MAX
+    }
+
+    fn referendum_remaining_secs(_ref_index: u32) -> Option<u64> {
+        None
+    }
+
+    fn vote(
+        _who: &AccountId,
+        _ref_index: u32,
+        _aye: bool,
+        _balance: Balance,
+        _conviction: u8,
+    ) -> DispatchResult {
+        Err(DispatchError::Other("Conviction voting is not supported"))
+    }
+}
+
+/// Balance of the account staked in eq-staking
+pub trait StakedBalance<AccountId, Balance> {
+    fn staked_balance(who: &AccountId) -> Balance;
+}
+
+impl<AccountId, Balance: Default> StakedBalance<AccountId, Balance> for () {
+    fn staked_balance(_who: &AccountId) -> Balance {
+        Balance::default()
+    }
+}
+
+/// Moves state of an account kept by a pallet to another account of the same owner
+pub trait AccountMigration<AccountId> {
+    /// Checks that the state of `from` can be moved to `to`
+    fn can_migrate(from: &AccountId, to: &AccountId) -> DispatchResult;
+    /// Moves the state of `from` to `to`, merging it with the existing state of `to`
+    fn migrate(from: &AccountId, to: &AccountId) -> DispatchResult;
+}
+
+// Pallets of the tuple move their parts of the account state in order
+#[impl_for_tuples(8)]
+impl<AccountId> AccountMigration<AccountId> for Tuple {
+    fn can_migrate(from: &AccountId, to: &AccountId) -> DispatchResult {
+        for_tuples!( #( Tuple::can_migrate(from, to)?; )* );
+        Ok(())
+    }
+
+    fn migrate(from: &AccountId, to: &AccountId) -> DispatchResult {
+        for_tuples!( #( Tuple::migrate(from, to)?; )* );
+        Ok(())
+    }
+}
+
+/// Storage upgraded in steps over several blocks
+pub trait SteppedMigration {
+    /// Upgrades up to `limit` items following the one with raw storage key `cursor`.
+    /// Returns the key of the last processed item, `None` if there is nothing left,
+    /// and the consumed weight.
+    fn migrate_step(cursor: Option<Vec<u8>>, limit: u32) -> (Option<Vec<u8>>, Weight);
+}
+
+impl SteppedMigration for () {
+    fn migrate_step(_cursor: Option<Vec<u8>>, _limit: u32) -> (Option<Vec<u8>>, Weight) {
+        (None, Weight::zero())
+    }
+}
+
 /// Equilibrium Vesting pallet trait used to update accounts locks
 pub trait Vesting<AccountId> {
     fn update_vest_lock(who: AccountId) -> DispatchResultWithPostInfo;
@@ -434,7 +806,7 @@ pub trait Crowdloan<AccountId, Balance> {
     fn allocation_exists(who: &AccountId) -> bool;
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Encode, Decode)]
 pub struct OrderChange {
     pub asset: Asset,
     pub amount: EqFixedU128,
@@ -529,6 +901,40 @@ pub enum XcmMode {
     Xcm(bool),
 }
 
+/// Deny-list of inbound XCM deposits, received funds are trapped instead of minted
+pub trait XcmDepositFreeze {
+    /// Deposits of `asset` are refused
+    fn is_asset_frozen(asset: &Asset) -> bool;
+    /// Deposits received from `origin` are refused
+    fn is_origin_frozen(origin: &xcm::v3::MultiLocation) -> bool;
+}
+
+impl XcmDepositFreeze for () {
+    fn is_asset_frozen(_asset: &Asset) -> bool {
+        false
+    }
+    fn is_origin_frozen(_origin: &xcm::v3::MultiLocation) -> bool {
+        false
+    }
+}
+
+/// Handler of inbound XCM deposits worth less than existential deposit
+pub trait XcmDustHandler<AccountId, Balance> {
+    /// Takes `amount` of `asset` deposited to `who` if it's dust, returns `false` if the
+    /// deposit should be minted to `who` as usual
+    fn handle_dust(who: &AccountId, asset: Asset, amount: Balance) -> Result<bool, DispatchError>;
+}
+
+impl<AccountId, Balance> XcmDustHandler<AccountId, Balance> for () {
+    fn handle_dust(
+        _who: &AccountId,
+        _asset: Asset,
+        _amount: Balance,
+    ) -> Result<bool, DispatchError> {
+        Ok(false)
+    }
+}
+
 /// Account used to distribute balances to bailsmen.
 /// Used by eq_bailsman & eq_rate pallets.
 pub const DISTRIBUTION_ACC: frame_support::PalletId = frame_support::PalletId(*b"distbail");
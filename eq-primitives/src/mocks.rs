@@ -136,6 +136,9 @@ impl<B: Zero> Get<B> for ZeroFee {
 /// `XcmToFee` with zero fee
 pub type XcmToFeeZeroMock = XcmToFeeMock<ZeroFee>;
 
+/// XCM response origin that never succeeds
+pub type NeverEnsureResponseMock = frame_support::traits::NeverEnsureOrigin<MultiLocation>;
+
 pub struct UniversalLocationMock;
 impl Get<InteriorMultiLocation> for UniversalLocationMock {
     fn get() -> InteriorMultiLocation {
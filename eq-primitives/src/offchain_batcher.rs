@@ -16,10 +16,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use codec::{Decode, Encode, MaxEncodedLen};
 #[allow(unused_imports)]
 use frame_support::debug;
-use sp_runtime::RuntimeDebug;
-use sp_std::prelude::Vec;
+use sp_runtime::{
+    transaction_validity::{InvalidTransaction, ValidTransaction},
+    RuntimeDebug,
+};
+use sp_std::{prelude::Vec, vec};
 
 /// Errors for offchain worker operations
 #[derive(RuntimeDebug)]
@@ -33,6 +37,105 @@ pub enum OffchainErr {
 /// Result of batcher execute
 pub type OffchainResult<T = ()> = Result<T, OffchainErr>;
 
+/// Validator duty performed with unsigned transactions.
+/// Nonces and per block limits are tracked for every validator and duty separately
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+pub enum UnsignedDuty {
+    /// `eq-rate` reinit, account deletion and removed asset balances
+    Rate,
+    /// `eq-dex` deletion of unfit orders
+    DexOrders,
+    /// `eq-bailsman` redistribution
+    BailsmanRedistribution,
+    /// `eq-lockdrop` unlocks
+    LockdropUnlock,
+    /// `eq-dex` update of corridors derived from volatility
+    DexCorridors,
+}
+
+/// Nonce and per block usage of a validator duty
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+pub struct DutyState<BlockNumber> {
+    /// Nonce expected in the next transaction, lower nonces are stale
+    pub nonce: u64,
+    /// Block of the last executed transaction
+    pub block: BlockNumber,
+    /// Number of transactions executed in `block`
+    pub used: u32,
+}
+
+impl<BlockNumber: PartialEq + Copy> DutyState<BlockNumber> {
+    /// Checks unsigned transaction with `nonce` in block `now`:
+    /// - stale nonces are rejected, so signed requests can't be replayed;
+    /// - no more than `cap` nonces ahead of the state are accepted into the pool;
+    /// - no more than `cap` transactions are executed in one block.
+    ///
+    /// Returned validity provides `(tag, nonce)` and requires `(tag, nonce - 1)` for nonces
+    /// ahead of the state, so the pool keeps transactions of the duty in nonce order.
+    pub fn validate<Tag: Encode>(
+        &self,
+        tag: Tag,
+        nonce: u64,
+        now: BlockNumber,
+        cap: u32,
+    ) -> Result<ValidTransaction, InvalidTransaction> {
+        if nonce < self.nonce {
+            return Err(InvalidTransaction::Stale);
+        }
+        if nonce - self.nonce >= cap as u64 {
+            return Err(InvalidTransaction::Future);
+        }
+        if self.block == now && self.used >= cap {
+            return Err(InvalidTransaction::ExhaustsResources);
+        }
+
+        let requires = if nonce > self.nonce {
+            vec![(&tag, nonce - 1).encode()]
+        } else {
+            Vec::new()
+        };
+        Ok(ValidTransaction {
+            requires,
+            provides: vec![(&tag, nonce).encode()],
+            ..Default::default()
+        })
+    }
+
+    /// Notes executed transaction with `nonce` in block `now`
+    pub fn note(&mut self, nonce: u64, now: BlockNumber) {
+        self.nonce = nonce.saturating_add(1);
+        if self.block == now {
+            self.used = self.used.saturating_add(1);
+        } else {
+            self.block = now;
+            self.used = 1;
+        }
+    }
+}
+
 /// Used for execute batch of operations in offchain worker
 /// on validators nodes
 pub trait ValidatorOffchainBatcher<AuthorityId, BlockNumber: Copy, AccountId> {
@@ -76,11 +179,26 @@ pub trait ValidatorOffchainBatcher<AuthorityId, BlockNumber: Copy, AccountId> {
 
     fn get_validators_len() -> u32;
 
+    /// Nonce and usage of `duty` by the validator with `authority_index`
+    fn duty_state(authority_index: u32, duty: UnsignedDuty) -> DutyState<BlockNumber>;
+
+    /// Checks nonce and per block limit of unsigned transaction,
+    /// should be called in `ValidateUnsigned` after the signature check.
+    /// Returned nonce tags should be combined with the validity of the transaction
+    fn validate_duty(
+        authority_index: u32,
+        duty: UnsignedDuty,
+        nonce: u64,
+    ) -> Result<ValidTransaction, InvalidTransaction>;
+
+    /// Notes executed unsigned transaction, should be called on dispatch
+    fn note_duty(authority_index: u32, duty: UnsignedDuty, nonce: u64);
+
     #[cfg(feature = "runtime-benchmarks")]
     fn set_local_authority_keys(keys: Vec<AuthorityId>);
 }
 
-impl<AuthorityId, BlockNumber: Copy, AccountId>
+impl<AuthorityId, BlockNumber: Copy + Default, AccountId>
     ValidatorOffchainBatcher<AuthorityId, BlockNumber, AccountId> for ()
 {
     fn authority_keys() -> Vec<AuthorityId> {
@@ -95,6 +213,73 @@ impl<AuthorityId, BlockNumber: Copy, AccountId>
         0
     }
 
+    fn duty_state(_authority_index: u32, _duty: UnsignedDuty) -> DutyState<BlockNumber> {
+        DutyState {
+            nonce: 0,
+            block: Default::default(),
+            used: 0,
+        }
+    }
+
+    fn validate_duty(
+        _authority_index: u32,
+        _duty: UnsignedDuty,
+        _nonce: u64,
+    ) -> Result<ValidTransaction, InvalidTransaction> {
+        Ok(ValidTransaction::default())
+    }
+
+    fn note_duty(_authority_index: u32, _duty: UnsignedDuty, _nonce: u64) {}
+
     #[cfg(feature = "runtime-benchmarks")]
     fn set_local_authority_keys(_keys: Vec<AuthorityId>) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duty_state_rejects_replay_and_flood() {
+        let cap = 2;
+        let mut state = DutyState::<u32>::default();
+
+        assert!(state.validate(0u32, 0, 1, cap).is_ok());
+        assert!(state.validate(0u32, 1, 1, cap).is_ok());
+        assert_eq!(
+            state.validate(0u32, 2, 1, cap),
+            Err(InvalidTransaction::Future)
+        );
+
+        state.note(1, 1);
+        assert_eq!(state.nonce, 2);
+        assert_eq!(
+            state.validate(0u32, 1, 1, cap),
+            Err(InvalidTransaction::Stale)
+        );
+        assert!(state.validate(0u32, 2, 1, cap).is_ok());
+
+        state.note(2, 1);
+        assert_eq!(
+            state.validate(0u32, 3, 1, cap),
+            Err(InvalidTransaction::ExhaustsResources)
+        );
+        // limit is reset in the next block
+        assert!(state.validate(0u32, 3, 2, cap).is_ok());
+        state.note(3, 2);
+        assert_eq!(state.used, 1);
+    }
+
+    #[test]
+    fn duty_state_orders_nonces_ahead() {
+        let state = DutyState::<u32>::default();
+
+        let current = state.validate(7u32, 0, 1, 2).unwrap();
+        assert_eq!(current.provides, vec![(7u32, 0u64).encode()]);
+        assert!(current.requires.is_empty());
+
+        let ahead = state.validate(7u32, 1, 1, 2).unwrap();
+        assert_eq!(ahead.provides, vec![(7u32, 1u64).encode()]);
+        assert_eq!(ahead.requires, vec![(7u32, 0u64).encode()]);
+    }
+}
@@ -0,0 +1,58 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Account state aggregated across pallets, see `EqBalancesApi::account_portfolio`
+
+use crate::{asset::Asset, dex::Order, signed_balance::SignedBalance, subaccount::SubAccType};
+use codec::{Decode, Encode};
+use frame_support::{traits::LockIdentifier, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// Lender deposit in lending pool
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct LendingPosition<Balance> {
+    pub asset: Asset,
+    /// Deposited amount
+    pub deposit: Balance,
+    /// Reward in main asset accrued and not paid out yet
+    pub pending_reward: Balance,
+}
+
+/// Balances and open orders of a subaccount
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct SubaccountPortfolio<AccountId, Balance> {
+    pub subacc_type: SubAccType,
+    pub account_id: AccountId,
+    pub balances: Vec<(Asset, SignedBalance<Balance>)>,
+    pub orders: Vec<(Asset, Order<AccountId>)>,
+}
+
+/// Everything about an account in one structure
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct AccountPortfolio<AccountId, Balance> {
+    pub balances: Vec<(Asset, SignedBalance<Balance>)>,
+    pub reserved: Vec<(Asset, Balance)>,
+    /// Locks by asset and lock id
+    pub locks: Vec<(Asset, LockIdentifier, Balance)>,
+    pub subaccounts: Vec<SubaccountPortfolio<AccountId, Balance>>,
+    /// Open DEX orders of the main account
+    pub orders: Vec<(Asset, Order<AccountId>)>,
+    pub lending: Vec<LendingPosition<Balance>>,
+    /// Amount yet to be vested per vesting asset
+    pub vesting: Vec<(Asset, Balance)>,
+}
@@ -32,6 +32,11 @@ pub trait PriceGetter {
     fn get_price<FixedNumber: FixedPointNumber + One + Zero + Debug + TryFrom<FixedI64>>(
         asset: &Asset,
     ) -> Result<FixedNumber, sp_runtime::DispatchError>;
+
+    /// Whether the `asset` price jumped over the allowed limit and isn't confirmed yet
+    fn is_price_suspect(_asset: &Asset) -> bool {
+        false
+    }
 }
 
 /// Interface for adding a new `DataPoint` containing `asset` price information
@@ -87,6 +92,11 @@ pub mod mock {
             result
         }
 
+        /// Sets `price` of `asset`, the setter of pallet local oracle mocks
+        pub fn set_price_mock(asset: &Asset, price: &FixedI64) {
+            Self::add(*asset, *price);
+        }
+
         pub fn remove(asset: &Asset) {
             Self::PRICES.with(|h| {
                 let mut hashmap = h.borrow().clone();
@@ -0,0 +1,52 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Chain-wide solvency state aggregated across pallets, see `EqSolvencyApi::solvency_metrics`
+
+use crate::{asset::Asset, balance_number::EqFixedU128, signed_balance::SignedBalance};
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Totals of an asset over all accounts
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct AssetSolvency<Balance> {
+    pub asset: Asset,
+    /// Positive balances of all accounts
+    pub collateral: Balance,
+    /// Debt of all accounts except bailsmen
+    pub debt: Balance,
+    /// Deposits of lenders in lending pool
+    pub lent: Balance,
+    /// Debt to amount lendable by lenders and bailsmen, `None` if nothing is lendable
+    pub lending_utilization: Option<EqFixedU128>,
+}
+
+/// Solvency dashboard metrics in one structure
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct SolvencyMetrics<Balance> {
+    pub assets: Vec<AssetSolvency<Balance>>,
+    /// Collateral of all assets in USD, assets without price are not counted
+    pub total_collateral_usd: Balance,
+    /// Debt of all assets in USD, assets without price are not counted
+    pub total_debt_usd: Balance,
+    /// Bailsman pool value to borrowers debt, `None` if there is no debt
+    pub bailsman_coverage: Option<EqFixedU128>,
+    /// Balances of treasury account
+    pub treasury: Vec<(Asset, SignedBalance<Balance>)>,
+}
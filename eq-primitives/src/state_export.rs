@@ -0,0 +1,85 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Compact state snapshots for cross-chain consumers, see `EqStateExportApi`.
+//!
+//! Snapshots are consumed by contracts and light clients on other chains, so their SCALE
+//! layout is a public format: a layout is never changed once released, a new layout is
+//! added as a new variant of the versioned enum instead.
+
+use crate::{asset::Asset, balance::Balance, signed_balance::SignedBalance};
+use codec::{Decode, Encode};
+use sp_arithmetic::FixedI64;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Balances of an account at `block_number`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct BalancesV1<AccountId> {
+    pub account: AccountId,
+    pub block_number: u32,
+    /// Non-zero balances, debt is `SignedBalance::Negative`
+    pub balances: Vec<(Asset, SignedBalance<Balance>)>,
+}
+
+/// Latest oracle price of an asset
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct PriceV1 {
+    pub asset: Asset,
+    /// USD price with 9 decimals
+    pub price: FixedI64,
+    /// Unix timestamp of the price in seconds
+    pub timestamp: u64,
+    /// Block in which the price was set
+    pub block_number: u32,
+}
+
+/// Versioned balances snapshot
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum VersionedBalances<AccountId> {
+    V1(BalancesV1<AccountId>),
+}
+
+/// Versioned prices snapshot
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum VersionedPrices {
+    V1(Vec<PriceV1>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset;
+
+    #[test]
+    fn v1_layout_is_stable() {
+        let prices = VersionedPrices::V1(vec![PriceV1 {
+            asset: asset::BTC,
+            price: FixedI64::from_inner(2),
+            timestamp: 3,
+            block_number: 4,
+        }]);
+
+        let mut expected = vec![0u8, 4];
+        expected.extend(asset::BTC.encode());
+        expected.extend(2i64.to_le_bytes());
+        expected.extend(3u64.to_le_bytes());
+        expected.extend(4u32.to_le_bytes());
+        assert_eq!(prices.encode(), expected);
+    }
+}
@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::RuntimeDebug;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -24,7 +24,18 @@ use sp_runtime::DispatchError;
 
 /// Types of subaccounts. Every master account can have only one subaccount of
 /// each type
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Hash, scale_info::TypeInfo)]
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    Hash,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[repr(u8)]
 pub enum SubAccType {
@@ -34,6 +45,9 @@ pub enum SubAccType {
     Trader,
     /// Same as trader
     Borrower,
+    /// Subaccount for positive balances only: can't have debt, isn't used as collateral
+    /// and in margin computation, supplies its balances to lending pools by default
+    Savings,
 }
 
 impl SubAccType {
@@ -43,6 +57,7 @@ impl SubAccType {
             SubAccType::Bailsman,
             SubAccType::Trader,
             SubAccType::Borrower,
+            SubAccType::Savings,
         ])
     }
 }
@@ -83,6 +98,12 @@ pub trait SubaccountsManager<AccountId> {
 
     /// Returns amount of subaccounts for `who` account
     fn get_subaccounts_amount(who: &AccountId) -> usize;
+
+    /// Returns master account that authorized `session_key` to place and cancel
+    /// orders of its `Trader` subaccount, if the session key is not expired
+    fn session_key_owner(_session_key: &AccountId) -> Option<AccountId> {
+        None
+    }
 }
 
 impl<AccountId> SubaccountsManager<AccountId> for () {
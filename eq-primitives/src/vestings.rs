@@ -17,6 +17,17 @@ pub trait EqVestingSchedule<Balance, AccountId> {
         starting_block: Self::Moment,
     ) -> DispatchResult;
 
+    /// Adds a vesting schedule to a given account keyed to unlock `epoch`, `starting_block`
+    /// is counted from the block registered for the epoch. Nothing is unlocked until
+    /// the epoch block is set.
+    fn add_vesting_schedule_at_epoch(
+        who: &AccountId,
+        locked: Balance,
+        per_block: Balance,
+        starting_block: Self::Moment,
+        epoch: u32,
+    ) -> DispatchResult;
+
     /// Updates an existings vesting schedule for a given account.
     fn update_vesting_schedule(
         who: &AccountId,
@@ -19,7 +19,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings)]
 
-use eq_primitives::DECIMALS;
 use sp_arithmetic::traits::{Saturating, Zero};
 use sp_arithmetic::*;
 pub mod converter;
@@ -53,55 +52,20 @@ pub fn multiply_by_rational(
     )
 }
 
+pub use eq_primitives::decimals::balance_swap_decimals;
+
 pub fn balance_from_xcm<Balance>(value: XcmBalance, decimals: u8) -> Option<Balance>
 where
     Balance: sp_std::convert::TryFrom<XcmBalance>,
 {
-    let value = balance_swap_decimals(value, decimals, DECIMALS)?;
-    Balance::try_from(value).ok()
+    eq_primitives::decimals::balance_from_decimals(value, decimals)
 }
 
 pub fn balance_into_xcm<Balance>(value: Balance, decimals: u8) -> Option<XcmBalance>
 where
     Balance: sp_std::convert::TryInto<XcmBalance>,
 {
-    let value = value.try_into().ok()?;
-    balance_swap_decimals(value, DECIMALS, decimals)
-}
-
-pub fn balance_swap_decimals(value: XcmBalance, from: u8, to: u8) -> Option<XcmBalance> {
-    const POW_TEN: [u128; 24] = [
-        1,
-        10,
-        100,
-        1_000,
-        10_000,
-        100_000,
-        1_000_000,
-        10_000_000,
-        100_000_000,
-        1_000_000_000,
-        10_000_000_000,
-        100_000_000_000,
-        1_000_000_000_000,
-        10_000_000_000_000,
-        100_000_000_000_000,
-        1_000_000_000_000_000,
-        10_000_000_000_000_000,
-        100_000_000_000_000_000,
-        1_000_000_000_000_000_000,
-        10_000_000_000_000_000_000,
-        100_000_000_000_000_000_000,
-        1_000_000_000_000_000_000_000,
-        10_000_000_000_000_000_000_000,
-        100_000_000_000_000_000_000_000,
-    ];
-    use sp_std::cmp::Ordering::*;
-    Some(match from.cmp(&to) {
-        Less => value.checked_mul(*POW_TEN.get((to - from) as usize)?)?,
-        Equal => value,
-        Greater => value.checked_div(*POW_TEN.get((from - to) as usize)?)?,
-    })
+    eq_primitives::decimals::balance_into_decimals(value, decimals)
 }
 
 use xcm::v3::{
@@ -21,7 +21,7 @@ use eq_primitives::{
     balance::{Balance as B, DepositReason, EqCurrency, WithdrawReason},
     chainbridge,
     price::PriceGetter,
-    XcmMode,
+    XcmDepositFreeze, XcmDustHandler, XcmMode,
 };
 use eq_utils::{multiply_by_rational, XcmBalance};
 use frame_support::{
@@ -57,6 +57,7 @@ pub struct EqCurrencyAdapter<
     EqBridge,
     AccountIdConverter,
     CheckedAccount,
+    XcmDust,
 >(
     PhantomData<(
         AccountId,
@@ -66,6 +67,7 @@ pub struct EqCurrencyAdapter<
         EqBridge,
         AccountIdConverter,
         CheckedAccount,
+        XcmDust,
     )>,
 );
 
@@ -79,12 +81,15 @@ impl<
             + MaybeSerializeDeserialize
             + sp_std::fmt::Debug
             + Default,
-        EqCurrency: eq_primitives::balance::EqCurrency<AccountId, Balance> + Get<Option<XcmMode>>,
+        EqCurrency: eq_primitives::balance::EqCurrency<AccountId, Balance>
+            + Get<Option<XcmMode>>
+            + XcmDepositFreeze,
         EqMatches: EqMatchesFungible<Asset, Balance>,
         EqBridge: chainbridge::Bridge<AccountId, Balance, chainbridge::ChainId, chainbridge::ResourceId>
             + chainbridge::ResourceGetter<chainbridge::ResourceId>,
         AccountIdConverter: xcm_executor::traits::Convert<MultiLocation, AccountId>,
         CheckedAccount: Get<Option<AccountId>>,
+        XcmDust: XcmDustHandler<AccountId, Balance>,
     > TransactAsset
     for EqCurrencyAdapter<
         AccountId,
@@ -94,6 +99,7 @@ impl<
         EqBridge,
         AccountIdConverter,
         CheckedAccount,
+        XcmDust,
     >
 {
     fn can_check_in(
@@ -124,11 +130,24 @@ impl<
     ) -> XcmResult<()> {
         log::trace!(target: "xcm::eq_currency_adapter", "deposit_asset {:?} to {:?}", what, who);
         let (asset, amount) = EqMatches::matches_fungible(&what).ok_or(XcmError::AssetNotFound)?;
+        // Failed deposit leaves assets in holding, so they are trapped instead of minted
+        if EqCurrency::is_asset_frozen(&asset) {
+            log::warn!(target: "xcm::eq_currency_adapter", "deposit_asset {:?} is frozen", what);
+            return Err(XcmError::FailedToTransactAsset("AssetIsFrozen"));
+        }
         let who = AccountIdConverter::convert_ref(who)
             .map_err(|()| XcmError::FailedToTransactAsset("AccountIdConversionFailed"))?;
 
         match <EqCurrency as Get<Option<XcmMode>>>::get() {
             None | Some(XcmMode::Xcm(_)) => {
+                // dust is converted or stored instead of killing the account
+                let is_dust = XcmDust::handle_dust(&who, asset, amount)
+                    .map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
+                if is_dust {
+                    log::trace!(target: "xcm::eq_currency_adapter", "deposit {:?} is dust", amount);
+                    return Ok(());
+                }
+
                 log::trace!(target: "xcm::eq_currency_adapter", "deposit_creating {:?}", amount);
                 EqCurrency::deposit_creating(
                     &who,
@@ -17,25 +17,55 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use core::marker::PhantomData;
-use eq_primitives::asset::AssetXcmGetter;
+use eq_primitives::{asset::AssetXcmGetter, XcmDepositFreeze};
 use frame_support::traits::{Contains, ProcessMessageError};
 use xcm::v3::{
-    Instruction, Instruction::*, Junction::*, Junctions::*, MultiLocation, Weight, WeightLimit::*,
+    Instruction, Instruction::*, Junction::*, Junctions::*, MultiLocation, OriginKind, Weight,
+    WeightLimit::*,
 };
 use xcm_executor::traits::ShouldExecute;
 
-pub struct AllowReserveAssetDepositedFrom<EqAssets, AllowedOrigins>(
-    PhantomData<(EqAssets, AllowedOrigins)>,
+/// Code of `Trap` instruction replacing deposit of assets received from frozen origin
+pub const FROZEN_ORIGIN_TRAP_CODE: u64 = 1;
+
+/// Allows reserve transfers of known assets from `AllowedOrigins`.
+/// Deposit of assets received from origin frozen in `Freeze` is replaced with `Trap`,
+/// so the assets are trapped instead of minted.
+pub struct AllowReserveAssetDepositedFrom<EqAssets, AllowedOrigins, Freeze>(
+    PhantomData<(EqAssets, AllowedOrigins, Freeze)>,
 );
 
-impl<EqAssets: AssetXcmGetter, AllowedOrigins: Contains<MultiLocation>>
-    AllowReserveAssetDepositedFrom<EqAssets, AllowedOrigins>
+impl<
+        EqAssets: AssetXcmGetter,
+        AllowedOrigins: Contains<MultiLocation>,
+        Freeze: XcmDepositFreeze,
+    > AllowReserveAssetDepositedFrom<EqAssets, AllowedOrigins, Freeze>
 {
     fn check<RuntimeCall>(
         origin: &MultiLocation,
         instructions: &mut [Instruction<RuntimeCall>],
         max_weight: Weight,
         _weight_credit: &mut Weight,
+    ) -> Result<(), ProcessMessageError> {
+        Self::check_instructions(origin, instructions, max_weight, _weight_credit)?;
+
+        if Freeze::is_origin_frozen(origin) {
+            log::warn!(target: "xcm::barriers", "Deposit from frozen origin {:?} is trapped", origin);
+            if let Some(last) = instructions.last_mut() {
+                if matches!(last, DepositAsset { .. }) {
+                    *last = Trap(FROZEN_ORIGIN_TRAP_CODE);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_instructions<RuntimeCall>(
+        origin: &MultiLocation,
+        instructions: &mut [Instruction<RuntimeCall>],
+        max_weight: Weight,
+        _weight_credit: &mut Weight,
     ) -> Result<(), ProcessMessageError> {
         if AllowedOrigins::contains(origin) {
             match instructions {
@@ -89,8 +119,11 @@ impl<EqAssets: AssetXcmGetter, AllowedOrigins: Contains<MultiLocation>>
     }
 }
 
-impl<EqAssets: AssetXcmGetter, AllowedOrigins: Contains<MultiLocation>> ShouldExecute
-    for AllowReserveAssetDepositedFrom<EqAssets, AllowedOrigins>
+impl<
+        EqAssets: AssetXcmGetter,
+        AllowedOrigins: Contains<MultiLocation>,
+        Freeze: XcmDepositFreeze,
+    > ShouldExecute for AllowReserveAssetDepositedFrom<EqAssets, AllowedOrigins, Freeze>
 {
     fn should_execute<RuntimeCall>(
         origin: &MultiLocation,
@@ -127,3 +160,58 @@ impl ShouldExecute for AllowReserveTransferAssetsFromAccountId {
         }
     }
 }
+
+/// Allows paid `Transact` of `AccountId32` users of sibling parachains trusted in `IsTrusted`.
+/// Fees are paid from the sibling sovereign account or with assets reserved by the sibling,
+/// then origin descends to the user, so the call is dispatched by the derived account and
+/// assets are never withdrawn from it.
+pub struct AllowRemoteTraderTransact<IsTrusted>(PhantomData<IsTrusted>);
+
+impl<IsTrusted: Contains<u32>> AllowRemoteTraderTransact<IsTrusted> {
+    fn check<RuntimeCall>(
+        origin: &MultiLocation,
+        instructions: &mut [Instruction<RuntimeCall>],
+        max_weight: Weight,
+    ) -> Result<(), ProcessMessageError> {
+        match origin {
+            MultiLocation {
+                parents: 1,
+                interior: X1(Parachain(para_id)),
+            } if IsTrusted::contains(para_id) => {}
+            _ => return Err(ProcessMessageError::Unsupported),
+        }
+
+        match instructions {
+            [WithdrawAsset(_) | ReserveAssetDeposited(_), BuyExecution {
+                ref mut weight_limit,
+                ..
+            }, DescendOrigin(X1(AccountId32 { .. })), Transact {
+                origin_kind: OriginKind::SovereignAccount,
+                ..
+            }, rest @ ..]
+                if rest
+                    .iter()
+                    .all(|i| matches!(i, RefundSurplus | DepositAsset { .. })) =>
+            {
+                *weight_limit = Limited(max_weight);
+                Ok(())
+            }
+            _ => Err(ProcessMessageError::Unsupported),
+        }
+    }
+}
+
+impl<IsTrusted: Contains<u32>> ShouldExecute for AllowRemoteTraderTransact<IsTrusted> {
+    fn should_execute<RuntimeCall>(
+        origin: &MultiLocation,
+        instructions: &mut [Instruction<RuntimeCall>],
+        max_weight: Weight,
+        _weight_credit: &mut Weight,
+    ) -> Result<(), ProcessMessageError> {
+        if let Some((SetTopic(_), rest)) = instructions.split_last_mut() {
+            Self::check(origin, rest, max_weight)
+        } else {
+            Self::check(origin, instructions, max_weight)
+        }
+    }
+}
@@ -16,7 +16,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use codec::Encode;
 use eq_primitives::AccountType;
+use frame_support::traits::Contains;
 use sp_std::marker::PhantomData;
 use xcm::v3::{Junction::*, Junctions::*, MultiLocation, NetworkId};
 
@@ -63,6 +65,36 @@ impl<AccountId: From<[u8; 32]> + Into<[u8; 32]> + Clone>
     }
 }
 
+/// Derives a local account for the `AccountId32` user of sibling parachain `para_id`.
+/// Derived accounts never collide with local keys, so remote users can't act as local ones.
+pub fn sibling_account_id<AccountId: From<[u8; 32]>>(para_id: u32, id: [u8; 32]) -> AccountId {
+    sp_io::hashing::blake2_256(&(b"eq/xcm-remote", para_id, id).encode()).into()
+}
+
+/// Converts `AccountId32` user of a trusted sibling parachain into derived local account.
+/// Used to dispatch `Transact` calls on behalf of remote users.
+pub struct SiblingAccountConversion<AccountId, IsTrusted>(PhantomData<(AccountId, IsTrusted)>);
+
+impl<AccountId: From<[u8; 32]> + Clone, IsTrusted: Contains<u32>>
+    xcm_executor::traits::Convert<MultiLocation, AccountId>
+    for SiblingAccountConversion<AccountId, IsTrusted>
+{
+    fn convert(location: MultiLocation) -> Result<AccountId, MultiLocation> {
+        match location {
+            MultiLocation {
+                parents: 1,
+                interior: X2(Parachain(para_id), AccountId32 { id, .. }),
+            } if IsTrusted::contains(&para_id) => Ok(sibling_account_id(para_id, id)),
+            _ => Err(location),
+        }
+    }
+
+    fn reverse(who: AccountId) -> Result<MultiLocation, AccountId> {
+        // derivation is one-way
+        Err(who)
+    }
+}
+
 frame_support::parameter_types! {
     pub const AnyNetwork: Option<NetworkId> = None;
 }
@@ -206,11 +206,15 @@ where
         call: Self::RelayChainCall,
         transact_weight: Weight,
     ) -> Xcm<()> {
-        let xcm_weight = crate::fees::polkadot::BaseXcmWeight::get()
+        #[cfg(feature = "kusama")]
+        use crate::fees::kusama as relay_fees;
+        #[cfg(not(feature = "kusama"))]
+        use crate::fees::polkadot as relay_fees;
+
+        let xcm_weight = relay_fees::BaseXcmWeight::get()
             .saturating_mul(4)
             .saturating_add(transact_weight);
-        let xcm_fee: XcmBalance =
-            crate::fees::polkadot::WeightToFee::weight_to_fee(&xcm_weight).into();
+        let xcm_fee: XcmBalance = relay_fees::WeightToFee::weight_to_fee(&xcm_weight).into();
 
         let asset = MultiAsset {
             id: Concrete(MultiLocation::here()),
@@ -31,7 +31,8 @@ use eq_node_runtime::{
     AuraConfig, BailsmanConfig, ClaimsConfig, CouncilConfig, CouncilMembershipConfig,
     DemocracyConfig, EqAssetsConfig, EqBalancesConfig, EqDexConfig, EqInvestorsConfig,
     EqLiquidityFarmingConfig, EqMultisigSudoConfig, EqTreasuryConfig, FinancialConfig, FixedI64,
-    GenesisConfig, OracleConfig, ParachainInfoConfig, PolkadotXcmConfig, RepublicConfig,
+    GenesisConfig, OracleConfig, ParachainInfoConfig, ParameterTrackConfig,
+    ParameterTrackMembershipConfig, PolkadotXcmConfig, RepublicConfig,
     SessionConfig, SubaccountsConfig, SystemConfig, TechnicalCommitteeConfig,
     TechnicalCommitteeMembershipConfig, Vesting2Config, Vesting3Config, Vesting4Config,
     VestingConfig, WhitelistsConfig, WASM_BINARY,
@@ -627,6 +628,15 @@ fn testnet_genesis(
             members: BoundedVec::checked_from(vec![root_key.clone()]).unwrap(),
         },
 
+        parameter_track: ParameterTrackConfig {
+            phantom: PhantomData,
+            members: vec![],
+        },
+        parameter_track_membership: ParameterTrackMembershipConfig {
+            phantom: PhantomData,
+            members: BoundedVec::checked_from(vec![root_key.clone()]).unwrap(),
+        },
+
         democracy: DemocracyConfig::default(),
 
         crowdloan_distribution: Default::default(),
@@ -709,6 +709,9 @@ fn testnet_genesis(
         eq_lending: Default::default(),
 
         gens_binary: Default::default(),
+
+        #[cfg(not(feature = "production"))]
+        eq_wrapped_ksm: Default::default(),
     }
 }
 
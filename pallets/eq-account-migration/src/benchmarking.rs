@@ -0,0 +1,74 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium Account Migration Pallet Benchmarking
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_core::{crypto::KeyTypeId, sr25519};
+
+const SEED: u32 = 0;
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"eqam");
+
+benchmarks! {
+    where_clause {
+        where
+            T::Signature: From<sr25519::Signature>,
+            T::Signer: From<sr25519::Public>,
+    }
+
+    migrate_account {
+        let who: T::AccountId = account("who", 0, SEED);
+        let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+        let new_account = T::Signer::from(public).into_account();
+        let proof = sp_io::crypto::sr25519_sign(KEY_TYPE, &public, &Pallet::<T>::proof_message(&who))
+            .expect("key is generated");
+    }: _(RawOrigin::Signed(who.clone()), new_account.clone(), proof.into())
+    verify {
+        assert_eq!(PendingMigrations::<T>::get(&who).map(|m| m.to), Some(new_account));
+    }
+
+    cancel_migration {
+        let who: T::AccountId = account("who", 0, SEED);
+        let to: T::AccountId = account("to", 0, SEED);
+        PendingMigrations::<T>::insert(&who, PendingMigration {
+            to,
+            execute_at: T::MigrationDelay::get(),
+        });
+    }: _(RawOrigin::Signed(who.clone()))
+    verify {
+        assert!(PendingMigrations::<T>::get(&who).is_none());
+    }
+
+    execute_migration {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let who: T::AccountId = account("who", 0, SEED);
+        let to: T::AccountId = account("to", 0, SEED);
+        PendingMigrations::<T>::insert(&who, PendingMigration {
+            to,
+            execute_at: T::MigrationDelay::get(),
+        });
+        frame_system::Pallet::<T>::set_block_number(T::MigrationDelay::get());
+    }: _(RawOrigin::Signed(caller), who.clone())
+    verify {
+        assert!(PendingMigrations::<T>::get(&who).is_none());
+    }
+}
@@ -0,0 +1,233 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium Account Migration Pallet
+//!
+//! Moves state of an account to another account of the same owner, e.g. after key rotation.
+//!
+//! The owner schedules migration with `migrate_account` signed by the old account. The call
+//! carries a signature of the new account over `proof_message` of the old one, so the state
+//! can't be pushed to an account without its consent. Anyone may execute the migration with
+//! `execute_migration` after `MigrationDelay` blocks, until then the old account may cancel it
+//! with `cancel_migration`, e.g. if its key is stolen.
+//!
+//! Balances, locks, vesting schedules, stakes and subaccounts are moved by pallets in
+//! `Migrations`. Migration is atomic: nothing is moved if any part of the state can't be moved.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(warnings)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode};
+use eq_primitives::AccountMigration;
+use frame_support::pallet_prelude::*;
+use sp_runtime::traits::{IdentifyAccount, Saturating, Verify};
+use sp_std::prelude::*;
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+/// Prefix of the message signed by the new account to prove its consent to migration
+pub const PROOF_PREFIX: &[u8] = b"eq/account-migration:";
+
+/// Migration of an account waiting for the end of the delay
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct PendingMigration<AccountId, BlockNumber> {
+    /// Account receiving the state
+    pub to: AccountId,
+    /// First block in which the migration may be executed
+    pub execute_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Signature of the new account over `proof_message`
+        type Signature: Verify<Signer = Self::Signer> + Parameter;
+        /// Public key of the new account
+        type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+        /// Pallets moving their parts of the account state
+        type Migrations: AccountMigration<Self::AccountId>;
+        /// Number of blocks between scheduling and execution of migration
+        #[pallet::constant]
+        type MigrationDelay: Get<Self::BlockNumber>;
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Scheduled migrations by old account
+    #[pallet::storage]
+    #[pallet::getter(fn pending_migration)]
+    pub type PendingMigrations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        PendingMigration<T::AccountId, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Migration of `from` to `to` may be executed from `execute_at` block
+        MigrationScheduled {
+            from: T::AccountId,
+            to: T::AccountId,
+            execute_at: T::BlockNumber,
+        },
+        /// Scheduled migration is cancelled by the old account
+        MigrationCancelled {
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// State of `from` is moved to `to`
+        AccountMigrated {
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Account can't be migrated to itself
+        SameAccount,
+        /// Account already has scheduled migration
+        MigrationAlreadyScheduled,
+        /// Signature of the new account is not valid
+        InvalidProof,
+        /// Account has no scheduled migration
+        NoScheduledMigration,
+        /// Migration delay is not passed yet
+        MigrationDelayNotPassed,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Schedules migration of the caller state to `new_account`.
+        /// `proof` is a signature of `new_account` over `proof_message` of the caller.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::migrate_account())]
+        pub fn migrate_account(
+            origin: OriginFor<T>,
+            new_account: T::AccountId,
+            proof: T::Signature,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(who != new_account, Error::<T>::SameAccount);
+            ensure!(
+                !PendingMigrations::<T>::contains_key(&who),
+                Error::<T>::MigrationAlreadyScheduled
+            );
+            ensure!(
+                proof.verify(&Self::proof_message(&who)[..], &new_account),
+                Error::<T>::InvalidProof
+            );
+            // fail early, state is checked again on execution
+            T::Migrations::can_migrate(&who, &new_account)?;
+
+            let execute_at =
+                frame_system::Pallet::<T>::block_number().saturating_add(T::MigrationDelay::get());
+            PendingMigrations::<T>::insert(
+                &who,
+                PendingMigration {
+                    to: new_account.clone(),
+                    execute_at,
+                },
+            );
+
+            Self::deposit_event(Event::MigrationScheduled {
+                from: who,
+                to: new_account,
+                execute_at,
+            });
+            Ok(().into())
+        }
+
+        /// Cancels scheduled migration of the caller
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::cancel_migration())]
+        pub fn cancel_migration(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let migration =
+                PendingMigrations::<T>::take(&who).ok_or(Error::<T>::NoScheduledMigration)?;
+
+            Self::deposit_event(Event::MigrationCancelled {
+                from: who,
+                to: migration.to,
+            });
+            Ok(().into())
+        }
+
+        /// Moves state of `who` to the account of its scheduled migration.
+        /// Any signed account may execute the migration after the delay.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::execute_migration())]
+        pub fn execute_migration(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let migration =
+                PendingMigrations::<T>::get(&who).ok_or(Error::<T>::NoScheduledMigration)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= migration.execute_at,
+                Error::<T>::MigrationDelayNotPassed
+            );
+
+            // call is transactional, any failure reverts already moved parts
+            T::Migrations::can_migrate(&who, &migration.to)?;
+            T::Migrations::migrate(&who, &migration.to)?;
+            PendingMigrations::<T>::remove(&who);
+
+            Self::deposit_event(Event::AccountMigrated {
+                from: who,
+                to: migration.to,
+            });
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Message signed by the new account to prove its consent to migration of `who`:
+    /// `PROOF_PREFIX` followed by SCALE encoded `who`
+    pub fn proof_message(who: &T::AccountId) -> Vec<u8> {
+        let mut message = PROOF_PREFIX.to_vec();
+        who.using_encoded(|encoded| message.extend_from_slice(encoded));
+        message
+    }
+}
@@ -0,0 +1,132 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as eq_account_migration;
+use frame_support::{parameter_types, traits::Everything};
+use sp_core::H256;
+use sp_runtime::{
+    testing::{Header, TestSignature, UintAuthorityId},
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError, DispatchResult,
+};
+use std::cell::RefCell;
+
+pub type AccountId = u64;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        EqAccountMigration: eq_account_migration::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MigrationDelay: u64 = 10;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Signature = TestSignature;
+    type Signer = UintAuthorityId;
+    type Migrations = AccountMigrationMock;
+    type MigrationDelay = MigrationDelay;
+    type WeightInfo = ();
+}
+
+thread_local! {
+    static MIGRATED: RefCell<Vec<(AccountId, AccountId)>> = RefCell::new(Vec::new());
+    static BLOCKED: RefCell<Vec<AccountId>> = RefCell::new(Vec::new());
+}
+
+/// Migrations made by `AccountMigrationMock`
+pub fn migrated() -> Vec<(AccountId, AccountId)> {
+    MIGRATED.with(|v| v.borrow().clone())
+}
+
+/// State of `who` can't be migrated until unblocked
+pub fn set_blocked(who: AccountId, blocked: bool) {
+    BLOCKED.with(|v| {
+        let mut accounts = v.borrow_mut();
+        accounts.retain(|acc| *acc != who);
+        if blocked {
+            accounts.push(who);
+        }
+    });
+}
+
+pub struct AccountMigrationMock;
+impl AccountMigration<AccountId> for AccountMigrationMock {
+    fn can_migrate(from: &AccountId, _to: &AccountId) -> DispatchResult {
+        if BLOCKED.with(|v| v.borrow().contains(from)) {
+            Err(DispatchError::Other("Blocked"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn migrate(from: &AccountId, to: &AccountId) -> DispatchResult {
+        MIGRATED.with(|v| v.borrow_mut().push((*from, *to)));
+        Ok(())
+    }
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let r = frame_system::GenesisConfig::default().build_storage::<Test>();
+
+    let mut ext: sp_io::TestExternalities = r.unwrap().into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
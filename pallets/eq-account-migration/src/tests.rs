@@ -0,0 +1,192 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{testing::TestSignature, DispatchError};
+
+const ALICE: AccountId = 1;
+const BOB: AccountId = 2;
+const CAROL: AccountId = 3;
+
+/// Signature of `new_account` allowing migration of `who` to it
+fn proof(new_account: AccountId, who: AccountId) -> TestSignature {
+    TestSignature(new_account, EqAccountMigration::proof_message(&who))
+}
+
+#[test]
+fn migrate_account_requires_proof_of_new_account() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EqAccountMigration::migrate_account(
+                RuntimeOrigin::signed(ALICE),
+                ALICE,
+                proof(ALICE, ALICE)
+            ),
+            Error::<Test>::SameAccount
+        );
+        // signed by another account
+        assert_noop!(
+            EqAccountMigration::migrate_account(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                proof(CAROL, ALICE)
+            ),
+            Error::<Test>::InvalidProof
+        );
+        // signed for migration of another account
+        assert_noop!(
+            EqAccountMigration::migrate_account(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                proof(BOB, CAROL)
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        set_blocked(ALICE, true);
+        assert_noop!(
+            EqAccountMigration::migrate_account(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                proof(BOB, ALICE)
+            ),
+            DispatchError::Other("Blocked")
+        );
+        set_blocked(ALICE, false);
+
+        assert_ok!(EqAccountMigration::migrate_account(
+            RuntimeOrigin::signed(ALICE),
+            BOB,
+            proof(BOB, ALICE)
+        ));
+        assert_eq!(
+            EqAccountMigration::pending_migration(ALICE),
+            Some(PendingMigration {
+                to: BOB,
+                execute_at: 1 + MigrationDelay::get(),
+            })
+        );
+        System::assert_last_event(
+            Event::MigrationScheduled {
+                from: ALICE,
+                to: BOB,
+                execute_at: 1 + MigrationDelay::get(),
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            EqAccountMigration::migrate_account(
+                RuntimeOrigin::signed(ALICE),
+                CAROL,
+                proof(CAROL, ALICE)
+            ),
+            Error::<Test>::MigrationAlreadyScheduled
+        );
+        assert!(migrated().is_empty());
+    });
+}
+
+#[test]
+fn migration_is_executed_after_delay() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EqAccountMigration::execute_migration(RuntimeOrigin::signed(CAROL), ALICE),
+            Error::<Test>::NoScheduledMigration
+        );
+
+        assert_ok!(EqAccountMigration::migrate_account(
+            RuntimeOrigin::signed(ALICE),
+            BOB,
+            proof(BOB, ALICE)
+        ));
+
+        System::set_block_number(MigrationDelay::get());
+        assert_noop!(
+            EqAccountMigration::execute_migration(RuntimeOrigin::signed(CAROL), ALICE),
+            Error::<Test>::MigrationDelayNotPassed
+        );
+
+        // state is checked again on execution
+        System::set_block_number(1 + MigrationDelay::get());
+        set_blocked(ALICE, true);
+        assert_noop!(
+            EqAccountMigration::execute_migration(RuntimeOrigin::signed(CAROL), ALICE),
+            DispatchError::Other("Blocked")
+        );
+        set_blocked(ALICE, false);
+
+        assert_ok!(EqAccountMigration::execute_migration(
+            RuntimeOrigin::signed(CAROL),
+            ALICE
+        ));
+        assert_eq!(migrated(), vec![(ALICE, BOB)]);
+        assert_eq!(EqAccountMigration::pending_migration(ALICE), None);
+        System::assert_last_event(
+            Event::AccountMigrated {
+                from: ALICE,
+                to: BOB,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn migration_is_cancelled_within_delay() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EqAccountMigration::cancel_migration(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::NoScheduledMigration
+        );
+
+        assert_ok!(EqAccountMigration::migrate_account(
+            RuntimeOrigin::signed(ALICE),
+            BOB,
+            proof(BOB, ALICE)
+        ));
+        // only the old account may cancel
+        assert_noop!(
+            EqAccountMigration::cancel_migration(RuntimeOrigin::signed(BOB)),
+            Error::<Test>::NoScheduledMigration
+        );
+
+        assert_ok!(EqAccountMigration::cancel_migration(RuntimeOrigin::signed(
+            ALICE
+        )));
+        assert_eq!(EqAccountMigration::pending_migration(ALICE), None);
+        System::assert_last_event(
+            Event::MigrationCancelled {
+                from: ALICE,
+                to: BOB,
+            }
+            .into(),
+        );
+
+        System::set_block_number(1 + MigrationDelay::get());
+        assert_noop!(
+            EqAccountMigration::execute_migration(RuntimeOrigin::signed(BOB), ALICE),
+            Error::<Test>::NoScheduledMigration
+        );
+        assert!(migrated().is_empty());
+    });
+}
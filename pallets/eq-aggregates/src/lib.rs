@@ -206,6 +206,14 @@ impl<T: Config> Pallet<T> {
         Box::new(<AccountUserGroups<T>>::iter_prefix(user_group).map(|(k, _v)| k))
     }
 
+    fn iter_account_from(
+        user_group: UserGroup,
+        start_after: &T::AccountId,
+    ) -> Box<dyn Iterator<Item = T::AccountId>> {
+        let start_key = <AccountUserGroups<T>>::hashed_key_for(&user_group, start_after);
+        Box::new(<AccountUserGroups<T>>::iter_prefix_from(user_group, start_key).map(|(k, _v)| k))
+    }
+
     fn iter_total(
         user_group: UserGroup,
     ) -> Box<dyn Iterator<Item = (Asset, TotalAggregates<T::Balance>)>> {
@@ -239,6 +247,12 @@ impl<T: Config> Aggregates<T::AccountId, T::Balance> for Pallet<T> {
     fn iter_account(user_group: UserGroup) -> Box<dyn Iterator<Item = T::AccountId>> {
         Self::iter_account(user_group)
     }
+    fn iter_account_from(
+        user_group: UserGroup,
+        start_after: &T::AccountId,
+    ) -> Box<dyn Iterator<Item = T::AccountId>> {
+        Self::iter_account_from(user_group, start_after)
+    }
     fn iter_total(
         user_group: UserGroup,
     ) -> Box<dyn Iterator<Item = (Asset, TotalAggregates<T::Balance>)>> {
@@ -254,6 +268,7 @@ impl<T: Config> AggregatesAssetRemover for Pallet<T> {
         <TotalUserGroups<T>>::remove(UserGroup::Bailsmen, asset);
         <TotalUserGroups<T>>::remove(UserGroup::Borrowers, asset);
         <TotalUserGroups<T>>::remove(UserGroup::Balances, asset);
+        <TotalUserGroups<T>>::remove(UserGroup::Savers, asset);
     }
 }
 
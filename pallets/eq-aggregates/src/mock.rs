@@ -236,6 +236,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -133,4 +133,67 @@ benchmarks! {
         assert!(stored_asset.is_ok());
         assert_eq!(stored_asset.unwrap(), updated_asset);
     }
+
+    set_existential_deposit {
+        let new_asset = AssetData {
+            id: NEW_ASSET,
+            lot: EqFixedU128::from_inner(0),
+            price_step: FixedI64::from_inner(0),
+            maker_fee: Permill::zero(),
+            taker_fee: Permill::zero(),
+            asset_xcm_data: AssetXcmData::None,
+            debt_weight: Permill::zero(),
+            lending_debt_weight: Permill::one(),
+            buyout_priority: 100_u64,
+            asset_type: AssetType::Physical,
+            is_dex_enabled: false,
+            collateral_discount: Percent::one()
+        };
+        let _ = Assets::<T>::mutate(|value| *value = Some(vec![new_asset]));
+    }: _(RawOrigin::Root, NEW_ASSET, Some(1_000_000_000))
+    verify {
+        assert_eq!(Pallet::<T>::existential_deposit_override(NEW_ASSET), Some(1_000_000_000));
+    }
+
+    set_risk_tier {
+        let new_asset = AssetData {
+            id: NEW_ASSET,
+            lot: EqFixedU128::from_inner(0),
+            price_step: FixedI64::from_inner(0),
+            maker_fee: Permill::zero(),
+            taker_fee: Permill::zero(),
+            asset_xcm_data: AssetXcmData::None,
+            debt_weight: Permill::zero(),
+            lending_debt_weight: Permill::one(),
+            buyout_priority: 100_u64,
+            asset_type: AssetType::Physical,
+            is_dex_enabled: false,
+            collateral_discount: Percent::one()
+        };
+        let _ = Assets::<T>::mutate(|value| *value = Some(vec![new_asset]));
+    }: _(RawOrigin::Root, NEW_ASSET, RiskTier::Tier2)
+    verify {
+        assert_eq!(Pallet::<T>::risk_tier_of(NEW_ASSET), Some(RiskTier::Tier2));
+    }
+
+    set_decimals {
+        let new_asset = AssetData {
+            id: NEW_ASSET,
+            lot: EqFixedU128::from_inner(0),
+            price_step: FixedI64::from_inner(0),
+            maker_fee: Permill::zero(),
+            taker_fee: Permill::zero(),
+            asset_xcm_data: AssetXcmData::None,
+            debt_weight: Permill::zero(),
+            lending_debt_weight: Permill::one(),
+            buyout_priority: 100_u64,
+            asset_type: AssetType::Physical,
+            is_dex_enabled: false,
+            collateral_discount: Percent::one()
+        };
+        let _ = Assets::<T>::mutate(|value| *value = Some(vec![new_asset]));
+    }: _(RawOrigin::Root, NEW_ASSET, Some(18))
+    verify {
+        assert_eq!(Pallet::<T>::decimals_of(NEW_ASSET), Some(18));
+    }
 }
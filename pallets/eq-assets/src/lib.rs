@@ -33,9 +33,12 @@ use core::{convert::TryInto, marker::PhantomData};
 use eq_primitives::{
     asset::{
         Asset, AssetData, AssetError, AssetGetter, AssetType, AssetXcmData, AssetXcmGetter,
-        OnNewAsset,
+        OnNewAsset, RiskTier,
     },
+    balance::Balance,
     balance_number::EqFixedU128,
+    decimals::MAX_DECIMALS,
+    DECIMALS,
 };
 use eq_utils::eq_ensure;
 #[allow(unused_imports)]
@@ -43,7 +46,9 @@ use frame_support::debug;
 use frame_support::dispatch::DispatchResultWithPostInfo;
 use frame_support::traits::Get;
 pub use pallet::*;
-use sp_runtime::{traits::Zero, DispatchError, FixedI64, FixedPointNumber, Percent, Permill};
+use sp_runtime::{
+    traits::Zero, DispatchError, FixedI64, FixedPointNumber, Percent, Permill, RuntimeDebug,
+};
 use sp_std::vec::Vec;
 use xcm::latest::AssetId;
 
@@ -82,8 +87,12 @@ pub mod pallet {
         type WeightInfo: WeightInfo;
     }
 
+    /// Storage version: 1 - per-asset existential deposits, 2 - asset risk tiers
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
     #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::storage]
@@ -94,6 +103,23 @@ pub mod pallet {
     #[pallet::getter(fn assets_to_remove)]
     pub type AssetsToRemove<T: Config> = StorageValue<_, Vec<Asset>>;
 
+    /// Existential deposits in asset units overriding the default one for dust-prone assets
+    #[pallet::storage]
+    #[pallet::getter(fn existential_deposit_override)]
+    pub type ExistentialDeposits<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, Balance, OptionQuery>;
+
+    /// Risk tiers of assets, limiting leverage, lending and bailsman collateral
+    #[pallet::storage]
+    #[pallet::getter(fn risk_tier_of)]
+    pub type RiskTiers<T: Config> = StorageMap<_, Blake2_128Concat, Asset, RiskTier, OptionQuery>;
+
+    /// Decimals of assets on bridged networks, when they differ from the native ones.
+    /// Assets transferable by XCM take decimals from their XCM data.
+    #[pallet::storage]
+    #[pallet::getter(fn decimals_of)]
+    pub type AssetDecimals<T: Config> = StorageMap<_, Blake2_128Concat, Asset, u8, OptionQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub assets: Vec<(
@@ -192,6 +218,12 @@ pub mod pallet {
         DeleteAsset(eq_primitives::asset::AssetIdInnerType, Vec<u8>),
         /// Asset updated in the store \[asset, asset_name\]
         UpdateAsset(eq_primitives::asset::AssetIdInnerType, Vec<u8>),
+        /// Existential deposit override set or removed \[asset, existential_deposit\]
+        ExistentialDepositSet(eq_primitives::asset::AssetIdInnerType, Option<Balance>),
+        /// Risk tier of an asset changed \[asset, tier\]
+        RiskTierSet(eq_primitives::asset::AssetIdInnerType, RiskTier),
+        /// Decimals of an asset set or removed \[asset, decimals\]
+        DecimalsSet(eq_primitives::asset::AssetIdInnerType, Option<u8>),
     }
 
     #[pallet::error]
@@ -219,6 +251,10 @@ pub mod pallet {
         Native,
         /// Collateral discount is negative
         CollateralDiscountNegative,
+        /// Decimals of the asset are defined by its XCM data
+        DecimalsDefinedByXcm,
+        /// Amounts in these decimals can't be scaled to native decimals
+        WrongDecimals,
     }
 
     #[pallet::hooks]
@@ -356,10 +392,187 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Sets existential deposit of an asset in asset units.
+        /// `None` removes the override, so the default existential deposit is used.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_existential_deposit())]
+        pub fn set_existential_deposit(
+            origin: OriginFor<T>,
+            asset: Asset,
+            existential_deposit: Option<Balance>,
+        ) -> DispatchResultWithPostInfo {
+            T::AssetManagementOrigin::ensure_origin(origin)?;
+
+            eq_ensure!(
+                <Self as AssetGetter>::exists(asset),
+                Error::<T>::AssetNotExists,
+                target: "eq_assets",
+                "Asset {:?} does not exist.",
+                asset
+            );
+
+            ExistentialDeposits::<T>::set(asset, existential_deposit);
+
+            Self::deposit_event(Event::ExistentialDepositSet(
+                asset.get_id(),
+                existential_deposit,
+            ));
+            Ok(().into())
+        }
+
+        /// Sets risk tier of an asset
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_risk_tier())]
+        pub fn set_risk_tier(
+            origin: OriginFor<T>,
+            asset: Asset,
+            tier: RiskTier,
+        ) -> DispatchResultWithPostInfo {
+            T::AssetManagementOrigin::ensure_origin(origin)?;
+
+            eq_ensure!(
+                <Self as AssetGetter>::exists(asset),
+                Error::<T>::AssetNotExists,
+                target: "eq_assets",
+                "Asset {:?} does not exist.",
+                asset
+            );
+
+            RiskTiers::<T>::insert(asset, tier);
+
+            Self::deposit_event(Event::RiskTierSet(asset.get_id(), tier));
+            Ok(().into())
+        }
+
+        /// Sets decimals of an asset on bridged networks.
+        /// `None` removes them, so native decimals are used.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::set_decimals())]
+        pub fn set_decimals(
+            origin: OriginFor<T>,
+            asset: Asset,
+            decimals: Option<u8>,
+        ) -> DispatchResultWithPostInfo {
+            T::AssetManagementOrigin::ensure_origin(origin)?;
+
+            let asset_data = <Self as AssetGetter>::get_asset_data(&asset)?;
+            eq_ensure!(
+                asset_data.get_xcm_data().is_none(),
+                Error::<T>::DecimalsDefinedByXcm,
+                target: "eq_assets",
+                "Decimals of asset {:?} are defined by XCM data.",
+                asset
+            );
+            eq_ensure!(
+                decimals.map_or(true, |d| d <= MAX_DECIMALS),
+                Error::<T>::WrongDecimals,
+                target: "eq_assets",
+                "Decimals {:?} of asset {:?} exceed {}.",
+                decimals,
+                asset,
+                MAX_DECIMALS
+            );
+
+            AssetDecimals::<T>::set(asset, decimals);
+
+            Self::deposit_event(Event::DecimalsSet(asset.get_id(), decimals));
+            Ok(().into())
+        }
+    }
+}
+
+/// Parameters of an asset created with `create_derivative_asset`
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DerivativeAssetParams {
+    pub lot: EqFixedU128,
+    pub price_step: FixedI64,
+    pub maker_fee: Permill,
+    pub taker_fee: Permill,
+    pub debt_weight: Permill,
+    pub buyout_priority: u64,
+    pub asset_type: AssetType,
+    pub is_dex_enabled: bool,
+    pub collateral_discount: Percent,
+    pub lending_debt_weight: Permill,
+    pub prices: Vec<FixedI64>,
+}
+
+impl DerivativeAssetParams {
+    /// Parameters of an LP token: not traded on DEX and not used as collateral,
+    /// prices are set by the pool
+    pub fn lp_token(asset_type: AssetType, debt_weight: Permill, buyout_priority: u64) -> Self {
+        Self {
+            lot: EqFixedU128::zero(),
+            price_step: FixedI64::zero(),
+            maker_fee: Permill::zero(),
+            taker_fee: Permill::zero(),
+            debt_weight,
+            buyout_priority,
+            asset_type,
+            is_dex_enabled: false,
+            collateral_discount: Percent::zero(),
+            lending_debt_weight: Permill::one(),
+            prices: Vec::new(),
+        }
     }
 }
 
 impl<T: Config> Pallet<T> {
+    /// Asset named `prefix` followed by decimal `discriminator`, e.g. `lpt0` or `lpt12`
+    pub fn derivative_asset(prefix: &[u8], discriminator: u32) -> Result<Asset, Error<T>> {
+        let mut digits = Vec::new();
+        let mut rest = discriminator;
+        loop {
+            digits.push(b'0' + (rest % 10) as u8);
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+        digits.reverse();
+
+        let name: Vec<u8> = prefix.iter().chain(digits.iter()).copied().collect();
+        Asset::from_bytes(&name).map_err(Self::map_asset_error)
+    }
+
+    /// Creates an asset derived from another entity (e.g. LP token of a pool) with a name
+    /// generated by `derivative_asset`. Fails if the name is invalid or collides
+    /// with an existing asset.
+    pub fn create_derivative_asset(
+        prefix: &[u8],
+        discriminator: u32,
+        params: DerivativeAssetParams,
+    ) -> Result<Asset, DispatchError> {
+        let asset = Self::derivative_asset(prefix, discriminator)?;
+        eq_ensure!(
+            !<Self as AssetGetter>::exists(asset),
+            Error::<T>::AssetAlreadyExists,
+            target: "eq_assets",
+            "Derivative asset {:?} already exists.",
+            asset
+        );
+
+        Self::do_add_asset(
+            asset,
+            params.lot,
+            params.price_step,
+            params.maker_fee,
+            params.taker_fee,
+            AssetXcmData::None,
+            params.debt_weight,
+            params.buyout_priority,
+            params.asset_type,
+            params.is_dex_enabled,
+            params.collateral_discount,
+            params.lending_debt_weight,
+            params.prices,
+        )
+        .map_err(|e| e.error)?;
+
+        Ok(asset)
+    }
+
     /// Adds an asset
     pub fn do_add_asset(
         asset: Asset,
@@ -400,6 +613,7 @@ impl<T: Config> Pallet<T> {
         };
 
         <Assets<T>>::put(assets);
+        RiskTiers::<T>::insert(asset, RiskTier::default_for(&asset_type));
 
         T::OnNewAsset::on_new_asset(asset, prices);
         Self::deposit_event(Event::NewAsset(asset.get_id(), asset.to_str_bytes()));
@@ -572,6 +786,24 @@ impl<T: Config> AssetGetter for Pallet<T> {
             Err(_) => EqFixedU128::zero(),
         }
     }
+
+    /// Gets existential deposit override of an asset
+    fn existential_deposit(asset: &Asset) -> Option<Balance> {
+        Self::existential_deposit_override(asset)
+    }
+
+    /// Gets risk tier of an asset, unknown assets are the most risky
+    fn risk_tier(asset: &Asset) -> RiskTier {
+        Self::risk_tier_of(asset).unwrap_or(RiskTier::Tier3)
+    }
+
+    /// Gets decimals of an asset from its XCM data or the decimals registry
+    fn decimals(asset: &Asset) -> u8 {
+        match Self::get_asset_data(asset).map(|data| data.get_xcm_data()) {
+            Ok(Some((_, decimals, _))) => decimals,
+            _ => Self::decimals_of(asset).unwrap_or(DECIMALS),
+        }
+    }
 }
 
 impl<T: Config> AssetXcmGetter for Pallet<T> {
@@ -20,7 +20,13 @@
 
 use core::convert::TryInto;
 
-use eq_primitives::asset::{Asset, AssetData};
+use crate::{Config, ExistentialDeposits, Pallet, RiskTiers};
+use eq_primitives::asset::{Asset, AssetData, RiskTier};
+use eq_primitives::balance::Balance;
+use frame_support::{
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
 use sp_std::vec::Vec;
 
 mod commit_85c486cb55336769e0543a66be0b2bafec90f62b {
@@ -213,3 +219,57 @@ pub fn migrate_assets_data(
         .collect::<Vec<_>>()
         .into()
 }
+
+/// Sets initial existential deposit overrides, e.g. for dust-prone bridged tokens.
+/// Assets that are not registered are skipped.
+pub fn migrate_existential_deposits<T: Config, Overrides: Get<Vec<(Asset, Balance)>>>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return Weight::zero();
+    }
+
+    let assets = Pallet::<T>::assets().unwrap_or_default();
+    let mut written = 0u64;
+    for (asset, existential_deposit) in Overrides::get() {
+        if assets.iter().any(|a| a.id == asset) {
+            ExistentialDeposits::<T>::insert(asset, existential_deposit);
+            written += 1;
+        }
+    }
+    StorageVersion::new(1).put::<Pallet<T>>();
+
+    log::info!(
+        target: "eq_assets",
+        "migrate_existential_deposits: {:?} overrides set",
+        written
+    );
+
+    T::DbWeight::get().reads_writes(2, written + 1)
+}
+
+/// Assigns risk tiers to existing assets: `Overrides` or the default tier of the asset type.
+/// Overrides for assets that are not registered are skipped.
+pub fn migrate_risk_tiers<T: Config, Overrides: Get<Vec<(Asset, RiskTier)>>>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 2 {
+        return Weight::zero();
+    }
+
+    let assets = Pallet::<T>::assets().unwrap_or_default();
+    let overrides = Overrides::get();
+    for asset in assets.iter() {
+        let tier = overrides
+            .iter()
+            .find(|(id, _)| *id == asset.id)
+            .map(|(_, tier)| *tier)
+            .unwrap_or_else(|| RiskTier::default_for(&asset.asset_type));
+        RiskTiers::<T>::insert(asset.id, tier);
+    }
+    StorageVersion::new(2).put::<Pallet<T>>();
+
+    log::info!(
+        target: "eq_assets",
+        "migrate_risk_tiers: {:?} tiers set",
+        assets.len()
+    );
+
+    T::DbWeight::get().reads_writes(2, assets.len() as u64 + 1)
+}
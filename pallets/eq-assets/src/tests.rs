@@ -565,3 +565,150 @@ fn update_only_one_field_in_asset() {
         assert_eq!(ModuleAssets::get_asset_data(&btc.id).unwrap(), btc.clone());
     });
 }
+
+#[test]
+fn derivative_asset_name() {
+    new_test_ext().execute_with(|| {
+        for id in [0u32, 1, 1234] {
+            let name: Vec<u8> = b"lpt"
+                .iter()
+                .chain(id.to_string().as_bytes())
+                .copied()
+                .collect();
+            assert_eq!(
+                ModuleAssets::derivative_asset(b"lpt", id).unwrap(),
+                Asset::from_bytes(&name).unwrap()
+            );
+        }
+
+        assert_err!(
+            ModuleAssets::derivative_asset(b"lpt", 123456).map_err(DispatchError::from),
+            Error::<Test>::AssetNameWrongLength
+        );
+        assert_err!(
+            ModuleAssets::derivative_asset(b"lp-", 1).map_err(DispatchError::from),
+            Error::<Test>::AssetNameWrongSymbols
+        );
+    });
+}
+
+#[test]
+fn create_derivative_asset() {
+    new_test_ext().execute_with(|| {
+        let params = DerivativeAssetParams::lp_token(
+            AssetType::Lp(eq_primitives::asset::AmmPool::Curve(7)),
+            Permill::from_percent(40),
+            u64::MAX,
+        );
+
+        let asset = ModuleAssets::create_derivative_asset(b"lpt", 7, params.clone()).unwrap();
+        assert_eq!(asset, Asset::from_bytes(b"lpt7").unwrap());
+
+        let asset_data = ModuleAssets::get_asset_data(&asset).unwrap();
+        assert_eq!(asset_data.asset_type, params.asset_type);
+        assert_eq!(asset_data.debt_weight, params.debt_weight);
+        assert_eq!(asset_data.is_dex_enabled, false);
+        assert_eq!(asset_data.collateral_discount, Percent::zero());
+
+        assert_err!(
+            ModuleAssets::create_derivative_asset(b"lpt", 7, params.clone()),
+            Error::<Test>::AssetAlreadyExists
+        );
+        assert_err!(
+            ModuleAssets::create_derivative_asset(b"lp.", 8, params),
+            Error::<Test>::AssetNameWrongSymbols
+        );
+    });
+}
+
+#[test]
+fn risk_tiers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let params = DerivativeAssetParams::lp_token(
+            AssetType::Lp(eq_primitives::asset::AmmPool::Curve(7)),
+            Permill::from_percent(40),
+            u64::MAX,
+        );
+        let lp_token = ModuleAssets::create_derivative_asset(b"lpt", 7, params).unwrap();
+
+        // new assets get the default tier of their type
+        assert_eq!(ModuleAssets::risk_tier(&lp_token), RiskTier::Tier3);
+        assert_eq!(ModuleAssets::risk_tier(&asset::BTC), RiskTier::Tier3);
+
+        assert_noop!(
+            ModuleAssets::set_risk_tier(RawOrigin::Root.into(), asset::BTC, RiskTier::Tier1),
+            Error::<Test>::AssetNotExists
+        );
+        assert_noop!(
+            ModuleAssets::set_risk_tier(RuntimeOrigin::signed(1), lp_token, RiskTier::Tier2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(ModuleAssets::set_risk_tier(
+            RawOrigin::Root.into(),
+            lp_token,
+            RiskTier::Tier2
+        ));
+        assert_eq!(ModuleAssets::risk_tier(&lp_token), RiskTier::Tier2);
+        System::assert_last_event(Event::RiskTierSet(lp_token.get_id(), RiskTier::Tier2).into());
+    });
+}
+
+#[test]
+fn decimals_registry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let mut dot = create_empty_asset(asset::DOT);
+        dot.asset_xcm_data = AssetXcmData::OtherReserved(asset::OtherReservedData {
+            multi_location: xcm::v3::MultiLocation::parent(),
+            decimals: 10,
+        });
+        let mut assets = vec![create_empty_asset(asset::ETH), dot];
+        assets.sort_by_key(|a| a.id);
+        Assets::<Test>::put(assets);
+
+        // native decimals by default
+        assert_eq!(ModuleAssets::decimals(&asset::ETH), DECIMALS);
+        assert_eq!(ModuleAssets::decimals(&asset::DOT), 10);
+
+        assert_noop!(
+            ModuleAssets::set_decimals(RuntimeOrigin::signed(1), asset::ETH, Some(18)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ModuleAssets::set_decimals(RawOrigin::Root.into(), asset::BTC, Some(8)),
+            Error::<Test>::AssetNotExists
+        );
+        assert_noop!(
+            ModuleAssets::set_decimals(RawOrigin::Root.into(), asset::DOT, Some(12)),
+            Error::<Test>::DecimalsDefinedByXcm
+        );
+        assert_noop!(
+            ModuleAssets::set_decimals(RawOrigin::Root.into(), asset::ETH, Some(MAX_DECIMALS + 1)),
+            Error::<Test>::WrongDecimals
+        );
+
+        assert_ok!(ModuleAssets::set_decimals(
+            RawOrigin::Root.into(),
+            asset::ETH,
+            Some(18)
+        ));
+        assert_eq!(ModuleAssets::decimals(&asset::ETH), 18);
+        System::assert_last_event(Event::DecimalsSet(asset::ETH.get_id(), Some(18)).into());
+        assert_eq!(
+            eq_primitives::decimals::to_native::<ModuleAssets, Balance>(
+                &asset::ETH,
+                1_500_000_000_000_000_000
+            ),
+            Some(1_500_000_000)
+        );
+
+        assert_ok!(ModuleAssets::set_decimals(
+            RawOrigin::Root.into(),
+            asset::ETH,
+            None
+        ));
+        assert_eq!(ModuleAssets::decimals(&asset::ETH), DECIMALS);
+    });
+}
@@ -26,6 +26,9 @@ pub trait WeightInfo {
     fn add_asset() -> Weight;
     fn remove_asset() -> Weight;
     fn update_asset() -> Weight;
+    fn set_existential_deposit() -> Weight;
+    fn set_risk_tier() -> Weight;
+    fn set_decimals() -> Weight;
 }
 
 // for tests
@@ -39,4 +42,13 @@ impl crate::WeightInfo for () {
     fn update_asset() -> Weight {
         Weight::zero()
     }
+    fn set_existential_deposit() -> Weight {
+        Weight::zero()
+    }
+    fn set_risk_tier() -> Weight {
+        Weight::zero()
+    }
+    fn set_decimals() -> Weight {
+        Weight::zero()
+    }
 }
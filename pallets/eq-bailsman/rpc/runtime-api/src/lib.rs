@@ -0,0 +1,40 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for `eq-bailsman` pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use eq_primitives::{LiquidityLineUtilization, QueuedDistribution};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait EqBailsmanApi<Balance, AccountId>
+    where
+        Balance: Codec,
+        AccountId: Codec
+    {
+        /// Treasury liquidity line of the bailsman pool, its utilization and current pool coverage
+        fn liquidity_line() -> Option<LiquidityLineUtilization<Balance>>;
+
+        /// Distributions queued for the bailsman with amounts each of them brings,
+        /// empty if account is not a bailsman
+        fn queued_distributions(bailsman: AccountId) -> Vec<QueuedDistribution<Balance>>;
+    }
+}
@@ -118,36 +118,40 @@ fn prepare_temp_balances<T: Config>() {
     }
 }
 
+fn fund_and_register_bailsman<T: Config>(bails: &T::AccountId) {
+    T::EqCurrency::deposit_creating(
+        bails,
+        asset::EQ,
+        From::<u128>::from(9_000_000_000_000u128),
+        true,
+        None,
+    )
+    .unwrap();
+    T::EqCurrency::deposit_creating(
+        bails,
+        asset::BTC,
+        From::<u128>::from(10_000_000_000_000u128),
+        true,
+        None,
+    )
+    .unwrap();
+    T::EqCurrency::deposit_creating(
+        bails,
+        asset::EQD,
+        From::<u128>::from(10_000_000_000_000u128),
+        true,
+        None,
+    )
+    .unwrap();
+    crate::Pallet::<T>::register_bailsman(bails).unwrap();
+}
+
 fn register_bailsmans<T: Config>(count: u32) -> Vec<T::AccountId> {
     let mut bailsmans = Vec::with_capacity(count as usize);
     for i in 0..count {
         let bails: T::AccountId = account("bails", i, SEED);
         bailsmans.push(bails.clone());
-        T::EqCurrency::deposit_creating(
-            &bails,
-            asset::EQ,
-            From::<u128>::from(9_000_000_000_000u128),
-            true,
-            None,
-        )
-        .unwrap();
-        T::EqCurrency::deposit_creating(
-            &bails,
-            asset::BTC,
-            From::<u128>::from(10_000_000_000_000u128),
-            true,
-            None,
-        )
-        .unwrap();
-        T::EqCurrency::deposit_creating(
-            &bails,
-            asset::EQD,
-            From::<u128>::from(10_000_000_000_000u128),
-            true,
-            None,
-        )
-        .unwrap();
-        crate::Pallet::<T>::register_bailsman(&bails).unwrap();
+        fund_and_register_bailsman::<T>(&bails);
     }
 
     bailsmans
@@ -192,6 +196,7 @@ benchmarks! {
                 higher_priority: false,
                 queue_len: z,
                 val_len: 1,
+                nonce: 0,
             };
 
         let validator = <T as pallet::Config>::AuthorityId::generate_pair(None);
@@ -268,4 +273,61 @@ benchmarks! {
     }:{
         EqBailsman::<T>::on_finalize(block_number);
     }
+
+    set_liquidity_line{
+        init::<T>();
+        let line = LiquidityLine {
+            asset: asset::EQD,
+            cap: From::<u128>::from(1_000_000_000_000_000u128),
+            coverage_threshold: EqFixedU128::one(),
+            rate: Permill::from_percent(5),
+        };
+    }:_(RawOrigin::Root, Some(line.clone()))
+    verify{
+        assert_eq!(crate::Pallet::<T>::treasury_line(), Some(line));
+    }
+
+    manage_liquidity_line{
+        init::<T>();
+        let _ = register_bailsmans::<T>(1);
+
+        let borrower: T::AccountId = account("borrower", 0, SEED);
+        T::Aggregates::set_usergroup(&borrower, UserGroup::Borrowers, true).unwrap();
+        T::EqCurrency::make_free_balance_be(
+            &borrower,
+            asset::EQD,
+            SignedBalance::Negative(From::<u128>::from(100_000_000_000_000u128)),
+        );
+        T::EqCurrency::make_free_balance_be(
+            &crate::Pallet::<T>::treasury_account_id(),
+            asset::EQD,
+            SignedBalance::Positive(From::<u128>::from(1_000_000_000_000_000u128)),
+        );
+        TreasuryLine::<T>::put(LiquidityLine {
+            asset: asset::EQD,
+            cap: From::<u128>::from(1_000_000_000_000_000u128),
+            coverage_threshold: EqFixedU128::one(),
+            rate: Permill::from_percent(5),
+        });
+    }:{
+        EqBailsman::<T>::manage_liquidity_line();
+    }
+    verify{
+        assert!(!crate::Pallet::<T>::treasury_line_debt().principal.is_zero());
+    }
+
+    exit_bailsman{
+        let z in 1..50;
+
+        init::<T>();
+        let _ = register_bailsmans::<T>(1);
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let bailsman = T::SubaccountsManager::create_subaccount_inner(&owner, &SubAccType::Bailsman).unwrap();
+        fund_and_register_bailsman::<T>(&bailsman);
+        prepare_distribution_queue::<T>(z);
+    }:_(RawOrigin::Signed(owner))
+    verify{
+        assert_eq!(crate::Pallet::<T>::bailsmen_count(), 1);
+        assert_eq!(LastDistribution::<T>::get(bailsman), None);
+    }
 }
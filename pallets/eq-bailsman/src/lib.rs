@@ -35,6 +35,13 @@
 //! inside the bailsman pool and split balances accrued on pallet’s account accordingly.
 //! In further releases we will optimize this by working with aggregates/integrals and not making redistribution on price updates.
 
+//! Participation in the pool is accounted with pool shares (bLP). Shares are minted on registration 1:1 with the
+//! net USD balance of the bailsman, synced with it on every redistribution of the bailsman and burned on unregistration.
+//! Pro-rata claim of a bailsman on the pool is its shares over `TotalShares`, no iteration over balances is needed.
+
+//! Redistributions sent by the offchain worker are free and dispatched in the operational class, so they aren't
+//! crowded out by normal transactions in full blocks. Their number is limited by `MaxPriorityRedistributionsPerBlock`.
+
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings)]
 
@@ -50,7 +57,7 @@ extern crate alloc;
 
 use codec::Codec;
 #[allow(unused_imports)]
-use eq_primitives::balance::{BalanceChecker, EqCurrency};
+use eq_primitives::balance::{BalanceCheckError, BalanceChecker, BalanceCheckerKind, EqCurrency};
 use sp_runtime::{
     traits::{CheckedAdd, CheckedSub, Saturating},
     ArithmeticError, FixedPointNumber,
@@ -63,12 +70,14 @@ use eq_primitives::{
     asset::{Asset, AssetGetter},
     balance::{BalanceGetter, DebtCollateralDiscounted},
     balance_number::EqFixedU128,
-    offchain_batcher::{OffchainErr, OffchainResult, ValidatorOffchainBatcher},
+    offchain_batcher::{OffchainErr, OffchainResult, UnsignedDuty, ValidatorOffchainBatcher},
     price::PriceGetter,
     signed_balance::SignedBalance,
-    subaccount::SubaccountsManager,
-    AccountDistribution, Aggregates, BailsmanManager, BalanceChange, Distribution, DistributionId,
-    MarginCallManager, MarginState, UserGroup, DISTRIBUTION_ACC,
+    subaccount::{SubAccType, SubaccountsManager},
+    AccountDistribution, Aggregates, BailsmanCoverage, BailsmanManager, BalanceChange,
+    Distribution, DistributionId, LiquidityLine, LiquidityLineDebt, LiquidityLineUtilization,
+    MarginCallManager, MarginState, QueuedDistribution, TransferReason, UserGroup,
+    DISTRIBUTION_ACC,
 };
 use eq_utils::{
     eq_ensure,
@@ -78,8 +87,9 @@ use eq_utils::{
 use frame_support::dispatch::{Pays, PostDispatchInfo};
 use frame_support::traits::WithdrawReasons;
 use frame_support::{
-    pallet_prelude::InvalidTransaction,
+    pallet_prelude::{InvalidTransaction, ValidTransaction},
     traits::{ExistenceRequirement, Get, UnixTime},
+    weights::Weight,
     PalletId, Parameter,
 };
 use frame_system as system;
@@ -100,6 +110,8 @@ pub use weights::WeightInfo;
 pub use pallet::*;
 
 const DB_PREFIX: &[u8] = b"eq-bailsman/";
+/// seconds in one year = 365.25*24*60*60
+const SECONDS_IN_YEAR: u64 = 31_557_600;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -118,6 +130,10 @@ pub mod pallet {
         #[pallet::constant]
         type PalletId: Get<PalletId>;
 
+        /// Treasury account that provides the liquidity line to the bailsman pool
+        #[pallet::constant]
+        type TreasuryModuleId: Get<PalletId>;
+
         type AuthorityId: Member + Parameter + RuntimeAppPublic + Ord + MaybeSerializeDeserialize;
 
         /// Gets currency prices from oracle
@@ -176,6 +192,13 @@ pub mod pallet {
         /// Special constant for improving weight in unsigned extrinsics
         #[pallet::constant]
         type QueueLengthWeightConstant: Get<u32>;
+        /// Weight spent on redistribution in `on_initialize`, zero disables it.
+        /// Remaining block weight is also used in `on_idle`
+        #[pallet::constant]
+        type RedistributionWeightBudget: Get<Weight>;
+        /// Max number of `redistribute_unsigned` calls in a block
+        #[pallet::constant]
+        type MaxPriorityRedistributionsPerBlock: Get<u32>;
     }
 
     #[pallet::call]
@@ -193,14 +216,27 @@ pub mod pallet {
         }
 
         /// Request to redistribute single bailsman sent by offchain worker.
+        /// Dispatched in the operational class, so redistribution isn't crowded out by normal
+        /// transactions, and limited by `MaxPriorityRedistributionsPerBlock`.
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::redistribute_unsigned(request.queue_len + T::QueueLengthWeightConstant::get()))]
+        #[pallet::weight((
+            <T as pallet::Config>::WeightInfo::redistribute_unsigned(
+                request.queue_len + T::QueueLengthWeightConstant::get()
+            ),
+            DispatchClass::Operational
+        ))]
         pub fn redistribute_unsigned(
             origin: OriginFor<T>,
             request: DistributionRequest<T::AccountId, T::BlockNumber>,
             _signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            Self::take_priority_slot()?;
+            T::ValidatorOffchainBatcher::note_duty(
+                request.auth_idx,
+                UnsignedDuty::BailsmanRedistribution,
+                request.nonce,
+            );
 
             let DistributionRequest { bailsman, .. } = request;
             <Self as BailsmanManager<_, _>>::redistribute(&bailsman)?;
@@ -209,7 +245,7 @@ pub mod pallet {
             let weight = T::WeightInfo::redistribute_unsigned(queue.len() as u32);
             Ok(PostDispatchInfo {
                 actual_weight: Some(weight),
-                pays_fee: Pays::Yes,
+                pays_fee: Pays::No,
             })
         }
 
@@ -228,6 +264,69 @@ pub mod pallet {
                 pays_fee: Pays::Yes,
             })
         }
+
+        /// Opens, changes or closes (`None`) the treasury liquidity line of the bailsman pool.
+        /// Line can't be closed or switched to another asset while it has outstanding debt.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_liquidity_line())]
+        pub fn set_liquidity_line(
+            origin: OriginFor<T>,
+            line: Option<LiquidityLine<T::Balance>>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            let debt = Self::accrue_line_interest();
+            let has_debt = !debt.principal.is_zero() || !debt.interest.is_zero();
+            if let Some(current) = Self::treasury_line() {
+                let same_asset = line.as_ref().map(|l| l.asset) == Some(current.asset);
+                eq_ensure!(
+                    !has_debt || same_asset,
+                    Error::<T>::LiquidityLineInUse,
+                    target: "eq_bailsman",
+                    "{}:{}. Liquidity line has outstanding debt. Principal: {:?}, interest: {:?}.",
+                    file!(),
+                    line!(),
+                    debt.principal,
+                    debt.interest
+                );
+            }
+
+            match &line {
+                Some(l) => TreasuryLine::<T>::put(l),
+                None => TreasuryLine::<T>::kill(),
+            }
+            Self::deposit_event(Event::LiquidityLineChanged(line));
+
+            Ok(().into())
+        }
+
+        /// Voluntary exit from the bailsman pool. Settles all queued distributions
+        /// of the caller's bailsman subaccount and unregisters it.
+        /// Caller pays for the settlement of the whole queue.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::exit_bailsman(30))]
+        pub fn exit_bailsman(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let bailsman = T::SubaccountsManager::get_subaccount_id(&who, &SubAccType::Bailsman)
+                .ok_or(Error::<T>::NotBailsman)?;
+            Self::ensure_bailsman(&bailsman)?;
+
+            let (_, queue) = DistributionQueue::<T>::get();
+            let last_distribution_id = LastDistribution::<T>::get(&bailsman).unwrap_or(0u32);
+            let settled = queue
+                .iter()
+                .filter(|(&id, _)| id > last_distribution_id)
+                .count() as u32;
+
+            <Self as BailsmanManager<_, _>>::unregister_bailsman(&bailsman)?;
+            Self::deposit_event(Event::BailsmanExited(who, bailsman, settled));
+
+            let weight = T::WeightInfo::exit_bailsman(queue.len() as u32);
+            Ok(PostDispatchInfo {
+                actual_weight: Some(weight),
+                pays_fee: Pays::Yes,
+            })
+        }
     }
 
     #[pallet::hooks]
@@ -237,6 +336,7 @@ pub mod pallet {
             let distribution_balances = DISTRIBUTION_ACC.into_account_truncating();
             let remaining_bailsmen = BailsmenCount::<T>::get();
             let mut queue_len = 0;
+            let line_weight = Self::manage_liquidity_line();
             if remaining_bailsmen != 0 {
                 if let Ok(DebtCollateralDiscounted {
                     debt: temp_debt_usd,
@@ -305,8 +405,24 @@ pub mod pallet {
                 }
             }
 
+            let budget = T::RedistributionWeightBudget::get();
+            let redistribution_weight = if budget.is_zero() {
+                Weight::zero()
+            } else {
+                Self::redistribute_chunk(budget, true)
+            };
+
             <T as pallet::Config>::WeightInfo::on_initialize()
                 + <T as pallet::Config>::WeightInfo::on_finalize(queue_len as u32)
+                + line_weight
+                + redistribution_weight
+        }
+
+        fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            if remaining_weight.any_lt(T::DbWeight::get().reads_writes(2, 1)) {
+                return Weight::zero();
+            }
+            Self::redistribute_chunk(remaining_weight, false)
         }
 
         fn on_finalize(_: BlockNumberFor<T>) {
@@ -369,6 +485,12 @@ pub mod pallet {
         Convert,
         /// Price not found for redistribution
         PriceNotFound,
+        /// Liquidity line can't be closed or change asset while it has outstanding debt
+        LiquidityLineInUse,
+        /// Only assets of Tier1 and Tier2 risk tiers are accepted as bailsman collateral
+        CollateralNotEligible,
+        /// Limit of priority redistributions in the block is reached
+        PriorityLaneExhausted,
     }
 
     #[pallet::event]
@@ -376,6 +498,20 @@ pub mod pallet {
     pub enum Event<T: Config> {
         /// Bailsman subaccount is no longer a bailsman. \[who\]
         UnregisteredBailsman(T::AccountId),
+        /// Treasury liquidity line is changed or closed. \[line\]
+        LiquidityLineChanged(Option<LiquidityLine<T::Balance>>),
+        /// Bailsman pool draws from the treasury liquidity line. \[asset, amount, coverage\]
+        LiquidityLineDrawn(Asset, T::Balance, Option<EqFixedU128>),
+        /// Bailsman pool repays the treasury liquidity line. \[asset, principal, interest\]
+        LiquidityLineRepaid(Asset, T::Balance, T::Balance),
+        /// Distributions up to id are applied to every bailsman. \[distribution_id\]
+        RedistributionCompleted(DistributionId),
+        /// Bailsman left the pool after settling queued distributions. \[who, bailsman, settled_distributions\]
+        BailsmanExited(T::AccountId, T::AccountId, u32),
+        /// Pool shares are minted to the bailsman. \[who, amount\]
+        SharesMinted(T::AccountId, T::Balance),
+        /// Pool shares of the bailsman are burned. \[who, amount\]
+        SharesBurned(T::AccountId, T::Balance),
     }
 
     /// Store total amount of bailsmen
@@ -400,10 +536,44 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Pass of block redistribution: last distribution id when the pass started
+    /// and last processed bailsman
+    #[pallet::storage]
+    pub type RedistributionCursor<T: Config> =
+        StorageValue<_, (DistributionId, T::AccountId), OptionQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn auto_redistribution_enabled)]
     pub type AutoRedistributionEnabled<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+    /// Priority liquidity line provided by the treasury to the bailsman pool
+    #[pallet::storage]
+    #[pallet::getter(fn treasury_line)]
+    pub type TreasuryLine<T: Config> = StorageValue<_, LiquidityLine<T::Balance>, OptionQuery>;
+
+    /// Outstanding debt of the bailsman pool on the treasury liquidity line
+    #[pallet::storage]
+    #[pallet::getter(fn treasury_line_debt)]
+    pub type TreasuryLineDebt<T: Config> =
+        StorageValue<_, LiquidityLineDebt<T::Balance>, ValueQuery>;
+
+    /// Pool shares (bLP) of bailsmen, valued 1:1 with the bailsman net USD balance
+    /// at the last sync
+    #[pallet::storage]
+    #[pallet::getter(fn shares)]
+    pub type Shares<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::Balance, ValueQuery>;
+
+    /// Total supply of pool shares
+    #[pallet::storage]
+    #[pallet::getter(fn total_shares)]
+    pub type TotalShares<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+
+    /// Block number and count of `redistribute_unsigned` calls dispatched in it
+    #[pallet::storage]
+    pub type PriorityRedistributions<T: Config> =
+        StorageValue<_, (T::BlockNumber, u32), ValueQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub bailsmen: Vec<T::AccountId>,
@@ -448,7 +618,12 @@ pub mod pallet {
         fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
             match (source, call) {
                 (_, Call::redistribute_unsigned { request, signature }) => {
-                    Self::check_unsigned_payload(&request, &signature)?;
+                    let duty = Self::check_unsigned_payload(&request, &signature)?;
+                    if Self::priority_redistributions()
+                        >= T::MaxPriorityRedistributionsPerBlock::get()
+                    {
+                        return InvalidTransaction::ExhaustsResources.into();
+                    }
                     let queue_len = request.queue_len as u64;
                     let priority = if request.higher_priority {
                         queue_len + T::UnsignedPriority::get() + 1
@@ -459,6 +634,7 @@ pub mod pallet {
                     ValidTransaction::with_tag_prefix("BailsRedistribution")
                         .priority(priority)
                         .and_provides(&request.bailsman)
+                        .combine_with(duty)
                         .longevity(5)
                         .propagate(true)
                         .build()
@@ -483,41 +659,60 @@ impl<T: Config> BalanceChecker<T::Balance, T::AccountId, T::BalanceGetter, T::Su
         who: &T::AccountId,
         changes: &Vec<(Asset, SignedBalance<T::Balance>)>,
         _withdraw_reasons: Option<WithdrawReasons>,
-    ) -> Result<(), DispatchError> {
+    ) -> Result<(), BalanceCheckError<T::Balance>> {
+        let fail = |e: DispatchError| BalanceCheckError::new(BalanceCheckerKind::Bailsman, e);
         let self_account_id = Self::get_account_id();
         let distribution_balance = DISTRIBUTION_ACC.into_account_truncating();
         if *who == distribution_balance {
-            return Err((Error::<T>::TempBalancesTransfer).into());
+            return Err(fail(Error::<T>::TempBalancesTransfer.into()));
         }
 
         if T::Aggregates::in_usergroup(who, UserGroup::Bailsmen) {
-            let temp_balance = T::BalanceGetter::get_debt_and_collateral(&self_account_id)?;
+            let adds_not_eligible = changes
+                .iter()
+                .any(|(asset, change)| Self::is_not_eligible_collateral(asset, change));
+            if adds_not_eligible {
+                return Err(fail(Error::<T>::CollateralNotEligible.into()));
+            }
+
+            let temp_balance =
+                T::BalanceGetter::get_debt_and_collateral(&self_account_id).map_err(fail)?;
             let min_temp_balance_usd = T::MinTempBalanceUsd::get();
 
-            if temp_balance.debt.saturating_add(temp_balance.collateral) > min_temp_balance_usd {
-                return Err((Error::<T>::TempBalancesNotDistributed).into());
+            let temp_balance_usd = temp_balance.debt.saturating_add(temp_balance.collateral);
+            if temp_balance_usd > min_temp_balance_usd {
+                return Err(fail(Error::<T>::TempBalancesNotDistributed.into())
+                    .with_amounts(temp_balance_usd, min_temp_balance_usd));
             }
 
             let DebtCollateralDiscounted {
                 debt: debt_value,
                 collateral: _,
                 discounted_collateral,
-            } = T::BalanceGetter::get_debt_and_collateral(who)?;
+            } = T::BalanceGetter::get_debt_and_collateral(who).map_err(fail)?;
 
             let should_unreg = Self::should_unreg_bailsman(
                 who,
                 &changes,
                 Some((debt_value, discounted_collateral)),
-            )?;
+            )
+            .map_err(fail)?;
 
             if should_unreg && !debt_value.is_zero() {
-                return Err((Error::<T>::BailsmanHasDebt).into());
+                return Err(fail(Error::<T>::BailsmanHasDebt.into())
+                    .with_amounts(debt_value, discounted_collateral));
             }
         }
-        let check_margin_result = Self::check_margin(who, changes)?;
+        let check_margin_result = Self::check_margin(who, changes).map_err(fail)?;
         check_margin_result
             .then(|| ())
-            .ok_or_else(|| Error::<T>::WrongMargin.into())
+            .ok_or_else(|| fail(Error::<T>::WrongMargin.into()))
+    }
+}
+
+impl<T: Config> BailsmanCoverage for Pallet<T> {
+    fn coverage_ratio() -> Option<EqFixedU128> {
+        Self::coverage_ratio()
     }
 }
 
@@ -534,6 +729,21 @@ impl<T: Config> BailsmanManager<T::AccountId, T::Balance> for Pallet<T> {
             who
         );
 
+        let not_eligible = T::BalanceGetter::iterate_account_balances(who)
+            .into_iter()
+            .find(|(asset, balance)| Self::is_not_eligible_collateral(asset, balance))
+            .map(|(asset, _)| asset);
+        eq_ensure!(
+            not_eligible.is_none(),
+            Error::<T>::CollateralNotEligible,
+            target: "eq_bailsman",
+            "{}:{}. Asset is not eligible as bailsman collateral. Who: {:?}, asset: {:?}",
+            file!(),
+            line!(),
+            who,
+            not_eligible
+        );
+
         let (is_enough, debt_usd, balance_usd, min_collateral) =
             Self::is_enough_to_become_bailsman(who)?;
 
@@ -559,6 +769,7 @@ impl<T: Config> BailsmanManager<T::AccountId, T::Balance> for Pallet<T> {
         T::Aggregates::set_usergroup(who, UserGroup::Bailsmen, true)?;
         BailsmenCount::<T>::mutate(|c| *c += 1);
         LastDistribution::<T>::insert(who, Self::get_current_distribution_id());
+        Self::sync_shares(who)?;
 
         Ok(())
     }
@@ -595,6 +806,7 @@ impl<T: Config> BailsmanManager<T::AccountId, T::Balance> for Pallet<T> {
         T::Aggregates::set_usergroup(who, UserGroup::Bailsmen, false)?;
         BailsmenCount::<T>::mutate(|c| *c -= 1);
         LastDistribution::<T>::remove(who);
+        Self::burn_shares(who);
         Self::deposit_event(Event::UnregisteredBailsman(who.clone()));
 
         Ok(())
@@ -750,6 +962,194 @@ impl<T: Config> Pallet<T> {
             .unwrap_or(0)
     }
 
+    /// Returns treasury account that provides the liquidity line
+    fn treasury_account_id() -> T::AccountId {
+        T::TreasuryModuleId::get().into_account_truncating()
+    }
+
+    /// Returns liquidity line state with current coverage of borrowers debt
+    pub fn liquidity_line_utilization() -> Option<LiquidityLineUtilization<T::Balance>> {
+        let line = Self::treasury_line()?;
+        let debt = Self::treasury_line_debt();
+        let pending = Self::pending_line_interest(&line, &debt, T::UnixTime::now().as_secs());
+
+        Some(LiquidityLineUtilization {
+            line,
+            principal: debt.principal,
+            interest: debt.interest.saturating_add(pending),
+            coverage: Self::coverage_ratio(),
+        })
+    }
+
+    /// Returns ratio of bailsman pool value to borrowers debt, `None` if there is no debt
+    pub fn coverage_ratio() -> Option<EqFixedU128> {
+        let (pool_usd, borrowers_debt_usd) = Self::pool_and_borrowers_debt_usd().ok()?;
+        Self::coverage(pool_usd, borrowers_debt_usd)
+    }
+
+    fn coverage(pool_usd: T::Balance, borrowers_debt_usd: T::Balance) -> Option<EqFixedU128> {
+        if borrowers_debt_usd.is_zero() {
+            None
+        } else {
+            EqFixedU128::checked_from_rational(pool_usd, borrowers_debt_usd)
+        }
+    }
+
+    /// Returns net USD value of the bailsman pool (including temp balances) and
+    /// total USD debt of borrowers
+    fn pool_and_borrowers_debt_usd() -> Result<(T::Balance, T::Balance), DispatchError> {
+        let mut pool_collateral_usd = T::Balance::zero();
+        let mut pool_debt_usd = T::Balance::zero();
+        for (asset, total) in T::Aggregates::iter_total(UserGroup::Bailsmen) {
+            let price = T::PriceGetter::get_price::<EqFixedU128>(&asset)?;
+            pool_collateral_usd =
+                pool_collateral_usd.saturating_add(price.saturating_mul_int(total.collateral));
+            pool_debt_usd = pool_debt_usd.saturating_add(price.saturating_mul_int(total.debt));
+        }
+
+        let mut borrowers_debt_usd = T::Balance::zero();
+        for (asset, total) in T::Aggregates::iter_total(UserGroup::Borrowers) {
+            if total.debt.is_zero() {
+                continue;
+            }
+            let price = T::PriceGetter::get_price::<EqFixedU128>(&asset)?;
+            borrowers_debt_usd =
+                borrowers_debt_usd.saturating_add(price.saturating_mul_int(total.debt));
+        }
+
+        Ok((
+            pool_collateral_usd.saturating_sub(pool_debt_usd),
+            borrowers_debt_usd,
+        ))
+    }
+
+    /// Simple interest accrued on the line principal since the last update
+    fn pending_line_interest(
+        line: &LiquidityLine<T::Balance>,
+        debt: &LiquidityLineDebt<T::Balance>,
+        now: u64,
+    ) -> T::Balance {
+        let elapsed = now.saturating_sub(debt.last_update);
+        if debt.principal.is_zero() || elapsed == 0 {
+            return T::Balance::zero();
+        }
+
+        eq_utils::multiply_by_rational(line.rate.mul_ceil(debt.principal), elapsed, SECONDS_IN_YEAR)
+            .map(T::Balance::from)
+            .unwrap_or_else(T::Balance::max_value)
+    }
+
+    /// Accrues interest on the liquidity line and returns updated debt
+    fn accrue_line_interest() -> LiquidityLineDebt<T::Balance> {
+        let now = T::UnixTime::now().as_secs();
+        let mut debt = Self::treasury_line_debt();
+        if let Some(line) = Self::treasury_line() {
+            let pending = Self::pending_line_interest(&line, &debt, now);
+            debt.interest = debt.interest.saturating_add(pending);
+        }
+        debt.last_update = now;
+        TreasuryLineDebt::<T>::put(&debt);
+
+        debt
+    }
+
+    /// Draws from the treasury liquidity line when pool coverage is below the threshold
+    /// or repays the line from bailsman temp balances when coverage is restored.
+    /// Errors are logged and don't affect block production.
+    fn manage_liquidity_line() -> Weight {
+        let line = match Self::treasury_line() {
+            Some(line) => line,
+            None => return Weight::zero(),
+        };
+
+        if let Err(err) = Self::do_manage_liquidity_line(line) {
+            log::error!(target: "eq_bailsman", "Liquidity line management failed. error: {:?}", err);
+        }
+
+        <T as pallet::Config>::WeightInfo::manage_liquidity_line()
+    }
+
+    fn do_manage_liquidity_line(line: LiquidityLine<T::Balance>) -> DispatchResult {
+        let mut debt = Self::accrue_line_interest();
+        let (pool_usd, borrowers_debt_usd) = Self::pool_and_borrowers_debt_usd()?;
+        let required_usd = line
+            .coverage_threshold
+            .saturating_mul_int(borrowers_debt_usd);
+        let price = T::PriceGetter::get_price::<EqFixedU128>(&line.asset)?;
+        let reciprocal = price.reciprocal().ok_or(ArithmeticError::DivisionByZero)?;
+
+        let temp_balances = Self::get_account_id();
+        let treasury = Self::treasury_account_id();
+        let positive_balance =
+            |who: &T::AccountId| match T::BalanceGetter::get_balance(who, &line.asset) {
+                SignedBalance::Positive(balance) => balance,
+                SignedBalance::Negative(_) => T::Balance::zero(),
+            };
+
+        if pool_usd < required_usd {
+            let needed = reciprocal.saturating_mul_int(required_usd - pool_usd);
+            let amount = needed
+                .min(line.cap.saturating_sub(debt.principal))
+                .min(positive_balance(&treasury));
+            if amount.is_zero() {
+                return Ok(());
+            }
+
+            T::EqCurrency::currency_transfer(
+                &treasury,
+                &temp_balances,
+                line.asset,
+                amount,
+                ExistenceRequirement::KeepAlive,
+                TransferReason::LiquidityLine,
+                false,
+            )?;
+
+            debt.principal = debt.principal.saturating_add(amount);
+            TreasuryLineDebt::<T>::put(&debt);
+
+            let coverage = Self::coverage(
+                pool_usd.saturating_add(price.saturating_mul_int(amount)),
+                borrowers_debt_usd,
+            );
+            Self::deposit_event(Event::LiquidityLineDrawn(line.asset, amount, coverage));
+        } else {
+            let outstanding = debt.principal.saturating_add(debt.interest);
+            if outstanding.is_zero() {
+                return Ok(());
+            }
+
+            let surplus = reciprocal.saturating_mul_int(pool_usd - required_usd);
+            let amount = outstanding
+                .min(surplus)
+                .min(positive_balance(&temp_balances));
+            if amount.is_zero() {
+                return Ok(());
+            }
+
+            T::EqCurrency::currency_transfer(
+                &temp_balances,
+                &treasury,
+                line.asset,
+                amount,
+                ExistenceRequirement::KeepAlive,
+                TransferReason::LiquidityLine,
+                false,
+            )?;
+
+            // interest is repaid first
+            let interest = amount.min(debt.interest);
+            let principal = amount - interest;
+            debt.interest = debt.interest - interest;
+            debt.principal = debt.principal.saturating_sub(principal);
+            TreasuryLineDebt::<T>::put(&debt);
+
+            Self::deposit_event(Event::LiquidityLineRepaid(line.asset, principal, interest));
+        }
+
+        Ok(())
+    }
+
     fn ensure_bailsman(who: &T::AccountId) -> DispatchResult {
         eq_ensure!(
             T::Aggregates::in_usergroup(who, UserGroup::Bailsmen),
@@ -764,22 +1164,91 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    /// Apply distributions for all bailsmen
-    fn _full_redistribute() -> Result<u32, DispatchError> {
-        let mut was_redistributed = 0;
+    /// Queued distributions not yet applied to bailsman `who`
+    /// with amounts each of them brings to the bailsman
+    pub fn queued_distributions(
+        who: &T::AccountId,
+    ) -> Result<Vec<QueuedDistribution<T::Balance>>, DispatchError> {
+        Self::ensure_bailsman(who)?;
+
+        let last_distribution_id = LastDistribution::<T>::get(who).unwrap_or(0u32);
+        let (_, queue) = DistributionQueue::<T>::get();
+        let mut before_distr_balances = T::BalanceGetter::iterate_account_balances(who);
+
+        queue
+            .iter()
+            .filter(|(&id, _)| id > last_distribution_id)
+            .map(|(&id, distribution)| {
+                let mut amounts = VecMap::new();
+                Self::apply_distribution(&mut before_distr_balances, distribution, &mut amounts)?;
+                Ok(QueuedDistribution {
+                    id,
+                    remaining_bailsmen: distribution.remaining_bailsmen,
+                    amounts: amounts.into_iter().collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Applies queued distributions to bailsmen following `RedistributionCursor`
+    /// until `budget` is spent. With `force_progress` at least one bailsman is processed.
+    /// Returns consumed weight
+    fn redistribute_chunk(budget: Weight, force_progress: bool) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let (current_distribution_id, queue) = Self::distribution_queue();
+        let mut consumed = db_weight.reads(2);
+        if queue.is_empty() {
+            return consumed;
+        }
+
+        let per_bailsman = T::WeightInfo::redistribute(queue.len() as u32);
         let self_account = Self::get_account_id();
         let distr_acc = DISTRIBUTION_ACC.into_account_truncating();
 
-        for bailsman_acc in T::Aggregates::iter_account(UserGroup::Bailsmen) {
-            if bailsman_acc == self_account || bailsman_acc == distr_acc {
-                continue;
+        let cursor = RedistributionCursor::<T>::get();
+        let pass_distribution_id = cursor
+            .as_ref()
+            .map(|(id, _)| *id)
+            .unwrap_or(current_distribution_id);
+        let mut last_processed = cursor.map(|(_, acc)| acc);
+        let mut bailsmen = match &last_processed {
+            Some(acc) => T::Aggregates::iter_account_from(UserGroup::Bailsmen, acc),
+            None => T::Aggregates::iter_account(UserGroup::Bailsmen),
+        };
+
+        let mut processed = 0u32;
+        loop {
+            let has_budget = !consumed.saturating_add(per_bailsman).any_gt(budget);
+            if !has_budget && (processed != 0 || !force_progress) {
+                if let Some(acc) = last_processed {
+                    RedistributionCursor::<T>::put((pass_distribution_id, acc));
+                }
+                return consumed.saturating_add(db_weight.writes(1));
             }
 
-            let redistributed = Self::do_redistribute(&bailsman_acc)?;
-            was_redistributed = was_redistributed.saturating_add(redistributed);
+            match bailsmen.next() {
+                Some(bailsman_acc) => {
+                    consumed = consumed.saturating_add(per_bailsman);
+                    processed += 1;
+                    if bailsman_acc != self_account && bailsman_acc != distr_acc {
+                        if let Err(err) = Self::do_redistribute(&bailsman_acc) {
+                            log::error!(
+                                target: "eq_bails",
+                                "Redistribution failed. bailsman: {:?}, error: {:?}",
+                                bailsman_acc,
+                                err
+                            );
+                        }
+                    }
+                    last_processed = Some(bailsman_acc);
+                }
+                None => {
+                    RedistributionCursor::<T>::kill();
+                    Self::deposit_event(Event::RedistributionCompleted(pass_distribution_id));
+                    return consumed.saturating_add(db_weight.writes(1));
+                }
+            }
         }
-
-        Ok(was_redistributed)
     }
 
     fn get_account_distribution(
@@ -841,6 +1310,7 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Apply all distributions from queue for account
+    #[frame_support::transactional]
     fn do_redistribute(bailsman_acc_id: &T::AccountId) -> Result<u32, DispatchError> {
         let AccountDistribution {
             transfers,
@@ -850,6 +1320,7 @@ impl<T: Config> Pallet<T> {
         } = Self::get_account_distribution(&bailsman_acc_id)?;
 
         if transfers.is_empty() {
+            Self::sync_shares(bailsman_acc_id)?;
             return Ok(0);
         }
 
@@ -857,10 +1328,57 @@ impl<T: Config> Pallet<T> {
 
         LastDistribution::<T>::insert(bailsman_acc_id, current_distribution_id);
         DistributionQueue::<T>::set((current_distribution_id, new_queue));
+        Self::sync_shares(bailsman_acc_id)?;
 
         Ok(current_distribution_id - last_distribution_id)
     }
 
+    /// Pro-rata claim of the bailsman on the pool: its shares over the total supply
+    pub fn pool_share(who: &T::AccountId) -> Option<EqFixedU128> {
+        let total = Self::total_shares();
+        if total.is_zero() {
+            return None;
+        }
+        EqFixedU128::checked_from_rational(Self::shares(who), total)
+    }
+
+    /// Mints or burns shares of the bailsman to match its net USD balance
+    fn sync_shares(who: &T::AccountId) -> DispatchResult {
+        let DebtCollateralDiscounted {
+            debt, collateral, ..
+        } = T::BalanceGetter::get_debt_and_collateral(who)?;
+        let net_usd = collateral.saturating_sub(debt);
+        let shares = Shares::<T>::get(who);
+
+        if net_usd > shares {
+            let minted = net_usd - shares;
+            TotalShares::<T>::try_mutate(|total| -> DispatchResult {
+                *total = total
+                    .checked_add(&minted)
+                    .ok_or(ArithmeticError::Overflow)?;
+                Ok(())
+            })?;
+            Shares::<T>::insert(who, net_usd);
+            Self::deposit_event(Event::SharesMinted(who.clone(), minted));
+        } else if net_usd < shares {
+            let burned = shares - net_usd;
+            TotalShares::<T>::mutate(|total| *total = total.saturating_sub(burned));
+            Shares::<T>::insert(who, net_usd);
+            Self::deposit_event(Event::SharesBurned(who.clone(), burned));
+        }
+
+        Ok(())
+    }
+
+    /// Burns all shares of the leaving bailsman
+    fn burn_shares(who: &T::AccountId) {
+        let burned = Shares::<T>::take(who);
+        if !burned.is_zero() {
+            TotalShares::<T>::mutate(|total| *total = total.saturating_sub(burned));
+            Self::deposit_event(Event::SharesBurned(who.clone(), burned));
+        }
+    }
+
     /// Calculate amount of distribution and save it to accumulator
     fn apply_distribution(
         before_distr_balances: &mut VecMap<Asset, SignedBalance<T::Balance>>,
@@ -1043,6 +1561,12 @@ impl<T: Config> Pallet<T> {
         Ok(is_margin_increased || margin_state == MarginState::Good)
     }
 
+    /// Checks that `balance` is collateral in an asset of a risk tier not accepted from bailsmen
+    fn is_not_eligible_collateral(asset: &Asset, balance: &SignedBalance<T::Balance>) -> bool {
+        matches!(balance, SignedBalance::Positive(value) if !value.is_zero())
+            && !T::AssetGetter::risk_tier(asset).is_bailsman_eligible()
+    }
+
     /// Returns (is_enough, debt_value, discounted_collateral_value, min_bailsman_collateral)
     fn is_enough_to_become_bailsman(
         who: &T::AccountId,
@@ -1072,10 +1596,37 @@ impl<T: Config> Pallet<T> {
         ))
     }
 
+    /// Number of `redistribute_unsigned` calls dispatched in the current block
+    fn priority_redistributions() -> u32 {
+        let (block, count) = PriorityRedistributions::<T>::get();
+        if block == frame_system::Pallet::<T>::block_number() {
+            count
+        } else {
+            0
+        }
+    }
+
+    /// Counts a priority redistribution in the current block, fails when
+    /// `MaxPriorityRedistributionsPerBlock` is reached
+    fn take_priority_slot() -> DispatchResult {
+        let count = Self::priority_redistributions();
+        eq_ensure!(
+            count < T::MaxPriorityRedistributionsPerBlock::get(),
+            Error::<T>::PriorityLaneExhausted,
+            target: "eq_bailsman",
+            "{}:{}. Priority redistributions limit is reached. Count: {:?}.",
+            file!(),
+            line!(),
+            count
+        );
+        PriorityRedistributions::<T>::put((frame_system::Pallet::<T>::block_number(), count + 1));
+        Ok(())
+    }
+
     fn check_unsigned_payload(
         request: &DistributionRequest<T::AccountId, T::BlockNumber>,
         signature: &<T::AuthorityId as RuntimeAppPublic>::Signature,
-    ) -> Result<(), InvalidTransaction> {
+    ) -> Result<ValidTransaction, InvalidTransaction> {
         const INVALID_VALIDATORS_LEN: u8 = 10;
         const WRONG_QUEUE_LEN: u8 = 11;
 
@@ -1093,6 +1644,12 @@ impl<T: Config> Pallet<T> {
             .then(|| ())
             .ok_or(InvalidTransaction::BadProof)?;
 
+        let duty = T::ValidatorOffchainBatcher::validate_duty(
+            request.auth_idx,
+            UnsignedDuty::BailsmanRedistribution,
+            request.nonce,
+        )?;
+
         let (_, queue) = DistributionQueue::<T>::get();
         let current_queue_len = queue.len() as u32;
 
@@ -1101,7 +1658,7 @@ impl<T: Config> Pallet<T> {
             return Err(InvalidTransaction::Custom(WRONG_QUEUE_LEN));
         }
 
-        Ok(())
+        Ok(duty)
     }
 
     fn check_bailsmen_for_single_auth(
@@ -1132,6 +1689,9 @@ impl<T: Config> Pallet<T> {
             }
         }
 
+        let mut nonce =
+            T::ValidatorOffchainBatcher::duty_state(auth_idx, UnsignedDuty::BailsmanRedistribution)
+                .nonce;
         bailsmen_ids
             .into_iter()
             .enumerate()
@@ -1157,7 +1717,10 @@ impl<T: Config> Pallet<T> {
                     curr_distr_id,
                     queue_len,
                     idx as u32 % val_len == auth_idx,
-                )
+                    nonce,
+                )?;
+                nonce += 1;
+                Ok(())
             })?;
 
         Ok(())
@@ -1173,6 +1736,7 @@ impl<T: Config> Pallet<T> {
         curr_distr_id: DistributionId,
         queue_len: u32,
         higher_priority: bool,
+        nonce: u64,
     ) -> OffchainResult {
         let request = DistributionRequest {
             bailsman,
@@ -1183,6 +1747,7 @@ impl<T: Config> Pallet<T> {
             val_len,
             block_number,
             higher_priority,
+            nonce,
         };
         let signature = auth_key
             .sign(&request.encode())
@@ -1216,4 +1781,6 @@ pub struct DistributionRequest<AccountId, BlockNumber> {
     pub higher_priority: bool,
     /// Distribution queue length plus constant
     pub queue_len: u32,
+    /// Nonce of the `UnsignedDuty::BailsmanRedistribution` duty of the authority
+    pub nonce: u64,
 }
@@ -21,28 +21,22 @@
 use super::*;
 
 use crate as eq_bailsman;
-use core::fmt::Debug;
 use eq_balances;
-use eq_primitives::{
-    asset,
-    asset::AssetType,
-    mocks::TimeZeroDurationMock,
-    subaccount::{SubAccType, SubaccountsManager},
-    EqBuyout, OrderChange, UpdateTimeManager, XcmMode,
-};
+use eq_primitives::{asset, mocks::TimeZeroDurationMock, OrderChange, UpdateTimeManager, XcmMode};
 #[cfg(feature = "std")]
 use frame_support::traits::GenesisBuild;
 use frame_support::{parameter_types, traits::Everything, weights::Weight};
-use sp_arithmetic::{FixedI64, FixedPointNumber, Permill};
+use sp_arithmetic::FixedPointNumber;
 use sp_core::H256;
 use sp_runtime::{
     testing::{Header, TestXt, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
-    DispatchError, Percent,
+    DispatchError,
 };
-use std::cell::RefCell;
 use std::marker::PhantomData;
 use system::EnsureRoot;
+use test_utils::builder::ExtBuilder;
+pub use test_utils::mock::subaccounts::SubaccountsManagerMock;
 
 type AccountId = u64;
 pub(crate) type Balance = eq_primitives::balance::Balance;
@@ -112,62 +106,7 @@ impl frame_system::Config for Test {
 
 pub const BALANCE_ACCURACY: f64 = 1_000_000_000.0;
 
-thread_local! {
-    static PRICES: RefCell<Vec<(asset::Asset, FixedI64)>> = RefCell::new(vec![
-        (asset::BTC, FixedI64::saturating_from_integer(10000)),
-        (asset::EOS, FixedI64::saturating_from_integer(3)),
-        (asset::ETH, FixedI64::saturating_from_integer(250)),
-        (asset::EQD, FixedI64::saturating_from_integer(1)),
-        (asset::EQ, FixedI64::saturating_from_integer(1)),
-        (asset::DOT, FixedI64::saturating_from_integer(4)),
-        (asset::CRV, FixedI64::saturating_from_integer(5)),
-        (asset::USDC, FixedI64::saturating_from_integer(1)),
-        (asset::USDT, FixedI64::saturating_from_integer(1)),
-        ]);
-}
-
-pub struct OracleMock;
-
-pub trait PriceSetter {
-    fn set_price_mock(currency: &asset::Asset, value: &FixedI64);
-}
-
-impl PriceSetter for OracleMock {
-    fn set_price_mock(currency: &asset::Asset, value: &FixedI64) {
-        PRICES.with(|v| {
-            let mut vec = v.borrow().clone();
-            for pair in vec.iter_mut() {
-                if pair.0 == *currency {
-                    pair.1 = value.clone();
-                }
-            }
-
-            *v.borrow_mut() = vec;
-        });
-    }
-}
-impl PriceGetter for OracleMock {
-    fn get_price<FixedNumber>(asset: &Asset) -> Result<FixedNumber, sp_runtime::DispatchError>
-    where
-        FixedNumber: FixedPointNumber + One + Zero + Debug + TryFrom<FixedI64>,
-    {
-        let mut return_value = Ok(FixedNumber::zero());
-        PRICES.with(|v| {
-            let value = v.borrow().clone();
-            for pair in value.iter() {
-                if pair.0 == *asset {
-                    return_value = pair
-                        .1
-                        .clone()
-                        .try_into()
-                        .map_err(|_| sp_runtime::DispatchError::Other("Positive price"));
-                }
-            }
-        });
-
-        return_value
-    }
-}
+pub type OracleMock = eq_primitives::price::mock::OracleMock<AccountId>;
 
 impl timestamp::Config for Test {
     type Moment = u64;
@@ -176,75 +115,6 @@ impl timestamp::Config for Test {
     type WeightInfo = ();
 }
 
-pub struct EqBuyoutMock;
-
-impl EqBuyout<u64, Balance> for EqBuyoutMock {
-    fn eq_buyout(_who: &AccountId, _amount: Balance) -> sp_runtime::DispatchResult {
-        Ok(())
-    }
-    fn is_enough(
-        _currency: asset::Asset,
-        _amount: Balance,
-        _amount_buyout: Balance,
-    ) -> Result<bool, DispatchError> {
-        Ok(true)
-    }
-}
-
-thread_local! {
-    static ACC_OWNER: RefCell<Option<(AccountId, SubAccType)>> = RefCell::new(None);
-}
-
-pub struct SubaccountsManagerMock;
-impl SubaccountsManagerMock {
-    pub fn set_account_owner(account_id: AccountId, subacc: SubAccType) {
-        ACC_OWNER.with(|v| {
-            *v.borrow_mut() = Some((account_id, subacc));
-        });
-    }
-}
-
-impl SubaccountsManager<AccountId> for SubaccountsManagerMock {
-    fn create_subaccount_inner(
-        _who: &AccountId,
-        _subacc_type: &SubAccType,
-    ) -> Result<AccountId, DispatchError> {
-        Ok(AccountId::default())
-    }
-
-    fn delete_subaccount_inner(
-        _who: &AccountId,
-        _subacc_type: &SubAccType,
-    ) -> Result<AccountId, DispatchError> {
-        Ok(AccountId::default())
-    }
-
-    fn has_subaccount(_who: &AccountId, _subacc_type: &SubAccType) -> bool {
-        true
-    }
-
-    fn get_subaccount_id(_who: &AccountId, _subacc_type: &SubAccType) -> Option<AccountId> {
-        Some(AccountId::default())
-    }
-
-    fn is_subaccount(_who: &AccountId, _subacc_id: &AccountId) -> bool {
-        false
-    }
-
-    fn get_owner_id(_subaccount: &AccountId) -> Option<(AccountId, SubAccType)> {
-        let mut result = None;
-        ACC_OWNER.with(|v| {
-            result = v.borrow().clone();
-        });
-
-        result
-    }
-
-    fn get_subaccounts_amount(_who: &AccountId) -> usize {
-        0
-    }
-}
-
 impl eq_balances::Config for Test {
     type ParachainId = eq_primitives::mocks::ParachainId;
     type RuntimeEvent = RuntimeEvent;
@@ -266,6 +136,10 @@ impl eq_balances::Config for Test {
     type AssetGetter = eq_assets::Pallet<Test>;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type ModuleId = BalancesModuleId;
@@ -337,6 +211,8 @@ parameter_types! {
     pub const MaxBailsmenToDistribute: u32 = 1;
     pub const UnsignedPriority: u64 = 0;
     pub const QueueLengthWeightConstant: u32 = 5;
+    pub const RedistributionWeightBudget: Weight = Weight::zero();
+    pub const MaxPriorityRedistributionsPerBlock: u32 = 10;
 }
 
 impl<LocalCall> SendTransactionTypes<LocalCall> for Test
@@ -358,6 +234,7 @@ impl Config for Test {
     type MinTempBalanceUsd = MinTempBalanceUsd;
     type UnixTime = timestamp::Pallet<Self>;
     type PalletId = BailsmanModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
     type Aggregates = eq_aggregates::Pallet<Test>;
     type WeightInfo = ();
     type MarginCallManager = MarginCallManagerMock;
@@ -367,6 +244,8 @@ impl Config for Test {
     type UnsignedPriority = UnsignedPriority;
     type ValidatorOffchainBatcher = ();
     type QueueLengthWeightConstant = QueueLengthWeightConstant;
+    type RedistributionWeightBudget = RedistributionWeightBudget;
+    type MaxPriorityRedistributionsPerBlock = MaxPriorityRedistributionsPerBlock;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -374,161 +253,34 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         .build_storage::<Test>()
         .unwrap();
 
+    let builder = ExtBuilder::default()
+        .with_default_assets()
+        .balance(1, asset::BTC, 1000_000_000_000_000 as Balance)
+        .balance(2, asset::BTC, 1000_000_000_000_000 as Balance);
+    builder.init_mocks();
+    // account 0 is the bailsman subaccount of any owner
+    SubaccountsManagerMock::set_default_subaccount(Some(0));
+
     eq_assets::GenesisConfig::<Test> {
         _runtime: PhantomData,
-        assets: // id, lot, price_step, maker_fee, taker_fee, debt_weight, buyout_priority
-        vec![
-            (
-                asset::EQD.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                1,
-                AssetType::Synthetic,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::BTC.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                2,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::ETH.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                3,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::EOS.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                4,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::DOT.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                5,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::CRV.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                6,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::EQ.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                u64::MAX,
-                AssetType::Native,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::USDC.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                7,
-                AssetType::Physical,
-                true,
-                Percent::zero(),
-                Permill::one(),
-            ),
-            (
-                asset::USDT.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                8,
-                AssetType::Physical,
-                true,
-                Percent::from_rational(5u32, 10u32),
-                Permill::one(),
-            ),
-        ],
+        assets: builder.assets_genesis(),
     }
     .assimilate_storage(&mut t)
     .unwrap();
 
     eq_balances::GenesisConfig::<Test> {
-        balances: vec![
-            (
-                1,
-                vec![(1000_000_000_000_000 as Balance, asset::BTC.get_id())],
-            ),
-            (
-                2,
-                vec![(1000_000_000_000_000 as Balance, asset::BTC.get_id())],
-            ),
-        ],
+        balances: builder.balances_genesis(),
         is_transfers_enabled: true,
         is_xcm_enabled: Some(XcmMode::Xcm(false)),
     }
     .assimilate_storage(&mut t)
     .unwrap();
 
-    eq_bailsman::GenesisConfig::<Test> { bailsmen: vec![] }
-        .assimilate_storage(&mut t)
-        .unwrap();
+    eq_bailsman::GenesisConfig::<Test> {
+        bailsmen: builder.bailsmen_genesis(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
 
     t.into()
 }
@@ -22,11 +22,15 @@ use super::*;
 use crate::mock::*;
 use core::slice::Iter;
 use eq_primitives::{
-    asset, balance::BalanceGetter, map, subaccount::SubAccType, Aggregates, SignedBalance,
+    asset,
+    balance::{BalanceCheckerKind, BalanceGetter},
+    map,
+    subaccount::SubAccType,
+    Aggregates, SignedBalance,
 };
 use eq_utils::ONE_TOKEN;
 use frame_support::{assert_err, assert_noop, assert_ok, traits::Hooks};
-use sp_arithmetic::FixedI64;
+use sp_arithmetic::{FixedI64, Permill};
 use sp_runtime::FixedPointNumber;
 use std::iter::FromIterator;
 type AccountId = u64;
@@ -317,6 +321,47 @@ fn register_bailsman_with_collat_less_than_min_error() {
     });
 }
 
+#[test]
+fn bailsman_collateral_must_be_eligible_by_risk_tier() {
+    new_test_ext().execute_with(|| {
+        let account_id_1 = 0;
+        let account_id_2 = 1;
+        for currency in iterator_with_usd() {
+            set_pos_balance_with_agg_unsafe(&account_id_1, &currency, 10.0);
+            set_pos_balance_with_agg_unsafe(&account_id_2, &currency, 10.0);
+        }
+
+        assert_ok!(ModuleAssets::set_risk_tier(
+            RuntimeOrigin::root(),
+            asset::EOS,
+            asset::RiskTier::Tier3
+        ));
+        assert_err!(
+            ModuleBailsman::register_bailsman(&account_id_1),
+            Error::<Test>::CollateralNotEligible
+        );
+
+        set_pos_balance_with_agg_unsafe(&account_id_1, &asset::EOS, 0.0);
+        assert_ok!(ModuleBailsman::register_bailsman(&account_id_1));
+
+        assert_err!(
+            ModuleBalances::transfer(
+                RuntimeOrigin::signed(account_id_2),
+                asset::EOS,
+                account_id_1,
+                ONE_TOKEN
+            ),
+            Error::<Test>::CollateralNotEligible
+        );
+        assert_ok!(ModuleBalances::transfer(
+            RuntimeOrigin::signed(account_id_2),
+            asset::BTC,
+            account_id_1,
+            ONE_TOKEN
+        ));
+    });
+}
+
 #[test]
 fn register_bailsman_twice_error() {
     new_test_ext().execute_with(|| {
@@ -1242,8 +1287,12 @@ fn can_change_balance_when_margin_state_is_not_good() {
                 &ACCOUNT_ID_BAD_SUB_GOOD,
                 &vec![(asset::EQD, SignedBalance::Negative(2 * ONE_TOKEN))],
                 None,
-            ),
-            Error::<Test>::WrongMargin
+            )
+            .map_err(|e| (e.checker, e.error)),
+            (
+                BalanceCheckerKind::Bailsman,
+                DispatchError::from(Error::<Test>::WrongMargin)
+            )
         );
     });
 }
@@ -1302,8 +1351,12 @@ fn cant_change_if_margin_decreased() {
                     (asset::DOT, SignedBalance::Negative(ONE_TOKEN * 2))
                 ],
                 None,
-            ),
-            crate::Error::<Test>::WrongMargin
+            )
+            .map_err(|e| (e.checker, e.error)),
+            (
+                BalanceCheckerKind::Bailsman,
+                DispatchError::from(crate::Error::<Test>::WrongMargin)
+            )
         );
     });
 }
@@ -1360,8 +1413,12 @@ fn can_change_bailsman_has_debt() {
                 &account_id_1,
                 &vec![(asset::DOT, SignedBalance::Negative(500_000_000))],
                 None,
-            ),
-            Error::<Test>::BailsmanHasDebt
+            )
+            .map_err(|e| (e.checker, e.error)),
+            (
+                BalanceCheckerKind::Bailsman,
+                DispatchError::from(Error::<Test>::BailsmanHasDebt)
+            )
         );
     });
 }
@@ -1405,8 +1462,12 @@ fn can_change_bailsman_has_debt_decreasing_collat_when_shoud_unreg() {
                 &account_id_1,
                 &vec![(asset::DOT, SignedBalance::Negative(100_000_000))],
                 None,
-            ),
-            Error::<Test>::BailsmanHasDebt
+            )
+            .map_err(|e| (e.checker, e.error)),
+            (
+                BalanceCheckerKind::Bailsman,
+                DispatchError::from(Error::<Test>::BailsmanHasDebt)
+            )
         );
     });
 }
@@ -1443,8 +1504,12 @@ fn can_change_balance_to_distribution_account_should_fail() {
         let distr_account_id = DISTRIBUTION_ACC.into_account_truncating();
 
         assert_err!(
-            ModuleBailsman::can_change_balance(&distr_account_id, &Vec::new(), None),
-            Error::<Test>::TempBalancesTransfer
+            ModuleBailsman::can_change_balance(&distr_account_id, &Vec::new(), None)
+                .map_err(|e| (e.checker, e.error)),
+            (
+                BalanceCheckerKind::Bailsman,
+                DispatchError::from(Error::<Test>::TempBalancesTransfer)
+            )
         );
     });
 }
@@ -1468,8 +1533,12 @@ fn can_change_balance_when_temp_balance_more_than_min_temp_baance_should_fail()
         ));
 
         assert_err!(
-            ModuleBailsman::can_change_balance(&account_id, &Vec::new(), None),
-            Error::<Test>::TempBalancesNotDistributed
+            ModuleBailsman::can_change_balance(&account_id, &Vec::new(), None)
+                .map_err(|e| (e.checker, e.error)),
+            (
+                BalanceCheckerKind::Bailsman,
+                DispatchError::from(Error::<Test>::TempBalancesNotDistributed)
+            )
         );
     });
 }
@@ -1496,8 +1565,12 @@ fn can_change_balance_when_bailsman_has_debt_should_fail() {
                 &account_id,
                 &vec![(asset::EQD, SignedBalance::Negative(200))],
                 None,
-            ),
-            Error::<Test>::BailsmanHasDebt
+            )
+            .map_err(|e| (e.checker, e.error)),
+            (
+                BalanceCheckerKind::Bailsman,
+                DispatchError::from(Error::<Test>::BailsmanHasDebt)
+            )
         );
     });
 }
@@ -1730,3 +1803,316 @@ fn get_rest_from_distribution_account() {
         assert_eq!(transfers[&asset::EQD], SignedBalance::Negative(103));
     });
 }
+
+#[test]
+fn treasury_liquidity_line_draw_and_repay() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        TestPrice::set_price_mock(&asset::EQD, &FixedI64::one());
+
+        let bailsman: AccountId = 100;
+        let borrower: AccountId = 200;
+        let treasury: AccountId = TreasuryModuleId::get().into_account_truncating();
+        let temp_balances = ModuleBailsman::get_account_id();
+
+        assert_ok!(ModuleAggregates::set_usergroup(
+            &bailsman,
+            UserGroup::Bailsmen,
+            true
+        ));
+        assert_ok!(ModuleAggregates::set_usergroup(
+            &borrower,
+            UserGroup::Borrowers,
+            true
+        ));
+        ModuleBalances::make_free_balance_be(
+            &bailsman,
+            asset::EQD,
+            SignedBalance::Positive(100 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &borrower,
+            asset::EQD,
+            SignedBalance::Negative(1_000 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &treasury,
+            asset::EQD,
+            SignedBalance::Positive(1_000 * ONE_TOKEN),
+        );
+
+        let line = LiquidityLine {
+            asset: asset::EQD,
+            cap: 300 * ONE_TOKEN,
+            coverage_threshold: EqFixedU128::saturating_from_rational(1, 2),
+            rate: Permill::from_percent(10),
+        };
+        assert_ok!(ModuleBailsman::set_liquidity_line(
+            RuntimeOrigin::root(),
+            Some(line.clone())
+        ));
+        assert_eq!(
+            ModuleBailsman::coverage_ratio(),
+            Some(EqFixedU128::saturating_from_rational(1, 10))
+        );
+
+        // coverage is 0.1 and 400 EQD are needed, draw is limited by the cap
+        ModuleBailsman::on_initialize(1);
+
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury, &asset::EQD),
+            SignedBalance::Positive(700 * ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&temp_balances, &asset::EQD),
+            SignedBalance::Positive(300 * ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBailsman::liquidity_line_utilization(),
+            Some(LiquidityLineUtilization {
+                line,
+                principal: 300 * ONE_TOKEN,
+                interest: 0,
+                coverage: Some(EqFixedU128::saturating_from_rational(4, 10)),
+            })
+        );
+        assert!(System::events().iter().any(|r| r.event
+            == RuntimeEvent::ModuleBailsman(Event::LiquidityLineDrawn(
+                asset::EQD,
+                300 * ONE_TOKEN,
+                Some(EqFixedU128::saturating_from_rational(4, 10))
+            ))));
+
+        assert_noop!(
+            ModuleBailsman::set_liquidity_line(RuntimeOrigin::root(), None),
+            Error::<Test>::LiquidityLineInUse
+        );
+
+        // borrowers debt decreased, 300 USD of surplus over the threshold go back to the treasury
+        ModuleBalances::make_free_balance_be(
+            &borrower,
+            asset::EQD,
+            SignedBalance::Negative(200 * ONE_TOKEN),
+        );
+        ModuleBailsman::on_initialize(2);
+
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury, &asset::EQD),
+            SignedBalance::Positive(1_000 * ONE_TOKEN)
+        );
+        assert_eq!(ModuleBailsman::treasury_line_debt().principal, 0);
+        assert!(System::events().iter().any(|r| r.event
+            == RuntimeEvent::ModuleBailsman(Event::LiquidityLineRepaid(
+                asset::EQD,
+                300 * ONE_TOKEN,
+                0
+            ))));
+
+        assert_ok!(ModuleBailsman::set_liquidity_line(
+            RuntimeOrigin::root(),
+            None
+        ));
+        assert_eq!(ModuleBailsman::liquidity_line_utilization(), None);
+    });
+}
+
+#[test]
+fn redistribution_resumes_from_cursor_in_idle_blocks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bails_acc = ModuleBailsman::get_account_id();
+        for account_id in 0..2 {
+            for currency in iterator_with_usd() {
+                set_pos_balance_with_agg_unsafe(&account_id, &currency, 10.0);
+            }
+            assert_ok!(ModuleBailsman::register_bailsman(&account_id));
+        }
+
+        update_debt_param(&bails_acc, asset::EQD, 500.0);
+        update_collat_param(&bails_acc, asset::EQ, 510.0);
+        ModuleBailsman::on_initialize(1);
+        let distribution_id = ModuleBailsman::get_current_distribution_id();
+
+        let order: Vec<AccountId> =
+            <ModuleAggregates as Aggregates<_, _>>::iter_account(UserGroup::Bailsmen)
+                .filter(|acc| *acc < 2)
+                .collect();
+        assert_eq!(order.len(), 2);
+
+        // previous block stopped right after the first bailsman
+        RedistributionCursor::<Test>::put((distribution_id, order[0]));
+        ModuleBailsman::on_idle(1, Weight::MAX);
+
+        assert_eq!(LastDistribution::<Test>::get(order[0]), None);
+        assert_eq!(
+            LastDistribution::<Test>::get(order[1]),
+            Some(distribution_id)
+        );
+        assert_eq!(RedistributionCursor::<Test>::get(), None);
+        assert!(System::events().iter().any(|r| r.event
+            == RuntimeEvent::ModuleBailsman(Event::RedistributionCompleted(distribution_id))));
+
+        // next pass starts from the beginning and finishes the distribution
+        ModuleBailsman::on_idle(2, Weight::MAX);
+        assert_eq!(
+            LastDistribution::<Test>::get(order[0]),
+            Some(distribution_id)
+        );
+        ModuleBailsman::on_finalize(2);
+        assert_eq!(ModuleBailsman::distribution_queue().1.len(), 0);
+    });
+}
+
+#[test]
+fn exit_bailsman_settles_queue_and_unregisters() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let temp_balances = BailsmanModuleId::get().into_account_truncating();
+        // mock subaccounts manager returns account 0 as bailsman subaccount of any owner
+        let owner = 5;
+        let bailsman = 0;
+        for account_id in 0..2 {
+            for currency in iterator_with_usd() {
+                set_pos_balance_with_agg_unsafe(&account_id, &currency, 10.0);
+            }
+            assert_ok!(ModuleBailsman::register_bailsman(&account_id));
+        }
+
+        ModuleBalances::make_free_balance_be(
+            &temp_balances,
+            asset::BTC,
+            SignedBalance::Positive(2 * ONE_TOKEN),
+        );
+        ModuleBailsman::on_initialize(1);
+        let distribution_id = ModuleBailsman::get_current_distribution_id();
+
+        let queued = ModuleBailsman::queued_distributions(&bailsman).unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].id, distribution_id);
+        assert_eq!(queued[0].remaining_bailsmen, 2);
+        assert_eq!(queued[0].amounts.len(), 1);
+        let (asset, amount) = queued[0].amounts[0].clone();
+        assert_eq!(asset, asset::BTC);
+        assert!(amount.is_positive());
+
+        let btc_before = ModuleBalances::get_balance(&bailsman, &asset::BTC);
+        assert_ok!(ModuleBailsman::exit_bailsman(RuntimeOrigin::signed(owner)));
+
+        assert_eq!(
+            ModuleBalances::get_balance(&bailsman, &asset::BTC),
+            btc_before + amount
+        );
+        assert!(!ModuleAggregates::in_usergroup(
+            &bailsman,
+            UserGroup::Bailsmen
+        ));
+        assert_eq!(LastDistribution::<Test>::get(bailsman), None);
+        assert_eq!(ModuleBailsman::bailsmen_count(), 1);
+        assert_eq!(
+            ModuleBailsman::distribution_queue().1[&distribution_id].remaining_bailsmen,
+            1
+        );
+        assert!(System::events()
+            .iter()
+            .any(|r| r.event
+                == RuntimeEvent::ModuleBailsman(Event::BailsmanExited(owner, bailsman, 1))));
+
+        assert_noop!(
+            ModuleBailsman::exit_bailsman(RuntimeOrigin::signed(owner)),
+            Error::<Test>::NotBailsman
+        );
+        assert_err!(
+            ModuleBailsman::queued_distributions(&bailsman),
+            Error::<Test>::NotBailsman
+        );
+    });
+}
+
+#[test]
+fn pool_shares_follow_bailsman_net_balance() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account_id_1 = 0;
+        let account_id_2 = 3;
+        for currency in iterator_with_usd() {
+            set_pos_balance_with_agg_unsafe(&account_id_1, &currency, 10.0);
+            set_pos_balance_with_agg_unsafe(&account_id_2, &currency, 30.0);
+        }
+        let net_usd = |who: &AccountId| {
+            let DebtCollateralDiscounted {
+                debt, collateral, ..
+            } = ModuleBalances::get_debt_and_collateral(who).unwrap();
+            collateral - debt
+        };
+        assert_eq!(ModuleBailsman::pool_share(&account_id_1), None);
+
+        assert_ok!(ModuleBailsman::register_bailsman(&account_id_1));
+        let shares_1 = net_usd(&account_id_1);
+        assert!(!shares_1.is_zero());
+        assert_eq!(ModuleBailsman::shares(account_id_1), shares_1);
+        assert_eq!(ModuleBailsman::total_shares(), shares_1);
+        assert_eq!(
+            ModuleBailsman::pool_share(&account_id_1),
+            Some(EqFixedU128::one())
+        );
+        assert!(System::events().iter().any(|r| r.event
+            == RuntimeEvent::ModuleBailsman(Event::SharesMinted(account_id_1, shares_1))));
+
+        assert_ok!(ModuleBailsman::register_bailsman(&account_id_2));
+        let shares_2 = net_usd(&account_id_2);
+        assert_eq!(ModuleBailsman::total_shares(), shares_1 + shares_2);
+        assert_eq!(
+            ModuleBailsman::pool_share(&account_id_1),
+            EqFixedU128::checked_from_rational(shares_1, shares_1 + shares_2)
+        );
+
+        // shares are synced with the net balance on redistribution
+        set_pos_balance_with_agg_unsafe(&account_id_1, &asset::BTC, 5.0);
+        assert_ok!(<ModuleBailsman as BailsmanManager<_, _>>::redistribute(
+            &account_id_1
+        ));
+        let synced_1 = net_usd(&account_id_1);
+        assert!(synced_1 < shares_1);
+        assert_eq!(ModuleBailsman::shares(account_id_1), synced_1);
+        assert_eq!(ModuleBailsman::total_shares(), synced_1 + shares_2);
+        assert!(System::events().iter().any(|r| r.event
+            == RuntimeEvent::ModuleBailsman(Event::SharesBurned(
+                account_id_1,
+                shares_1 - synced_1
+            ))));
+
+        assert_ok!(ModuleBailsman::unregister_bailsman(&account_id_1));
+        assert_eq!(ModuleBailsman::shares(account_id_1), 0);
+        assert_eq!(ModuleBailsman::total_shares(), shares_2);
+        assert_eq!(
+            ModuleBailsman::pool_share(&account_id_1),
+            Some(EqFixedU128::zero())
+        );
+        assert_eq!(
+            ModuleBailsman::pool_share(&account_id_2),
+            Some(EqFixedU128::one())
+        );
+    });
+}
+
+#[test]
+fn priority_redistributions_are_limited_per_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..MaxPriorityRedistributionsPerBlock::get() {
+            assert_ok!(ModuleBailsman::take_priority_slot());
+        }
+        assert_noop!(
+            ModuleBailsman::take_priority_slot(),
+            Error::<Test>::PriorityLaneExhausted
+        );
+        assert_eq!(
+            ModuleBailsman::priority_redistributions(),
+            MaxPriorityRedistributionsPerBlock::get()
+        );
+
+        System::set_block_number(2);
+        assert_eq!(ModuleBailsman::priority_redistributions(), 0);
+        assert_ok!(ModuleBailsman::take_priority_slot());
+    });
+}
@@ -28,6 +28,9 @@ pub trait WeightInfo {
     fn redistribute_unsigned(z: u32) -> Weight;
     fn on_initialize() -> Weight;
     fn on_finalize(z: u32) -> Weight;
+    fn set_liquidity_line() -> Weight;
+    fn manage_liquidity_line() -> Weight;
+    fn exit_bailsman(z: u32) -> Weight;
 }
 
 // for tests
@@ -51,4 +54,16 @@ impl crate::WeightInfo for () {
     fn on_finalize(_z: u32) -> Weight {
         Weight::zero()
     }
+
+    fn set_liquidity_line() -> Weight {
+        Weight::zero()
+    }
+
+    fn manage_liquidity_line() -> Weight {
+        Weight::zero()
+    }
+
+    fn exit_bailsman(_z: u32) -> Weight {
+        Weight::zero()
+    }
 }
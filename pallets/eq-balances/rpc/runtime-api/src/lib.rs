@@ -21,7 +21,16 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use sp_runtime::traits::MaybeDisplay;
+use eq_primitives::{
+    asset::Asset,
+    balance::TransferCheck,
+    portfolio::AccountPortfolio,
+    state_export::{VersionedBalances, VersionedPrices},
+    AccountType,
+};
+use sp_runtime::{traits::MaybeDisplay, DispatchError};
+use sp_std::vec::Vec;
+use xcm::v3::MultiLocation;
 
 sp_api::decl_runtime_apis! {
     pub trait EqBalancesApi<Balance, AccountId>
@@ -32,5 +41,28 @@ sp_api::decl_runtime_apis! {
         fn wallet_balance_in_usd(account_id: AccountId) -> Option<Balance>;
 
         fn portfolio_balance_in_usd(account_id: AccountId) -> Option<Balance>;
+
+        /// Dry run of `transfer` extrinsic: returns which check would reject the transfer
+        fn can_transfer(from: AccountId, to: AccountId, asset: Asset, amount: Balance) -> TransferCheck;
+
+        /// Balances, locks, subaccounts, DEX orders, lending deposits and vestings of an account
+        fn account_portfolio(account_id: AccountId) -> AccountPortfolio<AccountId, Balance>;
+
+        /// Dry run of native XCM transfer of `asset` to `to`: returns location of the
+        /// beneficiary or the error which would reject the transfer
+        fn validate_destination(asset: Asset, to: AccountType) -> Result<MultiLocation, DispatchError>;
+    }
+
+    /// Compact state snapshots with stable versioned SCALE layout,
+    /// intended for consumers on other chains (XCM queries, light client proofs)
+    pub trait EqStateExportApi<AccountId>
+    where
+        AccountId: Codec
+    {
+        /// Non-zero balances of an account
+        fn balances(account_id: AccountId) -> VersionedBalances<AccountId>;
+
+        /// Latest prices of `assets` with their timestamps, all priced assets if `assets` is empty
+        fn prices(assets: Vec<Asset>) -> VersionedPrices;
     }
 }
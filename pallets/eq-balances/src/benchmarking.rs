@@ -25,6 +25,7 @@ use eq_primitives::asset;
 use eq_primitives::PriceSetter;
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 // use frame_support::traits::Hooks;
+use frame_support::traits::EnsureOrigin;
 use frame_system::RawOrigin;
 use sp_runtime::traits::One;
 use sp_runtime::Percent;
@@ -172,7 +173,7 @@ benchmarks! {
     xcm_transfer_native {
         IsXcmTransfersEnabled::<T>::put(XcmMode::Xcm(true));
         let account_id = account("account", 0, SEED);
-        let recepient: [u8; 32] = [0;32];
+        let recepient: [u8; 32] = [1;32];
 
         crate::Pallet::<T>::deposit_creating(
             &account_id,
@@ -224,4 +225,353 @@ benchmarks! {
         ).unwrap();
         let amount = (50_000_000_000u128).try_into().unwrap_or_default();
     }: _(RawOrigin::Signed(account_id), asset::ACA, amount, to, XcmTransferDealWithFee::SovereignAccWillPay)
+
+    set_settlement_account {
+        let settler: T::AccountId = account("settler", 0, SEED);
+    }: _(RawOrigin::Root, settler.clone(), true)
+    verify {
+        assert!(SettlementAccounts::<T>::contains_key(&settler));
+    }
+
+    set_xcm_frozen {
+    }: set_xcm_asset_frozen(RawOrigin::Root, asset::DOT, true)
+    verify {
+        assert!(XcmFrozenAssets::<T>::contains_key(asset::DOT));
+    }
+
+    set_xcm_fee {
+        let params = XcmFeeParams {
+            fee_asset: asset::DOT,
+            base_xcm_weight: 1_000_000_000,
+            units_per_second: 1_000_000_000_000,
+        };
+    }: _(RawOrigin::Root, MultiLocation::parent(), asset::DOT, Some(params))
+    verify {
+        assert_eq!(XcmFees::<T>::get(MultiLocation::parent(), asset::DOT), Some(params));
+    }
+
+    set_asset_hub_asset {
+        let asset_hub_asset = AssetHubAsset {
+            general_index: 1984,
+            fee_mode: AssetHubFeeMode::RelayToken(asset::DOT),
+        };
+        let _ = eq_assets::Pallet::<T>::do_add_asset(
+            asset::USDT,
+            EqFixedU128::zero(),
+            FixedI64::zero(),
+            Permill::zero(),
+            Permill::zero(),
+            eq_primitives::asset::AssetXcmData::OtherReserved(
+                eq_primitives::asset::OtherReservedData {
+                    multi_location: asset_hub_asset.multi_location(),
+                    decimals: 6,
+                },
+            ),
+            Permill::zero(),
+            0,
+            eq_primitives::asset::AssetType::Physical,
+            true,
+            Percent::zero(),
+            Permill::one(),
+            vec![],
+        );
+    }: _(RawOrigin::Root, asset::USDT, Some(asset_hub_asset))
+    verify {
+        assert_eq!(AssetHubAssets::<T>::get(asset::USDT), Some(asset_hub_asset));
+    }
+
+    process_xcm_retries {
+        let a in 1..5;
+
+        let retry_account = crate::Pallet::<T>::xcm_retry_account();
+        let held: T::Balance = TRANSFER.try_into()
+            .map_err(|_| "balance conversion error")
+            .unwrap();
+        crate::Pallet::<T>::deposit_creating(
+            &retry_account,
+            asset::EQ,
+            held * a.into(),
+            true,
+            None
+        ).unwrap();
+        let now = frame_system::Pallet::<T>::block_number();
+        for i in 0..a {
+            let who: T::AccountId = account("who", i, SEED);
+            XcmRetryQueue::<T>::insert(
+                [i as u8; 32],
+                XcmRetry {
+                    who,
+                    transfer: XcmRetryTransfer::Transfer {
+                        transfer: (asset::EQ, held),
+                        fee: (asset::EQ, T::Balance::zero()),
+                    },
+                    destination: XcmDestination::Common(MultiLocation::parent()),
+                    held: vec![(asset::EQ, held)].into(),
+                    attempts: 0,
+                    next_retry: now,
+                    expiry: now,
+                },
+            );
+        }
+    }: {
+        crate::Pallet::<T>::process_xcm_retries(now);
+    }
+    verify {
+        assert_eq!(XcmRetryQueue::<T>::iter().count(), 0);
+    }
+
+    transfer_with_timelock {
+        crate::Pallet::<T>::enable_transfers(RawOrigin::Root.into())
+            .unwrap();
+
+        let price_setter: T::AccountId = account("price_setter", 0, SEED);
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone())
+            .unwrap();
+        crate::Pallet::<T>::deposit_creating(
+            &price_setter,
+            asset::EQ,
+            900_000_000_000_u128.try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap(),
+            true,
+            None
+        ).unwrap();
+        for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
+            <eq_oracle::Pallet::<T> as PriceSetter<T::AccountId>>::set_price(
+                price_setter.clone(),
+                curr,
+                FixedI64::one()
+            ).unwrap();
+        }
+
+        let caller: T::AccountId = whitelisted_caller();
+        crate::Pallet::<T>::deposit_creating(
+            &caller,
+            asset::EQ,
+            BUDGET.try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap(),
+            true,
+            None
+        ).unwrap();
+
+        let to: T::AccountId = account("to", 0, SEED);
+        let unlock_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+    }: _(RawOrigin::Signed(caller), to, asset::EQ, TRANSFER.try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap(), unlock_at)
+    verify {
+        assert!(Timelocks::<T>::contains_key(0));
+    }
+
+    cancel_timelock {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount: T::Balance = TRANSFER.try_into()
+            .map_err(|_| "balance conversion error")
+            .unwrap();
+        crate::Pallet::<T>::deposit_creating(
+            &crate::Pallet::<T>::timelock_account(),
+            asset::EQ,
+            amount,
+            true,
+            None
+        ).unwrap();
+        let now = frame_system::Pallet::<T>::block_number();
+        Timelocks::<T>::insert(
+            0,
+            TimelockedTransfer {
+                from: caller.clone(),
+                to: account("to", 0, SEED),
+                asset: asset::EQ,
+                amount,
+                unlock_at: now + 10u32.into(),
+                cancel_until: now,
+            },
+        );
+    }: _(RawOrigin::Signed(caller), 0)
+    verify {
+        assert!(!Timelocks::<T>::contains_key(0));
+    }
+
+    set_timelock_dispute_window {
+    }: _(RawOrigin::Root, 100u32.into())
+    verify {
+        assert_eq!(TimelockDisputeWindow::<T>::get(), 100u32.into());
+    }
+
+    process_timelocks {
+        let a in 1..10;
+
+        let amount: T::Balance = TRANSFER.try_into()
+            .map_err(|_| "balance conversion error")
+            .unwrap();
+        crate::Pallet::<T>::deposit_creating(
+            &crate::Pallet::<T>::timelock_account(),
+            asset::EQ,
+            amount * a.into(),
+            true,
+            None
+        ).unwrap();
+        let now = frame_system::Pallet::<T>::block_number();
+        for i in 0..a {
+            Timelocks::<T>::insert(
+                i as u64,
+                TimelockedTransfer {
+                    from: account("from", i, SEED),
+                    to: account("to", i, SEED),
+                    asset: asset::EQ,
+                    amount,
+                    unlock_at: now,
+                    cancel_until: now,
+                },
+            );
+        }
+    }: {
+        crate::Pallet::<T>::process_timelocks(now);
+    }
+    verify {
+        assert_eq!(Timelocks::<T>::iter().count(), 0);
+    }
+
+    refund_xcm_surplus {
+        let who: T::AccountId = account("who", 0, SEED);
+        let origin = T::XcmResponseOrigin::try_successful_origin()
+            .map_err(|_| "no successful response origin")?;
+        let responder = T::XcmResponseOrigin::ensure_origin(origin.clone()).unwrap();
+        let (location, decimals, _) = crate::Pallet::<T>::xcm_data(&asset::DOT).unwrap();
+        let fee: T::Balance = TRANSFER.try_into()
+            .map_err(|_| "balance conversion error")
+            .unwrap();
+        XcmRefunds::<T>::insert(
+            0,
+            XcmRefund {
+                who: who.clone(),
+                asset: asset::DOT,
+                fee,
+                responder,
+                message_hash: [0; 32],
+                expires_at: frame_system::Pallet::<T>::block_number(),
+            },
+        );
+        let response = Response::Assets(
+            MultiAsset {
+                id: Concrete(location),
+                fun: Fungible(balance_into_xcm(TRANSFER, decimals).unwrap()),
+            }
+            .into(),
+        );
+    }: _<T::RuntimeOrigin>(origin, 0, response)
+    verify {
+        assert!(!XcmRefunds::<T>::contains_key(0));
+        assert_eq!(crate::Pallet::<T>::free_balance(&who, asset::DOT), fee);
+    }
+
+    clear_expired_xcm_refund {
+        let caller: T::AccountId = whitelisted_caller();
+        let who: T::AccountId = account("who", 0, SEED);
+        let now = frame_system::Pallet::<T>::block_number();
+        XcmRefunds::<T>::insert(
+            0,
+            XcmRefund {
+                who,
+                asset: asset::DOT,
+                fee: T::Balance::zero(),
+                responder: MultiLocation::parent(),
+                message_hash: [0; 32],
+                expires_at: now,
+            },
+        );
+        frame_system::Pallet::<T>::set_block_number(now + One::one());
+    }: _(RawOrigin::Signed(caller), 0)
+    verify {
+        assert!(!XcmRefunds::<T>::contains_key(0));
+    }
+
+    set_asset_flags {
+        let who: T::AccountId = account("who", 0, SEED);
+        frame_system::Pallet::<T>::inc_providers(&who);
+        let flags = AssetFlags {
+            frozen: true,
+            ..Default::default()
+        };
+    }: _(RawOrigin::Root, who.clone(), asset::DOT, flags)
+    verify {
+        assert_eq!(T::AccountStore::get(&who).flags(&asset::DOT), flags);
+    }
+
+    authorize_settler {
+        let who: T::AccountId = whitelisted_caller();
+        let settler: T::AccountId = account("settler", 0, SEED);
+    }: _(RawOrigin::Signed(who.clone()), settler.clone(), true)
+    verify {
+        assert!(SettlerAuthorizations::<T>::contains_key(&who, &settler));
+    }
+
+    settle {
+        let a in 1..100;
+
+        crate::Pallet::<T>::enable_transfers(RawOrigin::Root.into())
+            .unwrap();
+
+        let price_setter: T::AccountId = account("price_setter", 0, SEED);
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone())
+            .unwrap();
+        crate::Pallet::<T>::deposit_creating(
+            &price_setter,
+            asset::EQ,
+            900_000_000_000_u128.try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap(),
+            true,
+            None
+        ).unwrap();
+        for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
+            <eq_oracle::Pallet::<T> as PriceSetter<T::AccountId>>::set_price(
+                price_setter.clone(),
+                curr,
+                FixedI64::one()
+            ).unwrap();
+        }
+
+        let settler: T::AccountId = account("settler", 0, SEED);
+        SettlementAccounts::<T>::insert(&settler, ());
+
+        let transfer: T::Balance = TRANSFER.try_into()
+            .map_err(|_| "balance conversion error")
+            .unwrap();
+        let mut deltas = vec![];
+        for i in 0..a {
+            let from: T::AccountId = account("from", i, SEED);
+            crate::Pallet::<T>::deposit_creating(
+                &from,
+                asset::EQ,
+                BUDGET.try_into()
+                    .map_err(|_| "balance conversion error")
+                    .unwrap(),
+                true,
+                None
+            ).unwrap();
+            SettlerAuthorizations::<T>::insert(&from, &settler, ());
+            deltas.push((from, asset::EQ, SignedBalance::Negative(transfer)));
+        }
+        let to: T::AccountId = account("to", 0, SEED);
+        crate::Pallet::<T>::deposit_creating(
+            &to,
+            asset::EQ,
+            BUDGET.try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap(),
+            true,
+            None
+        ).unwrap();
+        deltas.push((to.clone(), asset::EQ, SignedBalance::Positive(transfer * a.into())));
+    }: _(RawOrigin::Signed(settler), deltas)
+    verify {
+        assert_eq!(
+            crate::Pallet::<T>::free_balance(&to, asset::EQ),
+            transfer * a.into() + BUDGET.try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap()
+        );
+    }
 }
@@ -34,23 +34,25 @@
 #![forbid(unsafe_code)]
 #![deny(warnings)]
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 pub use eq_primitives::imbalances::{NegativeImbalance, PositiveImbalance};
 use eq_primitives::{
     asset::{Asset, AssetGetter, GLMR},
     balance::{
-        AccountData, BalanceChecker, BalanceGetter, BalanceRemover, DebtCollateralDiscounted,
-        DepositReason, EqCurrency, LockGetter, WithdrawReason, XcmDestination,
-        XcmTransferDealWithFee,
+        AccountData, AssetFlags, AssetLock, AssetLocks, BalanceCheckError, BalanceChecker,
+        BalanceCheckerKind, BalanceGetter, BalanceRemover, DebtCollateralDiscounted, DepositReason,
+        EqCurrency, EqNamedReservableCurrency, LockGetter, LockOwner, ReserveIdentifier,
+        TransferCheck, TransferSide, WithdrawReason, XcmDestination, XcmTransferDealWithFee,
     },
     balance_number::EqFixedU128,
     signed_balance::{SignedBalance, SignedBalance::*},
     str_asset,
     subaccount::{SubAccType, SubaccountsManager},
-    xcm_origins::dot::PARACHAIN_MOONBEAM,
-    AccountRefCounter, AccountRefCounts, AccountType, Aggregates, BailsmanManager,
-    EqPalletAccountInitializer, OrderAggregates, PalletAccountInitializer, PriceGetter,
-    TransferReason, UpdateTimeManager, UserGroup, XcmMode,
+    xcm_origins::dot::{PARACHAIN_MOONBEAM, PARACHAIN_STATEMINT},
+    AccountMigration, AccountRefCounter, AccountRefCounts, AccountType, Aggregates,
+    BailsmanManager, EqPalletAccountInitializer, OrderAggregates, PalletAccountInitializer,
+    PriceGetter, SteppedMigration, TransferReason, UpdateTimeManager, UserGroup, XcmDepositFreeze,
+    XcmMode,
 };
 use eq_utils::{
     balance_from_xcm, balance_into_xcm, balance_swap_decimals, eq_ensure, vec_map::VecMap,
@@ -61,41 +63,260 @@ use frame_support::{
     ensure,
     storage::PrefixIterator,
     traits::{
-        BalanceStatus, ExistenceRequirement, Get, Imbalance, LockIdentifier, StoredMap, UnixTime,
-        WithdrawReasons,
+        BalanceStatus, Contains, ExistenceRequirement, Get, Imbalance, LockIdentifier, StoredMap,
+        UnixTime, WithdrawReasons,
     },
+    weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight},
     PalletId,
 };
 pub use pallet::*;
+use scale_info::TypeInfo;
 #[allow(unused_imports)]
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
-    traits::{AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, Convert, Saturating, Zero},
-    ArithmeticError, DispatchResult, FixedPointNumber, TransactionOutcome,
+    traits::{
+        AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Convert, Saturating,
+        Zero,
+    },
+    ArithmeticError, DispatchResult, FixedPointNumber, RuntimeDebug, TransactionOutcome,
 };
 use sp_std::{
     collections::btree_map::BTreeMap,
     convert::{TryFrom, TryInto},
     fmt::Debug,
+    marker::PhantomData,
     prelude::*,
 };
 pub use weights::WeightInfo;
 use xcm::v3::{
-    AssetId::Concrete, Fungibility::Fungible, Instruction::*, InteriorMultiLocation, MultiAsset,
-    MultiLocation, SendResult, SendXcm, WeightLimit, WildMultiAsset::*, Xcm,
+    AssetId::Concrete,
+    Fungibility::Fungible,
+    Instruction::*,
+    InteriorMultiLocation,
+    Junction::{GeneralIndex, PalletInstance, Parachain},
+    Junctions::X3,
+    MultiAsset, MultiLocation, QueryId, QueryResponseInfo, Response, SendError, SendXcm,
+    Weight as XcmWeight, WeightLimit,
+    WildMultiAsset::*,
+    Xcm, XcmHash,
 };
 
 pub mod benchmarking;
 pub mod locked_balance_checker;
+pub mod migrations;
 mod mock;
 mod tests;
 pub mod weights;
 mod xcm_impl;
 mod xcm_impl_old;
 
+/// Parameters to repeat XCM transfer with
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum XcmRetryTransfer<Balance> {
+    /// `do_xcm_transfer` parameters
+    Transfer {
+        transfer: (Asset, Balance),
+        fee: (Asset, Balance),
+    },
+    /// `do_xcm_transfer_old` parameters
+    TransferOld {
+        asset: Asset,
+        amount: Balance,
+        deal_with_fee: XcmTransferDealWithFee,
+    },
+}
+
+/// XCM transfer that failed to send, funds are held on `Pallet::xcm_retry_account`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct XcmRetry<AccountId, Balance, BlockNumber> {
+    /// Sender of the transfer, receives held funds back after `expiry`
+    pub who: AccountId,
+    /// Transfer parameters
+    pub transfer: XcmRetryTransfer<Balance>,
+    /// Transfer destination
+    pub destination: XcmDestination,
+    /// Funds taken from `who`
+    pub held: VecMap<Asset, Balance>,
+    /// Number of failed retries
+    pub attempts: u32,
+    /// Block of the next retry
+    pub next_retry: BlockNumber,
+    /// Block after which held funds are refunded
+    pub expiry: BlockNumber,
+}
+
+/// XCM transfer waiting for the report of unspent fee from destination chain
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct XcmRefund<AccountId, Balance, BlockNumber> {
+    /// Sender of the transfer, receives unspent fee
+    pub who: AccountId,
+    /// Fee asset
+    pub asset: Asset,
+    /// Paid fee, refund can't exceed it
+    pub fee: Balance,
+    /// Destination chain, responder of the query
+    pub responder: MultiLocation,
+    /// Hash of the sent message
+    pub message_hash: XcmHash,
+    /// Block after which the query is considered expired
+    pub expires_at: BlockNumber,
+}
+
+/// Registers XCM queries which responses are dispatched as calls of this pallet
+pub trait XcmRefundQueryHandler<T: Config> {
+    /// Registers query expecting response from `responder` to message sent by
+    /// `match_querier`. `notify` is dispatched with query id and response.
+    /// Returns `None` if refunds are not tracked.
+    fn new_notify_query(
+        responder: MultiLocation,
+        notify: Call<T>,
+        timeout: T::BlockNumber,
+        match_querier: MultiLocation,
+    ) -> Option<QueryId>;
+}
+
+impl<T: Config> XcmRefundQueryHandler<T> for () {
+    fn new_notify_query(
+        _responder: MultiLocation,
+        _notify: Call<T>,
+        _timeout: T::BlockNumber,
+        _match_querier: MultiLocation,
+    ) -> Option<QueryId> {
+        None
+    }
+}
+
+/// Transfer held on `Pallet::timelock_account` until `unlock_at`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct TimelockedTransfer<AccountId, Balance, BlockNumber> {
+    /// Sender, may cancel the transfer until `cancel_until`
+    pub from: AccountId,
+    /// Recipient of funds after `unlock_at`
+    pub to: AccountId,
+    /// Transferred asset
+    pub asset: Asset,
+    /// Transferred amount
+    pub amount: Balance,
+    /// Block from which funds are released to `to`
+    pub unlock_at: BlockNumber,
+    /// End of dispute window, last block when the transfer may be cancelled
+    pub cancel_until: BlockNumber,
+}
+
+/// Fee formula of XCM execution on destination chain
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct XcmFeeParams {
+    /// Asset the fee is paid in
+    pub fee_asset: Asset,
+    /// Weight of single XCM instruction on destination chain
+    pub base_xcm_weight: u64,
+    /// Fee for a second of execution in `fee_asset` XCM units
+    pub units_per_second: XcmBalance,
+}
+
+impl XcmFeeParams {
+    /// Fee for `instructions` executed on destination, doubled the same way
+    /// as `eq_xcm::fees::XcmToFee` does
+    pub fn fee(&self, instructions: usize) -> XcmBalance {
+        let weight = (instructions as u128).saturating_mul(self.base_xcm_weight as u128);
+        weight_to_fee(weight, self.units_per_second).saturating_mul(2)
+    }
+}
+
+/// Index of `Assets` pallet in Asset Hub runtime
+pub const ASSET_HUB_ASSETS_PALLET: u8 = 50;
+
+/// How fee of XCM sent to Asset Hub with mapped asset is paid
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum AssetHubFeeMode {
+    /// Fee is paid in relay chain token by Asset Hub fee formula
+    RelayToken(Asset),
+    /// Asset is sufficient on Asset Hub and pays its own fee, in XCM units
+    /// per second. The same price is charged for inbound XCM execution.
+    Sufficient(XcmBalance),
+}
+
+/// Asset of Asset Hub `Assets` pallet mapped to local asset
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AssetHubAsset {
+    /// `GeneralIndex` of the asset on Asset Hub
+    pub general_index: u128,
+    /// Fee of XCM sent to Asset Hub with the asset
+    pub fee_mode: AssetHubFeeMode,
+}
+
+impl AssetHubAsset {
+    /// Location of the asset as seen from a sibling parachain
+    pub fn multi_location(&self) -> MultiLocation {
+        MultiLocation {
+            parents: 1,
+            interior: X3(
+                Parachain(1000),
+                PalletInstance(ASSET_HUB_ASSETS_PALLET),
+                GeneralIndex(self.general_index),
+            ),
+        }
+    }
+
+    /// Fee of executing `xcm` on Asset Hub: fee asset and amount in XCM units
+    pub fn fee(&self, asset: Asset, xcm: &Xcm<()>) -> (Asset, XcmBalance) {
+        use eq_xcm::fees::statemint;
+
+        match self.fee_mode {
+            AssetHubFeeMode::RelayToken(fee_asset) => {
+                (fee_asset, statemint::XcmToFee::convert(xcm))
+            }
+            AssetHubFeeMode::Sufficient(units_per_second) => {
+                let params = XcmFeeParams {
+                    fee_asset: asset,
+                    base_xcm_weight: statemint::BaseXcmWeight::get().ref_time(),
+                    units_per_second,
+                };
+                (asset, params.fee(xcm.len()))
+            }
+        }
+    }
+}
+
+fn weight_to_fee(ref_time: u128, units_per_second: XcmBalance) -> XcmBalance {
+    units_per_second.saturating_mul(ref_time) / WEIGHT_REF_TIME_PER_SECOND as u128
+}
+
+/// `FallbackWeightToFee` for `EqTrader`: reads `XcmWeightFees` first, then
+/// price of sufficient Asset Hub assets, and uses `Fallback` for the rest
+pub struct XcmWeightToFee<T, Fallback>(PhantomData<(T, Fallback)>);
+impl<T, Fallback> Convert<(Asset, XcmWeight), Option<XcmBalance>> for XcmWeightToFee<T, Fallback>
+where
+    T: Config,
+    Fallback: Convert<(Asset, XcmWeight), Option<XcmBalance>>,
+{
+    fn convert((asset, weight): (Asset, XcmWeight)) -> Option<XcmBalance> {
+        match XcmWeightFees::<T>::get(asset) {
+            Some(units_per_second) => {
+                Some(weight_to_fee(weight.ref_time() as u128, units_per_second))
+            }
+            None => match AssetHubAssets::<T>::get(asset).map(|a| a.fee_mode) {
+                Some(AssetHubFeeMode::Sufficient(units_per_second)) => {
+                    Some(weight_to_fee(weight.ref_time() as u128, units_per_second))
+                }
+                _ => Fallback::convert((asset, weight)),
+            },
+        }
+    }
+}
+
 frame_support::parameter_types! {
     pub const MaxLocks: u32 = 10;
+    pub const MaxReserves: u32 = 50;
+    /// Blocks before the first retry of failed XCM transfer, doubled after each failed retry
+    pub const XcmRetryDelay: u32 = 10;
+    /// Blocks after which funds of failed XCM transfer are refunded to the sender
+    pub const XcmRetryExpiry: u32 = 7_200;
+    /// Max number of failed XCM transfers processed in a block
+    pub const MaxXcmRetriesPerBlock: u32 = 5;
+    /// Max number of timelocked transfers released in a block
+    pub const MaxTimelockReleasesPerBlock: u32 = 10;
 }
 
 #[frame_support::pallet]
@@ -106,8 +327,12 @@ pub mod pallet {
     use frame_system::pallet_prelude::*;
     use sp_runtime::FixedPointOperand;
 
+    /// Storage version: 1 - locks on arbitrary assets with expiry and owner
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -190,6 +415,15 @@ pub mod pallet {
         type ParachainId: Get<eq_xcm::ParaId>;
         /// Timestamp provider
         type UnixTime: UnixTime;
+        /// Registers queries for unspent fee reports of sent XCM transfers
+        type XcmRefundQueryHandler: XcmRefundQueryHandler<Self>;
+        /// Origin of XCM query responses, returns responder location
+        type XcmResponseOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+        /// Number of blocks to wait for unspent fee report
+        #[pallet::constant]
+        type XcmRefundTimeout: Get<Self::BlockNumber>;
+        /// Owners of locks whose pallets move their state in account migration
+        type MigratableLockOwners: Contains<LockOwner>;
     }
 
     #[pallet::call]
@@ -308,7 +542,8 @@ pub mod pallet {
         ///
         /// `asset` - asset to transfer;
         /// `amount` - amount to transfer;
-        /// `to` - recipient account on target chain.
+        /// `to` - recipient account on target chain, must be non-zero and match the account
+        /// format of the chain, see `EqBalancesApi::validate_destination`.
         /// Will be deprecated, use `transfer_xcm_native` instead.
         #[pallet::call_index(6)]
         #[pallet::weight(T::WeightInfo::xcm_transfer_native())]
@@ -431,10 +666,397 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Allows or forbids account `who` to submit netted settlements
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::set_settlement_account())]
+        pub fn set_settlement_account(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            allowed: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::ToggleTransferOrigin::ensure_origin(origin)?;
+
+            if allowed {
+                SettlementAccounts::<T>::insert(&who, ());
+            } else {
+                SettlementAccounts::<T>::remove(&who);
+            }
+            Ok(().into())
+        }
+
+        /// Applies netted balance changes `deltas` of several accounts at once.
+        /// Changes of every asset must sum up to zero. Balance checks are made
+        /// once per account for all of its changes. Only for `SettlementAccounts`,
+        /// accounts with decreased balances must have authorized the settler.
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::settle(deltas.len() as u32))]
+        pub fn settle(
+            origin: OriginFor<T>,
+            deltas: Vec<(T::AccountId, Asset, SignedBalance<T::Balance>)>,
+        ) -> DispatchResultWithPostInfo {
+            let settler = ensure_signed(origin)?;
+            ensure!(
+                SettlementAccounts::<T>::contains_key(&settler),
+                Error::<T>::NotSettlementAccount
+            );
+
+            let accounts = Self::do_settle(&settler, deltas)?;
+
+            Self::deposit_event(Event::Settlement(settler, accounts));
+            Ok(().into())
+        }
+
+        /// Freezes or unfreezes inbound XCM deposits of `asset`.
+        /// Deposits of a frozen asset fail and received funds are trapped.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::set_xcm_frozen())]
+        pub fn set_xcm_asset_frozen(
+            origin: OriginFor<T>,
+            asset: Asset,
+            frozen: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::ToggleTransferOrigin::ensure_origin(origin)?;
+
+            if frozen {
+                XcmFrozenAssets::<T>::insert(asset, ());
+            } else {
+                XcmFrozenAssets::<T>::remove(asset);
+            }
+            Ok(().into())
+        }
+
+        /// Freezes or unfreezes all inbound XCM deposits from `location`.
+        /// Deposits of a frozen origin fail and received funds are trapped.
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::set_xcm_frozen())]
+        pub fn set_xcm_origin_frozen(
+            origin: OriginFor<T>,
+            location: MultiLocation,
+            frozen: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::ToggleTransferOrigin::ensure_origin(origin)?;
+
+            if frozen {
+                XcmFrozenOrigins::<T>::insert(location, ());
+            } else {
+                XcmFrozenOrigins::<T>::remove(location);
+            }
+            Ok(().into())
+        }
+
+        /// Sets or removes fee formula of XCM sent to `destination` with `asset`.
+        /// Without an entry the fee is computed by `T::XcmToFee`.
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::set_xcm_fee())]
+        pub fn set_xcm_fee(
+            origin: OriginFor<T>,
+            destination: MultiLocation,
+            asset: Asset,
+            params: Option<XcmFeeParams>,
+        ) -> DispatchResultWithPostInfo {
+            T::ToggleTransferOrigin::ensure_origin(origin)?;
+
+            XcmFees::<T>::set(&destination, asset, params);
+            Self::deposit_event(Event::XcmFeeSet(destination, asset, params));
+            Ok(().into())
+        }
+
+        /// Sets or removes price of inbound XCM execution paid with `asset`,
+        /// in `asset` XCM units per second. Without an entry the runtime
+        /// fallback conversion is used.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::set_xcm_fee())]
+        pub fn set_xcm_weight_fee(
+            origin: OriginFor<T>,
+            asset: Asset,
+            units_per_second: Option<XcmBalance>,
+        ) -> DispatchResultWithPostInfo {
+            T::ToggleTransferOrigin::ensure_origin(origin)?;
+
+            XcmWeightFees::<T>::set(asset, units_per_second);
+            Self::deposit_event(Event::XcmWeightFeeSet(asset, units_per_second));
+            Ok(().into())
+        }
+
+        /// Maps `asset` to Asset Hub asset or removes the mapping.
+        /// XCM location of `asset` must point to the Asset Hub asset.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::set_asset_hub_asset())]
+        pub fn set_asset_hub_asset(
+            origin: OriginFor<T>,
+            asset: Asset,
+            asset_hub_asset: Option<AssetHubAsset>,
+        ) -> DispatchResultWithPostInfo {
+            T::ToggleTransferOrigin::ensure_origin(origin)?;
+
+            if let Some(asset_hub_asset) = asset_hub_asset {
+                let (multi_location, _, _) = Self::xcm_data(&asset)?;
+                eq_ensure!(
+                    multi_location == asset_hub_asset.multi_location(),
+                    Error::<T>::AssetHubLocationMismatch,
+                    target: "eq_balances",
+                    "{}:{}. Asset location doesn't match Asset Hub asset. Asset: {:?}, location: {:?}, general index: {:?}.",
+                    file!(),
+                    line!(),
+                    str_asset!(asset),
+                    multi_location,
+                    asset_hub_asset.general_index
+                );
+            }
+
+            AssetHubAssets::<T>::set(asset, asset_hub_asset);
+            Self::deposit_event(Event::AssetHubAssetSet(asset, asset_hub_asset));
+            Ok(().into())
+        }
+
+        /// Transfers `amount` of `asset` to escrow, funds are released to `to`
+        /// at `unlock_at` block. Sender may cancel the transfer during
+        /// `TimelockDisputeWindow` blocks.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::transfer_with_timelock())]
+        pub fn transfer_with_timelock(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            asset: Asset,
+            amount: T::Balance,
+            unlock_at: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+
+            Self::ensure_transfers_enabled(&asset, amount)?;
+            ensure!(
+                Self::is_not_subaccount(&to),
+                Error::<T>::TransferToSubaccount
+            );
+            let now = frame_system::Pallet::<T>::block_number();
+            eq_ensure!(
+                unlock_at > now,
+                Error::<T>::TimelockInPast,
+                target: "eq_balances",
+                "{}:{}. Unlock block is not in the future. Unlock at: {:?}, now: {:?}.",
+                file!(),
+                line!(),
+                unlock_at,
+                now
+            );
+
+            let timelock_account = Self::timelock_account();
+            let timelock_account_info = frame_system::Pallet::<T>::account(&timelock_account);
+            if timelock_account_info.providers == timelock_account_info.consumers {
+                EqPalletAccountInitializer::<T>::initialize(&timelock_account);
+            }
+            Self::currency_transfer(
+                &from,
+                &timelock_account,
+                asset,
+                amount,
+                ExistenceRequirement::KeepAlive,
+                TransferReason::Timelock,
+                true,
+            )?;
+
+            let id = NextTimelockId::<T>::mutate(|id| {
+                let current = *id;
+                *id = id.saturating_add(1);
+                current
+            });
+            Timelocks::<T>::insert(
+                id,
+                TimelockedTransfer {
+                    from: from.clone(),
+                    to: to.clone(),
+                    asset,
+                    amount,
+                    unlock_at,
+                    cancel_until: now.saturating_add(TimelockDisputeWindow::<T>::get()),
+                },
+            );
+            Self::deposit_event(Event::TimelockCreated(
+                id, from, to, asset, amount, unlock_at,
+            ));
+            Ok(().into())
+        }
+
+        /// Returns funds of timelocked transfer `id` to the sender.
+        /// Allowed until the dispute window of the transfer ends.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::cancel_timelock())]
+        pub fn cancel_timelock(origin: OriginFor<T>, id: u64) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let timelock = Timelocks::<T>::get(id).ok_or(Error::<T>::TimelockNotFound)?;
+            ensure!(timelock.from == who, Error::<T>::TimelockNotSender);
+            let now = frame_system::Pallet::<T>::block_number();
+            eq_ensure!(
+                now <= timelock.cancel_until,
+                Error::<T>::TimelockDisputeWindowEnded,
+                target: "eq_balances",
+                "{}:{}. Dispute window ended. Id: {:?}, cancel until: {:?}, now: {:?}.",
+                file!(),
+                line!(),
+                id,
+                timelock.cancel_until,
+                now
+            );
+
+            Self::currency_transfer(
+                &Self::timelock_account(),
+                &who,
+                timelock.asset,
+                timelock.amount,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::Timelock,
+                false,
+            )?;
+            Timelocks::<T>::remove(id);
+            Self::deposit_event(Event::TimelockCancelled(id));
+            Ok(().into())
+        }
+
+        /// Sets number of blocks after creation of timelocked transfer
+        /// during which the sender may cancel it
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::set_timelock_dispute_window())]
+        pub fn set_timelock_dispute_window(
+            origin: OriginFor<T>,
+            window: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            T::ToggleTransferOrigin::ensure_origin(origin)?;
+
+            TimelockDisputeWindow::<T>::put(window);
+            Self::deposit_event(Event::TimelockDisputeWindowSet(window));
+            Ok(().into())
+        }
+
+        /// Credits unspent fee of XCM transfer reported by destination chain to the sender
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::refund_xcm_surplus())]
+        pub fn refund_xcm_surplus(
+            origin: OriginFor<T>,
+            query_id: QueryId,
+            response: Response,
+        ) -> DispatchResultWithPostInfo {
+            let responder = T::XcmResponseOrigin::ensure_origin(origin)?;
+
+            let refund = XcmRefunds::<T>::get(query_id).ok_or(Error::<T>::XcmUnknownRefund)?;
+            eq_ensure!(
+                refund.responder == responder,
+                Error::<T>::XcmUnexpectedResponder,
+                target: "eq_balances",
+                "{}:{}. Unexpected responder. Query: {:?}, expected: {:?}, actual: {:?}",
+                file!(),
+                line!(),
+                query_id,
+                refund.responder,
+                responder,
+            );
+            let assets = match response {
+                Response::Assets(assets) => assets,
+                _ => return Err(Error::<T>::XcmUnexpectedResponse.into()),
+            };
+
+            let (location, decimals, _) = Self::xcm_data(&refund.asset)?;
+            let xcm_amount = assets
+                .into_inner()
+                .into_iter()
+                .filter_map(|asset| match asset {
+                    MultiAsset {
+                        id: Concrete(id),
+                        fun: Fungible(amount),
+                    } if id == location => Some(amount),
+                    _ => None,
+                })
+                .fold(0, |acc: XcmBalance, amount| acc.saturating_add(amount));
+            // destination chain may report more than it got from us
+            let amount = balance_from_xcm::<T::Balance>(xcm_amount, decimals)
+                .unwrap_or(refund.fee)
+                .min(refund.fee);
+
+            XcmRefunds::<T>::remove(query_id);
+            Self::deposit_creating(
+                &refund.who,
+                refund.asset,
+                amount,
+                true,
+                Some(DepositReason::XcmFeeRefund),
+            )?;
+            Self::deposit_event(Event::XcmFeeRefunded(
+                refund.who,
+                refund.asset,
+                amount,
+                refund.message_hash,
+            ));
+            Ok(().into())
+        }
+
+        /// Removes XCM transfer which unspent fee was not reported before timeout
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::clear_expired_xcm_refund())]
+        pub fn clear_expired_xcm_refund(
+            origin: OriginFor<T>,
+            query_id: QueryId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let refund = XcmRefunds::<T>::get(query_id).ok_or(Error::<T>::XcmUnknownRefund)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() > refund.expires_at,
+                Error::<T>::XcmRefundNotExpired
+            );
+
+            XcmRefunds::<T>::remove(query_id);
+            Self::deposit_event(Event::XcmRefundExpired(refund.who, refund.message_hash));
+            Ok(().into())
+        }
+
+        /// Sets flags of `asset` on account `who`.
+        /// Balance and locks of a frozen asset can't be changed until it is unfrozen.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::set_asset_flags())]
+        pub fn set_asset_flags(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            asset: Asset,
+            flags: AssetFlags,
+        ) -> DispatchResultWithPostInfo {
+            T::ToggleTransferOrigin::ensure_origin(origin)?;
+            ensure!(
+                frame_system::Pallet::<T>::providers(&who) != 0,
+                Error::<T>::DeadAccount
+            );
+
+            T::AccountStore::mutate(&who, |data| data.set_flags(asset, flags))?;
+            Self::deposit_event(Event::AssetFlagsSet(who, asset, flags));
+            Ok(().into())
+        }
+
+        /// Allows or forbids settlement account `settler` to decrease balances of trx sender
+        /// in netted settlements
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::authorize_settler())]
+        pub fn authorize_settler(
+            origin: OriginFor<T>,
+            settler: T::AccountId,
+            allowed: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            if allowed {
+                SettlerAuthorizations::<T>::insert(&who, &settler, ());
+            } else {
+                SettlerAuthorizations::<T>::remove(&who, &settler);
+            }
+            Ok(().into())
+        }
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            Self::process_xcm_retries(n).saturating_add(Self::process_timelocks(n))
+        }
+    }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -491,6 +1113,55 @@ pub mod pallet {
         /// \[send_error\]
         XcmMessageSendError(xcm::latest::SendError),
         MigrationComplete,
+        /// Netted settlement applied. Included values are:
+        /// - settlement `AccountId`
+        /// - number of accounts with changed balances
+        /// \[settler, accounts\]
+        Settlement(T::AccountId, u32),
+        /// Balance check failed, deposited in debug builds only. Included values are:
+        /// - `AccountId` whose balance change was rejected
+        /// - checker that rejected the change
+        /// - required amount
+        /// - available amount
+        /// \[who, checker, required, available\]
+        BalanceCheckFailed(T::AccountId, BalanceCheckerKind, T::Balance, T::Balance),
+        /// XCM fee formula of destination changed. \[destination, asset, params\]
+        XcmFeeSet(MultiLocation, Asset, Option<XcmFeeParams>),
+        /// Inbound XCM execution price changed. \[asset, units_per_second\]
+        XcmWeightFeeSet(Asset, Option<XcmBalance>),
+        /// Asset Hub mapping of asset changed. \[asset, asset_hub_asset\]
+        AssetHubAssetSet(Asset, Option<AssetHubAsset>),
+        /// XCM transfer failed to send and is queued for retry. \[who, message_hash\]
+        XcmTransferQueued(T::AccountId, XcmHash),
+        /// Queued XCM transfer is sent. \[who, message_hash\]
+        XcmTransferRetried(T::AccountId, XcmHash),
+        /// Retry of queued XCM transfer failed. \[who, message_hash, attempts\]
+        XcmTransferRetryFailed(T::AccountId, XcmHash, u32),
+        /// Queued XCM transfer expired, held funds are returned. \[who, message_hash\]
+        XcmTransferRefunded(T::AccountId, XcmHash),
+        /// Transfer is held in escrow until unlock block.
+        /// \[id, from, to, asset, amount, unlock_at\]
+        TimelockCreated(
+            u64,
+            T::AccountId,
+            T::AccountId,
+            Asset,
+            T::Balance,
+            T::BlockNumber,
+        ),
+        /// Timelocked transfer is cancelled, funds are returned to the sender. \[id\]
+        TimelockCancelled(u64),
+        /// Timelocked transfer is released to the recipient. \[id\]
+        TimelockReleased(u64),
+        /// Dispute window of timelocked transfers changed. \[window\]
+        TimelockDisputeWindowSet(T::BlockNumber),
+        /// Unspent fee of XCM transfer is returned to the sender.
+        /// \[who, asset, amount, message_hash\]
+        XcmFeeRefunded(T::AccountId, Asset, T::Balance, XcmHash),
+        /// Unspent fee of XCM transfer was not reported in time. \[who, message_hash\]
+        XcmRefundExpired(T::AccountId, XcmHash),
+        /// Flags of account asset changed. \[who, asset, flags\]
+        AssetFlagsSet(T::AccountId, Asset, AssetFlags),
     }
 
     #[pallet::error]
@@ -527,6 +1198,8 @@ pub mod pallet {
         XcmUnknownAsset,
         /// Fee asset should be the same for self reserved assets
         XcmWrongFeeAsset,
+        /// Beneficiary account is zero or doesn't match the account format of the destination
+        XcmInvalidBeneficiary,
         /// no migration needed
         NoMigration,
         /// Is thrown in case of removing non-zero balance
@@ -537,6 +1210,44 @@ pub mod pallet {
         XcmTransfersLimitExceeded,
         /// Balance is less than locked amount
         Locked,
+        /// Account is not allowed to submit settlements
+        NotSettlementAccount,
+        /// Settler is not authorized to decrease balances of the account
+        SettlerNotAuthorized,
+        /// Settlement changes of some asset don't sum up to zero
+        SettlementNotBalanced,
+        /// Lock is managed by another pallet
+        LockOwnerMismatch,
+        /// There is no lock with such id on the asset
+        LockNotFound,
+        /// Number of named reserves of the asset exceeds `MaxReserves`
+        TooManyReserves,
+        /// Asset XCM location is not the location of Asset Hub asset
+        AssetHubLocationMismatch,
+        /// Unlock block of timelocked transfer must be in the future
+        TimelockInPast,
+        /// There is no timelocked transfer with such id
+        TimelockNotFound,
+        /// Only sender may cancel timelocked transfer
+        TimelockNotSender,
+        /// Timelocked transfer can't be cancelled after its dispute window
+        TimelockDisputeWindowEnded,
+        /// Account with debt can't be migrated
+        AccountHasDebt,
+        /// Account with reserved balances can't be migrated
+        AccountHasReserves,
+        /// Account with an active lock of a pallet not moving its state can't be migrated
+        LockNotMigratable,
+        /// There is no XCM transfer waiting for refund with such query id
+        XcmUnknownRefund,
+        /// Response came not from destination chain of XCM transfer
+        XcmUnexpectedResponder,
+        /// Response is not a holding report
+        XcmUnexpectedResponse,
+        /// Refund query is not expired yet
+        XcmRefundNotExpired,
+        /// Asset is frozen on the account
+        AssetFrozen,
     }
 
     /// Reserved balances
@@ -551,10 +1262,27 @@ pub mod pallet {
         ValueQuery,
     >;
 
-    // Accounts' locks
+    /// Parts of `Reserved` balances reserved under identifiers
+    #[pallet::storage]
+    pub type NamedReserves<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        Asset,
+        VecMap<ReserveIdentifier, T::Balance>,
+        OptionQuery,
+    >;
+
+    /// Accounts' locks by asset and lock id
     #[pallet::storage]
-    pub type Locked<T: Config> =
-        StorageMap<_, Identity, T::AccountId, VecMap<LockIdentifier, T::Balance>, ValueQuery>;
+    pub type Locked<T: Config> = StorageMap<
+        _,
+        Identity,
+        T::AccountId,
+        VecMap<(Asset, LockIdentifier), AssetLock<T::Balance, T::BlockNumber>>,
+        ValueQuery,
+    >;
 
     /// Flag for enable/disable transfers
     #[pallet::storage]
@@ -578,27 +1306,114 @@ pub mod pallet {
     #[pallet::storage]
     pub type DailyXcmLimit<T: Config> = StorageValue<_, T::Balance, OptionQuery>;
 
-    #[pallet::genesis_config]
-    pub struct GenesisConfig<T: Config> {
-        pub balances: Vec<(T::AccountId, Vec<(T::Balance, u64)>)>,
-        pub is_transfers_enabled: bool,
-        pub is_xcm_enabled: Option<XcmMode>,
-    }
+    /// Accounts allowed to submit netted settlements
+    #[pallet::storage]
+    pub type SettlementAccounts<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
 
-    #[cfg(feature = "std")]
-    impl<T: Config> Default for GenesisConfig<T> {
-        fn default() -> Self {
-            Self {
-                balances: Default::default(),
-                is_transfers_enabled: Default::default(),
-                is_xcm_enabled: Default::default(),
-            }
-        }
-    }
+    /// Settlement accounts authorized by account to decrease its balances
+    #[pallet::storage]
+    pub type SettlerAuthorizations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
 
-    #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
-        fn build(&self) {
+    /// Assets refused on inbound XCM deposits, received funds are trapped
+    #[pallet::storage]
+    pub type XcmFrozenAssets<T: Config> = StorageMap<_, Blake2_128Concat, Asset, (), OptionQuery>;
+
+    /// Origins whose inbound XCM deposits are refused, received funds are trapped
+    #[pallet::storage]
+    pub type XcmFrozenOrigins<T: Config> =
+        StorageMap<_, Blake2_128Concat, MultiLocation, (), OptionQuery>;
+
+    /// Fee formulas of XCM sent to destination with asset, override `T::XcmToFee`
+    #[pallet::storage]
+    pub type XcmFees<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        MultiLocation,
+        Blake2_128Concat,
+        Asset,
+        XcmFeeParams,
+        OptionQuery,
+    >;
+
+    /// Inbound XCM execution price in asset XCM units per second
+    #[pallet::storage]
+    pub type XcmWeightFees<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, XcmBalance, OptionQuery>;
+
+    /// Local assets mapped to Asset Hub assets, fees of XCM sent to Asset Hub
+    /// with them override `T::XcmToFee`
+    #[pallet::storage]
+    pub type AssetHubAssets<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, AssetHubAsset, OptionQuery>;
+
+    /// XCM transfers failed to send, by message hash
+    #[pallet::storage]
+    pub type XcmRetryQueue<T: Config> = StorageMap<
+        _,
+        Identity,
+        XcmHash,
+        XcmRetry<T::AccountId, T::Balance, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Id of the next timelocked transfer
+    #[pallet::storage]
+    pub type NextTimelockId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Transfers held in escrow until unlock block, by id
+    #[pallet::storage]
+    pub type Timelocks<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        TimelockedTransfer<T::AccountId, T::Balance, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Blocks after creation of timelocked transfer during which it may be cancelled
+    #[pallet::storage]
+    pub type TimelockDisputeWindow<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// XCM transfers waiting for unspent fee report, by query id
+    #[pallet::storage]
+    pub type XcmRefunds<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        QueryId,
+        XcmRefund<T::AccountId, T::Balance, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        pub balances: Vec<(T::AccountId, Vec<(T::Balance, u64)>)>,
+        pub is_transfers_enabled: bool,
+        pub is_xcm_enabled: Option<XcmMode>,
+    }
+
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self {
+                balances: Default::default(),
+                is_transfers_enabled: Default::default(),
+                is_xcm_enabled: Default::default(),
+            }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
             let config = self;
             for (ref who, balance) in config.balances.iter() {
                 // println!("eq-balances build: who {:?}", who);
@@ -645,9 +1460,7 @@ impl<T: Config> BalanceGetter<T::AccountId, T::Balance> for Pallet<T> {
     }
 
     fn iterate_account_balances(who: &T::AccountId) -> VecMap<Asset, SignedBalance<T::Balance>> {
-        match T::AccountStore::get(who) {
-            AccountData::V0 { balance, lock: _ } => balance,
-        }
+        T::AccountStore::get(who).into_balances()
     }
 
     fn get_debt_and_collateral(
@@ -760,7 +1573,8 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
         withdraw_reasons: WithdrawReasons,
         _new_balance: T::Balance,
     ) -> DispatchResult {
-        T::BalanceChecker::can_change_balance(
+        Self::ensure_not_frozen(who, &T::AccountStore::get(who), asset)?;
+        Self::check_balance_change(
             &who,
             &vec![(asset, SignedBalance::Negative(amount))],
             Some(withdraw_reasons),
@@ -775,7 +1589,7 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
                 str_asset!(asset),
                 e
             );
-            e
+            e.into()
         })
     }
 
@@ -801,23 +1615,7 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
         let providers = frame_system::Pallet::<T>::providers(dest);
 
         if providers == 0 && existence_requirement == ExistenceRequirement::KeepAlive {
-            let price = T::PriceGetter::get_price::<EqFixedU128>(&asset)?;
-            let amount_in_usd = price
-                .checked_mul_int(value)
-                .ok_or(ArithmeticError::Overflow)?;
-            let minimum_balance_value = Self::minimum_balance_value();
-
-            eq_ensure!(amount_in_usd >= minimum_balance_value,
-                Error::<T>::NotEnoughToKeepAlive,
-                target: "eq_balances",
-                "{}:{}. {:?} {:?} Not enough to keep account alive after first deposit. Who: {:?}, transactor {:?}.",
-                file!(),
-                line!(),
-                asset,
-                value,
-                dest,
-                transactor
-            );
+            Self::ensure_can_keep_alive(transactor, dest, asset, value)?;
         } // AllowDeath, for new account will be removed by offchain worker
 
         if providers == 0 {
@@ -827,13 +1625,16 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
         T::AccountStore::mutate(transactor, |from_account| -> DispatchResult {
             T::AccountStore::mutate(dest, |to_account| -> DispatchResult {
                 if ensure_can_change {
-                    T::BalanceChecker::can_change_balance(
+                    Self::ensure_not_frozen(transactor, from_account, asset)?;
+                    Self::ensure_not_frozen(dest, to_account, asset)?;
+
+                    Self::check_balance_change(
                         &transactor,
                         &vec![(asset, SignedBalance::Negative(value))],
                         None,
                     )?;
 
-                    T::BalanceChecker::can_change_balance(
+                    Self::check_balance_change(
                         &dest,
                         &vec![(asset, SignedBalance::Positive(value))],
                         None,
@@ -910,13 +1711,10 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
         );
 
         T::AccountStore::mutate(who, |balances| -> DispatchResult {
-            T::BalanceChecker::can_change_balance(
-                &who,
-                &vec![(asset, SignedBalance::Positive(value))],
-                None,
-            )
-            .map_err(|error| {
-                log::error!(
+            Self::ensure_not_frozen(who, balances, asset)?;
+            Self::check_balance_change(&who, &vec![(asset, SignedBalance::Positive(value))], None)
+                .map_err(|error| {
+                    log::error!(
                     "{}:{}. Cannot change balance. Who: {:?}, amount: {:?}, currency: {:?}. {:?}",
                     file!(),
                     line!(),
@@ -925,8 +1723,8 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
                     str_asset!(asset),
                     error
                 );
-                error
-            })?;
+                    error
+                })?;
 
             let balance = balances.entry(asset).or_default();
             let new_balance = balance
@@ -965,12 +1763,13 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
 
         T::AccountStore::mutate(who, |balances| -> DispatchResult {
             if !ensure_can_change
-                || T::BalanceChecker::can_change_balance(
-                    &who,
-                    &vec![(asset, SignedBalance::Positive(value))],
-                    None,
-                )
-                .map_or_else(|_| false, |_| true)
+                || (Self::ensure_not_frozen(who, balances, asset).is_ok()
+                    && Self::check_balance_change(
+                        &who,
+                        &vec![(asset, SignedBalance::Positive(value))],
+                        None,
+                    )
+                    .map_or_else(|_| false, |_| true))
             {
                 let balance = balances.entry(asset).or_default();
                 let new_balance = balance
@@ -1012,13 +1811,17 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
         Self::ensure_asset_exists(asset)?;
 
         T::AccountStore::mutate(who, |balances| -> DispatchResult {
+            if ensure_can_change {
+                Self::ensure_not_frozen(who, balances, asset)?;
+            }
+
             let balance = balances.entry(asset).or_default();
             let new_balance = balance
                 .sub_balance(&value)
                 .ok_or(ArithmeticError::Overflow)?;
             // if new balance is not negative then heave checks not needed
             if matches!(new_balance, SignedBalance::Negative(_)) && ensure_can_change {
-                T::BalanceChecker::can_change_balance(
+                Self::check_balance_change(
                     &who,
                     &vec![(asset,SignedBalance::Negative(value))],
                     Some(withdraw_reasons),
@@ -1106,6 +1909,8 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
             mut collateral,
             discounted_collateral: _,
         } = Self::get_debt_and_collateral(who)?;
+        let minimum_balance_value = Self::minimum_balance_value();
+        let mut existential_deposit = Self::get_existential_deposit(who, minimum_balance_value)?;
         for ref subacc_type in SubAccType::iterator() {
             if let Some(subacc_id) = T::SubaccountsManager::get_subaccount_id(who, subacc_type) {
                 let DebtCollateralDiscounted {
@@ -1115,11 +1920,14 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
                 } = Self::get_debt_and_collateral(&subacc_id)?;
                 collateral = collateral + sub_collat;
                 debt = debt + sub_debt;
+                existential_deposit = existential_deposit.max(Self::get_existential_deposit(
+                    &subacc_id,
+                    minimum_balance_value,
+                )?);
             }
         }
 
-        let minimum_balance_value = Self::minimum_balance_value();
-        if collateral.saturating_sub(debt) >= minimum_balance_value {
+        if collateral.saturating_sub(debt) >= existential_deposit {
             return Ok(false);
         }
 
@@ -1199,7 +2007,7 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
 
         T::AccountStore::mutate(accounts.0, |balances1| -> DispatchResult {
             T::AccountStore::mutate(accounts.1, |balances2| -> DispatchResult {
-                T::BalanceChecker::can_change_balance(
+                Self::check_balance_change(
                     &accounts.0,
                     &vec![
                         (*assets.0, SignedBalance::Negative(values.0)),
@@ -1211,7 +2019,7 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
                     err_acc = Some(accounts.0.clone());
                     err
                 })?;
-                T::BalanceChecker::can_change_balance(
+                Self::check_balance_change(
                     &accounts.1,
                     &vec![
                         (*assets.0, SignedBalance::Positive(values.0)),
@@ -1426,6 +2234,16 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
             return Ok(T::Balance::zero());
         }
 
+        if slashed == beneficiary {
+            return match status {
+                BalanceStatus::Free => {
+                    let unreserved = Self::unreserve(slashed, asset, to_slash);
+                    Ok(reserved.saturating_sub(unreserved))
+                }
+                BalanceStatus::Reserved => Ok(reserved),
+            };
+        }
+
         match status {
             BalanceStatus::Free => {
                 Self::currency_transfer(
@@ -1476,75 +2294,145 @@ impl<T: Config> EqCurrency<T::AccountId, T::Balance> for Pallet<T> {
     }
 
     fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: T::Balance) {
-        let (new_locked, remove): (T::Balance, bool) = Locked::<T>::mutate(who, |map| {
-            let rm;
-            if !amount.is_zero() {
-                *map.entry(id).or_default() = amount;
-                rm = false;
-            } else {
-                map.remove(&id);
-                rm = map.len() == 0;
-            }
-            (map.values().cloned().max().unwrap_or_default(), rm)
-        });
-
-        if remove {
-            Locked::<T>::remove(who);
+        let main_asset = T::AssetGetter::get_main_asset();
+        let mut locks = Self::account_locks(who);
+        if amount.is_zero() {
+            locks.remove(&(main_asset, id));
+        } else {
+            let owner = locks.get(&(main_asset, id)).map_or(id, |lock| lock.owner);
+            locks.insert(
+                (main_asset, id),
+                AssetLock {
+                    amount,
+                    expiry: None,
+                    owner,
+                },
+            );
         }
-
-        let _ = T::AccountStore::mutate(who, |balances| match balances {
-            AccountData::V0 {
-                balance: _,
-                ref mut lock,
-            } => {
-                *lock = new_locked;
-            }
-        });
+        Self::put_locks(who, locks);
     }
 
     fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: T::Balance) {
         if !amount.is_zero() {
-            Locked::<T>::mutate(who, |map| {
-                let lock = map.entry(id).or_default();
-                if *lock < amount {
-                    *lock = amount;
-                }
-            });
-
-            let _ = T::AccountStore::mutate(who, |balances| match balances {
-                AccountData::V0 {
-                    balance: _,
-                    ref mut lock,
-                } => {
-                    if *lock < amount {
-                        *lock = amount;
-                    }
-                }
+            let main_asset = T::AssetGetter::get_main_asset();
+            let mut locks = Self::account_locks(who);
+            let lock = locks.entry((main_asset, id)).or_insert_with(|| AssetLock {
+                amount: T::Balance::zero(),
+                expiry: None,
+                owner: id,
             });
+            if lock.amount < amount {
+                lock.amount = amount;
+            }
+            Self::put_locks(who, locks);
         }
     }
 
     fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
-        let (new_locked, remove): (T::Balance, bool) = Locked::<T>::mutate(who, |map| {
-            map.remove(&id);
-            (
-                map.values().cloned().max().unwrap_or_default(),
-                map.len() == 0,
-            )
-        });
+        let main_asset = T::AssetGetter::get_main_asset();
+        let mut locks = Self::account_locks(who);
+        locks.remove(&(main_asset, id));
+        Self::put_locks(who, locks);
+    }
+}
 
-        if remove {
-            Locked::<T>::remove(who);
+impl<T: Config> EqNamedReservableCurrency<T::AccountId, T::Balance> for Pallet<T> {
+    fn reserved_balance_named(
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        asset: Asset,
+    ) -> T::Balance {
+        NamedReserves::<T>::get(who, asset)
+            .and_then(|reserves| reserves.get(id).copied())
+            .unwrap_or_else(T::Balance::zero)
+    }
+
+    #[frame_support::transactional]
+    fn reserve_named(
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        if amount.is_zero() {
+            return Ok(());
         }
 
-        let _ = T::AccountStore::mutate(who, |balances| match balances {
-            AccountData::V0 {
-                balance: _,
-                ref mut lock,
-            } => {
-                *lock = new_locked;
-            }
-        });
+        Self::increase_named_reserve(id, who, asset, amount)?;
+        <Self as EqCurrency<_, _>>::reserve(who, asset, amount)
+    }
+
+    fn unreserve_named(
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+    ) -> T::Balance {
+        let to_unreserve = Self::reserved_balance_named(id, who, asset).min(amount);
+        if to_unreserve.is_zero() {
+            return amount;
+        }
+
+        let unreserved = <Self as EqCurrency<_, _>>::unreserve(who, asset, to_unreserve);
+        Self::decrease_named_reserve(id, who, asset, unreserved);
+        amount.saturating_sub(unreserved)
+    }
+
+    fn slash_reserved_named(
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        asset: Asset,
+        value: T::Balance,
+    ) -> (NegativeImbalance<T::Balance>, T::Balance) {
+        let to_slash = Self::reserved_balance_named(id, who, asset).min(value);
+        if to_slash.is_zero() {
+            return (NegativeImbalance::zero(), value);
+        }
+
+        let (imbalance, _) = <Self as EqCurrency<_, _>>::slash_reserved(who, asset, to_slash);
+        let slashed = imbalance.peek();
+        Self::decrease_named_reserve(id, who, asset, slashed);
+        (imbalance, value.saturating_sub(slashed))
+    }
+
+    #[frame_support::transactional]
+    fn repatriate_reserved_named(
+        id: &ReserveIdentifier,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        asset: Asset,
+        value: T::Balance,
+        status: BalanceStatus,
+    ) -> Result<T::Balance, DispatchError> {
+        if slashed == beneficiary {
+            return Ok(match status {
+                BalanceStatus::Free => Self::unreserve_named(id, slashed, asset, value),
+                BalanceStatus::Reserved => {
+                    value.saturating_sub(Self::reserved_balance_named(id, slashed, asset))
+                }
+            });
+        }
+
+        let to_move = Self::reserved_balance_named(id, slashed, asset).min(value);
+        if to_move.is_zero() {
+            return Ok(value);
+        }
+
+        let reserved = Reserved::<T>::get(slashed, asset);
+        let left = <Self as EqCurrency<_, _>>::repatriate_reserved(
+            slashed,
+            beneficiary,
+            asset,
+            to_move,
+            status,
+        )?;
+        let moved = reserved.saturating_sub(left);
+
+        Self::decrease_named_reserve(id, slashed, asset, moved);
+        if status == BalanceStatus::Reserved {
+            Self::increase_named_reserve(id, beneficiary, asset, moved)?;
+        }
+        Ok(value.saturating_sub(moved))
     }
 }
 
@@ -1560,7 +2448,346 @@ impl<T: Config> Get<Option<XcmMode>> for Pallet<T> {
     }
 }
 
+impl<T: Config> XcmDepositFreeze for Pallet<T> {
+    fn is_asset_frozen(asset: &Asset) -> bool {
+        XcmFrozenAssets::<T>::contains_key(asset)
+    }
+
+    fn is_origin_frozen(origin: &MultiLocation) -> bool {
+        XcmFrozenOrigins::<T>::contains_key(origin)
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Fee of executing `xcm` on `destination`: `XcmFees` entry if there is one,
+    /// `AssetHubAssets` fee mode for Asset Hub, `T::XcmToFee` otherwise
+    pub fn xcm_to_fee(
+        asset: Asset,
+        destination: &MultiLocation,
+        xcm: &Xcm<()>,
+    ) -> Option<(Asset, XcmBalance)> {
+        if let Some(params) = XcmFees::<T>::get(destination, asset) {
+            return Some((params.fee_asset, params.fee(xcm.len())));
+        }
+        if *destination == PARACHAIN_STATEMINT {
+            if let Some(asset_hub_asset) = AssetHubAssets::<T>::get(asset) {
+                return Some(asset_hub_asset.fee(asset, xcm));
+            }
+        }
+        T::XcmToFee::convert((asset, destination.clone(), xcm))
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Account holding funds of XCM transfers queued for retry
+    pub fn xcm_retry_account() -> T::AccountId {
+        T::ModuleId::get().into_sub_account_truncating(b"xcmretry")
+    }
+
+    /// Key of `XcmRetryQueue`, unique for sender and block
+    fn xcm_retry_hash(from: &T::AccountId, destination: &MultiLocation, xcm: &Xcm<()>) -> XcmHash {
+        (
+            from,
+            frame_system::Pallet::<T>::block_number(),
+            destination,
+            xcm,
+        )
+            .using_encoded(sp_io::hashing::blake2_256)
+    }
+
+    /// Errors that may disappear on their own, e.g. closed or overloaded channel
+    fn is_xcm_send_error_transient(send_error: &SendError) -> bool {
+        matches!(
+            send_error,
+            SendError::Transport(_) | SendError::Unroutable | SendError::Fees
+        )
+    }
+
+    /// Moves `held` funds of unsent transfer from `who` to `xcm_retry_account`
+    /// and queues the transfer. Transfers from `xcm_retry_account` itself and
+    /// transfers failed with non transient errors are not queued.
+    fn queue_xcm_retry(
+        who: &T::AccountId,
+        message_hash: XcmHash,
+        send_error: SendError,
+        transfer: XcmRetryTransfer<T::Balance>,
+        destination: XcmDestination,
+        held: VecMap<Asset, T::Balance>,
+    ) -> DispatchResult {
+        let retry_account = Self::xcm_retry_account();
+        ensure!(
+            who != &retry_account
+                && Self::is_xcm_send_error_transient(&send_error)
+                && !XcmRetryQueue::<T>::contains_key(message_hash),
+            Error::<T>::XcmSend
+        );
+
+        let retry_account_info = frame_system::Pallet::<T>::account(&retry_account);
+        if retry_account_info.providers == retry_account_info.consumers {
+            EqPalletAccountInitializer::<T>::initialize(&retry_account);
+        }
+        for (asset, amount) in held.iter() {
+            Self::currency_transfer(
+                who,
+                &retry_account,
+                *asset,
+                *amount,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::XcmTransfer,
+                true,
+            )?;
+        }
+
+        let now = frame_system::Pallet::<T>::block_number();
+        XcmRetryQueue::<T>::insert(
+            message_hash,
+            XcmRetry {
+                who: who.clone(),
+                transfer,
+                destination,
+                held,
+                attempts: 0,
+                next_retry: now.saturating_add(XcmRetryDelay::get().into()),
+                expiry: now.saturating_add(XcmRetryExpiry::get().into()),
+            },
+        );
+        Self::deposit_event(Event::XcmTransferQueued(who.clone(), message_hash));
+
+        Ok(())
+    }
+
+    /// Retries due transfers of `XcmRetryQueue` and refunds expired ones,
+    /// at most `MaxXcmRetriesPerBlock` per block
+    fn process_xcm_retries(now: T::BlockNumber) -> Weight {
+        let due: Vec<_> = XcmRetryQueue::<T>::iter()
+            .filter(|(_, retry)| retry.next_retry <= now || retry.expiry <= now)
+            .take(MaxXcmRetriesPerBlock::get() as usize)
+            .collect();
+        let count = due.len() as u32;
+
+        for (message_hash, retry) in due {
+            if retry.expiry <= now {
+                Self::refund_xcm_retry(message_hash, retry);
+            } else {
+                Self::retry_xcm_transfer(message_hash, retry, now);
+            }
+        }
+
+        T::WeightInfo::process_xcm_retries(count)
+    }
+
+    fn retry_xcm_transfer(
+        message_hash: XcmHash,
+        mut retry: XcmRetry<T::AccountId, T::Balance, T::BlockNumber>,
+        now: T::BlockNumber,
+    ) {
+        let retry_account = Self::xcm_retry_account();
+        let result =
+            frame_support::storage::with_transaction(|| -> TransactionOutcome<DispatchResult> {
+                use TransactionOutcome::*;
+
+                let balances_before: Vec<_> = retry
+                    .held
+                    .keys()
+                    .map(|asset| Self::free_balance(&retry_account, *asset))
+                    .collect();
+                let sent = match retry.transfer.clone() {
+                    XcmRetryTransfer::Transfer { transfer, fee } => Self::do_xcm_transfer(
+                        retry_account.clone(),
+                        transfer,
+                        fee,
+                        retry.destination.clone(),
+                    ),
+                    XcmRetryTransfer::TransferOld {
+                        asset,
+                        amount,
+                        deal_with_fee,
+                    } => Self::do_xcm_transfer_old(
+                        retry_account.clone(),
+                        asset,
+                        amount,
+                        retry.destination.clone(),
+                        deal_with_fee,
+                    ),
+                };
+                if let Err(err) = sent {
+                    return Rollback(Err(err));
+                }
+
+                // fee may be recalculated on retry, funds of other queued transfers must stay untouched
+                for ((asset, held), before) in retry.held.iter().zip(balances_before) {
+                    let spent = before.saturating_sub(Self::free_balance(&retry_account, *asset));
+                    let left = match held.checked_sub(&spent) {
+                        Some(left) => left,
+                        None => return Rollback(Err(Error::<T>::XcmNotEnoughToPayFee.into())),
+                    };
+                    let res = Self::currency_transfer(
+                        &retry_account,
+                        &retry.who,
+                        *asset,
+                        left,
+                        ExistenceRequirement::AllowDeath,
+                        TransferReason::XcmTransferRefund,
+                        false,
+                    );
+                    if let Err(err) = res {
+                        return Rollback(Err(err));
+                    }
+                }
+
+                Commit(Ok(()))
+            });
+
+        match result {
+            Ok(()) => {
+                XcmRetryQueue::<T>::remove(message_hash);
+                Self::deposit_event(Event::XcmTransferRetried(retry.who, message_hash));
+            }
+            Err(err) => {
+                log::error!(
+                    target: "eq_balances",
+                    "XCM transfer retry failed. message_hash: {:?}, error: {:?}",
+                    message_hash,
+                    err
+                );
+                retry.attempts = retry.attempts.saturating_add(1);
+                let delay =
+                    XcmRetryDelay::get().saturating_mul(2u32.saturating_pow(retry.attempts));
+                retry.next_retry = now.saturating_add(delay.into());
+                Self::deposit_event(Event::XcmTransferRetryFailed(
+                    retry.who.clone(),
+                    message_hash,
+                    retry.attempts,
+                ));
+                XcmRetryQueue::<T>::insert(message_hash, retry);
+            }
+        }
+    }
+
+    /// Returns held funds of expired transfer to the sender
+    fn refund_xcm_retry(
+        message_hash: XcmHash,
+        retry: XcmRetry<T::AccountId, T::Balance, T::BlockNumber>,
+    ) {
+        let retry_account = Self::xcm_retry_account();
+        for (asset, amount) in retry.held.iter() {
+            if let Err(err) = Self::currency_transfer(
+                &retry_account,
+                &retry.who,
+                *asset,
+                *amount,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::XcmTransferRefund,
+                false,
+            ) {
+                log::error!(
+                    target: "eq_balances",
+                    "XCM transfer refund failed. message_hash: {:?}, asset: {:?}, error: {:?}",
+                    message_hash,
+                    str_asset!(asset),
+                    err
+                );
+            }
+        }
+
+        XcmRetryQueue::<T>::remove(message_hash);
+        Self::deposit_event(Event::XcmTransferRefunded(retry.who, message_hash));
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Account holding funds of timelocked transfers
+    pub fn timelock_account() -> T::AccountId {
+        T::ModuleId::get().into_sub_account_truncating(b"timelock")
+    }
+
+    /// Releases due transfers of `Timelocks` to their recipients,
+    /// at most `MaxTimelockReleasesPerBlock` in a block
+    fn process_timelocks(now: T::BlockNumber) -> Weight {
+        let due: Vec<_> = Timelocks::<T>::iter()
+            .filter(|(_, timelock)| timelock.unlock_at <= now)
+            .take(MaxTimelockReleasesPerBlock::get() as usize)
+            .collect();
+        let count = due.len() as u32;
+
+        let timelock_account = Self::timelock_account();
+        for (id, timelock) in due {
+            if let Err(err) = Self::currency_transfer(
+                &timelock_account,
+                &timelock.to,
+                timelock.asset,
+                timelock.amount,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::Timelock,
+                false,
+            ) {
+                log::error!(
+                    target: "eq_balances",
+                    "Timelocked transfer release failed. id: {:?}, asset: {:?}, error: {:?}",
+                    id,
+                    str_asset!(timelock.asset),
+                    err
+                );
+            }
+
+            Timelocks::<T>::remove(id);
+            Self::deposit_event(Event::TimelockReleased(id));
+        }
+
+        T::WeightInfo::process_timelocks(count)
+    }
+}
+
 impl<T: Config> Pallet<T> {
+    /// Runs `T::BalanceChecker` and traces the failed checker.
+    /// `BalanceCheckFailed` is deposited in debug builds only.
+    fn check_balance_change(
+        who: &T::AccountId,
+        changes: &Vec<(Asset, SignedBalance<T::Balance>)>,
+        withdraw_reasons: Option<WithdrawReasons>,
+    ) -> Result<(), BalanceCheckError<T::Balance>> {
+        T::BalanceChecker::can_change_balance(who, changes, withdraw_reasons).map_err(|e| {
+            log::debug!(
+                target: "eq_balances",
+                "{}:{}. Balance check failed. Who: {:?}, checker: {:?}, required: {:?}, available: {:?}. {:?}",
+                file!(),
+                line!(),
+                who,
+                e.checker,
+                e.required,
+                e.available,
+                e.error
+            );
+            #[cfg(debug_assertions)]
+            Self::deposit_event(Event::BalanceCheckFailed(
+                who.clone(),
+                e.checker,
+                e.required,
+                e.available,
+            ));
+            e
+        })
+    }
+
+    fn ensure_not_frozen(
+        who: &T::AccountId,
+        data: &AccountData<T::Balance>,
+        asset: Asset,
+    ) -> DispatchResult {
+        eq_ensure!(
+            !data.flags(&asset).frozen,
+            Error::<T>::AssetFrozen,
+            target: "eq_balances",
+            "{}:{}. Asset is frozen. Who: {:?}, asset: {:?}.",
+            file!(),
+            line!(),
+            who,
+            str_asset!(asset)
+        );
+
+        Ok(())
+    }
+
     fn ensure_transfers_enabled(asset: &Asset, amount: T::Balance) -> DispatchResult {
         let is_enabled = <Self as eq_primitives::IsTransfersEnabled>::get();
         eq_ensure!(
@@ -1610,6 +2837,182 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    fn ensure_can_keep_alive(
+        transactor: &T::AccountId,
+        dest: &T::AccountId,
+        asset: Asset,
+        value: T::Balance,
+    ) -> DispatchResult {
+        let price = T::PriceGetter::get_price::<EqFixedU128>(&asset)?;
+        let amount_in_usd = price
+            .checked_mul_int(value)
+            .ok_or(ArithmeticError::Overflow)?;
+        let minimum_balance_value = Self::minimum_balance_value();
+
+        eq_ensure!(amount_in_usd >= minimum_balance_value,
+            Error::<T>::NotEnoughToKeepAlive,
+            target: "eq_balances",
+            "{}:{}. {:?} {:?} Not enough to keep account alive after first deposit. Who: {:?}, transactor {:?}.",
+            file!(),
+            line!(),
+            asset,
+            value,
+            dest,
+            transactor
+        );
+
+        Ok(())
+    }
+
+    /// Performs the checks of `transfer` extrinsic without changing balances.
+    /// Used by `EqBalancesApi::can_transfer` to explain why a transfer would fail.
+    pub fn can_transfer(
+        from: &T::AccountId,
+        to: &T::AccountId,
+        asset: Asset,
+        value: T::Balance,
+    ) -> TransferCheck {
+        let rejected = || -> DispatchResult {
+            Self::ensure_transfers_enabled(&asset, value)?;
+            ensure!(
+                Self::is_not_subaccount(to),
+                Error::<T>::TransferToSubaccount
+            );
+            if value.is_zero() || from == to {
+                return Ok(());
+            }
+            Self::ensure_asset_exists(asset)?;
+            Self::ensure_not_frozen(from, &T::AccountStore::get(from), asset)?;
+            Self::ensure_not_frozen(to, &T::AccountStore::get(to), asset)?;
+            if frame_system::Pallet::<T>::providers(to) == 0 {
+                Self::ensure_can_keep_alive(from, to, asset, value)?;
+            }
+
+            Ok(())
+        };
+        if let Err(error) = rejected() {
+            return TransferCheck::Rejected(error);
+        }
+        if value.is_zero() || from == to {
+            return TransferCheck::Allowed;
+        }
+
+        let checks = [
+            (TransferSide::Sender, from, SignedBalance::Negative(value)),
+            (TransferSide::Receiver, to, SignedBalance::Positive(value)),
+        ];
+        for (side, who, change) in checks {
+            if let Err(e) = T::BalanceChecker::can_change_balance(who, &vec![(asset, change)], None)
+            {
+                return TransferCheck::CheckFailed {
+                    side,
+                    checker: e.checker,
+                    error: e.error,
+                    margin: None,
+                };
+            }
+        }
+
+        TransferCheck::Allowed
+    }
+
+    /// Applies netted `deltas` submitted by `settler` and returns the number of changed
+    /// accounts. All balance checks are made before any balance is changed.
+    fn do_settle(
+        settler: &T::AccountId,
+        deltas: Vec<(T::AccountId, Asset, SignedBalance<T::Balance>)>,
+    ) -> Result<u32, DispatchError> {
+        let mut totals = BTreeMap::<Asset, SignedBalance<T::Balance>>::new();
+        let mut changes =
+            BTreeMap::<T::AccountId, BTreeMap<Asset, SignedBalance<T::Balance>>>::new();
+        for (who, asset, delta) in deltas {
+            Self::ensure_transfers_enabled(&asset, delta.abs())?;
+            ensure!(
+                Self::is_not_subaccount(&who),
+                Error::<T>::TransferToSubaccount
+            );
+
+            let total = totals.entry(asset).or_default();
+            *total = total.checked_add(&delta).ok_or(ArithmeticError::Overflow)?;
+            let change = changes.entry(who).or_default().entry(asset).or_default();
+            *change = change
+                .checked_add(&delta)
+                .ok_or(ArithmeticError::Overflow)?;
+        }
+
+        eq_ensure!(
+            totals.values().all(|total| total.is_zero()),
+            Error::<T>::SettlementNotBalanced,
+            target: "eq_balances",
+            "{}:{}. Settlement is not balanced. Totals: {:?}.",
+            file!(),
+            line!(),
+            totals
+        );
+        for asset in totals.keys() {
+            Self::ensure_asset_exists(*asset)?;
+        }
+
+        let changes: Vec<_> = changes
+            .into_iter()
+            .map(|(who, changes)| {
+                let changes: Vec<_> = changes
+                    .into_iter()
+                    .filter(|(_, change)| !change.is_zero())
+                    .collect();
+                (who, changes)
+            })
+            .filter(|(_, changes)| !changes.is_empty())
+            .collect();
+
+        for (who, changes) in changes.iter() {
+            eq_ensure!(
+                frame_system::Pallet::<T>::providers(who) != 0,
+                Error::<T>::DeadAccount,
+                target: "eq_balances",
+                "{}:{}. Cannot settle with new account. Who: {:?}.",
+                file!(),
+                line!(),
+                who
+            );
+            eq_ensure!(
+                who == settler
+                    || changes.iter().all(|(_, change)| change.is_positive())
+                    || SettlerAuthorizations::<T>::contains_key(who, settler),
+                Error::<T>::SettlerNotAuthorized,
+                target: "eq_balances",
+                "{}:{}. Settler is not authorized by account. Who: {:?}, settler: {:?}.",
+                file!(),
+                line!(),
+                who,
+                settler
+            );
+            Self::check_balance_change(who, changes, None)?;
+        }
+
+        for (who, changes) in changes.iter() {
+            T::AccountStore::mutate(who, |balances| -> DispatchResult {
+                for (asset, change) in changes {
+                    let balance = balances.entry(*asset).or_default();
+                    let new_balance = match change {
+                        Positive(value) => balance.add_balance(value),
+                        Negative(value) => balance.sub_balance(value),
+                    }
+                    .ok_or(ArithmeticError::Overflow)?;
+
+                    if change.is_positive() {
+                        T::Aggregates::set_usergroup(who, UserGroup::Balances, true)?;
+                    }
+                    T::Aggregates::update_total(who, *asset, balance, change)?;
+                    *balance = new_balance;
+                }
+                Ok(())
+            })??;
+        }
+
+        Ok(changes.len() as u32)
+    }
+
     fn ensure_asset_exists(asset: Asset) -> DispatchResult {
         match T::AssetGetter::get_asset_data(&asset) {
             Ok(_) => Ok(()),
@@ -1629,21 +3032,165 @@ impl<T: Config> Pallet<T> {
         Reserved::<T>::iter_prefix(&who).for_each(|(asset, reserved)| {
             Self::unreserve(who, asset, reserved);
         });
+        let _ = NamedReserves::<T>::clear_prefix(who, u32::MAX, None);
+    }
+
+    /// Adds `amount` to reserve `id`, fails if account has `MaxReserves` other reserves
+    fn increase_named_reserve(
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        NamedReserves::<T>::try_mutate(who, asset, |maybe_reserves| {
+            let reserves = maybe_reserves.get_or_insert_with(VecMap::new);
+            match reserves.get_mut(id) {
+                Some(reserved) => {
+                    *reserved = reserved
+                        .checked_add(&amount)
+                        .ok_or(ArithmeticError::Overflow)?;
+                }
+                None => {
+                    ensure!(
+                        (reserves.len() as u32) < MaxReserves::get(),
+                        Error::<T>::TooManyReserves
+                    );
+                    reserves.insert(*id, amount);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Subtracts `amount` from reserve `id`, removes it when empty
+    fn decrease_named_reserve(
+        id: &ReserveIdentifier,
+        who: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+    ) {
+        NamedReserves::<T>::mutate_exists(who, asset, |maybe_reserves| {
+            if let Some(reserves) = maybe_reserves {
+                if let Some(reserved) = reserves.get_mut(id) {
+                    *reserved = reserved.saturating_sub(amount);
+                    if reserved.is_zero() {
+                        reserves.remove(id);
+                    }
+                }
+                if reserves.is_empty() {
+                    *maybe_reserves = None;
+                }
+            }
+        });
     }
 
     fn is_not_subaccount(who: &T::AccountId) -> bool {
         T::SubaccountsManager::get_owner_id(who).is_none()
     }
 
+    /// Location of `asset`, its decimals from assets registry and whether it is self reserved
     fn xcm_data(asset: &Asset) -> Result<(MultiLocation, u8, bool), DispatchError> {
-        Ok(T::AssetGetter::get_asset_data(asset)?
+        let (location, _, self_reserved) = T::AssetGetter::get_asset_data(asset)?
             .get_xcm_data()
-            .ok_or(Error::<T>::XcmUnknownAsset)?)
+            .ok_or(Error::<T>::XcmUnknownAsset)?;
+        Ok((location, T::AssetGetter::decimals(asset), self_reserved))
     }
 
-    fn get_locked(who: &T::AccountId) -> T::Balance {
-        match T::AccountStore::get(who) {
-            AccountData::V0 { balance: _, lock } => lock,
+    /// Checks that `to` may receive funds on `destination` chain: the account isn't zero
+    /// and matches the account format of the chain
+    fn ensure_valid_beneficiary(destination: &MultiLocation, to: &AccountType) -> DispatchResult {
+        let network = T::UniversalLocation::get().global_consensus().ok();
+        let format = eq_primitives::address::account_format(network, destination);
+        to.validate(format).map_err(|err| {
+            log::error!(
+                target: "eq_balances",
+                "{}:{}. Invalid beneficiary. Destination: {:?}, to: {:?}, error: {:?}.",
+                file!(),
+                line!(),
+                destination,
+                to,
+                err
+            );
+            Error::<T>::XcmInvalidBeneficiary.into()
+        })
+    }
+
+    /// Dry run of native XCM transfer of `asset` to `to`: returns location of the beneficiary
+    /// relative to this chain. Used by `EqBalancesApi::validate_destination`.
+    pub fn validate_destination(
+        asset: Asset,
+        to: AccountType,
+    ) -> Result<MultiLocation, DispatchError> {
+        let (multi_location, _, _) = Self::xcm_data(&asset)?;
+        let XcmDestinationResolved {
+            destination,
+            beneficiary,
+            ..
+        } = Self::get_destination(XcmDestination::Native(to), multi_location)?;
+
+        destination
+            .appended_with(beneficiary)
+            .map_err(|_| Error::<T>::XcmInvalidDestination.into())
+    }
+
+    /// Locks of `who`, locks stored before storage version 1 are converted on read
+    pub fn account_locks(
+        who: &T::AccountId,
+    ) -> VecMap<(Asset, LockIdentifier), AssetLock<T::Balance, T::BlockNumber>> {
+        migrations::stored_locks::<T>(who).0
+    }
+
+    /// Stores `locks` of `who` and caches the largest active main asset lock in `AccountData`
+    fn put_locks(
+        who: &T::AccountId,
+        locks: VecMap<(Asset, LockIdentifier), AssetLock<T::Balance, T::BlockNumber>>,
+    ) {
+        let main_asset = T::AssetGetter::get_main_asset();
+        let now = frame_system::Pallet::<T>::block_number();
+        let new_locked = locks
+            .iter()
+            .filter(|((asset, _), lock)| *asset == main_asset && lock.is_active(&now))
+            .map(|(_, lock)| lock.amount)
+            .max()
+            .unwrap_or_default();
+
+        if locks.is_empty() {
+            Locked::<T>::remove(who);
+        } else {
+            Locked::<T>::insert(who, locks);
+        }
+
+        let _ = T::AccountStore::mutate(who, |balances| {
+            *balances.lock_mut() = new_locked;
+        });
+    }
+
+    fn ensure_lock_owner(
+        locks: &VecMap<(Asset, LockIdentifier), AssetLock<T::Balance, T::BlockNumber>>,
+        asset: Asset,
+        id: LockIdentifier,
+        owner: LockOwner,
+        allow_new: bool,
+    ) -> DispatchResult {
+        match locks.get(&(asset, id)) {
+            Some(lock) => {
+                eq_ensure!(
+                    lock.owner == owner,
+                    Error::<T>::LockOwnerMismatch,
+                    target: "eq_balances",
+                    "{}:{}. Lock is managed by another pallet. Lock id: {:?}, asset: {:?}, \
+                    owner: {:?}, caller: {:?}",
+                    file!(),
+                    line!(),
+                    id,
+                    str_asset!(asset),
+                    lock.owner,
+                    owner
+                );
+                Ok(())
+            }
+            None if allow_new => Ok(()),
+            None => Err(Error::<T>::LockNotFound.into()),
         }
     }
 
@@ -1680,13 +3227,226 @@ impl<T: Config> Pallet<T> {
             _ => existential_deposit_usd,
         }
     }
+
+    /// Get max of `minimum_balance_value` and existential deposits overridden in `AssetGetter`
+    /// of assets held by `who` worth in USD.
+    fn get_existential_deposit(
+        who: &T::AccountId,
+        minimum_balance_value: T::Balance,
+    ) -> Result<T::Balance, DispatchError> {
+        let mut existential_deposit = minimum_balance_value;
+        for (asset, balance) in Self::iterate_account_balances(who) {
+            let asset_existential_deposit =
+                match (balance, T::AssetGetter::existential_deposit(&asset)) {
+                    (Positive(_), Some(asset_existential_deposit)) => asset_existential_deposit,
+                    _ => continue,
+                };
+            let price = T::PriceGetter::get_price::<EqFixedU128>(&asset)?;
+            let value = price
+                .checked_mul_int(asset_existential_deposit)
+                .map(|b| b.try_into().ok())
+                .flatten()
+                .ok_or(ArithmeticError::Overflow)?;
+            existential_deposit = existential_deposit.max(value);
+        }
+
+        Ok(existential_deposit)
+    }
 }
 
 impl<T: Config> LockGetter<T::AccountId, T::Balance> for Pallet<T> {
     fn get_lock(who: T::AccountId, id: LockIdentifier) -> T::Balance {
-        *Locked::<T>::get(who)
-            .get(&id)
-            .unwrap_or(&T::Balance::zero())
+        Self::get_asset_lock(who, T::AssetGetter::get_main_asset(), id)
+    }
+
+    fn get_asset_lock(who: T::AccountId, asset: Asset, id: LockIdentifier) -> T::Balance {
+        let now = frame_system::Pallet::<T>::block_number();
+        Self::account_locks(who)
+            .get(&(asset, id))
+            .filter(|lock| lock.is_active(&now))
+            .map_or_else(T::Balance::zero, |lock| lock.amount)
+    }
+}
+
+impl<T: Config> AssetLocks<T::AccountId, T::Balance, T::BlockNumber> for Pallet<T> {
+    fn set_asset_lock(
+        id: LockIdentifier,
+        who: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+        expiry: Option<T::BlockNumber>,
+        owner: LockOwner,
+    ) -> DispatchResult {
+        Self::ensure_not_frozen(who, &T::AccountStore::get(who), asset)?;
+        let mut locks = Self::account_locks(who);
+        Self::ensure_lock_owner(&locks, asset, id, owner, true)?;
+        if amount.is_zero() {
+            locks.remove(&(asset, id));
+        } else {
+            locks.insert(
+                (asset, id),
+                AssetLock {
+                    amount,
+                    expiry,
+                    owner,
+                },
+            );
+        }
+        Self::put_locks(who, locks);
+
+        Ok(())
+    }
+
+    fn remove_asset_lock(
+        id: LockIdentifier,
+        who: &T::AccountId,
+        asset: Asset,
+        owner: LockOwner,
+    ) -> DispatchResult {
+        Self::ensure_not_frozen(who, &T::AccountStore::get(who), asset)?;
+        let mut locks = Self::account_locks(who);
+        Self::ensure_lock_owner(&locks, asset, id, owner, false)?;
+        locks.remove(&(asset, id));
+        Self::put_locks(who, locks);
+
+        Ok(())
+    }
+
+    fn delegate_asset_lock(
+        id: LockIdentifier,
+        who: &T::AccountId,
+        asset: Asset,
+        owner: LockOwner,
+        new_owner: LockOwner,
+    ) -> DispatchResult {
+        let mut locks = Self::account_locks(who);
+        Self::ensure_lock_owner(&locks, asset, id, owner, false)?;
+        if let Some(lock) = locks.get_mut(&(asset, id)) {
+            lock.owner = new_owner;
+        }
+        Locked::<T>::insert(who, locks);
+
+        Ok(())
+    }
+
+    fn asset_locked(who: &T::AccountId, asset: Asset) -> T::Balance {
+        let now = frame_system::Pallet::<T>::block_number();
+        Self::account_locks(who)
+            .iter()
+            .filter(|((lock_asset, _), lock)| *lock_asset == asset && lock.is_active(&now))
+            .map(|(_, lock)| lock.amount)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Bulk upgrade of `AccountData` to the latest version and of locks to the multi-asset format,
+/// accounts are also upgraded one by one on balance and lock changes
+impl<T: Config> SteppedMigration for Pallet<T> {
+    fn migrate_step(cursor: Option<Vec<u8>>, limit: u32) -> (Option<Vec<u8>>, Weight) {
+        let mut accounts = match cursor {
+            Some(cursor) => frame_system::Account::<T>::iter_keys_from(cursor),
+            None => frame_system::Account::<T>::iter_keys(),
+        };
+
+        let mut processed = 0u64;
+        let mut upgraded = 0u64;
+        for who in accounts.by_ref().take(limit as usize) {
+            processed += 1;
+            if !T::AccountStore::get(&who).is_latest() {
+                let _ = T::AccountStore::mutate(&who, |data| data.upgrade());
+                upgraded += 1;
+            }
+            if migrations::translate_locks::<T>(&who) {
+                upgraded += 1;
+            }
+        }
+
+        let next_cursor = (processed == limit as u64).then(|| accounts.last_raw_key().to_vec());
+        let weight = T::DbWeight::get().reads_writes(3 * processed + 1, upgraded);
+        (next_cursor, weight)
+    }
+}
+
+impl<T: Config> AccountMigration<T::AccountId> for Pallet<T> {
+    fn can_migrate(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        eq_ensure!(
+            Self::iterate_account_balances(from)
+                .iter()
+                .all(|(_, balance)| !matches!(balance, Negative(debt) if !debt.is_zero())),
+            Error::<T>::AccountHasDebt,
+            target: "eq_balances",
+            "{}:{}. Account has debt. Who: {:?}",
+            file!(),
+            line!(),
+            from
+        );
+        eq_ensure!(
+            Reserved::<T>::iter_prefix_values(from).all(|reserved| reserved.is_zero()),
+            Error::<T>::AccountHasReserves,
+            target: "eq_balances",
+            "{}:{}. Account has reserved balances. Who: {:?}",
+            file!(),
+            line!(),
+            from
+        );
+
+        // locked funds of other pallets would be freed on `to` while their state stays on `from`
+        let now = frame_system::Pallet::<T>::block_number();
+        let from_locks = Self::account_locks(from);
+        eq_ensure!(
+            from_locks
+                .iter()
+                .all(|(_, lock)| !lock.is_active(&now)
+                    || T::MigratableLockOwners::contains(&lock.owner)),
+            Error::<T>::LockNotMigratable,
+            target: "eq_balances",
+            "{}:{}. Account has lock of pallet not moving its state. Who: {:?}",
+            file!(),
+            line!(),
+            from
+        );
+
+        let to_locks = Self::account_locks(to);
+        for ((asset, id), lock) in from_locks.iter() {
+            Self::ensure_lock_owner(&to_locks, *asset, *id, lock.owner, true)?;
+        }
+
+        Ok(())
+    }
+
+    fn migrate(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        // locks of the same pallet are summed up, merged lock is active while any of them is
+        let mut locks = Self::account_locks(to);
+        for (key, lock) in Self::account_locks(from).iter() {
+            match locks.get_mut(key) {
+                Some(to_lock) => {
+                    to_lock.amount = to_lock.amount.saturating_add(lock.amount);
+                    to_lock.expiry = to_lock.expiry.zip(lock.expiry).map(|(a, b)| a.max(b));
+                }
+                None => {
+                    locks.insert(*key, lock.clone());
+                }
+            }
+        }
+        Self::put_locks(to, locks);
+        Self::put_locks(from, VecMap::new());
+
+        for (asset, balance) in Self::iterate_account_balances(from) {
+            if let Positive(value) = balance {
+                Self::currency_transfer(
+                    from,
+                    to,
+                    asset,
+                    value,
+                    ExistenceRequirement::AllowDeath,
+                    TransferReason::AccountMigration,
+                    false,
+                )?;
+            }
+        }
+
+        Ok(())
     }
 }
 
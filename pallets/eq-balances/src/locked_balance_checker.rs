@@ -35,28 +35,35 @@ impl<T: Config> BalanceChecker<T::Balance, T::AccountId, Pallet<T>, T::Subaccoun
         who: &T::AccountId,
         changes: &Vec<(Asset, SignedBalance<T::Balance>)>,
         withdraw_reasons: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), BalanceCheckError<T::Balance>> {
         let native_asset = T::AssetGetter::get_main_asset();
         for (asset, change) in changes.into_iter() {
-            if asset == &native_asset {
-                if withdraw_reasons
+            if asset == &native_asset
+                && withdraw_reasons
                     .unwrap_or(WithdrawReasons::empty())
                     .intersects(
                         WithdrawReasons::TRANSACTION_PAYMENT
                             | WithdrawReasons::FEE
                             | WithdrawReasons::TIP,
                     )
-                {
-                    return Ok(());
-                } else if T::SubaccountsManager::is_master(who) {
+            {
+                return Ok(());
+            } else if matches!(change, SignedBalance::Negative(_))
+                && T::SubaccountsManager::is_master(who)
+            {
+                let locked = Pallet::<T>::asset_locked(who, *asset);
+                if !locked.is_zero() {
                     let balance = Pallet::<T>::get_balance(who, asset);
-                    let locked = Pallet::<T>::get_locked(who);
-                    if !locked.is_zero() {
-                        if matches!(change, SignedBalance::Negative(_))
-                            && balance + *change < SignedBalance::Positive(locked)
-                        {
-                            frame_support::fail!(Error::<T>::Locked);
-                        }
+                    if balance + *change < SignedBalance::Positive(locked) {
+                        let available = match balance {
+                            SignedBalance::Positive(b) => b.saturating_sub(locked),
+                            SignedBalance::Negative(_) => T::Balance::zero(),
+                        };
+                        return Err(BalanceCheckError::new(
+                            BalanceCheckerKind::Locked,
+                            Error::<T>::Locked,
+                        )
+                        .with_amounts(change.abs(), available));
                     }
                 }
             }
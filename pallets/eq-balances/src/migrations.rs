@@ -0,0 +1,103 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage migrations of `eq-balances`
+
+use crate::*;
+use frame_support::{
+    storage::unhashed,
+    traits::{GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+
+/// Locks before storage version 1: main asset only, without expiry and owner
+pub type LockedV0<T> = VecMap<LockIdentifier, <T as Config>::Balance>;
+
+/// Locks since storage version 1: by asset and lock id, with expiry and owner
+pub type LockedV1<T> = VecMap<
+    (Asset, LockIdentifier),
+    AssetLock<<T as Config>::Balance, <T as frame_system::Config>::BlockNumber>,
+>;
+
+/// Starts the move of locks to the multi-asset format. Locks are translated account by
+/// account in `SteppedMigration` and on change, until then they are converted on read.
+pub fn migrate_locks<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return Weight::zero();
+    }
+
+    StorageVersion::new(1).put::<Pallet<T>>();
+    log::info!(
+        target: "eq_balances",
+        "migrate_locks: locks are translated by stepped migration"
+    );
+
+    T::DbWeight::get().reads_writes(1, 1)
+}
+
+/// Locks of `who` and whether they are stored in the format before storage version 1.
+/// Existing locks are put on the main asset, never expire and are owned by their
+/// `LockIdentifier`.
+pub(crate) fn stored_locks<T: Config>(who: &T::AccountId) -> (LockedV1<T>, bool) {
+    let raw = match unhashed::get_raw(&Locked::<T>::hashed_key_for(who)) {
+        Some(raw) => raw,
+        None => return (VecMap::new(), false),
+    };
+    if let Ok(locks) = LockedV1::<T>::decode(&mut &raw[..]) {
+        return (locks, false);
+    }
+
+    match LockedV0::<T>::decode(&mut &raw[..]) {
+        Ok(old) => {
+            let main_asset = T::AssetGetter::get_main_asset();
+            let locks = old
+                .into_iter()
+                .map(|(id, amount)| {
+                    let lock = AssetLock {
+                        amount,
+                        expiry: None,
+                        owner: id,
+                    };
+                    ((main_asset, id), lock)
+                })
+                .collect();
+            (locks, true)
+        }
+        Err(_) => {
+            log::error!(
+                target: "eq_balances",
+                "{}:{}. Locks can't be decoded. Who: {:?}",
+                file!(),
+                line!(),
+                who
+            );
+            (VecMap::new(), false)
+        }
+    }
+}
+
+/// Stores locks of `who` in the current format, returns `true` if they were translated
+pub(crate) fn translate_locks<T: Config>(who: &T::AccountId) -> bool {
+    match stored_locks::<T>(who) {
+        (locks, true) => {
+            Locked::<T>::insert(who, locks);
+            true
+        }
+        _ => false,
+    }
+}
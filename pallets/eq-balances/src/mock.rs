@@ -22,13 +22,12 @@ use super::*;
 
 use crate as eq_balances;
 use eq_primitives::{
-    asset, asset::AssetType, balance_number::EqFixedU128, AccountDistribution, Aggregates,
-    TotalAggregates, UserGroup,
+    asset, asset::AssetType, AccountDistribution, Aggregates, TotalAggregates, UserGroup,
 };
 use frame_support::{
     pallet_prelude::DispatchResult,
     parameter_types,
-    traits::{GenesisBuild, OnUnbalanced},
+    traits::{EnsureOrigin, GenesisBuild, OnUnbalanced},
     weights::Weight,
     PalletId,
 };
@@ -37,10 +36,14 @@ use sp_core::H256;
 use sp_runtime::{
     generic::Header,
     traits::{BlakeTwo256, IdentityLookup},
-    FixedI64, FixedPointNumber, Perbill, Percent,
+    Perbill, Percent,
 };
 use std::cell::RefCell;
 use std::marker::PhantomData;
+use test_utils::{
+    builder::{AssetConfig, ExtBuilder},
+    mock::{subaccounts::SubaccountsManagerMock, time::TimeMock},
+};
 
 pub(crate) type Balance = eq_primitives::balance::Balance;
 
@@ -143,53 +146,17 @@ impl BalanceChecker<Balance, u64, EqBalances, SubaccountsManagerMock> for Balanc
         who: &u64,
         _change: &Vec<(asset::Asset, SignedBalance<Balance>)>,
         _: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), BalanceCheckError<Balance>> {
         match who {
-            &FAIL_ACC => Err(DispatchError::Other("Expected error")),
+            &FAIL_ACC => Err(BalanceCheckError::new(
+                BalanceCheckerKind::Other,
+                DispatchError::Other("Expected error"),
+            )),
             _ => Ok(()),
         }
     }
 }
 
-pub struct SubaccountsManagerMock;
-
-impl SubaccountsManager<AccountId> for SubaccountsManagerMock {
-    fn create_subaccount_inner(
-        _who: &AccountId,
-        _subacc_type: &SubAccType,
-    ) -> Result<AccountId, DispatchError> {
-        Ok(9999_u64)
-    }
-
-    fn delete_subaccount_inner(
-        _who: &AccountId,
-        _subacc_type: &SubAccType,
-    ) -> Result<AccountId, DispatchError> {
-        Ok(9999_u64)
-    }
-
-    fn has_subaccount(_who: &AccountId, _subacc_type: &SubAccType) -> bool {
-        true
-    }
-
-    fn get_subaccount_id(_who: &AccountId, subacc_type: &SubAccType) -> Option<AccountId> {
-        match subacc_type {
-            SubAccType::Borrower => Some(9999_u64),
-            _ => None,
-        }
-    }
-
-    fn is_subaccount(_who: &AccountId, _subacc_id: &AccountId) -> bool {
-        false
-    }
-    fn get_owner_id(_subaccount: &AccountId) -> Option<(AccountId, SubAccType)> {
-        None
-    }
-    fn get_subaccounts_amount(_who: &AccountId) -> usize {
-        0
-    }
-}
-
 pub struct BailsmanManagerMock;
 
 impl BailsmanManager<AccountId, Balance> for BailsmanManagerMock {
@@ -243,23 +210,19 @@ impl eq_assets::Config for Test {
     type WeightInfo = ();
 }
 
-thread_local! {
-    pub static UNIX_NOW: RefCell<u64> = RefCell::new(0);
-}
-
-pub struct TimeMock;
+/// Root origin responds as relay chain
+pub struct EnsureResponderMock;
+impl EnsureOrigin<RuntimeOrigin> for EnsureResponderMock {
+    type Success = MultiLocation;
 
-impl TimeMock {
-    #[allow(dead_code)]
-    pub fn set_secs(secs: u64) {
-        UNIX_NOW.with(|now| *now.borrow_mut() = secs)
+    fn try_origin(o: RuntimeOrigin) -> Result<MultiLocation, RuntimeOrigin> {
+        EnsureRoot::<AccountId>::try_origin(o).map(|_| MultiLocation::parent())
     }
 }
 
-impl UnixTime for TimeMock {
-    fn now() -> core::time::Duration {
-        UNIX_NOW.with(|now| core::time::Duration::from_secs(*now.borrow()))
-    }
+parameter_types! {
+    pub const XcmRefundTimeout: u32 = 10;
+    pub const MigratableLockOwner: LockOwner = *b"owner   ";
 }
 
 impl Config for Test {
@@ -287,6 +250,10 @@ impl Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = ();
     type XcmToFee = ();
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = EnsureResponderMock;
+    type XcmRefundTimeout = XcmRefundTimeout;
+    type MigratableLockOwners = frame_support::traits::Equals<MigratableLockOwner>;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -370,17 +337,52 @@ pub const XDOT1: Asset = Asset(2002941027);
 pub const XDOT2: Asset = Asset(6450786);
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    OracleMock::init(vec![
-        (asset::BTC, FixedI64::saturating_from_integer(10)),
-        (asset::EOS, FixedI64::saturating_from_integer(10)),
-        (asset::ETH, FixedI64::saturating_from_integer(10)),
-        (asset::EQD, FixedI64::saturating_from_integer(10)),
-        (asset::EQ, FixedI64::saturating_from_integer(10)),
-        (asset::DOT, FixedI64::saturating_from_integer(10)),
-        (asset::CRV, FixedI64::saturating_from_integer(10)),
-        (XDOT1, FixedI64::saturating_from_integer(1)),
-        (XDOT2, FixedI64::saturating_from_integer(1)),
-    ]);
+    let builder = ExtBuilder::default()
+        .asset(AssetConfig::new(asset::EQD, AssetType::Synthetic, 1).price(10))
+        .asset(AssetConfig::new(asset::BTC, AssetType::Physical, 2).price(10))
+        .asset(AssetConfig::new(asset::ETH, AssetType::Physical, 3).price(10))
+        .asset(AssetConfig::new(asset::DOT, AssetType::Physical, 4).price(10))
+        .asset(AssetConfig::new(asset::CRV, AssetType::Physical, 5).price(10))
+        .asset(AssetConfig::new(asset::EOS, AssetType::Physical, 6).price(10))
+        .asset(
+            AssetConfig::new(asset::EQ, AssetType::Physical, u64::MAX)
+                .debt_weight(Permill::zero())
+                .price(10),
+        )
+        .asset(AssetConfig::new(asset::Q, AssetType::Native, u64::MAX))
+        .asset(
+            AssetConfig::new(XDOT1, AssetType::Physical, 10)
+                .debt_weight(Permill::zero())
+                .collateral_discount(Percent::zero())
+                .price(1),
+        )
+        .asset(
+            AssetConfig::new(XDOT2, AssetType::Physical, 11)
+                .debt_weight(Permill::zero())
+                .collateral_discount(Percent::from_rational(5u32, 10u32))
+                .price(1),
+        )
+        .asset(AssetConfig::new(asset::XDOT, AssetType::Physical, 12).debt_weight(Permill::zero()))
+        .asset(AssetConfig::new(asset::XDOT2, AssetType::Physical, 12).debt_weight(Permill::zero()))
+        .asset(AssetConfig::new(asset::XDOT3, AssetType::Physical, 12).debt_weight(Permill::zero()))
+        .asset(
+            AssetConfig::new(asset::CDOT613, AssetType::Physical, 13).debt_weight(Permill::zero()),
+        )
+        .asset(
+            AssetConfig::new(asset::CDOT714, AssetType::Physical, 13).debt_weight(Permill::zero()),
+        )
+        .asset(
+            AssetConfig::new(asset::CDOT815, AssetType::Physical, 13).debt_weight(Permill::zero()),
+        )
+        .balance(1, asset::BTC, 1000_000_000_000)
+        .balance(2, asset::BTC, 2000_000_000_000)
+        .balance(10, asset::EQD, 10_000_000_000)
+        .balance(20, asset::EQD, 20_000_000_000)
+        .balance(30, asset::EQD, 30_000_000_000)
+        .balance(15, asset::EQ, 15)
+        .balance(16, asset::EQ, 13)
+        .balance(17, asset::EQ, 20);
+    builder.init_mocks();
 
     let mut t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
@@ -388,254 +390,18 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
     eq_assets::GenesisConfig::<Test> {
         _runtime: PhantomData,
-        assets: // id, lot, price_step, maker_fee, taker_fee, debt_weight, buyout_priority
-        vec![
-            (
-                asset::EQD.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                1,
-                AssetType::Synthetic,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::BTC.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                2,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::ETH.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                3,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::DOT.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                4,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::CRV.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                5,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::EOS.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::from_rational(2u32, 5u32),
-                6,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::EQ.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                u64::MAX,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::Q.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                u64::MAX,
-                AssetType::Native,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                XDOT1.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                10,
-                AssetType::Physical,
-                true,
-                Percent::zero(),
-                Permill::one(),
-            ),
-            (
-                XDOT2.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                11,
-                AssetType::Physical,
-                true,
-                Percent::from_rational(5u32, 10u32),
-                Permill::one(),
-            ),
-            (
-                asset::XDOT.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                12,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::XDOT2.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                12,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::XDOT3.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                12,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::CDOT613.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                13,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::CDOT714.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                13,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            ),
-            (
-                asset::CDOT815.get_id(),
-                EqFixedU128::from(0),
-                FixedI64::from(0),
-                Permill::zero(),
-                Permill::zero(),
-                vec![],
-                Permill::zero(),
-                13,
-                AssetType::Physical,
-                true,
-                Percent::one(),
-                Permill::one(),
-            )
-        ],
+        assets: builder.assets_genesis(),
     }
     .assimilate_storage(&mut t)
     .unwrap();
 
     eq_balances::GenesisConfig::<Test> {
-        balances: vec![
-            (1, vec![(1000_000_000_000 as u128, asset::BTC.get_id())]),
-            (2, vec![(2000_000_000_000 as u128, asset::BTC.get_id())]),
-            (10, vec![(10_000_000_000 as u128, asset::EQD.get_id())]),
-            (20, vec![(20_000_000_000 as u128, asset::EQD.get_id())]),
-            (30, vec![(30_000_000_000 as u128, asset::EQD.get_id())]),
-            (15, vec![(15 as u128, asset::EQ.get_id())]),
-            (16, vec![(13 as u128, asset::EQ.get_id())]),
-            (17, vec![(20 as u128, asset::EQ.get_id())]),
-        ],
+        balances: builder.balances_genesis(),
         is_transfers_enabled: true,
         is_xcm_enabled: Some(XcmMode::Xcm(true)),
     }
     .assimilate_storage(&mut t)
     .unwrap();
 
-    // pub balances: Vec<(T::AccountId, Vec<(T::Balance, u64)>)>,
     t.into()
 }
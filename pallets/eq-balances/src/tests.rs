@@ -1013,6 +1013,42 @@ fn delete_account() {
     });
 }
 
+#[test]
+fn delete_account_with_asset_existential_deposit() {
+    new_test_ext().execute_with(|| {
+        let account_id = 30; // has 30_000_000_000 EQD
+        let account_id_2 = 20;
+
+        // 1000 EQD left worth 10_000, ExistentialDeposit - 20
+        assert_ok!(ModuleBalances::transfer(
+            RuntimeOrigin::signed(account_id),
+            EQD,
+            account_id_2,
+            29_999_999_000
+        ));
+        assert_eq!(ModuleBalances::can_be_deleted(&account_id).unwrap(), false);
+
+        assert_noop!(
+            eq_assets::Pallet::<Test>::set_existential_deposit(
+                RuntimeOrigin::signed(account_id),
+                EQD,
+                Some(10_000)
+            ),
+            BadOrigin
+        );
+        assert_ok!(eq_assets::Pallet::<Test>::set_existential_deposit(
+            RawOrigin::Root.into(),
+            EQD,
+            Some(10_000)
+        ));
+
+        // EQD existential deposit is worth 100_000 now
+        assert_eq!(ModuleBalances::can_be_deleted(&account_id).unwrap(), true);
+        assert_ok!(ModuleBalances::delete_account(&account_id));
+        assert!(frame_system::Pallet::<Test>::providers(&account_id) == 0);
+    });
+}
+
 #[test]
 fn exchange() {
     new_test_ext().execute_with(|| {
@@ -1183,6 +1219,120 @@ fn get_total_debt_and_collateral_when_price_getter_return_error_should_fail() {
     })
 }
 
+#[test]
+fn account_migration_moves_balances_and_locks() {
+    new_test_ext().execute_with(|| {
+        let (from, to) = (&0, &1);
+        let id = *b"asset_lk";
+        let owner = *b"owner   ";
+        frame_system::Pallet::<Test>::set_block_number(1);
+        assert_ok!(ModuleBalances::deposit_creating(
+            from,
+            BTC,
+            10 * ONE_TOKEN,
+            true,
+            None
+        ));
+        assert_ok!(ModuleBalances::deposit_creating(
+            from,
+            EQD,
+            5 * ONE_TOKEN,
+            true,
+            None
+        ));
+        assert_ok!(ModuleBalances::deposit_creating(
+            to, BTC, ONE_TOKEN, true, None
+        ));
+        assert_ok!(ModuleBalances::set_asset_lock(
+            id,
+            from,
+            BTC,
+            4 * ONE_TOKEN,
+            Some(10),
+            owner
+        ));
+        assert_ok!(ModuleBalances::set_asset_lock(
+            id,
+            to,
+            BTC,
+            ONE_TOKEN,
+            Some(20),
+            owner
+        ));
+
+        // lock of another pallet with the same id can't be merged
+        assert_ok!(ModuleBalances::set_asset_lock(
+            id,
+            &2,
+            BTC,
+            ONE_TOKEN,
+            None,
+            *b"other   "
+        ));
+        assert_err!(
+            ModuleBalances::can_migrate(from, &2),
+            Error::<Test>::LockOwnerMismatch
+        );
+
+        assert_ok!(ModuleBalances::reserve(from, EQD, ONE_TOKEN));
+        assert_err!(
+            ModuleBalances::can_migrate(from, to),
+            Error::<Test>::AccountHasReserves
+        );
+        ModuleBalances::unreserve(from, EQD, ONE_TOKEN);
+
+        assert_ok!(ModuleBalances::can_migrate(from, to));
+        assert_ok!(ModuleBalances::migrate(from, to));
+
+        assert_balance!(*from, 0, 0, BTC);
+        assert_balance!(*from, 0, 0, EQD);
+        assert_balance!(*to, 11 * ONE_TOKEN, 0, BTC);
+        assert_balance!(*to, 5 * ONE_TOKEN, 0, EQD);
+        assert!(Locked::<Test>::get(from).is_empty());
+        assert_eq!(
+            Locked::<Test>::get(to).get(&(BTC, id)),
+            Some(&AssetLock {
+                amount: 5 * ONE_TOKEN,
+                expiry: Some(20),
+                owner,
+            })
+        );
+    });
+}
+
+#[test]
+fn account_migration_rejects_active_locks_of_not_migrating_pallets() {
+    new_test_ext().execute_with(|| {
+        let (from, to) = (&0, &1);
+        let id = *b"democrac";
+        frame_system::Pallet::<Test>::set_block_number(1);
+        assert_ok!(ModuleBalances::deposit_creating(
+            from,
+            BTC,
+            10 * ONE_TOKEN,
+            true,
+            None
+        ));
+        assert_ok!(ModuleBalances::set_asset_lock(
+            id,
+            from,
+            BTC,
+            4 * ONE_TOKEN,
+            Some(10),
+            id
+        ));
+
+        assert_err!(
+            ModuleBalances::can_migrate(from, to),
+            Error::<Test>::LockNotMigratable
+        );
+
+        // expired lock doesn't hold any state
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_ok!(ModuleBalances::can_migrate(from, to));
+    });
+}
+
 #[test]
 fn reserve_should_reduce_account_balance() {
     new_test_ext().execute_with(|| {
@@ -1282,6 +1432,108 @@ fn reserve_and_slash_reserved() {
     });
 }
 
+#[test]
+fn named_reserve_unreserve_and_slash() {
+    new_test_ext().execute_with(|| {
+        let acc1 = &0;
+        let id_1 = [1u8; 8];
+        let id_2 = [2u8; 8];
+        let initial_balance = 100 * ONE_TOKEN;
+
+        assert_ok!(ModuleBalances::deposit_creating(
+            acc1,
+            EQD,
+            initial_balance,
+            true,
+            None
+        ));
+
+        assert_ok!(ModuleBalances::reserve_named(
+            &id_1,
+            acc1,
+            EQD,
+            20 * ONE_TOKEN
+        ));
+        assert_ok!(ModuleBalances::reserve_named(
+            &id_2,
+            acc1,
+            EQD,
+            10 * ONE_TOKEN
+        ));
+        assert_eq!(Reserved::<Test>::get(acc1, EQD), 30 * ONE_TOKEN);
+        assert_eq!(
+            ModuleBalances::reserved_balance_named(&id_1, acc1, EQD),
+            20 * ONE_TOKEN
+        );
+
+        // only reserved under id_1 could be unreserved
+        assert_eq!(
+            ModuleBalances::unreserve_named(&id_1, acc1, EQD, 25 * ONE_TOKEN),
+            5 * ONE_TOKEN
+        );
+        assert_eq!(ModuleBalances::reserved_balance_named(&id_1, acc1, EQD), 0);
+        assert_eq!(Reserved::<Test>::get(acc1, EQD), 10 * ONE_TOKEN);
+
+        let (imbalance, remaining) =
+            ModuleBalances::slash_reserved_named(&id_2, acc1, EQD, 15 * ONE_TOKEN);
+        assert_eq!(remaining, 5 * ONE_TOKEN);
+        SlashMock::on_unbalanced(imbalance);
+        assert_eq!(SlashMock::balance(), 10 * ONE_TOKEN);
+        assert_eq!(Reserved::<Test>::get(acc1, EQD), 0);
+        assert_eq!(NamedReserves::<Test>::get(acc1, EQD), None);
+        assert_eq!(
+            ModuleBalances::get_balance(acc1, &EQD),
+            SignedBalance::Positive(initial_balance - 10 * ONE_TOKEN)
+        );
+    });
+}
+
+#[test]
+fn repatriate_reserved_named_to_reserved_keeps_id() {
+    new_test_ext().execute_with(|| {
+        let acc1 = &0;
+        let acc2 = &1;
+        let id = [1u8; 8];
+        let initial_balance = 100 * ONE_TOKEN;
+
+        assert_ok!(ModuleBalances::deposit_creating(
+            acc1,
+            EQD,
+            initial_balance,
+            true,
+            None
+        ));
+        assert_ok!(ModuleBalances::reserve_named(
+            &id,
+            acc1,
+            EQD,
+            20 * ONE_TOKEN
+        ));
+        // not named reserve is not touched
+        assert_ok!(ModuleBalances::reserve(acc1, EQD, 10 * ONE_TOKEN));
+
+        assert_eq!(
+            ModuleBalances::repatriate_reserved_named(
+                &id,
+                acc1,
+                acc2,
+                EQD,
+                25 * ONE_TOKEN,
+                BalanceStatus::Reserved
+            ),
+            Ok(5 * ONE_TOKEN)
+        );
+
+        assert_eq!(ModuleBalances::reserved_balance_named(&id, acc1, EQD), 0);
+        assert_eq!(Reserved::<Test>::get(acc1, EQD), 10 * ONE_TOKEN);
+        assert_eq!(
+            ModuleBalances::reserved_balance_named(&id, acc2, EQD),
+            20 * ONE_TOKEN
+        );
+        assert_eq!(Reserved::<Test>::get(acc2, EQD), 20 * ONE_TOKEN);
+    });
+}
+
 #[test]
 fn unreserve_should_increase_account_balance() {
     new_test_ext().execute_with(|| {
@@ -1744,3 +1996,823 @@ fn locked_balance_remove_lock() {
         ));
     });
 }
+
+#[test]
+fn can_transfer_dry_run() {
+    new_test_ext().execute_with(|| {
+        let account_id_1: u64 = 1;
+        let account_id_2: u64 = 2;
+
+        assert_ok!(ModuleBalances::deposit_creating(
+            &FAIL_ACC, BTC, 100, false, None
+        ));
+
+        assert_ok!(ModuleBalances::disable_transfers(RawOrigin::Root.into()));
+        assert_eq!(
+            ModuleBalances::can_transfer(&account_id_1, &account_id_2, EQD, 10),
+            TransferCheck::Rejected(Error::<Test>::TransfersAreDisabled.into())
+        );
+
+        assert_ok!(ModuleBalances::enable_transfers(RawOrigin::Root.into()));
+        assert_eq!(
+            ModuleBalances::can_transfer(&account_id_1, &account_id_2, EQD, 10),
+            TransferCheck::Allowed
+        );
+        assert_eq!(
+            ModuleBalances::can_transfer(&FAIL_ACC, &account_id_1, BTC, 10),
+            TransferCheck::CheckFailed {
+                side: TransferSide::Sender,
+                checker: BalanceCheckerKind::Other,
+                error: DispatchError::Other("Expected error"),
+                margin: None,
+            }
+        );
+        assert_eq!(
+            ModuleBalances::can_transfer(&account_id_1, &FAIL_ACC, EQD, 10),
+            TransferCheck::CheckFailed {
+                side: TransferSide::Receiver,
+                checker: BalanceCheckerKind::Other,
+                error: DispatchError::Other("Expected error"),
+                margin: None,
+            }
+        );
+
+        // dry run doesn't change balances
+        assert_balance!(FAIL_ACC, 100, 0, BTC);
+        assert_balance!(account_id_1, 0, 0, BTC);
+    });
+}
+
+#[test]
+fn settle_netted_deltas() {
+    new_test_ext().execute_with(|| {
+        let settler: u64 = 1;
+        let account_id_10: u64 = 10;
+        let account_id_20: u64 = 20;
+        let account_id_30: u64 = 30;
+
+        assert_err!(
+            ModuleBalances::settle(RuntimeOrigin::signed(settler), vec![]),
+            Error::<Test>::NotSettlementAccount
+        );
+        assert_err!(
+            ModuleBalances::set_settlement_account(RuntimeOrigin::signed(settler), settler, true),
+            BadOrigin
+        );
+        assert_ok!(ModuleBalances::set_settlement_account(
+            RawOrigin::Root.into(),
+            settler,
+            true
+        ));
+        assert_ok!(ModuleBalances::authorize_settler(
+            RuntimeOrigin::signed(account_id_10),
+            settler,
+            true
+        ));
+
+        assert_noop!(
+            ModuleBalances::settle(
+                RuntimeOrigin::signed(settler),
+                vec![
+                    (account_id_10, EQD, SignedBalance::Negative(5_000_000_000)),
+                    (account_id_20, EQD, SignedBalance::Positive(4_000_000_000)),
+                ]
+            ),
+            Error::<Test>::SettlementNotBalanced
+        );
+
+        assert_ok!(ModuleBalances::deposit_creating(
+            &FAIL_ACC, EQD, 100, false, None
+        ));
+        assert_noop!(
+            ModuleBalances::settle(
+                RuntimeOrigin::signed(settler),
+                vec![
+                    (account_id_10, EQD, SignedBalance::Negative(5_000_000_000)),
+                    (FAIL_ACC, EQD, SignedBalance::Positive(5_000_000_000)),
+                ]
+            ),
+            DispatchError::Other("Expected error")
+        );
+
+        // offsetting changes of the same account are netted
+        assert_ok!(ModuleBalances::settle(
+            RuntimeOrigin::signed(settler),
+            vec![
+                (account_id_10, EQD, SignedBalance::Negative(15_000_000_000)),
+                (account_id_20, EQD, SignedBalance::Positive(3_000_000_000)),
+                (account_id_30, EQD, SignedBalance::Positive(2_000_000_000)),
+                (account_id_20, EQD, SignedBalance::Negative(1_000_000_000)),
+                (account_id_10, EQD, SignedBalance::Positive(11_000_000_000)),
+            ]
+        ));
+        assert_balance!(account_id_10, 6_000_000_000, 0, EQD);
+        assert_balance!(account_id_20, 22_000_000_000, 0, EQD);
+        assert_balance!(account_id_30, 32_000_000_000, 0, EQD);
+
+        assert_ok!(ModuleBalances::set_settlement_account(
+            RawOrigin::Root.into(),
+            settler,
+            false
+        ));
+        assert_err!(
+            ModuleBalances::settle(RuntimeOrigin::signed(settler), vec![]),
+            Error::<Test>::NotSettlementAccount
+        );
+    });
+}
+
+#[test]
+fn settle_decreases_balances_only_of_accounts_authorized_settler() {
+    new_test_ext().execute_with(|| {
+        let settler: u64 = 1;
+        let account_id_10: u64 = 10;
+        let account_id_20: u64 = 20;
+        let deltas = vec![
+            (account_id_10, EQD, SignedBalance::Negative(1_000_000_000)),
+            (account_id_20, EQD, SignedBalance::Positive(1_000_000_000)),
+        ];
+
+        assert_ok!(ModuleBalances::set_settlement_account(
+            RawOrigin::Root.into(),
+            settler,
+            true
+        ));
+        assert_noop!(
+            ModuleBalances::settle(RuntimeOrigin::signed(settler), deltas.clone()),
+            Error::<Test>::SettlerNotAuthorized
+        );
+
+        // authorization of another account doesn't matter
+        assert_ok!(ModuleBalances::authorize_settler(
+            RuntimeOrigin::signed(account_id_20),
+            settler,
+            true
+        ));
+        assert_noop!(
+            ModuleBalances::settle(RuntimeOrigin::signed(settler), deltas.clone()),
+            Error::<Test>::SettlerNotAuthorized
+        );
+
+        assert_ok!(ModuleBalances::authorize_settler(
+            RuntimeOrigin::signed(account_id_10),
+            settler,
+            true
+        ));
+        assert_ok!(ModuleBalances::settle(
+            RuntimeOrigin::signed(settler),
+            deltas.clone()
+        ));
+        assert_balance!(account_id_10, 9_000_000_000, 0, EQD);
+        assert_balance!(account_id_20, 21_000_000_000, 0, EQD);
+
+        assert_ok!(ModuleBalances::authorize_settler(
+            RuntimeOrigin::signed(account_id_10),
+            settler,
+            false
+        ));
+        assert!(!SettlerAuthorizations::<Test>::contains_key(
+            account_id_10,
+            settler
+        ));
+        assert_noop!(
+            ModuleBalances::settle(RuntimeOrigin::signed(settler), deltas),
+            Error::<Test>::SettlerNotAuthorized
+        );
+    });
+}
+
+#[test]
+fn failed_balance_check_is_traced() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+
+        // deposit is skipped, but the failed checker is reported
+        assert_ok!(ModuleBalances::deposit_creating(
+            &FAIL_ACC, EQD, 100, true, None
+        ));
+        assert_balance!(FAIL_ACC, 0, 0, EQD);
+        assert!(frame_system::Pallet::<Test>::events()
+            .iter()
+            .any(|r| r.event
+                == crate::mock::RuntimeEvent::EqBalances(Event::BalanceCheckFailed(
+                    FAIL_ACC,
+                    BalanceCheckerKind::Other,
+                    0,
+                    0
+                ))));
+    });
+}
+
+#[test]
+fn asset_lock_expiry_and_owner() {
+    new_test_ext().execute_with(|| {
+        let acc1 = &0;
+        let id = *b"asset_lk";
+        let owner = *b"owner   ";
+        let other = *b"other   ";
+        frame_system::Pallet::<Test>::set_block_number(1);
+        assert_ok!(ModuleBalances::deposit_creating(
+            acc1,
+            BTC,
+            10 * ONE_TOKEN,
+            true,
+            None
+        ));
+
+        assert_ok!(ModuleBalances::set_asset_lock(
+            id,
+            acc1,
+            BTC,
+            4 * ONE_TOKEN,
+            Some(10),
+            owner
+        ));
+        assert_eq!(ModuleBalances::asset_locked(acc1, BTC), 4 * ONE_TOKEN);
+        assert_eq!(
+            ModuleBalances::get_asset_lock(*acc1, BTC, id),
+            4 * ONE_TOKEN
+        );
+        assert_eq!(ModuleBalances::asset_locked(acc1, Q), 0);
+        assert_err!(
+            ModuleBalances::ensure_can_withdraw(
+                acc1,
+                BTC,
+                6 * ONE_TOKEN + 1,
+                WithdrawReasons::TRANSFER,
+                0
+            ),
+            Error::<Test>::Locked,
+        );
+
+        // only the owner may change the lock
+        assert_err!(
+            ModuleBalances::set_asset_lock(id, acc1, BTC, 0, None, other),
+            Error::<Test>::LockOwnerMismatch
+        );
+        assert_err!(
+            ModuleBalances::remove_asset_lock(id, acc1, BTC, other),
+            Error::<Test>::LockOwnerMismatch
+        );
+        assert_err!(
+            ModuleBalances::remove_asset_lock(*b"unknown ", acc1, BTC, owner),
+            Error::<Test>::LockNotFound
+        );
+
+        assert_ok!(ModuleBalances::delegate_asset_lock(
+            id, acc1, BTC, owner, other
+        ));
+        assert_err!(
+            ModuleBalances::remove_asset_lock(id, acc1, BTC, owner),
+            Error::<Test>::LockOwnerMismatch
+        );
+
+        // expired lock doesn't restrict withdrawals
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_eq!(ModuleBalances::asset_locked(acc1, BTC), 0);
+        assert_ok!(ModuleBalances::ensure_can_withdraw(
+            acc1,
+            BTC,
+            6 * ONE_TOKEN + 1,
+            WithdrawReasons::TRANSFER,
+            0
+        ));
+
+        assert_ok!(ModuleBalances::remove_asset_lock(id, acc1, BTC, other));
+        assert!(Locked::<Test>::get(acc1).is_empty());
+    });
+}
+
+#[test]
+fn expired_lock_is_not_cached() {
+    new_test_ext().execute_with(|| {
+        let acc1 = &1;
+        let main_asset = <Test as Config>::AssetGetter::get_main_asset();
+        frame_system::Pallet::<Test>::set_block_number(1);
+
+        assert_ok!(ModuleBalances::set_asset_lock(
+            *b"expiring",
+            acc1,
+            main_asset,
+            5 * ONE_TOKEN,
+            Some(10),
+            *b"owner   "
+        ));
+        assert_eq!(
+            *<Test as Config>::AccountStore::get(acc1).lock(),
+            5 * ONE_TOKEN
+        );
+
+        frame_system::Pallet::<Test>::set_block_number(11);
+        ModuleBalances::set_lock(*b"lasting ", acc1, 2 * ONE_TOKEN);
+        assert_eq!(
+            *<Test as Config>::AccountStore::get(acc1).lock(),
+            2 * ONE_TOKEN
+        );
+    });
+}
+
+#[test]
+fn locks_in_old_format_are_read_and_migrated() {
+    new_test_ext().execute_with(|| {
+        let acc1 = 1;
+        let id = *b"old_lock";
+        let main_asset = <Test as Config>::AssetGetter::get_main_asset();
+        let old: migrations::LockedV0<Test> = vec![(id, 3 * ONE_TOKEN)].into_iter().collect();
+        frame_support::storage::unhashed::put(&Locked::<Test>::hashed_key_for(acc1), &old);
+
+        // converted on read before the migration reaches the account
+        assert_eq!(ModuleBalances::get_lock(acc1, id), 3 * ONE_TOKEN);
+        assert!(Locked::<Test>::try_get(acc1).is_err());
+
+        let mut cursor = ModuleBalances::migrate_step(None, 1).0;
+        while let Some(next) = cursor {
+            cursor = ModuleBalances::migrate_step(Some(next), 1).0;
+        }
+        assert_eq!(
+            Locked::<Test>::get(acc1).get(&(main_asset, id)),
+            Some(&AssetLock {
+                amount: 3 * ONE_TOKEN,
+                expiry: None,
+                owner: id,
+            })
+        );
+    });
+}
+
+#[test]
+fn xcm_fee_table_overrides_fallback() {
+    new_test_ext().execute_with(|| {
+        let destination = MultiLocation::parent();
+        let xcm = Xcm::<()>(vec![ClearOrigin, ClearOrigin, ClearOrigin, ClearOrigin]);
+        let params = XcmFeeParams {
+            fee_asset: DOT,
+            base_xcm_weight: 250_000_000,
+            units_per_second: 1_000_000_000,
+        };
+
+        // mock `XcmToFee` knows no destinations
+        assert_eq!(ModuleBalances::xcm_to_fee(BTC, &destination, &xcm), None);
+
+        assert_noop!(
+            ModuleBalances::set_xcm_fee(RuntimeOrigin::signed(1), destination, BTC, Some(params)),
+            BadOrigin
+        );
+        assert_ok!(ModuleBalances::set_xcm_fee(
+            RawOrigin::Root.into(),
+            destination,
+            BTC,
+            Some(params)
+        ));
+        // 4 instructions of 0.25 sec, doubled
+        assert_eq!(
+            ModuleBalances::xcm_to_fee(BTC, &destination, &xcm),
+            Some((DOT, 2_000_000))
+        );
+        assert_eq!(ModuleBalances::xcm_to_fee(ETH, &destination, &xcm), None);
+
+        assert_ok!(ModuleBalances::set_xcm_weight_fee(
+            RawOrigin::Root.into(),
+            DOT,
+            Some(1_000)
+        ));
+        assert_eq!(
+            XcmWeightToFee::<Test, ()>::convert((DOT, XcmWeight::from_parts(500_000_000_000, 0))),
+            Some(500)
+        );
+        assert_eq!(
+            XcmWeightToFee::<Test, ()>::convert((BTC, XcmWeight::from_parts(500_000_000_000, 0))),
+            None
+        );
+
+        assert_ok!(ModuleBalances::set_xcm_fee(
+            RawOrigin::Root.into(),
+            destination,
+            BTC,
+            None
+        ));
+        assert_eq!(ModuleBalances::xcm_to_fee(BTC, &destination, &xcm), None);
+    });
+}
+
+#[test]
+fn validate_destination_checks_beneficiary_format() {
+    new_test_ext().execute_with(|| {
+        use xcm::v3::{Junction::AccountKey20, Junctions::X2};
+
+        assert_err!(
+            ModuleBalances::validate_destination(BTC, AccountType::Key20([1; 20])),
+            Error::<Test>::XcmUnknownAsset
+        );
+
+        // asset native for Moonbeam, an EVM chain
+        assert_ok!(eq_assets::Pallet::<Test>::do_update_asset(
+            BTC,
+            None,
+            None,
+            None,
+            None,
+            Some(AssetXcmData::OtherReserved(OtherReservedData {
+                multi_location: MultiLocation::new(1, X2(Parachain(2004), PalletInstance(10))),
+                decimals: 18,
+            })),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ));
+
+        assert_err!(
+            ModuleBalances::validate_destination(BTC, AccountType::Id32([1; 32])),
+            Error::<Test>::XcmInvalidBeneficiary
+        );
+        assert_err!(
+            ModuleBalances::validate_destination(BTC, AccountType::Key20([0; 20])),
+            Error::<Test>::XcmInvalidBeneficiary
+        );
+        assert_eq!(
+            ModuleBalances::validate_destination(BTC, AccountType::Key20([1; 20])),
+            Ok(MultiLocation::new(
+                1,
+                X2(
+                    Parachain(2004),
+                    AccountKey20 {
+                        network: None,
+                        key: [1; 20]
+                    }
+                )
+            ))
+        );
+    });
+}
+
+#[test]
+fn asset_hub_asset_fee_modes() {
+    new_test_ext().execute_with(|| {
+        let xcm = Xcm::<()>(vec![ClearOrigin, ClearOrigin, ClearOrigin, ClearOrigin]);
+        let relay_token = AssetHubAsset {
+            general_index: 30,
+            fee_mode: AssetHubFeeMode::RelayToken(DOT),
+        };
+        let sufficient = AssetHubAsset {
+            general_index: 30,
+            fee_mode: AssetHubFeeMode::Sufficient(1_000_000_000_000),
+        };
+
+        assert_noop!(
+            ModuleBalances::set_asset_hub_asset(RawOrigin::Root.into(), BTC, Some(relay_token)),
+            Error::<Test>::XcmUnknownAsset
+        );
+
+        assert_ok!(eq_assets::Pallet::<Test>::do_update_asset(
+            BTC,
+            None,
+            None,
+            None,
+            None,
+            Some(AssetXcmData::OtherReserved(OtherReservedData {
+                multi_location: relay_token.multi_location(),
+                decimals: 6,
+            })),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ));
+
+        assert_noop!(
+            ModuleBalances::set_asset_hub_asset(RuntimeOrigin::signed(1), BTC, Some(relay_token)),
+            BadOrigin
+        );
+        assert_noop!(
+            ModuleBalances::set_asset_hub_asset(
+                RawOrigin::Root.into(),
+                BTC,
+                Some(AssetHubAsset {
+                    general_index: 31,
+                    ..relay_token
+                })
+            ),
+            Error::<Test>::AssetHubLocationMismatch
+        );
+
+        assert_ok!(ModuleBalances::set_asset_hub_asset(
+            RawOrigin::Root.into(),
+            BTC,
+            Some(relay_token)
+        ));
+        assert_eq!(
+            ModuleBalances::xcm_to_fee(BTC, &PARACHAIN_STATEMINT, &xcm),
+            Some((DOT, eq_xcm::fees::statemint::XcmToFee::convert(&xcm)))
+        );
+        // other destinations still use `T::XcmToFee`
+        assert_eq!(
+            ModuleBalances::xcm_to_fee(BTC, &MultiLocation::parent(), &xcm),
+            None
+        );
+        assert_eq!(
+            XcmWeightToFee::<Test, ()>::convert((BTC, XcmWeight::from_parts(500_000_000_000, 0))),
+            None
+        );
+
+        assert_ok!(ModuleBalances::set_asset_hub_asset(
+            RawOrigin::Root.into(),
+            BTC,
+            Some(sufficient)
+        ));
+        // 4 instructions of 1 ms, doubled
+        assert_eq!(
+            ModuleBalances::xcm_to_fee(BTC, &PARACHAIN_STATEMINT, &xcm),
+            Some((BTC, 8_000_000_000))
+        );
+        assert_eq!(
+            XcmWeightToFee::<Test, ()>::convert((BTC, XcmWeight::from_parts(500_000_000_000, 0))),
+            Some(500_000_000_000)
+        );
+
+        assert_ok!(ModuleBalances::set_asset_hub_asset(
+            RawOrigin::Root.into(),
+            BTC,
+            None
+        ));
+        assert_eq!(
+            ModuleBalances::xcm_to_fee(BTC, &PARACHAIN_STATEMINT, &xcm),
+            None
+        );
+    });
+}
+
+#[test]
+fn xcm_retry_backs_off_and_refunds_after_expiry() {
+    new_test_ext().execute_with(|| {
+        let who = 1;
+        let amount = 10 * ONE_TOKEN;
+        let retry_account = ModuleBalances::xcm_retry_account();
+        let message_hash = [1u8; 32];
+        let balance_before = ModuleBalances::total_balance(&who, DOT);
+
+        assert_ok!(ModuleBalances::deposit_creating(
+            &retry_account,
+            DOT,
+            amount,
+            true,
+            None
+        ));
+        XcmRetryQueue::<Test>::insert(
+            message_hash,
+            XcmRetry {
+                who,
+                transfer: XcmRetryTransfer::Transfer {
+                    transfer: (DOT, amount),
+                    fee: (DOT, 0),
+                },
+                destination: XcmDestination::Common(MultiLocation::parent()),
+                held: vec![(DOT, amount)].into(),
+                attempts: 0,
+                next_retry: 10,
+                expiry: 100,
+            },
+        );
+
+        // not due yet
+        ModuleBalances::process_xcm_retries(9);
+        assert_eq!(
+            XcmRetryQueue::<Test>::get(message_hash).unwrap().attempts,
+            0
+        );
+
+        // mock assets have no XCM data, so every retry fails
+        ModuleBalances::process_xcm_retries(10);
+        let retry = XcmRetryQueue::<Test>::get(message_hash).unwrap();
+        assert_eq!((retry.attempts, retry.next_retry), (1, 10 + 20));
+
+        ModuleBalances::process_xcm_retries(30);
+        let retry = XcmRetryQueue::<Test>::get(message_hash).unwrap();
+        assert_eq!((retry.attempts, retry.next_retry), (2, 30 + 40));
+        assert_balance!(retry_account, amount, 0, DOT);
+
+        ModuleBalances::process_xcm_retries(100);
+        assert!(XcmRetryQueue::<Test>::get(message_hash).is_none());
+        assert_balance!(retry_account, 0, 0, DOT);
+        assert_balance!(who, balance_before + amount, 0, DOT);
+    });
+}
+
+#[test]
+fn timelocked_transfer_cancel_and_release() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        let timelock_account = ModuleBalances::timelock_account();
+
+        assert_noop!(
+            ModuleBalances::set_timelock_dispute_window(RuntimeOrigin::signed(1), 5),
+            BadOrigin
+        );
+        assert_ok!(ModuleBalances::set_timelock_dispute_window(
+            RawOrigin::Root.into(),
+            5
+        ));
+
+        assert_noop!(
+            ModuleBalances::transfer_with_timelock(RuntimeOrigin::signed(1), 2, BTC, 100, 1),
+            Error::<Test>::TimelockInPast
+        );
+        assert_ok!(ModuleBalances::transfer_with_timelock(
+            RuntimeOrigin::signed(1),
+            2,
+            BTC,
+            100,
+            10
+        ));
+        assert_ok!(ModuleBalances::transfer_with_timelock(
+            RuntimeOrigin::signed(1),
+            2,
+            BTC,
+            50,
+            20
+        ));
+        assert_eq!(
+            Timelocks::<Test>::get(0),
+            Some(TimelockedTransfer {
+                from: 1,
+                to: 2,
+                asset: BTC,
+                amount: 100,
+                unlock_at: 10,
+                cancel_until: 6,
+            })
+        );
+        assert_balance!(1, 1000_000_000_000 - 150, 0, BTC);
+        assert_balance!(timelock_account, 150, 0, BTC);
+
+        frame_system::Pallet::<Test>::set_block_number(3);
+        assert_noop!(
+            ModuleBalances::cancel_timelock(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::TimelockNotSender
+        );
+        assert_ok!(ModuleBalances::cancel_timelock(RuntimeOrigin::signed(1), 0));
+        assert!(Timelocks::<Test>::get(0).is_none());
+        assert_balance!(1, 1000_000_000_000 - 50, 0, BTC);
+        assert_balance!(timelock_account, 50, 0, BTC);
+
+        frame_system::Pallet::<Test>::set_block_number(7);
+        assert_noop!(
+            ModuleBalances::cancel_timelock(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::TimelockDisputeWindowEnded
+        );
+
+        // not due yet
+        ModuleBalances::process_timelocks(19);
+        assert!(Timelocks::<Test>::get(1).is_some());
+
+        ModuleBalances::process_timelocks(20);
+        assert!(Timelocks::<Test>::get(1).is_none());
+        assert_balance!(2, 2000_000_000_000 + 50, 0, BTC);
+        assert_balance!(timelock_account, 0, 0, BTC);
+
+        assert_noop!(
+            ModuleBalances::cancel_timelock(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::TimelockNotFound
+        );
+    });
+}
+
+#[test]
+fn xcm_refund_checks_response_and_expires() {
+    new_test_ext().execute_with(|| {
+        let refund = XcmRefund {
+            who: 1,
+            asset: DOT,
+            fee: ONE_TOKEN,
+            responder: MultiLocation::parent(),
+            message_hash: [1u8; 32],
+            expires_at: 10,
+        };
+        XcmRefunds::<Test>::insert(0, refund.clone());
+        XcmRefunds::<Test>::insert(
+            1,
+            XcmRefund {
+                responder: MultiLocation::here(),
+                ..refund.clone()
+            },
+        );
+
+        assert_noop!(
+            ModuleBalances::refund_xcm_surplus(RuntimeOrigin::signed(1), 0, Response::Null),
+            BadOrigin
+        );
+        assert_noop!(
+            ModuleBalances::refund_xcm_surplus(RawOrigin::Root.into(), 2, Response::Null),
+            Error::<Test>::XcmUnknownRefund
+        );
+        assert_noop!(
+            ModuleBalances::refund_xcm_surplus(RawOrigin::Root.into(), 1, Response::Null),
+            Error::<Test>::XcmUnexpectedResponder
+        );
+        assert_noop!(
+            ModuleBalances::refund_xcm_surplus(RawOrigin::Root.into(), 0, Response::Null),
+            Error::<Test>::XcmUnexpectedResponse
+        );
+
+        frame_system::Pallet::<Test>::set_block_number(10);
+        assert_noop!(
+            ModuleBalances::clear_expired_xcm_refund(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::XcmRefundNotExpired
+        );
+
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_ok!(ModuleBalances::clear_expired_xcm_refund(
+            RuntimeOrigin::signed(2),
+            0
+        ));
+        assert!(XcmRefunds::<Test>::get(0).is_none());
+        assert_noop!(
+            ModuleBalances::clear_expired_xcm_refund(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::XcmUnknownRefund
+        );
+    });
+}
+
+#[test]
+fn frozen_asset_cant_be_changed() {
+    new_test_ext().execute_with(|| {
+        let (acc1, acc2, dead) = (1u64, 2u64, 1_000u64);
+        let id = *b"asset_lk";
+        let owner = *b"owner   ";
+        let frozen = AssetFlags {
+            frozen: true,
+            ..Default::default()
+        };
+        frame_system::Pallet::<Test>::set_block_number(1);
+        assert_ok!(ModuleBalances::deposit_creating(
+            &acc1,
+            BTC,
+            10 * ONE_TOKEN,
+            true,
+            None
+        ));
+
+        assert_noop!(
+            ModuleBalances::set_asset_flags(RuntimeOrigin::signed(acc1), acc1, BTC, frozen),
+            BadOrigin
+        );
+        assert_noop!(
+            ModuleBalances::set_asset_flags(RawOrigin::Root.into(), dead, BTC, frozen),
+            Error::<Test>::DeadAccount
+        );
+        assert_ok!(ModuleBalances::set_asset_flags(
+            RawOrigin::Root.into(),
+            acc1,
+            BTC,
+            frozen
+        ));
+        assert_eq!(
+            <Test as crate::Config>::AccountStore::get(&acc1).flags(&BTC),
+            frozen
+        );
+
+        assert_err!(
+            ModuleBalances::transfer(RuntimeOrigin::signed(acc1), BTC, acc2, ONE_TOKEN),
+            Error::<Test>::AssetFrozen
+        );
+        assert_err!(
+            ModuleBalances::transfer(RuntimeOrigin::signed(acc2), BTC, acc1, ONE_TOKEN),
+            Error::<Test>::AssetFrozen
+        );
+        assert_noop!(
+            ModuleBalances::set_asset_lock(id, &acc1, BTC, ONE_TOKEN, None, owner),
+            Error::<Test>::AssetFrozen
+        );
+        assert!(matches!(
+            ModuleBalances::can_transfer(&acc1, &acc2, BTC, ONE_TOKEN),
+            TransferCheck::Rejected(_)
+        ));
+        // other assets of the account are not affected
+        assert_ok!(ModuleBalances::transfer(
+            RuntimeOrigin::signed(acc1),
+            EQD,
+            acc2,
+            ONE_TOKEN
+        ));
+
+        assert_ok!(ModuleBalances::set_asset_flags(
+            RawOrigin::Root.into(),
+            acc1,
+            BTC,
+            AssetFlags::default()
+        ));
+        assert_ok!(ModuleBalances::transfer(
+            RuntimeOrigin::signed(acc1),
+            BTC,
+            acc2,
+            ONE_TOKEN
+        ));
+        assert_ok!(ModuleBalances::set_asset_lock(
+            id, &acc1, BTC, ONE_TOKEN, None, owner
+        ));
+    });
+}
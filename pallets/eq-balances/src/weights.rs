@@ -33,6 +33,20 @@ pub trait WeightInfo {
     fn xcm_transfer_native() -> Weight;
     fn xcm_transfer() -> Weight;
     fn on_initialize(a: u32) -> Weight;
+    fn set_settlement_account() -> Weight;
+    fn settle(a: u32) -> Weight;
+    fn set_xcm_frozen() -> Weight;
+    fn set_xcm_fee() -> Weight;
+    fn set_asset_hub_asset() -> Weight;
+    fn transfer_with_timelock() -> Weight;
+    fn cancel_timelock() -> Weight;
+    fn set_timelock_dispute_window() -> Weight;
+    fn process_timelocks(a: u32) -> Weight;
+    fn process_xcm_retries(a: u32) -> Weight;
+    fn refund_xcm_surplus() -> Weight;
+    fn clear_expired_xcm_refund() -> Weight;
+    fn set_asset_flags() -> Weight;
+    fn authorize_settler() -> Weight;
 }
 
 // for tests
@@ -65,4 +79,46 @@ impl crate::WeightInfo for () {
     fn on_initialize(_a: u32) -> Weight {
         Weight::zero()
     }
+    fn set_settlement_account() -> Weight {
+        Weight::zero()
+    }
+    fn settle(_a: u32) -> Weight {
+        Weight::zero()
+    }
+    fn set_xcm_frozen() -> Weight {
+        Weight::zero()
+    }
+    fn set_xcm_fee() -> Weight {
+        Weight::zero()
+    }
+    fn set_asset_hub_asset() -> Weight {
+        Weight::zero()
+    }
+    fn transfer_with_timelock() -> Weight {
+        Weight::zero()
+    }
+    fn cancel_timelock() -> Weight {
+        Weight::zero()
+    }
+    fn set_timelock_dispute_window() -> Weight {
+        Weight::zero()
+    }
+    fn process_timelocks(_a: u32) -> Weight {
+        Weight::zero()
+    }
+    fn process_xcm_retries(_a: u32) -> Weight {
+        Weight::zero()
+    }
+    fn refund_xcm_surplus() -> Weight {
+        Weight::zero()
+    }
+    fn clear_expired_xcm_refund() -> Weight {
+        Weight::zero()
+    }
+    fn set_asset_flags() -> Weight {
+        Weight::zero()
+    }
+    fn authorize_settler() -> Weight {
+        Weight::zero()
+    }
 }
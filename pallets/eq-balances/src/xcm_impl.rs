@@ -42,7 +42,7 @@ impl<T: Config> Pallet<T> {
             destination,
             asset_location,
             beneficiary,
-        } = Self::get_destination(to, asset_native_location)?;
+        } = Self::get_destination(to.clone(), asset_native_location)?;
         // fee asset's location from their pov
         let (fee_location, fee_decimals, fee_self_reserved) = if fee_asset == asset {
             (asset_location.clone(), decimals, self_reserved)
@@ -91,7 +91,7 @@ impl<T: Config> Pallet<T> {
         // wrap in transaction all methods that could cause side effects
         // rollback on any error, but save send_result to show proper error
         let send_result = frame_support::storage::with_transaction(
-            || -> TransactionOutcome<Result<SendResult<_>, DispatchError>> {
+            || -> TransactionOutcome<Result<Result<(), (SendError, XcmHash)>, DispatchError>> {
                 // Initialize their_sovereign account as pallet to prevent ED deleting
                 let their_sovereign_info = frame_system::Pallet::<T>::account(&their_sovereign);
 
@@ -210,6 +210,13 @@ impl<T: Config> Pallet<T> {
                     return Rollback(Err(err));
                 }
 
+                // fee withdrawn from our sovereign account is reported back to refund unspent part
+                let refund = if (self_reserved, fee_self_reserved) == (false, false) {
+                    Self::new_xcm_refund_query(&destination)
+                } else {
+                    None
+                };
+
                 let mut xcm = if [EQ, EQD].contains(&asset) && destination == PARACHAIN_STATEMINT {
                     let mut xcm = Xcm::<()>(vec![
                         transfer_instruction,
@@ -244,7 +251,7 @@ impl<T: Config> Pallet<T> {
                     ));
                     xcm.0.push(DepositAsset {
                         assets: (AllOfCounted {
-                            id: Concrete(fee_location),
+                            id: Concrete(fee_location.clone()),
                             fun: WildFungibility::Fungible,
                             count: 2,
                         })
@@ -253,14 +260,15 @@ impl<T: Config> Pallet<T> {
                     });
                     xcm
                 } else {
-                    let mut xcm = Xcm::<()>(vec![
-                        transfer_instruction,
-                        ClearOrigin,
-                        BuyExecution {
-                            fees: fee_multi_asset,
-                            weight_limit: WeightLimit::Unlimited,
-                        },
-                    ]);
+                    let mut xcm = Xcm::<()>(vec![transfer_instruction]);
+                    // origin is kept to match holding report with the query
+                    if refund.is_none() {
+                        xcm.0.push(ClearOrigin);
+                    }
+                    xcm.0.push(BuyExecution {
+                        fees: fee_multi_asset,
+                        weight_limit: WeightLimit::Unlimited,
+                    });
 
                     if (self_reserved, fee_self_reserved) == (false, true) {
                         // Moonbeam case: pay EQ to withdraw mxUSDC and return remains EQ to our souvereign
@@ -307,7 +315,7 @@ impl<T: Config> Pallet<T> {
 
                         xcm.0.push(DepositAsset {
                             assets: (AllOfCounted {
-                                id: Concrete(fee_location),
+                                id: Concrete(fee_location.clone()),
                                 fun: WildFungibility::Fungible,
                                 count: 2,
                             })
@@ -319,23 +327,83 @@ impl<T: Config> Pallet<T> {
                     xcm
                 };
 
-                xcm.0.push(DepositAsset {
-                    assets: AllCounted(2).into(),
-                    beneficiary: beneficiary.clone(),
-                });
+                match &refund {
+                    Some((query_id, response_destination, _)) => xcm.0.extend([
+                        DepositAsset {
+                            assets: MultiAsset {
+                                id: Concrete(asset_location.clone()),
+                                fun: Fungible(xcm_amount),
+                            }
+                            .into(),
+                            beneficiary: beneficiary.clone(),
+                        },
+                        RefundSurplus,
+                        ReportHolding {
+                            response_info: QueryResponseInfo {
+                                destination: response_destination.clone(),
+                                query_id: *query_id,
+                                max_weight: T::WeightInfo::refund_xcm_surplus(),
+                            },
+                            assets: (AllOf {
+                                id: Concrete(fee_location),
+                                fun: WildFungibility::Fungible,
+                            })
+                            .into(),
+                        },
+                        // unspent fee goes to our sovereign account, sender is refunded locally
+                        DepositAsset {
+                            assets: AllCounted(1).into(),
+                            beneficiary: response_destination.clone(),
+                        },
+                    ]),
+                    None => xcm.0.push(DepositAsset {
+                        assets: AllCounted(2).into(),
+                        beneficiary: beneficiary.clone(),
+                    }),
+                }
 
                 log::trace!(target: "eq_balances", "Sending XcmMessage dest: {:?}, xcm: {:?}", destination, xcm);
+                let message_hash = Self::xcm_retry_hash(&from, &destination, &xcm);
                 match send_xcm::<T::XcmRouter>(destination.clone(), xcm) {
-                    Ok(send_result) => Commit(Ok(SendResult::Ok(send_result))),
-                    Err(err) => Rollback(Ok(SendResult::Err(err))),
+                    Ok((sent_hash, _)) => {
+                        if let Some((query_id, _, expires_at)) = refund {
+                            XcmRefunds::<T>::insert(
+                                query_id,
+                                XcmRefund {
+                                    who: from.clone(),
+                                    asset: fee_asset,
+                                    fee: fee_amount,
+                                    responder: destination.clone(),
+                                    message_hash: sent_hash,
+                                    expires_at,
+                                },
+                            );
+                        }
+                        Commit(Ok(Ok(())))
+                    }
+                    Err(err) => Rollback(Ok(Err((err, message_hash)))),
                 }
             },
         )?;
 
-        if let Err(send_error) = send_result {
+        if let Err((send_error, message_hash)) = send_result {
             log::error!("XcmRouter::SendError {:?}", send_error);
-            Self::deposit_event(Event::XcmMessageSendError(send_error));
-            frame_support::fail!(Error::<T>::XcmSend);
+            Self::deposit_event(Event::XcmMessageSendError(send_error.clone()));
+
+            let mut held = VecMap::new();
+            held.insert(asset, amount);
+            let fee_held = held.entry(fee_asset).or_default();
+            *fee_held = fee_held
+                .checked_add(&fee_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            Self::queue_xcm_retry(
+                &from,
+                message_hash,
+                send_error,
+                XcmRetryTransfer::Transfer { transfer, fee },
+                to,
+                held,
+            )?;
         } else {
             Self::deposit_event(Event::XcmTransfer(destination, beneficiary));
         }
@@ -343,6 +411,29 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Registers query for unspent fee report from `destination`, returns query id,
+    /// our location from destination pov and query expiry block
+    fn new_xcm_refund_query(
+        destination: &MultiLocation,
+    ) -> Option<(QueryId, MultiLocation, T::BlockNumber)> {
+        let response_destination = T::UniversalLocation::get()
+            .invert_target(destination)
+            .ok()?;
+        let expires_at =
+            frame_system::Pallet::<T>::block_number().saturating_add(T::XcmRefundTimeout::get());
+        let query_id = T::XcmRefundQueryHandler::new_notify_query(
+            destination.clone(),
+            Call::refund_xcm_surplus {
+                query_id: 0,
+                response: Response::Null,
+            },
+            expires_at,
+            MultiLocation::here(),
+        )?;
+
+        Some((query_id, response_destination, expires_at))
+    }
+
     pub fn get_destination(
         dest: XcmDestination,
         asset_native_location: MultiLocation,
@@ -359,6 +450,7 @@ impl<T: Config> Pallet<T> {
             XcmDestination::Native(to) => {
                 let destination = eq_utils::chain_part(&asset_native_location)
                     .ok_or(Error::<T>::XcmInvalidDestination)?;
+                Self::ensure_valid_beneficiary(&destination, &to)?;
                 let asset_location = eq_utils::non_chain_part(&asset_native_location).into();
                 let beneficiary = to.multi_location().into();
                 (destination, asset_location, beneficiary)
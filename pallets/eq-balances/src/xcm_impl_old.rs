@@ -70,6 +70,7 @@ impl<T: Config> Pallet<T> {
             XcmDestination::Native(to) => {
                 let destination = eq_utils::chain_part(&multi_location)
                     .ok_or(Error::<T>::XcmInvalidDestination)?;
+                Self::ensure_valid_beneficiary(&destination, &to)?;
                 let asset_location = eq_utils::non_chain_part(&multi_location).into();
                 let beneficiary = to.multi_location().into();
                 (destination, asset_location, beneficiary)
@@ -98,7 +99,7 @@ impl<T: Config> Pallet<T> {
             destination,
             asset_location,
             beneficiary,
-        } = Self::get_xcm_transfer_params(kind, multi_location)?;
+        } = Self::get_xcm_transfer_params(kind.clone(), multi_location)?;
 
         let xcm_amount =
             balance_into_xcm(amount.into(), decimals).ok_or(ArithmeticError::Overflow)?;
@@ -126,7 +127,7 @@ impl<T: Config> Pallet<T> {
                 ]);
 
                 let (xcm_fee_asset, xcm_fee_amount) =
-                    T::XcmToFee::convert((asset.clone(), destination.clone(), &temp_xcm))
+                    Self::xcm_to_fee(asset, &destination, &temp_xcm)
                         .ok_or(Error::<T>::XcmInvalidDestination)?;
                 let (xcm_fee_multilocation, xcm_fee_decimals, xcm_fee_self_reserved) =
                     Self::xcm_data(&xcm_fee_asset)?;
@@ -189,9 +190,8 @@ impl<T: Config> Pallet<T> {
                     beneficiary.clone(),
                     self_reserved,
                 );
-                let (xcm_fee_asset, xcm_fee_amount) =
-                    T::XcmToFee::convert((asset.clone(), destination.clone(), &xcm))
-                        .ok_or(Error::<T>::XcmInvalidDestination)?;
+                let (xcm_fee_asset, xcm_fee_amount) = Self::xcm_to_fee(asset, &destination, &xcm)
+                    .ok_or(Error::<T>::XcmInvalidDestination)?;
                 let (xcm_fee_multi_location, xcm_fee_decimals, fee_self_reserved) = if xcm_fee_asset
                     == asset
                 {
@@ -240,10 +240,10 @@ impl<T: Config> Pallet<T> {
         // wrap in transaction all methods that could cause side effects
         // rollback on any error, but save send_result to show proper error
         let send_result = frame_support::storage::with_transaction(
-            || -> TransactionOutcome<Result<SendResult<_>, DispatchError>> {
+            || -> TransactionOutcome<Result<Result<(), (SendError, XcmHash)>, DispatchError>> {
                 use TransactionOutcome::*;
 
-                for (to, asset, amount, reason) in to_transfer {
+                for (to, asset, amount, reason) in to_transfer.clone() {
                     let res = Self::currency_transfer(
                         &from,
                         &to,
@@ -258,7 +258,7 @@ impl<T: Config> Pallet<T> {
                     }
                 }
 
-                for (asset, amount, reason) in to_withdraw {
+                for (asset, amount, reason) in to_withdraw.clone() {
                     let res = Self::withdraw(
                         &from,
                         asset,
@@ -274,17 +274,43 @@ impl<T: Config> Pallet<T> {
                 }
 
                 log::trace!(target: "eq_balances", "Sending XcmMessage dest: {:?}, xcm: {:?}", destination, xcm);
+                let message_hash = Self::xcm_retry_hash(&from, &destination, &xcm);
                 match send_xcm::<T::XcmRouter>(destination.clone(), xcm) {
-                    Ok(send_result) => Commit(Ok(SendResult::Ok(send_result))),
-                    Err(err) => Rollback(Ok(SendResult::Err(err))),
+                    Ok(_) => Commit(Ok(Ok(()))),
+                    Err(err) => Rollback(Ok(Err((err, message_hash)))),
                 }
             },
         )?;
 
-        if let Err(send_error) = send_result {
+        if let Err((send_error, message_hash)) = send_result {
             log::error!("XcmRouter::SendError {:?}", send_error);
-            Self::deposit_event(Event::XcmMessageSendError(send_error));
-            frame_support::fail!(Error::<T>::XcmSend)
+            Self::deposit_event(Event::XcmMessageSendError(send_error.clone()));
+
+            let mut held: VecMap<Asset, T::Balance> = VecMap::new();
+            let transfers = to_transfer
+                .into_iter()
+                .map(|(_, asset, amount, _)| (asset, amount));
+            let withdraws = to_withdraw
+                .into_iter()
+                .map(|(asset, amount, _)| (asset, amount));
+            for (asset, amount) in transfers.chain(withdraws) {
+                let asset_held = held.entry(asset).or_default();
+                *asset_held = asset_held
+                    .checked_add(&amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+            }
+            Self::queue_xcm_retry(
+                &from,
+                message_hash,
+                send_error,
+                XcmRetryTransfer::TransferOld {
+                    asset,
+                    amount,
+                    deal_with_fee,
+                },
+                kind,
+                held,
+            )?;
         } else {
             Self::deposit_event(Event::XcmTransfer(destination, beneficiary));
         }
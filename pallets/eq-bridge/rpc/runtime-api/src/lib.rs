@@ -0,0 +1,40 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for `eq-bridge` pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use eq_bridge::ReserveStatus;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait EqBridgeApi<ResourceId, Balance, BlockNumber>
+    where
+        ResourceId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec
+    {
+        /// Minted supply of a resource compared with the last reported reserve
+        fn reserve_status(resource_id: ResourceId) -> Option<ReserveStatus<Balance, BlockNumber>>;
+
+        /// Reserve statuses of all resources with reports
+        fn reserve_statuses() -> Vec<(ResourceId, ReserveStatus<Balance, BlockNumber>)>;
+    }
+}
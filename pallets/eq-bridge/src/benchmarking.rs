@@ -171,4 +171,113 @@ benchmarks! {
     }: _(RawOrigin::Root, chain_id, resource_id, 1000u128.try_into()
                 .map_err(|_| "balance conversion error")
                 .unwrap())
+
+    set_resource_decimals{
+        let resource_id = chainbridge::derive_resource_id(1, b"hash");
+
+        EqBridge::<T>::set_resource(
+            RawOrigin::Root.into(),
+            resource_id,
+            asset::ETH
+        ).expect("set_resource unexpected panic");
+    }: _(RawOrigin::Root, resource_id, Some(6))
+    verify {
+        assert_eq!(EqBridge::<T>::resource_decimals(resource_id), Some(6));
+    }
+
+    report_reserve{
+        let resource_id = chainbridge::derive_resource_id(1, b"hash");
+        let relayer: T::AccountId = account("relayer", 0, SEED);
+
+        EqBridge::<T>::set_resource(
+            RawOrigin::Root.into(),
+            resource_id,
+            asset::ETH
+        ).expect("set_resource unexpected panic");
+        EqBridge::<T>::set_reserve_auto_pause(
+            RawOrigin::Root.into(),
+            resource_id,
+            true
+        ).expect("set_reserve_auto_pause unexpected panic");
+        chainbridge::Pallet::<T>::set_threshold(RawOrigin::Root.into(), 1)
+            .expect("set_threshold unexpected panic");
+        chainbridge::Pallet::<T>::add_relayer(RawOrigin::Root.into(), relayer.clone())
+            .expect("add_relayer unexpected panic");
+        let reserve: T::Balance = 0u128.saturated_into();
+    }: _(RawOrigin::Signed(relayer), resource_id, reserve)
+    verify {
+        assert!(EqBridge::<T>::reserve_report(resource_id).is_some());
+    }
+
+    set_reserve_auto_pause{
+        let resource_id = chainbridge::derive_resource_id(1, b"hash");
+
+        EqBridge::<T>::set_resource(
+            RawOrigin::Root.into(),
+            resource_id,
+            asset::ETH
+        ).expect("set_resource unexpected panic");
+    }: _(RawOrigin::Root, resource_id, true)
+    verify {
+        assert!(EqBridge::<T>::reserve_auto_pause(resource_id));
+    }
+
+    resume_resource{
+        let resource_id = chainbridge::derive_resource_id(1, b"hash");
+
+        PausedResources::<T>::insert(resource_id, PauseReason::InsufficientReserve);
+    }: _(RawOrigin::Root, resource_id)
+    verify {
+        assert!(!EqBridge::<T>::is_resource_paused(resource_id));
+    }
+
+    pause_resource{
+        let resource_id = chainbridge::derive_resource_id(1, b"hash");
+
+        EqBridge::<T>::set_resource(
+            RawOrigin::Root.into(),
+            resource_id,
+            asset::ETH
+        ).expect("set_resource unexpected panic");
+    }: _(RawOrigin::Root, resource_id, PauseReason::Maintenance)
+    verify {
+        assert_eq!(EqBridge::<T>::resource_pause_reason(resource_id), Some(PauseReason::Maintenance));
+    }
+
+    bind_resource{
+        let c in 0 .. 10;
+
+        let resource_id = chainbridge::derive_resource_id(1, b"hash");
+        let mut minimum_transfer_amounts = vec![];
+        for chain_id in 0..c {
+            let chain_id = chain_id as chainbridge::ChainId;
+            chainbridge::Pallet::<T>::whitelist_chain(RawOrigin::Root.into(), chain_id, 0u32.into()).unwrap();
+            minimum_transfer_amounts.push((chain_id, 1000u128.saturated_into()));
+        }
+    }: _(RawOrigin::Root, resource_id, asset::ETH, Some(6), minimum_transfer_amounts)
+    verify {
+        assert_eq!(EqBridge::<T>::resources(resource_id), Some(asset::ETH));
+    }
+
+    unbind_resource{
+        let resource_id = chainbridge::derive_resource_id(1, b"hash");
+        let minimum_transfer_amounts: Vec<_> = (0..10)
+            .map(|chain_id| {
+                chainbridge::Pallet::<T>::whitelist_chain(RawOrigin::Root.into(), chain_id, 0u32.into()).unwrap();
+                (chain_id, 1000u128.saturated_into())
+            })
+            .collect();
+
+        EqBridge::<T>::bind_resource(
+            RawOrigin::Root.into(),
+            resource_id,
+            asset::ETH,
+            Some(6),
+            minimum_transfer_amounts
+        ).expect("bind_resource unexpected panic");
+        EqBridge::<T>::enable_withdrawals(RawOrigin::Root.into(), resource_id, 0).unwrap();
+    }: _(RawOrigin::Root, resource_id)
+    verify {
+        assert_eq!(EqBridge::<T>::resources(resource_id), None);
+    }
 }
@@ -31,12 +31,20 @@
 //!     when a physical (bridgeable) asset is transferred, the tokens are minted (deposited) to the user.
 //!
 //! 4. SUDO management of resource_id <> asset_id mapping.
+//!
+//! 5. Proof of reserves: relayers report custodian balance of a bridged asset on external chain,
+//!     a report signed by `RelayerThreshold` relayers is compared with the minted supply.
+//!     Transfers out of a resource can be paused automatically when it is under-collateralized.
+//!
+//! 6. Pause of transfers out of the network by resource with a reason returned to users
+//!     in `ResourcePaused` error, so wallets can show what happened.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 #![deny(warnings)]
 
 pub mod benchmarking;
+pub mod migration;
 mod mock;
 mod tests;
 pub mod weights;
@@ -46,11 +54,15 @@ use core::convert::TryFrom;
 use core::convert::TryInto;
 use eq_primitives::asset::{Asset, AssetGetter, AssetType};
 use eq_primitives::balance::{EqCurrency, XcmDestination};
-use eq_primitives::AccountType;
+use eq_primitives::decimals::{balance_from_decimals, balance_into_decimals};
+use eq_primitives::{AccountType, XcmBalance, DECIMALS};
 use frame_support::traits::{Currency, EnsureOrigin, ExistenceRequirement, Get, WithdrawReasons};
 use frame_support::{dispatch::DispatchResultWithPostInfo, ensure};
 use frame_system::ensure_signed;
-use sp_arithmetic::traits::SaturatedConversion;
+use sp_arithmetic::{
+    traits::{SaturatedConversion, Saturating},
+    Permill,
+};
 use sp_core::U256;
 use sp_std::prelude::*;
 pub use weights::WeightInfo;
@@ -62,12 +74,183 @@ const SUBSTRATE_ADDRESS_LENGTH: usize = 32;
 const SUBSTRATE_PREFIX_LENGTH: usize = 4;
 const SUBSTRATE_WITH_PREFIX_ADDRESS_LENGTH: usize =
     SUBSTRATE_ADDRESS_LENGTH + SUBSTRATE_PREFIX_LENGTH;
+const SS58_CHECKSUM_LENGTH: usize = 2;
+const SS58_CHECKSUM_PREIMAGE_PREFIX: &[u8] = b"SS58PRE";
+const HEX_PREFIX: &[u8] = b"0x";
 
+/// Recipient address format of a destination chain.
+/// Recipient bytes are passed to relayers as is.
 #[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq, scale_info::TypeInfo)]
 pub enum ChainAddressType {
     Ethereum,
     Substrate,
     SubstrateWithPrefix,
+    /// Base58-decoded SS58 address: network prefix, account and checksum bytes
+    Ss58(u16),
+    /// `0x`-prefixed hex encoded ethereum address with EIP-55 mixed-case checksum
+    EthereumChecksum,
+}
+
+/// Protocol fee for outbound transfers to a destination chain
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct ProtocolFee<AccountId, Balance> {
+    /// Fixed part of the fee, charged in main asset
+    pub flat: Balance,
+    /// Part of the transferred amount, charged in transferred asset
+    pub percent: Permill,
+    /// Upper bound of the congestion surcharge that relayers can set
+    pub max_surcharge: Permill,
+    /// Account receiving the fee
+    pub recipient: AccountId,
+}
+
+/// Reason of recipient address rejection
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AddressValidationError {
+    /// Wrong length or encoding of the address
+    Format,
+    /// Checksum doesn't match the address
+    Checksum,
+    /// Address consists of zero bytes, funds would be burnt
+    ZeroAddress,
+}
+
+/// Custodian balance on external chain confirmed by relayers
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct ReserveReport<AccountId, Balance, BlockNumber> {
+    /// Reserve in native decimals
+    pub reserve: Balance,
+    /// Block of the report confirmation
+    pub block: BlockNumber,
+    /// Relayers signed the report
+    pub signers: Vec<AccountId>,
+}
+
+/// Minted supply of a bridged asset compared with the last reported reserve
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct ReserveStatus<Balance, BlockNumber> {
+    /// Bridged asset
+    pub asset: Asset,
+    /// Total issuance of the asset
+    pub supply: Balance,
+    /// Last reported reserve, `None` if never reported
+    pub reserve: Option<Balance>,
+    /// Block of the last report
+    pub reported_at: Option<BlockNumber>,
+    /// Supply exceeds reported reserve
+    pub is_under_collateralized: bool,
+    /// Transfers out of the network are paused
+    pub is_paused: bool,
+    /// Reason of the pause
+    pub pause_reason: Option<PauseReason>,
+}
+
+/// Reason of pause of transfers out of the network for a resource
+#[derive(
+    Encode,
+    Decode,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    scale_info::TypeInfo,
+    frame_support::PalletError,
+)]
+pub enum PauseReason {
+    /// Planned works on the bridge or the external chain
+    Maintenance,
+    /// Bridge or the external chain is compromised
+    SecurityIncident,
+    /// Reported reserve is lower than minted supply
+    InsufficientReserve,
+}
+
+impl ChainAddressType {
+    /// Checks length, format and checksum of `recipient`
+    pub fn validate(&self, recipient: &[u8]) -> Result<(), AddressValidationError> {
+        use AddressValidationError::*;
+
+        let is_zero = match *self {
+            ChainAddressType::Ethereum if recipient.len() == ETHEREUM_ADDRESS_LENGTH => {
+                recipient.iter().all(|b| *b == 0)
+            }
+            ChainAddressType::Substrate if recipient.len() == SUBSTRATE_ADDRESS_LENGTH => {
+                recipient.iter().all(|b| *b == 0)
+            }
+            ChainAddressType::SubstrateWithPrefix
+                if recipient.len() == SUBSTRATE_ADDRESS_LENGTH
+                    || recipient.len() == SUBSTRATE_WITH_PREFIX_ADDRESS_LENGTH =>
+            {
+                recipient.iter().all(|b| *b == 0)
+            }
+            ChainAddressType::Ss58(network) => {
+                let prefix = ss58_prefix(network).ok_or(Format)?;
+                let payload_length = prefix.len() + SUBSTRATE_ADDRESS_LENGTH;
+                if recipient.len() != payload_length + SS58_CHECKSUM_LENGTH
+                    || !recipient.starts_with(&prefix)
+                {
+                    return Err(Format);
+                }
+                let (payload, checksum) = recipient.split_at(payload_length);
+                if ss58_checksum(payload)[..SS58_CHECKSUM_LENGTH] != *checksum {
+                    return Err(Checksum);
+                }
+                payload[prefix.len()..].iter().all(|b| *b == 0)
+            }
+            ChainAddressType::EthereumChecksum => {
+                let hex = recipient
+                    .strip_prefix(HEX_PREFIX)
+                    .filter(|hex| hex.len() == 2 * ETHEREUM_ADDRESS_LENGTH)
+                    .ok_or(Format)?;
+                if !hex.iter().all(u8::is_ascii_hexdigit) {
+                    return Err(Format);
+                }
+                let lowercase: Vec<u8> = hex.iter().map(u8::to_ascii_lowercase).collect();
+                let hash = sp_io::hashing::keccak_256(&lowercase);
+                for (i, c) in hex.iter().enumerate() {
+                    let nibble = if i % 2 == 0 {
+                        hash[i / 2] >> 4
+                    } else {
+                        hash[i / 2] & 0x0f
+                    };
+                    if c.is_ascii_alphabetic() && c.is_ascii_uppercase() != (nibble >= 8) {
+                        return Err(Checksum);
+                    }
+                }
+                lowercase.iter().all(|c| *c == b'0')
+            }
+            _ => return Err(Format),
+        };
+
+        if is_zero {
+            Err(ZeroAddress)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Encoded SS58 network prefix, `None` for reserved networks
+fn ss58_prefix(network: u16) -> Option<Vec<u8>> {
+    match network {
+        0..=63 => Some([network as u8].to_vec()),
+        64..=16_383 => {
+            let first = ((network & 0b0000_0000_1111_1100) as u8) >> 2;
+            let second = ((network >> 8) as u8) | ((network & 0b0000_0000_0000_0011) as u8) << 6;
+            Some([first | 0b0100_0000, second].to_vec())
+        }
+        _ => None,
+    }
+}
+
+fn ss58_checksum(payload: &[u8]) -> [u8; 64] {
+    let preimage: Vec<u8> = SS58_CHECKSUM_PREIMAGE_PREFIX
+        .iter()
+        .chain(payload)
+        .copied()
+        .collect();
+    sp_core_hashing::blake2_512(&preimage)
 }
 
 #[frame_support::pallet]
@@ -77,7 +260,10 @@ pub mod pallet {
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -88,8 +274,7 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn resources)]
-    pub(super) type Resources<T: Config> =
-        StorageMap<_, Blake2_128Concat, chainbridge::ResourceId, Asset>;
+    pub type Resources<T: Config> = StorageMap<_, Blake2_128Concat, chainbridge::ResourceId, Asset>;
 
     #[pallet::storage]
     #[pallet::getter(fn minimum_transfer_amount)]
@@ -118,6 +303,65 @@ pub mod pallet {
     pub type ChainAddressTypes<T: Config> =
         StorageMap<_, Blake2_128Concat, chainbridge::ChainId, ChainAddressType, OptionQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn protocol_fee)]
+    pub type ProtocolFees<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        chainbridge::ChainId,
+        ProtocolFee<T::AccountId, T::Balance>,
+        OptionQuery,
+    >;
+
+    /// Congestion surcharge set by relayers, bounded by `ProtocolFee::max_surcharge`
+    #[pallet::storage]
+    #[pallet::getter(fn fee_surcharge)]
+    pub type FeeSurcharges<T: Config> =
+        StorageMap<_, Blake2_128Concat, chainbridge::ChainId, Permill, ValueQuery>;
+
+    /// Decimals of a resource on external chains, when they differ from the asset ones.
+    /// Bridged amounts are scaled exactly, relayers use it to validate proposals.
+    #[pallet::storage]
+    #[pallet::getter(fn resource_decimals)]
+    pub type ResourceDecimals<T: Config> =
+        StorageMap<_, Blake2_128Concat, chainbridge::ResourceId, u8, OptionQuery>;
+
+    /// Reserves reported by relayers and not confirmed yet, in native decimals
+    #[pallet::storage]
+    #[pallet::getter(fn reserve_vote)]
+    pub type ReserveVotes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        chainbridge::ResourceId,
+        Blake2_128Concat,
+        T::AccountId,
+        T::Balance,
+        OptionQuery,
+    >;
+
+    /// Last reserve report confirmed by `RelayerThreshold` relayers
+    #[pallet::storage]
+    #[pallet::getter(fn reserve_report)]
+    pub type ReserveReports<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        chainbridge::ResourceId,
+        ReserveReport<T::AccountId, T::Balance, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Resources with transfers out of the network paused automatically on under-collateralization
+    #[pallet::storage]
+    #[pallet::getter(fn reserve_auto_pause)]
+    pub type ReserveAutoPause<T: Config> =
+        StorageMap<_, Blake2_128Concat, chainbridge::ResourceId, bool, ValueQuery>;
+
+    /// Resources with disabled transfers out of the network and reasons of the pause
+    #[pallet::storage]
+    #[pallet::getter(fn resource_pause_reason)]
+    pub type PausedResources<T: Config> =
+        StorageMap<_, Blake2_128Concat, chainbridge::ResourceId, PauseReason, OptionQuery>;
+
     #[pallet::config]
     pub trait Config: frame_system::Config + chainbridge::Config {
         /// The overarching event type.
@@ -229,6 +473,52 @@ pub mod pallet {
             Self::update_minimum_transfer_amount(dest_id, resource_id, minimum_amount)
         }
 
+        /// Stores protocol fee for transfers to external chain, `None` removes the fee.
+        /// Surcharge is cut to the new bound.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_protocol_fee())]
+        pub fn set_protocol_fee(
+            origin: OriginFor<T>,
+            dest_id: chainbridge::ChainId,
+            fee: Option<ProtocolFee<T::AccountId, T::Balance>>,
+        ) -> DispatchResultWithPostInfo {
+            T::BridgeManagementOrigin::ensure_origin(origin)?;
+            Self::update_protocol_fee(dest_id, fee)
+        }
+
+        /// Sets congestion surcharge for transfers to external chain.
+        /// Relayers only, within `ProtocolFee::max_surcharge`.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_fee_surcharge())]
+        pub fn set_fee_surcharge(
+            origin: OriginFor<T>,
+            dest_id: chainbridge::ChainId,
+            surcharge: Permill,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                <chainbridge::Pallet<T>>::is_relayer(&who),
+                Error::<T>::NotRelayer
+            );
+            let fee = Self::protocol_fee(dest_id).ok_or(Error::<T>::ProtocolFeeNotSet)?;
+            ensure!(
+                surcharge <= fee.max_surcharge,
+                Error::<T>::SurchargeExceedsBound
+            );
+
+            FeeSurcharges::<T>::insert(dest_id, surcharge);
+            Self::deposit_event(Event::FeeSurchargeChanged(dest_id, surcharge));
+            Ok(().into())
+        }
+
         /// Stores chain id relation to chain address type.
         /// Sudo only.
         ///
@@ -246,6 +536,198 @@ pub mod pallet {
             Self::update_chain_address_type(dest_id, address_type)
         }
 
+        /// Stores decimals of a resource on external chains, `None` means decimals of the asset
+        /// in assets registry.
+        /// Sudo only.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_resource_decimals())]
+        pub fn set_resource_decimals(
+            origin: OriginFor<T>,
+            resource_id: chainbridge::ResourceId,
+            decimals: Option<u8>,
+        ) -> DispatchResultWithPostInfo {
+            T::BridgeManagementOrigin::ensure_origin(origin)?;
+            Self::update_resource_decimals(resource_id, decimals)
+        }
+
+        /// Reports custodian balance of a resource on external chain in resource decimals.
+        /// Relayers only, report is confirmed when `RelayerThreshold` relayers report the same value.
+        ///
+        /// # <weight>
+        /// - O(R) where R is number of relayers
+        /// # </weight>
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::report_reserve())]
+        pub fn report_reserve(
+            origin: OriginFor<T>,
+            resource_id: chainbridge::ResourceId,
+            reserve: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                <chainbridge::Pallet<T>>::is_relayer(&who),
+                Error::<T>::NotRelayer
+            );
+            let asset = Self::resources(resource_id).ok_or(Error::<T>::InvalidResourceId)?;
+            ensure!(
+                Self::is_mintable_asset(&asset)?,
+                Error::<T>::InvalidAssetType
+            );
+            let reserve = Self::amount_from_resource(resource_id, reserve)?;
+
+            Self::do_report_reserve(who, resource_id, asset, reserve)
+        }
+
+        /// Enables or disables pause of transfers out of the network
+        /// when reported reserve of a resource is lower than minted supply.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_reserve_auto_pause())]
+        pub fn set_reserve_auto_pause(
+            origin: OriginFor<T>,
+            resource_id: chainbridge::ResourceId,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::BridgeManagementOrigin::ensure_origin(origin)?;
+            Self::resources(resource_id).ok_or(Error::<T>::InvalidResourceId)?;
+
+            ReserveAutoPause::<T>::insert(resource_id, enabled);
+            Self::deposit_event(Event::ReserveAutoPauseChanged(resource_id, enabled));
+            Ok(().into())
+        }
+
+        /// Enables transfers out of the network for a paused resource.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::resume_resource())]
+        pub fn resume_resource(
+            origin: OriginFor<T>,
+            resource_id: chainbridge::ResourceId,
+        ) -> DispatchResultWithPostInfo {
+            T::BridgeManagementOrigin::ensure_origin(origin)?;
+            ensure!(
+                Self::is_resource_paused(resource_id),
+                Error::<T>::ResourceNotPaused
+            );
+
+            PausedResources::<T>::remove(resource_id);
+            Self::deposit_event(Event::ResourceResumed(resource_id));
+            Ok(().into())
+        }
+
+        /// Disables transfers out of the network for a resource, `reason` is returned to users
+        /// in `ResourcePaused` error. Changes the reason of already paused resource.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::pause_resource())]
+        pub fn pause_resource(
+            origin: OriginFor<T>,
+            resource_id: chainbridge::ResourceId,
+            reason: PauseReason,
+        ) -> DispatchResultWithPostInfo {
+            T::BridgeManagementOrigin::ensure_origin(origin)?;
+            Self::resources(resource_id).ok_or(Error::<T>::InvalidResourceId)?;
+
+            PausedResources::<T>::insert(resource_id, reason);
+            Self::deposit_event(Event::ResourcePaused(resource_id, reason));
+            Ok(().into())
+        }
+
+        /// Lists a bridged token: binds a free resource ID to an asset not bound yet,
+        /// with decimals on external chains and minimum transfer amounts to whitelisted chains.
+        ///
+        /// # <weight>
+        /// - O(C) where C is number of minimum transfer amounts
+        /// # </weight>
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bind_resource(minimum_transfer_amounts.len() as u32))]
+        pub fn bind_resource(
+            origin: OriginFor<T>,
+            resource_id: chainbridge::ResourceId,
+            asset: Asset,
+            decimals: Option<u8>,
+            minimum_transfer_amounts: Vec<(chainbridge::ChainId, T::Balance)>,
+        ) -> DispatchResultWithPostInfo {
+            T::BridgeManagementOrigin::ensure_origin(origin)?;
+            let _ = T::AssetGetter::get_asset_data(&asset)?;
+            ensure!(
+                !Resources::<T>::contains_key(resource_id),
+                Error::<T>::ResourceAlreadyBound
+            );
+            ensure!(
+                !AssetResource::<T>::contains_key(asset),
+                Error::<T>::AssetAlreadyBound
+            );
+            for (dest_id, _) in &minimum_transfer_amounts {
+                ensure!(
+                    <chainbridge::Pallet<T>>::chain_whitelisted(*dest_id),
+                    Error::<T>::ChainNotWhitelisted
+                );
+            }
+
+            Self::register_resource(resource_id, asset)?;
+            if let Some(decimals) = decimals {
+                ResourceDecimals::<T>::insert(resource_id, decimals);
+            }
+            Self::deposit_event(Event::ResourceBound(resource_id, asset, decimals));
+
+            for (dest_id, minimum_amount) in minimum_transfer_amounts {
+                Self::update_minimum_transfer_amount(dest_id, resource_id, minimum_amount)?;
+            }
+
+            Ok(().into())
+        }
+
+        /// Delists a bridged token: removes resource ID binding with all its settings.
+        /// Balances of the asset stay untouched.
+        ///
+        /// # <weight>
+        /// - O(C + R) where C is number of chains with minimum transfer amounts
+        ///   and R is number of relayers
+        /// # </weight>
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::unbind_resource())]
+        pub fn unbind_resource(
+            origin: OriginFor<T>,
+            resource_id: chainbridge::ResourceId,
+        ) -> DispatchResultWithPostInfo {
+            T::BridgeManagementOrigin::ensure_origin(origin)?;
+            let asset = Resources::<T>::take(resource_id).ok_or(Error::<T>::InvalidResourceId)?;
+
+            if Self::asset_resource(asset) == Some(resource_id) {
+                AssetResource::<T>::remove(asset);
+            }
+            ResourceDecimals::<T>::remove(resource_id);
+            EnabledWithdrawals::<T>::remove(resource_id);
+            let chains: Vec<_> = MinimumTransferAmount::<T>::iter_keys()
+                .filter(|(_, id)| *id == resource_id)
+                .map(|(chain_id, _)| chain_id)
+                .collect();
+            for chain_id in chains {
+                MinimumTransferAmount::<T>::remove(chain_id, resource_id);
+            }
+            let _ = ReserveVotes::<T>::clear_prefix(resource_id, u32::MAX, None);
+            ReserveReports::<T>::remove(resource_id);
+            ReserveAutoPause::<T>::remove(resource_id);
+            PausedResources::<T>::remove(resource_id);
+
+            Self::deposit_event(Event::ResourceUnbound(resource_id, asset));
+            Ok(().into())
+        }
+
         //
         // Executable calls. These can be triggered by a bridge transfer initiated on another chain
         //
@@ -262,6 +744,7 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let source = T::BridgeOrigin::ensure_origin(origin)?;
             let asset = Self::resources(resource_id).ok_or(Error::<T>::InvalidResourceId)?;
+            let amount = Self::amount_from_resource(resource_id, amount)?;
 
             let is_mintable_asset = Self::is_mintable_asset(&asset)?;
             if is_mintable_asset {
@@ -293,6 +776,7 @@ pub mod pallet {
             let from = T::BridgeOrigin::ensure_origin(origin)?;
             let asset = Self::resources(resource_id).ok_or(Error::<T>::InvalidResourceId)?;
             let _ = T::AssetGetter::get_asset_data(&asset)?;
+            let amount = Self::amount_from_resource(resource_id, amount)?;
 
             <T as Config>::EqCurrency::deposit_creating(&from, asset, amount, false, None)?;
             match AccountType::try_from(to.clone()) {
@@ -359,6 +843,41 @@ pub mod pallet {
         MinimumTransferAmountChanged(chainbridge::ChainId, chainbridge::ResourceId, T::Balance),
         /// ChainAddressType has changed. \[chainId, Option<ChainAddressType>\]
         ChainAddressTypeChanged(chainbridge::ChainId, Option<ChainAddressType>),
+        /// Protocol fee for transfers to chain has changed. \[chainId, Option<ProtocolFee>\]
+        ProtocolFeeChanged(
+            chainbridge::ChainId,
+            Option<ProtocolFee<T::AccountId, T::Balance>>,
+        ),
+        /// Congestion surcharge for transfers to chain has changed. \[chainId, surcharge\]
+        FeeSurchargeChanged(chainbridge::ChainId, Permill),
+        /// Decimals of resource on external chains have changed. \[resourceId, Option<decimals>\]
+        ResourceDecimalsChanged(chainbridge::ResourceId, Option<u8>),
+        /// Protocol fee charged for transfer out of the network.
+        /// \[who, chainId, flat_fee, asset, percent_fee, surcharge_fee\]
+        ProtocolFeeCharged(
+            T::AccountId,
+            chainbridge::ChainId,
+            T::Balance,
+            Asset,
+            T::Balance,
+            T::Balance,
+        ),
+        /// Relayer reported reserve of resource. \[who, resourceId, reserve\]
+        ReserveVoted(T::AccountId, chainbridge::ResourceId, T::Balance),
+        /// Reserve of resource is confirmed by relayers. \[resourceId, reserve, signers\]
+        ReserveReported(chainbridge::ResourceId, T::Balance, Vec<T::AccountId>),
+        /// Minted supply exceeds reported reserve. \[resourceId, supply, reserve\]
+        ReserveDeficit(chainbridge::ResourceId, T::Balance, T::Balance),
+        /// Auto pause on under-collateralization has changed. \[resourceId, enabled\]
+        ReserveAutoPauseChanged(chainbridge::ResourceId, bool),
+        /// Transfers out of the network are paused for resource. \[resourceId, reason\]
+        ResourcePaused(chainbridge::ResourceId, PauseReason),
+        /// Transfers out of the network are resumed for resource. \[resourceId\]
+        ResourceResumed(chainbridge::ResourceId),
+        /// Resource is bound to asset. \[resourceId, asset, Option<decimals>\]
+        ResourceBound(chainbridge::ResourceId, Asset, Option<u8>),
+        /// Resource is unbound from asset. \[resourceId, asset\]
+        ResourceUnbound(chainbridge::ResourceId, Asset),
     }
     #[pallet::error]
     pub enum Error<T> {
@@ -383,6 +902,30 @@ pub mod pallet {
         InvalidAccount,
         /// Attempt to set ChainAddressType to current value
         ChainAddressTypeEqual,
+        /// Recipient address checksum doesn't match
+        InvalidRecipientChecksum,
+        /// Recipient address is zero, funds would be burnt
+        ZeroRecipientAddress,
+        /// Only relayers can set fee surcharge
+        NotRelayer,
+        /// Protocol fee is not set for given `ChainId`
+        ProtocolFeeNotSet,
+        /// Surcharge is greater than `ProtocolFee::max_surcharge`
+        SurchargeExceedsBound,
+        /// Amount can't be represented in resource or native decimals
+        DecimalsConversionOverflow,
+        /// Amount has more precision than resource or native decimals allow
+        DecimalsPrecisionLoss,
+        /// Relayer already reported the same reserve
+        ReserveAlreadyReported,
+        /// Transfers out of the network are paused for resource, the reason is attached
+        ResourcePaused(PauseReason),
+        /// Resource is not paused
+        ResourceNotPaused,
+        /// Resource id is already mapped to `Asset`
+        ResourceAlreadyBound,
+        /// Asset is already mapped to another resource id
+        AssetAlreadyBound,
     }
 
     #[pallet::genesis_config]
@@ -500,6 +1043,229 @@ impl<T: Config> Pallet<T> {
         Ok(().into())
     }
 
+    fn update_protocol_fee(
+        dest_id: chainbridge::ChainId,
+        fee: Option<ProtocolFee<T::AccountId, T::Balance>>,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(
+            <chainbridge::Pallet<T>>::chain_whitelisted(dest_id),
+            Error::<T>::ChainNotWhitelisted
+        );
+
+        match &fee {
+            Some(fee) => {
+                ProtocolFees::<T>::insert(dest_id, fee);
+                FeeSurcharges::<T>::mutate(dest_id, |surcharge| {
+                    *surcharge = (*surcharge).min(fee.max_surcharge)
+                });
+            }
+            None => {
+                ProtocolFees::<T>::remove(dest_id);
+                FeeSurcharges::<T>::remove(dest_id);
+            }
+        }
+
+        Self::deposit_event(Event::ProtocolFeeChanged(dest_id, fee));
+        Ok(().into())
+    }
+
+    fn update_resource_decimals(
+        resource_id: chainbridge::ResourceId,
+        decimals: Option<u8>,
+    ) -> DispatchResultWithPostInfo {
+        Self::resources(resource_id).ok_or(Error::<T>::InvalidResourceId)?;
+
+        match decimals {
+            Some(value) => ResourceDecimals::<T>::insert(resource_id, value),
+            None => ResourceDecimals::<T>::remove(resource_id),
+        }
+
+        Self::deposit_event(Event::ResourceDecimalsChanged(resource_id, decimals));
+        Ok(().into())
+    }
+
+    /// Decimals of `resource_id` on external chains: the resource ones if set,
+    /// otherwise decimals of the bound asset
+    fn bridged_decimals(
+        resource_id: chainbridge::ResourceId,
+    ) -> Result<u8, sp_runtime::DispatchError> {
+        if let Some(decimals) = Self::resource_decimals(resource_id) {
+            return Ok(decimals);
+        }
+        let asset = Self::resources(resource_id).ok_or(Error::<T>::InvalidResourceId)?;
+        Ok(T::AssetGetter::decimals(&asset))
+    }
+
+    /// Scales `amount` bridged in `resource_id` decimals to native decimals.
+    /// Fails if the amount can't be converted back exactly.
+    pub fn amount_from_resource(
+        resource_id: chainbridge::ResourceId,
+        amount: T::Balance,
+    ) -> Result<T::Balance, sp_runtime::DispatchError> {
+        let decimals = Self::bridged_decimals(resource_id)?;
+        if decimals == DECIMALS {
+            return Ok(amount);
+        }
+        let external: XcmBalance = amount.saturated_into();
+        let native = balance_from_decimals::<T::Balance>(external, decimals)
+            .ok_or(Error::<T>::DecimalsConversionOverflow)?;
+        ensure!(
+            balance_into_decimals(native, decimals) == Some(external),
+            Error::<T>::DecimalsPrecisionLoss
+        );
+
+        Ok(native)
+    }
+
+    /// Scales native `amount` to `resource_id` decimals.
+    /// Fails if the amount can't be converted back exactly.
+    pub fn amount_into_resource(
+        resource_id: chainbridge::ResourceId,
+        amount: T::Balance,
+    ) -> Result<XcmBalance, sp_runtime::DispatchError> {
+        let decimals = Self::bridged_decimals(resource_id)?;
+        if decimals == DECIMALS {
+            return Ok(amount.saturated_into());
+        }
+        let external = balance_into_decimals(amount, decimals)
+            .ok_or(Error::<T>::DecimalsConversionOverflow)?;
+        ensure!(
+            balance_from_decimals::<T::Balance>(external, decimals) == Some(amount),
+            Error::<T>::DecimalsPrecisionLoss
+        );
+
+        Ok(external)
+    }
+
+    /// Charges protocol fee of `dest_id` chain for transfer of `amount` of `asset`
+    fn charge_protocol_fee(
+        source: &T::AccountId,
+        dest_id: chainbridge::ChainId,
+        asset: Asset,
+        amount: T::Balance,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        let fee = match Self::protocol_fee(dest_id) {
+            Some(fee) => fee,
+            None => return Ok(()),
+        };
+        let percent_fee = fee.percent.mul_ceil(amount);
+        let surcharge_fee = Self::fee_surcharge(dest_id).mul_ceil(amount);
+
+        T::EqCurrency::currency_transfer(
+            source,
+            &fee.recipient,
+            <T as eq_assets::Config>::MainAsset::get(),
+            fee.flat,
+            ExistenceRequirement::AllowDeath,
+            eq_primitives::TransferReason::BridgeFee,
+            true,
+        )?;
+        T::EqCurrency::currency_transfer(
+            source,
+            &fee.recipient,
+            asset,
+            percent_fee.saturating_add(surcharge_fee),
+            ExistenceRequirement::AllowDeath,
+            eq_primitives::TransferReason::BridgeFee,
+            true,
+        )?;
+
+        Self::deposit_event(Event::ProtocolFeeCharged(
+            source.clone(),
+            dest_id,
+            fee.flat,
+            asset,
+            percent_fee,
+            surcharge_fee,
+        ));
+        Ok(())
+    }
+
+    fn do_report_reserve(
+        who: T::AccountId,
+        resource_id: chainbridge::ResourceId,
+        asset: Asset,
+        reserve: T::Balance,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(
+            Self::reserve_vote(resource_id, &who) != Some(reserve),
+            Error::<T>::ReserveAlreadyReported
+        );
+        ReserveVotes::<T>::insert(resource_id, &who, reserve);
+        Self::deposit_event(Event::ReserveVoted(who, resource_id, reserve));
+
+        let mut signers: Vec<T::AccountId> = ReserveVotes::<T>::iter_prefix(resource_id)
+            .filter(|(_, vote)| *vote == reserve)
+            .map(|(relayer, _)| relayer)
+            .collect();
+        if (signers.len() as u32) < <chainbridge::Pallet<T>>::relayer_threshold() {
+            return Ok(().into());
+        }
+
+        signers.sort();
+        let _ = ReserveVotes::<T>::clear_prefix(resource_id, u32::MAX, None);
+        ReserveReports::<T>::insert(
+            resource_id,
+            ReserveReport {
+                reserve,
+                block: frame_system::Pallet::<T>::block_number(),
+                signers: signers.clone(),
+            },
+        );
+        Self::deposit_event(Event::ReserveReported(resource_id, reserve, signers));
+
+        let supply = T::EqCurrency::currency_total_issuance(asset);
+        if supply > reserve {
+            Self::deposit_event(Event::ReserveDeficit(resource_id, supply, reserve));
+            if Self::reserve_auto_pause(resource_id) && !Self::is_resource_paused(resource_id) {
+                PausedResources::<T>::insert(resource_id, PauseReason::InsufficientReserve);
+                Self::deposit_event(Event::ResourcePaused(
+                    resource_id,
+                    PauseReason::InsufficientReserve,
+                ));
+            }
+        }
+
+        Ok(().into())
+    }
+
+    /// Transfers out of the network are paused for resource
+    pub fn is_resource_paused(resource_id: chainbridge::ResourceId) -> bool {
+        PausedResources::<T>::contains_key(resource_id)
+    }
+
+    /// Minted supply of a resource compared with the last reported reserve
+    pub fn reserve_status(
+        resource_id: chainbridge::ResourceId,
+    ) -> Option<ReserveStatus<T::Balance, T::BlockNumber>> {
+        let asset = Self::resources(resource_id)?;
+        let supply = T::EqCurrency::currency_total_issuance(asset);
+        let report = Self::reserve_report(resource_id);
+        let pause_reason = Self::resource_pause_reason(resource_id);
+
+        Some(ReserveStatus {
+            asset,
+            supply,
+            reserve: report.as_ref().map(|r| r.reserve),
+            reported_at: report.as_ref().map(|r| r.block),
+            is_under_collateralized: report.map_or(false, |r| supply > r.reserve),
+            is_paused: pause_reason.is_some(),
+            pause_reason,
+        })
+    }
+
+    /// Reserve statuses of all resources with reports
+    pub fn reserve_statuses() -> Vec<(
+        chainbridge::ResourceId,
+        ReserveStatus<T::Balance, T::BlockNumber>,
+    )> {
+        ReserveReports::<T>::iter_keys()
+            .filter_map(|resource_id| {
+                Self::reserve_status(resource_id).map(|status| (resource_id, status))
+            })
+            .collect()
+    }
+
     fn is_mintable_asset(asset: &Asset) -> Result<bool, sp_runtime::DispatchError> {
         let asset_data = T::AssetGetter::get_asset_data(&asset)?;
 
@@ -529,15 +1295,16 @@ impl<T: Config> Pallet<T> {
             Self::withdrawals_enabled(resource_id, dest_id),
             Error::<T>::DisabledWithdrawals
         );
-        ensure!(
-            Self::is_address_valid(&recipient, dest_id),
-            Error::<T>::RecipientChainAddressTypeMismatch
-        );
+        if let Some(reason) = Self::resource_pause_reason(resource_id) {
+            return Err(Error::<T>::ResourcePaused(reason).into());
+        }
+        Self::ensure_address_valid(&recipient, dest_id)?;
         ensure!(
             amount >= <MinimumTransferAmount<T>>::get(dest_id, resource_id),
             Error::<T>::TransferAmountLowerMinimum
         );
         let asset = Self::resources(resource_id).ok_or(Error::<T>::InvalidResourceId)?;
+        let external_amount = Self::amount_into_resource(resource_id, amount)?;
         let is_basic_asset = asset == <T as eq_assets::Config>::MainAsset::get();
 
         let fee = chainbridge::Fees::<T>::get(dest_id);
@@ -558,6 +1325,7 @@ impl<T: Config> Pallet<T> {
             fee,
             ExistenceRequirement::AllowDeath,
         )?;
+        Self::charge_protocol_fee(&source, dest_id, asset, amount)?;
 
         let is_mintable_asset = Self::is_mintable_asset(&asset)?;
 
@@ -589,7 +1357,7 @@ impl<T: Config> Pallet<T> {
             dest_id,
             resource_id,
             recipient,
-            U256::from(amount.saturated_into::<u128>()),
+            U256::from(external_amount),
         )
     }
 
@@ -605,17 +1373,32 @@ impl<T: Config> Pallet<T> {
         }
     }
 
-    fn is_address_valid(recipient: &Vec<u8>, dest_id: chainbridge::ChainId) -> bool {
-        let expected_address_type = ChainAddressTypes::<T>::get(dest_id);
-        match expected_address_type {
-            Some(ChainAddressType::Ethereum) => recipient.len() == ETHEREUM_ADDRESS_LENGTH,
-            Some(ChainAddressType::Substrate) => recipient.len() == SUBSTRATE_ADDRESS_LENGTH,
-            Some(ChainAddressType::SubstrateWithPrefix) => {
-                recipient.len() == SUBSTRATE_ADDRESS_LENGTH
-                    || recipient.len() == SUBSTRATE_WITH_PREFIX_ADDRESS_LENGTH
+    fn ensure_address_valid(
+        recipient: &[u8],
+        dest_id: chainbridge::ChainId,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        let address_type = match ChainAddressTypes::<T>::get(dest_id) {
+            Some(address_type) => address_type,
+            None => return Ok(()),
+        };
+
+        address_type.validate(recipient).map_err(|error| {
+            frame_support::log::error!(
+                "{}:{}. Invalid recipient {:?} for chain {:?} with address type {:?}: {:?}",
+                file!(),
+                line!(),
+                recipient,
+                dest_id,
+                address_type,
+                error
+            );
+            match error {
+                AddressValidationError::Format => Error::<T>::RecipientChainAddressTypeMismatch,
+                AddressValidationError::Checksum => Error::<T>::InvalidRecipientChecksum,
+                AddressValidationError::ZeroAddress => Error::<T>::ZeroRecipientAddress,
             }
-            None => true,
-        }
+            .into()
+        })
     }
 }
 
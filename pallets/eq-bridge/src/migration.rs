@@ -0,0 +1,46 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Config, Pallet, PauseReason, PausedResources};
+use frame_support::{
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+
+/// Replaces `bool` pause flags with reasons. Resources could be paused only
+/// on under-collateralization before, so they get `InsufficientReserve`.
+pub fn migrate_paused_resources<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return Weight::zero();
+    }
+
+    let mut translated = 0u64;
+    PausedResources::<T>::translate::<bool, _>(|_, paused| {
+        translated += 1;
+        paused.then(|| PauseReason::InsufficientReserve)
+    });
+    StorageVersion::new(1).put::<Pallet<T>>();
+
+    frame_support::log::info!(
+        target: "eq_bridge",
+        "migrate_paused_resources: {:?} resources translated",
+        translated
+    );
+
+    T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
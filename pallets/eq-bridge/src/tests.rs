@@ -25,9 +25,9 @@ use crate::mock::{
 };
 
 use super::mock::{
-    assert_events, event_exists, expect_event, new_test_ext, ChainBridge, EqBridge, NativeTokenId,
-    ProposalLifetime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Test, DEFAULT_FEE, ENDOWED_BALANCE,
-    RELAYER_A, RELAYER_B, RELAYER_C, USER,
+    assert_events, event_exists, expect_event, new_test_ext, ChainBridge, EqAssets, EqBridge,
+    NativeTokenId, ProposalLifetime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Test, DEFAULT_FEE,
+    ENDOWED_BALANCE, RELAYER_A, RELAYER_B, RELAYER_C, USER,
 };
 use super::*;
 use codec::Encode;
@@ -1463,3 +1463,721 @@ fn transfer_invalid_asset_type() {
         assert_eq!(get_lpt0_balance(USER), Positive(0));
     })
 }
+
+#[test]
+fn validate_chain_address() {
+    use AddressValidationError::*;
+
+    let alice: [u8; 32] = [
+        0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f,
+        0xd6, 0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d,
+        0xa2, 0x7d,
+    ];
+    assert_eq!(ChainAddressType::Substrate.validate(&alice), Ok(()));
+    assert_eq!(
+        ChainAddressType::Substrate.validate(&alice[1..]),
+        Err(Format)
+    );
+    assert_eq!(
+        ChainAddressType::Substrate.validate(&[0; 32]),
+        Err(ZeroAddress)
+    );
+    assert_eq!(ChainAddressType::Ethereum.validate(&[1; 20]), Ok(()));
+    assert_eq!(
+        ChainAddressType::Ethereum.validate(&[0; 20]),
+        Err(ZeroAddress)
+    );
+
+    // polkadot address 15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5
+    let mut ss58 = vec![0];
+    ss58.extend_from_slice(&alice);
+    ss58.extend_from_slice(&[0x7f, 0x46]);
+    assert_eq!(ChainAddressType::Ss58(0).validate(&ss58), Ok(()));
+    assert_eq!(ChainAddressType::Ss58(2).validate(&ss58), Err(Format));
+    assert_eq!(ChainAddressType::Ss58(0).validate(&ss58[..33]), Err(Format));
+    ss58[34] ^= 1;
+    assert_eq!(ChainAddressType::Ss58(0).validate(&ss58), Err(Checksum));
+    assert_eq!(ChainAddressType::Ss58(16_384).validate(&ss58), Err(Format));
+
+    let eth = b"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    assert_eq!(ChainAddressType::EthereumChecksum.validate(eth), Ok(()));
+    assert_eq!(
+        ChainAddressType::EthereumChecksum.validate(b"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"),
+        Err(Checksum)
+    );
+    assert_eq!(
+        ChainAddressType::EthereumChecksum.validate(&eth[2..]),
+        Err(Format)
+    );
+    assert_eq!(
+        ChainAddressType::EthereumChecksum.validate(b"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeZ"),
+        Err(Format)
+    );
+    assert_eq!(
+        ChainAddressType::EthereumChecksum.validate(b"0x0000000000000000000000000000000000000000"),
+        Err(ZeroAddress)
+    );
+}
+
+#[test]
+fn transfer_native_invalid_recipient() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        let resource_id = NativeTokenId::get();
+        let amount = 100;
+
+        assert_ok!(EqBridge::set_resource(
+            RawOrigin::Root.into(),
+            resource_id,
+            eq_primitives::asset::EQ
+        ));
+        assert_ok!(ChainBridge::whitelist_chain(
+            RuntimeOrigin::root(),
+            dest_chain,
+            DEFAULT_FEE
+        ));
+        assert_ok!(EqBridge::enable_withdrawals(
+            RawOrigin::Root.into(),
+            resource_id,
+            dest_chain
+        ));
+        assert_ok!(EqBridge::set_chain_address_type(
+            RawOrigin::Root.into(),
+            dest_chain,
+            Some(ChainAddressType::EthereumChecksum)
+        ));
+
+        assert_noop!(
+            EqBridge::transfer_native(
+                RuntimeOrigin::signed(USER),
+                amount,
+                vec![99],
+                dest_chain,
+                resource_id,
+            ),
+            Error::<Test>::RecipientChainAddressTypeMismatch
+        );
+        assert_noop!(
+            EqBridge::transfer_native(
+                RuntimeOrigin::signed(USER),
+                amount,
+                b"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".to_vec(),
+                dest_chain,
+                resource_id,
+            ),
+            Error::<Test>::InvalidRecipientChecksum
+        );
+        assert_noop!(
+            EqBridge::transfer_native(
+                RuntimeOrigin::signed(USER),
+                amount,
+                b"0x0000000000000000000000000000000000000000".to_vec(),
+                dest_chain,
+                resource_id,
+            ),
+            Error::<Test>::ZeroRecipientAddress
+        );
+        assert_ok!(EqBridge::transfer_native(
+            RuntimeOrigin::signed(USER),
+            amount,
+            b"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_vec(),
+            dest_chain,
+            resource_id,
+        ));
+    })
+}
+
+#[test]
+fn transfer_native_with_protocol_fee() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 6;
+        let resource_id = EthTokenId::get();
+        let amount = 1_000_u128;
+        let recipient = vec![99];
+        let fee_recipient: AccountId = 77;
+        let asset = eq_primitives::asset::ETH;
+        let fee = ProtocolFee {
+            flat: 10,
+            percent: Permill::from_percent(1),
+            max_surcharge: Permill::from_percent(2),
+            recipient: fee_recipient,
+        };
+
+        assert_ok!(ModuleBalances::deposit(
+            RuntimeOrigin::root(),
+            asset,
+            USER,
+            amount + 20
+        ));
+        assert_ok!(EqBridge::set_resource(
+            RuntimeOrigin::root(),
+            resource_id,
+            asset
+        ));
+        assert_ok!(EqBridge::enable_withdrawals(
+            RawOrigin::Root.into(),
+            resource_id,
+            dest_chain
+        ));
+
+        assert_noop!(
+            EqBridge::set_protocol_fee(RuntimeOrigin::root(), dest_chain, Some(fee.clone())),
+            Error::<Test>::ChainNotWhitelisted
+        );
+        assert_ok!(ChainBridge::whitelist_chain(
+            RuntimeOrigin::root(),
+            dest_chain,
+            DEFAULT_FEE
+        ));
+        assert_noop!(
+            EqBridge::set_protocol_fee(RuntimeOrigin::signed(USER), dest_chain, Some(fee.clone())),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            EqBridge::set_fee_surcharge(
+                RuntimeOrigin::signed(RELAYER_A),
+                dest_chain,
+                Permill::from_percent(1)
+            ),
+            Error::<Test>::NotRelayer
+        );
+        assert_ok!(ChainBridge::add_relayer(RuntimeOrigin::root(), RELAYER_A));
+        assert_noop!(
+            EqBridge::set_fee_surcharge(
+                RuntimeOrigin::signed(RELAYER_A),
+                dest_chain,
+                Permill::from_percent(1)
+            ),
+            Error::<Test>::ProtocolFeeNotSet
+        );
+
+        assert_ok!(EqBridge::set_protocol_fee(
+            RuntimeOrigin::root(),
+            dest_chain,
+            Some(fee.clone())
+        ));
+        assert_noop!(
+            EqBridge::set_fee_surcharge(
+                RuntimeOrigin::signed(RELAYER_A),
+                dest_chain,
+                Permill::from_percent(3)
+            ),
+            Error::<Test>::SurchargeExceedsBound
+        );
+        assert_ok!(EqBridge::set_fee_surcharge(
+            RuntimeOrigin::signed(RELAYER_A),
+            dest_chain,
+            Permill::from_percent(1)
+        ));
+
+        assert_ok!(EqBridge::transfer_native(
+            RuntimeOrigin::signed(USER),
+            amount,
+            recipient,
+            dest_chain,
+            resource_id,
+        ));
+
+        event_exists(crate::Event::<Test>::ProtocolFeeCharged(
+            USER, dest_chain, 10, asset, 10, 10,
+        ));
+        assert_eq!(get_eth_balance(USER), Positive(0));
+        assert_eq!(get_eth_balance(fee_recipient), Positive(20));
+        assert_eq!(get_basic_balance(fee_recipient), Positive(10));
+        assert_eq!(
+            get_basic_balance(USER),
+            Positive(ENDOWED_BALANCE - DEFAULT_FEE - 10)
+        );
+
+        // lowering the bound cuts the surcharge
+        assert_ok!(EqBridge::set_protocol_fee(
+            RuntimeOrigin::root(),
+            dest_chain,
+            Some(ProtocolFee {
+                max_surcharge: Permill::zero(),
+                ..fee
+            })
+        ));
+        assert_eq!(EqBridge::fee_surcharge(dest_chain), Permill::zero());
+
+        assert_ok!(EqBridge::set_protocol_fee(
+            RuntimeOrigin::root(),
+            dest_chain,
+            None
+        ));
+        assert_eq!(EqBridge::protocol_fee(dest_chain), None);
+    })
+}
+
+#[test]
+fn transfers_with_resource_decimals() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 6;
+        let resource_id = EthTokenId::get();
+        let recipient = vec![99];
+        let asset = eq_primitives::asset::ETH;
+
+        assert_noop!(
+            EqBridge::set_resource_decimals(RuntimeOrigin::root(), resource_id, Some(6)),
+            Error::<Test>::InvalidResourceId
+        );
+        assert_ok!(EqBridge::set_resource(
+            RuntimeOrigin::root(),
+            resource_id,
+            asset
+        ));
+        assert_noop!(
+            EqBridge::set_resource_decimals(RuntimeOrigin::signed(USER), resource_id, Some(6)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(EqBridge::set_resource_decimals(
+            RuntimeOrigin::root(),
+            resource_id,
+            Some(6)
+        ));
+        event_exists(crate::Event::<Test>::ResourceDecimalsChanged(
+            resource_id,
+            Some(6),
+        ));
+        assert_eq!(EqBridge::resource_decimals(resource_id), Some(6));
+
+        // deposit of 1.5 tokens with 6 decimals
+        assert_ok!(EqBridge::transfer(
+            RuntimeOrigin::signed(ChainBridge::account_id()),
+            USER,
+            1_500_000,
+            resource_id
+        ));
+        assert_eq!(get_eth_balance(USER), Positive(1_500_000_000));
+
+        assert_ok!(ChainBridge::whitelist_chain(
+            RuntimeOrigin::root(),
+            dest_chain,
+            DEFAULT_FEE
+        ));
+        assert_ok!(EqBridge::enable_withdrawals(
+            RawOrigin::Root.into(),
+            resource_id,
+            dest_chain
+        ));
+
+        // last 3 digits can't be represented with 6 decimals
+        assert_noop!(
+            EqBridge::transfer_native(
+                RuntimeOrigin::signed(USER),
+                1_000_000_001,
+                recipient.clone(),
+                dest_chain,
+                resource_id,
+            ),
+            Error::<Test>::DecimalsPrecisionLoss
+        );
+
+        assert_ok!(EqBridge::transfer_native(
+            RuntimeOrigin::signed(USER),
+            1_000_000_000,
+            recipient.clone(),
+            dest_chain,
+            resource_id,
+        ));
+        expect_event(chainbridge::Event::FungibleTransfer(
+            dest_chain,
+            1,
+            resource_id,
+            1_000_000.into(),
+            recipient,
+        ));
+        assert_eq!(get_eth_balance(USER), Positive(500_000_000));
+
+        assert_ok!(EqBridge::set_resource_decimals(
+            RuntimeOrigin::root(),
+            resource_id,
+            None
+        ));
+        assert_eq!(EqBridge::resource_decimals(resource_id), None);
+    })
+}
+
+#[test]
+fn resource_decimals_reject_precision_loss_on_deposit() {
+    new_test_ext().execute_with(|| {
+        let resource_id = EthTokenId::get();
+
+        assert_ok!(EqBridge::set_resource(
+            RuntimeOrigin::root(),
+            resource_id,
+            eq_primitives::asset::ETH
+        ));
+        assert_ok!(EqBridge::set_resource_decimals(
+            RuntimeOrigin::root(),
+            resource_id,
+            Some(18)
+        ));
+
+        assert_noop!(
+            EqBridge::transfer(
+                RuntimeOrigin::signed(ChainBridge::account_id()),
+                USER,
+                1_000_000_001,
+                resource_id
+            ),
+            Error::<Test>::DecimalsPrecisionLoss
+        );
+        assert_ok!(EqBridge::transfer(
+            RuntimeOrigin::signed(ChainBridge::account_id()),
+            USER,
+            2_000_000_000,
+            resource_id
+        ));
+        assert_eq!(get_eth_balance(USER), Positive(2));
+    })
+}
+
+#[test]
+fn resource_decimals_default_to_asset_decimals() {
+    new_test_ext().execute_with(|| {
+        let resource_id = EthTokenId::get();
+        let asset = eq_primitives::asset::ETH;
+
+        assert_ok!(EqBridge::set_resource(
+            RuntimeOrigin::root(),
+            resource_id,
+            asset
+        ));
+        assert_ok!(EqAssets::set_decimals(
+            RuntimeOrigin::root(),
+            asset,
+            Some(18)
+        ));
+
+        assert_ok!(EqBridge::transfer(
+            RuntimeOrigin::signed(ChainBridge::account_id()),
+            USER,
+            2_000_000_000,
+            resource_id
+        ));
+        assert_eq!(get_eth_balance(USER), Positive(2));
+
+        // resource decimals take precedence
+        assert_ok!(EqBridge::set_resource_decimals(
+            RuntimeOrigin::root(),
+            resource_id,
+            Some(6)
+        ));
+        assert_ok!(EqBridge::transfer(
+            RuntimeOrigin::signed(ChainBridge::account_id()),
+            USER,
+            1_000_000,
+            resource_id
+        ));
+        assert_eq!(get_eth_balance(USER), Positive(1_000_000_002));
+    })
+}
+
+#[test]
+fn reserve_report_pauses_under_collateralized_resource() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 6;
+        let resource_id = EthTokenId::get();
+        let asset = eq_primitives::asset::ETH;
+
+        assert_ok!(ModuleBalances::deposit(
+            RuntimeOrigin::root(),
+            asset,
+            USER,
+            100
+        ));
+        assert_ok!(ChainBridge::set_threshold(
+            RuntimeOrigin::root(),
+            TEST_THRESHOLD,
+        ));
+        assert_ok!(ChainBridge::add_relayer(RuntimeOrigin::root(), RELAYER_A));
+        assert_ok!(ChainBridge::add_relayer(RuntimeOrigin::root(), RELAYER_B));
+        assert_ok!(ChainBridge::whitelist_chain(
+            RuntimeOrigin::root(),
+            dest_chain,
+            DEFAULT_FEE
+        ));
+
+        assert_noop!(
+            EqBridge::report_reserve(RuntimeOrigin::signed(RELAYER_A), resource_id, 100),
+            Error::<Test>::InvalidResourceId
+        );
+        assert_ok!(EqBridge::set_resource(
+            RuntimeOrigin::root(),
+            resource_id,
+            asset
+        ));
+        assert_ok!(EqBridge::enable_withdrawals(
+            RawOrigin::Root.into(),
+            resource_id,
+            dest_chain
+        ));
+        assert_noop!(
+            EqBridge::report_reserve(RuntimeOrigin::signed(USER), resource_id, 100),
+            Error::<Test>::NotRelayer
+        );
+        assert_noop!(
+            EqBridge::set_reserve_auto_pause(RuntimeOrigin::signed(USER), resource_id, true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(EqBridge::set_reserve_auto_pause(
+            RuntimeOrigin::root(),
+            resource_id,
+            true
+        ));
+
+        let supply = EqBridge::reserve_status(resource_id).unwrap().supply;
+        let reserve = supply - 1;
+
+        assert_ok!(EqBridge::report_reserve(
+            RuntimeOrigin::signed(RELAYER_A),
+            resource_id,
+            reserve
+        ));
+        assert_noop!(
+            EqBridge::report_reserve(RuntimeOrigin::signed(RELAYER_A), resource_id, reserve),
+            Error::<Test>::ReserveAlreadyReported
+        );
+        assert_eq!(EqBridge::reserve_report(resource_id), None);
+
+        assert_ok!(EqBridge::report_reserve(
+            RuntimeOrigin::signed(RELAYER_B),
+            resource_id,
+            reserve
+        ));
+        event_exists(crate::Event::<Test>::ReserveReported(
+            resource_id,
+            reserve,
+            vec![RELAYER_A, RELAYER_B],
+        ));
+        event_exists(crate::Event::<Test>::ReserveDeficit(
+            resource_id,
+            supply,
+            reserve,
+        ));
+        expect_event(crate::Event::<Test>::ResourcePaused(
+            resource_id,
+            PauseReason::InsufficientReserve,
+        ));
+        assert_eq!(EqBridge::reserve_vote(resource_id, RELAYER_A), None);
+
+        let status = EqBridge::reserve_status(resource_id).unwrap();
+        assert_eq!(status.reserve, Some(reserve));
+        assert!(status.is_under_collateralized);
+        assert!(status.is_paused);
+        assert_eq!(status.pause_reason, Some(PauseReason::InsufficientReserve));
+
+        assert_noop!(
+            EqBridge::transfer_native(
+                RuntimeOrigin::signed(USER),
+                10,
+                vec![99],
+                dest_chain,
+                resource_id,
+            ),
+            Error::<Test>::ResourcePaused(PauseReason::InsufficientReserve)
+        );
+
+        assert_ok!(EqBridge::resume_resource(
+            RuntimeOrigin::root(),
+            resource_id
+        ));
+        expect_event(crate::Event::<Test>::ResourceResumed(resource_id));
+        assert_noop!(
+            EqBridge::resume_resource(RuntimeOrigin::root(), resource_id),
+            Error::<Test>::ResourceNotPaused
+        );
+
+        // sufficient reserve doesn't pause the resource
+        for relayer in [RELAYER_A, RELAYER_B] {
+            assert_ok!(EqBridge::report_reserve(
+                RuntimeOrigin::signed(relayer),
+                resource_id,
+                supply
+            ));
+        }
+        let status = EqBridge::reserve_status(resource_id).unwrap();
+        assert_eq!(status.reserve, Some(supply));
+        assert!(!status.is_under_collateralized);
+        assert!(!status.is_paused);
+        assert_eq!(EqBridge::reserve_statuses(), vec![(resource_id, status)]);
+    })
+}
+
+#[test]
+fn pause_resource_with_reason() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 6;
+        let resource_id = EthTokenId::get();
+        let asset = eq_primitives::asset::ETH;
+
+        assert_ok!(ChainBridge::whitelist_chain(
+            RuntimeOrigin::root(),
+            dest_chain,
+            DEFAULT_FEE
+        ));
+        assert_noop!(
+            EqBridge::pause_resource(RuntimeOrigin::root(), resource_id, PauseReason::Maintenance),
+            Error::<Test>::InvalidResourceId
+        );
+        assert_ok!(EqBridge::set_resource(
+            RuntimeOrigin::root(),
+            resource_id,
+            asset
+        ));
+        assert_ok!(EqBridge::enable_withdrawals(
+            RawOrigin::Root.into(),
+            resource_id,
+            dest_chain
+        ));
+
+        assert_noop!(
+            EqBridge::pause_resource(
+                RuntimeOrigin::signed(USER),
+                resource_id,
+                PauseReason::Maintenance
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(EqBridge::pause_resource(
+            RuntimeOrigin::root(),
+            resource_id,
+            PauseReason::Maintenance
+        ));
+        expect_event(crate::Event::<Test>::ResourcePaused(
+            resource_id,
+            PauseReason::Maintenance,
+        ));
+        assert_noop!(
+            EqBridge::transfer_native(
+                RuntimeOrigin::signed(USER),
+                10,
+                vec![99],
+                dest_chain,
+                resource_id,
+            ),
+            Error::<Test>::ResourcePaused(PauseReason::Maintenance)
+        );
+
+        assert_ok!(EqBridge::pause_resource(
+            RuntimeOrigin::root(),
+            resource_id,
+            PauseReason::SecurityIncident
+        ));
+        assert_eq!(
+            EqBridge::resource_pause_reason(resource_id),
+            Some(PauseReason::SecurityIncident)
+        );
+
+        assert_ok!(EqBridge::resume_resource(
+            RuntimeOrigin::root(),
+            resource_id
+        ));
+        assert!(!EqBridge::is_resource_paused(resource_id));
+    })
+}
+
+#[test]
+fn bind_and_unbind_resource() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 6;
+        let resource_id = EthTokenId::get();
+        let asset = eq_primitives::asset::ETH;
+
+        assert_ok!(ChainBridge::whitelist_chain(
+            RuntimeOrigin::root(),
+            dest_chain,
+            DEFAULT_FEE
+        ));
+        assert_noop!(
+            EqBridge::bind_resource(
+                RuntimeOrigin::signed(USER),
+                resource_id,
+                asset,
+                Some(6),
+                vec![(dest_chain, 100)]
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            EqBridge::bind_resource(
+                RuntimeOrigin::root(),
+                resource_id,
+                asset,
+                Some(6),
+                vec![(dest_chain + 1, 100)]
+            ),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        assert_ok!(EqBridge::bind_resource(
+            RuntimeOrigin::root(),
+            resource_id,
+            asset,
+            Some(6),
+            vec![(dest_chain, 100)]
+        ));
+        event_exists(crate::Event::<Test>::ResourceBound(
+            resource_id,
+            asset,
+            Some(6),
+        ));
+        expect_event(crate::Event::<Test>::MinimumTransferAmountChanged(
+            dest_chain,
+            resource_id,
+            100,
+        ));
+        assert_eq!(EqBridge::resources(resource_id), Some(asset));
+        assert_eq!(EqBridge::asset_resource(asset), Some(resource_id));
+        assert_eq!(EqBridge::resource_decimals(resource_id), Some(6));
+        assert_eq!(
+            EqBridge::minimum_transfer_amount(dest_chain, resource_id),
+            100
+        );
+
+        assert_noop!(
+            EqBridge::bind_resource(
+                RuntimeOrigin::root(),
+                resource_id,
+                eq_primitives::asset::BTC,
+                None,
+                vec![]
+            ),
+            Error::<Test>::ResourceAlreadyBound
+        );
+        assert_noop!(
+            EqBridge::bind_resource(
+                RuntimeOrigin::root(),
+                chainbridge::derive_resource_id(dest_chain, b"other"),
+                asset,
+                None,
+                vec![]
+            ),
+            Error::<Test>::AssetAlreadyBound
+        );
+
+        assert_ok!(EqBridge::enable_withdrawals(
+            RawOrigin::Root.into(),
+            resource_id,
+            dest_chain
+        ));
+        assert_ok!(EqBridge::unbind_resource(
+            RuntimeOrigin::root(),
+            resource_id
+        ));
+        expect_event(crate::Event::<Test>::ResourceUnbound(resource_id, asset));
+        assert_eq!(EqBridge::resources(resource_id), None);
+        assert_eq!(EqBridge::asset_resource(asset), None);
+        assert_eq!(EqBridge::resource_decimals(resource_id), None);
+        assert_eq!(
+            EqBridge::minimum_transfer_amount(dest_chain, resource_id),
+            0
+        );
+        assert!(EqBridge::enabled_withdrawals(resource_id).is_empty());
+        assert_noop!(
+            EqBridge::unbind_resource(RuntimeOrigin::root(), resource_id),
+            Error::<Test>::InvalidResourceId
+        );
+    })
+}
@@ -31,6 +31,15 @@ pub trait WeightInfo {
     fn enable_withdrawals() -> Weight;
     fn disable_withdrawals() -> Weight;
     fn set_minimum_transfer_amount() -> Weight;
+    fn set_protocol_fee() -> Weight;
+    fn set_fee_surcharge() -> Weight;
+    fn set_resource_decimals() -> Weight;
+    fn report_reserve() -> Weight;
+    fn set_reserve_auto_pause() -> Weight;
+    fn resume_resource() -> Weight;
+    fn pause_resource() -> Weight;
+    fn bind_resource(c: u32) -> Weight;
+    fn unbind_resource() -> Weight;
 }
 
 // for tests
@@ -63,4 +72,40 @@ impl crate::WeightInfo for () {
     fn set_minimum_transfer_amount() -> Weight {
         Weight::zero()
     }
+
+    fn set_protocol_fee() -> Weight {
+        Weight::zero()
+    }
+
+    fn set_fee_surcharge() -> Weight {
+        Weight::zero()
+    }
+
+    fn set_resource_decimals() -> Weight {
+        Weight::zero()
+    }
+
+    fn report_reserve() -> Weight {
+        Weight::zero()
+    }
+
+    fn set_reserve_auto_pause() -> Weight {
+        Weight::zero()
+    }
+
+    fn resume_resource() -> Weight {
+        Weight::zero()
+    }
+
+    fn pause_resource() -> Weight {
+        Weight::zero()
+    }
+
+    fn bind_resource(_c: u32) -> Weight {
+        Weight::zero()
+    }
+
+    fn unbind_resource() -> Weight {
+        Weight::zero()
+    }
 }
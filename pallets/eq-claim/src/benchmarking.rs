@@ -150,6 +150,43 @@ benchmarks! {
         verify {
             assert_eq!(Claims::<T>::get(eth_address), None);
         }
+    set_claim_unlock_epoch {
+        let secret_key = secp256k1::SecretKey::parse(&keccak_256(&0u32.encode())).unwrap();
+        let eth_address = eth(&secret_key);
+        create_claim::<T>(0)?;
+    }: _(RawOrigin::Root, eth_address, Some(1))
+    verify {
+        assert_eq!(VestingUnlockEpoch::<T>::get(eth_address), Some(1));
+    }
+
+    set_claim_deadline {
+    }: _(RawOrigin::Root, Some(1u32.into()))
+    verify {
+        assert_eq!(ClaimDeadline::<T>::get(), Some(1u32.into()));
+    }
+
+    extend_claim_grace {
+        let secret_key = secp256k1::SecretKey::parse(&keccak_256(&0u32.encode())).unwrap();
+        let eth_address = eth(&secret_key);
+        create_claim::<T>(0)?;
+    }: _(RawOrigin::Root, eth_address, Some(1u32.into()))
+    verify {
+        assert_eq!(GraceExtensions::<T>::get(eth_address), Some(1u32.into()));
+    }
+
+    sweep_unclaimed {
+        let secret_key = secp256k1::SecretKey::parse(&keccak_256(&0u32.encode())).unwrap();
+        let eth_address = eth(&secret_key);
+        let caller: T::AccountId = account("caller", 0, SEED);
+        create_claim::<T>(0)?;
+        ClaimDeadline::<T>::put(T::BlockNumber::from(1u32));
+        GraceExtensions::<T>::insert(eth_address, T::BlockNumber::from(2u32));
+        frame_system::Pallet::<T>::set_block_number(3u32.into());
+    }: _(RawOrigin::Signed(caller), eth_address)
+    verify {
+        assert_eq!(Claims::<T>::get(eth_address), None);
+    }
+
     // Benchmark the time it takes to do `repeat` number of keccak256 hashes
     #[extra]
     keccak256 {
@@ -191,6 +228,10 @@ mod tests {
             assert_ok!(test_benchmark_mint_claim::<Test>());
             assert_ok!(test_benchmark_claim_attest::<Test>());
             assert_ok!(test_benchmark_attest::<Test>());
+            assert_ok!(test_benchmark_set_claim_unlock_epoch::<Test>());
+            assert_ok!(test_benchmark_set_claim_deadline::<Test>());
+            assert_ok!(test_benchmark_extend_claim_grace::<Test>());
+            assert_ok!(test_benchmark_sweep_unclaimed::<Test>());
             assert_ok!(test_benchmark_keccak256::<Test>());
             assert_ok!(test_benchmark_eth_recover::<Test>());
         });
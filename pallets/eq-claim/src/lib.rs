@@ -23,6 +23,9 @@
 //! Equilibrium's Balances Pallet is a Substrate module that processes claims
 //! from Ethereum addresses. It is used in Equilibrium Substrate to payout
 //! claims generated during Token Swap event
+//!
+//! Claims may be given a deadline block. Allocations left unclaimed after the deadline
+//! and the grace extension of the claim, if any, are swept to the treasury by anyone.
 
 mod benchmarking;
 mod mock;
@@ -67,6 +70,8 @@ pub enum ValidityError {
     SignerHasNoClaim = 1,
     /// An invalid statement was made for a claim
     InvalidStatement = 2,
+    /// The claim deadline has passed
+    ClaimExpired = 3,
 }
 
 impl From<ValidityError> for u8 {
@@ -113,6 +118,8 @@ pub mod pallet {
         type UnsignedPriority: Get<TransactionPriority>;
         /// Standard balances pallet for utility token or adapter
         type Currency: Currency<Self::AccountId, Balance = Self::Balance>;
+        /// Account receiving allocations swept after the claim deadline
+        type TreasuryAccountId: Get<Self::AccountId>;
     }
 
     #[pallet::call]
@@ -355,6 +362,8 @@ pub mod pallet {
                 line!(),
             );
             Vesting::<T>::take(&old).map(|c| Vesting::<T>::insert(&new, c));
+            VestingUnlockEpoch::<T>::take(&old).map(|e| VestingUnlockEpoch::<T>::insert(&new, e));
+            GraceExtensions::<T>::take(&old).map(|g| GraceExtensions::<T>::insert(&new, g));
             let s = <Signing<T>>::take(&old);
             <Signing<T>>::insert(&new, s);
             maybe_preclaim.map(|preclaim| {
@@ -367,6 +376,158 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Key vesting schedule of the claim to unlock `epoch` of the vesting pallet or
+        /// remove the key. Block number of the keyed schedule is counted from the epoch block.
+        ///
+        /// The dispatch origin for this call must be _Root_.
+        ///
+        /// Parameters:
+        /// - `who`: The Ethereum address of the claim with vesting schedule.
+        /// - `epoch`: Unlock epoch the vesting schedule starts at.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::set_claim_unlock_epoch())]
+        pub fn set_claim_unlock_epoch(
+            origin: OriginFor<T>,
+            who: EthereumAddress,
+            epoch: Option<u32>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            match epoch {
+                Some(epoch) => {
+                    eq_ensure!(
+                        <Vesting<T>>::contains_key(&who),
+                        Error::<T>::ClaimHasNoVesting,
+                        target: "eq_claim",
+                        "{}:{}. Claim has no vesting schedule. Address: {:?}.",
+                        file!(),
+                        line!(),
+                        who
+                    );
+                    <VestingUnlockEpoch<T>>::insert(who, epoch);
+                }
+                None => <VestingUnlockEpoch<T>>::remove(who),
+            }
+
+            Self::deposit_event(Event::<T>::ClaimUnlockEpochSet(who, epoch));
+            Ok(().into())
+        }
+
+        /// Set the block after which unclaimed allocations may be swept to the treasury
+        /// or remove the deadline.
+        ///
+        /// The dispatch origin for this call must be _Root_.
+        ///
+        /// Parameters:
+        /// - `deadline`: Last block in which claims may be collected.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::set_claim_deadline())]
+        pub fn set_claim_deadline(
+            origin: OriginFor<T>,
+            deadline: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            match deadline {
+                Some(deadline) => <ClaimDeadline<T>>::put(deadline),
+                None => <ClaimDeadline<T>>::kill(),
+            }
+
+            Self::deposit_event(Event::<T>::ClaimDeadlineSet(deadline));
+            Ok(().into())
+        }
+
+        /// Extend the deadline of a single claim past the common claim deadline
+        /// or remove the extension.
+        ///
+        /// The dispatch origin for this call must be _Root_.
+        ///
+        /// Parameters:
+        /// - `who`: The Ethereum address of the claim.
+        /// - `until`: Last block in which the claim may be collected.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::extend_claim_grace())]
+        pub fn extend_claim_grace(
+            origin: OriginFor<T>,
+            who: EthereumAddress,
+            until: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            match until {
+                Some(until) => {
+                    eq_ensure!(
+                        <Claims<T>>::contains_key(&who),
+                        Error::<T>::SignerHasNoClaim,
+                        target: "eq_claim",
+                        "{}:{}. Signer has no claim. Address: {:?}.",
+                        file!(),
+                        line!(),
+                        who
+                    );
+                    <GraceExtensions<T>>::insert(who, until);
+                }
+                None => <GraceExtensions<T>>::remove(who),
+            }
+
+            Self::deposit_event(Event::<T>::ClaimGraceExtended(who, until));
+            Ok(().into())
+        }
+
+        /// Sweep allocation of the claim not collected before its deadline to the treasury.
+        ///
+        /// The dispatch origin for this call must be _Signed_, any account may sweep.
+        ///
+        /// Parameters:
+        /// - `who`: The Ethereum address of the expired claim.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::sweep_unclaimed())]
+        pub fn sweep_unclaimed(
+            origin: OriginFor<T>,
+            who: EthereumAddress,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let option_balance_of = <Claims<T>>::get(&who);
+            let balance = ok_or_error!(
+                option_balance_of,
+                Error::<T>::SignerHasNoClaim,
+                "{}:{}. Signer has no claim. Address: {:?}.",
+                file!(),
+                line!(),
+                who
+            )?;
+            eq_ensure!(
+                Self::is_expired(&who),
+                Error::<T>::ClaimDeadlineNotPassed,
+                target: "eq_claim",
+                "{}:{}. Claim deadline has not passed. Address: {:?}, deadline: {:?}.",
+                file!(),
+                line!(),
+                who,
+                Self::deadline_of(&who)
+            );
+
+            let option_checked = Self::total().checked_sub(&balance);
+            let new_total = ok_or_error!(
+                option_checked,
+                Error::<T>::PotUnderflow,
+                "{}:{}. Not enough in the pot to sweep the claim. Total: {:?}, balance: {:?}.",
+                file!(),
+                line!(),
+                Self::total(),
+                balance
+            )?;
+
+            T::Currency::deposit_creating(&T::TreasuryAccountId::get(), balance);
+
+            <Total<T>>::put(new_total);
+            Self::remove_claim(&who);
+
+            Self::deposit_event(Event::<T>::UnclaimedSwept(who, balance));
+            Ok(().into())
+        }
     }
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
@@ -429,6 +590,17 @@ pub mod pallet {
                 signer
             );
 
+            let e = InvalidTransaction::Custom(ValidityError::ClaimExpired.into());
+            eq_ensure!(
+                !Self::is_expired(&signer),
+                e,
+                target: "eq_claim",
+                "{}:{}. Claim deadline has passed. Who: {:?}.",
+                file!(),
+                line!(),
+                signer
+            );
+
             let e = InvalidTransaction::Custom(ValidityError::InvalidStatement.into());
             let s = <Signing<T>>::get(signer);
             if s {
@@ -465,6 +637,18 @@ pub mod pallet {
         /// `AccountId` claimed `Balance` amount of currency reserved for `EthereumAddress`
         /// \[who, ethereum_account, amount\]
         Claimed(T::AccountId, EthereumAddress, T::Balance),
+        /// Vesting schedule of the claim is keyed to unlock epoch or the key is removed
+        /// \[ethereum_account, epoch\]
+        ClaimUnlockEpochSet(EthereumAddress, Option<u32>),
+        /// Claim deadline is set or removed
+        /// \[deadline\]
+        ClaimDeadlineSet(Option<T::BlockNumber>),
+        /// Deadline of the claim is extended or the extension is removed
+        /// \[ethereum_account, until\]
+        ClaimGraceExtended(EthereumAddress, Option<T::BlockNumber>),
+        /// Allocation not claimed before the deadline is swept to the treasury
+        /// \[ethereum_account, amount\]
+        UnclaimedSwept(EthereumAddress, T::Balance),
     }
 
     #[pallet::error]
@@ -486,6 +670,12 @@ pub mod pallet {
         MethodNotAllowed,
         /// Invalid receiver
         InvalidReceiver,
+        /// Claim has no vesting schedule
+        ClaimHasNoVesting,
+        /// Claim deadline has passed
+        ClaimExpired,
+        /// Claim may still be collected and can't be swept
+        ClaimDeadlineNotPassed,
     }
 
     /// Pallet storage - stores amount to be claimed by each `EthereumAddress`
@@ -507,6 +697,12 @@ pub mod pallet {
     pub type Vesting<T: Config> =
         StorageMap<_, Identity, EthereumAddress, (T::Balance, T::Balance, T::BlockNumber)>;
 
+    /// Pallet storage - unlock epoch of the vesting pallet the claim vesting schedule
+    /// is keyed to. Block number of the schedule is counted from the epoch block.
+    #[pallet::storage]
+    #[pallet::getter(fn vesting_unlock_epoch)]
+    pub type VestingUnlockEpoch<T: Config> = StorageMap<_, Identity, EthereumAddress, u32>;
+
     /// Pallet storage - stores Ethereum addresses from which additional statement
     /// singing is required
     #[pallet::storage]
@@ -517,6 +713,18 @@ pub mod pallet {
     #[pallet::storage]
     pub type Preclaims<T: Config> = StorageMap<_, Identity, T::AccountId, EthereumAddress>;
 
+    /// Pallet storage - last block in which claims may be collected, claims never
+    /// expire if not set
+    #[pallet::storage]
+    #[pallet::getter(fn claim_deadline)]
+    pub type ClaimDeadline<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+    /// Pallet storage - last block in which the claim may be collected if it is later
+    /// than `ClaimDeadline`
+    #[pallet::storage]
+    #[pallet::getter(fn grace_extension)]
+    pub type GraceExtensions<T: Config> = StorageMap<_, Identity, EthereumAddress, T::BlockNumber>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         #[doc = " Pallet storage - vesting schedule for a claim."]
@@ -761,6 +969,26 @@ impl<T: Config> Pallet<T> {
         Some(res)
     }
 
+    /// Last block in which the claim of `who` may be collected, `None` if it never expires
+    pub fn deadline_of(who: &EthereumAddress) -> Option<T::BlockNumber> {
+        let deadline = <ClaimDeadline<T>>::get()?;
+        Some(<GraceExtensions<T>>::get(who).map_or(deadline, |grace| grace.max(deadline)))
+    }
+
+    fn is_expired(who: &EthereumAddress) -> bool {
+        Self::deadline_of(who).map_or(false, |deadline| {
+            frame_system::Pallet::<T>::block_number() > deadline
+        })
+    }
+
+    fn remove_claim(who: &EthereumAddress) {
+        <Claims<T>>::remove(who);
+        <Vesting<T>>::remove(who);
+        <VestingUnlockEpoch<T>>::remove(who);
+        <Signing<T>>::remove(who);
+        <GraceExtensions<T>>::remove(who);
+    }
+
     fn process_claim(signer: EthereumAddress, dest: T::AccountId) -> DispatchResult {
         let option_balance_of = <Claims<T>>::get(&signer);
         let balance_due = ok_or_error!(
@@ -771,6 +999,15 @@ impl<T: Config> Pallet<T> {
             line!(),
             signer
         )?;
+        eq_ensure!(
+            !Self::is_expired(&signer),
+            Error::<T>::ClaimExpired,
+            target: "eq_claim",
+            "{}:{}. Claim deadline has passed. Address: {:?}.",
+            file!(),
+            line!(),
+            signer
+        );
 
         let option_checked = Self::total().checked_sub(&balance_due);
         let new_total = ok_or_error!(option_checked, Error::<T>::PotUnderflow,
@@ -796,16 +1033,19 @@ impl<T: Config> Pallet<T> {
 
             // This can only fail if the account already has a vesting schedule,
             // but this is checked above.
-            T::Vesting::add_vesting_schedule(&dest, vs.0, vs.1, vs.2)
-                .expect("No other vesting schedule exists, as checked above; qed");
+            match VestingUnlockEpoch::<T>::get(&signer) {
+                Some(epoch) => {
+                    T::Vesting::add_vesting_schedule_at_epoch(&dest, vs.0, vs.1, vs.2, epoch)
+                }
+                None => T::Vesting::add_vesting_schedule(&dest, vs.0, vs.1, vs.2),
+            }
+            .expect("No other vesting schedule exists, as checked above; qed");
         } else {
             T::Currency::deposit_creating(&dest, balance_due);
         }
 
         <Total<T>>::put(new_total);
-        <Claims<T>>::remove(&signer);
-        <Vesting<T>>::remove(&signer);
-        <Signing<T>>::remove(&signer);
+        Self::remove_claim(&signer);
 
         // Let's deposit an event to let the outside world know this happened.
         Self::deposit_event(Event::Claimed(dest, signer, balance_due));
@@ -896,6 +1136,16 @@ where
                     line!(),
                     who
                 )?;
+                let e = InvalidTransaction::Custom(ValidityError::ClaimExpired.into());
+                eq_ensure!(
+                    !Pallet::<T>::is_expired(&signer),
+                    e,
+                    target: "eq_claim",
+                    "{}:{}. Claim deadline has passed. Who: {:?}.",
+                    file!(),
+                    line!(),
+                    signer
+                );
                 let s = <Signing<T>>::get(signer);
                 if s {
                     let e = InvalidTransaction::Custom(ValidityError::InvalidStatement.into());
@@ -254,6 +254,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = ();
     type XcmToFee = ();
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU32<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -285,7 +289,7 @@ impl eq_primitives::balance::BalanceChecker<Balance, AccountId, EqBalances, Suba
         who: &AccountId,
         changes: &Vec<(Asset, eq_primitives::SignedBalance<Balance>)>,
         _: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), eq_primitives::balance::BalanceCheckError<Balance>> {
         let res = changes.iter().all(|(asset, change)| match change {
             eq_primitives::SignedBalance::Positive(_) => true,
             eq_primitives::SignedBalance::Negative(change_value) => {
@@ -300,8 +304,12 @@ impl eq_primitives::balance::BalanceChecker<Balance, AccountId, EqBalances, Suba
             }
         });
 
-        res.then(|| ())
-            .ok_or_else(|| DispatchError::Other("Expected error"))
+        res.then(|| ()).ok_or_else(|| {
+            eq_primitives::balance::BalanceCheckError::new(
+                eq_primitives::balance::BalanceCheckerKind::Other,
+                DispatchError::Other("Expected error"),
+            )
+        })
     }
 }
 
@@ -324,12 +332,19 @@ impl eq_vesting::Config for Test {
     type WeightInfo = ();
     type PalletId = VestingModuleId;
     type IsTransfersEnabled = Balances;
+    type FeeCurrency = BasicCurrency;
+    type AutoVestFee = frame_support::traits::ConstU128<0>;
+    type AutoVestFeeDestination = frame_support::traits::ConstU64<0>;
+    type MaxAutoVestPerBlock = frame_support::traits::ConstU32<0>;
 }
 
 parameter_types! {
     pub Prefix: &'static [u8] = b"Pay RUSTs to the TEST account:";
     pub ClaimUnsignedPriority: u64 = 100;
 }
+parameter_types! {
+    pub const ClaimsTreasuryAccount: AccountId = 1_000;
+}
 ord_parameter_types! {
     pub const Six: u64 = 6;
 }
@@ -344,6 +359,7 @@ impl Config for Test {
     type WeightInfo = ();
     type UnsignedPriority = ClaimUnsignedPriority;
     type Currency = BasicCurrency;
+    type TreasuryAccountId = ClaimsTreasuryAccount;
 }
 
 type Balances = eq_balances::Pallet<Test>;
@@ -853,6 +869,150 @@ fn add_claim_with_vesting_works() {
     });
 }
 
+#[test]
+fn claim_with_vesting_keyed_to_unlock_epoch_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Claims::mint_claim(
+            RuntimeOrigin::root(),
+            eth(&bob()),
+            200,
+            Some((50, 10, 1)),
+            false
+        ));
+        assert_noop!(
+            Claims::set_claim_unlock_epoch(RuntimeOrigin::signed(42), eth(&bob()), Some(3)),
+            BadOrigin,
+        );
+        assert_noop!(
+            Claims::set_claim_unlock_epoch(RuntimeOrigin::root(), eth(&dave()), Some(3)),
+            Error::<Test>::ClaimHasNoVesting,
+        );
+        assert_ok!(Claims::set_claim_unlock_epoch(
+            RuntimeOrigin::root(),
+            eth(&bob()),
+            Some(3)
+        ));
+        assert_eq!(Claims::vesting_unlock_epoch(eth(&bob())), Some(3));
+
+        frame_system::Pallet::<Test>::set_block_number(10);
+        assert_ok!(Claims::claim(
+            RuntimeOrigin::none(),
+            69,
+            sig::<Test>(&bob(), &69u64.encode(), &[][..])
+        ));
+        assert_eq!(Claims::vesting_unlock_epoch(eth(&bob())), None);
+        assert_eq!(BasicCurrency::free_balance(&69), 150);
+        // epoch block is not registered yet
+        assert_eq!(Vesting::vesting_balance(&69), Some(50));
+
+        assert_ok!(Vesting::set_unlock_epoch(RuntimeOrigin::root(), 3, Some(7)));
+        // vesting started at block 7 + 1
+        assert_eq!(Vesting::vesting_balance(&69), Some(30));
+    });
+}
+
+#[test]
+fn unclaimed_allocation_is_swept_after_deadline() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            Claims::set_claim_deadline(RuntimeOrigin::signed(42), Some(10)),
+            BadOrigin,
+        );
+        assert_ok!(Claims::set_claim_deadline(RuntimeOrigin::root(), Some(10)));
+        assert_eq!(Claims::claim_deadline(), Some(10));
+
+        assert_noop!(
+            Claims::sweep_unclaimed(RuntimeOrigin::signed(42), eth(&alice())),
+            Error::<Test>::ClaimDeadlineNotPassed,
+        );
+
+        System::set_block_number(11);
+        assert_noop!(
+            Claims::claim(
+                RuntimeOrigin::none(),
+                42,
+                sig::<Test>(&alice(), &42u64.encode(), &[][..])
+            ),
+            Error::<Test>::ClaimExpired,
+        );
+        assert_noop!(
+            Claims::sweep_unclaimed(RuntimeOrigin::signed(42), eth(&bob())),
+            Error::<Test>::SignerHasNoClaim,
+        );
+
+        assert_ok!(Claims::sweep_unclaimed(
+            RuntimeOrigin::signed(42),
+            eth(&alice())
+        ));
+        assert_eq!(
+            BasicCurrency::free_balance(&ClaimsTreasuryAccount::get()),
+            100
+        );
+        assert_eq!(Claims::total(), total_claims() - 100);
+        assert_eq!(Claims::claims(&eth(&alice())), None);
+        assert_eq!(Claims::vesting(&eth(&alice())), None);
+        System::assert_last_event(Event::UnclaimedSwept(eth(&alice()), 100).into());
+
+        // no deadline, claims never expire
+        assert_ok!(Claims::set_claim_deadline(RuntimeOrigin::root(), None));
+        assert_noop!(
+            Claims::sweep_unclaimed(RuntimeOrigin::signed(42), eth(&frank())),
+            Error::<Test>::ClaimDeadlineNotPassed,
+        );
+    });
+}
+
+#[test]
+fn grace_extension_delays_claim_deadline() {
+    use sp_runtime::traits::ValidateUnsigned;
+    let source = sp_runtime::transaction_validity::TransactionSource::External;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Claims::set_claim_deadline(RuntimeOrigin::root(), Some(10)));
+        assert_noop!(
+            Claims::extend_claim_grace(RuntimeOrigin::signed(42), eth(&dave()), Some(20)),
+            BadOrigin,
+        );
+        assert_noop!(
+            Claims::extend_claim_grace(RuntimeOrigin::root(), eth(&bob()), Some(20)),
+            Error::<Test>::SignerHasNoClaim,
+        );
+        assert_ok!(Claims::extend_claim_grace(
+            RuntimeOrigin::root(),
+            eth(&dave()),
+            Some(20)
+        ));
+        assert_eq!(Claims::deadline_of(&eth(&dave())), Some(20));
+        assert_eq!(Claims::deadline_of(&eth(&frank())), Some(10));
+
+        System::set_block_number(15);
+        assert_noop!(
+            Claims::sweep_unclaimed(RuntimeOrigin::signed(42), eth(&dave())),
+            Error::<Test>::ClaimDeadlineNotPassed,
+        );
+        assert_eq!(
+            <Pallet<Test>>::validate_unsigned(
+                source,
+                &ClaimsCall::claim {
+                    dest: 1,
+                    ethereum_signature: sig::<Test>(&frank(), &1u64.encode(), &[][..])
+                }
+            ),
+            InvalidTransaction::Custom(ValidityError::ClaimExpired.into()).into(),
+        );
+
+        assert_ok!(Claims::claim_attest(
+            RuntimeOrigin::none(),
+            69,
+            sig::<Test>(&dave(), &69u64.encode(), get_statement_text()),
+            get_statement_text().to_vec()
+        ));
+        assert_eq!(BasicCurrency::free_balance(&69), 200);
+        assert_eq!(Claims::grace_extension(eth(&dave())), None);
+    });
+}
+
 #[test]
 fn add_claim_with_statement_works() {
     new_test_ext().execute_with(|| {
@@ -27,6 +27,10 @@ pub trait WeightInfo {
     fn mint_claim() -> Weight;
     fn claim_attest() -> Weight;
     fn attest() -> Weight;
+    fn set_claim_unlock_epoch() -> Weight;
+    fn set_claim_deadline() -> Weight;
+    fn extend_claim_grace() -> Weight;
+    fn sweep_unclaimed() -> Weight;
 }
 
 // for tests
@@ -43,4 +47,16 @@ impl crate::WeightInfo for () {
     fn attest() -> Weight {
         Weight::zero()
     }
+    fn set_claim_unlock_epoch() -> Weight {
+        Weight::zero()
+    }
+    fn set_claim_deadline() -> Weight {
+        Weight::zero()
+    }
+    fn extend_claim_grace() -> Weight {
+        Weight::zero()
+    }
+    fn sweep_unclaimed() -> Weight {
+        Weight::zero()
+    }
 }
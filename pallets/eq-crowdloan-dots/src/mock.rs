@@ -52,6 +52,15 @@ parameter_types! {
     pub const MainAsset: eq_primitives::asset::Asset = eq_primitives::asset::EQ;
     pub const ExistentialDeposit: Balance = 1;
     pub const AccountsToMigratePerBlock: u32 = 2;
+    pub const MaxIsolatedCollateral: u32 = 5;
+    pub const MaxRewardCheckpoints: u32 = 10;
+    pub RiskTierBorrowCaps: eq_primitives::asset::RiskTierParams<Permill> =
+        eq_primitives::asset::RiskTierParams {
+            tier1: Permill::from_percent(100),
+            tier2: Permill::from_percent(100),
+            tier3: Permill::from_percent(100),
+        };
+    pub const RewardAccrualPeriod: u64 = 0;
     pub const TreasuryModuleId: PalletId = PalletId(*b"eq/trsry");
     pub const BailsmanModuleId: PalletId = PalletId(*b"eq/bails");
     pub const BalancesModuleId: PalletId = PalletId(*b"eq/balan");
@@ -251,6 +260,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = XcmRouterErrMock;
     type XcmToFee = XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = UniversalLocationMock;
     type OrderAggregates = ();
@@ -280,7 +293,15 @@ impl eq_lending::Config for Test {
     type BailsmanManager = BailsmanManagerMock;
     type UnixTime = TimeZeroDurationMock;
     type AccountsToMigratePerBlock = AccountsToMigratePerBlock;
+    type RewardAccrualPeriod = RewardAccrualPeriod;
     type WeightInfo = ();
+    type IsolationOrigin = EnsureRoot<AccountId>;
+    type MaxIsolatedCollateral = MaxIsolatedCollateral;
+    type RiskTierBorrowCaps = RiskTierBorrowCaps;
+    type ReserveOrigin = EnsureRoot<AccountId>;
+    type TreasuryModuleId = TreasuryModuleId;
+    type MaxRewardCheckpoints = MaxRewardCheckpoints;
+    type LpLendingOrigin = EnsureRoot<AccountId>;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -219,6 +219,7 @@ benchmarks! {
             validators_len: 1,
             block_num: T::BlockNumber::default(),
             reason: DeleteOrderReason::OutOfCorridor,
+            nonce: 0,
         };
         //assert!(request.should_operate_in_block()); TODO: !!!!
         let key = <T as eq_rate::Config>::AuthorityId::generate_pair(None);
@@ -307,6 +308,200 @@ benchmarks! {
         assert_eq!(ChunkCorridorByAsset::<T>::get(asset), 10);
     }
 
+    set_auto_corridor {
+        let asset = asset::DOT;
+        let params = AutoCorridor {
+            k: FixedI64::saturating_from_integer(2),
+            min: 5,
+            max: 20,
+        };
+    }: _(RawOrigin::Root, asset, Some(params))
+    verify {
+        assert_eq!(AutoCorridorByAsset::<T>::get(asset::DOT), Some(params));
+    }
+
+    set_self_trade_prevention {
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller.clone()), SelfTradePrevention::Decrement)
+    verify {
+        assert_eq!(SelfTradePreventionByAccount::<T>::get(&caller), SelfTradePrevention::Decrement);
+    }
+
+    set_trading_ban {
+        let who: T::AccountId = account("trader", 0, SEED);
+    }: _(RawOrigin::Root, who.clone(), true)
+    verify {
+        assert!(TradingBans::<T>::get(&who));
+    }
+
+    set_trusted_xcm_parachain {
+    }: _(RawOrigin::Root, 2000, true)
+    verify {
+        assert!(TrustedXcmParachains::<T>::get(2000));
+    }
+
+    create_iceberg_order {
+        let price_setter: T::AccountId = account("price_setter", 0, SEED);
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone())
+            .unwrap();
+        for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
+            <eq_oracle::Pallet::<T> as PriceSetter<_>>::set_price(price_setter.clone(), curr, FixedI64::one())
+                .unwrap();
+        }
+
+        let user = account("user", 0, SEED);
+        let borrower_id = eq_subaccounts::Pallet::<T>::create_subaccount_inner(&user, &SubAccType::Trader).unwrap();
+
+        let amount = BUDGET.try_into().map_err(|_|"balance conversion error").unwrap();
+
+        eq_balances::Pallet::<T>::deposit_creating(&borrower_id, asset::EQ, amount, true, None)
+            .unwrap();
+        eq_balances::Pallet::<T>::deposit_creating(&borrower_id, asset::DOT, amount, true, None)
+            .unwrap();
+
+        let asset = asset::DOT;
+        let asset_data = eq_assets::Pallet::<T>::get_asset_data(&asset).unwrap();
+        let display_amount = asset_data.lot;
+        let amount = display_amount * EqFixedU128::saturating_from_integer(3);
+        let price = FixedI64::one();
+    }: _(RawOrigin::Signed(user), asset, price, OrderSide::Buy, amount, display_amount, 100u64)
+    verify {
+        let order_id = crate::Pallet::<T>::order_id_counter();
+        let stored_order = crate::Pallet::<T>::find_order(&asset, order_id, price).unwrap();
+        assert_eq!(stored_order.amount, display_amount);
+        assert_eq!(
+            IcebergOrders::<T>::get(asset, order_id).map(|iceberg| iceberg.hidden_amount),
+            Some(amount - display_amount)
+        );
+    }
+
+    set_batch_auction {
+        let o in 1 .. T::MaxBatchOrders::get();
+
+        let price_setter: T::AccountId = account("price_setter", 0, SEED);
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone())
+            .unwrap();
+        for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
+            <eq_oracle::Pallet::<T> as PriceSetter<_>>::set_price(price_setter.clone(), curr, FixedI64::one())
+                .unwrap();
+        }
+
+        for i in 1..T::MaxBatchAuctionMarkets::get() {
+            BatchAuctionPeriod::<T>::insert(Asset(u64::MAX - i as u64), T::BlockNumber::one());
+        }
+
+        let asset = asset::DOT;
+        let asset_data = eq_assets::Pallet::<T>::get_asset_data(&asset).unwrap();
+        let budget = BUDGET.try_into().map_err(|_|"balance conversion error").unwrap();
+        let order_type = OrderType::Limit{price: FixedI64::one(), expiration_time: u64::MAX};
+
+        // resting orders are counted when market is switched to batch auction
+        for i in 0..o {
+            let user: T::AccountId = account("user", i, SEED);
+            let trader_id = eq_subaccounts::Pallet::<T>::create_subaccount_inner(&user, &SubAccType::Trader).unwrap();
+            eq_balances::Pallet::<T>::deposit_creating(&trader_id, asset::EQ, budget, true, None)
+                .unwrap();
+            eq_balances::Pallet::<T>::deposit_creating(&trader_id, asset::EQD, budget, true, None)
+                .unwrap();
+            crate::Pallet::<T>::create_order(RawOrigin::Signed(user).into(), asset, order_type, OrderSide::Buy, asset_data.lot)
+                .unwrap();
+        }
+
+        let period: T::BlockNumber = 10u32.into();
+    }: _(RawOrigin::Root, asset, Some(period))
+    verify {
+        assert_eq!(BatchAuctionPeriod::<T>::get(asset::DOT), Some(10u32.into()));
+        assert_eq!(BatchOrdersCount::<T>::get(asset::DOT), Some(o));
+    }
+
+    clear_batch_auction {
+        let o in 2 .. T::MaxBatchOrders::get();
+
+        let price_setter: T::AccountId = account("price_setter", 0, SEED);
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone())
+            .unwrap();
+        for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
+            <eq_oracle::Pallet::<T> as PriceSetter<_>>::set_price(price_setter.clone(), curr, FixedI64::one())
+                .unwrap();
+        }
+
+        let asset = asset::DOT;
+        BatchAuctionPeriod::<T>::insert(asset, T::BlockNumber::one());
+        BatchOrdersCount::<T>::insert(asset, 0);
+        let asset_data = eq_assets::Pallet::<T>::get_asset_data(&asset).unwrap();
+        let budget = BUDGET.try_into().map_err(|_|"balance conversion error").unwrap();
+        let order_type = OrderType::Limit{price: FixedI64::one(), expiration_time: u64::MAX};
+
+        for i in 0..o {
+            let side = if i % 2 == 0 { OrderSide::Buy } else { OrderSide::Sell };
+            let user: T::AccountId = account("user", i, SEED);
+            let trader_id = eq_subaccounts::Pallet::<T>::create_subaccount_inner(&user, &SubAccType::Trader).unwrap();
+            eq_balances::Pallet::<T>::deposit_creating(&trader_id, asset::EQ, budget, true, None)
+                .unwrap();
+            eq_balances::Pallet::<T>::deposit_creating(&trader_id, asset::DOT, budget, true, None)
+                .unwrap();
+            eq_balances::Pallet::<T>::deposit_creating(&trader_id, asset::EQD, budget, true, None)
+                .unwrap();
+            crate::Pallet::<T>::create_order(RawOrigin::Signed(user).into(), asset, order_type, side, asset_data.lot)
+                .unwrap();
+        }
+    }: {
+        let fills = crate::Pallet::<T>::do_clear_batch_auction(asset).unwrap();
+        assert_eq!(fills, o / 2);
+    }
+
+    set_trading_pair {
+        let base = asset::ETH;
+        let quote = asset::DOT;
+    }: _(RawOrigin::Root, base, quote, true)
+    verify {
+        assert!(TradingPairs::<T>::get(asset::ETH, asset::DOT));
+    }
+
+    create_pair_order {
+        let price_setter: T::AccountId = account("price_setter", 0, SEED);
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone())
+            .unwrap();
+        for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
+            <eq_oracle::Pallet::<T> as PriceSetter<_>>::set_price(price_setter.clone(), curr, FixedI64::one())
+                .unwrap();
+        }
+
+        let base = asset::ETH;
+        let quote = asset::DOT;
+        TradingPairs::<T>::insert(base, quote, true);
+        let base_data = eq_assets::Pallet::<T>::get_asset_data(&base).unwrap();
+        let quote_data = eq_assets::Pallet::<T>::get_asset_data(&quote).unwrap();
+        let budget = BUDGET.try_into().map_err(|_|"balance conversion error").unwrap();
+        let order_type = OrderType::Limit{price: FixedI64::one(), expiration_time: u64::MAX};
+
+        // makers sell base for EQD and buy quote for EQD
+        for (i, (asset, side, amount)) in [
+            (base, OrderSide::Sell, base_data.lot),
+            (quote, OrderSide::Buy, quote_data.lot * EqFixedU128::saturating_from_integer(1000)),
+        ].into_iter().enumerate() {
+            let maker: T::AccountId = account("maker", i as u32, SEED);
+            let trader_id = eq_subaccounts::Pallet::<T>::create_subaccount_inner(&maker, &SubAccType::Trader).unwrap();
+            for curr in [asset::EQ, asset::EQD, base, quote] {
+                eq_balances::Pallet::<T>::deposit_creating(&trader_id, curr, budget, true, None)
+                    .unwrap();
+            }
+            crate::Pallet::<T>::create_order(RawOrigin::Signed(maker).into(), asset, order_type, side, amount)
+                .unwrap();
+        }
+
+        let user: T::AccountId = account("user", 0, SEED);
+        let borrower_id = eq_subaccounts::Pallet::<T>::create_subaccount_inner(&user, &SubAccType::Trader).unwrap();
+        for curr in [asset::EQ, quote] {
+            eq_balances::Pallet::<T>::deposit_creating(&borrower_id, curr, budget, true, None)
+                .unwrap();
+        }
+        let limit_price = FixedI64::saturating_from_integer(2);
+    }: _(RawOrigin::Signed(user), base, quote, OrderSide::Buy, base_data.lot, limit_price)
+    verify {
+        assert!(crate::Pallet::<T>::account_orders(&borrower_id).is_empty());
+    }
+
     validate_unsigned {
         eq_balances::Pallet::<T>::deposit_creating(
             &PalletId(*b"eq/trsry").into_account_truncating(),
@@ -365,7 +560,8 @@ benchmarks! {
             authority_index: 0,
             validators_len: 1,
             block_num: T::BlockNumber::default(),
-            reason: DeleteOrderReason::Cancel
+            reason: DeleteOrderReason::Cancel,
+            nonce: 0,
         };
 
         let validator = <T as eq_rate::Config>::AuthorityId::generate_pair(None);
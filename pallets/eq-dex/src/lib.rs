@@ -32,20 +32,30 @@ pub use weights::WeightInfo;
 use codec::{Decode, Encode};
 use either::Either;
 use eq_primitives::{
-    asset::{Asset, AssetData, AssetGetter, EQD},
+    asset::{Asset, AssetData, AssetGetter, DOT, EQD},
     balance::{BalanceGetter, EqCurrency},
     balance_number::EqFixedU128,
-    offchain_batcher::{OffchainErr, OffchainResult, ValidatorOffchainBatcher},
+    financial_storage::FinancialStorage,
+    offchain_batcher::{OffchainErr, OffchainResult, UnsignedDuty, ValidatorOffchainBatcher},
     signed_balance::SignedBalance,
     subaccount::{SubAccType, SubaccountsManager},
-    DeleteOrderReason, EqBuyout, MarginCallManager, MarginState, Order, OrderAggregateBySide,
-    OrderAggregates, OrderChange, OrderId, OrderManagement, OrderSide, OrderType, Price,
-    PriceGetter,
+    DeleteOrderReason, EqBuyout, MarginCallManager, MarginState, MarkPriceGetter, Order,
+    OrderAggregateBySide, OrderAggregates, OrderChange, OrderId, OrderManagement, OrderSide,
+    OrderType, Price, PriceGetter, RiskNotificationKind, RiskNotifier, StakedBalance,
+    TreasuryAccounting, TreasuryFlowCategory,
+};
+use eq_utils::{
+    eq_ensure,
+    fixed::{
+        balance_from_eq_fixedu128, eq_fixedu128_from_balance, eq_fixedu128_from_fixedi64,
+        i64f64_to_fixedi64,
+    },
+    ok_or_error,
+    vec_map::VecMap,
 };
-use eq_utils::{eq_ensure, fixed::balance_from_eq_fixedu128, ok_or_error, vec_map::VecMap};
 use frame_support::{
     dispatch::DispatchResultWithPostInfo,
-    traits::{ExistenceRequirement, Get, WithdrawReasons},
+    traits::{BalanceStatus, ExistenceRequirement, Get, WithdrawReasons},
 };
 use frame_system::{
     ensure_signed,
@@ -55,7 +65,7 @@ use sp_application_crypto::RuntimeAppPublic;
 use sp_arithmetic::traits::BaseArithmetic;
 use sp_runtime::{
     traits::AccountIdConversion, ArithmeticError, DispatchError, DispatchResult, FixedI64,
-    FixedPointNumber, RuntimeDebug,
+    FixedPointNumber, Percent, RuntimeDebug, TransactionOutcome,
 };
 use sp_std::prelude::*;
 use sp_std::vec::Vec;
@@ -66,13 +76,17 @@ use eq_primitives::OrderSide::{Buy, Sell};
 use eq_primitives::OrderType::{Limit, Market};
 use frame_support::traits::UnixTime;
 pub use pallet::*;
-use sp_arithmetic::traits::{CheckedSub, Zero};
+use sp_arithmetic::traits::{CheckedMul, CheckedSub, Zero};
 use sp_runtime::traits::CheckedDiv;
 use sp_std::borrow::Cow;
 use sp_std::vec;
 
 type ChunkKey = u64;
 const DB_PREFIX: &[u8] = b"eq-dex/";
+/// Corridor width multiplier for assets with a suspect oracle price
+const SUSPECT_PRICE_CORRIDOR_MULTIPLIER: i64 = 3;
+/// Assets allowed as quote of cross-pair
+const QUOTE_ASSETS: [Asset; 2] = [EQD, DOT];
 
 #[derive(Decode, Encode, Debug, Clone, Copy, Eq, PartialEq)]
 enum Operation {
@@ -113,6 +127,75 @@ pub struct OperationRequestDexDeleteOrder<
     pub block_num: BlockNumber,
     /// Order delete reason
     pub reason: DeleteOrderReason,
+    /// Nonce of the `UnsignedDuty::DexOrders` duty of the authority
+    pub nonce: u64,
+}
+
+/// Request data for offchain signing of corridor update.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct OperationRequestDexUpdateCorridor<BlockNumber: Decode + Encode + Copy + BaseArithmetic> {
+    /// An asset which corridor is updated
+    pub asset: Asset,
+    /// An index of the authority on the list of validators.
+    pub authority_index: AuthIndex,
+    /// The length of session validator set.
+    pub validators_len: u32,
+    /// Number of a block.
+    pub block_num: BlockNumber,
+    /// Nonce of the `UnsignedDuty::DexCorridors` duty of the authority
+    pub nonce: u64,
+}
+
+/// Parameters of a corridor derived from asset volatility:
+/// `k * volatility * price` rounded up to chunks and bounded by `min` and `max` chunks
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct AutoCorridor {
+    /// Volatility multiplier
+    pub k: FixedI64,
+    /// Minimal corridor in chunks
+    pub min: u32,
+    /// Maximal corridor in chunks
+    pub max: u32,
+}
+
+/// What happens when taker order meets maker order of the same account
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum SelfTradePrevention {
+    /// Orders are matched with each other
+    Allow,
+    /// Rest of taker order is cancelled, maker order is kept
+    CancelNewest,
+    /// Maker order is cancelled, taker order continues matching
+    CancelOldest,
+    /// Both orders are decreased by the smaller amount without exchange
+    Decrement,
+}
+
+impl Default for SelfTradePrevention {
+    fn default() -> Self {
+        SelfTradePrevention::Allow
+    }
+}
+
+/// Hidden part of iceberg order. Only `display_amount` rests in the order book,
+/// the order is refilled from `hidden_amount` each time its displayed part is filled.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct IcebergOrder {
+    /// Amount shown in the order book after each refill
+    pub display_amount: EqFixedU128,
+    /// Amount not placed in the order book yet
+    pub hidden_amount: EqFixedU128,
+}
+
+/// Rest of taker order after matching with order book
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum TakerRest {
+    /// Taker order is fully matched
+    Filled,
+    /// Rest of taker order is not matched
+    Unmatched(EqFixedU128),
+    /// Rest of taker order is cancelled by self-trade prevention
+    Cancelled,
 }
 
 #[frame_support::pallet]
@@ -131,6 +214,13 @@ pub mod pallet {
     #[pallet::getter(fn order_id_counter)]
     pub(super) type OrderIdCounter<T: Config> = StorageValue<_, OrderId, ValueQuery>;
 
+    /// Sequence number of the last order book event of asset, lets indexers
+    /// detect missed events
+    #[pallet::storage]
+    #[pallet::getter(fn event_sequence)]
+    pub type EventSequenceByAsset<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, u64, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn orders_by_asset_and_chunk_key)]
     pub(super) type OrdersByAssetAndChunkKey<T: Config> = StorageDoubleMap<
@@ -169,6 +259,77 @@ pub mod pallet {
     pub(super) type ChunkCorridorByAsset<T: Config> =
         StorageMap<_, Blake2_128Concat, Asset, u32, ValueQuery>;
 
+    /// Assets with corridor derived from volatility, updated by offchain worker
+    #[pallet::storage]
+    #[pallet::getter(fn auto_corridor)]
+    pub type AutoCorridorByAsset<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, AutoCorridor, OptionQuery>;
+
+    /// Markets in frequent batch auction mode and their clearing period in blocks.
+    /// Orders of these markets are not matched on creation and are cleared
+    /// at a single uniform price every period.
+    #[pallet::storage]
+    #[pallet::getter(fn batch_auction_period)]
+    pub type BatchAuctionPeriod<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, T::BlockNumber, OptionQuery>;
+
+    /// Number of resting orders of markets in batch auction mode, bounds orders read
+    /// when the batch is cleared
+    #[pallet::storage]
+    #[pallet::getter(fn batch_orders_count)]
+    pub type BatchOrdersCount<T: Config> = StorageMap<_, Blake2_128Concat, Asset, u32, OptionQuery>;
+
+    /// Cross-pairs `(base, quote)` enabled for trading, quote asset is EQD or DOT
+    #[pallet::storage]
+    #[pallet::getter(fn trading_pairs)]
+    pub type TradingPairs<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, Asset, Blake2_128Concat, Asset, bool, ValueQuery>;
+
+    /// Self-trade prevention mode of account orders
+    #[pallet::storage]
+    #[pallet::getter(fn self_trade_prevention)]
+    pub type SelfTradePreventionByAccount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, SelfTradePrevention, ValueQuery>;
+
+    /// Accounts banned from placing DEX orders
+    #[pallet::storage]
+    #[pallet::getter(fn trading_banned)]
+    pub type TradingBans<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+    /// Sibling parachains allowed to place and cancel orders on behalf of their users
+    /// via XCM `Transact`
+    #[pallet::storage]
+    #[pallet::getter(fn trusted_xcm_parachain)]
+    pub type TrustedXcmParachains<T: Config> = StorageMap<_, Twox64Concat, u32, bool, ValueQuery>;
+
+    /// Hidden parts of iceberg orders by asset and id of their displayed order
+    #[pallet::storage]
+    #[pallet::getter(fn iceberg_order)]
+    pub type IcebergOrders<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        Asset,
+        Blake2_128Concat,
+        OrderId,
+        IcebergOrder,
+        OptionQuery,
+    >;
+
+    /// Storage deposits of resting orders by asset and order id, reserved in the main asset
+    /// on the order account
+    #[pallet::storage]
+    #[pallet::getter(fn order_deposit)]
+    pub type OrderDeposits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        Asset,
+        Blake2_128Concat,
+        OrderId,
+        T::Balance,
+        OptionQuery,
+    >;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig {
         pub chunk_corridors: Vec<(Asset, u32)>,
@@ -233,14 +394,36 @@ pub mod pallet {
         /// Used for calculation unsigned transaction priority
         #[pallet::constant]
         type DexUnsignedPriority: Get<TransactionPriority>;
+        /// Period in blocks of corridors update by offchain worker
+        #[pallet::constant]
+        type CorridorUpdatePeriod: Get<Self::BlockNumber>;
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
+        /// Stores notifications about penalized orders
+        type RiskNotifier: RiskNotifier<Self::AccountId>;
         /// Used to execute batch operations for every `AuthorityId` key in keys storage
         type ValidatorOffchainBatcher: ValidatorOffchainBatcher<
             Self::AuthorityId,
             Self::BlockNumber,
             Self::AccountId,
         >;
+        /// Balance staked in eq-staking, gives discounts on fees and penalties
+        type StakedBalance: StakedBalance<Self::AccountId, Self::Balance>;
+        /// Tiers of staked balance with discounts on fees and penalties.
+        /// Account gets the discount of the highest tier it reached.
+        type StakerDiscounts: Get<Vec<(Self::Balance, Percent)>>;
+        /// Storage deposit reserved for every resting order. Returned when the order is
+        /// cancelled or filled and forfeited to Treasury when the order is penalized.
+        #[pallet::constant]
+        type OrderDeposit: Get<Self::Balance>;
+        /// Records penalties and forfeited deposits paid to Treasury
+        type TreasuryAccounting: TreasuryAccounting<Self::Balance>;
+        /// Max number of resting orders in a batch auction market
+        #[pallet::constant]
+        type MaxBatchOrders: Get<u32>;
+        /// Max number of markets in batch auction mode
+        #[pallet::constant]
+        type MaxBatchAuctionMarkets: Get<u32>;
     }
 
     #[pallet::call]
@@ -254,7 +437,7 @@ pub mod pallet {
             side: OrderSide,
             amount: EqFixedU128,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
+            let who = Self::resolve_session_key(ensure_signed(origin)?);
 
             <Self as OrderManagement>::create_order(who, asset, order_type, side, amount)
         }
@@ -270,18 +453,34 @@ pub mod pallet {
             _signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            T::ValidatorOffchainBatcher::note_duty(
+                request.authority_index,
+                UnsignedDuty::DexOrders,
+                request.nonce,
+            );
 
             Self::charge_penalty_fee(&request.who, request.buyout)?;
 
-            <Self as OrderManagement>::delete_order(
+            let result = <Self as OrderManagement>::delete_order(
                 &request.asset,
                 request.order_id,
                 request.price,
                 request.reason,
-            )
+            )?;
+
+            T::RiskNotifier::notify(
+                &request.who,
+                RiskNotificationKind::OrderPenalized {
+                    asset: request.asset,
+                    order_id: request.order_id,
+                    reason: request.reason,
+                },
+            );
+
+            Ok(result)
         }
 
-        /// Delete order. This must be called by order owner or root.
+        /// Delete order. This must be called by order owner, its trading session key or root.
         #[pallet::call_index(2)]
         #[pallet::weight(<T as pallet::Config>::WeightInfo::delete_order_external())]
         pub fn delete_order_external(
@@ -292,7 +491,7 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let maybe_who = match T::DeleteOrderOrigin::try_origin(origin) {
                 Ok(_) => None,
-                Err(o) => Some(ensure_signed(o)?),
+                Err(o) => Some(Self::resolve_session_key(ensure_signed(o)?)),
             };
             if let Some(who) = &maybe_who {
                 let order =
@@ -324,10 +523,337 @@ pub mod pallet {
             Self::do_update_asset_corridor(asset, new_corridor_value);
             Ok(().into())
         }
+
+        /// Switch market of `asset` to frequent batch auction mode with clearing every
+        /// `period` blocks or back to continuous matching (`None`).
+        /// Pending batch is cleared before switching to continuous matching.
+        #[pallet::call_index(4)]
+        #[pallet::weight(
+            <T as pallet::Config>::WeightInfo::set_batch_auction(T::MaxBatchOrders::get())
+                .max(<T as pallet::Config>::WeightInfo::clear_batch_auction(T::MaxBatchOrders::get()))
+        )]
+        pub fn set_batch_auction(
+            origin: OriginFor<T>,
+            asset: Asset,
+            period: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateAssetCorridorOrigin::ensure_origin(origin)?;
+
+            match period {
+                Some(period) => {
+                    eq_ensure!(
+                        !period.is_zero(),
+                        Error::<T>::BatchAuctionPeriodShouldBePositive,
+                        target: "eq_dex",
+                        "{}:{}. Batch auction period should be positive. Asset: {:?}",
+                        file!(),
+                        line!(),
+                        asset,
+                    );
+                    if !BatchAuctionPeriod::<T>::contains_key(asset) {
+                        let markets = BatchAuctionPeriod::<T>::iter_keys().count() as u32;
+                        eq_ensure!(
+                            markets < T::MaxBatchAuctionMarkets::get(),
+                            Error::<T>::TooManyBatchAuctionMarkets,
+                            target: "eq_dex",
+                            "{}:{}. Too many markets in batch auction mode. Asset: {:?}",
+                            file!(),
+                            line!(),
+                            asset,
+                        );
+                        let orders = Self::orders_count(&asset, T::MaxBatchOrders::get());
+                        eq_ensure!(
+                            orders <= T::MaxBatchOrders::get(),
+                            Error::<T>::TooManyBatchOrders,
+                            target: "eq_dex",
+                            "{}:{}. Too many orders for batch auction. Asset: {:?}, orders: {:?}",
+                            file!(),
+                            line!(),
+                            asset,
+                            orders,
+                        );
+                        BatchOrdersCount::<T>::insert(asset, orders);
+                    }
+                    BatchAuctionPeriod::<T>::insert(asset, period);
+                }
+                None => {
+                    if BatchAuctionPeriod::<T>::contains_key(asset) {
+                        Self::do_clear_batch_auction(asset)?;
+                    }
+                    BatchAuctionPeriod::<T>::remove(asset);
+                    BatchOrdersCount::<T>::remove(asset);
+                }
+            }
+
+            Self::deposit_event(Event::BatchAuctionModeChanged(asset, period));
+            Ok(().into())
+        }
+
+        /// Enable or disable trading of `base` asset quoted in `quote` asset.
+        /// Quote asset should be EQD or DOT.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_trading_pair())]
+        pub fn set_trading_pair(
+            origin: OriginFor<T>,
+            base: Asset,
+            quote: Asset,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateAssetCorridorOrigin::ensure_origin(origin)?;
+
+            eq_ensure!(
+                QUOTE_ASSETS.contains(&quote),
+                Error::<T>::QuoteAssetNotSupported,
+                target: "eq_dex",
+                "{}:{}. Quote asset should be EQD or DOT. Quote: {:?}",
+                file!(),
+                line!(),
+                quote,
+            );
+            eq_ensure!(
+                base != quote && base != EQD,
+                Error::<T>::WrongTradingPair,
+                target: "eq_dex",
+                "{}:{}. Wrong trading pair. Base: {:?}, quote: {:?}",
+                file!(),
+                line!(),
+                base,
+                quote,
+            );
+            T::AssetGetter::get_asset_data(&base)?;
+
+            if enabled {
+                TradingPairs::<T>::insert(base, quote, true);
+            } else {
+                TradingPairs::<T>::remove(base, quote);
+            }
+
+            Self::deposit_event(Event::TradingPairChanged(base, quote, enabled));
+            Ok(().into())
+        }
+
+        /// Immediately exchange `amount` of `base` asset for `quote` asset.
+        /// Order of cross-pair is settled with two legs: `base` against EQD in `base` order book
+        /// and EQD against `quote` in `quote` order book. Both legs should be fully filled and
+        /// effective price should be not worse than `limit_price`, otherwise order is reverted.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::create_pair_order())]
+        pub fn create_pair_order(
+            origin: OriginFor<T>,
+            base: Asset,
+            quote: Asset,
+            side: OrderSide,
+            amount: EqFixedU128,
+            limit_price: FixedI64,
+        ) -> DispatchResultWithPostInfo {
+            let who = Self::resolve_session_key(ensure_signed(origin)?);
+
+            Self::do_create_pair_order(who, base, quote, side, amount, limit_price)?;
+            Ok(().into())
+        }
+
+        /// Enable corridor of `asset` derived from its volatility or disable it (`None`).
+        /// Corridor is periodically updated by offchain worker, last value is kept on disabling.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_auto_corridor())]
+        pub fn set_auto_corridor(
+            origin: OriginFor<T>,
+            asset: Asset,
+            params: Option<AutoCorridor>,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateAssetCorridorOrigin::ensure_origin(origin)?;
+
+            match params {
+                Some(params) => {
+                    eq_ensure!(
+                        params.k.is_positive() && params.min <= params.max,
+                        Error::<T>::WrongAutoCorridor,
+                        target: "eq_dex",
+                        "{}:{}. Wrong auto corridor. Asset: {:?}, params: {:?}",
+                        file!(),
+                        line!(),
+                        asset,
+                        params,
+                    );
+                    T::AssetGetter::get_asset_data(&asset)?;
+                    AutoCorridorByAsset::<T>::insert(asset, params);
+                }
+                None => AutoCorridorByAsset::<T>::remove(asset),
+            }
+
+            Self::deposit_event(Event::AutoCorridorChanged(asset, params));
+            Ok(().into())
+        }
+
+        /// Update corridor of an asset to the value derived from its volatility.
+        /// The dispatch origin for this call must be _None_ (unsigned transaction).
+        #[pallet::call_index(8)]
+        #[pallet::weight((<T as pallet::Config>::WeightInfo::update_auto_corridor() + <T as pallet::Config>::WeightInfo::validate_unsigned(),
+                          DispatchClass::Operational))]
+        pub fn update_auto_corridor(
+            origin: OriginFor<T>,
+            request: OperationRequestDexUpdateCorridor<T::BlockNumber>,
+            _signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            T::ValidatorOffchainBatcher::note_duty(
+                request.authority_index,
+                UnsignedDuty::DexCorridors,
+                request.nonce,
+            );
+
+            let corridor = Self::auto_corridor_value(request.asset)?;
+            Self::do_update_asset_corridor(request.asset, corridor);
+
+            Self::deposit_event(Event::AssetCorridorUpdated(request.asset, corridor));
+            Ok(().into())
+        }
+
+        /// Set self-trade prevention mode applied when orders of the sender meet each other.
+        /// This must be called by account owner or its trading session key.
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_self_trade_prevention())]
+        pub fn set_self_trade_prevention(
+            origin: OriginFor<T>,
+            mode: SelfTradePrevention,
+        ) -> DispatchResultWithPostInfo {
+            let who = Self::resolve_session_key(ensure_signed(origin)?);
+
+            if mode == SelfTradePrevention::default() {
+                SelfTradePreventionByAccount::<T>::remove(&who);
+            } else {
+                SelfTradePreventionByAccount::<T>::insert(&who, mode);
+            }
+
+            Self::deposit_event(Event::SelfTradePreventionChanged(who, mode));
+            Ok(().into())
+        }
+
+        /// Ban or unban account from placing DEX orders. Existing orders of banned
+        /// account are kept and could be removed with `delete_order_external`.
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_trading_ban())]
+        pub fn set_trading_ban(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            banned: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::DeleteOrderOrigin::ensure_origin(origin)?;
+
+            if banned {
+                TradingBans::<T>::insert(&who, true);
+            } else {
+                TradingBans::<T>::remove(&who);
+            }
+
+            Self::deposit_event(Event::TradingBanChanged(who, banned));
+            Ok(().into())
+        }
+
+        /// Allows or disallows users of sibling parachain `para_id` to trade via XCM `Transact`
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_trusted_xcm_parachain())]
+        pub fn set_trusted_xcm_parachain(
+            origin: OriginFor<T>,
+            para_id: u32,
+            trusted: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateAssetCorridorOrigin::ensure_origin(origin)?;
+
+            if trusted {
+                TrustedXcmParachains::<T>::insert(para_id, true);
+            } else {
+                TrustedXcmParachains::<T>::remove(para_id);
+            }
+
+            Self::deposit_event(Event::TrustedXcmParachainChanged(para_id, trusted));
+            Ok(().into())
+        }
+
+        /// Create limit order showing only `display_amount` in the order book.
+        /// The rest is matched as usual, then rests hidden and refills the displayed part
+        /// each time it is filled. Each refill gets new order id and loses time priority.
+        /// Margin should cover the whole order amount.
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::create_iceberg_order())]
+        pub fn create_iceberg_order(
+            origin: OriginFor<T>,
+            asset: Asset,
+            price: FixedI64,
+            side: OrderSide,
+            amount: EqFixedU128,
+            display_amount: EqFixedU128,
+            expiration_time: u64,
+        ) -> DispatchResultWithPostInfo {
+            let who = Self::resolve_session_key(ensure_signed(origin)?);
+
+            eq_ensure!(
+                !display_amount.is_zero() && display_amount < amount,
+                Error::<T>::WrongIcebergDisplayAmount,
+                target: "eq_dex",
+                "{}:{}. Wrong displayed amount of iceberg order. Amount: {:?}, displayed: {:?}",
+                file!(),
+                line!(),
+                amount,
+                display_amount,
+            );
+
+            Self::do_create_order(
+                who,
+                asset,
+                Limit {
+                    price,
+                    expiration_time,
+                },
+                side,
+                amount,
+                Some(display_amount),
+            )
+        }
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Clears batch auctions of markets whose period ends at block `now`
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+            for (asset, period) in BatchAuctionPeriod::<T>::iter() {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
+                if !(now % period).is_zero() {
+                    continue;
+                }
+
+                // orders read by clearing are bounded by `MaxBatchOrders`
+                let orders = BatchOrdersCount::<T>::get(asset).unwrap_or_default();
+                weight = weight.saturating_add(
+                    <T as pallet::Config>::WeightInfo::clear_batch_auction(orders)
+                        .saturating_add(T::DbWeight::get().reads(1)),
+                );
+
+                let result = frame_support::storage::with_transaction(
+                    || -> TransactionOutcome<Result<u32, DispatchError>> {
+                        match Self::do_clear_batch_auction(asset) {
+                            Ok(fills) => TransactionOutcome::Commit(Ok(fills)),
+                            Err(err) => TransactionOutcome::Rollback(Err(err)),
+                        }
+                    },
+                );
+
+                if let Err(err) = result {
+                    log::error!(
+                        target: "eq_dex",
+                        "{}:{}. Batch auction clearing failed. Asset: {:?}, error: {:?}",
+                        file!(),
+                        line!(),
+                        asset,
+                        err
+                    );
+                }
+            }
+
+            weight
+        }
+
         /// Starts the off-chain task for given block number
         fn offchain_worker(block_number: T::BlockNumber) {
             // Only send messages if we are a potential validator
@@ -349,6 +875,15 @@ pub mod pallet {
                         offchain::delete_unfit_orders::<T>,
                         "eq-dex",
                     );
+
+                    let period = T::CorridorUpdatePeriod::get();
+                    if !period.is_zero() && (block_number % period).is_zero() {
+                        T::ValidatorOffchainBatcher::execute_batch(
+                            block_number,
+                            offchain::update_auto_corridors::<T>,
+                            "eq-dex",
+                        );
+                    }
                 }
             });
 
@@ -367,7 +902,7 @@ pub mod pallet {
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// Order was created
-        /// `[subaccount_id, order_id, asset, amount, price, side, created_at, expiration_time]`
+        /// `[subaccount_id, order_id, asset, amount, price, side, created_at, expiration_time, chunk_key, sequence]`
         OrderCreated(
             T::AccountId,
             u64,
@@ -377,12 +912,22 @@ pub mod pallet {
             OrderSide,
             u64,
             u64,
+            u64,
+            u64,
         ),
         /// Order was deleted
-        /// `[account_id, order_id, asset, reason]`
-        OrderDeleted(T::AccountId, u64, Asset, DeleteOrderReason),
+        /// `[account_id, order_id, asset, reason, removed_amount, chunk_key, sequence]`
+        OrderDeleted(
+            T::AccountId,
+            u64,
+            Asset,
+            DeleteOrderReason,
+            EqFixedU128,
+            u64,
+            u64,
+        ),
         /// Orders matched
-        /// `[asset, taker_rest, maker_price, maker_order_id, maker, taker, maker_fee, taker_fee, exchange_amount, maker_side]`
+        /// `[asset, taker_rest, maker_price, maker_order_id, maker, taker, maker_fee, taker_fee, exchange_amount, maker_side, taker_order_id, maker_rest, chunk_key, sequence]`
         Match(
             Asset,
             EqFixedU128,
@@ -394,7 +939,82 @@ pub mod pallet {
             T::Balance,
             EqFixedU128,
             OrderSide,
+            OrderId,
+            EqFixedU128,
+            u64,
+            u64,
+        ),
+        /// Market is switched to batch auction mode or back to continuous matching
+        /// `[asset, period]`
+        BatchAuctionModeChanged(Asset, Option<T::BlockNumber>),
+        /// Orders matched in batch auction at uniform price
+        /// `[asset, price, buy_order_id, sell_order_id, buyer, seller, buyer_fee, seller_fee, exchange_amount, buy_rest, sell_rest, sequence]`
+        BatchMatch(
+            Asset,
+            FixedI64,
+            OrderId,
+            OrderId,
+            T::AccountId,
+            T::AccountId,
+            T::Balance,
+            T::Balance,
+            EqFixedU128,
+            EqFixedU128,
+            EqFixedU128,
+            u64,
+        ),
+        /// Batch auction is cleared
+        /// `[asset, clearing_price, volume]`
+        BatchAuctionCleared(Asset, FixedI64, EqFixedU128),
+        /// Cross-pair is enabled or disabled for trading
+        /// `[base, quote, enabled]`
+        TradingPairChanged(Asset, Asset, bool),
+        /// Cross-pair order is settled
+        /// `[account_id, base, quote, side, base_amount, quote_amount]`
+        PairOrderExecuted(
+            T::AccountId,
+            Asset,
+            Asset,
+            OrderSide,
+            EqFixedU128,
+            EqFixedU128,
+        ),
+        /// Corridor derived from volatility is enabled or disabled for asset
+        /// `[asset, params]`
+        AutoCorridorChanged(Asset, Option<AutoCorridor>),
+        /// Corridor of asset is updated from its volatility
+        /// `[asset, corridor]`
+        AssetCorridorUpdated(Asset, u32),
+        /// Self-trade prevention mode of account is changed
+        /// `[account_id, mode]`
+        SelfTradePreventionChanged(T::AccountId, SelfTradePrevention),
+        /// Account is banned or unbanned from trading
+        /// `[account_id, banned]`
+        TradingBanChanged(T::AccountId, bool),
+        /// Sibling parachain is allowed or disallowed to trade via XCM `Transact`
+        /// `[para_id, trusted]`
+        TrustedXcmParachainChanged(u32, bool),
+        /// Taker order met maker order of the same account
+        /// `[asset, account_id, maker_order_id, amount, mode, taker_order_id, sequence]`
+        SelfTradePrevented(
+            Asset,
+            T::AccountId,
+            OrderId,
+            EqFixedU128,
+            SelfTradePrevention,
+            OrderId,
+            u64,
         ),
+        /// Displayed part of iceberg order is filled and placed again as a new order
+        /// `[account_id, asset, filled_order_id, order_id, hidden_amount]`
+        IcebergOrderRefilled(T::AccountId, Asset, OrderId, OrderId, EqFixedU128),
+        /// Hidden part of iceberg order is removed with its displayed order or
+        /// after failed refill
+        /// `[account_id, asset, order_id, hidden_amount]`
+        IcebergOrderRemoved(T::AccountId, Asset, OrderId, EqFixedU128),
+        /// Storage deposit of the penalized order is forfeited to Treasury
+        /// `[account_id, asset, order_id, deposit]`
+        OrderDepositForfeited(T::AccountId, Asset, OrderId, T::Balance),
     }
 
     #[pallet::error]
@@ -425,6 +1045,34 @@ pub mod pallet {
         DexIsDisabledForAsset,
         /// Price step should be a positive value
         PriceStepShouldBePositive,
+        /// Batch auction period should be a positive number of blocks
+        BatchAuctionPeriodShouldBePositive,
+        /// Market orders can't be placed in batch auction markets
+        MarketOrderInBatchAuction,
+        /// Only EQD and DOT could be used as quote asset of trading pair
+        QuoteAssetNotSupported,
+        /// Base asset should differ from quote asset and EQD
+        WrongTradingPair,
+        /// Trading pair is not enabled
+        TradingPairIsNotEnabled,
+        /// Leg of cross-pair order is not fully filled
+        PairOrderNotFilled,
+        /// Effective price of cross-pair order is worse than limit price
+        PairOrderPriceNotSatisfied,
+        /// Volatility multiplier should be positive and min corridor not greater than max
+        WrongAutoCorridor,
+        /// Corridor derived from volatility is not enabled for asset
+        AutoCorridorNotSet,
+        /// There are no volatility metrics for asset
+        NoVolatilityMetrics,
+        /// Account is banned from trading
+        AccountIsBanned,
+        /// Displayed amount of iceberg order should be positive and less than order amount
+        WrongIcebergDisplayAmount,
+        /// Batch auction market has max number of resting orders
+        TooManyBatchOrders,
+        /// Max number of markets is already in batch auction mode
+        TooManyBatchAuctionMarkets,
     }
 
     #[pallet::validate_unsigned]
@@ -463,12 +1111,55 @@ pub mod pallet {
                         return InvalidTransaction::BadProof.into();
                     }
                     log::error!("signature_valid");
+                    let duty = T::ValidatorOffchainBatcher::validate_duty(
+                        request.authority_index,
+                        UnsignedDuty::DexOrders,
+                        request.nonce,
+                    )?;
 
                     let priority = T::DexUnsignedPriority::get();
 
                     ValidTransaction::with_tag_prefix("DexDeleteOrder")
                         .priority(priority)
                         .and_provides(request.order_id)
+                        .combine_with(duty)
+                        .longevity(64)
+                        .propagate(true)
+                        .build()
+                }
+                Call::update_auto_corridor { request, signature } => {
+                    let keys = eq_rate::Keys::<T>::get();
+                    if keys.len() as u32 != request.validators_len {
+                        return InvalidTransaction::Custom(INVALID_VALIDATORS_LEN).into();
+                    }
+
+                    let authority_id = match keys.get(request.authority_index as usize) {
+                        Some(id) => id,
+                        None => return InvalidTransaction::BadProof.into(),
+                    };
+
+                    // corridor should be changed by the request
+                    match Self::auto_corridor_value(request.asset) {
+                        Ok(corridor)
+                            if corridor != ChunkCorridorByAsset::<T>::get(request.asset) => {}
+                        _ => return InvalidTransaction::Stale.into(),
+                    }
+
+                    let signature_valid = request
+                        .using_encoded(|encoded_req| authority_id.verify(&encoded_req, &signature));
+                    if !signature_valid {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    let duty = T::ValidatorOffchainBatcher::validate_duty(
+                        request.authority_index,
+                        UnsignedDuty::DexCorridors,
+                        request.nonce,
+                    )?;
+
+                    ValidTransaction::with_tag_prefix("DexUpdateCorridor")
+                        .priority(T::DexUnsignedPriority::get())
+                        .and_provides(request.asset)
+                        .combine_with(duty)
                         .longevity(64)
                         .propagate(true)
                         .build()
@@ -504,13 +1195,15 @@ mod offchain {
         });
 
         let native_asset = T::AssetGetter::get_main_asset();
-        let penalty_fee = T::PenaltyFee::get();
+        let mut nonce =
+            T::ValidatorOffchainBatcher::duty_state(authority_index, UnsignedDuty::DexOrders).nonce;
         orders_data
             .into_iter()
             .filter(|(_, order_id, _, _, _)| {
                 *order_id % Into::<u64>::into(validators_len) == Into::<u64>::into(authority_index)
             })
             .for_each(|(asset, order_id, price, account_id, reason)| {
+                let penalty_fee = <Pallet<T>>::penalty_fee(&account_id);
                 let buyout = match T::BalanceGetter::get_balance(&account_id, &native_asset) {
                     SignedBalance::Negative(amount) => Some(amount + penalty_fee),
                     SignedBalance::Positive(amount) => {
@@ -518,7 +1211,7 @@ mod offchain {
                     }
                 };
 
-                let _ = <Pallet<T>>::submit_tx_delete_order_for_single_authority(
+                let submitted = <Pallet<T>>::submit_tx_delete_order_for_single_authority(
                     asset,
                     order_id,
                     price,
@@ -529,7 +1222,11 @@ mod offchain {
                     block,
                     validators_len,
                     reason,
+                    nonce,
                 );
+                if submitted.is_ok() {
+                    nonce += 1;
+                }
             });
 
         Ok(())
@@ -569,14 +1266,66 @@ mod offchain {
         orders_data
     }
 
-    fn get_orders_of_bad_margin_accounts<T: Config>(
-    ) -> Vec<(Asset, OrderId, Price, T::AccountId, DeleteOrderReason)> {
-        let mut orders_data = Vec::new();
-
-        let order_data_map = prepare_order_data_map::<T>();
+    /// Submit updates of corridors derived from volatility which differ from the stored ones
+    pub(super) fn update_auto_corridors<T: Config>(
+        authority_index: u32,
+        authority_key: T::AuthorityId,
+        block: T::BlockNumber,
+        validators_len: u32,
+    ) -> OffchainResult<()> {
+        let mut nonce =
+            T::ValidatorOffchainBatcher::duty_state(authority_index, UnsignedDuty::DexCorridors)
+                .nonce;
+
+        for (asset, _) in AutoCorridorByAsset::<T>::iter() {
+            if asset.get_id() % Into::<u64>::into(validators_len)
+                != Into::<u64>::into(authority_index)
+            {
+                continue;
+            }
 
-        for account_id in T::Aggregates::iter_account(UserGroup::Borrowers) {
-            if let Some(order_data_vec) = order_data_map.get(&account_id) {
+            let corridor = match <Pallet<T>>::auto_corridor_value(asset) {
+                Ok(corridor) => corridor,
+                Err(err) => {
+                    log::warn!(
+                        target: "eq_dex",
+                        "{}:{}. Can't derive corridor. Asset: {:?}, error: {:?}",
+                        file!(),
+                        line!(),
+                        asset,
+                        err
+                    );
+                    continue;
+                }
+            };
+            if corridor == ChunkCorridorByAsset::<T>::get(asset) {
+                continue;
+            }
+
+            let submitted = <Pallet<T>>::submit_tx_update_corridor(
+                asset,
+                authority_index,
+                authority_key.clone(),
+                block,
+                validators_len,
+                nonce,
+            );
+            if submitted.is_ok() {
+                nonce += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_orders_of_bad_margin_accounts<T: Config>(
+    ) -> Vec<(Asset, OrderId, Price, T::AccountId, DeleteOrderReason)> {
+        let mut orders_data = Vec::new();
+
+        let order_data_map = prepare_order_data_map::<T>();
+
+        for account_id in T::Aggregates::iter_account(UserGroup::Borrowers) {
+            if let Some(order_data_vec) = order_data_map.get(&account_id) {
                 match T::MarginCallManager::check_margin(&account_id) {
                     Err(margin_error) => {
                         log::error!(
@@ -625,6 +1374,58 @@ mod offchain {
 }
 
 impl<T: Config> Pallet<T> {
+    /// Returns master account if `who` is its active trading session key,
+    /// orders are placed and cancelled on behalf of the master's `Trader` subaccount
+    fn resolve_session_key(who: T::AccountId) -> T::AccountId {
+        T::SubaccountsManager::session_key_owner(&who).unwrap_or(who)
+    }
+
+    fn submit_tx_update_corridor(
+        asset: Asset,
+        authority_index: u32,
+        authority_key: T::AuthorityId,
+        block: T::BlockNumber,
+        validators_len: u32,
+        nonce: u64,
+    ) -> OffchainResult<()> {
+        let request = OperationRequestDexUpdateCorridor::<T::BlockNumber> {
+            asset,
+            authority_index,
+            validators_len,
+            block_num: block,
+            nonce,
+        };
+
+        let signature = ok_or_error!(
+            authority_key.sign(&request.encode()),
+            OffchainErr::FailedSigning,
+            "{}:{}. Couldn't sign. Key: {:?}, authority_index: {:?}, \
+                    validators_len: {:?}, block_num:{:?}.",
+            file!(),
+            line!(),
+            authority_key,
+            &request.authority_index,
+            &request.validators_len,
+            &request.block_num
+        )?;
+        let call = Call::update_auto_corridor { request, signature };
+
+        SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).map_err(|_| {
+            log::trace!(
+                target: "eq_dex",
+                "{}:{}. Submit update_auto_corridor error. Asset: {:?}, \
+                authority_index: {:?}, validators_len: {:?}, block_num:{:?}.",
+                file!(),
+                line!(),
+                asset,
+                authority_index,
+                validators_len,
+                block
+            );
+            OffchainErr::SubmitTransaction
+        })
+    }
+
     fn submit_tx_delete_order_for_single_authority(
         asset: Asset,
         order_id: OrderId,
@@ -636,6 +1437,7 @@ impl<T: Config> Pallet<T> {
         block: T::BlockNumber,
         validators_len: u32,
         reason: DeleteOrderReason,
+        nonce: u64,
     ) -> OffchainResult<()> {
         let request = OperationRequestDexDeleteOrder::<T::BlockNumber, T::AccountId, T::Balance> {
             asset,
@@ -647,6 +1449,7 @@ impl<T: Config> Pallet<T> {
             validators_len,
             block_num: block,
             reason,
+            nonce,
         };
 
         let option_signature = authority_key.sign(&request.encode());
@@ -688,12 +1491,89 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Discount of `who` on fees and penalties by the highest staking tier reached by
+    /// its master account
+    pub fn staker_discount(who: &T::AccountId) -> Percent {
+        let tiers = T::StakerDiscounts::get();
+        if tiers.is_empty() {
+            return Percent::zero();
+        }
+
+        let staker = T::SubaccountsManager::get_owner_id(who)
+            .map(|(owner, _)| owner)
+            .unwrap_or_else(|| who.clone());
+        let staked = T::StakedBalance::staked_balance(&staker);
+        tiers
+            .into_iter()
+            .filter(|(threshold, _)| staked >= *threshold)
+            .map(|(_, discount)| discount)
+            .max()
+            .unwrap_or_else(Percent::zero)
+    }
+
+    /// `amount` of fee or penalty paid by `who` with its staker discount
+    fn discounted(who: &T::AccountId, amount: T::Balance) -> T::Balance {
+        amount - Self::staker_discount(who).mul_floor(amount)
+    }
+
+    /// Penalty fee for deleting orders of `who`
+    pub fn penalty_fee(who: &T::AccountId) -> T::Balance {
+        Self::discounted(who, T::PenaltyFee::get())
+    }
+
+    /// Returns storage deposit of the removed `order` to its account,
+    /// deposit of the order penalized by offchain worker goes to Treasury
+    fn settle_order_deposit(
+        asset: &Asset,
+        order: &Order<T::AccountId>,
+        reason: DeleteOrderReason,
+    ) -> DispatchResult {
+        let deposit = match OrderDeposits::<T>::take(asset, order.order_id) {
+            Some(deposit) => deposit,
+            None => return Ok(()),
+        };
+        let main_asset = T::AssetGetter::get_main_asset();
+
+        match reason {
+            DeleteOrderReason::OutOfCorridor
+            | DeleteOrderReason::MarginCall
+            | DeleteOrderReason::DisableTradingPair => {
+                T::EqCurrency::repatriate_reserved(
+                    &order.account_id,
+                    &T::TreasuryModuleId::get().into_account_truncating(),
+                    main_asset,
+                    deposit,
+                    BalanceStatus::Free,
+                )?;
+                T::TreasuryAccounting::record_inflow(
+                    TreasuryFlowCategory::DexPenalties,
+                    main_asset,
+                    deposit,
+                );
+                Self::deposit_event(Event::OrderDepositForfeited(
+                    order.account_id.clone(),
+                    *asset,
+                    order.order_id,
+                    deposit,
+                ));
+            }
+            DeleteOrderReason::Cancel
+            | DeleteOrderReason::Match
+            | DeleteOrderReason::MakerError
+            | DeleteOrderReason::SelfTrade => {
+                T::EqCurrency::unreserve(&order.account_id, main_asset, deposit);
+            }
+        }
+
+        Ok(())
+    }
+
     fn charge_penalty_fee(
         who: &T::AccountId,
         buyout: Option<T::Balance>,
     ) -> DispatchResultWithPostInfo {
         let basic_asset = T::AssetGetter::get_main_asset();
-        let amount = T::PenaltyFee::get();
+        let amount = Self::penalty_fee(who);
         let treasury_acc = T::TreasuryModuleId::get().into_account_truncating();
 
         if let Some(amount) = buyout {
@@ -709,6 +1589,11 @@ impl<T: Config> Pallet<T> {
             eq_primitives::TransferReason::InterestFee,
             false,
         )?;
+        T::TreasuryAccounting::record_inflow(
+            TreasuryFlowCategory::DexPenalties,
+            basic_asset,
+            amount,
+        );
 
         Ok(().into())
     }
@@ -722,6 +1607,30 @@ impl<T: Config> Pallet<T> {
         amount: EqFixedU128,
         expiration_time: u64,
         asset_data: &AssetData<Asset>,
+    ) -> DispatchResultWithPostInfo {
+        let order_id = Self::get_order_id();
+        Self::create_limit_order_with_id(
+            order_id,
+            borrower_id,
+            asset,
+            price,
+            side,
+            amount,
+            expiration_time,
+            asset_data,
+        )
+    }
+
+    /// Create limit order with `order_id` already taken from `OrderIdCounter`
+    fn create_limit_order_with_id(
+        order_id: OrderId,
+        borrower_id: T::AccountId,
+        asset: Asset,
+        price: Price,
+        side: OrderSide,
+        amount: EqFixedU128,
+        expiration_time: u64,
+        asset_data: &AssetData<Asset>,
     ) -> DispatchResultWithPostInfo {
         Self::ensure_price_satisfies_price_step(&borrower_id, &asset_data, price)?;
         Self::ensure_order_in_corridor(asset, price)?;
@@ -747,7 +1656,12 @@ impl<T: Config> Pallet<T> {
             margin_state,
         );
 
-        let order_id = Self::get_order_id();
+        let deposit = T::OrderDeposit::get();
+        if !deposit.is_zero() {
+            T::EqCurrency::reserve(&borrower_id, T::AssetGetter::get_main_asset(), deposit)?;
+            OrderDeposits::<T>::insert(asset, order_id, deposit);
+        }
+
         let created_at = T::UnixTime::now().as_secs();
 
         let order = Order {
@@ -762,6 +1676,22 @@ impl<T: Config> Pallet<T> {
 
         let chunk_key = Self::get_chunk_key(price, asset_data.price_step)?;
 
+        BatchOrdersCount::<T>::try_mutate_exists(asset, |maybe_count| -> DispatchResult {
+            if let Some(count) = maybe_count {
+                eq_ensure!(
+                    *count < T::MaxBatchOrders::get(),
+                    Error::<T>::TooManyBatchOrders,
+                    target: "eq_dex",
+                    "{}:{}. Too many orders in batch auction. Asset: {:?}",
+                    file!(),
+                    line!(),
+                    asset,
+                );
+                *count += 1;
+            }
+            Ok(())
+        })?;
+
         OrdersByAssetAndChunkKey::<T>::try_mutate_exists(
             asset,
             chunk_key,
@@ -844,6 +1774,8 @@ impl<T: Config> Pallet<T> {
             side,
             created_at,
             expiration_time,
+            chunk_key,
+            Self::next_event_sequence(asset),
         ));
 
         Ok(().into())
@@ -898,6 +1830,30 @@ impl<T: Config> Pallet<T> {
         order_id
     }
 
+    /// Increments and returns sequence number of `asset` order book events
+    fn next_event_sequence(asset: Asset) -> u64 {
+        EventSequenceByAsset::<T>::mutate(asset, |sequence| {
+            *sequence = sequence.saturating_add(1);
+            *sequence
+        })
+    }
+
+    /// Open orders of `who` in all assets, used in runtime API.
+    /// Only assets with non-empty order aggregates of the account are scanned.
+    pub fn account_orders(who: &T::AccountId) -> Vec<(Asset, Order<T::AccountId>)> {
+        AssetWeightByAccountId::<T>::get(who)
+            .iter()
+            .flat_map(|(asset, _)| {
+                let asset = *asset;
+                ActualChunksByAsset::<T>::get(asset)
+                    .into_iter()
+                    .flat_map(move |chunk| OrdersByAssetAndChunkKey::<T>::get(asset, chunk))
+                    .filter(move |order| &order.account_id == who)
+                    .map(move |order| (asset, order))
+            })
+            .collect()
+    }
+
     pub(crate) fn get_chunk_key(
         price: FixedI64,
         price_step: FixedI64,
@@ -938,91 +1894,155 @@ impl<T: Config> Pallet<T> {
 
     fn try_match(
         taker_account: &T::AccountId,
+        taker_order_id: OrderId,
         taker_side: OrderSide,
         taker_type: OrderType,
         taker_amount: EqFixedU128,
         asset: &Asset,
-    ) -> Result<Option<EqFixedU128>, DispatchError> {
+        self_trade_prevention: SelfTradePrevention,
+    ) -> Result<TakerRest, DispatchError> {
         let asset_data = T::AssetGetter::get_asset_data(&asset)?;
 
-        let ask_bid_prices = Self::asset_ask_bid_prices(asset);
+        let mut rest = taker_amount;
+        loop {
+            let ask_bid_prices = Self::asset_ask_bid_prices(asset);
 
-        let (best_price, no_match_ord) = match taker_side {
-            Buy => (ask_bid_prices.ask, core::cmp::Ordering::Less),
-            Sell => (ask_bid_prices.bid, core::cmp::Ordering::Greater),
-        };
+            let (best_price, no_match_ord) = match taker_side {
+                Buy => (ask_bid_prices.ask, core::cmp::Ordering::Less),
+                Sell => (ask_bid_prices.bid, core::cmp::Ordering::Greater),
+            };
 
-        let best_price = match taker_type {
-            Limit { ref price, .. } => {
-                match best_price.filter(|best_price| price.cmp(best_price) != no_match_ord) {
-                    Some(best_price) => best_price,
-                    None => return Ok(Some(taker_amount)),
+            let best_price = match taker_type {
+                Limit { ref price, .. } => {
+                    match best_price.filter(|best_price| price.cmp(best_price) != no_match_ord) {
+                        Some(best_price) => best_price,
+                        None => return Ok(TakerRest::Unmatched(rest)),
+                    }
                 }
-            }
-            Market => match best_price {
-                Some(best_price) => best_price,
-                None => return Err(Error::<T>::NoBestPriceForMarketOrder.into()),
-            },
-        };
-
-        let chunks = Self::actual_price_chunks(asset);
-        let chunk_key = Self::get_chunk_key(best_price, asset_data.price_step)?;
-        let start_chunk_index = chunks
-            .binary_search(&chunk_key)
-            .map_err(|_| Error::<T>::InconsistentStorage)?;
-        let chunks_directed = match taker_side {
-            Buy => Either::Left(start_chunk_index..chunks.len()),
-            Sell => Either::Right((0..start_chunk_index + 1).rev()),
-        };
-
-        let mut rest = taker_amount;
-        'outer: for chunk_index in chunks_directed {
-            let chunk_id = chunks[chunk_index];
-            let chunk = Self::orders_by_asset_and_chunk_key(asset, chunk_id);
+                Market => match best_price {
+                    Some(best_price) => best_price,
+                    // book is exhausted by the previous pass
+                    None if rest != taker_amount => return Ok(TakerRest::Unmatched(rest)),
+                    None => return Err(Error::<T>::NoBestPriceForMarketOrder.into()),
+                },
+            };
 
-            let chunk_iterator = match taker_side {
-                Buy => Either::Left(chunk.iter()),
-                Sell => Either::Right(chunk.iter().rev()),
+            let chunks = Self::actual_price_chunks(asset);
+            let chunk_key = Self::get_chunk_key(best_price, asset_data.price_step)?;
+            let start_chunk_index = chunks
+                .binary_search(&chunk_key)
+                .map_err(|_| Error::<T>::InconsistentStorage)?;
+            let chunks_directed = match taker_side {
+                Buy => Either::Left(start_chunk_index..chunks.len()),
+                Sell => Either::Right((0..start_chunk_index + 1).rev()),
             };
 
-            for maker_order in chunk_iterator {
-                if taker_side == maker_order.side {
-                    continue;
-                }
+            let mut iceberg_matched = false;
+            'outer: for chunk_index in chunks_directed {
+                let chunk_id = chunks[chunk_index];
+                let chunk = Self::orders_by_asset_and_chunk_key(asset, chunk_id);
 
-                let price_match = match (taker_type, taker_side) {
-                    (Limit { price, .. }, Buy) => price >= maker_order.price,
-                    (Limit { price, .. }, Sell) => price <= maker_order.price,
-                    (Market, _) => true,
+                let chunk_iterator = match taker_side {
+                    Buy => Either::Left(chunk.iter()),
+                    Sell => Either::Right(chunk.iter().rev()),
                 };
 
-                if rest == EqFixedU128::zero() || !price_match {
-                    break 'outer;
-                }
+                for maker_order in chunk_iterator {
+                    if taker_side == maker_order.side {
+                        continue;
+                    }
 
-                let delta_rest = Self::match_two_orders(
-                    taker_account,
-                    rest,
-                    taker_type,
-                    taker_side,
-                    maker_order,
-                    asset,
-                )?;
+                    let price_match = match (taker_type, taker_side) {
+                        (Limit { price, .. }, Buy) => price >= maker_order.price,
+                        (Limit { price, .. }, Sell) => price <= maker_order.price,
+                        (Market, _) => true,
+                    };
+
+                    if rest == EqFixedU128::zero() || !price_match {
+                        break 'outer;
+                    }
 
-                rest = rest - delta_rest;
+                    if &maker_order.account_id == taker_account
+                        && self_trade_prevention != SelfTradePrevention::Allow
+                    {
+                        let prevented_amount = match self_trade_prevention {
+                            SelfTradePrevention::CancelOldest => maker_order.amount,
+                            SelfTradePrevention::Decrement => rest.min(maker_order.amount),
+                            _ => rest,
+                        };
+                        Self::deposit_event(Event::SelfTradePrevented(
+                            *asset,
+                            taker_account.clone(),
+                            maker_order.order_id,
+                            prevented_amount,
+                            self_trade_prevention,
+                            taker_order_id,
+                            Self::next_event_sequence(*asset),
+                        ));
+
+                        match self_trade_prevention {
+                            SelfTradePrevention::CancelOldest => {
+                                <Self as OrderManagement>::delete_order(
+                                    &asset,
+                                    maker_order.order_id,
+                                    maker_order.price,
+                                    DeleteOrderReason::SelfTrade,
+                                )
+                                .map_err(|e| e.error)?;
+                            }
+                            SelfTradePrevention::Decrement => {
+                                if maker_order.amount == prevented_amount {
+                                    <Self as OrderManagement>::delete_order(
+                                        &asset,
+                                        maker_order.order_id,
+                                        maker_order.price,
+                                        DeleteOrderReason::SelfTrade,
+                                    )
+                                    .map_err(|e| e.error)?;
+                                } else {
+                                    Self::decrease_order_amount(
+                                        asset,
+                                        maker_order,
+                                        prevented_amount,
+                                    )?;
+                                }
+                                rest = rest - prevented_amount;
+                            }
+                            _ => return Ok(TakerRest::Cancelled),
+                        }
+                        continue;
+                    }
+
+                    iceberg_matched |=
+                        IcebergOrders::<T>::contains_key(asset, maker_order.order_id);
+                    let delta_rest = Self::match_two_orders(
+                        taker_account,
+                        taker_order_id,
+                        rest,
+                        taker_type,
+                        taker_side,
+                        maker_order,
+                        asset,
+                    )?;
+
+                    rest = rest - delta_rest;
+                }
             }
-        }
 
-        if rest.is_zero() {
-            return Ok(None);
+            if rest.is_zero() {
+                return Ok(TakerRest::Filled);
+            }
+            // refilled iceberg orders are not in chunks read above, match them in another pass
+            if !iceberg_matched {
+                return Ok(TakerRest::Unmatched(rest));
+            }
         }
-
-        Ok(Some(rest))
     }
 
     /// Checks if `taker_price` and `taker_side` matches with `maker_order` and makes exchange.
     /// Arguments:
     /// - `taker_account` - taker's AccountId
+    /// - `taker_order_id` - id of taker's order, stored only if the order rests in the book
     /// - `taker_rest` - the rest of taker's order
     /// - `taker_price` - taker's price
     /// - `taker_side` - taker's order side
@@ -1034,6 +2054,7 @@ impl<T: Config> Pallet<T> {
     /// Maker's order will be deleted or modified. Maker's aggregate q(i) also will be modified.
     fn match_two_orders(
         taker_account: &T::AccountId,
+        taker_order_id: OrderId,
         taker_rest: EqFixedU128,
         _taker_type: OrderType,
         taker_side: OrderSide,
@@ -1063,8 +2084,10 @@ impl<T: Config> Pallet<T> {
         };
 
         let asset_data = T::AssetGetter::get_asset_data(asset)?;
-        let taker_fee_value = asset_data.taker_fee.mul_floor(usd_amount_b);
-        let maker_fee_value = asset_data.maker_fee.mul_floor(usd_amount_b);
+        let taker_fee_value =
+            Self::discounted(taker_account, asset_data.taker_fee.mul_floor(usd_amount_b));
+        let maker_fee_value =
+            Self::discounted(maker_account, asset_data.maker_fee.mul_floor(usd_amount_b));
 
         T::EqCurrency::withdraw(
             taker_account,
@@ -1138,44 +2161,7 @@ impl<T: Config> Pallet<T> {
             )
             .map_err(|e| e.error)?;
         } else {
-            let asset_data = T::AssetGetter::get_asset_data(asset)?;
-            let chunk_key = Self::get_chunk_key(maker_order.price, asset_data.price_step)?;
-            let new_amount = maker_order
-                .amount
-                .checked_sub(&exchange_amount)
-                .ok_or(ArithmeticError::Overflow)?;
-            let modified_order = Order {
-                amount: new_amount,
-                ..maker_order.clone()
-            };
-            OrdersByAssetAndChunkKey::<T>::try_mutate_exists(
-                asset,
-                chunk_key,
-                |maybe_orders| -> DispatchResult {
-                    match maybe_orders {
-                        Some(orders) => {
-                            match orders.binary_search_by(|o| {
-                                o.price
-                                    .cmp(&modified_order.price)
-                                    .then(o.order_id.cmp(&modified_order.order_id))
-                            }) {
-                                Ok(i) => orders[i] = modified_order.clone(),
-                                Err(_) => return Err(Error::<T>::InconsistentStorage.into()),
-                            };
-                        }
-                        None => return Err(Error::<T>::InconsistentStorage.into()),
-                    };
-                    Ok(())
-                },
-            )?;
-            Self::update_asset_weight(
-                modified_order.account_id,
-                *asset,
-                exchange_amount, //modified_order.amount,
-                modified_order.price,
-                modified_order.side,
-                Decrease,
-            )?;
+            Self::decrease_order_amount(asset, maker_order, exchange_amount)?;
         };
 
         if maker_exchange_failed {
@@ -1193,12 +2179,303 @@ impl<T: Config> Pallet<T> {
                 taker_fee_value,
                 exchange_amount,
                 maker_order.side,
+                taker_order_id,
+                maker_order.amount - exchange_amount,
+                Self::get_chunk_key(maker_order.price, asset_data.price_step)?,
+                Self::next_event_sequence(*asset),
             ));
 
             Ok(exchange_amount)
         }
     }
 
+    /// Decreases amount of partially filled `order` by `exchange_amount` and updates
+    /// account aggregates. Order amount should be greater than `exchange_amount`.
+    fn decrease_order_amount(
+        asset: &Asset,
+        order: &Order<T::AccountId>,
+        exchange_amount: EqFixedU128,
+    ) -> DispatchResult {
+        let asset_data = T::AssetGetter::get_asset_data(asset)?;
+        let chunk_key = Self::get_chunk_key(order.price, asset_data.price_step)?;
+        let new_amount = order
+            .amount
+            .checked_sub(&exchange_amount)
+            .ok_or(ArithmeticError::Overflow)?;
+        let modified_order = Order {
+            amount: new_amount,
+            ..order.clone()
+        };
+        OrdersByAssetAndChunkKey::<T>::try_mutate_exists(
+            asset,
+            chunk_key,
+            |maybe_orders| -> DispatchResult {
+                match maybe_orders {
+                    Some(orders) => {
+                        match orders.binary_search_by(|o| {
+                            o.price
+                                .cmp(&modified_order.price)
+                                .then(o.order_id.cmp(&modified_order.order_id))
+                        }) {
+                            Ok(i) => orders[i] = modified_order.clone(),
+                            Err(_) => return Err(Error::<T>::InconsistentStorage.into()),
+                        };
+                    }
+                    None => return Err(Error::<T>::InconsistentStorage.into()),
+                };
+                Ok(())
+            },
+        )?;
+        Self::update_asset_weight(
+            modified_order.account_id,
+            *asset,
+            exchange_amount, //modified_order.amount,
+            modified_order.price,
+            modified_order.side,
+            Decrease,
+        )
+    }
+
+    /// Uniform clearing price of the batch: price maximizing executed volume,
+    /// ties are broken by minimal imbalance between demand and supply and then by lowest price.
+    /// Returns `None` if orders don't cross.
+    fn batch_clearing_price(
+        buys: &[Order<T::AccountId>],
+        sells: &[Order<T::AccountId>],
+    ) -> Option<(Price, EqFixedU128)> {
+        let mut prices: Vec<Price> = buys.iter().chain(sells.iter()).map(|o| o.price).collect();
+        prices.sort();
+        prices.dedup();
+
+        let mut buys: Vec<_> = buys.iter().map(|o| (o.price, o.amount)).collect();
+        buys.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut sells: Vec<_> = sells.iter().map(|o| (o.price, o.amount)).collect();
+        sells.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // demand and supply at ascending prices are kept in one pass over sorted orders
+        let mut demand = buys.iter().fold(EqFixedU128::zero(), |acc, (_, amount)| {
+            acc.saturating_add(*amount)
+        });
+        let mut supply = EqFixedU128::zero();
+        let (mut buy_index, mut sell_index) = (0usize, 0usize);
+
+        let mut best: Option<(Price, EqFixedU128, EqFixedU128)> = None;
+        for price in prices {
+            while buy_index < buys.len() && buys[buy_index].0 < price {
+                demand = demand.saturating_sub(buys[buy_index].1);
+                buy_index += 1;
+            }
+            while sell_index < sells.len() && sells[sell_index].0 <= price {
+                supply = supply.saturating_add(sells[sell_index].1);
+                sell_index += 1;
+            }
+            let volume = demand.min(supply);
+            let imbalance = demand.max(supply) - volume;
+
+            let is_better = match best {
+                None => true,
+                Some((_, best_volume, best_imbalance)) => {
+                    volume > best_volume || (volume == best_volume && imbalance < best_imbalance)
+                }
+            };
+            if is_better {
+                best = Some((price, volume, imbalance));
+            }
+        }
+
+        best.filter(|(_, volume, _)| !volume.is_zero())
+            .map(|(price, volume, _)| (price, volume))
+    }
+
+    /// Clears batch auction of `asset`: matches all crossing orders at a single
+    /// uniform price. Returns number of executed fills.
+    fn do_clear_batch_auction(asset: Asset) -> Result<u32, DispatchError> {
+        let (mut buys, mut sells): (Vec<_>, Vec<_>) = ActualChunksByAsset::<T>::get(asset)
+            .into_iter()
+            .flat_map(|chunk_key| OrdersByAssetAndChunkKey::<T>::get(asset, chunk_key))
+            .partition(|o| o.side == Buy);
+
+        let (clearing_price, volume) = match Self::batch_clearing_price(&buys, &sells) {
+            Some(clearing) => clearing,
+            None => return Ok(0),
+        };
+
+        // price-time priority inside the batch
+        buys.retain(|o| o.price >= clearing_price);
+        buys.sort_by(|a, b| b.price.cmp(&a.price).then(a.order_id.cmp(&b.order_id)));
+        sells.retain(|o| o.price <= clearing_price);
+        sells.sort_by(|a, b| a.price.cmp(&b.price).then(a.order_id.cmp(&b.order_id)));
+
+        let mut rest = volume;
+        let mut fills = 0u32;
+        let mut executed = EqFixedU128::zero();
+        let (mut buy_index, mut sell_index) = (0usize, 0usize);
+        while !rest.is_zero() && buy_index < buys.len() && sell_index < sells.len() {
+            let exchange_amount = rest
+                .min(buys[buy_index].amount)
+                .min(sells[sell_index].amount);
+
+            match Self::settle_batch_fill(
+                &asset,
+                clearing_price,
+                &buys[buy_index],
+                &sells[sell_index],
+                exchange_amount,
+            )? {
+                None => {
+                    fills += 1;
+                    rest = rest - exchange_amount;
+                    executed = executed + exchange_amount;
+                    buys[buy_index].amount = buys[buy_index].amount - exchange_amount;
+                    sells[sell_index].amount = sells[sell_index].amount - exchange_amount;
+                    if buys[buy_index].amount.is_zero() {
+                        buy_index += 1;
+                    }
+                    if sells[sell_index].amount.is_zero() {
+                        sell_index += 1;
+                    }
+                }
+                Some(failed) => {
+                    let failed_order = if failed == buys[buy_index].account_id {
+                        &mut buy_index
+                    } else {
+                        &mut sell_index
+                    };
+                    *failed_order += 1;
+                }
+            }
+        }
+
+        Self::deposit_event(Event::BatchAuctionCleared(asset, clearing_price, executed));
+
+        Ok(fills)
+    }
+
+    /// Exchanges `exchange_amount` of `asset` between `buy` and `sell` orders at `price`,
+    /// both sides pay maker fee with their staker discounts. Returns account which caused exchange error,
+    /// its order is deleted with `MakerError` reason.
+    fn settle_batch_fill(
+        asset: &Asset,
+        price: Price,
+        buy: &Order<T::AccountId>,
+        sell: &Order<T::AccountId>,
+        exchange_amount: EqFixedU128,
+    ) -> Result<Option<T::AccountId>, DispatchError> {
+        let buyer = &buy.account_id;
+        let seller = &sell.account_id;
+        let usd_amount = exchange_amount
+            * price
+                .try_into()
+                .map_err(|_| Error::<T>::OrderPriceShouldBePositive)?;
+        let usd_amount_b =
+            balance_from_eq_fixedu128::<T::Balance>(usd_amount).ok_or(ArithmeticError::Overflow)?;
+        let exchange_amount_b = balance_from_eq_fixedu128::<T::Balance>(exchange_amount)
+            .ok_or(ArithmeticError::Overflow)?;
+
+        let asset_data = T::AssetGetter::get_asset_data(asset)?;
+        let fee_value = asset_data.maker_fee.mul_floor(usd_amount_b);
+        let buyer_fee = Self::discounted(buyer, fee_value);
+        let seller_fee = Self::discounted(seller, fee_value);
+
+        let withdraw_fee = |account: &T::AccountId, fee_value: T::Balance| {
+            T::EqCurrency::withdraw(
+                account,
+                EQD,
+                fee_value,
+                false,
+                None,
+                WithdrawReasons::empty(),
+                ExistenceRequirement::AllowDeath,
+            )
+        };
+
+        // one insolvent account shouldn't block clearing of the whole batch
+        let exchange_result = match withdraw_fee(buyer, buyer_fee) {
+            Err(error) => Err((error, Some(buyer.clone()))),
+            Ok(()) => match withdraw_fee(seller, seller_fee) {
+                Err(error) => {
+                    T::EqCurrency::deposit_creating(buyer, EQD, buyer_fee, false, None)?;
+                    Err((error, Some(seller.clone())))
+                }
+                Ok(()) => T::EqCurrency::exchange(
+                    (buyer, seller),
+                    (&EQD, asset),
+                    (usd_amount_b, exchange_amount_b),
+                )
+                .map_err(|err| {
+                    T::EqCurrency::deposit_creating(buyer, EQD, buyer_fee, false, None)
+                        .and_then(|_| {
+                            T::EqCurrency::deposit_creating(seller, EQD, seller_fee, false, None)
+                        })
+                        .map_or_else(|e| (e, None), |_| err)
+                }),
+            },
+        };
+
+        match exchange_result {
+            Ok(()) => {
+                T::EqCurrency::deposit_creating(
+                    &T::TreasuryModuleId::get().into_account_truncating(),
+                    EQD,
+                    buyer_fee + seller_fee,
+                    false,
+                    None,
+                )?;
+
+                for order in [buy, sell] {
+                    if order.amount == exchange_amount {
+                        <Self as OrderManagement>::delete_order(
+                            asset,
+                            order.order_id,
+                            order.price,
+                            DeleteOrderReason::Match,
+                        )
+                        .map_err(|e| e.error)?;
+                    } else {
+                        Self::decrease_order_amount(asset, order, exchange_amount)?;
+                    }
+                }
+
+                Self::deposit_event(Event::BatchMatch(
+                    *asset,
+                    price,
+                    buy.order_id,
+                    sell.order_id,
+                    buyer.clone(),
+                    seller.clone(),
+                    buyer_fee,
+                    seller_fee,
+                    exchange_amount,
+                    buy.amount - exchange_amount,
+                    sell.amount - exchange_amount,
+                    Self::next_event_sequence(*asset),
+                ));
+
+                Ok(None)
+            }
+            Err((error, may_be_account)) => {
+                let account_id = may_be_account.ok_or(error)?;
+                let failed_order = if &account_id == buyer {
+                    buy
+                } else if &account_id == seller {
+                    sell
+                } else {
+                    return Err(Error::<T>::InconsistentStorage.into());
+                };
+
+                <Self as OrderManagement>::delete_order(
+                    asset,
+                    failed_order.order_id,
+                    failed_order.price,
+                    DeleteOrderReason::MakerError,
+                )
+                .map_err(|e| e.error)?;
+
+                Ok(Some(account_id))
+            }
+        }
+    }
+
     fn ensure_amount_satisfies_lot(
         who: &T::AccountId,
         asset_data: &AssetData<Asset>,
@@ -1291,7 +2568,11 @@ impl<T: Config> Pallet<T> {
 
         let asset_data = T::AssetGetter::get_asset_data(&asset)?;
         let chunk_key = Self::get_chunk_key(price, asset_data.price_step)? as i64;
-        let corridor = ChunkCorridorByAsset::<T>::get(&asset) as i64;
+        let mut corridor = ChunkCorridorByAsset::<T>::get(&asset) as i64;
+        if T::PriceGetter::is_price_suspect(&asset) {
+            // oracle price may be wrong, don't force orders out of the book
+            corridor = corridor.saturating_mul(SUSPECT_PRICE_CORRIDOR_MULTIPLIER);
+        }
         let best_price = BestPriceByAsset::<T>::get(&asset);
         let oracle_price: FixedI64 = T::PriceGetter::get_price(&asset)?;
 
@@ -1322,6 +2603,252 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Number of resting orders of `asset`, stops reading chunks once it exceeds `limit`
+    fn orders_count(asset: &Asset, limit: u32) -> u32 {
+        let mut count = 0u32;
+        for chunk_key in ActualChunksByAsset::<T>::get(asset) {
+            let chunk_len =
+                OrdersByAssetAndChunkKey::<T>::decode_len(asset, chunk_key).unwrap_or(0);
+            count = count.saturating_add(chunk_len as u32);
+            if count > limit {
+                break;
+            }
+        }
+        count
+    }
+
+    fn ensure_not_batch_auction(asset: Asset) -> DispatchResult {
+        eq_ensure!(
+            !BatchAuctionPeriod::<T>::contains_key(asset),
+            Error::<T>::MarketOrderInBatchAuction,
+            target: "eq_dex",
+            "{}:{}. Market orders are not allowed in batch auction. Asset: {:?}",
+            file!(),
+            line!(),
+            asset,
+        );
+
+        Ok(())
+    }
+
+    /// Absolute change of `who` balance of `asset` since `before`
+    fn ensure_not_banned(who: &T::AccountId) -> DispatchResult {
+        eq_ensure!(
+            !Self::trading_banned(who),
+            Error::<T>::AccountIsBanned,
+            target: "eq_dex",
+            "{}:{}. Account is banned from trading. Who: {:?}",
+            file!(),
+            line!(),
+            who,
+        );
+        Ok(())
+    }
+
+    fn balance_delta(
+        who: &T::AccountId,
+        asset: &Asset,
+        before: SignedBalance<T::Balance>,
+    ) -> Result<EqFixedU128, DispatchError> {
+        let delta = T::BalanceGetter::get_balance(who, asset)
+            .checked_sub(&before)
+            .ok_or(ArithmeticError::Overflow)?;
+        Ok(eq_fixedu128_from_balance(delta.abs()))
+    }
+
+    /// Settles order of cross-pair `base`/`quote` for `who` trader subaccount.
+    /// `base` is exchanged for EQD by `limit_price` converted to EQD, then for DOT quoted pairs
+    /// received (spent) EQD is exchanged for (covered by) DOT by market. All changes are
+    /// reverted by extrinsic if some check fails.
+    fn do_create_pair_order(
+        who: T::AccountId,
+        base: Asset,
+        quote: Asset,
+        side: OrderSide,
+        amount: EqFixedU128,
+        limit_price: FixedI64,
+    ) -> DispatchResult {
+        eq_ensure!(
+            Self::trading_pairs(base, quote),
+            Error::<T>::TradingPairIsNotEnabled,
+            target: "eq_dex",
+            "{}:{}. Trading pair is not enabled. Base: {:?}, quote: {:?}",
+            file!(),
+            line!(),
+            base,
+            quote,
+        );
+        eq_ensure!(
+            limit_price.is_positive(),
+            Error::<T>::OrderPriceShouldBePositive,
+            target: "eq_dex",
+            "{}:{}. Order price should be positive. Who: {:?}, price: {:?}",
+            file!(),
+            line!(),
+            who,
+            limit_price,
+        );
+
+        Self::ensure_not_banned(&who)?;
+        let trading_acc_id = T::SubaccountsManager::get_subaccount_id(&who, &SubAccType::Trader)
+            .ok_or(Error::<T>::AccountIsNotTrader)?;
+        let base_data = T::AssetGetter::get_asset_data(&base)?;
+        Self::ensure_dex_is_enabled(&base_data)?;
+        Self::ensure_amount_satisfies_lot(&who, &base_data, &amount)?;
+        Self::ensure_not_batch_auction(base)?;
+
+        let quote_price: FixedI64 = if quote == EQD {
+            FixedI64::saturating_from_integer(1)
+        } else {
+            T::PriceGetter::get_price(&quote)?
+        };
+        let base_leg_price = limit_price
+            .checked_mul(&quote_price)
+            .ok_or(ArithmeticError::Overflow)?;
+        Self::ensure_order_in_corridor(base, base_leg_price)?;
+
+        let base_before = T::BalanceGetter::get_balance(&trading_acc_id, &base);
+        let eqd_before = T::BalanceGetter::get_balance(&trading_acc_id, &EQD);
+        let quote_before = T::BalanceGetter::get_balance(&trading_acc_id, &quote);
+
+        // legs should be fully filled, so any self-trade prevention cancels the pair order
+        let self_trade_prevention = match Self::self_trade_prevention(&who) {
+            SelfTradePrevention::Allow => SelfTradePrevention::Allow,
+            _ => SelfTradePrevention::CancelNewest,
+        };
+        // pair order is never stored, its legs share the id
+        let order_id = Self::get_order_id();
+        let base_rest = Self::try_match(
+            &trading_acc_id,
+            order_id,
+            side,
+            Limit {
+                price: base_leg_price,
+                expiration_time: 0,
+            },
+            amount,
+            &base,
+            self_trade_prevention,
+        )?;
+        eq_ensure!(
+            base_rest == TakerRest::Filled,
+            Error::<T>::PairOrderNotFilled,
+            target: "eq_dex",
+            "{}:{}. Base leg is not filled. Asset: {:?}, rest: {:?}",
+            file!(),
+            line!(),
+            base,
+            base_rest,
+        );
+
+        if quote != EQD {
+            let quote_data = T::AssetGetter::get_asset_data(&quote)?;
+            Self::ensure_dex_is_enabled(&quote_data)?;
+            Self::ensure_not_batch_auction(quote)?;
+
+            // USD price of quote implied by limit price should be in quote corridor too
+            let base_price: FixedI64 = T::PriceGetter::get_price(&base)?;
+            let quote_leg_price = base_price
+                .checked_div(&limit_price)
+                .ok_or(ArithmeticError::DivisionByZero)?;
+            Self::ensure_order_in_corridor(quote, quote_leg_price)?;
+
+            let eqd_amount = Self::balance_delta(&trading_acc_id, &EQD, eqd_before)?;
+            let lots = eq_fixedu128_from_fixedi64(quote_price)
+                .and_then(|price| eqd_amount.checked_div(&price))
+                .and_then(|quote_amount| quote_amount.checked_div(&quote_data.lot))
+                .ok_or(ArithmeticError::DivisionByZero)?;
+            // buyer covers spent EQD, seller spends not more than received
+            let (quote_side, lots) = match side {
+                Buy => (Sell, lots.ceil()),
+                Sell => (Buy, lots.floor()),
+            };
+            let quote_amount = lots
+                .checked_mul(&quote_data.lot)
+                .ok_or(ArithmeticError::Overflow)?;
+            eq_ensure!(
+                !quote_amount.is_zero(),
+                Error::<T>::PairOrderNotFilled,
+                target: "eq_dex",
+                "{}:{}. Quote leg is less than lot. Asset: {:?}, EQD amount: {:?}",
+                file!(),
+                line!(),
+                quote,
+                eqd_amount,
+            );
+
+            let quote_rest = Self::try_match(
+                &trading_acc_id,
+                order_id,
+                quote_side,
+                Market,
+                quote_amount,
+                &quote,
+                self_trade_prevention,
+            )?;
+            eq_ensure!(
+                quote_rest == TakerRest::Filled,
+                Error::<T>::PairOrderNotFilled,
+                target: "eq_dex",
+                "{}:{}. Quote leg is not filled. Asset: {:?}, rest: {:?}",
+                file!(),
+                line!(),
+                quote,
+                quote_rest,
+            );
+        }
+
+        let base_amount = Self::balance_delta(&trading_acc_id, &base, base_before)?;
+        let quote_amount = Self::balance_delta(&trading_acc_id, &quote, quote_before)?;
+
+        if quote != EQD {
+            // EQD leg price is checked by matching, DOT leg is executed by market
+            let effective_price = quote_amount
+                .checked_div(&base_amount)
+                .ok_or(ArithmeticError::DivisionByZero)?;
+            let limit = eq_fixedu128_from_fixedi64(limit_price)
+                .ok_or(Error::<T>::OrderPriceShouldBePositive)?;
+            let price_satisfied = match side {
+                Buy => effective_price <= limit,
+                Sell => effective_price >= limit,
+            };
+            eq_ensure!(
+                price_satisfied,
+                Error::<T>::PairOrderPriceNotSatisfied,
+                target: "eq_dex",
+                "{}:{}. Effective price {:?} is worse than limit {:?}. Side: {:?}",
+                file!(),
+                line!(),
+                effective_price,
+                limit,
+                side,
+            );
+        }
+
+        let margin_state = T::MarginCallManager::check_margin(&trading_acc_id)?;
+        eq_ensure!(
+            margin_state == MarginState::Good,
+            Error::<T>::BadMargin,
+            target: "eq_dex",
+            "{}:{}. Account should be with good margin. Account : {:?} margin_state {:?}.",
+            file!(),
+            line!(),
+            trading_acc_id,
+            margin_state,
+        );
+
+        Self::deposit_event(Event::PairOrderExecuted(
+            trading_acc_id,
+            base,
+            quote,
+            side,
+            base_amount,
+            quote_amount,
+        ));
+
+        Ok(())
+    }
+
     /// Only for offchain worker!
     pub(self) fn iter_orders_by_asset(
         asset: &Asset,
@@ -1335,55 +2862,139 @@ impl<T: Config> Pallet<T> {
         Box::new(OrdersByAssetAndChunkKey::<T>::iter())
     }
 
-    fn do_update_asset_corridor(asset: Asset, new_corridor_value: u32) {
-        // TODO: delete all orders / push orders again
-        let old_corridor_value = <ChunkCorridorByAsset<T>>::get(asset);
-        if old_corridor_value != new_corridor_value {
-            <ChunkCorridorByAsset<T>>::insert(asset, new_corridor_value);
-        }
-    }
-}
+    /// Corridor of `asset` in chunks derived from its volatility by `AutoCorridor` parameters
+    pub fn auto_corridor_value(asset: Asset) -> Result<u32, DispatchError> {
+        let params = AutoCorridorByAsset::<T>::get(asset).ok_or(Error::<T>::AutoCorridorNotSet)?;
+        let volatility = T::FinancialStorage::get_per_asset_metrics(&asset)
+            .map(|metrics| i64f64_to_fixedi64(metrics.volatility))
+            .ok_or(Error::<T>::NoVolatilityMetrics)?;
+        let asset_data = T::AssetGetter::get_asset_data(&asset)?;
+        let price: FixedI64 = T::PriceGetter::get_price(&asset)?;
 
-impl<T: Config> OrderManagement for Pallet<T> {
-    type AccountId = T::AccountId;
+        let chunk_width = asset_data
+            .price_step
+            .checked_mul(&FixedI64::saturating_from_integer(T::PriceStepCount::get()))
+            .ok_or(ArithmeticError::Overflow)?;
+        let width = params
+            .k
+            .checked_mul(&volatility)
+            .and_then(|w| w.checked_mul(&price))
+            .ok_or(ArithmeticError::Overflow)?;
+        let chunks = width
+            .checked_div(&chunk_width)
+            .ok_or(ArithmeticError::DivisionByZero)?
+            .into_inner()
+            .max(0);
 
-    fn create_order(
-        who: Self::AccountId,
+        // round up to whole chunks
+        let accuracy = FixedI64::accuracy();
+        let corridor = chunks / accuracy + if chunks % accuracy > 0 { 1 } else { 0 };
+        let corridor: u32 = corridor.try_into().unwrap_or(u32::MAX);
+
+        Ok(corridor.max(params.min).min(params.max))
+    }
+
+    /// Matches order with the order book and places its rest as limit order.
+    /// Only `display_amount` of the rest is placed for iceberg order, the rest is hidden.
+    fn do_create_order(
+        who: T::AccountId,
         asset: Asset,
         order_type: OrderType,
         side: OrderSide,
         amount: EqFixedU128,
+        display_amount: Option<EqFixedU128>,
     ) -> DispatchResultWithPostInfo {
         let asset_data = T::AssetGetter::get_asset_data(&asset)?;
+        Self::ensure_not_banned(&who)?;
         let trading_acc_id = T::SubaccountsManager::get_subaccount_id(&who, &SubAccType::Trader)
             .ok_or(Error::<T>::AccountIsNotTrader)?;
 
         Self::ensure_dex_is_enabled(&asset_data)?;
         Self::ensure_amount_satisfies_lot(&who, &asset_data, &amount)?;
+        if let (Some(_), Limit { price, .. }) = (display_amount, order_type) {
+            // only displayed part is checked on placement, hidden part should be covered too
+            let order_changes = &[OrderChange {
+                asset,
+                price,
+                amount,
+                side,
+            }];
+            let (margin_state, _) = T::MarginCallManager::check_margin_with_change(
+                &trading_acc_id,
+                &[],
+                order_changes,
+            )?;
+            eq_ensure!(
+                margin_state == MarginState::Good,
+                Error::<T>::BadMargin,
+                "{}:{}. Account should be with good margin. Account : {:?} margin_state {:?}.",
+                file!(),
+                line!(),
+                trading_acc_id,
+                margin_state,
+            );
+        }
+
+        // fills refer to the taker order by the id it gets if it rests in the book,
+        // id is taken before matching as refills of iceberg makers take ids too
+        let order_id = Self::get_order_id();
 
-        match (
-            order_type,
-            Self::try_match(&trading_acc_id, side, order_type, amount, &asset)?,
-        ) {
+        // orders of batch auction markets wait for clearing
+        let rest = if BatchAuctionPeriod::<T>::contains_key(asset) {
+            eq_ensure!(
+                order_type != Market,
+                Error::<T>::MarketOrderInBatchAuction,
+                target: "eq_dex",
+                "{}:{}. Market orders are not allowed in batch auction. Asset: {:?}",
+                file!(),
+                line!(),
+                asset,
+            );
+            TakerRest::Unmatched(amount)
+        } else {
+            Self::try_match(
+                &trading_acc_id,
+                order_id,
+                side,
+                order_type,
+                amount,
+                &asset,
+                Self::self_trade_prevention(&who),
+            )?
+        };
+
+        match (order_type, rest) {
             (
                 Limit {
                     price,
                     expiration_time,
                 },
-                Some(amount),
+                TakerRest::Unmatched(amount),
             ) => {
-                Self::create_limit_order(
+                let display_amount = display_amount.map_or(amount, |d| d.min(amount));
+                Self::create_limit_order_with_id(
+                    order_id,
                     trading_acc_id,
                     asset,
                     price,
                     side,
-                    amount,
+                    display_amount,
                     expiration_time,
                     &asset_data,
                 )?;
+                if display_amount < amount {
+                    IcebergOrders::<T>::insert(
+                        asset,
+                        order_id,
+                        IcebergOrder {
+                            display_amount,
+                            hidden_amount: amount - display_amount,
+                        },
+                    );
+                }
             }
-            // order is fully matched, we don't need to do anything
-            (Limit { .. }, None) => {}
+            // order is fully matched or its rest is cancelled, we don't need to do anything
+            (Limit { .. }, _) => {}
             // market order is only for matching
             (Market, _) => {}
         }
@@ -1391,6 +3002,97 @@ impl<T: Config> OrderManagement for Pallet<T> {
         Ok(().into())
     }
 
+    /// Places next displayed part of iceberg order after its `filled` order is matched.
+    /// Refill is a new limit order, so it is checked for corridor and margin. Hidden part
+    /// is removed if refill fails, matching of the filled order is not reverted.
+    fn refill_iceberg_order(asset: Asset, filled: &Order<T::AccountId>, iceberg: IcebergOrder) {
+        let amount = iceberg.display_amount.min(iceberg.hidden_amount);
+        let result = frame_support::storage::with_transaction(
+            || -> TransactionOutcome<Result<OrderId, DispatchError>> {
+                let order_id = Self::get_order_id();
+                let result = T::AssetGetter::get_asset_data(&asset).and_then(|asset_data| {
+                    Self::create_limit_order_with_id(
+                        order_id,
+                        filled.account_id.clone(),
+                        asset,
+                        filled.price,
+                        filled.side,
+                        amount,
+                        filled.expiration_time,
+                        &asset_data,
+                    )
+                    .map_err(|e| e.error)
+                });
+                match result {
+                    Ok(_) => TransactionOutcome::Commit(Ok(order_id)),
+                    Err(err) => TransactionOutcome::Rollback(Err(err)),
+                }
+            },
+        );
+
+        match result {
+            Ok(order_id) => {
+                let hidden_amount = iceberg.hidden_amount - amount;
+                if !hidden_amount.is_zero() {
+                    IcebergOrders::<T>::insert(
+                        asset,
+                        order_id,
+                        IcebergOrder {
+                            hidden_amount,
+                            ..iceberg
+                        },
+                    );
+                }
+                Self::deposit_event(Event::IcebergOrderRefilled(
+                    filled.account_id.clone(),
+                    asset,
+                    filled.order_id,
+                    order_id,
+                    hidden_amount,
+                ));
+            }
+            Err(err) => {
+                log::error!(
+                    target: "eq_dex",
+                    "{}:{}. Iceberg order refill failed. Asset: {:?}, order_id: {:?}, error: {:?}",
+                    file!(),
+                    line!(),
+                    asset,
+                    filled.order_id,
+                    err
+                );
+                Self::deposit_event(Event::IcebergOrderRemoved(
+                    filled.account_id.clone(),
+                    asset,
+                    filled.order_id,
+                    iceberg.hidden_amount,
+                ));
+            }
+        }
+    }
+
+    fn do_update_asset_corridor(asset: Asset, new_corridor_value: u32) {
+        // TODO: delete all orders / push orders again
+        let old_corridor_value = <ChunkCorridorByAsset<T>>::get(asset);
+        if old_corridor_value != new_corridor_value {
+            <ChunkCorridorByAsset<T>>::insert(asset, new_corridor_value);
+        }
+    }
+}
+
+impl<T: Config> OrderManagement for Pallet<T> {
+    type AccountId = T::AccountId;
+
+    fn create_order(
+        who: Self::AccountId,
+        asset: Asset,
+        order_type: OrderType,
+        side: OrderSide,
+        amount: EqFixedU128,
+    ) -> DispatchResultWithPostInfo {
+        Self::do_create_order(who, asset, order_type, side, amount, None)
+    }
+
     fn delete_order(
         asset: &Asset,
         order_id: OrderId,
@@ -1463,6 +3165,11 @@ impl<T: Config> OrderManagement for Pallet<T> {
                             .map_err(|_| Error::<T>::InconsistentStorage)?;
 
                         let removed = orders.remove(index);
+                        BatchOrdersCount::<T>::mutate_exists(asset, |maybe_count| {
+                            if let Some(count) = maybe_count {
+                                *count = count.saturating_sub(1);
+                            }
+                        });
 
                         let _ = BestPriceByAsset::<T>::try_mutate(asset, |b| match removed.side {
                             Sell if Some(removed.price) == b.ask => {
@@ -1512,12 +3219,30 @@ impl<T: Config> OrderManagement for Pallet<T> {
         )?;
 
         Self::deposit_event(Event::OrderDeleted(
-            order.account_id,
+            order.account_id.clone(),
             order_id,
             *asset,
             reason,
+            order.amount,
+            chunk_key,
+            Self::next_event_sequence(*asset),
         ));
 
+        Self::settle_order_deposit(asset, &order, reason)?;
+
+        if let Some(iceberg) = IcebergOrders::<T>::take(asset, order_id) {
+            if reason == DeleteOrderReason::Match {
+                Self::refill_iceberg_order(*asset, &order, iceberg);
+            } else {
+                Self::deposit_event(Event::IcebergOrderRemoved(
+                    order.account_id,
+                    *asset,
+                    order_id,
+                    iceberg.hidden_amount,
+                ));
+            }
+        }
+
         Ok(().into())
     }
 
@@ -1550,3 +3275,11 @@ impl<T: Config> OrderAggregates<T::AccountId> for Pallet<T> {
             .unwrap_or_default()
     }
 }
+
+impl<T: Config> MarkPriceGetter for Pallet<T> {
+    fn mark_price(asset: &Asset) -> Option<FixedI64> {
+        let best_price = BestPriceByAsset::<T>::get(asset);
+        let (ask, bid) = (best_price.ask?, best_price.bid?);
+        (ask + bid).checked_div(&FixedI64::from(2))
+    }
+}
@@ -28,7 +28,7 @@ use eq_primitives::{
     asset,
     asset::{Asset, AssetData, DAI, EQ},
     financial_storage::FinancialStorage,
-    subaccount::{SubAccType, SubaccountsManager},
+    subaccount::SubAccType,
     Aggregates, BalanceChange, EqBuyout, MarginCallManager, MarginState, OrderChange,
     SignedBalance, TotalAggregates, UpdateTimeManager, UserGroup,
 };
@@ -50,6 +50,8 @@ use sp_std::ops::Range;
 use std::cell::RefCell;
 use std::marker::PhantomData;
 use substrate_fixed::types::I64F64;
+use test_utils::builder::{AssetConfig, ExtBuilder};
+pub use test_utils::mock::subaccounts::SubaccountsManagerMock;
 
 type AccountId = u64;
 pub(crate) type Balance = eq_primitives::balance::Balance;
@@ -85,79 +87,6 @@ frame_support::construct_runtime!(
     }
 );
 
-pub struct SubaccountsManagerMock;
-impl SubaccountsManager<AccountId> for SubaccountsManagerMock {
-    fn create_subaccount_inner(
-        who: &AccountId,
-        subacc_type: &SubAccType,
-    ) -> Result<AccountId, DispatchError> {
-        let subaccount_id = who + 100;
-        SUBACCOUNTS.with(|v| {
-            let mut vec = v.borrow_mut();
-            vec.push((*who, *subacc_type, subaccount_id));
-        });
-
-        Ok(subaccount_id)
-    }
-    fn delete_subaccount_inner(
-        who: &AccountId,
-        subacc_type: &SubAccType,
-    ) -> Result<AccountId, DispatchError> {
-        SUBACCOUNTS.with(|v| {
-            let mut vec = v.borrow_mut();
-            let maybe_position = vec
-                .iter()
-                .position(|(acc_id, sub_type, _)| *acc_id == *who && *sub_type == *subacc_type);
-            if let Some(pos) = maybe_position {
-                vec.remove(pos);
-            }
-        });
-
-        Ok(0)
-    }
-    fn has_subaccount(who: &AccountId, _subacc_type: &SubAccType) -> bool {
-        match &who {
-            0u64 => false,
-            _ => true,
-        }
-    }
-    fn get_subaccount_id(who: &AccountId, subacc_type: &SubAccType) -> Option<AccountId> {
-        let mut subaccount_id: Option<AccountId> = None;
-        SUBACCOUNTS.with(|v| {
-            let maybe = v
-                .borrow()
-                .iter()
-                .filter(|(ai, st, _)| *ai == *who && *st == *subacc_type)
-                .map(|(_, _, id)| id.clone())
-                .next();
-            if let Some(id) = maybe {
-                subaccount_id = Some(id);
-            };
-        });
-
-        subaccount_id
-    }
-    fn is_subaccount(_who: &AccountId, _subacc_id: &AccountId) -> bool {
-        false
-    }
-    fn get_owner_id(subaccount: &AccountId) -> Option<(AccountId, SubAccType)> {
-        let mut result: Option<(AccountId, SubAccType)> = None;
-        SUBACCOUNTS.with(|v| {
-            let maybe = v
-                .borrow()
-                .iter()
-                .filter(|(_, _, sai)| *sai == *subaccount)
-                .map(|(ai, st, _)| (ai.clone(), *st))
-                .next();
-            result = maybe;
-        });
-
-        result
-    }
-    fn get_subaccounts_amount(_who: &AccountId) -> usize {
-        0
-    }
-}
 thread_local! {
     pub static ASSET_DATA: RefCell<AssetData<Asset>> = RefCell::new(AssetData {
         asset_type: AssetType::Physical,
@@ -176,12 +105,6 @@ thread_local! {
 
     pub static ORDER_AGGREGATES: RefCell<VecMap<Asset, OrderAggregateBySide>> = Default::default();
 
-    pub static SUBACCOUNTS: RefCell<Vec<(AccountId, SubAccType, AccountId)>> = RefCell::new(vec![
-        (1, SubAccType::Trader, 101),
-        (2, SubAccType::Trader, 102),
-        (FAIL_ACC, SubAccType::Trader, FAIL_SUBACC),
-    ]);
-
     pub static MARGIN_STATE: RefCell<Vec<(AccountId, (MarginState, bool))>> = Default::default();
 }
 
@@ -470,6 +393,10 @@ impl Financial for Pallet<Test> {
 }
 
 parameter_types! {
+    pub const MaxNotifications: u32 = 16;
+    pub const PriceRestorePeriod: u64 = 10;
+    pub const MaxPendingLiquidations: u32 = 10;
+    pub const MaxPriorityMarginCallsPerBlock: u32 = 10;
     pub const Period: u64 = 1;
     pub const Offset: u64 = 0;
     pub const BlockHashCount: u64 = 250;
@@ -480,6 +407,7 @@ parameter_types! {
     pub const MinSurplus: Balance = 1 * 1000_000_000; // 1 usd
     pub const MinTempBailsman: Balance = 20 * 1000_000_000; // 20 usd
     pub const UnsignedPriority: u64 = 100;
+    pub const MaxUnsignedPerBlock: u32 = 100;
     pub const DepositEq: Balance = 0;
     pub const TreasuryModuleId: PalletId = PalletId(*b"eq/trsry");
     pub const ExistentialDeposit: Balance = 1;
@@ -559,7 +487,7 @@ impl eq_primitives::balance::BalanceChecker<Balance, AccountId, Balances, Subacc
         who: &AccountId,
         change: &Vec<(asset::Asset, SignedBalance<Balance>)>,
         _: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), eq_primitives::balance::BalanceCheckError<Balance>> {
         let all_positive = change.iter().all(|(_, sb)| match sb {
             SignedBalance::Positive(_) => true,
             SignedBalance::Negative(_) => false,
@@ -568,7 +496,10 @@ impl eq_primitives::balance::BalanceChecker<Balance, AccountId, Balances, Subacc
             return Ok(());
         }
         match who {
-            &FAIL_ACC | &FAIL_SUBACC => Err(DispatchError::Other("Expected error")),
+            &FAIL_ACC | &FAIL_SUBACC => Err(eq_primitives::balance::BalanceCheckError::new(
+                eq_primitives::balance::BalanceCheckerKind::Other,
+                DispatchError::Other("Expected error"),
+            )),
             _ => Ok(()),
         }
     }
@@ -597,6 +528,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -633,6 +568,7 @@ impl eq_rate::Config for Test {
     type BalanceGetter = eq_balances::Pallet<Test>;
     type BalanceRemover = eq_balances::Pallet<Test>;
     type UnsignedPriority = UnsignedPriority;
+    type MaxUnsignedPerBlock = MaxUnsignedPerBlock;
     type MinSurplus = MinSurplus;
     type MinTempBailsman = MinTempBailsman;
     type UnixTime = ModuleTimestamp;
@@ -661,12 +597,16 @@ impl eq_rate::Config for Test {
     type LendingModuleId = LendingModuleId;
     type LendingPoolManager = ();
     type LendingAssetRemoval = ();
+    type InterestIncomeReceiver = ();
+    type TreasuryAccounting = ();
     type RedistributeWeightInfo = ();
 }
 
 parameter_types! {
     pub const MaxBailsmenToDistribute: u32 = 1;
     pub const QueueLengthWeightConstant: u32 = 5;
+    pub const RedistributionWeightBudget: frame_support::weights::Weight = frame_support::weights::Weight::zero();
+    pub const MaxPriorityRedistributionsPerBlock: u32 = 10;
 }
 
 impl eq_bailsman::Config for Test {
@@ -680,6 +620,7 @@ impl eq_bailsman::Config for Test {
     type MinTempBalanceUsd = MinTempBalanceUsd;
     type UnixTime = ModuleTimestamp;
     type PalletId = BailsmanModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
     type Aggregates = AggregatesMock;
     type WeightInfo = ();
     type MarginCallManager = MarginCallManagerMock;
@@ -690,6 +631,8 @@ impl eq_bailsman::Config for Test {
     type UnsignedPriority = UnsignedPriority;
     type ValidatorOffchainBatcher = ();
     type QueueLengthWeightConstant = QueueLengthWeightConstant;
+    type RedistributionWeightBudget = RedistributionWeightBudget;
+    type MaxPriorityRedistributionsPerBlock = MaxPriorityRedistributionsPerBlock;
 }
 
 impl eq_assets::Config for Test {
@@ -705,6 +648,12 @@ parameter_types! {
     pub MaintenanceMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(25, 1000);
     pub CriticalMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(5, 1000);
     pub MaintenancePeriod: u64 = 86_400u64;
+    pub RiskTierMarginMultipliers: eq_primitives::asset::RiskTierParams<EqFixedU128> =
+        eq_primitives::asset::RiskTierParams {
+            tier1: EqFixedU128::one(),
+            tier2: EqFixedU128::one(),
+            tier3: EqFixedU128::one(),
+        };
 }
 
 impl eq_margin_call::Config for Test {
@@ -719,15 +668,69 @@ impl eq_margin_call::Config for Test {
     type CriticalMargin = CriticalMargin;
     type MaintenancePeriod = MaintenancePeriod;
     type OrderAggregates = OrderAggregatesMock;
+    type DerivativePositions = ();
     type AssetGetter = AssetGetterMock;
     type SubaccountsManager = SubaccountsManagerMock;
+    type MaxNotifications = MaxNotifications;
     type WeightInfo = ();
+    type LendingIsolation = ();
+    type RiskTierMarginMultipliers = RiskTierMarginMultipliers;
+    type EqCurrency = eq_balances::Pallet<Test>;
+    type TreasuryModuleId = TreasuryModuleId;
+    type CompensationOrigin = EnsureRoot<AccountId>;
+    type PriceRestorePeriod = PriceRestorePeriod;
+    type MaxPendingLiquidations = MaxPendingLiquidations;
+    type KeeperOrigin = frame_system::EnsureNever<AccountId>;
+    type MaxPriorityMarginCallsPerBlock = MaxPriorityMarginCallsPerBlock;
 }
 
 parameter_types! {
     pub const PriceStepCount: u32 = 5;
     pub const PenaltyFee: Balance = 5_000_000_000;
     pub const DexUnsignedPriority: u64 = 100;
+    pub const CorridorUpdatePeriod: u64 = 10;
+    pub const MaxBatchOrders: u32 = 6;
+    pub const MaxBatchAuctionMarkets: u32 = 2;
+    pub StakerDiscounts: Vec<(Balance, Percent)> = vec![
+        (100 * ONE_TOKEN, Percent::from_percent(10)),
+        (1_000 * ONE_TOKEN, Percent::from_percent(50)),
+    ];
+}
+
+thread_local! {
+    static STAKED: RefCell<Vec<(AccountId, Balance)>> = RefCell::new(Vec::new());
+    static ORDER_DEPOSIT: RefCell<Balance> = RefCell::new(0);
+}
+
+pub fn set_order_deposit(deposit: Balance) {
+    ORDER_DEPOSIT.with(|v| *v.borrow_mut() = deposit);
+}
+
+pub struct OrderDepositMock;
+impl Get<Balance> for OrderDepositMock {
+    fn get() -> Balance {
+        ORDER_DEPOSIT.with(|v| *v.borrow())
+    }
+}
+
+pub fn set_staked(who: AccountId, amount: Balance) {
+    STAKED.with(|v| {
+        let mut staked = v.borrow_mut();
+        staked.retain(|(acc, _)| *acc != who);
+        staked.push((who, amount));
+    });
+}
+
+pub struct StakedBalanceMock;
+impl StakedBalance<AccountId, Balance> for StakedBalanceMock {
+    fn staked_balance(who: &AccountId) -> Balance {
+        STAKED.with(|v| {
+            v.borrow()
+                .iter()
+                .find(|(acc, _)| acc == who)
+                .map_or(0, |(_, amount)| *amount)
+        })
+    }
 }
 
 impl Config for Test {
@@ -737,8 +740,16 @@ impl Config for Test {
     type PriceStepCount = PriceStepCount;
     type PenaltyFee = PenaltyFee;
     type DexUnsignedPriority = DexUnsignedPriority;
+    type CorridorUpdatePeriod = CorridorUpdatePeriod;
     type WeightInfo = ();
+    type RiskNotifier = EqMarginCall;
     type ValidatorOffchainBatcher = eq_rate::Pallet<Test>;
+    type StakedBalance = StakedBalanceMock;
+    type StakerDiscounts = StakerDiscounts;
+    type OrderDeposit = OrderDepositMock;
+    type TreasuryAccounting = ();
+    type MaxBatchOrders = MaxBatchOrders;
+    type MaxBatchAuctionMarkets = MaxBatchAuctionMarkets;
 }
 
 pub fn all_orders(asset: Asset, expected_side: OrderSide) -> Vec<Order<AccountId>> {
@@ -750,122 +761,32 @@ pub fn all_orders(asset: Asset, expected_side: OrderSide) -> Vec<Order<AccountId
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    OracleMock::init(vec![
-        (asset::CRV, FixedI64::saturating_from_integer(10000)),
-        (asset::BTC, FixedI64::saturating_from_integer(10000)),
-        (asset::EOS, FixedI64::saturating_from_integer(3)),
-        (asset::ETH, FixedI64::saturating_from_integer(250)),
-        (asset::EQD, FixedI64::saturating_from_integer(1)),
-    ]);
+    let builder = ExtBuilder::default()
+        .asset(AssetConfig::new(asset::EQD, AssetType::Synthetic, 1).price(1))
+        .asset(AssetConfig::new(asset::BTC, AssetType::Physical, 2).price(10_000))
+        .asset(AssetConfig::new(asset::ETH, AssetType::Physical, 3).price(250))
+        .asset(AssetConfig::new(asset::DOT, AssetType::Physical, 4))
+        .asset(AssetConfig::new(asset::CRV, AssetType::Physical, 5).price(10_000))
+        .asset(AssetConfig::new(asset::EOS, AssetType::Physical, 6).price(3))
+        .asset(AssetConfig::new(asset::EQ, AssetType::Native, u64::MAX))
+        .balance(
+            TreasuryModuleId::get().into_account_truncating(),
+            asset::EQD,
+            100 * ONE_TOKEN,
+        )
+        .subaccount(1, SubAccType::Trader, 101)
+        .subaccount(2, SubAccType::Trader, 102)
+        .subaccount(FAIL_ACC, SubAccType::Trader, FAIL_SUBACC);
+    builder.init_mocks();
 
     let mut r = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
         .unwrap();
 
     eq_assets::GenesisConfig::<Test> {
-            _runtime: PhantomData,
-            assets: // id, lot, price_step, maker_fee, taker_fee, debt_weight, buyout_priority
-            vec![
-                (
-                    asset::EQD.get_id(),
-                    EqFixedU128::from(0),
-                    FixedI64::from(0),
-                    Permill::zero(),
-                    Permill::zero(),
-                    vec![],
-                    Permill::from_rational(2u32, 5u32),
-                    1,
-                    AssetType::Synthetic,
-                    true,
-                    Percent::one(),
-                    Permill::one(),
-                ),
-                (
-                    asset::BTC.get_id(),
-                    EqFixedU128::from(0),
-                    FixedI64::from(0),
-                    Permill::zero(),
-                    Permill::zero(),
-                    vec![],
-                    Permill::from_rational(2u32, 5u32),
-                    2,
-                    AssetType::Physical,
-                    true,
-                    Percent::one(),
-                    Permill::one(),
-                ),
-                (
-                    asset::ETH.get_id(),
-                    EqFixedU128::from(0),
-                    FixedI64::from(0),
-                    Permill::zero(),
-                    Permill::zero(),
-                    vec![],
-                    Permill::from_rational(2u32, 5u32),
-                    3,
-                    AssetType::Physical,
-                    true,
-                    Percent::one(),
-                    Permill::one(),
-                ),
-                (
-                    asset::DOT.get_id(),
-                    EqFixedU128::from(0),
-                    FixedI64::from(0),
-                    Permill::zero(),
-                    Permill::zero(),
-                    vec![],
-                    Permill::from_rational(2u32, 5u32),
-                    4,
-                    AssetType::Physical,
-                    true,
-                    Percent::one(),
-                    Permill::one(),
-                ),
-                (
-                    asset::CRV.get_id(),
-                    EqFixedU128::from(0),
-                    FixedI64::from(0),
-                    Permill::zero(),
-                    Permill::zero(),
-                    vec![],
-                    Permill::from_rational(2u32, 5u32),
-                    5,
-                    AssetType::Physical,
-                    true,
-                    Percent::one(),
-                    Permill::one(),
-                ),
-                (
-                    asset::EOS.get_id(),
-                    EqFixedU128::from(0),
-                    FixedI64::from(0),
-                    Permill::zero(),
-                    Permill::zero(),
-                    vec![],
-                    Permill::from_rational(2u32, 5u32),
-                    6,
-                    AssetType::Physical,
-                    true,
-                    Percent::one(),
-                    Permill::one(),
-                ),
-                (
-                    asset::EQ.get_id(),
-                    EqFixedU128::from(0),
-                    FixedI64::from(0),
-                    Permill::zero(),
-                    Permill::zero(),
-                    vec![],
-                    Permill::zero(),
-                    u64::MAX,
-                    AssetType::Native,
-                    true,
-                    Percent::one(),
-                    Permill::one(),
-                ),
-            ],
-        }
+        _runtime: PhantomData,
+        assets: builder.assets_genesis(),
+    }
     .assimilate_storage(&mut r)
     .unwrap();
 
@@ -900,10 +821,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     .unwrap();
 
     eq_balances::GenesisConfig::<Test> {
-        balances: vec![(
-            TreasuryModuleId::get().into_account_truncating(),
-            vec![(100 * ONE_TOKEN, asset::EQD.get_id())],
-        )],
+        balances: builder.balances_genesis(),
         is_transfers_enabled: true,
         is_xcm_enabled: Some(XcmMode::Xcm(true)),
     }
@@ -22,10 +22,11 @@ use super::*;
 use crate::mock::*;
 use eq_primitives::asset::{AssetType, BTC};
 use eq_primitives::{
-    asset::{Asset, DAI, EQD, ETH},
+    asset::{Asset, DAI, DOT, EQ, EQD, ETH},
     balance::BalanceGetter,
     Aggregates, OrderAggregate, PriceSetter, SignedBalance, UserGroup,
 };
+use eq_utils::ONE_TOKEN;
 use frame_support::{assert_err, assert_noop, assert_ok, dispatch::DispatchError};
 use frame_system::RawOrigin;
 use sp_arithmetic::traits::{Bounded, CheckedAdd};
@@ -804,6 +805,94 @@ fn create_order() {
     });
 }
 
+#[test]
+fn create_and_delete_order_with_session_key() {
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let session_key = 777;
+        let borrower_id =
+            SubaccountsManagerMock::create_subaccount_inner(&account_id, &SubAccType::Trader)
+                .unwrap();
+
+        let asset = ETH;
+        let price = FixedI64::from(250);
+
+        assert_err!(
+            ModuleDex::create_order(
+                RuntimeOrigin::signed(session_key),
+                asset,
+                Limit {
+                    price,
+                    expiration_time: 100u64
+                },
+                Buy,
+                EqFixedU128::from(1),
+            ),
+            Error::<Test>::AccountIsNotTrader
+        );
+
+        SubaccountsManagerMock::add_session_key(session_key, account_id);
+        assert_ok!(ModuleDex::create_order(
+            RuntimeOrigin::signed(session_key),
+            asset,
+            Limit {
+                price,
+                expiration_time: 100u64
+            },
+            Buy,
+            EqFixedU128::from(1),
+        ));
+
+        let order_id = OrderIdCounter::<Test>::get();
+        let order = ModuleDex::find_order(&asset, order_id, price).unwrap();
+        assert_eq!(order.account_id, borrower_id);
+
+        assert_ok!(ModuleDex::delete_order_external(
+            RuntimeOrigin::signed(session_key),
+            asset,
+            order_id,
+            price
+        ));
+        assert!(ModuleDex::find_order(&asset, order_id, price).is_none());
+    });
+}
+
+#[test]
+fn session_key_resolves_to_master_in_iceberg_and_self_trade_prevention() {
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let session_key = 777;
+        let trader = trader_subaccount(&account_id);
+        SubaccountsManagerMock::add_session_key(session_key, account_id);
+
+        assert_ok!(ModuleDex::set_self_trade_prevention(
+            RuntimeOrigin::signed(session_key),
+            SelfTradePrevention::CancelNewest
+        ));
+        assert_eq!(
+            SelfTradePreventionByAccount::<Test>::get(&account_id),
+            SelfTradePrevention::CancelNewest
+        );
+        assert_eq!(
+            SelfTradePreventionByAccount::<Test>::get(&session_key),
+            SelfTradePrevention::default()
+        );
+
+        assert_ok!(ModuleDex::create_iceberg_order(
+            RuntimeOrigin::signed(session_key),
+            ETH,
+            FixedI64::from(250),
+            Buy,
+            EqFixedU128::from(5),
+            EqFixedU128::from(2),
+            100u64,
+        ));
+        let orders = ModuleDex::account_orders(&trader);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].1.amount, EqFixedU128::from(2));
+    });
+}
+
 #[test]
 fn create_order_when_orders_has_same_price_chunks_should_be_sorted_by_create_time() {
     new_test_ext().execute_with(|| {
@@ -2047,6 +2136,7 @@ fn two_orders_match_total_with_no_rest_taker_buy() {
         assert_eq!(
             ModuleDex::match_two_orders(
                 &taker,
+                0,
                 taker_amount,
                 Limit {
                     price: taker_price,
@@ -2154,6 +2244,7 @@ fn two_orders_match_total_with_no_rest_taker_sell() {
         assert_eq!(
             ModuleDex::match_two_orders(
                 &taker,
+                0,
                 taker_amount,
                 Limit {
                     price: taker_price,
@@ -2261,6 +2352,7 @@ fn two_orders_match_successful_with_taker_rest_taker_buy() {
         assert_eq!(
             ModuleDex::match_two_orders(
                 &taker,
+                0,
                 taker_amount,
                 Limit {
                     price: taker_price,
@@ -2371,6 +2463,7 @@ fn two_orders_match_successful_with_taker_rest_taker_sell() {
         assert_eq!(
             ModuleDex::match_two_orders(
                 &taker,
+                0,
                 taker_amount,
                 Limit {
                     price: taker_price,
@@ -2481,6 +2574,7 @@ fn two_orders_match_successful_with_maker_rest_taker_buy() {
         assert_eq!(
             ModuleDex::match_two_orders(
                 &taker,
+                0,
                 taker_amount,
                 Limit {
                     price: taker_price,
@@ -2598,6 +2692,7 @@ fn two_orders_match_successful_with_maker_rest_taker_sell() {
         assert_eq!(
             ModuleDex::match_two_orders(
                 &taker,
+                0,
                 taker_amount,
                 Limit {
                     price: taker_price,
@@ -3521,6 +3616,7 @@ fn two_orders_match_maker_sell_fail() {
         assert_eq!(
             ModuleDex::match_two_orders(
                 &taker,
+                0,
                 taker_amount,
                 Limit {
                     price: taker_price,
@@ -3584,6 +3680,7 @@ fn two_orders_match_maker_buy_fail() {
         assert_eq!(
             ModuleDex::match_two_orders(
                 &taker,
+                0,
                 taker_amount,
                 Limit {
                     price: taker_price,
@@ -3837,3 +3934,797 @@ fn charge_penalty_fee() {
         );
     });
 }
+
+#[test]
+fn account_orders() {
+    new_test_ext().execute_with(|| {
+        let asset = ETH;
+        let asset_data = AssetGetterMock::get_asset_data(&asset).expect("Asset exists");
+        let amount = EqFixedU128::from(1);
+        let expiration_time = 100u64;
+
+        for (who, price, side) in [(101u64, 250, Buy), (102u64, 251, Buy), (101u64, 280, Sell)] {
+            assert_ok!(ModuleDex::create_limit_order(
+                who,
+                asset,
+                FixedI64::from(price),
+                side,
+                amount,
+                expiration_time,
+                &asset_data
+            ));
+        }
+
+        let orders = ModuleDex::account_orders(&101);
+        assert_eq!(orders.len(), 2);
+        assert!(orders
+            .iter()
+            .all(|(a, order)| *a == asset && order.account_id == 101));
+        assert_eq!(
+            orders.iter().map(|(_, o)| o.price).collect::<Vec<_>>(),
+            vec![FixedI64::from(250), FixedI64::from(280)]
+        );
+
+        assert_eq!(ModuleDex::account_orders(&102).len(), 1);
+        assert!(ModuleDex::account_orders(&103).is_empty());
+    });
+}
+
+#[test]
+fn batch_auction_clears_crossing_orders_at_uniform_price() {
+    use frame_support::traits::OnInitialize;
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let asset = ETH;
+        let asset_data = AssetGetterMock::get_asset_data(&asset).expect("Asset exists");
+
+        assert_noop!(
+            ModuleDex::set_batch_auction(RuntimeOrigin::signed(1), asset, Some(3)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ModuleDex::set_batch_auction(RawOrigin::Root.into(), asset, Some(0)),
+            Error::<Test>::BatchAuctionPeriodShouldBePositive
+        );
+        assert_ok!(ModuleDex::set_batch_auction(
+            RawOrigin::Root.into(),
+            asset,
+            Some(3)
+        ));
+
+        for (who, side, price) in [
+            (1_u64, Sell, 250),
+            (2, Sell, 251),
+            (3, Buy, 252),
+            (4, Buy, 251),
+        ] {
+            let _ = SubaccountsManagerMock::create_subaccount_inner(&who, &SubAccType::Trader)
+                .expect("Create borrower subaccount");
+            assert_ok!(<ModuleDex as OrderManagement>::create_order(
+                who,
+                asset,
+                Limit {
+                    price: FixedI64::saturating_from_integer(price),
+                    expiration_time: 999_000_000_000
+                },
+                side,
+                EqFixedU128::from(1),
+            ));
+        }
+
+        assert_noop!(
+            <ModuleDex as OrderManagement>::create_order(
+                4,
+                asset,
+                Market,
+                Buy,
+                EqFixedU128::from(1),
+            ),
+            Error::<Test>::MarketOrderInBatchAuction
+        );
+
+        // crossing orders rest in the book until the end of the period
+        let chunk_key = ModuleDex::get_chunk_key(
+            FixedI64::saturating_from_integer(250),
+            asset_data.price_step,
+        )
+        .unwrap();
+        ModuleDex::on_initialize(2);
+        assert_eq!(
+            OrdersByAssetAndChunkKey::<Test>::get(asset, chunk_key).len(),
+            4
+        );
+
+        ModuleDex::on_initialize(3);
+        assert_eq!(
+            OrdersByAssetAndChunkKey::<Test>::get(asset, chunk_key).len(),
+            0
+        );
+        let clearing_price = FixedI64::saturating_from_integer(251);
+        assert!(System::events().iter().any(|r| r.event
+            == RuntimeEvent::EqDex(Event::BatchAuctionCleared(
+                asset,
+                clearing_price,
+                EqFixedU128::from(2)
+            ))));
+        assert_eq!(
+            System::events()
+                .iter()
+                .filter(|r| matches!(
+                    r.event,
+                    RuntimeEvent::EqDex(Event::BatchMatch(_, price, ..)) if price == clearing_price
+                ))
+                .count(),
+            2
+        );
+
+        assert_ok!(ModuleDex::set_batch_auction(
+            RawOrigin::Root.into(),
+            asset,
+            None
+        ));
+        assert_eq!(ModuleDex::batch_auction_period(asset), None);
+    });
+}
+
+#[test]
+fn batch_auction_bounds_resting_orders_and_markets() {
+    new_test_ext().execute_with(|| {
+        let asset = ETH;
+        let price = FixedI64::saturating_from_integer(250);
+        trader_subaccount(&1);
+        let order_ids = create_orders(&1, asset, Buy, &[price; 7]);
+
+        // resting orders are counted when market is switched to batch auction
+        assert_noop!(
+            ModuleDex::set_batch_auction(RawOrigin::Root.into(), asset, Some(3)),
+            Error::<Test>::TooManyBatchOrders
+        );
+        assert_ok!(ModuleDex::delete_order_external(
+            RuntimeOrigin::signed(1),
+            asset,
+            order_ids[0],
+            price
+        ));
+        assert_ok!(ModuleDex::set_batch_auction(
+            RawOrigin::Root.into(),
+            asset,
+            Some(3)
+        ));
+        assert_eq!(ModuleDex::batch_orders_count(asset), Some(6));
+
+        assert_err!(
+            <ModuleDex as OrderManagement>::create_order(
+                1,
+                asset,
+                Limit {
+                    price,
+                    expiration_time: 100
+                },
+                Buy,
+                EqFixedU128::from(1),
+            ),
+            Error::<Test>::TooManyBatchOrders
+        );
+        assert_ok!(ModuleDex::delete_order_external(
+            RuntimeOrigin::signed(1),
+            asset,
+            order_ids[1],
+            price
+        ));
+        assert_eq!(ModuleDex::batch_orders_count(asset), Some(5));
+        create_orders(&1, asset, Buy, &[price]);
+        assert_eq!(ModuleDex::batch_orders_count(asset), Some(6));
+
+        assert_ok!(ModuleDex::set_batch_auction(
+            RawOrigin::Root.into(),
+            BTC,
+            Some(3)
+        ));
+        assert_noop!(
+            ModuleDex::set_batch_auction(RawOrigin::Root.into(), DOT, Some(3)),
+            Error::<Test>::TooManyBatchAuctionMarkets
+        );
+        // period of batch auction market is changed without a free slot
+        assert_ok!(ModuleDex::set_batch_auction(
+            RawOrigin::Root.into(),
+            BTC,
+            Some(5)
+        ));
+
+        assert_ok!(ModuleDex::set_batch_auction(
+            RawOrigin::Root.into(),
+            asset,
+            None
+        ));
+        assert_eq!(ModuleDex::batch_orders_count(asset), None);
+    });
+}
+
+#[test]
+fn pair_order_settles_base_and_quote_legs() {
+    new_test_ext().execute_with(|| {
+        frame_system::Pallet::<Test>::inc_providers(&101);
+        frame_system::Pallet::<Test>::inc_providers(&102);
+
+        let base = ETH;
+        let quote = DOT;
+        OracleMock::set_price(1, quote, FixedI64::from(5)).unwrap();
+
+        assert_noop!(
+            ModuleDex::set_trading_pair(RuntimeOrigin::signed(1), base, quote, true),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ModuleDex::set_trading_pair(RawOrigin::Root.into(), base, BTC, true),
+            Error::<Test>::QuoteAssetNotSupported
+        );
+        assert_noop!(
+            ModuleDex::set_trading_pair(RawOrigin::Root.into(), EQD, quote, true),
+            Error::<Test>::WrongTradingPair
+        );
+        assert_err!(
+            ModuleDex::create_pair_order(
+                RuntimeOrigin::signed(2),
+                base,
+                quote,
+                Buy,
+                EqFixedU128::from(1),
+                FixedI64::from(51)
+            ),
+            Error::<Test>::TradingPairIsNotEnabled
+        );
+
+        assert_ok!(ModuleDex::set_trading_pair(
+            RawOrigin::Root.into(),
+            base,
+            quote,
+            true
+        ));
+        assert!(ModuleDex::trading_pairs(base, quote));
+
+        let expiration_time = 100u64;
+        for (asset, price, side, amount) in [(base, 250, Sell, 1), (quote, 5, Buy, 100)] {
+            assert_ok!(<ModuleDex as OrderManagement>::create_order(
+                1,
+                asset,
+                Limit {
+                    price: FixedI64::from(price),
+                    expiration_time
+                },
+                side,
+                EqFixedU128::from(amount),
+            ));
+        }
+
+        // 1 ETH costs 250 EQD and 0.25 EQD taker fee, covered by 51 DOT
+        assert_ok!(ModuleDex::create_pair_order(
+            RuntimeOrigin::signed(2),
+            base,
+            quote,
+            Buy,
+            EqFixedU128::from(1),
+            FixedI64::from(51)
+        ));
+
+        assert_eq!(
+            ModuleBalances::get_balance(&102, &base),
+            SignedBalance::Positive(ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&102, &quote),
+            SignedBalance::Negative(51 * ONE_TOKEN)
+        );
+        assert!(all_orders(base, Sell).is_empty());
+        assert_eq!(
+            all_orders(quote, Buy).last().map(|order| order.amount),
+            Some(EqFixedU128::from(49))
+        );
+        assert!(System::events().iter().any(|r| r.event
+            == RuntimeEvent::EqDex(Event::PairOrderExecuted(
+                102,
+                base,
+                quote,
+                Buy,
+                EqFixedU128::from(1),
+                EqFixedU128::from(51)
+            ))));
+
+        // no liquidity left for the base leg
+        assert_err!(
+            ModuleDex::create_pair_order(
+                RuntimeOrigin::signed(2),
+                base,
+                quote,
+                Buy,
+                EqFixedU128::from(1),
+                FixedI64::from(51)
+            ),
+            Error::<Test>::PairOrderNotFilled
+        );
+    });
+}
+
+#[test]
+fn set_auto_corridor() {
+    new_test_ext().execute_with(|| {
+        let asset = ETH;
+        let params = AutoCorridor {
+            k: FixedI64::saturating_from_integer(2),
+            min: 2,
+            max: 10,
+        };
+
+        assert_noop!(
+            ModuleDex::set_auto_corridor(RuntimeOrigin::signed(1), asset, Some(params)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ModuleDex::set_auto_corridor(
+                RawOrigin::Root.into(),
+                asset,
+                Some(AutoCorridor {
+                    k: FixedI64::zero(),
+                    ..params
+                })
+            ),
+            Error::<Test>::WrongAutoCorridor
+        );
+        assert_noop!(
+            ModuleDex::set_auto_corridor(
+                RawOrigin::Root.into(),
+                asset,
+                Some(AutoCorridor { min: 11, ..params })
+            ),
+            Error::<Test>::WrongAutoCorridor
+        );
+        assert_err!(
+            ModuleDex::auto_corridor_value(asset),
+            Error::<Test>::AutoCorridorNotSet
+        );
+
+        assert_ok!(ModuleDex::set_auto_corridor(
+            RawOrigin::Root.into(),
+            asset,
+            Some(params)
+        ));
+        assert_eq!(ModuleDex::auto_corridor(asset), Some(params));
+        // financial metrics are not available in mock
+        assert_err!(
+            ModuleDex::auto_corridor_value(asset),
+            Error::<Test>::NoVolatilityMetrics
+        );
+
+        assert_ok!(ModuleDex::set_auto_corridor(
+            RawOrigin::Root.into(),
+            asset,
+            None
+        ));
+        assert_eq!(ModuleDex::auto_corridor(asset), None);
+    });
+}
+
+#[test]
+fn self_trade_prevention_modes() {
+    new_test_ext().execute_with(|| {
+        let asset = ETH;
+        let price = FixedI64::saturating_from_integer(250);
+        let amount = EqFixedU128::from(1);
+        let limit = Limit {
+            price,
+            expiration_time: 999_000_000_000,
+        };
+        let maker = 1_u64;
+        let other_maker = 2_u64;
+        let trader = SubaccountsManagerMock::create_subaccount_inner(&maker, &SubAccType::Trader)
+            .expect("Create borrower subaccount");
+        let other_trader =
+            SubaccountsManagerMock::create_subaccount_inner(&other_maker, &SubAccType::Trader)
+                .expect("Create borrower subaccount");
+
+        // taker rest is cancelled, own maker order is kept
+        assert_ok!(ModuleDex::set_self_trade_prevention(
+            RuntimeOrigin::signed(maker),
+            SelfTradePrevention::CancelNewest
+        ));
+        create_orders(&maker, asset, Sell, &[price]);
+        assert_ok!(<ModuleDex as OrderManagement>::create_order(
+            maker, asset, limit, Buy, amount,
+        ));
+        let orders = ModuleDex::account_orders(&trader);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].1.side, Sell);
+
+        // own maker order is cancelled, taker is matched with the next one
+        assert_ok!(ModuleDex::set_self_trade_prevention(
+            RuntimeOrigin::signed(maker),
+            SelfTradePrevention::CancelOldest
+        ));
+        create_orders(&other_maker, asset, Sell, &[price]);
+        assert_ok!(<ModuleDex as OrderManagement>::create_order(
+            maker, asset, limit, Buy, amount,
+        ));
+        assert!(ModuleDex::account_orders(&trader).is_empty());
+        assert!(ModuleDex::account_orders(&other_trader).is_empty());
+
+        // both orders are decremented without exchange
+        assert_ok!(ModuleDex::set_self_trade_prevention(
+            RuntimeOrigin::signed(maker),
+            SelfTradePrevention::Decrement
+        ));
+        create_orders(&maker, asset, Sell, &[price]);
+        assert_ok!(<ModuleDex as OrderManagement>::create_order(
+            maker, asset, limit, Buy, amount,
+        ));
+        assert!(ModuleDex::account_orders(&trader).is_empty());
+        assert!(ModuleDex::asset_weight(&trader).is_empty());
+
+        assert_ok!(ModuleDex::set_self_trade_prevention(
+            RuntimeOrigin::signed(maker),
+            SelfTradePrevention::Allow
+        ));
+        assert!(!SelfTradePreventionByAccount::<Test>::contains_key(&maker));
+    });
+}
+
+#[test]
+fn trading_ban() {
+    new_test_ext().execute_with(|| {
+        let asset = ETH;
+        let who = 1_u64;
+        let _ = SubaccountsManagerMock::create_subaccount_inner(&who, &SubAccType::Trader)
+            .expect("Create borrower subaccount");
+        let order_type = Limit {
+            price: FixedI64::saturating_from_integer(250),
+            expiration_time: 999_000_000_000,
+        };
+
+        assert_noop!(
+            ModuleDex::set_trading_ban(RuntimeOrigin::signed(2), who, true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(ModuleDex::set_trading_ban(
+            RawOrigin::Root.into(),
+            who,
+            true
+        ));
+        assert!(ModuleDex::trading_banned(who));
+
+        assert_noop!(
+            <ModuleDex as OrderManagement>::create_order(
+                who,
+                asset,
+                order_type,
+                Buy,
+                EqFixedU128::from(1),
+            ),
+            Error::<Test>::AccountIsBanned
+        );
+
+        assert_ok!(ModuleDex::set_trading_ban(
+            RawOrigin::Root.into(),
+            who,
+            false
+        ));
+        assert_ok!(<ModuleDex as OrderManagement>::create_order(
+            who,
+            asset,
+            order_type,
+            Buy,
+            EqFixedU128::from(1),
+        ));
+    });
+}
+
+#[test]
+fn trusted_xcm_parachains() {
+    new_test_ext().execute_with(|| {
+        let para_id = 2000_u32;
+
+        assert_noop!(
+            ModuleDex::set_trusted_xcm_parachain(RuntimeOrigin::signed(2), para_id, true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(ModuleDex::set_trusted_xcm_parachain(
+            RawOrigin::Root.into(),
+            para_id,
+            true
+        ));
+        assert!(ModuleDex::trusted_xcm_parachain(para_id));
+        assert!(!ModuleDex::trusted_xcm_parachain(para_id + 1));
+
+        assert_ok!(ModuleDex::set_trusted_xcm_parachain(
+            RawOrigin::Root.into(),
+            para_id,
+            false
+        ));
+        assert!(!TrustedXcmParachains::<Test>::contains_key(para_id));
+    });
+}
+
+#[test]
+fn order_book_events_are_sequenced() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let asset = ETH;
+        let asset_data = AssetGetterMock::get_asset_data(&asset).expect("Asset exists");
+        let price = FixedI64::saturating_from_integer(250);
+        let chunk_key = ModuleDex::get_chunk_key(price, asset_data.price_step).unwrap();
+        let limit = Limit {
+            price,
+            expiration_time: 999_000_000_000,
+        };
+        let (maker, taker) = (1_u64, 2_u64);
+        for who in [maker, taker] {
+            let _ = SubaccountsManagerMock::create_subaccount_inner(&who, &SubAccType::Trader)
+                .expect("Create borrower subaccount");
+        }
+
+        assert_ok!(<ModuleDex as OrderManagement>::create_order(
+            maker,
+            asset,
+            limit,
+            Sell,
+            EqFixedU128::from(2),
+        ));
+        let maker_order_id = OrderIdCounter::<Test>::get();
+
+        // filled taker order doesn't rest in the book, but still has an id
+        assert_ok!(<ModuleDex as OrderManagement>::create_order(
+            taker,
+            asset,
+            limit,
+            Buy,
+            EqFixedU128::from(1),
+        ));
+        let filled_taker_id = OrderIdCounter::<Test>::get();
+        assert_eq!(filled_taker_id, maker_order_id + 1);
+
+        assert_ok!(<ModuleDex as OrderManagement>::create_order(
+            taker,
+            asset,
+            limit,
+            Buy,
+            EqFixedU128::from(2),
+        ));
+        let resting_taker_id = OrderIdCounter::<Test>::get();
+        assert!(ModuleDex::find_order(&asset, resting_taker_id, price).is_some());
+
+        let sequences: Vec<u64> = System::events()
+            .into_iter()
+            .filter_map(|r| match r.event {
+                RuntimeEvent::EqDex(Event::OrderCreated(.., sequence))
+                | RuntimeEvent::EqDex(Event::OrderDeleted(.., sequence))
+                | RuntimeEvent::EqDex(Event::Match(.., sequence)) => Some(sequence),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(sequences, vec![1, 2, 3, 4, 5]);
+        assert_eq!(ModuleDex::event_sequence(asset), 5);
+
+        assert!(System::events().iter().any(|r| matches!(
+            r.event,
+            RuntimeEvent::EqDex(Event::Match(_, _, _, maker_id, _, _, _, _, _, Sell, taker_id, maker_rest, chunk, 2))
+                if maker_id == maker_order_id
+                    && taker_id == filled_taker_id
+                    && maker_rest == EqFixedU128::from(1)
+                    && chunk == chunk_key
+        )));
+        assert!(System::events().iter().any(|r| matches!(
+            r.event,
+            RuntimeEvent::EqDex(Event::Match(_, taker_rest, _, maker_id, _, _, _, _, _, Sell, taker_id, maker_rest, _, 4))
+                if maker_id == maker_order_id
+                    && taker_id == resting_taker_id
+                    && taker_rest == EqFixedU128::from(1)
+                    && maker_rest.is_zero()
+        )));
+        assert!(System::events().iter().any(|r| matches!(
+            r.event,
+            RuntimeEvent::EqDex(Event::OrderCreated(_, order_id, _, amount, _, Buy, _, _, chunk, 5))
+                if order_id == resting_taker_id
+                    && amount == EqFixedU128::from(1)
+                    && chunk == chunk_key
+        )));
+    });
+}
+
+#[test]
+fn iceberg_order_refills_displayed_part() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let asset = ETH;
+        let price = FixedI64::saturating_from_integer(250);
+        let expiration_time = 999_000_000_000;
+        let (maker, taker) = (1_u64, 2_u64);
+        let trader = SubaccountsManagerMock::create_subaccount_inner(&maker, &SubAccType::Trader)
+            .expect("Create borrower subaccount");
+        let _ = SubaccountsManagerMock::create_subaccount_inner(&taker, &SubAccType::Trader)
+            .expect("Create borrower subaccount");
+
+        assert_noop!(
+            ModuleDex::create_iceberg_order(
+                RuntimeOrigin::signed(maker),
+                asset,
+                price,
+                Sell,
+                EqFixedU128::from(5),
+                EqFixedU128::from(5),
+                expiration_time,
+            ),
+            Error::<Test>::WrongIcebergDisplayAmount
+        );
+
+        assert_ok!(ModuleDex::create_iceberg_order(
+            RuntimeOrigin::signed(maker),
+            asset,
+            price,
+            Sell,
+            EqFixedU128::from(5),
+            EqFixedU128::from(2),
+            expiration_time,
+        ));
+        let iceberg_id = OrderIdCounter::<Test>::get();
+        let orders = ModuleDex::account_orders(&trader);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].1.amount, EqFixedU128::from(2));
+        assert_eq!(
+            ModuleDex::iceberg_order(asset, iceberg_id),
+            Some(IcebergOrder {
+                display_amount: EqFixedU128::from(2),
+                hidden_amount: EqFixedU128::from(3),
+            })
+        );
+
+        // displayed part is filled and refilled, taker is matched with the refill
+        assert_ok!(<ModuleDex as OrderManagement>::create_order(
+            taker,
+            asset,
+            Limit {
+                price,
+                expiration_time,
+            },
+            Buy,
+            EqFixedU128::from(3),
+        ));
+        let refill_id = OrderIdCounter::<Test>::get();
+        assert_eq!(refill_id, iceberg_id + 2);
+        assert_eq!(ModuleDex::iceberg_order(asset, iceberg_id), None);
+        let orders = ModuleDex::account_orders(&trader);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].1.order_id, refill_id);
+        assert_eq!(orders[0].1.amount, EqFixedU128::from(1));
+        assert_eq!(
+            ModuleDex::iceberg_order(asset, refill_id),
+            Some(IcebergOrder {
+                display_amount: EqFixedU128::from(2),
+                hidden_amount: EqFixedU128::from(1),
+            })
+        );
+        assert!(System::events().iter().any(|r| r.event
+            == RuntimeEvent::EqDex(Event::IcebergOrderRefilled(
+                trader,
+                asset,
+                iceberg_id,
+                refill_id,
+                EqFixedU128::from(1)
+            ))));
+
+        // hidden part is removed with its displayed order
+        assert_ok!(ModuleDex::delete_order_external(
+            RuntimeOrigin::signed(maker),
+            asset,
+            refill_id,
+            price
+        ));
+        assert_eq!(ModuleDex::iceberg_order(asset, refill_id), None);
+        assert!(ModuleDex::account_orders(&trader).is_empty());
+        System::assert_last_event(
+            Event::IcebergOrderRemoved(trader, asset, refill_id, EqFixedU128::from(1)).into(),
+        );
+    });
+}
+
+#[test]
+fn staker_discount_reduces_fees_and_penalties() {
+    new_test_ext().execute_with(|| {
+        let trader = 101;
+        assert_eq!(ModuleDex::staker_discount(&trader), Percent::zero());
+        assert_eq!(ModuleDex::penalty_fee(&trader), PenaltyFee::get());
+
+        // stake of the master account counts for its trader subaccount
+        set_staked(1, 100 * ONE_TOKEN);
+        assert_eq!(ModuleDex::staker_discount(&1), Percent::from_percent(10));
+        assert_eq!(
+            ModuleDex::staker_discount(&trader),
+            Percent::from_percent(10)
+        );
+        assert_eq!(
+            ModuleDex::penalty_fee(&trader),
+            PenaltyFee::get() - PenaltyFee::get() / 10
+        );
+
+        set_staked(1, 5_000 * ONE_TOKEN);
+        assert_eq!(
+            ModuleDex::staker_discount(&trader),
+            Percent::from_percent(50)
+        );
+        assert_eq!(ModuleDex::penalty_fee(&trader), PenaltyFee::get() / 2);
+
+        set_staked(1, 100 * ONE_TOKEN - 1);
+        assert_eq!(ModuleDex::staker_discount(&trader), Percent::zero());
+        assert_eq!(ModuleDex::staker_discount(&102), Percent::zero());
+    });
+}
+
+#[test]
+fn order_deposit_is_refunded_on_cancel_and_forfeited_on_penalty() {
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let borrower_id =
+            SubaccountsManagerMock::create_subaccount_inner(&account_id, &SubAccType::Trader)
+                .unwrap();
+        let deposit = ONE_TOKEN;
+        set_order_deposit(deposit);
+        assert_ok!(ModuleBalances::deposit_creating(
+            &borrower_id,
+            EQ,
+            2 * deposit,
+            true,
+            None
+        ));
+
+        let asset = ETH;
+        let price = FixedI64::from(250);
+        let create_order = || {
+            assert_ok!(ModuleDex::create_order(
+                RuntimeOrigin::signed(account_id),
+                asset,
+                Limit {
+                    price,
+                    expiration_time: 100u64
+                },
+                Buy,
+                EqFixedU128::from(1),
+            ));
+            OrderIdCounter::<Test>::get()
+        };
+
+        let order_id = create_order();
+        assert_eq!(ModuleDex::order_deposit(asset, order_id), Some(deposit));
+        assert_eq!(ModuleBalances::reserved_balance(&borrower_id, EQ), deposit);
+
+        assert_ok!(ModuleDex::delete_order_external(
+            RuntimeOrigin::signed(account_id),
+            asset,
+            order_id,
+            price
+        ));
+        assert_eq!(ModuleDex::order_deposit(asset, order_id), None);
+        assert_eq!(ModuleBalances::reserved_balance(&borrower_id, EQ), 0);
+        assert_eq!(
+            ModuleBalances::get_balance(&borrower_id, &EQ),
+            SignedBalance::Positive(2 * deposit)
+        );
+
+        let order_id = create_order();
+        let treasury: u64 = TreasuryModuleId::get().into_account_truncating();
+        assert_ok!(<ModuleDex as OrderManagement>::delete_order(
+            &asset,
+            order_id,
+            price,
+            DeleteOrderReason::OutOfCorridor,
+        ));
+        assert_eq!(ModuleBalances::reserved_balance(&borrower_id, EQ), 0);
+        assert_eq!(
+            ModuleBalances::get_balance(&borrower_id, &EQ),
+            SignedBalance::Positive(deposit)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury, &EQ),
+            SignedBalance::Positive(deposit)
+        );
+        System::assert_last_event(
+            Event::OrderDepositForfeited(borrower_id, asset, order_id, deposit).into(),
+        );
+    });
+}
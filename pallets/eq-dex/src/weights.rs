@@ -29,6 +29,16 @@ pub trait WeightInfo {
     fn delete_order_external() -> Weight;
     fn update_asset_corridor() -> Weight;
     fn validate_unsigned() -> Weight;
+    fn set_batch_auction(o: u32) -> Weight;
+    fn clear_batch_auction(o: u32) -> Weight;
+    fn set_trading_pair() -> Weight;
+    fn create_pair_order() -> Weight;
+    fn set_auto_corridor() -> Weight;
+    fn update_auto_corridor() -> Weight;
+    fn set_self_trade_prevention() -> Weight;
+    fn set_trading_ban() -> Weight;
+    fn set_trusted_xcm_parachain() -> Weight;
+    fn create_iceberg_order() -> Weight;
 }
 
 // for tests
@@ -51,4 +61,34 @@ impl crate::WeightInfo for () {
     fn validate_unsigned() -> Weight {
         Weight::zero()
     }
+    fn set_batch_auction(_o: u32) -> Weight {
+        Weight::zero()
+    }
+    fn clear_batch_auction(_o: u32) -> Weight {
+        Weight::zero()
+    }
+    fn set_trading_pair() -> Weight {
+        Weight::zero()
+    }
+    fn create_pair_order() -> Weight {
+        Weight::zero()
+    }
+    fn set_auto_corridor() -> Weight {
+        Weight::zero()
+    }
+    fn update_auto_corridor() -> Weight {
+        Weight::zero()
+    }
+    fn set_self_trade_prevention() -> Weight {
+        Weight::zero()
+    }
+    fn set_trading_ban() -> Weight {
+        Weight::zero()
+    }
+    fn set_trusted_xcm_parachain() -> Weight {
+        Weight::zero()
+    }
+    fn create_iceberg_order() -> Weight {
+        Weight::zero()
+    }
 }
@@ -116,4 +116,66 @@ benchmarks_instance! {
         let acc = account("target", 0, SEED);
         assert_eq!(<eq_balances::Pallet<T>>::free_balance(&acc, basic_asset), VESTED_BALANCE.into());
     }
+
+    approve_budget {
+        let m in 1 .. T::MaxMilestones::get();
+
+        let basic_asset = eq_assets::Pallet::<T>::get_main_asset();
+        let amount_to_deposit = BUDGET.try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap();
+        <eq_balances::Pallet<T>>::deposit_creating(&crate::Pallet::<T, I>::account_id(), basic_asset, amount_to_deposit, false, None)
+            .unwrap();
+
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+        let milestones: Vec<_> = (0..m)
+            .map(|i| (<T as crate::Config<I>>::Balance::from(TRANSFER), T::BlockNumber::from(i + 10)))
+            .collect();
+    }: _(RawOrigin::Root, beneficiary, basic_asset, milestones)
+    verify {
+        assert!(crate::Budgets::<T, I>::contains_key(0));
+    }
+
+    release_milestone {
+        let basic_asset = eq_assets::Pallet::<T>::get_main_asset();
+        let amount_to_deposit = BUDGET.try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap();
+        <eq_balances::Pallet<T>>::deposit_creating(&crate::Pallet::<T, I>::account_id(), basic_asset, amount_to_deposit, false, None)
+            .unwrap();
+
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+        crate::Pallet::<T, I>::approve_budget(
+            RawOrigin::Root.into(),
+            beneficiary.clone(),
+            basic_asset,
+            vec![(<T as crate::Config<I>>::Balance::from(TRANSFER), 10u32.into()), (<T as crate::Config<I>>::Balance::from(TRANSFER), 10u32.into())],
+        ).unwrap();
+    }: _(RawOrigin::Root, 0, 0)
+    verify {
+        assert_eq!(<eq_balances::Pallet<T>>::free_balance(&beneficiary, basic_asset), TRANSFER.into());
+    }
+
+    claw_back_milestone {
+        let basic_asset = eq_assets::Pallet::<T>::get_main_asset();
+        let amount_to_deposit = BUDGET.try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap();
+        <eq_balances::Pallet<T>>::deposit_creating(&crate::Pallet::<T, I>::account_id(), basic_asset, amount_to_deposit, false, None)
+            .unwrap();
+
+        let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+        crate::Pallet::<T, I>::approve_budget(
+            RawOrigin::Root.into(),
+            beneficiary,
+            basic_asset,
+            vec![(<T as crate::Config<I>>::Balance::from(TRANSFER), 10u32.into()), (<T as crate::Config<I>>::Balance::from(TRANSFER), 10u32.into())],
+        ).unwrap();
+        frame_system::Pallet::<T>::set_block_number(11u32.into());
+
+        let caller: T::AccountId = account("caller", 0, SEED);
+    }: _(RawOrigin::Signed(caller), 0, 0)
+    verify {
+        assert!(crate::Budgets::<T, I>::contains_key(0));
+    }
 }
@@ -23,6 +23,10 @@
 //! and Liquidity Farming groups are all represented by instances of the Distribution pallet with preset initial balances of EQ tokens.
 //!
 //! Distribution pallet allows for transfer and vested_transfer of assets from its balance to specified accounts.
+//!
+//! Spending proposals: `ManagementOrigin` approves a budget for a beneficiary split into milestones.
+//! The whole budget moves to an escrow sub-account of the pallet, every milestone is released to
+//! the beneficiary by `MilestoneOrigin` before its deadline or clawed back to the pallet account by anyone after it.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
@@ -36,11 +40,14 @@ pub mod weights;
 
 use core::convert::{TryFrom, TryInto};
 use eq_primitives::vestings::EqVestingSchedule;
+use eq_primitives::{balance::EqCurrency, TransferReason};
 use eq_utils::eq_ensure;
 use frame_support::traits::{ExistenceRequirement, Get};
 use frame_support::PalletId;
 use frame_system::pallet_prelude::OriginFor;
-use sp_runtime::traits::{AccountIdConversion, AtLeast32BitUnsigned, Zero};
+use sp_runtime::traits::{AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, Zero};
+use sp_runtime::{ArithmeticError, DispatchError};
+use sp_std::vec::Vec;
 
 pub use pallet::*;
 pub use weights::WeightInfo;
@@ -54,6 +61,7 @@ pub mod pallet {
     use eq_primitives::TransferReason;
     use frame_support::pallet_prelude::*;
     use frame_support::traits::EitherOfDiverse;
+    use frame_support::{CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
     use frame_system::pallet_prelude::*;
     // use frame_system::EnsureRoot;
 
@@ -76,6 +84,7 @@ pub mod pallet {
             + Default
             + Copy
             + MaybeSerializeDeserialize
+            + MaxEncodedLen
             + TryFrom<eq_primitives::balance::Balance>
             + Into<eq_primitives::balance::Balance>;
         type ManagementOrigin: EnsureOrigin<Self::RuntimeOrigin>;
@@ -89,12 +98,80 @@ pub mod pallet {
         type EqCurrency: EqCurrency<Self::AccountId, Self::Balance>;
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self, I>>
+            + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Confirms budget milestones
+        type MilestoneOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Max number of milestones in a budget
+        #[pallet::constant]
+        type MaxMilestones: Get<u32>;
+    }
+
+    /// Status of a budget milestone
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum MilestoneStatus {
+        /// Funds are in escrow
+        Pending,
+        /// Funds are transferred to beneficiary
+        Released,
+        /// Funds are returned to pallet account after deadline
+        ClawedBack,
+    }
+
+    /// Part of a budget that is released on confirmation
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Milestone<Balance, BlockNumber> {
+        pub amount: Balance,
+        /// Last block when milestone can be released
+        pub deadline: BlockNumber,
+        pub status: MilestoneStatus,
+    }
+
+    /// Spending proposal approved by governance
+    #[derive(
+        Encode,
+        Decode,
+        CloneNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+        RuntimeDebugNoBound,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T, I))]
+    #[codec(mel_bound())]
+    pub struct Budget<T: Config<I>, I: 'static> {
+        pub beneficiary: T::AccountId,
+        pub asset: Asset,
+        pub milestones: BoundedVec<Milestone<T::Balance, T::BlockNumber>, T::MaxMilestones>,
     }
 
     #[pallet::storage]
     #[pallet::getter(fn manager)]
     pub type PalletManager<T: Config<I>, I: 'static = ()> = StorageValue<_, T::AccountId>;
 
+    /// Id of the next approved budget
+    #[pallet::storage]
+    pub type NextBudgetId<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+    /// Budgets with unsettled milestones
+    #[pallet::storage]
+    #[pallet::getter(fn budgets)]
+    pub type Budgets<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, u32, Budget<T, I>, OptionQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config<I>, I: 'static = ()> {
+        /// Budget is approved and moved to escrow. \[budget_id, beneficiary, asset, total\]
+        BudgetApproved(u32, T::AccountId, Asset, T::Balance),
+        /// Milestone is transferred to beneficiary. \[budget_id, milestone, amount\]
+        MilestoneReleased(u32, u32, T::Balance),
+        /// Milestone is returned to pallet account. \[budget_id, milestone, amount\]
+        MilestoneClawedBack(u32, u32, T::Balance),
+    }
+
     #[pallet::call]
     impl<T: Config<I>, I: 'static> Pallet<T, I> {
         /// Transfer funds from pallets account
@@ -186,6 +263,140 @@ pub mod pallet {
                 .expect("user does not have an existing vesting schedule; q.e.d.");
             Ok(().into())
         }
+
+        /// Approve spending proposal and move its funds to escrow
+        ///
+        /// The dispatch origin for this call must be `ManagementOrigin`.
+        ///
+        /// Parameters:
+        ///  - `beneficiary`: The account that receives released milestones;
+        ///  - `asset`: The budget asset;
+        ///  - `milestones`: Amounts with the last blocks they can be released at.
+        #[pallet::call_index(2)]
+        #[pallet::weight((
+            T::WeightInfo::approve_budget(milestones.len() as u32),
+            DispatchClass::Normal
+        ))]
+        pub fn approve_budget(
+            origin: OriginFor<T>,
+            beneficiary: T::AccountId,
+            asset: Asset,
+            milestones: Vec<(T::Balance, T::BlockNumber)>,
+        ) -> DispatchResultWithPostInfo {
+            T::ManagementOrigin::ensure_origin(origin)?;
+
+            eq_ensure!(
+                !milestones.is_empty(),
+                Error::<T, I>::NoMilestones,
+                target: "eq_distribution",
+                "{}:{}. Budget without milestones.",
+                file!(),
+                line!()
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut total = T::Balance::zero();
+            let mut bounded = BoundedVec::<_, T::MaxMilestones>::default();
+            for (amount, deadline) in milestones {
+                eq_ensure!(
+                    !amount.is_zero(),
+                    Error::<T, I>::AmountLow,
+                    target: "eq_distribution",
+                    "{}:{}. Milestone amount equals zero.",
+                    file!(),
+                    line!()
+                );
+                eq_ensure!(
+                    deadline > now,
+                    Error::<T, I>::DeadlineInPast,
+                    target: "eq_distribution",
+                    "{}:{}. Milestone deadline is in the past. Deadline: {:?}, now: {:?}.",
+                    file!(),
+                    line!(),
+                    deadline,
+                    now
+                );
+                total = total
+                    .checked_add(&amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+                bounded
+                    .try_push(Milestone {
+                        amount,
+                        deadline,
+                        status: MilestoneStatus::Pending,
+                    })
+                    .map_err(|_| Error::<T, I>::TooManyMilestones)?;
+            }
+
+            let budget_id = NextBudgetId::<T, I>::get();
+            T::EqCurrency::currency_transfer(
+                &Self::account_id(),
+                &Self::escrow_account_id(budget_id),
+                asset,
+                total,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::Common,
+                true,
+            )?;
+
+            Budgets::<T, I>::insert(
+                budget_id,
+                Budget {
+                    beneficiary: beneficiary.clone(),
+                    asset,
+                    milestones: bounded,
+                },
+            );
+            NextBudgetId::<T, I>::put(budget_id.checked_add(1).ok_or(ArithmeticError::Overflow)?);
+
+            Self::deposit_event(Event::BudgetApproved(budget_id, beneficiary, asset, total));
+
+            Ok(().into())
+        }
+
+        /// Release milestone funds from escrow to budget beneficiary
+        ///
+        /// The dispatch origin for this call must be `MilestoneOrigin`.
+        /// Milestone should be pending and its deadline should not be passed.
+        #[pallet::call_index(3)]
+        #[pallet::weight((
+            T::WeightInfo::release_milestone(),
+            DispatchClass::Normal
+        ))]
+        pub fn release_milestone(
+            origin: OriginFor<T>,
+            budget_id: u32,
+            milestone: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::MilestoneOrigin::ensure_origin(origin)?;
+
+            let amount = Self::settle_milestone(budget_id, milestone, MilestoneStatus::Released)?;
+            Self::deposit_event(Event::MilestoneReleased(budget_id, milestone, amount));
+
+            Ok(().into())
+        }
+
+        /// Return funds of an expired milestone from escrow to pallet account
+        ///
+        /// The dispatch origin for this call must be _Signed_.
+        /// Milestone should be pending and its deadline should be passed.
+        #[pallet::call_index(4)]
+        #[pallet::weight((
+            T::WeightInfo::claw_back_milestone(),
+            DispatchClass::Normal
+        ))]
+        pub fn claw_back_milestone(
+            origin: OriginFor<T>,
+            budget_id: u32,
+            milestone: u32,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let amount = Self::settle_milestone(budget_id, milestone, MilestoneStatus::ClawedBack)?;
+            Self::deposit_event(Event::MilestoneClawedBack(budget_id, milestone, amount));
+
+            Ok(().into())
+        }
     }
 
     #[pallet::hooks]
@@ -198,6 +409,22 @@ pub mod pallet {
 
         /// Amount being transferred is too low to create a vesting schedule
         AmountLow,
+        /// Budget should have at least one milestone
+        NoMilestones,
+        /// Budget has more than `MaxMilestones` milestones
+        TooManyMilestones,
+        /// Milestone deadline should be in the future
+        DeadlineInPast,
+        /// There is no budget with such id
+        UnknownBudget,
+        /// There is no milestone with such index in the budget
+        UnknownMilestone,
+        /// Milestone is already released or clawed back
+        MilestoneSettled,
+        /// Milestone deadline is passed, it can only be clawed back
+        MilestoneExpired,
+        /// Milestone deadline is not passed yet
+        DeadlineNotReached,
     }
 
     // empty genesis, only for adding ref to module's AccountId
@@ -234,6 +461,90 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
     pub fn account_id() -> T::AccountId {
         T::PalletId::get().into_account_truncating()
     }
+
+    /// Escrow sub-account holding unsettled milestones of a budget
+    pub fn escrow_account_id(budget_id: u32) -> T::AccountId {
+        T::PalletId::get().into_sub_account_truncating((b"escrow", budget_id))
+    }
+
+    /// Moves pending milestone funds out of escrow: to beneficiary when `status` is `Released`,
+    /// back to pallet account when it is `ClawedBack`. Budget is removed with its last milestone.
+    fn settle_milestone(
+        budget_id: u32,
+        index: u32,
+        status: MilestoneStatus,
+    ) -> Result<T::Balance, DispatchError> {
+        Budgets::<T, I>::try_mutate_exists(budget_id, |maybe_budget| {
+            let budget = maybe_budget.as_mut().ok_or(Error::<T, I>::UnknownBudget)?;
+            let milestone = budget
+                .milestones
+                .get_mut(index as usize)
+                .ok_or(Error::<T, I>::UnknownMilestone)?;
+            eq_ensure!(
+                milestone.status == MilestoneStatus::Pending,
+                Error::<T, I>::MilestoneSettled,
+                target: "eq_distribution",
+                "{}:{}. Milestone is already settled. Budget: {:?}, milestone: {:?}, status: {:?}.",
+                file!(),
+                line!(),
+                budget_id,
+                index,
+                milestone.status
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let dest = match status {
+                MilestoneStatus::Released => {
+                    eq_ensure!(
+                        now <= milestone.deadline,
+                        Error::<T, I>::MilestoneExpired,
+                        target: "eq_distribution",
+                        "{}:{}. Milestone deadline is passed. Deadline: {:?}, now: {:?}.",
+                        file!(),
+                        line!(),
+                        milestone.deadline,
+                        now
+                    );
+                    budget.beneficiary.clone()
+                }
+                _ => {
+                    eq_ensure!(
+                        now > milestone.deadline,
+                        Error::<T, I>::DeadlineNotReached,
+                        target: "eq_distribution",
+                        "{}:{}. Milestone deadline is not passed. Deadline: {:?}, now: {:?}.",
+                        file!(),
+                        line!(),
+                        milestone.deadline,
+                        now
+                    );
+                    Self::account_id()
+                }
+            };
+
+            let amount = milestone.amount;
+            T::EqCurrency::currency_transfer(
+                &Self::escrow_account_id(budget_id),
+                &dest,
+                budget.asset,
+                amount,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::Common,
+                true,
+            )?;
+            milestone.status = status;
+
+            if budget
+                .milestones
+                .iter()
+                .all(|m| m.status != MilestoneStatus::Pending)
+            {
+                *maybe_budget = None;
+            }
+
+            Ok(amount)
+        })
+    }
 }
 
 /// Instance16 to be used for instantiable pallet define with `pallet` macro.
@@ -20,40 +20,38 @@
 
 use super::*;
 use crate as eq_distribution;
-use eq_primitives::asset::{self, Asset, AssetGetter};
-use eq_primitives::balance::{DepositReason, EqCurrency, WithdrawReason};
+use eq_primitives::asset::{self, Asset, AssetGetter, AssetType};
 use eq_primitives::mocks::VestingAccountMock;
 use frame_support::parameter_types;
-use frame_support::traits::{EitherOfDiverse, LockIdentifier, WithdrawReasons};
+use frame_support::traits::EitherOfDiverse;
 use frame_support::weights::constants::WEIGHT_REF_TIME_PER_SECOND;
 use frame_support::weights::Weight;
 use frame_system::pallet_prelude::BlockNumberFor;
 use frame_system::EnsureRoot;
 use sp_core::H256;
-use sp_runtime::ModuleError;
+use sp_runtime::DispatchResult;
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
 };
-use sp_runtime::{DispatchError, DispatchResult};
 use std::{cell::RefCell, convert::TryFrom};
+use test_utils::builder::{AssetConfig, ExtBuilder};
+pub use test_utils::mock::assets::AssetGetterMock;
 
 parameter_types! {
     pub const ExistentialDeposit: Balance = 1;
     pub const DistributionModuleId: PalletId = PalletId(*b"eq/distr");
     pub const VestingModuleId: PalletId = PalletId(*b"eq/vestn");
+    pub const MaxMilestones: u32 = 3;
 }
 
 type AccountId = u64;
 pub(crate) type Balance = eq_primitives::balance::Balance;
+pub type EqCurrencyMock = test_utils::mock::currency::EqCurrencyMock<AccountId>;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 type BlockNumber = u64;
 
-use eq_primitives::balance_number::EqFixedU128;
-use eq_primitives::TransferReason;
-use sp_runtime::traits::One;
-
 frame_support::construct_runtime!(
     pub enum Test where
         Block = Block,
@@ -61,28 +59,69 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-        EqDistribution: eq_distribution::{Pallet, Call, Storage},
+        EqDistribution: eq_distribution::{Pallet, Call, Storage, Event<T>},
         EqMembers: pallet_collective::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
     }
 );
 
 pub const AMOUNT: Balance = 100;
 pub const ACC_ID: AccountId = 1;
+pub const PALLET_BALANCE: Balance = 1_000 * AMOUNT;
 
 thread_local! {
     pub static ADDED_VESTING: RefCell<Option<(DummyValidatorId, (Balance, Balance, u64))>> = RefCell::new(
         Option::None
     );
-    pub static TRANSFER: RefCell<Option<((Asset, DummyValidatorId, DummyValidatorId, Balance), ExistenceRequirement)>> = RefCell::new(
-        Option::None
-    );
     pub static VESTED_TRANSFER: RefCell<Option<((DummyValidatorId, DummyValidatorId, u64), ExistenceRequirement)>> = RefCell::new(
         Option::None
     );
     pub static VESTING_EXISTS: RefCell<bool> = RefCell::new(false);
+    pub static TRANSFER: RefCell<Option<((Asset, DummyValidatorId, DummyValidatorId, Balance), ExistenceRequirement)>> = RefCell::new(
+        Option::None
+    );
     pub static CAN_TRANSFER: RefCell<bool> = RefCell::new(true);
 }
 
+/// `EqCurrencyMock` transfer hook: common transfers with checks only, the last one is
+/// kept in `TRANSFER`
+fn check_transfer(
+    transactor: &[u8],
+    dest: &[u8],
+    asset: Asset,
+    value: Balance,
+    existence_requirement: ExistenceRequirement,
+    transfer_reason: eq_primitives::TransferReason,
+    ensure_can_change: bool,
+) -> DispatchResult {
+    use codec::Decode;
+    use sp_runtime::{DispatchError, ModuleError};
+
+    let result = CAN_TRANSFER.with(|v| v.borrow().clone())
+        && transfer_reason == eq_primitives::TransferReason::Common
+        && ensure_can_change;
+    if !result {
+        Err(DispatchError::Module(ModuleError {
+            index: 0,
+            error: *b"zero",
+            message: Option::None,
+        }))
+    } else if !AssetGetterMock::get_assets().contains(&asset) {
+        Err(DispatchError::Module(ModuleError {
+            index: 0,
+            error: *b"one1",
+            message: Option::None,
+        }))
+    } else {
+        let transactor = DummyValidatorId::decode(&mut &transactor[..]).expect("u64 account");
+        let dest = DummyValidatorId::decode(&mut &dest[..]).expect("u64 account");
+        TRANSFER.with(|v| {
+            *v.borrow_mut() =
+                Option::Some(((asset, transactor, dest, value), existence_requirement))
+        });
+        Ok(())
+    }
+}
+
 type DummyValidatorId = u64;
 
 parameter_types! {
@@ -200,184 +239,6 @@ impl frame_system::Config for Test {
 //     }
 // }
 
-frame_support::parameter_types! {
-    pub const MaxLocks: u32 = 10;
-}
-pub struct EqCurrencyMock;
-impl EqCurrency<DummyValidatorId, Balance> for EqCurrencyMock {
-    type Moment = BlockNumber;
-    type MaxLocks = MaxLocks;
-
-    fn total_balance(_who: &AccountId, _asset: Asset) -> Balance {
-        unimplemented!()
-    }
-
-    fn debt(_: &DummyValidatorId, _: Asset) -> Balance {
-        unimplemented!()
-    }
-
-    fn currency_total_issuance(_: asset::Asset) -> Balance {
-        unimplemented!()
-    }
-
-    fn minimum_balance_value() -> Balance {
-        unimplemented!()
-    }
-
-    fn free_balance(_: &DummyValidatorId, _: asset::Asset) -> Balance {
-        unimplemented!()
-    }
-
-    fn ensure_can_withdraw(
-        _: &DummyValidatorId,
-        _: asset::Asset,
-        _: Balance,
-        _: WithdrawReasons,
-        _: Balance,
-    ) -> DispatchResult {
-        unimplemented!()
-    }
-
-    fn currency_transfer(
-        transactor: &DummyValidatorId,
-        dest: &DummyValidatorId,
-        asset: Asset,
-        value: Balance,
-        existence_requirement: ExistenceRequirement,
-        transfer_reason: TransferReason,
-        ensure_can_change: bool,
-    ) -> DispatchResult {
-        let result = CAN_TRANSFER.with(|v| v.borrow().clone())
-            && transfer_reason == eq_primitives::TransferReason::Common
-            && ensure_can_change;
-        if !result {
-            Err(DispatchError::Module(ModuleError {
-                index: 0,
-                error: *b"zero",
-                message: Option::None,
-            }))
-        } else if !AssetGetterMock::get_assets().contains(&asset) {
-            Err(DispatchError::Module(ModuleError {
-                index: 0,
-                error: *b"one1",
-                message: Option::None,
-            }))
-        } else {
-            TRANSFER.with(|v| {
-                *v.borrow_mut() =
-                    Option::Some(((asset, *transactor, *dest, value), existence_requirement))
-            });
-            Ok(())
-        }
-    }
-
-    fn deposit_into_existing(
-        _: &DummyValidatorId,
-        _: Asset,
-        _: Balance,
-        _: Option<DepositReason>,
-    ) -> Result<(), DispatchError> {
-        unimplemented!()
-    }
-
-    fn deposit_creating(
-        _: &DummyValidatorId,
-        _: Asset,
-        _: Balance,
-        _: bool,
-        _: Option<DepositReason>,
-    ) -> Result<(), DispatchError> {
-        unimplemented!()
-    }
-
-    fn withdraw(
-        _: &DummyValidatorId,
-        _: Asset,
-        _: Balance,
-        _: bool,
-        _: Option<WithdrawReason>,
-        _: WithdrawReasons,
-        _: ExistenceRequirement,
-    ) -> Result<(), DispatchError> {
-        unimplemented!()
-    }
-
-    fn make_free_balance_be(
-        _: &DummyValidatorId,
-        _: Asset,
-        _: eq_primitives::SignedBalance<Balance>,
-    ) {
-        unimplemented!()
-    }
-
-    fn can_be_deleted(_: &DummyValidatorId) -> Result<bool, DispatchError> {
-        unimplemented!()
-    }
-
-    fn delete_account(_: &DummyValidatorId) -> Result<(), DispatchError> {
-        unimplemented!()
-    }
-
-    fn exchange(
-        _: (&DummyValidatorId, &DummyValidatorId),
-        _: (&Asset, &Asset),
-        _: (Balance, Balance),
-    ) -> Result<(), (DispatchError, Option<DummyValidatorId>)> {
-        unimplemented!()
-    }
-
-    fn reserve(_: &DummyValidatorId, _: Asset, _: Balance) -> DispatchResult {
-        unimplemented!()
-    }
-
-    fn unreserve(_: &DummyValidatorId, _: Asset, _: Balance) -> Balance {
-        unimplemented!()
-    }
-
-    fn xcm_transfer(
-        _from: &DummyValidatorId,
-        _asset: Asset,
-        _amount: Balance,
-        _to: eq_primitives::balance::XcmDestination,
-    ) -> DispatchResult {
-        unimplemented!()
-    }
-
-    fn set_lock(_: LockIdentifier, _: &AccountId, _: Balance) {
-        panic!("{}:{} - should not be called", file!(), line!())
-    }
-
-    fn extend_lock(_: LockIdentifier, _: &AccountId, _: Balance) {
-        panic!("{}:{} - should not be called", file!(), line!())
-    }
-
-    fn remove_lock(_: LockIdentifier, _: &AccountId) {
-        panic!("{}:{} - should not be called", file!(), line!())
-    }
-
-    fn reserved_balance(_: &DummyValidatorId, _: Asset) -> Balance {
-        unimplemented!()
-    }
-
-    fn slash_reserved(
-        _: &DummyValidatorId,
-        _: Asset,
-        _: Balance,
-    ) -> (eq_balances::NegativeImbalance<Balance>, Balance) {
-        unimplemented!()
-    }
-
-    fn repatriate_reserved(
-        _: &DummyValidatorId,
-        _: &DummyValidatorId,
-        _: Asset,
-        _: Balance,
-        _: frame_support::traits::BalanceStatus,
-    ) -> Result<Balance, DispatchError> {
-        unimplemented!()
-    }
-}
-
 pub struct VestingScheduleMock;
 impl EqVestingSchedule<Balance, DummyValidatorId> for VestingScheduleMock {
     type Moment = u64;
@@ -404,55 +265,23 @@ impl EqVestingSchedule<Balance, DummyValidatorId> for VestingScheduleMock {
         Ok(())
     }
 
-    fn update_vesting_schedule(
+    fn add_vesting_schedule_at_epoch(
         _who: &DummyValidatorId,
         _locked: Balance,
-        _duration_blocks: Balance,
+        _per_block: Balance,
+        _starting_block: Self::Moment,
+        _epoch: u32,
     ) -> DispatchResult {
         unimplemented!()
     }
-}
-
-pub struct AssetGetterMock;
-impl AssetGetter for AssetGetterMock {
-    fn get_asset_data(
-        _: &eq_primitives::asset::Asset,
-    ) -> Result<eq_primitives::asset::AssetData<eq_primitives::asset::Asset>, DispatchError> {
-        unimplemented!()
-    }
-
-    fn exists(_: eq_primitives::asset::Asset) -> bool {
-        unimplemented!()
-    }
-
-    fn get_assets_data() -> Vec<eq_primitives::asset::AssetData<eq_primitives::asset::Asset>> {
-        unimplemented!()
-    }
-
-    fn get_assets_data_with_usd(
-    ) -> Vec<eq_primitives::asset::AssetData<eq_primitives::asset::Asset>> {
-        unimplemented!()
-    }
-
-    fn get_assets() -> Vec<eq_primitives::asset::Asset> {
-        vec![asset::GENS, asset::BTC, asset::ETH, asset::HDOT]
-    }
 
-    fn get_assets_with_usd() -> Vec<eq_primitives::asset::Asset> {
-        unimplemented!()
-    }
-
-    fn priority(_: eq_primitives::asset::Asset) -> Option<u64> {
+    fn update_vesting_schedule(
+        _who: &DummyValidatorId,
+        _locked: Balance,
+        _duration_blocks: Balance,
+    ) -> DispatchResult {
         unimplemented!()
     }
-
-    fn get_main_asset() -> eq_primitives::asset::Asset {
-        asset::GENS
-    }
-
-    fn collateral_discount(_asset: &Asset) -> EqFixedU128 {
-        EqFixedU128::one()
-    }
 }
 
 parameter_types! {
@@ -488,11 +317,27 @@ impl Config for Test {
     type AssetGetter = AssetGetterMock;
     type EqCurrency = EqCurrencyMock;
     type WeightInfo = ();
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRoot<AccountId>;
+    type MaxMilestones = MaxMilestones;
 }
 
 pub type ModuleDistribution = Pallet<Test>;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
+    let pallet_account = DistributionModuleId::get().into_account_truncating();
+    let builder = ExtBuilder::default()
+        .asset(AssetConfig::new(asset::GENS, AssetType::Native, u64::MAX))
+        .asset(AssetConfig::new(asset::BTC, AssetType::Physical, 1))
+        .asset(AssetConfig::new(asset::ETH, AssetType::Physical, 2))
+        .asset(AssetConfig::new(asset::HDOT, AssetType::Physical, 3))
+        .balance(pallet_account, asset::GENS, PALLET_BALANCE)
+        .balance(pallet_account, asset::BTC, PALLET_BALANCE)
+        .balance(pallet_account, asset::ETH, PALLET_BALANCE)
+        .balance(pallet_account, asset::HDOT, PALLET_BALANCE);
+    builder.init_mocks();
+    EqCurrencyMock::set_transfer_hook(Some(check_transfer));
+
     let r = frame_system::GenesisConfig::default().build_storage::<Test>();
 
     r.unwrap().into()
@@ -22,8 +22,8 @@ use crate::mock::*;
 use crate::Error;
 use crate::ExistenceRequirement;
 use eq_primitives::asset;
-use frame_support::assert_ok;
 use frame_support::{assert_err, dispatch::DispatchError};
+use frame_support::{assert_noop, assert_ok};
 use sp_runtime::traits::AccountIdConversion;
 use sp_runtime::ModuleError;
 
@@ -392,3 +392,88 @@ fn no_manager_does_not_fail_only_root() {
         ));
     })
 }
+
+#[test]
+fn budget_milestones_are_released_or_clawed_back() {
+    new_test_ext().execute_with(|| {
+        CAN_TRANSFER.with(|v| *v.borrow_mut() = true);
+        let pallet_acc: u64 = DistributionModuleId::get().into_account_truncating();
+        let escrow = ModuleDistribution::escrow_account_id(0);
+
+        assert_noop!(
+            ModuleDistribution::approve_budget(
+                frame_system::RawOrigin::Root.into(),
+                ACC_ID,
+                asset::GENS,
+                vec![]
+            ),
+            Error::<Test>::NoMilestones
+        );
+        assert_noop!(
+            ModuleDistribution::approve_budget(
+                frame_system::RawOrigin::Root.into(),
+                ACC_ID,
+                asset::GENS,
+                vec![(10, 10); 4]
+            ),
+            Error::<Test>::TooManyMilestones
+        );
+
+        assert_ok!(ModuleDistribution::approve_budget(
+            frame_system::RawOrigin::Root.into(),
+            ACC_ID,
+            asset::GENS,
+            vec![(30, 10), (70, 20)]
+        ));
+        assert_eq!(
+            TRANSFER.with(|v| v.borrow().clone()).unwrap().0,
+            (asset::GENS, pallet_acc, escrow, 100)
+        );
+        assert_eq!(crate::NextBudgetId::<Test>::get(), 1);
+
+        assert_err!(
+            ModuleDistribution::release_milestone(frame_system::RawOrigin::Signed(2).into(), 0, 0),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ModuleDistribution::claw_back_milestone(
+                frame_system::RawOrigin::Signed(2).into(),
+                0,
+                0
+            ),
+            Error::<Test>::DeadlineNotReached
+        );
+
+        assert_ok!(ModuleDistribution::release_milestone(
+            frame_system::RawOrigin::Root.into(),
+            0,
+            0
+        ));
+        assert_eq!(
+            TRANSFER.with(|v| v.borrow().clone()).unwrap().0,
+            (asset::GENS, escrow, ACC_ID, 30)
+        );
+        assert_noop!(
+            ModuleDistribution::release_milestone(frame_system::RawOrigin::Root.into(), 0, 0),
+            Error::<Test>::MilestoneSettled
+        );
+
+        frame_system::Pallet::<Test>::set_block_number(21);
+        assert_noop!(
+            ModuleDistribution::release_milestone(frame_system::RawOrigin::Root.into(), 0, 1),
+            Error::<Test>::MilestoneExpired
+        );
+        assert_ok!(ModuleDistribution::claw_back_milestone(
+            frame_system::RawOrigin::Signed(2).into(),
+            0,
+            1
+        ));
+        assert_eq!(
+            TRANSFER.with(|v| v.borrow().clone()).unwrap().0,
+            (asset::GENS, escrow, pallet_acc, 70)
+        );
+
+        // all milestones are settled
+        assert!(ModuleDistribution::budgets(0).is_none());
+    });
+}
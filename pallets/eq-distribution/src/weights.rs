@@ -25,6 +25,9 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
     fn transfer() -> Weight;
     fn vested_transfer() -> Weight;
+    fn approve_budget(m: u32) -> Weight;
+    fn release_milestone() -> Weight;
+    fn claw_back_milestone() -> Weight;
 }
 
 // for tests
@@ -35,4 +38,13 @@ impl crate::WeightInfo for () {
     fn vested_transfer() -> Weight {
         Weight::zero()
     }
+    fn approve_budget(_m: u32) -> Weight {
+        Weight::zero()
+    }
+    fn release_milestone() -> Weight {
+        Weight::zero()
+    }
+    fn claw_back_milestone() -> Weight {
+        Weight::zero()
+    }
 }
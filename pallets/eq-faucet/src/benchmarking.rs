@@ -0,0 +1,48 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium Faucet Pallet Benchmarking
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use eq_primitives::asset;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::One;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+    drip {
+        let who: T::AccountId = account("who", 0, SEED);
+        DripAmounts::<T>::insert(asset::EQ, T::Balance::one());
+        // account got the asset before
+        LastDrips::<T>::insert(&who, asset::EQ, T::BlockNumber::zero());
+        frame_system::Pallet::<T>::set_block_number(T::DripPeriod::get());
+    }: _(RawOrigin::None, who.clone(), asset::EQ)
+    verify {
+        assert_eq!(LastDrips::<T>::get(&who, asset::EQ), Some(T::DripPeriod::get()));
+    }
+
+    set_drip_amount {
+    }: _(RawOrigin::Root, asset::EQ, Some(T::Balance::one()))
+    verify {
+        assert_eq!(DripAmounts::<T>::get(asset::EQ), Some(T::Balance::one()));
+    }
+}
@@ -0,0 +1,235 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium Faucet Pallet
+//!
+//! Funds accounts of development and test networks without sudo deposits.
+//!
+//! Anyone may request a drip of an asset to any account with unsigned `drip` call,
+//! so new accounts don't need funds for fees. `FaucetOrigin` sets drip amount of every
+//! asset. There is no captcha, drips are rate limited by blocks instead: account gets
+//! the asset at most once in `DripPeriod` blocks and at most `MaxDripsPerBlock` drips
+//! are made in a block. Limits are checked in `validate_unsigned`, so rejected drips
+//! don't get into blocks.
+//!
+//! Drips are disabled by "production" feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(warnings)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use eq_primitives::{
+    asset::Asset,
+    balance::{DepositReason, EqCurrency},
+};
+use frame_support::pallet_prelude::*;
+use sp_runtime::traits::{Saturating, Zero};
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::AtLeast32BitUnsigned;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Numerical representation of stored balances
+        type Balance: Parameter + Member + AtLeast32BitUnsigned + Copy;
+        /// Mints dripped assets
+        type EqCurrency: EqCurrency<Self::AccountId, Self::Balance>;
+        /// Origin setting drip amounts
+        type FaucetOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Min number of blocks between drips of the same asset to the same account
+        #[pallet::constant]
+        type DripPeriod: Get<Self::BlockNumber>;
+        /// Max number of drips in a single block
+        #[pallet::constant]
+        type MaxDripsPerBlock: Get<u32>;
+        /// For priority calculation of an unsigned transaction
+        #[pallet::constant]
+        type UnsignedPriority: Get<TransactionPriority>;
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Amount of a single drip by asset, assets without amount aren't dripped
+    #[pallet::storage]
+    #[pallet::getter(fn drip_amount)]
+    pub type DripAmounts<T: Config> = StorageMap<_, Identity, Asset, T::Balance, OptionQuery>;
+
+    /// Block of the last drip by account and asset
+    #[pallet::storage]
+    #[pallet::getter(fn last_drip)]
+    pub type LastDrips<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        Asset,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    /// Number of drips made in the block
+    #[pallet::storage]
+    #[pallet::getter(fn block_drips)]
+    pub type BlockDrips<T: Config> = StorageValue<_, (T::BlockNumber, u32), ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Drip amount of the asset is changed, `None` disables drips of the asset
+        DripAmountSet {
+            asset: Asset,
+            amount: Option<T::Balance>,
+        },
+        /// Account is funded by the faucet
+        Dripped {
+            who: T::AccountId,
+            asset: Asset,
+            amount: T::Balance,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// This method is not allowed in production
+        MethodNotAllowed,
+        /// The asset isn't dripped
+        AssetNotDripped,
+        /// Account got the asset less than `DripPeriod` blocks ago
+        DripTooEarly,
+        /// Max number of drips in the block is reached
+        TooManyDrips,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Funds `who` with drip amount of `asset`.
+        /// The dispatch origin for this call must be _None_ (unsigned transaction).
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::drip())]
+        pub fn drip(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            asset: Asset,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            let amount = Self::ensure_can_drip(&who, asset)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            T::EqCurrency::deposit_creating(
+                &who,
+                asset,
+                amount,
+                true,
+                Some(DepositReason::Extrinsic),
+            )?;
+            LastDrips::<T>::insert(&who, asset, now);
+            BlockDrips::<T>::mutate(|(block, drips)| {
+                if *block != now {
+                    *block = now;
+                    *drips = 0;
+                }
+                *drips += 1;
+            });
+
+            Self::deposit_event(Event::Dripped { who, asset, amount });
+            Ok(().into())
+        }
+
+        /// Sets drip amount of `asset`, `None` or zero disables drips of the asset
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::set_drip_amount())]
+        pub fn set_drip_amount(
+            origin: OriginFor<T>,
+            asset: Asset,
+            amount: Option<T::Balance>,
+        ) -> DispatchResultWithPostInfo {
+            T::FaucetOrigin::ensure_origin(origin)?;
+
+            let amount = amount.filter(|a| !a.is_zero());
+            DripAmounts::<T>::set(asset, amount);
+
+            Self::deposit_event(Event::DripAmountSet { asset, amount });
+            Ok(().into())
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            match call {
+                Call::drip { who, asset } => {
+                    Self::ensure_can_drip(who, *asset).map_err(|err| match err {
+                        Error::<T>::TooManyDrips => InvalidTransaction::ExhaustsResources,
+                        _ => InvalidTransaction::Call,
+                    })?;
+
+                    ValidTransaction::with_tag_prefix("EqFaucet")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((who, asset))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                _ => InvalidTransaction::Call.into(),
+            }
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Checks faucet limits, returns drip amount of `asset`
+    fn ensure_can_drip(who: &T::AccountId, asset: Asset) -> Result<T::Balance, Error<T>> {
+        ensure!(!cfg!(feature = "production"), Error::<T>::MethodNotAllowed);
+        let amount = DripAmounts::<T>::get(asset).ok_or(Error::<T>::AssetNotDripped)?;
+
+        let now = frame_system::Pallet::<T>::block_number();
+        if let Some(last_drip) = LastDrips::<T>::get(who, asset) {
+            ensure!(
+                now >= last_drip.saturating_add(T::DripPeriod::get()),
+                Error::<T>::DripTooEarly
+            );
+        }
+        let (block, drips) = BlockDrips::<T>::get();
+        ensure!(
+            block != now || drips < T::MaxDripsPerBlock::get(),
+            Error::<T>::TooManyDrips
+        );
+
+        Ok(amount)
+    }
+}
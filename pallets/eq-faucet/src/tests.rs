@@ -0,0 +1,122 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+use eq_primitives::asset::{DOT, EQ};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::DispatchError;
+
+const ALICE: AccountId = 1;
+const BOB: AccountId = 2;
+const CAROL: AccountId = 3;
+
+fn validate(who: AccountId, asset: Asset) -> TransactionValidity {
+    <EqFaucet as ValidateUnsigned>::validate_unsigned(
+        TransactionSource::External,
+        &Call::drip { who, asset },
+    )
+}
+
+#[test]
+fn set_drip_amount() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EqFaucet::set_drip_amount(RuntimeOrigin::signed(ALICE), EQ, Some(100)),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(EqFaucet::set_drip_amount(
+            RawOrigin::Root.into(),
+            EQ,
+            Some(100)
+        ));
+        assert_eq!(EqFaucet::drip_amount(EQ), Some(100));
+
+        assert_ok!(EqFaucet::set_drip_amount(
+            RawOrigin::Root.into(),
+            EQ,
+            Some(0)
+        ));
+        assert_eq!(EqFaucet::drip_amount(EQ), None);
+    });
+}
+
+#[test]
+fn drip_is_rate_limited() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(EqFaucet::set_drip_amount(
+            RawOrigin::Root.into(),
+            EQ,
+            Some(100)
+        ));
+
+        assert_eq!(validate(ALICE, DOT), InvalidTransaction::Call.into());
+        assert_noop!(
+            EqFaucet::drip(RawOrigin::None.into(), ALICE, DOT),
+            Error::<Test>::AssetNotDripped
+        );
+        assert_noop!(
+            EqFaucet::drip(RuntimeOrigin::signed(ALICE), ALICE, EQ),
+            DispatchError::BadOrigin
+        );
+
+        assert!(validate(ALICE, EQ).is_ok());
+        assert_ok!(EqFaucet::drip(RawOrigin::None.into(), ALICE, EQ));
+        assert_eq!(balance(ALICE, EQ), 100);
+        assert_eq!(EqFaucet::last_drip(ALICE, EQ), Some(1));
+        System::assert_last_event(
+            Event::Dripped {
+                who: ALICE,
+                asset: EQ,
+                amount: 100,
+            }
+            .into(),
+        );
+
+        // per account limit
+        assert_eq!(validate(ALICE, EQ), InvalidTransaction::Call.into());
+        assert_noop!(
+            EqFaucet::drip(RawOrigin::None.into(), ALICE, EQ),
+            Error::<Test>::DripTooEarly
+        );
+
+        // per block limit
+        assert_ok!(EqFaucet::drip(RawOrigin::None.into(), BOB, EQ));
+        assert_eq!(
+            validate(CAROL, EQ),
+            InvalidTransaction::ExhaustsResources.into()
+        );
+        assert_noop!(
+            EqFaucet::drip(RawOrigin::None.into(), CAROL, EQ),
+            Error::<Test>::TooManyDrips
+        );
+
+        System::set_block_number(2);
+        assert_ok!(EqFaucet::drip(RawOrigin::None.into(), CAROL, EQ));
+        assert_eq!(EqFaucet::block_drips(), (2, 1));
+
+        System::set_block_number(1 + DripPeriod::get());
+        assert!(validate(ALICE, EQ).is_ok());
+        assert_ok!(EqFaucet::drip(RawOrigin::None.into(), ALICE, EQ));
+        assert_eq!(balance(ALICE, EQ), 200);
+    });
+}
@@ -0,0 +1,69 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for `eq-lending` pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use eq_primitives::{
+    asset::Asset, balance_number::EqFixedU128, lending::RewardCheckpoint, solvency::SolvencyMetrics,
+};
+use sp_runtime::traits::MaybeDisplay;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait EqLendingApi<Balance, AccountId, BlockNumber>
+    where
+        Balance: Codec + MaybeDisplay,
+        AccountId: Codec,
+        BlockNumber: Codec
+    {
+        /// Cumulative reward per lent unit of `asset` including rewards accrued since the last
+        /// lending pool operation
+        fn reward_index(asset: Asset) -> EqFixedU128;
+
+        /// Reward accrued to `account_id` for `asset` deposit and not paid out yet
+        fn pending_reward(account_id: AccountId, asset: Asset) -> Balance;
+
+        /// Part of `asset` pool rewards accumulated in the pool reserve, in main asset
+        fn reserve(asset: Asset) -> Balance;
+
+        /// Kept history of `asset` reward index changes, the oldest checkpoint first
+        fn reward_checkpoints(asset: Asset) -> Vec<RewardCheckpoint<BlockNumber>>;
+
+        /// Reward in main asset accrued to `deposit` of `asset` from the end of `from` block
+        /// till the end of `to` block, `None` if `from` is older than the kept history
+        fn reward_between(
+            asset: Asset,
+            deposit: Balance,
+            from: BlockNumber,
+            to: BlockNumber,
+        ) -> Option<Balance>;
+    }
+
+    /// Chain-wide solvency state for monitoring dashboards
+    pub trait EqSolvencyApi<Balance>
+    where
+        Balance: Codec
+    {
+        /// Totals of collateral, debt and lending of all assets, bailsman pool coverage and
+        /// treasury balances
+        fn solvency_metrics() -> SolvencyMetrics<Balance>;
+    }
+}
@@ -160,4 +160,57 @@ benchmarks! {
 
         assert!(!EqLending::<T>::is_only_bailsmen_period());
     }:_(RawOrigin::Signed(caller.clone()), asset::ETH, caller.clone())
+
+    set_isolated_pool {
+        let collateral = sp_std::vec![asset::ETH, asset::BTC];
+    }:_(RawOrigin::Root, asset::EOS, Some(collateral))
+    verify {
+        assert!(EqLending::<T>::isolated_pool(asset::EOS).is_some());
+    }
+
+    set_reserve_factor {
+        let factor = sp_runtime::Permill::from_percent(10);
+    }:_(RawOrigin::Root, asset::ETH, factor)
+    verify {
+        assert_eq!(EqLending::<T>::reserve_factor(asset::ETH), factor);
+    }
+
+    withdraw_reserve {
+        init_prices::<T>();
+        init_lending_pool::<T>();
+        let amount: <T as pallet::Config>::Balance = 1_000_000_000u128.try_into().map_err(|_| "balance conversion error").unwrap();
+        ReserveFactors::<T>::insert(asset::ETH, sp_runtime::Permill::one());
+        add_reward::<T>(asset::ETH, amount);
+    }:_(RawOrigin::Root, asset::ETH, amount)
+    verify {
+        assert!(EqLending::<T>::reserve(asset::ETH).is_zero());
+    }
+
+    set_lp_lending {
+        eq_assets::Pallet::<T>::add_asset(
+            RawOrigin::Root.into(),
+            b"lpt".to_vec(),
+            0_u128,
+            0_i64,
+            sp_runtime::Permill::zero(),
+            sp_runtime::Permill::zero(),
+            eq_primitives::asset::AssetXcmData::None,
+            sp_runtime::Permill::zero(),
+            0_u64,
+            AssetType::Lp(AmmPool::Curve(0)),
+            false,
+            sp_runtime::Percent::zero(),
+            sp_runtime::Permill::one(),
+            sp_std::vec![FixedI64::one()],
+        ).unwrap();
+        let lp_asset = Asset::from_bytes(b"lpt").unwrap();
+        eq_oracle::Pallet::<T>::set_the_only_price(lp_asset, FixedI64::one());
+        let params = LpLendingParams {
+            haircut: sp_runtime::Permill::from_percent(10),
+            deposit_cap: 1_000_000_000_000u128.try_into().map_err(|_| "balance conversion error").unwrap(),
+        };
+    }:_(RawOrigin::Root, lp_asset, Some(params))
+    verify {
+        assert_eq!(EqLending::<T>::lp_lending_params(lp_asset), Some(params));
+    }
 }
@@ -22,11 +22,17 @@
 use codec::{Codec, Decode, Encode, MaxEncodedLen};
 use eq_primitives::{
     asset::{self, EQ, Q},
-    asset::{Asset, AssetGetter, AssetType},
-    balance::{BalanceChecker, BalanceGetter, DepositReason, EqCurrency, WithdrawReason},
+    asset::{AmmPool, Asset, AssetGetter, AssetType, RiskTierParams},
+    balance::{
+        BalanceCheckError, BalanceChecker, BalanceCheckerKind, BalanceGetter, DepositReason,
+        EqCurrency, WithdrawReason,
+    },
     balance_number::EqFixedU128,
+    lending::RewardCheckpoint,
+    portfolio::LendingPosition,
+    solvency::AssetSolvency,
     subaccount::SubaccountsManager,
-    Aggregates, BailsmanManager, PriceGetter, SignedBalance, UserGroup,
+    Aggregates, BailsmanManager, LendingIsolation, PriceGetter, SignedBalance, UserGroup,
 };
 #[allow(unused_imports)]
 use frame_support::debug;
@@ -37,8 +43,9 @@ use frame_support::{
 };
 use sp_arithmetic::{traits::CheckedSub, ArithmeticError};
 use sp_runtime::{
-    traits::{AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, Zero},
-    DispatchError, DispatchResult, FixedPointNumber, FixedPointOperand,
+    traits::{AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, One, Saturating, Zero},
+    DispatchError, DispatchResult, FixedPointNumber, FixedPointOperand, Permill,
+    SaturatedConversion,
 };
 use sp_std::{convert::TryInto, vec::Vec};
 
@@ -63,6 +70,31 @@ pub struct LenderData<Balance> {
     pub q_last_reward: EqFixedU128,
 }
 
+/// Reward added to lending pool of an asset which is accrued into the reward index
+/// gradually during `RewardAccrualPeriod`
+#[derive(
+    Clone, Debug, Default, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub struct RewardStream<Balance> {
+    /// Part of added rewards which is not accrued into the index yet
+    pub undistributed: Balance,
+    /// Amount accrued per second
+    pub per_second: Balance,
+    /// Timestamp of the last accrual, in seconds
+    pub last_accrual: u64,
+}
+
+/// Lending parameters of an approved Curve LP token
+#[derive(
+    Clone, Copy, Debug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub struct LpLendingParams<Balance> {
+    /// Part of lent LP tokens which can't be borrowed, applied on top of the asset debt weight
+    pub haircut: Permill,
+    /// Max amount of LP tokens in the lending pool
+    pub deposit_cap: Balance,
+}
+
 impl<Balance: Default> LenderData<Balance> {
     fn default_per_asset<T: Config>(asset: Asset) -> Self {
         Self {
@@ -118,6 +150,28 @@ pub mod pallet {
         type BailsmanManager: BailsmanManager<Self::AccountId, Self::Balance>;
         /// Timestamp provider
         type UnixTime: UnixTime;
+        /// Period in seconds during which added rewards are accrued to lenders.
+        /// Zero means rewards are accrued immediately.
+        #[pallet::constant]
+        type RewardAccrualPeriod: Get<u64>;
+        /// Origin for isolated pools settings
+        type IsolationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Max number of collateral assets allowed for an isolated pool
+        #[pallet::constant]
+        type MaxIsolatedCollateral: Get<u32>;
+        /// Share of the pool liquidity that can be borrowed by risk tier of an asset
+        #[pallet::constant]
+        type RiskTierBorrowCaps: Get<RiskTierParams<Permill>>;
+        /// Origin for reserve factors settings and reserve withdrawals
+        type ReserveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Treasury ModuleId, receives withdrawn reserves
+        #[pallet::constant]
+        type TreasuryModuleId: Get<PalletId>;
+        /// Max number of reward index checkpoints kept per asset, the oldest ones are dropped
+        #[pallet::constant]
+        type MaxRewardCheckpoints: Get<u32>;
+        /// Origin for approving Curve LP tokens for lending
+        type LpLendingOrigin: EnsureOrigin<Self::RuntimeOrigin>;
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -176,9 +230,57 @@ pub mod pallet {
     pub type QCumulatedReward<T: Config> =
         StorageMap<_, Blake2_128Concat, Asset, EqFixedU128, ValueQuery>;
 
+    /// Rewards being accrued into `CumulatedReward` (`QCumulatedReward`) per asset
+    #[pallet::storage]
+    #[pallet::getter(fn reward_stream)]
+    pub type RewardStreams<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, RewardStream<T::Balance>, ValueQuery>;
+
+    /// Isolated lending pools: an isolated asset can be borrowed only against the stored
+    /// collateral assets and has zero collateral weight
+    #[pallet::storage]
+    #[pallet::getter(fn isolated_pool)]
+    pub type IsolatedPools<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Asset,
+        BoundedVec<Asset, T::MaxIsolatedCollateral>,
+        OptionQuery,
+    >;
+
+    /// Part of lenders rewards per asset diverted into the pool reserve
+    #[pallet::storage]
+    #[pallet::getter(fn reserve_factor)]
+    pub type ReserveFactors<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, Permill, ValueQuery>;
+
+    /// Pool reserve per asset, kept in main asset on the pool account
+    #[pallet::storage]
+    #[pallet::getter(fn reserve)]
+    pub type Reserves<T: Config> = StorageMap<_, Blake2_128Concat, Asset, T::Balance, ValueQuery>;
+
+    /// Rolling history of reward index changes per asset, at most one checkpoint per block
+    #[pallet::storage]
+    #[pallet::getter(fn reward_checkpoints)]
+    pub type RewardCheckpoints<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Asset,
+        BoundedVec<RewardCheckpoint<T::BlockNumber>, T::MaxRewardCheckpoints>,
+        ValueQuery,
+    >;
+
+    /// Curve LP tokens approved for lending with their parameters. LP tokens of other
+    /// pools can't be deposited into the lending pool.
+    #[pallet::storage]
+    #[pallet::getter(fn lp_lending_params)]
+    pub type LpLendingPools<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, LpLendingParams<T::Balance>, OptionQuery>;
+
     #[pallet::error]
     pub enum Error<T> {
-        /// Only physical asset types allowed to deposit/withdraw in lending pool
+        /// Only physical assets and approved Curve LP tokens allowed to deposit/withdraw
+        /// in lending pool
         WrongAssetType,
         /// Not allowed because of debt weight
         DebtExceedLiquidity,
@@ -194,6 +296,16 @@ pub mod pallet {
         BailsmanCantBeUnregistered,
         /// Bailsman can't generate debt
         BailsmanCantGenerateDebt,
+        /// Isolated asset can't be borrowed against this collateral
+        IsolatedCollateralNotAllowed,
+        /// Too many collateral assets for an isolated pool
+        TooManyIsolatedCollateral,
+        /// Collateral of an isolated pool can't be isolated itself
+        WrongIsolatedCollateral,
+        /// Try to withdraw more than accumulated in the pool reserve
+        NotEnoughReserve,
+        /// Deposit exceeds the lending pool cap of LP token
+        LpDepositCapExceeded,
     }
 
     #[pallet::event]
@@ -214,6 +326,28 @@ pub mod pallet {
             asset: Asset,
             payout: T::Balance,
         },
+        IsolatedPoolSet {
+            asset: Asset,
+            collateral: Option<Vec<Asset>>,
+        },
+        ReserveFactorSet {
+            asset: Asset,
+            factor: Permill,
+        },
+        ReserveWithdrawn {
+            asset: Asset,
+            amount: T::Balance,
+        },
+        /// Reward index of `asset` pool increased to `index`
+        RewardAccrued {
+            asset: Asset,
+            index: EqFixedU128,
+        },
+        /// Lending parameters of LP token `asset` are set, `None` revokes the approval
+        LpLendingSet {
+            asset: Asset,
+            params: Option<LpLendingParams<T::Balance>>,
+        },
     }
 
     #[pallet::call]
@@ -259,6 +393,128 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Puts `asset` pool into isolation mode: the asset can be borrowed only against
+        /// `collateral` assets and has zero collateral weight. `None` returns the pool
+        /// into the cross-margin mode.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_isolated_pool())]
+        pub fn set_isolated_pool(
+            origin: OriginFor<T>,
+            asset: Asset,
+            collateral: Option<Vec<Asset>>,
+        ) -> DispatchResultWithPostInfo {
+            T::IsolationOrigin::ensure_origin(origin)?;
+            T::AssetGetter::get_asset_data(&asset)?;
+
+            match collateral.clone() {
+                Some(collateral) => {
+                    for collateral_asset in collateral.iter() {
+                        T::AssetGetter::get_asset_data(collateral_asset)?;
+                        ensure!(
+                            *collateral_asset != asset
+                                && !IsolatedPools::<T>::contains_key(collateral_asset),
+                            Error::<T>::WrongIsolatedCollateral
+                        );
+                    }
+                    ensure!(
+                        !IsolatedPools::<T>::iter_values().any(|c| c.contains(&asset)),
+                        Error::<T>::WrongIsolatedCollateral
+                    );
+                    let collateral: BoundedVec<_, _> = collateral
+                        .try_into()
+                        .map_err(|_| Error::<T>::TooManyIsolatedCollateral)?;
+                    IsolatedPools::<T>::insert(asset, collateral);
+                }
+                None => IsolatedPools::<T>::remove(asset),
+            }
+
+            Self::deposit_event(Event::<T>::IsolatedPoolSet { asset, collateral });
+            Ok(().into())
+        }
+
+        /// Sets the part of `asset` pool rewards which goes into the pool reserve
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_reserve_factor())]
+        pub fn set_reserve_factor(
+            origin: OriginFor<T>,
+            asset: Asset,
+            factor: Permill,
+        ) -> DispatchResultWithPostInfo {
+            T::ReserveOrigin::ensure_origin(origin)?;
+            T::AssetGetter::get_asset_data(&asset)?;
+
+            if factor.is_zero() {
+                ReserveFactors::<T>::remove(asset);
+            } else {
+                ReserveFactors::<T>::insert(asset, factor);
+            }
+
+            Self::deposit_event(Event::<T>::ReserveFactorSet { asset, factor });
+            Ok(().into())
+        }
+
+        /// Transfers `amount` of `asset` pool reserve to the Treasury account
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::withdraw_reserve())]
+        pub fn withdraw_reserve(
+            origin: OriginFor<T>,
+            asset: Asset,
+            amount: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            T::ReserveOrigin::ensure_origin(origin)?;
+            let reserve = Reserves::<T>::get(asset);
+            let rest = reserve
+                .checked_sub(&amount)
+                .ok_or(Error::<T>::NotEnoughReserve)?;
+
+            T::EqCurrency::currency_transfer(
+                &T::ModuleId::get().into_account_truncating(),
+                &T::TreasuryModuleId::get().into_account_truncating(),
+                T::AssetGetter::get_main_asset(),
+                amount,
+                ExistenceRequirement::KeepAlive,
+                eq_primitives::TransferReason::Common,
+                true,
+            )?;
+            if rest.is_zero() {
+                Reserves::<T>::remove(asset);
+            } else {
+                Reserves::<T>::insert(asset, rest);
+            }
+
+            Self::deposit_event(Event::<T>::ReserveWithdrawn { asset, amount });
+            Ok(().into())
+        }
+
+        /// Approves Curve LP token `asset` for lending with `params`. The LP token must be
+        /// priced by the oracle. `None` revokes the approval: existing deposits can be
+        /// withdrawn, but new deposits and borrowing from lenders are not allowed.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::set_lp_lending())]
+        pub fn set_lp_lending(
+            origin: OriginFor<T>,
+            asset: Asset,
+            params: Option<LpLendingParams<T::Balance>>,
+        ) -> DispatchResultWithPostInfo {
+            T::LpLendingOrigin::ensure_origin(origin)?;
+            let asset_data = T::AssetGetter::get_asset_data(&asset)?;
+            ensure!(
+                matches!(asset_data.asset_type, AssetType::Lp(AmmPool::Curve(_))),
+                Error::<T>::WrongAssetType
+            );
+
+            match params {
+                Some(params) => {
+                    T::PriceGetter::get_price::<EqFixedU128>(&asset)?;
+                    LpLendingPools::<T>::insert(asset, params);
+                }
+                None => LpLendingPools::<T>::remove(asset),
+            }
+
+            Self::deposit_event(Event::<T>::LpLendingSet { asset, params });
+            Ok(().into())
+        }
     }
 
     #[pallet::hooks]
@@ -339,12 +595,46 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Checks that `value` of `asset` can be deposited into the lending pool
+    fn ensure_lendable(asset: &Asset, value: T::Balance) -> DispatchResult {
+        let asset_data = T::AssetGetter::get_asset_data(asset)?;
+        match asset_data.asset_type {
+            AssetType::Physical => Ok(()),
+            AssetType::Lp(AmmPool::Curve(_)) => {
+                let params = LpLendingPools::<T>::get(asset).ok_or(Error::<T>::WrongAssetType)?;
+                // LP token price is derived from the pool virtual price by the oracle
+                T::PriceGetter::get_price::<EqFixedU128>(asset)?;
+                let total = <LendersAggregates<T>>::get(asset)
+                    .checked_add(&value)
+                    .ok_or(Error::<T>::Overflow)?;
+                ensure!(
+                    total <= params.deposit_cap,
+                    Error::<T>::LpDepositCapExceeded
+                );
+                Ok(())
+            }
+            _ => Err(Error::<T>::WrongAssetType.into()),
+        }
+    }
+
+    /// Part of `lendable` LP tokens which can be borrowed. LP tokens of not approved pools,
+    /// without oracle price or with a suspect one can't be borrowed from lenders.
+    fn lp_borrowable(asset: &Asset, lendable: T::Balance) -> T::Balance {
+        match LpLendingPools::<T>::get(asset) {
+            Some(params)
+                if T::PriceGetter::get_price::<EqFixedU128>(asset).is_ok()
+                    && !T::PriceGetter::is_price_suspect(asset) =>
+            {
+                (Permill::one() - params.haircut).mul_floor(lendable)
+            }
+            _ => T::Balance::zero(),
+        }
+    }
+
     fn do_deposit(who: &T::AccountId, asset: Asset, value: T::Balance) -> DispatchResult {
-        let asset_data = T::AssetGetter::get_asset_data(&asset)?;
-        ensure!(
-            asset_data.asset_type == AssetType::Physical,
-            Error::<T>::WrongAssetType
-        );
+        Self::ensure_lendable(&asset, value)?;
+
+        Self::accrue_reward(asset)?;
 
         let mut lender = Self::get_lender(who, &asset)
             .unwrap_or_else(|| LenderData::default_per_asset::<T>(asset));
@@ -434,12 +724,9 @@ impl<T: Config> Pallet<T> {
     }
 
     fn do_add_deposit(who: &T::AccountId, asset: &Asset, amount: &T::Balance) -> DispatchResult {
-        let asset_data = T::AssetGetter::get_asset_data(asset)?;
+        Self::ensure_lendable(asset, *amount)?;
 
-        ensure!(
-            asset_data.asset_type == AssetType::Physical,
-            Error::<T>::WrongAssetType
-        );
+        Self::accrue_reward(*asset)?;
 
         let mut lender = Self::get_lender(who, asset)
             .unwrap_or_else(|| LenderData::default_per_asset::<T>(*asset));
@@ -479,26 +766,198 @@ impl<T: Config> Pallet<T> {
             total_lendable > T::Balance::zero(),
             Error::<T>::NoLendersToClaim
         );
-
-        let diff_reward = EqFixedU128::checked_from_rational(reward, total_lendable)
-            .ok_or(Error::<T>::Overflow)?;
         let main_asset = T::AssetGetter::get_main_asset();
+        ensure!(
+            main_asset == EQ || main_asset == Q,
+            Error::<T>::WrongAssetType
+        );
 
-        match main_asset {
-            EQ => <CumulatedReward<T>>::try_mutate(asset, |cumulated| -> DispatchResult {
-                *cumulated = cumulated
-                    .checked_add(&diff_reward)
-                    .ok_or(Error::<T>::Overflow)?;
-                Ok(())
-            }),
-            Q => <QCumulatedReward<T>>::try_mutate(asset, |cumulated| -> DispatchResult {
-                *cumulated = cumulated
-                    .checked_add(&diff_reward)
+        Self::accrue_reward(asset)?;
+
+        let reserve_part = ReserveFactors::<T>::get(asset).mul_floor(reward);
+        if !reserve_part.is_zero() {
+            Reserves::<T>::try_mutate(asset, |reserve| -> DispatchResult {
+                *reserve = reserve
+                    .checked_add(&reserve_part)
                     .ok_or(Error::<T>::Overflow)?;
                 Ok(())
-            }),
-            _ => Err(Error::<T>::WrongAssetType.into()),
+            })?;
+        }
+        let reward = reward - reserve_part;
+        if reward.is_zero() {
+            return Ok(());
         }
+
+        let period: T::Balance = T::RewardAccrualPeriod::get().saturated_into();
+        if period.is_zero() {
+            let diff_reward = EqFixedU128::checked_from_rational(reward, total_lendable)
+                .ok_or(Error::<T>::Overflow)?;
+            let index = Self::stored_index(asset, main_asset)
+                .checked_add(&diff_reward)
+                .ok_or(Error::<T>::Overflow)?;
+            Self::put_index(asset, main_asset, index);
+
+            return Ok(());
+        }
+
+        <RewardStreams<T>>::try_mutate(asset, |stream| -> DispatchResult {
+            stream.undistributed = stream
+                .undistributed
+                .checked_add(&reward)
+                .ok_or(Error::<T>::Overflow)?;
+            // rounding up, so the whole reward is accrued within the period
+            stream.per_second = stream
+                .undistributed
+                .saturating_add(period - T::Balance::one())
+                / period;
+            stream.last_accrual = T::UnixTime::now().as_secs();
+            Ok(())
+        })
+    }
+
+    fn stored_index(asset: Asset, main_asset: Asset) -> EqFixedU128 {
+        match main_asset {
+            EQ => <CumulatedReward<T>>::get(asset),
+            Q => <QCumulatedReward<T>>::get(asset),
+            _ => EqFixedU128::zero(),
+        }
+    }
+
+    fn put_index(asset: Asset, main_asset: Asset, index: EqFixedU128) {
+        if Self::stored_index(asset, main_asset) == index {
+            return;
+        }
+        match main_asset {
+            EQ => <CumulatedReward<T>>::insert(asset, index),
+            Q => <QCumulatedReward<T>>::insert(asset, index),
+            _ => return,
+        }
+        Self::record_checkpoint(asset, index);
+    }
+
+    /// Stores `index` as the reward index of `asset` as of the current block, dropping
+    /// the oldest checkpoint if the history is full
+    fn record_checkpoint(asset: Asset, index: EqFixedU128) {
+        let block = frame_system::Pallet::<T>::block_number();
+        let timestamp = T::UnixTime::now().as_secs();
+
+        <RewardCheckpoints<T>>::mutate(asset, |checkpoints| match checkpoints.last_mut() {
+            Some(last) if last.block == block => {
+                last.timestamp = timestamp;
+                last.index = index;
+            }
+            _ => {
+                if checkpoints.is_full() && !checkpoints.is_empty() {
+                    checkpoints.remove(0);
+                }
+                let _ = checkpoints.try_push(RewardCheckpoint {
+                    block,
+                    timestamp,
+                    index,
+                });
+            }
+        });
+        Self::deposit_event(Event::<T>::RewardAccrued { asset, index });
+    }
+
+    /// Reward index of `asset` as of the end of `block`, taken from the checkpoints history.
+    /// Rewards streamed but not accrued by the end of the block are not included.
+    /// Returns `None` if `block` precedes the oldest kept checkpoint.
+    pub fn reward_index_at(asset: Asset, block: T::BlockNumber) -> Option<EqFixedU128> {
+        let checkpoints = <RewardCheckpoints<T>>::get(asset);
+        match checkpoints.partition_point(|c| c.block <= block) {
+            0 => None,
+            pos => Some(checkpoints[pos - 1].index),
+        }
+    }
+
+    /// Reward in main asset accrued to a `deposit` of `asset` kept in the pool from the end
+    /// of `from` block till the end of `to` block. Returns `None` if `from` precedes the
+    /// oldest kept checkpoint.
+    pub fn reward_between(
+        asset: Asset,
+        deposit: T::Balance,
+        from: T::BlockNumber,
+        to: T::BlockNumber,
+    ) -> Option<T::Balance> {
+        let start = Self::reward_index_at(asset, from)?;
+        let end = Self::reward_index_at(asset, to.max(from))?;
+        Some(end.saturating_sub(start).saturating_mul_int(deposit))
+    }
+
+    /// Reward index of `asset` with the part of reward stream accrued since the last accrual.
+    /// Returns the index and the rest of the stream, doesn't change storage.
+    fn streamed_index(
+        asset: Asset,
+        main_asset: Asset,
+    ) -> Result<(EqFixedU128, RewardStream<T::Balance>), DispatchError> {
+        let mut index = Self::stored_index(asset, main_asset);
+        let mut stream = <RewardStreams<T>>::get(asset);
+        let now = T::UnixTime::now().as_secs();
+        let total_lendable = <LendersAggregates<T>>::get(asset);
+
+        // without lenders the stream is paused until the next deposit
+        if !stream.undistributed.is_zero() && !total_lendable.is_zero() {
+            let elapsed: T::Balance = now.saturating_sub(stream.last_accrual).saturated_into();
+            let accrued = stream
+                .per_second
+                .saturating_mul(elapsed)
+                .min(stream.undistributed);
+            let diff_reward = EqFixedU128::checked_from_rational(accrued, total_lendable)
+                .ok_or(Error::<T>::Overflow)?;
+
+            index = index
+                .checked_add(&diff_reward)
+                .ok_or(Error::<T>::Overflow)?;
+            stream.undistributed = stream.undistributed - accrued;
+        }
+        stream.last_accrual = now;
+
+        Ok((index, stream))
+    }
+
+    /// Moves the part of reward stream accrued till now into the reward index.
+    /// Must be called before any change of lender deposits or `LendersAggregates`.
+    fn accrue_reward(asset: Asset) -> DispatchResult {
+        if !<RewardStreams<T>>::contains_key(asset) {
+            return Ok(());
+        }
+
+        let main_asset = T::AssetGetter::get_main_asset();
+        let (index, stream) = Self::streamed_index(asset, main_asset)?;
+        Self::put_index(asset, main_asset, index);
+        if stream.undistributed.is_zero() {
+            <RewardStreams<T>>::remove(asset);
+        } else {
+            <RewardStreams<T>>::insert(asset, stream);
+        }
+
+        Ok(())
+    }
+
+    /// Current reward index of `asset` including not yet accrued part of the reward stream
+    pub fn reward_index(asset: Asset) -> EqFixedU128 {
+        let main_asset = T::AssetGetter::get_main_asset();
+        Self::streamed_index(asset, main_asset)
+            .map(|(index, _)| index)
+            .unwrap_or_else(|_| Self::stored_index(asset, main_asset))
+    }
+
+    /// Reward in main asset that `who` would get on payout for `asset` deposit
+    pub fn pending_reward(who: &T::AccountId, asset: Asset) -> T::Balance {
+        let lender = match Self::get_lender(who, &asset) {
+            Some(lender) => lender,
+            None => return T::Balance::zero(),
+        };
+        let last_reward = match T::AssetGetter::get_main_asset() {
+            EQ => lender.last_reward,
+            Q => lender.q_last_reward,
+            _ => return T::Balance::zero(),
+        };
+
+        Self::reward_index(asset)
+            .saturating_sub(last_reward)
+            .saturating_mul_int(lender.value)
     }
 
     fn try_payout(
@@ -506,6 +965,7 @@ impl<T: Config> Pallet<T> {
         lender: &mut LenderData<T::Balance>,
         asset: Asset,
     ) -> Result<(), DispatchError> {
+        Self::accrue_reward(asset)?;
         let main_asset = T::AssetGetter::get_main_asset();
 
         if let Some(payout) = Self::calc_payout(lender, asset, main_asset) {
@@ -559,6 +1019,18 @@ impl<T: Config> Pallet<T> {
         (!payout.is_zero()).then(|| payout)
     }
 
+    /// Deposits of `who` in lending pool with not paid out rewards, used in runtime API
+    pub fn lender_positions(who: &T::AccountId) -> Vec<LendingPosition<T::Balance>> {
+        QLenders::<T>::iter_prefix(who)
+            .chain(Lenders::<T>::iter_prefix(who))
+            .map(|(asset, lender)| LendingPosition {
+                asset,
+                deposit: lender.value,
+                pending_reward: Self::pending_reward(who, asset),
+            })
+            .collect()
+    }
+
     pub fn get_total_collat(asset: Asset) -> T::Balance {
         T::Aggregates::get_total(UserGroup::Balances, asset).collateral
     }
@@ -579,10 +1051,75 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Totals of `asset` over all accounts and its lending pool, used in runtime API
+    pub fn asset_solvency(asset: Asset) -> AssetSolvency<T::Balance> {
+        let debt = Self::get_total_debt(asset);
+        let (lenders_lendable, bails_lendable) = Self::get_lendable_parts(asset);
+        let lendable = lenders_lendable.saturating_add(bails_lendable);
+
+        AssetSolvency {
+            asset,
+            collateral: Self::get_total_collat(asset),
+            debt,
+            lent: <LendersAggregates<T>>::get(asset),
+            lending_utilization: (!lendable.is_zero())
+                .then(|| EqFixedU128::saturating_from_rational(debt, lendable)),
+        }
+    }
+
     fn is_only_bailsmen_period() -> bool {
         T::UnixTime::now().as_secs() < OnlyBailsmanTill::<T>::get()
     }
 
+    /// Checks that isolated assets borrowed by `who` after `changes` are backed only by
+    /// their allowed collateral
+    fn check_isolated_borrow(
+        who: &T::AccountId,
+        changes: &Vec<(Asset, SignedBalance<T::Balance>)>,
+    ) -> DispatchResult {
+        let mut balances = T::BalanceGetter::iterate_account_balances(who);
+        for (asset, change) in changes.iter() {
+            balances
+                .entry(*asset)
+                .and_modify(|balance| *balance = balance.clone() + change.clone())
+                .or_insert(change.clone());
+        }
+
+        // checked whatever the changes are, otherwise collateral could be swapped with
+        // a deposit followed by a withdrawal while the debt stays the same.
+        // An account may borrow several isolated assets against their common collateral
+        let mut allowed: Option<Vec<Asset>> = None;
+        for (asset, balance) in balances.iter() {
+            if !matches!(balance, SignedBalance::Negative(debt) if !debt.is_zero()) {
+                continue;
+            }
+            if let Some(collateral) = IsolatedPools::<T>::get(asset) {
+                allowed = Some(match allowed {
+                    Some(mut allowed) => {
+                        allowed.retain(|a| collateral.contains(a));
+                        allowed
+                    }
+                    None => collateral.into_inner(),
+                });
+            }
+        }
+        let allowed = match allowed {
+            Some(allowed) => allowed,
+            None => return Ok(()),
+        };
+
+        for (asset, balance) in balances.iter() {
+            if let SignedBalance::Positive(value) = balance {
+                ensure!(
+                    value.is_zero() || allowed.contains(asset),
+                    Error::<T>::IsolatedCollateralNotAllowed
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     fn check_bails_pool_after_unreg(who: &T::AccountId) -> DispatchResult {
         T::BalanceGetter::iterate_account_balances(who)
             .into_iter()
@@ -629,7 +1166,8 @@ impl<T: Config> BalanceChecker<T::Balance, T::AccountId, T::BalanceGetter, T::Su
         who: &T::AccountId,
         initial_changes: &Vec<(Asset, SignedBalance<T::Balance>)>,
         _withdraw_reasons: Option<WithdrawReasons>,
-    ) -> DispatchResult {
+    ) -> Result<(), BalanceCheckError<T::Balance>> {
+        let fail = |e: DispatchError| BalanceCheckError::new(BalanceCheckerKind::Lending, e);
         // All lenders are on lending pool account
         let is_lender = who == &T::ModuleId::get().into_account_truncating();
         let is_bailsman = T::Aggregates::in_usergroup(who, UserGroup::Bailsmen);
@@ -649,16 +1187,20 @@ impl<T: Config> BalanceChecker<T::Balance, T::AccountId, T::BalanceGetter, T::Su
                             line!(),
                             who
                         );
-                        err
+                        fail(err)
                     },
                 )?;
             if should_unreg {
-                Self::check_bails_pool_after_unreg(who)?;
+                Self::check_bails_pool_after_unreg(who).map_err(fail)?;
             }
         }
 
+        if !is_lender {
+            Self::check_isolated_borrow(who, initial_changes).map_err(fail)?;
+        }
+
         for (asset, change) in initial_changes.iter() {
-            let asset_data = T::AssetGetter::get_asset_data(&asset)?;
+            let asset_data = T::AssetGetter::get_asset_data(&asset).map_err(fail)?;
 
             match (change, asset_data.asset_type) {
                 // we allow to generate debt in Synthetic asset with no weight limit
@@ -679,7 +1221,10 @@ impl<T: Config> BalanceChecker<T::Balance, T::AccountId, T::BalanceGetter, T::Su
 
                     // Bails debt can be created only from margin calls. Total debt doesn't increase in this case.
                     if is_bailsman {
-                        ensure!(debt_inc.is_zero(), Error::<T>::BailsmanCantGenerateDebt);
+                        if !debt_inc.is_zero() {
+                            return Err(fail(Error::<T>::BailsmanCantGenerateDebt.into())
+                                .with_amounts(debt_inc, T::Balance::zero()));
+                        }
 
                         if asset_type == AssetType::Synthetic {
                             continue;
@@ -702,15 +1247,24 @@ impl<T: Config> BalanceChecker<T::Balance, T::AccountId, T::BalanceGetter, T::Su
                         if is_lender {
                             lenders_lendable -= collat_dec;
                         }
+                        // LP haircut limits borrowing only, lenders can withdraw while
+                        // the debt is covered even if LP token approval is revoked
+                        if !is_lender && matches!(asset_type, AssetType::Lp(AmmPool::Curve(_))) {
+                            lenders_lendable = Self::lp_borrowable(asset, lenders_lendable);
+                        }
 
                         asset_data.lending_debt_weight * lenders_lendable
                     };
-
-                    ensure!(
-                        new_total_debt <= max_debt_liquidity
-                            && total_debt + new_total_debt <= total_collat,
-                        Error::<T>::DebtExceedLiquidity
-                    );
+                    let max_debt_liquidity = T::RiskTierBorrowCaps::get()
+                        .get(T::AssetGetter::risk_tier(asset))
+                        .mul_floor(max_debt_liquidity);
+
+                    if new_total_debt > max_debt_liquidity
+                        || total_debt + new_total_debt > total_collat
+                    {
+                        return Err(fail(Error::<T>::DebtExceedLiquidity.into())
+                            .with_amounts(new_total_debt, max_debt_liquidity));
+                    }
                 }
                 _ => {}
             }
@@ -732,6 +1286,32 @@ impl<T: Config> eq_primitives::LendingPoolManager<T::Balance, T::AccountId> for
     fn remove_deposit(account: &T::AccountId, asset: &Asset) -> Result<T::Balance, DispatchError> {
         Self::do_remove_deposit(account, asset)
     }
+
+    fn supply(account: &T::AccountId, asset: &Asset, amount: &T::Balance) -> DispatchResult {
+        Self::do_deposit(account, *asset, *amount)?;
+        Self::deposit_event(Event::<T>::Deposit {
+            who: account.clone(),
+            asset: *asset,
+            value: *amount,
+        });
+        Ok(())
+    }
+
+    fn redeem(account: &T::AccountId, asset: &Asset, amount: &T::Balance) -> DispatchResult {
+        Self::do_withdraw(account, *asset, *amount)?;
+        Self::deposit_event(Event::<T>::Withdraw {
+            who: account.clone(),
+            asset: *asset,
+            value: *amount,
+        });
+        Ok(())
+    }
+}
+
+impl<T: Config> LendingIsolation for Pallet<T> {
+    fn isolated_collateral(asset: &Asset) -> Option<Vec<Asset>> {
+        IsolatedPools::<T>::get(asset).map(|collateral| collateral.into_inner())
+    }
 }
 
 impl<T: Config> eq_primitives::LendingAssetRemoval<T::AccountId> for Pallet<T> {
@@ -739,6 +1319,10 @@ impl<T: Config> eq_primitives::LendingAssetRemoval<T::AccountId> for Pallet<T> {
         let main_asset = T::AssetGetter::get_main_asset();
 
         LendersAggregates::<T>::remove(asset);
+        RewardStreams::<T>::remove(asset);
+        IsolatedPools::<T>::remove(asset);
+        ReserveFactors::<T>::remove(asset);
+        LpLendingPools::<T>::remove(asset);
 
         match main_asset {
             EQ => CumulatedReward::<T>::remove(asset),
@@ -48,6 +48,7 @@ use std::cell::RefCell;
 type AccountId = u64;
 pub(crate) type Balance = eq_primitives::balance::Balance;
 pub(crate) type OracleMock = eq_primitives::price::mock::OracleMock<AccountId>;
+pub const LP_ASSET: Asset = Asset(0x6c7030); //::from_bytes(b"lp0");
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -176,7 +177,17 @@ impl MarginCallManager<AccountId, Balance> for MarginCallManagerMock {
 parameter_types! {
     pub const MaxBailsmenToDistribute: u32 = 1;
     pub const QueueLengthWeightConstant: u32 = 5;
+    pub const RedistributionWeightBudget: Weight = Weight::zero();
+    pub const MaxPriorityRedistributionsPerBlock: u32 = 10;
     pub const AccountsToMigratePerBlock: u32 = 2;
+    pub const MaxIsolatedCollateral: u32 = 5;
+    pub const MaxRewardCheckpoints: u32 = 3;
+    pub RiskTierBorrowCaps: eq_primitives::asset::RiskTierParams<Permill> =
+        eq_primitives::asset::RiskTierParams {
+            tier1: Permill::from_percent(100),
+            tier2: Permill::from_percent(100),
+            tier3: Permill::from_percent(50),
+        };
 }
 
 impl<LocalCall> SendTransactionTypes<LocalCall> for Test
@@ -198,6 +209,7 @@ impl eq_bailsman::Config for Test {
     type MinimalCollateral = MinimalCollateral;
     type UnixTime = TimeMock;
     type PalletId = BailsmanModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
     type Aggregates = eq_aggregates::Pallet<Test>;
     type WeightInfo = ();
     type MarginCallManager = MarginCallManagerMock;
@@ -208,6 +220,8 @@ impl eq_bailsman::Config for Test {
     type UnsignedPriority = UnsignedPriority;
     type ValidatorOffchainBatcher = ();
     type QueueLengthWeightConstant = QueueLengthWeightConstant;
+    type RedistributionWeightBudget = RedistributionWeightBudget;
+    type MaxPriorityRedistributionsPerBlock = MaxPriorityRedistributionsPerBlock;
 }
 
 impl Config for Test {
@@ -223,7 +237,15 @@ impl Config for Test {
     type EqCurrency = EqBalances;
     type UnixTime = TimeMock;
     type AccountsToMigratePerBlock = AccountsToMigratePerBlock;
+    type RewardAccrualPeriod = RewardAccrualPeriodMock;
     type WeightInfo = ();
+    type IsolationOrigin = EnsureRoot<AccountId>;
+    type MaxIsolatedCollateral = MaxIsolatedCollateral;
+    type RiskTierBorrowCaps = RiskTierBorrowCaps;
+    type ReserveOrigin = EnsureRoot<AccountId>;
+    type TreasuryModuleId = TreasuryModuleId;
+    type MaxRewardCheckpoints = MaxRewardCheckpoints;
+    type LpLendingOrigin = EnsureRoot<AccountId>;
 }
 
 thread_local! {
@@ -237,6 +259,21 @@ thread_local! {
         (asset::DOT, FixedI64::saturating_from_integer(4))
         ]);
     static CURRENT_TIME: RefCell<u64> = RefCell::new(1_598_006_981_634);
+    static REWARD_ACCRUAL_PERIOD: RefCell<u64> = RefCell::new(0);
+}
+
+pub struct RewardAccrualPeriodMock;
+
+impl RewardAccrualPeriodMock {
+    pub fn set(period: u64) {
+        REWARD_ACCRUAL_PERIOD.with(|v| *v.borrow_mut() = period)
+    }
+}
+
+impl Get<u64> for RewardAccrualPeriodMock {
+    fn get() -> u64 {
+        REWARD_ACCRUAL_PERIOD.with(|v| *v.borrow())
+    }
 }
 
 pub struct EqBuyoutMock;
@@ -323,6 +360,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -350,6 +391,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         (asset::EQD, FixedI64::saturating_from_integer(1)),
         (asset::EQ, FixedI64::saturating_from_integer(1)),
         (asset::DOT, FixedI64::saturating_from_integer(4)),
+        (LP_ASSET, FixedI64::saturating_from_integer(2)),
     ]);
 
     let mut r = frame_system::GenesisConfig::default()
@@ -429,6 +471,20 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
                 Percent::one(),
                 Permill::one(),
             ),
+            (
+                LP_ASSET.get_id(),
+                EqFixedU128::from(0),
+                FixedI64::from(0),
+                Permill::zero(),
+                Permill::zero(),
+                vec![],
+                Permill::from_rational(2u32, 5u32),
+                7,
+                AssetType::Lp(asset::AmmPool::Curve(0)),
+                false,
+                Percent::one(),
+                Permill::one(),
+            ),
             (
                 asset::EQ.get_id(),
                 EqFixedU128::from(0),
@@ -460,6 +516,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
             ),
             (1, vec![(1_000, asset::BTC.get_id())]),
             (1, vec![(1_000, asset::ETH.get_id())]),
+            (7, vec![(1_000, LP_ASSET.get_id())]),
             (2, vec![(1_000, asset::ETH.get_id())]),
             (3, vec![(1_000, asset::EQD.get_id())]),
             (4, vec![(1_000, asset::ETH.get_id())]),
@@ -352,6 +352,71 @@ fn transfer_pos_prev_err() {
     });
 }
 
+#[test]
+fn transfer_debt_is_capped_by_risk_tier() {
+    new_test_ext().execute_with(|| {
+        let account_id_from = 21;
+        let account_id_to = 22;
+        let account_id_bails = 23;
+
+        let bails_collateral_btc = 50;
+
+        assert_ok!(ModuleBalances::deposit_creating(
+            &account_id_to,
+            asset::BTC,
+            1,
+            true,
+            None
+        ));
+        assert_ok!(ModuleBalances::deposit_creating(
+            &account_id_bails,
+            asset::BTC,
+            bails_collateral_btc,
+            true,
+            None
+        ));
+        assert_ok!(ModuleAggregates::set_usergroup(
+            &account_id_bails,
+            UserGroup::Bailsmen,
+            true
+        ));
+
+        // max_btc_debt is 10 for weight=0.2 and bails_collateral_btc=50,
+        // tier 3 assets can use only a half of it in mock
+        assert_ok!(EqAssets::set_risk_tier(
+            RuntimeOrigin::root(),
+            asset::BTC,
+            asset::RiskTier::Tier3
+        ));
+        let max_btc_debt = 5;
+
+        let prev = 2;
+        assert_ok!(ModuleBalances::deposit_creating(
+            &account_id_from,
+            asset::BTC,
+            prev,
+            true,
+            None
+        ));
+
+        assert_err!(
+            ModuleBalances::transfer(
+                RuntimeOrigin::signed(account_id_from),
+                asset::BTC,
+                account_id_to,
+                prev + max_btc_debt + 1
+            ),
+            Error::<Test>::DebtExceedLiquidity
+        );
+        assert_ok!(ModuleBalances::transfer(
+            RuntimeOrigin::signed(account_id_from),
+            asset::BTC,
+            account_id_to,
+            prev + max_btc_debt
+        ));
+    });
+}
+
 #[test]
 fn transfer_negative_prev_err() {
     new_test_ext().execute_with(|| {
@@ -1526,3 +1591,529 @@ fn on_initialize() {
         );
     });
 }
+
+#[test]
+fn reward_is_streamed_during_accrual_period() {
+    new_test_ext().execute_with(|| {
+        OnlyBailsmanTill::<Test>::put(0);
+        RewardAccrualPeriodMock::set(100);
+        let start = TimeMock::now().as_secs();
+
+        use eq_primitives::LendingPoolManager as _;
+
+        assert_ok!(EqLending::deposit(RuntimeOrigin::signed(1), ETH, 400));
+        assert_ok!(EqLending::deposit(RuntimeOrigin::signed(2), ETH, 600));
+
+        assert_ok!(EqLending::add_reward(ETH, 1000));
+        assert_eq!(EqLending::rewards(ETH), EqFixedU128::zero());
+        assert_eq!(EqLending::reward_index(ETH), EqFixedU128::zero());
+        assert_eq!(
+            EqLending::reward_stream(ETH),
+            RewardStream {
+                undistributed: 1000,
+                per_second: 10,
+                last_accrual: start,
+            }
+        );
+
+        TimeMock::set((start + 50) * 1000);
+        assert_eq!(
+            EqLending::reward_index(ETH),
+            EqFixedU128::saturating_from_rational(1, 2)
+        );
+        assert_eq!(EqLending::pending_reward(&1, ETH), 200);
+        assert_eq!(EqLending::pending_reward(&2, ETH), 300);
+
+        assert_ok!(EqLending::payout(RuntimeOrigin::signed(1), ETH, 1));
+        assert_eq!(EqBalances::total_balance(&1, asset::EQ), 200);
+        assert_eq!(EqLending::pending_reward(&1, ETH), 0);
+        assert_eq!(
+            EqLending::rewards(ETH),
+            EqFixedU128::saturating_from_rational(1, 2)
+        );
+        assert_eq!(EqLending::reward_stream(ETH).undistributed, 500);
+
+        // stream doesn't accrue more than was added
+        TimeMock::set((start + 200) * 1000);
+        assert_eq!(EqLending::reward_index(ETH), EqFixedU128::one());
+        assert_eq!(EqLending::pending_reward(&1, ETH), 200);
+        assert_eq!(EqLending::pending_reward(&2, ETH), 600);
+
+        assert_ok!(EqLending::withdraw(RuntimeOrigin::signed(2), ETH, 600));
+        assert_eq!(EqBalances::total_balance(&2, asset::EQ), 600);
+        assert_eq!(EqLending::rewards(ETH), EqFixedU128::one());
+        assert!(!RewardStreams::<Test>::contains_key(ETH));
+    });
+}
+
+#[test]
+fn lender_positions() {
+    new_test_ext().execute_with(|| {
+        OnlyBailsmanTill::<Test>::put(0);
+
+        use eq_primitives::LendingPoolManager as _;
+
+        assert!(EqLending::lender_positions(&1).is_empty());
+
+        assert_ok!(EqLending::deposit(RuntimeOrigin::signed(1), ETH, 400));
+        assert_ok!(EqLending::deposit(RuntimeOrigin::signed(2), ETH, 600));
+        assert_ok!(EqLending::add_reward(ETH, 10));
+
+        assert_eq!(
+            EqLending::lender_positions(&1),
+            vec![LendingPosition {
+                asset: ETH,
+                deposit: 400,
+                pending_reward: 4,
+            }]
+        );
+    });
+}
+
+#[test]
+fn reserve_factor_diverts_rewards_to_reserve() {
+    new_test_ext().execute_with(|| {
+        OnlyBailsmanTill::<Test>::put(0);
+
+        use eq_primitives::LendingPoolManager as _;
+
+        let pool: u64 = LendingModuleId::get().into_account_truncating();
+        let treasury: u64 = TreasuryModuleId::get().into_account_truncating();
+        assert_ok!(EqBalances::deposit_creating(
+            &pool,
+            asset::EQ,
+            1000,
+            true,
+            None
+        ));
+        let treasury_before = EqBalances::total_balance(&treasury, asset::EQ);
+
+        assert_ok!(EqLending::deposit(RuntimeOrigin::signed(1), ETH, 400));
+        assert_ok!(EqLending::deposit(RuntimeOrigin::signed(2), ETH, 600));
+
+        assert_noop!(
+            EqLending::set_reserve_factor(RuntimeOrigin::signed(1), ETH, Permill::from_percent(20)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(EqLending::set_reserve_factor(
+            RuntimeOrigin::root(),
+            ETH,
+            Permill::from_percent(20)
+        ));
+
+        assert_ok!(EqLending::add_reward(ETH, 100));
+        assert_eq!(EqLending::reserve(ETH), 20);
+        assert_eq!(
+            EqLending::rewards(ETH),
+            EqFixedU128::saturating_from_rational(80, 1000)
+        );
+        assert_eq!(EqLending::pending_reward(&1, ETH), 32);
+
+        assert_noop!(
+            EqLending::withdraw_reserve(RuntimeOrigin::root(), ETH, 21),
+            Error::<Test>::NotEnoughReserve
+        );
+        assert_ok!(EqLending::withdraw_reserve(RuntimeOrigin::root(), ETH, 15));
+        assert_eq!(EqLending::reserve(ETH), 5);
+        assert_eq!(
+            EqBalances::total_balance(&treasury, asset::EQ),
+            treasury_before + 15
+        );
+
+        assert_ok!(EqLending::set_reserve_factor(
+            RuntimeOrigin::root(),
+            ETH,
+            Permill::zero()
+        ));
+        assert!(!ReserveFactors::<Test>::contains_key(ETH));
+        assert_ok!(EqLending::add_reward(ETH, 100));
+        assert_eq!(EqLending::reserve(ETH), 5);
+    });
+}
+
+#[test]
+fn reward_checkpoints_track_index_per_block() {
+    new_test_ext().execute_with(|| {
+        OnlyBailsmanTill::<Test>::put(0);
+        System::set_block_number(1);
+        let now = TimeMock::now().as_secs();
+
+        use eq_primitives::LendingPoolManager as _;
+
+        assert_ok!(EqLending::deposit(RuntimeOrigin::signed(1), ETH, 400));
+        assert_ok!(EqLending::deposit(RuntimeOrigin::signed(2), ETH, 600));
+        assert!(EqLending::reward_checkpoints(ETH).is_empty());
+
+        // one checkpoint per block
+        assert_ok!(EqLending::add_reward(ETH, 100));
+        assert_ok!(EqLending::add_reward(ETH, 100));
+        let index = EqFixedU128::saturating_from_rational(2, 10);
+        assert_eq!(
+            EqLending::reward_checkpoints(ETH).into_inner(),
+            vec![RewardCheckpoint {
+                block: 1,
+                timestamp: now,
+                index,
+            }]
+        );
+        System::assert_last_event(Event::RewardAccrued { asset: ETH, index }.into());
+
+        System::set_block_number(2);
+        assert_ok!(EqLending::add_reward(ETH, 200));
+        System::set_block_number(4);
+        assert_ok!(EqLending::add_reward(ETH, 100));
+
+        assert_eq!(EqLending::reward_index_at(ETH, 0), None);
+        assert_eq!(
+            EqLending::reward_index_at(ETH, 3),
+            Some(EqFixedU128::saturating_from_rational(4, 10))
+        );
+        assert_eq!(EqLending::reward_between(ETH, 400, 1, 4), Some(120));
+        assert_eq!(EqLending::reward_between(ETH, 400, 2, 10), Some(40));
+        assert_eq!(EqLending::reward_between(ETH, 400, 4, 2), Some(0));
+
+        // the oldest checkpoint is dropped
+        System::set_block_number(5);
+        assert_ok!(EqLending::add_reward(ETH, 100));
+        let checkpoints = EqLending::reward_checkpoints(ETH);
+        assert_eq!(checkpoints.len(), 3);
+        assert_eq!(checkpoints[0].block, 2);
+        assert_eq!(EqLending::reward_between(ETH, 400, 1, 5), None);
+        assert_eq!(EqLending::reward_between(ETH, 600, 2, 5), Some(120));
+    });
+}
+
+#[test]
+fn isolated_pool_borrow_against_allowed_collateral() {
+    new_test_ext().execute_with(|| {
+        TimeMock::set(OnlyBailsmanTill::<Test>::get() * 1_000);
+
+        let main = 0;
+        let borr = 666;
+        let lender = 1;
+
+        assert_ok!(EqAggregates::set_usergroup(
+            &borr,
+            UserGroup::Balances,
+            true
+        ));
+        assert_ok!(EqLending::do_deposit(&lender, asset::BTC, 50));
+        assert_ok!(EqBalances::deposit_creating(
+            &borr,
+            asset::EOS,
+            100,
+            true,
+            None
+        ));
+
+        assert_noop!(
+            EqLending::set_isolated_pool(
+                RuntimeOrigin::signed(borr),
+                asset::BTC,
+                Some(vec![asset::ETH])
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            EqLending::set_isolated_pool(RuntimeOrigin::root(), asset::BTC, Some(vec![asset::BTC])),
+            Error::<Test>::WrongIsolatedCollateral
+        );
+        assert_ok!(EqLending::set_isolated_pool(
+            RuntimeOrigin::root(),
+            asset::BTC,
+            Some(vec![asset::ETH])
+        ));
+        assert!(EqLending::is_isolated(&asset::BTC));
+
+        // EOS is not allowed collateral for BTC
+        assert_err!(
+            EqBalances::currency_transfer(
+                &borr,
+                &main,
+                asset::BTC,
+                10,
+                frame_support::traits::ExistenceRequirement::KeepAlive,
+                eq_primitives::TransferReason::Common,
+                true
+            ),
+            Error::<Test>::IsolatedCollateralNotAllowed
+        );
+
+        assert_ok!(EqLending::set_isolated_pool(
+            RuntimeOrigin::root(),
+            asset::BTC,
+            Some(vec![asset::ETH, asset::EOS])
+        ));
+        assert_ok!(EqBalances::currency_transfer(
+            &borr,
+            &main,
+            asset::BTC,
+            10,
+            frame_support::traits::ExistenceRequirement::KeepAlive,
+            eq_primitives::TransferReason::Common,
+            true
+        ));
+        assert_eq!(
+            EqBalances::get_balance(&borr, &asset::BTC),
+            SignedBalance::Negative(10),
+        );
+
+        assert_ok!(EqLending::set_isolated_pool(
+            RuntimeOrigin::root(),
+            asset::BTC,
+            None
+        ));
+        assert_eq!(EqLending::isolated_pool(asset::BTC), None);
+    });
+}
+
+#[test]
+fn isolated_pool_collateral_cant_be_swapped_by_deposit_and_withdraw() {
+    new_test_ext().execute_with(|| {
+        TimeMock::set(OnlyBailsmanTill::<Test>::get() * 1_000);
+
+        let main = 0;
+        let borr = 666;
+        let lender = 1;
+
+        assert_ok!(EqAggregates::set_usergroup(
+            &borr,
+            UserGroup::Balances,
+            true
+        ));
+        assert_ok!(EqLending::do_deposit(&lender, asset::BTC, 50));
+        assert_ok!(EqBalances::deposit_creating(
+            &borr,
+            asset::ETH,
+            100,
+            true,
+            None
+        ));
+        assert_ok!(EqLending::set_isolated_pool(
+            RuntimeOrigin::root(),
+            asset::BTC,
+            Some(vec![asset::ETH])
+        ));
+        assert_ok!(EqBalances::currency_transfer(
+            &borr,
+            &main,
+            asset::BTC,
+            10,
+            frame_support::traits::ExistenceRequirement::KeepAlive,
+            eq_primitives::TransferReason::Common,
+            true
+        ));
+
+        assert_ok!(EqBalances::deposit_creating(
+            &main,
+            asset::EOS,
+            100,
+            true,
+            None
+        ));
+
+        // deposit of not allowed collateral doesn't lower isolated asset balance
+        assert_err!(
+            EqBalances::deposit_creating(&borr, asset::EOS, 100, true, None),
+            Error::<Test>::IsolatedCollateralNotAllowed
+        );
+        assert_err!(
+            EqBalances::currency_transfer(
+                &main,
+                &borr,
+                asset::EOS,
+                100,
+                frame_support::traits::ExistenceRequirement::KeepAlive,
+                eq_primitives::TransferReason::Common,
+                true
+            ),
+            Error::<Test>::IsolatedCollateralNotAllowed
+        );
+        assert_eq!(
+            EqBalances::get_balance(&borr, &asset::EOS),
+            SignedBalance::Positive(0),
+        );
+
+        // withdrawal of allowed collateral is still possible
+        assert_ok!(EqBalances::currency_transfer(
+            &borr,
+            &main,
+            asset::ETH,
+            10,
+            frame_support::traits::ExistenceRequirement::KeepAlive,
+            eq_primitives::TransferReason::Common,
+            true
+        ));
+
+        // after the debt is repaid any collateral is fine
+        assert_ok!(EqBalances::deposit_creating(
+            &borr,
+            asset::BTC,
+            10,
+            true,
+            None
+        ));
+        assert_ok!(EqBalances::deposit_creating(
+            &borr,
+            asset::EOS,
+            100,
+            true,
+            None
+        ));
+    });
+}
+
+#[test]
+fn asset_solvency() {
+    new_test_ext().execute_with(|| {
+        TimeMock::set(OnlyBailsmanTill::<Test>::get() * 1_000);
+
+        let main = 0;
+        let borr = 666;
+        let lender = 1;
+
+        assert_ok!(EqLending::do_deposit(&lender, asset::BTC, 50));
+        let solvency = EqLending::asset_solvency(asset::BTC);
+        assert_eq!(solvency.lent, 50);
+        assert_eq!(solvency.debt, 0);
+        assert_eq!(solvency.lending_utilization, Some(EqFixedU128::zero()));
+
+        assert_ok!(EqAggregates::set_usergroup(
+            &borr,
+            UserGroup::Balances,
+            true
+        ));
+        assert_ok!(EqBalances::deposit_creating(
+            &borr,
+            asset::ETH,
+            100,
+            true,
+            None
+        ));
+        assert_ok!(EqBalances::currency_transfer(
+            &borr,
+            &main,
+            asset::BTC,
+            10,
+            frame_support::traits::ExistenceRequirement::KeepAlive,
+            eq_primitives::TransferReason::Common,
+            true
+        ));
+
+        let (lenders_lendable, bails_lendable) = EqLending::get_lendable_parts(asset::BTC);
+        assert_eq!(
+            EqLending::asset_solvency(asset::BTC),
+            AssetSolvency {
+                asset: asset::BTC,
+                collateral: EqLending::get_total_collat(asset::BTC),
+                debt: 10,
+                lent: 50,
+                lending_utilization: Some(EqFixedU128::saturating_from_rational(
+                    10,
+                    lenders_lendable + bails_lendable
+                )),
+            }
+        );
+    });
+}
+
+#[test]
+fn lp_lending_for_approved_curve_pool() {
+    new_test_ext().execute_with(|| {
+        TimeMock::set(OnlyBailsmanTill::<Test>::get() * 1_000);
+        System::set_block_number(1);
+
+        let main = 0;
+        let borr = 666;
+        let lender = 7;
+        let params = LpLendingParams {
+            haircut: Permill::from_percent(50),
+            deposit_cap: 500,
+        };
+        let borrow = |amount| {
+            EqBalances::currency_transfer(
+                &borr,
+                &main,
+                LP_ASSET,
+                amount,
+                frame_support::traits::ExistenceRequirement::KeepAlive,
+                eq_primitives::TransferReason::Common,
+                true,
+            )
+        };
+
+        assert_noop!(
+            EqLending::deposit(RuntimeOrigin::signed(lender), LP_ASSET, 400),
+            Error::<Test>::WrongAssetType
+        );
+        assert_noop!(
+            EqLending::set_lp_lending(RuntimeOrigin::signed(lender), LP_ASSET, Some(params)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            EqLending::set_lp_lending(RuntimeOrigin::root(), asset::ETH, Some(params)),
+            Error::<Test>::WrongAssetType
+        );
+        assert_ok!(EqLending::set_lp_lending(
+            RuntimeOrigin::root(),
+            LP_ASSET,
+            Some(params)
+        ));
+        System::assert_last_event(
+            Event::LpLendingSet {
+                asset: LP_ASSET,
+                params: Some(params),
+            }
+            .into(),
+        );
+
+        assert_ok!(EqLending::deposit(
+            RuntimeOrigin::signed(lender),
+            LP_ASSET,
+            400
+        ));
+        assert_noop!(
+            EqLending::deposit(RuntimeOrigin::signed(lender), LP_ASSET, 101),
+            Error::<Test>::LpDepositCapExceeded
+        );
+
+        assert_ok!(EqAggregates::set_usergroup(
+            &borr,
+            UserGroup::Balances,
+            true
+        ));
+        assert_ok!(EqBalances::deposit_creating(
+            &borr,
+            asset::ETH,
+            100,
+            true,
+            None
+        ));
+
+        // 400 lent, 50% haircut and 50% borrow cap of Tier3
+        assert_err!(borrow(101), Error::<Test>::DebtExceedLiquidity);
+        assert_ok!(borrow(100));
+        assert_eq!(
+            EqBalances::get_balance(&borr, &LP_ASSET),
+            SignedBalance::Negative(100),
+        );
+
+        // revoked LP token can't be deposited or borrowed, but can be withdrawn
+        assert_ok!(EqLending::set_lp_lending(
+            RuntimeOrigin::root(),
+            LP_ASSET,
+            None
+        ));
+        assert_eq!(EqLending::lp_lending_params(LP_ASSET), None);
+        assert_noop!(
+            EqLending::deposit(RuntimeOrigin::signed(lender), LP_ASSET, 10),
+            Error::<Test>::WrongAssetType
+        );
+        assert_err!(borrow(1), Error::<Test>::DebtExceedLiquidity);
+        assert_ok!(EqLending::withdraw(
+            RuntimeOrigin::signed(lender),
+            LP_ASSET,
+            100
+        ));
+    });
+}
@@ -22,6 +22,10 @@ pub trait WeightInfo {
     fn deposit() -> Weight;
     fn withdraw() -> Weight;
     fn payout() -> Weight;
+    fn set_isolated_pool() -> Weight;
+    fn set_reserve_factor() -> Weight;
+    fn withdraw_reserve() -> Weight;
+    fn set_lp_lending() -> Weight;
 }
 
 impl WeightInfo for () {
@@ -34,4 +38,16 @@ impl WeightInfo for () {
     fn payout() -> Weight {
         Weight::zero()
     }
+    fn set_isolated_pool() -> Weight {
+        Weight::zero()
+    }
+    fn set_reserve_factor() -> Weight {
+        Weight::zero()
+    }
+    fn withdraw_reserve() -> Weight {
+        Weight::zero()
+    }
+    fn set_lp_lending() -> Weight {
+        Weight::zero()
+    }
 }
@@ -99,6 +99,7 @@ benchmarks! {
             authority_index: 0,
             validators_len: 1,
             block_num: T::BlockNumber::default(),
+            nonce: 0,
         };
         let key = <T as eq_rate::Config>::AuthorityId::generate_pair(None);
         let signature = key.sign(&request.encode()).unwrap();
@@ -198,6 +199,7 @@ benchmarks! {
             authority_index: 0,
             validators_len: 1,
             block_num: T::BlockNumber::default(),
+            nonce: 0,
         };
         let validator = <T as eq_rate::Config>::AuthorityId::generate_pair(None);
         eq_rate::Keys::<T>::set(vec![validator.clone()]);
@@ -214,4 +216,40 @@ benchmarks! {
         let default = <T as eq_rate::Config>::Balance::default();
         assert_eq!(caller_lock, default);
     }
+
+    early_unlock{
+        let price_setter: T::AccountId = account("price_setter", 0, SEED);
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone()).unwrap();
+        for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
+            <eq_oracle::Pallet::<T> as PriceSetter<_>>::set_price(price_setter.clone(), curr, FixedI64::one()).unwrap();
+        }
+
+        let caller: T::AccountId = whitelisted_caller();
+        let balance: <T as eq_rate::Config>::Balance = BUDGET.try_into().map_err(|_|"balance conversion error").unwrap();
+        let _ = Locks::<T>::mutate(caller.clone(), |value| *value = balance);
+        let _ = LockStart::<T>::mutate(|start| *start = Some(10u64));
+
+        let lockdrop_acc_id = PalletId(*b"eq/lkdrp").into_account_truncating();
+        let amount = (5*BUDGET).try_into().map_err(|_|"balance conversion error").unwrap();
+        eq_balances::Pallet::<T>::deposit_creating(&lockdrop_acc_id, asset::EQ, amount, true, None).unwrap();
+        System::<T>::set_block_number(1u32.into());
+    }: _(RawOrigin::Signed(caller))
+    verify {
+        let caller: T::AccountId = whitelisted_caller();
+        let caller_lock: <T as eq_rate::Config>::Balance = Locks::<T>::get(caller);
+        let default = <T as eq_rate::Config>::Balance::default();
+        assert_eq!(caller_lock, default);
+    }
+
+    transfer_lock{
+        let caller: T::AccountId = whitelisted_caller();
+        let to: T::AccountId = account("user", 0, SEED);
+        let balance: <T as eq_rate::Config>::Balance = BUDGET.try_into().map_err(|_|"balance conversion error").unwrap();
+        let _ = Locks::<T>::mutate(caller.clone(), |value| *value = balance);
+        let _ = Locks::<T>::mutate(to.clone(), |value| *value = balance);
+    }: _(RawOrigin::Signed(caller), to.clone())
+    verify {
+        let balance: <T as eq_rate::Config>::Balance = (2 * BUDGET).try_into().map_err(|_|"balance conversion error").unwrap();
+        assert_eq!(Locks::<T>::get(to), balance);
+    }
 }
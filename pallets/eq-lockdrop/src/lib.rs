@@ -22,7 +22,7 @@
 use eq_primitives::balance::EqCurrency;
 use eq_primitives::{
     asset,
-    offchain_batcher::{OffchainErr, OffchainResult},
+    offchain_batcher::{OffchainErr, OffchainResult, UnsignedDuty, ValidatorOffchainBatcher},
     TransferReason, Vesting,
 };
 use eq_utils::{eq_ensure, ok_or_error};
@@ -33,7 +33,7 @@ use frame_support::{
 };
 use frame_system::offchain::SubmitTransaction;
 use sp_application_crypto::RuntimeAppPublic;
-use sp_runtime::traits::AccountIdConversion;
+use sp_runtime::traits::{AccountIdConversion, Saturating};
 use sp_runtime::{DispatchError, RuntimeDebug};
 
 pub use pallet::*;
@@ -64,6 +64,8 @@ where
     pub validators_len: u32,
     /// Number of a block
     pub block_num: BlockNumber,
+    /// Nonce of the `UnsignedDuty::LockdropUnlock` duty of the authority
+    pub nonce: u64,
 }
 
 #[frame_support::pallet]
@@ -73,6 +75,7 @@ pub mod pallet {
     use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*, PalletId};
     use frame_system::{offchain::SendTransactionTypes, pallet_prelude::*};
     use sp_application_crypto::RuntimeAppPublic;
+    use sp_runtime::Permill;
 
     use crate::{OperationRequest, WeightInfo};
 
@@ -95,6 +98,9 @@ pub mod pallet {
         /// Used for calculation unsigned transaction priority
         #[pallet::constant]
         type LockDropUnsignedPriority: Get<TransactionPriority>;
+        /// Part of the locked amount paid to the Treasury for unlock before the end of lock program
+        #[pallet::constant]
+        type EarlyUnlockPenalty: Get<Permill>;
 
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// Used to update accounts locks
@@ -150,6 +156,12 @@ pub mod pallet {
         /// User `who` unlocks `amount` of Eq
         /// \[who, amount\]
         Unlock(T::AccountId, T::Balance),
+        /// User `who` unlocks `amount` of Eq before the end of lock program and pays `penalty`
+        /// \[who, amount, penalty\]
+        EarlyUnlock(T::AccountId, T::Balance, T::Balance),
+        /// Locked position of `amount` Eq moved from `from` to `to`
+        /// \[from, to, amount\]
+        LockTransferred(T::AccountId, T::AccountId, T::Balance),
     }
 
     #[pallet::error]
@@ -164,6 +176,12 @@ pub mod pallet {
         MultipleTransferWithVesting,
         /// Lock amount is lower than minimum allowed
         LockAmountLow,
+        /// Account has no locked Eq
+        NoLock,
+        /// Lock program is over, regular unlock should be used
+        LockPeriodOver,
+        /// Not allowed to transfer locked position to the same account
+        LockTransferToSelf,
     }
 
     #[pallet::hooks]
@@ -246,6 +264,11 @@ pub mod pallet {
             _signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            T::ValidatorOffchainBatcher::note_duty(
+                request.authority_index,
+                UnsignedDuty::LockdropUnlock,
+                request.nonce,
+            );
 
             log::trace!(
                 target: "eq_lockdrop",
@@ -297,6 +320,30 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Unlock all account's locked Eq before the end of lock program.
+        /// `EarlyUnlockPenalty` part of the lock is transferred to the Treasury.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::early_unlock())]
+        pub fn early_unlock(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::do_early_unlock(who)?;
+
+            Ok(().into())
+        }
+
+        /// Move all account's locked Eq to `to`, e.g. for OTC sale of the position.
+        /// Locked Eq stays in the pallet till the end of lock program.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_lock())]
+        pub fn transfer_lock(origin: OriginFor<T>, to: T::AccountId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::do_transfer_lock(who, to)?;
+
+            Ok(().into())
+        }
     }
 
     #[pallet::validate_unsigned]
@@ -323,12 +370,18 @@ pub mod pallet {
                     if !signature_valid {
                         return InvalidTransaction::BadProof.into();
                     }
+                    let duty = T::ValidatorOffchainBatcher::validate_duty(
+                        request.authority_index,
+                        UnsignedDuty::LockdropUnlock,
+                        request.nonce,
+                    )?;
 
                     let priority = T::LockDropUnsignedPriority::get();
 
                     ValidTransaction::with_tag_prefix("Lkdrp")
                         .priority(priority)
                         .and_provides(request.account.clone())
+                        .combine_with(duty)
                         .longevity(64)
                         .propagate(true)
                         .build()
@@ -531,6 +584,105 @@ impl<T: Config> Pallet<T> {
         Ok(locked)
     }
 
+    /// Inner function that returns locked Eq to `who` without waiting for the end of lock
+    /// program, penalty goes to the Treasury
+    fn do_early_unlock(who: T::AccountId) -> DispatchResultWithPostInfo {
+        eq_ensure!(
+            !Self::is_lock_over(),
+            Error::<T>::LockPeriodOver,
+            target: "eq_lockdrop",
+            "{}:{}. Lock program is over, early unlock is not needed. Who: {:?}",
+            file!(),
+            line!(),
+            who,
+        );
+        eq_ensure!(
+            Self::has_lock(&who),
+            Error::<T>::NoLock,
+            target: "eq_lockdrop",
+            "{}:{}. Account has no lock. Who: {:?}",
+            file!(),
+            line!(),
+            who,
+        );
+
+        let locked = Self::locks(&who);
+        let penalty = T::EarlyUnlockPenalty::get().mul_ceil(locked);
+        let unlocked = locked.saturating_sub(penalty);
+
+        let treasury_account_id =
+            <T as eq_rate::Config>::TreasuryModuleId::get().into_account_truncating();
+        T::EqCurrency::currency_transfer(
+            &Self::get_account_id(),
+            &treasury_account_id,
+            asset::EQ,
+            penalty,
+            ExistenceRequirement::AllowDeath,
+            TransferReason::Unlock,
+            true,
+        )?;
+        T::EqCurrency::currency_transfer(
+            &Self::get_account_id(),
+            &who,
+            asset::EQ,
+            unlocked,
+            ExistenceRequirement::AllowDeath,
+            TransferReason::Unlock,
+            true,
+        )?;
+
+        <Locks<T>>::remove(&who);
+
+        Self::deposit_event(Event::EarlyUnlock(who, unlocked, penalty));
+
+        Ok(().into())
+    }
+
+    /// Inner function that moves locked position of `from` to `to`.
+    /// Vesting accounts are allowed to have a single lock, like in `do_lock`
+    fn do_transfer_lock(from: T::AccountId, to: T::AccountId) -> DispatchResultWithPostInfo {
+        eq_ensure!(
+            from != to,
+            Error::<T>::LockTransferToSelf,
+            target: "eq_lockdrop",
+            "{}:{}. Attempted to transfer lock to the same account. Who: {:?}",
+            file!(),
+            line!(),
+            from,
+        );
+        eq_ensure!(
+            Self::has_lock(&from),
+            Error::<T>::NoLock,
+            target: "eq_lockdrop",
+            "{}:{}. Account has no lock. Who: {:?}",
+            file!(),
+            line!(),
+            from,
+        );
+        eq_ensure!(
+            !Self::has_vesting(to.clone()) || !Self::has_lock(&to),
+            Error::<T>::MultipleTransferWithVesting,
+            target: "eq_lockdrop",
+            "{}:{}. Attempted to create more than one lock with vesting. Who: {:?}",
+            file!(),
+            line!(),
+            to,
+        );
+
+        if Self::has_vesting(to.clone()) {
+            T::Vesting::update_vest_lock(to.clone())?;
+        }
+
+        let locked = <Locks<T>>::take(&from);
+        <Locks<T>>::mutate(&to, |amount| {
+            *amount = *amount + locked;
+        });
+
+        Self::deposit_event(Event::LockTransferred(from, to, locked));
+
+        Ok(().into())
+    }
+
     /// Inner function that sets storage field LockStart
     /// - timestamp: unix time in seconds
     /// WARNING! Check twice before using it!
@@ -564,6 +716,11 @@ impl<T: Config> Pallet<T> {
     ) -> OffchainResult<()> {
         if Self::is_lock_over() {
             let offchain_unlocks = Self::offchain_unlocks();
+            let mut nonce = T::ValidatorOffchainBatcher::duty_state(
+                authority_index,
+                UnsignedDuty::LockdropUnlock,
+            )
+            .nonce;
 
             for (_, (who, _)) in <Locks<T>>::iter()
                 .enumerate()
@@ -575,6 +732,7 @@ impl<T: Config> Pallet<T> {
                     authority_index,
                     validators_len,
                     block_num: block_number,
+                    nonce,
                 };
 
                 let option_signature = key.sign(&unlock_data.encode());
@@ -617,6 +775,7 @@ impl<T: Config> Pallet<T> {
                         OffchainErr::SubmitTransaction
                     },
                 )?;
+                nonce += 1;
             }
         }
 
@@ -89,6 +89,7 @@ pub type ModuleLockdrop = eq_lockdrop::Pallet<Test>;
 pub type ModuleVesting = eq_vesting::Pallet<Test>;
 
 parameter_types! {
+    pub const MaxNotifications: u32 = 16;
     pub const BlockHashCount: u32 = 250;
     pub const SS58Prefix: u8 = 42;
     pub const MainAsset: eq_primitives::asset::Asset = eq_primitives::asset::EQ;
@@ -318,6 +319,7 @@ impl eq_margin_call::Config for Test {
     type MaintenancePeriod = MaintenancePeriod;
     type OrderAggregates = ();
     type AssetGetter = AssetGetterMock;
+    type MaxNotifications = MaxNotifications;
     type WeightInfo = ();
 }*/
 
@@ -357,6 +359,7 @@ impl SubaccountsManager<u64> for SubaccountsManagerMock {
     fn get_subaccounts_amount(_who: &AccountId) -> usize {
         0
     }
+    type LendingIsolation = ();
 }
 
 pub struct AggregatesMock;
@@ -497,6 +500,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = ();
     type XcmToFee = ();
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU32<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -523,10 +530,15 @@ impl eq_vesting::Config for Test {
     type MinVestedTransfer = MinVestedTransfer;
     type WeightInfo = ();
     type IsTransfersEnabled = ModuleBalances;
+    type FeeCurrency = BasicCurrency;
+    type AutoVestFee = frame_support::traits::ConstU128<0>;
+    type AutoVestFeeDestination = frame_support::traits::ConstU64<0>;
+    type MaxAutoVestPerBlock = frame_support::traits::ConstU32<0>;
 }
 
 parameter_types! {
     pub const LockDropUnsignedPriority: u64 = 100;
+    pub const EarlyUnlockPenalty: Permill = Permill::from_percent(20);
 }
 
 impl eq_lockdrop::Config for Test {
@@ -537,6 +549,7 @@ impl eq_lockdrop::Config for Test {
     type ValidatorOffchainBatcher = eq_rate::Pallet<Test>;
     type MinLockAmount = MinLockAmount;
     type LockDropUnsignedPriority = LockDropUnsignedPriority;
+    type EarlyUnlockPenalty = EarlyUnlockPenalty;
     type WeightInfo = ();
 }
 
@@ -557,6 +570,7 @@ parameter_types! {
     pub const MinimalCollateral: u128 = 10 * 1_000_000_000;
     pub const MinTempBalanceUsd: u128 = 0;
     pub const UnsignedPriority: u64 = 100;
+    pub const MaxUnsignedPerBlock: u32 = 100;
 }
 
 sp_runtime::impl_opaque_keys! {
@@ -608,6 +622,7 @@ impl eq_rate::Config for Test {
     type BalanceGetter = eq_balances::Pallet<Test>;
     type BalanceRemover = eq_balances::Pallet<Test>;
     type UnsignedPriority = UnsignedPriority;
+    type MaxUnsignedPerBlock = MaxUnsignedPerBlock;
     type MinSurplus = MinSurplus;
     type MinTempBailsman = MinTempBailsman;
     type UnixTime = ModuleTimestamp;
@@ -636,6 +651,8 @@ impl eq_rate::Config for Test {
     type LendingModuleId = LendingModuleId;
     type LendingPoolManager = ();
     type LendingAssetRemoval = ();
+    type InterestIncomeReceiver = ();
+    type TreasuryAccounting = ();
     type RedistributeWeightInfo = ();
 }
 
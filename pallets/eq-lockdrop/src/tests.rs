@@ -573,3 +573,119 @@ fn unlock_from_offchain_worker() {
         assert_eq!(req.block_num, 1);
     });
 }
+
+#[test]
+fn early_unlock_pays_penalty_to_treasury() {
+    new_test_ext().execute_with(|| {
+        use sp_runtime::traits::AccountIdConversion;
+
+        let acc_id = 1;
+        let initial_balance = 100;
+        let lock = 50;
+        let treasury_acc_id = TreasuryModuleId::get().into_account_truncating();
+
+        assert_ok!(ModuleBalances::deposit_creating(
+            &acc_id,
+            Eq,
+            initial_balance,
+            true,
+            None
+        ));
+        assert_noop!(
+            EqLockdrop::early_unlock(RuntimeOrigin::signed(acc_id)),
+            Error::<Test>::NoLock
+        );
+
+        let now = 2;
+        ModuleTimestamp::set_timestamp(now * MILLISECS_PER_SEC);
+        assert_ok!(ModuleLockdrop::do_set_lock_start(now));
+        assert_ok!(EqLockdrop::lock(RuntimeOrigin::signed(acc_id), lock));
+
+        // in the middle of lock program
+        ModuleTimestamp::set_timestamp((now + LockPeriod::get() / 2) * MILLISECS_PER_SEC);
+        assert_ok!(EqLockdrop::early_unlock(RuntimeOrigin::signed(acc_id)));
+
+        let penalty = EarlyUnlockPenalty::get() * lock;
+        assert_eq!(ModuleLockdrop::locks(acc_id), 0);
+        assert_eq!(
+            ModuleBalances::get_balance(&acc_id, &Eq),
+            SignedBalance::<u128>::Positive(initial_balance - penalty)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury_acc_id, &Eq),
+            SignedBalance::<u128>::Positive(penalty)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&ModuleLockdrop::get_account_id(), &Eq),
+            SignedBalance::<u128>::Positive(0)
+        );
+
+        // regular unlock after the end of lock program
+        crate::Locks::<Test>::insert(acc_id, lock);
+        ModuleTimestamp::set_timestamp((now + LockPeriod::get() + 1) * MILLISECS_PER_SEC);
+        assert_noop!(
+            EqLockdrop::early_unlock(RuntimeOrigin::signed(acc_id)),
+            Error::<Test>::LockPeriodOver
+        );
+    });
+}
+
+#[test]
+fn transfer_lock_moves_position() {
+    new_test_ext().execute_with(|| {
+        let seller = 1;
+        let buyer = 2;
+        let vesting_buyer = 3;
+        let initial_balance = 100;
+        let lock = 30;
+
+        for acc_id in [seller, buyer, vesting_buyer] {
+            assert_ok!(ModuleBalances::deposit_creating(
+                &acc_id,
+                Eq,
+                initial_balance,
+                true,
+                None
+            ));
+        }
+
+        assert_noop!(
+            EqLockdrop::transfer_lock(RuntimeOrigin::signed(seller), buyer),
+            Error::<Test>::NoLock
+        );
+        assert_ok!(EqLockdrop::lock(RuntimeOrigin::signed(seller), lock));
+        assert_ok!(EqLockdrop::lock(RuntimeOrigin::signed(buyer), lock));
+        assert_noop!(
+            EqLockdrop::transfer_lock(RuntimeOrigin::signed(seller), seller),
+            Error::<Test>::LockTransferToSelf
+        );
+
+        assert_ok!(EqLockdrop::transfer_lock(
+            RuntimeOrigin::signed(seller),
+            buyer
+        ));
+        assert_eq!(ModuleLockdrop::locks(seller), 0);
+        assert_eq!(ModuleLockdrop::locks(buyer), 2 * lock);
+        assert_eq!(
+            ModuleBalances::get_balance(&ModuleLockdrop::get_account_id(), &Eq),
+            SignedBalance::<u128>::Positive(2 * lock)
+        );
+
+        // vesting accounts can have a single lock only
+        assert_ok!(ModuleVesting::force_vested_transfer(
+            RuntimeOrigin::root(),
+            vesting_buyer,
+            vesting_buyer,
+            eq_vesting::VestingInfo {
+                starting_block: 1,
+                locked: 12,
+                per_block: 1,
+            }
+        ));
+        assert_ok!(EqLockdrop::lock(RuntimeOrigin::signed(vesting_buyer), lock));
+        assert_noop!(
+            EqLockdrop::transfer_lock(RuntimeOrigin::signed(buyer), vesting_buyer),
+            Error::<Test>::MultipleTransferWithVesting
+        );
+    });
+}
@@ -30,6 +30,8 @@ pub trait WeightInfo {
     fn clear_lock_start() -> Weight;
     fn set_auto_unlock() -> Weight;
     fn validate_unsigned() -> Weight;
+    fn early_unlock() -> Weight;
+    fn transfer_lock() -> Weight;
 }
 
 // for tests
@@ -55,4 +57,10 @@ impl crate::WeightInfo for () {
     fn validate_unsigned() -> Weight {
         Weight::zero()
     }
+    fn early_unlock() -> Weight {
+        Weight::zero()
+    }
+    fn transfer_lock() -> Weight {
+        Weight::zero()
+    }
 }
@@ -0,0 +1,150 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium LP Gauge Pallet Benchmarking
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use eq_primitives::{balance_number::EqFixedU128, SignedBalance};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::EnsureOrigin;
+use frame_system::{Pallet as System, RawOrigin};
+use sp_runtime::{traits::One, FixedI64, Percent, Permill};
+use sp_std::{convert::TryInto, vec};
+
+const SEED: u32 = 0;
+const LP_ASSET: Asset = Asset(0x6c7030); //::from_bytes(b"lp0");
+const BALANCE: u128 = 1_000_000_000_000;
+const STAKE: u128 = 1_000_000_000;
+
+pub struct Pallet<T: Config>(crate::Pallet<T>);
+
+pub trait Config: eq_assets::Config + crate::Config {}
+
+fn balance<T: Config>(amount: u128) -> T::Balance {
+    amount
+        .try_into()
+        .map_err(|_| "balance conversion error")
+        .unwrap()
+}
+
+fn init_gauge<T: Config>() {
+    eq_assets::Pallet::<T>::do_add_asset(
+        LP_ASSET,
+        EqFixedU128::zero(),
+        FixedI64::zero(),
+        Permill::zero(),
+        Permill::zero(),
+        eq_primitives::asset::AssetXcmData::None,
+        Permill::zero(),
+        0,
+        AssetType::Lp(AmmPool::Curve(0)),
+        false,
+        Percent::zero(),
+        Permill::one(),
+        vec![],
+    )
+    .unwrap();
+
+    let origin = T::GaugeManagementOrigin::try_successful_origin().unwrap();
+    crate::Pallet::<T>::set_gauge(origin, LP_ASSET, balance::<T>(STAKE)).unwrap();
+}
+
+fn init_staker<T: Config>(who: &T::AccountId) {
+    T::EqCurrency::make_free_balance_be(
+        who,
+        LP_ASSET,
+        SignedBalance::Positive(balance::<T>(BALANCE)),
+    );
+    crate::Pallet::<T>::stake(
+        RawOrigin::Signed(who.clone()).into(),
+        LP_ASSET,
+        balance::<T>(STAKE),
+    )
+    .unwrap();
+}
+
+benchmarks! {
+    set_gauge {
+        init_gauge::<T>();
+        let origin = T::GaugeManagementOrigin::try_successful_origin().unwrap();
+        let reward_per_block = balance::<T>(2 * STAKE);
+    }: _<T::RuntimeOrigin>(origin, LP_ASSET, reward_per_block)
+    verify {
+        assert_eq!(
+            Gauges::<T>::get(LP_ASSET).map(|g| g.reward_per_block),
+            Some(reward_per_block)
+        );
+    }
+
+    stake {
+        init_gauge::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        init_staker::<T>(&caller);
+        System::<T>::set_block_number(10u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), LP_ASSET, balance::<T>(STAKE))
+    verify {
+        assert_eq!(
+            Positions::<T>::get(LP_ASSET, &caller).map(|p| p.amount),
+            Some(balance::<T>(2 * STAKE))
+        );
+    }
+
+    unstake {
+        init_gauge::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        init_staker::<T>(&caller);
+        System::<T>::set_block_number(10u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), LP_ASSET, balance::<T>(STAKE / 2))
+    verify {
+        assert_eq!(
+            Positions::<T>::get(LP_ASSET, &caller).map(|p| p.amount),
+            Some(balance::<T>(STAKE / 2))
+        );
+    }
+
+    claim {
+        init_gauge::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        init_staker::<T>(&caller);
+        T::EqCurrency::make_free_balance_be(
+            &T::RewardsAccount::get(),
+            T::AssetGetter::get_main_asset(),
+            SignedBalance::Positive(balance::<T>(BALANCE)),
+        );
+        System::<T>::set_block_number(10u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), LP_ASSET)
+    verify {
+        assert_eq!(
+            Positions::<T>::get(LP_ASSET, &caller).map(|p| p.pending),
+            Some(T::Balance::zero())
+        );
+    }
+
+    kick {
+        init_gauge::<T>();
+        let who: T::AccountId = account("staker", 0, SEED);
+        init_staker::<T>(&who);
+        let caller: T::AccountId = whitelisted_caller();
+        System::<T>::set_block_number(10u32.into());
+    }: _(RawOrigin::Signed(caller), LP_ASSET, who.clone())
+    verify {
+        assert!(Positions::<T>::contains_key(LP_ASSET, &who));
+    }
+}
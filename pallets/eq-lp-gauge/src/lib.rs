@@ -0,0 +1,462 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium LP Gauge Pallet
+//!
+//! Liquidity mining for Curve pool LP tokens. Governance sets emission of the main
+//! asset per block for a gauge of LP asset, stakers of the LP asset receive it
+//! proportionally to their boosted stakes.
+//!
+//! Part of the stake covered by balance staked in eq-staking is boosted by `MaxBoost`.
+//! Boost is recalculated on every stake change, anyone can `kick` a staker to
+//! recalculate the boost after the eq-staking balance change.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![forbid(unsafe_code)]
+#![deny(warnings)]
+
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use eq_primitives::{
+    asset::{AmmPool, Asset, AssetGetter, AssetType},
+    balance::EqCurrency,
+    StakedBalance, TransferReason,
+};
+use frame_support::traits::{ExistenceRequirement, Get};
+use sp_runtime::{
+    traits::{AccountIdConversion, Saturating, UniqueSaturatedInto, Zero},
+    DispatchError, FixedPointNumber, FixedPointOperand, FixedU128, RuntimeDebug,
+};
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+/// Liquidity mining gauge of LP asset
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub struct Gauge<Balance, BlockNumber> {
+    /// Main asset emitted per block
+    pub reward_per_block: Balance,
+    /// Sum of boosted stakes
+    pub total_effective: Balance,
+    /// Reward accumulated per unit of boosted stake
+    pub reward_per_share: FixedU128,
+    /// Block of the last `reward_per_share` update
+    pub last_update: BlockNumber,
+}
+
+/// LP tokens staked in a gauge
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct Position<Balance> {
+    /// Staked LP tokens
+    pub amount: Balance,
+    /// Stake boosted by balance staked in eq-staking
+    pub effective: Balance,
+    /// `reward_per_share * effective` at the last position update
+    pub reward_debt: Balance,
+    /// Accrued and not claimed reward
+    pub pending: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{pallet_prelude::*, PalletId};
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::{traits::AtLeast32BitUnsigned, Permill};
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Numerical representation of stored balances
+        type Balance: Parameter
+            + Member
+            + AtLeast32BitUnsigned
+            + FixedPointOperand
+            + MaybeSerializeDeserialize
+            + Default
+            + Copy
+            + MaxEncodedLen;
+        /// Used for LP tokens and rewards transfers
+        type EqCurrency: EqCurrency<Self::AccountId, Self::Balance>;
+        /// Used to check LP assets and get the reward asset
+        type AssetGetter: AssetGetter;
+        /// Balance staked in eq-staking, boosts gauge stakes
+        type StakedBalance: StakedBalance<Self::AccountId, Self::Balance>;
+        /// Origin to set gauges emission
+        type GaugeManagementOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Account with liquidity to pay rewards
+        type RewardsAccount: Get<Self::AccountId>;
+        /// Pallet id of the account holding staked LP tokens
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+        /// Max boost of the stake covered by eq-staking balance
+        #[pallet::constant]
+        type MaxBoost: Get<Permill>;
+        /// Weight information for extrinsics of this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Gauges of LP assets
+    #[pallet::storage]
+    #[pallet::getter(fn gauges)]
+    pub type Gauges<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, Gauge<T::Balance, T::BlockNumber>, OptionQuery>;
+
+    /// Stakes in gauges
+    #[pallet::storage]
+    #[pallet::getter(fn positions)]
+    pub type Positions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        Asset,
+        Blake2_128Concat,
+        T::AccountId,
+        Position<T::Balance>,
+        OptionQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Gauge emission was set
+        GaugeSet {
+            lp_asset: Asset,
+            reward_per_block: T::Balance,
+        },
+        /// LP tokens were staked in the gauge
+        Staked {
+            who: T::AccountId,
+            lp_asset: Asset,
+            amount: T::Balance,
+        },
+        /// LP tokens were unstaked from the gauge
+        Unstaked {
+            who: T::AccountId,
+            lp_asset: Asset,
+            amount: T::Balance,
+        },
+        /// Reward was paid
+        Claimed {
+            who: T::AccountId,
+            lp_asset: Asset,
+            amount: T::Balance,
+        },
+        /// Boosted stake was recalculated
+        BoostUpdated {
+            who: T::AccountId,
+            lp_asset: Asset,
+            effective: T::Balance,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Asset is not a Curve pool LP token
+        NotCurveLpAsset,
+        /// There is no gauge for the asset
+        GaugeNotFound,
+        /// Zero amount
+        ZeroAmount,
+        /// Not enough LP tokens staked
+        InsufficientStake,
+        /// No stake in the gauge
+        PositionNotFound,
+        /// No reward to claim
+        NothingToClaim,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Creates a gauge of Curve pool LP asset or changes its emission.
+        /// Zero `reward_per_block` stops the emission, stakes can still be withdrawn.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_gauge())]
+        pub fn set_gauge(
+            origin: OriginFor<T>,
+            lp_asset: Asset,
+            reward_per_block: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            T::GaugeManagementOrigin::ensure_origin(origin)?;
+
+            let asset_data = T::AssetGetter::get_asset_data(&lp_asset)?;
+            ensure!(
+                matches!(asset_data.asset_type, AssetType::Lp(AmmPool::Curve(_))),
+                Error::<T>::NotCurveLpAsset
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut gauge = Gauges::<T>::get(lp_asset).unwrap_or(Gauge {
+                reward_per_block: T::Balance::zero(),
+                total_effective: T::Balance::zero(),
+                reward_per_share: FixedU128::zero(),
+                last_update: now,
+            });
+            Self::update_gauge(&mut gauge, now);
+            gauge.reward_per_block = reward_per_block;
+            Gauges::<T>::insert(lp_asset, gauge);
+
+            Self::deposit_event(Event::GaugeSet {
+                lp_asset,
+                reward_per_block,
+            });
+            Ok(().into())
+        }
+
+        /// Stakes `amount` of LP tokens in the gauge of `lp_asset`
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::stake())]
+        pub fn stake(
+            origin: OriginFor<T>,
+            lp_asset: Asset,
+            amount: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+            Self::update_position(&who, lp_asset, |position| {
+                position.amount = position.amount.saturating_add(amount);
+                T::EqCurrency::currency_transfer(
+                    &who,
+                    &Self::account_id(),
+                    lp_asset,
+                    amount,
+                    ExistenceRequirement::AllowDeath,
+                    TransferReason::Common,
+                    true,
+                )
+            })?;
+
+            Self::deposit_event(Event::Staked {
+                who,
+                lp_asset,
+                amount,
+            });
+            Ok(().into())
+        }
+
+        /// Withdraws `amount` of staked LP tokens from the gauge of `lp_asset`.
+        /// Accrued reward stays claimable.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::unstake())]
+        pub fn unstake(
+            origin: OriginFor<T>,
+            lp_asset: Asset,
+            amount: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(
+                Positions::<T>::contains_key(lp_asset, &who),
+                Error::<T>::PositionNotFound
+            );
+
+            Self::update_position(&who, lp_asset, |position| {
+                ensure!(position.amount >= amount, Error::<T>::InsufficientStake);
+                position.amount -= amount;
+                T::EqCurrency::currency_transfer(
+                    &Self::account_id(),
+                    &who,
+                    lp_asset,
+                    amount,
+                    ExistenceRequirement::AllowDeath,
+                    TransferReason::Common,
+                    true,
+                )
+            })?;
+
+            Self::deposit_event(Event::Unstaked {
+                who,
+                lp_asset,
+                amount,
+            });
+            Ok(().into())
+        }
+
+        /// Pays accrued reward of the stake in the gauge of `lp_asset`
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::claim())]
+        pub fn claim(origin: OriginFor<T>, lp_asset: Asset) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                Positions::<T>::contains_key(lp_asset, &who),
+                Error::<T>::PositionNotFound
+            );
+
+            let (amount, _) = Self::update_position(&who, lp_asset, |position| {
+                let amount = sp_std::mem::take(&mut position.pending);
+                ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+                T::EqCurrency::currency_transfer(
+                    &T::RewardsAccount::get(),
+                    &who,
+                    T::AssetGetter::get_main_asset(),
+                    amount,
+                    ExistenceRequirement::AllowDeath,
+                    TransferReason::Common,
+                    true,
+                )?;
+                Ok(amount)
+            })?;
+
+            Self::deposit_event(Event::Claimed {
+                who,
+                lp_asset,
+                amount,
+            });
+            Ok(().into())
+        }
+
+        /// Recalculates boosted stake of `who` in the gauge of `lp_asset`
+        /// after the change of its eq-staking balance
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::kick())]
+        pub fn kick(
+            origin: OriginFor<T>,
+            lp_asset: Asset,
+            who: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            ensure!(
+                Positions::<T>::contains_key(lp_asset, &who),
+                Error::<T>::PositionNotFound
+            );
+
+            let (_, effective) = Self::update_position(&who, lp_asset, |_| Ok(()))?;
+
+            Self::deposit_event(Event::BoostUpdated {
+                who,
+                lp_asset,
+                effective,
+            });
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Account holding staked LP tokens
+    pub fn account_id() -> T::AccountId {
+        T::PalletId::get().into_account_truncating()
+    }
+
+    /// Reward of `who` in the gauge of `lp_asset` available to claim now
+    pub fn pending_reward(lp_asset: Asset, who: &T::AccountId) -> T::Balance {
+        match (
+            Gauges::<T>::get(lp_asset),
+            Positions::<T>::get(lp_asset, who),
+        ) {
+            (Some(mut gauge), Some(position)) => {
+                Self::update_gauge(&mut gauge, frame_system::Pallet::<T>::block_number());
+                position
+                    .pending
+                    .saturating_add(Self::accrued(&gauge, &position))
+            }
+            _ => T::Balance::zero(),
+        }
+    }
+
+    /// Stake boosted by `MaxBoost` for the part covered by eq-staking balance
+    fn effective_stake(who: &T::AccountId, amount: T::Balance) -> T::Balance {
+        let covered = T::StakedBalance::staked_balance(who).min(amount);
+        amount.saturating_add(T::MaxBoost::get() * covered)
+    }
+
+    /// Reward accrued by `position` since its last update
+    fn accrued(
+        gauge: &Gauge<T::Balance, T::BlockNumber>,
+        position: &Position<T::Balance>,
+    ) -> T::Balance {
+        gauge
+            .reward_per_share
+            .saturating_mul_int(position.effective)
+            .saturating_sub(position.reward_debt)
+    }
+
+    /// Distributes emission since the last update over boosted stakes
+    fn update_gauge(gauge: &mut Gauge<T::Balance, T::BlockNumber>, now: T::BlockNumber) {
+        if now <= gauge.last_update {
+            return;
+        }
+
+        if !gauge.total_effective.is_zero() {
+            let blocks: u128 = (now - gauge.last_update).unique_saturated_into();
+            let reward_per_block: u128 = gauge.reward_per_block.unique_saturated_into();
+            let emitted = reward_per_block.saturating_mul(blocks);
+            let per_share = FixedU128::checked_from_rational(emitted, gauge.total_effective)
+                .unwrap_or_else(FixedU128::zero);
+            gauge.reward_per_share = gauge.reward_per_share.saturating_add(per_share);
+        }
+        gauge.last_update = now;
+    }
+
+    /// Accrues reward of `who` position, applies `f` to it and recalculates its boost.
+    /// Returns result of `f` and new boosted stake.
+    fn update_position<R>(
+        who: &T::AccountId,
+        lp_asset: Asset,
+        f: impl FnOnce(&mut Position<T::Balance>) -> Result<R, DispatchError>,
+    ) -> Result<(R, T::Balance), DispatchError> {
+        let mut gauge = Gauges::<T>::get(lp_asset).ok_or(Error::<T>::GaugeNotFound)?;
+        Self::update_gauge(&mut gauge, frame_system::Pallet::<T>::block_number());
+
+        let mut position = Positions::<T>::get(lp_asset, who).unwrap_or_default();
+        position.pending = position
+            .pending
+            .saturating_add(Self::accrued(&gauge, &position));
+
+        let result = f(&mut position)?;
+
+        let effective = Self::effective_stake(who, position.amount);
+        gauge.total_effective = gauge
+            .total_effective
+            .saturating_sub(position.effective)
+            .saturating_add(effective);
+        position.effective = effective;
+        position.reward_debt = gauge.reward_per_share.saturating_mul_int(effective);
+
+        Gauges::<T>::insert(lp_asset, gauge);
+        if position.amount.is_zero() && position.pending.is_zero() {
+            Positions::<T>::remove(lp_asset, who);
+        } else {
+            Positions::<T>::insert(lp_asset, who, position);
+        }
+
+        Ok((result, effective))
+    }
+}
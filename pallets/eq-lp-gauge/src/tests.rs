@@ -0,0 +1,88 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+use eq_primitives::{asset::EQ, SignedBalance};
+use frame_support::{assert_noop, assert_ok};
+
+#[test]
+fn rewards_distributed_by_boosted_stakes() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        EqCurrencyMock::make_free_balance_be(&1, LP_ASSET, SignedBalance::Positive(1_000));
+        EqCurrencyMock::make_free_balance_be(&2, LP_ASSET, SignedBalance::Positive(1_000));
+        EqCurrencyMock::make_free_balance_be(
+            &REWARDS_ACCOUNT,
+            EQ,
+            SignedBalance::Positive(1_000_000),
+        );
+        set_staked_balance(2, 1_000);
+
+        assert_noop!(
+            EqLpGauge::set_gauge(RuntimeOrigin::root(), EQ, 100),
+            Error::<Test>::NotCurveLpAsset
+        );
+        assert_noop!(
+            EqLpGauge::stake(RuntimeOrigin::signed(1), LP_ASSET, 1_000),
+            Error::<Test>::GaugeNotFound
+        );
+
+        assert_ok!(EqLpGauge::set_gauge(RuntimeOrigin::root(), LP_ASSET, 100));
+        assert_ok!(EqLpGauge::stake(RuntimeOrigin::signed(1), LP_ASSET, 1_000));
+        assert_ok!(EqLpGauge::stake(RuntimeOrigin::signed(2), LP_ASSET, 1_000));
+
+        // half of the stake of 2 is boosted by staked balance
+        assert_eq!(EqLpGauge::positions(LP_ASSET, 2).unwrap().effective, 1_500);
+        assert_eq!(EqLpGauge::gauges(LP_ASSET).unwrap().total_effective, 2_500);
+        assert_eq!(
+            EqCurrencyMock::free_balance(&EqLpGauge::account_id(), LP_ASSET),
+            2_000
+        );
+
+        System::set_block_number(11);
+        assert_eq!(EqLpGauge::pending_reward(LP_ASSET, &1), 400);
+        assert_ok!(EqLpGauge::claim(RuntimeOrigin::signed(1), LP_ASSET));
+        assert_ok!(EqLpGauge::claim(RuntimeOrigin::signed(2), LP_ASSET));
+        assert_eq!(EqCurrencyMock::free_balance(&1, EQ), 400);
+        assert_eq!(EqCurrencyMock::free_balance(&2, EQ), 600);
+        assert_noop!(
+            EqLpGauge::claim(RuntimeOrigin::signed(1), LP_ASSET),
+            Error::<Test>::NothingToClaim
+        );
+
+        assert_noop!(
+            EqLpGauge::unstake(RuntimeOrigin::signed(1), LP_ASSET, 1_001),
+            Error::<Test>::InsufficientStake
+        );
+        assert_ok!(EqLpGauge::unstake(
+            RuntimeOrigin::signed(1),
+            LP_ASSET,
+            1_000
+        ));
+        assert_eq!(EqCurrencyMock::free_balance(&1, LP_ASSET), 1_000);
+        assert!(EqLpGauge::positions(LP_ASSET, 1).is_none());
+
+        // boost is removed after eq-staking balance is unstaked
+        set_staked_balance(2, 0);
+        assert_ok!(EqLpGauge::kick(RuntimeOrigin::signed(1), LP_ASSET, 2));
+        assert_eq!(EqLpGauge::gauges(LP_ASSET).unwrap().total_effective, 1_000);
+    });
+}
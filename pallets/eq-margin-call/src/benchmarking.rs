@@ -22,10 +22,13 @@
 
 use super::*;
 use eq_assets;
-use eq_primitives::{asset, balance::EqCurrency, PriceSetter, SignedBalance};
+use eq_primitives::{
+    asset, balance::EqCurrency, Aggregates, PriceSetter, SignedBalance, UserGroup,
+};
 use eq_utils::ONE_TOKEN;
 use eq_whitelists;
 use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::EnsureOrigin;
 use frame_system::RawOrigin;
 use sp_runtime::{traits::One, FixedI64};
 
@@ -38,15 +41,61 @@ pub trait Config:
 {
 }
 
+fn set_prices<T: Config>() {
+    let price_setter: T::AccountId = account("price_setter", 0, SEED);
+    eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone())
+        .unwrap();
+    for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
+        <eq_oracle::Pallet<T> as PriceSetter<T::AccountId>>::set_price(
+            price_setter.clone(),
+            curr,
+            FixedI64::one(),
+        )
+        .unwrap();
+    }
+}
+
+/// Trader subaccount of `who` with `balances`, `(asset, balance)` in tokens
+fn trader_subaccount<T: Config>(
+    who: &T::AccountId,
+    balances: &[(Asset, SignedBalance<u128>)],
+) -> T::AccountId {
+    let subaccount =
+        <T as crate::Config>::SubaccountsManager::create_subaccount_inner(who, &SubAccType::Trader)
+            .unwrap();
+    <T as eq_balances::Config>::Aggregates::set_usergroup(&subaccount, UserGroup::Borrowers, true)
+        .unwrap();
+    for (asset, balance) in balances {
+        <eq_balances::Pallet<T> as EqCurrency<_, _>>::make_free_balance_be(
+            &subaccount,
+            *asset,
+            balance.map(|b| {
+                (b * ONE_TOKEN)
+                    .try_into()
+                    .map_err(|_| "balance conversion error")
+                    .unwrap()
+            }),
+        );
+    }
+    subaccount
+}
+
+fn position_transfer<T: Config>(
+    to: T::AccountId,
+) -> PositionTransfer<T::AccountId, <T as crate::Config>::Balance> {
+    PositionTransfer {
+        to,
+        subacc_type: SubAccType::Trader,
+        debt_asset: asset::EQD,
+        debt: <T as crate::Config>::Balance::from(500 * ONE_TOKEN),
+        collateral_asset: asset::BTC,
+        collateral: <T as crate::Config>::Balance::from(500 * ONE_TOKEN),
+    }
+}
+
 benchmarks! {
     try_margincall_external{
-        let price_setter: T::AccountId = account("price_setter", 0, SEED);
-        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone())
-            .unwrap();
-        for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
-            <eq_oracle::Pallet::<T> as PriceSetter<T::AccountId>>::set_price(price_setter.clone(), curr, FixedI64::one())
-                .unwrap();
-        }
+        set_prices::<T>();
 
         let acc: T::AccountId = account("account", 0, SEED);
         <eq_balances::Pallet::<T> as EqCurrency<_, _>>::make_free_balance_be(
@@ -68,4 +117,125 @@ benchmarks! {
         assert!(eq_balances::Pallet::<T>::get_balance(&acc, &asset::EQD).is_zero());
         assert!(eq_balances::Pallet::<T>::get_balance(&acc, &asset::BTC).is_zero());
     }
+
+    acknowledge_notifications{
+        let acc: T::AccountId = account("account", 0, SEED);
+        for order_id in 0..T::MaxNotifications::get() {
+            <crate::Pallet<T> as RiskNotifier<T::AccountId>>::notify(
+                &acc,
+                RiskNotificationKind::OrderPenalized {
+                    asset: asset::BTC,
+                    order_id: order_id.into(),
+                    reason: eq_primitives::DeleteOrderReason::OutOfCorridor,
+                },
+            );
+        }
+    }: _(RawOrigin::Signed(acc.clone()), None)
+    verify{
+        assert!(!Notifications::<T>::contains_key(&acc));
+    }
+
+    compensate_wrongful_liquidation{
+        set_prices::<T>();
+        let origin = T::CompensationOrigin::try_successful_origin()
+            .map_err(|_| "no successful origin")?;
+
+        let treasury: T::AccountId = <T as crate::Config>::TreasuryModuleId::get().into_account_truncating();
+        <eq_balances::Pallet::<T> as EqCurrency<_, _>>::make_free_balance_be(
+            &treasury,
+            asset::EQD,
+            SignedBalance::Positive((1_000_000 * ONE_TOKEN).try_into().map_err(|_| "balance conversion error").unwrap()),
+        );
+
+        let acc: T::AccountId = account("account", 0, SEED);
+        WrongfulLiquidations::<T>::insert(&acc, <T as crate::Config>::Balance::from(1_000 * ONE_TOKEN));
+    }: _<T::RuntimeOrigin>(origin, acc.clone(), asset::EQD)
+    verify{
+        assert!(!WrongfulLiquidations::<T>::contains_key(&acc));
+    }
+
+    check_pending_liquidations{
+        let p in 0 .. T::MaxPendingLiquidations::get();
+
+        set_prices::<T>();
+        let mut balances = VecMap::new();
+        balances.insert(asset::BTC, SignedBalance::Positive(<T as crate::Config>::Balance::from(200 * ONE_TOKEN)));
+        balances.insert(asset::EQD, SignedBalance::Negative(<T as crate::Config>::Balance::from(100 * ONE_TOKEN)));
+
+        let pending: Vec<_> = (0..p)
+            .map(|i| LiquidationSnapshot {
+                who: account("account", i, SEED),
+                block: frame_system::Pallet::<T>::block_number(),
+                balances: balances.clone(),
+                taken: balances.clone(),
+            })
+            .collect();
+    }: {
+        crate::Pallet::<T>::check_pending_liquidations(frame_system::Pallet::<T>::block_number(), pending);
+    }
+    verify{
+        assert!(PendingLiquidations::<T>::get().is_empty());
+        if p > 0 {
+            let acc: T::AccountId = account("account", 0, SEED);
+            assert!(WrongfulLiquidations::<T>::contains_key(&acc));
+        }
+    }
+
+    propose_position_transfer{
+        let from: T::AccountId = account("from", 0, SEED);
+        let to: T::AccountId = account("to", 0, SEED);
+        trader_subaccount::<T>(&from, &[
+            (asset::BTC, SignedBalance::Positive(1_000)),
+            (asset::EQD, SignedBalance::Negative(900)),
+        ]);
+        let transfer = position_transfer::<T>(to.clone());
+    }: _(
+        RawOrigin::Signed(from.clone()),
+        SubAccType::Trader,
+        to,
+        transfer.debt_asset,
+        transfer.debt,
+        transfer.collateral_asset,
+        transfer.collateral
+    )
+    verify{
+        assert_eq!(PositionTransfers::<T>::get(&from), Some(transfer));
+    }
+
+    cancel_position_transfer{
+        let from: T::AccountId = account("from", 0, SEED);
+        let to: T::AccountId = account("to", 0, SEED);
+        PositionTransfers::<T>::insert(&from, position_transfer::<T>(to));
+    }: _(RawOrigin::Signed(from.clone()))
+    verify{
+        assert!(!PositionTransfers::<T>::contains_key(&from));
+    }
+
+    accept_position_transfer{
+        set_prices::<T>();
+        let from: T::AccountId = account("from", 0, SEED);
+        let to: T::AccountId = account("to", 0, SEED);
+        let holder = trader_subaccount::<T>(&from, &[
+            (asset::BTC, SignedBalance::Positive(1_000)),
+            (asset::EQD, SignedBalance::Negative(900)),
+        ]);
+        let receiver = trader_subaccount::<T>(&to, &[
+            (asset::BTC, SignedBalance::Positive(1_000)),
+        ]);
+        let transfer = position_transfer::<T>(to.clone());
+        let expected_debt = (transfer.debt_asset, transfer.debt);
+        let expected_collateral = (transfer.collateral_asset, transfer.collateral);
+        PositionTransfers::<T>::insert(&from, transfer);
+    }: _(RawOrigin::Signed(to), from.clone(), SubAccType::Trader, expected_debt, expected_collateral)
+    verify{
+        assert!(!PositionTransfers::<T>::contains_key(&from));
+        assert_eq!(
+            eq_balances::Pallet::<T>::get_balance(&holder, &asset::EQD),
+            SignedBalance::Negative((400 * ONE_TOKEN).try_into().map_err(|_| "balance conversion error").unwrap())
+        );
+        assert_eq!(
+            eq_balances::Pallet::<T>::get_balance(&receiver, &asset::EQD),
+            SignedBalance::Negative((500 * ONE_TOKEN).try_into().map_err(|_| "balance conversion error").unwrap())
+        );
+    }
 }
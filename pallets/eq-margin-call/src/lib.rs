@@ -30,6 +30,21 @@
 //! * SubGood, the account is forbidden to borrow
 //! * MaintenanceTimerGoing, the margin call per se, a timer is activated that gives 24 h to top up the account to a necessary limit
 //! * SubCritical and MaintenanceTimerOver which result in a liquidation of the account
+//!
+//! Positions liquidated under `critical_margin` are kept for `PriceRestorePeriod` blocks.
+//! If the prices are restored during this period so that the position is above
+//! `critical_margin` again, the liquidation is recorded as wrongful and may be compensated
+//! from the treasury by `compensate_wrongful_liquidation`.
+//!
+//! Instead of being liquidated a debt position may be taken over by another account, e.g. an
+//! OTC desk. The holder offers the debt with matching collateral by `propose_position_transfer`,
+//! the position is moved when the receiver calls `accept_position_transfer` with the same terms,
+//! the margin of the receiver stays above `initial_margin` and the margin of the holder doesn't
+//! decrease.
+//!
+//! Keepers may trigger margin calls with `try_margincall_priority` in the operational class,
+//! so liquidations aren't delayed by full blocks. The number of such calls in a block is
+//! limited by `MaxPriorityMarginCallsPerBlock`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings)]
@@ -41,20 +56,25 @@ use frame_support::Parameter;
 use frame_system as system;
 use frame_system::offchain::SendTransactionTypes;
 
+use codec::{Decode, Encode};
 use frame_support::{
-    dispatch::DispatchResultWithPostInfo,
-    traits::{Get, UnixTime},
+    dispatch::{DispatchResultWithPostInfo, PostDispatchInfo},
+    traits::{ExistenceRequirement, Get, UnixTime},
+    BoundedVec, PalletId,
 };
+use scale_info::TypeInfo;
 
 use core::convert::TryInto;
+use eq_primitives::str_asset;
 use eq_primitives::{
     asset::*,
-    balance::BalanceGetter,
+    balance::{BalanceGetter, EqCurrency, MarginShortfall},
     balance_number::EqFixedU128,
     price::PriceGetter,
     subaccount::{SubAccType, SubaccountsManager},
-    BailsmanManager, BalanceChange, MarginCallManager, MarginState, OrderAggregateBySide,
-    OrderAggregates, OrderChange, OrderSide, SignedBalance, ONE_TOKEN,
+    BailsmanManager, BalanceChange, DerivativePositions, LendingIsolation, MarginCallManager,
+    MarginSimulation, MarginState, OrderAggregateBySide, OrderAggregates, OrderChange, OrderSide,
+    RiskNotification, RiskNotificationKind, RiskNotifier, SignedBalance, TransferReason, ONE_TOKEN,
 };
 use eq_utils::vec_map::VecMap;
 use eq_utils::{
@@ -62,8 +82,11 @@ use eq_utils::{
     multiply_by_rational,
 };
 use sp_runtime::{
-    traits::{AtLeast32BitUnsigned, Bounded, CheckedAdd, MaybeSerializeDeserialize, Member, Zero},
-    ArithmeticError, DispatchError, FixedPointNumber, Percent,
+    traits::{
+        AccountIdConversion, AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedDiv, CheckedSub,
+        MaybeSerializeDeserialize, Member, Saturating, Zero,
+    },
+    ArithmeticError, DispatchError, FixedPointNumber, Percent, RuntimeDebug,
 };
 
 #[cfg(test)]
@@ -77,6 +100,36 @@ pub use weights::WeightInfo;
 
 pub use pallet::*;
 
+/// Balances of an account liquidated under `critical_margin`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LiquidationSnapshot<AccountId, Balance, BlockNumber> {
+    /// Liquidated account
+    pub who: AccountId,
+    /// Block of the liquidation
+    pub block: BlockNumber,
+    /// Balances of the account right before the liquidation
+    pub balances: VecMap<Asset, SignedBalance<Balance>>,
+    /// Part of `balances` taken from the account by the liquidation
+    pub taken: VecMap<Asset, SignedBalance<Balance>>,
+}
+
+/// Debt position offered by its holder to another account, see `propose_position_transfer`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PositionTransfer<AccountId, Balance> {
+    /// Main account taking over the position
+    pub to: AccountId,
+    /// Subaccount of the holder with the position
+    pub subacc_type: SubAccType,
+    /// Borrowed asset
+    pub debt_asset: Asset,
+    /// Amount of debt taken over
+    pub debt: Balance,
+    /// Asset of collateral transferred with the debt
+    pub collateral_asset: Asset,
+    /// Amount of collateral transferred with the debt
+    pub collateral: Balance,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -84,6 +137,7 @@ pub mod pallet {
     use frame_system::pallet_prelude::*;
 
     #[pallet::pallet]
+    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     //Config
@@ -123,10 +177,42 @@ pub mod pallet {
         type MaintenancePeriod: Get<u64>;
         /// Provides aggregates for the margin calculation
         type OrderAggregates: OrderAggregates<Self::AccountId>;
+        /// Open derivative positions, accounted in the margin calculation as filled orders
+        type DerivativePositions: DerivativePositions<Self::AccountId>;
         /// Provides asset_data for the margin calculation
         type AssetGetter: AssetGetter;
         /// Provides subaccount info for MarginCall events
         type SubaccountsManager: SubaccountsManager<Self::AccountId>;
+        /// Max amount of unacknowledged risk notifications stored per account, the oldest
+        /// notification is dropped when the limit is reached
+        #[pallet::constant]
+        type MaxNotifications: Get<u32>;
+        /// Isolated lending pools, their assets have zero collateral weight
+        type LendingIsolation: LendingIsolation;
+        /// Multipliers of debt value in margin calculation by risk tier of the debt asset,
+        /// riskier debt requires more collateral
+        #[pallet::constant]
+        type RiskTierMarginMultipliers: Get<RiskTierParams<EqFixedU128>>;
+        /// Used to pay compensations of wrongful liquidations
+        type EqCurrency: EqCurrency<Self::AccountId, Self::Balance>;
+        /// Treasury account, source of wrongful liquidation compensations
+        #[pallet::constant]
+        type TreasuryModuleId: Get<PalletId>;
+        /// Origin allowed to pay compensations of wrongful liquidations
+        type CompensationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Number of blocks after a liquidation during which restored prices mark
+        /// the liquidation as wrongful
+        #[pallet::constant]
+        type PriceRestorePeriod: Get<Self::BlockNumber>;
+        /// Max amount of liquidations waiting for the prices to be restored, the oldest
+        /// liquidation is dropped when the limit is reached
+        #[pallet::constant]
+        type MaxPendingLiquidations: Get<u32>;
+        /// Origin of liquidation keepers allowed to use `try_margincall_priority`
+        type KeeperOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+        /// Max number of `try_margincall_priority` calls in a block
+        #[pallet::constant]
+        type MaxPriorityMarginCallsPerBlock: Get<u32>;
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -137,6 +223,55 @@ pub mod pallet {
     pub type MaintenanceTimers<T: Config> =
         StorageMap<_, Identity, T::AccountId, Option<u64>, ValueQuery>;
 
+    /// Last critical risk notifications of main accounts, events of subaccounts are stored
+    /// for their owners
+    #[pallet::storage]
+    #[pallet::getter(fn notifications)]
+    pub type Notifications<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<RiskNotification<T::AccountId>, T::MaxNotifications>,
+        ValueQuery,
+    >;
+
+    /// Id of the next risk notification
+    #[pallet::storage]
+    pub type NextNotificationId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Liquidations of the last `PriceRestorePeriod` blocks, checked against current prices
+    /// every block
+    #[pallet::storage]
+    pub type PendingLiquidations<T: Config> = StorageValue<
+        _,
+        BoundedVec<
+            LiquidationSnapshot<T::AccountId, T::Balance, T::BlockNumber>,
+            T::MaxPendingLiquidations,
+        >,
+        ValueQuery,
+    >;
+
+    /// USD value of positions lost in wrongful liquidations, waiting for compensation
+    #[pallet::storage]
+    #[pallet::getter(fn wrongful_liquidations)]
+    pub type WrongfulLiquidations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::Balance, ValueQuery>;
+
+    /// Debt positions waiting for acceptance, by main account of the holder
+    #[pallet::storage]
+    #[pallet::getter(fn position_transfers)]
+    pub type PositionTransfers<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        PositionTransfer<T::AccountId, T::Balance>,
+        OptionQuery,
+    >;
+
+    /// Block number and count of `try_margincall_priority` calls dispatched in it
+    #[pallet::storage]
+    pub type PriorityMarginCalls<T: Config> = StorageValue<_, (T::BlockNumber, u32), ValueQuery>;
+
     /* ------------ EVENTS --------------- */
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -147,16 +282,75 @@ pub mod pallet {
         /// Event is fired when an account is liquidated.
         /// \[main_acc, maybe(subacc_type,subacc_id)\]
         MarginCallExecuted(T::AccountId, Option<(SubAccType, T::AccountId)>),
+        /// Risk notifications were acknowledged by an account.
+        /// \[who, acknowledged_count\]
+        NotificationsAcknowledged(T::AccountId, u32),
+        /// Margin call is postponed because the account holds an asset with a suspect price.
+        /// \[who, asset\]
+        MarginCallPostponed(T::AccountId, Asset),
+        /// Prices were restored after the liquidation, position value in USD is recorded
+        /// for compensation.
+        /// \[who, value\]
+        WrongfulLiquidationRecorded(T::AccountId, T::Balance),
+        /// Wrongful liquidation is compensated from the treasury.
+        /// \[who, asset, amount\]
+        WrongfulLiquidationCompensated(T::AccountId, Asset, T::Balance),
+        /// Debt position is offered to another account.
+        /// \[from, to, debt_asset, debt\]
+        PositionTransferProposed(T::AccountId, T::AccountId, Asset, T::Balance),
+        /// Offer of a debt position is withdrawn by its holder.
+        /// \[from, to\]
+        PositionTransferCancelled(T::AccountId, T::AccountId),
+        /// Debt position with its collateral is taken over by another account.
+        /// \[from, to, debt_asset, debt\]
+        PositionTransferred(T::AccountId, T::AccountId, Asset, T::Balance),
     }
 
     /*------------ HOOKS ------------------*/
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            let pending = <PendingLiquidations<T>>::get();
+            if pending.is_empty() {
+                return T::WeightInfo::check_pending_liquidations(0);
+            }
+
+            let count = pending.len() as u32;
+            Self::check_pending_liquidations(n, pending.into_inner());
+            T::WeightInfo::check_pending_liquidations(count)
+        }
+    }
 
     #[pallet::error]
     pub enum Error<T> {
         /// Not allowed with zero collateral
         ZeroCollateral,
+        /// There are no notifications to acknowledge
+        NoNotifications,
+        /// There is no recorded wrongful liquidation of the account
+        NoWrongfulLiquidation,
+        /// Debt positions are held only by trader and borrower subaccounts
+        NotBorrowerSubaccount,
+        /// Account has no subaccount of this type
+        NoSubaccount,
+        /// Debt position can't be transferred to the same account
+        SameAccount,
+        /// Debt and collateral of the position are the same asset
+        SameAsset,
+        /// Transferred debt is zero or exceeds debt of the holder
+        NotEnoughDebt,
+        /// Account already offers a debt position
+        PositionTransferAlreadyProposed,
+        /// There is no debt position offered to the account
+        NoPositionTransfer,
+        /// Margin of the receiving account would be lower than `initial_margin`
+        PositionTransferMarginTooLow,
+        /// Limit of priority margin calls in the block is reached
+        PriorityLaneExhausted,
+        /// Offered debt position differs from the terms expected by the receiver
+        PositionTransferTermsChanged,
+        /// Margin of the holder would be lower after the position transfer
+        HolderMarginDecreased,
     }
 
     /* ------------------ GENESIS ------------------------- */
@@ -198,16 +392,272 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         /// Tries to margin-call an account from another account signed call.
         #[pallet::call_index(0)]
-        #[pallet::weight(T::WeightInfo::try_margincall_external())]
+        #[pallet::weight(
+            T::WeightInfo::try_margincall_external().saturating_add(Pallet::<T>::notify_weight())
+        )]
         pub fn try_margincall_external(
             origin: OriginFor<T>,
             who: <T as system::Config>::AccountId,
         ) -> DispatchResultWithPostInfo {
             ensure_signed(origin)?;
             log::trace!(target: "eq_margin_call", "Try margin call on account '{:?}' external", who);
-            let _ = Self::try_margincall(&who)?;
+            let state = Self::try_margincall(&who)?;
+            Ok(Some(Self::try_margincall_weight(
+                state,
+                T::WeightInfo::try_margincall_external(),
+            ))
+            .into())
+        }
+
+        /// Removes risk notifications of the caller with id less or equal to `up_to`.
+        /// Removes all notifications when `up_to` is `None`.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::acknowledge_notifications())]
+        pub fn acknowledge_notifications(
+            origin: OriginFor<T>,
+            up_to: Option<u64>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let acknowledged = <Notifications<T>>::mutate_exists(&who, |maybe_notifications| {
+                let notifications = match maybe_notifications {
+                    Some(n) => n,
+                    None => return 0,
+                };
+                let count_before = notifications.len();
+                match up_to {
+                    Some(up_to) => notifications.retain(|n| n.id > up_to),
+                    None => notifications.clear(),
+                }
+                let acknowledged = count_before - notifications.len();
+                if notifications.is_empty() {
+                    *maybe_notifications = None;
+                }
+                acknowledged as u32
+            });
+            ensure!(acknowledged > 0, Error::<T>::NoNotifications);
+
+            Self::deposit_event(Event::<T>::NotificationsAcknowledged(who, acknowledged));
+            Ok(().into())
+        }
+
+        /// Pays `who` the recorded value of its wrongful liquidations in `asset` from the
+        /// treasury. The value is converted from USD by the current `asset` price.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::compensate_wrongful_liquidation())]
+        pub fn compensate_wrongful_liquidation(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            asset: Asset,
+        ) -> DispatchResultWithPostInfo {
+            T::CompensationOrigin::ensure_origin(origin)?;
+
+            let value = <WrongfulLiquidations<T>>::get(&who);
+            ensure!(!value.is_zero(), Error::<T>::NoWrongfulLiquidation);
+
+            let price: EqFixedU128 = T::PriceGetter::get_price(&asset)?;
+            let amount: T::Balance = eq_fixedu128_from_balance(value)
+                .checked_div(&price)
+                .and_then(balance_from_eq_fixedu128)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            T::EqCurrency::currency_transfer(
+                &T::TreasuryModuleId::get().into_account_truncating(),
+                &who,
+                asset,
+                amount,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::LiquidationCompensation,
+                true,
+            )?;
+            <WrongfulLiquidations<T>>::remove(&who);
+
+            Self::deposit_event(Event::<T>::WrongfulLiquidationCompensated(
+                who, asset, amount,
+            ));
+            Ok(().into())
+        }
+
+        /// Offers `debt` of `debt_asset` held by the caller's `subacc_type` subaccount together
+        /// with `collateral` of `collateral_asset` to `to`, who takes it over with
+        /// `accept_position_transfer`. Nothing is moved until acceptance.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::propose_position_transfer())]
+        pub fn propose_position_transfer(
+            origin: OriginFor<T>,
+            subacc_type: SubAccType,
+            to: T::AccountId,
+            debt_asset: Asset,
+            debt: T::Balance,
+            collateral_asset: Asset,
+            collateral: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(who != to, Error::<T>::SameAccount);
+            ensure!(debt_asset != collateral_asset, Error::<T>::SameAsset);
+            ensure!(
+                !<PositionTransfers<T>>::contains_key(&who),
+                Error::<T>::PositionTransferAlreadyProposed
+            );
+
+            let holder = Self::borrower_subaccount(&who, subacc_type)?;
+            Self::ensure_debt(&holder, debt_asset, debt)?;
+
+            <PositionTransfers<T>>::insert(
+                &who,
+                PositionTransfer {
+                    to: to.clone(),
+                    subacc_type,
+                    debt_asset,
+                    debt,
+                    collateral_asset,
+                    collateral,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::PositionTransferProposed(
+                who, to, debt_asset, debt,
+            ));
+            Ok(().into())
+        }
+
+        /// Withdraws the debt position offered by the caller.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::cancel_position_transfer())]
+        pub fn cancel_position_transfer(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let transfer =
+                <PositionTransfers<T>>::take(&who).ok_or(Error::<T>::NoPositionTransfer)?;
+
+            Self::deposit_event(Event::<T>::PositionTransferCancelled(who, transfer.to));
+            Ok(().into())
+        }
+
+        /// Takes over the debt position offered by `from` to the caller's `subacc_type`
+        /// subaccount. `expected_debt` and `expected_collateral` are the (asset, amount) terms
+        /// seen by the caller, the call fails if the holder re-proposed other terms.
+        /// Margin of the subaccount with the position must stay above `initial_margin`,
+        /// margin of the holder must not get worse.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::accept_position_transfer())]
+        pub fn accept_position_transfer(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            subacc_type: SubAccType,
+            expected_debt: (Asset, T::Balance),
+            expected_collateral: (Asset, T::Balance),
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let transfer = <PositionTransfers<T>>::get(&from)
+                .filter(|t| t.to == who)
+                .ok_or(Error::<T>::NoPositionTransfer)?;
+            ensure!(
+                (transfer.debt_asset, transfer.debt) == expected_debt
+                    && (transfer.collateral_asset, transfer.collateral) == expected_collateral,
+                Error::<T>::PositionTransferTermsChanged
+            );
+
+            let holder = Self::borrower_subaccount(&from, transfer.subacc_type)?;
+            let receiver = Self::borrower_subaccount(&who, subacc_type)?;
+            // position could be partially repaid or liquidated after the proposal
+            Self::ensure_debt(&holder, transfer.debt_asset, transfer.debt)?;
+
+            let (holder_margin_before, _) = Self::calculate_portfolio_margin(&holder, &[], &[])?;
+            let (holder_margin_after, _) = Self::calculate_portfolio_margin(
+                &holder,
+                &[
+                    BalanceChange {
+                        change: SignedBalance::Negative(transfer.collateral),
+                        asset: transfer.collateral_asset,
+                    },
+                    BalanceChange {
+                        change: SignedBalance::Positive(transfer.debt),
+                        asset: transfer.debt_asset,
+                    },
+                ],
+                &[],
+            )?;
+            ensure!(
+                holder_margin_after >= holder_margin_before,
+                Error::<T>::HolderMarginDecreased
+            );
+
+            let (state, _) = Self::check_margin_with_change(
+                &receiver,
+                &[
+                    BalanceChange {
+                        change: SignedBalance::Positive(transfer.collateral),
+                        asset: transfer.collateral_asset,
+                    },
+                    BalanceChange {
+                        change: SignedBalance::Negative(transfer.debt),
+                        asset: transfer.debt_asset,
+                    },
+                ],
+                &[],
+            )?;
+            ensure!(
+                state == MarginState::Good,
+                Error::<T>::PositionTransferMarginTooLow
+            );
+
+            // balance checkers of both accounts, including margin checks, are run by exchange
+            T::EqCurrency::exchange(
+                (&holder, &receiver),
+                (&transfer.collateral_asset, &transfer.debt_asset),
+                (transfer.collateral, transfer.debt),
+            )
+            .map_err(|(error, _)| error)?;
+            <PositionTransfers<T>>::remove(&from);
+
+            Self::deposit_event(Event::<T>::PositionTransferred(
+                from,
+                who,
+                transfer.debt_asset,
+                transfer.debt,
+            ));
             Ok(().into())
         }
+
+        /// Tries to margin-call an account in the operational class, so the call isn't crowded
+        /// out by normal transactions when blocks are full. Available to keepers only and
+        /// limited by `MaxPriorityMarginCallsPerBlock`. The call is free if the position is
+        /// liquidated.
+        #[pallet::call_index(6)]
+        #[pallet::weight((
+            T::WeightInfo::try_margincall_external()
+                .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+                .saturating_add(Pallet::<T>::notify_weight()),
+            DispatchClass::Operational
+        ))]
+        pub fn try_margincall_priority(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let keeper = T::KeeperOrigin::ensure_origin(origin)?;
+            Self::take_priority_slot()?;
+            log::trace!(
+                target: "eq_margin_call",
+                "Try priority margin call on account '{:?}' by keeper '{:?}'",
+                who,
+                keeper
+            );
+
+            let state = Self::try_margincall(&who)?;
+            let pays_fee = if state.good_position() {
+                Pays::Yes
+            } else {
+                Pays::No
+            };
+            let weight = Self::try_margincall_weight(
+                state,
+                T::WeightInfo::try_margincall_external()
+                    .saturating_add(T::DbWeight::get().reads_writes(1, 1)),
+            );
+            Ok(PostDispatchInfo {
+                actual_weight: Some(weight),
+                pays_fee,
+            })
+        }
     }
 }
 
@@ -219,50 +669,18 @@ impl<T: Config> MarginCallManager<T::AccountId, T::Balance> for Pallet<T> {
         balance_changes: &[BalanceChange<T::Balance>],
         order_changes: &[OrderChange],
     ) -> Result<(MarginState, bool), DispatchError> {
+        // savings can't have debt and are excluded from margin computation
+        if let Some((_, SubAccType::Savings)) = T::SubaccountsManager::get_owner_id(who) {
+            return Ok((MarginState::Good, false));
+        }
+
         let (margin, is_margin_increased) =
             Self::calculate_portfolio_margin(who, balance_changes, order_changes)?;
 
         #[cfg(feature = "std")]
         println!("margin = {margin:?}");
 
-        let initial_margin = T::InitialMargin::get();
-        let maintenance_margin = T::MaintenanceMargin::get();
-        let critical_margin = T::CriticalMargin::get();
-        let maintenance_period = T::MaintenancePeriod::get();
-
-        let state = if margin < critical_margin {
-            // we're below x < critical_margin (5%), this is a MC
-            let no_orders = T::OrderAggregates::get_asset_weights(&who).is_empty();
-            if no_orders {
-                MarginState::SubCritical
-            } else {
-                MarginState::MaintenanceIsGoing
-            }
-        } else if let Some(start) = <MaintenanceTimers<T>>::get(who) {
-            // independently extract the timer and make it impact an output state
-            if margin < initial_margin {
-                let now = T::UnixTime::now().as_secs();
-                let no_orders = T::OrderAggregates::get_asset_weights(&who).is_empty();
-                if no_orders && now.saturating_sub(start) > maintenance_period {
-                    MarginState::MaintenanceTimeOver // this is a MC
-                } else {
-                    MarginState::MaintenanceIsGoing // else we still have time
-                }
-            } else {
-                MarginState::MaintenanceEnd
-            }
-        } else if margin < maintenance_margin {
-            // critical_margin (5%) <= x < maintenance_margin (10%), we create a maintenance timer
-            MarginState::MaintenanceStart
-        } else if margin < initial_margin {
-            // maintenance_margin (10%) <= x < initial_margin (20%)
-            MarginState::SubGood
-        } else {
-            // x > initial_margin (20%)
-            MarginState::Good
-        };
-
-        Ok((state, is_margin_increased))
+        Ok((Self::margin_state(who, margin), is_margin_increased))
     }
 
     /// Tries to margin-call an account and returns its margin check result as `MarginState`
@@ -271,7 +689,24 @@ impl<T: Config> MarginCallManager<T::AccountId, T::Balance> for Pallet<T> {
 
         match state {
             //Good and SubGood states now never undergo MC
-            MarginState::Good | MarginState::SubGood | MarginState::MaintenanceIsGoing => {}
+            MarginState::Good => {}
+
+            MarginState::SubGood => {
+                Self::notify(who, RiskNotificationKind::MarginBelowInitial);
+            }
+
+            // Position will be liquidated right after its orders are deleted
+            MarginState::MaintenanceIsGoing => {
+                let now = T::UnixTime::now().as_secs();
+                let liquidation_is_imminent = match <MaintenanceTimers<T>>::get(who) {
+                    // margin is below critical, only orders keep the position from MC
+                    None => true,
+                    Some(start) => now.saturating_sub(start) > T::MaintenancePeriod::get(),
+                };
+                if liquidation_is_imminent {
+                    Self::notify(who, RiskNotificationKind::IncomingLiquidation);
+                }
+            }
 
             // 1. Position is good now, delete a maintenance timer if exists
             MarginState::MaintenanceEnd => {
@@ -294,13 +729,38 @@ impl<T: Config> MarginCallManager<T::AccountId, T::Balance> for Pallet<T> {
                     // Main account
                     Self::deposit_event(Event::<T>::MaintenanceMarginCall(who.clone(), None, now));
                 }
+                Self::notify(
+                    who,
+                    RiskNotificationKind::ApproachingMaintenance {
+                        deadline: now.saturating_add(T::MaintenancePeriod::get()),
+                    },
+                );
                 state = MarginState::MaintenanceIsGoing;
             }
 
             //3. check if a timer is over or the margin is subcritical -> do the MC.
             MarginState::MaintenanceTimeOver | MarginState::SubCritical => {
+                if let Some(asset) = Self::suspect_asset(who) {
+                    log::warn!(
+                        target: "eq_margin_call",
+                        "{}:{}. Margin call is postponed due to suspect price. Who: {:?}, asset: {:?}",
+                        file!(),
+                        line!(),
+                        who,
+                        str_asset!(asset)
+                    );
+                    Self::deposit_event(Event::<T>::MarginCallPostponed(who.clone(), asset));
+                    return Ok(state);
+                }
+                // results of positions are settled into balances taken by bailsmen,
+                // profits the perps vault can't pay are recorded there and don't fail it
+                T::DerivativePositions::close_positions(who)?;
+                let balances = T::BalanceGetter::iterate_account_balances(who);
                 T::BailsmenManager::receive_position(who, false)?;
                 <MaintenanceTimers<T>>::remove(who);
+                if state == MarginState::SubCritical {
+                    Self::add_pending_liquidation(who, balances);
+                }
                 if let Some((owner, subacc_type)) = T::SubaccountsManager::get_owner_id(&who) {
                     // Subaccount
                     Self::deposit_event(Event::<T>::MarginCallExecuted(
@@ -324,9 +784,282 @@ impl<T: Config> MarginCallManager<T::AccountId, T::Balance> for Pallet<T> {
     }
 }
 
+//------------------- RiskNotifier --------------------------------------------
+impl<T: Config> RiskNotifier<T::AccountId> for Pallet<T> {
+    /// Stores notification for the main account, the same unacknowledged notification
+    /// is not duplicated
+    fn notify(who: &T::AccountId, kind: RiskNotificationKind) {
+        let (owner, subaccount) = match T::SubaccountsManager::get_owner_id(who) {
+            Some((owner, subacc_type)) => (owner, Some((subacc_type, who.clone()))),
+            None => (who.clone(), None),
+        };
+
+        <Notifications<T>>::mutate(&owner, |notifications| {
+            if notifications
+                .iter()
+                .any(|n| n.kind == kind && n.subaccount == subaccount)
+            {
+                return;
+            }
+
+            let id = <NextNotificationId<T>>::mutate(|next_id| {
+                let id = *next_id;
+                *next_id = next_id.wrapping_add(1);
+                id
+            });
+            let notification = RiskNotification {
+                id,
+                kind,
+                subaccount,
+                timestamp: T::UnixTime::now().as_secs(),
+            };
+            if !notifications.is_empty()
+                && notifications.len() >= T::MaxNotifications::get() as usize
+            {
+                notifications.remove(0);
+            }
+            let _ = notifications.try_push(notification);
+        });
+    }
+}
+
 /* ----------------- IMPL PALLET ------------------ */
 impl<T: Config> Pallet<T> {
-    /// Calculates sell and buy margin and returns min of them
+    /// DB weight of `notify`: owner lookup, notifications of the owner and notification id
+    fn notify_weight() -> Weight {
+        T::DbWeight::get().reads_writes(3, 2)
+    }
+
+    /// Weight of a margin call resulted in `state`, notification writes are added to
+    /// `base` for states `try_margincall` notifies about
+    fn try_margincall_weight(state: MarginState, base: Weight) -> Weight {
+        match state {
+            MarginState::SubGood | MarginState::MaintenanceIsGoing => {
+                base.saturating_add(Self::notify_weight())
+            }
+            _ => base,
+        }
+    }
+
+    /// Counts a priority margin call in the current block, fails when
+    /// `MaxPriorityMarginCallsPerBlock` is reached
+    fn take_priority_slot() -> Result<(), DispatchError> {
+        let now = frame_system::Pallet::<T>::block_number();
+        <PriorityMarginCalls<T>>::try_mutate(|(block, count)| {
+            if *block != now {
+                *block = now;
+                *count = 0;
+            }
+            frame_support::ensure!(
+                *count < T::MaxPriorityMarginCallsPerBlock::get(),
+                Error::<T>::PriorityLaneExhausted
+            );
+            *count += 1;
+            Ok(())
+        })
+    }
+
+    /// Subaccount of `who` which may hold a debt position
+    fn borrower_subaccount(
+        who: &T::AccountId,
+        subacc_type: SubAccType,
+    ) -> Result<T::AccountId, DispatchError> {
+        frame_support::ensure!(
+            matches!(subacc_type, SubAccType::Trader | SubAccType::Borrower),
+            Error::<T>::NotBorrowerSubaccount
+        );
+        T::SubaccountsManager::get_subaccount_id(who, &subacc_type)
+            .ok_or_else(|| Error::<T>::NoSubaccount.into())
+    }
+
+    /// Ensures `who` has at least `amount` of debt in `asset`
+    fn ensure_debt(
+        who: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+    ) -> Result<(), DispatchError> {
+        let has_debt = match T::BalanceGetter::get_balance(who, &asset) {
+            SignedBalance::Negative(debt) => !amount.is_zero() && amount <= debt,
+            SignedBalance::Positive(_) => false,
+        };
+        frame_support::ensure!(has_debt, Error::<T>::NotEnoughDebt);
+        Ok(())
+    }
+
+    /// First asset held by `who` which price is suspect by the oracle circuit breaker
+    fn suspect_asset(who: &T::AccountId) -> Option<Asset> {
+        T::AssetGetter::get_assets().into_iter().find(|asset| {
+            T::PriceGetter::is_price_suspect(asset)
+                && !T::BalanceGetter::get_balance(who, asset).is_zero()
+        })
+    }
+
+    /// Stores position of `who` liquidated in the current block, `balances` are taken
+    /// before the liquidation
+    fn add_pending_liquidation(
+        who: &T::AccountId,
+        balances: VecMap<Asset, SignedBalance<T::Balance>>,
+    ) {
+        let mut taken = balances.clone();
+        for (asset, rest) in T::BalanceGetter::iterate_account_balances(who) {
+            let before = taken
+                .get(&asset)
+                .cloned()
+                .unwrap_or_else(SignedBalance::zero);
+            if let Some(diff) = before.checked_sub(&rest) {
+                taken.insert(asset, diff);
+            }
+        }
+
+        let snapshot = LiquidationSnapshot {
+            who: who.clone(),
+            block: frame_system::Pallet::<T>::block_number(),
+            balances,
+            taken,
+        };
+        <PendingLiquidations<T>>::mutate(|pending| {
+            if !pending.is_empty() && pending.len() >= T::MaxPendingLiquidations::get() as usize {
+                pending.remove(0);
+            }
+            let _ = pending.try_push(snapshot);
+        });
+    }
+
+    /// Records liquidations which positions are above `critical_margin` with current prices
+    /// and drops liquidations older than `PriceRestorePeriod`
+    fn check_pending_liquidations(
+        now: T::BlockNumber,
+        pending: Vec<LiquidationSnapshot<T::AccountId, T::Balance, T::BlockNumber>>,
+    ) {
+        let restore_period = T::PriceRestorePeriod::get();
+        let still_pending: Vec<_> = pending
+            .into_iter()
+            .filter(|snapshot| match Self::restored_position_value(snapshot) {
+                Some(value) => {
+                    <WrongfulLiquidations<T>>::mutate(&snapshot.who, |recorded| {
+                        *recorded = recorded.saturating_add(value)
+                    });
+                    Self::deposit_event(Event::<T>::WrongfulLiquidationRecorded(
+                        snapshot.who.clone(),
+                        value,
+                    ));
+                    false
+                }
+                None => now.saturating_sub(snapshot.block) < restore_period,
+            })
+            .collect();
+
+        <PendingLiquidations<T>>::put(BoundedVec::truncate_from(still_pending));
+    }
+
+    /// USD value taken by the liquidation if the position is above `critical_margin` with
+    /// current prices
+    fn restored_position_value(
+        snapshot: &LiquidationSnapshot<T::AccountId, T::Balance, T::BlockNumber>,
+    ) -> Option<T::Balance> {
+        let margin =
+            Self::calculate_portfolio_margin_for_balances(&snapshot.who, &snapshot.balances, &[])
+                .ok()?;
+        if margin < T::CriticalMargin::get() {
+            return None;
+        }
+
+        let mut value = SignedBalance::<T::Balance>::zero();
+        for (asset, balance) in snapshot.taken.iter() {
+            let price: EqFixedU128 = T::PriceGetter::get_price(asset).ok()?;
+            value = match balance {
+                SignedBalance::Positive(collateral) => {
+                    value.add_balance(&price.checked_mul_int((*collateral).into())?.into())?
+                }
+                SignedBalance::Negative(debt) => {
+                    value.sub_balance(&price.checked_mul_int((*debt).into())?.into())?
+                }
+            };
+        }
+
+        match value {
+            SignedBalance::Positive(value) if !value.is_zero() => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Unacknowledged risk notifications of `who`, used in runtime API
+    pub fn get_notifications(who: &T::AccountId) -> Vec<RiskNotification<T::AccountId>> {
+        <Notifications<T>>::get(who).into_inner()
+    }
+
+    /// Margin of `who` after hypothetical balance and order changes, nothing is applied.
+    /// Used in runtime API to show the margin before submitting an order or transfer.
+    pub fn simulate_margin(
+        who: &T::AccountId,
+        balance_changes: Vec<(Asset, SignedBalance<T::Balance>)>,
+        order_changes: Vec<OrderChange>,
+    ) -> Result<MarginSimulation, DispatchError> {
+        let balance_changes: Vec<_> = balance_changes
+            .into_iter()
+            .map(|(asset, change)| BalanceChange { change, asset })
+            .collect();
+        let (margin, _) = Self::calculate_portfolio_margin(who, &balance_changes, &order_changes)?;
+        let (state, _) = Self::check_margin_with_change(who, &balance_changes, &order_changes)?;
+
+        Ok(MarginSimulation { state, margin })
+    }
+
+    /// Margin state of `who` with `margin`
+    fn margin_state(who: &T::AccountId, margin: EqFixedU128) -> MarginState {
+        let initial_margin = T::InitialMargin::get();
+        let maintenance_margin = T::MaintenanceMargin::get();
+        let critical_margin = T::CriticalMargin::get();
+        let maintenance_period = T::MaintenancePeriod::get();
+
+        if margin < critical_margin {
+            // we're below x < critical_margin (5%), this is a MC
+            let no_orders = T::OrderAggregates::get_asset_weights(&who).is_empty();
+            if no_orders {
+                MarginState::SubCritical
+            } else {
+                MarginState::MaintenanceIsGoing
+            }
+        } else if let Some(start) = <MaintenanceTimers<T>>::get(who) {
+            // independently extract the timer and make it impact an output state
+            if margin < initial_margin {
+                let now = T::UnixTime::now().as_secs();
+                let no_orders = T::OrderAggregates::get_asset_weights(&who).is_empty();
+                if no_orders && now.saturating_sub(start) > maintenance_period {
+                    MarginState::MaintenanceTimeOver // this is a MC
+                } else {
+                    MarginState::MaintenanceIsGoing // else we still have time
+                }
+            } else {
+                MarginState::MaintenanceEnd
+            }
+        } else if margin < maintenance_margin {
+            // critical_margin (5%) <= x < maintenance_margin (10%), we create a maintenance timer
+            MarginState::MaintenanceStart
+        } else if margin < initial_margin {
+            // maintenance_margin (10%) <= x < initial_margin (20%)
+            MarginState::SubGood
+        } else {
+            // x > initial_margin (20%)
+            MarginState::Good
+        }
+    }
+
+    /// Margin of `who` after `balance_changes` if it is lower than `initial_margin`.
+    /// Used in runtime API to explain rejected balance changes.
+    pub fn margin_shortfall(
+        who: &T::AccountId,
+        balance_changes: &[BalanceChange<T::Balance>],
+    ) -> Result<Option<MarginShortfall>, DispatchError> {
+        let (margin, _) = Self::calculate_portfolio_margin(who, balance_changes, &[])?;
+        let required = T::InitialMargin::get();
+
+        Ok((margin < required).then(|| MarginShortfall { margin, required }))
+    }
+
+    /// Calculates sell and buy margin and returns min of them.
+    /// Resting orders of an account fill independently, so opposite orders of the same asset
+    /// aren't netted: sell margin assumes all sell orders are filled, buy margin all buy orders
     fn calculate_portfolio_margin_for_balances(
         owner: &T::AccountId,
         balances: &VecMap<Asset, SignedBalance<T::Balance>>,
@@ -334,8 +1067,11 @@ impl<T: Config> Pallet<T> {
     ) -> Result<EqFixedU128, DispatchError> {
         let mut order_aggregates = T::OrderAggregates::get_asset_weights(&owner);
 
+        // open derivative positions are exposed as orders filled at the entry price
+        let position_changes = T::DerivativePositions::get_position_changes(&owner);
+
         //add order changes to order aggregates
-        for change in order_changes {
+        for change in order_changes.iter().chain(position_changes.iter()) {
             let price =
                 eq_fixedu128_from_fixedi64(change.price).ok_or(ArithmeticError::Overflow)?;
 
@@ -371,6 +1107,7 @@ impl<T: Config> Pallet<T> {
                                  balance: &SignedBalance<T::Balance>,
                                  price: EqFixedU128,
                                  discount: Percent,
+                                 multiplier: EqFixedU128,
                                  collateral: &mut SignedBalance<T::Balance>,
                                  collateral_eqd: &mut SignedBalance<T::Balance>,
                                  debt: &mut SignedBalance<T::Balance>|
@@ -400,7 +1137,9 @@ impl<T: Config> Pallet<T> {
                     *collateral = collateral.add_balance(&discounted.into())?;
                 }
                 SignedBalance::Negative(debt_part) => {
-                    *debt = debt.sub_balance(&price.checked_mul_int(debt_part.into())?.into())?;
+                    let dp_by_price = price.checked_mul_int(debt_part.into())?;
+                    let weighted = multiplier.checked_mul_int(dp_by_price)?;
+                    *debt = debt.sub_balance(&weighted.into())?;
                 }
             };
 
@@ -443,12 +1182,18 @@ impl<T: Config> Pallet<T> {
             Ok(margin)
         };
 
+        let multipliers = T::RiskTierMarginMultipliers::get();
         for asset in assets {
             let asset_data = T::AssetGetter::get_asset_data(&asset)?;
 
             let price = T::PriceGetter::get_price(&asset)?;
-            let discount = asset_data.collateral_discount;
+            let discount = if T::LendingIsolation::is_isolated(&asset) {
+                Percent::zero()
+            } else {
+                asset_data.collateral_discount
+            };
 
+            let multiplier = multipliers.get(T::AssetGetter::risk_tier(&asset));
             let maybe_order_aggregate = order_aggregates.get(&asset);
 
             let balance = balances.get(&asset).unwrap_or(&zero);
@@ -471,6 +1216,7 @@ impl<T: Config> Pallet<T> {
                 balance,
                 price,
                 discount,
+                multiplier,
                 &mut sell_collateral,
                 &mut sell_collateral_eqd,
                 &mut sell_debt,
@@ -483,6 +1229,7 @@ impl<T: Config> Pallet<T> {
                 balance,
                 price,
                 discount,
+                multiplier,
                 &mut buy_collateral,
                 &mut buy_collateral_eqd,
                 &mut buy_debt,
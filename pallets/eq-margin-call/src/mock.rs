@@ -162,8 +162,19 @@ parameter_types! {
     pub MaintenanceMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(25, 1000);
     pub CriticalMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(5, 1000);
     pub MaintenancePeriod: u64 = 86_400;
+    pub const MaxNotifications: u32 = 3;
+    pub const PriceRestorePeriod: u64 = 10;
+    pub const MaxPendingLiquidations: u32 = 10;
+    pub Keepers: Vec<AccountId> = vec![KEEPER];
+    pub const MaxPriorityMarginCallsPerBlock: u32 = 2;
     pub const MainAsset: eq_primitives::asset::Asset = eq_primitives::asset::EQ;
     pub const BalancesModuleId: PalletId = PalletId(*b"eq/resrv");
+    pub RiskTierMarginMultipliers: eq_primitives::asset::RiskTierParams<EqFixedU128> =
+        eq_primitives::asset::RiskTierParams {
+            tier1: EqFixedU128::one(),
+            tier2: EqFixedU128::one(),
+            tier3: EqFixedU128::from(2),
+        };
 }
 
 impl Config for Test {
@@ -178,11 +189,24 @@ impl Config for Test {
     type CriticalMargin = CriticalMargin;
     type MaintenancePeriod = MaintenancePeriod;
     type OrderAggregates = OrderAggregatesMock;
+    type DerivativePositions = ();
     type AssetGetter = eq_assets::Pallet<Test>;
     type SubaccountsManager = SubaccountsManagerMock;
+    type MaxNotifications = MaxNotifications;
     type WeightInfo = ();
+    type LendingIsolation = ();
+    type RiskTierMarginMultipliers = RiskTierMarginMultipliers;
+    type EqCurrency = eq_balances::Pallet<Test>;
+    type TreasuryModuleId = TreasuryModuleId;
+    type CompensationOrigin = EnsureRoot<AccountId>;
+    type PriceRestorePeriod = PriceRestorePeriod;
+    type MaxPendingLiquidations = MaxPendingLiquidations;
+    type KeeperOrigin = system::EnsureSignedBy<Keepers, AccountId>;
+    type MaxPriorityMarginCallsPerBlock = MaxPriorityMarginCallsPerBlock;
 }
 
+pub const KEEPER: AccountId = 100;
+
 impl_opaque_keys! {
     pub struct SessionKeys {
 
@@ -201,6 +225,9 @@ where
 
 /* ----------------------- eq_subaccounts -------------------- */
 
+/// Subaccount of `who` is `who + SUBACCOUNT_OFFSET` for every subaccount type
+pub const SUBACCOUNT_OFFSET: u64 = 1000;
+
 pub struct SubaccountsManagerMock;
 
 impl SubaccountsManager<u64> for SubaccountsManagerMock {
@@ -222,8 +249,8 @@ impl SubaccountsManager<u64> for SubaccountsManagerMock {
         true
     }
 
-    fn get_subaccount_id(_who: &AccountId, _subacc_type: &SubAccType) -> Option<u64> {
-        Some(9999_u64)
+    fn get_subaccount_id(who: &AccountId, _subacc_type: &SubAccType) -> Option<u64> {
+        Some(who + SUBACCOUNT_OFFSET)
     }
 
     fn is_subaccount(_who: &AccountId, _subacc_id: &u64) -> bool {
@@ -262,6 +289,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -432,6 +463,8 @@ parameter_types! {
     pub const MinTempBalanceUsd: Balance = 0; // always reinit
     pub const MaxBailsmenToDistribute: u32 = 1;
     pub const QueueLengthWeightConstant: u32 = 5;
+    pub const RedistributionWeightBudget: Weight = Weight::zero();
+    pub const MaxPriorityRedistributionsPerBlock: u32 = 10;
 }
 
 impl eq_bailsman::Config for Test {
@@ -445,6 +478,7 @@ impl eq_bailsman::Config for Test {
     type MinTempBalanceUsd = MinTempBalanceUsd;
     type UnixTime = ModuleTimestamp;
     type PalletId = BailsmanModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
     type Aggregates = AggregatesMock;
     type WeightInfo = ();
     type MarginCallManager = Pallet<Test>;
@@ -455,6 +489,8 @@ impl eq_bailsman::Config for Test {
     type UnsignedPriority = UnsignedPriority;
     type ValidatorOffchainBatcher = ();
     type QueueLengthWeightConstant = QueueLengthWeightConstant;
+    type RedistributionWeightBudget = RedistributionWeightBudget;
+    type MaxPriorityRedistributionsPerBlock = MaxPriorityRedistributionsPerBlock;
 }
 
 // -------------- eq-buyout -----------------------------------------
@@ -33,7 +33,7 @@ use eq_utils::{
 };
 use frame_support::dispatch::DispatchError::BadOrigin;
 use frame_support::dispatch::{DispatchError, DispatchErrorWithPostInfo, Pays, PostDispatchInfo};
-use frame_support::traits::OffchainWorker;
+use frame_support::traits::{Hooks, OffchainWorker};
 use frame_support::{assert_err, assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use sp_arithmetic::{FixedI128, FixedPointNumber, FixedU128};
@@ -96,6 +96,55 @@ fn margincall_external() {
     });
 }
 
+#[test]
+fn margincall_priority_is_limited_to_keepers_and_block_cap() {
+    new_test_ext().execute_with(|| {
+        let set_balances = |collateral: Balance, debt: Balance| {
+            ModuleBalances::make_free_balance_be(
+                &USER,
+                asset::BTC,
+                SignedBalance::<Balance>::Positive(collateral),
+            );
+            ModuleBalances::make_free_balance_be(
+                &USER,
+                asset::EQD,
+                SignedBalance::<Balance>::Negative(debt),
+            );
+        };
+
+        set_balances(100 * ONE_TOKEN, 9623 * ONE_TOKEN);
+        assert_noop!(
+            ModuleMarginCall::try_margincall_priority(RuntimeOrigin::signed(USER), USER),
+            BadOrigin
+        );
+
+        // nothing to liquidate, keeper pays for the call
+        let post_info =
+            ModuleMarginCall::try_margincall_priority(RuntimeOrigin::signed(KEEPER), USER).unwrap();
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+
+        set_balances(10 * ONE_TOKEN, 962380 * ONE_TOKEN);
+        let post_info =
+            ModuleMarginCall::try_margincall_priority(RuntimeOrigin::signed(KEEPER), USER).unwrap();
+        assert_eq!(post_info.pays_fee, Pays::No);
+        let DebtCollateralDiscounted { debt, .. } =
+            ModuleBalances::get_debt_and_collateral(&USER).unwrap();
+        assert_eq!(debt, Balance::zero());
+
+        set_balances(100 * ONE_TOKEN, 9623 * ONE_TOKEN);
+        assert_noop!(
+            ModuleMarginCall::try_margincall_priority(RuntimeOrigin::signed(KEEPER), USER),
+            Error::<Test>::PriorityLaneExhausted
+        );
+
+        System::set_block_number(System::block_number() + 1);
+        assert_ok!(ModuleMarginCall::try_margincall_priority(
+            RuntimeOrigin::signed(KEEPER),
+            USER
+        ));
+    });
+}
+
 use eq_primitives::OrderChange;
 #[allow(unused_imports)]
 use eq_primitives::{MarginCallManager, MarginState};
@@ -502,6 +551,43 @@ fn calculate_portfolio_margin_with_one_half_discount_asset() {
     });
 }
 
+#[test]
+fn calculate_portfolio_margin_should_weight_debt_by_risk_tier() {
+    new_test_ext().execute_with(|| {
+        let account_id = 102u64;
+        ModuleBalances::make_free_balance_be(
+            &account_id,
+            asset::ETH,
+            SignedBalance::<Balance>::Positive(1 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &account_id,
+            asset::DOT,
+            SignedBalance::<Balance>::Negative(25 * ONE_TOKEN),
+        );
+
+        let (margin, _) =
+            ModuleMarginCall::calculate_portfolio_margin(&account_id, &[], &[]).unwrap();
+        assert_eq!(
+            margin,
+            EqFixedU128::saturating_from_rational(250 - 100, 250)
+        );
+
+        // tier 3 debt is doubled in mock
+        assert_ok!(eq_assets::Pallet::<Test>::set_risk_tier(
+            RawOrigin::Root.into(),
+            asset::DOT,
+            asset::RiskTier::Tier3
+        ));
+        let (margin, _) =
+            ModuleMarginCall::calculate_portfolio_margin(&account_id, &[], &[]).unwrap();
+        assert_eq!(
+            margin,
+            EqFixedU128::saturating_from_rational(250 - 200, 250)
+        );
+    });
+}
+
 // #[test]
 // fn calculate_margin_coefficients_should_work_with_empty_changes() {
 //     assert_eq!(
@@ -948,3 +1034,569 @@ fn calc_margin_with_buy_sell_orders() {
         assert_eq!(margin, EqFixedU128::from_float(0.649204864));
     });
 }
+
+#[test]
+fn opposite_orders_are_margined_as_one_sided_fills() {
+    new_test_ext().execute_with(|| {
+        let account_id = 1u64;
+
+        ModuleBalances::make_free_balance_be(
+            &account_id,
+            asset::BTC,
+            SignedBalance::<u128>::Positive(10 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &account_id,
+            asset::EQD,
+            SignedBalance::<u128>::Negative(50_000 * ONE_TOKEN),
+        );
+
+        let side_margin = |side| {
+            let aggregate =
+                OrderAggregateBySide::new(EqFixedU128::from(100), EqFixedU128::from(250), side)
+                    .unwrap();
+            OrderAggregatesMock::set_order_aggregates(vec![(asset::ETH, aggregate)]);
+            ModuleMarginCall::calculate_portfolio_margin(&account_id, &[], &[])
+                .unwrap()
+                .0
+        };
+        let sell_margin = side_margin(OrderSide::Sell);
+        let buy_margin = side_margin(OrderSide::Buy);
+
+        // market maker quotes both sides of ETH, either side may be filled alone
+        let mut eth_order_aggregate = OrderAggregateBySide::new(
+            EqFixedU128::from(100),
+            EqFixedU128::from(250),
+            OrderSide::Sell,
+        )
+        .unwrap();
+        eth_order_aggregate
+            .add(
+                EqFixedU128::from(100),
+                EqFixedU128::from(250),
+                OrderSide::Buy,
+            )
+            .unwrap();
+        OrderAggregatesMock::set_order_aggregates(vec![(asset::ETH, eth_order_aggregate)]);
+
+        let (margin, _) =
+            ModuleMarginCall::calculate_portfolio_margin(&account_id, &[], &[]).unwrap();
+
+        // exposure of both sides isn't added up, the worse fill is taken
+        assert_eq!(margin, sell_margin.min(buy_margin));
+    });
+}
+
+#[test]
+fn margincall_subgood_notifies_once() {
+    new_test_ext().execute_with(|| {
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(100 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::EQD,
+            SignedBalance::<Balance>::Negative(962380 * ONE_TOKEN),
+        );
+        assert_eq!(
+            ModuleMarginCall::try_margincall(&USER).unwrap(),
+            MarginState::SubGood
+        );
+        assert_eq!(
+            ModuleMarginCall::try_margincall(&USER).unwrap(),
+            MarginState::SubGood
+        );
+
+        let notifications = ModuleMarginCall::get_notifications(&USER);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(
+            notifications[0].kind,
+            RiskNotificationKind::MarginBelowInitial
+        );
+        assert_eq!(notifications[0].subaccount, None);
+    });
+}
+
+#[test]
+fn margincall_maintenance_notifies_deadline_and_incoming_liquidation() {
+    new_test_ext().execute_with(|| {
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(100 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::EQD,
+            SignedBalance::<Balance>::Negative(975001 * ONE_TOKEN),
+        );
+        OrderAggregatesMock::set_order_aggregates(vec![(
+            asset::BTC,
+            OrderAggregateBySide::default(),
+        )]);
+
+        let now = ModuleTimestamp::now().as_secs();
+        assert_ok!(ModuleMarginCall::try_margincall(&USER));
+        assert_eq!(
+            ModuleMarginCall::get_notifications(&USER)
+                .iter()
+                .map(|n| n.kind)
+                .collect::<Vec<_>>(),
+            vec![RiskNotificationKind::ApproachingMaintenance {
+                deadline: now + MaintenancePeriod::get()
+            }]
+        );
+
+        ModuleTimestamp::set_timestamp(ModuleTimestamp::get() + 86_401_000);
+
+        // orders prevent the position from liquidation
+        assert_eq!(
+            ModuleMarginCall::try_margincall(&USER).unwrap(),
+            MarginState::MaintenanceIsGoing
+        );
+        let notifications = ModuleMarginCall::get_notifications(&USER);
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(
+            notifications[1].kind,
+            RiskNotificationKind::IncomingLiquidation
+        );
+    });
+}
+
+#[test]
+fn notifications_drop_oldest_when_full() {
+    new_test_ext().execute_with(|| {
+        for order_id in 0..5 {
+            ModuleMarginCall::notify(
+                &USER,
+                RiskNotificationKind::OrderPenalized {
+                    asset: asset::BTC,
+                    order_id,
+                    reason: eq_primitives::DeleteOrderReason::OutOfCorridor,
+                },
+            );
+        }
+
+        let ids: Vec<_> = ModuleMarginCall::get_notifications(&USER)
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(ids, vec![2, 3, 4]);
+    });
+}
+
+#[test]
+fn acknowledge_notifications() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ModuleMarginCall::acknowledge_notifications(RuntimeOrigin::signed(USER), None),
+            Error::<Test>::NoNotifications
+        );
+
+        for order_id in 0..3 {
+            ModuleMarginCall::notify(
+                &USER,
+                RiskNotificationKind::OrderPenalized {
+                    asset: asset::BTC,
+                    order_id,
+                    reason: eq_primitives::DeleteOrderReason::MarginCall,
+                },
+            );
+        }
+
+        assert_ok!(ModuleMarginCall::acknowledge_notifications(
+            RuntimeOrigin::signed(USER),
+            Some(1)
+        ));
+        let ids: Vec<_> = ModuleMarginCall::get_notifications(&USER)
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(ids, vec![2]);
+        assert_noop!(
+            ModuleMarginCall::acknowledge_notifications(RuntimeOrigin::signed(USER), Some(1)),
+            Error::<Test>::NoNotifications
+        );
+
+        assert_ok!(ModuleMarginCall::acknowledge_notifications(
+            RuntimeOrigin::signed(USER),
+            None
+        ));
+        assert!(!Notifications::<Test>::contains_key(&USER));
+    });
+}
+
+#[test]
+fn margin_shortfall() {
+    new_test_ext().execute_with(|| {
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(100 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::EQD,
+            SignedBalance::<Balance>::Negative(962380 * ONE_TOKEN),
+        );
+
+        let shortfall = ModuleMarginCall::margin_shortfall(&USER, &[])
+            .unwrap()
+            .unwrap();
+        assert_eq!(shortfall.required, InitialMargin::get());
+        assert!(shortfall.margin < shortfall.required);
+
+        let repay = BalanceChange {
+            change: SignedBalance::Positive(962380 * ONE_TOKEN),
+            asset: asset::EQD,
+        };
+        assert_eq!(
+            ModuleMarginCall::margin_shortfall(&USER, &[repay]).unwrap(),
+            None
+        );
+    });
+}
+
+#[test]
+fn simulate_margin_does_not_change_balances() {
+    new_test_ext().execute_with(|| {
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(100 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::EQD,
+            SignedBalance::<Balance>::Negative(9623 * ONE_TOKEN),
+        );
+
+        let simulation = ModuleMarginCall::simulate_margin(&USER, vec![], vec![]).unwrap();
+        assert_eq!(simulation.state, MarginState::Good);
+
+        let borrow = (
+            asset::EQD,
+            SignedBalance::Negative((962380 - 9623) * ONE_TOKEN),
+        );
+        let after_borrow = ModuleMarginCall::simulate_margin(&USER, vec![borrow], vec![]).unwrap();
+        assert_eq!(after_borrow.state, MarginState::SubGood);
+        assert!(after_borrow.margin < simulation.margin);
+
+        assert_eq!(
+            ModuleBalances::get_balance(&USER, &asset::EQD),
+            SignedBalance::Negative(9623 * ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleMarginCall::check_margin(&USER).unwrap(),
+            MarginState::Good
+        );
+    });
+}
+
+#[test]
+fn wrongful_liquidation_is_recorded_and_compensated() {
+    new_test_ext().execute_with(|| {
+        ModuleSystem::set_block_number(1);
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(100 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::EQD,
+            SignedBalance::<Balance>::Negative(999_999 * ONE_TOKEN),
+        );
+
+        let r = ModuleMarginCall::try_margincall(&USER).unwrap();
+        assert_eq!(r, MarginState::SubCritical);
+        assert_eq!(PendingLiquidations::<Test>::get().len(), 1);
+
+        // prices are still the same
+        ModuleMarginCall::on_initialize(2);
+        assert_eq!(PendingLiquidations::<Test>::get().len(), 1);
+        assert!(!WrongfulLiquidations::<Test>::contains_key(&USER));
+
+        assert_ok!(OracleMock::set_price(
+            0,
+            asset::BTC,
+            FixedI64::saturating_from_integer(11_000)
+        ));
+        ModuleMarginCall::on_initialize(3);
+        assert!(PendingLiquidations::<Test>::get().is_empty());
+        let value = ModuleMarginCall::wrongful_liquidations(&USER);
+        assert!(!value.is_zero());
+
+        let treasury: u64 = TreasuryModuleId::get().into_account_truncating();
+        ModuleBalances::make_free_balance_be(
+            &treasury,
+            asset::EQD,
+            SignedBalance::<Balance>::Positive(1_000_000 * ONE_TOKEN),
+        );
+        let eqd_before = ModuleBalances::get_balance(&USER, &asset::EQD);
+
+        assert_noop!(
+            ModuleMarginCall::compensate_wrongful_liquidation(
+                RuntimeOrigin::signed(USER),
+                USER,
+                asset::EQD
+            ),
+            BadOrigin
+        );
+        assert_ok!(ModuleMarginCall::compensate_wrongful_liquidation(
+            RuntimeOrigin::root(),
+            USER,
+            asset::EQD
+        ));
+        assert_eq!(
+            ModuleBalances::get_balance(&USER, &asset::EQD),
+            eqd_before + SignedBalance::Positive(value)
+        );
+        assert_noop!(
+            ModuleMarginCall::compensate_wrongful_liquidation(
+                RuntimeOrigin::root(),
+                USER,
+                asset::EQD
+            ),
+            Error::<Test>::NoWrongfulLiquidation
+        );
+    });
+}
+
+#[test]
+fn pending_liquidation_expires_without_restored_prices() {
+    new_test_ext().execute_with(|| {
+        ModuleSystem::set_block_number(1);
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(100 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &USER,
+            asset::EQD,
+            SignedBalance::<Balance>::Negative(999_999 * ONE_TOKEN),
+        );
+        assert_ok!(ModuleMarginCall::try_margincall(&USER));
+
+        ModuleMarginCall::on_initialize(PriceRestorePeriod::get());
+        assert_eq!(PendingLiquidations::<Test>::get().len(), 1);
+
+        ModuleMarginCall::on_initialize(1 + PriceRestorePeriod::get());
+        assert!(PendingLiquidations::<Test>::get().is_empty());
+        assert!(!WrongfulLiquidations::<Test>::contains_key(&USER));
+    });
+}
+
+#[test]
+fn position_transfer_moves_debt_and_collateral() {
+    new_test_ext().execute_with(|| {
+        ModuleSystem::set_block_number(1);
+        const OTC_DESK: u64 = 0x2;
+        let holder = USER + SUBACCOUNT_OFFSET;
+        let receiver = OTC_DESK + SUBACCOUNT_OFFSET;
+        // margin is 2%, below `initial_margin`
+        ModuleBalances::make_free_balance_be(
+            &holder,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(100 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &holder,
+            asset::EQD,
+            SignedBalance::<Balance>::Negative(980_000 * ONE_TOKEN),
+        );
+
+        let propose = |debt: Balance| {
+            ModuleMarginCall::propose_position_transfer(
+                RuntimeOrigin::signed(USER),
+                SubAccType::Trader,
+                OTC_DESK,
+                asset::EQD,
+                debt,
+                asset::BTC,
+                50 * ONE_TOKEN,
+            )
+        };
+        assert_noop!(
+            ModuleMarginCall::propose_position_transfer(
+                RuntimeOrigin::signed(USER),
+                SubAccType::Savings,
+                OTC_DESK,
+                asset::EQD,
+                495_000 * ONE_TOKEN,
+                asset::BTC,
+                50 * ONE_TOKEN,
+            ),
+            Error::<Test>::NotBorrowerSubaccount
+        );
+        assert_noop!(propose(1_000_000 * ONE_TOKEN), Error::<Test>::NotEnoughDebt);
+        assert_ok!(propose(495_000 * ONE_TOKEN));
+        assert_noop!(
+            propose(495_000 * ONE_TOKEN),
+            Error::<Test>::PositionTransferAlreadyProposed
+        );
+        // nothing is moved until acceptance
+        assert_eq!(
+            ModuleBalances::get_balance(&holder, &asset::EQD),
+            SignedBalance::Negative(980_000 * ONE_TOKEN)
+        );
+
+        assert_noop!(
+            ModuleMarginCall::accept_position_transfer(
+                RuntimeOrigin::signed(0x3),
+                USER,
+                SubAccType::Trader,
+                (asset::EQD, 495_000 * ONE_TOKEN),
+                (asset::BTC, 50 * ONE_TOKEN),
+            ),
+            Error::<Test>::NoPositionTransfer
+        );
+        assert_noop!(
+            ModuleMarginCall::accept_position_transfer(
+                RuntimeOrigin::signed(OTC_DESK),
+                USER,
+                SubAccType::Trader,
+                (asset::EQD, 495_000 * ONE_TOKEN),
+                (asset::BTC, 60 * ONE_TOKEN),
+            ),
+            Error::<Test>::PositionTransferTermsChanged
+        );
+        // receiver without collateral of its own would have margin of 1%
+        assert_noop!(
+            ModuleMarginCall::accept_position_transfer(
+                RuntimeOrigin::signed(OTC_DESK),
+                USER,
+                SubAccType::Trader,
+                (asset::EQD, 495_000 * ONE_TOKEN),
+                (asset::BTC, 50 * ONE_TOKEN),
+            ),
+            Error::<Test>::PositionTransferMarginTooLow
+        );
+
+        ModuleBalances::make_free_balance_be(
+            &receiver,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(50 * ONE_TOKEN),
+        );
+        assert_ok!(ModuleMarginCall::accept_position_transfer(
+            RuntimeOrigin::signed(OTC_DESK),
+            USER,
+            SubAccType::Trader,
+            (asset::EQD, 495_000 * ONE_TOKEN),
+            (asset::BTC, 50 * ONE_TOKEN),
+        ));
+        assert_eq!(
+            ModuleBalances::get_balance(&holder, &asset::BTC),
+            SignedBalance::Positive(50 * ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&holder, &asset::EQD),
+            SignedBalance::Negative(485_000 * ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&receiver, &asset::BTC),
+            SignedBalance::Positive(100 * ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&receiver, &asset::EQD),
+            SignedBalance::Negative(495_000 * ONE_TOKEN)
+        );
+        assert!(ModuleMarginCall::position_transfers(&USER).is_none());
+        ModuleSystem::assert_last_event(
+            Event::<Test>::PositionTransferred(USER, OTC_DESK, asset::EQD, 495_000 * ONE_TOKEN)
+                .into(),
+        );
+    });
+}
+
+#[test]
+fn position_transfer_does_not_decrease_holder_margin() {
+    new_test_ext().execute_with(|| {
+        ModuleSystem::set_block_number(1);
+        const OTC_DESK: u64 = 0x2;
+        let holder = USER + SUBACCOUNT_OFFSET;
+        let receiver = OTC_DESK + SUBACCOUNT_OFFSET;
+        ModuleBalances::make_free_balance_be(
+            &holder,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(100 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &holder,
+            asset::EQD,
+            SignedBalance::<Balance>::Negative(500_000 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &receiver,
+            asset::BTC,
+            SignedBalance::<Balance>::Positive(100 * ONE_TOKEN),
+        );
+
+        // collateral of the offer is larger than the share of the debt
+        assert_ok!(ModuleMarginCall::propose_position_transfer(
+            RuntimeOrigin::signed(USER),
+            SubAccType::Trader,
+            OTC_DESK,
+            asset::EQD,
+            100_000 * ONE_TOKEN,
+            asset::BTC,
+            50 * ONE_TOKEN,
+        ));
+        assert_noop!(
+            ModuleMarginCall::accept_position_transfer(
+                RuntimeOrigin::signed(OTC_DESK),
+                USER,
+                SubAccType::Trader,
+                (asset::EQD, 100_000 * ONE_TOKEN),
+                (asset::BTC, 50 * ONE_TOKEN),
+            ),
+            Error::<Test>::HolderMarginDecreased
+        );
+    });
+}
+
+#[test]
+fn position_transfer_is_cancelled_by_holder() {
+    new_test_ext().execute_with(|| {
+        ModuleSystem::set_block_number(1);
+        const OTC_DESK: u64 = 0x2;
+        ModuleBalances::make_free_balance_be(
+            &(USER + SUBACCOUNT_OFFSET),
+            asset::EQD,
+            SignedBalance::<Balance>::Negative(1_000 * ONE_TOKEN),
+        );
+        assert_noop!(
+            ModuleMarginCall::cancel_position_transfer(RuntimeOrigin::signed(USER)),
+            Error::<Test>::NoPositionTransfer
+        );
+
+        assert_ok!(ModuleMarginCall::propose_position_transfer(
+            RuntimeOrigin::signed(USER),
+            SubAccType::Borrower,
+            OTC_DESK,
+            asset::EQD,
+            1_000 * ONE_TOKEN,
+            asset::BTC,
+            ONE_TOKEN,
+        ));
+        assert_ok!(ModuleMarginCall::cancel_position_transfer(
+            RuntimeOrigin::signed(USER)
+        ));
+        ModuleSystem::assert_last_event(
+            Event::<Test>::PositionTransferCancelled(USER, OTC_DESK).into(),
+        );
+        assert_noop!(
+            ModuleMarginCall::accept_position_transfer(
+                RuntimeOrigin::signed(OTC_DESK),
+                USER,
+                SubAccType::Trader,
+                (asset::EQD, 1_000 * ONE_TOKEN),
+                (asset::BTC, ONE_TOKEN),
+            ),
+            Error::<Test>::NoPositionTransfer
+        );
+    });
+}
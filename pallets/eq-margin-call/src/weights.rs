@@ -24,6 +24,12 @@ use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
     fn try_margincall_external() -> Weight;
+    fn acknowledge_notifications() -> Weight;
+    fn compensate_wrongful_liquidation() -> Weight;
+    fn check_pending_liquidations(p: u32) -> Weight;
+    fn propose_position_transfer() -> Weight;
+    fn cancel_position_transfer() -> Weight;
+    fn accept_position_transfer() -> Weight;
 }
 
 // for tests
@@ -31,4 +37,22 @@ impl crate::WeightInfo for () {
     fn try_margincall_external() -> Weight {
         Weight::zero()
     }
+    fn acknowledge_notifications() -> Weight {
+        Weight::zero()
+    }
+    fn compensate_wrongful_liquidation() -> Weight {
+        Weight::zero()
+    }
+    fn check_pending_liquidations(_p: u32) -> Weight {
+        Weight::zero()
+    }
+    fn propose_position_transfer() -> Weight {
+        Weight::zero()
+    }
+    fn cancel_position_transfer() -> Weight {
+        Weight::zero()
+    }
+    fn accept_position_transfer() -> Weight {
+        Weight::zero()
+    }
 }
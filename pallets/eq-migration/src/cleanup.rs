@@ -0,0 +1,171 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Declarative storage cleanups. Storage items to be removed are declared by pallet and
+//! storage names in `Config::StorageCleanups`, raw prefixes are derived by hashing the names.
+//! `schedule_storage_cleanups` called on runtime upgrade queues declared cleanups that are not
+//! completed yet, then queued prefixes are cleared by `MigrationsPerBlock` keys every block.
+
+use super::*;
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::{storage_prefix, unhashed},
+    traits::StorageInfoTrait,
+    RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+/// Storage item to be cleared, declared by names
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct StorageCleanup {
+    /// Pallet name as declared in `construct_runtime`
+    pub pallet: &'static str,
+    /// Storage item name
+    pub storage: &'static str,
+    /// Hashed key prefix inside the storage item, the whole item is cleared if `None`
+    pub key_prefix: Option<&'static [u8]>,
+}
+
+impl StorageCleanup {
+    /// Cleanup of the whole `storage` item of `pallet`
+    pub const fn new(pallet: &'static str, storage: &'static str) -> Self {
+        Self {
+            pallet,
+            storage,
+            key_prefix: None,
+        }
+    }
+
+    /// Limits cleanup to the keys starting with hashed `key_prefix`
+    pub const fn with_key_prefix(self, key_prefix: &'static [u8]) -> Self {
+        Self {
+            key_prefix: Some(key_prefix),
+            ..self
+        }
+    }
+
+    /// Raw storage prefix: `twox128(pallet) ++ twox128(storage) ++ key_prefix`
+    pub fn prefix(&self) -> Vec<u8> {
+        let mut prefix = storage_prefix(self.pallet.as_bytes(), self.storage.as_bytes()).to_vec();
+        if let Some(key_prefix) = self.key_prefix {
+            prefix.extend_from_slice(key_prefix);
+        }
+        prefix
+    }
+}
+
+/// Queued cleanup of a raw storage prefix
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct CleanupProgress {
+    /// Raw storage prefix to clear
+    pub prefix: Vec<u8>,
+    /// Cursor to continue clearing from, `None` before the first step
+    pub cursor: Option<Vec<u8>>,
+    /// Number of keys removed so far
+    pub removed: u32,
+}
+
+/// Queues declared cleanups which are neither completed nor queued already. Cleanups of
+/// storage items existing in the runtime are rejected. Called from `OnRuntimeUpgrade`.
+pub fn schedule_storage_cleanups<T: Config>() -> Weight {
+    let cleanups = T::StorageCleanups::get();
+    let live_prefixes: Vec<Vec<u8>> = T::LiveStorage::storage_info()
+        .into_iter()
+        .map(|info| info.prefix)
+        .collect();
+
+    let mut queue = StorageCleanupQueue::<T>::get();
+    let mut scheduled: u16 = 0;
+    for cleanup in cleanups.iter() {
+        let prefix = cleanup.prefix();
+        let item_prefix = &prefix[..32];
+        if live_prefixes.iter().any(|live| &live[..] == item_prefix) {
+            Pallet::<T>::deposit_event(Event::StorageCleanupRejected(
+                cleanup.pallet.as_bytes().to_vec(),
+                cleanup.storage.as_bytes().to_vec(),
+            ));
+            continue;
+        }
+        if CompletedCleanups::<T>::contains_key(&prefix)
+            || queue.iter().any(|queued| queued.prefix == prefix)
+        {
+            continue;
+        }
+
+        queue.push(CleanupProgress {
+            prefix,
+            cursor: None,
+            removed: 0,
+        });
+        scheduled = scheduled.saturating_add(1);
+    }
+
+    if scheduled > 0 {
+        StorageCleanupQueue::<T>::put(queue);
+        Pallet::<T>::deposit_event(Event::StorageCleanupScheduled(scheduled));
+    }
+
+    T::DbWeight::get().reads_writes(cleanups.len() as u64 + 1, 1)
+}
+
+/// Clears up to `MigrationsPerBlock` keys of queued cleanups, completed ones are recorded
+/// with the number of removed keys
+pub(crate) fn cleanup_step<T: Config>() -> Weight {
+    let mut queue = StorageCleanupQueue::<T>::get();
+    let mut budget: u32 = T::MigrationsPerBlock::get().into();
+    let mut weight = T::DbWeight::get().reads_writes(2, 1);
+
+    while budget > 0 {
+        let progress = match queue.first_mut() {
+            Some(progress) => progress,
+            None => break,
+        };
+
+        let results =
+            unhashed::clear_prefix(&progress.prefix, Some(budget), progress.cursor.as_deref());
+        weight = weight.saturating_add(
+            T::DbWeight::get().reads_writes(results.loops.into(), results.unique.into()),
+        );
+        progress.removed = progress.removed.saturating_add(results.unique);
+        budget = budget.saturating_sub(results.loops.max(1));
+
+        match results.maybe_cursor {
+            Some(cursor) => {
+                progress.cursor = Some(cursor);
+                break;
+            }
+            None => {
+                let completed = queue.remove(0);
+                CompletedCleanups::<T>::insert(&completed.prefix, completed.removed);
+                Pallet::<T>::deposit_event(Event::StorageCleaned(
+                    completed.prefix,
+                    completed.removed,
+                ));
+                weight = weight.saturating_add(T::DbWeight::get().writes(1));
+            }
+        }
+    }
+
+    if queue.is_empty() {
+        StorageCleanupQueue::<T>::kill();
+    } else {
+        StorageCleanupQueue::<T>::put(queue);
+    }
+
+    weight
+}
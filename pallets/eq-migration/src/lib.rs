@@ -16,16 +16,25 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-//! # Equilibrium Whitelist Pallet
+//! # Equilibrium Migration Pallet
 //!
-//! Simple whitelist functionality, work only with the oracle module. Accounts may be added to whitelist / removed from whitelist delisted.
-//! There are methods to check if an account is whitelisted and to get the list of all whitelisted accounts.
+//! Applies raw key-value storage migrations set by root, `MigrationsPerBlock` items every block.
+//! When there is no raw migration in progress, runs `SteppedMigration` started by root:
+//! `MigrationsPerBlock` items are upgraded every block until it is completed.
+//! Storage cleanups declared in `StorageCleanups` are queued on runtime upgrade and run before
+//! `SteppedMigration`, see [`cleanup`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings)]
 
+pub mod cleanup;
+mod mock;
+mod tests;
 pub mod weights;
 
+pub use cleanup::{schedule_storage_cleanups, CleanupProgress, StorageCleanup};
+use eq_primitives::SteppedMigration;
+use frame_support::{traits::Get, weights::Weight};
 pub use weights::PalletWeightInfo;
 
 pub use pallet::*;
@@ -51,6 +60,12 @@ pub mod pallet {
         /// Set storage calls per block
         #[pallet::constant]
         type MigrationsPerBlock: Get<u16>;
+        /// Storage upgraded in steps, e.g. account data versions
+        type SteppedMigration: SteppedMigration;
+        /// Declared storage cleanups, queued by `schedule_storage_cleanups`
+        type StorageCleanups: Get<Vec<StorageCleanup>>;
+        /// Storage of the runtime pallets, cleanups of these storage items are rejected
+        type LiveStorage: frame_support::traits::StorageInfoTrait;
     }
 
     #[pallet::call]
@@ -80,6 +95,27 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Starts `SteppedMigration`, if it is not in progress
+        #[pallet::call_index(1)]
+        #[pallet::weight((
+            T::WeightInfo::start_stepped_migration(),
+            DispatchClass::Operational))
+        ]
+        pub fn start_stepped_migration(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            ensure!(
+                !SteppedMigrationCursor::<T>::exists(),
+                Error::<T>::MigrationIsInProgress
+            );
+
+            SteppedMigrationCursor::<T>::put(None::<Vec<u8>>);
+
+            Self::deposit_event(Event::SteppedMigrationStarted());
+
+            Ok(().into())
+        }
     }
 
     #[pallet::hooks]
@@ -113,7 +149,8 @@ pub mod pallet {
                         to_migrate_typed.into(),
                     )
                 }
-                None => T::DbWeight::get().reads(1),
+                None if StorageCleanupQueue::<T>::exists() => cleanup::cleanup_step::<T>(),
+                None => Self::stepped_migration_step(),
             }
         }
     }
@@ -127,6 +164,17 @@ pub mod pallet {
         MigrationProcessed(u16),
         /// Migration completed
         Migrated(),
+        /// Stepped migration started
+        SteppedMigrationStarted(),
+        /// Stepped migration completed
+        SteppedMigrationCompleted(),
+        /// N storage cleanups queued
+        StorageCleanupScheduled(u16),
+        /// Storage cleanup rejected because the storage item exists in the runtime:
+        /// pallet name, storage name
+        StorageCleanupRejected(Vec<u8>, Vec<u8>),
+        /// Storage cleanup completed: raw prefix, number of removed keys
+        StorageCleaned(Vec<u8>, u32),
     }
 
     #[pallet::error]
@@ -139,4 +187,43 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn migration)]
     pub type Migration<T: Config> = StorageValue<_, Vec<KeyValue>>;
+
+    /// Raw storage key of the last item processed by stepped migration,
+    /// exists while it is in progress
+    #[pallet::storage]
+    #[pallet::getter(fn stepped_migration_cursor)]
+    pub type SteppedMigrationCursor<T: Config> = StorageValue<_, Option<Vec<u8>>>;
+
+    /// Queued storage cleanups, the first one is in progress
+    #[pallet::storage]
+    #[pallet::getter(fn storage_cleanup_queue)]
+    pub type StorageCleanupQueue<T: Config> = StorageValue<_, Vec<CleanupProgress>, ValueQuery>;
+
+    /// Completed storage cleanups by raw prefix with the number of removed keys
+    #[pallet::storage]
+    #[pallet::getter(fn completed_cleanup)]
+    pub type CompletedCleanups<T: Config> =
+        StorageMap<_, Blake2_128Concat, Vec<u8>, u32, OptionQuery>;
+}
+
+impl<T: Config> Pallet<T> {
+    /// Runs a step of stepped migration, raw migrations go first
+    fn stepped_migration_step() -> Weight {
+        let cursor = match SteppedMigrationCursor::<T>::get() {
+            Some(cursor) => cursor,
+            None => return T::DbWeight::get().reads(2),
+        };
+
+        let (next_cursor, weight) =
+            T::SteppedMigration::migrate_step(cursor, T::MigrationsPerBlock::get().into());
+        match next_cursor {
+            Some(next_cursor) => SteppedMigrationCursor::<T>::put(Some(next_cursor)),
+            None => {
+                SteppedMigrationCursor::<T>::kill();
+                Self::deposit_event(Event::SteppedMigrationCompleted());
+            }
+        }
+
+        weight.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+    }
 }
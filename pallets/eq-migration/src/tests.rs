@@ -0,0 +1,151 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate::mock::*;
+use crate::*;
+use frame_support::{storage::unhashed, traits::Hooks};
+
+fn key(cleanup: &StorageCleanup, suffix: &[u8]) -> Vec<u8> {
+    let mut key = cleanup.prefix();
+    key.extend_from_slice(suffix);
+    key
+}
+
+fn has_event(event: Event<Test>) -> bool {
+    System::events()
+        .iter()
+        .any(|record| record.event == RuntimeEvent::EqMigration(event.clone()))
+}
+
+/// Fills storage of removed items and commits it, so cleanups run against the backend
+/// as they do on chain
+fn test_ext_with_old_storage() -> sp_io::TestExternalities {
+    let mut ext = new_test_ext();
+    ext.execute_with(|| {
+        for suffix in [[1u8], [2], [3], [4]] {
+            unhashed::put(&key(&OLD_ITEMS, &suffix), &1u32);
+        }
+        unhashed::put(&key(&OLD_FLAGS, &[1]), &true);
+        // owners under prefix 7 are removed, other owners stay
+        unhashed::put(&key(&OLD_OWNERS, &[1]), &1u64);
+        unhashed::put(&key(&OLD_OWNERS, &[2]), &2u64);
+        unhashed::put(&OLD_OWNERS.with_key_prefix(&[8]).prefix(), &3u64);
+
+        schedule_storage_cleanups::<Test>();
+    });
+    ext.commit_all().unwrap();
+    ext
+}
+
+fn next_block(ext: &mut sp_io::TestExternalities) {
+    ext.execute_with(|| {
+        let n = System::block_number() + 1;
+        System::set_block_number(n);
+        EqMigration::on_initialize(n);
+    });
+    ext.commit_all().unwrap();
+}
+
+#[test]
+fn schedule_storage_cleanups_queues_declared_items() {
+    new_test_ext().execute_with(|| {
+        schedule_storage_cleanups::<Test>();
+
+        let queued: Vec<_> = EqMigration::storage_cleanup_queue()
+            .into_iter()
+            .map(|progress| (progress.prefix, progress.cursor, progress.removed))
+            .collect();
+        assert_eq!(
+            queued,
+            vec![
+                (OLD_ITEMS.prefix(), None, 0),
+                (OLD_FLAGS.prefix(), None, 0),
+                (OLD_OWNERS.prefix(), None, 0),
+            ]
+        );
+        assert!(has_event(Event::StorageCleanupScheduled(3)));
+        assert!(has_event(Event::StorageCleanupRejected(
+            b"System".to_vec(),
+            b"Account".to_vec()
+        )));
+
+        // queued cleanups are not queued twice
+        System::reset_events();
+        schedule_storage_cleanups::<Test>();
+        assert_eq!(EqMigration::storage_cleanup_queue().len(), 3);
+        assert!(!has_event(Event::StorageCleanupScheduled(3)));
+    });
+}
+
+#[test]
+fn cleanup_resumes_across_blocks_within_budget() {
+    let mut ext = test_ext_with_old_storage();
+
+    // budget of 3 keys stops in the middle of the first prefix
+    next_block(&mut ext);
+    ext.execute_with(|| {
+        let queue = EqMigration::storage_cleanup_queue();
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue[0].removed, 3);
+        assert_eq!(queue[0].cursor, Some(key(&OLD_ITEMS, &[4])));
+        assert!(!unhashed::exists(&key(&OLD_ITEMS, &[3])));
+        assert!(unhashed::exists(&key(&OLD_ITEMS, &[4])));
+        assert!(unhashed::exists(&key(&OLD_FLAGS, &[1])));
+    });
+
+    // first prefix is completed from the cursor, the rest of the budget goes to the next ones
+    next_block(&mut ext);
+    ext.execute_with(|| {
+        assert!(!unhashed::exists(&key(&OLD_ITEMS, &[4])));
+        assert!(!unhashed::exists(&key(&OLD_FLAGS, &[1])));
+        assert!(!unhashed::exists(&key(&OLD_OWNERS, &[1])));
+        assert!(unhashed::exists(&key(&OLD_OWNERS, &[2])));
+        assert_eq!(EqMigration::completed_cleanup(OLD_ITEMS.prefix()), Some(4));
+        assert_eq!(EqMigration::completed_cleanup(OLD_FLAGS.prefix()), Some(1));
+        assert!(has_event(Event::StorageCleaned(OLD_ITEMS.prefix(), 4)));
+        assert!(has_event(Event::StorageCleaned(OLD_FLAGS.prefix(), 1)));
+
+        let queue = EqMigration::storage_cleanup_queue();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].prefix, OLD_OWNERS.prefix());
+        assert_eq!(queue[0].removed, 1);
+    });
+
+    // the last prefix is cleared and the queue is removed
+    next_block(&mut ext);
+    ext.execute_with(|| {
+        assert!(!unhashed::exists(&key(&OLD_OWNERS, &[2])));
+        assert!(unhashed::exists(&OLD_OWNERS.with_key_prefix(&[8]).prefix()));
+        assert_eq!(EqMigration::completed_cleanup(OLD_OWNERS.prefix()), Some(2));
+        assert!(has_event(Event::StorageCleaned(OLD_OWNERS.prefix(), 2)));
+        assert!(!StorageCleanupQueue::<Test>::exists());
+
+        // completed cleanups are not queued again on the next upgrade
+        schedule_storage_cleanups::<Test>();
+        assert!(!StorageCleanupQueue::<Test>::exists());
+    });
+
+    // nothing left to clean
+    next_block(&mut ext);
+    ext.execute_with(|| {
+        assert!(!StorageCleanupQueue::<Test>::exists());
+        assert!(unhashed::exists(&OLD_OWNERS.with_key_prefix(&[8]).prefix()));
+    });
+}
@@ -24,6 +24,7 @@ use sp_std::marker::PhantomData;
 
 pub trait PalletWeightInfo {
     fn set_migration() -> Weight;
+    fn start_stepped_migration() -> Weight;
 }
 
 pub struct EqWeight<T>(PhantomData<T>);
@@ -33,4 +34,9 @@ impl<T: crate::Config> PalletWeightInfo for EqWeight<T> {
             .writes(1)
             .saturating_add(Weight::from_parts(1_000_000, 0))
     }
+    fn start_stepped_migration() -> Weight {
+        T::DbWeight::get()
+            .reads_writes(1, 1)
+            .saturating_add(Weight::from_parts(1_000_000, 0))
+    }
 }
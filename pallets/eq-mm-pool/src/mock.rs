@@ -84,6 +84,7 @@ parameter_types! {
         frame_system::limits::BlockWeights::simple_max(Weight::from_parts(1024, 0));
     pub const MinimumPeriod: u64 = 1;
     pub const UnsignedPriority: u64 = 100;
+    pub const MaxUnsignedPerBlock: u32 = 100;
 }
 
 impl frame_system::Config for Test {
@@ -257,9 +258,12 @@ impl BalanceChecker<Balance, AccountId, EqBalances, SubaccountsManagerMock> for
         who: &AccountId,
         _change: &Vec<(asset::Asset, SignedBalance<Balance>)>,
         _: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), eq_primitives::balance::BalanceCheckError<Balance>> {
         match who {
-            &FAIL_ACC => Err(DispatchError::Other("Expected error")),
+            &FAIL_ACC => Err(eq_primitives::balance::BalanceCheckError::new(
+                eq_primitives::balance::BalanceCheckerKind::Other,
+                DispatchError::Other("Expected error"),
+            )),
             _ => Ok(()),
         }
     }
@@ -316,6 +320,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -375,6 +383,7 @@ parameter_types! {
     pub const PriceStepCount: u32 = 10_000;
     pub const PenaltyFee: Balance = 5_000_000_000;
     pub const DexUnsignedPriority: u64 = 100;
+    pub const CorridorUpdatePeriod: u64 = 10;
     pub const MinSurplus: Balance = 1 * 1_000_000_000; // 1 usd
     pub const MinTempBailsman: Balance = 20 * 1_000_000_000; // 20 usd
 }
@@ -386,8 +395,16 @@ impl eq_dex::Config for Test {
     type PriceStepCount = PriceStepCount;
     type PenaltyFee = PenaltyFee;
     type DexUnsignedPriority = DexUnsignedPriority;
+    type CorridorUpdatePeriod = CorridorUpdatePeriod;
     type WeightInfo = ();
+    type RiskNotifier = ();
     type ValidatorOffchainBatcher = EqRate;
+    type StakedBalance = ();
+    type StakerDiscounts = frame_support::traits::GetDefault;
+    type OrderDeposit = frame_support::traits::GetDefault;
+    type TreasuryAccounting = ();
+    type MaxBatchOrders = frame_support::traits::ConstU32<100>;
+    type MaxBatchAuctionMarkets = frame_support::traits::ConstU32<10>;
 }
 
 impl authorship::Config for Test {
@@ -429,6 +446,7 @@ impl eq_rate::Config for Test {
     type EqCurrency = EqBalances;
     type SubaccountsManager = SubaccountsManagerMock;
     type UnsignedPriority = UnsignedPriority;
+    type MaxUnsignedPerBlock = MaxUnsignedPerBlock;
 
     type RiskLowerBound = RiskLowerBound;
     type RiskUpperBound = RiskUpperBound;
@@ -449,6 +467,8 @@ impl eq_rate::Config for Test {
     type WeightInfo = ();
     type LendingPoolManager = ();
     type LendingAssetRemoval = ();
+    type InterestIncomeReceiver = ();
+    type TreasuryAccounting = ();
     type RedistributeWeightInfo = ();
 }
 
@@ -0,0 +1,36 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Runtime API to check calls against runtime `CallFilter` before proposing them
+//! to `eq-multisig-sudo`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use eq_primitives::call_filter::FilterVerdictWithReason;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait EqCallFilterApi<Call>
+    where
+        Call: Codec
+    {
+        /// Verdict of `CallFilter` for every call, including migration mode rules.
+        /// Calls are not dispatched.
+        fn validate_calls(calls: Vec<Call>) -> Vec<FilterVerdictWithReason>;
+    }
+}
@@ -138,4 +138,54 @@ benchmarks! {
         assert_eq!(MultisigProposals::<T>::get(&call_hash), None);
         assert_eq!(Threshold::<T>::get(), 2u32);
     }
+
+    veto {
+        let user: T::AccountId = account("user", 0, SEED);
+
+        crate::pallet::Pallet::<T>::add_key(RawOrigin::Root.into(), user.clone()).unwrap();
+        crate::pallet::Pallet::<T>::modify_threshold(RawOrigin::Root.into(), 2).unwrap();
+
+        let call: <T as Config>::RuntimeCall = EqMultisigSudo::Call::<T>::modify_threshold{ new_value: 1 }.into();
+        let call_data: OpaqueCall = Encode::encode(&call);
+        let call_hash = (
+            b"CALLHASH",
+            user.clone(),
+            &call_data[..],
+            <frame_system::Pallet<T>>::block_number(),
+        ).using_encoded(blake2_256);
+
+        crate::pallet::Pallet::<T>::propose(RawOrigin::Signed(user.clone()).into(), Box::new(call)).unwrap();
+    }: _(RawOrigin::Root, call_hash.clone())
+    verify {
+        assert_eq!(MultisigProposals::<T>::get(&call_hash), None);
+    }
+
+    expire_proposals {
+        let p in 0 .. 50;
+
+        let user: T::AccountId = account("user", 0, SEED);
+
+        crate::pallet::Pallet::<T>::add_key(RawOrigin::Root.into(), user.clone()).unwrap();
+        crate::pallet::Pallet::<T>::modify_threshold(RawOrigin::Root.into(), 2).unwrap();
+
+        for i in 0..p {
+            let call: <T as Config>::RuntimeCall = EqMultisigSudo::Call::<T>::modify_threshold{ new_value: i }.into();
+            crate::pallet::Pallet::<T>::propose(RawOrigin::Signed(user.clone()).into(), Box::new(call)).unwrap();
+        }
+        let expiry = <frame_system::Pallet<T>>::block_number() + T::ProposalLifetime::get();
+        assert_eq!(ProposalsExpiry::<T>::get(expiry).len(), p as usize);
+    }: {
+        <crate::pallet::Pallet<T> as frame_support::traits::Hooks<T::BlockNumber>>::on_initialize(expiry);
+    }
+    verify {
+        assert_eq!(MultisigProposals::<T>::iter().count(), 0);
+    }
+
+    set_xcm_transact_allowed {
+        let dest = xcm::v3::MultiLocation::new(1, xcm::v3::Junctions::X1(xcm::v3::Junction::Parachain(2000)));
+        let call_prefix = vec![10u8, 0u8];
+    }: _(RawOrigin::Root, Box::new(dest.into()), call_prefix.clone(), true)
+    verify {
+        assert!(XcmTransactAllowList::<T>::get(dest, call_prefix));
+    }
 }
@@ -26,6 +26,13 @@
 //! is allowed for a multisignature call to proceed. The proposer of the call, that can be any account of the network, makes the first signature.
 //! Signatory accounts can vote as either approve or cancel said call and if a number of approvals or cancellations exceeds
 //! the set threshold then the call is either sudo-ed or removed respectively.
+//! Proposals not resolved within `ProposalLifetime` blocks expire, and `VetoOrigin`
+//! may cancel any pending proposal.
+//!
+//! Proposed calls are checked by `ProposalFilter` on proposal and again on dispatch.
+//! XCM messages sent by proposals may `Transact` only calls whose encoding starts with
+//! a prefix allowed for their destination in `XcmTransactAllowList`, the list is
+//! maintained by `XcmAllowListOrigin`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings)]
@@ -47,7 +54,7 @@ use frame_support::Parameter;
 
 use frame_support::{
     dispatch::{DispatchResultWithPostInfo, GetDispatchInfo},
-    traits::{Get, UnfilteredDispatchable},
+    traits::{Contains, Get, UnfilteredDispatchable},
 };
 
 #[allow(unused_imports)]
@@ -58,9 +65,13 @@ use frame_system::ensure_signed;
 use eq_utils::eq_ensure;
 
 use codec::{Decode, Encode};
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 pub use pallet::*;
 use sp_io::hashing::blake2_256;
+use xcm::{
+    v3::{Instruction, MultiLocation, Xcm},
+    VersionedMultiLocation, VersionedXcm,
+};
 
 //32 bytes as a standard
 pub type CallHash = [u8; 32];
@@ -70,7 +81,7 @@ pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
-    use sp_runtime::DispatchResult;
+    use sp_runtime::{traits::Saturating, DispatchResult};
 
     #[pallet::pallet]
     #[pallet::without_storage_info]
@@ -88,6 +99,15 @@ pub mod pallet {
         /// Maximal number of signatories
         #[pallet::constant]
         type MaxSignatories: Get<u32>;
+        /// Number of blocks after which a pending proposal expires
+        #[pallet::constant]
+        type ProposalLifetime: Get<Self::BlockNumber>;
+        /// Origin allowed to veto a pending proposal
+        type VetoOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Calls allowed to be proposed, checked again when approved proposal is dispatched
+        type ProposalFilter: Contains<<Self as Config>::RuntimeCall>;
+        /// Origin maintaining `XcmTransactAllowList`
+        type XcmAllowListOrigin: EnsureOrigin<Self::RuntimeOrigin>;
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -129,10 +149,31 @@ pub mod pallet {
         MultisigSudid(CallHash, DispatchResult),
         /// Sudo critical failure
         SudoFailed(CallHash),
+        /// The proposal was not resolved within `ProposalLifetime` and removed
+        ProposalExpired(CallHash),
+        /// The proposal was vetoed by `VetoOrigin`
+        ProposalVetoed(CallHash),
+        /// Calls starting with the prefix are allowed or disallowed to be sent to the destination
+        /// with XCM `Transact`
+        /// `[dest, call_prefix, allowed]`
+        XcmTransactAllowListChanged(MultiLocation, Vec<u8>, bool),
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let expiring = <ProposalsExpiry<T>>::take(n);
+            let count = expiring.len() as u32;
+            for call_hash in expiring {
+                // resolved proposals are already removed
+                if <MultisigProposals<T>>::take(&call_hash).is_some() {
+                    Self::deposit_event(Event::<T>::ProposalExpired(call_hash));
+                }
+            }
+
+            T::WeightInfo::expire_proposals(count)
+        }
+    }
 
     #[pallet::error]
     pub enum Error<T> {
@@ -152,6 +193,12 @@ pub mod pallet {
         AlreadyApproved,
         /// The account already voted to cancel a proposal
         AlreadyCancelled,
+        /// The call is not allowed by `ProposalFilter`
+        CallFiltered,
+        /// Call prefix of XCM `Transact` allow-list should not be empty
+        EmptyCallPrefix,
+        /// XCM destination can't be converted to the latest version
+        BadXcmVersion,
     }
 
     /// The multisig signatory key list.
@@ -170,6 +217,25 @@ pub mod pallet {
     pub type MultisigProposals<T: Config> =
         StorageMap<_, Identity, [u8; 32], Multisig<T::AccountId>, OptionQuery>;
 
+    /// Hashes of proposals by the block they expire at
+    #[pallet::storage]
+    #[pallet::getter(fn proposals_expiry)]
+    pub type ProposalsExpiry<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<CallHash>, ValueQuery>;
+
+    /// Prefixes of encoded calls allowed to be sent with XCM `Transact` by destination
+    #[pallet::storage]
+    #[pallet::getter(fn xcm_transact_allowed)]
+    pub type XcmTransactAllowList<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        MultiLocation,
+        Blake2_128Concat,
+        Vec<u8>,
+        bool,
+        ValueQuery,
+    >;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub keys: Vec<T::AccountId>,
@@ -237,6 +303,13 @@ pub mod pallet {
             <MultisigProposals<T>>::remove(&call_hash);
 
             match maybe_call {
+                // allow-lists may be changed since the proposal
+                Some((call, _)) if !T::ProposalFilter::contains(&call) => {
+                    Self::deposit_event(Event::<T>::MultisigSudid(
+                        *call_hash,
+                        Err(Error::<T>::CallFiltered.into()),
+                    ));
+                }
                 Some((call, _)) => {
                     //sudo the call
                     let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
@@ -357,16 +430,21 @@ pub mod pallet {
                 line!(),
                 who
             );
+            eq_ensure!(
+                T::ProposalFilter::contains(&call),
+                Error::<T>::CallFiltered,
+                target: "eq_multisig_sudo",
+                "{}:{}. Proposed call is filtered. Who: {:?}",
+                file!(),
+                line!(),
+                who
+            );
 
             let call_data: OpaqueCall = Encode::encode(&call);
+            let current_block = <frame_system::Pallet<T>>::block_number();
 
-            let call_hash = (
-                b"CALLHASH",
-                who.clone(),
-                &call_data[..],
-                <frame_system::Pallet<T>>::block_number(),
-            )
-                .using_encoded(blake2_256);
+            let call_hash =
+                (b"CALLHASH", who.clone(), &call_data[..], current_block).using_encoded(blake2_256);
 
             let new_proposal = Multisig {
                 proposer: who.clone(),
@@ -376,6 +454,10 @@ pub mod pallet {
             };
 
             <MultisigProposals<T>>::insert(call_hash, new_proposal);
+            <ProposalsExpiry<T>>::append(
+                current_block.saturating_add(T::ProposalLifetime::get()),
+                call_hash,
+            );
 
             Self::deposit_event(Event::<T>::NewProposal(who, call_hash));
 
@@ -517,5 +599,91 @@ pub mod pallet {
             // Sudo user does not pay a fee.
             Ok(Pays::No.into())
         }
+
+        /// Removes a pending proposal regardless of votes. Requires `VetoOrigin`.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::veto())]
+        pub fn veto(origin: OriginFor<T>, call_hash: [u8; 32]) -> DispatchResultWithPostInfo {
+            T::VetoOrigin::ensure_origin(origin)?;
+
+            eq_ensure!(
+                <MultisigProposals<T>>::take(&call_hash).is_some(),
+                Error::<T>::ProposalNotFound,
+                target: "eq_multisig_sudo",
+                "{}:{}. Proposed call was not found. Proposal hash: {:?}",
+                file!(),
+                line!(),
+                call_hash
+            );
+
+            Self::deposit_event(Event::<T>::ProposalVetoed(call_hash));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Allows or disallows proposals to send calls starting with `call_prefix`, e.g.
+        /// pallet and call indices, to `dest` with XCM `Transact`. Requires `XcmAllowListOrigin`.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::set_xcm_transact_allowed())]
+        pub fn set_xcm_transact_allowed(
+            origin: OriginFor<T>,
+            dest: Box<VersionedMultiLocation>,
+            call_prefix: Vec<u8>,
+            allowed: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::XcmAllowListOrigin::ensure_origin(origin)?;
+
+            eq_ensure!(
+                !call_prefix.is_empty(),
+                Error::<T>::EmptyCallPrefix,
+                target: "eq_multisig_sudo",
+                "{}:{}. Call prefix should not be empty. Dest: {:?}",
+                file!(),
+                line!(),
+                dest
+            );
+            let dest = MultiLocation::try_from(*dest).map_err(|_| Error::<T>::BadXcmVersion)?;
+
+            if allowed {
+                <XcmTransactAllowList<T>>::insert(&dest, &call_prefix, true);
+            } else {
+                <XcmTransactAllowList<T>>::remove(&dest, &call_prefix);
+            }
+
+            Self::deposit_event(Event::<T>::XcmTransactAllowListChanged(
+                dest,
+                call_prefix,
+                allowed,
+            ));
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Checks that every `Transact` of `message` is allowed for `dest` by `XcmTransactAllowList`.
+    /// Messages of unsupported versions are not allowed.
+    pub fn is_xcm_send_allowed(dest: &VersionedMultiLocation, message: &VersionedXcm<()>) -> bool {
+        match (
+            MultiLocation::try_from(dest.clone()),
+            Xcm::<()>::try_from(message.clone()),
+        ) {
+            (Ok(dest), Ok(message)) => Self::is_xcm_allowed(&dest, &message),
+            _ => false,
+        }
+    }
+
+    fn is_xcm_allowed(dest: &MultiLocation, message: &Xcm<()>) -> bool {
+        message.0.iter().all(|instruction| match instruction {
+            Instruction::Transact { call, .. } => {
+                let call = call.clone().into_encoded();
+                <XcmTransactAllowList<T>>::iter_prefix(dest)
+                    .any(|(prefix, allowed)| allowed && call.starts_with(&prefix))
+            }
+            Instruction::SetAppendix(xcm) | Instruction::SetErrorHandler(xcm) => {
+                Self::is_xcm_allowed(dest, xcm)
+            }
+            _ => true,
+        })
     }
 }
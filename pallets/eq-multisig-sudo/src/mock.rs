@@ -31,6 +31,7 @@ use sp_runtime::{
 };
 
 use frame_support::{traits::Everything, weights::Weight};
+use frame_system::EnsureRoot;
 
 use frame_support::parameter_types;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -138,15 +139,31 @@ impl logger::Config for Test {
 
 parameter_types! {
     pub const MaxSignatories: u32 = 4;
+    pub const ProposalLifetime: u64 = 100;
 }
 
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type RuntimeCall = RuntimeCall;
     type MaxSignatories = MaxSignatories;
+    type ProposalLifetime = ProposalLifetime;
+    type VetoOrigin = EnsureRoot<u64>;
+    type ProposalFilter = ProposalFilterMock;
+    type XcmAllowListOrigin = EnsureRoot<u64>;
     type WeightInfo = ();
 }
 
+/// Rejects logging of negative numbers
+pub struct ProposalFilterMock;
+impl frame_support::traits::Contains<RuntimeCall> for ProposalFilterMock {
+    fn contains(call: &RuntimeCall) -> bool {
+        !matches!(
+            call,
+            RuntimeCall::Logger(LoggerCall::privileged_i32_log { i, .. }) if *i < 0
+        )
+    }
+}
+
 pub type ModuleMultisigSudo = Pallet<Test>;
 pub type LoggerCall = logger::RuntimeCall<Test>;
 pub type ModuleCall = eq_multisig_sudo::Call<Test>;
@@ -24,7 +24,7 @@ use super::*;
 use crate::mock::{new_test_ext, LoggerCall, ModuleMultisigSudo, RuntimeCall, RuntimeOrigin, Test};
 
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok, weights::Weight};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
 use frame_system::RawOrigin;
 use sp_io::hashing::blake2_256;
 
@@ -245,3 +245,139 @@ fn check_all_sudo() {
         assert_eq!(<MultisigProposals<Test>>::contains_key(&call_hash), false);
     })
 }
+
+#[test]
+fn proposals_expire_and_can_be_vetoed() {
+    new_test_ext(vec![1u64, 2u64, 3u64], THRESHOLD).execute_with(|| {
+        let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
+            i: 42,
+            weight: Weight::from_parts(1_000, 0),
+        }));
+        let call_data: OpaqueCall = Encode::encode(&call);
+        let alice_hash = get_call_hash(ALICE_ID, call_data.clone());
+        let bob_hash = get_call_hash(BOB_ID, call_data);
+        assert_ok!(ModuleMultisigSudo::propose(
+            RuntimeOrigin::signed(ALICE_ID),
+            call.clone()
+        ));
+        assert_ok!(ModuleMultisigSudo::propose(
+            RuntimeOrigin::signed(BOB_ID),
+            call
+        ));
+
+        //only VetoOrigin can veto
+        assert_noop!(
+            ModuleMultisigSudo::veto(RuntimeOrigin::signed(CHARLIE_ID), bob_hash),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(ModuleMultisigSudo::veto(RawOrigin::Root.into(), bob_hash));
+        assert_eq!(<MultisigProposals<Test>>::contains_key(&bob_hash), false);
+        assert_noop!(
+            ModuleMultisigSudo::veto(RawOrigin::Root.into(), bob_hash),
+            Error::<Test>::ProposalNotFound
+        );
+
+        //alice proposal is pending until ProposalLifetime passes
+        ModuleMultisigSudo::on_initialize(99);
+        assert_eq!(<MultisigProposals<Test>>::contains_key(&alice_hash), true);
+
+        ModuleMultisigSudo::on_initialize(100);
+        assert_eq!(<MultisigProposals<Test>>::contains_key(&alice_hash), false);
+        assert_eq!(ModuleMultisigSudo::proposals_expiry(100), vec![]);
+        assert_noop!(
+            ModuleMultisigSudo::approve(RuntimeOrigin::signed(BOB_ID), alice_hash),
+            Error::<Test>::ProposalNotFound
+        );
+    })
+}
+
+#[test]
+fn filtered_calls_cannot_be_proposed() {
+    new_test_ext(vec![1u64, 2u64, 3u64], THRESHOLD).execute_with(|| {
+        let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
+            i: -1,
+            weight: Weight::from_parts(1_000, 0),
+        }));
+        assert_noop!(
+            ModuleMultisigSudo::propose(RuntimeOrigin::signed(ALICE_ID), call),
+            Error::<Test>::CallFiltered
+        );
+    })
+}
+
+#[test]
+fn xcm_transact_allow_list() {
+    use xcm::v3::{Junction::Parachain, Junctions::X1, OriginKind};
+
+    new_test_ext(vec![1u64, 2u64, 3u64], THRESHOLD).execute_with(|| {
+        let dest = MultiLocation::new(1, X1(Parachain(2000)));
+        let other_dest = MultiLocation::new(1, X1(Parachain(2001)));
+        let transact = |call: Vec<u8>| {
+            VersionedXcm::V3(Xcm(vec![Instruction::Transact {
+                origin_kind: OriginKind::SovereignAccount,
+                require_weight_at_most: Weight::from_parts(1_000, 0),
+                call: call.into(),
+            }]))
+        };
+
+        assert_noop!(
+            ModuleMultisigSudo::set_xcm_transact_allowed(
+                RuntimeOrigin::signed(ALICE_ID),
+                Box::new(dest.into()),
+                vec![10, 0],
+                true
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ModuleMultisigSudo::set_xcm_transact_allowed(
+                RawOrigin::Root.into(),
+                Box::new(dest.into()),
+                vec![],
+                true
+            ),
+            Error::<Test>::EmptyCallPrefix
+        );
+        assert!(!ModuleMultisigSudo::is_xcm_send_allowed(
+            &dest.into(),
+            &transact(vec![10, 0, 1])
+        ));
+
+        assert_ok!(ModuleMultisigSudo::set_xcm_transact_allowed(
+            RawOrigin::Root.into(),
+            Box::new(dest.into()),
+            vec![10, 0],
+            true
+        ));
+        assert!(ModuleMultisigSudo::is_xcm_send_allowed(
+            &dest.into(),
+            &transact(vec![10, 0, 1])
+        ));
+        // other call of the same pallet
+        assert!(!ModuleMultisigSudo::is_xcm_send_allowed(
+            &dest.into(),
+            &transact(vec![10, 1, 1])
+        ));
+        // other destination
+        assert!(!ModuleMultisigSudo::is_xcm_send_allowed(
+            &other_dest.into(),
+            &transact(vec![10, 0, 1])
+        ));
+        // messages without `Transact` are not restricted
+        assert!(ModuleMultisigSudo::is_xcm_send_allowed(
+            &other_dest.into(),
+            &VersionedXcm::V3(Xcm(vec![Instruction::ClearOrigin]))
+        ));
+
+        assert_ok!(ModuleMultisigSudo::set_xcm_transact_allowed(
+            RawOrigin::Root.into(),
+            Box::new(dest.into()),
+            vec![10, 0],
+            false
+        ));
+        assert!(!ModuleMultisigSudo::is_xcm_send_allowed(
+            &dest.into(),
+            &transact(vec![10, 0, 1])
+        ));
+    })
+}
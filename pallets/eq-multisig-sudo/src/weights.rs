@@ -29,6 +29,9 @@ pub trait WeightInfo {
     fn propose() -> Weight;
     fn approve() -> Weight;
     fn cancel_proposal() -> Weight;
+    fn veto() -> Weight;
+    fn expire_proposals(p: u32) -> Weight;
+    fn set_xcm_transact_allowed() -> Weight;
 }
 
 // for tests
@@ -51,4 +54,13 @@ impl crate::WeightInfo for () {
     fn cancel_proposal() -> Weight {
         Weight::zero()
     }
+    fn veto() -> Weight {
+        Weight::zero()
+    }
+    fn expire_proposals(_p: u32) -> Weight {
+        Weight::zero()
+    }
+    fn set_xcm_transact_allowed() -> Weight {
+        Weight::zero()
+    }
 }
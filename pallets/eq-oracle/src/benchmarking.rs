@@ -20,16 +20,23 @@
 
 use super::*;
 use crate::Call;
-use eq_primitives::asset;
+use eq_primitives::asset::{self, AssetXcmData};
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_system::RawOrigin;
-use sp_runtime::FixedI64;
+use sp_core::sr25519;
+use sp_runtime::{FixedI64, Percent, Permill};
 
 pub struct Pallet<T: Config>(crate::Pallet<T>);
 
 pub trait Config: eq_assets::Config + eq_whitelists::Config + crate::Config {}
 
 benchmarks! {
+    where_clause {
+        where
+            T::Public: From<sr25519::Public>,
+            T::Signature: From<sr25519::Signature>,
+    }
+
     set_price {
         let b in 1 .. 20;
 
@@ -49,4 +56,189 @@ benchmarks! {
     verify {
 
     }
+
+    recalc_asset {
+        crate::Pallet::<T>::enqueue_financial_recalc(&[asset::BTC]);
+    }: {
+        crate::Pallet::<T>::process_financial_recalc(Weight::MAX);
+    }
+    verify {
+        assert_eq!(FinancialRecalcProgress::<T>::get(), Some(FinancialRecalcStage::Metrics));
+    }
+
+    recalc_metrics {
+        let a in 1 .. 50;
+
+        for i in 0..a {
+            let name = vec![b'b', b'n', b'a' + (i / 26) as u8, b'a' + (i % 26) as u8];
+            eq_assets::Pallet::<T>::add_asset(
+                RawOrigin::Root.into(),
+                name,
+                0_u128,
+                0_i64,
+                Permill::zero(),
+                Permill::zero(),
+                AssetXcmData::None,
+                Permill::zero(),
+                0_u64,
+                AssetType::Physical,
+                false,
+                Percent::zero(),
+                Permill::one(),
+                vec![FixedI64::one()],
+            ).unwrap();
+        }
+        crate::Pallet::<T>::enqueue_financial_recalc(&[]);
+    }: {
+        crate::Pallet::<T>::process_financial_recalc(Weight::MAX);
+    }
+    verify {
+        assert_eq!(FinancialRecalcProgress::<T>::get(), None);
+    }
+
+    set_max_price_jump {
+    }: _ (RawOrigin::Root, asset::BTC, Some(Permill::from_percent(20)))
+    verify {
+        assert_eq!(MaxPriceJump::<T>::get(asset::BTC), Some(Permill::from_percent(20)));
+    }
+
+    override_suspect_price {
+        SuspectPrices::<T>::insert(asset::BTC, SuspectPrice {
+            reference_price: FixedI64::one(),
+            price: FixedI64::saturating_from_integer(2),
+            until: frame_system::Pallet::<T>::block_number(),
+            confirmations: vec![whitelisted_caller()],
+        });
+    }: _ (RawOrigin::Root, asset::BTC)
+    verify {
+        assert!(SuspectPrices::<T>::get(asset::BTC).is_none());
+    }
+
+    set_feeder_weight {
+        let feeder: T::AccountId = whitelisted_caller();
+    }: _ (RawOrigin::Root, feeder.clone(), Some(3))
+    verify {
+        assert_eq!(FeederWeights::<T>::get(&feeder), 3);
+    }
+
+    set_outlier_rejection {
+        let params = OutlierRejectionParams {
+            band: Permill::from_percent(5),
+            max_deviations: 3,
+            window: 10,
+        };
+    }: _ (RawOrigin::Root, Some(params))
+    verify {
+        assert_eq!(OutlierRejection::<T>::get(), Some(params));
+    }
+
+    add_feeder_key {
+        let caller: T::AccountId = whitelisted_caller();
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), caller.clone())
+            .unwrap();
+        // expired keys are removed while adding a new one
+        for i in 0..T::MaxFeederKeys::get() {
+            let key: T::AccountId = account("key", i, 0);
+            FeederKeys::<T>::insert(&caller, &key, FeederKey { expires_at: Some(Zero::zero()) });
+            FeederKeyOwners::<T>::insert(&key, &caller);
+        }
+        let key: T::AccountId = account("new_key", 0, 0);
+    }: _ (RawOrigin::Signed(caller.clone()), key.clone())
+    verify {
+        assert_eq!(FeederKeyOwners::<T>::get(&key), Some(caller));
+    }
+
+    rotate_feeder_key {
+        let caller: T::AccountId = whitelisted_caller();
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), caller.clone())
+            .unwrap();
+        let old_key: T::AccountId = account("key", 0, 0);
+        crate::Pallet::<T>::add_feeder_key(RawOrigin::Signed(caller.clone()).into(), old_key.clone())
+            .unwrap();
+        let new_key: T::AccountId = account("new_key", 0, 0);
+    }: _ (RawOrigin::Signed(caller.clone()), old_key.clone(), new_key.clone())
+    verify {
+        assert!(FeederKeys::<T>::get(&caller, &old_key).unwrap().expires_at.is_some());
+        assert_eq!(FeederKeyOwners::<T>::get(&new_key), Some(caller));
+    }
+
+    remove_feeder_key {
+        let caller: T::AccountId = whitelisted_caller();
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), caller.clone())
+            .unwrap();
+        let key: T::AccountId = account("key", 0, 0);
+        crate::Pallet::<T>::add_feeder_key(RawOrigin::Signed(caller.clone()).into(), key.clone())
+            .unwrap();
+    }: _ (RawOrigin::Signed(caller), key.clone())
+    verify {
+        assert!(FeederKeyOwners::<T>::get(&key).is_none());
+    }
+
+    set_lp_price_adapter {
+        eq_assets::Pallet::<T>::add_asset(
+            RawOrigin::Root.into(),
+            b"lpt".to_vec(),
+            0_u128,
+            0_i64,
+            Permill::zero(),
+            Permill::zero(),
+            AssetXcmData::None,
+            Permill::zero(),
+            0_u64,
+            AssetType::Lp(asset::AmmPool::Curve(0)),
+            false,
+            Percent::zero(),
+            Permill::one(),
+            vec![FixedI64::one()],
+        ).unwrap();
+        let lp_asset = Asset::from_bytes(b"lpt").unwrap();
+        LpPriceAdapters::<T>::insert(lp_asset, 0);
+    }: _ (RawOrigin::Root, lp_asset, None)
+    verify {
+        assert!(LpPriceAdapters::<T>::get(lp_asset).is_none());
+    }
+
+    set_prices {
+        let p in 1 .. T::MaxPricesPerBatch::get();
+
+        let public: T::Public = sr25519::Public::from_raw([1u8; 32]).into();
+        let feeder: T::AccountId = public.clone().into_account();
+        eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), feeder.clone())
+            .unwrap();
+
+        let mut prices = Vec::new();
+        for i in 0..p {
+            let name = vec![b'b', b'p', b'a' + (i / 26) as u8, b'a' + (i % 26) as u8];
+            eq_assets::Pallet::<T>::add_asset(
+                RawOrigin::Root.into(),
+                name.clone(),
+                0_u128,
+                0_i64,
+                Permill::zero(),
+                Permill::zero(),
+                AssetXcmData::None,
+                Permill::zero(),
+                0_u64,
+                AssetType::Physical,
+                false,
+                Percent::zero(),
+                Permill::one(),
+                vec![FixedI64::one()],
+            ).unwrap();
+            prices.push((Asset::from_bytes(&name).unwrap(), FixedI64::one()));
+        }
+        let assets = prices.clone();
+        let payload = PricesPayload {
+            public,
+            prices,
+            block_number: frame_system::Pallet::<T>::block_number(),
+        };
+        let signature: T::Signature = sr25519::Signature::from_raw([0u8; 64]).into();
+    }: _ (RawOrigin::None, payload, signature)
+    verify {
+        for (asset, _) in assets {
+            let price_point = PricePoints::<T>::get(asset).unwrap();
+            assert!(price_point.data_points.iter().any(|dp| dp.account_id == feeder));
+        }
+    }
 }
@@ -55,6 +55,12 @@
 //! anyone willing to obtain the price will receive an error.
 
 //! 5. Oracle is implemented using offchain workers (implements Substrate’s offchain worker).
+//! The offchain worker submits prices of all its assets with `set_prices` unsigned transactions,
+//! up to `MaxPricesPerBatch` prices each. Prices of a batch are accepted independently: an
+//! invalid price is skipped without failing the others.
+//! Price submissions are free and dispatched in the operational class, so prices are updated
+//! when blocks are full of normal transactions. Their number in a block is limited by
+//! `MaxPriceSubmissionsPerBlock`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings)]
@@ -76,17 +82,21 @@ use frame_support::{
     codec::{Decode, Encode},
     dispatch::DispatchResult,
     traits::{Get, UnixTime},
+    weights::Weight,
 };
 use frame_system::offchain::{
     AppCrypto, CreateSignedTransaction, ForAll, SendUnsignedTransaction, SignedPayload, Signer,
     SigningTypes,
 };
-use sp_arithmetic::{FixedI64, FixedPointNumber};
+use sp_arithmetic::{FixedI64, FixedPointNumber, Permill};
 use sp_core::crypto::KeyTypeId;
 use sp_core::RuntimeDebug;
 use sp_runtime::traits::{AtLeast32BitUnsigned, IdentifyAccount, Saturating};
 use sp_runtime::RuntimeAppPublic;
-use sp_runtime::{offchain::StorageKind, DispatchError};
+use sp_runtime::{
+    offchain::StorageKind, transaction_validity::InvalidTransaction, DispatchError,
+    TransactionOutcome,
+};
 use sp_std::{fmt::Debug, iter::Iterator, prelude::*};
 use substrate_fixed::types::I64F64;
 
@@ -107,14 +117,16 @@ use eq_utils::{
     ONE_TOKEN,
 };
 use eq_whitelists::CheckWhitelisted;
+pub use lp_price::{LpPrice, LpPriceAdapter, LpPriceAdapterId};
 pub use pallet::*;
 use price_source::PriceSource;
-use sp_arithmetic::traits::UniqueSaturatedFrom;
+use sp_arithmetic::traits::{UniqueSaturatedFrom, UniqueSaturatedInto};
 use sp_runtime::traits::{One, Zero};
 use sp_runtime::FixedPointOperand;
 pub use weights::WeightInfo;
 
 pub mod benchmarking;
+pub mod lp_price;
 mod mock;
 mod price_source;
 mod regex_offsets;
@@ -176,6 +188,20 @@ impl<T: SigningTypes> SignedPayload<T> for PricePayload<T::Public, T::BlockNumbe
     }
 }
 
+/// Payload for setting prices of several assets with one unsigned transaction
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct PricesPayload<Public, BlockNumber> {
+    public: Public,
+    prices: Vec<(Asset, FixedI64)>,
+    block_number: BlockNumber,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for PricesPayload<T::Public, T::BlockNumber> {
+    fn public(&self) -> T::Public {
+        self.public.clone()
+    }
+}
+
 /// Struct for storing added asset price data from one source
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
 pub struct DataPoint<AccountId, BlockNumber> {
@@ -191,6 +217,63 @@ impl<AccountId, BlockNumber> DataPoint<AccountId, BlockNumber> {
     }
 }
 
+/// Price that jumped over `MaxPriceJump` from the last accepted one. While the price
+/// is suspect, DEX corridors are widened and margin calls of accounts holding the
+/// asset are postponed
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct SuspectPrice<AccountId, BlockNumber> {
+    /// Last accepted price before the jump
+    pub reference_price: FixedI64,
+    /// Median price that breached the limit
+    pub price: FixedI64,
+    /// Last block when the price is suspect
+    pub until: BlockNumber,
+    /// Feeders that submitted prices with the same jump
+    pub confirmations: Vec<AccountId>,
+}
+
+/// Key a whitelisted feeder signs prices with. A rotated out key keeps working
+/// till `expires_at`, so the feeder has time to switch its offchain workers
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct FeederKey<BlockNumber> {
+    /// First block when the key is not valid, `None` for an active key
+    pub expires_at: Option<BlockNumber>,
+}
+
+/// Band and window of the price feeders outlier rejection
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct OutlierRejectionParams {
+    /// Max deviation of a submitted price from the median of other feeders
+    pub band: Permill,
+    /// Feeder is excluded from the median when deviated in `max_deviations`
+    /// of the last `window` rounds
+    pub max_deviations: u32,
+    /// Number of last rounds to check, not greater than 32
+    pub window: u32,
+}
+
+/// Reliability of a price feeder, updated on every submitted price
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct FeederStats {
+    /// Number of submitted prices checked against other feeders
+    pub rounds: u32,
+    /// Number of submitted prices deviated beyond the band
+    pub deviations: u32,
+    /// Deviation flags of the last rounds, the lowest bit is the last round
+    pub recent_deviations: u32,
+    /// Feeder prices are not taken into account in the median
+    pub excluded: bool,
+}
+
+/// Stage of the financial metrics recalculation deferred to `on_idle`
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum FinancialRecalcStage {
+    /// Assets whose metrics are recalculated one by one before the common metrics
+    Assets(Vec<Asset>),
+    /// Common metrics of all assets: correlations and covariances
+    Metrics,
+}
+
 /// Struct for storing aggregated asset price data
 #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
 pub struct PricePoint<AccountId, BlockNumber> {
@@ -442,7 +525,7 @@ pub mod pallet {
         type LpPriceBlockTimeout: Get<u64>;
         /// Trait for Xdot pricing
         type XBasePrice: XBasePrice<Asset, Self::Balance, FixedI64>;
-        /// To get total amount of staked DOT
+        /// Price coefficient of the wrapped relay token: EQDOT on Polkadot, EQKSM on Kusama
         type EqDotPrice: eq_primitives::wrapped_dot::EqDotPrice;
         /// Used to work with `TotalAggregates` storing aggregated collateral and debt
         type Aggregates: Aggregates<Self::AccountId, Self::Balance>;
@@ -453,6 +536,32 @@ pub mod pallet {
         type UnsignedLifetimeInBlocks: Get<u32>;
         /// Used to clear LendersAggregates, CumulatedRewards storages while asset removal
         type LendingAssetRemoval: LendingAssetRemoval<Self::AccountId>;
+        /// Origin for circuit breaker settings and suspect price overrides
+        type PriceCircuitBreakerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Number of blocks a price stays suspect if it isn't confirmed
+        #[pallet::constant]
+        type SuspectPricePeriod: Get<Self::BlockNumber>;
+        /// Number of distinct feeders required to confirm a suspect price
+        #[pallet::constant]
+        type SuspectPriceConfirmations: Get<u32>;
+        /// Number of blocks a rotated out feeder key stays valid
+        #[pallet::constant]
+        type FeederKeyOverlap: Get<Self::BlockNumber>;
+        /// Max number of keys per feeder, including rotated out ones
+        #[pallet::constant]
+        type MaxFeederKeys: Get<u32>;
+        /// Custom pricing of LP tokens registered in `LpPriceAdapters`
+        type LpPriceAdapter: LpPriceAdapter;
+        /// Number of consecutive failed financial recalculations after which
+        /// `FinancialRecalcFailing` is deposited
+        #[pallet::constant]
+        type MaxFinancialRecalcFailures: Get<u32>;
+        /// Max number of prices in one `set_prices` transaction
+        #[pallet::constant]
+        type MaxPricesPerBatch: Get<u32>;
+        /// Max number of `set_price`, `set_price_unsigned` and `set_prices` calls in a block
+        #[pallet::constant]
+        type MaxPriceSubmissionsPerBlock: Get<u32>;
     }
 
     #[pallet::call]
@@ -467,9 +576,11 @@ pub mod pallet {
             asset: Asset,
             price: FixedI64,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
+            let key = ensure_signed(origin)?;
+            let who = Self::feeder_of(&key).unwrap_or(key);
             let current_block = frame_system::Pallet::<T>::block_number();
             Self::validate_params(who.clone(), asset, price, current_block)?;
+            Self::take_submission_slot()?;
 
             <Self as PriceSetter<T::AccountId>>::set_price(who, asset, price)?;
             Ok(Pays::No.into())
@@ -490,8 +601,10 @@ pub mod pallet {
                 price,
                 block_number: _,
             } = payload;
-            let who = public.into_account();
+            let key = public.into_account();
+            let who = Self::feeder_of(&key).unwrap_or(key);
             Self::validate_params(who.clone(), asset, price, payload.block_number)?;
+            Self::take_submission_slot()?;
             <Self as PriceSetter<T::AccountId>>::set_price(who, asset, price)
         }
 
@@ -507,6 +620,248 @@ pub mod pallet {
             log::trace!(target: "eq_oracle", "Auto recalc of financial metrics set to '{}'", enabled);
             Ok(().into())
         }
+
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_max_price_jump())]
+        /// Sets the max jump of the `asset` median price between consecutive price points.
+        /// Removes the limit when `max_jump` is `None`
+        pub fn set_max_price_jump(
+            origin: OriginFor<T>,
+            asset: Asset,
+            max_jump: Option<Permill>,
+        ) -> DispatchResultWithPostInfo {
+            T::PriceCircuitBreakerOrigin::ensure_origin(origin)?;
+            match max_jump {
+                Some(max_jump) => <MaxPriceJump<T>>::insert(asset, max_jump),
+                None => <MaxPriceJump<T>>::remove(asset),
+            }
+            Self::deposit_event(Event::MaxPriceJumpSet(asset, max_jump));
+            Ok(().into())
+        }
+
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::override_suspect_price())]
+        /// Accepts the current suspect `asset` price without feeders confirmation
+        pub fn override_suspect_price(
+            origin: OriginFor<T>,
+            asset: Asset,
+        ) -> DispatchResultWithPostInfo {
+            T::PriceCircuitBreakerOrigin::ensure_origin(origin)?;
+            let suspect = <SuspectPrices<T>>::take(asset).ok_or(Error::<T>::PriceIsNotSuspect)?;
+            Self::deposit_event(Event::SuspectPriceAccepted(asset, suspect.price));
+            Ok(().into())
+        }
+
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_feeder_weight())]
+        /// Sets the weight of `who` prices in the median. Feeders without
+        /// a weight have weight 1, zero weight mutes the feeder
+        pub fn set_feeder_weight(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            weight: Option<u32>,
+        ) -> DispatchResultWithPostInfo {
+            T::PriceCircuitBreakerOrigin::ensure_origin(origin)?;
+            match weight {
+                Some(weight) => <FeederWeights<T>>::insert(&who, weight),
+                None => <FeederWeights<T>>::remove(&who),
+            }
+            Self::deposit_event(Event::FeederWeightSet(who, weight));
+            Ok(().into())
+        }
+
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_outlier_rejection())]
+        /// Sets parameters of the feeders outlier rejection.
+        /// Disables the rejection and clears feeders stats when `params` is `None`
+        pub fn set_outlier_rejection(
+            origin: OriginFor<T>,
+            params: Option<OutlierRejectionParams>,
+        ) -> DispatchResultWithPostInfo {
+            T::PriceCircuitBreakerOrigin::ensure_origin(origin)?;
+            match params {
+                Some(params) => {
+                    ensure!(
+                        params.window > 0
+                            && params.window <= u32::BITS
+                            && params.max_deviations > 0
+                            && params.max_deviations <= params.window,
+                        Error::<T>::InvalidOutlierRejectionParams
+                    );
+                    <OutlierRejection<T>>::put(params);
+                }
+                None => {
+                    <OutlierRejection<T>>::kill();
+                    let _ = <FeederReliability<T>>::clear(u32::MAX, None);
+                }
+            }
+            Self::deposit_event(Event::OutlierRejectionSet(params));
+            Ok(().into())
+        }
+
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_feeder_key())]
+        /// Allows `key` to sign prices on behalf of the whitelisted caller
+        pub fn add_feeder_key(
+            origin: OriginFor<T>,
+            key: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let feeder = ensure_signed(origin)?;
+            ensure!(
+                T::Whitelist::in_whitelist(&feeder),
+                Error::<T>::NotAllowedToSubmitPrice
+            );
+            Self::do_add_feeder_key(&feeder, key.clone())?;
+            Self::deposit_event(Event::FeederKeyAdded(feeder, key));
+            Ok(().into())
+        }
+
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::rotate_feeder_key())]
+        /// Replaces `old_key` of the whitelisted caller with `new_key`. `old_key` stays
+        /// valid for `FeederKeyOverlap` blocks
+        pub fn rotate_feeder_key(
+            origin: OriginFor<T>,
+            old_key: T::AccountId,
+            new_key: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let feeder = ensure_signed(origin)?;
+            ensure!(
+                T::Whitelist::in_whitelist(&feeder),
+                Error::<T>::NotAllowedToSubmitPrice
+            );
+            let old =
+                <FeederKeys<T>>::get(&feeder, &old_key).ok_or(Error::<T>::FeederKeyNotFound)?;
+            let overlap_end =
+                frame_system::Pallet::<T>::block_number() + T::FeederKeyOverlap::get();
+            let expires_at = old
+                .expires_at
+                .map_or(overlap_end, |expires_at| expires_at.min(overlap_end));
+
+            <FeederKeys<T>>::insert(
+                &feeder,
+                &old_key,
+                FeederKey {
+                    expires_at: Some(expires_at),
+                },
+            );
+            Self::do_add_feeder_key(&feeder, new_key.clone())?;
+            Self::deposit_event(Event::FeederKeyRotated(
+                feeder, old_key, new_key, expires_at,
+            ));
+            Ok(().into())
+        }
+
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_feeder_key())]
+        /// Revokes `key` of the caller immediately, e.g. when the key is leaked
+        pub fn remove_feeder_key(
+            origin: OriginFor<T>,
+            key: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let feeder = ensure_signed(origin)?;
+            ensure!(
+                <FeederKeys<T>>::contains_key(&feeder, &key),
+                Error::<T>::FeederKeyNotFound
+            );
+            <FeederKeys<T>>::remove(&feeder, &key);
+            <FeederKeyOwners<T>>::remove(&key);
+            Self::deposit_event(Event::FeederKeyRemoved(feeder, key));
+            Ok(().into())
+        }
+
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_lp_price_adapter())]
+        /// Prices LP token `asset` with `adapter` instead of the built-in pool math.
+        /// Returns the asset to the built-in pricing when `adapter` is `None`
+        pub fn set_lp_price_adapter(
+            origin: OriginFor<T>,
+            asset: Asset,
+            adapter: Option<LpPriceAdapterId>,
+        ) -> DispatchResultWithPostInfo {
+            T::PriceCircuitBreakerOrigin::ensure_origin(origin)?;
+            let asset_data = T::AssetGetter::get_asset_data(&asset)?;
+            ensure!(
+                matches!(asset_data.asset_type, AssetType::Lp(_)),
+                Error::<T>::LpAssetExpected
+            );
+
+            match adapter {
+                Some(adapter) => {
+                    ensure!(
+                        T::LpPriceAdapter::is_known(adapter),
+                        Error::<T>::UnknownLpPriceAdapter
+                    );
+                    <LpPriceAdapters<T>>::insert(asset, adapter);
+                }
+                None => <LpPriceAdapters<T>>::remove(asset),
+            }
+            Self::deposit_event(Event::LpPriceAdapterSet(asset, adapter));
+            Ok(().into())
+        }
+
+        #[pallet::call_index(11)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::set_prices(payload.prices.len() as u32),
+            DispatchClass::Operational
+        ))]
+        /// Adds new `DataPoint`s for several assets from an unsigned transaction. Prices
+        /// are accepted one by one: a price failing validation doesn't affect the others
+        /// and is reported in `PricesBatchSet`
+        pub fn set_prices(
+            origin: OriginFor<T>,
+            payload: PricesPayload<T::Public, T::BlockNumber>,
+            _signature: T::Signature,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            let PricesPayload {
+                public,
+                prices,
+                block_number,
+            } = payload;
+            eq_ensure!(
+                Self::is_valid_batch_len(prices.len()),
+                Error::<T>::InvalidPricesBatch,
+                target: "eq_oracle",
+                "{}:{}. Wrong number of prices in batch: {:?}.",
+                file!(),
+                line!(),
+                prices.len()
+            );
+            Self::take_submission_slot()?;
+
+            let key = public.into_account();
+            let who = Self::feeder_of(&key).unwrap_or(key);
+            let mut accepted = 0u32;
+            let mut rejected = Vec::new();
+            for (asset, price) in prices {
+                let result = frame_support::storage::with_transaction(
+                    || -> TransactionOutcome<DispatchResult> {
+                        let result = Self::validate_params(who.clone(), asset, price, block_number)
+                            .and_then(|_| {
+                                <Self as PriceSetter<T::AccountId>>::set_price(
+                                    who.clone(),
+                                    asset,
+                                    price,
+                                )
+                                .map(|_| ())
+                                .map_err(|e| e.error)
+                            });
+                        match result {
+                            Ok(()) => TransactionOutcome::Commit(Ok(())),
+                            Err(err) => TransactionOutcome::Rollback(Err(err)),
+                        }
+                    },
+                );
+                match result {
+                    Ok(()) => accepted += 1,
+                    Err(_) => rejected.push(asset),
+                }
+            }
+
+            Self::deposit_event(Event::PricesBatchSet(who, accepted, rejected));
+            Ok(().into())
+        }
     }
 
     #[pallet::hooks]
@@ -525,7 +880,7 @@ pub mod pallet {
                         >>::GenericPublic::from(key);
                         let public: T::Public = generic_public.into();
                         let account_id = public.clone().into_account();
-                        if T::Whitelist::in_whitelist(&account_id) {
+                        if Self::feeder_of(&account_id).is_some() {
                             Option::Some(public.clone())
                         } else {
                             Option::None
@@ -617,13 +972,29 @@ pub mod pallet {
                     Ok(())
                 };
 
+                let update_adapter_price = |asset, adapter| -> DispatchResult {
+                    let LpPrice { price, related } = T::LpPriceAdapter::lp_price(adapter, asset)
+                        .ok_or(Error::<T>::UnknownLpPriceAdapter)??;
+                    for (related_asset, related_price) in related {
+                        Self::set_the_only_price(related_asset, related_price);
+                    }
+
+                    Self::set_the_only_price(asset, price);
+                    T::OnPriceSet::on_price_set(asset, fixedi64_to_i64f64(price))?;
+
+                    Ok(())
+                };
+
                 for asset_data in T::AssetGetter::get_assets_data() {
                     let asset = asset_data.id;
 
                     if let AssetType::Lp(amm_type) = asset_data.asset_type {
                         // Ignore a price update error for the individual pool
                         // so all existing pools have a chance to update
-                        let _ = update_price(asset, amm_type);
+                        let _ = match <LpPriceAdapters<T>>::get(asset) {
+                            Some(adapter) => update_adapter_price(asset, adapter),
+                            None => update_price(asset, amm_type),
+                        };
                     }
                 }
 
@@ -656,13 +1027,20 @@ pub mod pallet {
                 let _ = Self::remove_asset();
             }
 
+            let forced_recalc_weight = Self::force_stalled_metrics_recalc();
+
             if <FinMetricsRecalcEnabled<T>>::get()
                 && (current_block % T::FinancialRecalcPeriodBlocks::get()).is_zero()
             {
-                let _ = T::FinancialSystemTrait::recalc_inner();
+                Self::enqueue_financial_recalc(&[]);
             }
 
-            Weight::from_parts(10_000, 0)
+            Weight::from_parts(10_000, 0).saturating_add(forced_recalc_weight)
+        }
+
+        /// Proceeds with the deferred financial metrics recalculation within `remaining_weight`
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::process_financial_recalc(remaining_weight)
         }
     }
 
@@ -674,6 +1052,36 @@ pub mod pallet {
         /// aggregated price and `AccountId` of the price submitter
         /// \[asset, new_value, aggregated, submitter\]
         NewPrice(Asset, FixedI64, FixedI64, T::AccountId),
+        /// Max price jump is changed \[asset, max_jump\]
+        MaxPriceJumpSet(Asset, Option<Permill>),
+        /// Median price jumped over the max price jump and is suspect until the block
+        /// \[asset, reference_price, price, until\]
+        PriceSuspect(Asset, FixedI64, FixedI64, T::BlockNumber),
+        /// Suspect price is confirmed by feeders or governance \[asset, price\]
+        SuspectPriceAccepted(Asset, FixedI64),
+        /// Weight of the feeder prices in the median is changed \[who, weight\]
+        FeederWeightSet(T::AccountId, Option<u32>),
+        /// Outlier rejection parameters are changed \[params\]
+        OutlierRejectionSet(Option<OutlierRejectionParams>),
+        /// Feeder deviated too often and is excluded from the median \[who\]
+        FeederExcluded(T::AccountId),
+        /// Excluded feeder is taken into account in the median again \[who\]
+        FeederReinstated(T::AccountId),
+        /// Key is allowed to sign prices on behalf of the feeder \[feeder, key\]
+        FeederKeyAdded(T::AccountId, T::AccountId),
+        /// Feeder key is rotated, the old key is valid till the block
+        /// \[feeder, old_key, new_key, expires_at\]
+        FeederKeyRotated(T::AccountId, T::AccountId, T::AccountId, T::BlockNumber),
+        /// Feeder key is revoked \[feeder, key\]
+        FeederKeyRemoved(T::AccountId, T::AccountId),
+        /// Pricing adapter of LP token is changed \[asset, adapter\]
+        LpPriceAdapterSet(Asset, Option<LpPriceAdapterId>),
+        /// Financial recalculation of the asset (`None` for common metrics) keeps failing,
+        /// risk metrics are stale \[asset, failures\]
+        FinancialRecalcFailing(Option<Asset>, u32),
+        /// Batch of prices is processed, rejected prices are not added
+        /// \[submitter, accepted, rejected_assets\]
+        PricesBatchSet(T::AccountId, u32, Vec<Asset>),
     }
 
     #[pallet::error]
@@ -698,6 +1106,24 @@ pub mod pallet {
         PoolNotFound,
         /// A primitive asset is expected
         PrimitiveAssetExpected,
+        /// There is no suspect price for the asset
+        PriceIsNotSuspect,
+        /// Outlier rejection window should be in 1..=32 and max deviations in 1..=window
+        InvalidOutlierRejectionParams,
+        /// The key is already used by a feeder
+        FeederKeyInUse,
+        /// The feeder has no such key
+        FeederKeyNotFound,
+        /// The feeder has too many keys
+        TooManyFeederKeys,
+        /// An LP token is expected
+        LpAssetExpected,
+        /// No pricing adapter with this id
+        UnknownLpPriceAdapter,
+        /// Batch of prices is empty or exceeds `MaxPricesPerBatch`
+        InvalidPricesBatch,
+        /// Limit of price submissions in the block is reached
+        PriorityLaneExhausted,
     }
 
     /// Pallet storage for added price points
@@ -725,6 +1151,91 @@ pub mod pallet {
     pub type FinMetricsRecalcEnabled<T: Config> =
         StorageValue<_, bool, ValueQuery, DefaultForFinMetricsRecalcEnabled>;
 
+    /// Max jump of the median price between consecutive price points, per asset
+    #[pallet::storage]
+    #[pallet::getter(fn max_price_jump)]
+    pub type MaxPriceJump<T: Config> = StorageMap<_, Identity, Asset, Permill, OptionQuery>;
+
+    /// Prices that breached `MaxPriceJump` and are not confirmed yet
+    #[pallet::storage]
+    #[pallet::getter(fn suspect_prices)]
+    pub type SuspectPrices<T: Config> =
+        StorageMap<_, Identity, Asset, SuspectPrice<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultForFeederWeights() -> u32 {
+        1
+    }
+
+    /// Weights of feeders prices in the median
+    #[pallet::storage]
+    #[pallet::getter(fn feeder_weights)]
+    pub type FeederWeights<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery, DefaultForFeederWeights>;
+
+    /// Outlier rejection parameters, feeders are never excluded when not set
+    #[pallet::storage]
+    #[pallet::getter(fn outlier_rejection)]
+    pub type OutlierRejection<T: Config> = StorageValue<_, OutlierRejectionParams, OptionQuery>;
+
+    /// Feeders reliability stats
+    #[pallet::storage]
+    #[pallet::getter(fn feeder_reliability)]
+    pub type FeederReliability<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, FeederStats, ValueQuery>;
+
+    /// Pending financial metrics recalculation, processed in `on_idle`
+    #[pallet::storage]
+    #[pallet::getter(fn financial_recalc_progress)]
+    pub type FinancialRecalcProgress<T: Config> =
+        StorageValue<_, FinancialRecalcStage, OptionQuery>;
+
+    /// Keys whitelisted feeders sign prices with
+    #[pallet::storage]
+    #[pallet::getter(fn feeder_keys)]
+    pub type FeederKeys<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        FeederKey<T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Feeder of a key from `FeederKeys`
+    #[pallet::storage]
+    #[pallet::getter(fn feeder_key_owner)]
+    pub type FeederKeyOwners<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// Pricing adapters of LP tokens, tokens without adapter are priced by the built-in
+    /// Curve and yield pools math
+    #[pallet::storage]
+    #[pallet::getter(fn lp_price_adapter)]
+    pub type LpPriceAdapters<T: Config> =
+        StorageMap<_, Identity, Asset, LpPriceAdapterId, OptionQuery>;
+
+    /// Consecutive failed financial recalculations of assets, assets are retried
+    /// in later blocks until recalculation succeeds
+    #[pallet::storage]
+    #[pallet::getter(fn financial_recalc_failures)]
+    pub type FinancialRecalcFailures<T: Config> = StorageMap<_, Identity, Asset, u32, ValueQuery>;
+
+    /// Consecutive failed recalculations of common financial metrics
+    #[pallet::storage]
+    #[pallet::getter(fn financial_metrics_recalc_failures)]
+    pub type FinancialMetricsRecalcFailures<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Number of blocks common financial metrics are waiting for enough weight in `on_idle`
+    #[pallet::storage]
+    #[pallet::getter(fn financial_metrics_recalc_stall)]
+    pub type FinancialMetricsRecalcStall<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Block number and count of price submissions dispatched in it
+    #[pallet::storage]
+    pub type PriceSubmissions<T: Config> = StorageValue<_, (T::BlockNumber, u32), ValueQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig {
         pub prices: Vec<(u64, u64, u64)>,
@@ -781,47 +1292,237 @@ pub mod pallet {
         type Call = Call<T>;
 
         fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-            if let Call::set_price_unsigned { payload, signature } = call {
-                let signature_valid =
-                    SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
-                if !signature_valid {
-                    return InvalidTransaction::BadProof.into();
-                }
+            match call {
+                Call::set_price_unsigned { payload, signature } => {
+                    let signature_valid =
+                        SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
+                    if !signature_valid {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    Self::validate_unsigned_block(payload.block_number)?;
+                    Self::validate_submissions_left()?;
 
-                let current_block = <frame_system::Pallet<T>>::block_number();
+                    let key = payload.public.clone().into_account();
+                    let account = Self::feeder_of(&key).unwrap_or(key);
 
-                if payload.block_number > current_block {
-                    // transaction in future?
-                    return InvalidTransaction::Stale.into();
-                } else if payload.block_number + T::UnsignedLifetimeInBlocks::get().into()
-                    < current_block
-                {
-                    // transaction was in pool for 5 blocks
-                    return InvalidTransaction::Stale.into();
+                    Self::validate_params(
+                        account,
+                        payload.asset,
+                        payload.price,
+                        payload.block_number,
+                    )
+                    .map_err(|_| InvalidTransaction::Call)?;
+
+                    let priority = calculate_unsigned_priority(
+                        &T::UnsignedPriority::get(),
+                        payload.block_number,
+                    );
+
+                    ValidTransaction::with_tag_prefix("EqPrice")
+                        .priority(priority)
+                        .and_provides((payload.public.clone(), payload.asset))
+                        .longevity(5) // hotfix, transfer to config
+                        .propagate(true)
+                        .build()
                 }
+                Call::set_prices { payload, signature } => {
+                    let signature_valid =
+                        SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
+                    if !signature_valid {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    Self::validate_unsigned_block(payload.block_number)?;
+                    Self::validate_submissions_left()?;
+                    if !Self::is_valid_batch_len(payload.prices.len()) {
+                        return InvalidTransaction::Call.into();
+                    }
 
-                let account = payload.public.clone().into_account();
+                    let key = payload.public.clone().into_account();
+                    let account = Self::feeder_of(&key).unwrap_or(key);
 
-                Self::validate_params(account, payload.asset, payload.price, payload.block_number)
-                    .map_err(|_| InvalidTransaction::Call)?;
+                    // batch is accepted partially, it's enough for one price to be valid
+                    let has_valid_price = payload.prices.iter().any(|(asset, price)| {
+                        Self::validate_params(account.clone(), *asset, *price, payload.block_number)
+                            .is_ok()
+                    });
+                    if !has_valid_price {
+                        return InvalidTransaction::Call.into();
+                    }
 
-                let priority =
-                    calculate_unsigned_priority(&T::UnsignedPriority::get(), payload.block_number);
+                    let priority = calculate_unsigned_priority(
+                        &T::UnsignedPriority::get(),
+                        payload.block_number,
+                    );
 
-                ValidTransaction::with_tag_prefix("EqPrice")
-                    .priority(priority)
-                    .and_provides((payload.public.clone(), payload.asset))
-                    .longevity(5) // hotfix, transfer to config
-                    .propagate(true)
-                    .build()
-            } else {
-                InvalidTransaction::Call.into()
+                    ValidTransaction::with_tag_prefix("EqPrices")
+                        .priority(priority)
+                        .and_provides((payload.public.clone(), payload.block_number))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                _ => InvalidTransaction::Call.into(),
             }
         }
     }
 }
 
 impl<T: Config> Pallet<T> {
+    /// Requests financial metrics recalculation in `on_idle`, `assets` are recalculated
+    /// one by one before the common metrics. Hooks call it instead of recalculating
+    /// synchronously, so block weight doesn't grow with the number of assets.
+    pub fn enqueue_financial_recalc(assets: &[Asset]) {
+        FinancialRecalcProgress::<T>::mutate(|maybe_stage| {
+            let mut queued = match maybe_stage.take() {
+                Some(FinancialRecalcStage::Assets(queued)) => queued,
+                Some(FinancialRecalcStage::Metrics) | None => Vec::new(),
+            };
+            for asset in assets {
+                if !queued.contains(asset) {
+                    queued.push(*asset);
+                }
+            }
+            *maybe_stage = Some(if queued.is_empty() {
+                FinancialRecalcStage::Metrics
+            } else {
+                FinancialRecalcStage::Assets(queued)
+            });
+        });
+    }
+
+    /// Recalculates queued assets while `remaining_weight` allows, then common metrics
+    /// if there is enough weight for all assets. Returns consumed weight.
+    fn process_financial_recalc(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let mut consumed = db_weight.reads(1);
+        if consumed.any_gt(remaining_weight) {
+            return Weight::zero();
+        }
+
+        match FinancialRecalcProgress::<T>::get() {
+            None => {
+                // schedule retry of failed recalculations
+                let failed: Vec<_> = FinancialRecalcFailures::<T>::iter_keys().collect();
+                let retry_weight = db_weight.reads_writes(failed.len() as u64 + 2, 1);
+                if consumed
+                    .saturating_add(retry_weight)
+                    .any_gt(remaining_weight)
+                {
+                    return consumed;
+                }
+                if !failed.is_empty() || FinancialMetricsRecalcFailures::<T>::get() > 0 {
+                    Self::enqueue_financial_recalc(&failed);
+                }
+                consumed = consumed.saturating_add(retry_weight);
+            }
+            Some(FinancialRecalcStage::Assets(mut queued)) => {
+                let asset_weight =
+                    <T as Config>::WeightInfo::recalc_asset().saturating_add(db_weight.writes(1));
+                while let Some(asset) = queued.last().copied() {
+                    if consumed
+                        .saturating_add(asset_weight)
+                        .any_gt(remaining_weight)
+                    {
+                        break;
+                    }
+                    Self::note_financial_recalc_result(
+                        Some(asset),
+                        T::FinancialSystemTrait::recalc_asset_inner(asset),
+                    );
+                    queued.pop();
+                    consumed = consumed.saturating_add(asset_weight);
+                }
+
+                consumed = consumed.saturating_add(db_weight.writes(1));
+                FinancialRecalcProgress::<T>::put(if queued.is_empty() {
+                    FinancialRecalcStage::Metrics
+                } else {
+                    FinancialRecalcStage::Assets(queued)
+                });
+            }
+            Some(FinancialRecalcStage::Metrics) => {
+                let assets_count = T::AssetGetter::get_assets_data().len() as u32;
+                let metrics_weight = <T as Config>::WeightInfo::recalc_metrics(assets_count)
+                    .saturating_add(db_weight.reads_writes(1, 3));
+                if consumed
+                    .saturating_add(metrics_weight)
+                    .any_gt(remaining_weight)
+                {
+                    return consumed;
+                }
+                Self::note_financial_recalc_result(None, T::FinancialSystemTrait::recalc_inner());
+
+                consumed = consumed.saturating_add(metrics_weight);
+                FinancialRecalcProgress::<T>::kill();
+                FinancialMetricsRecalcStall::<T>::kill();
+            }
+        }
+
+        consumed
+    }
+
+    /// Recalculates common metrics in `on_initialize` when they didn't fit in `on_idle`
+    /// for `FinancialRecalcPeriodBlocks`, so metrics of many assets don't stay stale
+    /// while blocks are full. Returns consumed weight.
+    fn force_stalled_metrics_recalc() -> Weight {
+        let db_weight = T::DbWeight::get();
+        if FinancialRecalcProgress::<T>::get() != Some(FinancialRecalcStage::Metrics) {
+            return db_weight.reads(1);
+        }
+
+        let stalled = FinancialMetricsRecalcStall::<T>::mutate(|blocks| {
+            *blocks = blocks.saturating_add(1);
+            *blocks
+        });
+        let max_stall: u32 = T::FinancialRecalcPeriodBlocks::get().unique_saturated_into();
+        if stalled < max_stall.max(1) {
+            return db_weight.reads_writes(2, 1);
+        }
+
+        let assets_count = T::AssetGetter::get_assets_data().len() as u32;
+        Self::note_financial_recalc_result(None, T::FinancialSystemTrait::recalc_inner());
+        FinancialRecalcProgress::<T>::kill();
+        FinancialMetricsRecalcStall::<T>::kill();
+
+        <T as Config>::WeightInfo::recalc_metrics(assets_count)
+            .saturating_add(db_weight.reads_writes(2, 3))
+    }
+
+    /// Tracks consecutive failures of the financial recalculation of `asset`
+    /// (`None` for common metrics), failed ones are retried in later blocks
+    fn note_financial_recalc_result(asset: Option<Asset>, result: Result<(), DispatchError>) {
+        let failures = match result {
+            Ok(()) => {
+                match asset {
+                    Some(asset) => FinancialRecalcFailures::<T>::remove(asset),
+                    None => FinancialMetricsRecalcFailures::<T>::kill(),
+                }
+                return;
+            }
+            Err(err) => {
+                log::error!(
+                    "{}:{}. Financial recalculation of {:?} failed: {:?}",
+                    file!(),
+                    line!(),
+                    asset,
+                    err
+                );
+                let increment = |failures: &mut u32| {
+                    *failures = failures.saturating_add(1);
+                    *failures
+                };
+                match asset {
+                    Some(asset) => FinancialRecalcFailures::<T>::mutate(asset, increment),
+                    None => FinancialMetricsRecalcFailures::<T>::mutate(increment),
+                }
+            }
+        };
+
+        if failures % T::MaxFinancialRecalcFailures::get().max(1) == 0 {
+            Self::deposit_event(Event::FinancialRecalcFailing(asset, failures));
+        }
+    }
+
     fn find_asset_by_symbol(assets_data: &[AssetData<Asset>], symbol: &str) -> Option<Asset> {
         let asset = assets_data
             .iter()
@@ -927,13 +1628,14 @@ impl<T: Config> Pallet<T> {
         block_number: T::BlockNumber,
         signer: &Signer<T, T::AuthorityId, ForAll>,
     ) {
+        let mut prices = Vec::new();
         for (asset, price_result) in Self::get_prices(source_type) {
             if asset == asset::MXUSDC {
                 continue;
             }
             match price_result {
                 Ok(price) => {
-                    Self::submit_tx_update_price(asset, price, block_number, signer);
+                    Self::push_with_derived_prices(&mut prices, asset, price);
                 }
                 Err(err) => {
                     log::error!(
@@ -949,114 +1651,164 @@ impl<T: Config> Pallet<T> {
                 }
             }
         }
+
+        let batch_len = T::MaxPricesPerBatch::get().max(1) as usize;
+        for batch in prices.chunks(batch_len) {
+            Self::submit_tx_update_prices(batch, block_number, signer);
+        }
     }
 
-    /// Prepares unsigned transaction with new price
-    fn submit_tx_update_price(
+    /// Pushes `asset` price along with prices of the assets derived from it
+    fn push_with_derived_prices(
+        prices: &mut Vec<(Asset, FixedI64)>,
         asset: Asset,
         price: FixedI64,
-        block_number: T::BlockNumber,
-        signer: &Signer<T, T::AuthorityId, ForAll>,
     ) {
         if asset == asset::DOT {
-            if T::AssetGetter::exists(asset::HDOT) {
-                signer.send_unsigned_transaction(
-                    |account| PricePayload {
-                        public: account.public.clone(),
-                        asset: asset::HDOT,
-                        price: price,
-                        block_number,
-                    },
-                    |payload, signature| Call::set_price_unsigned { payload, signature },
-                );
-            }
-
-            if T::AssetGetter::exists(asset::STDOT) {
-                signer.send_unsigned_transaction(
-                    |account| PricePayload {
-                        public: account.public.clone(),
-                        asset: asset::STDOT,
-                        price: price,
-                        block_number,
-                    },
-                    |payload, signature| Call::set_price_unsigned { payload, signature },
-                );
-            }
-
-            if T::AssetGetter::exists(asset::XDOT) {
-                signer.send_unsigned_transaction(
-                    |account| PricePayload {
-                        public: account.public.clone(),
-                        asset: asset::XDOT,
-                        price: price,
-                        block_number,
-                    },
-                    |payload, signature| Call::set_price_unsigned { payload, signature },
-                );
-            }
-
-            if T::AssetGetter::exists(asset::XDOT2) {
-                signer.send_unsigned_transaction(
-                    |account| PricePayload {
-                        public: account.public.clone(),
-                        asset: asset::XDOT2,
-                        price: price,
-                        block_number,
-                    },
-                    |payload, signature| Call::set_price_unsigned { payload, signature },
-                );
-            }
-
-            if T::AssetGetter::exists(asset::XDOT3) {
-                signer.send_unsigned_transaction(
-                    |account| PricePayload {
-                        public: account.public.clone(),
-                        asset: asset::XDOT3,
-                        price: price,
-                        block_number,
-                    },
-                    |payload, signature| Call::set_price_unsigned { payload, signature },
-                );
+            for derived in [
+                asset::HDOT,
+                asset::STDOT,
+                asset::XDOT,
+                asset::XDOT2,
+                asset::XDOT3,
+            ] {
+                if T::AssetGetter::exists(derived) {
+                    prices.push((derived, price));
+                }
             }
 
             if T::AssetGetter::exists(asset::EQDOT) {
                 if let Some(eqdot_price_coeff) = T::EqDotPrice::get_price_coeff() {
-                    signer.send_unsigned_transaction(
-                        |account| PricePayload {
-                            public: account.public.clone(),
-                            asset: asset::EQDOT,
-                            price: price * eqdot_price_coeff,
-                            block_number,
-                        },
-                        |payload, signature| Call::set_price_unsigned { payload, signature },
-                    );
+                    prices.push((asset::EQDOT, price * eqdot_price_coeff));
                 }
             }
 
             if T::AssetGetter::exists(asset::TDOT) {
-                signer.send_unsigned_transaction(
-                    |account| PricePayload {
-                        public: account.public.clone(),
-                        asset: asset::TDOT,
-                        price: price,
-                        block_number,
-                    },
-                    |payload, signature| Call::set_price_unsigned { payload, signature },
-                );
+                prices.push((asset::TDOT, price));
             }
         }
 
+        if asset == asset::KSM && T::AssetGetter::exists(asset::EQKSM) {
+            if let Some(eqksm_price_coeff) = T::EqDotPrice::get_price_coeff() {
+                prices.push((asset::EQKSM, price * eqksm_price_coeff));
+            }
+        }
+
+        prices.push((asset, price));
+    }
+
+    /// Prepares unsigned transaction with a batch of new prices
+    fn submit_tx_update_prices(
+        prices: &[(Asset, FixedI64)],
+        block_number: T::BlockNumber,
+        signer: &Signer<T, T::AuthorityId, ForAll>,
+    ) {
         signer.send_unsigned_transaction(
-            |account| PricePayload {
+            |account| PricesPayload {
                 public: account.public.clone(),
-                asset,
-                price,
+                prices: prices.to_vec(),
                 block_number,
             },
-            |payload, signature| Call::set_price_unsigned { payload, signature },
+            |payload, signature| Call::set_prices { payload, signature },
         );
     }
 
+    /// Checks that `block_number` of an unsigned transaction payload is not in the future
+    /// and not older than `UnsignedLifetimeInBlocks`
+    fn validate_unsigned_block(block_number: T::BlockNumber) -> Result<(), InvalidTransaction> {
+        let current_block = <frame_system::Pallet<T>>::block_number();
+
+        if block_number > current_block {
+            // transaction in future?
+            Err(InvalidTransaction::Stale)
+        } else if block_number + T::UnsignedLifetimeInBlocks::get().into() < current_block {
+            // transaction was in pool for 5 blocks
+            Err(InvalidTransaction::Stale)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Number of price submissions dispatched in the current block
+    fn price_submissions() -> u32 {
+        let (block, count) = PriceSubmissions::<T>::get();
+        if block == frame_system::Pallet::<T>::block_number() {
+            count
+        } else {
+            0
+        }
+    }
+
+    /// Checks that `MaxPriceSubmissionsPerBlock` isn't reached in the current block
+    fn validate_submissions_left() -> Result<(), InvalidTransaction> {
+        if Self::price_submissions() < T::MaxPriceSubmissionsPerBlock::get() {
+            Ok(())
+        } else {
+            Err(InvalidTransaction::ExhaustsResources)
+        }
+    }
+
+    /// Counts a price submission in the current block, fails when
+    /// `MaxPriceSubmissionsPerBlock` is reached
+    fn take_submission_slot() -> DispatchResult {
+        let count = Self::price_submissions();
+        eq_ensure!(
+            count < T::MaxPriceSubmissionsPerBlock::get(),
+            Error::<T>::PriorityLaneExhausted,
+            target: "eq_oracle",
+            "{}:{}. Price submissions limit is reached. Count: {:?}.",
+            file!(),
+            line!(),
+            count
+        );
+        PriceSubmissions::<T>::put((frame_system::Pallet::<T>::block_number(), count + 1));
+        Ok(())
+    }
+
+    fn is_valid_batch_len(len: usize) -> bool {
+        len > 0 && len <= T::MaxPricesPerBatch::get() as usize
+    }
+
+    /// Whitelisted feeder `key` signs prices for: the key itself if it's whitelisted,
+    /// otherwise the owner of the not expired key from `FeederKeys`
+    pub fn feeder_of(key: &T::AccountId) -> Option<T::AccountId> {
+        if T::Whitelist::in_whitelist(key) {
+            return Some(key.clone());
+        }
+
+        let feeder = <FeederKeyOwners<T>>::get(key)?;
+        let now = frame_system::Pallet::<T>::block_number();
+        let is_valid = <FeederKeys<T>>::get(&feeder, key)
+            .map_or(false, |k| k.expires_at.map_or(true, |e| now < e));
+        (is_valid && T::Whitelist::in_whitelist(&feeder)).then(|| feeder)
+    }
+
+    /// Adds `key` to `feeder` keys, removing expired ones
+    fn do_add_feeder_key(feeder: &T::AccountId, key: T::AccountId) -> DispatchResult {
+        ensure!(
+            *feeder != key
+                && !<FeederKeyOwners<T>>::contains_key(&key)
+                && !T::Whitelist::in_whitelist(&key),
+            Error::<T>::FeederKeyInUse
+        );
+
+        let now = frame_system::Pallet::<T>::block_number();
+        let (expired, valid): (Vec<_>, Vec<_>) = <FeederKeys<T>>::iter_prefix(feeder)
+            .partition(|(_, k)| k.expires_at.map_or(false, |e| e <= now));
+        for (expired_key, _) in expired {
+            <FeederKeys<T>>::remove(feeder, &expired_key);
+            <FeederKeyOwners<T>>::remove(&expired_key);
+        }
+        ensure!(
+            (valid.len() as u32) < T::MaxFeederKeys::get(),
+            Error::<T>::TooManyFeederKeys
+        );
+
+        <FeederKeys<T>>::insert(feeder, &key, FeederKey { expires_at: None });
+        <FeederKeyOwners<T>>::insert(&key, feeder);
+        Ok(())
+    }
+
     /// Validates all the parameters
     fn validate_params(
         who: T::AccountId,
@@ -1222,7 +1974,9 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Calculate a median over **sorted** price points
-    fn calc_median_price(data_points: &Vec<DataPoint<T::AccountId, T::BlockNumber>>) -> FixedI64 {
+    fn calc_plain_median_price(
+        data_points: &Vec<DataPoint<T::AccountId, T::BlockNumber>>,
+    ) -> FixedI64 {
         let len = data_points.len();
         let new_price = if len % 2 == 0 {
             (data_points[len / 2 - 1].price + data_points[len / 2].price)
@@ -1234,8 +1988,92 @@ impl<T: Config> Pallet<T> {
         new_price
     }
 
+    /// Calculate a weighted median over **sorted** price points. Equals to the plain
+    /// median when all feeders have the same weight, falls back to the plain median
+    /// when all feeders are muted or excluded
+    fn calc_median_price(data_points: &Vec<DataPoint<T::AccountId, T::BlockNumber>>) -> FixedI64 {
+        let weights: Vec<u64> = data_points
+            .iter()
+            .map(|dp| Self::feeder_weight(&dp.account_id) as u64)
+            .collect();
+        let total: u64 = weights.iter().sum();
+        if total == 0 {
+            return Self::calc_plain_median_price(data_points);
+        }
+
+        let mut cumulative = 0_u64;
+        for (idx, weight) in weights.iter().enumerate() {
+            cumulative += weight;
+            if cumulative * 2 > total {
+                return data_points[idx].price;
+            }
+            if cumulative * 2 == total {
+                // exactly a half of the weight is below, average with the next weighted price
+                let next_price = data_points[idx + 1..]
+                    .iter()
+                    .zip(&weights[idx + 1..])
+                    .find(|(_, w)| **w > 0)
+                    .map_or(data_points[idx].price, |(dp, _)| dp.price);
+                return (data_points[idx].price + next_price) / (FixedI64::one() + FixedI64::one());
+            }
+        }
+
+        data_points[data_points.len() - 1].price
+    }
+
+    /// Weight of `who` prices in the median, zero for excluded feeders
+    fn feeder_weight(who: &T::AccountId) -> u32 {
+        if <FeederReliability<T>>::get(who).excluded {
+            0
+        } else {
+            <FeederWeights<T>>::get(who)
+        }
+    }
+
+    /// Checks `price` of `who` against the median of other feeders `data_points`
+    /// and excludes or reinstates the feeder according to `OutlierRejection`
+    fn update_feeder_reliability(
+        who: &T::AccountId,
+        price: FixedI64,
+        data_points: &Vec<DataPoint<T::AccountId, T::BlockNumber>>,
+    ) {
+        let params = match <OutlierRejection<T>>::get() {
+            Some(params) => params,
+            None => return,
+        };
+        let others: Vec<_> = data_points
+            .iter()
+            .filter(|dp| dp.account_id != *who)
+            .cloned()
+            .collect();
+        if others.is_empty() {
+            return;
+        }
+
+        let reference = Self::calc_median_price(&others);
+        let deviated = Self::is_price_jump(reference, price, params.band);
+        let window_mask = u32::MAX >> (u32::BITS - params.window);
+
+        let mut stats = <FeederReliability<T>>::get(who);
+        stats.rounds = stats.rounds.saturating_add(1);
+        if deviated {
+            stats.deviations = stats.deviations.saturating_add(1);
+        }
+        stats.recent_deviations = ((stats.recent_deviations << 1) | deviated as u32) & window_mask;
+
+        let excluded = stats.recent_deviations.count_ones() >= params.max_deviations;
+        if excluded && !stats.excluded {
+            Self::deposit_event(Event::FeederExcluded(who.clone()));
+        } else if !excluded && stats.excluded {
+            Self::deposit_event(Event::FeederReinstated(who.clone()));
+        }
+        stats.excluded = excluded;
+        <FeederReliability<T>>::insert(who, stats);
+    }
+
     /// Remove prices from `who` and recalc median price for each asset
     pub fn filter_prices_from(who: &T::AccountId) {
+        <FeederReliability<T>>::remove(who);
         T::AssetGetter::get_assets().iter().for_each(|asset| {
             <PricePoints<T>>::mutate_exists(asset, |maybe_price_point| {
                 match maybe_price_point.as_mut() {
@@ -1261,6 +2099,77 @@ impl<T: Config> Pallet<T> {
         });
     }
 
+    /// Whether `price` differs from `reference` by more than `max_jump`
+    fn is_price_jump(reference: FixedI64, price: FixedI64, max_jump: Permill) -> bool {
+        if reference.is_zero() {
+            return false;
+        }
+        let jump = (price - reference).saturating_abs() / reference;
+        jump > FixedI64::from(max_jump)
+    }
+
+    /// Circuit breaker: marks `new_price` suspect if it jumped over `MaxPriceJump` from
+    /// `old_price`, otherwise counts `who` as a confirmation of the current suspect price
+    fn check_price_jump(
+        who: &T::AccountId,
+        asset: Asset,
+        price: FixedI64,
+        old_price: Option<FixedI64>,
+        new_price: FixedI64,
+    ) {
+        let max_jump = match <MaxPriceJump<T>>::get(asset) {
+            Some(max_jump) => max_jump,
+            None => return,
+        };
+        let current_block = frame_system::Pallet::<T>::block_number();
+
+        <SuspectPrices<T>>::mutate_exists(asset, |maybe_suspect| match maybe_suspect {
+            Some(suspect) if current_block <= suspect.until => {
+                // the feeder confirms the jump if its own price jumped the same way
+                let confirms = Self::is_price_jump(suspect.reference_price, price, max_jump)
+                    && (price > suspect.reference_price)
+                        == (suspect.price > suspect.reference_price);
+                if !confirms || suspect.confirmations.contains(who) {
+                    return;
+                }
+                suspect.confirmations.push(who.clone());
+                if suspect.confirmations.len() as u32 >= T::SuspectPriceConfirmations::get() {
+                    Self::deposit_event(Event::SuspectPriceAccepted(asset, suspect.price));
+                    *maybe_suspect = None;
+                }
+            }
+            _ => {
+                *maybe_suspect = None;
+                if let Some(old_price) = old_price {
+                    if Self::is_price_jump(old_price, new_price, max_jump) {
+                        let until = current_block + T::SuspectPricePeriod::get();
+                        log::warn!(
+                            target: "eq_oracle",
+                            "{}:{}. Price jump over the limit. Asset: {:?}, reference price: {:?}, \
+                            price: {:?}, max jump: {:?}, suspect until: {:?}",
+                            file!(),
+                            line!(),
+                            str_asset!(asset),
+                            old_price,
+                            new_price,
+                            max_jump,
+                            until
+                        );
+                        *maybe_suspect = Some(SuspectPrice {
+                            reference_price: old_price,
+                            price: new_price,
+                            until,
+                            confirmations: vec![who.clone()],
+                        });
+                        Self::deposit_event(Event::PriceSuspect(
+                            asset, old_price, new_price, until,
+                        ));
+                    }
+                }
+            }
+        });
+    }
+
     fn remove_asset() -> DispatchResult {
         let mut assets_to_remove = eq_assets::AssetsToRemove::<T>::get().unwrap_or(Vec::new());
 
@@ -1271,14 +2180,19 @@ impl<T: Config> Pallet<T> {
 
             if balances_removed {
                 PricePoints::<T>::remove(asset_to_remove);
+                MaxPriceJump::<T>::remove(asset_to_remove);
+                LpPriceAdapters::<T>::remove(asset_to_remove);
+                FinancialRecalcFailures::<T>::remove(asset_to_remove);
+                SuspectPrices::<T>::remove(asset_to_remove);
                 T::FinancialAssetRemover::remove_asset(asset_to_remove);
                 T::LendingAssetRemoval::remove_from_aggregates_and_rewards(asset_to_remove);
                 T::AggregatesAssetRemover::remove_asset(asset_to_remove);
-                let _ = T::FinancialSystemTrait::recalc_inner();
+                Self::enqueue_financial_recalc(&[]);
 
                 let mut assets = T::AssetGetter::get_assets_data_with_usd();
                 assets.retain(|asset| asset.id != *asset_to_remove);
                 eq_assets::Assets::<T>::put(assets);
+                eq_assets::RiskTiers::<T>::remove(asset_to_remove);
             }
 
             !balances_removed
@@ -1346,15 +2260,22 @@ impl<T: Config> PriceGetter for Pallet<T> {
         );
         Ok(price)
     }
+
+    fn is_price_suspect(asset: &Asset) -> bool {
+        let current_block = frame_system::Pallet::<T>::block_number();
+        <SuspectPrices<T>>::get(asset).map_or(false, |suspect| current_block <= suspect.until)
+    }
 }
 
 impl<T: Config> PriceSetter<T::AccountId> for Pallet<T> {
     /// The actual implementation of updating an asset price value for the current timestamp
     fn set_price(who: T::AccountId, asset: Asset, price: FixedI64) -> DispatchResultWithPostInfo {
         let mut new_price = price;
+        let mut old_price = None;
         // mutate a price point in the storage by the asset
         <PricePoints<T>>::mutate(&asset, |maybe_price_point| {
             let mut price_point = maybe_price_point.clone().unwrap_or_default();
+            old_price = maybe_price_point.as_ref().map(|p| p.price);
             let current_block = frame_system::Pallet::<T>::block_number();
             let current_time = <T as pallet::Config>::UnixTime::now().as_secs(); // always same within block
             if price_point.block_number == current_block {
@@ -1393,6 +2314,7 @@ impl<T: Config> PriceSetter<T::AccountId> for Pallet<T> {
 
             // calculate a median over price points for the moment
             actual_data_points.sort_by(|a, b| a.price.cmp(&b.price));
+            Self::update_feeder_reliability(&who, price, &actual_data_points);
 
             new_price = Self::calc_median_price(&actual_data_points);
             price_point.price = new_price;
@@ -1407,6 +2329,7 @@ impl<T: Config> PriceSetter<T::AccountId> for Pallet<T> {
             *maybe_price_point = Some(price_point);
             Ok(().into())
         })?;
+        Self::check_price_jump(&who, asset, price, old_price, new_price);
         T::OnPriceSet::on_price_set(asset.clone(), fixedi64_to_i64f64(price))?;
         Self::deposit_event(Event::NewPrice(asset, price, new_price, who));
 
@@ -0,0 +1,69 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Pluggable pricing of LP tokens
+
+use eq_primitives::asset::Asset;
+use impl_trait_for_tuples::impl_for_tuples;
+use sp_arithmetic::FixedI64;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+/// Identifier of an adapter registered for an asset in `LpPriceAdapters`
+pub type LpPriceAdapterId = u32;
+
+/// Prices calculated by an adapter
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LpPrice {
+    /// Price of the priced asset
+    pub price: FixedI64,
+    /// Prices of other assets calculated along the way, e.g. xbase token of a yield pool
+    pub related: Vec<(Asset, FixedI64)>,
+}
+
+/// Custom pricing logic of LP tokens. Implemented for tuples, so the runtime can plug
+/// several adapters, each handling its own ids
+pub trait LpPriceAdapter {
+    /// Calculates `asset` price with adapter `id`. Returns `None` if the implementation
+    /// doesn't handle `id`
+    fn lp_price(id: LpPriceAdapterId, asset: Asset) -> Option<Result<LpPrice, DispatchError>>;
+
+    /// Returns true if the implementation handles adapter `id`
+    fn is_known(id: LpPriceAdapterId) -> bool;
+}
+
+#[impl_for_tuples(5)]
+impl LpPriceAdapter for Tuple {
+    fn lp_price(id: LpPriceAdapterId, asset: Asset) -> Option<Result<LpPrice, DispatchError>> {
+        for_tuples!( #(
+            if let Some(result) = Tuple::lp_price(id, asset) {
+                return Some(result);
+            }
+        )* );
+        None
+    }
+
+    fn is_known(id: LpPriceAdapterId) -> bool {
+        for_tuples!( #(
+            if Tuple::is_known(id) {
+                return true;
+            }
+        )* );
+        false
+    }
+}
@@ -260,14 +260,26 @@ impl eq_assets::Config for Test {
     type WeightInfo = ();
 }
 
+impl FinancialMock {
+    fn recalc_result(asset: Option<Asset>) -> Result<(), DispatchError> {
+        if FAILING_RECALCS.with(|v| v.borrow().contains(&asset)) {
+            Err(DispatchError::Other("Recalculation failed"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
 impl FinancialSystemTrait for FinancialMock {
     type Asset = Asset;
     type AccountId = AccountId;
     fn recalc_inner() -> Result<(), DispatchError> {
-        Ok(())
+        FINANCIAL_RECALCS.with(|v| v.borrow_mut().push(None));
+        Self::recalc_result(None)
     }
-    fn recalc_asset_inner(_asset: Self::Asset) -> Result<(), DispatchError> {
-        Ok(())
+    fn recalc_asset_inner(asset: Self::Asset) -> Result<(), DispatchError> {
+        FINANCIAL_RECALCS.with(|v| v.borrow_mut().push(Some(asset)));
+        Self::recalc_result(Some(asset))
     }
     fn recalc_portfolio_inner(
         _account_id: Self::AccountId,
@@ -281,6 +293,32 @@ parameter_types! {
     pub const LpPriceBlockTimeout: u64 = 10u64;
     pub const UnsignedLifetimeInBlocks: u32 = 5;
     pub const FinancialRecalcPeriodBlocks: u64  = (1000 * 60 * 60 * 4) as u64 / 6000;
+    pub const SuspectPricePeriod: u64 = 10;
+    pub const SuspectPriceConfirmations: u32 = 2;
+    pub const FeederKeyOverlap: u64 = 10;
+    pub const MaxFeederKeys: u32 = 2;
+    pub const MaxFinancialRecalcFailures: u32 = 2;
+    pub const MaxPricesPerBatch: u32 = 4;
+    pub const MaxPriceSubmissionsPerBlock: u32 = 32;
+}
+
+pub const LP_PRICE_ADAPTER: LpPriceAdapterId = 1;
+
+/// Prices any LP token at 42 and DOT at 7
+pub struct LpPriceAdapterMock;
+impl LpPriceAdapter for LpPriceAdapterMock {
+    fn lp_price(id: LpPriceAdapterId, _asset: Asset) -> Option<Result<LpPrice, DispatchError>> {
+        Self::is_known(id).then(|| {
+            Ok(LpPrice {
+                price: FixedI64::saturating_from_integer(42),
+                related: vec![(asset::DOT, FixedI64::saturating_from_integer(7))],
+            })
+        })
+    }
+
+    fn is_known(id: LpPriceAdapterId) -> bool {
+        id == LP_PRICE_ADAPTER
+    }
 }
 
 pub struct XbasePriceMock;
@@ -312,6 +350,10 @@ impl eq_primitives::xdot_pool::XBasePrice<Asset, Balance, FixedI64> for XbasePri
 thread_local! {
     pub static USER_GROUPS: RefCell<Vec<(UserGroup,AccountId)>>  = Default::default();
     pub static BALANCES: RefCell<HashMap<(AccountId, Asset), substrate_fixed::types::I64F64>> = RefCell::new(HashMap::new());
+    /// Recalculations invoked in `FinancialMock`, `None` for common metrics
+    pub static FINANCIAL_RECALCS: RefCell<Vec<Option<Asset>>> = Default::default();
+    /// Recalculations failing in `FinancialMock`
+    pub static FAILING_RECALCS: RefCell<Vec<Option<Asset>>> = Default::default();
 }
 
 pub struct AggregatesMock;
@@ -431,6 +473,15 @@ impl Config for Test {
     type Aggregates = AggregatesMock;
     type AggregatesAssetRemover = ();
     type LendingAssetRemoval = ();
+    type PriceCircuitBreakerOrigin = EnsureRoot<AccountId>;
+    type SuspectPricePeriod = SuspectPricePeriod;
+    type SuspectPriceConfirmations = SuspectPriceConfirmations;
+    type FeederKeyOverlap = FeederKeyOverlap;
+    type MaxFeederKeys = MaxFeederKeys;
+    type LpPriceAdapter = LpPriceAdapterMock;
+    type MaxFinancialRecalcFailures = MaxFinancialRecalcFailures;
+    type MaxPricesPerBatch = MaxPricesPerBatch;
+    type MaxPriceSubmissionsPerBlock = MaxPriceSubmissionsPerBlock;
 }
 
 pub type ModuleOracle = Pallet<Test>;
@@ -25,7 +25,8 @@ use eq_primitives::asset;
 
 use crate::{
     mock::{
-        new_test_ext, EqAssets, ModuleOracle, ModuleSystem, ModuleTimestamp, ModuleWhitelist, Test,
+        new_test_ext, EqAssets, FeederKeyOverlap, MaxPriceSubmissionsPerBlock, MaxPricesPerBatch,
+        ModuleOracle, ModuleSystem, ModuleTimestamp, ModuleWhitelist, SuspectPricePeriod, Test,
     },
     price_source::WithUrl,
 };
@@ -54,6 +55,32 @@ fn set_price(
     )
 }
 
+fn set_prices(
+    account: Sign,
+    prices: &[(Asset, f64)],
+    block_number: u64,
+) -> DispatchResultWithPostInfo {
+    let dummy_signature = sp_core::sr25519::Signature([0u8; 64]);
+    let payload = PricesPayload {
+        public: account,
+        prices: prices
+            .iter()
+            .map(|&(asset, price)| {
+                (
+                    asset,
+                    FixedI64::from_inner((price * (FixedI64::accuracy() as f64)) as i64),
+                )
+            })
+            .collect(),
+        block_number,
+    };
+    ModuleOracle::set_prices(
+        frame_system::RawOrigin::None.into(),
+        payload,
+        dummy_signature,
+    )
+}
+
 fn set_price_ok(account: Sign, asset: Asset, price: f64, block_number: u64) {
     assert_ok!(set_price(account, asset, price, block_number));
 }
@@ -659,3 +686,579 @@ fn url_symbol_case() {
         "https://api.kraken.com/0/public/Ticker?pair=XXBTZUSD"
     );
 }
+
+#[test]
+fn financial_recalc_is_deferred_to_on_idle() {
+    new_test_ext().execute_with(|| {
+        use crate::mock::{FinancialRecalcPeriodBlocks, FINANCIAL_RECALCS};
+        use frame_support::traits::{OnIdle, OnInitialize};
+
+        let recalcs = || FINANCIAL_RECALCS.with(|v| v.borrow_mut().split_off(0));
+        recalcs();
+
+        ModuleOracle::on_initialize(FinancialRecalcPeriodBlocks::get());
+        assert_eq!(recalcs(), vec![]);
+        assert_eq!(
+            ModuleOracle::financial_recalc_progress(),
+            Some(FinancialRecalcStage::Metrics)
+        );
+
+        ModuleOracle::enqueue_financial_recalc(&[asset::BTC, asset::ETH]);
+        ModuleOracle::enqueue_financial_recalc(&[asset::ETH, asset::DOT]);
+        assert_eq!(
+            ModuleOracle::financial_recalc_progress(),
+            Some(FinancialRecalcStage::Assets(vec![
+                asset::BTC,
+                asset::ETH,
+                asset::DOT
+            ]))
+        );
+
+        ModuleOracle::on_idle(1, Weight::MAX);
+        assert_eq!(
+            recalcs(),
+            vec![Some(asset::DOT), Some(asset::ETH), Some(asset::BTC)]
+        );
+        assert_eq!(
+            ModuleOracle::financial_recalc_progress(),
+            Some(FinancialRecalcStage::Metrics)
+        );
+
+        ModuleOracle::on_idle(2, Weight::MAX);
+        assert_eq!(recalcs(), vec![None]);
+        assert_eq!(ModuleOracle::financial_recalc_progress(), None);
+
+        ModuleOracle::on_idle(3, Weight::MAX);
+        assert_eq!(recalcs(), vec![]);
+    });
+}
+
+#[test]
+fn stalled_metrics_recalc_is_forced_in_on_initialize() {
+    new_test_ext().execute_with(|| {
+        use crate::mock::{FinancialRecalcPeriodBlocks, FINANCIAL_RECALCS};
+        use frame_support::traits::{OnIdle, OnInitialize};
+
+        let recalcs = || FINANCIAL_RECALCS.with(|v| v.borrow_mut().split_off(0));
+        recalcs();
+        ModuleSystem::set_block_number(1);
+        ModuleOracle::enqueue_financial_recalc(&[]);
+
+        // full blocks leave no weight for common metrics
+        for n in 1..FinancialRecalcPeriodBlocks::get() {
+            ModuleSystem::set_block_number(n);
+            ModuleOracle::on_initialize(n);
+            ModuleOracle::on_idle(n, Weight::zero());
+        }
+        assert_eq!(recalcs(), vec![]);
+        assert_eq!(
+            ModuleOracle::financial_metrics_recalc_stall(),
+            FinancialRecalcPeriodBlocks::get() as u32 - 1
+        );
+
+        let n = FinancialRecalcPeriodBlocks::get() + 1;
+        ModuleSystem::set_block_number(n);
+        ModuleOracle::on_initialize(n);
+        assert_eq!(recalcs(), vec![None]);
+        assert_eq!(ModuleOracle::financial_recalc_progress(), None);
+        assert_eq!(ModuleOracle::financial_metrics_recalc_stall(), 0);
+    });
+}
+
+#[test]
+fn failed_financial_recalc_is_retried() {
+    new_test_ext().execute_with(|| {
+        use crate::mock::{FAILING_RECALCS, FINANCIAL_RECALCS};
+        use frame_support::traits::OnIdle;
+
+        let recalcs = || FINANCIAL_RECALCS.with(|v| v.borrow_mut().split_off(0));
+        let failing_events = || {
+            ModuleSystem::events()
+                .into_iter()
+                .filter_map(|r| match r.event {
+                    crate::mock::RuntimeEvent::EqOracle(Event::FinancialRecalcFailing(
+                        asset,
+                        failures,
+                    )) => Some((asset, failures)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        recalcs();
+        ModuleSystem::set_block_number(1);
+        FAILING_RECALCS.with(|v| *v.borrow_mut() = vec![Some(asset::ETH), None]);
+
+        ModuleOracle::enqueue_financial_recalc(&[asset::BTC, asset::ETH]);
+        ModuleOracle::on_idle(1, Weight::MAX);
+        ModuleOracle::on_idle(2, Weight::MAX);
+        assert_eq!(recalcs(), vec![Some(asset::ETH), Some(asset::BTC), None]);
+        assert_eq!(ModuleOracle::financial_recalc_failures(asset::ETH), 1);
+        assert!(!FinancialRecalcFailures::<Test>::contains_key(asset::BTC));
+        assert_eq!(ModuleOracle::financial_metrics_recalc_failures(), 1);
+        assert_eq!(failing_events(), vec![]);
+
+        // failed recalculations are scheduled again
+        ModuleOracle::on_idle(3, Weight::MAX);
+        assert_eq!(
+            ModuleOracle::financial_recalc_progress(),
+            Some(FinancialRecalcStage::Assets(vec![asset::ETH]))
+        );
+        ModuleOracle::on_idle(4, Weight::MAX);
+        ModuleOracle::on_idle(5, Weight::MAX);
+        assert_eq!(recalcs(), vec![Some(asset::ETH), None]);
+        assert_eq!(ModuleOracle::financial_recalc_failures(asset::ETH), 2);
+        assert_eq!(failing_events(), vec![(Some(asset::ETH), 2), (None, 2)]);
+
+        FAILING_RECALCS.with(|v| v.borrow_mut().clear());
+        ModuleOracle::on_idle(6, Weight::MAX);
+        ModuleOracle::on_idle(7, Weight::MAX);
+        ModuleOracle::on_idle(8, Weight::MAX);
+        assert_eq!(recalcs(), vec![Some(asset::ETH), None]);
+        assert!(!FinancialRecalcFailures::<Test>::contains_key(asset::ETH));
+        assert_eq!(ModuleOracle::financial_metrics_recalc_failures(), 0);
+
+        ModuleOracle::on_idle(9, Weight::MAX);
+        assert_eq!(recalcs(), vec![]);
+        assert_eq!(ModuleOracle::financial_recalc_progress(), None);
+    });
+}
+
+#[test]
+fn price_jump_marks_price_suspect() {
+    new_test_ext().execute_with(|| {
+        let account_id_1 = Sign { 0: [1; 32] };
+        let account_id_2 = Sign { 0: [2; 32] };
+        let account_id_3 = Sign { 0: [3; 32] };
+        for account in [account_id_1, account_id_2, account_id_3] {
+            assert_ok!(ModuleWhitelist::add_to_whitelist(
+                frame_system::RawOrigin::Root.into(),
+                account
+            ));
+        }
+        assert_err!(
+            ModuleOracle::set_max_price_jump(
+                frame_system::RawOrigin::Signed(account_id_1).into(),
+                asset::BTC,
+                Some(Permill::from_percent(20))
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        for asset in [asset::BTC, asset::ETH] {
+            assert_ok!(ModuleOracle::set_max_price_jump(
+                frame_system::RawOrigin::Root.into(),
+                asset,
+                Some(Permill::from_percent(20))
+            ));
+        }
+
+        ModuleSystem::set_block_number(1);
+        set_price_ok(account_id_1, asset::BTC, 100., 1);
+        set_price_ok(account_id_2, asset::BTC, 100., 1);
+        assert!(!ModuleOracle::is_price_suspect(&asset::BTC));
+
+        // median jumps from 100 to 150
+        ModuleSystem::set_block_number(2);
+        set_price_ok(account_id_1, asset::BTC, 200., 2);
+        assert!(ModuleOracle::is_price_suspect(&asset::BTC));
+        let suspect = ModuleOracle::suspect_prices(asset::BTC).unwrap();
+        assert_eq!(
+            suspect.reference_price,
+            FixedI64::saturating_from_integer(100)
+        );
+        assert_eq!(suspect.price, FixedI64::saturating_from_integer(150));
+        assert_eq!(suspect.until, 2 + SuspectPricePeriod::get());
+
+        // price within the limit doesn't confirm the jump
+        set_price_ok(account_id_3, asset::BTC, 110., 2);
+        assert!(ModuleOracle::is_price_suspect(&asset::BTC));
+
+        ModuleSystem::set_block_number(3);
+        set_price_ok(account_id_2, asset::BTC, 210., 3);
+        assert!(!ModuleOracle::is_price_suspect(&asset::BTC));
+        assert_eq!(ModuleOracle::suspect_prices(asset::BTC), None);
+
+        // governance override
+        set_price_ok(account_id_1, asset::ETH, 100., 3);
+        ModuleSystem::set_block_number(4);
+        set_price_ok(account_id_1, asset::ETH, 200., 4);
+        assert!(ModuleOracle::is_price_suspect(&asset::ETH));
+        assert_ok!(ModuleOracle::override_suspect_price(
+            frame_system::RawOrigin::Root.into(),
+            asset::ETH
+        ));
+        assert!(!ModuleOracle::is_price_suspect(&asset::ETH));
+        assert_err!(
+            ModuleOracle::override_suspect_price(frame_system::RawOrigin::Root.into(), asset::ETH),
+            Error::<Test>::PriceIsNotSuspect
+        );
+
+        // suspect price expires
+        ModuleSystem::set_block_number(5);
+        set_price_ok(account_id_1, asset::ETH, 500., 5);
+        assert!(ModuleOracle::is_price_suspect(&asset::ETH));
+        ModuleSystem::set_block_number(6 + SuspectPricePeriod::get());
+        assert!(!ModuleOracle::is_price_suspect(&asset::ETH));
+    });
+}
+
+#[test]
+fn weighted_median_excludes_deviating_feeder() {
+    new_test_ext().execute_with(|| {
+        let account_id_1 = Sign { 0: [1; 32] };
+        let account_id_2 = Sign { 0: [2; 32] };
+        let account_id_3 = Sign { 0: [3; 32] };
+        for account in [account_id_1, account_id_2, account_id_3] {
+            assert_ok!(ModuleWhitelist::add_to_whitelist(
+                frame_system::RawOrigin::Root.into(),
+                account
+            ));
+        }
+        assert_err!(
+            ModuleOracle::set_feeder_weight(
+                frame_system::RawOrigin::Signed(account_id_1).into(),
+                account_id_3,
+                Some(3)
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(ModuleOracle::set_feeder_weight(
+            frame_system::RawOrigin::Root.into(),
+            account_id_3,
+            Some(3)
+        ));
+        assert_err!(
+            ModuleOracle::set_outlier_rejection(
+                frame_system::RawOrigin::Root.into(),
+                Some(OutlierRejectionParams {
+                    band: Permill::from_percent(20),
+                    max_deviations: 4,
+                    window: 3,
+                })
+            ),
+            Error::<Test>::InvalidOutlierRejectionParams
+        );
+        assert_ok!(ModuleOracle::set_outlier_rejection(
+            frame_system::RawOrigin::Root.into(),
+            Some(OutlierRejectionParams {
+                band: Permill::from_percent(20),
+                max_deviations: 2,
+                window: 3,
+            })
+        ));
+        let price = || ModuleOracle::price_points(asset::BTC).unwrap().price;
+
+        ModuleSystem::set_block_number(1);
+        set_price_ok(account_id_1, asset::BTC, 100., 1);
+        set_price_ok(account_id_2, asset::BTC, 110., 1);
+        set_price_ok(account_id_3, asset::BTC, 200., 1);
+        // 3 of 5 weight is at 200
+        assert_eq!(price(), FixedI64::saturating_from_integer(200));
+        let stats = ModuleOracle::feeder_reliability(account_id_3);
+        assert_eq!((stats.rounds, stats.deviations), (1, 1));
+        assert!(!stats.excluded);
+
+        ModuleSystem::set_block_number(2);
+        set_price_ok(account_id_3, asset::BTC, 200., 2);
+        assert!(ModuleOracle::feeder_reliability(account_id_3).excluded);
+        assert_eq!(price(), FixedI64::saturating_from_integer(105));
+
+        ModuleSystem::set_block_number(3);
+        set_price_ok(account_id_3, asset::BTC, 105., 3);
+        assert!(ModuleOracle::feeder_reliability(account_id_3).excluded);
+
+        ModuleSystem::set_block_number(4);
+        set_price_ok(account_id_3, asset::BTC, 106., 4);
+        let stats = ModuleOracle::feeder_reliability(account_id_3);
+        assert_eq!((stats.rounds, stats.deviations), (4, 2));
+        assert!(!stats.excluded);
+        assert_eq!(price(), FixedI64::saturating_from_integer(106));
+
+        assert_ok!(ModuleOracle::set_outlier_rejection(
+            frame_system::RawOrigin::Root.into(),
+            None
+        ));
+        assert_eq!(
+            ModuleOracle::feeder_reliability(account_id_3),
+            FeederStats::default()
+        );
+    });
+}
+
+#[test]
+fn feeder_keys_rotation() {
+    new_test_ext().execute_with(|| {
+        let feeder = Sign { 0: [1; 32] };
+        let key_1 = Sign { 0: [2; 32] };
+        let key_2 = Sign { 0: [3; 32] };
+        let key_3 = Sign { 0: [4; 32] };
+        let signed = |who| frame_system::RawOrigin::Signed(who).into();
+
+        ModuleSystem::set_block_number(1);
+        assert_err!(
+            ModuleOracle::add_feeder_key(signed(feeder), key_1),
+            Error::<Test>::NotAllowedToSubmitPrice
+        );
+        assert_ok!(ModuleWhitelist::add_to_whitelist(
+            frame_system::RawOrigin::Root.into(),
+            feeder
+        ));
+        assert_ok!(ModuleOracle::add_feeder_key(signed(feeder), key_1));
+        assert_err!(
+            ModuleOracle::add_feeder_key(signed(feeder), key_1),
+            Error::<Test>::FeederKeyInUse
+        );
+        assert_eq!(ModuleOracle::feeder_of(&key_1), Some(feeder));
+
+        // price of the key is the feeder price
+        set_price_ok(key_1, asset::BTC, 100., 1);
+        assert_eq!(
+            ModuleOracle::price_points(asset::BTC).unwrap().data_points[0].account_id,
+            feeder
+        );
+        check_error(set_price(feeder, asset::BTC, 100., 1), "PriceAlreadyAdded");
+
+        assert_ok!(ModuleOracle::rotate_feeder_key(
+            signed(feeder),
+            key_1,
+            key_2
+        ));
+        let expires_at = 1 + FeederKeyOverlap::get();
+        assert_eq!(
+            ModuleOracle::feeder_keys(feeder, key_1),
+            Some(FeederKey {
+                expires_at: Some(expires_at)
+            })
+        );
+        assert_err!(
+            ModuleOracle::add_feeder_key(signed(feeder), key_3),
+            Error::<Test>::TooManyFeederKeys
+        );
+
+        // both keys are valid during the overlap
+        ModuleSystem::set_block_number(2);
+        set_price_ok(key_2, asset::BTC, 100., 2);
+        assert_eq!(ModuleOracle::feeder_of(&key_1), Some(feeder));
+
+        ModuleSystem::set_block_number(expires_at);
+        assert_eq!(ModuleOracle::feeder_of(&key_1), None);
+        check_error(
+            set_price(key_1, asset::BTC, 100., expires_at),
+            "NotAllowedToSubmitPrice",
+        );
+
+        // expired key is dropped when a new one is added
+        assert_ok!(ModuleOracle::add_feeder_key(signed(feeder), key_3));
+        assert_eq!(ModuleOracle::feeder_keys(feeder, key_1), None);
+        assert_eq!(ModuleOracle::feeder_key_owner(key_1), None);
+
+        assert_ok!(ModuleOracle::remove_feeder_key(signed(feeder), key_3));
+        assert_eq!(ModuleOracle::feeder_of(&key_3), None);
+        assert_err!(
+            ModuleOracle::remove_feeder_key(signed(feeder), key_3),
+            Error::<Test>::FeederKeyNotFound
+        );
+
+        assert_ok!(ModuleWhitelist::remove_from_whitelist(
+            frame_system::RawOrigin::Root.into(),
+            feeder
+        ));
+        assert_eq!(ModuleOracle::feeder_of(&key_2), None);
+    });
+}
+
+#[test]
+fn lp_token_priced_by_registered_adapter() {
+    new_test_ext().execute_with(|| {
+        use crate::mock::{LpPriceBlockTimeout, LP_PRICE_ADAPTER};
+        use eq_primitives::asset::{AmmPool, AssetXcmData};
+        use frame_support::traits::OnInitialize;
+        use sp_runtime::Percent;
+
+        assert_err!(
+            ModuleOracle::set_lp_price_adapter(
+                frame_system::RawOrigin::Root.into(),
+                asset::BTC,
+                Some(LP_PRICE_ADAPTER)
+            ),
+            Error::<Test>::LpAssetExpected
+        );
+
+        assert_ok!(EqAssets::add_asset(
+            frame_system::RawOrigin::Root.into(),
+            b"lpt".to_vec(),
+            0,
+            0,
+            Permill::zero(),
+            Permill::zero(),
+            AssetXcmData::None,
+            Permill::zero(),
+            0,
+            AssetType::Lp(AmmPool::Curve(0)),
+            false,
+            Percent::zero(),
+            Permill::one(),
+            vec![FixedI64::one()],
+        ));
+        let lp_asset = Asset::from_bytes(b"lpt").unwrap();
+
+        assert_err!(
+            ModuleOracle::set_lp_price_adapter(
+                frame_system::RawOrigin::Root.into(),
+                lp_asset,
+                Some(LP_PRICE_ADAPTER + 1)
+            ),
+            Error::<Test>::UnknownLpPriceAdapter
+        );
+        assert_ok!(ModuleOracle::set_lp_price_adapter(
+            frame_system::RawOrigin::Root.into(),
+            lp_asset,
+            Some(LP_PRICE_ADAPTER)
+        ));
+        assert_eq!(
+            ModuleOracle::lp_price_adapter(lp_asset),
+            Some(LP_PRICE_ADAPTER)
+        );
+
+        ModuleOracle::on_initialize(LpPriceBlockTimeout::get());
+        let price = |asset| ModuleOracle::price_points(asset).unwrap().price;
+        assert_eq!(price(lp_asset), FixedI64::saturating_from_integer(42));
+        assert_eq!(price(asset::DOT), FixedI64::saturating_from_integer(7));
+
+        assert_ok!(ModuleOracle::set_lp_price_adapter(
+            frame_system::RawOrigin::Root.into(),
+            lp_asset,
+            None
+        ));
+        assert_eq!(ModuleOracle::lp_price_adapter(lp_asset), None);
+    });
+}
+
+#[test]
+fn set_prices_accepts_valid_prices_of_batch() {
+    new_test_ext().execute_with(|| {
+        let account_id_1 = Sign { 0: [0; 32] };
+        let batch_set_events = || {
+            ModuleSystem::events()
+                .into_iter()
+                .filter_map(|r| match r.event {
+                    crate::mock::RuntimeEvent::EqOracle(Event::PricesBatchSet(
+                        who,
+                        accepted,
+                        rejected,
+                    )) => Some((who, accepted, rejected)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        ModuleTimestamp::set_timestamp(2000);
+        ModuleSystem::set_block_number(1);
+
+        assert_ok!(set_prices(
+            account_id_1,
+            &[(asset::EQ, 1.), (asset::BTC, 2.)],
+            1
+        ));
+        assert_eq!(
+            batch_set_events(),
+            vec![(account_id_1, 0, vec![asset::EQ, asset::BTC])]
+        );
+
+        assert_ok!(ModuleWhitelist::add_to_whitelist(
+            frame_system::RawOrigin::Root.into(),
+            account_id_1
+        ));
+        ModuleSystem::reset_events();
+        assert_ok!(set_prices(
+            account_id_1,
+            &[
+                (asset::EQ, 1.),
+                (asset::EQD, 1.),
+                (asset::DOT, 0.),
+                (asset::BTC, 2.)
+            ],
+            1
+        ));
+        assert_eq!(
+            batch_set_events(),
+            vec![(account_id_1, 2, vec![asset::EQD, asset::DOT])]
+        );
+        check_price(asset::EQ, 1.);
+        check_price(asset::BTC, 2.);
+
+        // the same asset twice in a batch
+        ModuleSystem::set_block_number(2);
+        ModuleSystem::reset_events();
+        assert_ok!(set_prices(
+            account_id_1,
+            &[(asset::EQ, 3.), (asset::EQ, 4.)],
+            2
+        ));
+        assert_eq!(batch_set_events(), vec![(account_id_1, 1, vec![asset::EQ])]);
+        check_price(asset::EQ, 3.);
+    });
+}
+
+#[test]
+fn set_prices_batch_len_is_limited() {
+    new_test_ext().execute_with(|| {
+        let account_id_1 = Sign { 0: [0; 32] };
+        assert_ok!(ModuleWhitelist::add_to_whitelist(
+            frame_system::RawOrigin::Root.into(),
+            account_id_1
+        ));
+
+        assert_err!(
+            set_prices(account_id_1, &[], 0),
+            Error::<Test>::InvalidPricesBatch
+        );
+        let too_many = [
+            (asset::EQ, 1.),
+            (asset::BTC, 1.),
+            (asset::ETH, 1.),
+            (asset::DOT, 1.),
+            (asset::CRV, 1.),
+        ];
+        assert_eq!(too_many.len() as u32, MaxPricesPerBatch::get() + 1);
+        assert_err!(
+            set_prices(account_id_1, &too_many, 0),
+            Error::<Test>::InvalidPricesBatch
+        );
+        assert_ok!(set_prices(
+            account_id_1,
+            &too_many[..MaxPricesPerBatch::get() as usize],
+            0
+        ));
+        check_price(asset::DOT, 1.);
+    });
+}
+
+#[test]
+fn price_submissions_are_limited_per_block() {
+    new_test_ext().execute_with(|| {
+        let account_id_1 = Sign { 0: [0; 32] };
+        assert_ok!(ModuleWhitelist::add_to_whitelist(
+            frame_system::RawOrigin::Root.into(),
+            account_id_1
+        ));
+
+        let block = ModuleSystem::block_number();
+        PriceSubmissions::<Test>::put((block, MaxPriceSubmissionsPerBlock::get() - 1));
+        assert_ok!(set_prices(account_id_1, &[(asset::BTC, 1.)], block));
+        assert_err!(
+            set_prices(account_id_1, &[(asset::ETH, 1.)], block),
+            Error::<Test>::PriorityLaneExhausted
+        );
+        assert_err!(
+            ModuleOracle::set_price(
+                frame_system::RawOrigin::Signed(account_id_1).into(),
+                asset::ETH,
+                FixedI64::one()
+            ),
+            Error::<Test>::PriorityLaneExhausted
+        );
+
+        ModuleSystem::set_block_number(block + 1);
+        assert_ok!(set_prices(account_id_1, &[(asset::DOT, 1.)], block + 1));
+        check_price(asset::DOT, 1.);
+    });
+}
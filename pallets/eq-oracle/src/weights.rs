@@ -24,6 +24,17 @@ use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
     fn set_price(b: u32) -> Weight;
+    fn recalc_asset() -> Weight;
+    fn recalc_metrics(a: u32) -> Weight;
+    fn set_max_price_jump() -> Weight;
+    fn override_suspect_price() -> Weight;
+    fn set_feeder_weight() -> Weight;
+    fn set_outlier_rejection() -> Weight;
+    fn add_feeder_key() -> Weight;
+    fn rotate_feeder_key() -> Weight;
+    fn remove_feeder_key() -> Weight;
+    fn set_lp_price_adapter() -> Weight;
+    fn set_prices(p: u32) -> Weight;
 }
 
 // for tests
@@ -31,4 +42,37 @@ impl crate::WeightInfo for () {
     fn set_price(_b: u32) -> Weight {
         Weight::zero()
     }
+    fn recalc_asset() -> Weight {
+        Weight::zero()
+    }
+    fn recalc_metrics(_a: u32) -> Weight {
+        Weight::zero()
+    }
+    fn set_max_price_jump() -> Weight {
+        Weight::zero()
+    }
+    fn override_suspect_price() -> Weight {
+        Weight::zero()
+    }
+    fn set_feeder_weight() -> Weight {
+        Weight::zero()
+    }
+    fn set_outlier_rejection() -> Weight {
+        Weight::zero()
+    }
+    fn add_feeder_key() -> Weight {
+        Weight::zero()
+    }
+    fn rotate_feeder_key() -> Weight {
+        Weight::zero()
+    }
+    fn remove_feeder_key() -> Weight {
+        Weight::zero()
+    }
+    fn set_lp_price_adapter() -> Weight {
+        Weight::zero()
+    }
+    fn set_prices(_p: u32) -> Weight {
+        Weight::zero()
+    }
 }
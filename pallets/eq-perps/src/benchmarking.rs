@@ -0,0 +1,161 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! # Equilibrium Perps Pallet Benchmarking
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use eq_assets;
+use eq_primitives::{asset, asset::AssetGetter, Aggregates, PriceSetter, UserGroup};
+use eq_utils::ONE_TOKEN;
+use eq_whitelists;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::One;
+
+const SEED: u32 = 0;
+const MAX_MARKETS: u32 = 20;
+
+pub struct Pallet<T: Config>(crate::Pallet<T>);
+
+pub trait Config:
+    eq_whitelists::Config + eq_oracle::Config + eq_assets::Config + eq_balances::Config + crate::Config
+{
+}
+
+fn set_prices<T: Config>() {
+    let price_setter: T::AccountId = account("price_setter", 0, SEED);
+    eq_whitelists::Pallet::<T>::add_to_whitelist(RawOrigin::Root.into(), price_setter.clone())
+        .unwrap();
+    for curr in eq_assets::Pallet::<T>::get_assets_with_usd() {
+        <eq_oracle::Pallet<T> as PriceSetter<T::AccountId>>::set_price(
+            price_setter.clone(),
+            curr,
+            FixedI64::one(),
+        )
+        .unwrap();
+    }
+}
+
+/// Caller with a trader subaccount holding EQD collateral
+fn trader<T: Config>() -> (T::AccountId, T::AccountId) {
+    let who: T::AccountId = account("who", 0, SEED);
+    let subaccount = <T as crate::Config>::SubaccountsManager::create_subaccount_inner(
+        &who,
+        &SubAccType::Trader,
+    )
+    .unwrap();
+    <T as eq_balances::Config>::Aggregates::set_usergroup(&subaccount, UserGroup::Borrowers, true)
+        .unwrap();
+    <eq_balances::Pallet<T> as EqCurrency<_, _>>::make_free_balance_be(
+        &subaccount,
+        asset::EQD,
+        SignedBalance::Positive(
+            (1_000_000 * ONE_TOKEN)
+                .try_into()
+                .map_err(|_| "balance conversion error")
+                .unwrap(),
+        ),
+    );
+    (who, subaccount)
+}
+
+fn amount<T: Config>(tokens: u128) -> <T as crate::Config>::Balance {
+    <T as crate::Config>::Balance::from(tokens * ONE_TOKEN)
+}
+
+benchmarks! {
+    add_market {
+    }: _(RawOrigin::Root, asset::BTC, amount::<T>(1_000))
+    verify {
+        assert!(Markets::<T>::contains_key(asset::BTC));
+    }
+
+    // short position is reversed: settled, reopened and margin is checked
+    trade {
+        set_prices::<T>();
+        let (who, subaccount) = trader::<T>();
+        crate::Pallet::<T>::add_market(RawOrigin::Root.into(), asset::BTC, amount::<T>(1_000))
+            .unwrap();
+        crate::Pallet::<T>::trade(
+            RawOrigin::Signed(who.clone()).into(),
+            asset::BTC,
+            OrderSide::Sell,
+            amount::<T>(1),
+        )
+        .unwrap();
+    }: _(RawOrigin::Signed(who), asset::BTC, OrderSide::Buy, amount::<T>(2))
+    verify {
+        assert!(Positions::<T>::get(&subaccount, asset::BTC).map_or(false, |p| p.size.is_positive()));
+    }
+
+    close_position {
+        set_prices::<T>();
+        let (who, subaccount) = trader::<T>();
+        crate::Pallet::<T>::add_market(RawOrigin::Root.into(), asset::BTC, amount::<T>(1_000))
+            .unwrap();
+        crate::Pallet::<T>::trade(
+            RawOrigin::Signed(who.clone()).into(),
+            asset::BTC,
+            OrderSide::Buy,
+            amount::<T>(1),
+        )
+        .unwrap();
+    }: _(RawOrigin::Signed(who), asset::BTC)
+    verify {
+        assert!(!Positions::<T>::contains_key(&subaccount, asset::BTC));
+    }
+
+    update_funding {
+        let m in 1 .. MAX_MARKETS;
+        set_prices::<T>();
+        for asset in eq_assets::Pallet::<T>::get_assets().into_iter().take(m as usize) {
+            crate::Pallet::<T>::add_market(RawOrigin::Root.into(), asset, amount::<T>(1_000))
+                .unwrap();
+        }
+    }: {
+        crate::Pallet::<T>::update_fundings();
+    }
+
+    set_open_interest_limit {
+        crate::Pallet::<T>::add_market(RawOrigin::Root.into(), asset::BTC, amount::<T>(1_000))
+            .unwrap();
+    }: _(RawOrigin::Root, asset::BTC, amount::<T>(2_000))
+    verify {
+        assert_eq!(OpenInterestLimits::<T>::get(asset::BTC), amount::<T>(2_000));
+    }
+
+    claim_unpaid_pnl {
+        let (who, subaccount) = trader::<T>();
+        UnpaidPnl::<T>::insert(&subaccount, amount::<T>(10));
+        VaultDeficit::<T>::put(amount::<T>(10));
+        <eq_balances::Pallet<T> as EqCurrency<_, _>>::make_free_balance_be(
+            &crate::Pallet::<T>::vault_account(),
+            asset::EQD,
+            SignedBalance::Positive(
+                (1_000 * ONE_TOKEN)
+                    .try_into()
+                    .map_err(|_| "balance conversion error")
+                    .unwrap(),
+            ),
+        );
+    }: _(RawOrigin::Signed(who))
+    verify {
+        assert!(!UnpaidPnl::<T>::contains_key(&subaccount));
+    }
+}
@@ -0,0 +1,736 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! # Equilibrium Perps Pallet
+//!
+//! Perpetual futures on assets traded on eq-dex.
+//!
+//! Positions are held by trader subaccounts, the perps vault is the counterparty of all
+//! of them. The mark price is the mid price of the eq-dex order book kept within
+//! `MaxPriceDeviation` of the oracle index price, or the index price when the book has
+//! no bid or ask. Trades are executed at the worse of the index and the mark prices for
+//! the trader with `TradeSpread` applied, so resting orders moving the book mid can't
+//! improve the price of a trade against the vault. Long and short open interest of each
+//! market is capped by its limit set by root.
+//!
+//! Every `FundingPeriod` the funding rate of each market is computed from the basis
+//! between the mark price and the index price, capped by `MaxFundingRate`. Positive rate
+//! is paid by longs to shorts. Funding accrues to the position entry price and is settled
+//! in EQD together with the position result when the position is reduced or closed.
+//!
+//! Profits the vault can't pay are recorded as unpaid and added to the vault deficit,
+//! so closing positions never depends on the vault balance. Traders claim unpaid profits
+//! once the vault is funded again.
+//!
+//! Open positions are accounted by eq-margin-call as orders filled at the entry price.
+//! A position can't be increased unless the subaccount margin stays good. When the
+//! subaccount is margin called, its positions are closed and settled first, and the
+//! balances are taken by bailsmen as usual.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(warnings)]
+
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode};
+use eq_primitives::{
+    asset::{Asset, EQD},
+    balance::EqCurrency,
+    subaccount::{SubAccType, SubaccountsManager},
+    DerivativePositions, MarginCallManager, MarginState, MarkPriceGetter, OrderChange, OrderSide,
+    PriceGetter, SignedBalance, TransferReason,
+};
+use eq_utils::fixed::eq_fixedu128_from_balance;
+use frame_support::{
+    pallet_prelude::*,
+    traits::{ExistenceRequirement, UnixTime},
+    PalletId,
+};
+use sp_runtime::{
+    traits::{AccountIdConversion, CheckedAdd, CheckedDiv, Saturating, Zero},
+    ArithmeticError, FixedI64, FixedPointNumber,
+};
+use sp_std::prelude::*;
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+/// Perpetual position of a trader subaccount
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct Position<Balance> {
+    /// Positive for long positions, negative for short ones
+    pub size: SignedBalance<Balance>,
+    /// Average price the position was opened at
+    pub entry_price: FixedI64,
+    /// Market funding index accounted in `entry_price`
+    pub funding_index: FixedI64,
+}
+
+impl<Balance> Position<Balance> {
+    /// Entry price with the funding accrued since the position was last changed.
+    /// Funding paid by a long and funding received by a short both raise it.
+    pub fn entry_price_with_funding(&self, funding_index: FixedI64) -> FixedI64 {
+        self.entry_price
+            .saturating_add(funding_index.saturating_sub(self.funding_index))
+    }
+}
+
+/// Total size of long and short positions in a market
+#[derive(
+    Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct OpenInterest<Balance> {
+    pub long: Balance,
+    pub short: Balance,
+}
+
+impl<Balance: CheckedAdd + Saturating + Copy> OpenInterest<Balance> {
+    fn remove_position(&mut self, size: &SignedBalance<Balance>) {
+        match size {
+            SignedBalance::Positive(value) => self.long = self.long.saturating_sub(*value),
+            SignedBalance::Negative(value) => self.short = self.short.saturating_sub(*value),
+        }
+    }
+
+    fn add_position(&mut self, size: &SignedBalance<Balance>) -> Option<()> {
+        match size {
+            SignedBalance::Positive(value) => self.long = self.long.checked_add(value)?,
+            SignedBalance::Negative(value) => self.short = self.short.checked_add(value)?,
+        }
+        Some(())
+    }
+
+    /// Open interest of the side `size` belongs to
+    fn side(&self, size: &SignedBalance<Balance>) -> Balance {
+        match size {
+            SignedBalance::Positive(_) => self.long,
+            SignedBalance::Negative(_) => self.short,
+        }
+    }
+}
+
+/// Funding state of a perpetual market
+#[derive(
+    Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct Market {
+    /// Funding rate of the last period, paid by longs to shorts when positive
+    pub funding_rate: FixedI64,
+    /// Cumulative funding paid by a long position of one token
+    pub funding_index: FixedI64,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::AtLeast32BitUnsigned;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Numerical representation of stored balances
+        type Balance: Parameter
+            + Member
+            + AtLeast32BitUnsigned
+            + Copy
+            + Default
+            + From<eq_primitives::balance::Balance>
+            + Into<eq_primitives::balance::Balance>;
+        /// Settles positions results
+        type EqCurrency: EqCurrency<Self::AccountId, Self::Balance>;
+        /// Gets the index price of an asset
+        type PriceGetter: PriceGetter;
+        /// Gets the mark price of an asset from the order book
+        type MarkPriceGetter: MarkPriceGetter;
+        /// Gets trader subaccounts holding positions
+        type SubaccountsManager: SubaccountsManager<Self::AccountId>;
+        /// Checks margin of subaccounts increasing positions
+        type MarginCallManager: MarginCallManager<Self::AccountId, Self::Balance>;
+        /// Timestamp provider
+        type UnixTime: UnixTime;
+        /// Perps vault ModuleId, the counterparty of all positions
+        #[pallet::constant]
+        type PerpsModuleId: Get<PalletId>;
+        /// Period of funding rate updates in seconds
+        #[pallet::constant]
+        type FundingPeriod: Get<u64>;
+        /// Max absolute funding rate per period
+        #[pallet::constant]
+        type MaxFundingRate: Get<FixedI64>;
+        /// Max relative deviation of the mark price from the index price
+        #[pallet::constant]
+        type MaxPriceDeviation: Get<FixedI64>;
+        /// Relative spread added to the price of buys and subtracted from the price of sells
+        #[pallet::constant]
+        type TradeSpread: Get<FixedI64>;
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Perpetual markets by underlying asset
+    #[pallet::storage]
+    #[pallet::getter(fn market)]
+    pub type Markets<T: Config> = StorageMap<_, Blake2_128Concat, Asset, Market, OptionQuery>;
+
+    /// Open positions of trader subaccounts
+    #[pallet::storage]
+    #[pallet::getter(fn position)]
+    pub type Positions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        Asset,
+        Position<T::Balance>,
+        OptionQuery,
+    >;
+
+    /// Max long and max short open interest of markets
+    #[pallet::storage]
+    #[pallet::getter(fn open_interest_limit)]
+    pub type OpenInterestLimits<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, T::Balance, ValueQuery>;
+
+    /// Long and short open interest of markets
+    #[pallet::storage]
+    #[pallet::getter(fn open_interest)]
+    pub type OpenInterests<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, OpenInterest<T::Balance>, ValueQuery>;
+
+    /// Profits of trader subaccounts the vault couldn't pay
+    #[pallet::storage]
+    #[pallet::getter(fn unpaid_pnl)]
+    pub type UnpaidPnl<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::Balance, ValueQuery>;
+
+    /// Total of unpaid profits
+    #[pallet::storage]
+    #[pallet::getter(fn vault_deficit)]
+    pub type VaultDeficit<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+
+    /// Unix time in seconds of the last funding rates update
+    #[pallet::storage]
+    #[pallet::getter(fn last_funding_time)]
+    pub type LastFundingTime<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Perpetual market is added
+        MarketAdded { asset: Asset },
+        /// Position of the trader subaccount is changed at `price`
+        PositionChanged {
+            who: T::AccountId,
+            asset: Asset,
+            size: SignedBalance<T::Balance>,
+            price: FixedI64,
+        },
+        /// Result of the reduced part of the position is settled in EQD
+        PositionSettled {
+            who: T::AccountId,
+            asset: Asset,
+            pnl: SignedBalance<T::Balance>,
+        },
+        /// Position is closed because the subaccount is margin called
+        PositionLiquidated {
+            who: T::AccountId,
+            asset: Asset,
+            size: SignedBalance<T::Balance>,
+        },
+        /// Funding rate of the market is updated
+        FundingUpdated { asset: Asset, rate: FixedI64 },
+        /// Max long and max short open interest of the market is set
+        OpenInterestLimitSet { asset: Asset, limit: T::Balance },
+        /// Part of the settled profit the vault couldn't pay is added to the vault deficit
+        PnlUnpaid {
+            who: T::AccountId,
+            amount: T::Balance,
+        },
+        /// Unpaid profit is paid by the vault
+        UnpaidPnlClaimed {
+            who: T::AccountId,
+            amount: T::Balance,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Market for the asset already exists
+        MarketAlreadyExists,
+        /// There is no market for the asset
+        MarketNotFound,
+        /// Caller has no trader subaccount
+        NoTraderSubaccount,
+        /// Amount must be greater than zero
+        ZeroAmount,
+        /// Trader subaccount has no position in the asset
+        NoPosition,
+        /// Margin of the trader subaccount isn't good after the position is increased
+        MarginTooLow,
+        /// Open interest of the market side would exceed its limit
+        OpenInterestLimitExceeded,
+        /// Trader subaccount has no unpaid profit
+        NoUnpaidPnl,
+        /// Vault has no EQD to pay unpaid profits
+        VaultIsEmpty,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: T::BlockNumber) -> Weight {
+            let now = T::UnixTime::now().as_secs();
+            if now < Self::last_funding_time().saturating_add(T::FundingPeriod::get()) {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let markets = Self::update_fundings();
+            LastFundingTime::<T>::put(now);
+
+            T::WeightInfo::update_funding(markets)
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Adds a perpetual market for `asset` with max long and max short open interest
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::add_market())]
+        pub fn add_market(
+            origin: OriginFor<T>,
+            asset: Asset,
+            open_interest_limit: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                !Markets::<T>::contains_key(asset),
+                Error::<T>::MarketAlreadyExists
+            );
+
+            Markets::<T>::insert(asset, Market::default());
+            OpenInterestLimits::<T>::insert(asset, open_interest_limit);
+
+            Self::deposit_event(Event::MarketAdded { asset });
+            Ok(().into())
+        }
+
+        /// Buys or sells `amount` of `asset` perpetual at the execution price on the caller's
+        /// trader subaccount. A reduced part of the position is settled in EQD.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::trade())]
+        pub fn trade(
+            origin: OriginFor<T>,
+            asset: Asset,
+            side: OrderSide,
+            amount: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            let trader = Self::trader_subaccount(&who)?;
+
+            let increased = Self::change_position(&trader, asset, side, amount)?;
+            if increased {
+                let state = T::MarginCallManager::check_margin(&trader)?;
+                ensure!(state == MarginState::Good, Error::<T>::MarginTooLow);
+            }
+
+            Ok(().into())
+        }
+
+        /// Closes the caller's `asset` position at the execution price and settles it in EQD
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::close_position())]
+        pub fn close_position(origin: OriginFor<T>, asset: Asset) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let trader = Self::trader_subaccount(&who)?;
+            let position = Positions::<T>::get(&trader, asset).ok_or(Error::<T>::NoPosition)?;
+
+            let (side, amount) = Self::closing_trade(&position);
+            Self::change_position(&trader, asset, side, amount)?;
+
+            Ok(().into())
+        }
+
+        /// Sets max long and max short open interest of the `asset` market.
+        /// Positions above a lowered limit can still be reduced and closed.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_open_interest_limit())]
+        pub fn set_open_interest_limit(
+            origin: OriginFor<T>,
+            asset: Asset,
+            limit: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                Markets::<T>::contains_key(asset),
+                Error::<T>::MarketNotFound
+            );
+
+            OpenInterestLimits::<T>::insert(asset, limit);
+
+            Self::deposit_event(Event::OpenInterestLimitSet { asset, limit });
+            Ok(().into())
+        }
+
+        /// Pays the caller's trader subaccount the unpaid profit, as much as the vault has
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::claim_unpaid_pnl())]
+        pub fn claim_unpaid_pnl(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let trader = Self::trader_subaccount(&who)?;
+            let unpaid = UnpaidPnl::<T>::get(&trader);
+            ensure!(!unpaid.is_zero(), Error::<T>::NoUnpaidPnl);
+
+            let paid = Self::pay_from_vault(&trader, unpaid);
+            ensure!(!paid.is_zero(), Error::<T>::VaultIsEmpty);
+
+            UnpaidPnl::<T>::mutate_exists(&trader, |value| {
+                *value = Some(unpaid - paid).filter(|v| !v.is_zero())
+            });
+            VaultDeficit::<T>::mutate(|deficit| *deficit = deficit.saturating_sub(paid));
+
+            Self::deposit_event(Event::UnpaidPnlClaimed {
+                who: trader,
+                amount: paid,
+            });
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Account of the perps vault
+    pub fn vault_account() -> T::AccountId {
+        T::PerpsModuleId::get().into_account_truncating()
+    }
+
+    /// Mid price of the order book within `MaxPriceDeviation` of `index_price`,
+    /// index price if the book has no bid or ask
+    pub fn mark_price(asset: &Asset, index_price: FixedI64) -> FixedI64 {
+        let max_deviation = index_price
+            .saturating_mul(T::MaxPriceDeviation::get())
+            .saturating_abs();
+        T::MarkPriceGetter::mark_price(asset)
+            .map(|price| {
+                price
+                    .max(index_price.saturating_sub(max_deviation))
+                    .min(index_price.saturating_add(max_deviation))
+            })
+            .unwrap_or(index_price)
+    }
+
+    /// Price of `side` trade against the vault: the worse of the index and the mark prices
+    /// for the trader with `TradeSpread` applied
+    pub fn execution_price(asset: &Asset, side: OrderSide) -> Result<FixedI64, DispatchError> {
+        let index_price: FixedI64 = T::PriceGetter::get_price(asset)?;
+        let mark_price = Self::mark_price(asset, index_price);
+        let spread = index_price.saturating_mul(T::TradeSpread::get());
+
+        Ok(match side {
+            OrderSide::Buy => index_price.max(mark_price).saturating_add(spread),
+            OrderSide::Sell => index_price.min(mark_price).saturating_sub(spread),
+        })
+    }
+
+    fn trader_subaccount(who: &T::AccountId) -> Result<T::AccountId, DispatchError> {
+        T::SubaccountsManager::get_subaccount_id(who, &SubAccType::Trader)
+            .ok_or_else(|| Error::<T>::NoTraderSubaccount.into())
+    }
+
+    /// Side and amount of the trade closing `position`
+    fn closing_trade(position: &Position<T::Balance>) -> (OrderSide, T::Balance) {
+        let side = match position.size {
+            SignedBalance::Positive(_) => OrderSide::Sell,
+            SignedBalance::Negative(_) => OrderSide::Buy,
+        };
+        (side, position.size.abs())
+    }
+
+    /// Updates funding rates of all markets, returns the number of markets
+    pub(crate) fn update_fundings() -> u32 {
+        let markets: Vec<_> = Markets::<T>::iter().collect();
+        for (asset, mut market) in markets.iter().copied() {
+            match Self::update_funding(&asset, &mut market) {
+                Ok(()) => {
+                    Markets::<T>::insert(asset, market);
+                    Self::deposit_event(Event::FundingUpdated {
+                        asset,
+                        rate: market.funding_rate,
+                    });
+                }
+                Err(error) => log::error!(
+                    target: "eq_perps",
+                    "{}:{}. Funding rate is not updated. Asset: {:?}, error: {:?}",
+                    file!(),
+                    line!(),
+                    asset,
+                    error
+                ),
+            }
+        }
+
+        markets.len() as u32
+    }
+
+    /// Computes the funding rate of the last period from the basis between the mark and
+    /// the index prices and accrues it to the market funding index
+    fn update_funding(asset: &Asset, market: &mut Market) -> DispatchResult {
+        let index_price: FixedI64 = T::PriceGetter::get_price(asset)?;
+        let mark_price = Self::mark_price(asset, index_price);
+
+        let max_rate = T::MaxFundingRate::get();
+        let rate = mark_price
+            .saturating_sub(index_price)
+            .checked_div(&index_price)
+            .ok_or(ArithmeticError::DivisionByZero)?
+            .min(max_rate)
+            .max(FixedI64::zero().saturating_sub(max_rate));
+
+        market.funding_rate = rate;
+        market.funding_index = market
+            .funding_index
+            .saturating_add(rate.saturating_mul(index_price));
+        Ok(())
+    }
+
+    /// Trades `amount` of `asset` perpetual on `trader` at the execution price. Returns
+    /// whether the position is increased or opened in the opposite direction.
+    fn change_position(
+        trader: &T::AccountId,
+        asset: Asset,
+        side: OrderSide,
+        amount: T::Balance,
+    ) -> Result<bool, DispatchError> {
+        let market = Markets::<T>::get(asset).ok_or(Error::<T>::MarketNotFound)?;
+        let price = Self::execution_price(&asset, side)?;
+
+        let mut position = Positions::<T>::get(trader, asset).unwrap_or(Position {
+            size: SignedBalance::zero(),
+            entry_price: price,
+            funding_index: market.funding_index,
+        });
+        let mut open_interest = OpenInterests::<T>::get(asset);
+        open_interest.remove_position(&position.size);
+        position.entry_price = position.entry_price_with_funding(market.funding_index);
+        position.funding_index = market.funding_index;
+
+        let (reduced, increased) = match (&position.size, side) {
+            (SignedBalance::Positive(size), OrderSide::Sell)
+            | (SignedBalance::Negative(size), OrderSide::Buy) => {
+                let reduced = amount.min(*size);
+                (reduced, amount - reduced)
+            }
+            _ => (Zero::zero(), amount),
+        };
+
+        if !reduced.is_zero() {
+            // long position gains when the price is above the entry, short one when below
+            let pnl_per_token = match side {
+                OrderSide::Sell => price.saturating_sub(position.entry_price),
+                OrderSide::Buy => position.entry_price.saturating_sub(price),
+            };
+            let pnl = Self::settle(trader, pnl_per_token, reduced)?;
+            Self::deposit_event(Event::PositionSettled {
+                who: trader.clone(),
+                asset,
+                pnl,
+            });
+        }
+
+        if !increased.is_zero() {
+            let current = if reduced.is_zero() {
+                position.size.abs()
+            } else {
+                // position is reversed, the rest is opened at the current price
+                Zero::zero()
+            };
+            let total = current
+                .checked_add(&increased)
+                .ok_or(ArithmeticError::Overflow)?;
+            let weight = FixedI64::checked_from_rational(
+                Into::<eq_primitives::balance::Balance>::into(increased),
+                Into::<eq_primitives::balance::Balance>::into(total),
+            )
+            .ok_or(ArithmeticError::Overflow)?;
+            position.entry_price = position.entry_price.saturating_add(
+                price
+                    .saturating_sub(position.entry_price)
+                    .saturating_mul(weight),
+            );
+        }
+
+        position.size = match side {
+            OrderSide::Buy => position.size.add_balance(&amount),
+            OrderSide::Sell => position.size.sub_balance(&amount),
+        }
+        .ok_or(ArithmeticError::Overflow)?;
+
+        open_interest
+            .add_position(&position.size)
+            .ok_or(ArithmeticError::Overflow)?;
+        if !increased.is_zero() {
+            ensure!(
+                open_interest.side(&position.size) <= Self::open_interest_limit(asset),
+                Error::<T>::OpenInterestLimitExceeded
+            );
+        }
+        OpenInterests::<T>::insert(asset, open_interest);
+
+        if position.size.is_zero() {
+            Positions::<T>::remove(trader, asset);
+        } else {
+            Positions::<T>::insert(trader, asset, position.clone());
+        }
+
+        Self::deposit_event(Event::PositionChanged {
+            who: trader.clone(),
+            asset,
+            size: position.size,
+            price,
+        });
+        Ok(!increased.is_zero())
+    }
+
+    /// Transfers the result of `amount` of the position between `trader` and the vault.
+    /// Losses are charged regardless of the trader margin: they were already accounted
+    /// as the position exposure. Profit the vault can't pay is recorded as unpaid.
+    fn settle(
+        trader: &T::AccountId,
+        pnl_per_token: FixedI64,
+        amount: T::Balance,
+    ) -> Result<SignedBalance<T::Balance>, DispatchError> {
+        let value: T::Balance = pnl_per_token
+            .saturating_abs()
+            .checked_mul_int(Into::<eq_primitives::balance::Balance>::into(amount))
+            .ok_or(ArithmeticError::Overflow)?
+            .into();
+        if value.is_zero() {
+            return Ok(SignedBalance::zero());
+        }
+
+        let vault = Self::vault_account();
+        if pnl_per_token.is_negative() {
+            T::EqCurrency::currency_transfer(
+                trader,
+                &vault,
+                EQD,
+                value,
+                ExistenceRequirement::KeepAlive,
+                TransferReason::PerpsSettlement,
+                false,
+            )?;
+            Ok(SignedBalance::Negative(value))
+        } else {
+            let unpaid = value - Self::pay_from_vault(trader, value);
+            if !unpaid.is_zero() {
+                UnpaidPnl::<T>::mutate(trader, |value| *value = value.saturating_add(unpaid));
+                VaultDeficit::<T>::mutate(|deficit| *deficit = deficit.saturating_add(unpaid));
+                Self::deposit_event(Event::PnlUnpaid {
+                    who: trader.clone(),
+                    amount: unpaid,
+                });
+            }
+            Ok(SignedBalance::Positive(value))
+        }
+    }
+
+    /// Transfers up to `value` EQD from the vault to `trader`, returns the paid amount
+    fn pay_from_vault(trader: &T::AccountId, value: T::Balance) -> T::Balance {
+        let vault = Self::vault_account();
+        let paid = value.min(T::EqCurrency::free_balance(&vault, EQD));
+        if paid.is_zero() {
+            return paid;
+        }
+
+        let result = frame_support::storage::with_storage_layer(|| {
+            T::EqCurrency::currency_transfer(
+                &vault,
+                trader,
+                EQD,
+                paid,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::PerpsSettlement,
+                true,
+            )
+        });
+        match result {
+            Ok(()) => paid,
+            Err(error) => {
+                log::error!(
+                    target: "eq_perps",
+                    "{}:{}. Vault payment failed. Who: {:?}, amount: {:?}, error: {:?}",
+                    file!(),
+                    line!(),
+                    trader,
+                    paid,
+                    error
+                );
+                Zero::zero()
+            }
+        }
+    }
+}
+
+impl<T: Config> DerivativePositions<T::AccountId> for Pallet<T> {
+    fn get_position_changes(who: &T::AccountId) -> Vec<OrderChange> {
+        Positions::<T>::iter_prefix(who)
+            .map(|(asset, position)| {
+                let funding_index = Markets::<T>::get(asset)
+                    .map(|market| market.funding_index)
+                    .unwrap_or(position.funding_index);
+                let side = match position.size {
+                    SignedBalance::Positive(_) => OrderSide::Buy,
+                    SignedBalance::Negative(_) => OrderSide::Sell,
+                };
+                OrderChange {
+                    asset,
+                    amount: eq_fixedu128_from_balance(position.size.abs()),
+                    price: position
+                        .entry_price_with_funding(funding_index)
+                        .max(FixedI64::zero()),
+                    side,
+                }
+            })
+            .collect()
+    }
+
+    fn close_positions(who: &T::AccountId) -> DispatchResult {
+        let positions: Vec<_> = Positions::<T>::iter_prefix(who).collect();
+        for (asset, position) in positions {
+            let (side, amount) = Self::closing_trade(&position);
+            Self::change_position(who, asset, side, amount)?;
+
+            Self::deposit_event(Event::PositionLiquidated {
+                who: who.clone(),
+                asset,
+                size: position.size,
+            });
+        }
+
+        Ok(())
+    }
+}
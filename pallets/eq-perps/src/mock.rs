@@ -0,0 +1,191 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as eq_perps;
+use eq_primitives::{
+    asset::{self, AssetType},
+    balance_number::EqFixedU128,
+    BalanceChange,
+};
+use frame_support::{parameter_types, traits::Everything};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError,
+};
+use std::{cell::RefCell, collections::HashMap};
+use test_utils::{
+    builder::{AssetConfig, ExtBuilder},
+    mock::{subaccounts::SubaccountsManagerMock, time::TimeMock},
+};
+
+pub type AccountId = u64;
+pub type Balance = eq_primitives::balance::Balance;
+pub type EqCurrencyMock = test_utils::mock::currency::EqCurrencyMock<AccountId>;
+pub type OracleMock = eq_primitives::price::mock::OracleMock<AccountId>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// Account without a trader subaccount
+pub const NO_TRADER: AccountId = 99;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        EqPerps: eq_perps::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const PerpsModuleId: PalletId = PalletId(*b"eq/perps");
+    pub const FundingPeriod: u64 = 3600;
+    pub MaxFundingRate: FixedI64 = FixedI64::saturating_from_rational(5, 100);
+    pub static MaxPriceDeviation: FixedI64 = FixedI64::saturating_from_rational(50, 100);
+    pub static TradeSpread: FixedI64 = FixedI64::zero();
+    pub CriticalMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(5, 1000);
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqCurrencyMock;
+    type PriceGetter = OracleMock;
+    type MarkPriceGetter = MarkPriceMock;
+    type SubaccountsManager = SubaccountsManagerMock;
+    type MarginCallManager = MarginCallManagerMock;
+    type UnixTime = TimeMock;
+    type PerpsModuleId = PerpsModuleId;
+    type FundingPeriod = FundingPeriod;
+    type MaxFundingRate = MaxFundingRate;
+    type MaxPriceDeviation = MaxPriceDeviation;
+    type TradeSpread = TradeSpread;
+    type WeightInfo = ();
+}
+
+thread_local! {
+    static MARK_PRICES: RefCell<HashMap<Asset, FixedI64>> = RefCell::new(HashMap::new());
+    static MARGIN_STATE: RefCell<MarginState> = RefCell::new(MarginState::Good);
+}
+
+pub fn vault() -> AccountId {
+    PerpsModuleId::get().into_account_truncating()
+}
+
+pub fn trader(who: AccountId) -> AccountId {
+    SubaccountsManagerMock::get_subaccount_id(&who, &SubAccType::Trader)
+        .expect("Trader subaccount is created in new_test_ext")
+}
+
+/// EQD balance, may be negative
+pub fn balance(who: AccountId) -> i128 {
+    match EqCurrencyMock::balance(&who, asset::EQD) {
+        SignedBalance::Positive(value) => value as i128,
+        SignedBalance::Negative(value) => -(value as i128),
+    }
+}
+
+pub fn set_mark_price(asset: Asset, price: Option<FixedI64>) {
+    MARK_PRICES.with(|v| match price {
+        Some(price) => v.borrow_mut().insert(asset, price),
+        None => v.borrow_mut().remove(&asset),
+    });
+}
+
+/// Margin state of every account
+pub fn set_margin_state(state: MarginState) {
+    MARGIN_STATE.with(|v| *v.borrow_mut() = state);
+}
+
+pub struct MarkPriceMock;
+impl MarkPriceGetter for MarkPriceMock {
+    fn mark_price(asset: &Asset) -> Option<FixedI64> {
+        MARK_PRICES.with(|v| v.borrow().get(asset).copied())
+    }
+}
+
+pub struct MarginCallManagerMock;
+impl MarginCallManager<AccountId, Balance> for MarginCallManagerMock {
+    fn check_margin_with_change(
+        _: &AccountId,
+        _: &[BalanceChange<Balance>],
+        _: &[OrderChange],
+    ) -> Result<(MarginState, bool), DispatchError> {
+        Ok((MARGIN_STATE.with(|v| *v.borrow()), false))
+    }
+
+    fn try_margincall(owner: &AccountId) -> Result<MarginState, DispatchError> {
+        Self::check_margin(owner)
+    }
+
+    fn get_critical_margin() -> EqFixedU128 {
+        CriticalMargin::get()
+    }
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    ExtBuilder::default()
+        .subaccount(1, SubAccType::Trader, 101)
+        .subaccount(2, SubAccType::Trader, 102)
+        .asset(AssetConfig::new(asset::EQD, AssetType::Synthetic, 1))
+        .asset(AssetConfig::new(asset::BTC, AssetType::Physical, 2))
+        .asset(AssetConfig::new(asset::ETH, AssetType::Physical, 3))
+        .init_mocks();
+
+    let r = frame_system::GenesisConfig::default().build_storage::<Test>();
+
+    let mut ext: sp_io::TestExternalities = r.unwrap().into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,475 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+use eq_primitives::{asset, ONE_TOKEN};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::{traits::One, DispatchError};
+use test_utils::mock::time::TimeMock;
+
+const ALICE: AccountId = 1;
+const BOB: AccountId = 2;
+
+fn price(value: i64) -> FixedI64 {
+    FixedI64::saturating_from_integer(value)
+}
+
+fn tokens(value: u128) -> Balance {
+    value * ONE_TOKEN
+}
+
+/// BTC market with index price 100 and EQD in the vault to pay profits
+fn init_market() {
+    OracleMock::init(vec![(asset::BTC, price(100))]);
+    EqCurrencyMock::set_balance(&vault(), asset::EQD, tokens(1_000));
+    assert_ok!(EqPerps::add_market(
+        RuntimeOrigin::root(),
+        asset::BTC,
+        tokens(1_000)
+    ));
+}
+
+fn trade(who: AccountId, side: OrderSide, amount: u128) {
+    assert_ok!(EqPerps::trade(
+        RuntimeOrigin::signed(who),
+        asset::BTC,
+        side,
+        tokens(amount)
+    ));
+}
+
+#[test]
+fn add_market_is_root_only_and_unique() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EqPerps::add_market(RuntimeOrigin::signed(ALICE), asset::BTC, tokens(1_000)),
+            DispatchError::BadOrigin
+        );
+        init_market();
+        assert_eq!(EqPerps::market(asset::BTC), Some(Market::default()));
+        assert_eq!(EqPerps::open_interest_limit(asset::BTC), tokens(1_000));
+        assert_noop!(
+            EqPerps::add_market(RuntimeOrigin::root(), asset::BTC, tokens(1_000)),
+            Error::<Test>::MarketAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn trade_opens_position_on_trader_subaccount() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EqPerps::trade(
+                RuntimeOrigin::signed(ALICE),
+                asset::BTC,
+                OrderSide::Buy,
+                tokens(1)
+            ),
+            Error::<Test>::MarketNotFound
+        );
+        init_market();
+        assert_noop!(
+            EqPerps::trade(
+                RuntimeOrigin::signed(NO_TRADER),
+                asset::BTC,
+                OrderSide::Buy,
+                tokens(1)
+            ),
+            Error::<Test>::NoTraderSubaccount
+        );
+        assert_noop!(
+            EqPerps::trade(RuntimeOrigin::signed(ALICE), asset::BTC, OrderSide::Buy, 0),
+            Error::<Test>::ZeroAmount
+        );
+
+        // no order book, index price is used
+        trade(ALICE, OrderSide::Buy, 1);
+        // average entry price
+        set_mark_price(asset::BTC, Some(price(120)));
+        trade(ALICE, OrderSide::Buy, 1);
+
+        assert_eq!(
+            EqPerps::position(trader(ALICE), asset::BTC),
+            Some(Position {
+                size: SignedBalance::Positive(tokens(2)),
+                entry_price: price(110),
+                funding_index: FixedI64::zero(),
+            })
+        );
+        assert_eq!(
+            <EqPerps as DerivativePositions<_>>::get_position_changes(&trader(ALICE))
+                .into_iter()
+                .map(|c| (c.asset, c.amount, c.price, c.side))
+                .collect::<Vec<_>>(),
+            vec![(
+                asset::BTC,
+                eq_fixedu128_from_balance(tokens(2)),
+                price(110),
+                OrderSide::Buy
+            )]
+        );
+        assert_eq!(balance(trader(ALICE)), 0);
+    });
+}
+
+#[test]
+fn position_is_increased_only_with_good_margin() {
+    new_test_ext().execute_with(|| {
+        init_market();
+        trade(ALICE, OrderSide::Sell, 2);
+
+        set_margin_state(MarginState::SubGood);
+        assert_noop!(
+            EqPerps::trade(
+                RuntimeOrigin::signed(ALICE),
+                asset::BTC,
+                OrderSide::Sell,
+                tokens(1)
+            ),
+            Error::<Test>::MarginTooLow
+        );
+        // reversed position is increased too
+        assert_noop!(
+            EqPerps::trade(
+                RuntimeOrigin::signed(ALICE),
+                asset::BTC,
+                OrderSide::Buy,
+                tokens(3)
+            ),
+            Error::<Test>::MarginTooLow
+        );
+
+        trade(ALICE, OrderSide::Buy, 1);
+        assert_ok!(EqPerps::close_position(
+            RuntimeOrigin::signed(ALICE),
+            asset::BTC
+        ));
+        assert_eq!(EqPerps::position(trader(ALICE), asset::BTC), None);
+        assert_noop!(
+            EqPerps::close_position(RuntimeOrigin::signed(ALICE), asset::BTC),
+            Error::<Test>::NoPosition
+        );
+    });
+}
+
+#[test]
+fn reduced_position_is_settled_in_eqd() {
+    new_test_ext().execute_with(|| {
+        init_market();
+        trade(ALICE, OrderSide::Buy, 10);
+        trade(BOB, OrderSide::Sell, 5);
+
+        OracleMock::init(vec![(asset::BTC, price(110))]);
+        trade(ALICE, OrderSide::Sell, 4);
+        assert_eq!(balance(trader(ALICE)), tokens(40) as i128);
+        System::assert_has_event(
+            Event::PositionSettled {
+                who: trader(ALICE),
+                asset: asset::BTC,
+                pnl: SignedBalance::Positive(tokens(40)),
+            }
+            .into(),
+        );
+
+        // short loss is charged regardless of the trader balance
+        // and the rest of the order reverses the position at the current price
+        trade(BOB, OrderSide::Buy, 8);
+        assert_eq!(balance(trader(BOB)), -(tokens(50) as i128));
+        assert_eq!(
+            EqPerps::position(trader(BOB), asset::BTC),
+            Some(Position {
+                size: SignedBalance::Positive(tokens(3)),
+                entry_price: price(110),
+                funding_index: FixedI64::zero(),
+            })
+        );
+        assert_eq!(balance(vault()), tokens(1_010) as i128);
+    });
+}
+
+#[test]
+fn funding_is_updated_each_period_and_settled_with_position() {
+    new_test_ext().execute_with(|| {
+        init_market();
+        trade(ALICE, OrderSide::Buy, 10);
+        trade(BOB, OrderSide::Sell, 10);
+        set_mark_price(asset::BTC, Some(price(101)));
+
+        TimeMock::set_secs(FundingPeriod::get());
+        EqPerps::on_initialize(1);
+        let rate = FixedI64::saturating_from_rational(1, 100);
+        assert_eq!(
+            EqPerps::market(asset::BTC),
+            Some(Market {
+                funding_rate: rate,
+                funding_index: FixedI64::one(),
+            })
+        );
+        System::assert_last_event(
+            Event::FundingUpdated {
+                asset: asset::BTC,
+                rate,
+            }
+            .into(),
+        );
+
+        // not updated again until the period is over
+        set_mark_price(asset::BTC, Some(price(150)));
+        TimeMock::set_secs(2 * FundingPeriod::get() - 1);
+        EqPerps::on_initialize(2);
+        assert_eq!(EqPerps::market(asset::BTC).unwrap().funding_rate, rate);
+
+        // rate is capped
+        TimeMock::set_secs(2 * FundingPeriod::get());
+        EqPerps::on_initialize(3);
+        assert_eq!(
+            EqPerps::market(asset::BTC),
+            Some(Market {
+                funding_rate: MaxFundingRate::get(),
+                funding_index: price(6),
+            })
+        );
+
+        // long paid and short received 6 EQD per token of funding
+        set_mark_price(asset::BTC, None);
+        assert_ok!(EqPerps::close_position(
+            RuntimeOrigin::signed(ALICE),
+            asset::BTC
+        ));
+        assert_ok!(EqPerps::close_position(
+            RuntimeOrigin::signed(BOB),
+            asset::BTC
+        ));
+        assert_eq!(balance(trader(ALICE)), -(tokens(60) as i128));
+        assert_eq!(balance(trader(BOB)), tokens(60) as i128);
+    });
+}
+
+#[test]
+fn positions_are_closed_on_margin_call() {
+    new_test_ext().execute_with(|| {
+        init_market();
+        OracleMock::init(vec![(asset::BTC, price(100)), (asset::ETH, price(10))]);
+        assert_ok!(EqPerps::add_market(
+            RuntimeOrigin::root(),
+            asset::ETH,
+            tokens(1_000)
+        ));
+        trade(ALICE, OrderSide::Buy, 10);
+        assert_ok!(EqPerps::trade(
+            RuntimeOrigin::signed(ALICE),
+            asset::ETH,
+            OrderSide::Sell,
+            tokens(5)
+        ));
+
+        OracleMock::init(vec![(asset::BTC, price(90)), (asset::ETH, price(12))]);
+        assert_ok!(<EqPerps as DerivativePositions<_>>::close_positions(
+            &trader(ALICE)
+        ));
+
+        assert_eq!(balance(trader(ALICE)), -(tokens(110) as i128));
+        assert!(
+            <EqPerps as DerivativePositions<_>>::get_position_changes(&trader(ALICE)).is_empty()
+        );
+        System::assert_has_event(
+            Event::PositionLiquidated {
+                who: trader(ALICE),
+                asset: asset::ETH,
+                size: SignedBalance::Negative(tokens(5)),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn trades_are_executed_at_worse_price_for_trader_with_spread() {
+    new_test_ext().execute_with(|| {
+        init_market();
+        MaxPriceDeviation::set(&FixedI64::saturating_from_rational(10, 100));
+        TradeSpread::set(&FixedI64::saturating_from_rational(1, 100));
+
+        // no order book
+        assert_eq!(
+            EqPerps::execution_price(&asset::BTC, OrderSide::Buy),
+            Ok(price(101))
+        );
+        assert_eq!(
+            EqPerps::execution_price(&asset::BTC, OrderSide::Sell),
+            Ok(price(99))
+        );
+
+        // book mid above the index only makes buys worse, within max deviation
+        set_mark_price(asset::BTC, Some(price(150)));
+        assert_eq!(EqPerps::mark_price(&asset::BTC, price(100)), price(110));
+        assert_eq!(
+            EqPerps::execution_price(&asset::BTC, OrderSide::Buy),
+            Ok(price(111))
+        );
+        assert_eq!(
+            EqPerps::execution_price(&asset::BTC, OrderSide::Sell),
+            Ok(price(99))
+        );
+
+        // book mid below the index only makes sells worse
+        set_mark_price(asset::BTC, Some(price(95)));
+        assert_eq!(
+            EqPerps::execution_price(&asset::BTC, OrderSide::Buy),
+            Ok(price(101))
+        );
+        assert_eq!(
+            EqPerps::execution_price(&asset::BTC, OrderSide::Sell),
+            Ok(price(94))
+        );
+
+        // moving the mid with resting orders doesn't pay off
+        trade(ALICE, OrderSide::Sell, 1);
+        set_mark_price(asset::BTC, Some(price(105)));
+        trade(ALICE, OrderSide::Buy, 1);
+        assert_eq!(balance(trader(ALICE)), -(tokens(12) as i128));
+    });
+}
+
+#[test]
+fn open_interest_is_limited_per_market_side() {
+    new_test_ext().execute_with(|| {
+        OracleMock::init(vec![(asset::BTC, price(100))]);
+        assert_ok!(EqPerps::add_market(
+            RuntimeOrigin::root(),
+            asset::BTC,
+            tokens(10)
+        ));
+
+        trade(ALICE, OrderSide::Buy, 8);
+        assert_noop!(
+            EqPerps::trade(
+                RuntimeOrigin::signed(BOB),
+                asset::BTC,
+                OrderSide::Buy,
+                tokens(3)
+            ),
+            Error::<Test>::OpenInterestLimitExceeded
+        );
+        trade(BOB, OrderSide::Sell, 10);
+        assert_eq!(
+            EqPerps::open_interest(asset::BTC),
+            OpenInterest {
+                long: tokens(8),
+                short: tokens(10),
+            }
+        );
+
+        // reversed position is checked on the new side
+        assert_noop!(
+            EqPerps::trade(
+                RuntimeOrigin::signed(ALICE),
+                asset::BTC,
+                OrderSide::Sell,
+                tokens(9)
+            ),
+            Error::<Test>::OpenInterestLimitExceeded
+        );
+
+        assert_noop!(
+            EqPerps::set_open_interest_limit(RuntimeOrigin::signed(ALICE), asset::BTC, 0),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            EqPerps::set_open_interest_limit(RuntimeOrigin::root(), asset::ETH, 0),
+            Error::<Test>::MarketNotFound
+        );
+        assert_ok!(EqPerps::set_open_interest_limit(
+            RuntimeOrigin::root(),
+            asset::BTC,
+            0
+        ));
+
+        // positions above the limit are still reduced and closed
+        trade(ALICE, OrderSide::Sell, 3);
+        assert_ok!(EqPerps::close_position(
+            RuntimeOrigin::signed(BOB),
+            asset::BTC
+        ));
+        assert_eq!(
+            EqPerps::open_interest(asset::BTC),
+            OpenInterest {
+                long: tokens(5),
+                short: 0,
+            }
+        );
+    });
+}
+
+#[test]
+fn profit_vault_cant_pay_is_recorded_and_claimed_later() {
+    new_test_ext().execute_with(|| {
+        init_market();
+        EqCurrencyMock::set_balance(&vault(), asset::EQD, tokens(30));
+        trade(ALICE, OrderSide::Buy, 10);
+
+        assert_noop!(
+            EqPerps::claim_unpaid_pnl(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::NoUnpaidPnl
+        );
+
+        // liquidation doesn't depend on the vault balance
+        OracleMock::init(vec![(asset::BTC, price(110))]);
+        assert_ok!(<EqPerps as DerivativePositions<_>>::close_positions(
+            &trader(ALICE)
+        ));
+        assert_eq!(balance(trader(ALICE)), tokens(30) as i128);
+        assert_eq!(balance(vault()), 0);
+        assert_eq!(EqPerps::unpaid_pnl(trader(ALICE)), tokens(70));
+        assert_eq!(EqPerps::vault_deficit(), tokens(70));
+        System::assert_has_event(
+            Event::PnlUnpaid {
+                who: trader(ALICE),
+                amount: tokens(70),
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            EqPerps::claim_unpaid_pnl(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::VaultIsEmpty
+        );
+
+        // partially paid while the vault is short
+        EqCurrencyMock::set_balance(&vault(), asset::EQD, tokens(50));
+        assert_ok!(EqPerps::claim_unpaid_pnl(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(EqPerps::unpaid_pnl(trader(ALICE)), tokens(20));
+        assert_eq!(EqPerps::vault_deficit(), tokens(20));
+
+        EqCurrencyMock::set_balance(&vault(), asset::EQD, tokens(1_000));
+        assert_ok!(EqPerps::claim_unpaid_pnl(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(balance(trader(ALICE)), tokens(100) as i128);
+        assert!(!UnpaidPnl::<Test>::contains_key(trader(ALICE)));
+        assert_eq!(EqPerps::vault_deficit(), 0);
+        System::assert_last_event(
+            Event::UnpaidPnlClaimed {
+                who: trader(ALICE),
+                amount: tokens(20),
+            }
+            .into(),
+        );
+    });
+}
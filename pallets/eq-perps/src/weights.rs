@@ -0,0 +1,54 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+    fn add_market() -> Weight;
+    fn trade() -> Weight;
+    fn close_position() -> Weight;
+    fn update_funding(m: u32) -> Weight;
+    fn set_open_interest_limit() -> Weight;
+    fn claim_unpaid_pnl() -> Weight;
+}
+
+// for tests
+impl crate::WeightInfo for () {
+    fn add_market() -> Weight {
+        Weight::zero()
+    }
+    fn trade() -> Weight {
+        Weight::zero()
+    }
+    fn close_position() -> Weight {
+        Weight::zero()
+    }
+    fn update_funding(_m: u32) -> Weight {
+        Weight::zero()
+    }
+    fn set_open_interest_limit() -> Weight {
+        Weight::zero()
+    }
+    fn claim_unpaid_pnl() -> Weight {
+        Weight::zero()
+    }
+}
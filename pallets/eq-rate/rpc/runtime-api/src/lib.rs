@@ -0,0 +1,37 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for `eq-rate` pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use eq_primitives::{asset::Asset, InterestStatement};
+use sp_runtime::traits::MaybeDisplay;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait EqRateApi<Balance, AccountId>
+    where
+        Balance: Codec + MaybeDisplay,
+        AccountId: Codec
+    {
+        /// Cumulative interest charged from `account_id` by fee asset
+        fn interest_statements(account_id: AccountId) -> Vec<(Asset, InterestStatement<Balance>)>;
+    }
+}
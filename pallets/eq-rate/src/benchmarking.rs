@@ -207,7 +207,8 @@ benchmarks! {
             validators_len: 1,
             block_num: T::BlockNumber::default(),
             account: Some(borrower),
-            higher_priority: false
+            higher_priority: false,
+            nonce: 0,
         };
 
         let signature = sign_request::<T>(&request);
@@ -259,7 +260,8 @@ benchmarks! {
             validators_len: 1,
             block_num: T::BlockNumber::default(),
             account: Some(account_to_delete),
-            higher_priority: false
+            higher_priority: false,
+            nonce: 0,
         };
 
         let signature = sign_request::<T>(&request);
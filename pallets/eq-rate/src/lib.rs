@@ -59,8 +59,9 @@ use eq_primitives::{
     bailsman_redistribute_weight::RedistributeWeightInfo,
     balance::{BalanceGetter, BalanceRemover, DepositReason, EqCurrency, WithdrawReason},
     offchain_batcher::*,
-    Aggregates, BailsmanManager, EqBuyout, LendingAssetRemoval, LendingPoolManager,
-    MarginCallManager, MarginState, PriceGetter, SignedBalance, UpdateTimeManager,
+    Aggregates, BailsmanManager, EqBuyout, InterestIncomeReceiver, InterestStatement,
+    LendingAssetRemoval, LendingPoolManager, MarginCallManager, MarginState, PriceGetter,
+    SignedBalance, TreasuryAccounting, TreasuryFlowCategory, UpdateTimeManager,
 };
 use eq_utils::{
     eq_ensure,
@@ -80,7 +81,10 @@ use sp_application_crypto::RuntimeAppPublic;
 use sp_core::crypto::KeyTypeId;
 use sp_runtime::traits::One;
 use sp_runtime::{
-    traits::{AccountIdConversion, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Zero},
+    traits::{
+        AccountIdConversion, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Saturating,
+        Zero,
+    },
     transaction_validity::{
         InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
         ValidTransaction,
@@ -130,6 +134,8 @@ where
     pub block_num: BlockNumber,
     /// Determines whether this request has the higher priority:
     pub higher_priority: bool,
+    /// Nonce of the `UnsignedDuty::Rate` duty of the authority
+    pub nonce: u64,
 }
 
 /// Request data for offchain signing
@@ -151,6 +157,8 @@ where
     pub block_num: BlockNumber,
     /// Determines whether this request has the higher priority:
     pub higher_priority: bool,
+    /// Nonce of the `UnsignedDuty::Rate` duty of the authority
+    pub nonce: u64,
 }
 
 use crate::rate::InterestRateCalculator;
@@ -252,6 +260,10 @@ pub mod pallet {
         /// For unsigned transaction priority calculation
         #[pallet::constant]
         type UnsignedPriority: Get<TransactionPriority>;
+        /// Max number of unsigned transactions of a single validator duty executed in one block,
+        /// also bounds how far ahead of the stored nonce transactions are accepted
+        #[pallet::constant]
+        type MaxUnsignedPerBlock: Get<u32>;
         /// Lower bound for scaling risk model
         #[pallet::constant]
         type RiskLowerBound: Get<FixedI128>;
@@ -299,6 +311,10 @@ pub mod pallet {
         type LendingPoolManager: LendingPoolManager<Self::Balance, Self::AccountId>;
         /// Used to clear Lenders storage while asset removal
         type LendingAssetRemoval: LendingAssetRemoval<Self::AccountId>;
+        /// Receives treasury part of charged interest fees, e.g. to fund EQD savings
+        type InterestIncomeReceiver: InterestIncomeReceiver<Self::Balance>;
+        /// Records treasury share of interest fees
+        type TreasuryAccounting: TreasuryAccounting<Self::Balance>;
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
         /// Weight information of bailsman redistribution
@@ -324,6 +340,7 @@ pub mod pallet {
             _signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            Self::note_duty(request.authority_index, UnsignedDuty::Rate, request.nonce);
             eq_ensure!(
                 Self::auto_reinit_enabled(),
                 Error::<T>::AutoReinitIsDisabled,
@@ -374,6 +391,7 @@ pub mod pallet {
             _signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            Self::note_duty(request.authority_index, UnsignedDuty::Rate, request.nonce);
 
             eq_ensure!(
                 request.account.is_some(),
@@ -405,6 +423,7 @@ pub mod pallet {
             _signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            Self::note_duty(request.authority_index, UnsignedDuty::Rate, request.nonce);
 
             let assets_to_remove = eq_assets::AssetsToRemove::<T>::get().unwrap_or(Vec::new());
             eq_ensure!(
@@ -445,6 +464,7 @@ pub mod pallet {
             _signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            Self::note_duty(request.authority_index, UnsignedDuty::Rate, request.nonce);
 
             let assets_to_remove = eq_assets::AssetsToRemove::<T>::get().unwrap_or(Vec::new());
             eq_ensure!(
@@ -636,6 +656,30 @@ pub mod pallet {
     pub type AutoReinitEnabled<T: Config> =
         StorageValue<_, bool, ValueQuery, DefaultForAutoReinitEnabled>;
 
+    /// Cumulative interest charged from account in fee asset, kept after account is killed
+    #[pallet::storage]
+    pub type InterestStatements<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        Asset,
+        InterestStatement<T::Balance>,
+        ValueQuery,
+    >;
+
+    /// Nonces and per block usage of unsigned transactions for each validator and duty
+    #[pallet::storage]
+    pub type UnsignedDutyStates<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        AuthIndex,
+        Twox64Concat,
+        UnsignedDuty,
+        DutyState<T::BlockNumber>,
+        ValueQuery,
+    >;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub keys: Vec<T::AuthorityId>,
@@ -699,6 +743,11 @@ pub mod pallet {
                         &request.encode(),
                         &signature,
                     )?;
+                    let duty = Self::validate_duty(
+                        request.authority_index,
+                        UnsignedDuty::Rate,
+                        request.nonce,
+                    )?;
 
                     if !Self::need_to_reinit(request.account.as_ref().unwrap()) {
                         return InvalidTransaction::Custom(CHECK_NOT_PASSED).into();
@@ -712,6 +761,7 @@ pub mod pallet {
                     ValidTransaction::with_tag_prefix("EqFee")
                         .priority(priority)
                         .and_provides(request.account.clone().unwrap())
+                        .combine_with(duty)
                         .longevity(5)
                         .propagate(true)
                         .build()
@@ -723,6 +773,11 @@ pub mod pallet {
                         &request.encode(),
                         &signature,
                     )?;
+                    let duty = Self::validate_duty(
+                        request.authority_index,
+                        UnsignedDuty::Rate,
+                        request.nonce,
+                    )?;
                     // request.account checked in check_signature
                     if !T::EqCurrency::can_be_deleted(request.account.as_ref().unwrap())
                         .unwrap_or(false)
@@ -738,6 +793,7 @@ pub mod pallet {
                     ValidTransaction::with_tag_prefix("DelAcc")
                         .priority(priority)
                         .and_provides(request.account.clone().unwrap())
+                        .combine_with(duty)
                         .longevity(5)
                         .propagate(true)
                         .build()
@@ -749,6 +805,11 @@ pub mod pallet {
                         &request.encode(),
                         &signature,
                     )?;
+                    let duty = Self::validate_duty(
+                        request.authority_index,
+                        UnsignedDuty::Rate,
+                        request.nonce,
+                    )?;
 
                     let assets_to_remove =
                         eq_assets::AssetsToRemove::<T>::get().unwrap_or(Vec::new());
@@ -768,6 +829,7 @@ pub mod pallet {
                     ValidTransaction::with_tag_prefix("Withdraw")
                         .priority(priority)
                         .and_provides(request.account.clone())
+                        .combine_with(duty)
                         .longevity(5)
                         .propagate(true)
                         .build()
@@ -779,6 +841,11 @@ pub mod pallet {
                         &request.encode(),
                         &signature,
                     )?;
+                    let duty = Self::validate_duty(
+                        request.authority_index,
+                        UnsignedDuty::Rate,
+                        request.nonce,
+                    )?;
 
                     let assets_to_remove =
                         eq_assets::AssetsToRemove::<T>::get().unwrap_or(Vec::new());
@@ -798,6 +865,7 @@ pub mod pallet {
                     ValidTransaction::with_tag_prefix("Deposit")
                         .priority(priority)
                         .and_provides(request.account.clone())
+                        .combine_with(duty)
                         .longevity(5)
                         .propagate(true)
                         .build()
@@ -824,6 +892,15 @@ impl<T: Config> UpdateTimeManager<T::AccountId> for Pallet<T> {
     }
 }
 
+impl<T: Config> Pallet<T> {
+    /// Cumulative interest charged from `account_id` by fee asset
+    pub fn interest_statements(
+        account_id: &T::AccountId,
+    ) -> Vec<(Asset, InterestStatement<T::Balance>)> {
+        InterestStatements::<T>::iter_prefix(account_id).collect()
+    }
+}
+
 impl<T: Config> Pallet<T> {
     /// used only in genesis
     fn initialize_keys(keys: &[T::AuthorityId]) {
@@ -852,6 +929,7 @@ impl<T: Config> Pallet<T> {
             eq_primitives::DISTRIBUTION_ACC.into_account_truncating();
 
         let assets_to_remove = eq_assets::AssetsToRemove::<T>::get().unwrap_or(Vec::new());
+        let mut nonce = Self::duty_state(authority_index, UnsignedDuty::Rate).nonce;
         for (index, account_id, balances) in T::BalanceGetter::iterate_balances()
             .into_iter()
             .enumerate()
@@ -878,9 +956,11 @@ impl<T: Config> Pallet<T> {
                         &key,
                         block_number,
                         validators_len,
+                        nonce,
                         &account_id,
                         (index as u32) % validators_len == authority_index,
                     )?;
+                    nonce += 1;
                 }
 
                 if T::EqCurrency::can_be_deleted(&account_id).unwrap_or(false) {
@@ -889,9 +969,11 @@ impl<T: Config> Pallet<T> {
                         &key,
                         block_number,
                         validators_len,
+                        nonce,
                         &account_id,
                         (index as u32) % validators_len == authority_index,
                     )?;
+                    nonce += 1;
                 }
             }
 
@@ -909,11 +991,13 @@ impl<T: Config> Pallet<T> {
                         &key,
                         block_number,
                         validators_len,
+                        nonce,
                         &account_id,
                         asset,
                         balance,
                         false,
                     )?;
+                    nonce += 1;
                 }
             }
         }
@@ -926,6 +1010,7 @@ impl<T: Config> Pallet<T> {
         key: &T::AuthorityId,
         block_number: T::BlockNumber,
         validators_len: u32,
+        nonce: u64,
         account_id: &T::AccountId,
         asset: Asset,
         balance: SignedBalance<<T as Config>::Balance>,
@@ -939,6 +1024,7 @@ impl<T: Config> Pallet<T> {
             validators_len,
             block_num: block_number,
             higher_priority,
+            nonce,
         };
         let signature = key.sign(&request.encode()).unwrap();
         let debug_sign = signature.clone();
@@ -973,6 +1059,7 @@ impl<T: Config> Pallet<T> {
         key: &T::AuthorityId,
         block_number: T::BlockNumber,
         validators_len: u32,
+        nonce: u64,
         account_id: &T::AccountId,
         higher_priority: bool,
     ) -> OffchainResult<()> {
@@ -982,6 +1069,7 @@ impl<T: Config> Pallet<T> {
             validators_len,
             block_num: block_number,
             higher_priority,
+            nonce,
         };
 
         let option_signature = key.sign(&call_data.encode());
@@ -1026,6 +1114,7 @@ impl<T: Config> Pallet<T> {
         key: &T::AuthorityId,
         block_number: T::BlockNumber,
         validators_len: u32,
+        nonce: u64,
         account_id: &T::AccountId,
         higher_priority: bool,
     ) -> OffchainResult<()> {
@@ -1035,6 +1124,7 @@ impl<T: Config> Pallet<T> {
             validators_len,
             block_num: block_number,
             higher_priority,
+            nonce,
         };
 
         let option_signature = key.sign(&reinit_data.encode());
@@ -1354,18 +1444,30 @@ impl<T: Config> Pallet<T> {
             },
         };
 
-        Self::charge_treasury_fee(account_id, fee.basic_asset, fee.treasury)?;
+        let treasury = Self::charge_treasury_fee(account_id, fee.basic_asset, fee.treasury)?;
+        if !treasury.is_zero() {
+            T::InterestIncomeReceiver::on_interest_income(fee.basic_asset, treasury);
+        }
         Self::charge_bailsman_fee(account_id, fee.basic_asset, fee.bailsman)?;
-        Self::charge_lender_fee(account_id, fee.basic_asset, fee.lender)?;
+        let lender = Self::charge_lender_fee(account_id, fee.basic_asset, fee.lender)?;
+
+        InterestStatements::<T>::mutate(account_id, fee.basic_asset, |statement| {
+            let total = treasury + fee.bailsman + lender;
+            statement.total = statement.total.saturating_add(total);
+            statement.treasury = statement.treasury.saturating_add(treasury);
+            statement.bailsman = statement.bailsman.saturating_add(fee.bailsman);
+            statement.lender = statement.lender.saturating_add(lender);
+        });
 
         Ok(())
     }
 
+    /// Returns charged amount, nothing is charged if block author is unknown
     fn charge_treasury_fee(
         account_id: &T::AccountId,
         basic_asset: Asset,
         fee_amount: T::Balance,
-    ) -> DispatchResult {
+    ) -> Result<T::Balance, DispatchError> {
         // treasury module account id
         let treasury_account = T::TreasuryModuleId::get().into_account_truncating();
         //
@@ -1385,6 +1487,11 @@ impl<T: Config> Pallet<T> {
                 eq_primitives::TransferReason::InterestFee,
                 false,
             )?;
+            T::TreasuryAccounting::record_inflow(
+                TreasuryFlowCategory::InterestShare,
+                basic_asset,
+                fee_for_account,
+            );
 
             // transferring a fee to the author
             T::EqCurrency::currency_transfer(
@@ -1396,9 +1503,11 @@ impl<T: Config> Pallet<T> {
                 eq_primitives::TransferReason::InterestFee,
                 false,
             )?;
-        }
 
-        Ok(())
+            Ok(fee_amount)
+        } else {
+            Ok(T::Balance::zero())
+        }
     }
 
     fn charge_bailsman_fee(
@@ -1419,12 +1528,13 @@ impl<T: Config> Pallet<T> {
         )
     }
 
+    /// Returns charged amount, fees of assets without lending pool are not charged
     #[frame_support::transactional]
     fn charge_lender_fee(
         account_id: &T::AccountId,
         basic_asset: Asset,
         fees: Vec<(Asset, T::Balance)>,
-    ) -> DispatchResult {
+    ) -> Result<T::Balance, DispatchError> {
         let lending_pool = T::LendingModuleId::get().into_account_truncating();
 
         // we charge lender_fee by asset when lending pool has asset
@@ -1450,7 +1560,7 @@ impl<T: Config> Pallet<T> {
             false,
         )?;
 
-        Ok(())
+        Ok(fee_amount)
     }
 
     fn is_bailsman(account_id: &T::AccountId) -> bool {
@@ -1526,6 +1636,28 @@ impl<T: Config> ValidatorOffchainBatcher<T::AuthorityId, T::BlockNumber, T::Acco
         <pallet_session::Pallet<T>>::validators().len() as u32
     }
 
+    fn duty_state(authority_index: u32, duty: UnsignedDuty) -> DutyState<T::BlockNumber> {
+        UnsignedDutyStates::<T>::get(authority_index, duty)
+    }
+
+    fn validate_duty(
+        authority_index: u32,
+        duty: UnsignedDuty,
+        nonce: u64,
+    ) -> Result<ValidTransaction, InvalidTransaction> {
+        UnsignedDutyStates::<T>::get(authority_index, duty).validate(
+            (duty, authority_index),
+            nonce,
+            frame_system::Pallet::<T>::block_number(),
+            T::MaxUnsignedPerBlock::get(),
+        )
+    }
+
+    fn note_duty(authority_index: u32, duty: UnsignedDuty, nonce: u64) {
+        let now = frame_system::Pallet::<T>::block_number();
+        UnsignedDutyStates::<T>::mutate(authority_index, duty, |state| state.note(nonce, now));
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn set_local_authority_keys(keys: Vec<T::AuthorityId>) {
         Keys::<T>::put(keys);
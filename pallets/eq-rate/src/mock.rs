@@ -88,6 +88,10 @@ impl_opaque_keys! {
 pub type Extrinsic = TestXt<RuntimeCall, ()>;
 
 parameter_types! {
+    pub const MaxNotifications: u32 = 16;
+    pub const PriceRestorePeriod: u64 = 10;
+    pub const MaxPendingLiquidations: u32 = 10;
+    pub const MaxPriorityMarginCallsPerBlock: u32 = 10;
     pub const MinimumPeriod: u64 = 1;
     pub const EpochDuration: u64 = 3;
     pub const ExpectedBlockTime: u64 = 1;
@@ -162,6 +166,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -228,6 +236,7 @@ parameter_types! {
     pub const Period: u64 = 1;
     pub const Offset: u64 = 0;
     pub const UnsignedPriority: u64 = 100;
+    pub const MaxUnsignedPerBlock: u32 = 100;
     pub const MinSurplus: Balance = 1 * 1000_000_000; // 1 usd
     pub const MinTempBalanceUsd: Balance = 20 * 1000_000_000; // 20 usd
     pub const BailsmanModuleId: PalletId = PalletId(*b"eq/bails");
@@ -351,6 +360,8 @@ impl MarginCallManager<AccountId, Balance> for MarginCallManagerMock {
 parameter_types! {
     pub const MaxBailsmenToDistribute: u32 = 1;
     pub const QueueLengthWeightConstant: u32 = 5;
+    pub const RedistributionWeightBudget: Weight = Weight::zero();
+    pub const MaxPriorityRedistributionsPerBlock: u32 = 10;
 }
 
 impl eq_bailsman::Config for Test {
@@ -365,6 +376,7 @@ impl eq_bailsman::Config for Test {
     type UnixTime = ModuleTimestamp;
 
     type PalletId = BailsmanModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
     type Aggregates = ModuleAggregates;
 
     type WeightInfo = ();
@@ -376,6 +388,8 @@ impl eq_bailsman::Config for Test {
     type UnsignedPriority = UnsignedPriority;
     type ValidatorOffchainBatcher = ();
     type QueueLengthWeightConstant = QueueLengthWeightConstant;
+    type RedistributionWeightBudget = RedistributionWeightBudget;
+    type MaxPriorityRedistributionsPerBlock = MaxPriorityRedistributionsPerBlock;
 }
 
 parameter_types! {
@@ -401,6 +415,7 @@ impl Config for Test {
     type BalanceGetter = eq_balances::Pallet<Test>;
     type BalanceRemover = eq_balances::Pallet<Test>;
     type UnsignedPriority = UnsignedPriority;
+    type MaxUnsignedPerBlock = MaxUnsignedPerBlock;
     type MinSurplus = MinSurplus;
     type MinTempBailsman = MinTempBalanceUsd;
     type UnixTime = ModuleTimestamp;
@@ -429,6 +444,8 @@ impl Config for Test {
     type LendingModuleId = LendingModuleId;
     type LendingPoolManager = ();
     type LendingAssetRemoval = ();
+    type InterestIncomeReceiver = ();
+    type TreasuryAccounting = ();
     type RedistributeWeightInfo = ();
 }
 
@@ -739,6 +756,15 @@ impl AssetGetter for AssetGetterMock {
     }
 }
 
+parameter_types! {
+    pub RiskTierMarginMultipliers: eq_primitives::asset::RiskTierParams<EqFixedU128> =
+        eq_primitives::asset::RiskTierParams {
+            tier1: EqFixedU128::one(),
+            tier2: EqFixedU128::one(),
+            tier3: EqFixedU128::one(),
+        };
+}
+
 impl eq_margin_call::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type Balance = Balance;
@@ -751,9 +777,20 @@ impl eq_margin_call::Config for Test {
     type CriticalMargin = CriticalMargin;
     type MaintenancePeriod = MaintenancePeriod;
     type OrderAggregates = ();
+    type DerivativePositions = ();
     type AssetGetter = AssetGetterMock;
     type SubaccountsManager = SubaccountsManagerMock;
+    type MaxNotifications = MaxNotifications;
     type WeightInfo = ();
+    type LendingIsolation = ();
+    type RiskTierMarginMultipliers = RiskTierMarginMultipliers;
+    type EqCurrency = eq_balances::Pallet<Test>;
+    type TreasuryModuleId = TreasuryModuleId;
+    type CompensationOrigin = EnsureRoot<AccountId>;
+    type PriceRestorePeriod = PriceRestorePeriod;
+    type MaxPendingLiquidations = MaxPendingLiquidations;
+    type KeeperOrigin = system::EnsureNever<AccountId>;
+    type MaxPriorityMarginCallsPerBlock = MaxPriorityMarginCallsPerBlock;
 }
 
 pub type ModuleRate = Pallet<Test>;
@@ -311,6 +311,7 @@ fn reinit_sufficient_eq_no_buyout() {
             validators_len: 0,
             block_num: 0,
             higher_priority: false,
+            nonce: 0,
         };
 
         let id: UintAuthorityId = UintAuthorityId::from(acc_id);
@@ -354,6 +355,7 @@ fn reinit_less_than_debt_eq_partial_buyout() {
             validators_len: 0,
             block_num: 0,
             higher_priority: false,
+            nonce: 0,
         };
 
         let id: UintAuthorityId = UintAuthorityId::from(acc_id);
@@ -416,6 +418,7 @@ fn reinit_zero_eq_full_buyout() {
             validators_len: 0,
             block_num: 0,
             higher_priority: false,
+            nonce: 0,
         };
 
         let id: UintAuthorityId = UintAuthorityId::from(acc_id);
@@ -935,3 +938,95 @@ fn asset_removal_non_zero_collateral_and_debt() {
         assert_eq!(deposit.block_num, 1);
     });
 }
+
+#[test]
+fn unsigned_duty_nonces_are_tracked_per_authority() {
+    new_test_ext().execute_with(|| {
+        ModuleSystem::set_block_number(1);
+        assert_ok!(ModuleRate::validate_duty(0, UnsignedDuty::Rate, 0));
+
+        ModuleRate::note_duty(0, UnsignedDuty::Rate, 0);
+        assert_eq!(
+            ModuleRate::validate_duty(0, UnsignedDuty::Rate, 0),
+            Err(InvalidTransaction::Stale)
+        );
+        assert_ok!(ModuleRate::validate_duty(0, UnsignedDuty::Rate, 1));
+        assert_eq!(
+            ModuleRate::validate_duty(0, UnsignedDuty::Rate, MaxUnsignedPerBlock::get() as u64 + 1),
+            Err(InvalidTransaction::Future)
+        );
+
+        // other authorities and duties are not affected
+        assert_ok!(ModuleRate::validate_duty(1, UnsignedDuty::Rate, 0));
+        assert_ok!(ModuleRate::validate_duty(0, UnsignedDuty::DexOrders, 0));
+
+        for nonce in 1..MaxUnsignedPerBlock::get() as u64 {
+            ModuleRate::note_duty(0, UnsignedDuty::Rate, nonce);
+        }
+        let next = MaxUnsignedPerBlock::get() as u64;
+        assert_eq!(
+            ModuleRate::validate_duty(0, UnsignedDuty::Rate, next),
+            Err(InvalidTransaction::ExhaustsResources)
+        );
+
+        ModuleSystem::set_block_number(2);
+        assert_ok!(ModuleRate::validate_duty(0, UnsignedDuty::Rate, next));
+    });
+}
+
+#[test]
+fn interest_statement_accumulates_charged_fees() {
+    new_test_ext().execute_with(|| {
+        let acc_id = 1;
+        let initial_eq_balance = 20_000 * ONE_TOKEN;
+
+        ModuleBalances::make_free_balance_be(
+            &acc_id,
+            asset::EQ,
+            SignedBalance::<Balance>::Positive(initial_eq_balance),
+        );
+        ModuleBalances::make_free_balance_be(
+            &acc_id,
+            asset::BTC,
+            SignedBalance::<Balance>::Negative(1 * ONE_TOKEN),
+        );
+        assert!(ModuleRate::interest_statements(&acc_id).is_empty());
+
+        for day in 1..=2 {
+            let request = OperationRequest::<AccountId, u64> {
+                account: Some(acc_id),
+                authority_index: 0,
+                validators_len: 0,
+                block_num: 0,
+                higher_priority: false,
+                nonce: day - 1,
+            };
+            let signature = UintAuthorityId::from(acc_id)
+                .sign(&request.encode())
+                .unwrap();
+
+            ModuleTimestamp::set_timestamp(day * 24 * 60 * 60 * 1_000);
+            assert_ok!(ModuleRate::reinit(
+                system::RawOrigin::None.into(),
+                request,
+                signature
+            ));
+        }
+
+        let paid = initial_eq_balance
+            - match ModuleBalances::get_balance(&acc_id, &asset::EQ) {
+                SignedBalance::Positive(balance) => balance,
+                SignedBalance::Negative(_) => panic!("unexpected EQ debt"),
+            };
+        let statements = ModuleRate::interest_statements(&acc_id);
+        assert_eq!(statements.len(), 1);
+        let (fee_asset, statement) = &statements[0];
+        assert_eq!(*fee_asset, asset::EQ);
+        assert!(paid > 0);
+        assert_eq!(statement.total, paid);
+        assert_eq!(
+            statement.total,
+            statement.treasury + statement.bailsman + statement.lender
+        );
+    });
+}
@@ -22,19 +22,38 @@ use super::*;
 
 use eq_primitives::balance_number::EqFixedU128;
 use eq_utils::ONE_TOKEN;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
-use frame_support::BoundedVec;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{traits::Hooks, BoundedVec};
 use frame_system::RawOrigin;
-use sp_runtime::{traits::Get, FixedI64, Percent, Permill};
+use sp_runtime::{
+    traits::{Get, One},
+    FixedI64, Percent, Permill,
+};
 use sp_std::vec;
 
 const BALANCE: u128 = 100 * ONE_TOKEN;
+const SEED: u32 = 0;
 
 pub struct Pallet<T: Config>(crate::Pallet<T>);
 
 pub trait Config:
     eq_assets::Config + eq_balances::Config + timestamp::Config + eq_rate::Config + crate::Config
 {
+    /// Starts referendum ongoing for `ConvictionVoting` and returns its index
+    fn start_referendum() -> u32;
+}
+
+fn set_eq_balance<T: Config>(who: &T::AccountId, amount: u128) {
+    eq_balances::Pallet::<T>::make_free_balance_be(
+        who,
+        asset::EQ,
+        SignedBalance::Positive(
+            amount
+                .try_into()
+                .map_err(|_| "balance convertion error")
+                .unwrap(),
+        ),
+    );
 }
 
 fn add_asset_and_deposit<T: Config>(who: &T::AccountId, amount: u128) {
@@ -137,5 +156,126 @@ benchmarks! {
         assert_eq!(Rewards::<T>::get(caller), None);
     }
 
+    restake {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = ONE_TOKEN.try_into().map_err(|_| "balance convertion error").unwrap();
+        add_asset_and_deposit::<T>(&caller, BALANCE);
+        let _ = crate::Pallet::<T>::stake(RawOrigin::Signed(caller.clone()).into(), amount, StakePeriod::One);
+    }: _(RawOrigin::Signed(caller.clone()), 0, StakePeriod::Three)
+    verify {
+        assert_eq!(Stakes::<T>::get(caller)[0].period, StakePeriod::Three);
+    }
+
+    compound_reward {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = ONE_TOKEN.try_into().map_err(|_| "balance convertion error").unwrap();
+        add_asset_and_deposit::<T>(&caller, BALANCE);
+        eq_balances::Pallet::<T>::make_free_balance_be(
+            &T::LiquidityAccount::get(),
+            asset::EQ,
+            SignedBalance::Positive(
+                BALANCE
+                    .try_into()
+                    .map_err(|_| "balance convertion error")
+                    .unwrap(),
+            ),
+        );
+        let _ = crate::Pallet::<T>::stake(RawOrigin::Signed(caller.clone()).into(), amount, StakePeriod::One);
+        let _ = crate::Pallet::<T>::reward(RawOrigin::Root.into(), caller.clone(), amount, 0 as u64).unwrap();
+        let _ = eq_rate::Pallet::<T>::set_now_millis_offset(
+            RawOrigin::Root.into(),
+            (T::RewardsLockPeriod::get().as_secs() * 1000).try_into().map_err(|_| "").unwrap())
+            .unwrap();
+    }: _(RawOrigin::Signed(caller.clone()), Some(0), T::RewardsLockPeriod::get())
+    verify {
+        assert_eq!(Rewards::<T>::get(caller), None);
+    }
+
+    set_emission_schedule {
+        let per_era: T::Balance = ONE_TOKEN.try_into().map_err(|_| "balance convertion error").unwrap();
+    }: _(RawOrigin::Root, per_era, Permill::from_percent(10))
+    verify {
+        assert!(Emission::<T>::get().is_some());
+    }
+
+    emission_step {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = ONE_TOKEN.try_into().map_err(|_| "balance convertion error").unwrap();
+        add_asset_and_deposit::<T>(&caller, BALANCE);
+        eq_balances::Pallet::<T>::make_free_balance_be(
+            &T::TreasuryAccount::get(),
+            asset::EQ,
+            SignedBalance::Positive(
+                BALANCE
+                    .try_into()
+                    .map_err(|_| "balance convertion error")
+                    .unwrap(),
+            ),
+        );
+        let _ = crate::Pallet::<T>::stake(RawOrigin::Signed(caller.clone()).into(), amount, StakePeriod::Twelve);
+        EmissionDistribution::<T>::put(EmissionProgress {
+            stage: EmissionStage::Paying,
+            amount,
+            paid: T::Balance::zero(),
+            total_weight: crate::Pallet::<T>::emission_weight(&Stakes::<T>::get(&caller), 0),
+            accounts: 0,
+            cursor: None,
+        });
+        let step = T::WeightInfo::emission_step();
+        let weight = T::DbWeight::get().reads_writes(3, 2).saturating_add(step);
+    }: {
+        crate::Pallet::<T>::process_emission(weight);
+    }
+    verify {
+        assert_eq!(Rewards::<T>::get(caller).map(|r| r.amount), Some(amount));
+    }
+
+    add_manager {
+        let manager: T::AccountId = account("manager", 0, SEED);
+    }: _(RawOrigin::Root, manager.clone())
+    verify {
+        assert_eq!(PalletManager::<T>::get(), Some(manager));
+    }
+
+    custom_reward {
+        let r in 1..100;
+
+        let amount: T::Balance = ONE_TOKEN.try_into().map_err(|_| "balance convertion error").unwrap();
+        add_asset_and_deposit::<T>(&whitelisted_caller(), BALANCE);
+        set_eq_balance::<T>(&T::LiquidityAccountCustom::get(), BALANCE * (r as u128 + 1));
+        let rewards: Vec<_> = (0..r)
+            .map(|i| (account("receiver", i, SEED), StakePeriod::One, amount))
+            .collect();
+        let last: T::AccountId = account("receiver", r - 1, SEED);
+    }: _(RawOrigin::Root, rewards)
+    verify {
+        assert_eq!(Stakes::<T>::get(last).len(), 1);
+    }
+
+    vote_with_stake {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = ONE_TOKEN.try_into().map_err(|_| "balance convertion error").unwrap();
+        add_asset_and_deposit::<T>(&caller, BALANCE);
+        let _ = crate::Pallet::<T>::stake(RawOrigin::Signed(caller.clone()).into(), amount, StakePeriod::Twelve);
+        let ref_index = T::start_referendum();
+    }: _(RawOrigin::Signed(caller), ref_index, true)
+
+    on_initialize {
+        let a in 0..T::AccountsPerBlock::get();
+
+        let amount = ONE_TOKEN.try_into().map_err(|_| "balance convertion error").unwrap();
+        add_asset_and_deposit::<T>(&whitelisted_caller(), BALANCE);
+        for i in 0..a {
+            let who: T::AccountId = account("staker", i, SEED);
+            set_eq_balance::<T>(&who, BALANCE);
+            let _ = crate::Pallet::<T>::stake(RawOrigin::Signed(who).into(), amount, StakePeriod::One);
+        }
+    }: {
+        crate::Pallet::<T>::on_initialize(T::BlockNumber::one());
+    }
+    verify {
+        assert_eq!(Stakes::<T>::iter_keys().count(), 0);
+    }
+
     // impl_benchmark_test_suite!(crate::Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
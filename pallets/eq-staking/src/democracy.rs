@@ -0,0 +1,222 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Currency adapter for `pallet_democracy` which doesn't lock staked balance twice.
+//!
+//! Democracy lock of an account is reduced by its stakes which stay locked longer than
+//! any democracy lock made now (`ConvictionVoting::max_lock_secs`), so voting with such
+//! stakes requires no additional lock. All other operations are passed to `C`.
+
+use super::*;
+use frame_support::traits::{
+    BalanceStatus, Currency, ExistenceRequirement, LockableCurrency, ReservableCurrency,
+    SignedImbalance, WithdrawReasons,
+};
+use sp_runtime::DispatchError;
+use sp_std::marker::PhantomData;
+
+/// Lock identifier used by `pallet_democracy`
+pub const DEMOCRACY_ID: LockIdentifier = *b"democrac";
+
+pub struct StakeLockAdapter<T, C>(PhantomData<(T, C)>);
+
+impl<T, C> StakeLockAdapter<T, C>
+where
+    T: Config,
+{
+    /// Part of democracy lock `amount` which is not covered by stakes
+    fn uncovered_lock(who: &T::AccountId, amount: T::Balance) -> T::Balance {
+        let until = T::UnixTime::now()
+            .as_secs()
+            .saturating_add(T::ConvictionVoting::max_lock_secs());
+        amount.saturating_sub(Pallet::<T>::staked_until(who, until))
+    }
+}
+
+impl<T, C> Currency<T::AccountId> for StakeLockAdapter<T, C>
+where
+    T: Config,
+    C: Currency<T::AccountId, Balance = T::Balance>,
+{
+    type Balance = T::Balance;
+    type PositiveImbalance = C::PositiveImbalance;
+    type NegativeImbalance = C::NegativeImbalance;
+
+    fn total_balance(who: &T::AccountId) -> Self::Balance {
+        C::total_balance(who)
+    }
+
+    fn can_slash(who: &T::AccountId, value: Self::Balance) -> bool {
+        C::can_slash(who, value)
+    }
+
+    fn total_issuance() -> Self::Balance {
+        C::total_issuance()
+    }
+
+    fn active_issuance() -> Self::Balance {
+        C::active_issuance()
+    }
+
+    fn minimum_balance() -> Self::Balance {
+        C::minimum_balance()
+    }
+
+    fn burn(amount: Self::Balance) -> Self::PositiveImbalance {
+        C::burn(amount)
+    }
+
+    fn issue(amount: Self::Balance) -> Self::NegativeImbalance {
+        C::issue(amount)
+    }
+
+    fn free_balance(who: &T::AccountId) -> Self::Balance {
+        C::free_balance(who)
+    }
+
+    fn ensure_can_withdraw(
+        who: &T::AccountId,
+        amount: Self::Balance,
+        reasons: WithdrawReasons,
+        new_balance: Self::Balance,
+    ) -> DispatchResult {
+        C::ensure_can_withdraw(who, amount, reasons, new_balance)
+    }
+
+    fn transfer(
+        source: &T::AccountId,
+        dest: &T::AccountId,
+        value: Self::Balance,
+        existence_requirement: ExistenceRequirement,
+    ) -> DispatchResult {
+        C::transfer(source, dest, value, existence_requirement)
+    }
+
+    fn slash(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+        C::slash(who, value)
+    }
+
+    fn deposit_into_existing(
+        who: &T::AccountId,
+        value: Self::Balance,
+    ) -> Result<Self::PositiveImbalance, DispatchError> {
+        C::deposit_into_existing(who, value)
+    }
+
+    fn deposit_creating(who: &T::AccountId, value: Self::Balance) -> Self::PositiveImbalance {
+        C::deposit_creating(who, value)
+    }
+
+    fn withdraw(
+        who: &T::AccountId,
+        value: Self::Balance,
+        reasons: WithdrawReasons,
+        liveness: ExistenceRequirement,
+    ) -> Result<Self::NegativeImbalance, DispatchError> {
+        C::withdraw(who, value, reasons, liveness)
+    }
+
+    fn make_free_balance_be(
+        who: &T::AccountId,
+        balance: Self::Balance,
+    ) -> SignedImbalance<Self::Balance, Self::PositiveImbalance> {
+        C::make_free_balance_be(who, balance)
+    }
+}
+
+impl<T, C> ReservableCurrency<T::AccountId> for StakeLockAdapter<T, C>
+where
+    T: Config,
+    C: ReservableCurrency<T::AccountId, Balance = T::Balance>,
+{
+    fn can_reserve(who: &T::AccountId, value: Self::Balance) -> bool {
+        C::can_reserve(who, value)
+    }
+
+    fn reserved_balance(who: &T::AccountId) -> Self::Balance {
+        C::reserved_balance(who)
+    }
+
+    fn reserve(who: &T::AccountId, value: Self::Balance) -> DispatchResult {
+        C::reserve(who, value)
+    }
+
+    fn unreserve(who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+        C::unreserve(who, value)
+    }
+
+    fn slash_reserved(
+        who: &T::AccountId,
+        value: Self::Balance,
+    ) -> (Self::NegativeImbalance, Self::Balance) {
+        C::slash_reserved(who, value)
+    }
+
+    fn repatriate_reserved(
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        value: Self::Balance,
+        status: BalanceStatus,
+    ) -> Result<Self::Balance, DispatchError> {
+        C::repatriate_reserved(slashed, beneficiary, value, status)
+    }
+}
+
+impl<T, C> LockableCurrency<T::AccountId> for StakeLockAdapter<T, C>
+where
+    T: Config,
+    C: LockableCurrency<T::AccountId, Balance = T::Balance>,
+{
+    type Moment = C::Moment;
+
+    type MaxLocks = C::MaxLocks;
+
+    fn set_lock(
+        id: LockIdentifier,
+        who: &T::AccountId,
+        amount: Self::Balance,
+        reasons: WithdrawReasons,
+    ) {
+        if id == DEMOCRACY_ID {
+            match Self::uncovered_lock(who, amount) {
+                uncovered if uncovered.is_zero() => C::remove_lock(id, who),
+                uncovered => C::set_lock(id, who, uncovered, reasons),
+            }
+        } else {
+            C::set_lock(id, who, amount, reasons)
+        }
+    }
+
+    fn extend_lock(
+        id: LockIdentifier,
+        who: &T::AccountId,
+        amount: Self::Balance,
+        reasons: WithdrawReasons,
+    ) {
+        let amount = if id == DEMOCRACY_ID {
+            Self::uncovered_lock(who, amount)
+        } else {
+            amount
+        };
+        C::extend_lock(id, who, amount, reasons)
+    }
+
+    fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
+        C::remove_lock(id, who)
+    }
+}
@@ -22,6 +22,7 @@
 #![forbid(unsafe_code)]
 #![deny(warnings)]
 pub mod benchmarking;
+pub mod democracy;
 #[cfg(test)]
 mod mock;
 mod origin;
@@ -34,22 +35,29 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use eq_primitives::{
     asset,
     balance::{BalanceGetter, EqCurrency, LockGetter},
-    SignedBalance, TransferReason,
+    AccountMigration, ConvictionVoting, SignedBalance, StakedBalance, TransferReason,
 };
 use frame_support::{
-    pallet_prelude::DispatchResult,
+    pallet_prelude::{DispatchResult, Weight},
     storage::bounded_btree_set::BoundedBTreeSet,
     traits::{EitherOfDiverse, ExistenceRequirement, LockIdentifier, UnixTime},
 };
-use sp_runtime::traits::{
-    AtLeast32BitUnsigned, CheckedAdd, MaybeSerializeDeserialize, Member, Saturating, Zero,
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational_with_rounding,
+    traits::{
+        AtLeast32BitUnsigned, CheckedAdd, MaybeSerializeDeserialize, Member, Saturating, Zero,
+    },
+    ArithmeticError, DispatchError, Permill, Rounding, TransactionOutcome,
 };
 use sp_std::{
     convert::{TryFrom, TryInto},
     vec::Vec,
 };
 
-const STAKING_ID: LockIdentifier = *b"staking ";
+pub const STAKING_ID: LockIdentifier = *b"staking ";
+
+/// Conviction classes of `ConvictionVoting` with number of lock periods they require
+const CONVICTION_LOCK_PERIODS: [(u8, u64); 6] = [(1, 1), (2, 2), (3, 4), (4, 8), (5, 16), (6, 32)];
 
 pub use pallet::*;
 
@@ -103,6 +111,13 @@ pub mod pallet {
         type MaxRewardExternalIdsCount: Get<u32>;
         #[pallet::constant]
         type AccountsPerBlock: Get<u32>;
+        /// Used to vote in democracy with staked balance
+        type ConvictionVoting: ConvictionVoting<Self::AccountId, Self::Balance>;
+        /// Account funding scheduled emission rewards
+        type TreasuryAccount: Get<Self::AccountId>;
+        /// Duration of emission era in seconds
+        #[pallet::constant]
+        type EmissionEraDuration: Get<u64>;
     }
 
     #[pallet::storage]
@@ -125,6 +140,15 @@ pub mod pallet {
     pub type RewardExternalIds<T: Config> =
         StorageValue<_, BoundedBTreeSet<u64, T::MaxRewardExternalIdsCount>, ValueQuery>;
 
+    /// Schedule of rewards emission from the treasury
+    #[pallet::storage]
+    pub type Emission<T: Config> = StorageValue<_, EmissionSchedule<T::Balance>, OptionQuery>;
+
+    /// Distribution of the current era emission, processed in `on_idle`
+    #[pallet::storage]
+    pub type EmissionDistribution<T: Config> =
+        StorageValue<_, EmissionProgress<T::AccountId, T::Balance>, OptionQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -140,6 +164,31 @@ pub mod pallet {
             amount: T::Balance,
             external_id: u64,
         },
+        /// Stake was rolled into a new lock period without unlocking
+        Restaked {
+            who: T::AccountId,
+            amount: T::Balance,
+            period: StakePeriod,
+        },
+        /// Unlocked reward was added to stakes
+        Compounded {
+            who: T::AccountId,
+            amount: T::Balance,
+            period: StakePeriod,
+        },
+        /// Staked balance was used to vote in democracy
+        StakeVoted {
+            who: T::AccountId,
+            ref_index: u32,
+            balance: T::Balance,
+            conviction: u8,
+        },
+        /// Emission schedule was set, zero `per_era` removes the schedule
+        EmissionScheduleSet { per_era: T::Balance, decay: Permill },
+        /// Emission era started, `amount` is distributed over stakers
+        EmissionEraStarted { amount: T::Balance },
+        /// Era emission was distributed
+        EmissionDistributed { amount: T::Balance, accounts: u32 },
     }
 
     #[pallet::error]
@@ -156,20 +205,32 @@ pub mod pallet {
         CustomReward(u8),
         /// Error while adding reward external ID
         UnableToAddRewardExternalId,
+        /// New lock period ends before the current one
+        LockPeriodShortened,
+        /// Referendum is not ongoing
+        ReferendumNotOngoing,
+        /// No stake is locked long enough to vote with conviction
+        NoStakeForConviction,
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+            let mut removed = 0;
             Stakes::<T>::iter_keys()
                 .take(T::AccountsPerBlock::get() as usize)
                 .for_each(|account| {
                     T::EqCurrency::set_lock(STAKING_ID, &account, T::Balance::zero());
                     Stakes::<T>::remove(account.clone());
                     Rewards::<T>::remove(account);
+                    removed += 1;
                 });
 
-            T::DbWeight::get().writes(T::AccountsPerBlock::get() as u64)
+            T::WeightInfo::on_initialize(removed)
+        }
+
+        fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::process_emission(remaining_weight)
         }
     }
 
@@ -217,50 +278,7 @@ pub mod pallet {
                     Ok(())
                 })?;
 
-                let now = T::UnixTime::now().as_secs();
-                let _ = Rewards::<T>::mutate(who.clone(), |maybe_stake| -> DispatchResult {
-                    match maybe_stake {
-                        Some(stake) if now >= stake.start + stake.period.as_secs() => {
-                            // unstake and new stake
-                            let _ = Self::unlock_stake(who.clone(), *stake)?;
-                            *maybe_stake = Some(Stake {
-                                start: now,
-                                amount,
-                                period: T::RewardsLockPeriod::get(),
-                            });
-                        }
-                        Some(stake) => {
-                            let new_stake_amount = stake
-                                .amount
-                                .checked_add(&amount)
-                                .ok_or(DispatchError::Arithmetic(ArithmeticError::Overflow))?;
-                            (*stake).amount = new_stake_amount;
-                        }
-                        None => {
-                            *maybe_stake = Some(Stake {
-                                start: now,
-                                amount,
-                                period: T::RewardsLockPeriod::get(),
-                            });
-                        }
-                    };
-
-                    let _ = T::EqCurrency::currency_transfer(
-                        &T::LiquidityAccount::get(),
-                        &who,
-                        asset::EQ,
-                        amount,
-                        ExistenceRequirement::AllowDeath,
-                        TransferReason::Common,
-                        true,
-                    )?;
-                    let new_stake_lock = T::LockGetter::get_lock(who.clone(), STAKING_ID)
-                        .checked_add(&amount)
-                        .ok_or(DispatchError::Arithmetic(ArithmeticError::Overflow))?;
-                    T::EqCurrency::extend_lock(STAKING_ID, &who, new_stake_lock);
-
-                    Ok(())
-                })?;
+                Self::do_reward(&who, amount, &T::LiquidityAccount::get())?;
 
                 Self::deposit_event(Event::Rewarded {
                     who,
@@ -273,7 +291,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(2)]
-        #[pallet::weight(T::DbWeight::get().writes(1).ref_time())]
+        #[pallet::weight(T::WeightInfo::add_manager())]
         pub fn add_manager(
             origin: OriginFor<T>,
             manager: T::AccountId,
@@ -318,7 +336,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(4)]
-        #[pallet::weight(T::WeightInfo::stake() * rewards.len() as u64)]
+        #[pallet::weight(T::WeightInfo::custom_reward(rewards.len() as u32))]
         pub fn custom_reward(
             origin: OriginFor<T>,
             rewards: Vec<(T::AccountId, StakePeriod, T::Balance)>,
@@ -350,10 +368,253 @@ pub mod pallet {
 
             Ok(Pays::No.into())
         }
+
+        /// Roll stake with `stake_index` into a new lock `period` starting now without unlocking.
+        /// New lock period must not end before the current one.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::restake())]
+        pub fn restake(
+            origin: OriginFor<T>,
+            stake_index: u32,
+            period: StakePeriod,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let now = T::UnixTime::now().as_secs();
+
+            let amount = Stakes::<T>::try_mutate(
+                who.clone(),
+                |stakes| -> Result<T::Balance, DispatchError> {
+                    let stake = stakes
+                        .get_mut(stake_index as usize)
+                        .ok_or(Error::<T>::StakeNotFound)?;
+                    Self::roll_stake(stake, now, period)?;
+
+                    Ok(stake.amount)
+                },
+            )?;
+
+            Self::deposit_event(Event::Restaked {
+                who,
+                amount,
+                period,
+            });
+
+            Ok(())
+        }
+
+        /// Move rewards with ended lock period into stake with `stake_index` (rolling it into `period`)
+        /// or into a new stake for `period` if `mb_stake_index` is none.
+        /// Rewarded funds stay locked.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::compound_reward())]
+        pub fn compound_reward(
+            origin: OriginFor<T>,
+            mb_stake_index: Option<u32>,
+            period: StakePeriod,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let now = T::UnixTime::now().as_secs();
+
+            let reward = Rewards::<T>::get(who.clone()).ok_or(Error::<T>::StakeNotFound)?;
+            frame_support::ensure!(
+                now >= reward.start + reward.period.as_secs(),
+                Error::<T>::LockPeriodNotEnded
+            );
+
+            Stakes::<T>::try_mutate(who.clone(), |stakes| -> DispatchResult {
+                match mb_stake_index {
+                    Some(stake_index) => {
+                        let stake = stakes
+                            .get_mut(stake_index as usize)
+                            .ok_or(Error::<T>::StakeNotFound)?;
+                        Self::roll_stake(stake, now, period)?;
+                        stake.amount = stake
+                            .amount
+                            .checked_add(&reward.amount)
+                            .ok_or(DispatchError::Arithmetic(ArithmeticError::Overflow))?;
+                    }
+                    None => stakes
+                        .try_push(Stake {
+                            period,
+                            start: now,
+                            amount: reward.amount,
+                        })
+                        .map_err(|_| Error::<T>::MaxStakesNumberReached)?,
+                };
+
+                Ok(())
+            })?;
+            Rewards::<T>::remove(who.clone());
+
+            Self::deposit_event(Event::Compounded {
+                who,
+                amount: reward.amount,
+                period,
+            });
+
+            Ok(())
+        }
+
+        /// Vote in referendum `ref_index` with staked balance. Conviction is derived from stakes
+        /// lock periods: stakes are counted for the conviction if they stay locked until
+        /// the end of its democracy lock, so no additional lock is required.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::vote_with_stake())]
+        pub fn vote_with_stake(origin: OriginFor<T>, ref_index: u32, aye: bool) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let now = T::UnixTime::now().as_secs();
+
+            let remaining = T::ConvictionVoting::referendum_remaining_secs(ref_index)
+                .ok_or(Error::<T>::ReferendumNotOngoing)?;
+            let (balance, conviction) = Self::stake_conviction(&who, now.saturating_add(remaining))
+                .ok_or(Error::<T>::NoStakeForConviction)?;
+
+            T::ConvictionVoting::vote(&who, ref_index, aye, balance, conviction)?;
+
+            Self::deposit_event(Event::StakeVoted {
+                who,
+                ref_index,
+                balance,
+                conviction,
+            });
+
+            Ok(())
+        }
+
+        /// Set emission of `per_era` rewards from the treasury, distributed at era boundaries
+        /// over active stakes weighted by lock periods. `per_era` is reduced by `decay`
+        /// after every era. Zero `per_era` removes the schedule.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::set_emission_schedule())]
+        pub fn set_emission_schedule(
+            origin: OriginFor<T>,
+            per_era: T::Balance,
+            decay: Permill,
+        ) -> DispatchResult {
+            T::RewardManagementOrigin::ensure_origin(origin)?;
+
+            if per_era.is_zero() {
+                Emission::<T>::kill();
+            } else {
+                let next_era = T::UnixTime::now()
+                    .as_secs()
+                    .saturating_add(T::EmissionEraDuration::get());
+                Emission::<T>::put(EmissionSchedule {
+                    per_era,
+                    decay,
+                    next_era,
+                });
+            }
+
+            Self::deposit_event(Event::EmissionScheduleSet { per_era, decay });
+
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> StakedBalance<T::AccountId, T::Balance> for Pallet<T> {
+    fn staked_balance(who: &T::AccountId) -> T::Balance {
+        Stakes::<T>::get(who)
+            .iter()
+            .fold(T::Balance::zero(), |acc, stake| {
+                acc.saturating_add(stake.amount)
+            })
+    }
+}
+
+impl<T: Config> AccountMigration<T::AccountId> for Pallet<T> {
+    fn can_migrate(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        let stakes_count = Stakes::<T>::decode_len(from)
+            .unwrap_or_default()
+            .saturating_add(Stakes::<T>::decode_len(to).unwrap_or_default());
+        frame_support::ensure!(
+            stakes_count <= T::MaxStakesCount::get() as usize,
+            Error::<T>::MaxStakesNumberReached
+        );
+        Ok(())
+    }
+
+    /// Stakes are moved as is, `STAKING_ID` lock is moved with other balance locks
+    fn migrate(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        let from_stakes = Stakes::<T>::take(from);
+        if !from_stakes.is_empty() {
+            Stakes::<T>::try_mutate(to, |stakes| -> DispatchResult {
+                for stake in from_stakes {
+                    stakes
+                        .try_push(stake)
+                        .map_err(|_| Error::<T>::MaxStakesNumberReached)?;
+                }
+                Ok(())
+            })?;
+        }
+
+        if let Some(reward) = Rewards::<T>::take(from) {
+            Rewards::<T>::mutate(to, |maybe_reward| {
+                *maybe_reward = Some(match maybe_reward.take() {
+                    // merged reward is locked until the later of two ends
+                    Some(to_reward) => {
+                        let (mut longer, shorter) = if to_reward.start + to_reward.period.as_secs()
+                            >= reward.start + reward.period.as_secs()
+                        {
+                            (to_reward, reward)
+                        } else {
+                            (reward, to_reward)
+                        };
+                        longer.amount = longer.amount.saturating_add(shorter.amount);
+                        longer
+                    }
+                    None => reward,
+                });
+            });
+        }
+
+        Ok(())
     }
 }
 
 impl<T: Config> Pallet<T> {
+    /// Sum of `who` stakes which stay locked at least until `until`
+    pub fn staked_until(who: &T::AccountId, until: u64) -> T::Balance {
+        Stakes::<T>::get(who)
+            .iter()
+            .filter(|stake| stake.start + stake.period.as_secs() >= until)
+            .fold(T::Balance::zero(), |acc, stake| {
+                acc.saturating_add(stake.amount)
+            })
+    }
+
+    /// Returns staked balance and conviction giving the most votes for a referendum
+    /// ending at `referendum_end`
+    pub fn stake_conviction(who: &T::AccountId, referendum_end: u64) -> Option<(T::Balance, u8)> {
+        let lock_period = T::ConvictionVoting::lock_period_secs();
+        let mut best: Option<(T::Balance, u8, T::Balance)> = None;
+
+        for (conviction, lock_periods) in CONVICTION_LOCK_PERIODS {
+            let unlock_at = referendum_end.saturating_add(lock_period.saturating_mul(lock_periods));
+            let balance = Self::staked_until(who, unlock_at);
+            let votes = balance.saturating_mul(conviction.into());
+            if !balance.is_zero() && best.map_or(true, |(_, _, best_votes)| votes > best_votes) {
+                best = Some((balance, conviction, votes));
+            }
+        }
+
+        best.map(|(balance, conviction, _)| (balance, conviction))
+    }
+
+    /// Restarts `stake` at `now` with new `period`.
+    /// Fails if the new lock period ends before the current one.
+    fn roll_stake(stake: &mut Stake<T::Balance>, now: u64, period: StakePeriod) -> DispatchResult {
+        frame_support::ensure!(
+            now + period.as_secs() >= stake.start + stake.period.as_secs(),
+            Error::<T>::LockPeriodShortened
+        );
+
+        stake.start = now;
+        stake.period = period;
+        Ok(())
+    }
+
     fn unlock_stake(who: T::AccountId, stake: Stake<T::Balance>) -> DispatchResult {
         let Stake {
             start,
@@ -410,6 +671,193 @@ impl<T: Config> Pallet<T> {
 
         Ok(())
     }
+
+    /// Transfers `amount` from `source` to `who` and locks it as reward
+    fn do_reward(who: &T::AccountId, amount: T::Balance, source: &T::AccountId) -> DispatchResult {
+        let now = T::UnixTime::now().as_secs();
+        let _ = Rewards::<T>::mutate(who.clone(), |maybe_stake| -> DispatchResult {
+            match maybe_stake {
+                Some(stake) if now >= stake.start + stake.period.as_secs() => {
+                    // unstake and new stake
+                    let _ = Self::unlock_stake(who.clone(), *stake)?;
+                    *maybe_stake = Some(Stake {
+                        start: now,
+                        amount,
+                        period: T::RewardsLockPeriod::get(),
+                    });
+                }
+                Some(stake) => {
+                    let new_stake_amount = stake
+                        .amount
+                        .checked_add(&amount)
+                        .ok_or(ArithmeticError::Overflow)?;
+                    (*stake).amount = new_stake_amount;
+                }
+                None => {
+                    *maybe_stake = Some(Stake {
+                        start: now,
+                        amount,
+                        period: T::RewardsLockPeriod::get(),
+                    });
+                }
+            };
+
+            let _ = T::EqCurrency::currency_transfer(
+                source,
+                who,
+                asset::EQ,
+                amount,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::Common,
+                true,
+            )?;
+            let new_stake_lock = T::LockGetter::get_lock(who.clone(), STAKING_ID)
+                .checked_add(&amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            T::EqCurrency::extend_lock(STAKING_ID, who, new_stake_lock);
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    /// Emission weight of `stakes` locked at `now`
+    fn emission_weight(stakes: &[Stake<T::Balance>], now: u64) -> u128 {
+        stakes
+            .iter()
+            .filter(|stake| now < stake.start + stake.period.as_secs())
+            .fold(0u128, |acc, stake| {
+                let amount: eq_primitives::balance::Balance = stake.amount.into();
+                acc.saturating_add(amount.saturating_mul(stake.period.emission_multiplier()))
+            })
+    }
+
+    /// Starts distribution of era emission if the era has come
+    fn start_emission_era(now: u64) -> Option<EmissionProgress<T::AccountId, T::Balance>> {
+        let mut schedule = Emission::<T>::get()?;
+        if now < schedule.next_era {
+            return None;
+        }
+
+        let amount = schedule.per_era;
+        schedule.per_era = amount.saturating_sub(schedule.decay * amount);
+        schedule.next_era = schedule
+            .next_era
+            .saturating_add(T::EmissionEraDuration::get());
+        if schedule.next_era <= now {
+            // missed eras are skipped
+            schedule.next_era = now.saturating_add(T::EmissionEraDuration::get());
+        }
+        Emission::<T>::put(schedule);
+
+        Self::deposit_event(Event::EmissionEraStarted { amount });
+
+        Some(EmissionProgress {
+            stage: EmissionStage::Summing,
+            amount,
+            paid: T::Balance::zero(),
+            total_weight: 0,
+            accounts: 0,
+            cursor: None,
+        })
+    }
+
+    /// Pays `who` share of era emission, nothing is changed on failure
+    fn pay_emission_share(
+        who: &T::AccountId,
+        weight: u128,
+        progress: &EmissionProgress<T::AccountId, T::Balance>,
+    ) -> Result<T::Balance, DispatchError> {
+        let share = multiply_by_rational_with_rounding(
+            progress.amount.into(),
+            weight,
+            progress.total_weight,
+            Rounding::Down,
+        )
+        .ok_or(ArithmeticError::Overflow)?;
+        let reward = T::Balance::try_from(share)
+            .map_err(|_| ArithmeticError::Overflow)?
+            .min(progress.amount.saturating_sub(progress.paid));
+        if reward.is_zero() {
+            return Ok(reward);
+        }
+
+        frame_support::storage::with_transaction(|| {
+            match Self::do_reward(who, reward, &T::TreasuryAccount::get()) {
+                Ok(()) => TransactionOutcome::Commit(Ok(reward)),
+                Err(err) => TransactionOutcome::Rollback(Err(err)),
+            }
+        })
+    }
+
+    /// Distributes era emission within `remaining_weight`. Stakes are iterated twice:
+    /// first to sum up weights of all stakes, then to pay proportional shares.
+    fn process_emission(remaining_weight: Weight) -> Weight {
+        let step_weight = T::WeightInfo::emission_step();
+        let mut consumed = T::DbWeight::get().reads_writes(3, 2);
+        if consumed
+            .saturating_add(step_weight)
+            .any_gt(remaining_weight)
+        {
+            return Weight::zero();
+        }
+
+        let now = T::UnixTime::now().as_secs();
+        let mut progress =
+            match EmissionDistribution::<T>::get().or_else(|| Self::start_emission_era(now)) {
+                Some(progress) => progress,
+                None => return consumed,
+            };
+
+        let mut stakes = match &progress.cursor {
+            Some(last) => Stakes::<T>::iter_from(Stakes::<T>::hashed_key_for(last)),
+            None => Stakes::<T>::iter(),
+        };
+        while !consumed
+            .saturating_add(step_weight)
+            .any_gt(remaining_weight)
+        {
+            consumed = consumed.saturating_add(step_weight);
+
+            let (who, account_stakes) = match stakes.next() {
+                Some(next) => next,
+                None if progress.stage == EmissionStage::Summing => {
+                    progress.stage = EmissionStage::Paying;
+                    progress.cursor = None;
+                    stakes = Stakes::<T>::iter();
+                    continue;
+                }
+                None => {
+                    EmissionDistribution::<T>::kill();
+                    Self::deposit_event(Event::EmissionDistributed {
+                        amount: progress.paid,
+                        accounts: progress.accounts,
+                    });
+                    return consumed;
+                }
+            };
+
+            let weight = Self::emission_weight(&account_stakes, now);
+            match progress.stage {
+                EmissionStage::Summing => {
+                    progress.total_weight = progress.total_weight.saturating_add(weight);
+                }
+                EmissionStage::Paying if weight != 0 => {
+                    // failed account is skipped, its share stays in the treasury
+                    if let Ok(reward) = Self::pay_emission_share(&who, weight, &progress) {
+                        progress.paid = progress.paid.saturating_add(reward);
+                        progress.accounts += 1;
+                    }
+                }
+                EmissionStage::Paying => {}
+            }
+            progress.cursor = Some(who);
+        }
+
+        EmissionDistribution::<T>::put(progress);
+        consumed
+    }
 }
 
 /// Possible lock periods in months
@@ -445,6 +893,20 @@ impl StakePeriod {
             Self::TwentyFour => 24 * MONTH_IN_SECS,
         }
     }
+
+    /// Multiplier of stake weight in emission distribution, percents
+    fn emission_multiplier(&self) -> u128 {
+        match self {
+            Self::One => 100,
+            Self::Two => 110,
+            Self::Three => 120,
+            Self::Six => 150,
+            Self::Twelve => 200,
+            Self::Sixteen => 230,
+            Self::Eighteen => 250,
+            Self::TwentyFour => 300,
+        }
+    }
 }
 
 #[derive(
@@ -456,6 +918,45 @@ pub struct Stake<Balance> {
     amount: Balance,
 }
 
+/// Schedule of staking rewards emission from the treasury
+#[derive(
+    Copy, Debug, Decode, Encode, Clone, Eq, PartialEq, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub struct EmissionSchedule<Balance> {
+    /// Amount distributed in the next era
+    pub per_era: Balance,
+    /// Part of `per_era` cut after every era
+    pub decay: Permill,
+    /// Timestamp of the next era start
+    pub next_era: u64,
+}
+
+#[derive(
+    Copy, Debug, Decode, Encode, Clone, Eq, PartialEq, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub enum EmissionStage {
+    /// Summing up weights of active stakes
+    Summing,
+    /// Paying shares of era emission
+    Paying,
+}
+
+/// Distribution of era emission over stakers
+#[derive(Debug, Decode, Encode, Clone, Eq, PartialEq, scale_info::TypeInfo, MaxEncodedLen)]
+pub struct EmissionProgress<AccountId, Balance> {
+    pub stage: EmissionStage,
+    /// Era emission
+    pub amount: Balance,
+    /// Already paid part of `amount`
+    pub paid: Balance,
+    /// Sum of weighted active stakes
+    pub total_weight: u128,
+    /// Number of rewarded accounts
+    pub accounts: u32,
+    /// Last processed account of the stage
+    pub cursor: Option<AccountId>,
+}
+
 #[derive(Encode, Decode, scale_info::TypeInfo)]
 #[repr(u8)]
 pub enum CustomRewardError {
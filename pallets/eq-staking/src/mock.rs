@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use core::marker::PhantomData;
+use std::cell::RefCell;
 
 use super::*;
 use crate as eq_staking;
@@ -259,6 +260,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = XcmRouterErrMock;
     type XcmToFee = XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = UniversalLocationMock;
     type OrderAggregates = ();
@@ -283,6 +288,7 @@ impl Get<AccountId> for TreasuryAccount {
 parameter_types! {
     pub const MaxRewardExternalIdsCount: u32 = 1000;
     pub const AccountsPerBlock: u32 = 2;
+    pub const EmissionEraDuration: u64 = 60;
 }
 
 impl eq_staking::Config for Test {
@@ -300,6 +306,63 @@ impl eq_staking::Config for Test {
     type WeightInfo = ();
     type MaxRewardExternalIdsCount = MaxRewardExternalIdsCount;
     type AccountsPerBlock = AccountsPerBlock;
+    type ConvictionVoting = ConvictionVotingMock;
+    type TreasuryAccount = TreasuryAccount;
+    type EmissionEraDuration = EmissionEraDuration;
+}
+
+pub type DemocracyCurrency = crate::democracy::StakeLockAdapter<
+    Test,
+    eq_primitives::balance_adapter::BalanceAdapter<Balance, EqBalances, MainAsset>,
+>;
+
+thread_local! {
+    static REFERENDUM_REMAINING: RefCell<Option<u64>> = RefCell::new(None);
+    /// (who, ref_index, aye, balance, conviction)
+    static VOTES: RefCell<Vec<(AccountId, u32, bool, Balance, u8)>> = RefCell::new(Vec::new());
+}
+
+pub struct ConvictionVotingMock;
+
+impl ConvictionVotingMock {
+    pub const LOCK_PERIOD: u64 = 100;
+    pub const VOTING_PERIOD: u64 = 100;
+
+    pub fn set_referendum_remaining(remaining: Option<u64>) {
+        REFERENDUM_REMAINING.with(|r| *r.borrow_mut() = remaining);
+    }
+
+    pub fn votes() -> Vec<(AccountId, u32, bool, Balance, u8)> {
+        VOTES.with(|v| v.borrow().clone())
+    }
+}
+
+impl ConvictionVoting<AccountId, Balance> for ConvictionVotingMock {
+    fn lock_period_secs() -> u64 {
+        Self::LOCK_PERIOD
+    }
+
+    fn max_lock_secs() -> u64 {
+        Self::VOTING_PERIOD + 32 * Self::LOCK_PERIOD
+    }
+
+    fn referendum_remaining_secs(_ref_index: u32) -> Option<u64> {
+        REFERENDUM_REMAINING.with(|r| *r.borrow())
+    }
+
+    fn vote(
+        who: &AccountId,
+        ref_index: u32,
+        aye: bool,
+        balance: Balance,
+        conviction: u8,
+    ) -> DispatchResult {
+        VOTES.with(|v| {
+            v.borrow_mut()
+                .push((*who, ref_index, aye, balance, conviction))
+        });
+        Ok(())
+    }
 }
 
 pub const ACCOUNT_1: AccountId = 1234;
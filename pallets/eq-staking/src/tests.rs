@@ -18,16 +18,19 @@
 
 use core::convert::TryInto;
 
-use crate::{mock::*, Error, Pallet, Rewards, Stake, StakePeriod, Stakes, STAKING_ID};
+use crate::{
+    mock::*, Emission, EmissionDistribution, EmissionSchedule, Error, Pallet, Rewards, Stake,
+    StakePeriod, Stakes, STAKING_ID,
+};
 use eq_primitives::{
     asset,
     balance::{BalanceGetter, EqCurrency, LockGetter},
-    SignedBalance,
+    AccountMigration, SignedBalance,
 };
-use frame_support::pallet_prelude::Hooks;
-use frame_support::{assert_noop, assert_ok, BoundedVec};
+use frame_support::pallet_prelude::{Hooks, Weight};
+use frame_support::{assert_noop, assert_ok, traits::Get, BoundedVec};
 use frame_system::RawOrigin;
-use sp_runtime::traits::Zero;
+use sp_runtime::{traits::Zero, DispatchError, Permill};
 
 #[test]
 fn stake_ok() {
@@ -463,6 +466,178 @@ fn unlock_rewards_err() {
     });
 }
 
+#[test]
+fn restake_ok() {
+    new_test_ext().execute_with(|| {
+        let stake = 500 * ONE_TOKEN;
+        assert_ok!(Pallet::<Test>::stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            stake,
+            StakePeriod::One
+        ));
+        let lock_before = eq_balances::Pallet::<Test>::get_lock(ACCOUNT_1, STAKING_ID);
+
+        let now = StakePeriod::One.as_secs();
+        timestamp::Pallet::<Test>::set_timestamp(now * 1000);
+        assert_ok!(Pallet::<Test>::restake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            0,
+            StakePeriod::Three
+        ));
+
+        assert_eq!(
+            Stakes::<Test>::get(ACCOUNT_1)[0],
+            Stake {
+                period: StakePeriod::Three,
+                start: now,
+                amount: stake,
+            }
+        );
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_lock(ACCOUNT_1, STAKING_ID),
+            lock_before
+        );
+        assert_noop!(
+            Pallet::<Test>::unlock(RuntimeOrigin::signed(ACCOUNT_1), Some(0)),
+            Error::<Test>::LockPeriodNotEnded
+        );
+    });
+}
+
+#[test]
+fn restake_err() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Pallet::<Test>::restake(RuntimeOrigin::signed(ACCOUNT_1), 0, StakePeriod::One),
+            Error::<Test>::StakeNotFound
+        );
+
+        assert_ok!(Pallet::<Test>::stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            500 * ONE_TOKEN,
+            StakePeriod::Six
+        ));
+        timestamp::Pallet::<Test>::set_timestamp(StakePeriod::One.as_secs() * 1000);
+        assert_noop!(
+            Pallet::<Test>::restake(RuntimeOrigin::signed(ACCOUNT_1), 0, StakePeriod::Three),
+            Error::<Test>::LockPeriodShortened
+        );
+    });
+}
+
+#[test]
+fn compound_reward_ok() {
+    new_test_ext().execute_with(|| {
+        let stake = 500 * ONE_TOKEN;
+        let reward = ONE_TOKEN;
+        for acc in [ACCOUNT_1, ACCOUNT_2] {
+            assert_ok!(Pallet::<Test>::stake(
+                RuntimeOrigin::signed(acc),
+                stake,
+                StakePeriod::One
+            ));
+        }
+        assert_ok!(Pallet::<Test>::reward(
+            RawOrigin::Root.into(),
+            ACCOUNT_1,
+            reward,
+            EXTERNAL_ID,
+        ));
+        assert_ok!(Pallet::<Test>::reward(
+            RawOrigin::Root.into(),
+            ACCOUNT_2,
+            reward,
+            EXTERNAL_ID + 1,
+        ));
+        let lock_before = eq_balances::Pallet::<Test>::get_lock(ACCOUNT_1, STAKING_ID);
+
+        let now = RewardsLockPeriod::get().as_secs();
+        timestamp::Pallet::<Test>::set_timestamp(now * 1000);
+
+        // into existing stake
+        assert_ok!(Pallet::<Test>::compound_reward(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            Some(0),
+            StakePeriod::Three
+        ));
+        assert!(Rewards::<Test>::get(ACCOUNT_1).is_none());
+        assert_eq!(
+            Stakes::<Test>::get(ACCOUNT_1).into_inner(),
+            vec![Stake {
+                period: StakePeriod::Three,
+                start: now,
+                amount: stake + reward,
+            }]
+        );
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_lock(ACCOUNT_1, STAKING_ID),
+            lock_before
+        );
+
+        // into new stake
+        assert_ok!(Pallet::<Test>::compound_reward(
+            RuntimeOrigin::signed(ACCOUNT_2),
+            None,
+            StakePeriod::Two
+        ));
+        assert!(Rewards::<Test>::get(ACCOUNT_2).is_none());
+        assert_eq!(
+            Stakes::<Test>::get(ACCOUNT_2)[1],
+            Stake {
+                period: StakePeriod::Two,
+                start: now,
+                amount: reward,
+            }
+        );
+    });
+}
+
+#[test]
+fn compound_reward_err() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Pallet::<Test>::compound_reward(
+                RuntimeOrigin::signed(ACCOUNT_1),
+                None,
+                StakePeriod::One
+            ),
+            Error::<Test>::StakeNotFound
+        );
+
+        assert_ok!(Pallet::<Test>::reward(
+            RawOrigin::Root.into(),
+            ACCOUNT_1,
+            ONE_TOKEN,
+            EXTERNAL_ID,
+        ));
+        assert_noop!(
+            Pallet::<Test>::compound_reward(
+                RuntimeOrigin::signed(ACCOUNT_1),
+                None,
+                StakePeriod::One
+            ),
+            Error::<Test>::LockPeriodNotEnded
+        );
+
+        for _ in 0..MaxStakesCount::get() {
+            assert_ok!(Pallet::<Test>::stake(
+                RuntimeOrigin::signed(ACCOUNT_1),
+                ONE_TOKEN,
+                StakePeriod::One
+            ));
+        }
+        timestamp::Pallet::<Test>::set_timestamp(RewardsLockPeriod::get().as_secs() * 1000);
+        assert_noop!(
+            Pallet::<Test>::compound_reward(
+                RuntimeOrigin::signed(ACCOUNT_1),
+                None,
+                StakePeriod::One
+            ),
+            Error::<Test>::MaxStakesNumberReached
+        );
+    });
+}
+
 #[test]
 fn on_initialize_remove_stake() {
     new_test_ext().execute_with(|| {
@@ -553,3 +728,248 @@ fn on_initialize_remove_correct_number_of_locks() {
         }
     });
 }
+
+#[test]
+fn vote_with_stake_ok() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(EqStaking::stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            500 * ONE_TOKEN,
+            StakePeriod::Three
+        ));
+        assert_ok!(EqStaking::stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            300 * ONE_TOKEN,
+            StakePeriod::Twelve
+        ));
+        ConvictionVotingMock::set_referendum_remaining(Some(100));
+
+        // Three months stake is unlocked before Locked6x lock ends:
+        // 800 * 5 votes with Locked5x are more than 300 * 6 votes with Locked6x
+        assert_ok!(EqStaking::vote_with_stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            7,
+            true
+        ));
+        assert_eq!(
+            ConvictionVotingMock::votes(),
+            vec![(ACCOUNT_1, 7, true, 800 * ONE_TOKEN, 5)]
+        );
+    });
+}
+
+#[test]
+fn vote_with_stake_err() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EqStaking::vote_with_stake(RuntimeOrigin::signed(ACCOUNT_1), 0, true),
+            Error::<Test>::ReferendumNotOngoing
+        );
+
+        ConvictionVotingMock::set_referendum_remaining(Some(100));
+        assert_noop!(
+            EqStaking::vote_with_stake(RuntimeOrigin::signed(ACCOUNT_1), 0, true),
+            Error::<Test>::NoStakeForConviction
+        );
+
+        assert_ok!(EqStaking::stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            500 * ONE_TOKEN,
+            StakePeriod::One
+        ));
+        // One month stake ends before referendum does
+        ConvictionVotingMock::set_referendum_remaining(Some(StakePeriod::One.as_secs()));
+        assert_noop!(
+            EqStaking::vote_with_stake(RuntimeOrigin::signed(ACCOUNT_1), 0, true),
+            Error::<Test>::NoStakeForConviction
+        );
+    });
+}
+
+#[test]
+fn democracy_lock_covered_by_stakes() {
+    use crate::democracy::DEMOCRACY_ID;
+    use frame_support::traits::{LockableCurrency, WithdrawReasons};
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(EqStaking::stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            500 * ONE_TOKEN,
+            StakePeriod::One
+        ));
+        assert_ok!(EqStaking::stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            300 * ONE_TOKEN,
+            StakePeriod::Twelve
+        ));
+
+        // only stakes outliving any democracy lock are taken into account
+        DemocracyCurrency::set_lock(
+            DEMOCRACY_ID,
+            &ACCOUNT_1,
+            1000 * ONE_TOKEN,
+            WithdrawReasons::TRANSFER,
+        );
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_lock(ACCOUNT_1, DEMOCRACY_ID),
+            700 * ONE_TOKEN
+        );
+
+        DemocracyCurrency::set_lock(
+            DEMOCRACY_ID,
+            &ACCOUNT_1,
+            200 * ONE_TOKEN,
+            WithdrawReasons::TRANSFER,
+        );
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_lock(ACCOUNT_1, DEMOCRACY_ID),
+            0
+        );
+
+        DemocracyCurrency::extend_lock(
+            DEMOCRACY_ID,
+            &ACCOUNT_1,
+            400 * ONE_TOKEN,
+            WithdrawReasons::TRANSFER,
+        );
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_lock(ACCOUNT_1, DEMOCRACY_ID),
+            100 * ONE_TOKEN
+        );
+
+        // other locks are not changed
+        DemocracyCurrency::set_lock(
+            *b"other   ",
+            &ACCOUNT_1,
+            1000 * ONE_TOKEN,
+            WithdrawReasons::TRANSFER,
+        );
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_lock(ACCOUNT_1, *b"other   "),
+            1000 * ONE_TOKEN
+        );
+    });
+}
+
+#[test]
+fn emission_distributed_at_era_boundary() {
+    new_test_ext().execute_with(|| {
+        eq_balances::Pallet::<Test>::make_free_balance_be(
+            &TreasuryAccount::get(),
+            asset::EQ,
+            SignedBalance::Positive(BALANCE),
+        );
+        let stake = 100 * ONE_TOKEN;
+        assert_ok!(Pallet::<Test>::stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            stake,
+            StakePeriod::One
+        ));
+        assert_ok!(Pallet::<Test>::stake(
+            RuntimeOrigin::signed(ACCOUNT_2),
+            stake,
+            StakePeriod::Twelve
+        ));
+
+        assert_noop!(
+            Pallet::<Test>::set_emission_schedule(
+                RuntimeOrigin::signed(ACCOUNT_1),
+                300 * ONE_TOKEN,
+                Permill::from_percent(10)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Pallet::<Test>::set_emission_schedule(
+            RawOrigin::Root.into(),
+            300 * ONE_TOKEN,
+            Permill::from_percent(10)
+        ));
+
+        // era is not started yet
+        Pallet::<Test>::on_idle(1, Weight::MAX);
+        assert!(Rewards::<Test>::get(ACCOUNT_1).is_none());
+
+        let now = EmissionEraDuration::get();
+        timestamp::Pallet::<Test>::set_timestamp(now * 1000);
+        Pallet::<Test>::on_idle(2, Weight::MAX);
+
+        // 12 months stake has doubled weight
+        assert_eq!(
+            Rewards::<Test>::get(ACCOUNT_1).map(|r| r.amount),
+            Some(100 * ONE_TOKEN)
+        );
+        assert_eq!(
+            Rewards::<Test>::get(ACCOUNT_2).map(|r| r.amount),
+            Some(200 * ONE_TOKEN)
+        );
+        assert!(Rewards::<Test>::get(ACCOUNT_3).is_none());
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_lock(ACCOUNT_2, STAKING_ID),
+            stake + 200 * ONE_TOKEN
+        );
+        assert!(EmissionDistribution::<Test>::get().is_none());
+        assert_eq!(
+            Emission::<Test>::get(),
+            Some(EmissionSchedule {
+                per_era: 270 * ONE_TOKEN,
+                decay: Permill::from_percent(10),
+                next_era: 2 * now,
+            })
+        );
+
+        assert_ok!(Pallet::<Test>::set_emission_schedule(
+            RawOrigin::Root.into(),
+            0,
+            Permill::zero()
+        ));
+        assert!(Emission::<Test>::get().is_none());
+    });
+}
+
+#[test]
+fn stakes_are_moved_by_account_migration() {
+    type Migration = (eq_balances::Pallet<Test>, Pallet<Test>);
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(EqStaking::stake(
+            RuntimeOrigin::signed(ACCOUNT_1),
+            500 * ONE_TOKEN,
+            StakePeriod::One
+        ));
+        assert_ok!(EqStaking::stake(
+            RuntimeOrigin::signed(ACCOUNT_2),
+            300 * ONE_TOKEN,
+            StakePeriod::Two
+        ));
+        for _ in 0..MaxStakesCount::get() {
+            assert_ok!(EqStaking::stake(
+                RuntimeOrigin::signed(ACCOUNT_3),
+                ONE_TOKEN,
+                StakePeriod::One
+            ));
+        }
+
+        assert_noop!(
+            Migration::can_migrate(&ACCOUNT_1, &ACCOUNT_3),
+            Error::<Test>::MaxStakesNumberReached
+        );
+
+        assert_ok!(Migration::can_migrate(&ACCOUNT_1, &ACCOUNT_2));
+        assert_ok!(Migration::migrate(&ACCOUNT_1, &ACCOUNT_2));
+
+        assert!(Stakes::<Test>::get(ACCOUNT_1).is_empty());
+        assert_eq!(Stakes::<Test>::get(ACCOUNT_2).len(), 2);
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_lock(ACCOUNT_1, STAKING_ID),
+            0
+        );
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_lock(ACCOUNT_2, STAKING_ID),
+            800 * ONE_TOKEN
+        );
+        assert_eq!(
+            eq_balances::Pallet::<Test>::get_balance(&ACCOUNT_2, &asset::EQ),
+            SignedBalance::Positive(2 * BALANCE)
+        );
+    });
+}
@@ -21,9 +21,16 @@ use frame_support::weights::Weight;
 pub trait WeightInfo {
     fn stake() -> Weight;
     fn reward() -> Weight;
+    fn add_manager() -> Weight;
     fn unlock_stake() -> Weight;
     fn unlock_reward() -> Weight;
-    fn on_initialize() -> Weight;
+    fn restake() -> Weight;
+    fn compound_reward() -> Weight;
+    fn custom_reward(r: u32) -> Weight;
+    fn vote_with_stake() -> Weight;
+    fn on_initialize(a: u32) -> Weight;
+    fn set_emission_schedule() -> Weight;
+    fn emission_step() -> Weight;
 }
 
 // for tests
@@ -34,13 +41,34 @@ impl crate::weights::WeightInfo for () {
     fn reward() -> Weight {
         Weight::zero()
     }
+    fn add_manager() -> Weight {
+        Weight::zero()
+    }
     fn unlock_stake() -> Weight {
         Weight::zero()
     }
     fn unlock_reward() -> Weight {
         Weight::zero()
     }
-    fn on_initialize() -> Weight {
+    fn restake() -> Weight {
+        Weight::zero()
+    }
+    fn compound_reward() -> Weight {
+        Weight::zero()
+    }
+    fn custom_reward(_r: u32) -> Weight {
+        Weight::zero()
+    }
+    fn vote_with_stake() -> Weight {
+        Weight::zero()
+    }
+    fn on_initialize(_a: u32) -> Weight {
+        Weight::zero()
+    }
+    fn set_emission_schedule() -> Weight {
+        Weight::zero()
+    }
+    fn emission_step() -> Weight {
         Weight::zero()
     }
 }
@@ -0,0 +1,44 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for `eq-subaccounts` pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use eq_primitives::subaccount::SubAccType;
+use eq_subaccounts::{NavSample, SubaccountInfo};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait EqSubaccountsApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec
+    {
+        /// NAV samples of account, oldest first. Empty if sampling is not enabled
+        fn nav_history(account_id: AccountId) -> Vec<NavSample<Balance, BlockNumber>>;
+
+        /// Subaccounts of master account with inputs of their ids derivation
+        fn subaccounts(account_id: AccountId) -> Vec<SubaccountInfo<AccountId, BlockNumber>>;
+
+        /// Master account and type of subaccount, `None` if `subaccount` is not a subaccount
+        fn subaccount_owner(subaccount: AccountId) -> Option<(AccountId, SubAccType)>;
+    }
+}
@@ -151,4 +151,67 @@ benchmarks! {
         crate::Pallet::<T>::transfer_to_subaccount(RawOrigin::Signed(caller.clone()).into(), SubAccType::Bailsman, asset::BTC, 20_000_000_000_000u128.unique_saturated_into())?;
         prepare_distribution_queue::<T>(r);
     }: transfer_from_subaccount(RawOrigin::Signed(caller), SubAccType::Bailsman, asset::BTC, 20_000_000_000_000u64.unique_saturated_into())
+
+    authorize_session_key {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let session_key: T::AccountId = account("session_key", 0, SEED);
+        init::<T>();
+        init_account_balance::<T>(&caller);
+        crate::Pallet::<T>::transfer_to_subaccount(RawOrigin::Signed(caller.clone()).into(), SubAccType::Trader, asset::BTC, 20_000_000_000_000u128.unique_saturated_into())?;
+    }: _(RawOrigin::Signed(caller), session_key.clone(), 100u32.into())
+    verify {
+        assert!(SessionKeys::<T>::contains_key(&session_key));
+    }
+
+    revoke_session_key {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let session_key: T::AccountId = account("session_key", 0, SEED);
+        init::<T>();
+        init_account_balance::<T>(&caller);
+        crate::Pallet::<T>::transfer_to_subaccount(RawOrigin::Signed(caller.clone()).into(), SubAccType::Trader, asset::BTC, 20_000_000_000_000u128.unique_saturated_into())?;
+        crate::Pallet::<T>::authorize_session_key(RawOrigin::Signed(caller.clone()).into(), session_key.clone(), 100u32.into())?;
+    }: _(RawOrigin::Signed(caller), session_key.clone())
+    verify {
+        assert!(!SessionKeys::<T>::contains_key(&session_key));
+    }
+
+    enable_nav_sampling {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        init::<T>();
+        init_account_balance::<T>(&caller);
+        crate::Pallet::<T>::transfer_to_subaccount(RawOrigin::Signed(caller.clone()).into(), SubAccType::Trader, asset::BTC, 20_000_000_000_000u128.unique_saturated_into())?;
+        let trader = crate::Pallet::<T>::subaccount(&caller, &SubAccType::Trader).unwrap();
+    }: _(RawOrigin::Signed(caller), Some(SubAccType::Trader))
+    verify {
+        assert!(NavSampledAccounts::<T>::contains_key(&trader));
+    }
+
+    disable_nav_sampling {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        init::<T>();
+        init_account_balance::<T>(&caller);
+        crate::Pallet::<T>::transfer_to_subaccount(RawOrigin::Signed(caller.clone()).into(), SubAccType::Trader, asset::BTC, 20_000_000_000_000u128.unique_saturated_into())?;
+        let trader = crate::Pallet::<T>::subaccount(&caller, &SubAccType::Trader).unwrap();
+        crate::Pallet::<T>::enable_nav_sampling(RawOrigin::Signed(caller.clone()).into(), Some(SubAccType::Trader))?;
+        crate::Pallet::<T>::sample_nav(One::one());
+    }: _(RawOrigin::Signed(caller), Some(SubAccType::Trader))
+    verify {
+        assert!(!NavSampledAccounts::<T>::contains_key(&trader));
+    }
+
+    sample_nav {
+        let a in 0..100;
+        init::<T>();
+        for i in 0..a {
+            let caller: T::AccountId = account("caller", i, SEED);
+            init_account_balance::<T>(&caller);
+            crate::Pallet::<T>::enable_nav_sampling(RawOrigin::Signed(caller).into(), None)?;
+        }
+        let block: T::BlockNumber = One::one();
+    }: {
+        crate::Pallet::<T>::sample_nav(block);
+    }
+    verify {
+        assert_eq!(NavSampledAccounts::<T>::iter().count(), a as usize);
+    }
 }
@@ -64,12 +64,12 @@ pub mod weights;
 use codec::Codec;
 use core::convert::TryInto;
 use eq_primitives::{
-    asset::Asset,
-    balance::{BalanceChecker, BalanceGetter, EqCurrency},
+    asset::{Asset, AssetType},
+    balance::{BalanceCheckError, BalanceChecker, BalanceCheckerKind, BalanceGetter, EqCurrency},
     str_asset,
     subaccount::{SubAccType, SubaccountsManager},
-    Aggregates, BailsmanManager, IsTransfersEnabled, SignedBalance, TransferReason,
-    UpdateTimeManager, UserGroup,
+    AccountMigration, Aggregates, BailsmanManager, IsTransfersEnabled, LendingPoolManager,
+    SignedBalance, TransferReason, UpdateTimeManager, UserGroup,
 };
 use eq_utils::{eq_ensure, ok_or_error};
 use eq_whitelists::CheckWhitelisted;
@@ -77,17 +77,63 @@ use frame_support::{
     codec::{Decode, Encode},
     traits::{ExistenceRequirement, WithdrawReasons},
     weights::Weight,
-    Parameter,
+    PalletId, Parameter,
 };
 use frame_system::ensure_signed;
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
-    traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member},
-    DispatchError, DispatchResult,
+    traits::{
+        AccountIdConversion, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Saturating,
+        Zero,
+    },
+    DispatchError, DispatchResult, RuntimeDebug,
 };
 use sp_std::{fmt::Debug, prelude::*};
 pub use weights::WeightInfo;
 
+/// Session key authorized by master account to trade with its `Trader` subaccount
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct SessionKey<AccountId, BlockNumber> {
+    /// Master account that authorized the key
+    pub master: AccountId,
+    /// `Trader` subaccount of master at the moment of authorization
+    pub subaccount: AccountId,
+    /// Last block in which the key may be used
+    pub expiry: BlockNumber,
+}
+
+/// Sample of account value in USD taken every `NavSamplingPeriod` blocks
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct NavSample<Balance, BlockNumber> {
+    /// Block in which the sample was taken
+    pub block: BlockNumber,
+    /// Value of positive balances
+    pub collateral: Balance,
+    /// Value of negative balances
+    pub debt: Balance,
+    /// Net asset value: collateral minus debt
+    pub nav: SignedBalance<Balance>,
+}
+
+/// Inputs of subaccount id derivation: the id is `blake2_256` of
+/// `(b"eq/subaccounts__", master, block, extrinsic_index, subacc_type, 0)`
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct SubaccountDerivation<BlockNumber> {
+    /// Block in which the subaccount was created
+    pub block: BlockNumber,
+    /// Index of the creating extrinsic in the block
+    pub extrinsic_index: u32,
+}
+
+/// Subaccount of master account with derivation inputs, used in runtime API.
+/// `derivation` is `None` for subaccounts created before derivations were recorded
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct SubaccountInfo<AccountId, BlockNumber> {
+    pub subacc_type: SubAccType,
+    pub subaccount: AccountId,
+    pub derivation: Option<SubaccountDerivation<BlockNumber>>,
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -135,10 +181,27 @@ pub mod pallet {
         type UpdateTimeManager: eq_primitives::UpdateTimeManager<Self::AccountId>;
         /// Whitelist checking
         type Whitelist: eq_whitelists::CheckWhitelisted<Self::AccountId>;
+        /// Supplies balances of `Savings` subaccounts to lending pools
+        type LendingPoolManager: LendingPoolManager<Self::Balance, Self::AccountId>;
         /// Weight information for extrinsics in this pallet
         type WeightInfo: WeightInfo;
         /// Checks if transaction disabled flag is off
         type IsTransfersEnabled: eq_primitives::IsTransfersEnabled;
+        /// Treasury account receiving NAV sampling fees
+        #[pallet::constant]
+        type TreasuryModuleId: Get<PalletId>;
+        /// Period in blocks between NAV samples
+        #[pallet::constant]
+        type NavSamplingPeriod: Get<Self::BlockNumber>;
+        /// Fee in main asset charged for enabling NAV sampling
+        #[pallet::constant]
+        type NavSamplingFee: Get<Self::Balance>;
+        /// Max number of samples kept for account, oldest samples are dropped
+        #[pallet::constant]
+        type MaxNavSamples: Get<u32>;
+        /// Max number of accounts with enabled NAV sampling
+        #[pallet::constant]
+        type MaxNavSampledAccounts: Get<u32>;
     }
 
     #[pallet::call]
@@ -155,69 +218,8 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             Self::ensure_transfers_enabled()?;
 
-            let mut subaccount_created = false;
-            let subacc_id = match <Subaccount<T>>::get(&who, &subacc_type) {
-                Some(account_id) => account_id,
-                None => {
-                    subaccount_created = true;
-                    let account_id = Self::create_subaccount_inner(&who, &subacc_type)?;
-                    if subacc_type != SubAccType::Bailsman {
-                        // bailsmen usergroup will be set after transfer if value is enough to become bailsman
-                        Self::try_set_usergroup(&account_id, &subacc_type).err();
-                    }
-                    Self::deposit_event(Event::SubaccountCreated(
-                        who.clone(),
-                        account_id.clone(),
-                        subacc_type,
-                    ));
-                    account_id
-                }
-            };
-
-            let redistribute_amount =
-                if T::Aggregates::in_usergroup(&subacc_id, UserGroup::Bailsmen) {
-                    // if subaccount is bailsman we need to reinit or fail on transfer.
-                    T::BailsmenManager::redistribute(&subacc_id)?
-                } else {
-                    0
-                };
-
-            T::EqCurrency::currency_transfer(
-                &who,
-                &subacc_id,
-                asset,
-                value,
-                ExistenceRequirement::AllowDeath,
-                TransferReason::Subaccount,
-                true
-            ).map_err(|err| {
-                log::error!(
-                    "{}:{}. Error transferring to subaccount. Who: {:?}, amount: {:?}, currency: {:?}, subaccount type: {:?}, subaccount id {:?}",
-                    file!(),
-                    line!(),
-                    who,
-                    value,
-                    str_asset!(asset),
-                    subacc_type,
-                    subacc_id
-                );
-                err
-            })?;
-
-            if subacc_type == SubAccType::Bailsman
-                && !T::Aggregates::in_usergroup(&subacc_id, UserGroup::Bailsmen)
-            {
-                Self::try_set_usergroup(&subacc_id, &subacc_type).map_or((), |_| {
-                    Self::deposit_event(Event::RegisterBailsman(who, subacc_id))
-                })
-            }
-
-            Ok(Some(Self::transfer_post_weight(
-                subacc_type,
-                redistribute_amount,
-                subaccount_created,
-            ))
-            .into())
+            let post_weight = Self::do_transfer_to_subaccount(&who, subacc_type, asset, value)?;
+            Ok(Some(post_weight).into())
         }
 
         /// Transfers `amount` of `currency` from subaccount to main account. If `subacc_type`
@@ -272,6 +274,221 @@ pub mod pallet {
             ))
             .into())
         }
+
+        /// Authorizes `session_key` to place and cancel eq-dex orders of caller's `Trader`
+        /// subaccount until `expiry` block inclusive. Session key can't sign any other
+        /// operations on behalf of the caller. Authorizing already owned key updates its expiry.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::authorize_session_key())]
+        pub fn authorize_session_key(
+            origin: OriginFor<T>,
+            session_key: T::AccountId,
+            expiry: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_is_master_acc(&who)?;
+            let subaccount = Self::try_get_subaccount(&who, &SubAccType::Trader)?;
+
+            eq_ensure!(
+                expiry > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::SessionKeyExpired,
+                target: "eq_subaccounts",
+                "{}:{}. Session key expiry is in the past. Who: {:?}, expiry: {:?}",
+                file!(),
+                line!(),
+                who,
+                expiry
+            );
+            eq_ensure!(
+                session_key != who
+                    && Self::is_master(&session_key)
+                    && Self::get_subaccounts_amount(&session_key) == 0,
+                Error::<T>::InvalidSessionKey,
+                target: "eq_subaccounts",
+                "{}:{}. Session key should be a separate account without subaccounts. \
+                Who: {:?}, session key: {:?}",
+                file!(),
+                line!(),
+                who,
+                session_key
+            );
+            if let Some(existing) = <SessionKeys<T>>::get(&session_key) {
+                eq_ensure!(
+                    existing.master == who,
+                    Error::<T>::SessionKeyInUse,
+                    target: "eq_subaccounts",
+                    "{}:{}. Session key is authorized by another account. \
+                    Who: {:?}, session key: {:?}",
+                    file!(),
+                    line!(),
+                    who,
+                    session_key
+                );
+            }
+
+            <SessionKeys<T>>::insert(
+                &session_key,
+                SessionKey {
+                    master: who.clone(),
+                    subaccount: subaccount.clone(),
+                    expiry,
+                },
+            );
+            Self::deposit_event(Event::SessionKeyAuthorized(
+                who,
+                session_key,
+                subaccount,
+                expiry,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Revokes `session_key` authorized by caller
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::revoke_session_key())]
+        pub fn revoke_session_key(
+            origin: OriginFor<T>,
+            session_key: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let is_owner = <SessionKeys<T>>::get(&session_key)
+                .map(|key| key.master == who)
+                .unwrap_or(false);
+            eq_ensure!(
+                is_owner,
+                Error::<T>::NotSessionKeyOwner,
+                target: "eq_subaccounts",
+                "{}:{}. Session key is not authorized by caller. Who: {:?}, session key: {:?}",
+                file!(),
+                line!(),
+                who,
+                session_key
+            );
+
+            <SessionKeys<T>>::remove(&session_key);
+            Self::deposit_event(Event::SessionKeyRevoked(who, session_key));
+
+            Ok(().into())
+        }
+
+        /// Enables periodic NAV sampling of caller or its `subacc_type` subaccount.
+        /// Caller pays `NavSamplingFee` in main asset to treasury.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::enable_nav_sampling())]
+        pub fn enable_nav_sampling(
+            origin: OriginFor<T>,
+            subacc_type: Option<SubAccType>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account = Self::nav_sampling_account(&who, subacc_type)?;
+
+            eq_ensure!(
+                !<NavSampledAccounts<T>>::contains_key(&account),
+                Error::<T>::NavSamplingAlreadyEnabled,
+                target: "eq_subaccounts",
+                "{}:{}. NAV sampling is already enabled. Who: {:?}, account: {:?}",
+                file!(),
+                line!(),
+                who,
+                account
+            );
+            let count = <NavSampledAccountsCount<T>>::get();
+            eq_ensure!(
+                count < T::MaxNavSampledAccounts::get(),
+                Error::<T>::TooManyNavSampledAccounts,
+                target: "eq_subaccounts",
+                "{}:{}. Max number of NAV sampled accounts reached. Who: {:?}",
+                file!(),
+                line!(),
+                who
+            );
+
+            T::EqCurrency::currency_transfer(
+                &who,
+                &T::TreasuryModuleId::get().into_account_truncating(),
+                T::AssetGetter::get_main_asset(),
+                T::NavSamplingFee::get(),
+                ExistenceRequirement::KeepAlive,
+                TransferReason::Common,
+                true,
+            )?;
+
+            <NavSampledAccounts<T>>::insert(&account, ());
+            <NavSampledAccountsCount<T>>::put(count + 1);
+            Self::deposit_event(Event::NavSamplingEnabled(who, account));
+
+            Ok(().into())
+        }
+
+        /// Disables NAV sampling of caller or its `subacc_type` subaccount and removes its history
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::disable_nav_sampling())]
+        pub fn disable_nav_sampling(
+            origin: OriginFor<T>,
+            subacc_type: Option<SubAccType>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account = Self::nav_sampling_account(&who, subacc_type)?;
+
+            eq_ensure!(
+                <NavSampledAccounts<T>>::contains_key(&account),
+                Error::<T>::NavSamplingNotEnabled,
+                target: "eq_subaccounts",
+                "{}:{}. NAV sampling is not enabled. Who: {:?}, account: {:?}",
+                file!(),
+                line!(),
+                who,
+                account
+            );
+
+            <NavSampledAccounts<T>>::remove(&account);
+            <NavSampledAccountsCount<T>>::mutate(|count| *count = count.saturating_sub(1));
+            <NavHistory<T>>::remove(&account);
+            Self::deposit_event(Event::NavSamplingDisabled(who, account));
+
+            Ok(().into())
+        }
+
+        /// Creates missing subaccounts of `fundings` types and transfers corresponding
+        /// amounts of `asset` to them. Zero amount only creates the subaccount.
+        #[pallet::call_index(7)]
+        #[pallet::weight((Pallet::<T>::create_subaccounts_max_weight(fundings), DispatchClass::Normal))]
+        pub fn create_subaccounts(
+            origin: OriginFor<T>,
+            asset: Asset,
+            fundings: Vec<(SubAccType, T::Balance)>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_transfers_enabled()?;
+
+            let is_unique = fundings.iter().enumerate().all(|(i, (subacc_type, _))| {
+                !fundings[..i].iter().any(|(other, _)| other == subacc_type)
+            });
+            eq_ensure!(
+                is_unique,
+                Error::<T>::DuplicateSubaccountType,
+                target: "eq_subaccounts",
+                "{}:{}. Subaccount types should be unique. Who: {:?}, fundings: {:?}",
+                file!(),
+                line!(),
+                who,
+                fundings
+            );
+
+            let mut post_weight = Weight::zero();
+            for (subacc_type, value) in fundings {
+                let weight = if value.is_zero() {
+                    let (_, created) = Self::get_or_create_subaccount(&who, subacc_type)?;
+                    Self::transfer_post_weight(subacc_type, 0, created)
+                } else {
+                    Self::do_transfer_to_subaccount(&who, subacc_type, asset, value)?
+                };
+                post_weight = post_weight.saturating_add(weight);
+            }
+
+            Ok(Some(post_weight).into())
+        }
     }
 
     #[pallet::event]
@@ -288,6 +505,21 @@ pub mod pallet {
         /// - second element is subaccount of type Bailsman
         /// \[owner, subaccount\]
         RegisterBailsman(T::AccountId, T::AccountId),
+        /// Session key authorized to trade with `Trader` subaccount
+        /// \[owner, session_key, subaccount, expiry\]
+        SessionKeyAuthorized(T::AccountId, T::AccountId, T::AccountId, T::BlockNumber),
+        /// Session key revoked
+        /// \[owner, session_key\]
+        SessionKeyRevoked(T::AccountId, T::AccountId),
+        /// NAV sampling enabled
+        /// \[payer, account\]
+        NavSamplingEnabled(T::AccountId, T::AccountId),
+        /// NAV sampling disabled
+        /// \[owner, account\]
+        NavSamplingDisabled(T::AccountId, T::AccountId),
+        /// Subaccount is moved to the new master account by account migration
+        /// \[old_owner, new_owner, type, subaccount\]
+        SubaccountMigrated(T::AccountId, T::AccountId, SubAccType, T::AccountId),
     }
 
     #[pallet::error]
@@ -307,10 +539,36 @@ pub mod pallet {
         EntropyError,
         /// Account is not a master account. Transfers to external subaccounts prohibited.
         AccountIsNotMaster,
+        /// Session key expiry should be greater than current block
+        SessionKeyExpired,
+        /// Session key should be a separate master account without subaccounts
+        InvalidSessionKey,
+        /// Session key is already authorized by another account
+        SessionKeyInUse,
+        /// Session key is not authorized by caller
+        NotSessionKeyOwner,
+        /// NAV sampling is already enabled for account
+        NavSamplingAlreadyEnabled,
+        /// NAV sampling is not enabled for account
+        NavSamplingNotEnabled,
+        /// Max number of accounts with enabled NAV sampling reached
+        TooManyNavSampledAccounts,
+        /// Subaccount type is repeated in a batch
+        DuplicateSubaccountType,
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            let period = T::NavSamplingPeriod::get();
+            if period.is_zero() || !(n % period).is_zero() {
+                return Weight::zero();
+            }
+
+            let sampled = Self::sample_nav(n);
+            T::WeightInfo::sample_nav(sampled)
+        }
+    }
 
     /// Pallet storage - double map storing subaccounts as `AccountId` where
     /// user's main `AccountId` and `SubAccType` used as keys
@@ -334,6 +592,37 @@ pub mod pallet {
     pub type OwnerAccount<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, (T::AccountId, SubAccType)>;
 
+    /// Pallet storage - inputs of subaccount id derivation for each existing subaccount
+    #[pallet::storage]
+    #[pallet::getter(fn subaccount_derivation)]
+    pub type SubaccountDerivations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, SubaccountDerivation<T::BlockNumber>>;
+
+    /// Pallet storage - session keys authorized to trade with `Trader` subaccounts
+    #[pallet::storage]
+    #[pallet::getter(fn session_keys)]
+    pub type SessionKeys<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, SessionKey<T::AccountId, T::BlockNumber>>;
+
+    /// Pallet storage - accounts with enabled NAV sampling
+    #[pallet::storage]
+    pub type NavSampledAccounts<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+    /// Pallet storage - number of accounts with enabled NAV sampling
+    #[pallet::storage]
+    pub type NavSampledAccountsCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Pallet storage - last `MaxNavSamples` NAV samples of account, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn nav_history)]
+    pub type NavHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Vec<NavSample<T::Balance, T::BlockNumber>>,
+        ValueQuery,
+    >;
+
     /// Vec<(Master account, SubAccType, Subaccount, Vec<(amount, asset)>)>
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -463,6 +752,10 @@ impl<T: Config> Pallet<T> {
             false
         };
 
+        if subacc_type == SubAccType::Savings {
+            Self::redeem_savings(&subaccount, asset, amount)?;
+        }
+
         // Transfer will fail if bailsman has debt,
         // because change is negative
         // only positive changes are allowed
@@ -507,6 +800,38 @@ impl<T: Config> Pallet<T> {
         Ok(redistribute_amount)
     }
 
+    /// Supplies `amount` of `asset` transferred to `Savings` subaccount to the lending
+    /// pool, only physical assets can be lent
+    fn supply_savings(
+        subaccount: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        let asset_data = T::AssetGetter::get_asset_data(&asset)?;
+        if asset_data.asset_type == AssetType::Physical {
+            T::LendingPoolManager::supply(subaccount, &asset, &amount)?;
+        }
+        Ok(())
+    }
+
+    /// Redeems from the lending pool the part of `amount` which is not covered by
+    /// free balance of `Savings` subaccount
+    fn redeem_savings(
+        subaccount: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        let free = match T::BalanceGetter::get_balance(subaccount, &asset) {
+            SignedBalance::Positive(balance) => balance,
+            SignedBalance::Negative(_) => T::Balance::zero(),
+        };
+        let shortfall = amount.saturating_sub(free);
+        if !shortfall.is_zero() {
+            T::LendingPoolManager::redeem(subaccount, &asset, &shortfall)?;
+        }
+        Ok(())
+    }
+
     fn ensure_transfers_enabled() -> DispatchResult {
         let is_enabled = T::IsTransfersEnabled::get();
         eq_ensure!(
@@ -521,6 +846,84 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Returns `subacc_type` subaccount of `who`, creating it if it doesn't exist.
+    /// Second element is true if the subaccount is created
+    fn get_or_create_subaccount(
+        who: &T::AccountId,
+        subacc_type: SubAccType,
+    ) -> Result<(T::AccountId, bool), DispatchError> {
+        if let Some(account_id) = <Subaccount<T>>::get(who, &subacc_type) {
+            return Ok((account_id, false));
+        }
+
+        let account_id = Self::create_subaccount_inner(who, &subacc_type)?;
+        if subacc_type != SubAccType::Bailsman {
+            // bailsmen usergroup will be set after transfer if value is enough to become bailsman
+            Self::try_set_usergroup(&account_id, &subacc_type).err();
+        }
+        Self::deposit_event(Event::SubaccountCreated(
+            who.clone(),
+            account_id.clone(),
+            subacc_type,
+        ));
+
+        Ok((account_id, true))
+    }
+
+    fn do_transfer_to_subaccount(
+        who: &T::AccountId,
+        subacc_type: SubAccType,
+        asset: Asset,
+        value: T::Balance,
+    ) -> Result<Weight, DispatchError> {
+        let (subacc_id, subaccount_created) = Self::get_or_create_subaccount(who, subacc_type)?;
+
+        let redistribute_amount = if T::Aggregates::in_usergroup(&subacc_id, UserGroup::Bailsmen) {
+            // if subaccount is bailsman we need to reinit or fail on transfer.
+            T::BailsmenManager::redistribute(&subacc_id)?
+        } else {
+            0
+        };
+
+        T::EqCurrency::currency_transfer(
+            who,
+            &subacc_id,
+            asset,
+            value,
+            ExistenceRequirement::AllowDeath,
+            TransferReason::Subaccount,
+            true
+        ).map_err(|err| {
+            log::error!(
+                "{}:{}. Error transferring to subaccount. Who: {:?}, amount: {:?}, currency: {:?}, subaccount type: {:?}, subaccount id {:?}",
+                file!(),
+                line!(),
+                who,
+                value,
+                str_asset!(asset),
+                subacc_type,
+                subacc_id
+            );
+            err
+        })?;
+
+        if subacc_type == SubAccType::Bailsman
+            && !T::Aggregates::in_usergroup(&subacc_id, UserGroup::Bailsmen)
+        {
+            Self::try_set_usergroup(&subacc_id, &subacc_type).map_or((), |_| {
+                Self::deposit_event(Event::RegisterBailsman(who.clone(), subacc_id))
+            })
+        } else if subacc_type == SubAccType::Savings {
+            Self::supply_savings(&subacc_id, asset, value)?;
+        }
+
+        Ok(Self::transfer_post_weight(
+            subacc_type,
+            redistribute_amount,
+            subaccount_created,
+        ))
+    }
+
     fn ensure_is_master_acc(who: &T::AccountId) -> DispatchResult {
         eq_ensure!(
             Self::is_master(who),
@@ -535,6 +938,63 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Returns `who` or its `subacc_type` subaccount
+    fn nav_sampling_account(
+        who: &T::AccountId,
+        subacc_type: Option<SubAccType>,
+    ) -> Result<T::AccountId, DispatchError> {
+        Self::ensure_is_master_acc(who)?;
+        match subacc_type {
+            Some(subacc_type) => Ok(Self::try_get_subaccount(who, &subacc_type)?),
+            None => Ok(who.clone()),
+        }
+    }
+
+    /// Appends NAV sample of every sampled account to its history, returns number of accounts.
+    /// Accounts with unknown prices are skipped until the next period.
+    fn sample_nav(block: T::BlockNumber) -> u32 {
+        let max_samples = T::MaxNavSamples::get() as usize;
+        let mut sampled = 0;
+        for account in <NavSampledAccounts<T>>::iter_keys() {
+            sampled += 1;
+            let debt_collateral = match T::BalanceGetter::get_debt_and_collateral(&account) {
+                Ok(debt_collateral) => debt_collateral,
+                Err(err) => {
+                    log::error!(
+                        target: "eq_subaccounts",
+                        "{}:{}. NAV sampling failed. Account: {:?}, error: {:?}",
+                        file!(),
+                        line!(),
+                        account,
+                        err
+                    );
+                    continue;
+                }
+            };
+            let (collateral, debt) = (debt_collateral.collateral, debt_collateral.debt);
+            let nav = if collateral >= debt {
+                SignedBalance::Positive(collateral - debt)
+            } else {
+                SignedBalance::Negative(debt - collateral)
+            };
+
+            <NavHistory<T>>::mutate(&account, |history| {
+                history.push(NavSample {
+                    block,
+                    collateral,
+                    debt,
+                    nav,
+                });
+                if history.len() > max_samples {
+                    let excess = history.len() - max_samples;
+                    history.drain(..excess);
+                }
+            });
+        }
+
+        sampled
+    }
+
     fn try_get_subaccount(
         who: &T::AccountId,
         subacc_type: &SubAccType,
@@ -557,13 +1017,55 @@ impl<T: Config> Pallet<T> {
     fn generate_account_id(
         who: &T::AccountId,
         subacc_type: &SubAccType,
-    ) -> Result<T::AccountId, sp_runtime::DispatchError> {
-        let height = frame_system::Pallet::<T>::block_number();
-        let ext_index = frame_system::Pallet::<T>::extrinsic_index().unwrap_or_default();
+    ) -> Result<(T::AccountId, SubaccountDerivation<T::BlockNumber>), sp_runtime::DispatchError>
+    {
+        let derivation = SubaccountDerivation {
+            block: frame_system::Pallet::<T>::block_number(),
+            extrinsic_index: frame_system::Pallet::<T>::extrinsic_index().unwrap_or_default(),
+        };
+        let subaccount = Self::derive_subaccount_id(who, *subacc_type, derivation)
+            .ok_or(Error::<T>::EntropyError)?;
+
+        Ok((subaccount, derivation))
+    }
 
-        let entropy =
-            (b"eq/subaccounts__", who, height, ext_index, subacc_type, 0).using_encoded(blake2_256);
-        T::AccountId::decode(&mut &entropy[..]).map_err(|_| Error::<T>::EntropyError.into())
+    /// Subaccount id of `who` derived from `derivation` inputs, used to audit stored ids
+    pub fn derive_subaccount_id(
+        who: &T::AccountId,
+        subacc_type: SubAccType,
+        derivation: SubaccountDerivation<T::BlockNumber>,
+    ) -> Option<T::AccountId> {
+        let entropy = (
+            b"eq/subaccounts__",
+            who,
+            derivation.block,
+            derivation.extrinsic_index,
+            subacc_type,
+            0,
+        )
+            .using_encoded(blake2_256);
+        T::AccountId::decode(&mut &entropy[..]).ok()
+    }
+
+    /// Subaccounts of `who` with derivation inputs, used in runtime API
+    pub fn subaccounts_info(
+        who: &T::AccountId,
+    ) -> Vec<SubaccountInfo<T::AccountId, T::BlockNumber>> {
+        <Subaccount<T>>::iter_prefix(who)
+            .map(|(subacc_type, subaccount)| SubaccountInfo {
+                subacc_type,
+                derivation: <SubaccountDerivations<T>>::get(&subaccount),
+                subaccount,
+            })
+            .collect()
+    }
+
+    fn create_subaccounts_max_weight(fundings: &[(SubAccType, T::Balance)]) -> Weight {
+        fundings
+            .iter()
+            .fold(Weight::zero(), |weight, (subacc_type, _)| {
+                weight.saturating_add(Self::transfer_max_weight(subacc_type, true))
+            })
     }
 
     /// Adds subaccount to corresponding UserGroup according to `subacc_type`
@@ -578,6 +1080,8 @@ impl<T: Config> Pallet<T> {
             SubAccType::Bailsman => {
                 T::BailsmenManager::register_bailsman(&subacc_id)?;
             }
+            // savings can't have debt, so they are not borrowers
+            SubAccType::Savings => {}
         };
         Ok(())
     }
@@ -660,9 +1164,10 @@ impl<T: Config> SubaccountsManager<T::AccountId> for Pallet<T> {
             subacc_type
         );
 
-        let subaccount = Self::generate_account_id(&who, &subacc_type)?;
+        let (subaccount, derivation) = Self::generate_account_id(&who, &subacc_type)?;
         T::UpdateTimeManager::set_last_update(&subaccount);
         <OwnerAccount<T>>::insert(&subaccount, (&who, &subacc_type));
+        <SubaccountDerivations<T>>::insert(&subaccount, derivation);
         <Subaccount<T>>::insert(&who, &subacc_type, &subaccount);
         // increment subaccount providers here, so it will no happen in currency_transfer
         frame_system::Pallet::<T>::inc_providers(&subaccount.clone().into());
@@ -708,10 +1213,12 @@ impl<T: Config> SubaccountsManager<T::AccountId> for Pallet<T> {
                     })?;
                 }
             }
+            SubAccType::Savings => {}
         };
 
         T::UpdateTimeManager::remove_last_update(&subaccount);
         <OwnerAccount<T>>::remove(&subaccount);
+        <SubaccountDerivations<T>>::remove(&subaccount);
         <Subaccount<T>>::remove(&who, &subacc_type);
         frame_system::Pallet::<T>::dec_providers(&subaccount)?;
         frame_system::Pallet::<T>::dec_providers(&who)?;
@@ -742,6 +1249,14 @@ impl<T: Config> SubaccountsManager<T::AccountId> for Pallet<T> {
     fn get_subaccounts_amount(who: &T::AccountId) -> usize {
         <Subaccount<T>>::iter_prefix(&who).count()
     }
+
+    fn session_key_owner(session_key: &T::AccountId) -> Option<T::AccountId> {
+        let key = <SessionKeys<T>>::get(session_key)?;
+        let is_active = key.expiry >= frame_system::Pallet::<T>::block_number()
+            && <Subaccount<T>>::get(&key.master, &SubAccType::Trader) == Some(key.subaccount);
+
+        is_active.then(|| key.master)
+    }
 }
 
 impl<T: Config> BalanceChecker<T::Balance, T::AccountId, T::BalanceGetter, Pallet<T>>
@@ -751,15 +1266,83 @@ impl<T: Config> BalanceChecker<T::Balance, T::AccountId, T::BalanceGetter, Palle
         who: &T::AccountId,
         changes: &Vec<(Asset, SignedBalance<T::Balance>)>,
         _withdraw_reasons: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), BalanceCheckError<T::Balance>> {
         if T::Aggregates::in_usergroup(who, UserGroup::Borrowers) {
             return Ok(());
         }
 
         for (asset, change) in changes.iter() {
             let new_balance = T::BalanceGetter::get_balance(who, &asset) + change.clone();
-            if let SignedBalance::Negative(_) = &new_balance {
-                return Err(Error::<T>::Debt.into());
+            if let SignedBalance::Negative(debt) = &new_balance {
+                return Err(BalanceCheckError::new(
+                    BalanceCheckerKind::Subaccounts,
+                    Error::<T>::Debt,
+                )
+                .with_amounts(change.abs(), change.abs().saturating_sub(*debt)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Config> AccountMigration<T::AccountId> for Pallet<T> {
+    fn can_migrate(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        eq_ensure!(
+            Self::is_master(from) && Self::is_master(to),
+            Error::<T>::AccountIsNotMaster,
+            target: "eq_subaccounts",
+            "{}:{}. Subaccounts can't be migrated. From: {:?}, to: {:?}",
+            file!(),
+            line!(),
+            from,
+            to
+        );
+
+        for subacc_type in SubAccType::iterator() {
+            if !Self::has_subaccount(from, &subacc_type) {
+                continue;
+            }
+            eq_ensure!(
+                !T::Whitelist::in_whitelist(to),
+                Error::<T>::AccountInWhiteList,
+                target: "eq_subaccounts",
+                "{}:{}. Account is in whitelist. Who: {:?}",
+                file!(),
+                line!(),
+                to
+            );
+            eq_ensure!(
+                !Self::has_subaccount(to, &subacc_type),
+                Error::<T>::AlreadyHasSubaccount,
+                target: "eq_subaccounts",
+                "{}:{}. Account already has subaccount of this type. Who: {:?}, \
+                subaccount type: {:?}",
+                file!(),
+                line!(),
+                to,
+                subacc_type
+            );
+        }
+
+        Ok(())
+    }
+
+    fn migrate(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        for subacc_type in SubAccType::iterator() {
+            if let Some(subaccount) = <Subaccount<T>>::take(from, &subacc_type) {
+                <Subaccount<T>>::insert(to, &subacc_type, &subaccount);
+                <OwnerAccount<T>>::insert(&subaccount, (to, &subacc_type));
+                // master providers are incremented for every subaccount
+                frame_system::Pallet::<T>::inc_providers(to);
+                frame_system::Pallet::<T>::dec_providers(from)?;
+
+                Self::deposit_event(Event::SubaccountMigrated(
+                    from.clone(),
+                    to.clone(),
+                    subacc_type,
+                    subaccount,
+                ));
             }
         }
 
@@ -98,6 +98,10 @@ parameter_types! {
     pub const MainAsset: eq_primitives::asset::Asset = eq_primitives::asset::EQ;
     pub CriticalMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(5, 1000);
     pub const BalancesModuleId: PalletId = PalletId(*b"eq/balan");
+    pub const NavSamplingPeriod: u64 = 10;
+    pub const NavSamplingFee: Balance = 1_000_000_000;
+    pub const MaxNavSamples: u32 = 3;
+    pub const MaxNavSampledAccounts: u32 = 2;
 }
 
 parameter_types! {
@@ -203,6 +207,8 @@ where
 
 parameter_types! {
     pub const QueueLengthWeightConstant: u32 = 5;
+    pub const RedistributionWeightBudget: Weight = Weight::zero();
+    pub const MaxPriorityRedistributionsPerBlock: u32 = 10;
 }
 
 impl eq_bailsman::Config for Test {
@@ -216,6 +222,7 @@ impl eq_bailsman::Config for Test {
     type MinTempBalanceUsd = MinTempBalanceUsd;
     type UnixTime = timestamp::Pallet<Self>;
     type PalletId = BailsmanModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
     type Aggregates = ModuleAggregates;
     type WeightInfo = ();
     type MarginCallManager = MarginCallManagerMock;
@@ -226,6 +233,8 @@ impl eq_bailsman::Config for Test {
     type UnsignedPriority = UnsignedPriority;
     type ValidatorOffchainBatcher = ();
     type QueueLengthWeightConstant = QueueLengthWeightConstant;
+    type RedistributionWeightBudget = RedistributionWeightBudget;
+    type MaxPriorityRedistributionsPerBlock = MaxPriorityRedistributionsPerBlock;
 }
 
 impl eq_balances::Config for Test {
@@ -251,6 +260,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -286,13 +299,75 @@ impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type Whitelist = eq_whitelists::Pallet<Test>;
     type UpdateTimeManager = RateMock;
+    type LendingPoolManager = LendingPoolManagerMock;
     type WeightInfo = ();
     type IsTransfersEnabled = ModuleBalances;
     type AssetGetter = eq_assets::Pallet<Test>;
+    type TreasuryModuleId = TreasuryModuleId;
+    type NavSamplingPeriod = NavSamplingPeriod;
+    type NavSamplingFee = NavSamplingFee;
+    type MaxNavSamples = MaxNavSamples;
+    type MaxNavSampledAccounts = MaxNavSampledAccounts;
 }
 
 pub type ModuleSubaccounts = Pallet<Test>;
 
+pub const LENDING_POOL: AccountId = 777;
+
+thread_local! {
+    static IS_LENDING_ENABLED: RefCell<bool> = RefCell::new(false);
+}
+
+/// Moves supplied balances to `LENDING_POOL` account when enabled, does nothing otherwise
+pub struct LendingPoolManagerMock;
+impl LendingPoolManagerMock {
+    pub fn enable() {
+        IS_LENDING_ENABLED.with(|v| *v.borrow_mut() = true);
+    }
+
+    fn transfer(
+        from: &AccountId,
+        to: &AccountId,
+        asset: &Asset,
+        amount: &Balance,
+    ) -> DispatchResult {
+        if !IS_LENDING_ENABLED.with(|v| *v.borrow()) {
+            return Ok(());
+        }
+        ModuleBalances::currency_transfer(
+            from,
+            to,
+            *asset,
+            *amount,
+            ExistenceRequirement::AllowDeath,
+            TransferReason::Common,
+            true,
+        )
+    }
+}
+
+impl LendingPoolManager<Balance, AccountId> for LendingPoolManagerMock {
+    fn add_reward(_: Asset, _: Balance) -> DispatchResult {
+        Ok(())
+    }
+
+    fn add_deposit(_: &AccountId, _: &Asset, _: &Balance) -> DispatchResult {
+        Ok(())
+    }
+
+    fn remove_deposit(_: &AccountId, _: &Asset) -> Result<Balance, DispatchError> {
+        Ok(0)
+    }
+
+    fn supply(account: &AccountId, asset: &Asset, amount: &Balance) -> DispatchResult {
+        Self::transfer(account, &LENDING_POOL, asset, amount)
+    }
+
+    fn redeem(account: &AccountId, asset: &Asset, amount: &Balance) -> DispatchResult {
+        Self::transfer(&LENDING_POOL, account, asset, amount)
+    }
+}
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
     OracleMock::init(vec![
         (asset::BTC, FixedI64::saturating_from_integer(10000)),
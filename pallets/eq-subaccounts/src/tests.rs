@@ -19,8 +19,8 @@
 #![cfg(test)]
 
 use crate::mock::{
-    new_test_ext, AccountId, Balance, MarginCallManagerMock, ModuleAggregates, ModuleBalances,
-    ModuleSubaccounts, RuntimeOrigin, Test,
+    new_test_ext, AccountId, Balance, LendingPoolManagerMock, MarginCallManagerMock,
+    ModuleAggregates, ModuleBalances, ModuleSubaccounts, RuntimeOrigin, Test, LENDING_POOL,
 };
 use crate::{Error, SubAccType};
 use eq_primitives::TransferReason;
@@ -29,7 +29,7 @@ use eq_primitives::{
     asset::Asset,
     balance::{BalanceGetter, EqCurrency},
     subaccount::SubaccountsManager,
-    Aggregates, MarginState, SignedBalance, TotalAggregates, UserGroup,
+    AccountMigration, Aggregates, MarginState, SignedBalance, TotalAggregates, UserGroup,
 };
 use eq_utils::ONE_TOKEN;
 use frame_support::traits::ExistenceRequirement;
@@ -55,6 +55,16 @@ fn check_subacc_in_user_group(
     let expected_user_group = match subacc_type {
         SubAccType::Trader | SubAccType::Borrower => UserGroup::Borrowers,
         SubAccType::Bailsman => UserGroup::Bailsmen,
+        // savings never join borrowers or bailsmen
+        SubAccType::Savings => {
+            assert!(
+                !ModuleAggregates::in_usergroup(&subaccount, UserGroup::Borrowers)
+                    && !ModuleAggregates::in_usergroup(&subaccount, UserGroup::Bailsmen),
+                "Savings subaccount is in user group on test step: '{}'",
+                step_name
+            );
+            return;
+        }
     };
 
     assert_eq!(
@@ -235,8 +245,8 @@ fn transfer_to_subacc_updates_aggregates() {
         assert_ok!(ModuleBalances::enable_transfers(RawOrigin::Root.into()));
         let acc_id: AccountId = 1;
         let asset = asset::ETH;
-        let total_balance = 3_000_000_000; // Would be 3 of asset
-        let transferred_amount = total_balance / 3;
+        let total_balance = 4_000_000_000; // Would be 4 of asset
+        let transferred_amount = total_balance / 4;
         assert_ok!(ModuleBalances::deposit_creating(
             &acc_id,
             asset,
@@ -305,6 +315,18 @@ fn transfer_to_subacc_updates_aggregates() {
                         subacc_type
                     );
                 }
+                // Savings don't change borrowers aggregates
+                SubAccType::Savings => {
+                    assert_eq!(
+                        ModuleAggregates::total_user_groups(UserGroup::Borrowers, asset),
+                        TotalAggregates {
+                            collateral: new_total.collateral * 2,
+                            debt: new_total.debt
+                        },
+                        "Wrong aggregates for subacc type: {:?}",
+                        subacc_type
+                    );
+                }
                 // Not enough to reg as bailsman
                 SubAccType::Bailsman => {
                     assert_eq!(
@@ -455,7 +477,7 @@ fn subaccs_count() {
             check_subacc_in_user_group(created_subacc, subacc_type, true, "after creation");
         }
 
-        assert_eq!(ModuleSubaccounts::get_subaccounts_amount(&acc_id), 3);
+        assert_eq!(ModuleSubaccounts::get_subaccounts_amount(&acc_id), 4);
     });
 }
 
@@ -486,8 +508,8 @@ fn transfer_to_subacc() {
             assert_balance!(created_subacc, 11_000_000_000, 0, asset::BTC);
             check_subacc_in_user_group(created_subacc, subacc_type, true, "after creation");
         }
-        // expected_value = 10_000 - 11 - 11 - 11 = 9_967
-        assert_balance!(acc_id, 9_967_000_000_000, 0, asset::BTC);
+        // expected_value = 10_000 - 11 - 11 - 11 - 11 = 9_956
+        assert_balance!(acc_id, 9_956_000_000_000, 0, asset::BTC);
 
         // subaccounts exist
         for subacc_type in SubAccType::iterator() {
@@ -501,8 +523,78 @@ fn transfer_to_subacc() {
             // check for balances
             assert_balance!(subacc_id, 12_000_000_000, 0, asset::BTC);
         }
-        //expected_value = 9_967 - 1 - 1 - 1 = 9_964
-        assert_balance!(acc_id, 9_964_000_000_000, 0, asset::BTC);
+        //expected_value = 9_956 - 1 - 1 - 1 - 1 = 9_952
+        assert_balance!(acc_id, 9_952_000_000_000, 0, asset::BTC);
+    });
+}
+
+#[test]
+fn create_subaccounts_with_funding_split() {
+    new_test_ext().execute_with(|| {
+        let acc_id: AccountId = 42;
+        assert_ok!(ModuleBalances::enable_transfers(RawOrigin::Root.into()));
+        ModuleBalances::make_free_balance_be(
+            &acc_id,
+            asset::BTC,
+            SignedBalance::Positive(10_000_000_000_000),
+        );
+
+        assert_noop!(
+            ModuleSubaccounts::create_subaccounts(
+                RuntimeOrigin::signed(acc_id),
+                asset::BTC,
+                vec![
+                    (SubAccType::Trader, 11_000_000_000),
+                    (SubAccType::Trader, 0)
+                ],
+            ),
+            Error::<Test>::DuplicateSubaccountType
+        );
+
+        assert_ok!(ModuleSubaccounts::create_subaccounts(
+            RuntimeOrigin::signed(acc_id),
+            asset::BTC,
+            vec![
+                (SubAccType::Bailsman, 11_000_000_000),
+                (SubAccType::Trader, 5_000_000_000),
+                (SubAccType::Savings, 0),
+            ],
+        ));
+        assert!(!ModuleSubaccounts::has_subaccount(
+            &acc_id,
+            &SubAccType::Borrower
+        ));
+        // expected_value = 10_000 - 11 - 5 = 9_984
+        assert_balance!(acc_id, 9_984_000_000_000, 0, asset::BTC);
+
+        let trader = ModuleSubaccounts::subaccount(&acc_id, &SubAccType::Trader).unwrap();
+        assert_balance!(trader, 5_000_000_000, 0, asset::BTC);
+        check_subacc_in_user_group(trader, SubAccType::Trader, true, "after creation");
+        let bailsman = ModuleSubaccounts::subaccount(&acc_id, &SubAccType::Bailsman).unwrap();
+        check_subacc_in_user_group(bailsman, SubAccType::Bailsman, true, "after creation");
+        let savings = ModuleSubaccounts::subaccount(&acc_id, &SubAccType::Savings).unwrap();
+        assert_balance!(savings, 0, 0, asset::BTC);
+
+        // ids are reproducible from recorded derivation inputs
+        let info = ModuleSubaccounts::subaccounts_info(&acc_id);
+        assert_eq!(info.len(), 3);
+        for subaccount in info {
+            let derivation = subaccount.derivation.unwrap();
+            assert_eq!(
+                ModuleSubaccounts::derive_subaccount_id(
+                    &acc_id,
+                    subaccount.subacc_type,
+                    derivation
+                ),
+                Some(subaccount.subaccount)
+            );
+        }
+
+        assert_ok!(ModuleSubaccounts::delete_subaccount_inner(
+            &acc_id,
+            &SubAccType::Savings
+        ));
+        assert!(ModuleSubaccounts::subaccount_derivation(savings).is_none());
     });
 }
 
@@ -1247,3 +1339,263 @@ fn create_subaccount_inner_inc_provider() {
         assert_eq!(frame_system::Pallet::<Test>::account(bailsman).providers, 1);
     });
 }
+
+#[test]
+fn session_key_authorize_and_revoke() {
+    new_test_ext().execute_with(|| {
+        let master: AccountId = 1;
+        let session_key: AccountId = 777;
+        frame_system::Pallet::<Test>::set_block_number(1);
+
+        assert_noop!(
+            ModuleSubaccounts::authorize_session_key(
+                RuntimeOrigin::signed(master),
+                session_key,
+                10
+            ),
+            Error::<Test>::NoSubaccountOfThisType
+        );
+
+        let trader = create_subaccount(&master, SubAccType::Trader);
+        assert_noop!(
+            ModuleSubaccounts::authorize_session_key(RuntimeOrigin::signed(master), trader, 10),
+            Error::<Test>::InvalidSessionKey
+        );
+        assert_noop!(
+            ModuleSubaccounts::authorize_session_key(RuntimeOrigin::signed(master), session_key, 1),
+            Error::<Test>::SessionKeyExpired
+        );
+
+        assert_ok!(ModuleSubaccounts::authorize_session_key(
+            RuntimeOrigin::signed(master),
+            session_key,
+            10
+        ));
+        assert_eq!(
+            ModuleSubaccounts::session_key_owner(&session_key),
+            Some(master)
+        );
+        assert_eq!(ModuleSubaccounts::session_key_owner(&master), None);
+
+        let other_master: AccountId = 2;
+        create_subaccount(&other_master, SubAccType::Trader);
+        assert_noop!(
+            ModuleSubaccounts::authorize_session_key(
+                RuntimeOrigin::signed(other_master),
+                session_key,
+                10
+            ),
+            Error::<Test>::SessionKeyInUse
+        );
+        assert_noop!(
+            ModuleSubaccounts::revoke_session_key(RuntimeOrigin::signed(other_master), session_key),
+            Error::<Test>::NotSessionKeyOwner
+        );
+
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_eq!(ModuleSubaccounts::session_key_owner(&session_key), None);
+
+        assert_ok!(ModuleSubaccounts::revoke_session_key(
+            RuntimeOrigin::signed(master),
+            session_key
+        ));
+        assert!(ModuleSubaccounts::session_keys(session_key).is_none());
+    });
+}
+
+#[test]
+fn subaccounts_are_moved_by_account_migration() {
+    new_test_ext().execute_with(|| {
+        let (master, other_master, new_master): (AccountId, AccountId, AccountId) = (1, 2, 3);
+        let session_key: AccountId = 777;
+        frame_system::Pallet::<Test>::set_block_number(1);
+
+        let trader = create_subaccount(&master, SubAccType::Trader);
+        let bailsman = create_subaccount(&master, SubAccType::Bailsman);
+        create_subaccount(&other_master, SubAccType::Trader);
+        assert_ok!(ModuleSubaccounts::authorize_session_key(
+            RuntimeOrigin::signed(master),
+            session_key,
+            10
+        ));
+
+        assert_err!(
+            ModuleSubaccounts::can_migrate(&master, &other_master),
+            Error::<Test>::AlreadyHasSubaccount
+        );
+        assert_err!(
+            ModuleSubaccounts::can_migrate(&trader, &new_master),
+            Error::<Test>::AccountIsNotMaster
+        );
+        assert_err!(
+            ModuleSubaccounts::can_migrate(&master, &bailsman),
+            Error::<Test>::AccountIsNotMaster
+        );
+
+        assert_ok!(ModuleSubaccounts::can_migrate(&master, &new_master));
+        assert_ok!(ModuleSubaccounts::migrate(&master, &new_master));
+
+        assert_eq!(ModuleSubaccounts::get_subaccounts_amount(&master), 0);
+        assert_eq!(
+            ModuleSubaccounts::subaccount(&new_master, &SubAccType::Trader),
+            Some(trader)
+        );
+        assert_eq!(
+            ModuleSubaccounts::get_owner_id(&bailsman),
+            Some((new_master, SubAccType::Bailsman))
+        );
+        assert_eq!(frame_system::Pallet::<Test>::providers(&new_master), 2);
+        // session key authorized by the old master is not valid anymore
+        assert_eq!(ModuleSubaccounts::session_key_owner(&session_key), None);
+    });
+}
+
+#[test]
+fn savings_subaccount_supplies_to_lending_and_cannot_have_debt() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ModuleBalances::enable_transfers(RawOrigin::Root.into()));
+        LendingPoolManagerMock::enable();
+        let main_acc: AccountId = 42;
+        ModuleBalances::make_free_balance_be(
+            &main_acc,
+            asset::BTC,
+            SignedBalance::Positive(100 * ONE_TOKEN),
+        );
+
+        assert_ok!(ModuleSubaccounts::transfer_to_subaccount(
+            RuntimeOrigin::signed(main_acc),
+            SubAccType::Savings,
+            asset::BTC,
+            10 * ONE_TOKEN,
+        ));
+        let savings = ModuleSubaccounts::subaccount(&main_acc, &SubAccType::Savings).unwrap();
+        check_subacc_in_user_group(savings, SubAccType::Savings, false, "after creation");
+        assert_balance!(savings, 0, 0, asset::BTC);
+        assert_balance!(LENDING_POOL, 10 * ONE_TOKEN, 0, asset::BTC);
+
+        // free balance is spent first, the rest is redeemed from lending
+        set_subacc_balance_directly(savings, asset::BTC, &SignedBalance::Positive(ONE_TOKEN));
+        assert_ok!(ModuleSubaccounts::transfer_from_subaccount(
+            RuntimeOrigin::signed(main_acc),
+            SubAccType::Savings,
+            asset::BTC,
+            4 * ONE_TOKEN,
+        ));
+        assert_balance!(savings, 0, 0, asset::BTC);
+        assert_balance!(LENDING_POOL, 7 * ONE_TOKEN, 0, asset::BTC);
+        assert_balance!(main_acc, 94 * ONE_TOKEN, 0, asset::BTC);
+
+        // synthetic assets aren't lent and can't be borrowed
+        assert_noop!(
+            ModuleSubaccounts::transfer_from_subaccount(
+                RuntimeOrigin::signed(main_acc),
+                SubAccType::Savings,
+                asset::EQD,
+                ONE_TOKEN,
+            ),
+            Error::<Test>::Debt
+        );
+    });
+}
+
+#[test]
+fn nav_sampling_keeps_bounded_history() {
+    new_test_ext().execute_with(|| {
+        use frame_support::traits::Hooks;
+        use sp_runtime::traits::AccountIdConversion;
+
+        assert_ok!(ModuleBalances::enable_transfers(RawOrigin::Root.into()));
+        let master: AccountId = 1;
+        let other: AccountId = 2;
+        let treasury: AccountId = crate::mock::TreasuryModuleId::get().into_account_truncating();
+        let fee = crate::mock::NavSamplingFee::get();
+        for acc in [master, other] {
+            ModuleBalances::make_free_balance_be(
+                &acc,
+                asset::EQ,
+                SignedBalance::Positive(10 * ONE_TOKEN),
+            );
+        }
+        let trader = create_subaccount(&master, SubAccType::Trader);
+        ModuleBalances::make_free_balance_be(
+            &trader,
+            asset::BTC,
+            SignedBalance::Positive(ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &trader,
+            asset::ETH,
+            SignedBalance::Negative(4 * ONE_TOKEN),
+        );
+
+        assert_noop!(
+            ModuleSubaccounts::disable_nav_sampling(
+                RuntimeOrigin::signed(master),
+                Some(SubAccType::Trader)
+            ),
+            Error::<Test>::NavSamplingNotEnabled
+        );
+        assert_noop!(
+            ModuleSubaccounts::enable_nav_sampling(
+                RuntimeOrigin::signed(master),
+                Some(SubAccType::Borrower)
+            ),
+            Error::<Test>::NoSubaccountOfThisType
+        );
+
+        assert_ok!(ModuleSubaccounts::enable_nav_sampling(
+            RuntimeOrigin::signed(master),
+            Some(SubAccType::Trader)
+        ));
+        assert_eq!(
+            ModuleBalances::get_balance(&master, &asset::EQ),
+            SignedBalance::Positive(10 * ONE_TOKEN - fee)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury, &asset::EQ),
+            SignedBalance::Positive(fee)
+        );
+        assert_noop!(
+            ModuleSubaccounts::enable_nav_sampling(
+                RuntimeOrigin::signed(master),
+                Some(SubAccType::Trader)
+            ),
+            Error::<Test>::NavSamplingAlreadyEnabled
+        );
+        assert_ok!(ModuleSubaccounts::enable_nav_sampling(
+            RuntimeOrigin::signed(master),
+            None
+        ));
+        assert_noop!(
+            ModuleSubaccounts::enable_nav_sampling(RuntimeOrigin::signed(other), None),
+            Error::<Test>::TooManyNavSampledAccounts
+        );
+
+        ModuleSubaccounts::on_initialize(5);
+        assert!(ModuleSubaccounts::nav_history(&trader).is_empty());
+
+        for block in [10, 20, 30, 40] {
+            ModuleSubaccounts::on_initialize(block);
+        }
+        let history = ModuleSubaccounts::nav_history(&trader);
+        assert_eq!(
+            history.iter().map(|s| s.block).collect::<Vec<_>>(),
+            vec![20, 30, 40]
+        );
+        let last = history.last().unwrap();
+        assert_eq!(last.collateral, 10_000 * ONE_TOKEN);
+        assert_eq!(last.debt, 1_000 * ONE_TOKEN);
+        assert_eq!(last.nav, SignedBalance::Positive(9_000 * ONE_TOKEN));
+        assert_eq!(ModuleSubaccounts::nav_history(&master).len(), 3);
+
+        assert_ok!(ModuleSubaccounts::disable_nav_sampling(
+            RuntimeOrigin::signed(master),
+            Some(SubAccType::Trader)
+        ));
+        assert!(ModuleSubaccounts::nav_history(&trader).is_empty());
+        assert_ok!(ModuleSubaccounts::enable_nav_sampling(
+            RuntimeOrigin::signed(other),
+            None
+        ));
+    });
+}
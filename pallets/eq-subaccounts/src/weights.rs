@@ -29,6 +29,11 @@ pub trait WeightInfo {
     fn transfer_to_subaccount() -> Weight;
     fn transfer_from_subaccount() -> Weight;
     fn transfer_from_subaccount_redistribute(r: u32) -> Weight;
+    fn authorize_session_key() -> Weight;
+    fn revoke_session_key() -> Weight;
+    fn enable_nav_sampling() -> Weight;
+    fn disable_nav_sampling() -> Weight;
+    fn sample_nav(a: u32) -> Weight;
 }
 
 // for tests
@@ -56,4 +61,24 @@ impl crate::WeightInfo for () {
     fn transfer_from_subaccount_redistribute(_r: u32) -> Weight {
         Weight::zero()
     }
+
+    fn authorize_session_key() -> Weight {
+        Weight::zero()
+    }
+
+    fn revoke_session_key() -> Weight {
+        Weight::zero()
+    }
+
+    fn enable_nav_sampling() -> Weight {
+        Weight::zero()
+    }
+
+    fn disable_nav_sampling() -> Weight {
+        Weight::zero()
+    }
+
+    fn sample_nav(_a: u32) -> Weight {
+        Weight::zero()
+    }
 }
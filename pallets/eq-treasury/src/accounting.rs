@@ -0,0 +1,126 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Treasury accounting. Inflows and outflows of treasury are valued in USD by oracle prices
+//! and accumulated by category for `ReportPeriod` blocks, then the report is closed and kept
+//! in the history of at most `MaxReports` reports.
+
+use super::*;
+use eq_primitives::{TreasuryAccounting, TreasuryFlowCategory};
+
+/// USD value of treasury flows of a category
+#[derive(Clone, Copy, Debug, Default, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct TreasuryFlows<Balance> {
+    /// Value received by treasury
+    pub inflow: Balance,
+    /// Value paid by treasury
+    pub outflow: Balance,
+}
+
+/// Treasury flows of a reporting period
+#[derive(Clone, Debug, Default, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct TreasuryReport<Balance, BlockNumber> {
+    /// First block of the period
+    pub start: BlockNumber,
+    /// Last block of the period, not set until the report is closed
+    pub end: BlockNumber,
+    /// Flows by category, sorted by category
+    pub flows: Vec<(TreasuryFlowCategory, TreasuryFlows<Balance>)>,
+}
+
+impl<T: Config> Pallet<T> {
+    /// Closes the current report in the first block of every period
+    pub(crate) fn process_report(n: T::BlockNumber) -> Weight {
+        let period = T::ReportPeriod::get();
+        if period.is_zero() || !(n % period).is_zero() {
+            return Weight::zero();
+        }
+
+        Self::close_report(n);
+        T::WeightInfo::close_treasury_report()
+    }
+
+    /// Moves the current report to the history, dropping the oldest one if it's full,
+    /// and starts a new report at block `n`
+    pub(crate) fn close_report(n: T::BlockNumber) {
+        let mut report = CurrentReport::<T>::get();
+        report.end = n.saturating_sub(One::one());
+        let (start, end) = (report.start, report.end);
+
+        ReportHistory::<T>::mutate(|history| {
+            if !history.is_empty() && history.len() >= T::MaxReports::get() as usize {
+                history.remove(0);
+            }
+            let _ = history.try_push(report);
+        });
+        CurrentReport::<T>::put(TreasuryReport {
+            start: n,
+            end: Zero::zero(),
+            flows: Vec::new(),
+        });
+
+        Self::deposit_event(Event::<T>::TreasuryReportClosed { start, end });
+    }
+
+    fn record_flow(category: TreasuryFlowCategory, asset: Asset, amount: T::Balance, inflow: bool) {
+        // flows of assets without price are not valued
+        let value = T::PriceGetter::get_price::<EqFixedU128>(&asset)
+            .ok()
+            .and_then(|price| price.checked_mul_int(amount))
+            .unwrap_or_else(Zero::zero);
+        if value.is_zero() {
+            return;
+        }
+
+        CurrentReport::<T>::mutate(|report| {
+            let index = match report.flows.binary_search_by_key(&category, |(c, _)| *c) {
+                Ok(index) => index,
+                Err(index) => {
+                    report.flows.insert(index, (category, Default::default()));
+                    index
+                }
+            };
+            let flows = &mut report.flows[index].1;
+            if inflow {
+                flows.inflow = flows.inflow.saturating_add(value);
+            } else {
+                flows.outflow = flows.outflow.saturating_add(value);
+            }
+        });
+    }
+
+    /// Closed treasury reports, oldest first
+    pub fn treasury_reports() -> Vec<TreasuryReport<T::Balance, T::BlockNumber>> {
+        ReportHistory::<T>::get().into_inner()
+    }
+
+    /// Treasury report of the current period
+    pub fn current_treasury_report() -> TreasuryReport<T::Balance, T::BlockNumber> {
+        CurrentReport::<T>::get()
+    }
+}
+
+impl<T: Config> TreasuryAccounting<T::Balance> for Pallet<T> {
+    fn record_inflow(category: TreasuryFlowCategory, asset: Asset, amount: T::Balance) {
+        Self::record_flow(category, asset, amount, true);
+    }
+
+    fn record_outflow(category: TreasuryFlowCategory, asset: Asset, amount: T::Balance) {
+        Self::record_flow(category, asset, amount, false);
+    }
+}
@@ -23,9 +23,10 @@ use eq_assets;
 use eq_primitives::balance::EqCurrency;
 use eq_primitives::{asset, PriceSetter, SignedBalance};
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::BoundedVec;
 use frame_system::RawOrigin;
 use sp_runtime::FixedI64;
-use sp_std::prelude::*;
+use sp_std::{prelude::*, vec};
 
 pub struct Pallet<T: Config>(crate::Pallet<T>);
 
@@ -86,4 +87,88 @@ benchmarks! {
 
     update_buyout_limit {
     }: _(RawOrigin::Root, Some(100_000_000_000u128.try_into().unwrap_or_default()))
+
+    update_total_buyout_limit {
+    }: _(RawOrigin::Root, Some(1_000_000_000_000u128.try_into().unwrap_or_default()))
+
+    set_lending_policy {
+        init_prices::<T>();
+
+        let treas_acc = crate::Pallet::<T>::account_id();
+        <T as pallet::Config>::EqCurrency::make_free_balance_be(
+            &treas_acc,
+            asset::DOT,
+            SignedBalance::Positive((1000u128 * 1_000_000_000u128).try_into().unwrap_or_default())
+        );
+    }: _(RawOrigin::Root, asset::DOT, Some(Permill::from_percent(50)))
+    verify{
+        assert!(!LendingPositions::<T>::get(asset::DOT).is_zero());
+    }
+
+    rebalance_lending {
+        init_prices::<T>();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let treas_acc = crate::Pallet::<T>::account_id();
+        <T as pallet::Config>::EqCurrency::make_free_balance_be(
+            &treas_acc,
+            asset::DOT,
+            SignedBalance::Positive((1000u128 * 1_000_000_000u128).try_into().unwrap_or_default())
+        );
+        LendingPolicy::<T>::insert(asset::DOT, Permill::from_percent(50));
+    }: _(RawOrigin::Signed(caller), asset::DOT)
+    verify{
+        assert!(!LendingPositions::<T>::get(asset::DOT).is_zero());
+    }
+
+    set_buyback_paused {
+    }: _(RawOrigin::Root, true)
+    verify{
+        assert!(BuybackPaused::<T>::get());
+    }
+
+    claim_xcm_dust {
+        init_prices::<T>();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let treas_acc = crate::Pallet::<T>::account_id();
+        let amount: T::Balance = 1_000_000u128.try_into().unwrap_or_default();
+        <T as pallet::Config>::EqCurrency::make_free_balance_be(
+            &caller,
+            asset::ETH,
+            SignedBalance::Positive((1000u128 * 1_000_000_000u128).try_into().unwrap_or_default())
+        );
+        <T as pallet::Config>::EqCurrency::make_free_balance_be(
+            &treas_acc,
+            asset::DOT,
+            SignedBalance::Positive(amount)
+        );
+        XcmDust::<T>::insert(&caller, asset::DOT, amount);
+    }: _(RawOrigin::Signed(caller.clone()), asset::DOT)
+    verify{
+        assert!(!XcmDust::<T>::contains_key(&caller, asset::DOT));
+    }
+
+    close_treasury_report {
+        let flows = vec![
+            (TreasuryFlowCategory::TxFees, TreasuryFlows::default()),
+            (TreasuryFlowCategory::DexPenalties, TreasuryFlows::default()),
+            (TreasuryFlowCategory::Buyouts, TreasuryFlows::default()),
+            (TreasuryFlowCategory::InterestShare, TreasuryFlows::default()),
+        ];
+        let report = TreasuryReport {
+            start: T::BlockNumber::zero(),
+            end: T::BlockNumber::zero(),
+            flows,
+        };
+        let history = vec![report.clone(); T::MaxReports::get() as usize];
+        ReportHistory::<T>::put(BoundedVec::truncate_from(history));
+        CurrentReport::<T>::put(report);
+        let n = T::ReportPeriod::get();
+    }: {
+        crate::Pallet::<T>::close_report(n);
+    }
+    verify {
+        assert_eq!(CurrentReport::<T>::get().start, n);
+    }
 }
@@ -23,10 +23,19 @@
 //! 2. Charges treasury fee i.e. a small fee on an active debt.
 //! 3. Provides a conversion of non-basic assets to the basic asset in case when an account must pay some fee
 //! yet lacks sufficient funds in the basic asset (Treasury buyout).
+//! 4. Supplies a governance-configured fraction of idle treasury assets into lending pools
+//! and withdraws them back when the treasury needs liquidity for buyouts.
+//! 5. Converts any priced asset into assets of a Curve pool by oracle prices plus fee
+//! and deposits them into the pool in one transaction (zap).
+//! 6. Runs a governance-configured buyback program: every epoch spends a capped value of
+//! treasury assets to buy the basic asset in a Curve pool and burns it.
+//! 7. Accounts treasury inflows and outflows by category in USD and keeps history of
+//! periodic reports available through runtime API.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings)]
 
+mod accounting;
 pub mod benchmarking;
 mod mock;
 mod tests;
@@ -38,9 +47,10 @@ use core::convert::{TryFrom, TryInto};
 use eq_balances::NegativeImbalance;
 use eq_primitives::{
     asset::{Asset, AssetGetter, EQ, GENS},
-    balance::{BalanceGetter, EqCurrency},
+    balance::{BalanceGetter, DepositReason, EqCurrency, WithdrawReason},
     balance_number::EqFixedU128,
-    EqBuyout, PriceGetter, SignedBalance,
+    CurveLiquidity, CurvePoolId, EqBuyout, LendingPoolManager, PriceGetter, SignedBalance,
+    TreasuryAccounting, TreasuryFlowCategory, XcmDustHandler,
 };
 #[allow(unused_imports)]
 use eq_primitives::{AccountRefCounter, AccountRefCounts};
@@ -49,27 +59,33 @@ use frame_support::traits::IsSubType;
 use frame_support::{
     dispatch::DispatchResult,
     ensure, fail,
-    traits::{ExistenceRequirement, Get, OnUnbalanced, UnixTime},
+    traits::{ExistenceRequirement, Get, OnUnbalanced, UnixTime, WithdrawReasons},
+    weights::Weight,
     PalletId, Parameter,
 };
 use frame_support::{pallet_prelude::DispatchResultWithPostInfo, traits::Imbalance};
 use frame_system as system;
 use sp_arithmetic::{FixedPointNumber, FixedPointOperand};
 use sp_runtime::{
-    traits::{AccountIdConversion, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Zero},
+    traits::{
+        AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, MaybeSerializeDeserialize, Member,
+        Zero,
+    },
     traits::{DispatchInfoOf, One, SignedExtension},
     transaction_validity::{
         InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
     },
-    DispatchError,
+    DispatchError, TransactionOutcome,
 };
 use sp_runtime::{ArithmeticError, Permill};
 use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, marker::PhantomData, vec::Vec};
 use system::ensure_signed;
 
+pub use accounting::{TreasuryFlows, TreasuryReport};
 pub use pallet::*;
 
 const BUYOUT_LIMIT_PERIOD_IN_SEC: u64 = 86400; // 1 day
+const MAX_BUYBACK_ASSETS: usize = 5;
 
 /// Type of amount
 #[derive(
@@ -82,6 +98,32 @@ pub enum Amount<Balance> {
     Exchange(Balance),
 }
 
+/// Governance-configured program of buying back the basic asset for treasury assets
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct BuybackProgram<Balance, BlockNumber> {
+    /// Curve pool with the basic asset and all of `assets`
+    pub pool_id: CurvePoolId,
+    /// Treasury assets to spend, in order of spending
+    pub assets: Vec<Asset>,
+    /// Buyback is executed in the first block of every epoch
+    pub epoch_length: BlockNumber,
+    /// Max USD value of treasury assets spent per epoch
+    pub epoch_cap: Balance,
+    /// Max shortfall of bought amount from the amount by oracle prices
+    pub max_slippage: Permill,
+}
+
+/// Accounting of a buyback epoch
+#[derive(Clone, Debug, Default, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct BuybackEpoch<Balance> {
+    /// Spent amounts of treasury assets
+    pub spent: Vec<(Asset, Balance)>,
+    /// USD value of spent assets
+    pub spent_usd: Balance,
+    /// Amount of the basic asset bought and burnt
+    pub burned: Balance,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -130,6 +172,16 @@ pub mod pallet {
         /// Min amount of native token to buyout
         #[pallet::constant]
         type MinAmountToBuyout: Get<Self::Balance>;
+        /// Lending pools to supply idle treasury assets into
+        type LendingPoolManager: LendingPoolManager<Self::Balance, Self::AccountId>;
+        /// Curve pools for zap deposits
+        type CurveLiquidity: CurveLiquidity<Self::AccountId, Self::Balance>;
+        /// Number of blocks in a treasury reporting period
+        #[pallet::constant]
+        type ReportPeriod: Get<Self::BlockNumber>;
+        /// Max number of closed treasury reports kept in history
+        #[pallet::constant]
+        type MaxReports: Get<u32>;
     }
 
     #[pallet::call]
@@ -168,6 +220,144 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Set/unset total buyout limit
+        /// Parameters:
+        /// `limit` - max value of native token all users could get with help of buyout for a period(day), None - to disable the total limit
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::update_total_buyout_limit())]
+        pub fn update_total_buyout_limit(
+            origin: OriginFor<T>,
+            limit: Option<T::Balance>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            match limit {
+                Some(limit) => TotalBuyoutLimit::<T>::put(limit),
+                None => TotalBuyoutLimit::<T>::kill(),
+            }
+
+            Ok(().into())
+        }
+
+        /// Set/unset lending policy for treasury asset and rebalance lending position
+        /// Parameters:
+        /// `asset` - treasury asset to supply into lending pool
+        /// `fraction` - part of treasury `asset` balance to keep supplied, None - to withdraw whole position
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::set_lending_policy())]
+        pub fn set_lending_policy(
+            origin: OriginFor<T>,
+            asset: Asset,
+            fraction: Option<Permill>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            match fraction {
+                Some(fraction) => LendingPolicy::<T>::insert(asset, fraction),
+                None => LendingPolicy::<T>::remove(asset),
+            }
+            Self::deposit_event(Event::<T>::LendingPolicyChanged { asset, fraction });
+
+            Self::do_rebalance_lending(asset)?;
+            Ok(().into())
+        }
+
+        /// Supply or withdraw treasury `asset` so that lending position matches lending policy
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::rebalance_lending())]
+        pub fn rebalance_lending(origin: OriginFor<T>, asset: Asset) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::do_rebalance_lending(asset)?;
+            Ok(().into())
+        }
+
+        /// Exchange asset to assets of Curve pool by oracle price plus fee and add them into the pool.
+        /// Pool assets are split proportionally to pool balances,
+        /// part of `asset_in` that is a pool asset itself is deposited as is.
+        /// Parameters:
+        /// `pool_id` - Curve pool to add liquidity into
+        /// `asset_in` - asset to exchange
+        /// `amount` - amount of exchange asset user will give
+        /// `min_lp_out` - min amount of pool LP tokens user will get
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::zap_add_liquidity())]
+        pub fn zap_add_liquidity(
+            origin: OriginFor<T>,
+            pool_id: CurvePoolId,
+            asset_in: Asset,
+            amount: T::Balance,
+            min_lp_out: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_zap_add_liquidity(who, pool_id, asset_in, amount, min_lp_out)?;
+            Ok(().into())
+        }
+
+        /// Set/unset buyback program
+        /// Parameters:
+        /// `program` - buyback program, None - to stop buybacks
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::set_buyback_program())]
+        pub fn set_buyback_program(
+            origin: OriginFor<T>,
+            program: Option<BuybackProgram<T::Balance, T::BlockNumber>>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            match &program {
+                Some(program) => {
+                    Self::ensure_valid_buyback_program(program)?;
+                    Buyback::<T>::put(program);
+                }
+                None => Buyback::<T>::kill(),
+            }
+            Self::deposit_event(Event::<T>::BuybackProgramChanged { program });
+
+            Ok(().into())
+        }
+
+        /// Pause/resume buyback program without changing it
+        /// Parameters:
+        /// `paused` - true to skip buybacks until resumed
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::set_buyback_paused())]
+        pub fn set_buyback_paused(
+            origin: OriginFor<T>,
+            paused: bool,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            BuybackPaused::<T>::put(paused);
+            Self::deposit_event(Event::<T>::BuybackPausedChanged { paused });
+
+            Ok(().into())
+        }
+
+        /// Pays the caller inbound XCM deposits of `asset` stored as dust
+        /// Parameters:
+        /// `asset` - asset of stored deposits
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::claim_xcm_dust())]
+        pub fn claim_xcm_dust(origin: OriginFor<T>, asset: Asset) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let amount = XcmDust::<T>::get(&who, asset);
+            ensure!(!amount.is_zero(), Error::<T>::NoXcmDust);
+            ensure!(
+                !Self::is_dust(&who, asset, amount),
+                Error::<T>::XcmDustBelowExistentialDeposit
+            );
+
+            T::EqCurrency::currency_transfer(
+                &Self::account_id(),
+                &who,
+                asset,
+                amount,
+                ExistenceRequirement::AllowDeath,
+                eq_primitives::TransferReason::XcmDust,
+                true,
+            )?;
+            XcmDust::<T>::remove(&who, asset);
+            Self::deposit_event(Event::<T>::XcmDustClaimed { who, asset, amount });
+
+            Ok(().into())
+        }
     }
 
     #[pallet::error]
@@ -176,6 +366,8 @@ pub mod pallet {
         WrongAssetToBuyout,
         /// Daily buyout limit exceeded
         BuyoutLimitExceeded,
+        /// Daily total buyout limit of all accounts exceeded
+        TotalBuyoutLimitExceeded,
         /// One of transacted currencies is missing price information
         /// or the price is outdated
         NoPrice,
@@ -183,6 +375,17 @@ pub mod pallet {
         InsufficientTreasuryBalance,
         /// The account balance is too low for an operation
         InsufficientAccountBalance,
+        /// Curve pool doesn't exist
+        PoolNotFound,
+        /// Zap amount is too small to get any pool asset
+        ZapAmountTooLow,
+        /// Buyback program has zero epoch, no or too many assets
+        /// or assets missing in the pool
+        InvalidBuybackProgram,
+        /// There are no stored XCM deposits of the asset
+        NoXcmDust,
+        /// Stored XCM deposits are still worth less than existential deposit
+        XcmDustBelowExistentialDeposit,
     }
 
     /// Stores limit amount user could by for a period.
@@ -195,6 +398,70 @@ pub mod pallet {
     pub type Buyouts<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, (T::Balance, u64), ValueQuery>;
 
+    /// Stores limit amount all users could buy for a period.
+    /// When `None` - total buyouts not limited
+    #[pallet::storage]
+    pub type TotalBuyoutLimit<T: Config> = StorageValue<_, T::Balance, OptionQuery>;
+
+    /// Stores amount of buyouts of all users (amount, timestamp of last buyout)
+    #[pallet::storage]
+    pub type TotalBuyouts<T: Config> = StorageValue<_, (T::Balance, u64), ValueQuery>;
+
+    /// Part of treasury asset balance that should be supplied into lending pool
+    #[pallet::storage]
+    pub type LendingPolicy<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, Permill, OptionQuery>;
+
+    /// Amount of treasury asset currently supplied into lending pool
+    #[pallet::storage]
+    pub type LendingPositions<T: Config> =
+        StorageMap<_, Blake2_128Concat, Asset, T::Balance, ValueQuery>;
+
+    /// Buyback program of the basic asset, None - no buybacks
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type Buyback<T: Config> =
+        StorageValue<_, BuybackProgram<T::Balance, T::BlockNumber>, OptionQuery>;
+
+    /// Kill switch of buyback program
+    #[pallet::storage]
+    pub type BuybackPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Accounting of executed buyback epochs by epoch index
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type BuybackEpochs<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, BuybackEpoch<T::Balance>, OptionQuery>;
+
+    /// Inbound XCM deposits worth less than existential deposit, which couldn't be converted
+    /// to the basic asset, held by treasury until claimed
+    #[pallet::storage]
+    #[pallet::getter(fn xcm_dust)]
+    pub type XcmDust<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        Asset,
+        T::Balance,
+        ValueQuery,
+    >;
+
+    /// Treasury flows of the current reporting period
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type CurrentReport<T: Config> =
+        StorageValue<_, TreasuryReport<T::Balance, T::BlockNumber>, ValueQuery>;
+
+    /// Closed treasury reports, oldest first
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type ReportHistory<T: Config> = StorageValue<
+        _,
+        BoundedVec<TreasuryReport<T::Balance, T::BlockNumber>, T::MaxReports>,
+        ValueQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -205,10 +472,80 @@ pub mod pallet {
             asset: Asset,
             exchange_amount: T::Balance,
         },
+        /// Lending policy for treasury asset is changed
+        LendingPolicyChanged {
+            asset: Asset,
+            fraction: Option<Permill>,
+        },
+        /// Treasury asset is supplied into lending pool
+        LendingSupplied { asset: Asset, amount: T::Balance },
+        /// Treasury asset is withdrawn from lending pool
+        LendingWithdrawn { asset: Asset, amount: T::Balance },
+        /// Asset is exchanged to pool assets and added into Curve pool
+        ZapAddLiquidity {
+            who: T::AccountId,
+            pool_id: CurvePoolId,
+            asset_in: Asset,
+            amount: T::Balance,
+            amounts: Vec<T::Balance>,
+        },
+        /// Buyback program is changed
+        BuybackProgramChanged {
+            program: Option<BuybackProgram<T::Balance, T::BlockNumber>>,
+        },
+        /// Buyback program is paused or resumed
+        BuybackPausedChanged { paused: bool },
+        /// Treasury asset is spent to buy and burn the basic asset
+        BuybackExecuted {
+            epoch: T::BlockNumber,
+            asset: Asset,
+            spent: T::Balance,
+            burned: T::Balance,
+        },
+        /// Treasury asset can't be spent in this epoch
+        BuybackFailed {
+            epoch: T::BlockNumber,
+            asset: Asset,
+            error: DispatchError,
+        },
+        /// Buyback epoch is finished
+        BuybackEpochFinished {
+            epoch: T::BlockNumber,
+            spent_usd: T::Balance,
+            burned: T::Balance,
+        },
+        /// Inbound XCM deposit worth less than existential deposit is exchanged to the basic asset
+        XcmDustConverted {
+            who: T::AccountId,
+            asset: Asset,
+            amount: T::Balance,
+            buyout_amount: T::Balance,
+        },
+        /// Inbound XCM deposit worth less than existential deposit is stored for claim
+        XcmDustStored {
+            who: T::AccountId,
+            asset: Asset,
+            amount: T::Balance,
+        },
+        /// Stored XCM deposits are paid to the account
+        XcmDustClaimed {
+            who: T::AccountId,
+            asset: Asset,
+            amount: T::Balance,
+        },
+        /// Treasury report of the period is closed and stored in history
+        TreasuryReportClosed {
+            start: T::BlockNumber,
+            end: T::BlockNumber,
+        },
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            Self::process_buyback(n).saturating_add(Self::process_report(n))
+        }
+    }
 
     #[pallet::genesis_config]
     pub struct GenesisConfig {
@@ -256,15 +593,88 @@ impl<T: Config> Pallet<T> {
                 Buyouts::<T>::insert(account_id, (buyouts, now));
             };
 
+            let buyouts = buyouts
+                .checked_add(&buyout_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            ensure!(buyouts <= buyout_limit, Error::<T>::BuyoutLimitExceeded);
+        }
+
+        if let Some(total_buyout_limit) = TotalBuyoutLimit::<T>::get() {
+            let now = T::UnixTime::now().as_secs();
+            let current_period = (now / BUYOUT_LIMIT_PERIOD_IN_SEC) * BUYOUT_LIMIT_PERIOD_IN_SEC;
+            let (mut total_buyouts, last_buyout) = TotalBuyouts::<T>::get();
+
+            if !total_buyouts.is_zero() && last_buyout < current_period {
+                total_buyouts = Default::default();
+                TotalBuyouts::<T>::put((total_buyouts, now));
+            };
+
+            let total_buyouts = total_buyouts
+                .checked_add(&buyout_amount)
+                .ok_or(ArithmeticError::Overflow)?;
             ensure!(
-                buyouts + buyout_amount <= buyout_limit,
-                Error::<T>::BuyoutLimitExceeded
+                total_buyouts <= total_buyout_limit,
+                Error::<T>::TotalBuyoutLimitExceeded
             );
         }
 
         Ok(())
     }
 
+    /// Positive treasury balance of `asset` that isn't supplied into lending pool
+    fn free_balance(asset: &Asset) -> T::Balance {
+        match T::BalanceGetter::get_balance(&Self::account_id(), asset) {
+            SignedBalance::Positive(balance) => balance,
+            SignedBalance::Negative(_) => T::Balance::zero(),
+        }
+    }
+
+    fn do_rebalance_lending(asset: Asset) -> DispatchResult {
+        let fraction = LendingPolicy::<T>::get(asset).unwrap_or_else(Permill::zero);
+        let supplied = LendingPositions::<T>::get(asset);
+        let target = fraction * Self::free_balance(&asset).saturating_add(supplied);
+
+        if target > supplied {
+            Self::supply_to_lending(asset, target - supplied)
+        } else if target < supplied {
+            Self::withdraw_from_lending(asset, supplied - target)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn supply_to_lending(asset: Asset, amount: T::Balance) -> DispatchResult {
+        T::LendingPoolManager::supply(&Self::account_id(), &asset, &amount)?;
+        LendingPositions::<T>::mutate(asset, |supplied| {
+            *supplied = supplied.saturating_add(amount)
+        });
+        Self::deposit_event(Event::<T>::LendingSupplied { asset, amount });
+
+        Ok(())
+    }
+
+    fn withdraw_from_lending(asset: Asset, amount: T::Balance) -> DispatchResult {
+        T::LendingPoolManager::redeem(&Self::account_id(), &asset, &amount)?;
+        LendingPositions::<T>::mutate_exists(asset, |supplied| {
+            let rest = supplied.unwrap_or_default().saturating_sub(amount);
+            *supplied = (!rest.is_zero()).then(|| rest);
+        });
+        Self::deposit_event(Event::<T>::LendingWithdrawn { asset, amount });
+
+        Ok(())
+    }
+
+    /// Withdraws treasury `asset` from lending pool if its free balance is less than `amount`
+    fn ensure_liquid(asset: Asset, amount: T::Balance) -> DispatchResult {
+        let supplied = LendingPositions::<T>::get(asset);
+        let free = Self::free_balance(&asset);
+        if supplied.is_zero() || free >= amount {
+            return Ok(());
+        }
+
+        Self::withdraw_from_lending(asset, (amount - free).min(supplied))
+    }
+
     fn ensure_not_eq_or_gens_buyout(asset: &Asset) -> DispatchResult {
         ensure!(
             asset != &EQ && asset != &GENS,
@@ -274,13 +684,27 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    fn update_buyouts(account_id: &T::AccountId, buyout_amount: T::Balance) {
+    fn update_buyouts(account_id: &T::AccountId, buyout_amount: T::Balance) -> DispatchResult {
         if BuyoutLimit::<T>::get().is_some() {
-            Buyouts::<T>::mutate(account_id, |(prev_buyouts, last)| {
-                *prev_buyouts = *prev_buyouts + buyout_amount;
+            Buyouts::<T>::try_mutate(account_id, |(prev_buyouts, last)| -> DispatchResult {
+                *prev_buyouts = prev_buyouts
+                    .checked_add(&buyout_amount)
+                    .ok_or(ArithmeticError::Overflow)?;
                 *last = T::UnixTime::now().as_secs();
-            });
+                Ok(())
+            })?;
+        }
+        if TotalBuyoutLimit::<T>::get().is_some() {
+            TotalBuyouts::<T>::try_mutate(|(prev_buyouts, last)| -> DispatchResult {
+                *prev_buyouts = prev_buyouts
+                    .checked_add(&buyout_amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+                *last = T::UnixTime::now().as_secs();
+                Ok(())
+            })?;
         }
+
+        Ok(())
     }
 
     fn calc_amount_to_exchange(
@@ -362,11 +786,267 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Amounts of pool assets for `amount` of `asset_in` and part of `amount` exchanged with treasury
+    fn calc_zap_amounts(
+        pool: &[(Asset, T::Balance)],
+        asset_in: Asset,
+        amount: T::Balance,
+    ) -> Result<(Vec<T::Balance>, T::Balance), DispatchError> {
+        let price_in: EqFixedU128 = T::PriceGetter::get_price(&asset_in)?;
+        let prices = pool
+            .iter()
+            .map(|(asset, _)| T::PriceGetter::get_price::<EqFixedU128>(asset))
+            .collect::<Result<Vec<_>, _>>()?;
+        // value of pool balances, empty pool is split equally
+        let values: Vec<u128> = pool
+            .iter()
+            .zip(prices.iter())
+            .map(|((_, balance), price)| {
+                let balance: u128 = (*balance).into();
+                price.saturating_mul_int(balance)
+            })
+            .collect();
+        let total_value = values.iter().fold(0u128, |acc, v| acc.saturating_add(*v));
+        let fee = EqFixedU128::from(T::SellFee::get()) + EqFixedU128::one();
+
+        let mut amounts = Vec::with_capacity(pool.len());
+        let mut exchanged = T::Balance::zero();
+        for ((asset, _), (price, value)) in pool.iter().zip(prices.into_iter().zip(values)) {
+            let part = if total_value.is_zero() {
+                multiply_by_rational(amount, 1u128, pool.len() as u128)
+            } else {
+                multiply_by_rational(amount, value, total_value)
+            }
+            .and_then(|p| T::Balance::try_from(p).ok())
+            .ok_or(ArithmeticError::Overflow)?;
+
+            let pool_amount = if *asset == asset_in {
+                part
+            } else {
+                exchanged = exchanged.saturating_add(part);
+                multiply_by_rational(part, price_in.into_inner(), (price * fee).into_inner())
+                    .and_then(|a| T::Balance::try_from(a).ok())
+                    .ok_or(ArithmeticError::Overflow)?
+            };
+            amounts.push(pool_amount);
+        }
+
+        Ok((amounts, exchanged))
+    }
+
+    fn do_zap_add_liquidity(
+        who: T::AccountId,
+        pool_id: CurvePoolId,
+        asset_in: Asset,
+        amount: T::Balance,
+        min_lp_out: T::Balance,
+    ) -> DispatchResult {
+        let pool = T::CurveLiquidity::pool_balances(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        let (amounts, exchanged) = Self::calc_zap_amounts(&pool, asset_in, amount)?;
+        ensure!(
+            amounts.iter().any(|a| !a.is_zero()),
+            Error::<T>::ZapAmountTooLow
+        );
+
+        let self_account_id = Self::account_id();
+        if !exchanged.is_zero() {
+            T::EqCurrency::currency_transfer(
+                &who,
+                &self_account_id,
+                asset_in,
+                exchanged,
+                ExistenceRequirement::AllowDeath,
+                eq_primitives::TransferReason::Common,
+                true,
+            )
+            .map_err(|_| Error::<T>::InsufficientAccountBalance)?;
+        }
+        for ((asset, _), pool_amount) in pool.iter().zip(amounts.iter()) {
+            if *asset == asset_in || pool_amount.is_zero() {
+                continue;
+            }
+            Self::ensure_liquid(*asset, *pool_amount)?;
+            T::EqCurrency::currency_transfer(
+                &self_account_id,
+                &who,
+                *asset,
+                *pool_amount,
+                ExistenceRequirement::KeepAlive,
+                eq_primitives::TransferReason::Common,
+                true,
+            )
+            .map_err(|_| Error::<T>::InsufficientTreasuryBalance)?;
+        }
+
+        T::CurveLiquidity::add_liquidity(&who, pool_id, amounts.clone(), min_lp_out)?;
+
+        Self::deposit_event(Event::<T>::ZapAddLiquidity {
+            who,
+            pool_id,
+            asset_in,
+            amount,
+            amounts,
+        });
+
+        Ok(())
+    }
+
+    fn ensure_valid_buyback_program(
+        program: &BuybackProgram<T::Balance, T::BlockNumber>,
+    ) -> DispatchResult {
+        let basic_asset = T::AssetGetter::get_main_asset();
+        let pool =
+            T::CurveLiquidity::pool_balances(program.pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        let in_pool = |asset: &Asset| pool.iter().any(|(a, _)| a == asset);
+
+        ensure!(
+            !program.epoch_length.is_zero()
+                && !program.assets.is_empty()
+                && program.assets.len() <= MAX_BUYBACK_ASSETS
+                && in_pool(&basic_asset)
+                && program
+                    .assets
+                    .iter()
+                    .all(|asset| *asset != basic_asset && in_pool(asset)),
+            Error::<T>::InvalidBuybackProgram
+        );
+
+        Ok(())
+    }
+
+    /// Executes buyback program in the first block of every epoch
+    fn process_buyback(n: T::BlockNumber) -> Weight {
+        let program = match Buyback::<T>::get() {
+            Some(program) if !BuybackPaused::<T>::get() => program,
+            _ => return T::DbWeight::get().reads(2),
+        };
+        if !(n % program.epoch_length).is_zero() {
+            return T::DbWeight::get().reads(2);
+        }
+
+        Self::do_buyback(n / program.epoch_length, &program);
+        T::WeightInfo::buyback(program.assets.len() as u32)
+    }
+
+    /// Spends treasury assets of buyback program up to epoch cap, failed assets are skipped
+    fn do_buyback(epoch: T::BlockNumber, program: &BuybackProgram<T::Balance, T::BlockNumber>) {
+        let mut report = BuybackEpoch::<T::Balance>::default();
+
+        for asset in program.assets.iter() {
+            let remaining_usd = program.epoch_cap.saturating_sub(report.spent_usd);
+            if remaining_usd.is_zero() {
+                break;
+            }
+
+            let result = frame_support::storage::with_transaction(|| {
+                let result = Self::buyback_asset(*asset, remaining_usd, program);
+                if result.is_ok() {
+                    TransactionOutcome::Commit(Ok(result))
+                } else {
+                    TransactionOutcome::Rollback(Ok(result))
+                }
+            })
+            .and_then(|result| result);
+
+            match result {
+                Ok((spent, _, _)) if spent.is_zero() => {}
+                Ok((spent, spent_usd, burned)) => {
+                    report.spent.push((*asset, spent));
+                    report.spent_usd = report.spent_usd.saturating_add(spent_usd);
+                    report.burned = report.burned.saturating_add(burned);
+                    Self::deposit_event(Event::<T>::BuybackExecuted {
+                        epoch,
+                        asset: *asset,
+                        spent,
+                        burned,
+                    });
+                }
+                Err(error) => {
+                    log::error!(
+                        target: "eq_treasury",
+                        "{}:{}. Buyback failed. Epoch: {:?}, asset: {:?}, error: {:?}",
+                        file!(),
+                        line!(),
+                        epoch,
+                        asset,
+                        error
+                    );
+                    Self::deposit_event(Event::<T>::BuybackFailed {
+                        epoch,
+                        asset: *asset,
+                        error,
+                    });
+                }
+            }
+        }
+
+        Self::deposit_event(Event::<T>::BuybackEpochFinished {
+            epoch,
+            spent_usd: report.spent_usd,
+            burned: report.burned,
+        });
+        BuybackEpochs::<T>::insert(epoch, report);
+    }
+
+    /// Exchanges treasury `asset` worth at most `max_usd` to the basic asset and burns it.
+    /// Returns spent amount, its USD value and burnt amount.
+    fn buyback_asset(
+        asset: Asset,
+        max_usd: T::Balance,
+        program: &BuybackProgram<T::Balance, T::BlockNumber>,
+    ) -> Result<(T::Balance, T::Balance, T::Balance), DispatchError> {
+        let basic_asset = T::AssetGetter::get_main_asset();
+        let pool =
+            T::CurveLiquidity::pool_balances(program.pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        let index_of = |asset: Asset| {
+            pool.iter()
+                .position(|(a, _)| *a == asset)
+                .map(|i| i as u32)
+                .ok_or(Error::<T>::InvalidBuybackProgram)
+        };
+        let (i, j) = (index_of(asset)?, index_of(basic_asset)?);
+
+        let price: EqFixedU128 = T::PriceGetter::get_price(&asset)?;
+        let basic_price: EqFixedU128 = T::PriceGetter::get_price(&basic_asset)?;
+        let max_amount = multiply_by_rational(max_usd, EqFixedU128::accuracy(), price.into_inner())
+            .and_then(|a| T::Balance::try_from(a).ok())
+            .ok_or(ArithmeticError::Overflow)?;
+        let amount = Self::free_balance(&asset).min(max_amount);
+        if amount.is_zero() {
+            return Ok(Default::default());
+        }
+
+        let spent_usd = price
+            .checked_mul_int(amount)
+            .ok_or(ArithmeticError::Overflow)?;
+        let expected = multiply_by_rational(amount, price.into_inner(), basic_price.into_inner())
+            .and_then(|a| T::Balance::try_from(a).ok())
+            .ok_or(ArithmeticError::Overflow)?;
+        let min_out = expected - program.max_slippage * expected;
+
+        let self_account_id = Self::account_id();
+        let before = Self::free_balance(&basic_asset);
+        T::CurveLiquidity::exchange(&self_account_id, program.pool_id, i, j, amount, min_out)?;
+        let burned = Self::free_balance(&basic_asset).saturating_sub(before);
+        T::EqCurrency::withdraw(
+            &self_account_id,
+            basic_asset,
+            burned,
+            true,
+            Some(WithdrawReason::Buyback),
+            WithdrawReasons::empty(),
+            ExistenceRequirement::KeepAlive,
+        )?;
+
+        Ok((amount, spent_usd, burned))
+    }
+
     fn do_buyout(who: T::AccountId, asset: Asset, amount: Amount<T::Balance>) -> DispatchResult {
         Self::ensure_not_eq_or_gens_buyout(&asset)?;
         let basic_asset = T::AssetGetter::get_main_asset();
         let (buyout_amount, exchange_amount) = Self::split_to_buyout_and_exchange(asset, amount)?;
         Self::ensure_buyout_limit_not_exceeded(&who, buyout_amount)?;
+        Self::ensure_liquid(basic_asset, buyout_amount)?;
         let self_account_id = Self::account_id();
 
         T::EqCurrency::exchange(
@@ -387,7 +1067,9 @@ impl<T: Config> Pallet<T> {
             _ => error,
         })?;
 
-        Self::update_buyouts(&who, buyout_amount);
+        Self::record_inflow(TreasuryFlowCategory::Buyouts, asset, exchange_amount);
+        Self::record_outflow(TreasuryFlowCategory::Buyouts, basic_asset, buyout_amount);
+        Self::update_buyouts(&who, buyout_amount)?;
         Self::deposit_event(Event::<T>::Buyout {
             who,
             buyout_amount,
@@ -398,6 +1080,49 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Deposit of `amount` of `asset` leaves `who` with collateral worth less than
+    /// existential deposit. Deposits of assets without price are never dust.
+    fn is_dust(who: &T::AccountId, asset: Asset, amount: T::Balance) -> bool {
+        let value = match T::PriceGetter::get_price::<EqFixedU128>(&asset) {
+            Ok(price) => price.saturating_mul_int(amount),
+            Err(_) => return false,
+        };
+        let collateral = T::BalanceGetter::get_debt_and_collateral(who)
+            .map(|d| d.collateral)
+            .unwrap_or_default();
+
+        collateral.saturating_add(value) < T::EqCurrency::minimum_balance_value()
+    }
+
+    /// Pays `who` the basic asset for `amount` of `asset` held by treasury, as in buyout
+    /// without buyout limits
+    fn convert_dust(
+        who: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+    ) -> Result<T::Balance, DispatchError> {
+        Self::ensure_not_eq_or_gens_buyout(&asset)?;
+        let basic_asset = T::AssetGetter::get_main_asset();
+        let buyout_amount = Self::calc_buyout_amount(asset, amount)?;
+        Self::ensure_liquid(basic_asset, buyout_amount)?;
+        ensure!(
+            Self::free_balance(&basic_asset) >= buyout_amount,
+            Error::<T>::InsufficientTreasuryBalance
+        );
+
+        T::EqCurrency::currency_transfer(
+            &Self::account_id(),
+            who,
+            basic_asset,
+            buyout_amount,
+            ExistenceRequirement::AllowDeath,
+            eq_primitives::TransferReason::XcmDust,
+            true,
+        )?;
+
+        Ok(buyout_amount)
+    }
+
     /// Gets priority value for a currency. Priority value determines which currency
     /// will be used first to withdraw fees when account has insufficient basic_asset
     fn get_currency_priority(asset: Asset) -> u64 {
@@ -433,6 +1158,7 @@ impl<T: Config> EqBuyout<T::AccountId, T::Balance> for Pallet<T> {
         prices.insert(basic_asset, basic_token_price);
 
         // check basic asset module balance and issue if not enough
+        Self::ensure_liquid(basic_asset, amount)?;
         let self_eq_balance = T::BalanceGetter::get_balance(&self_account_id, &basic_asset);
         frame_support::ensure!(
             self_eq_balance >= SignedBalance::Positive(amount),
@@ -477,6 +1203,7 @@ impl<T: Config> EqBuyout<T::AccountId, T::Balance> for Pallet<T> {
                             false,
                         )
                         .expect("currency_transfer failure");
+                        Self::record_inflow(TreasuryFlowCategory::Buyouts, asset, balance);
 
                         amount_left = amount_left - balance_in_eq;
                     } else {
@@ -499,6 +1226,11 @@ impl<T: Config> EqBuyout<T::AccountId, T::Balance> for Pallet<T> {
                             false,
                         )
                         .expect("currency_transfer failure");
+                        Self::record_inflow(
+                            TreasuryFlowCategory::Buyouts,
+                            asset,
+                            balance_to_change,
+                        );
 
                         break;
                     }
@@ -519,6 +1251,7 @@ impl<T: Config> EqBuyout<T::AccountId, T::Balance> for Pallet<T> {
             false,
         )
         .expect("currency transfer failure");
+        Self::record_outflow(TreasuryFlowCategory::Buyouts, basic_asset, amount);
 
         Ok(())
     }
@@ -570,6 +1303,8 @@ pub enum ValidityError {
     LessThanMinBuyoutAmount = 3,
     /// Wrong asset
     WrongAssetToBuyout = 4,
+    /// Total buyout limit of all accounts exceeded
+    TotalBuyoutLimitExceeded = 5,
 }
 
 impl From<ValidityError> for u8 {
@@ -645,7 +1380,7 @@ where
     /// Checks:
     /// - buyout_amount is greater or equal `MinAmountToBuyout`
     /// - `who` has enough to make buyout
-    /// - buyout limit not exceeded for `who`
+    /// - buyout limit not exceeded for `who` and total buyout limit not exceeded
     fn validate(
         &self,
         who: &Self::AccountId,
@@ -680,9 +1415,16 @@ where
                     )),
                 }
 
-                Pallet::<T>::ensure_buyout_limit_not_exceeded(who, buyout_amount).map_err(
-                    |_| InvalidTransaction::Custom(ValidityError::BuyoutLimitExceeded.into()),
-                )?;
+                Pallet::<T>::ensure_buyout_limit_not_exceeded(who, buyout_amount).map_err(|e| {
+                    let total_limit_error: DispatchError =
+                        Error::<T>::TotalBuyoutLimitExceeded.into();
+                    let error = if e == total_limit_error {
+                        ValidityError::TotalBuyoutLimitExceeded
+                    } else {
+                        ValidityError::BuyoutLimitExceeded
+                    };
+                    InvalidTransaction::Custom(error.into())
+                })?;
             }
         }
 
@@ -692,12 +1434,69 @@ where
 
 impl<T: Config> OnUnbalanced<NegativeImbalance<T::Balance>> for Pallet<T> {
     fn on_nonzero_unbalanced(amount: NegativeImbalance<T::Balance>) {
-        let _ = T::EqCurrency::deposit_creating(
+        let basic_asset = T::AssetGetter::get_main_asset();
+        if T::EqCurrency::deposit_creating(
             &Pallet::<T>::account_id(),
-            T::AssetGetter::get_main_asset(),
+            basic_asset,
             amount.peek(),
             false,
             None,
-        );
+        )
+        .is_ok()
+        {
+            Pallet::<T>::record_inflow(TreasuryFlowCategory::TxFees, basic_asset, amount.peek());
+        }
+    }
+}
+
+/// Inbound XCM deposits of non-basic assets worth less than existential deposit are minted to
+/// treasury and exchanged to the basic asset by buyout price. When the exchange isn't possible,
+/// e.g. the treasury lacks the basic asset, the deposit is stored for `claim_xcm_dust`.
+impl<T: Config> XcmDustHandler<T::AccountId, T::Balance> for Pallet<T> {
+    fn handle_dust(
+        who: &T::AccountId,
+        asset: Asset,
+        amount: T::Balance,
+    ) -> Result<bool, DispatchError> {
+        if asset == T::AssetGetter::get_main_asset() || !Self::is_dust(who, asset, amount) {
+            return Ok(false);
+        }
+
+        T::EqCurrency::deposit_creating(
+            &Self::account_id(),
+            asset,
+            amount,
+            false,
+            Some(DepositReason::XcmTransfer),
+        )?;
+
+        let converted = frame_support::storage::with_transaction(|| {
+            let result = Self::convert_dust(who, asset, amount);
+            if result.is_ok() {
+                TransactionOutcome::Commit(Ok(result))
+            } else {
+                TransactionOutcome::Rollback(Ok(result))
+            }
+        })
+        .and_then(|result| result);
+
+        match converted {
+            Ok(buyout_amount) => Self::deposit_event(Event::<T>::XcmDustConverted {
+                who: who.clone(),
+                asset,
+                amount,
+                buyout_amount,
+            }),
+            Err(_) => {
+                XcmDust::<T>::mutate(who, asset, |dust| *dust = dust.saturating_add(amount));
+                Self::deposit_event(Event::<T>::XcmDustStored {
+                    who: who.clone(),
+                    asset,
+                    amount,
+                });
+            }
+        }
+
+        Ok(true)
     }
 }
@@ -85,7 +85,6 @@ parameter_types! {
     pub const TreasuryModuleId: PalletId = PalletId(*b"eq/trsry");
     pub const BailsmanModuleId: PalletId = PalletId(*b"eq/bails");
     pub const BalancesModuleId: PalletId = PalletId(*b"eq/balan");
-    pub const ExistentialDeposit: Balance = 1;
     pub const MainAsset: eq_primitives::asset::Asset = eq_primitives::asset::EQ;
 }
 
@@ -126,6 +125,21 @@ impl system::Config for Test {
 
 thread_local! {
     static CURRENT_TIME: RefCell<u64> = RefCell::new(1598006981634);
+    static EXISTENTIAL_DEPOSIT: RefCell<Balance> = RefCell::new(1);
+}
+
+pub struct ExistentialDeposit;
+
+impl ExistentialDeposit {
+    pub fn set(value: Balance) {
+        EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = value);
+    }
+}
+
+impl Get<Balance> for ExistentialDeposit {
+    fn get() -> Balance {
+        EXISTENTIAL_DEPOSIT.with(|v| *v.borrow())
+    }
 }
 
 pub struct SubaccountsManagerMock;
@@ -234,6 +248,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -266,7 +284,7 @@ impl
         who: &DummyValidatorId,
         changes: &Vec<(Asset, eq_primitives::SignedBalance<Balance>)>,
         _: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), eq_primitives::balance::BalanceCheckError<Balance>> {
         let res = changes.iter().all(|(asset, change)| match change {
             eq_primitives::SignedBalance::Positive(_) => true,
             eq_primitives::SignedBalance::Negative(change_value) => {
@@ -282,8 +300,12 @@ impl
                 }
             }
         });
-        res.then(|| ())
-            .ok_or_else(|| DispatchError::Other("Can change balance mock"))
+        res.then(|| ()).ok_or_else(|| {
+            eq_primitives::balance::BalanceCheckError::new(
+                eq_primitives::balance::BalanceCheckerKind::Other,
+                DispatchError::Other("Can change balance mock"),
+            )
+        })
     }
 }
 
@@ -315,6 +337,8 @@ impl UnixTime for TimeMock {
 
 parameter_types! {
     pub const MinAmountToBuyout: Balance = 100 * eq_utils::ONE_TOKEN;
+    pub const ReportPeriod: u64 = 10;
+    pub const MaxReports: u32 = 2;
 }
 
 impl Config for Test {
@@ -330,6 +354,172 @@ impl Config for Test {
     type UnixTime = TimeMock;
     type WeightInfo = ();
     type MinAmountToBuyout = MinAmountToBuyout;
+    type LendingPoolManager = LendingPoolMock;
+    type CurveLiquidity = CurvePoolMock;
+    type ReportPeriod = ReportPeriod;
+    type MaxReports = MaxReports;
+}
+
+parameter_types! {
+    pub const LendingModuleId: PalletId = PalletId(*b"eq/lendr");
+}
+
+thread_local! {
+    static LENDING_DEPOSITS: RefCell<Vec<(AccountId, Asset, Balance)>> = RefCell::new(Vec::new());
+}
+
+/// Moves supplied balances to the `LendingModuleId` account and tracks deposits
+pub struct LendingPoolMock;
+
+impl LendingPoolMock {
+    pub fn deposit(who: &AccountId, asset: &Asset) -> Balance {
+        LENDING_DEPOSITS.with(|v| {
+            v.borrow()
+                .iter()
+                .find(|(w, a, _)| w == who && a == asset)
+                .map(|(_, _, value)| *value)
+                .unwrap_or_default()
+        })
+    }
+
+    fn set_deposit(who: &AccountId, asset: &Asset, value: Balance) {
+        LENDING_DEPOSITS.with(|v| {
+            let mut deposits = v.borrow_mut();
+            deposits.retain(|(w, a, _)| !(w == who && a == asset));
+            deposits.push((*who, *asset, value));
+        });
+    }
+}
+
+impl LendingPoolManager<Balance, AccountId> for LendingPoolMock {
+    fn add_reward(_asset: Asset, _reward: Balance) -> DispatchResult {
+        Ok(())
+    }
+
+    fn add_deposit(_account: &AccountId, _asset: &Asset, _amount: &Balance) -> DispatchResult {
+        Ok(())
+    }
+
+    fn remove_deposit(_account: &AccountId, _asset: &Asset) -> Result<Balance, DispatchError> {
+        Ok(0)
+    }
+
+    fn supply(account: &AccountId, asset: &Asset, amount: &Balance) -> DispatchResult {
+        ModuleBalances::currency_transfer(
+            account,
+            &LendingModuleId::get().into_account_truncating(),
+            *asset,
+            *amount,
+            ExistenceRequirement::KeepAlive,
+            eq_primitives::TransferReason::Common,
+            false,
+        )?;
+        Self::set_deposit(account, asset, Self::deposit(account, asset) + amount);
+        Ok(())
+    }
+
+    fn redeem(account: &AccountId, asset: &Asset, amount: &Balance) -> DispatchResult {
+        let deposit = Self::deposit(account, asset);
+        if deposit < *amount {
+            return Err(DispatchError::Other("Not enough to withdraw"));
+        }
+        ModuleBalances::currency_transfer(
+            &LendingModuleId::get().into_account_truncating(),
+            account,
+            *asset,
+            *amount,
+            ExistenceRequirement::AllowDeath,
+            eq_primitives::TransferReason::Common,
+            false,
+        )?;
+        Self::set_deposit(account, asset, deposit - amount);
+        Ok(())
+    }
+}
+
+parameter_types! {
+    pub const CurveModuleId: PalletId = PalletId(*b"eq/crvam");
+}
+
+thread_local! {
+    static CURVE_POOL: RefCell<Vec<(Asset, Balance)>> = RefCell::new(Vec::new());
+    static CURVE_EXCHANGE_RATE: RefCell<Balance> = RefCell::new(1);
+}
+
+/// Single Curve pool with id 0, moves added liquidity to the `CurveModuleId` account
+/// and mints sum of amounts as LP tokens. Exchanges `dx` to `dx * rate` of another asset
+pub struct CurvePoolMock;
+
+impl CurvePoolMock {
+    pub fn init(pool: Vec<(Asset, Balance)>) {
+        CURVE_POOL.with(|v| *v.borrow_mut() = pool);
+    }
+
+    pub fn set_exchange_rate(rate: Balance) {
+        CURVE_EXCHANGE_RATE.with(|v| *v.borrow_mut() = rate);
+    }
+}
+
+impl CurveLiquidity<AccountId, Balance> for CurvePoolMock {
+    fn pool_balances(pool_id: CurvePoolId) -> Option<Vec<(Asset, Balance)>> {
+        let pool = CURVE_POOL.with(|v| v.borrow().clone());
+        (pool_id == 0 && !pool.is_empty()).then(|| pool)
+    }
+
+    fn add_liquidity(
+        who: &AccountId,
+        _pool_id: CurvePoolId,
+        amounts: Vec<Balance>,
+        min_mint_amount: Balance,
+    ) -> DispatchResult {
+        if amounts.iter().sum::<Balance>() < min_mint_amount {
+            return Err(DispatchError::Other("Slippage"));
+        }
+        let pool = CURVE_POOL.with(|v| v.borrow().clone());
+        for ((asset, _), amount) in pool.iter().zip(amounts) {
+            ModuleBalances::currency_transfer(
+                who,
+                &CurveModuleId::get().into_account_truncating(),
+                *asset,
+                amount,
+                ExistenceRequirement::AllowDeath,
+                eq_primitives::TransferReason::Common,
+                false,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn exchange(
+        who: &AccountId,
+        _pool_id: CurvePoolId,
+        i: u32,
+        j: u32,
+        dx: Balance,
+        min_dy: Balance,
+    ) -> DispatchResult {
+        let dy = CURVE_EXCHANGE_RATE.with(|v| *v.borrow()) * dx;
+        if dy < min_dy {
+            return Err(DispatchError::Other("Slippage"));
+        }
+        let pool = CURVE_POOL.with(|v| v.borrow().clone());
+        let pool_account: AccountId = CurveModuleId::get().into_account_truncating();
+        for (from, to, asset, amount) in [
+            (who, &pool_account, pool[i as usize].0, dx),
+            (&pool_account, who, pool[j as usize].0, dy),
+        ] {
+            ModuleBalances::currency_transfer(
+                from,
+                to,
+                asset,
+                amount,
+                ExistenceRequirement::AllowDeath,
+                eq_primitives::TransferReason::Common,
+                false,
+            )?;
+        }
+        Ok(())
+    }
 }
 
 pub type ModuleTreasury = Pallet<Test>;
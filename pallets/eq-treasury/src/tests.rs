@@ -20,10 +20,14 @@
 
 use super::{Config, Error, ValidityError};
 use crate::mock::{
-    new_test_ext, AccountId, Balance, DummyValidatorId, ModuleAggregates, ModuleBalances,
-    ModuleTreasury, OracleMock, RuntimeCall, RuntimeOrigin, Test, TimeMock,
+    new_test_ext, AccountId, Balance, CurveModuleId, CurvePoolMock, DummyValidatorId,
+    ExistentialDeposit, LendingPoolMock, ModuleAggregates, ModuleBalances, ModuleTreasury,
+    OracleMock, RuntimeCall, RuntimeOrigin, Test, TimeMock,
+};
+use crate::{
+    Amount, BuybackEpoch, BuybackEpochs, BuybackProgram, BuyoutLimit, Buyouts, CheckBuyout,
+    LendingPolicy, LendingPositions, TotalBuyoutLimit, TotalBuyouts, TreasuryFlows, XcmDust,
 };
-use crate::{Amount, BuyoutLimit, Buyouts, CheckBuyout};
 use eq_primitives::balance_number::EqFixedU128;
 use eq_primitives::{
     asset,
@@ -31,16 +35,18 @@ use eq_primitives::{
     balance::{BalanceGetter, EqCurrency},
     eqfxu128,
     price::PriceSetter,
-    Aggregates, SignedBalance, UserGroup,
+    Aggregates, SignedBalance, TreasuryAccounting, TreasuryFlowCategory, UserGroup, XcmDustHandler,
 };
 use eq_utils::{fixed::fixedi64_from_eq_fixedu128, ONE_TOKEN};
 use frame_support::dispatch::DispatchInfo;
 use frame_support::traits::UnixTime;
 use frame_support::weights::Weight;
 use frame_support::{assert_err, assert_noop, assert_ok, assert_storage_noop};
-use sp_arithmetic::FixedPointNumber;
+use sp_arithmetic::{FixedPointNumber, Permill};
+use sp_runtime::traits::AccountIdConversion;
 use sp_runtime::traits::SignedExtension;
 use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
+use sp_runtime::{ArithmeticError, DispatchError};
 
 fn set_price(asset: &Asset, price: &EqFixedU128) {
     assert_ok!(OracleMock::set_price(
@@ -471,18 +477,66 @@ fn update_buyouts_works() {
         let now = TimeMock::now().as_secs();
 
         //without limit
-        assert_storage_noop!(ModuleTreasury::update_buyouts(&account_id, buyout_amount));
+        assert_storage_noop!(assert_ok!(ModuleTreasury::update_buyouts(
+            &account_id,
+            buyout_amount
+        )));
 
         //with limit
         BuyoutLimit::<Test>::put(100);
-        ModuleTreasury::update_buyouts(&account_id, buyout_amount);
+        assert_ok!(ModuleTreasury::update_buyouts(&account_id, buyout_amount));
         assert_eq!(Buyouts::<Test>::get(account_id), (buyout_amount, now));
 
         //with limit and with existed buyout
         BuyoutLimit::<Test>::put(100);
         Buyouts::<Test>::insert(&account_id, (50, now));
-        ModuleTreasury::update_buyouts(&account_id, buyout_amount);
+        assert_ok!(ModuleTreasury::update_buyouts(&account_id, buyout_amount));
         assert_eq!(Buyouts::<Test>::get(account_id), (150, now));
+
+        Buyouts::<Test>::insert(&account_id, (Balance::MAX, now));
+        assert_err!(
+            ModuleTreasury::update_buyouts(&account_id, buyout_amount),
+            ArithmeticError::Overflow
+        );
+        assert_err!(
+            ModuleTreasury::ensure_buyout_limit_not_exceeded(&account_id, buyout_amount),
+            ArithmeticError::Overflow
+        );
+    });
+}
+
+#[test]
+fn total_buyout_limit_works() {
+    new_test_ext().execute_with(|| {
+        let now = TimeMock::now().as_secs();
+
+        assert_ok!(ModuleTreasury::update_total_buyout_limit(
+            RuntimeOrigin::root(),
+            Some(100)
+        ));
+        assert_eq!(TotalBuyoutLimit::<Test>::get(), Some(100));
+
+        assert_ok!(ModuleTreasury::update_buyouts(&1, 60));
+        assert_eq!(TotalBuyouts::<Test>::get(), (60, now));
+
+        // other account is limited by buyouts of all accounts
+        assert_ok!(ModuleTreasury::ensure_buyout_limit_not_exceeded(&2, 40));
+        assert_err!(
+            ModuleTreasury::ensure_buyout_limit_not_exceeded(&2, 41),
+            Error::<Test>::TotalBuyoutLimitExceeded
+        );
+
+        // buyouts of prev periods are reset
+        TotalBuyouts::<Test>::put((100, 0));
+        assert_ok!(ModuleTreasury::ensure_buyout_limit_not_exceeded(&2, 100));
+        assert_eq!(TotalBuyouts::<Test>::get(), (0, now));
+
+        assert_ok!(ModuleTreasury::update_total_buyout_limit(
+            RuntimeOrigin::root(),
+            None
+        ));
+        assert_eq!(TotalBuyoutLimit::<Test>::get(), None);
+        assert_storage_noop!(assert_ok!(ModuleTreasury::update_buyouts(&1, 60)));
     });
 }
 
@@ -775,3 +829,452 @@ mod signed_extension {
         });
     }
 }
+
+#[test]
+fn lending_policy_supplies_and_withdraws() {
+    new_test_ext().execute_with(|| {
+        let treasury = ModuleTreasury::account_id();
+        ModuleBalances::make_free_balance_be(
+            &treasury,
+            asset::BTC,
+            SignedBalance::Positive(100 * ONE_TOKEN),
+        );
+
+        assert_ok!(ModuleTreasury::set_lending_policy(
+            RuntimeOrigin::root(),
+            asset::BTC,
+            Some(Permill::from_percent(40))
+        ));
+
+        assert_eq!(
+            LendingPolicy::<Test>::get(asset::BTC),
+            Some(Permill::from_percent(40))
+        );
+        assert_eq!(LendingPositions::<Test>::get(asset::BTC), 40 * ONE_TOKEN);
+        assert_eq!(
+            LendingPoolMock::deposit(&treasury, &asset::BTC),
+            40 * ONE_TOKEN
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury, &asset::BTC),
+            SignedBalance::Positive(60 * ONE_TOKEN)
+        );
+
+        // treasury got more BTC, position follows the policy
+        ModuleBalances::make_free_balance_be(
+            &treasury,
+            asset::BTC,
+            SignedBalance::Positive(160 * ONE_TOKEN),
+        );
+        assert_ok!(ModuleTreasury::rebalance_lending(
+            RuntimeOrigin::signed(1),
+            asset::BTC
+        ));
+        assert_eq!(LendingPositions::<Test>::get(asset::BTC), 80 * ONE_TOKEN);
+
+        assert_ok!(ModuleTreasury::set_lending_policy(
+            RuntimeOrigin::root(),
+            asset::BTC,
+            None
+        ));
+        assert!(!LendingPositions::<Test>::contains_key(asset::BTC));
+        assert_eq!(LendingPoolMock::deposit(&treasury, &asset::BTC), 0);
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury, &asset::BTC),
+            SignedBalance::Positive(200 * ONE_TOKEN)
+        );
+    });
+}
+
+#[test]
+fn buyout_withdraws_from_lending() {
+    new_test_ext().execute_with(|| {
+        let account_id = 1u64;
+        let treasury = ModuleTreasury::account_id();
+        ModuleBalances::make_free_balance_be(
+            &account_id,
+            asset::ETH,
+            SignedBalance::Positive(50 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &treasury,
+            asset::EQ,
+            SignedBalance::Positive(10_000 * ONE_TOKEN),
+        );
+        assert_ok!(ModuleTreasury::set_lending_policy(
+            RuntimeOrigin::root(),
+            asset::EQ,
+            Some(Permill::from_percent(50))
+        ));
+        assert_eq!(LendingPositions::<Test>::get(asset::EQ), 5_000 * ONE_TOKEN);
+
+        let buyout_amount = 5_500 * ONE_TOKEN;
+        assert_ok!(ModuleTreasury::buyout(
+            RuntimeOrigin::signed(account_id),
+            asset::ETH,
+            Amount::Buyout(buyout_amount)
+        ));
+
+        assert_eq!(
+            ModuleBalances::get_balance(&account_id, &asset::EQ),
+            SignedBalance::Positive(buyout_amount)
+        );
+        assert_eq!(LendingPositions::<Test>::get(asset::EQ), 4_500 * ONE_TOKEN);
+        assert_eq!(
+            LendingPoolMock::deposit(&treasury, &asset::EQ),
+            4_500 * ONE_TOKEN
+        );
+    });
+}
+
+#[test]
+fn zap_add_liquidity_exchanges_and_deposits() {
+    new_test_ext().execute_with(|| {
+        let account_id = 1u64;
+        let pool_account: AccountId = CurveModuleId::get().into_account_truncating();
+
+        // pool values are equal: 1000 EQD and 4 ETH by 250
+        CurvePoolMock::init(vec![
+            (asset::EQD, 1_000 * ONE_TOKEN),
+            (asset::ETH, 4 * ONE_TOKEN),
+        ]);
+        ModuleBalances::make_free_balance_be(
+            &account_id,
+            asset::ETH,
+            SignedBalance::Positive(5 * ONE_TOKEN),
+        );
+        ModuleBalances::make_free_balance_be(
+            &ModuleTreasury::account_id(),
+            asset::EQD,
+            SignedBalance::Positive(10_000 * ONE_TOKEN),
+        );
+
+        assert_noop!(
+            ModuleTreasury::zap_add_liquidity(
+                RuntimeOrigin::signed(account_id),
+                1,
+                asset::ETH,
+                2 * ONE_TOKEN,
+                0
+            ),
+            Error::<Test>::PoolNotFound
+        );
+        assert_noop!(
+            ModuleTreasury::zap_add_liquidity(
+                RuntimeOrigin::signed(account_id),
+                0,
+                asset::ETH,
+                2 * ONE_TOKEN,
+                1_000 * ONE_TOKEN
+            ),
+            DispatchError::Other("Slippage")
+        );
+
+        assert_ok!(ModuleTreasury::zap_add_liquidity(
+            RuntimeOrigin::signed(account_id),
+            0,
+            asset::ETH,
+            2 * ONE_TOKEN,
+            0
+        ));
+
+        // half of ETH is deposited as is, another half is exchanged by 250 / (1 + 10%)
+        let eqd_amount = 227_272_727_272;
+        assert_eq!(
+            ModuleBalances::get_balance(&account_id, &asset::ETH),
+            SignedBalance::Positive(3 * ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&ModuleTreasury::account_id(), &asset::ETH),
+            SignedBalance::Positive(ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&pool_account, &asset::ETH),
+            SignedBalance::Positive(ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&pool_account, &asset::EQD),
+            SignedBalance::Positive(eqd_amount)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&ModuleTreasury::account_id(), &asset::EQD),
+            SignedBalance::Positive(10_000 * ONE_TOKEN - eqd_amount)
+        );
+    });
+}
+
+#[test]
+fn buyback_spends_capped_value_and_burns() {
+    new_test_ext().execute_with(|| {
+        use frame_support::traits::Hooks;
+
+        let treasury = ModuleTreasury::account_id();
+        let pool_account: AccountId = CurveModuleId::get().into_account_truncating();
+        CurvePoolMock::init(vec![
+            (asset::EQD, 1_000 * ONE_TOKEN),
+            (asset::EQ, 1_000 * ONE_TOKEN),
+            (asset::ETH, 4 * ONE_TOKEN),
+        ]);
+        for (who, asset, amount) in [
+            (treasury, asset::EQD, 300 * ONE_TOKEN),
+            (treasury, asset::ETH, ONE_TOKEN),
+            (treasury, asset::EQ, 1_000 * ONE_TOKEN),
+            (pool_account, asset::EQ, 10_000 * ONE_TOKEN),
+        ] {
+            ModuleBalances::make_free_balance_be(&who, asset, SignedBalance::Positive(amount));
+        }
+
+        let program = BuybackProgram {
+            pool_id: 0,
+            assets: vec![asset::EQD, asset::ETH],
+            epoch_length: 10,
+            epoch_cap: 400 * ONE_TOKEN,
+            max_slippage: Permill::from_percent(5),
+        };
+        assert_noop!(
+            ModuleTreasury::set_buyback_program(RuntimeOrigin::signed(1), Some(program.clone())),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ModuleTreasury::set_buyback_program(
+                RuntimeOrigin::root(),
+                Some(BuybackProgram {
+                    pool_id: 1,
+                    ..program.clone()
+                })
+            ),
+            Error::<Test>::PoolNotFound
+        );
+        assert_noop!(
+            ModuleTreasury::set_buyback_program(
+                RuntimeOrigin::root(),
+                Some(BuybackProgram {
+                    epoch_length: 0,
+                    ..program.clone()
+                })
+            ),
+            Error::<Test>::InvalidBuybackProgram
+        );
+        assert_noop!(
+            ModuleTreasury::set_buyback_program(
+                RuntimeOrigin::root(),
+                Some(BuybackProgram {
+                    assets: vec![asset::EQD, asset::EQ],
+                    ..program.clone()
+                })
+            ),
+            Error::<Test>::InvalidBuybackProgram
+        );
+        assert_ok!(ModuleTreasury::set_buyback_program(
+            RuntimeOrigin::root(),
+            Some(program)
+        ));
+
+        ModuleTreasury::on_initialize(5);
+        assert_eq!(BuybackEpochs::<Test>::iter().count(), 0);
+
+        // all EQD is spent, 100 USD of ETH would get 0.4 EQ in the pool instead of 100 EQ by oracle
+        ModuleTreasury::on_initialize(10);
+        assert_eq!(
+            BuybackEpochs::<Test>::get(1),
+            Some(BuybackEpoch {
+                spent: vec![(asset::EQD, 300 * ONE_TOKEN)],
+                spent_usd: 300 * ONE_TOKEN,
+                burned: 300 * ONE_TOKEN,
+            })
+        );
+        for (who, asset, amount) in [
+            (treasury, asset::EQD, 0),
+            (treasury, asset::ETH, ONE_TOKEN),
+            (treasury, asset::EQ, 1_000 * ONE_TOKEN),
+            (pool_account, asset::EQD, 300 * ONE_TOKEN),
+            (pool_account, asset::EQ, 9_700 * ONE_TOKEN),
+        ] {
+            assert_eq!(
+                ModuleBalances::get_balance(&who, &asset),
+                SignedBalance::Positive(amount)
+            );
+        }
+
+        assert_ok!(ModuleTreasury::set_buyback_paused(
+            RuntimeOrigin::root(),
+            true
+        ));
+        ModuleTreasury::on_initialize(20);
+        assert_eq!(BuybackEpochs::<Test>::get(2), None);
+
+        assert_ok!(ModuleTreasury::set_buyback_paused(
+            RuntimeOrigin::root(),
+            false
+        ));
+        CurvePoolMock::set_exchange_rate(250);
+        ModuleTreasury::on_initialize(30);
+        assert_eq!(
+            BuybackEpochs::<Test>::get(3),
+            Some(BuybackEpoch {
+                spent: vec![(asset::ETH, ONE_TOKEN)],
+                spent_usd: 250 * ONE_TOKEN,
+                burned: 250 * ONE_TOKEN,
+            })
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury, &asset::EQ),
+            SignedBalance::Positive(1_000 * ONE_TOKEN)
+        );
+    });
+}
+
+#[test]
+fn xcm_dust_is_converted_to_basic_asset() {
+    new_test_ext().execute_with(|| {
+        ExistentialDeposit::set(10 * ONE_TOKEN);
+        let account_id = 1u64;
+        let treasury = ModuleTreasury::account_id();
+        ModuleBalances::make_free_balance_be(
+            &treasury,
+            asset::EQ,
+            SignedBalance::Positive(10_000 * ONE_TOKEN),
+        );
+
+        // basic asset and deposits worth more than existential deposit are minted as usual
+        assert_eq!(
+            ModuleTreasury::handle_dust(&account_id, asset::EQ, ONE_TOKEN),
+            Ok(false)
+        );
+        assert_eq!(
+            ModuleTreasury::handle_dust(&account_id, asset::DOT, ONE_TOKEN),
+            Ok(false)
+        );
+
+        // 0.5 DOT is worth 8.5 USD
+        let amount = ONE_TOKEN / 2;
+        assert_eq!(
+            ModuleTreasury::handle_dust(&account_id, asset::DOT, amount),
+            Ok(true)
+        );
+
+        // 8.5 USD by EQ price with 10% sell fee
+        let buyout_amount = 7_727_272_727;
+        assert_eq!(
+            ModuleBalances::get_balance(&account_id, &asset::EQ),
+            SignedBalance::Positive(buyout_amount)
+        );
+        assert!(ModuleBalances::get_balance(&account_id, &asset::DOT).is_zero());
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury, &asset::DOT),
+            SignedBalance::Positive(amount)
+        );
+        assert!(!XcmDust::<Test>::contains_key(&account_id, asset::DOT));
+    });
+}
+
+#[test]
+fn xcm_dust_is_stored_and_claimed() {
+    new_test_ext().execute_with(|| {
+        ExistentialDeposit::set(10 * ONE_TOKEN);
+        let account_id = 1u64;
+        let treasury = ModuleTreasury::account_id();
+        let amount = ONE_TOKEN / 2;
+
+        assert_noop!(
+            ModuleTreasury::claim_xcm_dust(RuntimeOrigin::signed(account_id), asset::DOT),
+            Error::<Test>::NoXcmDust
+        );
+
+        // treasury has no basic asset to convert the deposit
+        assert_eq!(
+            ModuleTreasury::handle_dust(&account_id, asset::DOT, amount),
+            Ok(true)
+        );
+        assert_eq!(
+            ModuleTreasury::handle_dust(&account_id, asset::DOT, amount),
+            Ok(true)
+        );
+        assert_eq!(
+            ModuleTreasury::xcm_dust(&account_id, asset::DOT),
+            2 * amount
+        );
+        assert!(ModuleBalances::get_balance(&account_id, &asset::EQ).is_zero());
+        assert_eq!(
+            ModuleBalances::get_balance(&treasury, &asset::DOT),
+            SignedBalance::Positive(2 * amount)
+        );
+
+        // 17 USD is still less than existential deposit with the stored deposit
+        ExistentialDeposit::set(20 * ONE_TOKEN);
+        assert_noop!(
+            ModuleTreasury::claim_xcm_dust(RuntimeOrigin::signed(account_id), asset::DOT),
+            Error::<Test>::XcmDustBelowExistentialDeposit
+        );
+
+        ModuleBalances::make_free_balance_be(
+            &account_id,
+            asset::ETH,
+            SignedBalance::Positive(ONE_TOKEN),
+        );
+        assert_ok!(ModuleTreasury::claim_xcm_dust(
+            RuntimeOrigin::signed(account_id),
+            asset::DOT
+        ));
+        assert_eq!(
+            ModuleBalances::get_balance(&account_id, &asset::DOT),
+            SignedBalance::Positive(2 * amount)
+        );
+        assert!(ModuleBalances::get_balance(&treasury, &asset::DOT).is_zero());
+        assert!(!XcmDust::<Test>::contains_key(&account_id, asset::DOT));
+    });
+}
+
+#[test]
+fn treasury_report_accounts_flows_and_keeps_history() {
+    new_test_ext().execute_with(|| {
+        use frame_support::traits::Hooks;
+
+        set_price(&asset::DOT, &eqfxu128!(10, 0));
+        set_price(&asset::EQ, &eqfxu128!(2, 0));
+
+        ModuleTreasury::record_inflow(TreasuryFlowCategory::InterestShare, asset::DOT, ONE_TOKEN);
+        ModuleTreasury::record_inflow(TreasuryFlowCategory::TxFees, asset::EQ, 3 * ONE_TOKEN);
+        ModuleTreasury::record_outflow(TreasuryFlowCategory::TxFees, asset::EQ, ONE_TOKEN);
+
+        let flows = vec![
+            (
+                TreasuryFlowCategory::TxFees,
+                TreasuryFlows {
+                    inflow: 6 * ONE_TOKEN,
+                    outflow: 2 * ONE_TOKEN,
+                },
+            ),
+            (
+                TreasuryFlowCategory::InterestShare,
+                TreasuryFlows {
+                    inflow: 10 * ONE_TOKEN,
+                    outflow: 0,
+                },
+            ),
+        ];
+        assert_eq!(ModuleTreasury::current_treasury_report().flows, flows);
+
+        // not the first block of period
+        ModuleTreasury::on_initialize(9);
+        assert!(ModuleTreasury::treasury_reports().is_empty());
+
+        ModuleTreasury::on_initialize(10);
+        let reports = ModuleTreasury::treasury_reports();
+        assert_eq!(reports.len(), 1);
+        assert_eq!((reports[0].start, reports[0].end), (0, 9));
+        assert_eq!(reports[0].flows, flows);
+        let current = ModuleTreasury::current_treasury_report();
+        assert_eq!(current.start, 10);
+        assert!(current.flows.is_empty());
+
+        // history keeps at most `MaxReports` latest reports
+        ModuleTreasury::on_initialize(20);
+        ModuleTreasury::on_initialize(30);
+        let starts: Vec<_> = ModuleTreasury::treasury_reports()
+            .iter()
+            .map(|report| report.start)
+            .collect();
+        assert_eq!(starts, vec![10, 20]);
+    });
+}
@@ -25,6 +25,15 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
     fn buyout() -> Weight;
     fn update_buyout_limit() -> Weight;
+    fn update_total_buyout_limit() -> Weight;
+    fn set_lending_policy() -> Weight;
+    fn rebalance_lending() -> Weight;
+    fn zap_add_liquidity() -> Weight;
+    fn set_buyback_program() -> Weight;
+    fn set_buyback_paused() -> Weight;
+    fn buyback(a: u32) -> Weight;
+    fn claim_xcm_dust() -> Weight;
+    fn close_treasury_report() -> Weight;
 }
 
 // for tests
@@ -35,4 +44,31 @@ impl crate::WeightInfo for () {
     fn update_buyout_limit() -> Weight {
         Weight::zero()
     }
+    fn update_total_buyout_limit() -> Weight {
+        Weight::zero()
+    }
+    fn set_lending_policy() -> Weight {
+        Weight::zero()
+    }
+    fn rebalance_lending() -> Weight {
+        Weight::zero()
+    }
+    fn zap_add_liquidity() -> Weight {
+        Weight::zero()
+    }
+    fn set_buyback_program() -> Weight {
+        Weight::zero()
+    }
+    fn set_buyback_paused() -> Weight {
+        Weight::zero()
+    }
+    fn buyback(_a: u32) -> Weight {
+        Weight::zero()
+    }
+    fn claim_xcm_dust() -> Weight {
+        Weight::zero()
+    }
+    fn close_treasury_report() -> Weight {
+        Weight::zero()
+    }
 }
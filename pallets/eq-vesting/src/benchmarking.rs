@@ -181,6 +181,34 @@ benchmarks_instance_pallet! {
             .unwrap();
 
     }: force_vested_transfer(RawOrigin::Root, caller_lookup, target_lookup, vesting_schedule)
+
+    set_auto_vest {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        add_vesting_schedule::<I, T>(&caller)?;
+    }: _(RawOrigin::Signed(caller.clone()), true)
+    verify {
+        assert!(AutoVest::<T, I>::contains_key(&caller));
+    }
+
+    auto_vest {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        T::Currency::make_free_balance_be(&<T as pallet::Config<I>>::PalletId::get().into_account_truncating(), (1_000_000u32).into());
+        T::FeeCurrency::make_free_balance_be(&caller, (1_000_000_000_000u64).unique_saturated_into());
+        add_vesting_schedule::<I, T>(&caller)?;
+        AutoVest::<T, I>::insert(&caller, ());
+        System::<T>::set_block_number(5u32.into());
+    }: {
+        Vesting::<T, I>::process_auto_vest(Weight::MAX);
+    }
+    verify {
+        assert!(Vesting::<T, I>::vested(&caller).is_some(), "Nothing was vested");
+    }
+
+    set_unlock_epoch {
+    }: _(RawOrigin::Root, 1, Some(100u32.into()))
+    verify {
+        assert_eq!(UnlockEpochs::<T, I>::get(1), Some(100u32.into()));
+    }
 }
 
 #[cfg(test)]
@@ -200,6 +228,7 @@ mod tests {
                 assert_ok!(test_benchmark_vest_other_locked::<Test>());
                 assert_ok!(test_benchmark_vest_other_unlocked::<Test>());
                 assert_ok!(test_benchmark_force_vested_transfer::<Test>());
+                assert_ok!(test_benchmark_set_unlock_epoch::<Test>());
             });
     }
 }
@@ -34,10 +34,11 @@ pub mod weights;
 use codec::{Decode, Encode};
 use core::convert::{TryFrom, TryInto};
 use eq_primitives::vestings::EqVestingSchedule;
-use eq_primitives::{AccountRefCounter, AccountRefCounts, IsTransfersEnabled};
+use eq_primitives::{AccountMigration, AccountRefCounter, AccountRefCounts, IsTransfersEnabled};
 use eq_utils::{eq_ensure, ok_or_error};
 use frame_support::pallet_prelude::DispatchResultWithPostInfo;
 use frame_support::traits::{Currency, ExistenceRequirement, Get};
+use frame_support::weights::Weight;
 use frame_support::PalletId;
 use frame_system::{ensure_root, ensure_signed};
 use sp_arithmetic::traits::CheckedDiv;
@@ -45,9 +46,10 @@ use sp_arithmetic::ArithmeticError;
 use sp_runtime::traits::AccountIdConversion;
 use sp_runtime::{
     traits::{
-        AtLeast32BitUnsigned, Convert, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero,
+        AtLeast32BitUnsigned, Bounded, Convert, MaybeSerializeDeserialize, Saturating,
+        StaticLookup, Zero,
     },
-    DispatchResult, RuntimeDebug,
+    DispatchResult, RuntimeDebug, TransactionOutcome,
 };
 use sp_std::fmt::Debug;
 use sp_std::prelude::*;
@@ -60,7 +62,8 @@ pub struct VestingInfo<Balance, BlockNumber> {
     pub locked: Balance,
     /// Amount that gets unlocked every block after `starting_block`
     pub per_block: Balance,
-    /// Starting block for unlocking (vesting)
+    /// Starting block for unlocking (vesting). For schedules keyed to an unlock epoch
+    /// it is counted from the epoch block
     pub starting_block: BlockNumber,
 }
 
@@ -144,6 +147,16 @@ pub mod pallet {
         type WeightInfo: WeightInfo;
         /// Checks if transaction disabled flag is off
         type IsTransfersEnabled: eq_primitives::IsTransfersEnabled;
+        /// Currency of auto-vest fee (EQ)
+        type FeeCurrency: Currency<Self::AccountId, Balance = Self::Balance>;
+        /// Fee charged from beneficiary for every vest made in `on_idle`
+        #[pallet::constant]
+        type AutoVestFee: Get<Self::Balance>;
+        /// Receiver of auto-vest fee
+        type AutoVestFeeDestination: Get<Self::AccountId>;
+        /// Max number of registered accounts vested in `on_idle` per block
+        #[pallet::constant]
+        type MaxAutoVestPerBlock: Get<u32>;
     }
 
     #[pallet::call]
@@ -257,6 +270,58 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Register or unregister the sender account for automatic vesting in `on_idle`.
+        /// Every automatic vest charges `AutoVestFee` from the account.
+        ///
+        /// The dispatch origin for this call must be _Signed_ and the sender must have funds still
+        /// locked under this module to register.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_auto_vest())]
+        pub fn set_auto_vest(origin: OriginFor<T>, enabled: bool) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            if enabled {
+                eq_ensure!(
+                    Vesting::<T, I>::contains_key(&who),
+                    Error::<T, I>::NotVesting,
+                    target: "eq_vesting",
+                    "{}:{}. The account is not vesting. Who: {:?}.",
+                    file!(),
+                    line!(),
+                    who
+                );
+                AutoVest::<T, I>::insert(&who, ());
+            } else {
+                AutoVest::<T, I>::remove(&who);
+            }
+
+            Self::deposit_event(Event::<T, I>::AutoVestSet(who, enabled));
+            Ok(().into())
+        }
+
+        /// Set or clear the block of unlock `epoch` (e.g. parachain lease end). Vesting of
+        /// schedules keyed to the epoch starts at this block, nothing is unlocked while
+        /// the epoch block is not set.
+        ///
+        /// The dispatch origin for this call must be _Root_.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_unlock_epoch())]
+        pub fn set_unlock_epoch(
+            origin: OriginFor<T>,
+            epoch: u32,
+            block: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            match block {
+                Some(block) => UnlockEpochs::<T, I>::insert(epoch, block),
+                None => UnlockEpochs::<T, I>::remove(epoch),
+            }
+
+            Self::deposit_event(Event::<T, I>::UnlockEpochSet(epoch, block));
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -272,6 +337,12 @@ pub mod pallet {
         /// New value of AccountsPerBlock set
         /// \[accounts_per_block\]
         NewAccountsPerBlock(u32),
+        /// Account is registered or unregistered for automatic vesting
+        /// \[account, enabled\]
+        AutoVestSet(T::AccountId, bool),
+        /// Block of unlock epoch is set or cleared
+        /// \[epoch, block\]
+        UnlockEpochSet(u32, Option<T::BlockNumber>),
     }
 
     #[pallet::error]
@@ -289,7 +360,12 @@ pub mod pallet {
     }
 
     #[pallet::hooks]
-    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
+    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+        /// Vests due amounts of accounts registered for automatic vesting within `remaining_weight`
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::process_auto_vest(remaining_weight)
+        }
+    }
 
     /// Pallet storage: information regarding the vesting of a given account
     #[pallet::storage]
@@ -303,6 +379,29 @@ pub mod pallet {
     pub type Vested<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, T::AccountId, T::Balance>;
 
+    /// Pallet storage: accounts registered for automatic vesting in `on_idle`
+    #[pallet::storage]
+    #[pallet::getter(fn auto_vest)]
+    pub type AutoVest<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+    /// Pallet storage: raw key of the last account processed by automatic vesting,
+    /// next block continues after it
+    #[pallet::storage]
+    pub type AutoVestCursor<T: Config<I>, I: 'static = ()> = StorageValue<_, Vec<u8>>;
+
+    /// Pallet storage: blocks of unlock epochs registered by governance
+    #[pallet::storage]
+    #[pallet::getter(fn unlock_epochs)]
+    pub type UnlockEpochs<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, T::BlockNumber>;
+
+    /// Pallet storage: unlock epoch the vesting schedule of given account is keyed to
+    #[pallet::storage]
+    #[pallet::getter(fn vesting_unlock_epoch)]
+    pub type VestingUnlockEpoch<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
         pub vestings: Vec<(T::AccountId, T::Balance, T::Balance, T::BlockNumber)>,
@@ -352,10 +451,26 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
     pub fn account_id() -> T::AccountId {
         T::PalletId::get().into_account_truncating()
     }
+
+    /// Vesting schedule of `who` with starting block resolved against the unlock epoch.
+    /// Schedule keyed to an epoch without registered block never starts.
+    pub fn effective_vesting(
+        who: &T::AccountId,
+    ) -> Option<VestingInfo<T::Balance, T::BlockNumber>> {
+        let mut vesting = Self::vesting(who)?;
+        if let Some(epoch) = Self::vesting_unlock_epoch(who) {
+            vesting.starting_block = match Self::unlock_epochs(epoch) {
+                Some(block) => block.saturating_add(vesting.starting_block),
+                None => T::BlockNumber::max_value(),
+            };
+        }
+        Some(vesting)
+    }
+
     /// (Re)set or remove the module's currency lock on `who`'s account in accordance with their
     /// current unvested amount.
     fn update_lock(who: T::AccountId) -> DispatchResultWithPostInfo {
-        let option_vesting_info = Self::vesting(&who);
+        let option_vesting_info = Self::effective_vesting(&who);
         let vesting = ok_or_error!(
             option_vesting_info,
             Error::<T, I>::NotVesting,
@@ -380,6 +495,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
             if unlocked_now == vesting.locked {
                 Vesting::<T, I>::remove(&who);
                 Vested::<T, I>::remove(&who);
+                AutoVest::<T, I>::remove(&who);
+                VestingUnlockEpoch::<T, I>::remove(&who);
                 AccountRefCounter::<T>::dec_ref(&who);
                 Self::deposit_event(Event::<T, I>::VestingCompleted(who));
             } else {
@@ -390,6 +507,100 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         };
         Ok(().into())
     }
+
+    /// Amount of `who` available to be vested now
+    fn due_amount(who: &T::AccountId) -> Option<T::Balance> {
+        let vesting = Self::effective_vesting(who)?;
+        let now = <frame_system::Pallet<T>>::block_number();
+        let unlocked_now = vesting.unlocked_at::<T::BlockNumberToBalance>(now);
+        let vested = Self::vested(who).unwrap_or_else(T::Balance::zero);
+        Some(unlocked_now.saturating_sub(vested))
+    }
+
+    /// Charges auto-vest fee and vests due amount of `who`.
+    /// Nothing is changed if fee can't be paid.
+    fn auto_vest_account(who: &T::AccountId) -> DispatchResult {
+        frame_support::storage::with_transaction(|| {
+            let result = T::FeeCurrency::transfer(
+                who,
+                &T::AutoVestFeeDestination::get(),
+                T::AutoVestFee::get(),
+                ExistenceRequirement::KeepAlive,
+            )
+            .and_then(|_| {
+                Self::update_lock(who.clone())
+                    .map(|_| ())
+                    .map_err(|e| e.error)
+            });
+
+            match result {
+                Ok(()) => TransactionOutcome::Commit(Ok(())),
+                Err(err) => TransactionOutcome::Rollback(Err(err)),
+            }
+        })
+    }
+
+    /// Vests registered accounts starting after `AutoVestCursor`, not more than
+    /// `MaxAutoVestPerBlock` accounts and within `remaining_weight`
+    fn process_auto_vest(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let account_weight = T::WeightInfo::auto_vest();
+        let mut consumed = db_weight.reads_writes(1, 1);
+        if consumed
+            .saturating_add(account_weight)
+            .any_gt(remaining_weight)
+        {
+            return Weight::zero();
+        }
+
+        let mut accounts = match AutoVestCursor::<T, I>::get() {
+            Some(cursor) => AutoVest::<T, I>::iter_keys_from(cursor),
+            None => AutoVest::<T, I>::iter_keys(),
+        };
+        let mut processed = 0u32;
+        let mut last_key = None;
+        while processed < T::MaxAutoVestPerBlock::get()
+            && !consumed
+                .saturating_add(account_weight)
+                .any_gt(remaining_weight)
+        {
+            let who = match accounts.next() {
+                Some(who) => who,
+                None => {
+                    // whole registry is processed, start from the beginning next time
+                    last_key = None;
+                    break;
+                }
+            };
+            last_key = Some(accounts.last_raw_key().to_vec());
+            processed += 1;
+            consumed = consumed.saturating_add(account_weight);
+
+            match Self::due_amount(&who) {
+                None => AutoVest::<T, I>::remove(&who),
+                Some(due) if !due.is_zero() => {
+                    if let Err(err) = Self::auto_vest_account(&who) {
+                        log::error!(
+                            "{}:{}. Automatic vesting failed. Who: {:?}, error: {:?}",
+                            file!(),
+                            line!(),
+                            who,
+                            err
+                        );
+                    }
+                }
+                // nothing is unlocked since the last vest, don't charge the fee
+                Some(_) => {}
+            }
+        }
+
+        match last_key {
+            Some(key) => AutoVestCursor::<T, I>::put(key),
+            None => AutoVestCursor::<T, I>::kill(),
+        }
+
+        consumed
+    }
 }
 
 impl<T: Config<I>, I: 'static> EqVestingSchedule<T::Balance, T::AccountId> for Pallet<T, I>
@@ -400,7 +611,7 @@ where
 
     /// Vesting amount rest if user called vest now.
     fn vesting_balance(who: &T::AccountId) -> Option<T::Balance> {
-        if let Some(v) = Self::vesting(who) {
+        if let Some(v) = Self::effective_vesting(who) {
             let now = <frame_system::Pallet<T>>::block_number();
             let locked_now = v.locked_at::<T::BlockNumberToBalance>(now);
             Some(locked_now)
@@ -451,6 +662,34 @@ where
         Ok(())
     }
 
+    /// Adds a vesting schedule keyed to unlock `epoch` to a given account, `starting_block`
+    /// is counted from the epoch block.
+    ///
+    /// If there already exists a vesting schedule for the given account, an `Err` is returned
+    /// and nothing is updated.
+    fn add_vesting_schedule_at_epoch(
+        who: &T::AccountId,
+        locked: T::Balance,
+        per_block: T::Balance,
+        starting_block: T::BlockNumber,
+        epoch: u32,
+    ) -> DispatchResult {
+        if locked.is_zero() {
+            return Ok(());
+        }
+        eq_ensure!(
+            !Vesting::<T, I>::contains_key(who),
+            Error::<T, I>::ExistingVestingSchedule,
+            target: "eq_vesting",
+            "{}:{}. An existing vesting schedule already exists for account. Who: {:?}.",
+            file!(),
+            line!(),
+            who
+        );
+        VestingUnlockEpoch::<T, I>::insert(who, epoch);
+        Self::add_vesting_schedule(who, locked, per_block, starting_block)
+    }
+
     /// Updates an existings vesting schedule for a given account.
     fn update_vesting_schedule(
         who: &T::AccountId,
@@ -493,3 +732,36 @@ impl<T: Config<I>, I: 'static> eq_primitives::Vesting<T::AccountId> for Pallet<T
         Self::vesting(&who).is_some()
     }
 }
+
+impl<T: Config<I>, I: 'static> AccountMigration<T::AccountId> for Pallet<T, I> {
+    fn can_migrate(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        eq_ensure!(
+            !Vesting::<T, I>::contains_key(from) || !Vesting::<T, I>::contains_key(to),
+            Error::<T, I>::ExistingVestingSchedule,
+            target: "eq_vesting",
+            "{}:{}. An existing vesting schedule already exists for account. Who: {:?}.",
+            file!(),
+            line!(),
+            to
+        );
+        Ok(())
+    }
+
+    fn migrate(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+        if let Some(vesting) = Vesting::<T, I>::take(from) {
+            Vesting::<T, I>::insert(to, vesting);
+            if let Some(vested) = Vested::<T, I>::take(from) {
+                Vested::<T, I>::insert(to, vested);
+            }
+            if AutoVest::<T, I>::take(from).is_some() {
+                AutoVest::<T, I>::insert(to, ());
+            }
+            if let Some(epoch) = VestingUnlockEpoch::<T, I>::take(from) {
+                VestingUnlockEpoch::<T, I>::insert(to, epoch);
+            }
+            AccountRefCounter::<T>::inc_ref(to);
+            AccountRefCounter::<T>::dec_ref(from);
+        }
+        Ok(())
+    }
+}
@@ -235,6 +235,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = ();
     type XcmToFee = ();
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU32<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -255,6 +259,9 @@ impl eq_aggregates::Config for Test {
 
 parameter_types! {
     pub const MinVestedTransfer: u128 = 1_000_000_000;
+    pub const AutoVestFee: u128 = 1_000_000;
+    pub const AutoVestFeeDestination: AccountId = 999;
+    pub const MaxAutoVestPerBlock: u32 = 2;
     pub const BasicCurrencyGet: asset::Asset = asset::EQ;
     pub const VestingModuleId: PalletId = PalletId(*b"eq/vestn");
 }
@@ -270,6 +277,10 @@ impl eq_vesting::Config<()> for Test {
     type WeightInfo = ();
     type PalletId = VestingModuleId;
     type IsTransfersEnabled = EqBalances;
+    type FeeCurrency = BasicCurrency;
+    type AutoVestFee = AutoVestFee;
+    type AutoVestFeeDestination = AutoVestFeeDestination;
+    type MaxAutoVestPerBlock = MaxAutoVestPerBlock;
 }
 
 thread_local! {
@@ -25,10 +25,10 @@ use crate::mock::{
 };
 use eq_primitives::balance::EqCurrency;
 use eq_primitives::vestings::EqVestingSchedule;
-use eq_primitives::{asset, balance::BalanceGetter, SignedBalance};
+use eq_primitives::{asset, balance::BalanceGetter, AccountMigration, SignedBalance};
 use eq_utils::fx128;
 use frame_support::pallet_prelude::Hooks;
-use frame_support::{assert_err, assert_ok};
+use frame_support::{assert_err, assert_ok, weights::Weight};
 use frame_system::RawOrigin;
 use sp_arithmetic::FixedI128;
 use sp_runtime::traits::BadOrigin;
@@ -337,3 +337,182 @@ fn on_initialize_remove_correct_number_of_vesting_schedules() {
         }
     });
 }
+
+#[test]
+fn auto_vest_in_on_idle() {
+    new_test_ext().execute_with(|| {
+        let fee_destination = 999;
+        set_pos_balance_with_agg_unsafe(&1, &asset::EQ, fx128!(100, 0));
+        set_pos_balance_with_agg_unsafe(&2, &asset::EQ, fx128!(1, 0));
+
+        System::set_block_number(1);
+
+        let vesting_info = super::VestingInfo {
+            locked: fx128!(10, 0).into_inner() as u128,
+            per_block: fx128!(1, 0).into_inner() as u128,
+            starting_block: 10,
+        };
+        assert_ok!(ModuleVesting::force_vested_transfer(
+            RawOrigin::Root.into(),
+            1,
+            2,
+            vesting_info
+        ));
+
+        assert_err!(
+            ModuleVesting::set_auto_vest(RuntimeOrigin::signed(3), true),
+            Error::<Test>::NotVesting
+        );
+        assert_ok!(ModuleVesting::set_auto_vest(RuntimeOrigin::signed(2), true));
+        assert_eq!(ModuleVesting::auto_vest(2), Some(()));
+
+        // nothing is unlocked yet, fee is not charged
+        ModuleVesting::on_idle(1, Weight::MAX);
+        assert_eq!(ModuleVesting::vested(2), None);
+        assert_eq!(
+            <ModuleBalances as BalanceGetter<u64, u128>>::get_balance(&fee_destination, &asset::EQ),
+            SignedBalance::Positive(0)
+        );
+
+        System::set_block_number(11);
+        ModuleVesting::on_idle(11, Weight::MAX);
+
+        assert_eq!(
+            ModuleVesting::vested(2),
+            Some(fx128!(1, 0).into_inner() as u128)
+        );
+        assert_eq!(
+            <ModuleBalances as BalanceGetter<u64, u128>>::get_balance(&fee_destination, &asset::EQ),
+            SignedBalance::Positive(1_000_000)
+        );
+        assert_eq!(
+            <ModuleBalances as BalanceGetter<u64, u128>>::get_balance(&2, &asset::EQ),
+            SignedBalance::Positive(fx128!(2, 0).into_inner() as u128 - 1_000_000)
+        );
+
+        System::set_block_number(20);
+        ModuleVesting::on_idle(20, Weight::MAX);
+
+        assert_eq!(ModuleVesting::vesting(2), None);
+        assert_eq!(ModuleVesting::auto_vest(2), None);
+    });
+}
+
+#[test]
+fn vesting_keyed_to_unlock_epoch() {
+    new_test_ext().execute_with(|| {
+        let module_account_id = ModuleVesting::account_id();
+        set_pos_balance_with_agg_unsafe(&module_account_id, &asset::EQ, fx128!(100, 0));
+        set_pos_balance_with_agg_unsafe(&2, &asset::EQ, fx128!(1, 0));
+        System::set_block_number(1);
+
+        let locked = fx128!(10, 0).into_inner() as u128;
+        let per_block = fx128!(1, 0).into_inner() as u128;
+        assert_ok!(
+            <ModuleVesting as EqVestingSchedule<u128, u64>>::add_vesting_schedule_at_epoch(
+                &2, locked, per_block, 2, 7
+            )
+        );
+        assert_err!(
+            <ModuleVesting as EqVestingSchedule<u128, u64>>::add_vesting_schedule_at_epoch(
+                &2, locked, per_block, 2, 7
+            ),
+            Error::<Test>::ExistingVestingSchedule
+        );
+        assert_eq!(ModuleVesting::vesting_unlock_epoch(2), Some(7));
+
+        // epoch block is not registered, nothing is unlocked
+        System::set_block_number(100);
+        assert_eq!(ModuleVesting::vesting_balance(&2), Some(locked));
+        assert_ok!(ModuleVesting::vest(RuntimeOrigin::signed(2)));
+        assert_eq!(ModuleVesting::vested(2), None);
+
+        assert_err!(
+            ModuleVesting::set_unlock_epoch(RuntimeOrigin::signed(2), 7, Some(90)),
+            BadOrigin
+        );
+        // vesting starts at epoch block + 2
+        assert_ok!(ModuleVesting::set_unlock_epoch(
+            RawOrigin::Root.into(),
+            7,
+            Some(90)
+        ));
+        assert_eq!(
+            ModuleVesting::vesting_balance(&2),
+            Some(locked - 8 * per_block)
+        );
+
+        // unlock date is shifted for every schedule keyed to the epoch
+        assert_ok!(ModuleVesting::set_unlock_epoch(
+            RawOrigin::Root.into(),
+            7,
+            Some(95)
+        ));
+        assert_eq!(
+            ModuleVesting::vesting_balance(&2),
+            Some(locked - 3 * per_block)
+        );
+        assert_ok!(ModuleVesting::vest(RuntimeOrigin::signed(2)));
+        assert_eq!(ModuleVesting::vested(2), Some(3 * per_block));
+
+        System::set_block_number(107);
+        assert_ok!(ModuleVesting::vest(RuntimeOrigin::signed(2)));
+        assert_eq!(ModuleVesting::vesting(2), None);
+        assert_eq!(ModuleVesting::vesting_unlock_epoch(2), None);
+        assert_eq!(
+            <ModuleBalances as BalanceGetter<u64, u128>>::get_balance(&2, &asset::EQ),
+            SignedBalance::Positive(fx128!(11, 0).into_inner() as u128)
+        );
+    });
+}
+
+#[test]
+fn vesting_is_moved_by_account_migration() {
+    new_test_ext().execute_with(|| {
+        let module_account_id = ModuleVesting::account_id();
+        set_pos_balance_with_agg_unsafe(&module_account_id, &asset::EQ, fx128!(100, 0));
+        set_pos_balance_with_agg_unsafe(&2, &asset::EQ, fx128!(1, 0));
+        set_pos_balance_with_agg_unsafe(&4, &asset::EQ, fx128!(1, 0));
+        System::set_block_number(1);
+
+        let locked = fx128!(10, 0).into_inner() as u128;
+        let per_block = fx128!(1, 0).into_inner() as u128;
+        assert_ok!(
+            <ModuleVesting as EqVestingSchedule<u128, u64>>::add_vesting_schedule(
+                &2, locked, per_block, 2
+            )
+        );
+        assert_ok!(
+            <ModuleVesting as EqVestingSchedule<u128, u64>>::add_vesting_schedule(
+                &4, locked, per_block, 2
+            )
+        );
+        System::set_block_number(5);
+        assert_ok!(ModuleVesting::vest(RuntimeOrigin::signed(2)));
+        assert_ok!(ModuleVesting::set_auto_vest(RuntimeOrigin::signed(2), true));
+
+        // schedules aren't merged
+        assert_err!(
+            <ModuleVesting as AccountMigration<u64>>::can_migrate(&2, &4),
+            Error::<Test>::ExistingVestingSchedule
+        );
+
+        assert_ok!(<ModuleVesting as AccountMigration<u64>>::can_migrate(
+            &2, &3
+        ));
+        assert_ok!(<ModuleVesting as AccountMigration<u64>>::migrate(&2, &3));
+        assert_eq!(ModuleVesting::vesting(2), None);
+        assert_eq!(ModuleVesting::vested(2), None);
+        assert_eq!(ModuleVesting::auto_vest(2), None);
+        assert_eq!(ModuleVesting::vested(3), Some(3 * per_block));
+        assert_eq!(ModuleVesting::auto_vest(3), Some(()));
+
+        System::set_block_number(7);
+        assert_ok!(ModuleVesting::vest(RuntimeOrigin::signed(3)));
+        assert_eq!(ModuleVesting::vested(3), Some(5 * per_block));
+        assert_eq!(
+            <ModuleBalances as BalanceGetter<u64, u128>>::get_balance(&3, &asset::EQ),
+            SignedBalance::Positive(2 * per_block)
+        );
+    });
+}
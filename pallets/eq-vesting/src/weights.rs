@@ -28,6 +28,9 @@ pub trait WeightInfo {
     fn vest_other_locked() -> Weight;
     fn vest_other_unlocked() -> Weight;
     fn vested_transfer() -> Weight;
+    fn set_auto_vest() -> Weight;
+    fn auto_vest() -> Weight;
+    fn set_unlock_epoch() -> Weight;
 }
 
 // for tests
@@ -48,4 +51,13 @@ impl crate::WeightInfo for () {
     fn vested_transfer() -> Weight {
         Weight::zero()
     }
+    fn set_auto_vest() -> Weight {
+        Weight::zero()
+    }
+    fn auto_vest() -> Weight {
+        Weight::zero()
+    }
+    fn set_unlock_epoch() -> Weight {
+        Weight::zero()
+    }
 }
@@ -0,0 +1,64 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium Watchtower Pallet Benchmarking
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::One;
+
+const SEED: u32 = 0;
+
+fn fund<T: Config>(who: &T::AccountId, value: T::Balance) {
+    T::EqCurrency::deposit_creating(who, T::AssetGetter::get_main_asset(), value, true, None)
+        .expect("deposit must not fail");
+}
+
+benchmarks! {
+    register {
+        let who: T::AccountId = account("who", 0, SEED);
+        fund::<T>(&who, T::WatchtowerBond::get());
+    }: _(RawOrigin::Signed(who.clone()))
+    verify {
+        assert!(Watchtowers::<T>::contains_key(&who));
+    }
+
+    unregister {
+        let who: T::AccountId = account("who", 0, SEED);
+        fund::<T>(&who, T::WatchtowerBond::get());
+        Pallet::<T>::register(RawOrigin::Signed(who.clone()).into()).unwrap();
+    }: _(RawOrigin::Signed(who.clone()))
+    verify {
+        assert!(!Watchtowers::<T>::contains_key(&who));
+    }
+
+    // price of the main asset may be set, so the alert is either paid or slashed
+    submit_alert {
+        let who: T::AccountId = account("who", 0, SEED);
+        fund::<T>(&who, T::WatchtowerBond::get());
+        fund::<T>(&T::TreasuryModuleId::get().into_account_truncating(), T::WatchtowerBond::get());
+        Pallet::<T>::register(RawOrigin::Signed(who.clone()).into()).unwrap();
+        frame_system::Pallet::<T>::set_block_number(One::one());
+    }: _(RawOrigin::Signed(who.clone()), Alert::StalePrice(T::AssetGetter::get_main_asset()))
+    verify {
+        assert!(Watchtowers::<T>::get(&who).map_or(true, |w| w.confirmed_alerts + w.false_alerts == 1));
+    }
+}
@@ -0,0 +1,292 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! # Equilibrium Watchtower Pallet
+//!
+//! Crowdsources protocol monitoring to independent watchtowers.
+//!
+//! An account becomes a watchtower by bonding `WatchtowerBond` of the main asset with
+//! `register`. Watchtowers post alerts about protocol risks with `submit_alert`, such as a
+//! stale oracle price or an undercollateralized bailsman pool. An alert is verified on-chain
+//! against the current storage right away:
+//! - a correct alert is paid `AlertBounty` from the treasury. The same alert is paid at most
+//!   once in `AlertCooldown` blocks, so a lasting problem isn't paid repeatedly;
+//! - an incorrect alert slashes `FalseAlertSlash` of the watchtower bond to the treasury.
+//!   A watchtower whose bond can't cover the next slash is unregistered.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(warnings)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode};
+use eq_primitives::{
+    asset::{Asset, AssetGetter},
+    balance::EqCurrency,
+    balance_number::EqFixedU128,
+    BailsmanCoverage, PriceGetter, TransferReason,
+};
+use frame_support::{
+    pallet_prelude::*,
+    traits::{BalanceStatus, ExistenceRequirement},
+    PalletId,
+};
+use sp_runtime::{
+    traits::{AccountIdConversion, Saturating},
+    FixedI64,
+};
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+/// Protocol risk reported by a watchtower
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum Alert {
+    /// Price of the asset is outdated or missing
+    StalePrice(Asset),
+    /// Price of the asset jumped over the allowed limit and isn't confirmed yet
+    SuspectPrice(Asset),
+    /// Bailsman pool covers less than `MinBailsmanCoverage` of borrowers debt
+    BailsmanUndercollateralized,
+}
+
+/// Bond and alerts statistics of a watchtower
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct Watchtower<Balance> {
+    /// Bonded amount of the main asset, reserved on the watchtower account
+    pub bond: Balance,
+    /// Number of correct alerts
+    pub confirmed_alerts: u32,
+    /// Number of incorrect alerts
+    pub false_alerts: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::AtLeast32BitUnsigned;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Numerical representation of stored balances
+        type Balance: Parameter + Member + AtLeast32BitUnsigned + Copy + Default;
+        /// Reserves bonds and pays bounties
+        type EqCurrency: EqCurrency<Self::AccountId, Self::Balance>;
+        /// Gets the main asset bonds and bounties are paid in
+        type AssetGetter: AssetGetter;
+        /// Verifies price alerts
+        type PriceGetter: PriceGetter;
+        /// Verifies bailsman pool alerts
+        type BailsmanCoverage: BailsmanCoverage;
+        /// Treasury ModuleId, pays bounties and receives slashed bonds
+        #[pallet::constant]
+        type TreasuryModuleId: Get<PalletId>;
+        /// Bond required to register a watchtower
+        #[pallet::constant]
+        type WatchtowerBond: Get<Self::Balance>;
+        /// Bounty paid for a correct alert
+        #[pallet::constant]
+        type AlertBounty: Get<Self::Balance>;
+        /// Part of the bond slashed for an incorrect alert
+        #[pallet::constant]
+        type FalseAlertSlash: Get<Self::Balance>;
+        /// Min number of blocks between bounties for the same alert
+        #[pallet::constant]
+        type AlertCooldown: Get<Self::BlockNumber>;
+        /// Bailsman pool coverage of borrowers debt below which the pool is undercollateralized
+        #[pallet::constant]
+        type MinBailsmanCoverage: Get<EqFixedU128>;
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Registered watchtowers
+    #[pallet::storage]
+    #[pallet::getter(fn watchtower)]
+    pub type Watchtowers<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Watchtower<T::Balance>, OptionQuery>;
+
+    /// Block of the last paid bounty by alert
+    #[pallet::storage]
+    #[pallet::getter(fn last_paid_alert)]
+    pub type LastPaidAlerts<T: Config> =
+        StorageMap<_, Blake2_128Concat, Alert, T::BlockNumber, OptionQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Account bonded and became a watchtower
+        WatchtowerRegistered { who: T::AccountId, bond: T::Balance },
+        /// Watchtower is unregistered and the rest of its bond is returned
+        WatchtowerUnregistered { who: T::AccountId, bond: T::Balance },
+        /// Alert is verified and the bounty is paid
+        AlertConfirmed {
+            who: T::AccountId,
+            alert: Alert,
+            bounty: T::Balance,
+        },
+        /// Alert is incorrect and the watchtower bond is slashed
+        AlertRejected {
+            who: T::AccountId,
+            alert: Alert,
+            slashed: T::Balance,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Account is already a watchtower
+        AlreadyRegistered,
+        /// Account is not a watchtower
+        NotRegistered,
+        /// Bounty for the alert was paid less than `AlertCooldown` blocks ago
+        AlertCooldown,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Bonds `WatchtowerBond` of the main asset and registers the caller as a watchtower
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::register())]
+        pub fn register(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                !Watchtowers::<T>::contains_key(&who),
+                Error::<T>::AlreadyRegistered
+            );
+
+            let bond = T::WatchtowerBond::get();
+            T::EqCurrency::reserve(&who, T::AssetGetter::get_main_asset(), bond)?;
+            Watchtowers::<T>::insert(
+                &who,
+                Watchtower {
+                    bond,
+                    ..Default::default()
+                },
+            );
+
+            Self::deposit_event(Event::WatchtowerRegistered { who, bond });
+            Ok(().into())
+        }
+
+        /// Unregisters the caller and returns the rest of its bond
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::unregister())]
+        pub fn unregister(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let watchtower = Watchtowers::<T>::get(&who).ok_or(Error::<T>::NotRegistered)?;
+
+            Self::do_unregister(who, watchtower.bond);
+            Ok(().into())
+        }
+
+        /// Posts an alert which is verified against the current state. Correct alert is paid
+        /// `AlertBounty` from the treasury, incorrect one slashes `FalseAlertSlash` of the bond.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::submit_alert())]
+        pub fn submit_alert(origin: OriginFor<T>, alert: Alert) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let mut watchtower = Watchtowers::<T>::get(&who).ok_or(Error::<T>::NotRegistered)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            if let Some(last_paid) = LastPaidAlerts::<T>::get(alert) {
+                ensure!(
+                    now >= last_paid.saturating_add(T::AlertCooldown::get()),
+                    Error::<T>::AlertCooldown
+                );
+            }
+
+            let main_asset = T::AssetGetter::get_main_asset();
+            let treasury = T::TreasuryModuleId::get().into_account_truncating();
+            if Self::is_alert_correct(&alert) {
+                let bounty = T::AlertBounty::get();
+                T::EqCurrency::currency_transfer(
+                    &treasury,
+                    &who,
+                    main_asset,
+                    bounty,
+                    ExistenceRequirement::KeepAlive,
+                    TransferReason::Common,
+                    true,
+                )?;
+                LastPaidAlerts::<T>::insert(alert, now);
+                watchtower.confirmed_alerts = watchtower.confirmed_alerts.saturating_add(1);
+                Watchtowers::<T>::insert(&who, watchtower);
+
+                Self::deposit_event(Event::AlertConfirmed { who, alert, bounty });
+            } else {
+                let slashed = T::FalseAlertSlash::get().min(watchtower.bond);
+                T::EqCurrency::repatriate_reserved(
+                    &who,
+                    &treasury,
+                    main_asset,
+                    slashed,
+                    BalanceStatus::Free,
+                )?;
+                watchtower.bond = watchtower.bond - slashed;
+                watchtower.false_alerts = watchtower.false_alerts.saturating_add(1);
+                Self::deposit_event(Event::AlertRejected {
+                    who: who.clone(),
+                    alert,
+                    slashed,
+                });
+
+                if watchtower.bond < T::FalseAlertSlash::get() {
+                    Self::do_unregister(who, watchtower.bond);
+                } else {
+                    Watchtowers::<T>::insert(&who, watchtower);
+                }
+            }
+
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Checks `alert` against the current state
+    pub fn is_alert_correct(alert: &Alert) -> bool {
+        match alert {
+            Alert::StalePrice(asset) => {
+                T::AssetGetter::exists(*asset)
+                    && T::PriceGetter::get_price::<FixedI64>(asset).is_err()
+            }
+            Alert::SuspectPrice(asset) => T::PriceGetter::is_price_suspect(asset),
+            Alert::BailsmanUndercollateralized => T::BailsmanCoverage::coverage_ratio()
+                .map_or(false, |coverage| coverage < T::MinBailsmanCoverage::get()),
+        }
+    }
+
+    fn do_unregister(who: T::AccountId, bond: T::Balance) {
+        T::EqCurrency::unreserve(&who, T::AssetGetter::get_main_asset(), bond);
+        Watchtowers::<T>::remove(&who);
+
+        Self::deposit_event(Event::WatchtowerUnregistered { who, bond });
+    }
+}
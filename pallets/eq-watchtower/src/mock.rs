@@ -0,0 +1,152 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as eq_watchtower;
+use eq_primitives::asset::{self, AssetType};
+use frame_support::{parameter_types, traits::Everything};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    FixedPointNumber,
+};
+use std::cell::RefCell;
+use test_utils::{
+    builder::{AssetConfig, ExtBuilder},
+    mock::assets::AssetGetterMock,
+};
+
+pub type AccountId = u64;
+pub type Balance = eq_primitives::balance::Balance;
+pub type EqCurrencyMock = test_utils::mock::currency::EqCurrencyMock<AccountId>;
+pub type OracleMock = eq_primitives::price::mock::OracleMock<AccountId>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        EqWatchtower: eq_watchtower::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const TreasuryModuleId: PalletId = PalletId(*b"eq/trsry");
+    pub const WatchtowerBond: Balance = 100;
+    pub const AlertBounty: Balance = 10;
+    pub const FalseAlertSlash: Balance = 40;
+    pub const AlertCooldown: u64 = 10;
+    pub MinBailsmanCoverage: EqFixedU128 = EqFixedU128::saturating_from_rational(11, 10);
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqCurrencyMock;
+    type AssetGetter = AssetGetterMock;
+    type PriceGetter = OracleMock;
+    type BailsmanCoverage = BailsmanCoverageMock;
+    type TreasuryModuleId = TreasuryModuleId;
+    type WatchtowerBond = WatchtowerBond;
+    type AlertBounty = AlertBounty;
+    type FalseAlertSlash = FalseAlertSlash;
+    type AlertCooldown = AlertCooldown;
+    type MinBailsmanCoverage = MinBailsmanCoverage;
+    type WeightInfo = ();
+}
+
+thread_local! {
+    static COVERAGE: RefCell<Option<EqFixedU128>> = RefCell::new(None);
+}
+
+pub fn treasury() -> AccountId {
+    TreasuryModuleId::get().into_account_truncating()
+}
+
+/// Free balance of the main asset
+pub fn balance(who: AccountId) -> Balance {
+    EqCurrencyMock::free_balance(&who, asset::EQ)
+}
+
+pub fn reserved(who: AccountId) -> Balance {
+    EqCurrencyMock::reserved_balance(&who, asset::EQ)
+}
+
+pub fn set_balance(who: AccountId, value: Balance) {
+    EqCurrencyMock::set_balance(&who, asset::EQ, value);
+}
+
+pub fn set_coverage(coverage: Option<EqFixedU128>) {
+    COVERAGE.with(|v| *v.borrow_mut() = coverage);
+}
+
+pub struct BailsmanCoverageMock;
+impl BailsmanCoverage for BailsmanCoverageMock {
+    fn coverage_ratio() -> Option<EqFixedU128> {
+        COVERAGE.with(|v| *v.borrow())
+    }
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    ExtBuilder::default()
+        .asset(AssetConfig::new(asset::EQ, AssetType::Native, u64::MAX))
+        .asset(AssetConfig::new(asset::BTC, AssetType::Physical, 1))
+        .init_mocks();
+
+    let r = frame_system::GenesisConfig::default().build_storage::<Test>();
+
+    let mut ext: sp_io::TestExternalities = r.unwrap().into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
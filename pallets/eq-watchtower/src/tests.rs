@@ -0,0 +1,178 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+use eq_primitives::asset;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{traits::One, DispatchError};
+
+const ALICE: AccountId = 1;
+const BOB: AccountId = 2;
+
+fn register(who: AccountId) {
+    set_balance(who, WatchtowerBond::get());
+    assert_ok!(EqWatchtower::register(RuntimeOrigin::signed(who)));
+}
+
+#[test]
+fn register_reserves_bond() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            EqWatchtower::register(RuntimeOrigin::signed(ALICE)),
+            DispatchError::Other("Not enough balance")
+        );
+
+        register(ALICE);
+        assert_eq!(reserved(ALICE), WatchtowerBond::get());
+        assert_eq!(
+            EqWatchtower::watchtower(ALICE),
+            Some(Watchtower {
+                bond: WatchtowerBond::get(),
+                confirmed_alerts: 0,
+                false_alerts: 0,
+            })
+        );
+        assert_noop!(
+            EqWatchtower::register(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::AlreadyRegistered
+        );
+
+        assert_noop!(
+            EqWatchtower::unregister(RuntimeOrigin::signed(BOB)),
+            Error::<Test>::NotRegistered
+        );
+        assert_ok!(EqWatchtower::unregister(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(reserved(ALICE), 0);
+        assert_eq!(balance(ALICE), WatchtowerBond::get());
+        assert_eq!(EqWatchtower::watchtower(ALICE), None);
+    });
+}
+
+#[test]
+fn correct_alert_is_paid_once_per_cooldown() {
+    new_test_ext().execute_with(|| {
+        set_balance(treasury(), 100);
+        register(ALICE);
+        register(BOB);
+        let alert = Alert::StalePrice(asset::BTC);
+
+        assert_ok!(EqWatchtower::submit_alert(
+            RuntimeOrigin::signed(ALICE),
+            alert
+        ));
+        assert_eq!(balance(ALICE), AlertBounty::get());
+        assert_eq!(balance(treasury()), 100 - AlertBounty::get());
+        assert_eq!(EqWatchtower::last_paid_alert(alert), Some(1));
+        System::assert_last_event(
+            Event::AlertConfirmed {
+                who: ALICE,
+                alert,
+                bounty: AlertBounty::get(),
+            }
+            .into(),
+        );
+
+        // the same problem is reported by another watchtower
+        assert_noop!(
+            EqWatchtower::submit_alert(RuntimeOrigin::signed(BOB), alert),
+            Error::<Test>::AlertCooldown
+        );
+
+        System::set_block_number(1 + AlertCooldown::get());
+        assert_ok!(EqWatchtower::submit_alert(
+            RuntimeOrigin::signed(BOB),
+            alert
+        ));
+        assert_eq!(balance(BOB), AlertBounty::get());
+        assert_eq!(EqWatchtower::watchtower(BOB).unwrap().confirmed_alerts, 1);
+    });
+}
+
+#[test]
+fn false_alert_slashes_bond() {
+    new_test_ext().execute_with(|| {
+        OracleMock::init(vec![(asset::BTC, FixedI64::one())]);
+        register(ALICE);
+
+        let alert = Alert::StalePrice(asset::BTC);
+        assert_ok!(EqWatchtower::submit_alert(
+            RuntimeOrigin::signed(ALICE),
+            alert
+        ));
+        assert_eq!(
+            reserved(ALICE),
+            WatchtowerBond::get() - FalseAlertSlash::get()
+        );
+        assert_eq!(balance(treasury()), FalseAlertSlash::get());
+        assert_eq!(EqWatchtower::watchtower(ALICE).unwrap().false_alerts, 1);
+        assert_eq!(EqWatchtower::last_paid_alert(alert), None);
+        System::assert_last_event(
+            Event::AlertRejected {
+                who: ALICE,
+                alert,
+                slashed: FalseAlertSlash::get(),
+            }
+            .into(),
+        );
+
+        // price of unknown asset isn't stale, rest of the bond can't cover the next slash
+        assert_ok!(EqWatchtower::submit_alert(
+            RuntimeOrigin::signed(ALICE),
+            Alert::StalePrice(asset::DOT)
+        ));
+        let bond_left = WatchtowerBond::get() - 2 * FalseAlertSlash::get();
+        assert_eq!(EqWatchtower::watchtower(ALICE), None);
+        assert_eq!(reserved(ALICE), 0);
+        assert_eq!(balance(ALICE), bond_left);
+        assert_eq!(balance(treasury()), 2 * FalseAlertSlash::get());
+        System::assert_last_event(
+            Event::WatchtowerUnregistered {
+                who: ALICE,
+                bond: bond_left,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn bailsman_alert_is_checked_against_coverage() {
+    new_test_ext().execute_with(|| {
+        set_balance(treasury(), 100);
+        register(ALICE);
+
+        // no debt to cover
+        assert!(!EqWatchtower::is_alert_correct(
+            &Alert::BailsmanUndercollateralized
+        ));
+        set_coverage(Some(EqFixedU128::one()));
+        assert!(EqWatchtower::is_alert_correct(
+            &Alert::BailsmanUndercollateralized
+        ));
+
+        assert_ok!(EqWatchtower::submit_alert(
+            RuntimeOrigin::signed(ALICE),
+            Alert::BailsmanUndercollateralized
+        ));
+        assert_eq!(balance(ALICE), AlertBounty::get());
+        assert_eq!(reserved(ALICE), WatchtowerBond::get());
+    });
+}
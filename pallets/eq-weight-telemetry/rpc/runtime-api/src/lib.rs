@@ -0,0 +1,44 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for `eq-weight-telemetry` pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use eq_weight_telemetry::{CallClass, MeasuredSample, WeightSample};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait EqWeightTelemetryApi<BlockNumber>
+    where
+        BlockNumber: Codec
+    {
+        /// Stored weight samples grouped by (pallet index, call index)
+        fn call_weights() -> Vec<(CallClass, Vec<WeightSample<BlockNumber>>)>;
+
+        /// Call classes whose reported weight exceeded declared one, with the worst sample
+        fn underweighted_calls() -> Vec<(CallClass, WeightSample<BlockNumber>)>;
+
+        /// Stored weight samples with ref time measured on the node, requires offchain indexing
+        fn measured_call_weights() -> Vec<(CallClass, Vec<MeasuredSample<BlockNumber>>)>;
+
+        /// Call classes whose measured ref time exceeded declared one, with the worst sample
+        fn measured_underweighted_calls() -> Vec<(CallClass, MeasuredSample<BlockNumber>)>;
+    }
+}
@@ -0,0 +1,357 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium Weight Telemetry Pallet
+//!
+//! Collects declared and actual weights of dispatched signed extrinsics grouped by
+//! call class (pallet index, call index) into per-class ring buffers, so that
+//! systematically under-weighted extrinsics can be found on a live network.
+//!
+//! Reported weight is the one returned by the call in `PostDispatchInfo` before it is
+//! capped by the declared weight. Calls that don't report actual weight are recorded
+//! with their declared weight. Reported weight is computed from the same `WeightInfo` as
+//! the declared one, so on-chain samples only catch post-dispatch corrections exceeding
+//! the declared weight.
+//!
+//! Actual cost is measured as ref time elapsed between `pre_dispatch` and `post_dispatch`
+//! of the `RecordCallWeight` extension with the benchmarking `current_time` host function.
+//! Measured time is not deterministic, so it is not put into state: it is written with
+//! offchain indexing under [`measured_key`] of the sample and is read by offchain calls,
+//! see [`Pallet::measured_call_weights`]. Nodes must run with offchain indexing enabled
+//! and have benchmarking host functions to collect it.
+//!
+//! Samples are recorded by the `RecordCallWeight` signed extension only when the
+//! `telemetry` feature is enabled, otherwise the extension is a no-op. Runtimes include
+//! the pallet and the extension only in instrumented builds. Recording a sample is
+//! registered as extra block weight of the call dispatch class.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(warnings)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    dispatch::{DispatchInfo, PostDispatchInfo},
+    traits::Get,
+    weights::Weight,
+};
+use sp_runtime::{
+    offchain::StorageKind,
+    traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension},
+    transaction_validity::TransactionValidityError,
+    DispatchResult, RuntimeDebug,
+};
+use sp_std::{fmt::Debug, marker::PhantomData, vec::Vec};
+
+pub use pallet::*;
+
+/// Pallet index and call index of a dispatched call
+pub type CallClass = (u8, u8);
+
+/// Prefix of offchain indexed measured ref times
+pub const MEASURED_PREFIX: &[u8] = b"eq-weight-telemetry::measured";
+
+/// Offchain key of the ref time measured for a call dispatched in `extrinsic` of `block`
+pub fn measured_key<BlockNumber: Encode>(block: BlockNumber, extrinsic: u32) -> Vec<u8> {
+    (MEASURED_PREFIX, block, extrinsic).encode()
+}
+
+/// Weights of a single dispatched call
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub struct WeightSample<BlockNumber> {
+    /// Block the call was dispatched in
+    pub block: BlockNumber,
+    /// Index of the extrinsic in the block
+    pub extrinsic: u32,
+    /// Weight declared in `DispatchInfo`
+    pub declared: Weight,
+    /// Weight reported by the call after dispatch, see module docs
+    pub reported: Weight,
+}
+
+impl<BlockNumber> WeightSample<BlockNumber> {
+    /// Reported ref time or proof size exceeds declared one
+    pub fn is_underweighted(&self) -> bool {
+        self.reported.any_gt(self.declared)
+    }
+}
+
+/// Weight sample with ref time measured on the node
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct MeasuredSample<BlockNumber> {
+    pub sample: WeightSample<BlockNumber>,
+    /// Measured ref time, `None` if it wasn't indexed by the node
+    pub measured: Option<u64>,
+}
+
+impl<BlockNumber> MeasuredSample<BlockNumber> {
+    /// Measured ref time exceeds declared one
+    pub fn is_underweighted(&self) -> bool {
+        self.measured
+            .map_or(false, |measured| measured > self.sample.declared.ref_time())
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Max number of samples stored for a single call class
+        #[pallet::constant]
+        type MaxSamples: Get<u32>;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Last `MaxSamples` weight samples of each call class
+    #[pallet::storage]
+    pub type CallWeights<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        CallClass,
+        BoundedVec<WeightSample<T::BlockNumber>, T::MaxSamples>,
+        ValueQuery,
+    >;
+}
+
+impl<T: Config> Pallet<T> {
+    /// Appends a sample to `class` ring buffer dropping the oldest one if buffer is full.
+    /// Returns dropped sample.
+    pub fn record(
+        class: CallClass,
+        declared: Weight,
+        reported: Weight,
+    ) -> Option<WeightSample<T::BlockNumber>> {
+        let sample = WeightSample {
+            block: frame_system::Pallet::<T>::block_number(),
+            extrinsic: frame_system::Pallet::<T>::extrinsic_index().unwrap_or_default(),
+            declared,
+            reported,
+        };
+
+        CallWeights::<T>::mutate(class, |samples| {
+            let dropped = if samples.is_full() && !samples.is_empty() {
+                Some(samples.remove(0))
+            } else {
+                None
+            };
+            let _ = samples.try_push(sample);
+            dropped
+        })
+    }
+
+    /// Indexes ref time measured for the current extrinsic and clears the one of `dropped`
+    /// sample. Offchain indexing doesn't change state.
+    fn index_measured(measured: u64, dropped: Option<WeightSample<T::BlockNumber>>) {
+        let key = measured_key(
+            frame_system::Pallet::<T>::block_number(),
+            frame_system::Pallet::<T>::extrinsic_index().unwrap_or_default(),
+        );
+        sp_io::offchain_index::set(&key, &measured.encode());
+
+        if let Some(dropped) = dropped {
+            sp_io::offchain_index::clear(&measured_key(dropped.block, dropped.extrinsic));
+        }
+    }
+
+    /// Ref time measured for `sample`, available only in offchain calls
+    pub fn measured_ref_time(sample: &WeightSample<T::BlockNumber>) -> Option<u64> {
+        sp_io::offchain::local_storage_get(
+            StorageKind::PERSISTENT,
+            &measured_key(sample.block, sample.extrinsic),
+        )
+        .and_then(|value| u64::decode(&mut &value[..]).ok())
+    }
+
+    /// All stored samples grouped by call class
+    pub fn call_weights() -> Vec<(CallClass, Vec<WeightSample<T::BlockNumber>>)> {
+        CallWeights::<T>::iter()
+            .map(|(class, samples)| (class, samples.into_inner()))
+            .collect()
+    }
+
+    /// Call classes with at least one under-weighted sample and the worst of them
+    pub fn underweighted_calls() -> Vec<(CallClass, WeightSample<T::BlockNumber>)> {
+        CallWeights::<T>::iter()
+            .filter_map(|(class, samples)| {
+                samples
+                    .into_iter()
+                    .filter(WeightSample::is_underweighted)
+                    .max_by_key(|s| s.reported.ref_time().saturating_sub(s.declared.ref_time()))
+                    .map(|sample| (class, sample))
+            })
+            .collect()
+    }
+
+    /// All stored samples with measured ref time grouped by call class
+    pub fn measured_call_weights() -> Vec<(CallClass, Vec<MeasuredSample<T::BlockNumber>>)> {
+        CallWeights::<T>::iter()
+            .map(|(class, samples)| {
+                let samples = samples
+                    .into_iter()
+                    .map(|sample| MeasuredSample {
+                        measured: Self::measured_ref_time(&sample),
+                        sample,
+                    })
+                    .collect();
+                (class, samples)
+            })
+            .collect()
+    }
+
+    /// Call classes with measured ref time exceeding declared one and the worst sample
+    pub fn measured_underweighted_calls() -> Vec<(CallClass, MeasuredSample<T::BlockNumber>)> {
+        Self::measured_call_weights()
+            .into_iter()
+            .filter_map(|(class, samples)| {
+                samples
+                    .into_iter()
+                    .filter(MeasuredSample::is_underweighted)
+                    .max_by_key(|s| {
+                        s.measured
+                            .unwrap_or_default()
+                            .saturating_sub(s.sample.declared.ref_time())
+                    })
+                    .map(|sample| (class, sample))
+            })
+            .collect()
+    }
+}
+
+/// Records declared, reported and measured weights of dispatched calls
+#[derive(Encode, Decode, Clone, Eq, PartialEq, scale_info::TypeInfo)]
+pub struct RecordCallWeight<T: Config + Send + Sync + scale_info::TypeInfo>(PhantomData<T>);
+
+impl<T: Config + Send + Sync + scale_info::TypeInfo> Debug for RecordCallWeight<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "RecordCallWeight")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync + scale_info::TypeInfo> Default for RecordCallWeight<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Config + Send + Sync + scale_info::TypeInfo> RecordCallWeight<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    /// Call class is read from the first two bytes of encoded `RuntimeCall`,
+    /// returned with current time in nanoseconds
+    #[cfg(feature = "telemetry")]
+    fn call_class(call: &T::RuntimeCall) -> Option<(CallClass, u128)>
+    where
+        T::RuntimeCall: Encode,
+    {
+        call.using_encoded(|encoded| match encoded {
+            [pallet_index, call_index, ..] => Some((
+                (*pallet_index, *call_index),
+                frame_benchmarking::benchmarking::current_time(),
+            )),
+            _ => None,
+        })
+    }
+
+    #[cfg(not(feature = "telemetry"))]
+    fn call_class(_call: &T::RuntimeCall) -> Option<(CallClass, u128)> {
+        None
+    }
+
+    /// Ref time elapsed since `start`, includes `post_dispatch` of preceding extensions
+    #[cfg(feature = "telemetry")]
+    fn measure(start: u128) -> u64 {
+        let elapsed = frame_benchmarking::benchmarking::current_time().saturating_sub(start);
+        (elapsed.min(u64::MAX.into()) as u64)
+            .saturating_mul(frame_support::weights::constants::WEIGHT_REF_TIME_PER_NANOS)
+    }
+
+    #[cfg(not(feature = "telemetry"))]
+    fn measure(_start: u128) -> u64 {
+        0
+    }
+}
+
+impl<T: Config + Send + Sync + scale_info::TypeInfo> SignedExtension for RecordCallWeight<T>
+where
+    T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + Encode,
+{
+    const IDENTIFIER: &'static str = "RecordCallWeight";
+    type AccountId = T::AccountId;
+    type Call = T::RuntimeCall;
+    type AdditionalSigned = ();
+    type Pre = Option<(CallClass, u128)>;
+
+    fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        Ok(())
+    }
+
+    fn pre_dispatch(
+        self,
+        _who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        Ok(Self::call_class(call))
+    }
+
+    fn post_dispatch(
+        pre: Option<Self::Pre>,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        _len: usize,
+        _result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        if let Some(Some((class, start))) = pre {
+            let measured = Self::measure(start);
+            let dropped = Pallet::<T>::record(
+                class,
+                info.weight,
+                post_info.actual_weight.unwrap_or(info.weight),
+            );
+            Pallet::<T>::index_measured(measured, dropped);
+            // ring buffer read and write, not included in the call weight
+            frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                T::DbWeight::get().reads_writes(1, 1),
+                info.class,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate::mock::{new_test_ext, Test};
+use crate::{measured_key, CallWeights, MeasuredSample, Pallet, WeightSample};
+use codec::Encode;
+use frame_support::weights::Weight;
+use sp_core::offchain::{testing::TestOffchainExt, OffchainDbExt, StorageKind};
+
+#[test]
+fn record_drops_oldest_sample() {
+    new_test_ext().execute_with(|| {
+        let class = (5, 1);
+        for i in 1..=4u64 {
+            frame_system::Pallet::<Test>::set_block_number(i);
+            Pallet::<Test>::record(
+                class,
+                Weight::from_parts(100, 0),
+                Weight::from_parts(100 + i, 0),
+            );
+        }
+
+        let samples = CallWeights::<Test>::get(class);
+        assert_eq!(samples.len(), 3);
+        assert_eq!(
+            samples.iter().map(|s| s.block).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert!(CallWeights::<Test>::get((5, 2)).is_empty());
+    });
+}
+
+#[test]
+fn underweighted_calls_returns_worst_sample() {
+    new_test_ext().execute_with(|| {
+        let declared = Weight::from_parts(100, 100);
+        Pallet::<Test>::record((1, 0), declared, Weight::from_parts(50, 50));
+        Pallet::<Test>::record((2, 0), declared, Weight::from_parts(150, 50));
+        Pallet::<Test>::record((2, 0), declared, Weight::from_parts(300, 50));
+        Pallet::<Test>::record((2, 0), declared, Weight::from_parts(90, 50));
+        Pallet::<Test>::record((3, 0), declared, Weight::from_parts(100, 101));
+
+        let mut underweighted = Pallet::<Test>::underweighted_calls();
+        underweighted.sort_by_key(|(class, _)| *class);
+
+        assert_eq!(
+            underweighted,
+            vec![
+                (
+                    (2, 0),
+                    WeightSample {
+                        block: 0,
+                        extrinsic: 0,
+                        declared,
+                        reported: Weight::from_parts(300, 50)
+                    }
+                ),
+                (
+                    (3, 0),
+                    WeightSample {
+                        block: 0,
+                        extrinsic: 0,
+                        declared,
+                        reported: Weight::from_parts(100, 101)
+                    }
+                ),
+            ]
+        );
+        assert_eq!(Pallet::<Test>::call_weights().len(), 3);
+    });
+}
+
+#[test]
+fn measured_underweighted_calls_compares_measured_ref_time() {
+    let mut ext = new_test_ext();
+    let (offchain, _) = TestOffchainExt::new();
+    ext.register_extension(OffchainDbExt::new(offchain));
+
+    ext.execute_with(|| {
+        let declared = Weight::from_parts(100, 100);
+        for extrinsic in 0..3u32 {
+            frame_system::Pallet::<Test>::set_extrinsic_index(extrinsic);
+            Pallet::<Test>::record((1, 0), declared, declared);
+        }
+        frame_system::Pallet::<Test>::set_extrinsic_index(3);
+        Pallet::<Test>::record((2, 0), declared, declared);
+
+        // reported weights don't exceed declared ones
+        assert!(Pallet::<Test>::underweighted_calls().is_empty());
+
+        for (extrinsic, measured) in [(0u32, 150u64), (1, 400), (3, 90)] {
+            sp_io::offchain::local_storage_set(
+                StorageKind::PERSISTENT,
+                &measured_key(0u64, extrinsic),
+                &measured.encode(),
+            );
+        }
+
+        assert_eq!(
+            Pallet::<Test>::measured_underweighted_calls(),
+            vec![(
+                (1, 0),
+                MeasuredSample {
+                    sample: WeightSample {
+                        block: 0,
+                        extrinsic: 1,
+                        declared,
+                        reported: declared,
+                    },
+                    measured: Some(400),
+                }
+            )]
+        );
+
+        let measured: Vec<_> = Pallet::<Test>::measured_call_weights()
+            .into_iter()
+            .flat_map(|(_, samples)| samples.into_iter().map(|s| s.measured))
+            .collect();
+        assert_eq!(measured.len(), 4);
+        assert!(measured.contains(&None));
+    });
+}
+
+#[cfg(feature = "telemetry")]
+#[test]
+fn extension_records_call_weights() {
+    use crate::RecordCallWeight;
+    use frame_support::{
+        dispatch::{DispatchInfo, PostDispatchInfo},
+        traits::Get,
+        weights::constants::RocksDbWeight,
+    };
+    use sp_runtime::traits::SignedExtension;
+
+    new_test_ext().execute_with(|| {
+        let call = crate::mock::RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let info = DispatchInfo {
+            weight: Weight::from_parts(100, 0),
+            ..Default::default()
+        };
+        let post_info = PostDispatchInfo {
+            actual_weight: Some(Weight::from_parts(120, 0)),
+            pays_fee: Default::default(),
+        };
+
+        let pre = RecordCallWeight::<Test>::new()
+            .pre_dispatch(&1, &call, &info, 0)
+            .unwrap();
+        assert_eq!(pre.map(|(class, _)| class), Some((0, 0)));
+        assert!(
+            RecordCallWeight::<Test>::post_dispatch(Some(pre), &info, &post_info, 0, &Ok(()))
+                .is_ok()
+        );
+
+        assert_eq!(
+            CallWeights::<Test>::get((0, 0)).into_inner(),
+            vec![WeightSample {
+                block: 0,
+                extrinsic: 0,
+                declared: Weight::from_parts(100, 0),
+                reported: Weight::from_parts(120, 0),
+            }]
+        );
+        assert_eq!(
+            frame_system::Pallet::<Test>::block_weight().total(),
+            RocksDbWeight::get().reads_writes(1, 1)
+        );
+    });
+}
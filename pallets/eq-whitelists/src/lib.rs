@@ -26,11 +26,13 @@
 
 pub mod benchmarking;
 mod mock;
+mod origin;
 mod tests;
 pub mod weights;
 
 use core::convert::TryInto;
 use eq_primitives::{AccountRefCounter, AccountRefCounts};
+pub use origin::EnsureWhitelisted;
 use sp_std::prelude::*;
 pub use weights::WeightInfo;
 
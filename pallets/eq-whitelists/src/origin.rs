@@ -0,0 +1,45 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CheckWhitelisted, Config, Pallet};
+use frame_support::traits::EnsureOrigin;
+
+/// Origin signed by a whitelisted account, e.g. a price feeder or a liquidation keeper
+pub struct EnsureWhitelisted<T>(T);
+
+impl<T: Config> EnsureOrigin<T::RuntimeOrigin> for EnsureWhitelisted<T> {
+    type Success = T::AccountId;
+
+    fn try_origin(o: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+        use frame_system::RawOrigin;
+        use RawOrigin::Signed;
+        o.into().and_then(|raw| match raw {
+            Signed(acc_id) if Pallet::<T>::in_whitelist(&acc_id) => Ok(acc_id),
+            r => Err(T::RuntimeOrigin::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> T::RuntimeOrigin {
+        let who = Pallet::<T>::accounts()
+            .into_iter()
+            .next()
+            .expect("Whitelist is empty");
+        T::RuntimeOrigin::from(frame_system::RawOrigin::Signed(who))
+    }
+}
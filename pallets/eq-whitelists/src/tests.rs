@@ -18,9 +18,9 @@
 
 #![cfg(test)]
 
-use crate::mock::{new_test_ext, ModuleWhitelists, Test};
-use crate::CheckWhitelisted;
-use frame_support::assert_ok;
+use crate::mock::{new_test_ext, ModuleWhitelists, RuntimeOrigin, Test};
+use crate::{CheckWhitelisted, EnsureWhitelisted};
+use frame_support::{assert_ok, traits::EnsureOrigin};
 
 #[test]
 fn add_whitelist() {
@@ -67,3 +67,21 @@ fn remove_from_whitelist() {
         assert!(frame_system::Pallet::<Test>::providers(&account_id) == refs_before - 1);
     });
 }
+
+#[test]
+fn ensure_whitelisted_accepts_whitelisted_accounts() {
+    new_test_ext().execute_with(|| {
+        let account_id: u64 = 1;
+        assert!(EnsureWhitelisted::<Test>::try_origin(RuntimeOrigin::signed(account_id)).is_err());
+
+        assert_ok!(ModuleWhitelists::add_to_whitelist(
+            frame_system::RawOrigin::Root.into(),
+            account_id
+        ));
+        assert_eq!(
+            EnsureWhitelisted::<Test>::try_origin(RuntimeOrigin::signed(account_id)).ok(),
+            Some(account_id)
+        );
+        assert!(EnsureWhitelisted::<Test>::try_origin(RuntimeOrigin::root()).is_err());
+    });
+}
@@ -0,0 +1,40 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for `eq-wrapped-dot` pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use eq_wrapped_dot::{LotAvailability, UnbondingLot, UnbondingLotId};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait EqWrappedDotApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec
+    {
+        /// Unbonding lots of the account waiting in the withdraw queue
+        fn unbonding_lots(who: AccountId) -> Vec<UnbondingLot<AccountId, Balance>>;
+
+        /// Expected time from which the pending withdrawal may be fulfilled
+        fn lot_availability(lot_id: UnbondingLotId) -> Option<LotAvailability<BlockNumber>>;
+    }
+}
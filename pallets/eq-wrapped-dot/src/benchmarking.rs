@@ -19,7 +19,7 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use eq_primitives::asset::AssetGetter;
+use eq_primitives::asset::{self, AssetGetter};
 use eq_primitives::{PriceSetter, SignedBalance};
 use eq_utils::ONE_TOKEN;
 use frame_benchmarking::{account, benchmarks};
@@ -45,7 +45,7 @@ fn init_wrapped_dot_supply<T: Config>() {
 
     T::EqCurrency::make_free_balance_be(
         &account_id,
-        asset::EQDOT,
+        T::WrappedAsset::get(),
         SignedBalance::Positive(balance),
     );
 
@@ -111,11 +111,11 @@ fn prepare_withdraw_queue<T: Config>(count: u32) {
 
     T::EqCurrency::make_free_balance_be(
         &pallet_account,
-        asset::EQDOT,
+        T::WrappedAsset::get(),
         SignedBalance::Positive(initial_eqdot),
     );
 
-    let mut withdraw_queue: Vec<(_, _, _)> = vec![];
+    let mut withdraw_queue = vec![];
 
     for i in 0..count {
         let beneficiary: T::AccountId = account("beneficiary", i, SEED);
@@ -123,12 +123,20 @@ fn prepare_withdraw_queue<T: Config>(count: u32) {
 
         T::EqCurrency::make_free_balance_be(
             &beneficiary,
-            asset::EQDOT,
+            T::WrappedAsset::get(),
             SignedBalance::Positive(initial_eqdot),
         );
 
-        withdraw_queue.push((beneficiary, amount_to_withdraw, to_burn))
+        withdraw_queue.push(UnbondingLot {
+            id: i as UnbondingLotId,
+            beneficiary,
+            withdraw_amount: amount_to_withdraw,
+            burn_amount: to_burn,
+            requested_era: 1,
+            unlock_era: 1 + T::BondingDuration::get(),
+        })
     }
+    NextUnbondingLotId::<T>::put(count as UnbondingLotId);
 
     WithdrawQueue::<T>::put(withdraw_queue);
 }
@@ -207,7 +215,7 @@ benchmarks! {
 
         T::EqCurrency::deposit_creating(
             &caller,
-            asset::DOT,
+            T::RelayAsset::get(),
             deposit_amount,
             true,
             None,
@@ -215,7 +223,7 @@ benchmarks! {
     }:_(RawOrigin::Signed(caller.clone()), deposit_amount)
     verify{
         assert_eq!(
-            T::EqCurrency::total_balance(&caller, asset::DOT),
+            T::EqCurrency::total_balance(&caller, T::RelayAsset::get()),
             <T as pallet::Config>::Balance::zero()
         )
     }
@@ -237,13 +245,13 @@ benchmarks! {
 
         T::EqCurrency::make_free_balance_be(
             &caller,
-            asset::EQDOT,
+            T::WrappedAsset::get(),
             SignedBalance::Positive(initial_eqdot_amount),
         );
     }:_(RawOrigin::Signed(caller.clone()), WithdrawAmount::Dot(withdraw_amount))
     verify{
         assert_eq!(
-            T::EqCurrency::total_balance(&caller, asset::DOT),
+            T::EqCurrency::total_balance(&caller, T::RelayAsset::get()),
             withdraw_amount
         );
     }
@@ -266,7 +274,7 @@ benchmarks! {
 
         T::EqCurrency::make_free_balance_be(
             &caller,
-            asset::EQDOT,
+            T::WrappedAsset::get(),
             SignedBalance::Positive(initial_eqdot_amount),
         );
 
@@ -20,6 +20,7 @@
 #![deny(warnings)]
 
 pub mod benchmarking;
+pub mod migration;
 mod mock;
 mod tests;
 pub mod weights;
@@ -27,7 +28,7 @@ pub use weights::WeightInfo;
 
 use codec::{Codec, Decode, Encode, HasCompact, MaxEncodedLen};
 use eq_primitives::{
-    asset,
+    asset::Asset,
     balance::EqCurrency,
     balance::{DepositReason, WithdrawReason},
     balance_number::EqFixedU128,
@@ -151,6 +152,35 @@ pub enum WithdrawAmount<Balance> {
     EqDot(Balance),
 }
 
+/// Identifier of a withdrawal waiting in `WithdrawQueue`
+pub type UnbondingLotId = u64;
+
+/// Withdrawal waiting for DOT to be unbonded on relay chain or for reserves to replenish.
+/// Lots are fulfilled in the order of the queue
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct UnbondingLot<AccountId, Balance> {
+    pub id: UnbondingLotId,
+    /// Account receiving DOT
+    pub beneficiary: AccountId,
+    /// Amount of DOT to withdraw
+    pub withdraw_amount: Balance,
+    /// Amount of EQDOT to burn, held by the pallet account until fulfillment
+    pub burn_amount: Balance,
+    /// Relay era in which the unbonding was requested
+    pub requested_era: EraIndex,
+    /// Relay era in which unbonded DOT are unlocked on relay chain
+    pub unlock_era: EraIndex,
+}
+
+/// Expected time from which a lot may be fulfilled
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct LotAvailability<BlockNumber> {
+    /// Relay era in which DOT for the lot and all lots ahead of it are unlocked
+    pub era: EraIndex,
+    /// Estimated parachain block of `era`
+    pub block: BlockNumber,
+}
+
 impl<Balance: Copy + BaseArithmetic + Default> StakingBalance<Balance> {
     fn total(&self) -> Balance {
         self.staked + self.transferable
@@ -158,6 +188,7 @@ impl<Balance: Copy + BaseArithmetic + Default> StakingBalance<Balance> {
 }
 
 pub const DOT_DECIMALS: u8 = 10;
+pub const KSM_DECIMALS: u8 = 12;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -215,6 +246,18 @@ pub mod pallet {
         #[pallet::constant]
         type ParachainId: Get<ParaId>;
 
+        /// Relay chain native asset: DOT on Polkadot, KSM on Kusama
+        #[pallet::constant]
+        type RelayAsset: Get<Asset>;
+
+        /// Liquid staking wrapper of `RelayAsset`: EQDOT or EQKSM
+        #[pallet::constant]
+        type WrappedAsset: Get<Asset>;
+
+        /// Decimals of `RelayAsset` on relay chain
+        #[pallet::constant]
+        type RelayDecimals: Get<u8>;
+
         /// Used to get DOT price
         type PriceGetter: PriceGetter;
 
@@ -229,11 +272,23 @@ pub mod pallet {
         #[pallet::constant]
         type PalletId: Get<PalletId>;
 
+        /// Number of relay eras unbonded DOT stay locked on relay chain
+        #[pallet::constant]
+        type BondingDuration: Get<EraIndex>;
+
+        /// Approximate length of relay era in parachain blocks, used to estimate
+        /// availability of withdrawals
+        #[pallet::constant]
+        type EraLength: Get<Self::BlockNumber>;
+
         /// Extrisic weights
         type WeightInfo: WeightInfo;
     }
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -249,11 +304,15 @@ pub mod pallet {
     #[pallet::getter(fn current_balance)]
     pub type CurrentBalance<T: Config> = StorageValue<_, StakingBalance<T::Balance>, ValueQuery>;
 
-    /// Withdraw queue, (Beneficiary, DOT amount to withdraw, EQDOT amount to burn)
+    /// Withdraw queue of unbonding lots, fulfilled in order
     #[pallet::storage]
     #[pallet::getter(fn withdraw_queue)]
     pub(super) type WithdrawQueue<T: Config> =
-        StorageValue<_, Vec<(T::AccountId, T::Balance, T::Balance)>, ValueQuery>;
+        StorageValue<_, Vec<UnbondingLot<T::AccountId, T::Balance>>, ValueQuery>;
+
+    /// Id of the next unbonding lot
+    #[pallet::storage]
+    pub(super) type NextUnbondingLotId<T: Config> = StorageValue<_, UnbondingLotId, ValueQuery>;
 
     /// Total unlocking sum
     #[pallet::storage]
@@ -354,8 +413,10 @@ pub mod pallet {
                 Error::<T>::InsufficientWithdraw
             );
 
+            // queued lots are fulfilled first
             let current_balance = CurrentBalance::<T>::get();
-            if current_balance.transferable >= withdraw_amount {
+            let queued_amount = Self::queued_withdraw_amount();
+            if current_balance.transferable >= queued_amount.saturating_add(withdraw_amount) {
                 Self::deposit_dot_burn_wrapped_dot(account_id, withdraw_amount, burn_amount)?;
                 CurrentBalance::<T>::mutate(|v| v.transferable -= withdraw_amount);
                 Ok(().into())
@@ -374,8 +435,21 @@ pub mod pallet {
                 };
 
                 Self::send_xcm_unbond(withdraw_without_fee)?;
+                let requested_era = Self::current_relay_era();
+                let id = NextUnbondingLotId::<T>::mutate(|next_id| {
+                    let id = *next_id;
+                    *next_id = next_id.saturating_add(1);
+                    id
+                });
                 WithdrawQueue::<T>::mutate(|queue| {
-                    queue.push((account_id.clone(), withdraw_without_fee, burn_without_fee))
+                    queue.push(UnbondingLot {
+                        id,
+                        beneficiary: account_id.clone(),
+                        withdraw_amount: withdraw_without_fee,
+                        burn_amount: burn_without_fee,
+                        requested_era,
+                        unlock_era: requested_era.saturating_add(T::BondingDuration::get()),
+                    })
                 });
                 Self::transfer_wrapped_dot_to_pallet(account_id, burn_without_fee)?;
 
@@ -396,7 +470,7 @@ pub mod pallet {
             let xcm_message = T::RelayChainCallBuilder::finalize_call_into_xcm_message(
                 T::RelayChainCallBuilder::staking_bond(
                     sovereign_account,
-                    balance_into_xcm(bond, DOT_DECIMALS)
+                    balance_into_xcm(bond, T::RelayDecimals::get())
                         .ok_or(Error::<T>::XcmBalanceConversionError)?,
                     pallet_staking::RewardDestination::Staked,
                 ),
@@ -408,7 +482,7 @@ pub mod pallet {
 
             T::EqCurrency::deposit_creating(
                 &account_id,
-                asset::EQDOT,
+                T::WrappedAsset::get(),
                 bond,
                 true,
                 Some(DepositReason::Staking),
@@ -441,9 +515,12 @@ pub mod pallet {
             // Errors are returned before `staking_balance` will change,
             // so we can just igrore errors
             if let Some((current_era, ledger)) = RelayStakingInfo::<T>::get() {
-                let maybe_relay_unlocking =
-                    balance_from_xcm::<T::Balance>(ledger.total - ledger.active, DOT_DECIMALS);
-                let maybe_relay_total = balance_from_xcm::<T::Balance>(ledger.total, DOT_DECIMALS);
+                let maybe_relay_unlocking = balance_from_xcm::<T::Balance>(
+                    ledger.total - ledger.active,
+                    T::RelayDecimals::get(),
+                );
+                let maybe_relay_total =
+                    balance_from_xcm::<T::Balance>(ledger.total, T::RelayDecimals::get());
 
                 let mut staking_balance = CurrentBalance::<T>::get();
 
@@ -494,7 +571,7 @@ impl<T: Config> Pallet<T> {
         if current_era > last_withdraw_era {
             let unlocking = ledger.consolidate_unlocked(current_era);
             if unlocking != 0 {
-                let unlocking = balance_from_xcm(unlocking, DOT_DECIMALS)
+                let unlocking = balance_from_xcm(unlocking, T::RelayDecimals::get())
                     .ok_or(Error::<T>::XcmBalanceConversionError)?;
                 Self::send_xcm_withdraw_unbond(unlocking)?;
 
@@ -504,7 +581,7 @@ impl<T: Config> Pallet<T> {
             LastWithdrawEra::<T>::put(current_era);
         }
 
-        let ledger_total = balance_from_xcm(ledger.total, DOT_DECIMALS)
+        let ledger_total = balance_from_xcm(ledger.total, T::RelayDecimals::get())
             .ok_or(Error::<T>::XcmBalanceConversionError)?;
         if ledger_total > *staked {
             *staked = ledger_total;
@@ -519,15 +596,20 @@ impl<T: Config> Pallet<T> {
         let withdraw_queue = WithdrawQueue::<T>::get();
         let mut to_remove_amount = 0;
         let mut total_burnt_eqdot = T::Balance::zero();
-        while let Some((beneficiary, withdraw_amount, burn_amount)) =
-            withdraw_queue.get(to_remove_amount)
+        // lots are fulfilled strictly in order, a lot can't overtake the ones ahead of it
+        while let Some(UnbondingLot {
+            beneficiary,
+            withdraw_amount,
+            burn_amount,
+            ..
+        }) = withdraw_queue.get(to_remove_amount)
         {
             if *transferable < *withdraw_amount {
                 break;
             }
             T::EqCurrency::deposit_creating(
                 beneficiary,
-                asset::DOT,
+                T::RelayAsset::get(),
                 *withdraw_amount,
                 true,
                 Some(DepositReason::Staking),
@@ -540,7 +622,7 @@ impl<T: Config> Pallet<T> {
         if !total_burnt_eqdot.is_zero() {
             T::EqCurrency::withdraw(
                 &T::PalletId::get().into_account_truncating(),
-                asset::EQDOT,
+                T::WrappedAsset::get(),
                 total_burnt_eqdot,
                 false,
                 Some(WithdrawReason::Staking),
@@ -570,11 +652,7 @@ impl<T: Config> Pallet<T> {
             *transferable = transferable.saturating_sub(delta);
             *staked = staked.saturating_add(delta);
         } else {
-            let to_withdraw = WithdrawQueue::<T>::get()
-                .iter()
-                .fold(T::Balance::zero(), |acc, (_, withdraw_amount, _)| {
-                    acc.saturating_add(*withdraw_amount)
-                });
+            let to_withdraw = Self::queued_withdraw_amount();
             let unlocking_and_transferable =
                 *transferable + TotalUnlocking::<T>::get() - to_withdraw;
             let total_without_withdraw = total - to_withdraw; // contains unbonds from previous iterations
@@ -591,6 +669,60 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Unbonding lots of `who` waiting in the queue
+    pub fn unbonding_lots(who: &T::AccountId) -> Vec<UnbondingLot<T::AccountId, T::Balance>> {
+        WithdrawQueue::<T>::get()
+            .into_iter()
+            .filter(|lot| lot.beneficiary == *who)
+            .collect()
+    }
+
+    /// Expected time from which the lot may be fulfilled. Lots are fulfilled in order,
+    /// so the lot waits for DOT of all lots ahead of it. `None` if there is no such lot
+    pub fn lot_availability(lot_id: UnbondingLotId) -> Option<LotAvailability<T::BlockNumber>> {
+        let queue = WithdrawQueue::<T>::get();
+        let position = queue.iter().position(|lot| lot.id == lot_id)?;
+        let ahead = &queue[..=position];
+
+        let current_era = Self::current_relay_era();
+        let now = frame_system::Pallet::<T>::block_number();
+        let required = ahead.iter().fold(T::Balance::zero(), |acc, lot| {
+            acc.saturating_add(lot.withdraw_amount)
+        });
+        if CurrentBalance::<T>::get().transferable >= required {
+            // reserves already cover the lot, it's fulfilled by the next staking routine
+            return Some(LotAvailability {
+                era: current_era,
+                block: now,
+            });
+        }
+
+        let era = ahead
+            .iter()
+            .map(|lot| lot.unlock_era)
+            .fold(current_era, |acc, unlock_era| acc.max(unlock_era));
+        let eras_left = T::BlockNumber::from(era - current_era);
+        Some(LotAvailability {
+            era,
+            block: now.saturating_add(T::EraLength::get().saturating_mul(eras_left)),
+        })
+    }
+
+    /// Relay era of the last fetched relay staking info
+    fn current_relay_era() -> EraIndex {
+        RelayStakingInfo::<T>::get()
+            .map(|(era, _)| era)
+            .unwrap_or_else(Self::last_withdraw_era)
+    }
+
+    fn queued_withdraw_amount() -> T::Balance {
+        WithdrawQueue::<T>::get()
+            .iter()
+            .fold(T::Balance::zero(), |acc, lot| {
+                acc.saturating_add(lot.withdraw_amount)
+            })
+    }
+
     #[cfg(not(feature = "runtime-benchmarks"))]
     fn fetch_relay_storages() -> (EraIndex, StakingLedger<RelayRuntime>) {
         use eq_xcm::relay_interface::storage::*;
@@ -620,13 +752,13 @@ impl<T: Config> Pallet<T> {
     }
 
     fn wrapped_dot_total_supply() -> T::Balance {
-        let eqdot_aggregate = T::Aggregates::get_total(UserGroup::Balances, asset::EQDOT);
+        let eqdot_aggregate = T::Aggregates::get_total(UserGroup::Balances, T::WrappedAsset::get());
         eqdot_aggregate.collateral - eqdot_aggregate.debt
     }
 
     fn send_xcm_bond_extra(value: T::Balance) -> DispatchResult {
-        let bond_amount =
-            balance_into_xcm(value, DOT_DECIMALS).ok_or(Error::<T>::XcmBalanceConversionError)?;
+        let bond_amount = balance_into_xcm(value, T::RelayDecimals::get())
+            .ok_or(Error::<T>::XcmBalanceConversionError)?;
 
         let transact_weight_base = StakingWeights::<T>::bond_extra();
 
@@ -649,8 +781,8 @@ impl<T: Config> Pallet<T> {
         // from substrate
         const SPECULATIVE_NUM_SPANS: u32 = 32;
 
-        let unbond_amount =
-            balance_into_xcm(value, DOT_DECIMALS).ok_or(Error::<T>::XcmBalanceConversionError)?;
+        let unbond_amount = balance_into_xcm(value, T::RelayDecimals::get())
+            .ok_or(Error::<T>::XcmBalanceConversionError)?;
 
         // weight from polkadot runtime
         let transact_weight_base =
@@ -700,7 +832,7 @@ impl<T: Config> Pallet<T> {
         T::EqCurrency::currency_transfer(
             &account_id,
             &pallet_account_id,
-            asset::EQDOT,
+            T::WrappedAsset::get(),
             wrapped_dot_amount,
             ExistenceRequirement::KeepAlive,
             TransferReason::Common,
@@ -758,7 +890,7 @@ impl<T: Config> Pallet<T> {
     ) -> DispatchResult {
         T::EqCurrency::withdraw(
             &account_id,
-            asset::DOT,
+            T::RelayAsset::get(),
             deposit_amount,
             true,
             Some(WithdrawReason::Staking),
@@ -769,7 +901,7 @@ impl<T: Config> Pallet<T> {
         let mint_amount = Self::calc_mint_wrapped_amount(deposit_amount)?;
         T::EqCurrency::deposit_creating(
             &account_id,
-            asset::EQDOT,
+            T::WrappedAsset::get(),
             mint_amount,
             true,
             Some(DepositReason::Staking),
@@ -783,7 +915,7 @@ impl<T: Config> Pallet<T> {
     ) -> DispatchResult {
         T::EqCurrency::deposit_creating(
             &account_id,
-            asset::DOT,
+            T::RelayAsset::get(),
             deposit_amount,
             true,
             Some(DepositReason::Staking),
@@ -791,7 +923,7 @@ impl<T: Config> Pallet<T> {
 
         T::EqCurrency::withdraw(
             &account_id,
-            asset::EQDOT,
+            T::WrappedAsset::get(),
             burn_amount,
             true,
             Some(WithdrawReason::Staking),
@@ -0,0 +1,73 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Config, NextUnbondingLotId, Pallet, UnbondingLot, WithdrawQueue};
+use frame_support::{
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+use sp_runtime::traits::Saturating;
+use sp_std::vec::Vec;
+
+/// Withdraw queue before unbonding lots: (beneficiary, DOT amount to withdraw, EQDOT amount to burn)
+type OldWithdrawQueue<AccountId, Balance> = Vec<(AccountId, Balance, Balance)>;
+
+/// Turns withdraw queue entries into unbonding lots. Unbonding era of the old entries
+/// is unknown, so they are considered requested in the current relay era.
+pub fn migrate_withdraw_queue<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return Weight::zero();
+    }
+
+    let requested_era = Pallet::<T>::current_relay_era();
+    let unlock_era = requested_era.saturating_add(T::BondingDuration::get());
+    let mut next_id = NextUnbondingLotId::<T>::get();
+    let mut translated = 0u64;
+    let _ = WithdrawQueue::<T>::translate::<OldWithdrawQueue<T::AccountId, T::Balance>, _>(
+        |maybe_queue| {
+            maybe_queue.map(|queue| {
+                queue
+                    .into_iter()
+                    .map(|(beneficiary, withdraw_amount, burn_amount)| {
+                        let id = next_id;
+                        next_id = next_id.saturating_add(1);
+                        translated += 1;
+                        UnbondingLot {
+                            id,
+                            beneficiary,
+                            withdraw_amount,
+                            burn_amount,
+                            requested_era,
+                            unlock_era,
+                        }
+                    })
+                    .collect()
+            })
+        },
+    );
+    NextUnbondingLotId::<T>::put(next_id);
+    StorageVersion::new(1).put::<Pallet<T>>();
+
+    frame_support::log::info!(
+        target: "eq_wrapped_dot",
+        "migrate_withdraw_queue: {:?} lots translated",
+        translated
+    );
+
+    T::DbWeight::get().reads_writes(4, 3)
+}
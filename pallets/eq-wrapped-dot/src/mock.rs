@@ -19,7 +19,7 @@
 #![cfg(test)]
 
 use super::*;
-use eq_primitives::asset::{AssetType, AssetXcmData, OtherReservedData};
+use eq_primitives::asset::{self, AssetType, AssetXcmData, OtherReservedData};
 use eq_primitives::balance_number::EqFixedU128;
 use eq_primitives::mocks::{
     ParachainId, TimeZeroDurationMock, UpdateTimeManagerEmptyMock, XcmRouterCachedMessagesMock,
@@ -254,6 +254,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -278,6 +282,11 @@ parameter_types! {
     pub const MinStakingDeposit: Balance = 5_000_000_000; //5 DOT
     pub EqDotWithdrawFee: Permill = Permill::from_float(0.98940904738);
     pub const WrappedDotPalletId: PalletId = PalletId(*b"eq/wrdot");
+    pub const RelayAsset: asset::Asset = asset::DOT;
+    pub const WrappedAsset: asset::Asset = asset::EQDOT;
+    pub const RelayDecimals: u8 = DOT_DECIMALS;
+    pub const BondingDuration: u32 = 28;
+    pub const EraLength: u64 = 100;
 }
 
 #[derive(RuntimeDebug)]
@@ -300,10 +309,15 @@ impl Config for Test {
     type RelayChainCallBuilder = RelayChainCallBuilder<RelayRuntimeMock, ParachainId>;
     type XcmRouter = XcmRouterCachedMessagesMock;
     type ParachainId = ParachainId;
+    type RelayAsset = RelayAsset;
+    type WrappedAsset = WrappedAsset;
+    type RelayDecimals = RelayDecimals;
     type PriceGetter = OracleMock;
     type EqCurrency = EqBalances;
     type WithdrawFee = EqDotWithdrawFee;
     type PalletId = WrappedDotPalletId;
+    type BondingDuration = BondingDuration;
+    type EraLength = EraLength;
     type WeightInfo = ();
 }
 
@@ -20,6 +20,7 @@
 
 use super::*;
 use crate::mock::*;
+use eq_primitives::asset;
 use eq_primitives::balance::{BalanceGetter, EqCurrency};
 use eq_primitives::mocks::XcmRouterCachedMessagesMock;
 use eq_primitives::wrapped_dot::EqDotPrice;
@@ -298,7 +299,14 @@ fn withdraw_dot_send_xcm_when_transferable_not_enough() {
         assert_eq!(withdraw_queue.len(), 1);
         assert_eq!(
             withdraw_queue[withdraw_queue.len() - 1],
-            (account_id, withdraw_dot_amount, amount_to_burn_without_fee)
+            UnbondingLot {
+                id: 0,
+                beneficiary: account_id,
+                withdraw_amount: withdraw_dot_amount,
+                burn_amount: amount_to_burn_without_fee,
+                requested_era: 0,
+                unlock_era: BondingDuration::get(),
+            }
         );
 
         let unbond_amount = balance_into_xcm(withdraw_dot_amount, DOT_DECIMALS).unwrap();
@@ -345,11 +353,14 @@ fn withdraw_eqdot_send_xcm_when_transferable_not_enough() {
         assert_eq!(withdraw_queue.len(), 1);
         assert_eq!(
             withdraw_queue[withdraw_queue.len() - 1],
-            (
-                account_id,
-                deposit_amount_without_fee,
-                withdraw_eqdot_amount
-            )
+            UnbondingLot {
+                id: 0,
+                beneficiary: account_id,
+                withdraw_amount: deposit_amount_without_fee,
+                burn_amount: withdraw_eqdot_amount,
+                requested_era: 0,
+                unlock_era: BondingDuration::get(),
+            }
         );
 
         let unbond_amount = balance_into_xcm(deposit_amount_without_fee, DOT_DECIMALS).unwrap();
@@ -386,11 +397,14 @@ fn withdraw_clear_queue() {
         assert_eq!(withdraw_queue.len(), 1);
         assert_eq!(
             withdraw_queue[withdraw_queue.len() - 1],
-            (
-                account_id,
-                deposit_amount_without_fee,
-                withdraw_eqdot_amount
-            )
+            UnbondingLot {
+                id: 0,
+                beneficiary: account_id,
+                withdraw_amount: deposit_amount_without_fee,
+                burn_amount: withdraw_eqdot_amount,
+                requested_era: 0,
+                unlock_era: BondingDuration::get(),
+            }
         );
 
         let new_account_id = 2u64;
@@ -416,6 +430,77 @@ fn withdraw_clear_queue() {
     });
 }
 
+#[test]
+fn withdrawals_are_fulfilled_in_queue_order() {
+    new_test_ext().execute_with(|| {
+        init_wrapped_dot_supply();
+        let account_id_1 = 1u64;
+        let account_id_2 = 2u64;
+        for account_id in [account_id_1, account_id_2] {
+            ModuleBalances::make_free_balance_be(
+                &account_id,
+                asset::EQDOT,
+                SignedBalance::Positive(400 * ONE_TOKEN),
+            );
+        }
+
+        assert_ok!(ModuleWrappedDot::withdraw(
+            RuntimeOrigin::signed(account_id_1),
+            WithdrawAmount::Dot(210 * ONE_TOKEN)
+        ));
+        // transferable covers the withdrawal, but not together with the queued lot
+        assert_ok!(ModuleWrappedDot::withdraw(
+            RuntimeOrigin::signed(account_id_2),
+            WithdrawAmount::Dot(10 * ONE_TOKEN)
+        ));
+        assert_eq!(
+            ModuleBalances::get_balance(&account_id_2, &asset::DOT),
+            SignedBalance::zero()
+        );
+
+        let lots = ModuleWrappedDot::unbonding_lots(&account_id_2);
+        assert_eq!(lots.len(), 1);
+        assert_eq!(lots[0].id, 1);
+        assert_eq!(lots[0].unlock_era, BondingDuration::get());
+        assert_eq!(ModuleWrappedDot::unbonding_lots(&account_id_1)[0].id, 0);
+
+        let now = frame_system::Pallet::<Test>::block_number();
+        let expected = LotAvailability {
+            era: BondingDuration::get(),
+            block: now + EraLength::get() * BondingDuration::get() as u64,
+        };
+        assert_eq!(
+            ModuleWrappedDot::lot_availability(0),
+            Some(expected.clone())
+        );
+        assert_eq!(ModuleWrappedDot::lot_availability(1), Some(expected));
+        assert_eq!(ModuleWrappedDot::lot_availability(2), None);
+
+        // reserves are replenished by a deposit
+        assert_ok!(ModuleWrappedDot::deposit(
+            RuntimeOrigin::signed(3u64),
+            100 * ONE_TOKEN
+        ));
+        assert_eq!(
+            ModuleWrappedDot::lot_availability(1),
+            Some(LotAvailability { era: 0, block: now })
+        );
+
+        let mut current_balance = CurrentBalance::<Test>::get();
+        assert_ok!(ModuleWrappedDot::clear_withdraw_queue(&mut current_balance));
+        assert!(WithdrawQueue::<Test>::get().is_empty());
+        assert_eq!(
+            ModuleBalances::get_balance(&account_id_1, &asset::DOT),
+            SignedBalance::Positive(210 * ONE_TOKEN)
+        );
+        assert_eq!(
+            ModuleBalances::get_balance(&account_id_2, &asset::DOT),
+            SignedBalance::Positive(10 * ONE_TOKEN)
+        );
+        assert_eq!(current_balance.transferable, 80 * ONE_TOKEN);
+    });
+}
+
 #[test]
 fn calc_burn_wrapped_amount_should_work() {
     new_test_ext().execute_with(|| {
@@ -0,0 +1,124 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium XCM Operations Pallet Benchmarking
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use eq_primitives::asset;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{One, Zero};
+use xcm::v3::Junction::Parachain;
+
+const SEED: u32 = 0;
+
+fn insert_pending<T: Config>(
+    query_id: QueryId,
+    destination: MultiLocation,
+    expires_at: T::BlockNumber,
+) -> T::AccountId {
+    let who: T::AccountId = account("user", 0, SEED);
+    PendingTransacts::<T>::insert(
+        query_id,
+        PendingTransact {
+            who: who.clone(),
+            destination,
+            asset: asset::DOT,
+            amount: T::Balance::one(),
+            expires_at,
+        },
+    );
+    who
+}
+
+benchmarks! {
+    on_transact_response {
+        let origin = T::ResponseOrigin::try_successful_origin()
+            .map_err(|_| "no successful origin")?;
+        let responder = T::ResponseOrigin::try_origin(origin.clone())
+            .map_err(|_| "origin is not successful")?;
+        let query_id: QueryId = 1;
+        insert_pending::<T>(query_id, responder, T::BlockNumber::one());
+    }: _<T::RuntimeOrigin>(origin, query_id, Response::ExecutionResult(None))
+    verify {
+        assert!(PendingTransacts::<T>::get(query_id).is_none());
+    }
+
+    clear_expired_transact {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let query_id: QueryId = 1;
+        insert_pending::<T>(
+            query_id,
+            MultiLocation::new(1, X1(Parachain(2000))),
+            T::BlockNumber::zero(),
+        );
+        frame_system::Pallet::<T>::set_block_number(T::BlockNumber::one());
+    }: _(RawOrigin::Signed(caller), query_id)
+    verify {
+        assert!(PendingTransacts::<T>::get(query_id).is_none());
+    }
+
+    set_repatriation_template {
+        let origin = T::SovereignManagementOrigin::try_successful_origin()
+            .map_err(|_| "no successful origin")?;
+        let destination = MultiLocation::new(1, X1(Parachain(2000)));
+        let fee: MultiAsset = (MultiLocation::new(1, X1(Parachain(2000))), 1_000u128).into();
+        let template = RepatriationTemplate {
+            remote_fee: fee.clone(),
+            local_fee: fee,
+        };
+    }: _<T::RuntimeOrigin>(origin, destination.clone(), Some(template))
+    verify {
+        assert!(RepatriationTemplates::<T>::get(&destination).is_some());
+    }
+
+    set_xcm_partner {
+        let origin = T::PartnerManagementOrigin::try_successful_origin()
+            .map_err(|_| "no successful origin")?;
+        let location = MultiLocation::new(1, X1(Parachain(2000)));
+    }: _<T::RuntimeOrigin>(origin, location.clone(), true)
+    verify {
+        assert!(XcmPartners::<T>::get().contains(&location));
+    }
+
+    subscribe_partner_versions {
+        let p in 1..T::MaxXcmPartners::get();
+
+        let origin = T::PartnerManagementOrigin::try_successful_origin()
+            .map_err(|_| "no successful origin")?;
+        for i in 0..p {
+            XcmPartners::<T>::try_append(MultiLocation::new(1, X1(Parachain(2000 + i))))
+                .map_err(|_| "too many partners")?;
+        }
+    }: _<T::RuntimeOrigin>(origin)
+
+    check_partner_versions {
+        let p in 1..T::MaxXcmPartners::get();
+
+        let caller: T::AccountId = account("caller", 0, SEED);
+        for i in 0..p {
+            let location = MultiLocation::new(1, X1(Parachain(2000 + i)));
+            XcmPartners::<T>::try_append(location.clone())
+                .map_err(|_| "too many partners")?;
+            PartnerVersions::<T>::insert(location, XcmVersion::MAX);
+        }
+    }: _(RawOrigin::Signed(caller))
+}
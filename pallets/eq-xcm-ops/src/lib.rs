@@ -0,0 +1,702 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium XCM Operations Pallet
+//!
+//! Transfer-then-Transact flows: sends an asset to an account on destination chain
+//! and dispatches an encoded call there right after it (e.g. contribute transferred
+//! asset to a pool on destination chain).
+//!
+//! The asset leg is an ordinary xcm transfer made by `EqCurrency`. The call leg is
+//! a separate message sent to the same destination after the transfer, so it is
+//! executed after assets are deposited. The call is dispatched from the account
+//! derived on destination chain from the sender location (`DescendOrigin`), its
+//! execution fee is withdrawn from that account. Send transferred assets to that
+//! account to use them in the call.
+//!
+//! Execution result of the call leg is reported back with `ReportError` and is
+//! tracked by the query registered in `XcmQueryHandler`. If the call fails on
+//! destination, transferred assets stay on beneficiary account and unspent fee is
+//! refunded to the derived account, so nothing is lost. If any of the messages
+//! can't be sent, the whole operation is reverted on our side.
+//!
+//! Sovereign repatriation: assets accumulated on our sovereign accounts on other
+//! chains (e.g. fee refunds) are sent back to the treasury. Governance stores a
+//! template with execution fees for every remote chain and then repatriates the
+//! chosen assets with `repatriate_sovereign_assets`. Repatriated assets should be
+//! reserved by the remote chain, unspent remote fee returns to the sovereign account.
+//!
+//! XCM version monitoring: governance keeps a list of partner chains and subscribes
+//! to their XCM version notifications. `check_partner_versions` compares versions
+//! reported by partners with the stored ones and signals downgrades, which may
+//! silently break transfers to that chain.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(warnings)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode};
+use eq_primitives::{
+    asset::Asset,
+    balance::{EqCurrency, XcmDestination},
+};
+use eq_utils::eq_ensure;
+use frame_support::{weights::Weight, BoundedVec};
+use sp_runtime::{traits::Saturating, DispatchError, DispatchResult, RuntimeDebug};
+use sp_std::{convert::TryInto, prelude::*};
+pub use weights::WeightInfo;
+use xcm::v3::{
+    send_xcm, Error as XcmError, Instruction::*, InteriorMultiLocation, Junction, Junctions::X1,
+    MultiAsset, MultiAssetFilter, MultiAssets, MultiLocation, OriginKind, QueryId,
+    QueryResponseInfo, Response, SendXcm, WeightLimit, WildMultiAsset::AllCounted, Xcm,
+};
+pub use xcm::Version as XcmVersion;
+
+pub use pallet::*;
+
+/// Transfer-then-Transact operation waiting for response from destination chain
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct PendingTransact<AccountId, Balance, BlockNumber> {
+    /// Sender of the operation
+    pub who: AccountId,
+    /// Destination chain, responder of the query
+    pub destination: MultiLocation,
+    /// Transferred asset
+    pub asset: Asset,
+    /// Transferred amount
+    pub amount: Balance,
+    /// Block after which the query is considered expired
+    pub expires_at: BlockNumber,
+}
+
+/// Fees of the message repatriating assets from our sovereign account on remote chain
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct RepatriationTemplate {
+    /// Execution fee on remote chain from its pov, withdrawn from sovereign account
+    pub remote_fee: MultiAsset,
+    /// Execution fee on our chain from our pov, paid from repatriated assets
+    pub local_fee: MultiAsset,
+}
+
+/// Registers XCM queries which responses are dispatched as calls of this pallet
+pub trait XcmQueryHandler<T: Config> {
+    /// Registers query expecting response from `responder` to message sent by
+    /// `match_querier`. `notify` is dispatched with query id and response.
+    fn new_notify_query(
+        responder: MultiLocation,
+        notify: Call<T>,
+        timeout: T::BlockNumber,
+        match_querier: MultiLocation,
+    ) -> QueryId;
+}
+
+/// Subscribes to XCM version notifications of remote chains
+pub trait XcmVersionManager {
+    /// Asks `location` to notify us about its XCM version and further changes
+    fn subscribe_version_notify(location: MultiLocation) -> DispatchResult;
+    /// Last XCM version reported by `location`
+    fn known_version(location: &MultiLocation) -> Option<XcmVersion>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::AtLeast32BitUnsigned;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Numerical representation of stored balances
+        type Balance: Parameter + Member + AtLeast32BitUnsigned + Copy;
+        /// Makes the asset leg of operation
+        type EqCurrency: EqCurrency<Self::AccountId, Self::Balance>;
+        /// Used for sending XCM
+        type XcmRouter: SendXcm;
+        /// Registers queries for call leg results
+        type XcmQueryHandler: XcmQueryHandler<Self>;
+        /// Origin of query responses, returns responder location
+        type ResponseOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+        /// Used to build locations from destination chain pov
+        type UniversalLocation: Get<InteriorMultiLocation>;
+        /// Number of blocks to wait for response
+        #[pallet::constant]
+        type QueryTimeout: Get<Self::BlockNumber>;
+        /// Max length of encoded call for destination chain
+        #[pallet::constant]
+        type MaxTransactCallSize: Get<u32>;
+        /// Origin to manage and repatriate assets of sovereign accounts
+        type SovereignManagementOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Receiver of repatriated assets
+        type TreasuryAccount: Get<Self::AccountId>;
+        /// Origin to manage partner chains and subscribe to their XCM versions
+        type PartnerManagementOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Subscribes to and reads XCM versions of partner chains
+        type XcmVersionManager: XcmVersionManager;
+        /// Max number of partner chains
+        #[pallet::constant]
+        type MaxXcmPartners: Get<u32>;
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Operations waiting for response by query id
+    #[pallet::storage]
+    #[pallet::getter(fn pending_transacts)]
+    pub type PendingTransacts<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        QueryId,
+        PendingTransact<T::AccountId, T::Balance, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Repatriation templates by remote chain location
+    #[pallet::storage]
+    #[pallet::getter(fn repatriation_templates)]
+    pub type RepatriationTemplates<T: Config> =
+        StorageMap<_, Blake2_128Concat, MultiLocation, RepatriationTemplate, OptionQuery>;
+
+    /// Partner chains which XCM versions are monitored
+    #[pallet::storage]
+    #[pallet::getter(fn xcm_partners)]
+    pub type XcmPartners<T: Config> =
+        StorageValue<_, BoundedVec<MultiLocation, T::MaxXcmPartners>, ValueQuery>;
+
+    /// Last checked XCM version of partner chains
+    #[pallet::storage]
+    #[pallet::getter(fn partner_versions)]
+    pub type PartnerVersions<T: Config> =
+        StorageMap<_, Blake2_128Concat, MultiLocation, XcmVersion, OptionQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Asset and call are sent to destination chain
+        TransactSent {
+            who: T::AccountId,
+            destination: MultiLocation,
+            query_id: QueryId,
+        },
+        /// Call is successfully executed on destination chain
+        TransactSucceeded {
+            who: T::AccountId,
+            query_id: QueryId,
+        },
+        /// Call failed on destination chain, transferred asset stays on beneficiary account
+        TransactFailed {
+            who: T::AccountId,
+            query_id: QueryId,
+            instruction: u32,
+            error: XcmError,
+        },
+        /// No response from destination chain before timeout
+        TransactExpired {
+            who: T::AccountId,
+            query_id: QueryId,
+        },
+        /// Repatriation template is set or removed
+        RepatriationTemplateSet {
+            destination: MultiLocation,
+            template: Option<RepatriationTemplate>,
+        },
+        /// Assets of sovereign account are sent to the treasury
+        SovereignAssetsRepatriated {
+            destination: MultiLocation,
+            assets: MultiAssets,
+        },
+        /// Chain is added to or removed from partners
+        XcmPartnerSet {
+            location: MultiLocation,
+            is_partner: bool,
+        },
+        /// Version notifications are requested from partners
+        PartnerVersionsSubscribed { subscribed: u32 },
+        /// Partner reported a new XCM version
+        PartnerVersionChanged {
+            location: MultiLocation,
+            version: XcmVersion,
+        },
+        /// Partner reported a lower XCM version than before
+        PartnerVersionDowngraded {
+            location: MultiLocation,
+            from: XcmVersion,
+            to: XcmVersion,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Destination should be a chain location
+        XcmInvalidDestination,
+        /// Account can't be represented as AccountId32 junction
+        XcmInvalidAccount,
+        /// Message for the call leg wasn't sent
+        XcmSend,
+        /// No pending operation for the query
+        UnknownQuery,
+        /// Response came not from destination chain of the operation
+        UnexpectedResponder,
+        /// Response is not an execution result
+        UnexpectedResponse,
+        /// Query is not expired yet
+        QueryNotExpired,
+        /// No repatriation template for the chain
+        NoRepatriationTemplate,
+        /// No assets to repatriate
+        NothingToRepatriate,
+        /// Partners limit is reached
+        TooManyPartners,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Transfers asset to destination chain and dispatches `call` there.
+        ///
+        /// `transfer` - asset and amount to transfer;
+        /// `to` - recipient location from current chain;
+        /// `fee` - asset from destination chain pov to pay for the call execution,
+        ///         withdrawn from the account derived from sender location;
+        /// `call` - encoded call of destination chain;
+        /// `require_weight_at_most` - weight of the call on destination chain.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::transfer_and_transact())]
+        pub fn transfer_and_transact(
+            origin: OriginFor<T>,
+            transfer: (Asset, T::Balance),
+            to: MultiLocation,
+            fee: MultiAsset,
+            call: BoundedVec<u8, T::MaxTransactCallSize>,
+            require_weight_at_most: Weight,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let (query_id, destination) = Self::do_transfer_and_transact(
+                &who,
+                transfer,
+                to,
+                fee,
+                call,
+                require_weight_at_most,
+            )?;
+
+            Self::deposit_event(Event::TransactSent {
+                who,
+                destination,
+                query_id,
+            });
+            Ok(().into())
+        }
+
+        /// Handles execution result of the call leg reported by destination chain
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::on_transact_response())]
+        pub fn on_transact_response(
+            origin: OriginFor<T>,
+            query_id: QueryId,
+            response: Response,
+        ) -> DispatchResultWithPostInfo {
+            let responder = T::ResponseOrigin::ensure_origin(origin)?;
+
+            let pending = PendingTransacts::<T>::get(query_id).ok_or(Error::<T>::UnknownQuery)?;
+            eq_ensure!(
+                pending.destination == responder,
+                Error::<T>::UnexpectedResponder,
+                target: "eq_xcm_ops",
+                "{}:{}. Unexpected responder. Query: {:?}, expected: {:?}, actual: {:?}",
+                file!(),
+                line!(),
+                query_id,
+                pending.destination,
+                responder,
+            );
+
+            let event = match response {
+                Response::ExecutionResult(None) => Event::TransactSucceeded {
+                    who: pending.who,
+                    query_id,
+                },
+                Response::ExecutionResult(Some((instruction, error))) => Event::TransactFailed {
+                    who: pending.who,
+                    query_id,
+                    instruction,
+                    error,
+                },
+                _ => return Err(Error::<T>::UnexpectedResponse.into()),
+            };
+
+            PendingTransacts::<T>::remove(query_id);
+            Self::deposit_event(event);
+            Ok(().into())
+        }
+
+        /// Removes operation which got no response before timeout
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::clear_expired_transact())]
+        pub fn clear_expired_transact(
+            origin: OriginFor<T>,
+            query_id: QueryId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let pending = PendingTransacts::<T>::get(query_id).ok_or(Error::<T>::UnknownQuery)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() > pending.expires_at,
+                Error::<T>::QueryNotExpired
+            );
+
+            PendingTransacts::<T>::remove(query_id);
+            Self::deposit_event(Event::TransactExpired {
+                who: pending.who,
+                query_id,
+            });
+            Ok(().into())
+        }
+
+        /// Sets or removes (`None`) template for repatriation from our sovereign
+        /// account on `destination` chain
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_repatriation_template())]
+        pub fn set_repatriation_template(
+            origin: OriginFor<T>,
+            destination: MultiLocation,
+            template: Option<RepatriationTemplate>,
+        ) -> DispatchResultWithPostInfo {
+            T::SovereignManagementOrigin::ensure_origin(origin)?;
+            ensure!(
+                eq_utils::chain_part(&destination).as_ref() == Some(&destination),
+                Error::<T>::XcmInvalidDestination
+            );
+
+            RepatriationTemplates::<T>::set(&destination, template.clone());
+            Self::deposit_event(Event::RepatriationTemplateSet {
+                destination,
+                template,
+            });
+            Ok(().into())
+        }
+
+        /// Sends `assets` from our sovereign account on `destination` chain to the
+        /// treasury. `assets` are from `destination` chain pov.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::repatriate_sovereign_assets())]
+        pub fn repatriate_sovereign_assets(
+            origin: OriginFor<T>,
+            destination: MultiLocation,
+            assets: MultiAssets,
+        ) -> DispatchResultWithPostInfo {
+            T::SovereignManagementOrigin::ensure_origin(origin)?;
+            ensure!(!assets.is_empty(), Error::<T>::NothingToRepatriate);
+            let template = RepatriationTemplates::<T>::get(&destination)
+                .ok_or(Error::<T>::NoRepatriationTemplate)?;
+
+            Self::do_repatriate(&destination, assets.clone(), template)?;
+
+            Self::deposit_event(Event::SovereignAssetsRepatriated {
+                destination,
+                assets,
+            });
+            Ok(().into())
+        }
+
+        /// Adds `location` to partner chains or removes it
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::set_xcm_partner())]
+        pub fn set_xcm_partner(
+            origin: OriginFor<T>,
+            location: MultiLocation,
+            is_partner: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::PartnerManagementOrigin::ensure_origin(origin)?;
+            ensure!(
+                eq_utils::chain_part(&location).as_ref() == Some(&location),
+                Error::<T>::XcmInvalidDestination
+            );
+
+            XcmPartners::<T>::try_mutate(|partners| {
+                let position = partners.iter().position(|p| p == &location);
+                match (position, is_partner) {
+                    (None, true) => partners
+                        .try_push(location.clone())
+                        .map_err(|_| Error::<T>::TooManyPartners),
+                    (Some(index), false) => {
+                        partners.remove(index);
+                        PartnerVersions::<T>::remove(&location);
+                        Ok(())
+                    }
+                    _ => Ok(()),
+                }
+            })?;
+
+            Self::deposit_event(Event::XcmPartnerSet {
+                location,
+                is_partner,
+            });
+            Ok(().into())
+        }
+
+        /// Requests XCM version notifications from all partner chains
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::subscribe_partner_versions(T::MaxXcmPartners::get()))]
+        pub fn subscribe_partner_versions(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            T::PartnerManagementOrigin::ensure_origin(origin)?;
+
+            let partners = XcmPartners::<T>::get();
+            let mut subscribed = 0u32;
+            for location in partners.iter() {
+                // already subscribed partners fail, others are still requested
+                match T::XcmVersionManager::subscribe_version_notify(location.clone()) {
+                    Ok(()) => subscribed += 1,
+                    Err(err) => log::warn!(
+                        target: "eq_xcm_ops",
+                        "Version subscription failed. Location: {:?}, error: {:?}",
+                        location,
+                        err
+                    ),
+                }
+            }
+
+            Self::deposit_event(Event::PartnerVersionsSubscribed { subscribed });
+            Ok(Some(T::WeightInfo::subscribe_partner_versions(
+                partners.len() as u32
+            ))
+            .into())
+        }
+
+        /// Compares XCM versions reported by partner chains with stored ones.
+        /// Emits `PartnerVersionDowngraded` for partners that lowered their version.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::check_partner_versions(T::MaxXcmPartners::get()))]
+        pub fn check_partner_versions(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let partners = XcmPartners::<T>::get();
+            for location in partners.iter() {
+                Self::check_version(location);
+            }
+
+            Ok(Some(T::WeightInfo::check_partner_versions(partners.len() as u32)).into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn do_transfer_and_transact(
+        who: &T::AccountId,
+        transfer: (Asset, T::Balance),
+        to: MultiLocation,
+        fee: MultiAsset,
+        call: BoundedVec<u8, T::MaxTransactCallSize>,
+        require_weight_at_most: Weight,
+    ) -> Result<(QueryId, MultiLocation), DispatchError> {
+        let (asset, amount) = transfer;
+        let destination = eq_utils::chain_part(&to).ok_or(Error::<T>::XcmInvalidDestination)?;
+        let account_id: [u8; 32] = who
+            .encode()
+            .try_into()
+            .map_err(|_| Error::<T>::XcmInvalidAccount)?;
+        let account = Junction::AccountId32 {
+            network: None,
+            id: account_id,
+        };
+        // our chain and sender locations from destination pov
+        let response_destination = T::UniversalLocation::get()
+            .invert_target(&destination)
+            .map_err(|_| Error::<T>::XcmInvalidDestination)?;
+        let sender_location = response_destination
+            .pushed_with_interior(account.clone())
+            .map_err(|_| Error::<T>::XcmInvalidDestination)?;
+
+        T::EqCurrency::xcm_transfer(who, asset, amount, XcmDestination::Common(to))?;
+
+        let now = frame_system::Pallet::<T>::block_number();
+        let timeout = now.saturating_add(T::QueryTimeout::get());
+        let query_id = T::XcmQueryHandler::new_notify_query(
+            destination.clone(),
+            Call::on_transact_response {
+                query_id: 0,
+                response: Response::Null,
+            },
+            timeout,
+            MultiLocation::new(0, X1(account.clone())),
+        );
+
+        // appendix is set before Transact to report its error and refund the fee anyway
+        let message = Xcm::<()>(vec![
+            DescendOrigin(X1(account)),
+            WithdrawAsset(fee.clone().into()),
+            BuyExecution {
+                fees: fee,
+                weight_limit: WeightLimit::Unlimited,
+            },
+            SetAppendix(Xcm(vec![
+                ReportError(QueryResponseInfo {
+                    destination: response_destination,
+                    query_id,
+                    max_weight: T::WeightInfo::on_transact_response(),
+                }),
+                RefundSurplus,
+                DepositAsset {
+                    assets: AllCounted(1).into(),
+                    beneficiary: sender_location,
+                },
+            ])),
+            Transact {
+                origin_kind: OriginKind::SovereignAccount,
+                require_weight_at_most,
+                call: call.into_inner().into(),
+            },
+        ]);
+
+        log::trace!(target: "eq_xcm_ops", "Sending XcmMessage dest: {:?}, xcm: {:?}", destination, message);
+        send_xcm::<T::XcmRouter>(destination.clone(), message).map_err(|err| {
+            log::error!(target: "eq_xcm_ops", "XcmRouter::SendError {:?}", err);
+            Error::<T>::XcmSend
+        })?;
+
+        PendingTransacts::<T>::insert(
+            query_id,
+            PendingTransact {
+                who: who.clone(),
+                destination: destination.clone(),
+                asset,
+                amount,
+                expires_at: timeout,
+            },
+        );
+
+        Ok((query_id, destination))
+    }
+
+    fn do_repatriate(
+        destination: &MultiLocation,
+        assets: MultiAssets,
+        template: RepatriationTemplate,
+    ) -> Result<(), DispatchError> {
+        let treasury: [u8; 32] = T::TreasuryAccount::get()
+            .encode()
+            .try_into()
+            .map_err(|_| Error::<T>::XcmInvalidAccount)?;
+        // our chain location from destination pov, it's also our sovereign account there
+        let self_location = T::UniversalLocation::get()
+            .invert_target(destination)
+            .map_err(|_| Error::<T>::XcmInvalidDestination)?;
+        let count = assets.len() as u32;
+
+        let mut withdraw = assets.clone();
+        withdraw.push(template.remote_fee.clone());
+        // appendix returns unspent fee and not sent assets back to sovereign account
+        let message = Xcm::<()>(vec![
+            WithdrawAsset(withdraw),
+            BuyExecution {
+                fees: template.remote_fee,
+                weight_limit: WeightLimit::Unlimited,
+            },
+            SetAppendix(Xcm(vec![
+                RefundSurplus,
+                DepositAsset {
+                    assets: AllCounted(count.saturating_add(1)).into(),
+                    beneficiary: self_location.clone(),
+                },
+            ])),
+            DepositReserveAsset {
+                assets: MultiAssetFilter::Definite(assets),
+                dest: self_location,
+                xcm: Xcm(vec![
+                    BuyExecution {
+                        fees: template.local_fee,
+                        weight_limit: WeightLimit::Unlimited,
+                    },
+                    DepositAsset {
+                        assets: AllCounted(count).into(),
+                        beneficiary: MultiLocation::new(
+                            0,
+                            X1(Junction::AccountId32 {
+                                network: None,
+                                id: treasury,
+                            }),
+                        ),
+                    },
+                ]),
+            },
+        ]);
+
+        log::trace!(target: "eq_xcm_ops", "Sending XcmMessage dest: {:?}, xcm: {:?}", destination, message);
+        send_xcm::<T::XcmRouter>(destination.clone(), message).map_err(|err| {
+            log::error!(target: "eq_xcm_ops", "XcmRouter::SendError {:?}", err);
+            Error::<T>::XcmSend
+        })?;
+
+        Ok(())
+    }
+
+    /// Updates stored version of partner `location` if it reported another one
+    fn check_version(location: &MultiLocation) {
+        let version = match T::XcmVersionManager::known_version(location) {
+            Some(version) => version,
+            None => return,
+        };
+
+        match PartnerVersions::<T>::get(location) {
+            Some(from) if from == version => return,
+            Some(from) if from > version => {
+                log::warn!(
+                    target: "eq_xcm_ops",
+                    "Partner XCM version downgraded. Location: {:?}, from: {:?}, to: {:?}",
+                    location,
+                    from,
+                    version
+                );
+                Self::deposit_event(Event::PartnerVersionDowngraded {
+                    location: location.clone(),
+                    from,
+                    to: version,
+                });
+            }
+            _ => Self::deposit_event(Event::PartnerVersionChanged {
+                location: location.clone(),
+                version,
+            }),
+        }
+        PartnerVersions::<T>::insert(location, version);
+    }
+
+    /// Operations of `who` waiting for response
+    pub fn pending_transacts_of(
+        who: &T::AccountId,
+    ) -> Vec<(
+        QueryId,
+        PendingTransact<T::AccountId, T::Balance, T::BlockNumber>,
+    )> {
+        PendingTransacts::<T>::iter()
+            .filter(|(_, pending)| &pending.who == who)
+            .collect()
+    }
+}
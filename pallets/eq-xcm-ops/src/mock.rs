@@ -0,0 +1,232 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as eq_xcm_ops;
+use eq_primitives::asset::{self, AssetType};
+use frame_support::{
+    parameter_types,
+    traits::{EnsureOrigin, Everything},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    AccountId32, DispatchResult,
+};
+use std::cell::RefCell;
+use test_utils::builder::{AssetConfig, ExtBuilder};
+use xcm::v3::{Junction::Parachain, MultiAssets, SendError, SendResult, XcmHash};
+
+pub type AccountId = AccountId32;
+pub type Balance = eq_primitives::balance::Balance;
+pub type EqCurrencyMock = test_utils::mock::currency::EqCurrencyMock<AccountId>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        EqXcmOps: eq_xcm_ops::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const QueryTimeout: u64 = 10;
+    pub const MaxTransactCallSize: u32 = 64;
+    pub const TreasuryAccount: AccountId = AccountId32::new([9; 32]);
+    pub const MaxXcmPartners: u32 = 2;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqCurrencyMock;
+    type XcmRouter = XcmRouterMock;
+    type XcmQueryHandler = XcmQueryHandlerMock;
+    type ResponseOrigin = EnsureResponderMock;
+    type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
+    type QueryTimeout = QueryTimeout;
+    type MaxTransactCallSize = MaxTransactCallSize;
+    type SovereignManagementOrigin = EnsureRoot<AccountId>;
+    type TreasuryAccount = TreasuryAccount;
+    type PartnerManagementOrigin = EnsureRoot<AccountId>;
+    type XcmVersionManager = XcmVersionManagerMock;
+    type MaxXcmPartners = MaxXcmPartners;
+    type WeightInfo = ();
+}
+
+/// Sibling chain used as destination in tests
+pub fn destination() -> MultiLocation {
+    MultiLocation::new(1, X1(Parachain(2000)))
+}
+
+/// (from, asset, amount, to) of `EqCurrencyMock` xcm transfers
+pub fn xcm_transfers() -> Vec<(AccountId, asset::Asset, Balance, MultiLocation)> {
+    EqCurrencyMock::xcm_transfers()
+        .into_iter()
+        .map(|(from, asset, amount, kind)| {
+            let to = match kind {
+                eq_primitives::balance::XcmDestination::Common(to) => to,
+                eq_primitives::balance::XcmDestination::Native(_) => unimplemented!(),
+            };
+            (from, asset, amount, to)
+        })
+        .collect()
+}
+
+thread_local! {
+    static SENT_XCM: RefCell<Vec<(MultiLocation, Xcm<()>)>> = RefCell::new(Vec::new());
+    static NEXT_QUERY_ID: RefCell<QueryId> = RefCell::new(0);
+    static IS_ROUTER_BROKEN: RefCell<bool> = RefCell::new(false);
+    static VERSION_SUBSCRIPTIONS: RefCell<Vec<MultiLocation>> = RefCell::new(Vec::new());
+    static KNOWN_VERSIONS: RefCell<Vec<(MultiLocation, XcmVersion)>> = RefCell::new(Vec::new());
+}
+
+pub fn sent_xcm() -> Vec<(MultiLocation, Xcm<()>)> {
+    SENT_XCM.with(|v| v.borrow().clone())
+}
+
+pub fn break_router() {
+    IS_ROUTER_BROKEN.with(|v| *v.borrow_mut() = true);
+}
+
+pub fn version_subscriptions() -> Vec<MultiLocation> {
+    VERSION_SUBSCRIPTIONS.with(|v| v.borrow().clone())
+}
+
+pub fn set_known_version(location: MultiLocation, version: XcmVersion) {
+    KNOWN_VERSIONS.with(|v| {
+        let mut versions = v.borrow_mut();
+        versions.retain(|(l, _)| l != &location);
+        versions.push((location, version));
+    });
+}
+
+pub struct XcmVersionManagerMock;
+impl XcmVersionManager for XcmVersionManagerMock {
+    fn subscribe_version_notify(location: MultiLocation) -> DispatchResult {
+        VERSION_SUBSCRIPTIONS.with(|v| v.borrow_mut().push(location));
+        Ok(())
+    }
+
+    fn known_version(location: &MultiLocation) -> Option<XcmVersion> {
+        KNOWN_VERSIONS.with(|v| {
+            v.borrow()
+                .iter()
+                .find(|(l, _)| l == location)
+                .map(|(_, version)| *version)
+        })
+    }
+}
+
+pub struct XcmRouterMock;
+impl SendXcm for XcmRouterMock {
+    type Ticket = (MultiLocation, Xcm<()>);
+
+    fn validate(
+        dest: &mut Option<MultiLocation>,
+        msg: &mut Option<Xcm<()>>,
+    ) -> SendResult<Self::Ticket> {
+        if IS_ROUTER_BROKEN.with(|v| *v.borrow()) {
+            return Err(SendError::Transport("broken"));
+        }
+        let ticket = (
+            dest.take().ok_or(SendError::MissingArgument)?,
+            msg.take().ok_or(SendError::MissingArgument)?,
+        );
+        Ok((ticket, MultiAssets::new()))
+    }
+
+    fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+        SENT_XCM.with(|v| v.borrow_mut().push(ticket));
+        Ok([0; 32])
+    }
+}
+
+pub struct XcmQueryHandlerMock;
+impl XcmQueryHandler<Test> for XcmQueryHandlerMock {
+    fn new_notify_query(
+        _responder: MultiLocation,
+        _notify: Call<Test>,
+        _timeout: u64,
+        _match_querier: MultiLocation,
+    ) -> QueryId {
+        NEXT_QUERY_ID.with(|v| {
+            let mut id = v.borrow_mut();
+            *id += 1;
+            *id
+        })
+    }
+}
+
+/// Root origin responds as `destination()`
+pub struct EnsureResponderMock;
+impl EnsureOrigin<RuntimeOrigin> for EnsureResponderMock {
+    type Success = MultiLocation;
+
+    fn try_origin(o: RuntimeOrigin) -> Result<MultiLocation, RuntimeOrigin> {
+        EnsureRoot::<AccountId>::try_origin(o).map(|_| destination())
+    }
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    ExtBuilder::default()
+        .asset(AssetConfig::new(asset::DOT, AssetType::Physical, 1))
+        .init_mocks();
+
+    let r = frame_system::GenesisConfig::default().build_storage::<Test>();
+
+    r.unwrap().into()
+}
@@ -0,0 +1,363 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+use eq_primitives::asset::DOT;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+use xcm::v3::{
+    Fungibility::Fungible,
+    Junction::Parachain,
+    Junctions::{Here, X2},
+};
+
+const ALICE: AccountId = AccountId::new([1; 32]);
+
+fn fee() -> MultiAsset {
+    (MultiLocation::new(1, Here), 1_000_000_000u128).into()
+}
+
+fn recipient() -> MultiLocation {
+    MultiLocation::new(
+        1,
+        X2(
+            Parachain(2000),
+            Junction::AccountId32 {
+                network: None,
+                id: [2; 32],
+            },
+        ),
+    )
+}
+
+fn send_transfer_and_transact() -> QueryId {
+    assert_ok!(EqXcmOps::transfer_and_transact(
+        RuntimeOrigin::signed(ALICE),
+        (DOT, 10),
+        recipient(),
+        fee(),
+        vec![1, 2, 3].try_into().unwrap(),
+        Weight::from_parts(1_000_000, 0),
+    ));
+    PendingTransacts::<Test>::iter_keys().next().unwrap()
+}
+
+fn has_event(event: Event<Test>) -> bool {
+    System::events()
+        .iter()
+        .any(|r| r.event == RuntimeEvent::EqXcmOps(event.clone()))
+}
+
+#[test]
+fn transfer_and_transact_sends_transfer_and_call() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let query_id = send_transfer_and_transact();
+
+        assert_eq!(xcm_transfers(), vec![(ALICE, DOT, 10, recipient())]);
+
+        let sent = sent_xcm();
+        assert_eq!(sent.len(), 1);
+        let (dest, message) = &sent[0];
+        assert_eq!(dest, &destination());
+        assert_eq!(
+            message.0[0],
+            DescendOrigin(X1(Junction::AccountId32 {
+                network: None,
+                id: [1; 32],
+            }))
+        );
+        assert!(matches!(
+            &message.0[2],
+            BuyExecution { fees, .. } if fees.fun == Fungible(1_000_000_000)
+        ));
+        assert!(matches!(&message.0[3], SetAppendix(appendix)
+            if matches!(&appendix.0[0], ReportError(info) if info.query_id == query_id)));
+        assert!(matches!(
+            &message.0[4],
+            Transact { origin_kind: OriginKind::SovereignAccount, call, .. }
+                if call.clone().into_encoded() == vec![1, 2, 3]
+        ));
+
+        let pending = EqXcmOps::pending_transacts(query_id).unwrap();
+        assert_eq!(pending.who, ALICE);
+        assert_eq!(pending.destination, destination());
+        assert_eq!(pending.expires_at, 11);
+        assert!(has_event(Event::TransactSent {
+            who: ALICE,
+            destination: destination(),
+            query_id,
+        }));
+    });
+}
+
+#[test]
+fn transfer_and_transact_fails_when_call_is_not_sent() {
+    new_test_ext().execute_with(|| {
+        break_router();
+        assert_noop!(
+            EqXcmOps::transfer_and_transact(
+                RuntimeOrigin::signed(ALICE),
+                (DOT, 10),
+                recipient(),
+                fee(),
+                vec![1, 2, 3].try_into().unwrap(),
+                Weight::from_parts(1_000_000, 0),
+            ),
+            Error::<Test>::XcmSend
+        );
+    });
+}
+
+#[test]
+fn transact_response_resolves_pending_operation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let succeeded = send_transfer_and_transact();
+        let failed = send_transfer_and_transact();
+
+        assert_noop!(
+            EqXcmOps::on_transact_response(
+                RuntimeOrigin::signed(ALICE),
+                succeeded,
+                Response::ExecutionResult(None)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            EqXcmOps::on_transact_response(
+                RuntimeOrigin::root(),
+                100,
+                Response::ExecutionResult(None)
+            ),
+            Error::<Test>::UnknownQuery
+        );
+        assert_noop!(
+            EqXcmOps::on_transact_response(RuntimeOrigin::root(), succeeded, Response::Null),
+            Error::<Test>::UnexpectedResponse
+        );
+
+        assert_ok!(EqXcmOps::on_transact_response(
+            RuntimeOrigin::root(),
+            succeeded,
+            Response::ExecutionResult(None)
+        ));
+        assert_ok!(EqXcmOps::on_transact_response(
+            RuntimeOrigin::root(),
+            failed,
+            Response::ExecutionResult(Some((4, XcmError::FailedToTransactAsset(""))))
+        ));
+
+        assert_eq!(PendingTransacts::<Test>::iter().count(), 0);
+        assert!(has_event(Event::TransactSucceeded {
+            who: ALICE,
+            query_id: succeeded,
+        }));
+        assert!(has_event(Event::TransactFailed {
+            who: ALICE,
+            query_id: failed,
+            instruction: 4,
+            error: XcmError::FailedToTransactAsset(""),
+        }));
+    });
+}
+
+#[test]
+fn clear_expired_transact_after_timeout() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let query_id = send_transfer_and_transact();
+
+        System::set_block_number(11);
+        assert_noop!(
+            EqXcmOps::clear_expired_transact(RuntimeOrigin::signed(ALICE), query_id),
+            Error::<Test>::QueryNotExpired
+        );
+
+        System::set_block_number(12);
+        assert_ok!(EqXcmOps::clear_expired_transact(
+            RuntimeOrigin::signed(ALICE),
+            query_id
+        ));
+        assert!(EqXcmOps::pending_transacts(query_id).is_none());
+        assert!(has_event(Event::TransactExpired {
+            who: ALICE,
+            query_id,
+        }));
+    });
+}
+
+#[test]
+fn repatriate_sovereign_assets_sends_assets_to_treasury() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let remote_asset = |amount: u128| -> MultiAsset {
+            (MultiLocation::new(0, X1(Junction::GeneralIndex(1))), amount).into()
+        };
+        let template = RepatriationTemplate {
+            remote_fee: remote_asset(1_000),
+            local_fee: (
+                MultiLocation::new(1, X2(Parachain(2000), Junction::GeneralIndex(1))),
+                10u128,
+            )
+                .into(),
+        };
+
+        assert_noop!(
+            EqXcmOps::repatriate_sovereign_assets(
+                RuntimeOrigin::root(),
+                destination(),
+                remote_asset(1_000).into()
+            ),
+            Error::<Test>::NoRepatriationTemplate
+        );
+        assert_noop!(
+            EqXcmOps::set_repatriation_template(
+                RuntimeOrigin::signed(ALICE),
+                destination(),
+                Some(template.clone())
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            EqXcmOps::set_repatriation_template(
+                RuntimeOrigin::root(),
+                recipient(),
+                Some(template.clone())
+            ),
+            Error::<Test>::XcmInvalidDestination
+        );
+        assert_ok!(EqXcmOps::set_repatriation_template(
+            RuntimeOrigin::root(),
+            destination(),
+            Some(template.clone())
+        ));
+
+        let assets: MultiAssets = remote_asset(50_000).into();
+        assert_ok!(EqXcmOps::repatriate_sovereign_assets(
+            RuntimeOrigin::root(),
+            destination(),
+            assets.clone()
+        ));
+
+        let sent = sent_xcm();
+        assert_eq!(sent.len(), 1);
+        let (dest, message) = &sent[0];
+        assert_eq!(dest, &destination());
+        assert!(matches!(
+            &message.0[0],
+            WithdrawAsset(withdraw) if withdraw.get(0).map(|a| &a.fun) == Some(&Fungible(51_000))
+        ));
+        assert!(matches!(
+            &message.0[3],
+            DepositReserveAsset { dest, xcm, .. }
+                if dest.parents == 1
+                    && matches!(
+                        &xcm.0[1],
+                        DepositAsset { beneficiary, .. } if beneficiary == &MultiLocation::new(
+                            0,
+                            X1(Junction::AccountId32 { network: None, id: [9; 32] })
+                        )
+                    )
+        ));
+        assert!(has_event(Event::SovereignAssetsRepatriated {
+            destination: destination(),
+            assets,
+        }));
+    });
+}
+
+#[test]
+fn partner_version_downgrade_is_reported() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let partner = destination();
+
+        assert_noop!(
+            EqXcmOps::set_xcm_partner(RuntimeOrigin::signed(ALICE), partner.clone(), true),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            EqXcmOps::set_xcm_partner(RuntimeOrigin::root(), recipient(), true),
+            Error::<Test>::XcmInvalidDestination
+        );
+        assert_ok!(EqXcmOps::set_xcm_partner(
+            RuntimeOrigin::root(),
+            partner.clone(),
+            true
+        ));
+        assert_ok!(EqXcmOps::set_xcm_partner(
+            RuntimeOrigin::root(),
+            MultiLocation::parent(),
+            true
+        ));
+        assert_noop!(
+            EqXcmOps::set_xcm_partner(
+                RuntimeOrigin::root(),
+                MultiLocation::new(1, X1(Parachain(3000))),
+                true
+            ),
+            Error::<Test>::TooManyPartners
+        );
+
+        assert_ok!(EqXcmOps::subscribe_partner_versions(RuntimeOrigin::root()));
+        assert_eq!(
+            version_subscriptions(),
+            vec![partner.clone(), MultiLocation::parent()]
+        );
+        assert!(has_event(Event::PartnerVersionsSubscribed {
+            subscribed: 2
+        }));
+
+        set_known_version(partner.clone(), 3);
+        assert_ok!(EqXcmOps::check_partner_versions(RuntimeOrigin::signed(
+            ALICE
+        )));
+        assert_eq!(EqXcmOps::partner_versions(&partner), Some(3));
+        assert_eq!(EqXcmOps::partner_versions(&MultiLocation::parent()), None);
+        assert!(has_event(Event::PartnerVersionChanged {
+            location: partner.clone(),
+            version: 3,
+        }));
+
+        set_known_version(partner.clone(), 2);
+        assert_ok!(EqXcmOps::check_partner_versions(RuntimeOrigin::signed(
+            ALICE
+        )));
+        assert_eq!(EqXcmOps::partner_versions(&partner), Some(2));
+        assert!(has_event(Event::PartnerVersionDowngraded {
+            location: partner.clone(),
+            from: 3,
+            to: 2,
+        }));
+
+        assert_ok!(EqXcmOps::set_xcm_partner(
+            RuntimeOrigin::root(),
+            partner.clone(),
+            false
+        ));
+        assert_eq!(EqXcmOps::partner_versions(&partner), None);
+        assert_eq!(
+            EqXcmOps::xcm_partners().into_inner(),
+            vec![MultiLocation::parent()]
+        );
+    });
+}
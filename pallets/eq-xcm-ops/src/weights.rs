@@ -0,0 +1,62 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+    fn transfer_and_transact() -> Weight;
+    fn on_transact_response() -> Weight;
+    fn clear_expired_transact() -> Weight;
+    fn set_repatriation_template() -> Weight;
+    fn repatriate_sovereign_assets() -> Weight;
+    fn set_xcm_partner() -> Weight;
+    fn subscribe_partner_versions(p: u32) -> Weight;
+    fn check_partner_versions(p: u32) -> Weight;
+}
+
+// for tests
+impl crate::WeightInfo for () {
+    fn transfer_and_transact() -> Weight {
+        Weight::zero()
+    }
+    fn on_transact_response() -> Weight {
+        Weight::zero()
+    }
+    fn clear_expired_transact() -> Weight {
+        Weight::zero()
+    }
+    fn set_repatriation_template() -> Weight {
+        Weight::zero()
+    }
+    fn repatriate_sovereign_assets() -> Weight {
+        Weight::zero()
+    }
+    fn set_xcm_partner() -> Weight {
+        Weight::zero()
+    }
+    fn subscribe_partner_versions(_p: u32) -> Weight {
+        Weight::zero()
+    }
+    fn check_partner_versions(_p: u32) -> Weight {
+        Weight::zero()
+    }
+}
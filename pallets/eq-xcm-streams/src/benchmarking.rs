@@ -0,0 +1,103 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium XCM Streams Pallet Benchmarking
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use eq_primitives::asset;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::Hooks;
+use frame_system::RawOrigin;
+use sp_runtime::traits::One;
+use xcm::v3::{Junction::Parachain, Junctions::X1};
+
+const SEED: u32 = 0;
+
+fn insert_stream<T: Config>(id: StreamId, owner: T::AccountId, at: T::BlockNumber) {
+    Streams::<T>::insert(
+        id,
+        XcmStream {
+            owner: owner.clone(),
+            asset: asset::DOT,
+            amount: T::Balance::one(),
+            to: MultiLocation::new(1, X1(Parachain(2000))),
+            period: T::MinPeriod::get(),
+            next_payment: at,
+            remaining_payments: None,
+            failures: 0,
+            scheduled_at: at,
+        },
+    );
+    StreamsCount::<T>::mutate(&owner, |count| *count += 1);
+    Agenda::<T>::try_append(at, id).expect("agenda is not full");
+    // payment may fail in benchmark runtime, failed one is cheaper anyway
+    let _ = T::EqCurrency::deposit_creating(&owner, asset::DOT, 1_000u32.into(), true, None);
+}
+
+benchmarks! {
+    schedule_stream {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let start = T::BlockNumber::one();
+        // agendas near the start block are full, stream lands in the last possible block
+        let max = T::MaxStreamsPerBlock::get();
+        for shift in 0..max - 1 {
+            let at = start.saturating_add(shift.into());
+            for i in 0..max {
+                Agenda::<T>::try_append(at, StreamId::MAX - (shift * max + i) as StreamId)
+                    .expect("agenda is not full");
+            }
+        }
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        (asset::DOT, T::Balance::one()),
+        MultiLocation::new(1, X1(Parachain(2000))),
+        T::MinPeriod::get(),
+        start,
+        None
+    )
+    verify {
+        assert_eq!(StreamsCount::<T>::get(&caller), 1);
+    }
+
+    cancel_stream {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let at = T::BlockNumber::one();
+        insert_stream::<T>(0, caller.clone(), at);
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert!(Streams::<T>::get(0).is_none());
+        assert_eq!(StreamsCount::<T>::get(&caller), 0);
+    }
+
+    on_initialize {
+        let s in 0..T::MaxStreamsPerBlock::get();
+
+        let at = T::BlockNumber::one();
+        for i in 0..s {
+            let owner: T::AccountId = account("owner", i, SEED);
+            insert_stream::<T>(i as StreamId, owner, at);
+        }
+    }: {
+        Pallet::<T>::on_initialize(at);
+    }
+    verify {
+        assert!(Agenda::<T>::get(at).is_empty());
+    }
+}
@@ -0,0 +1,421 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium XCM Streams Pallet
+//!
+//! Recurring xcm transfers, e.g. paying contributors on other chains every week.
+//!
+//! Account schedules a stream: asset, amount, recipient location, period between
+//! payments and optional number of payments. Every payment is an ordinary xcm
+//! transfer made by `EqCurrency` from the owner account in `on_initialize` of the
+//! block it's due, so the owner keeps the stream funded.
+//!
+//! Streams are queued in `Agenda` by block, at most `MaxStreamsPerBlock` per block,
+//! stream lands in the next free block when its block is full.
+//!
+//! Failed payment (e.g. not enough balance) is retried after `RetryDelay` blocks,
+//! the delay doubles with every consecutive failure. Stream is aborted after
+//! `MaxFailures` consecutive failures.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(warnings)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode};
+use eq_primitives::{
+    asset::Asset,
+    balance::{EqCurrency, XcmDestination},
+};
+use frame_support::{storage::TransactionOutcome, weights::Weight};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    DispatchError, RuntimeDebug,
+};
+use sp_std::prelude::*;
+pub use weights::WeightInfo;
+use xcm::v3::MultiLocation;
+
+pub use pallet::*;
+
+pub type StreamId = u64;
+
+/// Recurring xcm transfer funded by its owner
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct XcmStream<AccountId, Balance, BlockNumber> {
+    /// Account paying for the stream
+    pub owner: AccountId,
+    /// Transferred asset
+    pub asset: Asset,
+    /// Amount of every payment
+    pub amount: Balance,
+    /// Recipient location from current chain
+    pub to: MultiLocation,
+    /// Blocks between payments
+    pub period: BlockNumber,
+    /// Block the next payment is due
+    pub next_payment: BlockNumber,
+    /// Payments left, `None` for endless stream
+    pub remaining_payments: Option<u32>,
+    /// Consecutive failed attempts of the next payment
+    pub failures: u32,
+    /// Block of `Agenda` the stream is queued at
+    pub scheduled_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::AtLeast32BitUnsigned;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Numerical representation of stored balances
+        type Balance: Parameter + Member + AtLeast32BitUnsigned + Copy;
+        /// Makes payments of streams
+        type EqCurrency: EqCurrency<Self::AccountId, Self::Balance>;
+        /// Min number of blocks between payments
+        #[pallet::constant]
+        type MinPeriod: Get<Self::BlockNumber>;
+        /// Max number of streams executed in a single block
+        #[pallet::constant]
+        type MaxStreamsPerBlock: Get<u32>;
+        /// Max number of streams of a single account
+        #[pallet::constant]
+        type MaxStreamsPerAccount: Get<u32>;
+        /// Delay before the first retry of failed payment, doubles with every failure
+        #[pallet::constant]
+        type RetryDelay: Get<Self::BlockNumber>;
+        /// Stream is aborted after this number of consecutive failed payments
+        #[pallet::constant]
+        type MaxFailures: Get<u32>;
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Id of the next scheduled stream
+    #[pallet::storage]
+    pub type NextStreamId<T: Config> = StorageValue<_, StreamId, ValueQuery>;
+
+    /// Scheduled streams by id
+    #[pallet::storage]
+    #[pallet::getter(fn streams)]
+    pub type Streams<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        StreamId,
+        XcmStream<T::AccountId, T::Balance, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Streams to execute by block
+    #[pallet::storage]
+    #[pallet::getter(fn agenda)]
+    pub type Agenda<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        BoundedVec<StreamId, T::MaxStreamsPerBlock>,
+        ValueQuery,
+    >;
+
+    /// Number of streams of account
+    #[pallet::storage]
+    #[pallet::getter(fn streams_count)]
+    pub type StreamsCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Stream is scheduled
+        StreamScheduled {
+            id: StreamId,
+            owner: T::AccountId,
+            first_payment: T::BlockNumber,
+        },
+        /// Payment is sent
+        StreamPaid {
+            id: StreamId,
+            amount: T::Balance,
+            remaining_payments: Option<u32>,
+        },
+        /// Payment failed and will be retried
+        StreamPaymentFailed {
+            id: StreamId,
+            error: DispatchError,
+            failures: u32,
+            retry_at: T::BlockNumber,
+        },
+        /// All payments of the stream are sent
+        StreamCompleted { id: StreamId },
+        /// Stream is cancelled by its owner
+        StreamCancelled { id: StreamId },
+        /// Stream is removed after too many failed payments
+        StreamAborted { id: StreamId, error: DispatchError },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Destination should be a chain location
+        XcmInvalidDestination,
+        /// Payment amount is zero
+        ZeroAmount,
+        /// Number of payments is zero
+        ZeroPayments,
+        /// Period is less than `MinPeriod`
+        PeriodTooShort,
+        /// First payment should be in the future
+        StartInPast,
+        /// Account has `MaxStreamsPerAccount` streams
+        TooManyStreams,
+        /// No free place in agenda near the block
+        AgendaFull,
+        /// No stream with such id
+        UnknownStream,
+        /// Only owner can cancel the stream
+        NotOwner,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let ids = Agenda::<T>::take(now);
+            for id in ids.iter() {
+                Self::execute(*id, now);
+            }
+
+            T::WeightInfo::on_initialize(ids.len() as u32)
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Schedules recurring xcm transfer from the caller account.
+        ///
+        /// `transfer` - asset and amount of every payment;
+        /// `to` - recipient location from current chain;
+        /// `period` - blocks between payments;
+        /// `start` - block of the first payment;
+        /// `payments` - number of payments, `None` for endless stream.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::schedule_stream())]
+        pub fn schedule_stream(
+            origin: OriginFor<T>,
+            transfer: (Asset, T::Balance),
+            to: MultiLocation,
+            period: T::BlockNumber,
+            start: T::BlockNumber,
+            payments: Option<u32>,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let (asset, amount) = transfer;
+
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(payments != Some(0), Error::<T>::ZeroPayments);
+            ensure!(period >= T::MinPeriod::get(), Error::<T>::PeriodTooShort);
+            ensure!(
+                start > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::StartInPast
+            );
+            ensure!(
+                eq_utils::chain_part(&to).is_some(),
+                Error::<T>::XcmInvalidDestination
+            );
+            ensure!(
+                StreamsCount::<T>::get(&owner) < T::MaxStreamsPerAccount::get(),
+                Error::<T>::TooManyStreams
+            );
+
+            let id = NextStreamId::<T>::get();
+            let scheduled_at = Self::schedule(id, start)?;
+            NextStreamId::<T>::put(id.saturating_add(1));
+
+            Streams::<T>::insert(
+                id,
+                XcmStream {
+                    owner: owner.clone(),
+                    asset,
+                    amount,
+                    to,
+                    period,
+                    next_payment: start,
+                    remaining_payments: payments,
+                    failures: 0,
+                    scheduled_at,
+                },
+            );
+            StreamsCount::<T>::mutate(&owner, |count| *count += 1);
+
+            Self::deposit_event(Event::StreamScheduled {
+                id,
+                owner,
+                first_payment: scheduled_at,
+            });
+            Ok(().into())
+        }
+
+        /// Cancels stream of the caller, no more payments are sent
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::cancel_stream())]
+        pub fn cancel_stream(origin: OriginFor<T>, id: StreamId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let stream = Streams::<T>::get(id).ok_or(Error::<T>::UnknownStream)?;
+            ensure!(stream.owner == who, Error::<T>::NotOwner);
+
+            Agenda::<T>::mutate(stream.scheduled_at, |agenda| {
+                agenda.retain(|scheduled| *scheduled != id)
+            });
+            Self::remove_stream(id, &stream.owner);
+
+            Self::deposit_event(Event::StreamCancelled { id });
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Puts stream into agenda of the block `at` or the nearest next block with
+    /// free place. Returns the block stream is queued at.
+    fn schedule(id: StreamId, at: T::BlockNumber) -> Result<T::BlockNumber, DispatchError> {
+        let mut block = at;
+        for _ in 0..T::MaxStreamsPerBlock::get() {
+            if Agenda::<T>::try_append(block, id).is_ok() {
+                return Ok(block);
+            }
+            block = block.saturating_add(1u32.into());
+        }
+
+        Err(Error::<T>::AgendaFull.into())
+    }
+
+    /// Sends the payment of stream `id` and schedules the next one or retry
+    fn execute(id: StreamId, now: T::BlockNumber) {
+        let mut stream = match Streams::<T>::get(id) {
+            Some(stream) => stream,
+            None => return,
+        };
+
+        // nothing is changed if the payment fails
+        let result =
+            frame_support::storage::with_transaction(|| {
+                match T::EqCurrency::xcm_transfer(
+                    &stream.owner,
+                    stream.asset,
+                    stream.amount,
+                    XcmDestination::Common(stream.to.clone()),
+                ) {
+                    Ok(()) => TransactionOutcome::Commit(Ok(())),
+                    Err(err) => TransactionOutcome::Rollback(Err(err)),
+                }
+            });
+
+        let next_block = match result {
+            Ok(()) => {
+                stream.failures = 0;
+                stream.remaining_payments = stream.remaining_payments.map(|p| p.saturating_sub(1));
+                Self::deposit_event(Event::StreamPaid {
+                    id,
+                    amount: stream.amount,
+                    remaining_payments: stream.remaining_payments,
+                });
+
+                if stream.remaining_payments == Some(0) {
+                    Self::remove_stream(id, &stream.owner);
+                    Self::deposit_event(Event::StreamCompleted { id });
+                    return;
+                }
+
+                // keep the cadence, but never schedule into the past after delayed payment
+                stream.next_payment = stream
+                    .next_payment
+                    .saturating_add(stream.period)
+                    .max(now.saturating_add(1u32.into()));
+                stream.next_payment
+            }
+            Err(error) => {
+                log::warn!(
+                    target: "eq_xcm_streams",
+                    "Stream payment failed. Id: {:?}, error: {:?}",
+                    id,
+                    error
+                );
+                stream.failures = stream.failures.saturating_add(1);
+                if stream.failures >= T::MaxFailures::get() {
+                    Self::remove_stream(id, &stream.owner);
+                    Self::deposit_event(Event::StreamAborted { id, error });
+                    return;
+                }
+
+                let backoff = 2u32.saturating_pow(stream.failures - 1);
+                let delay = T::RetryDelay::get().saturating_mul(backoff.into());
+                now.saturating_add(delay.max(1u32.into()))
+            }
+        };
+
+        match Self::schedule(id, next_block) {
+            Ok(scheduled_at) => {
+                stream.scheduled_at = scheduled_at;
+                if let Err(error) = result {
+                    Self::deposit_event(Event::StreamPaymentFailed {
+                        id,
+                        error,
+                        failures: stream.failures,
+                        retry_at: scheduled_at,
+                    });
+                }
+                Streams::<T>::insert(id, stream);
+            }
+            Err(error) => {
+                Self::remove_stream(id, &stream.owner);
+                Self::deposit_event(Event::StreamAborted { id, error });
+            }
+        }
+    }
+
+    fn remove_stream(id: StreamId, owner: &T::AccountId) {
+        Streams::<T>::remove(id);
+        StreamsCount::<T>::mutate(owner, |count| *count = count.saturating_sub(1));
+    }
+
+    /// Streams of `who`
+    pub fn streams_of(
+        who: &T::AccountId,
+    ) -> Vec<(
+        StreamId,
+        XcmStream<T::AccountId, T::Balance, T::BlockNumber>,
+    )> {
+        Streams::<T>::iter()
+            .filter(|(_, stream)| &stream.owner == who)
+            .collect()
+    }
+}
@@ -0,0 +1,214 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+use eq_primitives::asset::DOT;
+use frame_support::{assert_noop, assert_ok};
+
+const ALICE: AccountId = 1;
+const BOB: AccountId = 2;
+
+fn schedule(who: AccountId, start: u64, payments: Option<u32>) -> StreamId {
+    let id = NextStreamId::<Test>::get();
+    assert_ok!(EqXcmStreams::schedule_stream(
+        RuntimeOrigin::signed(who),
+        (DOT, 100),
+        recipient(),
+        10,
+        start,
+        payments,
+    ));
+    id
+}
+
+fn has_event(event: Event<Test>) -> bool {
+    System::events()
+        .iter()
+        .any(|r| r.event == RuntimeEvent::EqXcmStreams(event.clone()))
+}
+
+#[test]
+fn stream_pays_every_period_until_completed() {
+    new_test_ext().execute_with(|| {
+        let id = schedule(ALICE, 5, Some(2));
+        assert_eq!(EqXcmStreams::streams_count(ALICE), 1);
+        assert_eq!(EqXcmStreams::agenda(5).into_inner(), vec![id]);
+
+        run_to_block(4);
+        assert!(xcm_transfers().is_empty());
+
+        run_to_block(5);
+        assert_eq!(xcm_transfers(), vec![(ALICE, DOT, 100, recipient())]);
+        let stream = EqXcmStreams::streams(id).unwrap();
+        assert_eq!(stream.next_payment, 15);
+        assert_eq!(stream.remaining_payments, Some(1));
+        assert_eq!(EqXcmStreams::agenda(15).into_inner(), vec![id]);
+
+        run_to_block(15);
+        assert_eq!(xcm_transfers().len(), 2);
+        assert_eq!(EqXcmStreams::streams(id), None);
+        assert_eq!(EqXcmStreams::streams_count(ALICE), 0);
+        assert!(has_event(Event::StreamCompleted { id }));
+    });
+}
+
+#[test]
+fn schedule_stream_checks_params() {
+    new_test_ext().execute_with(|| {
+        let origin = RuntimeOrigin::signed(ALICE);
+        assert_noop!(
+            EqXcmStreams::schedule_stream(origin.clone(), (DOT, 0), recipient(), 10, 5, None),
+            Error::<Test>::ZeroAmount
+        );
+        assert_noop!(
+            EqXcmStreams::schedule_stream(origin.clone(), (DOT, 1), recipient(), 10, 5, Some(0)),
+            Error::<Test>::ZeroPayments
+        );
+        assert_noop!(
+            EqXcmStreams::schedule_stream(origin.clone(), (DOT, 1), recipient(), 9, 5, None),
+            Error::<Test>::PeriodTooShort
+        );
+        assert_noop!(
+            EqXcmStreams::schedule_stream(origin.clone(), (DOT, 1), recipient(), 10, 1, None),
+            Error::<Test>::StartInPast
+        );
+        assert_noop!(
+            EqXcmStreams::schedule_stream(
+                origin.clone(),
+                (DOT, 1),
+                MultiLocation::here(),
+                10,
+                5,
+                None
+            ),
+            Error::<Test>::XcmInvalidDestination
+        );
+
+        for _ in 0..MaxStreamsPerAccount::get() {
+            schedule(ALICE, 20, None);
+        }
+        assert_noop!(
+            EqXcmStreams::schedule_stream(origin, (DOT, 1), recipient(), 10, 30, None),
+            Error::<Test>::TooManyStreams
+        );
+    });
+}
+
+#[test]
+fn stream_moves_to_next_block_when_agenda_is_full() {
+    new_test_ext().execute_with(|| {
+        let first = schedule(ALICE, 5, None);
+        let second = schedule(ALICE, 5, None);
+        let third = schedule(ALICE, 5, None);
+
+        assert_eq!(EqXcmStreams::agenda(5).into_inner(), vec![first, second]);
+        assert_eq!(EqXcmStreams::agenda(6).into_inner(), vec![third]);
+        assert_eq!(EqXcmStreams::streams(third).unwrap().scheduled_at, 6);
+
+        schedule(BOB, 6, None);
+        assert_noop!(
+            EqXcmStreams::schedule_stream(
+                RuntimeOrigin::signed(BOB),
+                (DOT, 1),
+                recipient(),
+                10,
+                5,
+                None
+            ),
+            Error::<Test>::AgendaFull
+        );
+    });
+}
+
+#[test]
+fn failed_payment_is_retried_with_backoff() {
+    new_test_ext().execute_with(|| {
+        let id = schedule(ALICE, 5, None);
+        set_transfer_failing(true);
+
+        run_to_block(5);
+        assert!(xcm_transfers().is_empty());
+        assert_eq!(EqXcmStreams::streams(id).unwrap().failures, 1);
+        assert!(has_event(Event::StreamPaymentFailed {
+            id,
+            error: DispatchError::Other("transfer failed"),
+            failures: 1,
+            retry_at: 7,
+        }));
+
+        run_to_block(7);
+        let stream = EqXcmStreams::streams(id).unwrap();
+        assert_eq!(stream.failures, 2);
+        assert_eq!(stream.scheduled_at, 11);
+
+        set_transfer_failing(false);
+        run_to_block(11);
+        assert_eq!(xcm_transfers().len(), 1);
+        let stream = EqXcmStreams::streams(id).unwrap();
+        assert_eq!(stream.failures, 0);
+        // cadence is kept after successful retry
+        assert_eq!(stream.next_payment, 15);
+        assert_eq!(stream.scheduled_at, 15);
+    });
+}
+
+#[test]
+fn stream_is_aborted_after_max_failures() {
+    new_test_ext().execute_with(|| {
+        let id = schedule(ALICE, 5, None);
+        set_transfer_failing(true);
+
+        run_to_block(11);
+        assert_eq!(EqXcmStreams::streams(id), None);
+        assert_eq!(EqXcmStreams::streams_count(ALICE), 0);
+        assert!(has_event(Event::StreamAborted {
+            id,
+            error: DispatchError::Other("transfer failed"),
+        }));
+    });
+}
+
+#[test]
+fn cancel_stream_stops_payments() {
+    new_test_ext().execute_with(|| {
+        let id = schedule(ALICE, 5, None);
+
+        assert_noop!(
+            EqXcmStreams::cancel_stream(RuntimeOrigin::signed(BOB), id),
+            Error::<Test>::NotOwner
+        );
+        assert_noop!(
+            EqXcmStreams::cancel_stream(RuntimeOrigin::signed(ALICE), id + 1),
+            Error::<Test>::UnknownStream
+        );
+
+        assert_ok!(EqXcmStreams::cancel_stream(
+            RuntimeOrigin::signed(ALICE),
+            id
+        ));
+        assert!(EqXcmStreams::agenda(5).is_empty());
+        assert_eq!(EqXcmStreams::streams_count(ALICE), 0);
+        assert!(has_event(Event::StreamCancelled { id }));
+
+        run_to_block(5);
+        assert!(xcm_transfers().is_empty());
+    });
+}
@@ -19,3 +19,222 @@
 //! # Equilibrium XDot Pallet Benchmarking
 
 #![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use eq_primitives::asset::{self, Asset};
+use eq_utils::ONE_TOKEN;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+const BASE: Asset = asset::DOT;
+const XBASE: Asset = asset::XDOT;
+const ONE_MILLION: u128 = 1_000_000 * ONE_TOKEN;
+/// Three months in seconds
+const MATURITY_PERIOD: u64 = 3 * 30 * 24 * 60 * 60;
+/// Ten years in seconds
+const TS_PERIOD: u64 = 315_576_000;
+
+fn balance<T: Config>(amount: u128) -> T::Balance {
+    amount
+        .try_into()
+        .map_err(|_| "balance conversion error")
+        .unwrap()
+}
+
+fn fund<T: Config>(who: &T::AccountId)
+where
+    T::AssetId: From<Asset>,
+{
+    T::Assets::mint(BASE.into(), who, balance::<T>(ONE_MILLION)).unwrap();
+    T::Assets::mint(XBASE.into(), who, balance::<T>(ONE_MILLION)).unwrap();
+}
+
+fn create_pool<T: Config>(initializer: T::AccountId) -> PoolId
+where
+    T::AssetId: From<Asset>,
+{
+    let one = T::NumberConvert::convert(1);
+    let g1 = T::NumberConvert::convert(95) / T::NumberConvert::convert(100);
+    let g2 = one / g1;
+    let maturity = eq_rate::Pallet::<T>::now().as_secs() + MATURITY_PERIOD;
+    let ts = one / T::NumberConvert::convert(TS_PERIOD);
+
+    Pallet::<T>::do_create_pool(BASE.into(), XBASE.into(), g1, g2, maturity, ts, initializer)
+        .unwrap();
+    PoolCount::<T>::get() - 1
+}
+
+/// Creates initialized pool with some xbase sold to it like in trade tests,
+/// so every trade in benchmarks is possible
+fn init_pool<T: Config>() -> PoolId
+where
+    T::AssetId: From<Asset>,
+{
+    let initializer: T::AccountId = account("initializer", 0, SEED);
+    fund::<T>(&initializer);
+    let pool_id = create_pool::<T>(initializer.clone());
+    Pallet::<T>::do_initialize(
+        initializer.clone(),
+        pool_id,
+        balance::<T>(ONE_MILLION / 2),
+        balance::<T>(ONE_MILLION / 2),
+    )
+    .unwrap();
+    Pallet::<T>::do_sell_xbase(
+        &initializer,
+        pool_id,
+        balance::<T>(30 * ONE_TOKEN),
+        Zero::zero(),
+    )
+    .unwrap();
+    pool_id
+}
+
+benchmarks! {
+    where_clause {
+        where
+            T::AssetId: From<Asset>,
+    }
+
+    create_pool {
+        let origin = T::PoolsManagementOrigin::try_successful_origin()
+            .map_err(|_| "no successful origin")?;
+        let initializer: T::AccountId = account("initializer", 0, SEED);
+        let g1 = T::NumberConvert::convert(95) / T::NumberConvert::convert(100);
+        let g2 = T::NumberConvert::convert(1) / g1;
+        let maturity = eq_rate::Pallet::<T>::now().as_secs() + MATURITY_PERIOD;
+        let ts_period = T::NumberConvert::convert(TS_PERIOD);
+    }: _<T::RuntimeOrigin>(
+        origin, initializer, BASE.into(), XBASE.into(), g1, g2, maturity, ts_period
+    )
+    verify {
+        assert_eq!(PoolCount::<T>::get(), 1);
+    }
+
+    initialize {
+        let initializer: T::AccountId = account("initializer", 0, SEED);
+        fund::<T>(&initializer);
+        let pool_id = create_pool::<T>(initializer.clone());
+        let amount = balance::<T>(ONE_MILLION / 2);
+    }: _(RawOrigin::Signed(initializer), pool_id, amount, amount)
+    verify {
+        assert!(Initializer::<T>::get(pool_id).is_none());
+    }
+
+    change_initializer {
+        let origin = T::PoolsManagementOrigin::try_successful_origin()
+            .map_err(|_| "no successful origin")?;
+        let pool_id = create_pool::<T>(account("initializer", 0, SEED));
+        let new_initializer: T::AccountId = account("initializer", 1, SEED);
+    }: _<T::RuntimeOrigin>(origin, pool_id, Some(new_initializer.clone()))
+    verify {
+        assert_eq!(Initializer::<T>::get(pool_id), Some(new_initializer));
+    }
+
+    remove_pool {
+        let origin = T::PoolsManagementOrigin::try_successful_origin()
+            .map_err(|_| "no successful origin")?;
+        let pool_id = create_pool::<T>(account("initializer", 0, SEED));
+    }: _<T::RuntimeOrigin>(origin, pool_id)
+    verify {
+        assert!(Pools::<T>::get(pool_id).is_none());
+    }
+
+    mint {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        fund::<T>(&caller);
+        let pool_id = init_pool::<T>();
+        let pool_asset = Pallet::<T>::get_pool(pool_id)?.pool_asset;
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        pool_id,
+        (0, 1),
+        (u32::MAX, 1),
+        balance::<T>(ONE_TOKEN),
+        Zero::zero(),
+        balance::<T>(ONE_TOKEN / 1000)
+    )
+    verify {
+        assert!(!T::Assets::balance(pool_asset, &caller).is_zero());
+    }
+
+    burn {
+        let caller: T::AccountId = account("initializer", 0, SEED);
+        let pool_id = init_pool::<T>();
+        let supply = Pallet::<T>::get_pool(pool_id)?.lp_total_supply;
+        let lp_to_burn = balance::<T>(ONE_TOKEN);
+    }: _(RawOrigin::Signed(caller), pool_id, (0, 1), (u32::MAX, 1), lp_to_burn, true)
+    verify {
+        assert_eq!(Pallet::<T>::get_pool(pool_id)?.lp_total_supply, supply - lp_to_burn);
+    }
+
+    sell_base {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        fund::<T>(&caller);
+        let pool_id = init_pool::<T>();
+        let xbase_before = T::Assets::balance(XBASE.into(), &caller);
+    }: _(RawOrigin::Signed(caller.clone()), pool_id, balance::<T>(ONE_TOKEN), Zero::zero())
+    verify {
+        assert!(T::Assets::balance(XBASE.into(), &caller) > xbase_before);
+    }
+
+    buy_base {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        fund::<T>(&caller);
+        let pool_id = init_pool::<T>();
+        let base_before = T::Assets::balance(BASE.into(), &caller);
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        pool_id,
+        balance::<T>(ONE_TOKEN),
+        balance::<T>(2 * ONE_TOKEN),
+        balance::<T>(2 * ONE_TOKEN)
+    )
+    verify {
+        assert!(T::Assets::balance(BASE.into(), &caller) > base_before);
+    }
+
+    sell_xbase {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        fund::<T>(&caller);
+        let pool_id = init_pool::<T>();
+        let base_before = T::Assets::balance(BASE.into(), &caller);
+    }: _(RawOrigin::Signed(caller.clone()), pool_id, balance::<T>(ONE_TOKEN), Zero::zero())
+    verify {
+        assert!(T::Assets::balance(BASE.into(), &caller) > base_before);
+    }
+
+    buy_xbase {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        fund::<T>(&caller);
+        let pool_id = init_pool::<T>();
+        let xbase_before = T::Assets::balance(XBASE.into(), &caller);
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        pool_id,
+        balance::<T>(2 * ONE_TOKEN),
+        balance::<T>(ONE_TOKEN),
+        balance::<T>(2 * ONE_TOKEN)
+    )
+    verify {
+        assert!(T::Assets::balance(XBASE.into(), &caller) > xbase_before);
+    }
+
+    optimal_mint {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        fund::<T>(&caller);
+        let pool_id = init_pool::<T>();
+        let pool_asset = Pallet::<T>::get_pool(pool_id)?.pool_asset;
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        pool_id,
+        balance::<T>(10 * ONE_TOKEN),
+        Zero::zero(),
+        balance::<T>(ONE_TOKEN),
+        None
+    )
+    verify {
+        assert_eq!(T::Assets::balance(pool_asset, &caller), balance::<T>(ONE_TOKEN));
+    }
+}
@@ -188,7 +188,7 @@ pub mod pallet {
         /// - ts_period: period in secs for ts coeff
 
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::create_pool())]
         pub fn create_pool(
             origin: OriginFor<T>,
             initializer: T::AccountId,
@@ -217,7 +217,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::initialize())]
         pub fn initialize(
             origin: OriginFor<T>,
             pool_id: PoolId,
@@ -231,7 +231,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(2)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::change_initializer())]
         pub fn change_initializer(
             origin: OriginFor<T>,
             pool_id: PoolId,
@@ -249,7 +249,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(3)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_pool())]
         pub fn remove_pool(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResultWithPostInfo {
             T::PoolsManagementOrigin::ensure_origin(origin)?;
 
@@ -265,7 +265,7 @@ pub mod pallet {
         /// plus the `xbase_in`. A proportional amount of base tokens need to be sent.
         /// It fails if amount of base tokens for trade less than `base_in`
         #[pallet::call_index(4)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::mint())]
         pub fn mint(
             origin: OriginFor<T>,
             pool_id: PoolId,
@@ -293,7 +293,7 @@ pub mod pallet {
 
         /// Burn liquidity tokens in exchange for base and fyToken or base only with `trade_to_base=true`
         #[pallet::call_index(5)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::burn())]
         pub fn burn(
             origin: OriginFor<T>,
             pool_id: PoolId,
@@ -320,7 +320,7 @@ pub mod pallet {
         /// min -  minimm accepted amount of xbase token
         /// Returns amount of xbase token that will be transfered on caller account
         #[pallet::call_index(6)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::sell_base())]
         pub fn sell_base(
             origin: OriginFor<T>,
             pool_id: PoolId,
@@ -338,7 +338,7 @@ pub mod pallet {
         /// buy_base_amount - amount of base being bought that will be deposited to caller
         /// max - maximum amount of xbase token that will be paid for the trade
         #[pallet::call_index(7)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::buy_base())]
         pub fn buy_base(
             origin: OriginFor<T>,
             pool_id: PoolId,
@@ -357,7 +357,7 @@ pub mod pallet {
         /// xbase_to_sell - amount of xbase token to sell for base
         /// min - minimum accepted amount of base
         #[pallet::call_index(8)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::sell_xbase())]
         pub fn sell_xbase(
             origin: OriginFor<T>,
             pool_id: PoolId,
@@ -374,7 +374,7 @@ pub mod pallet {
         /// xbase_to_buy - amount of xbase being bought that will be transfered to caller
         /// max - maximum amount of base token that will be paid for the trade
         #[pallet::call_index(9)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::buy_xbase())]
         pub fn buy_xbase(
             origin: OriginFor<T>,
             pool_id: PoolId,
@@ -390,7 +390,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(10)]
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_native())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::optimal_mint())]
         pub fn optimal_mint(
             origin: OriginFor<T>,
             pool_id: PoolId,
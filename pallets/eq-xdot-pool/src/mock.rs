@@ -78,6 +78,7 @@ parameter_types! {
     pub const MinSurplus:u64 = 1 * 1000_000_000; // 1 usd
     pub const MinTempBailsman:u64 = 20 * 1000_000_000; // 20 usd
     pub const UnsignedPriority: u64 = 100;
+    pub const MaxUnsignedPerBlock: u32 = 100;
     pub const BasicCurrencyGet: asset::Asset = asset::EQ;
     pub LpTokensDebtWeight: Permill = Permill::from_rational(2u32, 5u32);
     pub const LpTokenBuyoutPriority: u64 = u64::MAX;
@@ -211,6 +212,7 @@ impl eq_rate::Config for Test {
     type BalanceGetter = Balances;
     type BalanceRemover = Balances;
     type UnsignedPriority = UnsignedPriority;
+    type MaxUnsignedPerBlock = MaxUnsignedPerBlock;
     type MinSurplus = MinSurplus;
     type MinTempBailsman = MinTempBailsman;
     type UnixTime = Timestamp;
@@ -239,6 +241,8 @@ impl eq_rate::Config for Test {
     type LendingModuleId = LendingModuleId;
     type LendingPoolManager = ();
     type LendingAssetRemoval = ();
+    type InterestIncomeReceiver = ();
+    type TreasuryAccounting = ();
     type RedistributeWeightInfo = ();
 }
 
@@ -371,6 +375,10 @@ impl eq_balances::Config for Test {
     type OrderAggregates = ();
     type XcmRouter = ();
     type XcmToFee = ();
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type UnixTime = TimeZeroDurationMock;
@@ -392,30 +400,17 @@ impl eq_assets::Config for Test {
 
 pub struct Assets;
 
-pub const LP_TOKEN: u64 = 1952805748; // b"test"
-
 impl crate::traits::Assets<AssetId, Balance, AccountId> for Assets {
     fn create_lp_asset(pool_id: crate::PoolId) -> Result<AssetId, DispatchError> {
-        let asset = Asset(LP_TOKEN + pool_id as u64);
-
-        EqAssets::do_add_asset(
-            asset,
-            EqFixedU128::from(0),
-            FixedI64::from(0),
-            Permill::zero(),
-            Permill::zero(),
-            asset::AssetXcmData::None,
-            LpTokensDebtWeight::get(),
-            LpTokenBuyoutPriority::get(),
-            eq_primitives::asset::AssetType::Lp(eq_primitives::asset::AmmPool::Yield(pool_id)),
-            false,
-            Percent::zero(),
-            Permill::one(),
-            vec![],
+        EqAssets::create_derivative_asset(
+            b"xlp",
+            pool_id,
+            eq_assets::DerivativeAssetParams::lp_token(
+                eq_primitives::asset::AssetType::Lp(eq_primitives::asset::AmmPool::Yield(pool_id)),
+                LpTokensDebtWeight::get(),
+                LpTokenBuyoutPriority::get(),
+            ),
         )
-        .map_err(|e| e.error)?;
-
-        Ok(asset)
     }
 
     fn mint(asset: AssetId, dest: &AccountId, amount: Balance) -> DispatchResult {
@@ -23,24 +23,52 @@ use frame_support::{traits::Get, weights::Weight};
 use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
-    fn transfer_native() -> Weight;
-    fn transfer_basic() -> Weight;
-    fn transfer() -> Weight;
-    fn remark() -> Weight;
+    fn create_pool() -> Weight;
+    fn initialize() -> Weight;
+    fn change_initializer() -> Weight;
+    fn remove_pool() -> Weight;
+    fn mint() -> Weight;
+    fn burn() -> Weight;
+    fn sell_base() -> Weight;
+    fn buy_base() -> Weight;
+    fn sell_xbase() -> Weight;
+    fn buy_xbase() -> Weight;
+    fn optimal_mint() -> Weight;
 }
 
-// for tests/temp
+// for tests
 impl crate::WeightInfo for () {
-    fn transfer_basic() -> Weight {
-        Weight::from_parts(10_000, 0)
+    fn create_pool() -> Weight {
+        Weight::zero()
     }
-    fn transfer_native() -> Weight {
-        Weight::from_parts(10_000, 0)
+    fn initialize() -> Weight {
+        Weight::zero()
     }
-    fn transfer() -> Weight {
-        Weight::from_parts(10_000, 0)
+    fn change_initializer() -> Weight {
+        Weight::zero()
     }
-    fn remark() -> Weight {
-        Weight::from_parts(10_000, 0)
+    fn remove_pool() -> Weight {
+        Weight::zero()
+    }
+    fn mint() -> Weight {
+        Weight::zero()
+    }
+    fn burn() -> Weight {
+        Weight::zero()
+    }
+    fn sell_base() -> Weight {
+        Weight::zero()
+    }
+    fn buy_base() -> Weight {
+        Weight::zero()
+    }
+    fn sell_xbase() -> Weight {
+        Weight::zero()
+    }
+    fn buy_xbase() -> Weight {
+        Weight::zero()
+    }
+    fn optimal_mint() -> Weight {
+        Weight::zero()
     }
 }
@@ -0,0 +1,87 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium EQD Savings Pallet Benchmarking
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use eq_primitives::SignedBalance;
+use eq_utils::ONE_TOKEN;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+/// Funds `who` and treasury, adds savings rate and budget so deposit and withdrawal
+/// accrue interest
+fn prepare<T: Config>(who: &T::AccountId) {
+    T::EqCurrency::make_free_balance_be(
+        who,
+        EQD,
+        SignedBalance::Positive(T::Balance::from(1_000 * ONE_TOKEN)),
+    );
+    T::EqCurrency::make_free_balance_be(
+        &T::TreasuryModuleId::get().into_account_truncating(),
+        EQD,
+        SignedBalance::Positive(T::Balance::from(1_000_000 * ONE_TOKEN)),
+    );
+    SavingsRate::<T>::put(Permill::from_percent(5));
+    IncomeBudget::<T>::put(T::Balance::from(1_000 * ONE_TOKEN));
+    // interest accrual may fail without EQD price in benchmark runtime,
+    // failed one is cheaper anyway
+    LastAccrual::<T>::put(1);
+}
+
+benchmarks! {
+    deposit {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        prepare::<T>(&caller);
+        let depositor: T::AccountId = account("depositor", 0, SEED);
+        prepare::<T>(&depositor);
+        Pallet::<T>::deposit(RawOrigin::Signed(depositor).into(), T::Balance::from(100 * ONE_TOKEN))?;
+        LastAccrual::<T>::put(1);
+    }: _(RawOrigin::Signed(caller.clone()), T::Balance::from(100 * ONE_TOKEN))
+    verify {
+        assert!(!Shares::<T>::get(&caller).is_zero());
+    }
+
+    withdraw {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        prepare::<T>(&caller);
+        Pallet::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), T::Balance::from(100 * ONE_TOKEN))?;
+        LastAccrual::<T>::put(1);
+        let shares = Shares::<T>::get(&caller);
+    }: _(RawOrigin::Signed(caller.clone()), shares)
+    verify {
+        assert!(!Shares::<T>::contains_key(&caller));
+    }
+
+    set_savings_rate {
+        let origin = T::RateOrigin::try_successful_origin()
+            .map_err(|_| "no successful origin")?;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        prepare::<T>(&caller);
+        Pallet::<T>::deposit(RawOrigin::Signed(caller).into(), T::Balance::from(100 * ONE_TOKEN))?;
+        LastAccrual::<T>::put(1);
+    }: _<T::RuntimeOrigin>(origin, Permill::from_percent(10))
+    verify {
+        assert_eq!(SavingsRate::<T>::get(), Permill::from_percent(10));
+    }
+}
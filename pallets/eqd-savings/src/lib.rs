@@ -0,0 +1,379 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Equilibrium EQD Savings Pallet
+//!
+//! Users deposit EQD and earn the savings rate set by governance.
+//!
+//! Deposits are kept on the pallet account and accounted in shares. A depositor gets
+//! shares by the current share price and withdraws EQD by the share price at the moment
+//! of withdrawal, there is no withdrawal delay. Interest is accrued on every deposit,
+//! withdrawal and rate change by a transfer of EQD from the treasury to the pallet account,
+//! so the share price grows.
+//!
+//! Savings are funded from protocol interest income: eq-rate reports the treasury part of
+//! charged interest fees through `InterestIncomeReceiver` and its USD value is added to
+//! `IncomeBudget`. Accrued interest never exceeds the budget.
+//!
+//! Pallet account is in `UserGroup::Savers`, total EQD in savings is available in
+//! `EqAggregates` totals.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(warnings)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use eq_primitives::{
+    asset::{Asset, EQD},
+    balance::EqCurrency,
+    balance_number::EqFixedU128,
+    price::PriceGetter,
+    Aggregates, InterestIncomeReceiver, TransferReason, UserGroup,
+};
+use eq_utils::{
+    fixed::{balance_from_eq_fixedu128, eq_fixedu128_from_balance},
+    multiply_by_rational,
+};
+use frame_support::{
+    traits::{ExistenceRequirement, Get, UnixTime},
+    PalletId,
+};
+use sp_runtime::{
+    traits::{AccountIdConversion, CheckedDiv, Saturating, Zero},
+    ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, Permill,
+};
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+/// 365.25 * 24 * 60 * 60
+const SECONDS_PER_YEAR: u64 = 31_557_600;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::AtLeast32BitUnsigned;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Numerical representation of stored balances
+        type Balance: Parameter
+            + Member
+            + AtLeast32BitUnsigned
+            + MaybeSerializeDeserialize
+            + Copy
+            + Default
+            + MaxEncodedLen
+            + From<eq_primitives::balance::Balance>
+            + Into<eq_primitives::balance::Balance>;
+        /// Transfers of deposits and interest
+        type EqCurrency: EqCurrency<Self::AccountId, Self::Balance>;
+        /// Used to value interest income and accrued interest in USD
+        type PriceGetter: PriceGetter;
+        /// Timestamp provider
+        type UnixTime: UnixTime;
+        /// Used to add pallet account to `UserGroup::Savers`
+        type Aggregates: Aggregates<Self::AccountId, Self::Balance>;
+        /// Pallet account keeping deposits
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+        /// Treasury account, pays accrued interest
+        #[pallet::constant]
+        type TreasuryModuleId: Get<PalletId>;
+        /// Origin allowed to set the savings rate
+        type RateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Min amount of a single deposit
+        #[pallet::constant]
+        type MinDeposit: Get<Self::Balance>;
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Annual savings rate
+    #[pallet::storage]
+    #[pallet::getter(fn savings_rate)]
+    pub type SavingsRate<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+    /// Shares of depositors
+    #[pallet::storage]
+    #[pallet::getter(fn shares)]
+    pub type Shares<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::Balance, ValueQuery>;
+
+    /// Total amount of shares
+    #[pallet::storage]
+    #[pallet::getter(fn total_shares)]
+    pub type TotalShares<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+
+    /// Timestamp (in seconds) of the last interest accrual
+    #[pallet::storage]
+    pub type LastAccrual<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// USD value of interest income not paid to savers yet
+    #[pallet::storage]
+    #[pallet::getter(fn income_budget)]
+    pub type IncomeBudget<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// EQD is deposited to savings
+        /// \[who, amount, shares\]
+        Deposited(T::AccountId, T::Balance, T::Balance),
+        /// EQD is withdrawn from savings
+        /// \[who, amount, shares\]
+        Withdrawn(T::AccountId, T::Balance, T::Balance),
+        /// Interest is accrued to savings
+        /// \[amount\]
+        InterestAccrued(T::Balance),
+        /// Savings rate is changed
+        /// \[rate\]
+        SavingsRateSet(Permill),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Deposit is less than `MinDeposit`
+        DepositTooSmall,
+        /// Amount is too small to get or burn a share
+        ZeroShares,
+        /// Account has less shares than requested
+        NotEnoughShares,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Deposits `amount` of EQD to savings
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::deposit())]
+        pub fn deposit(origin: OriginFor<T>, amount: T::Balance) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(amount >= T::MinDeposit::get(), Error::<T>::DepositTooSmall);
+
+            Self::accrue_interest();
+
+            let total_shares = <TotalShares<T>>::get();
+            let pool = Self::pool_balance();
+            let shares = if total_shares.is_zero() || pool.is_zero() {
+                amount
+            } else {
+                multiply_by_rational(amount, total_shares, pool)
+                    .map(Into::into)
+                    .ok_or(ArithmeticError::Overflow)?
+            };
+            ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+
+            let account_id = Self::account_id();
+            T::Aggregates::set_usergroup(&account_id, UserGroup::Savers, true)?;
+            T::EqCurrency::currency_transfer(
+                &who,
+                &account_id,
+                EQD,
+                amount,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::Common,
+                true,
+            )?;
+
+            <Shares<T>>::mutate(&who, |s| *s = s.saturating_add(shares));
+            <TotalShares<T>>::put(total_shares.saturating_add(shares));
+
+            Self::deposit_event(Event::Deposited(who, amount, shares));
+            Ok(().into())
+        }
+
+        /// Burns `shares` of the caller and withdraws their EQD value from savings
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::withdraw())]
+        pub fn withdraw(origin: OriginFor<T>, shares: T::Balance) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+            let own_shares = <Shares<T>>::get(&who);
+            ensure!(own_shares >= shares, Error::<T>::NotEnoughShares);
+
+            Self::accrue_interest();
+
+            let total_shares = <TotalShares<T>>::get();
+            let amount: T::Balance =
+                multiply_by_rational(shares, Self::pool_balance(), total_shares)
+                    .map(Into::into)
+                    .ok_or(ArithmeticError::Overflow)?;
+
+            T::EqCurrency::currency_transfer(
+                &Self::account_id(),
+                &who,
+                EQD,
+                amount,
+                ExistenceRequirement::AllowDeath,
+                TransferReason::Common,
+                false,
+            )?;
+
+            let rest = own_shares - shares;
+            if rest.is_zero() {
+                <Shares<T>>::remove(&who);
+            } else {
+                <Shares<T>>::insert(&who, rest);
+            }
+            <TotalShares<T>>::put(total_shares.saturating_sub(shares));
+
+            Self::deposit_event(Event::Withdrawn(who, amount, shares));
+            Ok(().into())
+        }
+
+        /// Sets annual savings rate, interest by the previous rate is accrued first
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::set_savings_rate())]
+        pub fn set_savings_rate(origin: OriginFor<T>, rate: Permill) -> DispatchResultWithPostInfo {
+            T::RateOrigin::ensure_origin(origin)?;
+
+            Self::accrue_interest();
+            <SavingsRate<T>>::put(rate);
+
+            Self::deposit_event(Event::SavingsRateSet(rate));
+            Ok(().into())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Account keeping deposits
+    pub fn account_id() -> T::AccountId {
+        T::PalletId::get().into_account_truncating()
+    }
+
+    /// EQD kept in savings
+    pub fn pool_balance() -> T::Balance {
+        T::EqCurrency::free_balance(&Self::account_id(), EQD)
+    }
+
+    /// EQD value of `who` shares, used in runtime API
+    pub fn deposit_of(who: &T::AccountId) -> T::Balance {
+        let shares = <Shares<T>>::get(who);
+        let total_shares = <TotalShares<T>>::get();
+        if shares.is_zero() || total_shares.is_zero() {
+            return T::Balance::zero();
+        }
+
+        multiply_by_rational(shares, Self::pool_balance(), total_shares)
+            .map(Into::into)
+            .unwrap_or_else(Zero::zero)
+    }
+
+    /// Pays interest by the savings rate since the last accrual, limited by `IncomeBudget`.
+    /// Interest isn't paid for the period if the treasury can't pay it.
+    fn accrue_interest() {
+        let now = T::UnixTime::now().as_secs();
+        let last = <LastAccrual<T>>::get();
+        <LastAccrual<T>>::put(now);
+        if last.is_zero() || now <= last {
+            return;
+        }
+
+        if let Err(e) = Self::pay_interest(now - last) {
+            log::warn!(
+                target: "eqd_savings",
+                "{}:{}. Interest is not accrued: {:?}",
+                file!(),
+                line!(),
+                e
+            );
+        }
+    }
+
+    fn pay_interest(elapsed: u64) -> DispatchResult {
+        let rate = <SavingsRate<T>>::get();
+        let pool = Self::pool_balance();
+        if rate.is_zero() || pool.is_zero() {
+            return Ok(());
+        }
+
+        let price: EqFixedU128 = T::PriceGetter::get_price(&EQD)?;
+        let budget = <IncomeBudget<T>>::get();
+        let max_interest: T::Balance = eq_fixedu128_from_balance(budget)
+            .checked_div(&price)
+            .and_then(balance_from_eq_fixedu128)
+            .ok_or(ArithmeticError::Overflow)?;
+
+        let interest: T::Balance =
+            multiply_by_rational(rate.mul_floor(pool), elapsed, SECONDS_PER_YEAR)
+                .map(Into::into)
+                .ok_or(ArithmeticError::Overflow)?;
+        let interest = interest.min(max_interest);
+        if interest.is_zero() {
+            return Ok(());
+        }
+
+        T::EqCurrency::currency_transfer(
+            &T::TreasuryModuleId::get().into_account_truncating(),
+            &Self::account_id(),
+            EQD,
+            interest,
+            ExistenceRequirement::KeepAlive,
+            TransferReason::InterestFee,
+            true,
+        )?;
+
+        let interest_value: T::Balance = price
+            .checked_mul_int(Into::<u128>::into(interest))
+            .map(Into::into)
+            .ok_or(ArithmeticError::Overflow)?;
+        <IncomeBudget<T>>::put(budget.saturating_sub(interest_value));
+
+        Self::deposit_event(Event::InterestAccrued(interest));
+        Ok(())
+    }
+
+    fn add_income(asset: Asset, amount: T::Balance) -> Result<(), DispatchError> {
+        let price: EqFixedU128 = T::PriceGetter::get_price(&asset)?;
+        let value: T::Balance = price
+            .checked_mul_int(Into::<u128>::into(amount))
+            .map(Into::into)
+            .ok_or(ArithmeticError::Overflow)?;
+        <IncomeBudget<T>>::mutate(|budget| *budget = budget.saturating_add(value));
+        Ok(())
+    }
+}
+
+impl<T: Config> InterestIncomeReceiver<T::Balance> for Pallet<T> {
+    fn on_interest_income(asset: Asset, amount: T::Balance) {
+        if let Err(e) = Self::add_income(asset, amount) {
+            log::error!(
+                target: "eqd_savings",
+                "{}:{}. Interest income is not added to savings budget. Asset: {:?}, amount: {:?}, error: {:?}",
+                file!(),
+                line!(),
+                asset,
+                amount,
+                e
+            );
+        }
+    }
+}
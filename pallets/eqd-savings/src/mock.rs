@@ -0,0 +1,174 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as eqd_savings;
+use eq_primitives::{
+    asset::{self, AssetType},
+    SignedBalance, TotalAggregates,
+};
+use frame_support::{parameter_types, traits::Everything};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    FixedI64,
+};
+use std::cell::RefCell;
+use test_utils::builder::{AssetConfig, ExtBuilder};
+pub use test_utils::mock::time::TimeMock;
+
+pub type AccountId = u64;
+pub type Balance = eq_primitives::balance::Balance;
+pub type EqCurrencyMock = test_utils::mock::currency::EqCurrencyMock<AccountId>;
+pub type OracleMock = eq_primitives::price::mock::OracleMock<AccountId>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        EqdSavings: eqd_savings::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SavingsModuleId: PalletId = PalletId(*b"eq/savng");
+    pub const TreasuryModuleId: PalletId = PalletId(*b"eq/trsry");
+    pub const MinDeposit: Balance = 1_000_000_000;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqCurrencyMock;
+    type PriceGetter = OracleMock;
+    type UnixTime = TimeMock;
+    type Aggregates = AggregatesMock;
+    type PalletId = SavingsModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
+    type RateOrigin = EnsureRoot<AccountId>;
+    type MinDeposit = MinDeposit;
+    type WeightInfo = ();
+}
+
+thread_local! {
+    static SAVERS: RefCell<Vec<AccountId>> = RefCell::new(Vec::new());
+}
+
+pub fn set_balance(who: AccountId, asset: Asset, amount: Balance) {
+    EqCurrencyMock::set_balance(&who, asset, amount);
+}
+
+pub fn treasury() -> AccountId {
+    TreasuryModuleId::get().into_account_truncating()
+}
+
+pub struct AggregatesMock;
+
+impl Aggregates<AccountId, Balance> for AggregatesMock {
+    fn in_usergroup(account_id: &AccountId, user_group: UserGroup) -> bool {
+        user_group == UserGroup::Savers && SAVERS.with(|s| s.borrow().contains(account_id))
+    }
+
+    fn set_usergroup(account_id: &AccountId, user_group: UserGroup, is_in: bool) -> DispatchResult {
+        assert_eq!(user_group, UserGroup::Savers);
+        SAVERS.with(|s| {
+            let mut s = s.borrow_mut();
+            s.retain(|acc| acc != account_id);
+            if is_in {
+                s.push(*account_id);
+            }
+        });
+        Ok(())
+    }
+
+    fn update_total(
+        _: &AccountId,
+        _: Asset,
+        _: &SignedBalance<Balance>,
+        _: &SignedBalance<Balance>,
+    ) -> DispatchResult {
+        unimplemented!()
+    }
+
+    fn iter_account(_: UserGroup) -> Box<dyn Iterator<Item = AccountId>> {
+        unimplemented!()
+    }
+
+    fn iter_total(_: UserGroup) -> Box<dyn Iterator<Item = (Asset, TotalAggregates<Balance>)>> {
+        unimplemented!()
+    }
+
+    fn get_total(_: UserGroup, _: Asset) -> TotalAggregates<Balance> {
+        unimplemented!()
+    }
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    ExtBuilder::default()
+        .asset(AssetConfig::new(EQD, AssetType::Synthetic, 1).price(1))
+        .asset(
+            AssetConfig::new(asset::EQ, AssetType::Native, u64::MAX)
+                .fixed_price(FixedI64::saturating_from_rational(1, 2)),
+        )
+        .init_mocks();
+    TimeMock::set_secs(1_000);
+
+    let r = frame_system::GenesisConfig::default().build_storage::<Test>();
+
+    let mut ext: sp_io::TestExternalities = r.unwrap().into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,126 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError::BadOrigin};
+
+const ONE_TOKEN: Balance = eq_utils::ONE_TOKEN;
+const USER_1: AccountId = 1;
+const USER_2: AccountId = 2;
+
+#[test]
+fn deposit_and_withdraw_with_accrued_interest() {
+    new_test_ext().execute_with(|| {
+        set_balance(USER_1, EQD, 1_000 * ONE_TOKEN);
+        set_balance(USER_2, EQD, 2_000 * ONE_TOKEN);
+        set_balance(treasury(), EQD, 10_000 * ONE_TOKEN);
+
+        assert_ok!(EqdSavings::deposit(
+            RuntimeOrigin::signed(USER_1),
+            1_000 * ONE_TOKEN
+        ));
+        assert_eq!(EqdSavings::shares(USER_1), 1_000 * ONE_TOKEN);
+        assert!(AggregatesMock::in_usergroup(
+            &EqdSavings::account_id(),
+            UserGroup::Savers
+        ));
+
+        assert_ok!(EqdSavings::set_savings_rate(
+            RuntimeOrigin::root(),
+            Permill::from_percent(10)
+        ));
+        // 200 EQ by 0.5 USD
+        EqdSavings::on_interest_income(eq_primitives::asset::EQ, 200 * ONE_TOKEN);
+        assert_eq!(EqdSavings::income_budget(), 100 * ONE_TOKEN);
+
+        TimeMock::set_secs(1_000 + SECONDS_PER_YEAR);
+        assert_ok!(EqdSavings::deposit(
+            RuntimeOrigin::signed(USER_2),
+            1_100 * ONE_TOKEN
+        ));
+        assert_eq!(EqdSavings::income_budget(), 0);
+        assert_eq!(
+            EqCurrencyMock::free_balance(&treasury(), EQD),
+            9_900 * ONE_TOKEN
+        );
+        // share price is 1.1 after accrual
+        assert_eq!(EqdSavings::shares(USER_2), 1_000 * ONE_TOKEN);
+        assert_eq!(EqdSavings::deposit_of(&USER_1), 1_100 * ONE_TOKEN);
+
+        assert_ok!(EqdSavings::withdraw(
+            RuntimeOrigin::signed(USER_1),
+            1_000 * ONE_TOKEN
+        ));
+        assert_eq!(
+            EqCurrencyMock::free_balance(&USER_1, EQD),
+            1_100 * ONE_TOKEN
+        );
+        assert!(!Shares::<Test>::contains_key(USER_1));
+        assert_eq!(EqdSavings::total_shares(), 1_000 * ONE_TOKEN);
+        assert_eq!(EqdSavings::pool_balance(), 1_100 * ONE_TOKEN);
+    });
+}
+
+#[test]
+fn interest_is_limited_by_income_budget() {
+    new_test_ext().execute_with(|| {
+        set_balance(USER_1, EQD, 1_000 * ONE_TOKEN);
+        set_balance(treasury(), EQD, 10_000 * ONE_TOKEN);
+
+        assert_ok!(EqdSavings::deposit(
+            RuntimeOrigin::signed(USER_1),
+            1_000 * ONE_TOKEN
+        ));
+        assert_ok!(EqdSavings::set_savings_rate(
+            RuntimeOrigin::root(),
+            Permill::from_percent(10)
+        ));
+        EqdSavings::on_interest_income(EQD, 30 * ONE_TOKEN);
+
+        TimeMock::set_secs(1_000 + SECONDS_PER_YEAR);
+        assert_ok!(EqdSavings::set_savings_rate(
+            RuntimeOrigin::root(),
+            Permill::zero()
+        ));
+        assert_eq!(EqdSavings::pool_balance(), 1_030 * ONE_TOKEN);
+        assert_eq!(EqdSavings::income_budget(), 0);
+    });
+}
+
+#[test]
+fn deposit_and_withdraw_errors() {
+    new_test_ext().execute_with(|| {
+        set_balance(USER_1, EQD, 1_000 * ONE_TOKEN);
+
+        assert_noop!(
+            EqdSavings::deposit(RuntimeOrigin::signed(USER_1), MinDeposit::get() - 1),
+            Error::<Test>::DepositTooSmall
+        );
+        assert_noop!(
+            EqdSavings::withdraw(RuntimeOrigin::signed(USER_1), ONE_TOKEN),
+            Error::<Test>::NotEnoughShares
+        );
+        assert_noop!(
+            EqdSavings::set_savings_rate(RuntimeOrigin::signed(USER_1), Permill::from_percent(10)),
+            BadOrigin
+        );
+    });
+}
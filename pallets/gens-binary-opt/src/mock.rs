@@ -222,6 +222,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = eq_primitives::mocks::XcmRouterErrMock;
     type XcmToFee = eq_primitives::mocks::XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU64<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = eq_primitives::mocks::UniversalLocationMock;
     type OrderAggregates = ();
@@ -338,7 +342,7 @@ impl eq_primitives::balance::BalanceChecker<Balance, AccountId, EqBalances, Suba
         who: &u64,
         changes: &Vec<(Asset, SignedBalance<Balance>)>,
         _reason: Option<WithdrawReasons>,
-    ) -> Result<(), sp_runtime::DispatchError> {
+    ) -> Result<(), eq_primitives::balance::BalanceCheckError<Balance>> {
         let all_positive = changes.iter().all(|(_, sb)| match sb {
             SignedBalance::Positive(_) => true,
             SignedBalance::Negative(_) => false,
@@ -347,7 +351,10 @@ impl eq_primitives::balance::BalanceChecker<Balance, AccountId, EqBalances, Suba
             return Ok(());
         }
         match who {
-            &FAIL_ACC => Err(DispatchError::Other("Expected error")),
+            &FAIL_ACC => Err(eq_primitives::balance::BalanceCheckError::new(
+                eq_primitives::balance::BalanceCheckerKind::Other,
+                DispatchError::Other("Expected error"),
+            )),
             _ => Ok(()),
         }
     }
@@ -281,6 +281,10 @@ impl eq_balances::Config for Test {
     type ModuleId = BalancesModuleId;
     type XcmRouter = XcmRouterErrMock;
     type XcmToFee = XcmToFeeZeroMock;
+    type XcmRefundQueryHandler = ();
+    type XcmResponseOrigin = eq_primitives::mocks::NeverEnsureResponseMock;
+    type XcmRefundTimeout = frame_support::traits::ConstU32<100>;
+    type MigratableLockOwners = frame_support::traits::Everything;
     type LocationToAccountId = ();
     type UniversalLocation = UniversalLocationMock;
     type OrderAggregates = ();
@@ -297,6 +301,10 @@ impl eq_vesting::Config<VestingInstance1> for Test {
     type WeightInfo = ();
     type IsTransfersEnabled = ModuleBalances;
     type BlockNumberToBalance = BlockNumberToBalance;
+    type FeeCurrency = QCurrency;
+    type AutoVestFee = frame_support::traits::ConstU128<0>;
+    type AutoVestFeeDestination = frame_support::traits::ConstU128<0>;
+    type MaxAutoVestPerBlock = frame_support::traits::ConstU32<0>;
 }
 
 type VestingInstance2 = eq_vesting::Instance2;
@@ -309,6 +317,10 @@ impl eq_vesting::Config<VestingInstance2> for Test {
     type WeightInfo = ();
     type IsTransfersEnabled = ModuleBalances;
     type BlockNumberToBalance = BlockNumberToBalance;
+    type FeeCurrency = QCurrency;
+    type AutoVestFee = frame_support::traits::ConstU128<0>;
+    type AutoVestFeeDestination = frame_support::traits::ConstU128<0>;
+    type MaxAutoVestPerBlock = frame_support::traits::ConstU32<0>;
 }
 
 type VestingInstance3 = eq_vesting::Instance3;
@@ -321,6 +333,10 @@ impl eq_vesting::Config<VestingInstance3> for Test {
     type WeightInfo = ();
     type IsTransfersEnabled = ModuleBalances;
     type BlockNumberToBalance = BlockNumberToBalance;
+    type FeeCurrency = QCurrency;
+    type AutoVestFee = frame_support::traits::ConstU128<0>;
+    type AutoVestFeeDestination = frame_support::traits::ConstU128<0>;
+    type MaxAutoVestPerBlock = frame_support::traits::ConstU32<0>;
 }
 
 impl q_swap::Config for Test {
@@ -0,0 +1,304 @@
+// This file is part of Equilibrium.
+
+// Copyright (C) 2023 EQ Lab.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Call filter policy shared by runtimes. A runtime describes its calls with `CallPolicyRules`
+//! and `CallPolicy` gives verdicts by the same rules for all runtimes:
+//! - calls of disabled pallets are rejected in any mode;
+//! - while migration is in progress only calls allowed in migration pass, XCM calls are
+//!   rejected;
+//! - otherwise disabled calls and XCM calls are rejected, XCM calls are allowed only in
+//!   multisig proposals to allowed destinations, batches are checked call by call.
+
+use eq_primitives::call_filter::{FilterReason, FilterVerdictWithReason};
+use frame_support::traits::Contains;
+use sp_std::{boxed::Box, marker::PhantomData};
+
+/// Per-runtime rule table of `CallPolicy`
+pub trait CallPolicyRules<Call> {
+    /// Whether migration is in progress
+    fn migration_in_progress() -> bool;
+    /// Pallet toggles: calls of pallets (or single calls) disabled in production,
+    /// rejected in any mode
+    fn is_pallet_disabled(call: &Call) -> bool;
+    /// Calls disabled in production, rejected as not allowed in migration while it's in
+    /// progress
+    fn is_call_disabled(call: &Call) -> bool;
+    /// Call allowed while migration is in progress, e.g. system and multisig calls
+    fn is_allowed_in_migration(call: &Call) -> bool;
+    /// Call sends XCM
+    fn is_xcm(call: &Call) -> bool;
+    /// XCM call proposed by multisig is allowed, e.g. `Transact` to allowed destination
+    fn is_multisig_xcm_allowed(call: &Call) -> bool;
+    /// Call proposed by multisig, if `call` is a multisig proposal
+    fn multisig_proposal(call: &Call) -> Option<&Call>;
+    /// Calls of a batch, if `call` is a batch
+    fn batch(call: &Call) -> Option<&[Call]>;
+}
+
+/// Call filter applying `Rules`
+pub struct CallPolicy<Call, Rules>(PhantomData<(Call, Rules)>);
+
+impl<Call, Rules: CallPolicyRules<Call>> CallPolicy<Call, Rules> {
+    /// Verdict of the filter with the reason of rejection
+    pub fn verdict(call: &Call) -> FilterVerdictWithReason {
+        if Rules::is_pallet_disabled(call) {
+            return FilterVerdictWithReason::Rejected(FilterReason::Disabled);
+        }
+
+        if Rules::migration_in_progress() {
+            return if Rules::is_xcm(call) {
+                FilterVerdictWithReason::Rejected(FilterReason::XcmNotAllowed)
+            } else if Rules::is_allowed_in_migration(call) {
+                FilterVerdictWithReason::Allowed
+            } else {
+                FilterVerdictWithReason::Rejected(FilterReason::MigrationInProgress)
+            };
+        }
+
+        if let Some(proposed) = Rules::multisig_proposal(call) {
+            return if Rules::is_xcm(proposed) {
+                // allow send xcm from msig to allowed destinations
+                Self::multisig_xcm_verdict(proposed)
+            } else if let Some(calls) = Rules::batch(proposed) {
+                // allow send xcm batch from msig
+                Self::batch_verdict(calls, true)
+            } else {
+                Self::verdict(proposed)
+            };
+        }
+
+        if let Some(calls) = Rules::batch(call) {
+            Self::batch_verdict(calls, false)
+        } else if Rules::is_call_disabled(call) {
+            FilterVerdictWithReason::Rejected(FilterReason::Disabled)
+        } else if Rules::is_xcm(call) {
+            FilterVerdictWithReason::Rejected(FilterReason::XcmNotAllowed)
+        } else {
+            FilterVerdictWithReason::Allowed
+        }
+    }
+
+    /// Verdict of XCM call proposed by multisig
+    fn multisig_xcm_verdict(call: &Call) -> FilterVerdictWithReason {
+        if Rules::is_multisig_xcm_allowed(call) {
+            FilterVerdictWithReason::Allowed
+        } else {
+            FilterVerdictWithReason::Rejected(FilterReason::XcmTransactNotAllowed)
+        }
+    }
+
+    /// First rejected call of batch, XCM calls are allowed in batches of multisig proposals
+    fn batch_verdict(calls: &[Call], allow_xcm: bool) -> FilterVerdictWithReason {
+        calls
+            .iter()
+            .enumerate()
+            .find_map(|(index, call)| {
+                let verdict = if allow_xcm && Rules::is_xcm(call) {
+                    Self::multisig_xcm_verdict(call)
+                } else {
+                    Self::verdict(call)
+                };
+                match verdict {
+                    FilterVerdictWithReason::Allowed => None,
+                    FilterVerdictWithReason::Rejected(reason) => Some(FilterReason::BatchedCall {
+                        index: index as u32,
+                        reason: Box::new(reason),
+                    }),
+                }
+            })
+            .map_or(
+                FilterVerdictWithReason::Allowed,
+                FilterVerdictWithReason::Rejected,
+            )
+    }
+}
+
+impl<Call, Rules: CallPolicyRules<Call>> Contains<Call> for CallPolicy<Call, Rules> {
+    fn contains(call: &Call) -> bool {
+        Self::verdict(call).is_allowed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    enum TestCall {
+        System,
+        Faucet,
+        Transfer,
+        Deposit,
+        Xcm { allowed: bool },
+        Propose(Box<TestCall>),
+        Batch(Vec<TestCall>),
+    }
+
+    thread_local! {
+        static MIGRATION: Cell<bool> = Cell::new(false);
+    }
+
+    struct TestRules;
+    impl CallPolicyRules<TestCall> for TestRules {
+        fn migration_in_progress() -> bool {
+            MIGRATION.with(|v| v.get())
+        }
+        fn is_pallet_disabled(call: &TestCall) -> bool {
+            matches!(call, TestCall::Faucet)
+        }
+        fn is_call_disabled(call: &TestCall) -> bool {
+            matches!(call, TestCall::Deposit)
+        }
+        fn is_allowed_in_migration(call: &TestCall) -> bool {
+            matches!(call, TestCall::System | TestCall::Propose(_))
+        }
+        fn is_xcm(call: &TestCall) -> bool {
+            matches!(call, TestCall::Xcm { .. })
+        }
+        fn is_multisig_xcm_allowed(call: &TestCall) -> bool {
+            matches!(call, TestCall::Xcm { allowed: true })
+        }
+        fn multisig_proposal(call: &TestCall) -> Option<&TestCall> {
+            match call {
+                TestCall::Propose(call) => Some(&**call),
+                _ => None,
+            }
+        }
+        fn batch(call: &TestCall) -> Option<&[TestCall]> {
+            match call {
+                TestCall::Batch(calls) => Some(&calls[..]),
+                _ => None,
+            }
+        }
+    }
+
+    type Policy = CallPolicy<TestCall, TestRules>;
+
+    fn rejected(reason: FilterReason) -> FilterVerdictWithReason {
+        FilterVerdictWithReason::Rejected(reason)
+    }
+
+    fn batched(index: u32, reason: FilterReason) -> FilterVerdictWithReason {
+        rejected(FilterReason::BatchedCall {
+            index,
+            reason: Box::new(reason),
+        })
+    }
+
+    #[test]
+    fn disabled_and_xcm_calls_rejected() {
+        MIGRATION.with(|v| v.set(false));
+
+        assert!(Policy::contains(&TestCall::System));
+        assert!(Policy::contains(&TestCall::Transfer));
+        assert_eq!(
+            Policy::verdict(&TestCall::Faucet),
+            rejected(FilterReason::Disabled)
+        );
+        assert_eq!(
+            Policy::verdict(&TestCall::Deposit),
+            rejected(FilterReason::Disabled)
+        );
+        assert_eq!(
+            Policy::verdict(&TestCall::Xcm { allowed: true }),
+            rejected(FilterReason::XcmNotAllowed)
+        );
+    }
+
+    #[test]
+    fn multisig_proposals_may_send_allowed_xcm() {
+        MIGRATION.with(|v| v.set(false));
+
+        let propose = |call| TestCall::Propose(Box::new(call));
+        assert!(Policy::contains(&propose(TestCall::Xcm { allowed: true })));
+        assert_eq!(
+            Policy::verdict(&propose(TestCall::Xcm { allowed: false })),
+            rejected(FilterReason::XcmTransactNotAllowed)
+        );
+        assert_eq!(
+            Policy::verdict(&propose(TestCall::Deposit)),
+            rejected(FilterReason::Disabled)
+        );
+        assert!(Policy::contains(&propose(TestCall::Batch(vec![
+            TestCall::Transfer,
+            TestCall::Xcm { allowed: true },
+        ]))));
+        assert_eq!(
+            Policy::verdict(&propose(TestCall::Batch(vec![
+                TestCall::Transfer,
+                TestCall::Xcm { allowed: false },
+            ]))),
+            batched(1, FilterReason::XcmTransactNotAllowed)
+        );
+    }
+
+    #[test]
+    fn batches_checked_call_by_call() {
+        MIGRATION.with(|v| v.set(false));
+
+        assert!(Policy::contains(&TestCall::Batch(vec![
+            TestCall::Transfer,
+            TestCall::System,
+        ])));
+        assert_eq!(
+            Policy::verdict(&TestCall::Batch(vec![
+                TestCall::Transfer,
+                TestCall::Xcm { allowed: true },
+            ])),
+            batched(1, FilterReason::XcmNotAllowed)
+        );
+        assert_eq!(
+            Policy::verdict(&TestCall::Batch(vec![TestCall::Batch(vec![
+                TestCall::Deposit
+            ])])),
+            batched(
+                0,
+                FilterReason::BatchedCall {
+                    index: 0,
+                    reason: Box::new(FilterReason::Disabled),
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn only_allowed_calls_during_migration() {
+        MIGRATION.with(|v| v.set(true));
+
+        assert!(Policy::contains(&TestCall::System));
+        assert!(Policy::contains(&TestCall::Propose(Box::new(
+            TestCall::Transfer
+        ))));
+        assert_eq!(
+            Policy::verdict(&TestCall::Transfer),
+            rejected(FilterReason::MigrationInProgress)
+        );
+        assert_eq!(
+            Policy::verdict(&TestCall::Batch(vec![TestCall::System])),
+            rejected(FilterReason::MigrationInProgress)
+        );
+        assert_eq!(
+            Policy::verdict(&TestCall::Faucet),
+            rejected(FilterReason::Disabled)
+        );
+        assert_eq!(
+            Policy::verdict(&TestCall::Xcm { allowed: true }),
+            rejected(FilterReason::XcmNotAllowed)
+        );
+    }
+}
@@ -31,6 +31,7 @@ pub use sp_runtime::{
 pub use sp_std::prelude::*;
 use xcm::v3::{AssetId, Junction::Parachain, Junctions::X1, MultiAsset, MultiLocation};
 
+pub mod call_filter;
 pub mod mocks;
 
 /// An index to a block.
@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use super::{AccountId, CouncilInstance, TechnicalCommitteeInstance};
+use super::{AccountId, CouncilInstance, ParameterTrackInstance, TechnicalCommitteeInstance};
 use frame_support::traits::EitherOfDiverse;
 use frame_system::EnsureRoot;
 use pallet_collective::*;
@@ -75,3 +75,14 @@ pub type EnsureAtLeastOneOfTechnicalCommittee = EitherOfDiverse<
     EnsureMember<AccountId, TechnicalCommitteeInstance>,
     EnsureMembers<AccountId, TechnicalCommitteeInstance, 1>,
 >;
+
+/// Parameter track motion. Accepted only by origins of bounded parameter tweaks,
+/// any other call proposed on the track fails with `BadOrigin`
+pub type EnsureMoreThanHalfParameterTrack =
+    EnsureProportionMoreThan<AccountId, ParameterTrackInstance, 1, 2>;
+
+pub type EnsureRootOrTwoThirdsCouncilOrParameterTrack =
+    EitherOfDiverse<EnsureRootOrTwoThirdsCouncil, EnsureMoreThanHalfParameterTrack>;
+
+pub type EnsureRootOrTwoThirdsTechnicalCommitteeOrParameterTrack =
+    EitherOfDiverse<EnsureRootOrTwoThirdsTechnicalCommittee, EnsureMoreThanHalfParameterTrack>;
@@ -26,6 +26,7 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 pub use chainbridge;
+use common_runtime::call_filter::CallPolicyRules;
 use core::convert::{TryFrom, TryInto};
 pub use eq_assets;
 pub use eq_balances;
@@ -35,13 +36,16 @@ pub use eq_claim;
 pub use eq_distribution;
 pub use eq_multisig_sudo;
 pub use eq_primitives;
+use eq_primitives::asset::OnNewAsset;
 use eq_primitives::asset::{self, Asset, AssetGetter, AssetType};
-use eq_primitives::asset::{AssetXcmData, OnNewAsset};
 use eq_primitives::balance::{AccountData, DebtCollateralDiscounted, EqCurrency};
 use eq_primitives::balance_number::EqFixedU128;
+use eq_primitives::call_filter::FilterVerdictWithReason;
 use eq_primitives::curve_number::{CurveNumber, CurveNumberConvert};
 use eq_primitives::subaccount::SubAccType;
 use eq_primitives::xcm_origins::{dot::*, RELAY};
+#[cfg(not(feature = "production"))]
+use eq_primitives::xdot_pool::XdotNumber;
 use eq_primitives::BlockNumberToBalance;
 use eq_primitives::{Aggregates, TransferReason, UnsignedPriorityPair, UserGroup};
 pub use eq_rate;
@@ -52,7 +56,6 @@ pub use eq_vesting;
 use eq_whitelists;
 use eq_xcm::ParaId;
 pub use equilibrium_curve_amm;
-use financial_pallet::{self, FinancialSystemTrait};
 use financial_primitives::{CalcReturnType, CalcVolatilityType};
 use frame_support::traits::tokens::imbalance::SplitTwoWays;
 use frame_support::traits::{
@@ -115,7 +118,7 @@ use xcm::v3::{
 };
 use xcm_builder::{
     AllowKnownQueryResponses, AllowSubscriptionsFrom, EnsureXcmOrigin, FixedWeightBounds,
-    ParentIsPreset, SiblingParachainConvertsVia,
+    ParentIsPreset, SiblingParachainConvertsVia, SovereignSignedViaLocation,
 };
 use xcm_executor::traits::{ConvertOrigin, WithOriginFilter};
 use xcm_executor::{Config, XcmExecutor};
@@ -247,84 +250,83 @@ parameter_types! {
 /// Call filter for exctrinsics
 /// XCM extrinsics aren't allowed in prod
 pub struct CallFilter;
-impl frame_support::traits::Contains<RuntimeCall> for CallFilter {
+impl CallFilter {
+    /// Verdict of the filter with the reason of rejection, used by `contains`
+    /// and by `EqCallFilterApi` to check calls without dispatching
     #[allow(unused_variables)]
-    fn contains(c: &RuntimeCall) -> bool {
+    pub fn verdict(c: &RuntimeCall) -> FilterVerdictWithReason {
         #[cfg(feature = "production")]
-        match (eq_migration::Migration::<Runtime>::exists(), c) {
-            (_, RuntimeCall::EqWrappedDot(eq_wrapped_dot::Call::initialize { .. })) => false,
-
-            // no migration, custom filter
-            // (false, Call::Sudo(sudo_call)) => match sudo_call {
-            //     sudo::Call::sudo { call }
-            //     | sudo::Call::sudo_as { call, .. }
-            //     | sudo::Call::sudo_unchecked_weight { call, .. } => {
-            //         match **call {
-            //             Call::PolkadotXcm(_) => true, // allow send xcm from sudo
-            //             _ => Self::contains(&**call),
-            //         }
-            //     }
-            //     _ => true,
-            // },
-            (false, RuntimeCall::EqMultisigSudo(proposal_call)) => match proposal_call {
-                eq_multisig_sudo::Call::propose { call } => match *call.clone() {
-                    RuntimeCall::PolkadotXcm(_) => true, // allow send xcm from msig
-                    RuntimeCall::Utility(utility_call) => {
-                        // allow send xcm batch from msig
-                        match utility_call {
-                            pallet_utility::Call::batch { calls, .. }
-                            | pallet_utility::Call::batch_all { calls, .. } => {
-                                calls.iter().all(|call| {
-                                    if let RuntimeCall::PolkadotXcm(_) = call {
-                                        true
-                                    } else {
-                                        Self::contains(call)
-                                    }
-                                })
-                            }
-                            _ => true,
-                        }
-                    }
-                    _ => Self::contains(&**call),
-                },
-                _ => true,
-            },
-            (false, RuntimeCall::Utility(utility_call)) => match utility_call {
-                pallet_utility::Call::batch { calls, .. }
-                | pallet_utility::Call::batch_all { calls, .. } => {
-                    calls.iter().all(|call| Self::contains(call))
-                }
-                _ => true,
-            },
-            (false, RuntimeCall::EqBalances(call)) => match call {
-                eq_balances::Call::deposit { .. } => false,
-                _ => true,
-            },
-            (
-                false,
-                RuntimeCall::Oracle(eq_oracle::Call::set_fin_metrics_recalc_enabled { .. }),
-            ) => false,
-            (false, RuntimeCall::EqRate(eq_rate::Call::set_now_millis_offset { .. })) => false,
-            (false, RuntimeCall::Vesting(eq_vesting::Call::force_vested_transfer { .. })) => false,
-            (false, RuntimeCall::Vesting2(eq_vesting::Call::force_vested_transfer { .. })) => false,
-            (false, RuntimeCall::Vesting3(eq_vesting::Call::force_vested_transfer { .. })) => false,
-            (false, RuntimeCall::Vesting4(eq_vesting::Call::force_vested_transfer { .. })) => false,
-            // XCM disallowed
-            (_, &RuntimeCall::PolkadotXcm(_)) => false,
-            (false, _) => true,
-
-            // only system and sudo are allowed during migration
-            (true, &RuntimeCall::ParachainSystem(_)) => true,
-            (true, &RuntimeCall::System(_)) => true,
-            // (true, &Call::Sudo(_)) => true,
-            (true, &RuntimeCall::Timestamp(_)) => true,
-            (true, &RuntimeCall::EqMultisigSudo(_)) => true,
-
-            // all other pallets are disallowed during migration
-            (true, _) => false,
+        {
+            common_runtime::call_filter::CallPolicy::<RuntimeCall, CallFilterRules>::verdict(c)
         }
         #[cfg(not(feature = "production"))]
-        true
+        FilterVerdictWithReason::Allowed
+    }
+}
+
+impl frame_support::traits::Contains<RuntimeCall> for CallFilter {
+    fn contains(c: &RuntimeCall) -> bool {
+        Self::verdict(c).is_allowed()
+    }
+}
+
+/// Rule table of production `CallFilter`, see `common_runtime::call_filter`
+pub struct CallFilterRules;
+impl CallPolicyRules<RuntimeCall> for CallFilterRules {
+    fn migration_in_progress() -> bool {
+        eq_migration::Migration::<Runtime>::exists()
+    }
+
+    fn is_pallet_disabled(call: &RuntimeCall) -> bool {
+        matches!(call, RuntimeCall::EqWrappedDot(eq_wrapped_dot::Call::initialize { .. }))
+    }
+
+    fn is_call_disabled(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::EqBalances(eq_balances::Call::deposit { .. })
+                | RuntimeCall::Oracle(eq_oracle::Call::set_fin_metrics_recalc_enabled { .. })
+                | RuntimeCall::EqRate(eq_rate::Call::set_now_millis_offset { .. })
+                | RuntimeCall::Vesting(eq_vesting::Call::force_vested_transfer { .. })
+                | RuntimeCall::Vesting2(eq_vesting::Call::force_vested_transfer { .. })
+                | RuntimeCall::Vesting3(eq_vesting::Call::force_vested_transfer { .. })
+                | RuntimeCall::Vesting4(eq_vesting::Call::force_vested_transfer { .. })
+        )
+    }
+
+    fn is_allowed_in_migration(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::ParachainSystem(_)
+                | RuntimeCall::System(_)
+                | RuntimeCall::Timestamp(_)
+                | RuntimeCall::EqMultisigSudo(_)
+        )
+    }
+
+    fn is_xcm(call: &RuntimeCall) -> bool {
+        matches!(call, RuntimeCall::PolkadotXcm(_))
+    }
+
+    fn is_multisig_xcm_allowed(call: &RuntimeCall) -> bool {
+        MultisigProposalFilter::contains(call)
+    }
+
+    fn multisig_proposal(call: &RuntimeCall) -> Option<&RuntimeCall> {
+        match call {
+            RuntimeCall::EqMultisigSudo(eq_multisig_sudo::Call::propose { call }) => Some(&**call),
+            _ => None,
+        }
+    }
+
+    fn batch(call: &RuntimeCall) -> Option<&[RuntimeCall]> {
+        match call {
+            RuntimeCall::Utility(pallet_utility::Call::batch { calls, .. })
+            | RuntimeCall::Utility(pallet_utility::Call::batch_all { calls, .. }) => {
+                Some(&calls[..])
+            }
+            _ => None,
+        }
     }
 }
 
@@ -401,20 +403,59 @@ impl eq_assets::Config for Runtime {
 //----------- eq-multisig-sudo ------------------
 parameter_types! {
     pub const MaxSignatories: u32 = 10;
+    pub const MultisigProposalLifetime: BlockNumber = 7 * DAYS;
 }
 
 impl eq_multisig_sudo::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type RuntimeCall = RuntimeCall;
     type MaxSignatories = MaxSignatories;
+    type ProposalLifetime = MultisigProposalLifetime;
+    type VetoOrigin = EnsureRootOrHalfTechnicalCommittee;
+    type ProposalFilter = MultisigProposalFilter;
+    type XcmAllowListOrigin = EnsureRootOrTwoThirdsCouncil;
     type WeightInfo = weights::pallet_multisig_sudo::WeightInfo<Runtime>;
 }
+
+/// XCM messages sent by multisig proposals may `Transact` only calls allowed
+/// for their destination by `EqMultisigSudo` allow-list
+pub struct MultisigProposalFilter;
+impl Contains<RuntimeCall> for MultisigProposalFilter {
+    fn contains(call: &RuntimeCall) -> bool {
+        match call {
+            RuntimeCall::PolkadotXcm(pallet_xcm::Call::send { dest, message }) => {
+                EqMultisigSudo::is_xcm_send_allowed(dest, message)
+            }
+            RuntimeCall::Utility(
+                pallet_utility::Call::batch { calls, .. }
+                | pallet_utility::Call::batch_all { calls, .. }
+                | pallet_utility::Call::force_batch { calls, .. },
+            ) => calls.iter().all(Self::contains),
+            RuntimeCall::Utility(
+                pallet_utility::Call::as_derivative { call, .. }
+                | pallet_utility::Call::dispatch_as { call, .. }
+                | pallet_utility::Call::with_weight { call, .. },
+            ) => Self::contains(call),
+            _ => true,
+        }
+    }
+}
 //------------ eq-margin-call -------------------
 parameter_types! {
     pub InitialMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(2, 10);
     pub MaintenanceMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(1, 10);
     pub CriticalMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(5, 100);
     pub MaintenancePeriod: u64 = 60*60*24;
+    pub const MaxRiskNotifications: u32 = 16;
+    pub const PriceRestorePeriod: BlockNumber = 30 * MINUTES;
+    pub const MaxPendingLiquidations: u32 = 100;
+    pub const MaxPriorityMarginCallsPerBlock: u32 = 50;
+    pub RiskTierMarginMultipliers: eq_primitives::asset::RiskTierParams<EqFixedU128> =
+        eq_primitives::asset::RiskTierParams {
+            tier1: EqFixedU128::from(1),
+            tier2: EqFixedU128::saturating_from_rational(125, 100),
+            tier3: EqFixedU128::saturating_from_rational(150, 100),
+        };
 }
 
 impl eq_margin_call::Config for Runtime {
@@ -429,9 +470,23 @@ impl eq_margin_call::Config for Runtime {
     type CriticalMargin = CriticalMargin;
     type MaintenancePeriod = MaintenancePeriod;
     type OrderAggregates = EqDex;
+    #[cfg(not(feature = "production"))]
+    type DerivativePositions = EqPerps;
+    #[cfg(feature = "production")]
+    type DerivativePositions = ();
     type AssetGetter = EqAssets;
     type SubaccountsManager = Subaccounts;
+    type MaxNotifications = MaxRiskNotifications;
     type WeightInfo = weights::pallet_margin_call::WeightInfo<Runtime>;
+    type LendingIsolation = EqLending;
+    type RiskTierMarginMultipliers = RiskTierMarginMultipliers;
+    type EqCurrency = EqBalances;
+    type TreasuryModuleId = TreasuryModuleId;
+    type CompensationOrigin = EnsureRootOrTwoThirdsCouncil;
+    type PriceRestorePeriod = PriceRestorePeriod;
+    type MaxPendingLiquidations = MaxPendingLiquidations;
+    type KeeperOrigin = eq_whitelists::EnsureWhitelisted<Runtime>;
+    type MaxPriorityMarginCallsPerBlock = MaxPriorityMarginCallsPerBlock;
 }
 
 parameter_types! {
@@ -454,6 +509,10 @@ parameter_types! {
     pub const ExistentialDeposit: Balance = EXISTENTIAL_DEPOSIT_USD; // 0.1 USD
     pub const ExistentialDepositBasic: Balance = EXISTENTIAL_DEPOSIT_Q; // 0.125 Q
     pub const ExistentialDepositEq: Balance = EXISTENTIAL_DEPOSIT_EQ; // 100 EQ
+    /// Initial per-asset existential deposits, later managed by `EqAssets::set_existential_deposit`
+    pub ExistentialDepositOverrides: Vec<(eq_primitives::asset::Asset, Balance)> = vec![];
+    pub RiskTierOverrides: Vec<(eq_primitives::asset::Asset, eq_primitives::asset::RiskTier)> =
+        vec![];
     pub const BasicCurrencyGet: eq_primitives::asset::Asset = eq_primitives::asset::EQ;
     pub const QCurrencyGet: eq_primitives::asset::Asset = eq_primitives::asset::Q;
     pub const EqCurrencyGet: eq_primitives::asset::Asset = eq_primitives::asset::EQ;
@@ -520,7 +579,11 @@ impl sp_runtime::traits::Convert<(Asset, XcmWeight), Option<eq_utils::XcmBalance
                 interlay::ibtc::WeightToFee::weight_to_fee(&weight)
             }
             asset::EQD => crate::fee::XcmWeightToFee::weight_to_fee(&weight),
-            asset::USDT => crate::fee::XcmWeightToFee::weight_to_fee(&weight) / 1_000,
+            asset::USDT => eq_primitives::decimals::balance_swap_decimals(
+                crate::fee::XcmWeightToFee::weight_to_fee(&weight),
+                eq_primitives::DECIMALS,
+                EqAssets::decimals(&asset),
+            )?,
             asset::EQ => crate::fee::XcmWeightToFee::weight_to_fee(&weight) / 10,
             _ => return None,
         })
@@ -642,6 +705,29 @@ impl<'xcm, Call>
     }
 }
 
+/// Registers queries for unspent fee of XCM transfers in PolkadotXcm
+pub struct XcmRefundQueryHandler;
+impl eq_balances::XcmRefundQueryHandler<Runtime> for XcmRefundQueryHandler {
+    fn new_notify_query(
+        responder: MultiLocation,
+        notify: eq_balances::Call<Runtime>,
+        timeout: BlockNumber,
+        match_querier: MultiLocation,
+    ) -> Option<xcm::v3::QueryId> {
+        Some(PolkadotXcm::new_notify_query(
+            responder,
+            RuntimeCall::EqBalances(notify),
+            timeout,
+            match_querier,
+        ))
+    }
+}
+
+parameter_types! {
+    pub const XcmRefundTimeout: BlockNumber = 100;
+    pub const MigratableLockOwner: eq_primitives::balance::LockOwner = eq_staking::STAKING_ID;
+}
+
 impl eq_balances::Config for Runtime {
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type ToggleTransferOrigin = EnsureRootOrHalfTechnicalCommittee;
@@ -679,6 +765,10 @@ impl eq_balances::Config for Runtime {
     type OrderAggregates = EqDex;
     type ParachainId = ParachainInfo;
     type UnixTime = EqRate;
+    type XcmRefundQueryHandler = XcmRefundQueryHandler;
+    type XcmResponseOrigin = pallet_xcm::EnsureResponse<frame_support::traits::Everything>;
+    type XcmRefundTimeout = XcmRefundTimeout;
+    type MigratableLockOwners = frame_support::traits::Equals<MigratableLockOwner>;
 }
 
 pub type BasicCurrency = eq_primitives::balance_adapter::BalanceAdapter<
@@ -817,12 +907,21 @@ parameter_types! {
     pub const LpPriceBlockTimeout: u64 = PRICE_TIMEOUT_IN_SECONDS * 1000 / MILLISECS_PER_BLOCK;
     pub const UnsignedLifetimeInBlocks: u32 = 5;
     pub const FinancialRecalcPeriodBlocks: BlockNumber  = (1000 * 60 * 60 * 4) / MILLISECS_PER_BLOCK as BlockNumber; // 4 hours in blocks
+    pub const SuspectPricePeriod: BlockNumber = (1000 * 60 * 30) / MILLISECS_PER_BLOCK as BlockNumber; // 30 minutes in blocks
+    pub const SuspectPriceConfirmations: u32 = 3;
+    pub const FeederKeyOverlap: BlockNumber = (1000 * 60 * 60) / MILLISECS_PER_BLOCK as BlockNumber; // 1 hour in blocks
+    pub const MaxFeederKeys: u32 = 4;
+    pub const MaxFinancialRecalcFailures: u32 = 10;
+    pub const MaxPricesPerBatch: u32 = 32;
+    pub const MaxPriceSubmissionsPerBlock: u32 = 64;
 }
 
 parameter_types! {
     pub BuyFee: Permill = PerThing::from_rational::<u32>(1, 100);
     pub SellFee: Permill = PerThing::from_rational::<u32>(15, 100);
     pub const MinAmountToBuyout: Balance = 100 * ONE_TOKEN; // 100 Eq
+    pub const TreasuryReportPeriod: BlockNumber = 30 * DAYS;
+    pub const MaxTreasuryReports: u32 = 24;
 }
 
 impl eq_treasury::Config for Runtime {
@@ -838,6 +937,10 @@ impl eq_treasury::Config for Runtime {
     type UnixTime = eq_rate::Pallet<Runtime>;
     type WeightInfo = weights::pallet_treasury::WeightInfo<Runtime>;
     type MinAmountToBuyout = MinAmountToBuyout;
+    type LendingPoolManager = EqLending;
+    type CurveLiquidity = curve_utils::CurveLiquidityAdapter;
+    type ReportPeriod = TreasuryReportPeriod;
+    type MaxReports = MaxTreasuryReports;
 }
 
 parameter_types! {
@@ -882,6 +985,12 @@ impl Get<AccountId> for Vesting4Account {
     }
 }
 
+parameter_types! {
+    /// Fee of automatic vesting in `on_idle`, EQ
+    pub const AutoVestFee: Balance = ONE_TOKEN / 10;
+    pub const MaxAutoVestPerBlock: u32 = 20;
+}
+
 // Used in eq_claim
 type VestingInstance1 = eq_vesting::Instance1;
 impl eq_vesting::Config<VestingInstance1> for Runtime {
@@ -893,6 +1002,10 @@ impl eq_vesting::Config<VestingInstance1> for Runtime {
     type WeightInfo = weights::pallet_vesting::WeightInfo<Runtime>;
     type PalletId = Vesting1ModuleId;
     type IsTransfersEnabled = eq_balances::Pallet<Runtime>;
+    type FeeCurrency = EqTokenCurrency;
+    type AutoVestFee = AutoVestFee;
+    type AutoVestFeeDestination = TreasuryAccount;
+    type MaxAutoVestPerBlock = MaxAutoVestPerBlock;
 }
 
 type VestingInstance2 = eq_vesting::Instance2;
@@ -905,6 +1018,10 @@ impl eq_vesting::Config<VestingInstance2> for Runtime {
     type WeightInfo = weights::pallet_vesting::WeightInfo<Runtime>;
     type PalletId = Vesting2ModuleId;
     type IsTransfersEnabled = eq_balances::Pallet<Runtime>;
+    type FeeCurrency = EqTokenCurrency;
+    type AutoVestFee = AutoVestFee;
+    type AutoVestFeeDestination = TreasuryAccount;
+    type MaxAutoVestPerBlock = MaxAutoVestPerBlock;
 }
 
 type VestingInstance3 = eq_vesting::Instance3;
@@ -917,6 +1034,10 @@ impl eq_vesting::Config<VestingInstance3> for Runtime {
     type WeightInfo = weights::pallet_vesting::WeightInfo<Runtime>;
     type PalletId = Vesting3ModuleId;
     type IsTransfersEnabled = eq_balances::Pallet<Runtime>;
+    type FeeCurrency = EqTokenCurrency;
+    type AutoVestFee = AutoVestFee;
+    type AutoVestFeeDestination = TreasuryAccount;
+    type MaxAutoVestPerBlock = MaxAutoVestPerBlock;
 }
 
 type VestingInstance4 = eq_vesting::Instance4;
@@ -929,6 +1050,10 @@ impl eq_vesting::Config<VestingInstance4> for Runtime {
     type WeightInfo = weights::pallet_vesting::WeightInfo<Runtime>;
     type PalletId = Vesting4ModuleId;
     type IsTransfersEnabled = eq_balances::Pallet<Runtime>;
+    type FeeCurrency = EqTokenCurrency;
+    type AutoVestFee = AutoVestFee;
+    type AutoVestFeeDestination = TreasuryAccount;
+    type MaxAutoVestPerBlock = MaxAutoVestPerBlock;
 }
 
 impl eq_claim::Config for Runtime {
@@ -941,6 +1066,7 @@ impl eq_claim::Config for Runtime {
     type WeightInfo = weights::pallet_claim::WeightInfo<Runtime>;
     type UnsignedPriority = ClaimUnsignedPriorityPair;
     type Currency = EqTokenCurrency;
+    type TreasuryAccountId = TreasuryAccount;
 }
 
 parameter_types! {
@@ -948,6 +1074,7 @@ parameter_types! {
     pub const LockPeriod: u64 = 90 * 24 * 60 * 60;
     pub const MinLockAmount: Balance = 10 * ONE_TOKEN;
     pub const LockDropUnsignedPriorityPair: TransactionPriority = TransactionPriority::min_value();
+    pub const LockdropEarlyUnlockPenalty: Permill = Permill::from_percent(20);
 }
 
 impl eq_lockdrop::Config for Runtime {
@@ -958,6 +1085,7 @@ impl eq_lockdrop::Config for Runtime {
     type ValidatorOffchainBatcher = eq_rate::Pallet<Runtime>;
     type MinLockAmount = MinLockAmount;
     type LockDropUnsignedPriority = LockDropUnsignedPriorityPair;
+    type EarlyUnlockPenalty = LockdropEarlyUnlockPenalty;
     type WeightInfo = weights::pallet_lockdrop::WeightInfo<Runtime>;
 }
 
@@ -1032,13 +1160,15 @@ impl OnNewAsset for FinancialPalletOnNewAsset {
             );
         }
 
-        // May be that new prices are breaking financial pallet recalculation,
-        // but it is very rare case
-        #[allow(unused_must_use)]
-        let _ = Financial::recalc_inner();
+        // Recalculation is heavy, it is deferred to idle blocks
+        Oracle::enqueue_financial_recalc(&[asset]);
     }
 }
 
+parameter_types! {
+    pub const MaxBudgetMilestones: u32 = 16;
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 type DistriBenchInstance = eq_distribution::Instance16;
 #[cfg(feature = "runtime-benchmarks")]
@@ -1047,10 +1177,13 @@ impl eq_distribution::Config<DistriBenchInstance> for Runtime {
     type PalletId = TreasuryModuleId;
     type Vesting = Vesting;
     type Balance = Balance;
-    type Vesting1AccountId = Vesting1Account;
+    type VestingAccountId = Vesting1Account;
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type EqCurrency = eq_balances::Pallet<Runtime>;
     type WeightInfo = weights::pallet_distribution::WeightInfo<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type MaxMilestones = MaxBudgetMilestones;
 }
 
 type TreasuryInstance = eq_distribution::Instance5;
@@ -1063,6 +1196,9 @@ impl eq_distribution::Config<TreasuryInstance> for Runtime {
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type EqCurrency = eq_balances::Pallet<Runtime>;
     type WeightInfo = weights::pallet_distribution::WeightInfo<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type MaxMilestones = MaxBudgetMilestones;
 }
 
 type RepublicInstance = eq_distribution::Instance2;
@@ -1075,6 +1211,9 @@ impl eq_distribution::Config<RepublicInstance> for Runtime {
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type EqCurrency = eq_balances::Pallet<Runtime>;
     type WeightInfo = weights::pallet_distribution::WeightInfo<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type MaxMilestones = MaxBudgetMilestones;
 }
 
 type Investors = eq_distribution::Instance3;
@@ -1087,6 +1226,9 @@ impl eq_distribution::Config<Investors> for Runtime {
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type EqCurrency = eq_balances::Pallet<Runtime>;
     type WeightInfo = weights::pallet_distribution::WeightInfo<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type MaxMilestones = MaxBudgetMilestones;
 }
 
 type LiquidityFarmingD = eq_distribution::Instance4;
@@ -1099,6 +1241,9 @@ impl eq_distribution::Config<LiquidityFarmingD> for Runtime {
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type EqCurrency = eq_balances::Pallet<Runtime>;
     type WeightInfo = weights::pallet_distribution::WeightInfo<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type MaxMilestones = MaxBudgetMilestones;
 }
 
 type CrowdloanDistributionInstance = eq_distribution::Instance6;
@@ -1111,6 +1256,9 @@ impl eq_distribution::Config<CrowdloanDistributionInstance> for Runtime {
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type EqCurrency = eq_balances::Pallet<Runtime>;
     type WeightInfo = weights::pallet_distribution::WeightInfo<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type MaxMilestones = MaxBudgetMilestones;
 }
 
 type StabilizationPoolInstance = eq_distribution::Instance7;
@@ -1123,10 +1271,14 @@ impl eq_distribution::Config<StabilizationPoolInstance> for Runtime {
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type EqCurrency = eq_balances::Pallet<Runtime>;
     type WeightInfo = weights::pallet_distribution::WeightInfo<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type MaxMilestones = MaxBudgetMilestones;
 }
 
 parameter_types! {
     pub const RateUnsignedPriority: TransactionPriority = TransactionPriority::min_value();
+    pub const RateMaxUnsignedPerBlock: u32 = 256;
 
 
     pub TreasuryFee: Permill = Permill::from_percent(1);
@@ -1172,6 +1324,7 @@ impl eq_rate::Config for Runtime {
     type MarginCallManager = EqMarginCall;
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type UnsignedPriority = RateUnsignedPriority;
+    type MaxUnsignedPerBlock = RateMaxUnsignedPerBlock;
     type WeightInfo = weights::pallet_rate::WeightInfo<Runtime>;
     type RedistributeWeightInfo = WeightInfoGetter;
     type RiskLowerBound = RiskLowerBound;
@@ -1192,6 +1345,11 @@ impl eq_rate::Config for Runtime {
     type LendingModuleId = LendingModuleId;
     type LendingPoolManager = EqLending;
     type LendingAssetRemoval = EqLending;
+    #[cfg(not(feature = "production"))]
+    type InterestIncomeReceiver = EqdSavings;
+    #[cfg(feature = "production")]
+    type InterestIncomeReceiver = ();
+    type TreasuryAccounting = Treasury;
 }
 
 impl eq_session_manager::Config for Runtime {
@@ -1203,6 +1361,13 @@ impl eq_session_manager::Config for Runtime {
     type WeightInfo = weights::pallet_session_manager::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+    pub const NavSamplingPeriod: BlockNumber = HOURS;
+    pub const NavSamplingFee: Balance = 10 * ONE_TOKEN;
+    pub const MaxNavSamples: u32 = 720; // 30 days of hourly samples
+    pub const MaxNavSampledAccounts: u32 = 100;
+}
+
 impl eq_subaccounts::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type Balance = Balance;
@@ -1213,13 +1378,28 @@ impl eq_subaccounts::Config for Runtime {
     type BailsmenManager = Bailsman;
     type PriceGetter = Oracle;
     type Whitelist = Whitelists;
+    type LendingPoolManager = EqLending;
     type UpdateTimeManager = EqRate;
     type WeightInfo = weights::pallet_subaccounts::WeightInfo<Runtime>;
     type IsTransfersEnabled = EqBalances;
+    type TreasuryModuleId = TreasuryModuleId;
+    type NavSamplingPeriod = NavSamplingPeriod;
+    type NavSamplingFee = NavSamplingFee;
+    type MaxNavSamples = MaxNavSamples;
+    type MaxNavSampledAccounts = MaxNavSampledAccounts;
 }
 
 parameter_types! {
     pub const AccountsPerBlock: u32 = 100;
+    pub const LendingRewardAccrualPeriod: u64 = 24 * 60 * 60;
+    pub const MaxLendingRewardCheckpoints: u32 = 600;
+    pub const MaxIsolatedCollateral: u32 = 5;
+    pub RiskTierBorrowCaps: eq_primitives::asset::RiskTierParams<Permill> =
+        eq_primitives::asset::RiskTierParams {
+            tier1: Permill::from_percent(100),
+            tier2: Permill::from_percent(75),
+            tier3: Permill::from_percent(50),
+        };
 }
 
 impl eq_lending::Config for Runtime {
@@ -1235,6 +1415,14 @@ impl eq_lending::Config for Runtime {
     type BailsmanManager = Bailsman;
     type UnixTime = EqRate;
     type AccountsToMigratePerBlock = AccountsPerBlock;
+    type RewardAccrualPeriod = LendingRewardAccrualPeriod;
+    type IsolationOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type MaxIsolatedCollateral = MaxIsolatedCollateral;
+    type RiskTierBorrowCaps = RiskTierBorrowCaps;
+    type ReserveOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type TreasuryModuleId = TreasuryModuleId;
+    type MaxRewardCheckpoints = MaxLendingRewardCheckpoints;
+    type LpLendingOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
     type WeightInfo = weights::pallet_lending::WeightInfo<Runtime>;
 }
 
@@ -1288,6 +1476,8 @@ where
             eq_claim::PrevalidateAttests::<Runtime>::new(),
             eq_treasury::CheckBuyout::<Runtime>::new(),
             // q_swap::CheckQSwap::<Runtime>::new(),
+            #[cfg(feature = "weight-telemetry")]
+            eq_weight_telemetry::RecordCallWeight::<Runtime>::new(),
         );
 
         let raw_payload = SignedPayload::new(call, extra)
@@ -1332,27 +1522,16 @@ pub struct EqCurveAssetsAdapter;
 type AssetId = eq_primitives::asset::Asset;
 impl equilibrium_curve_amm::traits::Assets<AssetId, Balance, AccountId> for EqCurveAssetsAdapter {
     fn create_asset(pool_id: equilibrium_curve_amm::PoolId) -> Result<AssetId, DispatchError> {
-        let asset = AssetGenerator::generate_asset_for_pool(pool_id, b"lpt".to_vec());
-
-        EqAssets::do_add_asset(
-            asset,
-            EqFixedU128::from(0),
-            FixedI64::from(0),
-            Permill::zero(),
-            Permill::zero(),
-            AssetXcmData::None,
-            LPTokensDebtWeight::get(),
-            LpTokenBuyoutPriority::get(),
-            eq_primitives::asset::AssetType::Lp(eq_primitives::asset::AmmPool::Curve(pool_id)),
-            false,
-            Percent::zero(),
-            Permill::one(),
-            // prices will be set at at OnPoolCreated
-            vec![],
+        // prices will be set at OnPoolCreated
+        EqAssets::create_derivative_asset(
+            b"lpt",
+            pool_id,
+            eq_assets::DerivativeAssetParams::lp_token(
+                eq_primitives::asset::AssetType::Lp(eq_primitives::asset::AmmPool::Curve(pool_id)),
+                LPTokensDebtWeight::get(),
+                LpTokenBuyoutPriority::get(),
+            ),
         )
-        .map_err(|e| e.error)?;
-
-        Ok(asset)
     }
 
     fn mint(asset: AssetId, dest: &AccountId, amount: Balance) -> DispatchResult {
@@ -1447,7 +1626,7 @@ impl equilibrium_curve_amm::traits::Assets<AssetId, Balance, AccountId> for EqCu
             FixedI64::from(0),
             Permill::zero(),
             Permill::zero(),
-            AssetXcmData::None,
+            eq_primitives::asset::AssetXcmData::None,
             Permill::from_rational(2u32, 5u32),
             0,
             eq_primitives::asset::AssetType::Native,
@@ -1472,7 +1651,6 @@ mod curve_utils {
     use eq_utils::fixed::{fixedi64_to_i64f64, i64f64_to_fixedi64};
     use equilibrium_curve_amm::traits::CurveAmm;
     use equilibrium_curve_amm::PoolId;
-    use financial_pallet::FinancialSystemTrait;
     use financial_pallet::PriceLogs;
     use financial_pallet::{
         get_index_range, get_period_id_range, get_range_intersection, PriceLog,
@@ -1535,6 +1713,39 @@ mod curve_utils {
         }
     }
 
+    pub struct CurveLiquidityAdapter;
+
+    impl eq_primitives::CurveLiquidity<AccountId, Balance> for CurveLiquidityAdapter {
+        fn pool_balances(pool_id: PoolId) -> Option<Vec<(AssetId, Balance)>> {
+            super::CurveAmm::pool(pool_id)
+                .map(|pool| pool.assets.into_iter().zip(pool.balances).collect())
+        }
+
+        fn add_liquidity(
+            who: &AccountId,
+            pool_id: PoolId,
+            amounts: Vec<Balance>,
+            min_mint_amount: Balance,
+        ) -> DispatchResult {
+            <super::CurveAmm as CurveAmm>::add_liquidity(who, pool_id, amounts, min_mint_amount)
+                .map(|_| ())
+                .map_err(|e| e.error)
+        }
+
+        fn exchange(
+            who: &AccountId,
+            pool_id: PoolId,
+            i: u32,
+            j: u32,
+            dx: Balance,
+            min_dy: Balance,
+        ) -> DispatchResult {
+            <super::CurveAmm as CurveAmm>::exchange(who, pool_id, i, j, dx, min_dy)
+                .map(|_| ())
+                .map_err(|e| e.error)
+        }
+    }
+
     pub struct OnPoolCreated;
 
     impl equilibrium_curve_amm::traits::OnPoolCreated for OnPoolCreated {
@@ -1607,10 +1818,8 @@ mod curve_utils {
                 },
             );
 
-            // May be that new prices are breaking financial pallet recalculation,
-            // but it is very rare case
-            #[allow(unused_must_use)]
-            let _ = Financial::recalc_inner();
+            // Recalculation is heavy, it is deferred to idle blocks
+            Oracle::enqueue_financial_recalc(&[pool.pool_asset]);
         }
     }
 
@@ -1739,6 +1948,15 @@ parameter_types! {
     pub const PriceStepCount: u32 = 5;
     pub const PenaltyFee: Balance = 10 * ONE_TOKEN;
     pub const DexUnsignedPriority: TransactionPriority = TransactionPriority::min_value();
+    pub const DexCorridorUpdatePeriod: BlockNumber = HOURS;
+    pub const DexOrderDeposit: Balance = ONE_TOKEN;
+    pub const DexMaxBatchOrders: u32 = 100;
+    pub const DexMaxBatchAuctionMarkets: u32 = 10;
+    pub DexStakerDiscounts: Vec<(Balance, Percent)> = vec![
+        (10_000 * ONE_TOKEN, Percent::from_percent(10)),
+        (100_000 * ONE_TOKEN, Percent::from_percent(25)),
+        (1_000_000 * ONE_TOKEN, Percent::from_percent(50)),
+    ];
 }
 
 parameter_types! {
@@ -1791,36 +2009,6 @@ impl pallet_proxy::Config for Runtime {
     type WeightInfo = ();
 }
 
-pub struct AssetGenerator;
-
-impl AssetGenerator {
-    fn generate_asset_for_pool(pool_id: u32, name_base: Vec<u8>) -> eq_primitives::asset::Asset {
-        let zero_in_bytes = '0' as u8;
-        let mut pool_id_bytes: Vec<u8> = Vec::new();
-        let mut pool_id_for_bytes = pool_id;
-        while pool_id_for_bytes > 0 {
-            let byte = TryInto::<u8>::try_into(pool_id_for_bytes % 10).unwrap() + zero_in_bytes;
-            pool_id_bytes.push(byte);
-            pool_id_for_bytes = pool_id_for_bytes / 10;
-        }
-
-        // special case for 0, we want lpt0 instead of just lpt
-        if pool_id == 0 {
-            pool_id_bytes.push(zero_in_bytes);
-        }
-
-        pool_id_bytes.reverse();
-
-        let name: Vec<u8> = name_base
-            .iter()
-            .chain(pool_id_bytes.iter())
-            .map(|item| item.clone())
-            .collect();
-        eq_primitives::asset::Asset::from_bytes(&name)
-            .expect("Asset name cannot has wrong symbols!")
-    }
-}
-
 use eq_primitives::proxy::ProxyType;
 
 //////////////////////////////////////////////////////////////////////////////
@@ -1861,6 +2049,37 @@ pub type LocationToAccountId = (
     eq_xcm::origins::AccountIdConversion<AccountId>,
 );
 
+/// Sibling parachains allowed by governance to trade on behalf of their users
+pub struct TrustedXcmParachains;
+impl Contains<u32> for TrustedXcmParachains {
+    fn contains(para_id: &u32) -> bool {
+        EqDex::trusted_xcm_parachain(para_id)
+    }
+}
+
+/// Derived accounts of trusted sibling parachain users, used only as `Transact` origins,
+/// assets are never withdrawn from or deposited to remote locations through it
+pub type RemoteTraderAccount =
+    eq_xcm::origins::SiblingAccountConversion<AccountId, TrustedXcmParachains>;
+
+/// Calls which trusted sibling parachains may `Transact` on behalf of their users
+pub struct RemoteTraderCalls;
+impl Contains<RuntimeCall> for RemoteTraderCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::EqDex(
+                eq_dex::Call::create_order { .. }
+                    | eq_dex::Call::create_pair_order { .. }
+                    | eq_dex::Call::delete_order_external { .. }
+            ) | RuntimeCall::Subaccounts(
+                eq_subaccounts::Call::transfer_to_subaccount { .. }
+                    | eq_subaccounts::Call::transfer_from_subaccount { .. }
+            )
+        )
+    }
+}
+
 /// Means for transacting assets on this chain or through bridge.
 pub type LocalAssetTransactor = eq_xcm::assets::EqCurrencyAdapter<
     // Our chain's account ID type (we can't get away without mentioning it explicitly):
@@ -1877,6 +2096,8 @@ pub type LocalAssetTransactor = eq_xcm::assets::EqCurrencyAdapter<
     LocationToAccountId,
     // We don't track any teleports.
     (),
+    // Converts deposits worth less than existential deposit or stores them for claim
+    Treasury,
 >;
 
 pub struct TransactIsNotAllowed;
@@ -1893,7 +2114,9 @@ impl<Origin> ConvertOrigin<Origin> for TransactIsNotAllowed {
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
-pub type XcmOriginToTransactDispatchOrigin = TransactIsNotAllowed;
+/// Only users of trusted sibling parachains may `Transact`, as signed derived accounts.
+pub type XcmOriginToTransactDispatchOrigin =
+    SovereignSignedViaLocation<RemoteTraderAccount, RuntimeOrigin>;
 
 parameter_types! {
     // One XCM operation is 200_000_000 weight - litle overcharging estimate.
@@ -1954,7 +2177,7 @@ pub type EqTrader = eq_xcm::assets::EqTrader<
     Oracle,
     TreasuryAccount,
     fee::XcmWeightToFee,
-    FallbackWeightToFee,
+    eq_balances::XcmWeightToFee<Runtime, FallbackWeightToFee>,
 >;
 
 match_types! {
@@ -1977,10 +2200,12 @@ match_types! {
 }
 
 pub type Barrier = (
-    eq_xcm::barrier::AllowReserveAssetDepositedFrom<EqAssets, TrustedOrigins>,
+    eq_xcm::barrier::AllowReserveAssetDepositedFrom<EqAssets, TrustedOrigins, EqBalances>,
     eq_xcm::barrier::AllowReserveTransferAssetsFromAccountId,
     AllowKnownQueryResponses<PolkadotXcm>,
     AllowSubscriptionsFrom<TrustedOrigins>,
+    // Paid `Transact` of users of trusted sibling parachains, fees paid by the sibling
+    eq_xcm::barrier::AllowRemoteTraderTransact<TrustedXcmParachains>,
 );
 
 pub type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
@@ -2009,8 +2234,8 @@ impl Config for XcmConfig {
     type FeeManager = ();
     type MessageExporter = ();
     type UniversalAliases = Nothing;
-    type CallDispatcher = WithOriginFilter<Nothing>;
-    type SafeCallFilter = Nothing;
+    type CallDispatcher = WithOriginFilter<RemoteTraderCalls>;
+    type SafeCallFilter = RemoteTraderCalls;
 }
 
 /// The means for routing XCM messages which are not for local execution into the right message
@@ -2068,7 +2293,7 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
     type VersionWrapper = PolkadotXcm;
     type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
     type ControllerOrigin = EnsureRoot<AccountId>;
-    type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin;
+    type ControllerOriginConverter = TransactIsNotAllowed;
     type PriceForSiblingDelivery = ();
     type WeightInfo = ();
 }
@@ -2078,11 +2303,17 @@ pub type CollatorSelectionUpdateOrigin = EnsureRoot<AccountId>;
 
 parameter_types! {
     pub const MigrationsPerBlock: u16 = 2_000;
+    /// Storage of removed pallets and storage items, e.g.
+    /// `StorageCleanup::new("EqOldPallet", "Items")`
+    pub StorageCleanups: Vec<eq_migration::StorageCleanup> = vec![];
 }
 
 impl eq_migration::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type MigrationsPerBlock = MigrationsPerBlock;
+    type SteppedMigration = EqBalances;
+    type StorageCleanups = StorageCleanups;
+    type LiveStorage = AllPalletsWithSystem;
     type WeightInfo = eq_migration::weights::EqWeight<Runtime>;
 }
 
@@ -2111,16 +2342,28 @@ impl eq_oracle::Config for Runtime {
     type UnsignedLifetimeInBlocks = UnsignedLifetimeInBlocks;
     type LendingAssetRemoval = EqLending;
     type EqDotPrice = EqWrappedDot;
+    type PriceCircuitBreakerOrigin = EnsureRootOrTwoThirdsTechnicalCommitteeOrParameterTrack;
+    type SuspectPricePeriod = SuspectPricePeriod;
+    type SuspectPriceConfirmations = SuspectPriceConfirmations;
+    type FeederKeyOverlap = FeederKeyOverlap;
+    type MaxFeederKeys = MaxFeederKeys;
+    type LpPriceAdapter = ();
+    type MaxFinancialRecalcFailures = MaxFinancialRecalcFailures;
+    type MaxPricesPerBatch = MaxPricesPerBatch;
+    type MaxPriceSubmissionsPerBlock = MaxPriceSubmissionsPerBlock;
 }
 
 parameter_types! {
     pub const BailsmenUnsignedPriority: TransactionPriority = TransactionPriority::min_value();
     pub const MaxBailsmenToDistribute: u32 = 20;
     pub const QueueLengthWeightConstant: u32 = 5;
+    pub const BailsmanRedistributionWeightBudget: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(20);
+    pub const MaxPriorityRedistributionsPerBlock: u32 = 100;
 }
 
 impl eq_bailsman::Config for Runtime {
     type PalletId = BailsmanModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
     type PriceGetter = Oracle;
     type UnixTime = EqRate;
     type Balance = eq_primitives::balance::Balance;
@@ -2140,17 +2383,27 @@ impl eq_bailsman::Config for Runtime {
     type UnsignedPriority = BailsmenUnsignedPriority;
     type MaxBailsmenToDistribute = MaxBailsmenToDistribute;
     type QueueLengthWeightConstant = QueueLengthWeightConstant;
+    type RedistributionWeightBudget = BailsmanRedistributionWeightBudget;
+    type MaxPriorityRedistributionsPerBlock = MaxPriorityRedistributionsPerBlock;
 }
 
 impl eq_dex::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type DeleteOrderOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
-    type UpdateAssetCorridorOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type UpdateAssetCorridorOrigin = EnsureRootOrTwoThirdsTechnicalCommitteeOrParameterTrack;
     type PriceStepCount = PriceStepCount;
     type PenaltyFee = PenaltyFee;
     type DexUnsignedPriority = DexUnsignedPriority;
+    type CorridorUpdatePeriod = DexCorridorUpdatePeriod;
     type WeightInfo = weights::pallet_dex::WeightInfo<Runtime>;
+    type RiskNotifier = EqMarginCall;
     type ValidatorOffchainBatcher = eq_rate::Pallet<Runtime>;
+    type StakedBalance = ();
+    type StakerDiscounts = DexStakerDiscounts;
+    type OrderDeposit = DexOrderDeposit;
+    type TreasuryAccounting = Treasury;
+    type MaxBatchOrders = DexMaxBatchOrders;
+    type MaxBatchAuctionMarkets = DexMaxBatchAuctionMarkets;
 }
 
 use eq_xcm::relay_interface::{call::RelayChainCallBuilder, config::RelayRuntime};
@@ -2162,6 +2415,11 @@ parameter_types! {
     pub EqDotWithdrawFee: Permill = Permill::from_rational(989_409_u32, 1_000_000_u32); // (1/(1+14.9%)^(28/365.25) = 0.98940904738)
     pub const MinStakingDeposit: Balance = 1 * ONE_TOKEN;
     pub const WrappedDotPalletId: PalletId = PalletId(*b"eq/wrdot");
+    pub const RelayAsset: Asset = eq_primitives::asset::DOT;
+    pub const WrappedRelayAsset: Asset = eq_primitives::asset::EQDOT;
+    pub const RelayDecimals: u8 = eq_wrapped_dot::DOT_DECIMALS;
+    pub const RelayBondingDuration: u32 = 28;
+    pub const RelayEraLength: BlockNumber = DAYS;
 }
 
 impl eq_wrapped_dot::Config for Runtime {
@@ -2175,10 +2433,15 @@ impl eq_wrapped_dot::Config for Runtime {
     type RelayChainCallBuilder = RelayChainCallBuilder<RelayRuntime, ParachainInfo>;
     type XcmRouter = XcmRouter;
     type ParachainId = ParachainInfo;
+    type RelayAsset = RelayAsset;
+    type WrappedAsset = WrappedRelayAsset;
+    type RelayDecimals = RelayDecimals;
     type PriceGetter = Oracle;
     type EqCurrency = EqBalances;
     type WithdrawFee = EqDotWithdrawFee;
     type PalletId = WrappedDotPalletId;
+    type BondingDuration = RelayBondingDuration;
+    type EraLength = RelayEraLength;
     type WeightInfo = weights::pallet_wrapped_dot::WeightInfo<Runtime>;
 }
 
@@ -2306,6 +2569,46 @@ impl pallet_membership::Config<TechnicalCommitteeMembershipInstance> for Runtime
     type WeightInfo = weights::pallet_membership::WeightInfo<Runtime>;
 }
 
+// Parameter track setup: short motions for parameter tweaks, see `EnsureMoreThanHalfParameterTrack`
+parameter_types! {
+    pub const ParameterTrackMotionDuration: BlockNumber = if cfg!(feature = "production") {
+        6 * HOURS
+    } else {
+        10 * MINUTES
+    };
+    pub const ParameterTrackMaxProposals: u32 = 20;
+    pub const ParameterTrackMaxMembers: u32 = 20;
+}
+
+pub type ParameterTrackInstance = pallet_collective::Instance3;
+impl pallet_collective::Config<ParameterTrackInstance> for Runtime {
+    type RuntimeOrigin = RuntimeOrigin;
+    type Proposal = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type MotionDuration = ParameterTrackMotionDuration;
+    type MaxProposals = ParameterTrackMaxProposals;
+    type MaxMembers = ParameterTrackMaxMembers;
+    type DefaultVote = pallet_collective::PrimeDefaultVote;
+    type SetMembersOrigin = EnsureRootOrTwoThirdsCouncil;
+    type MaxProposalWeight = MaxProposalWeight;
+    type WeightInfo = weights::pallet_collective::WeightInfo<Runtime>;
+}
+
+pub type ParameterTrackMembershipOrigin = EnsureRootOrTwoThirdsCouncil;
+pub type ParameterTrackMembershipInstance = pallet_membership::Instance3;
+impl pallet_membership::Config<ParameterTrackMembershipInstance> for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxMembers = ParameterTrackMaxMembers;
+    type AddOrigin = ParameterTrackMembershipOrigin;
+    type RemoveOrigin = ParameterTrackMembershipOrigin;
+    type SwapOrigin = ParameterTrackMembershipOrigin;
+    type ResetOrigin = ParameterTrackMembershipOrigin;
+    type PrimeOrigin = ParameterTrackMembershipOrigin;
+    type MembershipInitialized = ParameterTrack;
+    type MembershipChanged = ParameterTrack;
+    type WeightInfo = weights::pallet_membership::WeightInfo<Runtime>;
+}
+
 parameter_types! {
     pub const LaunchPeriod: BlockNumber = if cfg!(feature = "production") {
         1 * WEEKS
@@ -2345,6 +2648,10 @@ parameter_types! {
 impl pallet_democracy::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
 
+    // democracy lock is not added on top of long enough stakes
+    #[cfg(not(feature = "production"))]
+    type Currency = eq_staking::democracy::StakeLockAdapter<Runtime, BasicCurrency>;
+    #[cfg(feature = "production")]
     type Currency = BasicCurrency;
     type PalletsOrigin = OriginCaller;
     type Scheduler = Scheduler;
@@ -2384,6 +2691,56 @@ impl pallet_democracy::Config for Runtime {
     type WeightInfo = weights::pallet_democracy::WeightInfo<Runtime>;
 }
 
+#[cfg(not(feature = "production"))]
+/// Votes in `pallet_democracy` with balances staked in `eq_staking`
+pub struct DemocracyConvictionVoting;
+
+#[cfg(not(feature = "production"))]
+impl DemocracyConvictionVoting {
+    fn blocks_to_secs(blocks: BlockNumber) -> u64 {
+        (blocks as u64).saturating_mul(MILLISECS_PER_BLOCK / 1000)
+    }
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_primitives::ConvictionVoting<AccountId, Balance> for DemocracyConvictionVoting {
+    fn lock_period_secs() -> u64 {
+        Self::blocks_to_secs(VoteLockingPeriod::get())
+    }
+
+    fn max_lock_secs() -> u64 {
+        let max_lock = VoteLockingPeriod::get()
+            .saturating_mul(pallet_democracy::Conviction::Locked6x.lock_periods());
+        Self::blocks_to_secs(VotingPeriod::get().saturating_add(max_lock))
+    }
+
+    fn referendum_remaining_secs(ref_index: u32) -> Option<u64> {
+        let status = Democracy::referendum_status(ref_index).ok()?;
+        Some(Self::blocks_to_secs(
+            status.end.saturating_sub(System::block_number()),
+        ))
+    }
+
+    fn vote(
+        who: &AccountId,
+        ref_index: u32,
+        aye: bool,
+        balance: Balance,
+        conviction: u8,
+    ) -> DispatchResult {
+        let conviction = pallet_democracy::Conviction::try_from(conviction)
+            .map_err(|_| DispatchError::Other("Invalid conviction"))?;
+        Democracy::vote(
+            RuntimeOrigin::signed(who.clone()),
+            ref_index,
+            pallet_democracy::AccountVote::Standard {
+                vote: pallet_democracy::Vote { aye, conviction },
+                balance,
+            },
+        )
+    }
+}
+
 impl eq_crowdloan_dots::Config for Runtime {
     type AllowCrowdloanOrigin = EnsureRootOrHalfTechnicalCommittee;
     type Balance = Balance;
@@ -2421,86 +2778,524 @@ impl q_swap::Config for Runtime {
     type WeightInfo = ();
 }
 
-construct_runtime!(
-    pub enum Runtime where
-        Block = Block,
-        NodeBlock = common_runtime::opaque::Block,
-        UncheckedExtrinsic = UncheckedExtrinsic
-    {
-        System: system::{Pallet, Call, Config, Storage, Event<T>} = 0,
-        ParachainSystem: cumulus_pallet_parachain_system::{
-            Pallet, Call, Config, Storage, Inherent, Event<T>, ValidateUnsigned,
-        } = 1,
-        Utility: pallet_utility::{Pallet, Call, Event} = 2,
-        Timestamp: timestamp::{Pallet, Call, Storage, Inherent} = 4,
-        ParachainInfo: parachain_info::{Pallet, Storage, Config} = 5,
-        EqSessionManager: eq_session_manager::{Pallet, Call, Storage, Event<T>, Config<T>,} = 6,
+#[cfg(feature = "weight-telemetry")]
+parameter_types! {
+    pub const MaxWeightSamples: u32 = 32;
+}
 
-        // Collator support. the order of these 4 are important and shall not change.
-        Authorship: authorship::{Pallet, Storage} = 7,
-        Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>} = 8,
-        Aura: aura::{Pallet, Config<T>} = 9,
-        AuraExt: cumulus_pallet_aura_ext::{Pallet, Storage, Config} = 10,
+#[cfg(feature = "weight-telemetry")]
+impl eq_weight_telemetry::Config for Runtime {
+    type MaxSamples = MaxWeightSamples;
+}
 
-        EqAssets: eq_assets::{Pallet, Call, Config<T>, Storage, Event} = 11, // Assets genesis must be built first
-        Oracle: eq_oracle::{Pallet, Call, Storage, Event<T>, Config, ValidateUnsigned} = 12,
-        EqTreasury: eq_distribution::<Instance5>::{Pallet, Call, Storage, Config} = 13,
-        Treasury: eq_treasury::{Pallet, Call, Storage, Config, Event<T>} = 14,
-        EqBalances: eq_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 15,
+#[cfg(not(feature = "production"))]
+/// Registers transfer-then-Transact queries in PolkadotXcm, the runtime response handler
+pub struct XcmOpsQueryHandler;
+#[cfg(not(feature = "production"))]
+impl eq_xcm_ops::XcmQueryHandler<Runtime> for XcmOpsQueryHandler {
+    fn new_notify_query(
+        responder: MultiLocation,
+        notify: eq_xcm_ops::Call<Runtime>,
+        timeout: BlockNumber,
+        match_querier: MultiLocation,
+    ) -> xcm::v3::QueryId {
+        PolkadotXcm::new_notify_query(
+            responder,
+            RuntimeCall::EqXcmOps(notify),
+            timeout,
+            match_querier,
+        )
+    }
+}
 
-        TransactionPayment: transaction_payment::{Pallet, Storage, Event<T>} = 16,
-        // Sudo: sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 17,
-        Bailsman: eq_bailsman::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned} = 18,
-        Whitelists: eq_whitelists::{Pallet, Call, Storage, Event<T>, Config<T>,} = 19,
-        EqRate: eq_rate::{Pallet, Storage, Call, ValidateUnsigned} = 20,
-        Republic: eq_distribution::<Instance2>::{Pallet, Call, Storage, Config} = 21,
-        EqInvestors: eq_distribution::<Instance3>::{Pallet, Call, Storage, Config} = 22,
+#[cfg(not(feature = "production"))]
+pub struct XcmOpsVersionManager;
+#[cfg(not(feature = "production"))]
+impl eq_xcm_ops::XcmVersionManager for XcmOpsVersionManager {
+    fn subscribe_version_notify(location: MultiLocation) -> DispatchResult {
+        PolkadotXcm::force_subscribe_version_notify(
+            RuntimeOrigin::root(),
+            Box::new(location.into()),
+        )
+    }
 
-        EqLiquidityFarming: eq_distribution::<Instance4>::{Pallet, Call, Storage, Config} = 23,
+    fn known_version(location: &MultiLocation) -> Option<eq_xcm_ops::XcmVersion> {
+        use xcm::GetVersion;
+        PolkadotXcm::get_version_for(location)
+    }
+}
 
-        Vesting: eq_vesting::<Instance1>::{Pallet, Call, Storage, Event<T, Instance1>, Config<T, Instance1>} = 24,
-        Claims: eq_claim::{Pallet, Call, Storage, Event<T>, Config<T>, ValidateUnsigned} = 26,
-        EqAggregates: eq_aggregates::{Pallet, Storage} = 27,
-        Subaccounts: eq_subaccounts::{Pallet, Call, Storage, Event<T>, Config<T>} = 28,
-        Financial: financial_pallet::{Pallet, Call, Storage, Config<T>, Event<T>} = 29,
-        ChainBridge: chainbridge::{Pallet, Call, Storage, Event<T>, Config<T>} = 30,
-        EqBridge: eq_bridge::{Pallet, Call, Storage, Event<T>, Config<T>} = 31,
-        EqMultisigSudo: eq_multisig_sudo::{Pallet, Call, Storage, Config<T>, Event<T>} = 32,
-        EqMarginCall: eq_margin_call::{Pallet, Call, Storage, Event<T>} = 33,
-        EqDex: eq_dex::{Pallet, Call, Storage, Event<T>, Config, ValidateUnsigned} = 34,
-        EqLending: eq_lending::{Pallet, Call, Storage, Event<T>, Config<T>} = 35,
-        EqLockdrop: eq_lockdrop::{Pallet, Call, Storage, Event<T>, ValidateUnsigned, Config<T>} = 36,
-        Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 37,
-        Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 38,
-        EqMarketMaker: eq_market_maker::{Pallet, Call, Storage, Event<T>} = 39,
-        Migration: eq_migration::{Pallet, Call, Storage, Event<T>} = 41,
-        CurveAmm: equilibrium_curve_amm::{Pallet, Call, Storage, Event<T>} = 42,
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const XcmOpsQueryTimeout: BlockNumber = 100;
+    pub const MaxTransactCallSize: u32 = 1024;
+    pub const MaxXcmPartners: u32 = 32;
+}
 
-        // XCM helpers.
-        PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Storage, Origin, Config} = 43,
-        DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 44,
-        XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>} = 45,
+#[cfg(not(feature = "production"))]
+impl eq_xcm_ops::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqBalances;
+    type XcmRouter = XcmRouter;
+    type XcmQueryHandler = XcmOpsQueryHandler;
+    type ResponseOrigin = pallet_xcm::EnsureResponse<frame_support::traits::Everything>;
+    type UniversalLocation = UniversalLocation;
+    type QueryTimeout = XcmOpsQueryTimeout;
+    type MaxTransactCallSize = MaxTransactCallSize;
+    type SovereignManagementOrigin = EnsureRootOrTwoThirdsCouncil;
+    type TreasuryAccount = TreasuryAccount;
+    type PartnerManagementOrigin = EnsureRootOrTwoThirdsCouncil;
+    type XcmVersionManager = XcmOpsVersionManager;
+    type MaxXcmPartners = MaxXcmPartners;
+    type WeightInfo = weights::pallet_xcm_ops::WeightInfo<Runtime>;
+}
 
-        EqWrappedDot: eq_wrapped_dot::{Pallet, Call, Storage, Config} = 46,
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const LpGaugePalletId: PalletId = PalletId(*b"eq/lpgau");
+    pub const LpGaugeMaxBoost: Permill = Permill::from_percent(50);
+}
 
-        // Governance
-        Preimage: pallet_preimage = 60,
-        Scheduler: pallet_scheduler = 61,
+#[cfg(not(feature = "production"))]
+impl eq_lp_gauge::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = eq_balances::Pallet<Runtime>;
+    type AssetGetter = eq_assets::Pallet<Runtime>;
+    type StakedBalance = ();
+    type GaugeManagementOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type RewardsAccount = TreasuryAccount;
+    type PalletId = LpGaugePalletId;
+    type MaxBoost = LpGaugeMaxBoost;
+    type WeightInfo = weights::pallet_lp_gauge::WeightInfo<Runtime>;
+}
 
-        Council: pallet_collective::<Instance1> = 62,
-        CouncilMembership: pallet_membership::<Instance1> = 63,
-        TechnicalCommittee: pallet_collective::<Instance2> = 64,
-        TechnicalCommitteeMembership: pallet_membership::<Instance2> = 65,
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const XcmStreamMinPeriod: BlockNumber = HOURS;
+    pub const MaxXcmStreamsPerBlock: u32 = 10;
+    pub const MaxXcmStreamsPerAccount: u32 = 16;
+    pub const XcmStreamRetryDelay: BlockNumber = 10 * MINUTES;
+    pub const XcmStreamMaxFailures: u32 = 5;
+}
 
-        Democracy: pallet_democracy = 66,
+#[cfg(not(feature = "production"))]
+impl eq_xcm_streams::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = eq_balances::Pallet<Runtime>;
+    type MinPeriod = XcmStreamMinPeriod;
+    type MaxStreamsPerBlock = MaxXcmStreamsPerBlock;
+    type MaxStreamsPerAccount = MaxXcmStreamsPerAccount;
+    type RetryDelay = XcmStreamRetryDelay;
+    type MaxFailures = XcmStreamMaxFailures;
+    type WeightInfo = weights::pallet_xcm_streams::WeightInfo<Runtime>;
+}
 
-        EqCrowdLoanDots: eq_crowdloan_dots::{ Pallet, Call, Storage } = 68,
-        QSwap: q_swap::{ Pallet, Call, Event<T>, Storage } = 69,
-        Vesting2: eq_vesting::<Instance2>::{Pallet, Call, Storage, Event<T, Instance2>, Config<T, Instance2>} = 70,
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const EqdSavingsModuleId: PalletId = PalletId(*b"eq/savng");
+    pub const MinEqdSavingsDeposit: Balance = 10 * ONE_TOKEN;
+}
+
+#[cfg(not(feature = "production"))]
+impl eqd_savings::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = eq_balances::Pallet<Runtime>;
+    type PriceGetter = Oracle;
+    type UnixTime = EqRate;
+    type Aggregates = EqAggregates;
+    type PalletId = EqdSavingsModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
+    type RateOrigin = EnsureRootOrTwoThirdsCouncilOrParameterTrack;
+    type MinDeposit = MinEqdSavingsDeposit;
+    type WeightInfo = weights::pallet_eqd_savings::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const FaucetDripPeriod: BlockNumber = DAYS;
+    pub const MaxFaucetDripsPerBlock: u32 = 10;
+    pub const FaucetUnsignedPriority: TransactionPriority = TransactionPriority::min_value();
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_faucet::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = eq_balances::Pallet<Runtime>;
+    type FaucetOrigin = EnsureRoot<AccountId>;
+    type DripPeriod = FaucetDripPeriod;
+    type MaxDripsPerBlock = MaxFaucetDripsPerBlock;
+    type UnsignedPriority = FaucetUnsignedPriority;
+    type WeightInfo = weights::pallet_faucet::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const AccountMigrationDelay: BlockNumber = DAYS;
+}
+
+#[cfg(not(feature = "production"))]
+/// Rejects migration of accounts with state of pallets not moving it to the new account
+pub struct UnmovedStateCheck;
+
+#[cfg(not(feature = "production"))]
+impl eq_primitives::AccountMigration<AccountId> for UnmovedStateCheck {
+    fn can_migrate(from: &AccountId, _to: &AccountId) -> DispatchResult {
+        let voting = pallet_democracy::VotingOf::<Runtime>::get(from);
+        frame_support::ensure!(
+            voting.locked_balance() == 0
+                && matches!(
+                    voting,
+                    pallet_democracy::Voting::Direct { ref votes, .. } if votes.is_empty()
+                ),
+            DispatchError::Other("Account has democracy votes")
+        );
+        frame_support::ensure!(
+            EqLockdrop::locks(from) == 0,
+            DispatchError::Other("Account has lockdrop lock")
+        );
+        frame_support::ensure!(
+            EqdSavings::shares(from) == 0,
+            DispatchError::Other("Account has savings deposit")
+        );
+        frame_support::ensure!(
+            EqLending::lender_positions(from).is_empty(),
+            DispatchError::Other("Account has lending deposits")
+        );
+        frame_support::ensure!(
+            eq_lp_gauge::Gauges::<Runtime>::iter_keys()
+                .all(|lp_asset| !eq_lp_gauge::Positions::<Runtime>::contains_key(lp_asset, from)),
+            DispatchError::Other("Account has gauge stakes")
+        );
+
+        Ok(())
+    }
+
+    fn migrate(_from: &AccountId, _to: &AccountId) -> DispatchResult {
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_account_migration::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Signature = Signature;
+    type Signer = <Signature as traits::Verify>::Signer;
+    type Migrations = (
+        UnmovedStateCheck,
+        EqBalances,
+        Vesting,
+        Vesting2,
+        Vesting3,
+        Vesting4,
+        Subaccounts,
+        EqStaking,
+    );
+    type MigrationDelay = AccountMigrationDelay;
+    type WeightInfo = weights::pallet_account_migration::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const WatchtowerBond: Balance = 1_000 * ONE_TOKEN;
+    pub const WatchtowerAlertBounty: Balance = 100 * ONE_TOKEN;
+    pub const WatchtowerFalseAlertSlash: Balance = 250 * ONE_TOKEN;
+    pub const WatchtowerAlertCooldown: BlockNumber = HOURS;
+    pub MinBailsmanCoverage: EqFixedU128 = EqFixedU128::saturating_from_rational(11, 10);
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_watchtower::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqBalances;
+    type AssetGetter = EqAssets;
+    type PriceGetter = Oracle;
+    type BailsmanCoverage = Bailsman;
+    type TreasuryModuleId = TreasuryModuleId;
+    type WatchtowerBond = WatchtowerBond;
+    type AlertBounty = WatchtowerAlertBounty;
+    type FalseAlertSlash = WatchtowerFalseAlertSlash;
+    type AlertCooldown = WatchtowerAlertCooldown;
+    type MinBailsmanCoverage = MinBailsmanCoverage;
+    type WeightInfo = weights::pallet_watchtower::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const PerpsModuleId: PalletId = PalletId(*b"eq/perps");
+    pub const PerpsFundingPeriod: u64 = 60 * 60;
+    pub PerpsMaxFundingRate: FixedI64 = FixedI64::saturating_from_rational(75, 10_000);
+    pub PerpsMaxPriceDeviation: FixedI64 = FixedI64::saturating_from_rational(2, 100);
+    pub PerpsTradeSpread: FixedI64 = FixedI64::saturating_from_rational(1, 1_000);
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_perps::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqBalances;
+    type PriceGetter = Oracle;
+    type MarkPriceGetter = EqDex;
+    type SubaccountsManager = Subaccounts;
+    type MarginCallManager = EqMarginCall;
+    type UnixTime = EqRate;
+    type PerpsModuleId = PerpsModuleId;
+    type FundingPeriod = PerpsFundingPeriod;
+    type MaxFundingRate = PerpsMaxFundingRate;
+    type MaxPriceDeviation = PerpsMaxPriceDeviation;
+    type TradeSpread = PerpsTradeSpread;
+    type WeightInfo = weights::pallet_perps::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const StakingRewardsModuleId: PalletId = PalletId(*b"eq/stkrw");
+    pub const StakingCustomRewardsModuleId: PalletId = PalletId(*b"eq/stkcr");
+    pub const MaxStakesCount: u32 = 10;
+    pub const StakingRewardsLockPeriod: eq_staking::StakePeriod = eq_staking::StakePeriod::Twelve;
+    pub const MaxRewardExternalIdsCount: u32 = 1000;
+    /// Stakes are not removed in `on_initialize`
+    pub const StakingAccountsPerBlock: u32 = 0;
+    pub const StakingEmissionEraDuration: u64 = 7 * 24 * 60 * 60;
+}
+
+#[cfg(not(feature = "production"))]
+pub struct StakingRewardsAccount;
+#[cfg(not(feature = "production"))]
+impl Get<AccountId> for StakingRewardsAccount {
+    fn get() -> AccountId {
+        StakingRewardsModuleId::get().into_account_truncating()
+    }
+}
+
+#[cfg(not(feature = "production"))]
+pub struct StakingCustomRewardsAccount;
+#[cfg(not(feature = "production"))]
+impl Get<AccountId> for StakingCustomRewardsAccount {
+    fn get() -> AccountId {
+        StakingCustomRewardsModuleId::get().into_account_truncating()
+    }
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_staking::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqBalances;
+    type BalanceGetter = EqBalances;
+    type LockGetter = EqBalances;
+    type UnixTime = EqRate;
+    type MaxStakesCount = MaxStakesCount;
+    type RewardManagementOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type LiquidityAccount = StakingRewardsAccount;
+    type LiquidityAccountCustom = StakingCustomRewardsAccount;
+    type RewardsLockPeriod = StakingRewardsLockPeriod;
+    type WeightInfo = weights::pallet_staking::WeightInfo<Runtime>;
+    type MaxRewardExternalIdsCount = MaxRewardExternalIdsCount;
+    type AccountsPerBlock = StakingAccountsPerBlock;
+    type ConvictionVoting = DemocracyConvictionVoting;
+    type TreasuryAccount = TreasuryAccount;
+    type EmissionEraDuration = StakingEmissionEraDuration;
+}
+
+#[cfg(not(feature = "production"))]
+pub struct EqXdotAssetsAdapter;
+#[cfg(not(feature = "production"))]
+impl eq_xdot_pool::traits::Assets<AssetId, Balance, AccountId> for EqXdotAssetsAdapter {
+    fn create_lp_asset(pool_id: eq_xdot_pool::PoolId) -> Result<AssetId, DispatchError> {
+        EqAssets::create_derivative_asset(
+            b"xlp",
+            pool_id,
+            eq_assets::DerivativeAssetParams::lp_token(
+                eq_primitives::asset::AssetType::Lp(eq_primitives::asset::AmmPool::Yield(pool_id)),
+                LPTokensDebtWeight::get(),
+                LpTokenBuyoutPriority::get(),
+            ),
+        )
+    }
+
+    fn mint(asset: AssetId, dest: &AccountId, amount: Balance) -> DispatchResult {
+        EqBalances::deposit_creating(dest, asset, amount, true, None)
+    }
+
+    fn burn(asset: AssetId, dest: &AccountId, amount: Balance) -> DispatchResult {
+        EqBalances::withdraw(
+            dest,
+            asset,
+            amount,
+            true,
+            None,
+            WithdrawReasons::empty(),
+            ExistenceRequirement::AllowDeath,
+        )
+    }
+
+    fn transfer(
+        asset: AssetId,
+        source: &AccountId,
+        dest: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        EqBalances::currency_transfer(
+            source,
+            dest,
+            asset,
+            amount,
+            ExistenceRequirement::AllowDeath,
+            TransferReason::Common,
+            true,
+        )
+    }
+
+    fn balance(asset: AssetId, who: &AccountId) -> Balance {
+        EqBalances::free_balance(who, asset)
+    }
+
+    fn total_issuance(asset: AssetId) -> Balance {
+        EqAggregates::get_total(UserGroup::Balances, asset).collateral
+    }
+}
+
+#[cfg(not(feature = "production"))]
+pub struct XdotNumberPriceConvert;
+#[cfg(not(feature = "production"))]
+impl Convert<XdotNumber, FixedI64> for XdotNumberPriceConvert {
+    fn convert(n: XdotNumber) -> FixedI64 {
+        eq_utils::fixed::i64f64_to_fixedi64(n)
+    }
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_xdot_pool::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type PoolsManagementOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+    type FixedNumberBits = i128;
+    type XdotNumber = XdotNumber;
+    type NumberConvert = eq_xdot_pool::yield_math::YieldConvert;
+    type BalanceConvert = eq_primitives::xdot_pool::XdotBalanceConvert;
+    type Assets = EqXdotAssetsAdapter;
+    type AssetId = AssetId;
+    type YieldMath = eq_xdot_pool::yield_math::YieldMath<
+        XdotNumber,
+        eq_xdot_pool::yield_math::YieldConvert,
+    >;
+    type PriceNumber = FixedI64;
+    type PriceConvert = XdotNumberPriceConvert;
+    type FixedNumberConvert = eq_primitives::xdot_pool::XdotFixedNumberConvert;
+    type OnPoolInitialized = ();
+    type AssetChecker = ();
+    type WeightInfo = weights::pallet_xdot_pool::WeightInfo<Runtime>;
+}
+
+construct_runtime!(
+    pub enum Runtime where
+        Block = Block,
+        NodeBlock = common_runtime::opaque::Block,
+        UncheckedExtrinsic = UncheckedExtrinsic
+    {
+        System: system::{Pallet, Call, Config, Storage, Event<T>} = 0,
+        ParachainSystem: cumulus_pallet_parachain_system::{
+            Pallet, Call, Config, Storage, Inherent, Event<T>, ValidateUnsigned,
+        } = 1,
+        Utility: pallet_utility::{Pallet, Call, Event} = 2,
+        Timestamp: timestamp::{Pallet, Call, Storage, Inherent} = 4,
+        ParachainInfo: parachain_info::{Pallet, Storage, Config} = 5,
+        EqSessionManager: eq_session_manager::{Pallet, Call, Storage, Event<T>, Config<T>,} = 6,
+
+        // Collator support. the order of these 4 are important and shall not change.
+        Authorship: authorship::{Pallet, Storage} = 7,
+        Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>} = 8,
+        Aura: aura::{Pallet, Config<T>} = 9,
+        AuraExt: cumulus_pallet_aura_ext::{Pallet, Storage, Config} = 10,
+
+        EqAssets: eq_assets::{Pallet, Call, Config<T>, Storage, Event} = 11, // Assets genesis must be built first
+        Oracle: eq_oracle::{Pallet, Call, Storage, Event<T>, Config, ValidateUnsigned} = 12,
+        EqTreasury: eq_distribution::<Instance5>::{Pallet, Call, Storage, Config, Event<T>} = 13,
+        Treasury: eq_treasury::{Pallet, Call, Storage, Config, Event<T>} = 14,
+        EqBalances: eq_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 15,
+
+        TransactionPayment: transaction_payment::{Pallet, Storage, Event<T>} = 16,
+        // Sudo: sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 17,
+        Bailsman: eq_bailsman::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned} = 18,
+        Whitelists: eq_whitelists::{Pallet, Call, Storage, Event<T>, Config<T>,} = 19,
+        EqRate: eq_rate::{Pallet, Storage, Call, ValidateUnsigned} = 20,
+        Republic: eq_distribution::<Instance2>::{Pallet, Call, Storage, Config, Event<T>} = 21,
+        EqInvestors: eq_distribution::<Instance3>::{Pallet, Call, Storage, Config, Event<T>} = 22,
+
+        EqLiquidityFarming: eq_distribution::<Instance4>::{Pallet, Call, Storage, Config, Event<T>} = 23,
+
+        Vesting: eq_vesting::<Instance1>::{Pallet, Call, Storage, Event<T, Instance1>, Config<T, Instance1>} = 24,
+        Claims: eq_claim::{Pallet, Call, Storage, Event<T>, Config<T>, ValidateUnsigned} = 26,
+        EqAggregates: eq_aggregates::{Pallet, Storage} = 27,
+        Subaccounts: eq_subaccounts::{Pallet, Call, Storage, Event<T>, Config<T>} = 28,
+        Financial: financial_pallet::{Pallet, Call, Storage, Config<T>, Event<T>} = 29,
+        ChainBridge: chainbridge::{Pallet, Call, Storage, Event<T>, Config<T>} = 30,
+        EqBridge: eq_bridge::{Pallet, Call, Storage, Event<T>, Config<T>} = 31,
+        EqMultisigSudo: eq_multisig_sudo::{Pallet, Call, Storage, Config<T>, Event<T>} = 32,
+        EqMarginCall: eq_margin_call::{Pallet, Call, Storage, Event<T>} = 33,
+        EqDex: eq_dex::{Pallet, Call, Storage, Event<T>, Config, ValidateUnsigned} = 34,
+        EqLending: eq_lending::{Pallet, Call, Storage, Event<T>, Config<T>} = 35,
+        EqLockdrop: eq_lockdrop::{Pallet, Call, Storage, Event<T>, ValidateUnsigned, Config<T>} = 36,
+        Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 37,
+        Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 38,
+        EqMarketMaker: eq_market_maker::{Pallet, Call, Storage, Event<T>} = 39,
+        Migration: eq_migration::{Pallet, Call, Storage, Event<T>} = 41,
+        CurveAmm: equilibrium_curve_amm::{Pallet, Call, Storage, Event<T>} = 42,
+
+        // XCM helpers.
+        PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Storage, Origin, Config} = 43,
+        DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 44,
+        XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>} = 45,
+
+        EqWrappedDot: eq_wrapped_dot::{Pallet, Call, Storage, Config} = 46,
+
+        // Governance
+        Preimage: pallet_preimage = 60,
+        Scheduler: pallet_scheduler = 61,
+
+        Council: pallet_collective::<Instance1> = 62,
+        CouncilMembership: pallet_membership::<Instance1> = 63,
+        TechnicalCommittee: pallet_collective::<Instance2> = 64,
+        TechnicalCommitteeMembership: pallet_membership::<Instance2> = 65,
+
+        Democracy: pallet_democracy = 66,
+
+        EqCrowdLoanDots: eq_crowdloan_dots::{ Pallet, Call, Storage } = 68,
+        QSwap: q_swap::{ Pallet, Call, Event<T>, Storage } = 69,
+        Vesting2: eq_vesting::<Instance2>::{Pallet, Call, Storage, Event<T, Instance2>, Config<T, Instance2>} = 70,
         Vesting3: eq_vesting::<Instance3>::{Pallet, Call, Storage, Event<T, Instance3>, Config<T, Instance3>} = 71,
         Vesting4: eq_vesting::<Instance4>::{Pallet, Call, Storage, Event<T, Instance4>, Config<T, Instance4>} = 72,
-        CrowdloanDistribution: eq_distribution::<Instance6>::{Pallet, Call, Storage, Config} = 73,
-        StabilizationPool: eq_distribution::<Instance7>::{Pallet, Call, Storage, Config} = 74,
+        CrowdloanDistribution: eq_distribution::<Instance6>::{Pallet, Call, Storage, Config, Event<T>} = 73,
+        StabilizationPool: eq_distribution::<Instance7>::{Pallet, Call, Storage, Config, Event<T>} = 74,
+        #[cfg(feature = "weight-telemetry")]
+        EqWeightTelemetry: eq_weight_telemetry::{Pallet, Storage} = 75,
+        #[cfg(not(feature = "production"))]
+        EqXcmOps: eq_xcm_ops::{Pallet, Call, Storage, Event<T>} = 76,
+        #[cfg(not(feature = "production"))]
+        EqLpGauge: eq_lp_gauge::{Pallet, Call, Storage, Event<T>} = 77,
+        #[cfg(not(feature = "production"))]
+        EqXcmStreams: eq_xcm_streams::{Pallet, Call, Storage, Event<T>} = 78,
+        #[cfg(not(feature = "production"))]
+        EqdSavings: eqd_savings::{Pallet, Call, Storage, Event<T>} = 79,
+        ParameterTrack: pallet_collective::<Instance3> = 80,
+        ParameterTrackMembership: pallet_membership::<Instance3> = 81,
+        #[cfg(not(feature = "production"))]
+        EqFaucet: eq_faucet::{Pallet, Call, Storage, Event<T>, ValidateUnsigned} = 82,
+        #[cfg(not(feature = "production"))]
+        EqAccountMigration: eq_account_migration::{Pallet, Call, Storage, Event<T>} = 83,
+        #[cfg(not(feature = "production"))]
+        EqWatchtower: eq_watchtower::{Pallet, Call, Storage, Event<T>} = 84,
+        #[cfg(not(feature = "production"))]
+        EqPerps: eq_perps::{Pallet, Call, Storage, Event<T>} = 85,
+        #[cfg(not(feature = "production"))]
+        EqStaking: eq_staking::{Pallet, Call, Storage, Event<T>} = 86,
+        #[cfg(not(feature = "production"))]
+        EqXdotPool: eq_xdot_pool::{Pallet, Call, Storage, Event<T>} = 87,
     }
 );
 
@@ -2513,6 +3308,23 @@ pub type SignedBlock = generic::SignedBlock<Block>;
 /// BlockId type as expected by this runtime.
 pub type BlockId = generic::BlockId<Block>;
 /// The SignedExtension to the basic transaction logic.
+#[cfg(not(feature = "weight-telemetry"))]
+pub type SignedExtra = (
+    system::CheckSpecVersion<Runtime>,
+    system::CheckTxVersion<Runtime>,
+    system::CheckGenesis<Runtime>,
+    system::CheckEra<Runtime>,
+    system::CheckNonce<Runtime>,
+    system::CheckWeight<Runtime>,
+    transaction_payment::ChargeTransactionPayment<Runtime>,
+    eq_rate::reinit_extension::ReinitAccount<Runtime, CallsWithReinit>,
+    eq_claim::PrevalidateAttests<Runtime>,
+    eq_treasury::CheckBuyout<Runtime>,
+    // q_swap::CheckQSwap<Runtime>,
+);
+/// The SignedExtension to the basic transaction logic, instrumented build
+/// recording weights of dispatched calls
+#[cfg(feature = "weight-telemetry")]
 pub type SignedExtra = (
     system::CheckSpecVersion<Runtime>,
     system::CheckTxVersion<Runtime>,
@@ -2525,6 +3337,7 @@ pub type SignedExtra = (
     eq_claim::PrevalidateAttests<Runtime>,
     eq_treasury::CheckBuyout<Runtime>,
     // q_swap::CheckQSwap<Runtime>,
+    eq_weight_telemetry::RecordCallWeight<Runtime>,
 );
 
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
@@ -2555,7 +3368,18 @@ pub struct CustomOnRuntimeUpgrade;
 
 impl frame_support::traits::OnRuntimeUpgrade for CustomOnRuntimeUpgrade {
     fn on_runtime_upgrade() -> Weight {
-        Weight::from_parts(1, 0)
+        eq_balances::migrations::migrate_locks::<Runtime>()
+            .saturating_add(eq_assets::migration::migrate_existential_deposits::<
+                Runtime,
+                ExistentialDepositOverrides,
+            >())
+            .saturating_add(eq_assets::migration::migrate_risk_tiers::<
+                Runtime,
+                RiskTierOverrides,
+            >())
+            .saturating_add(eq_bridge::migration::migrate_paused_resources::<Runtime>())
+            .saturating_add(eq_wrapped_dot::migration::migrate_withdraw_queue::<Runtime>())
+            .saturating_add(eq_migration::schedule_storage_cleanups::<Runtime>())
     }
 }
 
@@ -2567,36 +3391,58 @@ extern crate frame_benchmarking;
 mod benches {
     use frame_benchmarking::define_benchmarks;
 
-    define_benchmarks!(
-        [frame_benchmarking, BaselineBench::<Runtime>]
-        [frame_system, SystemBench::<Runtime>]
-        [cumulus_pallet_session_benchmarking, SessionBench::<Runtime>]
-        [eq_balances, BalancesBench::<Runtime>]
-        [eq_distribution, DistriBench::<Runtime, DistriBenchInstance>]
-        [eq_vesting, VestingBench::<Runtime, eq_vesting::Instance1>]
-        [eq_subaccounts, SubaccountsBench::<Runtime>]
-        [eq_treasury, TreasuryBench::<Runtime>]
-        [eq_claim, Claims]
-        [eq_whitelists, WhitelistsBench::<Runtime>]
-        [eq_rate, RateBench::<Runtime>]
-        [eq_lockdrop, LockdropBench::<Runtime>]
-        [eq_session_manager, SessionManagerBench::<Runtime>]
-        [equilibrium_curve_amm, CurveAmmBench::<Runtime>]
-        [chainbridge, ChainBridge]
-        [eq_bridge, BridgeBench::<Runtime>]
-        [eq_assets, EqAssets]
-        [eq_multisig_sudo, EqMultisigSudo]
-        [eq_bailsman, BailsmanBench::<Runtime>]
-        [eq_oracle, OracleBench::<Runtime>]
-        [eq_dex, DexBench::<Runtime>]
-        [eq_margin_call, MarginBench::<Runtime>]
-        [eq_lending, LendingBench::<Runtime>]
-        [eq_wrapped_dot, WrappedDotBench::<Runtime>]
-        [pallet_preimage, Preimage]
-        [pallet_scheduler, Scheduler]
-        [pallet_collective, Council]
-        [pallet_membership, CouncilMembership]
-        [pallet_democracy, Democracy]
+    // pallets not benchmarked yet are compiled out in production
+    macro_rules! define_eq_benchmarks {
+        ($($dev:tt)*) => {
+            define_benchmarks!(
+                [frame_benchmarking, BaselineBench::<Runtime>]
+                [frame_system, SystemBench::<Runtime>]
+                [cumulus_pallet_session_benchmarking, SessionBench::<Runtime>]
+                [eq_balances, BalancesBench::<Runtime>]
+                [eq_distribution, DistriBench::<Runtime, DistriBenchInstance>]
+                [eq_vesting, VestingBench::<Runtime, eq_vesting::Instance1>]
+                [eq_subaccounts, SubaccountsBench::<Runtime>]
+                [eq_treasury, TreasuryBench::<Runtime>]
+                [eq_claim, Claims]
+                [eq_whitelists, WhitelistsBench::<Runtime>]
+                [eq_rate, RateBench::<Runtime>]
+                [eq_lockdrop, LockdropBench::<Runtime>]
+                [eq_session_manager, SessionManagerBench::<Runtime>]
+                [equilibrium_curve_amm, CurveAmmBench::<Runtime>]
+                [chainbridge, ChainBridge]
+                [eq_bridge, BridgeBench::<Runtime>]
+                [eq_assets, EqAssets]
+                [eq_multisig_sudo, EqMultisigSudo]
+                [eq_bailsman, BailsmanBench::<Runtime>]
+                [eq_oracle, OracleBench::<Runtime>]
+                [eq_dex, DexBench::<Runtime>]
+                [eq_margin_call, MarginBench::<Runtime>]
+                [eq_lending, LendingBench::<Runtime>]
+                [eq_wrapped_dot, WrappedDotBench::<Runtime>]
+                $($dev)*
+                [pallet_preimage, Preimage]
+                [pallet_scheduler, Scheduler]
+                [pallet_collective, Council]
+                [pallet_membership, CouncilMembership]
+                [pallet_democracy, Democracy]
+            );
+        };
+    }
+
+    #[cfg(feature = "production")]
+    define_eq_benchmarks!();
+    #[cfg(not(feature = "production"))]
+    define_eq_benchmarks!(
+        [eq_xcm_ops, EqXcmOps]
+        [eq_lp_gauge, EqLpGauge]
+        [eq_xcm_streams, EqXcmStreams]
+        [eqd_savings, EqdSavings]
+        [eq_faucet, EqFaucet]
+        [eq_account_migration, EqAccountMigration]
+        [eq_watchtower, EqWatchtower]
+        [eq_perps, PerpsBench::<Runtime>]
+        [eq_staking, StakingBench::<Runtime>]
+        [eq_xdot_pool, EqXdotPool]
     );
 }
 
@@ -2798,6 +3644,101 @@ impl_runtime_apis! {
         }
     }
 
+    impl eq_rate_rpc_runtime_api::EqRateApi<Block, Balance, AccountId> for Runtime {
+        fn interest_statements(account_id: AccountId) -> Vec<(Asset, eq_primitives::InterestStatement<Balance>)> {
+            EqRate::interest_statements(&account_id)
+        }
+    }
+
+    impl eq_treasury_rpc_runtime_api::EqTreasuryApi<Block, Balance, BlockNumber> for Runtime {
+        fn treasury_reports() -> Vec<eq_treasury::TreasuryReport<Balance, BlockNumber>> {
+            Treasury::treasury_reports()
+        }
+
+        fn current_treasury_report() -> eq_treasury::TreasuryReport<Balance, BlockNumber> {
+            Treasury::current_treasury_report()
+        }
+    }
+
+    impl eq_lending_rpc_runtime_api::EqLendingApi<Block, Balance, AccountId, BlockNumber> for Runtime {
+        fn reward_index(asset: Asset) -> EqFixedU128 {
+            EqLending::reward_index(asset)
+        }
+
+        fn pending_reward(account_id: AccountId, asset: Asset) -> Balance {
+            EqLending::pending_reward(&account_id, asset)
+        }
+
+        fn reserve(asset: Asset) -> Balance {
+            EqLending::reserve(asset)
+        }
+
+        fn reward_checkpoints(asset: Asset) -> Vec<eq_primitives::lending::RewardCheckpoint<BlockNumber>> {
+            EqLending::reward_checkpoints(asset).into_inner()
+        }
+
+        fn reward_between(asset: Asset, deposit: Balance, from: BlockNumber, to: BlockNumber) -> Option<Balance> {
+            EqLending::reward_between(asset, deposit, from, to)
+        }
+    }
+
+    impl eq_lending_rpc_runtime_api::EqSolvencyApi<Block, Balance> for Runtime {
+        fn solvency_metrics() -> eq_primitives::solvency::SolvencyMetrics<Balance> {
+            use eq_primitives::{
+                balance::BalanceGetter,
+                solvency::{AssetSolvency, SolvencyMetrics},
+                PriceGetter,
+            };
+
+            let assets: Vec<_> = EqAssets::get_assets()
+                .into_iter()
+                .map(EqLending::asset_solvency)
+                .collect();
+            // assets without price are not counted
+            let total_usd = |amount: fn(&AssetSolvency<Balance>) -> Balance| -> Balance {
+                assets
+                    .iter()
+                    .filter_map(|a| {
+                        let price: EqFixedU128 = Oracle::get_price(&a.asset).ok()?;
+                        Some(price.saturating_mul_int(amount(a)))
+                    })
+                    .fold(0, |total, usd| total.saturating_add(usd))
+            };
+
+            SolvencyMetrics {
+                total_collateral_usd: total_usd(|a| a.collateral),
+                total_debt_usd: total_usd(|a| a.debt),
+                bailsman_coverage: Bailsman::coverage_ratio(),
+                treasury: EqBalances::iterate_account_balances(&Treasury::account_id()).into(),
+                assets,
+            }
+        }
+    }
+
+    impl eq_bailsman_rpc_runtime_api::EqBailsmanApi<Block, Balance, AccountId> for Runtime {
+        fn liquidity_line() -> Option<eq_primitives::LiquidityLineUtilization<Balance>> {
+            Bailsman::liquidity_line_utilization()
+        }
+
+        fn queued_distributions(bailsman: AccountId) -> Vec<eq_primitives::QueuedDistribution<Balance>> {
+            Bailsman::queued_distributions(&bailsman).unwrap_or_default()
+        }
+    }
+
+    impl eq_margin_call_rpc_runtime_api::EqMarginCallApi<Block, AccountId, Balance> for Runtime {
+        fn notifications(account_id: AccountId) -> Vec<eq_primitives::RiskNotification<AccountId>> {
+            EqMarginCall::get_notifications(&account_id)
+        }
+
+        fn simulate(
+            account_id: AccountId,
+            balance_changes: Vec<(Asset, eq_primitives::SignedBalance<Balance>)>,
+            order_changes: Vec<eq_primitives::OrderChange>,
+        ) -> Option<eq_primitives::MarginSimulation> {
+            EqMarginCall::simulate_margin(&account_id, balance_changes, order_changes).ok()
+        }
+    }
+
     impl eq_balances_rpc_runtime_api::EqBalancesApi<Block, Balance, AccountId> for Runtime {
         fn wallet_balance_in_usd(account_id: AccountId) -> Option<Balance> {
             use eq_primitives::balance::BalanceGetter;
@@ -2818,6 +3759,176 @@ impl_runtime_apis! {
             }
             collateral.checked_sub(debt)
         }
+        fn can_transfer(from: AccountId, to: AccountId, asset: Asset, amount: Balance) -> eq_primitives::balance::TransferCheck {
+            use eq_primitives::balance::{TransferCheck, TransferSide};
+
+            let mut check = EqBalances::can_transfer(&from, &to, asset, amount);
+            if let TransferCheck::CheckFailed { side: TransferSide::Sender, ref mut margin, .. } = check {
+                let change = eq_primitives::BalanceChange { change: eq_primitives::SignedBalance::Negative(amount), asset };
+                *margin = EqMarginCall::margin_shortfall(&from, &[change]).ok().flatten();
+            }
+            check
+        }
+        fn account_portfolio(account_id: AccountId) -> eq_primitives::portfolio::AccountPortfolio<AccountId, Balance> {
+            use eq_primitives::{balance::BalanceGetter, portfolio::{AccountPortfolio, SubaccountPortfolio}, subaccount::SubaccountsManager};
+            use frame_support::traits::VestingSchedule;
+
+            let balances = |who: &AccountId| -> Vec<_> { EqBalances::iterate_account_balances(who).into() };
+            let subaccounts = SubAccType::iterator()
+                .filter_map(|subacc_type| {
+                    let subacc_id = Subaccounts::get_subaccount_id(&account_id, &subacc_type)?;
+                    Some(SubaccountPortfolio {
+                        subacc_type,
+                        balances: balances(&subacc_id),
+                        orders: EqDex::account_orders(&subacc_id),
+                        account_id: subacc_id,
+                    })
+                })
+                .collect();
+            let vesting = IntoIterator::into_iter([
+                (EqCurrencyGet::get(), <Vesting as VestingSchedule<AccountId>>::vesting_balance(&account_id)),
+                (QCurrencyGet::get(), <Vesting2 as VestingSchedule<AccountId>>::vesting_balance(&account_id)),
+                (QCurrencyGet::get(), <Vesting3 as VestingSchedule<AccountId>>::vesting_balance(&account_id)),
+                (QCurrencyGet::get(), <Vesting4 as VestingSchedule<AccountId>>::vesting_balance(&account_id)),
+            ])
+            .filter_map(|(asset, locked)| Some((asset, locked?)))
+            .collect();
+
+            AccountPortfolio {
+                balances: balances(&account_id),
+                reserved: eq_balances::Reserved::<Runtime>::iter_prefix(&account_id).collect(),
+                locks: eq_balances::Pallet::<Runtime>::account_locks(&account_id)
+                    .into_iter()
+                    .map(|((asset, id), lock)| (asset, id, lock.amount))
+                    .collect(),
+                subaccounts,
+                orders: EqDex::account_orders(&account_id),
+                lending: EqLending::lender_positions(&account_id),
+                vesting,
+            }
+        }
+
+        fn validate_destination(
+            asset: eq_primitives::asset::Asset,
+            to: eq_primitives::AccountType,
+        ) -> Result<MultiLocation, sp_runtime::DispatchError> {
+            EqBalances::validate_destination(asset, to)
+        }
+    }
+
+    impl eq_balances_rpc_runtime_api::EqStateExportApi<Block, AccountId> for Runtime {
+        fn balances(account_id: AccountId) -> eq_primitives::state_export::VersionedBalances<AccountId> {
+            use eq_primitives::{balance::BalanceGetter, state_export::{BalancesV1, VersionedBalances}};
+
+            let balances = EqBalances::iterate_account_balances(&account_id)
+                .into_iter()
+                .filter(|(_, balance)| !sp_runtime::traits::Zero::is_zero(balance))
+                .collect();
+
+            VersionedBalances::V1(BalancesV1 {
+                account: account_id,
+                block_number: System::block_number(),
+                balances,
+            })
+        }
+
+        fn prices(assets: Vec<Asset>) -> eq_primitives::state_export::VersionedPrices {
+            use eq_primitives::state_export::{PriceV1, VersionedPrices};
+
+            let assets = if assets.is_empty() { EqAssets::get_assets() } else { assets };
+            let prices = assets
+                .into_iter()
+                .filter_map(|asset| {
+                    let price_point = Oracle::price_points(asset)?;
+                    (price_point.get_timestamp() != 0).then(|| PriceV1 {
+                        asset,
+                        price: FixedI64::from_inner(price_point.get_price()),
+                        timestamp: price_point.get_timestamp(),
+                        block_number: *price_point.get_block_number(),
+                    })
+                })
+                .collect();
+
+            VersionedPrices::V1(prices)
+        }
+    }
+
+    impl eq_weight_telemetry_rpc_runtime_api::EqWeightTelemetryApi<Block, BlockNumber> for Runtime {
+        fn call_weights() -> Vec<(eq_weight_telemetry::CallClass, Vec<eq_weight_telemetry::WeightSample<BlockNumber>>)> {
+            #[cfg(feature = "weight-telemetry")]
+            {
+                EqWeightTelemetry::call_weights()
+            }
+            #[cfg(not(feature = "weight-telemetry"))]
+            Vec::new()
+        }
+
+        fn underweighted_calls() -> Vec<(eq_weight_telemetry::CallClass, eq_weight_telemetry::WeightSample<BlockNumber>)> {
+            #[cfg(feature = "weight-telemetry")]
+            {
+                EqWeightTelemetry::underweighted_calls()
+            }
+            #[cfg(not(feature = "weight-telemetry"))]
+            Vec::new()
+        }
+
+        fn measured_call_weights() -> Vec<(eq_weight_telemetry::CallClass, Vec<eq_weight_telemetry::MeasuredSample<BlockNumber>>)> {
+            #[cfg(feature = "weight-telemetry")]
+            {
+                EqWeightTelemetry::measured_call_weights()
+            }
+            #[cfg(not(feature = "weight-telemetry"))]
+            Vec::new()
+        }
+
+        fn measured_underweighted_calls() -> Vec<(eq_weight_telemetry::CallClass, eq_weight_telemetry::MeasuredSample<BlockNumber>)> {
+            #[cfg(feature = "weight-telemetry")]
+            {
+                EqWeightTelemetry::measured_underweighted_calls()
+            }
+            #[cfg(not(feature = "weight-telemetry"))]
+            Vec::new()
+        }
+    }
+
+    impl eq_multisig_sudo_rpc_runtime_api::EqCallFilterApi<Block, RuntimeCall> for Runtime {
+        fn validate_calls(calls: Vec<RuntimeCall>) -> Vec<FilterVerdictWithReason> {
+            calls.iter().map(CallFilter::verdict).collect()
+        }
+    }
+
+    impl eq_bridge_rpc_runtime_api::EqBridgeApi<Block, chainbridge::ResourceId, Balance, BlockNumber> for Runtime {
+        fn reserve_status(resource_id: chainbridge::ResourceId) -> Option<eq_bridge::ReserveStatus<Balance, BlockNumber>> {
+            EqBridge::reserve_status(resource_id)
+        }
+
+        fn reserve_statuses() -> Vec<(chainbridge::ResourceId, eq_bridge::ReserveStatus<Balance, BlockNumber>)> {
+            EqBridge::reserve_statuses()
+        }
+    }
+
+    impl eq_wrapped_dot_rpc_runtime_api::EqWrappedDotApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn unbonding_lots(who: AccountId) -> Vec<eq_wrapped_dot::UnbondingLot<AccountId, Balance>> {
+            EqWrappedDot::unbonding_lots(&who)
+        }
+
+        fn lot_availability(lot_id: eq_wrapped_dot::UnbondingLotId) -> Option<eq_wrapped_dot::LotAvailability<BlockNumber>> {
+            EqWrappedDot::lot_availability(lot_id)
+        }
+    }
+
+    impl eq_subaccounts_rpc_runtime_api::EqSubaccountsApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn nav_history(account_id: AccountId) -> Vec<eq_subaccounts::NavSample<Balance, BlockNumber>> {
+            Subaccounts::nav_history(account_id)
+        }
+
+        fn subaccounts(account_id: AccountId) -> Vec<eq_subaccounts::SubaccountInfo<AccountId, BlockNumber>> {
+            Subaccounts::subaccounts_info(&account_id)
+        }
+
+        fn subaccount_owner(subaccount: AccountId) -> Option<(AccountId, eq_primitives::subaccount::SubAccType)> {
+            Subaccounts::owner_account(subaccount)
+        }
     }
 
     #[cfg(feature = "try-runtime")]
@@ -2872,6 +3983,10 @@ impl_runtime_apis! {
             use eq_margin_call::benchmarking::Pallet as MarginBench;
             use eq_lending::benchmarking::Pallet as LendingBench;
             use eq_wrapped_dot::benchmarking::Pallet as WrappedDotBench;
+            #[cfg(not(feature = "production"))]
+            use eq_perps::benchmarking::Pallet as PerpsBench;
+            #[cfg(not(feature = "production"))]
+            use eq_staking::benchmarking::Pallet as StakingBench;
 
             let mut list = Vec::<BenchmarkList>::new();
             list_benchmarks!(list, extra);
@@ -2947,6 +4062,27 @@ impl_runtime_apis! {
             use eq_wrapped_dot::benchmarking::Pallet as WrappedDotBench;
             impl eq_wrapped_dot::benchmarking::Config for Runtime {}
 
+            #[cfg(not(feature = "production"))]
+            use eq_perps::benchmarking::Pallet as PerpsBench;
+            #[cfg(not(feature = "production"))]
+            impl eq_perps::benchmarking::Config for Runtime {}
+
+            #[cfg(not(feature = "production"))]
+            use eq_staking::benchmarking::Pallet as StakingBench;
+            #[cfg(not(feature = "production"))]
+            impl eq_staking::benchmarking::Config for Runtime {
+                fn start_referendum() -> u32 {
+                    use frame_support::traits::StorePreimage;
+
+                    let call = RuntimeCall::System(system::Call::remark { remark: Vec::new() });
+                    Democracy::internal_start_referendum(
+                        Preimage::bound(call).expect("remark call is bounded"),
+                        pallet_democracy::VoteThreshold::SuperMajorityApprove,
+                        0,
+                    )
+                }
+            }
+
             let whitelist: Vec<TrackedStorageKey> = vec![
                 // Block Number
                 hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
@@ -3004,50 +4140,6 @@ cumulus_pallet_parachain_system::register_validate_block! {
 mod tests {
     use super::*;
 
-    #[test]
-    fn generate_lp_token_asset_works() {
-        let id: u32 = 1234u32;
-        let name: Vec<u8> = b"lpt"
-            .iter()
-            .chain(id.to_string().as_bytes().iter())
-            .map(|item| item.clone())
-            .collect();
-        let asset = eq_primitives::asset::Asset::from_bytes(&name).unwrap();
-        assert_eq!(
-            asset,
-            AssetGenerator::generate_asset_for_pool(id, b"lpt".to_vec())
-        );
-    }
-
-    #[test]
-    fn generate_lp_token_asset_works_for0() {
-        let id: u32 = 0u32;
-        let name: Vec<u8> = b"lpt"
-            .iter()
-            .chain(id.to_string().as_bytes().iter())
-            .map(|item| item.clone())
-            .collect();
-        let asset = eq_primitives::asset::Asset::from_bytes(&name).unwrap();
-        assert_eq!(
-            asset,
-            AssetGenerator::generate_asset_for_pool(id, b"lpt".to_vec())
-        );
-    }
-
-    #[test]
-    fn generate_lp_token_asset_works_for1() {
-        let id: u32 = 1u32;
-        let name: Vec<u8> = b"lpt"
-            .iter()
-            .chain(id.to_string().as_bytes().iter())
-            .map(|item| item.clone())
-            .collect();
-        let asset = eq_primitives::asset::Asset::from_bytes(&name).unwrap();
-        assert_eq!(
-            asset,
-            AssetGenerator::generate_asset_for_pool(id, b"lpt".to_vec())
-        );
-    }
     #[test]
     fn t() {
         let assets = [
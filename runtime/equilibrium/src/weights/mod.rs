@@ -26,3 +26,25 @@ pub mod pallet_treasury;
 pub mod pallet_vesting;
 pub mod pallet_whitelists;
 pub mod pallet_wrapped_dot;
+
+// pallets not benchmarked yet are compiled out in production
+#[cfg(not(feature = "production"))]
+pub mod pallet_lp_gauge;
+#[cfg(not(feature = "production"))]
+pub mod pallet_xcm_ops;
+#[cfg(not(feature = "production"))]
+pub mod pallet_xcm_streams;
+#[cfg(not(feature = "production"))]
+pub mod pallet_eqd_savings;
+#[cfg(not(feature = "production"))]
+pub mod pallet_faucet;
+#[cfg(not(feature = "production"))]
+pub mod pallet_account_migration;
+#[cfg(not(feature = "production"))]
+pub mod pallet_watchtower;
+#[cfg(not(feature = "production"))]
+pub mod pallet_perps;
+#[cfg(not(feature = "production"))]
+pub mod pallet_staking;
+#[cfg(not(feature = "production"))]
+pub mod pallet_xdot_pool;
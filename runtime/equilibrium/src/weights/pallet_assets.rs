@@ -54,4 +54,23 @@ impl<T: frame_system::Config> eq_assets::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqAssets ExistentialDeposits (r:0 w:1)
+	fn set_existential_deposit() -> Weight {
+		Weight::from_parts(14_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqAssets RiskTiers (r:0 w:1)
+	fn set_risk_tier() -> Weight {
+		Weight::from_parts(14_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_decimals() -> Weight {
+		Weight::from_parts(14_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
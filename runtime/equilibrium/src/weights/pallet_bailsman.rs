@@ -92,4 +92,41 @@ impl<T: frame_system::Config> eq_bailsman::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: Bailsman TreasuryLine (r:1 w:1)
+	// Storage: Bailsman TreasuryLineDebt (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	fn set_liquidity_line() -> Weight {
+		Weight::from_parts(18_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Bailsman TreasuryLine (r:1 w:0)
+	// Storage: Bailsman TreasuryLineDebt (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:42 w:2)
+	// Storage: Oracle PricePoints (r:21 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAssets Assets (r:1 w:0)
+	fn manage_liquidity_line() -> Weight {
+		Weight::from_parts(412_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(69 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: EqSubaccounts Subaccount (r:1 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:6 w:1)
+	// Storage: Bailsman DistributionQueue (r:1 w:1)
+	// Storage: Bailsman LastDistribution (r:1 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances TempMigration (r:2 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:44 w:43)
+	// Storage: Bailsman BailsmenCount (r:1 w:1)
+	/// The range of component `z` is `[1, 50]`.
+	fn exit_bailsman(z: u32, ) -> Weight {
+		Weight::from_parts(842_316_000 as u64, 0)
+			// Standard Error: 42_000
+			.saturating_add(Weight::from_parts(6_603_000 as u64, 0).saturating_mul(z as u64))
+			.saturating_add(T::DbWeight::get().reads(59 as u64))
+			.saturating_add(T::DbWeight::get().writes(50 as u64))
+	}
 }
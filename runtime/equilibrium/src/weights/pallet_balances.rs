@@ -118,4 +118,140 @@ impl<T: frame_system::Config> eq_balances::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(a as u64)))
 			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(a as u64)))
 	}
+	// Storage: EqBalances SettlementAccounts (r:0 w:1)
+	fn set_settlement_account() -> Weight {
+		Weight::from_parts(4_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqBalances SettlementAccounts (r:1 w:0)
+	// Storage: EqBalances IsTransfersEnabled (r:1 w:0)
+	// Storage: EqBalances SettlerAuthorizations (r:1 w:0)
+	// Storage: Subaccounts OwnerAccount (r:1 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: EqAggregates AccountUserGroups (r:3 w:1)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate LastFeeUpdate (r:0 w:1)
+	/// The range of component `a` is `[1, 100]`.
+	fn settle(a: u32, ) -> Weight {
+		Weight::from_parts(12_000_000 as u64, 0)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(38_000_000 as u64, 0).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((8 as u64).saturating_mul(a as u64)))
+			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(a as u64)))
+	}
+	// Storage: EqBalances XcmFrozenAssets (r:0 w:1)
+	fn set_xcm_frozen() -> Weight {
+		Weight::from_parts(4_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqBalances XcmFees (r:0 w:1)
+	fn set_xcm_fee() -> Weight {
+		Weight::from_parts(5_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances AssetHubAssets (r:0 w:1)
+	fn set_asset_hub_asset() -> Weight {
+		Weight::from_parts(12_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqBalances XcmRetryQueue (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:2 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate LastFeeUpdate (r:0 w:2)
+	/// The range of component `a` is `[1, 5]`.
+	fn process_xcm_retries(a: u32, ) -> Weight {
+		Weight::from_parts(6_000_000 as u64, 0)
+			// Standard Error: 21_000
+			.saturating_add(Weight::from_parts(41_270_000 as u64, 0).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((7 as u64).saturating_mul(a as u64)))
+			.saturating_add(T::DbWeight::get().writes((6 as u64).saturating_mul(a as u64)))
+	}
+	// Storage: EqBalances IsTransfersEnabled (r:1 w:0)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:6 w:1)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqBalances TimelockDisputeWindow (r:1 w:0)
+	// Storage: EqBalances NextTimelockId (r:1 w:1)
+	// Storage: EqRate LastFeeUpdate (r:0 w:1)
+	// Storage: EqBalances Timelocks (r:0 w:1)
+	fn transfer_with_timelock() -> Weight {
+		Weight::from_parts(96_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
+	// Storage: EqBalances Timelocks (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:2 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate LastFeeUpdate (r:0 w:1)
+	fn cancel_timelock() -> Weight {
+		Weight::from_parts(58_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: EqBalances TimelockDisputeWindow (r:0 w:1)
+	fn set_timelock_dispute_window() -> Weight {
+		Weight::from_parts(4_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqBalances Timelocks (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:2 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate LastFeeUpdate (r:0 w:2)
+	/// The range of component `a` is `[1, 10]`.
+	fn process_timelocks(a: u32, ) -> Weight {
+		Weight::from_parts(5_000_000 as u64, 0)
+			// Standard Error: 18_000
+			.saturating_add(Weight::from_parts(38_410_000 as u64, 0).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((7 as u64).saturating_mul(a as u64)))
+			.saturating_add(T::DbWeight::get().writes((6 as u64).saturating_mul(a as u64)))
+	}
+	// Storage: EqBalances XcmRefunds (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: EqAggregates AccountUserGroups (r:1 w:1)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate LastFeeUpdate (r:0 w:1)
+	fn refund_xcm_surplus() -> Weight {
+		Weight::from_parts(42_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: EqBalances XcmRefunds (r:1 w:1)
+	fn clear_expired_xcm_refund() -> Weight {
+		Weight::from_parts(14_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Not benchmarked yet, estimated from `set_xcm_frozen`
+	// Storage: System Account (r:1 w:1)
+	fn set_asset_flags() -> Weight {
+		Weight::from_parts(8_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqBalances SettlerAuthorizations (r:0 w:1)
+	fn authorize_settler() -> Weight {
+		Weight::from_parts(4_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
@@ -106,4 +106,93 @@ impl<T: frame_system::Config> eq_bridge::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: ChainBridge ChainNonces (r:1 w:0)
+	// Storage: EqBridge ProtocolFees (r:0 w:1)
+	// Storage: EqBridge FeeSurcharges (r:1 w:1)
+	fn set_protocol_fee() -> Weight {
+		Weight::from_parts(20_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: ChainBridge Relayers (r:1 w:0)
+	// Storage: EqBridge ProtocolFees (r:1 w:0)
+	// Storage: EqBridge FeeSurcharges (r:0 w:1)
+	fn set_fee_surcharge() -> Weight {
+		Weight::from_parts(18_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqBridge Resources (r:1 w:0)
+	// Storage: EqBridge ResourceDecimals (r:0 w:1)
+	fn set_resource_decimals() -> Weight {
+		Weight::from_parts(16_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: ChainBridge Relayers (r:1 w:0)
+	// Storage: EqBridge Resources (r:1 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBridge ResourceDecimals (r:1 w:0)
+	// Storage: EqBridge ReserveVotes (r:11 w:11)
+	// Storage: ChainBridge RelayerThreshold (r:1 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:0)
+	// Storage: EqBridge ReserveAutoPause (r:1 w:0)
+	// Storage: EqBridge PausedResources (r:1 w:1)
+	// Storage: EqBridge ReserveReports (r:0 w:1)
+	fn report_reserve() -> Weight {
+		Weight::from_parts(98_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(20 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	// Storage: EqBridge Resources (r:1 w:0)
+	// Storage: EqBridge ReserveAutoPause (r:0 w:1)
+	fn set_reserve_auto_pause() -> Weight {
+		Weight::from_parts(16_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqBridge PausedResources (r:1 w:1)
+	fn resume_resource() -> Weight {
+		Weight::from_parts(15_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqBridge Resources (r:1 w:0)
+	// Storage: EqBridge PausedResources (r:0 w:1)
+	fn pause_resource() -> Weight {
+		Weight::from_parts(16_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBridge Resources (r:1 w:1)
+	// Storage: EqBridge AssetResource (r:1 w:1)
+	// Storage: ChainBridge ChainNonces (r:1 w:0)
+	// Storage: EqBridge ResourceDecimals (r:0 w:1)
+	// Storage: EqBridge MinimumTransferAmount (r:0 w:1)
+	/// The range of component `c` is `[0, 10]`.
+	fn bind_resource(c: u32, ) -> Weight {
+		Weight::from_parts(27_000_000 as u64, 0)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(6_312_000 as u64, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+	}
+	// Storage: EqBridge Resources (r:1 w:1)
+	// Storage: EqBridge AssetResource (r:1 w:1)
+	// Storage: EqBridge MinimumTransferAmount (r:11 w:10)
+	// Storage: EqBridge ReserveVotes (r:0 w:1)
+	// Storage: EqBridge ReserveReports (r:0 w:1)
+	// Storage: EqBridge ReserveAutoPause (r:0 w:1)
+	// Storage: EqBridge PausedResources (r:0 w:1)
+	// Storage: EqBridge ResourceDecimals (r:0 w:1)
+	// Storage: EqBridge EnabledWithdrawals (r:0 w:1)
+	fn unbind_resource() -> Weight {
+		Weight::from_parts(71_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(18 as u64))
+	}
 }
@@ -100,4 +100,41 @@ impl<T: frame_system::Config> eq_claim::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(22 as u64))
 			.saturating_add(T::DbWeight::get().writes(12 as u64))
 	}
+	// Storage: Claims Vesting (r:1 w:0)
+	// Storage: Claims VestingUnlockEpoch (r:0 w:1)
+	fn set_claim_unlock_epoch() -> Weight {
+		Weight::from_parts(15_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Claims ClaimDeadline (r:0 w:1)
+	fn set_claim_deadline() -> Weight {
+		Weight::from_parts(12_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Claims Claims (r:1 w:0)
+	// Storage: Claims GraceExtensions (r:0 w:1)
+	fn extend_claim_grace() -> Weight {
+		Weight::from_parts(16_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Claims Claims (r:1 w:1)
+	// Storage: Claims ClaimDeadline (r:1 w:0)
+	// Storage: Claims GraceExtensions (r:1 w:1)
+	// Storage: Claims Total (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances TempMigration (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Subaccounts OwnerAccount (r:1 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:3 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: Claims Vesting (r:0 w:1)
+	// Storage: Claims VestingUnlockEpoch (r:0 w:1)
+	// Storage: Claims Signing (r:0 w:1)
+	fn sweep_unclaimed() -> Weight {
+		Weight::from_parts(78_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
 }
@@ -61,4 +61,41 @@ impl<T: frame_system::Config> eq_distribution::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(23 as u64))
 			.saturating_add(T::DbWeight::get().writes(9 as u64))
 	}
+	// Storage: EqTreasury NextBudgetId (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:6 w:1)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: EqTreasury Budgets (r:0 w:1)
+	/// The range of component `m` is `[1, 16]`.
+	fn approve_budget(m: u32, ) -> Weight {
+		Weight::from_parts(85_000_000 as u64, 0)
+			// Standard Error: 10_000
+			.saturating_add(Weight::from_parts(1_200_000 as u64, 0).saturating_mul(m as u64))
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqTreasury Budgets (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:6 w:1)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	fn release_milestone() -> Weight {
+		Weight::from_parts(85_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: EqTreasury Budgets (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:6 w:1)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	fn claw_back_milestone() -> Weight {
+		Weight::from_parts(85_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
 }
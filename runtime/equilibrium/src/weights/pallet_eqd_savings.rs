@@ -0,0 +1,77 @@
+
+//! Weights for `eqd_savings`
+
+// Command to regenerate:
+// ./target/production/eq-node
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// eqd_savings
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// ./runtime/equilibrium/src/weights/pallet_eqd_savings.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `eqd_savings`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> eqd_savings::WeightInfo for WeightInfo<T> {
+	// Storage: EqdSavings TotalShares (r:1 w:1)
+	// Storage: EqdSavings LastAccrual (r:1 w:1)
+	// Storage: EqdSavings SavingsRate (r:1 w:0)
+	// Storage: EqdSavings IncomeBudget (r:1 w:1)
+	// Storage: EqdSavings Shares (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: System Account (r:3 w:3)
+	// Storage: EqAggregates AccountUserGroups (r:8 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:6 w:6)
+	// Storage: EqAssets Assets (r:1 w:0)
+	fn deposit() -> Weight {
+		Weight::from_parts(148_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(25 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	// Storage: EqdSavings Shares (r:1 w:1)
+	// Storage: EqdSavings TotalShares (r:1 w:1)
+	// Storage: EqdSavings LastAccrual (r:1 w:1)
+	// Storage: EqdSavings SavingsRate (r:1 w:0)
+	// Storage: EqdSavings IncomeBudget (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: System Account (r:3 w:3)
+	// Storage: EqAggregates AccountUserGroups (r:8 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:6 w:6)
+	// Storage: EqAssets Assets (r:1 w:0)
+	fn withdraw() -> Weight {
+		Weight::from_parts(139_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(25 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	// Storage: EqdSavings LastAccrual (r:1 w:1)
+	// Storage: EqdSavings SavingsRate (r:1 w:1)
+	// Storage: EqdSavings IncomeBudget (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:6 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:4 w:4)
+	// Storage: EqAssets Assets (r:1 w:0)
+	fn set_savings_rate() -> Weight {
+		Weight::from_parts(97_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+}
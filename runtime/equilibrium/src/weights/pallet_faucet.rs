@@ -0,0 +1,49 @@
+
+//! Weights for `eq_faucet`
+
+// Command to regenerate:
+// ./target/production/eq-node
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// eq_faucet
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// ./runtime/equilibrium/src/weights/pallet_faucet.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `eq_faucet`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> eq_faucet::WeightInfo for WeightInfo<T> {
+	// Storage: EqFaucet DripAmounts (r:1 w:0)
+	// Storage: EqFaucet LastDrips (r:1 w:1)
+	// Storage: EqFaucet BlockDrips (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: EqAggregates AccountUserGroups (r:1 w:1)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: EqAggregates TotalAggregates (r:1 w:1)
+	fn drip() -> Weight {
+		Weight::from_parts(48_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqFaucet DripAmounts (r:0 w:1)
+	fn set_drip_amount() -> Weight {
+		Weight::from_parts(12_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+}
@@ -76,4 +76,38 @@ impl<T: frame_system::Config> eq_lending::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(15 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqLending IsolatedPools (r:6 w:1)
+	fn set_isolated_pool() -> Weight {
+		Weight::from_parts(38_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqLending ReserveFactors (r:0 w:1)
+	fn set_reserve_factor() -> Weight {
+		Weight::from_parts(24_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqLending Reserves (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances TempMigration (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:4 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	fn withdraw_reserve() -> Weight {
+		Weight::from_parts(84_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: EqLending LpLendingPools (r:0 w:1)
+	fn set_lp_lending() -> Weight {
+		Weight::from_parts(24_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
@@ -110,4 +110,27 @@ impl<T: frame_system::Config> eq_lockdrop::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(17 as u64))
 			.saturating_add(T::DbWeight::get().writes(6 as u64))
 	}
+	// Storage: EqLockdrop LockStart (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqLockdrop Locks (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqBalances Account (r:2 w:0)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:9 w:1)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: EqRate LastFeeUpdate (r:0 w:1)
+	fn early_unlock() -> Weight {
+		Weight::from_parts(178_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(21 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: Vesting Vesting (r:1 w:0)
+	// Storage: EqLockdrop Locks (r:2 w:2)
+	fn transfer_lock() -> Weight {
+		Weight::from_parts(24_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
 }
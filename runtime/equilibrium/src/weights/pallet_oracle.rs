@@ -36,6 +36,8 @@ impl<T: frame_system::Config> eq_oracle::WeightInfo for WeightInfo<T> {
 	// Storage: Whitelists WhiteList (r:1 w:0)
 	// Storage: EqAssets Assets (r:1 w:0)
 	// Storage: Oracle PricePoints (r:1 w:1)
+	// Storage: Oracle MaxPriceJump (r:1 w:0)
+	// Storage: Oracle SuspectPrices (r:1 w:1)
 	// Storage: Timestamp Now (r:1 w:0)
 	// Storage: FinancialModule Updates (r:1 w:1)
 	// Storage: FinancialModule PriceLogs (r:1 w:0)
@@ -44,7 +46,99 @@ impl<T: frame_system::Config> eq_oracle::WeightInfo for WeightInfo<T> {
 		Weight::from_parts(42_914_000 as u64, 0)
 			// Standard Error: 7_000
 			.saturating_add(Weight::from_parts(156_000 as u64, 0).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Oracle FinancialRecalcProgress (r:1 w:1)
+	// Storage: FinancialModule PriceLogs (r:1 w:0)
+	// Storage: FinancialModule PerAssetMetrics (r:0 w:1)
+	fn recalc_asset() -> Weight {
+		Weight::from_parts(120_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Oracle FinancialRecalcProgress (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: FinancialModule PriceLogs (r:50 w:0)
+	// Storage: FinancialModule Metrics (r:0 w:1)
+	// Storage: FinancialModule PerAssetMetrics (r:0 w:50)
+	/// The range of component `a` is `[1, 50]`.
+	fn recalc_metrics(a: u32, ) -> Weight {
+		Weight::from_parts(250_000_000 as u64, 0)
+			// Standard Error: 95_000
+			.saturating_add(Weight::from_parts(26_400_000 as u64, 0).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(a as u64)))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(a as u64)))
+	}
+	// Storage: Oracle MaxPriceJump (r:0 w:1)
+	fn set_max_price_jump() -> Weight {
+		Weight::from_parts(14_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Oracle SuspectPrices (r:1 w:1)
+	fn override_suspect_price() -> Weight {
+		Weight::from_parts(18_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Oracle FeederWeights (r:0 w:1)
+	fn set_feeder_weight() -> Weight {
+		Weight::from_parts(13_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Oracle OutlierRejection (r:0 w:1)
+	fn set_outlier_rejection() -> Weight {
+		Weight::from_parts(12_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Whitelists WhiteList (r:2 w:0)
+	// Storage: Oracle FeederKeyOwners (r:5 w:6)
+	// Storage: Oracle FeederKeys (r:5 w:6)
+	fn add_feeder_key() -> Weight {
+		Weight::from_parts(58_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
+	}
+	// Storage: Whitelists WhiteList (r:2 w:0)
+	// Storage: Oracle FeederKeys (r:3 w:2)
+	// Storage: Oracle FeederKeyOwners (r:1 w:1)
+	fn rotate_feeder_key() -> Weight {
+		Weight::from_parts(36_000_000 as u64, 0)
 			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Oracle FeederKeys (r:1 w:1)
+	// Storage: Oracle FeederKeyOwners (r:0 w:1)
+	fn remove_feeder_key() -> Weight {
+		Weight::from_parts(18_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(2 as u64))
 	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Oracle LpPriceAdapters (r:0 w:1)
+	fn set_lp_price_adapter() -> Weight {
+		Weight::from_parts(21_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Whitelists WhiteList (r:1 w:0)
+	// Storage: Oracle FeederKeyOwners (r:1 w:0)
+	// Storage: EqAssets Assets (r:32 w:0)
+	// Storage: Oracle PricePoints (r:32 w:32)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Oracle MaxPriceJump (r:32 w:0)
+	// Storage: Oracle SuspectPrices (r:32 w:32)
+	// Storage: FinancialModule Updates (r:32 w:32)
+	// Storage: FinancialModule PriceLogs (r:32 w:0)
+	/// The range of component `p` is `[1, 32]`.
+	fn set_prices(p: u32, ) -> Weight {
+		Weight::from_parts(20_000_000 as u64, 0)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(38_500_000 as u64, 0).saturating_mul(p as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(p as u64)))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(p as u64)))
+	}
 }
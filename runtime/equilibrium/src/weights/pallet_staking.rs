@@ -1,10 +1,8 @@
 
-//! Autogenerated weights for `eq_staking`
+//! Weights for `eq_staking`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-01-24, STEPS: `1`, REPEAT: 1, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! HOSTNAME: `MacBook-Pro-Maks.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: None, WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! `stake`, `reward` and `unlock_stake` are from the run of 2023-01-24 below,
+//! extrinsics added after it are not benchmarked yet.
 
 // Executed Command:
 // ./target/release/eq-node
@@ -51,6 +49,11 @@ impl<T: frame_system::Config> eq_staking::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(17 as u64))
 			.saturating_add(T::DbWeight::get().writes(4 as u64))
 	}
+	// Storage: EqStaking PalletManager (r:0 w:1)
+	fn add_manager() -> Weight {
+		Weight::from_parts(12_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 	// Storage: EqStaking Stakes (r:1 w:1)
 	// Storage: Timestamp Now (r:1 w:0)
 	// Storage: EqRate NowMillisOffset (r:1 w:0)
@@ -69,4 +72,84 @@ impl<T: frame_system::Config> eq_staking::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as u64))
 			.saturating_add(T::DbWeight::get().writes(2 as u64))
 	}
+	// Storage: EqStaking Stakes (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	fn restake() -> Weight {
+		Weight::from_parts(30_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqStaking Rewards (r:1 w:1)
+	// Storage: EqStaking Stakes (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	fn compound_reward() -> Weight {
+		Weight::from_parts(36_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances TempMigration (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:6 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: EqBalances Locked (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqStaking Stakes (r:1 w:1)
+	/// The range of component `r` is `[1, 100]`.
+	fn custom_reward(r: u32, ) -> Weight {
+		Weight::from_parts(21_000_000 as u64, 0)
+			.saturating_add(Weight::from_parts(96_000_000 as u64, 0).saturating_mul(r as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((15 as u64).saturating_mul(r as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(r as u64)))
+	}
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqStaking Stakes (r:1 w:0)
+	// Storage: Democracy ReferendumInfoOf (r:1 w:1)
+	// Storage: Democracy VotingOf (r:1 w:1)
+	// Storage: EqBalances Locked (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn vote_with_stake() -> Weight {
+		Weight::from_parts(64_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: EqStaking Stakes (r:1 w:1)
+	// Storage: EqBalances Locked (r:1 w:1)
+	// Storage: EqStaking Rewards (r:0 w:1)
+	/// The range of component `a` is `[0, 100]`.
+	fn on_initialize(a: u32, ) -> Weight {
+		Weight::from_parts(4_000_000 as u64, 0)
+			.saturating_add(Weight::from_parts(16_000_000 as u64, 0).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(a as u64)))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(a as u64)))
+	}
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqStaking Emission (r:0 w:1)
+	fn set_emission_schedule() -> Weight {
+		Weight::from_parts(16_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqStaking Stakes (r:1 w:0)
+	// Storage: EqStaking Rewards (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances TempMigration (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:6 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: EqBalances Locked (r:1 w:1)
+	fn emission_step() -> Weight {
+		Weight::from_parts(88_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(17 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
 }
@@ -171,4 +171,55 @@ impl<T: frame_system::Config> eq_subaccounts::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(77 as u64))
 			.saturating_add(T::DbWeight::get().writes(38 as u64))
 	}
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: Subaccounts Subaccount (r:2 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: Subaccounts SessionKeys (r:1 w:1)
+	fn authorize_session_key() -> Weight {
+		Weight::from_parts(38_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Subaccounts SessionKeys (r:1 w:1)
+	fn revoke_session_key() -> Weight {
+		Weight::from_parts(21_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Subaccounts OwnerAccount (r:1 w:0)
+	// Storage: Subaccounts Subaccount (r:1 w:0)
+	// Storage: Subaccounts NavSampledAccounts (r:1 w:1)
+	// Storage: Subaccounts NavSampledAccountsCount (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	fn enable_nav_sampling() -> Weight {
+		Weight::from_parts(64_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: Subaccounts OwnerAccount (r:1 w:0)
+	// Storage: Subaccounts Subaccount (r:1 w:0)
+	// Storage: Subaccounts NavSampledAccounts (r:1 w:1)
+	// Storage: Subaccounts NavSampledAccountsCount (r:1 w:1)
+	// Storage: Subaccounts NavHistory (r:0 w:1)
+	fn disable_nav_sampling() -> Weight {
+		Weight::from_parts(29_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Subaccounts NavSampledAccounts (r:101 w:0)
+	// Storage: System Account (r:100 w:0)
+	// Storage: Oracle PricePoints (r:2 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Subaccounts NavHistory (r:100 w:100)
+	/// The range of component `a` is `[0, 100]`.
+	fn sample_nav(a: u32, ) -> Weight {
+		Weight::from_parts(4_000_000 as u64, 0)
+			// Standard Error: 12_000
+			.saturating_add(Weight::from_parts(31_457_000 as u64, 0).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(a as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(a as u64)))
+	}
 }
@@ -87,4 +87,30 @@ impl<T: frame_system::Config> eq_vesting::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(24 as u64))
 			.saturating_add(T::DbWeight::get().writes(9 as u64))
 	}
+	// Storage: Vesting Vesting (r:1 w:0)
+	// Storage: Vesting AutoVest (r:0 w:1)
+	fn set_auto_vest() -> Weight {
+		Weight::from_parts(13_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Vesting AutoVest (r:1 w:0)
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: Vesting Vested (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances TempMigration (r:3 w:0)
+	// Storage: System Account (r:3 w:3)
+	// Storage: Subaccounts OwnerAccount (r:3 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:9 w:2)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	fn auto_vest() -> Weight {
+		Weight::from_parts(141_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(23 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	// Storage: Vesting UnlockEpochs (r:0 w:1)
+	fn set_unlock_epoch() -> Weight {
+		Weight::from_parts(11_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
@@ -0,0 +1,114 @@
+
+//! Weights for `eq_xcm_ops`
+
+// Command to regenerate:
+// ./target/production/eq-node
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// eq_xcm_ops
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// ./runtime/equilibrium/src/weights/pallet_xcm_ops.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `eq_xcm_ops`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> eq_xcm_ops::WeightInfo for WeightInfo<T> {
+	// Storage: EqBalances IsXcmTransfersEnabled (r:1 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:3 w:3)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: Oracle PricePoints (r:2 w:0)
+	// Storage: Subaccounts OwnerAccount (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: PolkadotXcm SupportedVersion (r:2 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	// Storage: XcmpQueue OutboundXcmpMessages (r:0 w:2)
+	// Storage: EqXcmOps PendingTransacts (r:0 w:1)
+	fn transfer_and_transact() -> Weight {
+		Weight::from_parts(312_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(17 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	// Storage: EqXcmOps PendingTransacts (r:1 w:1)
+	fn on_transact_response() -> Weight {
+		Weight::from_parts(21_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqXcmOps PendingTransacts (r:1 w:1)
+	fn clear_expired_transact() -> Weight {
+		Weight::from_parts(19_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqXcmOps RepatriationTemplates (r:0 w:1)
+	fn set_repatriation_template() -> Weight {
+		Weight::from_parts(17_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqXcmOps RepatriationTemplates (r:1 w:0)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	// Storage: XcmpQueue OutboundXcmpMessages (r:0 w:1)
+	fn repatriate_sovereign_assets() -> Weight {
+		Weight::from_parts(64_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: EqXcmOps XcmPartners (r:1 w:1)
+	// Storage: EqXcmOps PartnerVersions (r:0 w:1)
+	fn set_xcm_partner() -> Weight {
+		Weight::from_parts(18_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: EqXcmOps XcmPartners (r:1 w:0)
+	// Storage: PolkadotXcm VersionNotifiers (r:1 w:1)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	// Storage: XcmpQueue OutboundXcmpMessages (r:0 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	fn subscribe_partner_versions(p: u32, ) -> Weight {
+		Weight::from_parts(12_000_000 as u64, 0)
+			.saturating_add(Weight::from_parts(52_000_000 as u64, 0).saturating_mul(p as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((7 as u64).saturating_mul(p as u64)))
+			.saturating_add(T::DbWeight::get().writes((6 as u64).saturating_mul(p as u64)))
+	}
+	// Storage: EqXcmOps XcmPartners (r:1 w:0)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: EqXcmOps PartnerVersions (r:1 w:1)
+	fn check_partner_versions(p: u32, ) -> Weight {
+		Weight::from_parts(10_000_000 as u64, 0)
+			.saturating_add(Weight::from_parts(9_000_000 as u64, 0).saturating_mul(p as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(p as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(p as u64)))
+	}
+}
@@ -0,0 +1,66 @@
+
+//! Weights for `eq_xcm_streams`
+
+// Command to regenerate:
+// ./target/production/eq-node
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// eq_xcm_streams
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// ./runtime/equilibrium/src/weights/pallet_xcm_streams.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `eq_xcm_streams`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> eq_xcm_streams::WeightInfo for WeightInfo<T> {
+	// Storage: EqXcmStreams StreamsCount (r:1 w:1)
+	// Storage: EqXcmStreams NextStreamId (r:1 w:1)
+	// Storage: EqXcmStreams Agenda (r:10 w:1)
+	// Storage: EqXcmStreams Streams (r:0 w:1)
+	fn schedule_stream() -> Weight {
+		Weight::from_parts(41_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: EqXcmStreams Streams (r:1 w:1)
+	// Storage: EqXcmStreams Agenda (r:1 w:1)
+	// Storage: EqXcmStreams StreamsCount (r:1 w:1)
+	fn cancel_stream() -> Weight {
+		Weight::from_parts(27_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: EqXcmStreams Agenda (r:2 w:2)
+	// Storage: EqXcmStreams Streams (r:1 w:1)
+	// Storage: EqBalances IsXcmTransfersEnabled (r:1 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: EqBalances TempMigration (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: EqAggregates AccountUserGroups (r:3 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	/// The range of component `s` is `[0, 10]`.
+	fn on_initialize(s: u32, ) -> Weight {
+		Weight::from_parts(3_000_000 as u64, 0)
+			.saturating_add(Weight::from_parts(84_112_000 as u64, 0).saturating_mul(s as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((11 as u64).saturating_mul(s as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(s as u64)))
+	}
+}
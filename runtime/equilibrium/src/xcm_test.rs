@@ -149,6 +149,15 @@ mod multi {
         },
         decimals: 6,
     };
+
+    /// From statemint, not wired in `XcmToFee`
+    pub const DED: OtherReservedData = OtherReservedData {
+        multi_location: MultiLocation {
+            parents: 1,
+            interior: X3(Parachain(1000), PalletInstance(50), GeneralIndex(30)),
+        },
+        decimals: 10,
+    };
 }
 
 mod resources {
@@ -418,6 +427,177 @@ fn transact_dispatch_origin_not_allowed() {
     }
 }
 
+#[test]
+fn transact_dispatch_origin_from_trusted_sibling() {
+    parachain_test_ext().unwrap().execute_with(|| {
+        let id = [0x01; 32];
+        let remote = |para_id| MultiLocation {
+            parents: 1,
+            interior: X2(Parachain(para_id), AccountId32 { network: None, id }),
+        };
+
+        assert_ok!(EqDex::set_trusted_xcm_parachain(
+            RuntimeOrigin::root(),
+            2000,
+            true
+        ));
+
+        let result: Result<RuntimeOrigin, _> = XcmOriginToTransactDispatchOrigin::convert_origin(
+            remote(2000),
+            OriginKind::SovereignAccount,
+        );
+        let derived: AccountId = eq_xcm::origins::sibling_account_id(2000, id);
+        assert_eq!(
+            frame_system::ensure_signed(result.unwrap()),
+            Ok(derived.clone())
+        );
+        // assets are never resolved to derived accounts
+        assert_err!(LocationToAccountId::convert(remote(2000)), remote(2000));
+
+        for (origin, kind) in [
+            (remote(2000), OriginKind::Native),
+            (remote(2000), OriginKind::Superuser),
+            (remote(2001), OriginKind::SovereignAccount),
+        ] {
+            let result: Result<RuntimeOrigin, _> =
+                XcmOriginToTransactDispatchOrigin::convert_origin(origin.clone(), kind);
+            assert_eq!(result.err().unwrap(), origin);
+        }
+
+        assert!(RemoteTraderCalls::contains(&RuntimeCall::EqDex(
+            eq_dex::Call::delete_order_external {
+                asset: asset::DOT,
+                order_id: 0,
+                price: Default::default(),
+            }
+        )));
+        assert!(!RemoteTraderCalls::contains(&RuntimeCall::EqDex(
+            eq_dex::Call::set_trusted_xcm_parachain {
+                para_id: 2000,
+                trusted: true,
+            }
+        )));
+        assert!(
+            !<XcmConfig as xcm_executor::Config>::SafeCallFilter::contains(
+                &RuntimeCall::EqBalances(eq_balances::Call::transfer {
+                    asset: asset::DOT,
+                    to: derived,
+                    value: 1,
+                })
+            )
+        );
+    });
+}
+
+#[test]
+fn barrier_remote_trader_transact() {
+    parachain_test_ext().unwrap().execute_with(|| {
+        let sibling = |para_id| MultiLocation {
+            parents: 1,
+            interior: X1(Parachain(para_id)),
+        };
+        let message = |origin_kind| -> Vec<Instruction<()>> {
+            vec![
+                ReserveAssetDeposited(vec![multi_asset_from(TO_SEND_AMOUNT, &multi::DOT)].into()),
+                BuyExecution {
+                    fees: multi_asset_from(TO_SEND_AMOUNT, &multi::DOT),
+                    weight_limit: Unlimited,
+                },
+                DescendOrigin(X1(AccountId32 {
+                    network: None,
+                    id: [0x01; 32],
+                })),
+                Transact {
+                    origin_kind,
+                    require_weight_at_most: XcmWeight::from_parts(1_000_000, 0),
+                    call: Vec::<u8>::new().into(),
+                },
+                RefundSurplus,
+            ]
+        };
+        let max_weight = XcmWeight::from_parts(1_000_000_000, 0);
+        let mut weight_credit = XcmWeight::zero();
+
+        assert_ok!(EqDex::set_trusted_xcm_parachain(
+            RuntimeOrigin::root(),
+            2000,
+            true
+        ));
+
+        let mut trusted = message(OriginKind::SovereignAccount);
+        assert_ok!(crate::Barrier::should_execute(
+            &sibling(2000),
+            &mut trusted,
+            max_weight,
+            &mut weight_credit,
+        ));
+        assert!(matches!(
+            trusted[1],
+            BuyExecution {
+                weight_limit: Limited(limit),
+                ..
+            } if limit == max_weight
+        ));
+
+        // untrusted sibling
+        assert_noop!(
+            crate::Barrier::should_execute(
+                &sibling(2001),
+                &mut message(OriginKind::SovereignAccount),
+                max_weight,
+                &mut weight_credit,
+            ),
+            ProcessMessageError::Unsupported
+        );
+
+        // remote user can't be the origin of the message
+        assert_noop!(
+            crate::Barrier::should_execute(
+                &MultiLocation {
+                    parents: 1,
+                    interior: X2(
+                        Parachain(2000),
+                        AccountId32 {
+                            network: None,
+                            id: [0x01; 32],
+                        }
+                    ),
+                },
+                &mut message(OriginKind::SovereignAccount),
+                max_weight,
+                &mut weight_credit,
+            ),
+            ProcessMessageError::Unsupported
+        );
+
+        // call dispatched with other origin than the derived account
+        for origin_kind in [OriginKind::Native, OriginKind::Superuser, OriginKind::Xcm] {
+            assert_noop!(
+                crate::Barrier::should_execute(
+                    &sibling(2000),
+                    &mut message(origin_kind),
+                    max_weight,
+                    &mut weight_credit,
+                ),
+                ProcessMessageError::Unsupported
+            );
+        }
+
+        // origin isn't descended to the user
+        let mut not_descended = message(OriginKind::SovereignAccount);
+        not_descended.remove(2);
+        assert_noop!(
+            crate::Barrier::should_execute(
+                &sibling(2000),
+                &mut not_descended,
+                max_weight,
+                &mut weight_credit,
+            ),
+            ProcessMessageError::Unsupported
+        );
+    });
+}
+
 #[allow(dead_code)]
 const USER_X: AccountId = AccountId::new([0x01; 32]);
 #[allow(dead_code)]
@@ -1289,6 +1469,222 @@ fn xcm_transfer_deal_with_fee_unimplemented() {
 //     });
 // }
 
+#[test]
+fn xcm_asset_hub_asset_reserve_transfers() {
+    parachain_test_ext().unwrap().execute_with(|| {
+        use eq_balances::{AssetHubAsset, AssetHubFeeMode};
+        use eq_primitives::balance::EqCurrency as _;
+
+        System::set_block_number(1);
+
+        let ded = Asset::from_bytes(b"ded").unwrap();
+        assert_ok!(EqAssets::do_add_asset(
+            ded,
+            EqFixedU128::from(0),
+            FixedI64::from(0),
+            Permill::zero(),
+            Permill::zero(),
+            AssetXcmData::OtherReserved(multi::DED),
+            Permill::from_rational(2u32, 5u32),
+            9,
+            AssetType::Physical,
+            true,
+            Percent::zero(),
+            Permill::one(),
+            vec![],
+        ));
+        assert_ok!(EqBalances::deposit_creating(
+            &USER_X,
+            ded,
+            INITIAL_AMOUNT,
+            true,
+            None
+        ));
+
+        // not wired in `XcmToFee`
+        assert_noop!(
+            EqBalances::do_xcm_transfer_old(
+                USER_X,
+                ded,
+                TO_SEND_AMOUNT,
+                XcmDestination::Native(AccountType::Id32(USER_Y.into())),
+                XcmTransferDealWithFee::SovereignAccWillPay
+            ),
+            eq_balances::Error::<Runtime>::XcmInvalidDestination
+        );
+
+        assert_noop!(
+            EqBalances::set_asset_hub_asset(
+                RuntimeOrigin::root(),
+                ded,
+                Some(AssetHubAsset {
+                    general_index: 1984,
+                    fee_mode: AssetHubFeeMode::RelayToken(asset::DOT),
+                })
+            ),
+            eq_balances::Error::<Runtime>::AssetHubLocationMismatch
+        );
+        assert_ok!(EqBalances::set_asset_hub_asset(
+            RuntimeOrigin::root(),
+            ded,
+            Some(AssetHubAsset {
+                general_index: 30,
+                fee_mode: AssetHubFeeMode::Sufficient(10_000_000_000),
+            })
+        ));
+
+        // inbound: no oracle price, execution is paid by Asset Hub price
+        let fee = 10_000_000_000 * XCM_MSG_WEIGHT.ref_time() as u128 / 1_000_000_000_000;
+        let mut payment = xcm_executor::Assets::new();
+        payment.fungible.insert(
+            AssetId::Concrete(multi::DED.multi_location.clone()),
+            fee - 1,
+        );
+        let mut trader = crate::EqTrader::new();
+        assert_noop!(
+            trader.buy_weight(XCM_MSG_WEIGHT, payment),
+            XcmError::TooExpensive
+        );
+
+        let mut payment = xcm_executor::Assets::new();
+        payment
+            .fungible
+            .insert(AssetId::Concrete(multi::DED.multi_location.clone()), fee);
+        let mut trader = crate::EqTrader::new();
+        assert_ok!(
+            trader.buy_weight(XCM_MSG_WEIGHT, payment),
+            xcm_executor::Assets::new()
+        );
+
+        assert_ok!(Oracle::set_price(
+            RuntimeOrigin::signed(USER_X),
+            ded,
+            FixedI64::from_inner(2_000_000_000) // $2
+        ));
+        assert_ok!(Oracle::set_price(
+            RuntimeOrigin::signed(USER_X),
+            asset::DOT,
+            FixedI64::from_inner(5_000_000_000) // $5
+        ));
+
+        // outbound, fee is paid in the asset itself
+        let (fee_asset, fee_amount) = EqBalances::xcm_to_fee(
+            ded,
+            &xcm_origins::dot::PARACHAIN_STATEMINT,
+            &Xcm::<()>(vec![ClearOrigin; 4]),
+        )
+        .unwrap();
+        assert_eq!(fee_asset, ded);
+        assert_eq!(fee_amount, 2 * 40_000_000);
+
+        assert_ok!(EqBalances::do_xcm_transfer_old(
+            USER_X,
+            ded,
+            TO_SEND_AMOUNT,
+            XcmDestination::Native(AccountType::Id32(USER_Y.into())),
+            XcmTransferDealWithFee::SovereignAccWillPay
+        ));
+        assert_eq!(
+            EqBalances::total_balance(&USER_X, ded),
+            INITIAL_AMOUNT - TO_SEND_AMOUNT
+        );
+
+        let ded_location = MultiLocation::new(0, X2(PalletInstance(50), GeneralIndex(30)));
+        let xcm_amount = balance_into_xcm(TO_SEND_AMOUNT, 10).unwrap();
+        let beneficiary: MultiLocation = X1(AccountId32 {
+            network: None,
+            id: USER_Y.into(),
+        })
+        .into();
+        let ded_fee = MultiAsset {
+            id: AssetId::Concrete(ded_location.clone()),
+            fun: Fungibility::Fungible(fee_amount),
+        };
+        let sufficient_xcm = Xcm(vec![
+            WithdrawAsset(
+                vec![
+                    MultiAsset {
+                        id: AssetId::Concrete(ded_location.clone()),
+                        fun: Fungibility::Fungible(xcm_amount - fee_amount),
+                    },
+                    ded_fee.clone(),
+                ]
+                .into(),
+            ),
+            ClearOrigin,
+            BuyExecution {
+                fees: ded_fee,
+                weight_limit: Unlimited,
+            },
+            DepositAsset {
+                assets: xcm::latest::WildMultiAsset::All.into(),
+                beneficiary: beneficiary.clone(),
+            },
+        ]);
+
+        // outbound, fee is paid in DOT
+        assert_ok!(EqBalances::set_asset_hub_asset(
+            RuntimeOrigin::root(),
+            ded,
+            Some(AssetHubAsset {
+                general_index: 30,
+                fee_mode: AssetHubFeeMode::RelayToken(asset::DOT),
+            })
+        ));
+        let (fee_asset, fee_amount) = EqBalances::xcm_to_fee(
+            ded,
+            &xcm_origins::dot::PARACHAIN_STATEMINT,
+            &Xcm::<()>(vec![ClearOrigin; 4]),
+        )
+        .unwrap();
+        assert_eq!(fee_asset, asset::DOT);
+        assert_eq!(fee_amount, 2 * 35_199_492);
+
+        assert_ok!(EqBalances::do_xcm_transfer_old(
+            USER_X,
+            ded,
+            TO_SEND_AMOUNT,
+            XcmDestination::Native(AccountType::Id32(USER_Y.into())),
+            XcmTransferDealWithFee::SovereignAccWillPay
+        ));
+
+        let fee_in_ded = fee_amount * 5 / 2; // same decimals
+        let dot_fee = MultiAsset {
+            id: AssetId::Concrete(Parent.into()),
+            fun: Fungibility::Fungible(fee_amount),
+        };
+        let relay_token_xcm = Xcm(vec![
+            WithdrawAsset(
+                vec![
+                    MultiAsset {
+                        id: AssetId::Concrete(ded_location),
+                        fun: Fungibility::Fungible(xcm_amount - fee_in_ded),
+                    },
+                    dot_fee.clone(),
+                ]
+                .into(),
+            ),
+            ClearOrigin,
+            BuyExecution {
+                fees: dot_fee,
+                weight_limit: Unlimited,
+            },
+            DepositAsset {
+                assets: xcm::latest::WildMultiAsset::All.into(),
+                beneficiary,
+            },
+        ]);
+
+        assert_eq!(
+            xcm_message_container(),
+            vec![
+                (xcm_origins::dot::PARACHAIN_STATEMINT, sufficient_xcm),
+                (xcm_origins::dot::PARACHAIN_STATEMINT, relay_token_xcm),
+            ]
+        );
+    });
+}
+
 #[test]
 fn xcm_message_received_unlimited_weight_bridge() {
     parachain_test_ext().unwrap().execute_with(|| {
@@ -1769,6 +2165,107 @@ fn xcm_message_received_unlimited_weight() {
     })
 }
 
+#[test]
+fn xcm_message_received_frozen_deposit_is_trapped() {
+    parachain_test_ext().unwrap().execute_with(|| {
+        use eq_primitives::balance::EqCurrency as _;
+
+        System::set_block_number(1);
+
+        assert_ok!(Oracle::set_price(
+            RuntimeOrigin::signed(USER_X),
+            asset::DOT,
+            FixedI64::from_inner(5_000_000_000)
+        ));
+
+        let fee_in_usd = crate::fee::XcmWeightToFee::weight_to_fee(&XCM_MSG_WEIGHT) as Balance;
+        let fee_in_dot = (fee_in_usd * 10) / 5;
+        let fee_asset = MultiAsset {
+            id: AssetId::Concrete(multi::DOT.multi_location),
+            fun: Fungibility::Fungible(2 * fee_in_dot),
+        };
+        let rcvd_xcm_message = Xcm::<RuntimeCall>(vec![
+            ReserveAssetDeposited(
+                vec![
+                    multi_asset_from(TO_RECV_AMOUNT, &multi::DOT),
+                    fee_asset.clone(),
+                ]
+                .into(),
+            ),
+            ClearOrigin,
+            BuyExecution {
+                fees: fee_asset,
+                weight_limit: Unlimited,
+            },
+            DepositAsset {
+                assets: MultiAssetFilter::Definite(
+                    vec![multi_asset_from(TO_RECV_AMOUNT, &multi::DOT)].into(),
+                ),
+                beneficiary: MultiLocation {
+                    parents: 0,
+                    interior: X1(AccountId32 {
+                        network: None,
+                        id: USER_X.into(),
+                    }),
+                },
+            },
+        ]);
+        let execute = || {
+            XcmExecutor::<XcmConfig>::execute_xcm_in_credit(
+                xcm_origins::RELAY,
+                rcvd_xcm_message.clone(),
+                hash_xcm(rcvd_xcm_message.clone()),
+                XcmWeight::MAX,
+                XcmWeight::MAX,
+            )
+        };
+
+        assert_ok!(EqBalances::set_xcm_asset_frozen(
+            RuntimeOrigin::root(),
+            asset::DOT,
+            true
+        ));
+        assert!(matches!(
+            execute(),
+            Outcome::Incomplete(_, XcmError::FailedToTransactAsset(_)),
+        ));
+        assert_eq!(
+            EqBalances::total_balance(&USER_X, asset::DOT),
+            INITIAL_AMOUNT
+        );
+
+        assert_ok!(EqBalances::set_xcm_asset_frozen(
+            RuntimeOrigin::root(),
+            asset::DOT,
+            false
+        ));
+        assert_ok!(EqBalances::set_xcm_origin_frozen(
+            RuntimeOrigin::root(),
+            xcm_origins::RELAY,
+            true
+        ));
+        assert!(matches!(
+            execute(),
+            Outcome::Incomplete(_, XcmError::Trap(eq_xcm::barrier::FROZEN_ORIGIN_TRAP_CODE)),
+        ));
+        assert_eq!(
+            EqBalances::total_balance(&USER_X, asset::DOT),
+            INITIAL_AMOUNT
+        );
+
+        assert_ok!(EqBalances::set_xcm_origin_frozen(
+            RuntimeOrigin::root(),
+            xcm_origins::RELAY,
+            false
+        ));
+        assert!(matches!(execute(), Outcome::Complete(_)));
+        assert_eq!(
+            EqBalances::total_balance(&USER_X, asset::DOT),
+            INITIAL_AMOUNT + TO_RECV_AMOUNT
+        );
+    })
+}
+
 #[test]
 fn xcm_message_reseived_limited_weight_ok() {
     parachain_test_ext().unwrap().execute_with(|| {
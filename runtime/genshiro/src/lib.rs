@@ -27,6 +27,7 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 pub use chainbridge;
 use codec::{Decode, Encode};
+use common_runtime::call_filter::CallPolicyRules;
 use core::marker::PhantomData;
 pub use eq_assets;
 pub use eq_balances;
@@ -35,13 +36,16 @@ pub use eq_distribution;
 pub use eq_lending;
 pub use eq_multisig_sudo;
 pub use eq_primitives;
-use eq_primitives::{balance::EqCurrency, Aggregates, UserGroup};
+use eq_primitives::{
+    balance::EqCurrency, call_filter::FilterVerdictWithReason, Aggregates, UserGroup,
+};
+#[cfg(not(feature = "production"))]
+use eq_primitives::xdot_pool::XdotNumber;
 pub use eq_rate;
 pub use eq_treasury;
 use eq_utils::XcmBalance;
 pub use eq_vesting;
 use eq_xcm::ParaId;
-use financial_pallet::FinancialSystemTrait;
 use financial_primitives::{CalcReturnType, CalcVolatilityType};
 use frame_support::pallet_prelude::Get;
 use frame_support::traits::UnixTime;
@@ -66,7 +70,7 @@ use polkadot_parachain::primitives::Sibling;
 use polkadot_runtime_common::SlowAdjustingFeeUpdate;
 use polkadot_runtime_constants::weights::RocksDbWeight;
 use sp_api::impl_runtime_apis;
-use sp_arithmetic::{FixedI64, FixedPointNumber, PerThing, Percent};
+use sp_arithmetic::{FixedI64, FixedPointNumber, PerThing};
 use sp_consensus_aura::{sr25519::AuthorityId as AuraId, SlotDuration};
 use sp_core::ConstU32;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
@@ -98,7 +102,7 @@ use xcm::v3::{
 };
 use xcm_builder::{
     AllowKnownQueryResponses, AllowSubscriptionsFrom, EnsureXcmOrigin, FixedWeightBounds,
-    ParentIsPreset, SiblingParachainConvertsVia,
+    ParentIsPreset, SiblingParachainConvertsVia, SovereignSignedViaLocation,
 };
 use xcm_executor::traits::WithOriginFilter;
 use xcm_executor::{traits::ConvertOrigin, Config, XcmExecutor};
@@ -228,64 +232,80 @@ parameter_types! {
 }
 
 pub struct CallFilter;
-impl frame_support::traits::Contains<RuntimeCall> for CallFilter {
+impl CallFilter {
+    /// Verdict of the filter with the reason of rejection, used by `contains`
+    /// and by `EqCallFilterApi` to check calls without dispatching
     #[allow(unused_variables)]
-    fn contains(c: &RuntimeCall) -> bool {
+    pub fn verdict(c: &RuntimeCall) -> FilterVerdictWithReason {
         #[cfg(feature = "production")]
-        match (eq_migration::Migration::<Runtime>::exists(), c) {
-            (false, RuntimeCall::EqMultisigSudo(proposal_call)) => match proposal_call {
-                eq_multisig_sudo::Call::propose { call } => match *call.clone() {
-                    RuntimeCall::PolkadotXcm(_) => true, // allow send xcm from msig
-                    RuntimeCall::Utility(utility_call) => {
-                        // allow send xcm batch from msig
-                        match utility_call {
-                            pallet_utility::Call::batch { calls, .. }
-                            | pallet_utility::Call::batch_all { calls, .. } => {
-                                calls.iter().all(|call| {
-                                    if let RuntimeCall::PolkadotXcm(_) = call {
-                                        true
-                                    } else {
-                                        Self::contains(call)
-                                    }
-                                })
-                            }
-                            _ => true,
-                        }
-                    }
-                    _ => Self::contains(&**call),
-                },
-                _ => true,
-            },
-            (false, RuntimeCall::Utility(utility_call)) => match utility_call {
-                pallet_utility::Call::batch { calls, .. }
-                | pallet_utility::Call::batch_all { calls, .. } => {
-                    calls.iter().all(|call| Self::contains(call))
-                }
-                _ => true,
-            },
-            (false, RuntimeCall::EqBalances(call)) => match call {
-                eq_balances::Call::deposit { .. } | eq_balances::Call::burn { .. } => false,
-                _ => true,
-            },
-            // (false, Call::Oracle(eq_oracle::Call::set_fin_metrics_recalc_enabled { .. })) => false,
-            (false, RuntimeCall::EqRate(eq_rate::Call::set_now_millis_offset { .. })) => false,
-            (false, RuntimeCall::Vesting(eq_vesting::Call::force_vested_transfer { .. })) => false,
-            // XCM disallowed
-            (_, &RuntimeCall::PolkadotXcm(_)) => false,
-            (false, _) => true,
-
-            // only system and sudo are allowed during migration
-            (true, &RuntimeCall::ParachainSystem(_)) => true,
-            (true, &RuntimeCall::System(_)) => true,
-            // (true, &Call::Sudo(_)) => true,
-            (true, &RuntimeCall::Timestamp(_)) => true,
-            (true, &RuntimeCall::EqMultisigSudo(_)) => true,
-
-            // all other pallets are disallowed during migration
-            (true, _) => false,
+        {
+            common_runtime::call_filter::CallPolicy::<RuntimeCall, CallFilterRules>::verdict(c)
         }
         #[cfg(not(feature = "production"))]
-        true
+        FilterVerdictWithReason::Allowed
+    }
+}
+
+impl frame_support::traits::Contains<RuntimeCall> for CallFilter {
+    fn contains(c: &RuntimeCall) -> bool {
+        Self::verdict(c).is_allowed()
+    }
+}
+
+/// Rule table of production `CallFilter`, see `common_runtime::call_filter`
+pub struct CallFilterRules;
+impl CallPolicyRules<RuntimeCall> for CallFilterRules {
+    fn migration_in_progress() -> bool {
+        eq_migration::Migration::<Runtime>::exists()
+    }
+
+    fn is_pallet_disabled(_call: &RuntimeCall) -> bool {
+        false
+    }
+
+    fn is_call_disabled(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::EqBalances(eq_balances::Call::deposit { .. })
+                | RuntimeCall::EqBalances(eq_balances::Call::burn { .. })
+                | RuntimeCall::EqRate(eq_rate::Call::set_now_millis_offset { .. })
+                | RuntimeCall::Vesting(eq_vesting::Call::force_vested_transfer { .. })
+        )
+    }
+
+    fn is_allowed_in_migration(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::ParachainSystem(_)
+                | RuntimeCall::System(_)
+                | RuntimeCall::Timestamp(_)
+                | RuntimeCall::EqMultisigSudo(_)
+        )
+    }
+
+    fn is_xcm(call: &RuntimeCall) -> bool {
+        matches!(call, RuntimeCall::PolkadotXcm(_))
+    }
+
+    fn is_multisig_xcm_allowed(call: &RuntimeCall) -> bool {
+        MultisigProposalFilter::contains(call)
+    }
+
+    fn multisig_proposal(call: &RuntimeCall) -> Option<&RuntimeCall> {
+        match call {
+            RuntimeCall::EqMultisigSudo(eq_multisig_sudo::Call::propose { call }) => Some(&**call),
+            _ => None,
+        }
+    }
+
+    fn batch(call: &RuntimeCall) -> Option<&[RuntimeCall]> {
+        match call {
+            RuntimeCall::Utility(pallet_utility::Call::batch { calls, .. })
+            | RuntimeCall::Utility(pallet_utility::Call::batch_all { calls, .. }) => {
+                Some(&calls[..])
+            }
+            _ => None,
+        }
     }
 }
 
@@ -362,11 +382,17 @@ impl eq_assets::Config for Runtime {
 parameter_types! {
     pub const MigrationsPerBlock: u16 = 2_000;
     pub const AccountsPerBlock: u16 = 100;
+    /// Storage of removed pallets and storage items, e.g.
+    /// `StorageCleanup::new("EqOldPallet", "Items")`
+    pub StorageCleanups: Vec<eq_migration::StorageCleanup> = vec![];
 }
 
 impl eq_migration::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type MigrationsPerBlock = MigrationsPerBlock;
+    type SteppedMigration = EqBalances;
+    type StorageCleanups = StorageCleanups;
+    type LiveStorage = AllPalletsWithSystem;
     type WeightInfo = eq_migration::weights::EqWeight<Runtime>;
 }
 
@@ -394,17 +420,32 @@ impl eq_oracle::Config for Runtime {
     type XBasePrice = XbasePriceMock<Asset, Balance, FixedI64>;
     type UnsignedLifetimeInBlocks = UnsignedLifetimeInBlocks;
     type LendingAssetRemoval = EqLending;
+    #[cfg(not(feature = "production"))]
+    type EqDotPrice = EqWrappedKsm;
+    #[cfg(feature = "production")]
     type EqDotPrice = ();
+    type PriceCircuitBreakerOrigin = EnsureRoot<AccountId>;
+    type SuspectPricePeriod = SuspectPricePeriod;
+    type SuspectPriceConfirmations = SuspectPriceConfirmations;
+    type FeederKeyOverlap = FeederKeyOverlap;
+    type MaxFeederKeys = MaxFeederKeys;
+    type LpPriceAdapter = ();
+    type MaxFinancialRecalcFailures = MaxFinancialRecalcFailures;
+    type MaxPricesPerBatch = MaxPricesPerBatch;
+    type MaxPriceSubmissionsPerBlock = MaxPriceSubmissionsPerBlock;
 }
 
 parameter_types! {
     pub const BailsmenUnsignedPriority: TransactionPriority = TransactionPriority::min_value();
     pub const MaxBailsmenToDistribute: u32 = 20;
     pub const QueueLengthWeightConstant: u32 = 5;
+    pub const BailsmanRedistributionWeightBudget: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(20);
+    pub const MaxPriorityRedistributionsPerBlock: u32 = 100;
 }
 
 impl eq_bailsman::Config for Runtime {
     type PalletId = BailsmanModuleId;
+    type TreasuryModuleId = TreasuryModuleId;
     type PriceGetter = Oracle;
     type UnixTime = EqRate;
     type Balance = eq_primitives::balance::Balance;
@@ -423,12 +464,23 @@ impl eq_bailsman::Config for Runtime {
     type UnsignedPriority = BailsmenUnsignedPriority;
     type MaxBailsmenToDistribute = MaxBailsmenToDistribute;
     type QueueLengthWeightConstant = QueueLengthWeightConstant;
+    type RedistributionWeightBudget = BailsmanRedistributionWeightBudget;
+    type MaxPriorityRedistributionsPerBlock = MaxPriorityRedistributionsPerBlock;
 }
 
 parameter_types! {
     pub const PriceStepCount: u32 = 5;
     pub const PenaltyFee: Balance = 10 * ONE_TOKEN;
     pub const DexUnsignedPriority: TransactionPriority = TransactionPriority::min_value();
+    pub const DexCorridorUpdatePeriod: BlockNumber = HOURS;
+    pub const DexOrderDeposit: Balance = ONE_TOKEN;
+    pub const DexMaxBatchOrders: u32 = 100;
+    pub const DexMaxBatchAuctionMarkets: u32 = 10;
+    pub DexStakerDiscounts: Vec<(Balance, sp_runtime::Percent)> = vec![
+        (10_000 * ONE_TOKEN, sp_runtime::Percent::from_percent(10)),
+        (100_000 * ONE_TOKEN, sp_runtime::Percent::from_percent(25)),
+        (1_000_000 * ONE_TOKEN, sp_runtime::Percent::from_percent(50)),
+    ];
 }
 
 impl eq_dex::Config for Runtime {
@@ -438,8 +490,16 @@ impl eq_dex::Config for Runtime {
     type PriceStepCount = PriceStepCount;
     type PenaltyFee = PenaltyFee;
     type DexUnsignedPriority = DexUnsignedPriority;
+    type CorridorUpdatePeriod = DexCorridorUpdatePeriod;
     type WeightInfo = weights::pallet_dex::WeightInfo<Runtime>;
+    type RiskNotifier = EqMarginCall;
     type ValidatorOffchainBatcher = eq_rate::Pallet<Runtime>;
+    type StakedBalance = ();
+    type StakerDiscounts = DexStakerDiscounts;
+    type OrderDeposit = DexOrderDeposit;
+    type TreasuryAccounting = Treasury;
+    type MaxBatchOrders = DexMaxBatchOrders;
+    type MaxBatchAuctionMarkets = DexMaxBatchAuctionMarkets;
 }
 
 parameter_types! {
@@ -447,6 +507,16 @@ parameter_types! {
     pub MaintenanceMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(1, 10);
     pub CriticalMargin: EqFixedU128 = EqFixedU128::saturating_from_rational(5, 100);
     pub MaintenancePeriod: u64 = 60*60*24;
+    pub const MaxRiskNotifications: u32 = 16;
+    pub const PriceRestorePeriod: BlockNumber = 30 * MINUTES;
+    pub const MaxPendingLiquidations: u32 = 100;
+    pub const MaxPriorityMarginCallsPerBlock: u32 = 50;
+    pub RiskTierMarginMultipliers: eq_primitives::asset::RiskTierParams<EqFixedU128> =
+        eq_primitives::asset::RiskTierParams {
+            tier1: EqFixedU128::from(1),
+            tier2: EqFixedU128::saturating_from_rational(125, 100),
+            tier3: EqFixedU128::saturating_from_rational(150, 100),
+        };
 }
 
 impl eq_margin_call::Config for Runtime {
@@ -461,22 +531,65 @@ impl eq_margin_call::Config for Runtime {
     type CriticalMargin = CriticalMargin;
     type MaintenancePeriod = MaintenancePeriod;
     type OrderAggregates = EqDex;
+    #[cfg(not(feature = "production"))]
+    type DerivativePositions = EqPerps;
+    #[cfg(feature = "production")]
+    type DerivativePositions = ();
     type AssetGetter = EqAssets;
     type SubaccountsManager = Subaccounts;
+    type MaxNotifications = MaxRiskNotifications;
     type WeightInfo = weights::pallet_margin_call::WeightInfo<Runtime>;
+    type LendingIsolation = EqLending;
+    type RiskTierMarginMultipliers = RiskTierMarginMultipliers;
+    type EqCurrency = EqBalances;
+    type TreasuryModuleId = TreasuryModuleId;
+    type CompensationOrigin = EnsureRoot<AccountId>;
+    type PriceRestorePeriod = PriceRestorePeriod;
+    type MaxPendingLiquidations = MaxPendingLiquidations;
+    type KeeperOrigin = eq_whitelists::EnsureWhitelisted<Runtime>;
+    type MaxPriorityMarginCallsPerBlock = MaxPriorityMarginCallsPerBlock;
 }
 
 parameter_types! {
     pub const MaxSignatories: u32 = 10;
+    pub const MultisigProposalLifetime: BlockNumber = 7 * DAYS;
 }
 
 impl eq_multisig_sudo::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type RuntimeCall = RuntimeCall;
     type MaxSignatories = MaxSignatories;
+    type ProposalLifetime = MultisigProposalLifetime;
+    type VetoOrigin = EnsureRoot<AccountId>;
+    type ProposalFilter = MultisigProposalFilter;
+    type XcmAllowListOrigin = EnsureRoot<AccountId>;
     type WeightInfo = weights::pallet_multisig_sudo::WeightInfo<Runtime>;
 }
 
+/// XCM messages sent by multisig proposals may `Transact` only calls allowed
+/// for their destination by `EqMultisigSudo` allow-list
+pub struct MultisigProposalFilter;
+impl Contains<RuntimeCall> for MultisigProposalFilter {
+    fn contains(call: &RuntimeCall) -> bool {
+        match call {
+            RuntimeCall::PolkadotXcm(pallet_xcm::Call::send { dest, message }) => {
+                EqMultisigSudo::is_xcm_send_allowed(dest, message)
+            }
+            RuntimeCall::Utility(
+                pallet_utility::Call::batch { calls, .. }
+                | pallet_utility::Call::batch_all { calls, .. }
+                | pallet_utility::Call::force_batch { calls, .. },
+            ) => calls.iter().all(Self::contains),
+            RuntimeCall::Utility(
+                pallet_utility::Call::as_derivative { call, .. }
+                | pallet_utility::Call::dispatch_as { call, .. }
+                | pallet_utility::Call::with_weight { call, .. },
+            ) => Self::contains(call),
+            _ => true,
+        }
+    }
+}
+
 parameter_types! {
     pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
 }
@@ -496,6 +609,10 @@ parameter_types! {
     pub const ExistentialDeposit: Balance = EXISTENSIAL_DEPOSIT; // 0.1 USD
     pub const ExistentialDepositBasic: Balance = EXISTENSIAL_DEPOSIT_BASIC; // 100 GENS
     pub const ExistentialDepositEq: Balance = EXISTENSIAL_DEPOSIT_BASIC; // Temporary value for compatibility
+    /// Initial per-asset existential deposits, later managed by `EqAssets::set_existential_deposit`
+    pub ExistentialDepositOverrides: Vec<(eq_primitives::asset::Asset, Balance)> = vec![];
+    pub RiskTierOverrides: Vec<(eq_primitives::asset::Asset, eq_primitives::asset::RiskTier)> =
+        vec![];
     pub const BasicCurrencyGet: eq_primitives::asset::Asset = eq_primitives::asset::GENS;
     pub const RelayCurrencyGet: eq_primitives::asset::Asset = eq_primitives::asset::KSM;
 }
@@ -648,6 +765,29 @@ impl<'xcm, Call>
     }
 }
 
+/// Registers queries for unspent fee of XCM transfers in PolkadotXcm
+pub struct XcmRefundQueryHandler;
+impl eq_balances::XcmRefundQueryHandler<Runtime> for XcmRefundQueryHandler {
+    fn new_notify_query(
+        responder: MultiLocation,
+        notify: eq_balances::Call<Runtime>,
+        timeout: BlockNumber,
+        match_querier: MultiLocation,
+    ) -> Option<xcm::v3::QueryId> {
+        Some(PolkadotXcm::new_notify_query(
+            responder,
+            RuntimeCall::EqBalances(notify),
+            timeout,
+            match_querier,
+        ))
+    }
+}
+
+parameter_types! {
+    pub const XcmRefundTimeout: BlockNumber = 100;
+    pub const MigratableLockOwner: eq_primitives::balance::LockOwner = eq_staking::STAKING_ID;
+}
+
 impl eq_balances::Config for Runtime {
     type AssetGetter = eq_assets::Pallet<Runtime>;
     /// The type for recording an account's balance.
@@ -685,6 +825,10 @@ impl eq_balances::Config for Runtime {
     type ToggleTransferOrigin = EnsureRoot<AccountId>;
     type ParachainId = ParachainInfo;
     type UniversalLocation = UniversalLocation;
+    type XcmRefundQueryHandler = XcmRefundQueryHandler;
+    type XcmResponseOrigin = pallet_xcm::EnsureResponse<frame_support::traits::Everything>;
+    type XcmRefundTimeout = XcmRefundTimeout;
+    type MigratableLockOwners = frame_support::traits::Equals<MigratableLockOwner>;
 }
 
 pub type BasicCurrency = eq_primitives::balance_adapter::BalanceAdapter<
@@ -834,6 +978,13 @@ parameter_types! {
     pub const LpPriceBlockTimeout: u64 = PRICE_TIMEOUT_IN_SECONDS * 1000 / MILLISECS_PER_BLOCK;
     pub const UnsignedLifetimeInBlocks: u32 = 5;
     pub const FinancialRecalcPeriodBlocks: BlockNumber  = (1000 * 60 * 60 * 4) / MILLISECS_PER_BLOCK as BlockNumber; // 4 hours in blocks
+    pub const SuspectPricePeriod: BlockNumber = (1000 * 60 * 30) / MILLISECS_PER_BLOCK as BlockNumber; // 30 minutes in blocks
+    pub const SuspectPriceConfirmations: u32 = 3;
+    pub const FeederKeyOverlap: BlockNumber = (1000 * 60 * 60) / MILLISECS_PER_BLOCK as BlockNumber; // 1 hour in blocks
+    pub const MaxFeederKeys: u32 = 4;
+    pub const MaxFinancialRecalcFailures: u32 = 10;
+    pub const MaxPricesPerBatch: u32 = 32;
+    pub const MaxPriceSubmissionsPerBlock: u32 = 64;
     pub const BalancesModuleId: PalletId = PalletId(*b"eq/balan");
 }
 
@@ -841,6 +992,8 @@ parameter_types! {
     pub BuyFee: Permill = PerThing::from_rational::<u32>(1, 1000);
     pub SellFee: Permill = PerThing::from_rational::<u32>(1, 1000);
     pub const MinAmountToBuyout: Balance = 100 * ONE_TOKEN;
+    pub const TreasuryReportPeriod: BlockNumber = 30 * DAYS;
+    pub const MaxTreasuryReports: u32 = 24;
 }
 
 impl eq_treasury::Config for Runtime {
@@ -856,6 +1009,10 @@ impl eq_treasury::Config for Runtime {
     type UnixTime = eq_rate::Pallet<Runtime>;
     type WeightInfo = weights::pallet_treasury::WeightInfo<Runtime>;
     type MinAmountToBuyout = MinAmountToBuyout;
+    type LendingPoolManager = EqLending;
+    type CurveLiquidity = curve_utils::CurveLiquidityAdapter;
+    type ReportPeriod = TreasuryReportPeriod;
+    type MaxReports = MaxTreasuryReports;
 }
 
 parameter_types! {
@@ -878,6 +1035,12 @@ impl sp_runtime::traits::Convert<BlockNumber, Balance> for BlockNumberToBalance
     }
 }
 
+parameter_types! {
+    /// Fee of automatic vesting in `on_idle`, EQ
+    pub const AutoVestFee: Balance = ONE_TOKEN / 10;
+    pub const MaxAutoVestPerBlock: u32 = 20;
+}
+
 type VestingInstance = eq_vesting::Instance1;
 impl eq_vesting::Config<VestingInstance> for Runtime {
     type RuntimeEvent = RuntimeEvent;
@@ -888,6 +1051,10 @@ impl eq_vesting::Config<VestingInstance> for Runtime {
     type WeightInfo = weights::pallet_vesting::WeightInfo<Runtime>;
     type PalletId = VestingModuleId;
     type IsTransfersEnabled = eq_balances::Pallet<Runtime>;
+    type FeeCurrency = BasicCurrency;
+    type AutoVestFee = AutoVestFee;
+    type AutoVestFeeDestination = TreasuryAccount;
+    type MaxAutoVestPerBlock = MaxAutoVestPerBlock;
 }
 
 parameter_types! {
@@ -963,13 +1130,15 @@ impl OnNewAsset for FinancialPalletOnNewAsset {
             );
         }
 
-        // May be that new prices are breaking financial pallet recalculation,
-        // but it is very rare case
-        #[allow(unused_must_use)]
-        let _ = Financial::recalc_inner();
+        // Recalculation is heavy, it is deferred to idle blocks
+        Oracle::enqueue_financial_recalc(&[asset]);
     }
 }
 
+parameter_types! {
+    pub const MaxBudgetMilestones: u32 = 16;
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 type DistriBenchInstance = eq_distribution::Instance16;
 #[cfg(feature = "runtime-benchmarks")]
@@ -982,6 +1151,9 @@ impl eq_distribution::Config<DistriBenchInstance> for Runtime {
     type EqCurrency = EqBalances;
     type ManagementOrigin = EnsureRoot<AccountId>;
     type WeightInfo = weights::pallet_distribution::WeightInfo<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRoot<AccountId>;
+    type MaxMilestones = MaxBudgetMilestones;
 }
 
 type TreasuryInstance = eq_distribution::Instance5;
@@ -994,10 +1166,14 @@ impl eq_distribution::Config<TreasuryInstance> for Runtime {
     type EqCurrency = EqBalances;
     type ManagementOrigin = EnsureRoot<AccountId>;
     type WeightInfo = weights::pallet_distribution::WeightInfo<Runtime>;
+    type RuntimeEvent = RuntimeEvent;
+    type MilestoneOrigin = EnsureRoot<AccountId>;
+    type MaxMilestones = MaxBudgetMilestones;
 }
 
 parameter_types! {
     pub const RateUnsignedPriority: TransactionPriority = TransactionPriority::min_value();
+    pub const RateMaxUnsignedPerBlock: u32 = 256;
 
     pub TreasuryFee: Permill = Permill::from_percent(1);
     pub const WeightFeeTreasury: u32 = 80;
@@ -1041,6 +1217,7 @@ impl eq_rate::Config for Runtime {
     type MarginCallManager = EqMarginCall;
     type AssetGetter = eq_assets::Pallet<Runtime>;
     type UnsignedPriority = RateUnsignedPriority;
+    type MaxUnsignedPerBlock = RateMaxUnsignedPerBlock;
     type WeightInfo = weights::pallet_rate::WeightInfo<Runtime>;
     type RedistributeWeightInfo = WeightInfoGetter;
     type PriceGetter = Oracle;
@@ -1061,6 +1238,8 @@ impl eq_rate::Config for Runtime {
     type LendingModuleId = LendingModuleId;
     type LendingPoolManager = EqLending;
     type LendingAssetRemoval = EqLending;
+    type InterestIncomeReceiver = ();
+    type TreasuryAccounting = Treasury;
     type AutoReinitToggleOrigin = EnsureRoot<AccountId>;
 }
 
@@ -1073,6 +1252,13 @@ impl eq_session_manager::Config for Runtime {
     type WeightInfo = weights::pallet_session_manager::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+    pub const NavSamplingPeriod: BlockNumber = HOURS;
+    pub const NavSamplingFee: Balance = 10 * ONE_TOKEN;
+    pub const MaxNavSamples: u32 = 720; // 30 days of hourly samples
+    pub const MaxNavSampledAccounts: u32 = 100;
+}
+
 impl eq_subaccounts::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type Balance = Balance;
@@ -1083,9 +1269,27 @@ impl eq_subaccounts::Config for Runtime {
     type BailsmenManager = Bailsman;
     type PriceGetter = Oracle;
     type Whitelist = Whitelists;
+    type LendingPoolManager = EqLending;
     type UpdateTimeManager = EqRate;
     type WeightInfo = weights::pallet_subaccounts::WeightInfo<Runtime>;
     type IsTransfersEnabled = EqBalances;
+    type TreasuryModuleId = TreasuryModuleId;
+    type NavSamplingPeriod = NavSamplingPeriod;
+    type NavSamplingFee = NavSamplingFee;
+    type MaxNavSamples = MaxNavSamples;
+    type MaxNavSampledAccounts = MaxNavSampledAccounts;
+}
+
+parameter_types! {
+    pub const LendingRewardAccrualPeriod: u64 = 24 * 60 * 60;
+    pub const MaxLendingRewardCheckpoints: u32 = 600;
+    pub const MaxIsolatedCollateral: u32 = 5;
+    pub RiskTierBorrowCaps: eq_primitives::asset::RiskTierParams<Permill> =
+        eq_primitives::asset::RiskTierParams {
+            tier1: Permill::from_percent(100),
+            tier2: Permill::from_percent(75),
+            tier3: Permill::from_percent(50),
+        };
 }
 
 impl eq_lending::Config for Runtime {
@@ -1101,6 +1305,14 @@ impl eq_lending::Config for Runtime {
     type UnixTime = EqRate;
     type PriceGetter = Oracle;
     type AccountsToMigratePerBlock = AccountsPerBlock;
+    type RewardAccrualPeriod = LendingRewardAccrualPeriod;
+    type IsolationOrigin = EnsureRoot<AccountId>;
+    type MaxIsolatedCollateral = MaxIsolatedCollateral;
+    type RiskTierBorrowCaps = RiskTierBorrowCaps;
+    type ReserveOrigin = EnsureRoot<AccountId>;
+    type TreasuryModuleId = TreasuryModuleId;
+    type MaxRewardCheckpoints = MaxLendingRewardCheckpoints;
+    type LpLendingOrigin = EnsureRoot<AccountId>;
     type WeightInfo = weights::pallet_lending::WeightInfo<Runtime>;
 }
 
@@ -1245,6 +1457,37 @@ pub type LocationToAccountId = (
     eq_xcm::origins::AccountIdConversion<AccountId>,
 );
 
+/// Sibling parachains allowed by governance to trade on behalf of their users
+pub struct TrustedXcmParachains;
+impl Contains<u32> for TrustedXcmParachains {
+    fn contains(para_id: &u32) -> bool {
+        EqDex::trusted_xcm_parachain(para_id)
+    }
+}
+
+/// Derived accounts of trusted sibling parachain users, used only as `Transact` origins,
+/// assets are never withdrawn from or deposited to remote locations through it
+pub type RemoteTraderAccount =
+    eq_xcm::origins::SiblingAccountConversion<AccountId, TrustedXcmParachains>;
+
+/// Calls which trusted sibling parachains may `Transact` on behalf of their users
+pub struct RemoteTraderCalls;
+impl Contains<RuntimeCall> for RemoteTraderCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::EqDex(
+                eq_dex::Call::create_order { .. }
+                    | eq_dex::Call::create_pair_order { .. }
+                    | eq_dex::Call::delete_order_external { .. }
+            ) | RuntimeCall::Subaccounts(
+                eq_subaccounts::Call::transfer_to_subaccount { .. }
+                    | eq_subaccounts::Call::transfer_from_subaccount { .. }
+            )
+        )
+    }
+}
+
 /// Means for transacting assets on this chain or through bridge.
 pub type LocalAssetTransactor = eq_xcm::assets::EqCurrencyAdapter<
     // Our chain's account ID type (we can't get away without mentioning it explicitly):
@@ -1261,6 +1504,8 @@ pub type LocalAssetTransactor = eq_xcm::assets::EqCurrencyAdapter<
     LocationToAccountId,
     // We don't track any teleports.
     (),
+    // Converts deposits worth less than existential deposit or stores them for claim
+    Treasury,
 >;
 
 pub struct TransactIsNotAllowed;
@@ -1277,7 +1522,9 @@ impl<Origin> ConvertOrigin<Origin> for TransactIsNotAllowed {
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
-pub type XcmOriginToTransactDispatchOrigin = TransactIsNotAllowed;
+/// Only users of trusted sibling parachains may `Transact`, as signed derived accounts.
+pub type XcmOriginToTransactDispatchOrigin =
+    SovereignSignedViaLocation<RemoteTraderAccount, RuntimeOrigin>;
 
 parameter_types! {
     // One XCM operation is 1_000_000 weight - almost certainly a conservative estimate.
@@ -1303,7 +1550,7 @@ pub type EqTrader = eq_xcm::assets::EqTrader<
     Oracle,
     TreasuryAccount,
     fee::XcmWeightToFee,
-    FallbackWeightToFee,
+    eq_balances::XcmWeightToFee<Runtime, FallbackWeightToFee>,
 >;
 
 match_types! {
@@ -1318,10 +1565,12 @@ match_types! {
 }
 
 pub type Barrier = (
-    eq_xcm::barrier::AllowReserveAssetDepositedFrom<EqAssets, TrustedOrigins>,
+    eq_xcm::barrier::AllowReserveAssetDepositedFrom<EqAssets, TrustedOrigins, EqBalances>,
     eq_xcm::barrier::AllowReserveTransferAssetsFromAccountId,
     AllowKnownQueryResponses<PolkadotXcm>,
     AllowSubscriptionsFrom<TrustedOrigins>,
+    // Paid `Transact` of users of trusted sibling parachains, fees paid by the sibling
+    eq_xcm::barrier::AllowRemoteTraderTransact<TrustedXcmParachains>,
 );
 
 pub struct XcmConfig;
@@ -1348,8 +1597,8 @@ impl Config for XcmConfig {
     type MessageExporter = ();
     type UniversalLocation = UniversalLocation;
     type UniversalAliases = Nothing;
-    type CallDispatcher = WithOriginFilter<Nothing>;
-    type SafeCallFilter = Nothing;
+    type CallDispatcher = WithOriginFilter<RemoteTraderCalls>;
+    type SafeCallFilter = RemoteTraderCalls;
 }
 
 /// The means for routing XCM messages which are not for local execution into the right message
@@ -1404,7 +1653,7 @@ impl cumulus_pallet_xcmp_queue::Config for Runtime {
     type VersionWrapper = PolkadotXcm;
     type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
     type ControllerOrigin = EnsureRoot<AccountId>;
-    type ControllerOriginConverter = XcmOriginToTransactDispatchOrigin;
+    type ControllerOriginConverter = TransactIsNotAllowed;
     type PriceForSiblingDelivery = ();
     type WeightInfo = ();
 }
@@ -1464,8 +1713,414 @@ impl gens_binary_opt::Config for Runtime {
     type UpdateOnceInBlocks = UpdateOnceInBlocks;
 }
 
+#[cfg(not(feature = "production"))]
+/// Registers transfer-then-Transact queries in PolkadotXcm, the runtime response handler
+pub struct XcmOpsQueryHandler;
+#[cfg(not(feature = "production"))]
+impl eq_xcm_ops::XcmQueryHandler<Runtime> for XcmOpsQueryHandler {
+    fn new_notify_query(
+        responder: MultiLocation,
+        notify: eq_xcm_ops::Call<Runtime>,
+        timeout: BlockNumber,
+        match_querier: MultiLocation,
+    ) -> xcm::v3::QueryId {
+        PolkadotXcm::new_notify_query(
+            responder,
+            RuntimeCall::EqXcmOps(notify),
+            timeout,
+            match_querier,
+        )
+    }
+}
+
+#[cfg(not(feature = "production"))]
+pub struct XcmOpsVersionManager;
+#[cfg(not(feature = "production"))]
+impl eq_xcm_ops::XcmVersionManager for XcmOpsVersionManager {
+    fn subscribe_version_notify(location: MultiLocation) -> DispatchResult {
+        PolkadotXcm::force_subscribe_version_notify(
+            RuntimeOrigin::root(),
+            Box::new(location.into()),
+        )
+    }
+
+    fn known_version(location: &MultiLocation) -> Option<eq_xcm_ops::XcmVersion> {
+        use xcm::GetVersion;
+        PolkadotXcm::get_version_for(location)
+    }
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const XcmOpsQueryTimeout: BlockNumber = 100;
+    pub const MaxTransactCallSize: u32 = 1024;
+    pub const MaxXcmPartners: u32 = 32;
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_xcm_ops::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = eq_balances::Pallet<Runtime>;
+    type XcmRouter = XcmRouter;
+    type XcmQueryHandler = XcmOpsQueryHandler;
+    type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+    type UniversalLocation = UniversalLocation;
+    type QueryTimeout = XcmOpsQueryTimeout;
+    type MaxTransactCallSize = MaxTransactCallSize;
+    type SovereignManagementOrigin = EnsureRoot<AccountId>;
+    type TreasuryAccount = TreasuryAccount;
+    type PartnerManagementOrigin = EnsureRoot<AccountId>;
+    type XcmVersionManager = XcmOpsVersionManager;
+    type MaxXcmPartners = MaxXcmPartners;
+    type WeightInfo = weights::pallet_xcm_ops::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const LpGaugePalletId: PalletId = PalletId(*b"eq/lpgau");
+    pub const LpGaugeMaxBoost: Permill = Permill::from_percent(50);
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_lp_gauge::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = eq_balances::Pallet<Runtime>;
+    type AssetGetter = eq_assets::Pallet<Runtime>;
+    type StakedBalance = ();
+    type GaugeManagementOrigin = EnsureRoot<AccountId>;
+    type RewardsAccount = TreasuryAccount;
+    type PalletId = LpGaugePalletId;
+    type MaxBoost = LpGaugeMaxBoost;
+    type WeightInfo = weights::pallet_lp_gauge::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const XcmStreamMinPeriod: BlockNumber = HOURS;
+    pub const MaxXcmStreamsPerBlock: u32 = 10;
+    pub const MaxXcmStreamsPerAccount: u32 = 16;
+    pub const XcmStreamRetryDelay: BlockNumber = 10 * MINUTES;
+    pub const XcmStreamMaxFailures: u32 = 5;
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_xcm_streams::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = eq_balances::Pallet<Runtime>;
+    type MinPeriod = XcmStreamMinPeriod;
+    type MaxStreamsPerBlock = MaxXcmStreamsPerBlock;
+    type MaxStreamsPerAccount = MaxXcmStreamsPerAccount;
+    type RetryDelay = XcmStreamRetryDelay;
+    type MaxFailures = XcmStreamMaxFailures;
+    type WeightInfo = weights::pallet_xcm_streams::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const FaucetDripPeriod: BlockNumber = DAYS;
+    pub const MaxFaucetDripsPerBlock: u32 = 10;
+    pub const FaucetUnsignedPriority: TransactionPriority = TransactionPriority::min_value();
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_faucet::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = eq_balances::Pallet<Runtime>;
+    type FaucetOrigin = EnsureRoot<AccountId>;
+    type DripPeriod = FaucetDripPeriod;
+    type MaxDripsPerBlock = MaxFaucetDripsPerBlock;
+    type UnsignedPriority = FaucetUnsignedPriority;
+    type WeightInfo = weights::pallet_faucet::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const AccountMigrationDelay: BlockNumber = DAYS;
+}
+
+#[cfg(not(feature = "production"))]
+/// Rejects migration of accounts with state of pallets not moving it to the new account
+pub struct UnmovedStateCheck;
+
+#[cfg(not(feature = "production"))]
+impl eq_primitives::AccountMigration<AccountId> for UnmovedStateCheck {
+    fn can_migrate(from: &AccountId, _to: &AccountId) -> DispatchResult {
+        frame_support::ensure!(
+            EqLending::lender_positions(from).is_empty(),
+            DispatchError::Other("Account has lending deposits")
+        );
+        frame_support::ensure!(
+            eq_lp_gauge::Gauges::<Runtime>::iter_keys()
+                .all(|lp_asset| !eq_lp_gauge::Positions::<Runtime>::contains_key(lp_asset, from)),
+            DispatchError::Other("Account has gauge stakes")
+        );
+
+        Ok(())
+    }
+
+    fn migrate(_from: &AccountId, _to: &AccountId) -> DispatchResult {
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_account_migration::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Signature = Signature;
+    type Signer = <Signature as traits::Verify>::Signer;
+    type Migrations = (
+        UnmovedStateCheck,
+        EqBalances,
+        Vesting,
+        Subaccounts,
+        EqStaking,
+    );
+    type MigrationDelay = AccountMigrationDelay;
+    type WeightInfo = weights::pallet_account_migration::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const WatchtowerBond: Balance = 1_000 * ONE_TOKEN;
+    pub const WatchtowerAlertBounty: Balance = 100 * ONE_TOKEN;
+    pub const WatchtowerFalseAlertSlash: Balance = 250 * ONE_TOKEN;
+    pub const WatchtowerAlertCooldown: BlockNumber = HOURS;
+    pub MinBailsmanCoverage: EqFixedU128 = EqFixedU128::saturating_from_rational(11, 10);
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_watchtower::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqBalances;
+    type AssetGetter = EqAssets;
+    type PriceGetter = Oracle;
+    type BailsmanCoverage = Bailsman;
+    type TreasuryModuleId = TreasuryModuleId;
+    type WatchtowerBond = WatchtowerBond;
+    type AlertBounty = WatchtowerAlertBounty;
+    type FalseAlertSlash = WatchtowerFalseAlertSlash;
+    type AlertCooldown = WatchtowerAlertCooldown;
+    type MinBailsmanCoverage = MinBailsmanCoverage;
+    type WeightInfo = weights::pallet_watchtower::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const PerpsModuleId: PalletId = PalletId(*b"eq/perps");
+    pub const PerpsFundingPeriod: u64 = 60 * 60;
+    pub PerpsMaxFundingRate: FixedI64 = FixedI64::saturating_from_rational(75, 10_000);
+    pub PerpsMaxPriceDeviation: FixedI64 = FixedI64::saturating_from_rational(2, 100);
+    pub PerpsTradeSpread: FixedI64 = FixedI64::saturating_from_rational(1, 1_000);
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_perps::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqBalances;
+    type PriceGetter = Oracle;
+    type MarkPriceGetter = EqDex;
+    type SubaccountsManager = Subaccounts;
+    type MarginCallManager = EqMarginCall;
+    type UnixTime = EqRate;
+    type PerpsModuleId = PerpsModuleId;
+    type FundingPeriod = PerpsFundingPeriod;
+    type MaxFundingRate = PerpsMaxFundingRate;
+    type MaxPriceDeviation = PerpsMaxPriceDeviation;
+    type TradeSpread = PerpsTradeSpread;
+    type WeightInfo = weights::pallet_perps::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub const StakingRewardsModuleId: PalletId = PalletId(*b"eq/stkrw");
+    pub const StakingCustomRewardsModuleId: PalletId = PalletId(*b"eq/stkcr");
+    pub const MaxStakesCount: u32 = 10;
+    pub const StakingRewardsLockPeriod: eq_staking::StakePeriod = eq_staking::StakePeriod::Twelve;
+    pub const MaxRewardExternalIdsCount: u32 = 1000;
+    /// Stakes are not removed in `on_initialize`
+    pub const StakingAccountsPerBlock: u32 = 0;
+    pub const StakingEmissionEraDuration: u64 = 7 * 24 * 60 * 60;
+}
+
+#[cfg(not(feature = "production"))]
+pub struct StakingRewardsAccount;
+#[cfg(not(feature = "production"))]
+impl Get<AccountId> for StakingRewardsAccount {
+    fn get() -> AccountId {
+        StakingRewardsModuleId::get().into_account_truncating()
+    }
+}
+
+#[cfg(not(feature = "production"))]
+pub struct StakingCustomRewardsAccount;
+#[cfg(not(feature = "production"))]
+impl Get<AccountId> for StakingCustomRewardsAccount {
+    fn get() -> AccountId {
+        StakingCustomRewardsModuleId::get().into_account_truncating()
+    }
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_staking::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type EqCurrency = EqBalances;
+    type BalanceGetter = EqBalances;
+    type LockGetter = EqBalances;
+    type UnixTime = EqRate;
+    type MaxStakesCount = MaxStakesCount;
+    type RewardManagementOrigin = EnsureRoot<AccountId>;
+    type LiquidityAccount = StakingRewardsAccount;
+    type LiquidityAccountCustom = StakingCustomRewardsAccount;
+    type RewardsLockPeriod = StakingRewardsLockPeriod;
+    type WeightInfo = weights::pallet_staking::WeightInfo<Runtime>;
+    type MaxRewardExternalIdsCount = MaxRewardExternalIdsCount;
+    type AccountsPerBlock = StakingAccountsPerBlock;
+    // no democracy in Genshiro, `vote_with_stake` always fails
+    type ConvictionVoting = ();
+    type TreasuryAccount = TreasuryAccount;
+    type EmissionEraDuration = StakingEmissionEraDuration;
+}
+
+#[cfg(not(feature = "production"))]
+pub struct EqXdotAssetsAdapter;
+#[cfg(not(feature = "production"))]
+impl eq_xdot_pool::traits::Assets<AssetId, Balance, AccountId> for EqXdotAssetsAdapter {
+    fn create_lp_asset(pool_id: eq_xdot_pool::PoolId) -> Result<AssetId, DispatchError> {
+        EqAssets::create_derivative_asset(
+            b"xlp",
+            pool_id,
+            eq_assets::DerivativeAssetParams::lp_token(
+                eq_primitives::asset::AssetType::Lp(eq_primitives::asset::AmmPool::Yield(pool_id)),
+                LPTokensDebtWeight::get(),
+                LpTokenBuyoutPriority::get(),
+            ),
+        )
+    }
+
+    fn mint(asset: AssetId, dest: &AccountId, amount: Balance) -> DispatchResult {
+        EqBalances::deposit_creating(dest, asset, amount, true, None)
+    }
+
+    fn burn(asset: AssetId, dest: &AccountId, amount: Balance) -> DispatchResult {
+        EqBalances::withdraw(
+            dest,
+            asset,
+            amount,
+            true,
+            None,
+            WithdrawReasons::empty(),
+            ExistenceRequirement::AllowDeath,
+        )
+    }
+
+    fn transfer(
+        asset: AssetId,
+        source: &AccountId,
+        dest: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        EqBalances::currency_transfer(
+            source,
+            dest,
+            asset,
+            amount,
+            ExistenceRequirement::AllowDeath,
+            TransferReason::Common,
+            true,
+        )
+    }
+
+    fn balance(asset: AssetId, who: &AccountId) -> Balance {
+        EqBalances::free_balance(who, asset)
+    }
+
+    fn total_issuance(asset: AssetId) -> Balance {
+        EqAggregates::get_total(UserGroup::Balances, asset).collateral
+    }
+}
+
+#[cfg(not(feature = "production"))]
+pub struct XdotNumberPriceConvert;
+#[cfg(not(feature = "production"))]
+impl Convert<XdotNumber, FixedI64> for XdotNumberPriceConvert {
+    fn convert(n: XdotNumber) -> FixedI64 {
+        eq_utils::fixed::i64f64_to_fixedi64(n)
+    }
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_xdot_pool::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type PoolsManagementOrigin = EnsureRoot<AccountId>;
+    type FixedNumberBits = i128;
+    type XdotNumber = XdotNumber;
+    type NumberConvert = eq_xdot_pool::yield_math::YieldConvert;
+    type BalanceConvert = eq_primitives::xdot_pool::XdotBalanceConvert;
+    type Assets = EqXdotAssetsAdapter;
+    type AssetId = AssetId;
+    type YieldMath = eq_xdot_pool::yield_math::YieldMath<
+        XdotNumber,
+        eq_xdot_pool::yield_math::YieldConvert,
+    >;
+    type PriceNumber = FixedI64;
+    type PriceConvert = XdotNumberPriceConvert;
+    type FixedNumberConvert = eq_primitives::xdot_pool::XdotFixedNumberConvert;
+    type OnPoolInitialized = ();
+    type AssetChecker = ();
+    type WeightInfo = weights::pallet_xdot_pool::WeightInfo<Runtime>;
+}
+
+#[cfg(not(feature = "production"))]
+use eq_xcm::relay_interface::{call::RelayChainCallBuilder, config::RelayRuntime};
+
+#[cfg(not(feature = "production"))]
+parameter_types! {
+    pub TargetReserve: Permill = Permill::from_percent(15);
+    pub MaxReserve: Permill = Permill::from_percent(20);
+    pub MinReserve: Permill = Permill::from_percent(10);
+    pub EqKsmWithdrawFee: Permill = Permill::from_rational(989_409_u32, 1_000_000_u32);
+    pub const MinStakingDeposit: Balance = 1 * ONE_TOKEN;
+    pub const WrappedKsmPalletId: PalletId = PalletId(*b"eq/wrksm");
+    pub const RelayAsset: Asset = eq_primitives::asset::KSM;
+    pub const WrappedRelayAsset: Asset = eq_primitives::asset::EQKSM;
+    pub const RelayDecimals: u8 = eq_wrapped_dot::KSM_DECIMALS;
+    pub const RelayBondingDuration: u32 = 28;
+    pub const RelayEraLength: BlockNumber = 6 * HOURS;
+}
+
+#[cfg(not(feature = "production"))]
+impl eq_wrapped_dot::Config for Runtime {
+    type StakingInitializeOrigin = EnsureRoot<AccountId>;
+    type Balance = Balance;
+    type Aggregates = EqAggregates;
+    type TargetReserve = TargetReserve;
+    type MaxReserve = MaxReserve;
+    type MinReserve = MinReserve;
+    type MinDeposit = MinStakingDeposit;
+    type RelayChainCallBuilder = RelayChainCallBuilder<RelayRuntime, ParachainInfo>;
+    type XcmRouter = XcmRouter;
+    type ParachainId = ParachainInfo;
+    type RelayAsset = RelayAsset;
+    type WrappedAsset = WrappedRelayAsset;
+    type RelayDecimals = RelayDecimals;
+    type PriceGetter = Oracle;
+    type EqCurrency = EqBalances;
+    type WithdrawFee = EqKsmWithdrawFee;
+    type PalletId = WrappedKsmPalletId;
+    type BondingDuration = RelayBondingDuration;
+    type EraLength = RelayEraLength;
+    type WeightInfo = weights::pallet_wrapped_dot::WeightInfo<Runtime>;
+}
+
 use eq_primitives::{
-    asset::{Asset, AssetXcmData, OnNewAsset},
+    asset::{Asset, OnNewAsset},
     balance::AccountData,
     balance_number::EqFixedU128,
     curve_number::{CurveNumber, CurveNumberConvert},
@@ -1497,7 +2152,7 @@ construct_runtime!(
 
         EqAssets: eq_assets::{Pallet, Call, Config<T>, Storage, Event}, // Assets genesis must be built first
         Oracle: eq_oracle::{Pallet, Call, Storage, Event<T>, Config, ValidateUnsigned},
-        EqTreasury: eq_distribution::<Instance5>::{Pallet, Call, Storage, Config},
+        EqTreasury: eq_distribution::<Instance5>::{Pallet, Call, Storage, Config, Event<T>},
         Treasury: eq_treasury::{Pallet, Call, Storage, Config, Event<T>},
         EqBalances: eq_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         // ..... //
@@ -1526,6 +2181,28 @@ construct_runtime!(
         PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Storage, Origin, Config},
         DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>},
         XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>},
+
+        // compiled out in production, last to keep indices of other pallets the same
+        #[cfg(not(feature = "production"))]
+        EqLpGauge: eq_lp_gauge::{Pallet, Call, Storage, Event<T>},
+        #[cfg(not(feature = "production"))]
+        EqXcmOps: eq_xcm_ops::{Pallet, Call, Storage, Event<T>},
+        #[cfg(not(feature = "production"))]
+        EqXcmStreams: eq_xcm_streams::{Pallet, Call, Storage, Event<T>},
+        #[cfg(not(feature = "production"))]
+        EqWrappedKsm: eq_wrapped_dot::{Pallet, Call, Storage, Config},
+        #[cfg(not(feature = "production"))]
+        EqAccountMigration: eq_account_migration::{Pallet, Call, Storage, Event<T>},
+        #[cfg(not(feature = "production"))]
+        EqWatchtower: eq_watchtower::{Pallet, Call, Storage, Event<T>},
+        #[cfg(not(feature = "production"))]
+        EqPerps: eq_perps::{Pallet, Call, Storage, Event<T>},
+        #[cfg(not(feature = "production"))]
+        EqStaking: eq_staking::{Pallet, Call, Storage, Event<T>},
+        #[cfg(not(feature = "production"))]
+        EqXdotPool: eq_xdot_pool::{Pallet, Call, Storage, Event<T>},
+        #[cfg(not(feature = "production"))]
+        EqFaucet: eq_faucet::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
     }
 );
 
@@ -1597,35 +2274,24 @@ struct OldPoolInfo<AccountId, AssetId, Number, Balance> {
 
 pub struct CustomOnRuntimeUpgrade;
 
-impl frame_support::traits::OnRuntimeUpgrade for CustomOnRuntimeUpgrade {}
-
-pub struct AssetGenerator;
-
-impl AssetGenerator {
-    fn generate_asset_for_pool(pool_id: u32, name_base: Vec<u8>) -> eq_primitives::asset::Asset {
-        let zero_in_bytes = '0' as u8;
-        let mut pool_id_bytes: Vec<u8> = Vec::new();
-        let mut pool_id_for_bytes = pool_id;
-        while pool_id_for_bytes > 0 {
-            let byte = TryInto::<u8>::try_into(pool_id_for_bytes % 10).unwrap() + zero_in_bytes;
-            pool_id_bytes.push(byte);
-            pool_id_for_bytes = pool_id_for_bytes / 10;
-        }
-
-        // special case for 0, we want lpt0 instead of just lpt
-        if pool_id == 0 {
-            pool_id_bytes.push(zero_in_bytes);
-        }
-
-        pool_id_bytes.reverse();
+impl frame_support::traits::OnRuntimeUpgrade for CustomOnRuntimeUpgrade {
+    fn on_runtime_upgrade() -> Weight {
+        let weight = eq_balances::migrations::migrate_locks::<Runtime>()
+            .saturating_add(eq_assets::migration::migrate_existential_deposits::<
+                Runtime,
+                ExistentialDepositOverrides,
+            >())
+            .saturating_add(eq_assets::migration::migrate_risk_tiers::<
+                Runtime,
+                RiskTierOverrides,
+            >())
+            .saturating_add(eq_bridge::migration::migrate_paused_resources::<Runtime>())
+            .saturating_add(eq_migration::schedule_storage_cleanups::<Runtime>());
+        #[cfg(not(feature = "production"))]
+        let weight =
+            weight.saturating_add(eq_wrapped_dot::migration::migrate_withdraw_queue::<Runtime>());
 
-        let name: Vec<u8> = name_base
-            .iter()
-            .chain(pool_id_bytes.iter())
-            .map(|item| item.clone())
-            .collect();
-        eq_primitives::asset::Asset::from_bytes(&name)
-            .expect("Asset name cannot has wrong symbols!")
+        weight
     }
 }
 
@@ -1633,27 +2299,16 @@ pub struct EqCurveAssetsAdapter;
 type AssetId = eq_primitives::asset::Asset;
 impl equilibrium_curve_amm::traits::Assets<AssetId, Balance, AccountId> for EqCurveAssetsAdapter {
     fn create_asset(pool_id: equilibrium_curve_amm::PoolId) -> Result<AssetId, DispatchError> {
-        let asset = AssetGenerator::generate_asset_for_pool(pool_id, b"lpt".to_vec());
-
-        EqAssets::do_add_asset(
-            asset,
-            EqFixedU128::from(0),
-            FixedI64::from(0),
-            Permill::zero(),
-            Permill::zero(),
-            AssetXcmData::None,
-            LPTokensDebtWeight::get(),
-            LpTokenBuyoutPriority::get(),
-            eq_primitives::asset::AssetType::Lp(eq_primitives::asset::AmmPool::Curve(pool_id)),
-            false,
-            Percent::zero(),
-            Permill::one(),
-            // prices will be set at at OnPoolCreated
-            vec![],
+        // prices will be set at OnPoolCreated
+        EqAssets::create_derivative_asset(
+            b"lpt",
+            pool_id,
+            eq_assets::DerivativeAssetParams::lp_token(
+                eq_primitives::asset::AssetType::Lp(eq_primitives::asset::AmmPool::Curve(pool_id)),
+                LPTokensDebtWeight::get(),
+                LpTokenBuyoutPriority::get(),
+            ),
         )
-        .map_err(|e| e.error)?;
-
-        Ok(asset)
     }
 
     fn mint(asset: AssetId, dest: &AccountId, amount: Balance) -> DispatchResult {
@@ -1748,7 +2403,7 @@ impl equilibrium_curve_amm::traits::Assets<AssetId, Balance, AccountId> for EqCu
             0,
             0,
             0,
-            AssetXcmData::None,
+            eq_primitives::asset::AssetXcmData::None,
             EqFixedU128::saturating_from_rational(2, 5).into_inner() as u128,
             0,
             eq_primitives::asset::AssetType::Native,
@@ -1773,7 +2428,6 @@ mod curve_utils {
     use eq_utils::{fixedi64_to_i64f64, i64f64_to_fixedi64};
     use equilibrium_curve_amm::traits::CurveAmm;
     use equilibrium_curve_amm::PoolId;
-    use financial_pallet::FinancialSystemTrait;
     use financial_pallet::PriceLogs;
     use financial_pallet::{
         get_index_range, get_period_id_range, get_range_intersection, PriceLog,
@@ -1839,6 +2493,39 @@ mod curve_utils {
         }
     }
 
+    pub struct CurveLiquidityAdapter;
+
+    impl eq_primitives::CurveLiquidity<AccountId, Balance> for CurveLiquidityAdapter {
+        fn pool_balances(pool_id: PoolId) -> Option<Vec<(AssetId, Balance)>> {
+            super::CurveAmm::pool(pool_id)
+                .map(|pool| pool.assets.into_iter().zip(pool.balances).collect())
+        }
+
+        fn add_liquidity(
+            who: &AccountId,
+            pool_id: PoolId,
+            amounts: Vec<Balance>,
+            min_mint_amount: Balance,
+        ) -> DispatchResult {
+            <super::CurveAmm as CurveAmm>::add_liquidity(who, pool_id, amounts, min_mint_amount)
+                .map(|_| ())
+                .map_err(|e| e.error)
+        }
+
+        fn exchange(
+            who: &AccountId,
+            pool_id: PoolId,
+            i: u32,
+            j: u32,
+            dx: Balance,
+            min_dy: Balance,
+        ) -> DispatchResult {
+            <super::CurveAmm as CurveAmm>::exchange(who, pool_id, i, j, dx, min_dy)
+                .map(|_| ())
+                .map_err(|e| e.error)
+        }
+    }
+
     pub struct OnPoolCreated;
 
     impl equilibrium_curve_amm::traits::OnPoolCreated for OnPoolCreated {
@@ -1912,10 +2599,8 @@ mod curve_utils {
                 },
             );
 
-            // May be that new prices are breaking financial pallet recalculation,
-            // but it is very rare case
-            #[allow(unused_must_use)]
-            let _ = Financial::recalc_inner();
+            // Recalculation is heavy, it is deferred to idle blocks
+            Oracle::enqueue_financial_recalc(&[pool.pool_asset]);
         }
     }
 
@@ -2194,12 +2879,266 @@ impl_runtime_apis! {
         }
     }
 
+    impl eq_rate_rpc_runtime_api::EqRateApi<Block, Balance, AccountId> for Runtime {
+        fn interest_statements(account_id: AccountId) -> Vec<(Asset, eq_primitives::InterestStatement<Balance>)> {
+            EqRate::interest_statements(&account_id)
+        }
+    }
+
+    impl eq_treasury_rpc_runtime_api::EqTreasuryApi<Block, Balance, BlockNumber> for Runtime {
+        fn treasury_reports() -> Vec<eq_treasury::TreasuryReport<Balance, BlockNumber>> {
+            Treasury::treasury_reports()
+        }
+
+        fn current_treasury_report() -> eq_treasury::TreasuryReport<Balance, BlockNumber> {
+            Treasury::current_treasury_report()
+        }
+    }
+
+    impl eq_lending_rpc_runtime_api::EqLendingApi<Block, Balance, AccountId, BlockNumber> for Runtime {
+        fn reward_index(asset: Asset) -> EqFixedU128 {
+            EqLending::reward_index(asset)
+        }
+
+        fn pending_reward(account_id: AccountId, asset: Asset) -> Balance {
+            EqLending::pending_reward(&account_id, asset)
+        }
+
+        fn reserve(asset: Asset) -> Balance {
+            EqLending::reserve(asset)
+        }
+
+        fn reward_checkpoints(asset: Asset) -> Vec<eq_primitives::lending::RewardCheckpoint<BlockNumber>> {
+            EqLending::reward_checkpoints(asset).into_inner()
+        }
+
+        fn reward_between(asset: Asset, deposit: Balance, from: BlockNumber, to: BlockNumber) -> Option<Balance> {
+            EqLending::reward_between(asset, deposit, from, to)
+        }
+    }
+
+    impl eq_lending_rpc_runtime_api::EqSolvencyApi<Block, Balance> for Runtime {
+        fn solvency_metrics() -> eq_primitives::solvency::SolvencyMetrics<Balance> {
+            use eq_primitives::{
+                asset::AssetGetter,
+                balance::BalanceGetter,
+                solvency::{AssetSolvency, SolvencyMetrics},
+                PriceGetter,
+            };
+
+            let assets: Vec<_> = EqAssets::get_assets()
+                .into_iter()
+                .map(EqLending::asset_solvency)
+                .collect();
+            // assets without price are not counted
+            let total_usd = |amount: fn(&AssetSolvency<Balance>) -> Balance| -> Balance {
+                assets
+                    .iter()
+                    .filter_map(|a| {
+                        let price: EqFixedU128 = Oracle::get_price(&a.asset).ok()?;
+                        Some(price.saturating_mul_int(amount(a)))
+                    })
+                    .fold(0, |total, usd| total.saturating_add(usd))
+            };
+
+            SolvencyMetrics {
+                total_collateral_usd: total_usd(|a| a.collateral),
+                total_debt_usd: total_usd(|a| a.debt),
+                bailsman_coverage: Bailsman::coverage_ratio(),
+                treasury: EqBalances::iterate_account_balances(&Treasury::account_id()).into(),
+                assets,
+            }
+        }
+    }
+
+    impl eq_bailsman_rpc_runtime_api::EqBailsmanApi<Block, Balance, AccountId> for Runtime {
+        fn liquidity_line() -> Option<eq_primitives::LiquidityLineUtilization<Balance>> {
+            Bailsman::liquidity_line_utilization()
+        }
+
+        fn queued_distributions(bailsman: AccountId) -> Vec<eq_primitives::QueuedDistribution<Balance>> {
+            Bailsman::queued_distributions(&bailsman).unwrap_or_default()
+        }
+    }
+
+    impl eq_margin_call_rpc_runtime_api::EqMarginCallApi<Block, AccountId, Balance> for Runtime {
+        fn notifications(account_id: AccountId) -> Vec<eq_primitives::RiskNotification<AccountId>> {
+            EqMarginCall::get_notifications(&account_id)
+        }
+
+        fn simulate(
+            account_id: AccountId,
+            balance_changes: Vec<(Asset, eq_primitives::SignedBalance<Balance>)>,
+            order_changes: Vec<eq_primitives::OrderChange>,
+        ) -> Option<eq_primitives::MarginSimulation> {
+            EqMarginCall::simulate_margin(&account_id, balance_changes, order_changes).ok()
+        }
+    }
+
     impl eq_balances_rpc_runtime_api::EqBalancesApi<Block, Balance, AccountId> for Runtime {
-        fn wallet_balance_in_usd(_account_id: AccountId) -> Option<Balance> {
-            None
+        fn wallet_balance_in_usd(account_id: AccountId) -> Option<Balance> {
+            use eq_primitives::balance::{BalanceGetter, DebtCollateralDiscounted};
+
+            let DebtCollateralDiscounted {debt, collateral, discounted_collateral: _} = EqBalances::get_debt_and_collateral(&account_id).ok()?;
+            collateral.checked_sub(debt)
         }
-        fn portfolio_balance_in_usd(_account_id: AccountId) -> Option<Balance> {
-            None
+        fn portfolio_balance_in_usd(account_id: AccountId) -> Option<Balance> {
+            use eq_primitives::{balance::{BalanceGetter, DebtCollateralDiscounted}, subaccount::{SubAccType, SubaccountsManager}};
+
+            let DebtCollateralDiscounted { mut debt, mut collateral, discounted_collateral: _} = EqBalances::get_debt_and_collateral(&account_id).ok()?;
+            for subacc_type in SubAccType::iterator() {
+                if let Some(subacc_id) = Subaccounts::get_subaccount_id(&account_id, &subacc_type) {
+                    let DebtCollateralDiscounted { debt: subacc_debt, collateral: subacc_collateral, discounted_collateral: _ } = EqBalances::get_debt_and_collateral(&subacc_id).ok()?;
+                    debt = debt.saturating_add(subacc_debt);
+                    collateral = collateral.saturating_add(subacc_collateral);
+                }
+            }
+            collateral.checked_sub(debt)
+        }
+        fn can_transfer(from: AccountId, to: AccountId, asset: Asset, amount: Balance) -> eq_primitives::balance::TransferCheck {
+            use eq_primitives::balance::{TransferCheck, TransferSide};
+
+            let mut check = EqBalances::can_transfer(&from, &to, asset, amount);
+            if let TransferCheck::CheckFailed { side: TransferSide::Sender, ref mut margin, .. } = check {
+                let change = eq_primitives::BalanceChange { change: eq_primitives::SignedBalance::Negative(amount), asset };
+                *margin = EqMarginCall::margin_shortfall(&from, &[change]).ok().flatten();
+            }
+            check
+        }
+        fn account_portfolio(account_id: AccountId) -> eq_primitives::portfolio::AccountPortfolio<AccountId, Balance> {
+            use eq_primitives::{balance::BalanceGetter, portfolio::{AccountPortfolio, SubaccountPortfolio}, subaccount::{SubAccType, SubaccountsManager}};
+            use frame_support::traits::VestingSchedule;
+
+            let balances = |who: &AccountId| -> Vec<_> { EqBalances::iterate_account_balances(who).into() };
+            let subaccounts = SubAccType::iterator()
+                .filter_map(|subacc_type| {
+                    let subacc_id = Subaccounts::get_subaccount_id(&account_id, &subacc_type)?;
+                    Some(SubaccountPortfolio {
+                        subacc_type,
+                        balances: balances(&subacc_id),
+                        orders: EqDex::account_orders(&subacc_id),
+                        account_id: subacc_id,
+                    })
+                })
+                .collect();
+            let vesting = IntoIterator::into_iter([
+                (BasicCurrencyGet::get(), <Vesting as VestingSchedule<AccountId>>::vesting_balance(&account_id)),
+            ])
+            .filter_map(|(asset, locked)| Some((asset, locked?)))
+            .collect();
+
+            AccountPortfolio {
+                balances: balances(&account_id),
+                reserved: eq_balances::Reserved::<Runtime>::iter_prefix(&account_id).collect(),
+                locks: eq_balances::Pallet::<Runtime>::account_locks(&account_id)
+                    .into_iter()
+                    .map(|((asset, id), lock)| (asset, id, lock.amount))
+                    .collect(),
+                subaccounts,
+                orders: EqDex::account_orders(&account_id),
+                lending: EqLending::lender_positions(&account_id),
+                vesting,
+            }
+        }
+
+        fn validate_destination(
+            asset: eq_primitives::asset::Asset,
+            to: eq_primitives::AccountType,
+        ) -> Result<MultiLocation, sp_runtime::DispatchError> {
+            EqBalances::validate_destination(asset, to)
+        }
+    }
+
+    impl eq_balances_rpc_runtime_api::EqStateExportApi<Block, AccountId> for Runtime {
+        fn balances(account_id: AccountId) -> eq_primitives::state_export::VersionedBalances<AccountId> {
+            use eq_primitives::{balance::BalanceGetter, state_export::{BalancesV1, VersionedBalances}};
+
+            let balances = EqBalances::iterate_account_balances(&account_id)
+                .into_iter()
+                .filter(|(_, balance)| !sp_runtime::traits::Zero::is_zero(balance))
+                .collect();
+
+            VersionedBalances::V1(BalancesV1 {
+                account: account_id,
+                block_number: System::block_number(),
+                balances,
+            })
+        }
+
+        fn prices(assets: Vec<Asset>) -> eq_primitives::state_export::VersionedPrices {
+            use eq_primitives::{asset::AssetGetter, state_export::{PriceV1, VersionedPrices}};
+
+            let assets = if assets.is_empty() { EqAssets::get_assets() } else { assets };
+            let prices = assets
+                .into_iter()
+                .filter_map(|asset| {
+                    let price_point = Oracle::price_points(asset)?;
+                    (price_point.get_timestamp() != 0).then(|| PriceV1 {
+                        asset,
+                        price: FixedI64::from_inner(price_point.get_price()),
+                        timestamp: price_point.get_timestamp(),
+                        block_number: *price_point.get_block_number(),
+                    })
+                })
+                .collect();
+
+            VersionedPrices::V1(prices)
+        }
+    }
+
+    impl eq_multisig_sudo_rpc_runtime_api::EqCallFilterApi<Block, RuntimeCall> for Runtime {
+        fn validate_calls(calls: Vec<RuntimeCall>) -> Vec<FilterVerdictWithReason> {
+            calls.iter().map(CallFilter::verdict).collect()
+        }
+    }
+
+    impl eq_bridge_rpc_runtime_api::EqBridgeApi<Block, chainbridge::ResourceId, Balance, BlockNumber> for Runtime {
+        fn reserve_status(resource_id: chainbridge::ResourceId) -> Option<eq_bridge::ReserveStatus<Balance, BlockNumber>> {
+            EqBridge::reserve_status(resource_id)
+        }
+
+        fn reserve_statuses() -> Vec<(chainbridge::ResourceId, eq_bridge::ReserveStatus<Balance, BlockNumber>)> {
+            EqBridge::reserve_statuses()
+        }
+    }
+
+    impl eq_wrapped_dot_rpc_runtime_api::EqWrappedDotApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn unbonding_lots(who: AccountId) -> Vec<eq_wrapped_dot::UnbondingLot<AccountId, Balance>> {
+            #[cfg(not(feature = "production"))]
+            let lots = EqWrappedKsm::unbonding_lots(&who);
+            #[cfg(feature = "production")]
+            let lots = {
+                let _ = who;
+                Vec::new()
+            };
+
+            lots
+        }
+
+        fn lot_availability(lot_id: eq_wrapped_dot::UnbondingLotId) -> Option<eq_wrapped_dot::LotAvailability<BlockNumber>> {
+            #[cfg(not(feature = "production"))]
+            let availability = EqWrappedKsm::lot_availability(lot_id);
+            #[cfg(feature = "production")]
+            let availability = {
+                let _ = lot_id;
+                None
+            };
+
+            availability
+        }
+    }
+
+    impl eq_subaccounts_rpc_runtime_api::EqSubaccountsApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn nav_history(account_id: AccountId) -> Vec<eq_subaccounts::NavSample<Balance, BlockNumber>> {
+            Subaccounts::nav_history(account_id)
+        }
+
+        fn subaccounts(account_id: AccountId) -> Vec<eq_subaccounts::SubaccountInfo<AccountId, BlockNumber>> {
+            Subaccounts::subaccounts_info(&account_id)
+        }
+
+        fn subaccount_owner(subaccount: AccountId) -> Option<(AccountId, eq_primitives::subaccount::SubAccType)> {
+            Subaccounts::owner_account(subaccount)
         }
     }
 
@@ -2349,54 +3288,3 @@ cumulus_pallet_parachain_system::register_validate_block! {
     BlockExecutor = cumulus_pallet_aura_ext::BlockExecutor::<Runtime, Executive>,
     CheckInherents = CheckInherents,
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn generate_lp_token_asset_works() {
-        let id: u32 = 1234u32;
-        let name: Vec<u8> = b"lpt"
-            .iter()
-            .chain(id.to_string().as_bytes().iter())
-            .map(|item| item.clone())
-            .collect();
-        let asset = eq_primitives::asset::Asset::from_bytes(&name).unwrap();
-        assert_eq!(
-            asset,
-            AssetGenerator::generate_asset_for_pool(id, b"lpt".to_vec())
-        );
-    }
-
-    #[test]
-    fn generate_lp_token_asset_works_for0() {
-        let id: u32 = 0u32;
-        let name: Vec<u8> = b"lpt"
-            .iter()
-            .chain(id.to_string().as_bytes().iter())
-            .map(|item| item.clone())
-            .collect();
-        let _asset = eq_primitives::asset::Asset::from_bytes(&name).unwrap();
-        let asset = eq_primitives::asset::Asset::from_bytes(&name).unwrap();
-        assert_eq!(
-            asset,
-            AssetGenerator::generate_asset_for_pool(id, b"lpt".to_vec())
-        );
-    }
-
-    #[test]
-    fn generate_lp_token_asset_works_for1() {
-        let id: u32 = 1u32;
-        let name: Vec<u8> = b"lpt"
-            .iter()
-            .chain(id.to_string().as_bytes().iter())
-            .map(|item| item.clone())
-            .collect();
-        let asset = eq_primitives::asset::Asset::from_bytes(&name).unwrap();
-        assert_eq!(
-            asset,
-            AssetGenerator::generate_asset_for_pool(id, b"lpt".to_vec())
-        );
-    }
-}
@@ -0,0 +1,61 @@
+
+//! Weights for `eq_account_migration`
+
+// Command to regenerate:
+// ./target/production/eq-node
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// eq_account_migration
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// ./runtime/genshiro/src/weights/pallet_account_migration.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `eq_account_migration`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> eq_account_migration::WeightInfo for WeightInfo<T> {
+	// Storage: EqAccountMigration PendingMigrations (r:1 w:1)
+	// Storage: EqBalances Reserved (r:1 w:0)
+	// Storage: EqBalances Locked (r:2 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Vesting Vesting (r:2 w:0)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: Subaccounts Subaccount (r:8 w:0)
+	fn migrate_account() -> Weight {
+		Weight::from_parts(85_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(17 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqAccountMigration PendingMigrations (r:1 w:1)
+	fn cancel_migration() -> Weight {
+		Weight::from_parts(18_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqAccountMigration PendingMigrations (r:1 w:1)
+	// Storage: EqBalances Reserved (r:1 w:0)
+	// Storage: EqBalances Locked (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Vesting Vesting (r:2 w:0)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: Subaccounts Subaccount (r:8 w:0)
+	fn execute_migration() -> Weight {
+		Weight::from_parts(64_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+}
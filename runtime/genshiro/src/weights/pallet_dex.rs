@@ -119,4 +119,114 @@ impl<T: frame_system::Config> eq_dex::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(20 as u64))
 			.saturating_add(T::DbWeight::get().writes(8 as u64))
 	}
+	// Storage: EqDex BatchAuctionPeriod (r:10 w:1)
+	// Storage: EqDex ActualChunksByAsset (r:1 w:0)
+	// Storage: EqDex OrdersByAssetAndChunkKey (r:1 w:0)
+	// Storage: EqDex BatchOrdersCount (r:0 w:1)
+	/// The range of component `o` is `[1, 100]`.
+	fn set_batch_auction(o: u32, ) -> Weight {
+		Weight::from_parts(24_000_000 as u64, 0)
+			.saturating_add(Weight::from_parts(2_000_000 as u64, 0).saturating_mul(o as u64))
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(o as u64)))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: EqDex ActualChunksByAsset (r:1 w:1)
+	// Storage: EqDex OrdersByAssetAndChunkKey (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:5 w:5)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:4 w:2)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	// Storage: EqDex AssetWeightByAccountId (r:2 w:2)
+	// Storage: EqDex BestPriceByAsset (r:1 w:1)
+	// Storage: EqDex BatchOrdersCount (r:1 w:1)
+	/// The range of component `o` is `[2, 100]`.
+	fn clear_batch_auction(o: u32, ) -> Weight {
+		Weight::from_parts(31_000_000 as u64, 0)
+			.saturating_add(Weight::from_parts(72_000_000 as u64, 0).saturating_mul(o as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((8 as u64).saturating_mul(o as u64)))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+			.saturating_add(T::DbWeight::get().writes((7 as u64).saturating_mul(o as u64)))
+	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqDex TradingPairs (r:0 w:1)
+	fn set_trading_pair() -> Weight {
+		Weight::from_parts(12_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqDex TradingPairs (r:1 w:0)
+	// Storage: Subaccounts Subaccount (r:1 w:0)
+	// Storage: EqAssets Assets (r:4 w:0)
+	// Storage: EqDex BatchAuctionPeriod (r:2 w:0)
+	// Storage: Oracle PricePoints (r:4 w:0)
+	// Storage: EqDex ChunkCorridorByAsset (r:2 w:0)
+	// Storage: EqDex BestPriceByAsset (r:2 w:2)
+	// Storage: System Account (r:6 w:6)
+	// Storage: EqDex ActualChunksByAsset (r:2 w:2)
+	// Storage: EqDex OrdersByAssetAndChunkKey (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:8 w:4)
+	// Storage: EqAggregates TotalUserGroups (r:2 w:2)
+	// Storage: EqDex AssetWeightByAccountId (r:2 w:2)
+	// Storage: EqMarginCall MaintenanceTimers (r:1 w:0)
+	fn create_pair_order() -> Weight {
+		Weight::from_parts(412_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(39 as u64))
+			.saturating_add(T::DbWeight::get().writes(22 as u64))
+	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqDex AutoCorridorByAsset (r:0 w:1)
+	fn set_auto_corridor() -> Weight {
+		Weight::from_parts(14_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqRate UnsignedDutyStates (r:1 w:1)
+	// Storage: EqDex AutoCorridorByAsset (r:1 w:0)
+	// Storage: Financial PerAssetMetrics (r:1 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: EqDex ChunkCorridorByAsset (r:0 w:1)
+	fn update_auto_corridor() -> Weight {
+		Weight::from_parts(27_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Subaccounts SessionKeys (r:1 w:0)
+	// Storage: EqDex SelfTradePreventionByAccount (r:0 w:1)
+	fn set_self_trade_prevention() -> Weight {
+		Weight::from_parts(13_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqDex TradingBans (r:0 w:1)
+	fn set_trading_ban() -> Weight {
+		Weight::from_parts(11_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqDex TrustedXcmParachains (r:0 w:1)
+	fn set_trusted_xcm_parachain() -> Weight {
+		Weight::from_parts(10_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Subaccounts Subaccount (r:1 w:0)
+	// Storage: EqDex BestPriceByAsset (r:1 w:1)
+	// Storage: EqDex ChunkCorridorByAsset (r:1 w:0)
+	// Storage: Oracle PricePoints (r:2 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: EqDex AssetWeightByAccountId (r:1 w:1)
+	// Storage: EqMarginCall MaintenanceTimers (r:1 w:0)
+	// Storage: EqDex OrderIdCounter (r:1 w:1)
+	// Storage: EqDex OrdersByAssetAndChunkKey (r:1 w:1)
+	// Storage: EqDex ActualChunksByAsset (r:1 w:1)
+	// Storage: EqDex IcebergOrders (r:0 w:1)
+	fn create_iceberg_order() -> Weight {
+		Weight::from_parts(121_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
 }
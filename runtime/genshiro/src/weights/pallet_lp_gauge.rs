@@ -0,0 +1,85 @@
+
+//! Weights for `eq_lp_gauge`
+
+// Command to regenerate:
+// ./target/production/eq-node
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// eq_lp_gauge
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// ./runtime/genshiro/src/weights/pallet_lp_gauge.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `eq_lp_gauge`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> eq_lp_gauge::WeightInfo for WeightInfo<T> {
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqLpGauge Gauges (r:1 w:1)
+	fn set_gauge() -> Weight {
+		Weight::from_parts(24_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqLpGauge Gauges (r:1 w:1)
+	// Storage: EqLpGauge Positions (r:1 w:1)
+	// Storage: EqStaking Stakes (r:1 w:0)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: Subaccounts OwnerAccount (r:1 w:0)
+	fn stake() -> Weight {
+		Weight::from_parts(98_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqLpGauge Positions (r:1 w:1)
+	// Storage: EqLpGauge Gauges (r:1 w:1)
+	// Storage: EqStaking Stakes (r:1 w:0)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: Subaccounts OwnerAccount (r:1 w:0)
+	fn unstake() -> Weight {
+		Weight::from_parts(101_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqLpGauge Positions (r:1 w:1)
+	// Storage: EqLpGauge Gauges (r:1 w:1)
+	// Storage: EqStaking Stakes (r:1 w:0)
+	// Storage: EqAssets Assets (r:2 w:0)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: Subaccounts OwnerAccount (r:1 w:0)
+	fn claim() -> Weight {
+		Weight::from_parts(104_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqLpGauge Positions (r:1 w:1)
+	// Storage: EqLpGauge Gauges (r:1 w:1)
+	// Storage: EqStaking Stakes (r:1 w:0)
+	fn kick() -> Weight {
+		Weight::from_parts(36_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+}
@@ -48,4 +48,66 @@ impl<T: frame_system::Config> eq_margin_call::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(19 as u64))
 			.saturating_add(T::DbWeight::get().writes(8 as u64))
 	}
+	// Storage: EqMarginCall Notifications (r:1 w:1)
+	fn acknowledge_notifications() -> Weight {
+		Weight::from_parts(21_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqMarginCall WrongfulLiquidations (r:1 w:1)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:2 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	fn compensate_wrongful_liquidation() -> Weight {
+		Weight::from_parts(64_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqMarginCall PendingLiquidations (r:1 w:1)
+	// Storage: EqDex AssetWeightByAccountId (r:1 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Oracle PricePoints (r:3 w:0)
+	// Storage: EqMarginCall WrongfulLiquidations (r:1 w:1)
+	/// The range of component `p` is `[0, 100]`.
+	fn check_pending_liquidations(p: u32, ) -> Weight {
+		Weight::from_parts(4_000_000 as u64, 0)
+			// Standard Error: 12_000
+			.saturating_add(Weight::from_parts(38_000_000 as u64, 0).saturating_mul(p as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(p as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(p as u64)))
+	}
+	// Storage: EqMarginCall PositionTransfers (r:1 w:1)
+	// Storage: Subaccounts Subaccount (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	fn propose_position_transfer() -> Weight {
+		Weight::from_parts(31_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqMarginCall PositionTransfers (r:1 w:1)
+	fn cancel_position_transfer() -> Weight {
+		Weight::from_parts(20_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqMarginCall PositionTransfers (r:1 w:1)
+	// Storage: Subaccounts Subaccount (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	// Storage: EqDex AssetWeightByAccountId (r:2 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Oracle PricePoints (r:2 w:0)
+	// Storage: EqMarginCall MaintenanceTimers (r:2 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:6 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:4 w:4)
+	fn accept_position_transfer() -> Weight {
+		Weight::from_parts(176_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(24 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
 }
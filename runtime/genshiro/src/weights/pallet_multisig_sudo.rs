@@ -56,10 +56,11 @@ impl<T: frame_system::Config> eq_multisig_sudo::WeightInfo for WeightInfo<T> {
 	// Storage: EqMultisigSudo Keys (r:1 w:0)
 	// Storage: EqMultisigSudo Threshold (r:1 w:0)
 	// Storage: EqMultisigSudo MultisigProposals (r:0 w:1)
+	// Storage: EqMultisigSudo ProposalsExpiry (r:0 w:1)
 	fn propose() -> Weight {
 		Weight::from_parts(27_871_000 as u64, 0)
 			.saturating_add(T::DbWeight::get().reads(2 as u64))
-			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
 	}
 	// Storage: EqMultisigSudo Keys (r:1 w:0)
 	// Storage: EqMultisigSudo MultisigProposals (r:1 w:1)
@@ -77,4 +78,27 @@ impl<T: frame_system::Config> eq_multisig_sudo::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(3 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: EqMultisigSudo MultisigProposals (r:1 w:1)
+	fn veto() -> Weight {
+		Weight::from_parts(18_415_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqMultisigSudo ProposalsExpiry (r:1 w:1)
+	// Storage: EqMultisigSudo MultisigProposals (r:1 w:1)
+	/// The range of component `p` is `[0, 50]`.
+	fn expire_proposals(p: u32, ) -> Weight {
+		Weight::from_parts(6_120_000 as u64, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(9_306_000 as u64, 0).saturating_mul(p as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(p as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(p as u64)))
+	}
+	// Storage: EqMultisigSudo XcmTransactAllowList (r:0 w:1)
+	fn set_xcm_transact_allowed() -> Weight {
+		Weight::from_parts(14_350_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
@@ -0,0 +1,96 @@
+
+//! Weights for `eq_perps`
+
+// Command to regenerate:
+// ./target/production/eq-node
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// eq_perps
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// ./runtime/genshiro/src/weights/pallet_perps.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `eq_perps`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> eq_perps::WeightInfo for WeightInfo<T> {
+	// Storage: EqPerps Markets (r:1 w:1)
+	// Storage: EqPerps OpenInterestLimits (r:0 w:1)
+	fn add_market() -> Weight {
+		Weight::from_parts(15_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Subaccounts Subaccount (r:1 w:0)
+	// Storage: EqPerps Markets (r:2 w:0)
+	// Storage: EqDex BestPriceByAsset (r:1 w:0)
+	// Storage: Oracle PricePoints (r:3 w:0)
+	// Storage: EqPerps Positions (r:2 w:1)
+	// Storage: EqPerps OpenInterests (r:1 w:1)
+	// Storage: EqPerps OpenInterestLimits (r:1 w:0)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: System Account (r:2 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqDex AssetWeightByAccountId (r:1 w:0)
+	// Storage: EqMarginCall MaintenanceTimers (r:1 w:0)
+	fn trade() -> Weight {
+		Weight::from_parts(124_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: Subaccounts Subaccount (r:1 w:0)
+	// Storage: EqPerps Positions (r:1 w:1)
+	// Storage: EqPerps Markets (r:1 w:0)
+	// Storage: EqDex BestPriceByAsset (r:1 w:0)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: EqPerps OpenInterests (r:1 w:1)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: System Account (r:2 w:1)
+	fn close_position() -> Weight {
+		Weight::from_parts(66_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: EqPerps Markets (r:21 w:20)
+	// Storage: Oracle PricePoints (r:20 w:0)
+	// Storage: EqDex BestPriceByAsset (r:20 w:0)
+	/// The range of component `m` is `[1, 20]`.
+	fn update_funding(m: u32, ) -> Weight {
+		Weight::from_parts(6_000_000 as u64, 0)
+			.saturating_add(Weight::from_parts(14_000_000 as u64, 0).saturating_mul(m as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(m as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(m as u64)))
+	}
+	// Storage: EqPerps Markets (r:1 w:0)
+	// Storage: EqPerps OpenInterestLimits (r:0 w:1)
+	fn set_open_interest_limit() -> Weight {
+		Weight::from_parts(14_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Subaccounts Subaccount (r:1 w:0)
+	// Storage: EqPerps UnpaidPnl (r:1 w:1)
+	// Storage: EqPerps VaultDeficit (r:1 w:1)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: System Account (r:2 w:1)
+	fn claim_unpaid_pnl() -> Weight {
+		Weight::from_parts(52_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+}
@@ -38,18 +38,117 @@ impl<T: frame_system::Config> eq_treasury::WeightInfo for WeightInfo<T> {
 	// Storage: EqRate NowMillisOffset (r:1 w:0)
 	// Storage: Treasury BuyoutLimit (r:1 w:0)
 	// Storage: Treasury Buyouts (r:1 w:1)
+	// Storage: Treasury TotalBuyoutLimit (r:1 w:0)
+	// Storage: Treasury TotalBuyouts (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Subaccounts OwnerAccount (r:2 w:0)
 	// Storage: EqAggregates AccountUserGroups (r:6 w:0)
 	// Storage: EqAggregates TotalUserGroups (r:2 w:2)
 	fn buyout() -> Weight {
 		Weight::from_parts(172_807_000 as u64, 0)
-			.saturating_add(T::DbWeight::get().reads(17 as u64))
-			.saturating_add(T::DbWeight::get().writes(4 as u64))
+			.saturating_add(T::DbWeight::get().reads(19 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
 	}
 	// Storage: Treasury BuyoutLimit (r:0 w:1)
 	fn update_buyout_limit() -> Weight {
 		Weight::from_parts(5_778_000 as u64, 0)
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: Treasury TotalBuyoutLimit (r:0 w:1)
+	fn update_total_buyout_limit() -> Weight {
+		Weight::from_parts(5_778_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Treasury LendingPolicy (r:1 w:1)
+	// Storage: Treasury LendingPositions (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqLending LendersAggregates (r:1 w:1)
+	// Storage: EqLending QLenders (r:1 w:1)
+	// Storage: EqLending QCumulatedReward (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:4 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:2 w:2)
+	fn set_lending_policy() -> Weight {
+		Weight::from_parts(96_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	// Storage: Treasury LendingPolicy (r:1 w:0)
+	// Storage: Treasury LendingPositions (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqLending LendersAggregates (r:1 w:1)
+	// Storage: EqLending QLenders (r:1 w:1)
+	// Storage: EqLending QCumulatedReward (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:4 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:2 w:2)
+	fn rebalance_lending() -> Weight {
+		Weight::from_parts(92_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(14 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
+	// Storage: CurveAmm Pools (r:1 w:1)
+	// Storage: Oracle PricePoints (r:3 w:0)
+	// Storage: EqAssets Assets (r:3 w:0)
+	// Storage: Treasury LendingPositions (r:2 w:0)
+	// Storage: System Account (r:4 w:4)
+	// Storage: EqAggregates AccountUserGroups (r:4 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:6 w:6)
+	// Storage: EqRate LastUpdate (r:1 w:0)
+	// Storage: Subaccounts OwnerAccount (r:2 w:0)
+	fn zap_add_liquidity() -> Weight {
+		Weight::from_parts(412_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(26 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
+	}
+	// Storage: CurveAmm Pools (r:1 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Treasury Buyback (r:0 w:1)
+	fn set_buyback_program() -> Weight {
+		Weight::from_parts(27_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Treasury BuybackPaused (r:0 w:1)
+	fn set_buyback_paused() -> Weight {
+		Weight::from_parts(12_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Treasury Buyback (r:1 w:0)
+	// Storage: Treasury BuybackPaused (r:1 w:0)
+	// Storage: CurveAmm Pools (r:5 w:5)
+	// Storage: Oracle PricePoints (r:10 w:0)
+	// Storage: EqAssets Assets (r:10 w:0)
+	// Storage: System Account (r:10 w:10)
+	// Storage: EqAggregates AccountUserGroups (r:10 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:10 w:10)
+	// Storage: Treasury BuybackEpochs (r:0 w:1)
+	/// The range of component `a` is `[1, 5]`.
+	fn buyback(a: u32, ) -> Weight {
+		Weight::from_parts(24_000_000 as u64, 0)
+			// Standard Error: 61_000
+			.saturating_add(Weight::from_parts(386_000_000 as u64, 0).saturating_mul(a as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().reads((11 as u64).saturating_mul(a as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((5 as u64).saturating_mul(a as u64)))
+	}
+	// Storage: Treasury XcmDust (r:1 w:1)
+	// Storage: Oracle PricePoints (r:2 w:0)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EqAggregates AccountUserGroups (r:2 w:1)
+	// Storage: EqAggregates TotalUserGroups (r:1 w:1)
+	fn claim_xcm_dust() -> Weight {
+		Weight::from_parts(71_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: Treasury ReportHistory (r:1 w:1)
+	// Storage: Treasury CurrentReport (r:1 w:1)
+	fn close_treasury_report() -> Weight {
+		Weight::from_parts(18_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
 }
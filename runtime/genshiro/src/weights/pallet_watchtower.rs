@@ -0,0 +1,62 @@
+
+//! Weights for `eq_watchtower`
+
+// Command to regenerate:
+// ./target/production/eq-node
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// eq_watchtower
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// ./runtime/genshiro/src/weights/pallet_watchtower.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `eq_watchtower`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> eq_watchtower::WeightInfo for WeightInfo<T> {
+	// Storage: EqWatchtower Watchtowers (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances Account (r:1 w:1)
+	// Storage: EqBalances Reserved (r:1 w:1)
+	fn register() -> Weight {
+		Weight::from_parts(41_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: EqWatchtower Watchtowers (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: EqBalances Account (r:1 w:1)
+	// Storage: EqBalances Reserved (r:1 w:1)
+	fn unregister() -> Weight {
+		Weight::from_parts(38_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: EqWatchtower Watchtowers (r:1 w:1)
+	// Storage: EqWatchtower LastPaidAlerts (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:0)
+	// Storage: Oracle PricePoints (r:1 w:0)
+	// Storage: Bailsman Distributions (r:1 w:0)
+	// Storage: EqBalances Account (r:2 w:2)
+	// Storage: EqBalances Reserved (r:1 w:1)
+	// Storage: System Account (r:2 w:1)
+	fn submit_alert() -> Weight {
+		Weight::from_parts(72_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+}
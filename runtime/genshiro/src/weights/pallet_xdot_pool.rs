@@ -0,0 +1,173 @@
+
+//! Weights for `eq_xdot_pool`
+
+// Command to regenerate:
+// ./target/production/eq-node
+// benchmark
+// pallet
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// eq_xdot_pool
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// ./runtime/genshiro/src/weights/pallet_xdot_pool.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `eq_xdot_pool`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> eq_xdot_pool::WeightInfo for WeightInfo<T> {
+	// Storage: EqXdotPool PoolCount (r:1 w:1)
+	// Storage: EqXdotPool Pools (r:1 w:1)
+	// Storage: EqAssets Assets (r:1 w:1)
+	// Storage: EqAssets RiskTiers (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: EqXdotPool Initializer (r:0 w:1)
+	fn create_pool() -> Weight {
+		Weight::from_parts(58_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqXdotPool Initializer (r:1 w:1)
+	// Storage: EqXdotPool Pools (r:1 w:1)
+	// Storage: EqAssets Assets (r:3 w:0)
+	// Storage: EqBalances TempMigration (r:6 w:0)
+	// Storage: System Account (r:5 w:5)
+	// Storage: Subaccounts OwnerAccount (r:6 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:14 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:2 w:2)
+	fn initialize() -> Weight {
+		Weight::from_parts(164_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(38 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
+	}
+	// Storage: EqXdotPool Pools (r:1 w:0)
+	// Storage: EqXdotPool Initializer (r:0 w:1)
+	fn change_initializer() -> Weight {
+		Weight::from_parts(17_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqXdotPool Pools (r:1 w:1)
+	fn remove_pool() -> Weight {
+		Weight::from_parts(18_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EqXdotPool Initializer (r:1 w:0)
+	// Storage: EqXdotPool Pools (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqAssets Assets (r:3 w:0)
+	// Storage: EqBalances TempMigration (r:6 w:0)
+	// Storage: System Account (r:6 w:6)
+	// Storage: Subaccounts OwnerAccount (r:6 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:16 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:3 w:3)
+	fn mint() -> Weight {
+		Weight::from_parts(231_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(44 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
+	}
+	// Storage: EqXdotPool Initializer (r:1 w:0)
+	// Storage: EqXdotPool Pools (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqAssets Assets (r:3 w:0)
+	// Storage: EqBalances TempMigration (r:6 w:0)
+	// Storage: System Account (r:6 w:6)
+	// Storage: Subaccounts OwnerAccount (r:6 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:16 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:3 w:3)
+	fn burn() -> Weight {
+		Weight::from_parts(226_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(44 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
+	}
+	// Storage: EqXdotPool Initializer (r:1 w:0)
+	// Storage: EqXdotPool Pools (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqAssets Assets (r:3 w:0)
+	// Storage: EqBalances TempMigration (r:4 w:0)
+	// Storage: System Account (r:4 w:4)
+	// Storage: Subaccounts OwnerAccount (r:4 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:12 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:2 w:2)
+	fn sell_base() -> Weight {
+		Weight::from_parts(142_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(33 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqXdotPool Initializer (r:1 w:0)
+	// Storage: EqXdotPool Pools (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqAssets Assets (r:3 w:0)
+	// Storage: EqBalances TempMigration (r:4 w:0)
+	// Storage: System Account (r:4 w:4)
+	// Storage: Subaccounts OwnerAccount (r:4 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:12 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:2 w:2)
+	fn buy_base() -> Weight {
+		Weight::from_parts(147_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(33 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqXdotPool Initializer (r:1 w:0)
+	// Storage: EqXdotPool Pools (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqAssets Assets (r:3 w:0)
+	// Storage: EqBalances TempMigration (r:4 w:0)
+	// Storage: System Account (r:4 w:4)
+	// Storage: Subaccounts OwnerAccount (r:4 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:12 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:2 w:2)
+	fn sell_xbase() -> Weight {
+		Weight::from_parts(139_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(33 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqXdotPool Initializer (r:1 w:0)
+	// Storage: EqXdotPool Pools (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqAssets Assets (r:3 w:0)
+	// Storage: EqBalances TempMigration (r:4 w:0)
+	// Storage: System Account (r:4 w:4)
+	// Storage: Subaccounts OwnerAccount (r:4 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:12 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:2 w:2)
+	fn buy_xbase() -> Weight {
+		Weight::from_parts(149_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(33 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: EqXdotPool Initializer (r:1 w:0)
+	// Storage: EqXdotPool Pools (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: EqRate NowMillisOffset (r:1 w:0)
+	// Storage: EqAssets Assets (r:3 w:0)
+	// Storage: EqBalances TempMigration (r:6 w:0)
+	// Storage: System Account (r:6 w:6)
+	// Storage: Subaccounts OwnerAccount (r:6 w:0)
+	// Storage: EqAggregates AccountUserGroups (r:16 w:0)
+	// Storage: EqAggregates TotalUserGroups (r:3 w:3)
+	fn optimal_mint() -> Weight {
+		Weight::from_parts(218_000_000 as u64, 0)
+			.saturating_add(T::DbWeight::get().reads(44 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
+	}
+}
@@ -411,6 +411,177 @@ fn transact_dispatch_origin_not_allowed() {
     }
 }
 
+#[test]
+fn transact_dispatch_origin_from_trusted_sibling() {
+    parachain_test_ext().unwrap().execute_with(|| {
+        let id = [0x01; 32];
+        let remote = |para_id| MultiLocation {
+            parents: 1,
+            interior: X2(Parachain(para_id), AccountId32 { network: None, id }),
+        };
+
+        assert_ok!(EqDex::set_trusted_xcm_parachain(
+            RuntimeOrigin::root(),
+            2000,
+            true
+        ));
+
+        let result: Result<RuntimeOrigin, _> = XcmOriginToTransactDispatchOrigin::convert_origin(
+            remote(2000),
+            OriginKind::SovereignAccount,
+        );
+        let derived: AccountId = eq_xcm::origins::sibling_account_id(2000, id);
+        assert_eq!(
+            frame_system::ensure_signed(result.unwrap()),
+            Ok(derived.clone())
+        );
+        // assets are never resolved to derived accounts
+        assert_err!(LocationToAccountId::convert(remote(2000)), remote(2000));
+
+        for (origin, kind) in [
+            (remote(2000), OriginKind::Native),
+            (remote(2000), OriginKind::Superuser),
+            (remote(2001), OriginKind::SovereignAccount),
+        ] {
+            let result: Result<RuntimeOrigin, _> =
+                XcmOriginToTransactDispatchOrigin::convert_origin(origin.clone(), kind);
+            assert_eq!(result.err().unwrap(), origin);
+        }
+
+        assert!(RemoteTraderCalls::contains(&RuntimeCall::EqDex(
+            eq_dex::Call::delete_order_external {
+                asset: asset::DOT,
+                order_id: 0,
+                price: Default::default(),
+            }
+        )));
+        assert!(!RemoteTraderCalls::contains(&RuntimeCall::EqDex(
+            eq_dex::Call::set_trusted_xcm_parachain {
+                para_id: 2000,
+                trusted: true,
+            }
+        )));
+        assert!(
+            !<XcmConfig as xcm_executor::Config>::SafeCallFilter::contains(
+                &RuntimeCall::EqBalances(eq_balances::Call::transfer {
+                    asset: asset::KSM,
+                    to: derived,
+                    value: 1,
+                })
+            )
+        );
+    });
+}
+
+#[test]
+fn barrier_remote_trader_transact() {
+    parachain_test_ext().unwrap().execute_with(|| {
+        let sibling = |para_id| MultiLocation {
+            parents: 1,
+            interior: X1(Parachain(para_id)),
+        };
+        let message = |origin_kind| -> Vec<Instruction<()>> {
+            vec![
+                ReserveAssetDeposited(vec![multi_asset_from(TO_SEND_AMOUNT, &multi::KSM)].into()),
+                BuyExecution {
+                    fees: multi_asset_from(TO_SEND_AMOUNT, &multi::KSM),
+                    weight_limit: Unlimited,
+                },
+                DescendOrigin(X1(AccountId32 {
+                    network: None,
+                    id: [0x01; 32],
+                })),
+                Transact {
+                    origin_kind,
+                    require_weight_at_most: XcmWeight::from_parts(1_000_000, 0),
+                    call: Vec::<u8>::new().into(),
+                },
+                RefundSurplus,
+            ]
+        };
+        let max_weight = XcmWeight::from_parts(1_000_000_000, 0);
+        let mut weight_credit = XcmWeight::zero();
+
+        assert_ok!(EqDex::set_trusted_xcm_parachain(
+            RuntimeOrigin::root(),
+            2000,
+            true
+        ));
+
+        let mut trusted = message(OriginKind::SovereignAccount);
+        assert_ok!(crate::Barrier::should_execute(
+            &sibling(2000),
+            &mut trusted,
+            max_weight,
+            &mut weight_credit,
+        ));
+        assert!(matches!(
+            trusted[1],
+            BuyExecution {
+                weight_limit: Limited(limit),
+                ..
+            } if limit == max_weight
+        ));
+
+        // untrusted sibling
+        assert_err!(
+            crate::Barrier::should_execute(
+                &sibling(2001),
+                &mut message(OriginKind::SovereignAccount),
+                max_weight,
+                &mut weight_credit,
+            ),
+            ProcessMessageError::Unsupported
+        );
+
+        // remote user can't be the origin of the message
+        assert_err!(
+            crate::Barrier::should_execute(
+                &MultiLocation {
+                    parents: 1,
+                    interior: X2(
+                        Parachain(2000),
+                        AccountId32 {
+                            network: None,
+                            id: [0x01; 32],
+                        }
+                    ),
+                },
+                &mut message(OriginKind::SovereignAccount),
+                max_weight,
+                &mut weight_credit,
+            ),
+            ProcessMessageError::Unsupported
+        );
+
+        // call dispatched with other origin than the derived account
+        for origin_kind in [OriginKind::Native, OriginKind::Superuser, OriginKind::Xcm] {
+            assert_err!(
+                crate::Barrier::should_execute(
+                    &sibling(2000),
+                    &mut message(origin_kind),
+                    max_weight,
+                    &mut weight_credit,
+                ),
+                ProcessMessageError::Unsupported
+            );
+        }
+
+        // origin isn't descended to the user
+        let mut not_descended = message(OriginKind::SovereignAccount);
+        not_descended.remove(2);
+        assert_err!(
+            crate::Barrier::should_execute(
+                &sibling(2000),
+                &mut not_descended,
+                max_weight,
+                &mut weight_credit,
+            ),
+            ProcessMessageError::Unsupported
+        );
+    });
+}
+
 #[allow(dead_code)]
 const USER_X: AccountId = AccountId::new([0x01; 32]);
 #[allow(dead_code)]
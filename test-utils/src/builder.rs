@@ -0,0 +1,250 @@
+///! Configurable genesis data for unit-tests Test Runtimes.
+///!
+///! Pallet mocks keep their own `construct_runtime!` and `Config` impls, but take
+///! the assets, prices, balances, subaccounts and bailsmen from `ExtBuilder`
+///! instead of copy-pasting them. Runtimes without `eq_assets` and `eq_balances`
+///! use `AssetGetterMock` and `EqCurrencyMock` seeded by `init_mocks`. Example:
+///!
+///!     let builder = test_utils::builder::ExtBuilder::default()
+///!         .with_default_assets()
+///!         .balance(1, asset::BTC, 1_000 * ONE_TOKEN)
+///!         .bailsman(2);
+///!     builder.init_mocks();
+///!
+///!     eq_assets::GenesisConfig::<Test> {
+///!         assets: builder.assets_genesis(),
+///!         _runtime: PhantomData,
+///!     }
+///!     .assimilate_storage(&mut t)
+///!     .unwrap();
+use crate::mock::{
+    assets::AssetGetterMock, currency::EqCurrencyMock, subaccounts::SubaccountsManagerMock,
+};
+use eq_primitives::{
+    asset::{self, Asset, AssetData, AssetIdInnerType, AssetType, AssetXcmData},
+    balance::Balance,
+    balance_number::EqFixedU128,
+    price::mock::OracleMock,
+    subaccount::SubAccType,
+};
+use sp_arithmetic::{traits::Zero, FixedI64, FixedPointNumber, Percent, Permill};
+
+/// Tuple accepted by `eq_assets::GenesisConfig::assets`
+pub type AssetGenesis = (
+    AssetIdInnerType, // u64
+    EqFixedU128,      // lot
+    FixedI64,         // price step
+    Permill,          // maker fee
+    Permill,          // taker fee
+    Vec<u8>,          // raw xcm data
+    Permill,          // debt weight
+    u64,              // buyout priority
+    AssetType,        // asset type (Synthetic / Physical)
+    bool,             // is the asset enabled in the DEX
+    Percent,          // collateral discount
+    Permill,          // lending debt weight
+);
+
+/// Asset description with defaults used across pallet mocks
+#[derive(Clone, Debug)]
+pub struct AssetConfig {
+    pub asset: Asset,
+    pub lot: EqFixedU128,
+    pub price_step: FixedI64,
+    pub maker_fee: Permill,
+    pub taker_fee: Permill,
+    pub debt_weight: Permill,
+    pub buyout_priority: u64,
+    pub asset_type: AssetType,
+    pub is_dex_enabled: bool,
+    pub collateral_discount: Percent,
+    pub lending_debt_weight: Permill,
+    pub price: Option<FixedI64>,
+}
+
+impl AssetConfig {
+    pub fn new(asset: Asset, asset_type: AssetType, buyout_priority: u64) -> Self {
+        let native = asset_type == AssetType::Native;
+        Self {
+            asset,
+            lot: EqFixedU128::zero(),
+            price_step: FixedI64::zero(),
+            maker_fee: Permill::zero(),
+            taker_fee: Permill::zero(),
+            debt_weight: if native {
+                Permill::zero()
+            } else {
+                Permill::from_rational(2u32, 5u32)
+            },
+            buyout_priority,
+            asset_type,
+            is_dex_enabled: true,
+            collateral_discount: Percent::one(),
+            lending_debt_weight: Permill::one(),
+            price: None,
+        }
+    }
+
+    pub fn price(mut self, price: i64) -> Self {
+        self.price = Some(FixedI64::saturating_from_integer(price));
+        self
+    }
+
+    pub fn fixed_price(mut self, price: FixedI64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn collateral_discount(mut self, collateral_discount: Percent) -> Self {
+        self.collateral_discount = collateral_discount;
+        self
+    }
+
+    pub fn debt_weight(mut self, debt_weight: Permill) -> Self {
+        self.debt_weight = debt_weight;
+        self
+    }
+
+    pub fn fees(mut self, maker_fee: Permill, taker_fee: Permill) -> Self {
+        self.maker_fee = maker_fee;
+        self.taker_fee = taker_fee;
+        self
+    }
+
+    pub fn lot(mut self, lot: EqFixedU128, price_step: FixedI64) -> Self {
+        self.lot = lot;
+        self.price_step = price_step;
+        self
+    }
+
+    pub fn dex_enabled(mut self, is_dex_enabled: bool) -> Self {
+        self.is_dex_enabled = is_dex_enabled;
+        self
+    }
+
+    fn data(&self) -> AssetData<Asset> {
+        AssetData {
+            id: self.asset,
+            lot: self.lot,
+            price_step: self.price_step,
+            maker_fee: self.maker_fee,
+            taker_fee: self.taker_fee,
+            asset_xcm_data: AssetXcmData::None,
+            debt_weight: self.debt_weight,
+            lending_debt_weight: self.lending_debt_weight,
+            buyout_priority: self.buyout_priority,
+            asset_type: self.asset_type,
+            is_dex_enabled: self.is_dex_enabled,
+            collateral_discount: self.collateral_discount,
+        }
+    }
+
+    fn genesis(&self) -> AssetGenesis {
+        (
+            self.asset.get_id(),
+            self.lot,
+            self.price_step,
+            self.maker_fee,
+            self.taker_fee,
+            vec![],
+            self.debt_weight,
+            self.buyout_priority,
+            self.asset_type,
+            self.is_dex_enabled,
+            self.collateral_discount,
+            self.lending_debt_weight,
+        )
+    }
+}
+
+/// Genesis data builder for unit-tests Test Runtimes
+#[derive(Clone, Debug, Default)]
+pub struct ExtBuilder {
+    assets: Vec<AssetConfig>,
+    balances: Vec<(u64, Vec<(Balance, AssetIdInnerType)>)>,
+    subaccounts: Vec<(u64, SubAccType, u64)>,
+    bailsmen: Vec<u64>,
+}
+
+impl ExtBuilder {
+    /// Assets and prices used by most of pallet mocks
+    pub fn with_default_assets(self) -> Self {
+        self.asset(AssetConfig::new(asset::EQD, AssetType::Synthetic, 1).price(1))
+            .asset(AssetConfig::new(asset::BTC, AssetType::Physical, 2).price(10_000))
+            .asset(AssetConfig::new(asset::ETH, AssetType::Physical, 3).price(250))
+            .asset(AssetConfig::new(asset::EOS, AssetType::Physical, 4).price(3))
+            .asset(AssetConfig::new(asset::DOT, AssetType::Physical, 5).price(4))
+            .asset(AssetConfig::new(asset::CRV, AssetType::Physical, 6).price(5))
+            .asset(AssetConfig::new(asset::EQ, AssetType::Native, u64::MAX).price(1))
+            .asset(
+                AssetConfig::new(asset::USDC, AssetType::Physical, 7)
+                    .price(1)
+                    .collateral_discount(Percent::zero()),
+            )
+            .asset(
+                AssetConfig::new(asset::USDT, AssetType::Physical, 8)
+                    .price(1)
+                    .collateral_discount(Percent::from_rational(5u32, 10u32)),
+            )
+    }
+
+    /// Adds or replaces an asset
+    pub fn asset(mut self, config: AssetConfig) -> Self {
+        self.assets.retain(|a| a.asset != config.asset);
+        self.assets.push(config);
+        self
+    }
+
+    /// Adds `amount` of `asset` to `who` genesis balances
+    pub fn balance(mut self, who: u64, asset: Asset, amount: Balance) -> Self {
+        match self.balances.iter_mut().find(|(acc, _)| *acc == who) {
+            Some((_, balances)) => balances.push((amount, asset.get_id())),
+            None => self.balances.push((who, vec![(amount, asset.get_id())])),
+        }
+        self
+    }
+
+    /// Registers `subaccount` of type `subacc_type` for `master`
+    pub fn subaccount(mut self, master: u64, subacc_type: SubAccType, subaccount: u64) -> Self {
+        self.subaccounts.push((master, subacc_type, subaccount));
+        self
+    }
+
+    /// Adds `who` to genesis bailsmen
+    pub fn bailsman(mut self, who: u64) -> Self {
+        self.bailsmen.push(who);
+        self
+    }
+
+    /// Value for `eq_assets::GenesisConfig::assets`
+    pub fn assets_genesis(&self) -> Vec<AssetGenesis> {
+        self.assets.iter().map(AssetConfig::genesis).collect()
+    }
+
+    /// Value for `eq_balances::GenesisConfig::balances`
+    pub fn balances_genesis(&self) -> Vec<(u64, Vec<(Balance, AssetIdInnerType)>)> {
+        self.balances.clone()
+    }
+
+    /// Value for `eq_bailsman::GenesisConfig::bailsmen`
+    pub fn bailsmen_genesis(&self) -> Vec<u64> {
+        self.bailsmen.clone()
+    }
+
+    /// Prices of configured assets
+    pub fn prices(&self) -> Vec<(Asset, FixedI64)> {
+        self.assets
+            .iter()
+            .filter_map(|a| a.price.map(|p| (a.asset, p)))
+            .collect()
+    }
+
+    /// Seeds thread local storages of `OracleMock`, `SubaccountsManagerMock`,
+    /// `AssetGetterMock` and `EqCurrencyMock`
+    pub fn init_mocks(&self) {
+        OracleMock::<u64>::init(self.prices());
+        SubaccountsManagerMock::init(self.subaccounts.clone());
+        AssetGetterMock::init(self.assets.iter().map(AssetConfig::data).collect());
+        EqCurrencyMock::<u64>::init(self.balances_genesis());
+    }
+}
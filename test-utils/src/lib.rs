@@ -1,5 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings)]
 
+#[cfg(feature = "std")]
+pub mod builder;
 pub mod financial_config;
 pub mod mock;
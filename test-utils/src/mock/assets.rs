@@ -0,0 +1,84 @@
+use eq_primitives::{
+    asset::{self, Asset, AssetData, AssetGetter, AssetType},
+    balance_number::EqFixedU128,
+};
+use sp_runtime::{traits::Zero, DispatchError};
+use std::cell::RefCell;
+
+thread_local! {
+    static ASSETS: RefCell<Vec<AssetData<Asset>>> = RefCell::new(Vec::new());
+}
+
+/// Thread local assets registry for unit-tests Test Runtime.
+/// Filled by `ExtBuilder` or directly from tests with `AssetGetterMock::set_asset_data`.
+pub struct AssetGetterMock;
+
+impl AssetGetterMock {
+    /// Replaces all registered assets
+    pub fn init(assets: Vec<AssetData<Asset>>) {
+        ASSETS.with(|v| *v.borrow_mut() = assets);
+    }
+
+    /// Adds or replaces an asset
+    pub fn set_asset_data(asset_data: AssetData<Asset>) {
+        ASSETS.with(|v| {
+            let mut assets = v.borrow_mut();
+            assets.retain(|a| a.id != asset_data.id);
+            assets.push(asset_data);
+        });
+    }
+}
+
+impl AssetGetter for AssetGetterMock {
+    fn get_asset_data(asset: &Asset) -> Result<AssetData<Asset>, DispatchError> {
+        ASSETS
+            .with(|v| v.borrow().iter().find(|a| a.id == *asset).cloned())
+            .ok_or(DispatchError::Other("Asset not exists"))
+    }
+
+    fn exists(asset: Asset) -> bool {
+        Self::get_asset_data(&asset).is_ok()
+    }
+
+    fn get_assets_data() -> Vec<AssetData<Asset>> {
+        ASSETS.with(|v| v.borrow().clone())
+    }
+
+    fn get_assets_data_with_usd() -> Vec<AssetData<Asset>> {
+        Self::get_assets_data()
+    }
+
+    /// Same as `eq_assets`: all assets except EQD
+    fn get_assets() -> Vec<Asset> {
+        Self::get_assets_with_usd()
+            .into_iter()
+            .filter(|a| *a != asset::EQD)
+            .collect()
+    }
+
+    fn get_assets_with_usd() -> Vec<Asset> {
+        ASSETS.with(|v| v.borrow().iter().map(|a| a.id).collect())
+    }
+
+    fn priority(asset: Asset) -> Option<u64> {
+        Self::get_asset_data(&asset).ok().map(|a| a.buyout_priority)
+    }
+
+    /// First registered native asset, EQ if there is none
+    fn get_main_asset() -> Asset {
+        ASSETS
+            .with(|v| {
+                v.borrow()
+                    .iter()
+                    .find(|a| a.asset_type == AssetType::Native)
+                    .map(|a| a.id)
+            })
+            .unwrap_or(asset::EQ)
+    }
+
+    fn collateral_discount(asset: &Asset) -> EqFixedU128 {
+        Self::get_asset_data(asset)
+            .map(|a| a.collateral_discount.into())
+            .unwrap_or_else(|_| EqFixedU128::zero())
+    }
+}
@@ -0,0 +1,444 @@
+use super::assets::AssetGetterMock;
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use eq_primitives::{
+    asset::{Asset, AssetGetter, AssetIdInnerType},
+    balance::{Balance, DepositReason, EqCurrency, WithdrawReason, XcmDestination},
+    balance_adapter::NegativeImbalance,
+    SignedBalance, TransferReason,
+};
+use frame_support::traits::{
+    BalanceStatus, ConstU32, ExistenceRequirement, LockIdentifier, WithdrawReasons,
+};
+use sp_runtime::{traits::Zero, DispatchError, DispatchResult};
+use std::{cell::RefCell, collections::HashMap};
+
+/// Error of `currency_transfer` and `xcm_transfer` after `EqCurrencyMock::set_failing(true)`
+pub const TRANSFER_FAILED: DispatchError = DispatchError::Other("Transfer failed");
+/// Error of checked decrease of balance below zero
+pub const NOT_ENOUGH_BALANCE: DispatchError = DispatchError::Other("Not enough balance");
+/// Error of operations with assets not registered in `AssetGetterMock`
+pub const UNKNOWN_ASSET: DispatchError = DispatchError::Other("Unknown asset");
+
+/// (transactor, dest, asset, value, existence requirement)
+pub type Transfer<AccountId> = (AccountId, AccountId, Asset, Balance, ExistenceRequirement);
+/// (from, asset, amount, destination)
+pub type XcmTransfer<AccountId> = (AccountId, Asset, Balance, XcmDestination);
+/// Check of `currency_transfer` arguments run before the transfer, accounts are SCALE encoded
+pub type TransferHook =
+    fn(&[u8], &[u8], Asset, Balance, ExistenceRequirement, TransferReason, bool) -> DispatchResult;
+
+/// Accounts are stored SCALE encoded, so the same storage serves any `AccountId`
+type EncodedAccount = Vec<u8>;
+
+thread_local! {
+    static BALANCES: RefCell<HashMap<(EncodedAccount, Asset), SignedBalance<Balance>>> = RefCell::new(HashMap::new());
+    static RESERVED: RefCell<HashMap<(EncodedAccount, Asset), Balance>> = RefCell::new(HashMap::new());
+    static LOCKS: RefCell<HashMap<(EncodedAccount, LockIdentifier), Balance>> = RefCell::new(HashMap::new());
+    static TRANSFERS: RefCell<Vec<Transfer<EncodedAccount>>> = RefCell::new(Vec::new());
+    static XCM_TRANSFERS: RefCell<Vec<XcmTransfer<EncodedAccount>>> = RefCell::new(Vec::new());
+    static FAILING_WITH: RefCell<Option<DispatchError>> = RefCell::new(None);
+    static TRANSFER_HOOK: RefCell<Option<TransferHook>> = RefCell::new(None);
+}
+
+/// Thread local balances ledger for unit-tests Test Runtime without `eq_balances`.
+/// Filled by `ExtBuilder` or directly from tests with `EqCurrencyMock::set_balance`.
+/// Balances may go negative when a change isn't checked (`ensure_can_change == false`),
+/// assets have to be registered in `AssetGetterMock`. `xcm_transfer` is only recorded.
+pub struct EqCurrencyMock<AccountId>(PhantomData<AccountId>);
+
+impl<AccountId: Encode + Decode> EqCurrencyMock<AccountId> {
+    /// Replaces all balances, value for `eq_balances::GenesisConfig::balances` is accepted
+    pub fn init(balances: Vec<(AccountId, Vec<(Balance, AssetIdInnerType)>)>) {
+        BALANCES.with(|v| {
+            *v.borrow_mut() = balances
+                .into_iter()
+                .flat_map(|(who, balances)| {
+                    let who = who.encode();
+                    balances.into_iter().map(move |(amount, asset)| {
+                        ((who.clone(), Asset(asset)), SignedBalance::Positive(amount))
+                    })
+                })
+                .collect()
+        });
+        RESERVED.with(|v| v.borrow_mut().clear());
+        LOCKS.with(|v| v.borrow_mut().clear());
+        TRANSFERS.with(|v| v.borrow_mut().clear());
+        XCM_TRANSFERS.with(|v| v.borrow_mut().clear());
+        Self::set_failing(false);
+        Self::set_transfer_hook(None);
+    }
+
+    /// Signed free balance
+    pub fn balance(who: &AccountId, asset: Asset) -> SignedBalance<Balance> {
+        BALANCES.with(|v| {
+            v.borrow()
+                .get(&(who.encode(), asset))
+                .cloned()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Sets positive free balance
+    pub fn set_balance(who: &AccountId, asset: Asset, value: Balance) {
+        Self::make_free_balance_be(who, asset, SignedBalance::Positive(value));
+    }
+
+    /// Amount locked under `id`
+    pub fn lock(who: &AccountId, id: LockIdentifier) -> Balance {
+        LOCKS.with(|v| {
+            v.borrow()
+                .get(&(who.encode(), id))
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Successful `currency_transfer` calls
+    pub fn transfers() -> Vec<Transfer<AccountId>> {
+        TRANSFERS.with(|v| {
+            v.borrow()
+                .iter()
+                .map(|(from, to, asset, value, existence_requirement)| {
+                    (
+                        Self::decode_account(from),
+                        Self::decode_account(to),
+                        *asset,
+                        *value,
+                        *existence_requirement,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    /// Successful `xcm_transfer` calls
+    pub fn xcm_transfers() -> Vec<XcmTransfer<AccountId>> {
+        XCM_TRANSFERS.with(|v| {
+            v.borrow()
+                .iter()
+                .map(|(from, asset, amount, to)| {
+                    (Self::decode_account(from), *asset, *amount, to.clone())
+                })
+                .collect()
+        })
+    }
+
+    /// Makes `currency_transfer` and `xcm_transfer` fail with `TRANSFER_FAILED`
+    pub fn set_failing(is_failing: bool) {
+        Self::fail_with(if is_failing {
+            Some(TRANSFER_FAILED)
+        } else {
+            None
+        });
+    }
+
+    /// Makes `currency_transfer` and `xcm_transfer` fail with `error`
+    pub fn fail_with(error: Option<DispatchError>) {
+        FAILING_WITH.with(|v| *v.borrow_mut() = error);
+    }
+
+    /// Runs `hook` before each `currency_transfer`, pallet mocks keep their own
+    /// transfer rules with it
+    pub fn set_transfer_hook(hook: Option<TransferHook>) {
+        TRANSFER_HOOK.with(|v| *v.borrow_mut() = hook);
+    }
+
+    fn decode_account(encoded: &EncodedAccount) -> AccountId {
+        AccountId::decode(&mut &encoded[..]).expect("Stored as encoded AccountId")
+    }
+
+    fn ensure_known(asset: Asset) -> DispatchResult {
+        if AssetGetterMock::exists(asset) {
+            Ok(())
+        } else {
+            Err(UNKNOWN_ASSET)
+        }
+    }
+
+    fn ensure_not_failing() -> DispatchResult {
+        match FAILING_WITH.with(|v| v.borrow().clone()) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn change(who: &AccountId, asset: Asset, delta: SignedBalance<Balance>) {
+        let balance = Self::balance(who, asset) + delta;
+        Self::make_free_balance_be(who, asset, balance);
+    }
+
+    fn add_reserved(who: &AccountId, asset: Asset, amount: Balance) {
+        RESERVED.with(|v| *v.borrow_mut().entry((who.encode(), asset)).or_default() += amount);
+    }
+
+    fn set_reserved(who: &AccountId, asset: Asset, amount: Balance) {
+        RESERVED.with(|v| v.borrow_mut().insert((who.encode(), asset), amount));
+    }
+}
+
+impl<AccountId: Encode + Decode> EqCurrency<AccountId, Balance> for EqCurrencyMock<AccountId> {
+    type Moment = u64;
+    type MaxLocks = ConstU32<10>;
+
+    fn total_balance(who: &AccountId, asset: Asset) -> Balance {
+        Self::free_balance(who, asset) + Self::reserved_balance(who, asset)
+    }
+
+    fn debt(who: &AccountId, asset: Asset) -> Balance {
+        match Self::balance(who, asset) {
+            SignedBalance::Negative(debt) => debt,
+            SignedBalance::Positive(_) => Balance::zero(),
+        }
+    }
+
+    fn currency_total_issuance(asset: Asset) -> Balance {
+        BALANCES.with(|v| {
+            v.borrow()
+                .iter()
+                .filter(|((_, a), _)| *a == asset)
+                .map(|(_, balance)| match balance {
+                    SignedBalance::Positive(value) => *value,
+                    SignedBalance::Negative(_) => Balance::zero(),
+                })
+                .sum()
+        })
+    }
+
+    fn minimum_balance_value() -> Balance {
+        Balance::zero()
+    }
+
+    fn free_balance(who: &AccountId, asset: Asset) -> Balance {
+        match Self::balance(who, asset) {
+            SignedBalance::Positive(value) => value,
+            SignedBalance::Negative(_) => Balance::zero(),
+        }
+    }
+
+    fn ensure_can_withdraw(
+        who: &AccountId,
+        asset: Asset,
+        amount: Balance,
+        _: WithdrawReasons,
+        _: Balance,
+    ) -> DispatchResult {
+        if Self::free_balance(who, asset) >= amount {
+            Ok(())
+        } else {
+            Err(NOT_ENOUGH_BALANCE)
+        }
+    }
+
+    fn currency_transfer(
+        transactor: &AccountId,
+        dest: &AccountId,
+        asset: Asset,
+        value: Balance,
+        existence_requirement: ExistenceRequirement,
+        transfer_reason: TransferReason,
+        ensure_can_change: bool,
+    ) -> DispatchResult {
+        if let Some(hook) = TRANSFER_HOOK.with(|v| *v.borrow()) {
+            hook(
+                &transactor.encode(),
+                &dest.encode(),
+                asset,
+                value,
+                existence_requirement,
+                transfer_reason,
+                ensure_can_change,
+            )?;
+        }
+        Self::ensure_known(asset)?;
+        Self::ensure_not_failing()?;
+        if ensure_can_change {
+            Self::ensure_can_withdraw(
+                transactor,
+                asset,
+                value,
+                WithdrawReasons::TRANSFER,
+                Balance::zero(),
+            )?;
+        }
+        Self::change(transactor, asset, SignedBalance::Negative(value));
+        Self::change(dest, asset, SignedBalance::Positive(value));
+        TRANSFERS.with(|v| {
+            v.borrow_mut().push((
+                transactor.encode(),
+                dest.encode(),
+                asset,
+                value,
+                existence_requirement,
+            ))
+        });
+        Ok(())
+    }
+
+    fn deposit_into_existing(
+        who: &AccountId,
+        asset: Asset,
+        value: Balance,
+        _: Option<DepositReason>,
+    ) -> Result<(), DispatchError> {
+        Self::ensure_known(asset)?;
+        Self::change(who, asset, SignedBalance::Positive(value));
+        Ok(())
+    }
+
+    fn deposit_creating(
+        who: &AccountId,
+        asset: Asset,
+        value: Balance,
+        _: bool,
+        event: Option<DepositReason>,
+    ) -> Result<(), DispatchError> {
+        Self::deposit_into_existing(who, asset, value, event)
+    }
+
+    fn withdraw(
+        who: &AccountId,
+        asset: Asset,
+        value: Balance,
+        ensure_can_change: bool,
+        _: Option<WithdrawReason>,
+        withdraw_reasons: WithdrawReasons,
+        _: ExistenceRequirement,
+    ) -> Result<(), DispatchError> {
+        Self::ensure_known(asset)?;
+        if ensure_can_change {
+            Self::ensure_can_withdraw(who, asset, value, withdraw_reasons, Balance::zero())?;
+        }
+        Self::change(who, asset, SignedBalance::Negative(value));
+        Ok(())
+    }
+
+    fn make_free_balance_be(who: &AccountId, asset: Asset, value: SignedBalance<Balance>) {
+        BALANCES.with(|v| v.borrow_mut().insert((who.encode(), asset), value));
+    }
+
+    fn can_be_deleted(who: &AccountId) -> Result<bool, DispatchError> {
+        let who = who.encode();
+        let is_empty = BALANCES.with(|v| {
+            v.borrow()
+                .iter()
+                .all(|((acc, _), balance)| *acc != who || balance.is_zero())
+        }) && RESERVED.with(|v| {
+            v.borrow()
+                .iter()
+                .all(|((acc, _), reserved)| *acc != who || reserved.is_zero())
+        });
+        Ok(is_empty)
+    }
+
+    fn delete_account(who: &AccountId) -> Result<(), DispatchError> {
+        let who = who.encode();
+        BALANCES.with(|v| v.borrow_mut().retain(|(acc, _), _| *acc != who));
+        RESERVED.with(|v| v.borrow_mut().retain(|(acc, _), _| *acc != who));
+        LOCKS.with(|v| v.borrow_mut().retain(|(acc, _), _| *acc != who));
+        Ok(())
+    }
+
+    fn exchange(
+        accounts: (&AccountId, &AccountId),
+        assets: (&Asset, &Asset),
+        values: (Balance, Balance),
+    ) -> Result<(), (DispatchError, Option<AccountId>)> {
+        Self::ensure_known(*assets.0).map_err(|e| (e, None))?;
+        Self::ensure_known(*assets.1).map_err(|e| (e, None))?;
+        Self::change(accounts.0, *assets.0, SignedBalance::Negative(values.0));
+        Self::change(accounts.1, *assets.0, SignedBalance::Positive(values.0));
+        Self::change(accounts.1, *assets.1, SignedBalance::Negative(values.1));
+        Self::change(accounts.0, *assets.1, SignedBalance::Positive(values.1));
+        Ok(())
+    }
+
+    fn reserved_balance(who: &AccountId, asset: Asset) -> Balance {
+        RESERVED.with(|v| {
+            v.borrow()
+                .get(&(who.encode(), asset))
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+
+    fn reserve(who: &AccountId, asset: Asset, amount: Balance) -> DispatchResult {
+        Self::ensure_known(asset)?;
+        Self::ensure_can_withdraw(
+            who,
+            asset,
+            amount,
+            WithdrawReasons::RESERVE,
+            Balance::zero(),
+        )?;
+        Self::change(who, asset, SignedBalance::Negative(amount));
+        Self::add_reserved(who, asset, amount);
+        Ok(())
+    }
+
+    fn slash_reserved(
+        who: &AccountId,
+        asset: Asset,
+        value: Balance,
+    ) -> (NegativeImbalance<Balance>, Balance) {
+        let reserved = Self::reserved_balance(who, asset);
+        let to_slash = reserved.min(value);
+        Self::set_reserved(who, asset, reserved - to_slash);
+        (NegativeImbalance::new(to_slash), reserved - to_slash)
+    }
+
+    fn repatriate_reserved(
+        slashed: &AccountId,
+        beneficiary: &AccountId,
+        asset: Asset,
+        value: Balance,
+        status: BalanceStatus,
+    ) -> Result<Balance, DispatchError> {
+        let reserved = Self::reserved_balance(slashed, asset);
+        let to_slash = reserved.min(value);
+        Self::set_reserved(slashed, asset, reserved - to_slash);
+        match status {
+            BalanceStatus::Free => {
+                Self::change(beneficiary, asset, SignedBalance::Positive(to_slash))
+            }
+            BalanceStatus::Reserved => Self::add_reserved(beneficiary, asset, to_slash),
+        }
+        Ok(reserved - to_slash)
+    }
+
+    fn unreserve(who: &AccountId, asset: Asset, amount: Balance) -> Balance {
+        let reserved = Self::reserved_balance(who, asset);
+        let amount = reserved.min(amount);
+        Self::set_reserved(who, asset, reserved - amount);
+        Self::change(who, asset, SignedBalance::Positive(amount));
+        amount
+    }
+
+    fn xcm_transfer(
+        from: &AccountId,
+        asset: Asset,
+        amount: Balance,
+        kind: XcmDestination,
+    ) -> DispatchResult {
+        Self::ensure_known(asset)?;
+        Self::ensure_not_failing()?;
+        XCM_TRANSFERS.with(|v| v.borrow_mut().push((from.encode(), asset, amount, kind)));
+        Ok(())
+    }
+
+    fn set_lock(id: LockIdentifier, who: &AccountId, amount: Balance) {
+        LOCKS.with(|v| v.borrow_mut().insert((who.encode(), id), amount));
+    }
+
+    fn extend_lock(id: LockIdentifier, who: &AccountId, amount: Balance) {
+        LOCKS.with(|v| {
+            let mut locks = v.borrow_mut();
+            let lock = locks.entry((who.encode(), id)).or_default();
+            *lock = (*lock).max(amount);
+        });
+    }
+
+    fn remove_lock(id: LockIdentifier, who: &AccountId) {
+        LOCKS.with(|v| v.borrow_mut().remove(&(who.encode(), id)));
+    }
+}
@@ -2,8 +2,16 @@ use eq_primitives::asset::Asset;
 use eq_primitives::EqBuyout;
 
 pub mod aggregates;
+#[cfg(feature = "std")]
+pub mod assets;
+#[cfg(feature = "std")]
+pub mod currency;
 pub mod financial;
 pub mod oracle;
+#[cfg(feature = "std")]
+pub mod subaccounts;
+#[cfg(feature = "std")]
+pub mod time;
 
 pub struct EqBuyoutMock;
 impl<AccountId, Balance> EqBuyout<AccountId, Balance> for EqBuyoutMock {
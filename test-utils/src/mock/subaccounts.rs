@@ -0,0 +1,125 @@
+use eq_primitives::subaccount::{SubAccType, SubaccountsManager};
+use sp_runtime::DispatchError;
+use std::cell::RefCell;
+
+thread_local! {
+    /// (master, subaccount type, subaccount id)
+    static SUBACCOUNTS: RefCell<Vec<(u64, SubAccType, u64)>> = RefCell::new(Vec::new());
+    /// (session key, master)
+    static SESSION_KEYS: RefCell<Vec<(u64, u64)>> = RefCell::new(Vec::new());
+    /// Owner of accounts not registered as subaccounts
+    static ACCOUNT_OWNER: RefCell<Option<(u64, SubAccType)>> = RefCell::new(None);
+    /// Subaccount of masters without registered subaccounts
+    static DEFAULT_SUBACCOUNT: RefCell<Option<u64>> = RefCell::new(None);
+}
+
+/// Thread local registry of subaccounts for unit-tests Test Runtime.
+/// Filled by `ExtBuilder` or directly from tests with `SubaccountsManagerMock::add`.
+pub struct SubaccountsManagerMock;
+
+impl SubaccountsManagerMock {
+    /// Replaces all registered subaccounts
+    pub fn init(subaccounts: Vec<(u64, SubAccType, u64)>) {
+        SUBACCOUNTS.with(|v| *v.borrow_mut() = subaccounts);
+        SESSION_KEYS.with(|v| v.borrow_mut().clear());
+        ACCOUNT_OWNER.with(|v| *v.borrow_mut() = None);
+        DEFAULT_SUBACCOUNT.with(|v| *v.borrow_mut() = None);
+    }
+
+    /// Registers `subaccount` of type `subacc_type` for `master`
+    pub fn add(master: u64, subacc_type: SubAccType, subaccount: u64) {
+        SUBACCOUNTS.with(|v| {
+            let mut subaccounts = v.borrow_mut();
+            subaccounts.retain(|(m, t, _)| !(*m == master && *t == subacc_type));
+            subaccounts.push((master, subacc_type, subaccount));
+        });
+    }
+
+    /// Removes all registered subaccounts
+    pub fn clear() {
+        Self::init(Vec::new());
+    }
+
+    /// Reports every account not registered as a subaccount as `subacc_type`
+    /// subaccount of `owner`
+    pub fn set_account_owner(owner: u64, subacc_type: SubAccType) {
+        ACCOUNT_OWNER.with(|v| *v.borrow_mut() = Some((owner, subacc_type)));
+    }
+
+    /// Reports `subaccount` as subaccount of any type for masters without
+    /// registered subaccounts
+    pub fn set_default_subaccount(subaccount: Option<u64>) {
+        DEFAULT_SUBACCOUNT.with(|v| *v.borrow_mut() = subaccount);
+    }
+
+    /// Authorizes active trading `session_key` for `master`
+    pub fn add_session_key(session_key: u64, master: u64) {
+        SESSION_KEYS.with(|v| {
+            let mut keys = v.borrow_mut();
+            keys.retain(|(k, _)| *k != session_key);
+            keys.push((session_key, master));
+        });
+    }
+
+    fn find<F>(f: F) -> Option<(u64, SubAccType, u64)>
+    where
+        F: Fn(&(u64, SubAccType, u64)) -> bool,
+    {
+        SUBACCOUNTS.with(|v| v.borrow().iter().find(|s| f(s)).cloned())
+    }
+}
+
+impl SubaccountsManager<u64> for SubaccountsManagerMock {
+    fn create_subaccount_inner(who: &u64, subacc_type: &SubAccType) -> Result<u64, DispatchError> {
+        if let Some((_, _, subaccount)) = Self::find(|(m, t, _)| m == who && t == subacc_type) {
+            return Ok(subaccount);
+        }
+        // subaccount ids of pallet mocks were `who + 100`, tests rely on them
+        let subaccount = who + 100;
+        Self::add(*who, *subacc_type, subaccount);
+        Ok(subaccount)
+    }
+
+    fn delete_subaccount_inner(who: &u64, subacc_type: &SubAccType) -> Result<u64, DispatchError> {
+        let (_, _, subaccount) = Self::find(|(m, t, _)| m == who && t == subacc_type)
+            .ok_or(DispatchError::Other("No subaccount"))?;
+        SUBACCOUNTS.with(|v| {
+            v.borrow_mut()
+                .retain(|(m, t, _)| !(m == who && t == subacc_type))
+        });
+        Ok(subaccount)
+    }
+
+    fn has_subaccount(who: &u64, subacc_type: &SubAccType) -> bool {
+        Self::get_subaccount_id(who, subacc_type).is_some()
+    }
+
+    fn get_subaccount_id(who: &u64, subacc_type: &SubAccType) -> Option<u64> {
+        Self::find(|(m, t, _)| m == who && t == subacc_type)
+            .map(|(_, _, s)| s)
+            .or_else(|| DEFAULT_SUBACCOUNT.with(|v| *v.borrow()))
+    }
+
+    fn is_subaccount(who: &u64, subaccount_id: &u64) -> bool {
+        Self::find(|(m, _, s)| m == who && s == subaccount_id).is_some()
+    }
+
+    fn get_owner_id(subaccount: &u64) -> Option<(u64, SubAccType)> {
+        Self::find(|(_, _, s)| s == subaccount)
+            .map(|(m, t, _)| (m, t))
+            .or_else(|| ACCOUNT_OWNER.with(|v| *v.borrow()))
+    }
+
+    fn get_subaccounts_amount(who: &u64) -> usize {
+        SUBACCOUNTS.with(|v| v.borrow().iter().filter(|(m, _, _)| m == who).count())
+    }
+
+    fn session_key_owner(session_key: &u64) -> Option<u64> {
+        SESSION_KEYS.with(|v| {
+            v.borrow()
+                .iter()
+                .find(|(k, _)| k == session_key)
+                .map(|(_, m)| *m)
+        })
+    }
+}
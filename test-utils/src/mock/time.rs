@@ -0,0 +1,21 @@
+use frame_support::traits::UnixTime;
+use std::cell::RefCell;
+
+thread_local! {
+    static UNIX_NOW: RefCell<u64> = RefCell::new(0);
+}
+
+/// Thread local `UnixTime` for unit-tests Test Runtime, starts at zero
+pub struct TimeMock;
+
+impl TimeMock {
+    pub fn set_secs(secs: u64) {
+        UNIX_NOW.with(|now| *now.borrow_mut() = secs)
+    }
+}
+
+impl UnixTime for TimeMock {
+    fn now() -> core::time::Duration {
+        UNIX_NOW.with(|now| core::time::Duration::from_secs(*now.borrow()))
+    }
+}